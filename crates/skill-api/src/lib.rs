@@ -0,0 +1,13 @@
+//! Skill API - request and response payloads of the Skill HTTP API
+//!
+//! `skill-http` serves these types and `skill-client` sends and receives
+//! them, so both sides of the API share one definition. Every type derives
+//! `ToSchema` for the server's OpenAPI document.
+//!
+//! Values the server takes from skill-runtime are carried as plain data:
+//! workflow runs, output diffs, requirement statuses and enhance progress
+//! as JSON, resource usage and output streams as the types in [`types`].
+
+pub mod types;
+
+pub use types::*;
@@ -0,0 +1,2098 @@
+//! API types for request and response payloads
+//!
+//! Shared by the server (`skill-http`) and the client (`skill-client`).
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use utoipa::ToSchema;
+
+/// Host service requirement with current status
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SkillServiceRequirement {
+    /// Service name (e.g., "kubectl-proxy")
+    pub name: String,
+    /// Human-readable description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// If true, service enhances functionality but isn't required
+    pub optional: bool,
+    /// Default port the service runs on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_port: Option<u16>,
+    /// Started automatically before the skill's tools run
+    #[serde(default)]
+    pub start_on_demand: bool,
+    /// Current service status
+    pub status: ServiceStatus,
+}
+
+/// Summary information about an installed skill
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SkillSummary {
+    /// Unique skill name
+    pub name: String,
+    /// Skill version
+    pub version: String,
+    /// Short description
+    pub description: String,
+    /// Source (git URL, local path, registry)
+    pub source: String,
+    /// Runtime type (wasm, docker, native)
+    pub runtime: String,
+    /// Number of tools provided
+    pub tools_count: usize,
+    /// Number of configured instances
+    pub instances_count: usize,
+    /// Last time a tool was executed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<DateTime<Utc>>,
+    /// Total execution count
+    pub execution_count: u64,
+    /// Required host services with their current status
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_services: Vec<SkillServiceRequirement>,
+}
+
+/// Detailed skill information
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SkillDetail {
+    /// Basic summary
+    #[serde(flatten)]
+    pub summary: SkillSummary,
+    /// Full description (markdown)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full_description: Option<String>,
+    /// Author information
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Repository URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// License
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Tools provided by this skill
+    pub tools: Vec<ToolInfo>,
+    /// Configured instances
+    pub instances: Vec<InstanceInfo>,
+    /// Host requirements every tool of the skill shares, with their status
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schema(value_type = Vec<Object>)]
+    pub requirements: Vec<serde_json::Value>,
+}
+
+/// A skill's usage documentation
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SkillDocsResponse {
+    /// Skill name
+    pub name: String,
+    /// File the documentation came from (`SKILL.md` or `README.md`)
+    pub file: String,
+    /// Markdown content, without SKILL.md frontmatter
+    pub content: String,
+}
+
+/// Information about a tool
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ToolInfo {
+    /// Tool name
+    pub name: String,
+    /// Tool description
+    pub description: String,
+    /// Parameters
+    pub parameters: Vec<ParameterInfo>,
+    /// Whether this tool supports streaming
+    pub streaming: bool,
+    /// Whether the host meets the tool's requirements
+    pub available: bool,
+    /// Requirements the host doesn't meet (e.g. `binary:kubectl>=1.27 (found 1.25.3)`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unmet_requirements: Vec<String>,
+}
+
+/// Information about a parameter
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ParameterInfo {
+    /// Parameter name
+    pub name: String,
+    /// Parameter type
+    #[serde(rename = "type")]
+    pub param_type: String,
+    /// Description
+    pub description: String,
+    /// Whether parameter is required
+    pub required: bool,
+    /// Default value if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<String>,
+    /// Values the parameter accepts; any if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_values: Vec<String>,
+    /// Regex the value must match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Smallest allowed value of a numeric parameter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+    /// Largest allowed value of a numeric parameter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+}
+
+/// Information about a skill instance
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct InstanceInfo {
+    /// Instance name
+    pub name: String,
+    /// Description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether this is the default instance
+    pub is_default: bool,
+    /// Configuration keys (values hidden for security)
+    pub config_keys: Vec<String>,
+}
+
+/// Request to install a skill
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct InstallSkillRequest {
+    /// Source to install from (git URL, local path, registry)
+    pub source: String,
+    /// Optional name override
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Git ref (branch, tag, commit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// Instance to create (defaults to "default")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Whether to force reinstall
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Stage of a skill installation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallStage {
+    /// Waiting to start
+    Queued,
+    /// Cloning, pulling or downloading the source
+    Fetching,
+    /// Building the WASM component from source
+    Building,
+    /// Checking the signature and validating the component
+    Verifying,
+    /// Copying the component to the registry and creating the instance
+    Installing,
+    /// Adding the skill to the search index
+    Indexing,
+    /// Every skill the source provides is installed
+    Completed,
+    /// The installation failed; see `error`
+    Failed,
+    /// The installation was cancelled
+    Cancelled,
+}
+
+impl InstallStage {
+    /// Whether the installation is over
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// A skill installation running in the background
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct InstallJob {
+    /// Job ID, for `GET /api/installs/{id}`
+    pub id: String,
+    /// Source being installed
+    pub source: String,
+    /// Current stage
+    pub stage: InstallStage,
+    /// Overall progress, 0-100
+    pub progress: u8,
+    /// What the current stage is doing, e.g. "Receiving objects 120/480"
+    pub message: String,
+    /// Skills installed so far
+    pub installed: Vec<String>,
+    /// Tools the installed skills provide
+    pub tools_count: usize,
+    /// Why the installation failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// When the installation was requested
+    pub started_at: DateTime<Utc>,
+    /// When the installation finished
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Request to execute a tool
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionRequest {
+    /// Skill name
+    pub skill: String,
+    /// Tool name
+    pub tool: String,
+    /// Instance to use (defaults to "default")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Arguments as key-value pairs
+    #[serde(default)]
+    pub args: HashMap<String, serde_json::Value>,
+    /// Whether to stream output
+    #[serde(default)]
+    pub stream: bool,
+    /// Timeout in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Maximum characters in the output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output: Option<usize>,
+    /// Truncation strategy when `max_output` is exceeded: head, tail, middle or smart (default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncate: Option<String>,
+    /// Regex keeping only matching output lines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grep: Option<String>,
+    /// Keep lines that don't match `grep` instead
+    #[serde(default)]
+    pub grep_invert: bool,
+    /// Keep only the first N lines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<usize>,
+    /// Keep only the last N lines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tail: Option<usize>,
+    /// Output format: json, lines, count or compact
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// jq filter applied to JSON output (e.g. `.items[] | select(.ready) | .name`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jq: Option<String>,
+    /// Data written to the tool's standard input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdin: Option<String>,
+    /// Execute even if a cached result of this read-only tool is available
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_cache: bool,
+}
+
+/// Result of a tool execution
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionResponse {
+    /// Unique execution ID
+    pub id: String,
+    /// Execution status
+    pub status: ExecutionStatus,
+    /// Output content
+    pub output: String,
+    /// Error message if failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Execution duration in milliseconds
+    pub duration_ms: u64,
+    /// Additional metadata
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+    /// Files captured from the skill's declared `outputs`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<ExecutionArtifact>,
+    /// Process exit code (native skills)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// Standard error, separate from `output`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub stderr: String,
+    /// CPU time, peak memory and output size, where they could be measured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_usage: Option<ResourceUsage>,
+    /// Whether `output` was truncated by `max_output`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+    /// Result cache metadata, for read-only tools whose results are cached
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<ExecutionCacheInfo>,
+}
+
+/// Resources used by an execution
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ResourceUsage {
+    /// User plus system CPU time in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_time_ms: Option<u64>,
+    /// Peak resident memory in kilobytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_memory_kb: Option<u64>,
+    /// Bytes of output (stdout plus stderr) the execution produced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_bytes: Option<u64>,
+}
+
+/// Whether an execution result came from the result cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionCacheInfo {
+    /// The tool wasn't executed; this is the result of an earlier execution
+    pub hit: bool,
+    /// Age of the cached result in milliseconds (0 when just executed)
+    pub age_ms: u64,
+    /// How long results of this tool are reused, in milliseconds
+    pub ttl_ms: u64,
+}
+
+/// Execution status
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionStatus {
+    Pending,
+    Running,
+    Success,
+    Failed,
+    Timeout,
+    Cancelled,
+}
+
+/// Execution history entry
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionHistoryEntry {
+    /// Execution ID
+    pub id: String,
+    /// Skill name
+    pub skill: String,
+    /// Tool name
+    pub tool: String,
+    /// Instance used
+    pub instance: String,
+    /// Status
+    pub status: ExecutionStatus,
+    /// Duration in milliseconds
+    pub duration_ms: u64,
+    /// When the execution started
+    pub started_at: DateTime<Utc>,
+    /// Error message if failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Output content (stdout/result)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// Arguments the tool was called with, kept so the execution can be re-run
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub args: HashMap<String, serde_json::Value>,
+    /// What the tool ran with, for debugging failures after the fact
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<ExecutionEnvironment>,
+    /// CPU time, peak memory and output size, where they could be measured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_usage: Option<ResourceUsage>,
+}
+
+/// Snapshot of the environment an execution ran in
+///
+/// Only names are recorded for configuration and environment variables;
+/// their values (which may be secrets) never are.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionEnvironment {
+    /// Keys of the resolved instance configuration
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub config_keys: Vec<String>,
+    /// Names of the environment variables set for the tool
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_vars: Vec<String>,
+    /// Kubernetes context the instance targets (`KUBE_CONTEXT` or `context`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    /// Versions of the binaries involved, e.g. `kubectl` => `1.29.2`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub binaries: BTreeMap<String, String>,
+}
+
+/// Result of re-running a past execution
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RerunResponse {
+    /// ID of the execution that was re-run
+    pub original_id: String,
+    /// The new execution
+    pub execution: ExecutionResponse,
+    /// Differences from the original output: by jq-style path for JSON,
+    /// line by line otherwise
+    #[schema(value_type = Object)]
+    pub diff: serde_json::Value,
+}
+
+/// A saved run: a named skill, tool, instance and arguments to run again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RunPreset {
+    /// Preset ID
+    pub id: String,
+    /// Name shown on the dashboard and in the sidebar
+    pub name: String,
+    /// Skill name
+    pub skill: String,
+    /// Tool name
+    pub tool: String,
+    /// Instance to run on
+    pub instance: String,
+    /// Arguments the tool is called with
+    #[serde(default)]
+    pub args: HashMap<String, serde_json::Value>,
+    /// Whether the preset is pinned to the sidebar
+    #[serde(default)]
+    pub pinned: bool,
+    /// When the preset was saved
+    pub created_at: DateTime<Utc>,
+    /// When the preset was last run, if ever
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<DateTime<Utc>>,
+}
+
+/// Request to save a run preset
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreatePresetRequest {
+    /// Preset name
+    pub name: String,
+    /// Skill name
+    pub skill: String,
+    /// Tool name
+    pub tool: String,
+    /// Instance to run on (defaults to "default")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Arguments the tool is called with
+    #[serde(default)]
+    pub args: HashMap<String, serde_json::Value>,
+    /// Pin the preset to the sidebar
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// Changes to a run preset; omitted fields are left as they are
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct UpdatePresetRequest {
+    /// New name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// New instance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// New arguments, replacing the saved ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<HashMap<String, serde_json::Value>>,
+    /// Pin or unpin the preset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<bool>,
+}
+
+/// An agent's summary of a past execution
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionSummaryResponse {
+    /// ID of the summarized execution
+    pub id: String,
+    /// What the execution did and, for failures, what to check
+    pub summary: String,
+    /// Agent runtime that wrote the summary
+    pub runtime: AgentRuntime,
+    /// Model that wrote the summary
+    pub model: String,
+}
+
+/// Query parameters for a full-text search over execution history
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionSearchQuery {
+    /// Words that must all appear in the output or error
+    pub q: String,
+    /// Only search executions from the last N days
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days: Option<u32>,
+    /// Maximum results (server default 50)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+/// An execution matching a history search
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionSearchHit {
+    /// The matching execution
+    pub execution: ExecutionHistoryEntry,
+    /// Excerpt of the matching output or error, with matched words wrapped
+    /// in `«` and `»`
+    pub snippet: String,
+}
+
+/// Results of a full-text search over execution history
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionSearchResponse {
+    /// The search text
+    pub query: String,
+    /// Matching executions, most recent first
+    pub results: Vec<ExecutionSearchHit>,
+}
+
+/// A workflow that can be run through the API
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WorkflowSummary {
+    /// Workflow name
+    pub name: String,
+    /// What the workflow does
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON Schema for the workflow's inputs
+    #[schema(value_type = Object)]
+    pub inputs: serde_json::Map<String, serde_json::Value>,
+    /// Steps, in run order
+    pub steps: Vec<WorkflowStepInfo>,
+}
+
+/// A workflow step, with the earlier steps it uses
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WorkflowStepInfo {
+    /// Step ID
+    pub id: String,
+    /// Tool the step calls (`skill:tool` or `skill@instance:tool`)
+    pub tool: String,
+    /// jq condition the step runs under
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    /// IDs of the steps whose results this step uses
+    pub depends_on: Vec<String>,
+    /// Extra attempts after a failure
+    pub retries: u32,
+    /// Whether the workflow continues if this step fails
+    pub continue_on_error: bool,
+}
+
+/// Request to run a workflow
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct RunWorkflowRequest {
+    /// Workflow inputs by name
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub inputs: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A finished workflow run
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WorkflowRunResponse {
+    /// Job queue entry recording the run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_id: Option<String>,
+    /// Overall and per-step status, outputs and errors
+    #[schema(value_type = Object)]
+    pub run: serde_json::Value,
+}
+
+/// Event streamed while a workflow runs; the SSE event name is `event`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WorkflowEvent {
+    /// The run started
+    Started {
+        /// Workflow name
+        workflow: String,
+        /// Step IDs, in run order
+        steps: Vec<String>,
+    },
+    /// A step started
+    StepStarted {
+        /// Step ID
+        step: String,
+        /// Tool the step calls
+        tool: String,
+    },
+    /// Part of a finished step's output
+    StepOutput {
+        /// Step ID
+        step: String,
+        /// Output text
+        chunk: String,
+    },
+    /// A step succeeded
+    StepCompleted {
+        /// Step ID
+        step: String,
+        /// Time spent on all attempts
+        duration_ms: u64,
+        /// Values bound from the output
+        #[schema(value_type = Object)]
+        outputs: std::collections::BTreeMap<String, serde_json::Value>,
+    },
+    /// A step failed after all attempts
+    StepFailed {
+        /// Step ID
+        step: String,
+        /// Why the step failed
+        error: String,
+        /// Attempts made
+        attempts: u32,
+    },
+    /// A step was skipped by its condition or an earlier failure
+    StepSkipped {
+        /// Step ID
+        step: String,
+    },
+    /// The run finished
+    Finished {
+        /// Job queue entry recording the run
+        #[serde(skip_serializing_if = "Option::is_none")]
+        job_id: Option<String>,
+        /// Final state of the run
+        #[schema(value_type = Object)]
+        run: serde_json::Value,
+    },
+    /// The run couldn't start
+    Error {
+        /// What went wrong
+        message: String,
+    },
+}
+
+impl WorkflowEvent {
+    /// SSE event name, matching the `event` field
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Started { .. } => "started",
+            Self::StepStarted { .. } => "step_started",
+            Self::StepOutput { .. } => "step_output",
+            Self::StepCompleted { .. } => "step_completed",
+            Self::StepFailed { .. } => "step_failed",
+            Self::StepSkipped { .. } => "step_skipped",
+            Self::Finished { .. } => "finished",
+            Self::Error { .. } => "error",
+        }
+    }
+}
+
+/// Which of a tool's output streams some output came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    /// Standard output
+    Stdout,
+    /// Standard error
+    Stderr,
+}
+
+/// Event streamed by `POST /execute/stream`; the SSE event name is `event`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ExecutionStreamEvent {
+    /// The execution was accepted
+    Started {
+        /// Skill name
+        skill: String,
+        /// Tool name
+        tool: String,
+        /// Instance name
+        instance: String,
+    },
+    /// Output the tool wrote
+    Output {
+        /// `stdout` or `stderr`
+        stream: OutputStream,
+        /// Output text
+        text: String,
+    },
+    /// The execution finished; the same response `POST /execute` returns
+    Completed {
+        /// Execution result
+        execution: ExecutionResponse,
+    },
+    /// The execution couldn't run
+    Error {
+        /// HTTP status `POST /execute` would have answered with
+        status: u16,
+        /// What went wrong
+        error: ApiError,
+    },
+}
+
+impl ExecutionStreamEvent {
+    /// SSE event name, matching the `event` field
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Started { .. } => "started",
+            Self::Output { .. } => "output",
+            Self::Completed { .. } => "completed",
+            Self::Error { .. } => "error",
+        }
+    }
+}
+
+/// Query parameters for paging through an execution's output
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionOutputQuery {
+    /// `<start>-<end>` (end exclusive) or `<start>-`; the first 8000 characters if absent
+    pub range: Option<String>,
+}
+
+/// One page of an execution's full output
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionOutputPage {
+    /// Execution ID
+    pub execution_id: String,
+    /// Page content
+    pub content: String,
+    /// Offset of the page in the full output
+    pub offset: usize,
+    /// Offset of the next page, if there is more output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<usize>,
+    /// Length of the full output
+    pub total_length: usize,
+}
+
+/// A file captured from an execution's declared outputs
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionArtifact {
+    /// Path relative to the output directory; download it from
+    /// `/api/executions/{id}/artifacts/{name}`
+    pub name: String,
+    /// Size in bytes
+    pub size: u64,
+    /// MIME type guessed from the extension
+    pub content_type: String,
+    /// When the artifact was captured
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request to search for skills/tools
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SearchRequest {
+    /// Search query
+    pub query: String,
+    /// Maximum number of results
+    #[serde(default = "default_top_k")]
+    pub top_k: usize,
+    /// Filter by skill name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill_filter: Option<String>,
+    /// Include AI-generated examples in results
+    #[serde(default)]
+    pub include_examples: bool,
+    /// Conversational session ID; follow-up queries are expanded using
+    /// the previous queries in the same session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Structured filters applied inside the vector store and BM25 index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filters: Option<SearchFilters>,
+    /// Include a per-result scoring breakdown
+    #[serde(default)]
+    pub explain: bool,
+}
+
+/// Structured search filters
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct SearchFilters {
+    /// Runtime type (wasm, docker, native)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<String>,
+    /// Tags the tool must carry (all must match)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Instance name (e.g., "prod")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Category
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+}
+
+fn default_top_k() -> usize {
+    5
+}
+
+/// Search result
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SearchResult {
+    /// Result ID
+    pub id: String,
+    /// Skill name
+    pub skill: String,
+    /// Tool name
+    pub tool: String,
+    /// Content/description
+    pub content: String,
+    /// Relevance score (0.0 - 1.0)
+    pub score: f32,
+    /// Rerank score if available
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rerank_score: Option<f32>,
+    /// Scoring breakdown (only when `explain` was requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<ScoreExplanation>,
+}
+
+/// Per-result scoring breakdown
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ScoreExplanation {
+    /// Dense (vector) similarity score
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dense_score: Option<f32>,
+    /// 1-based rank in the dense results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dense_rank: Option<usize>,
+    /// BM25 keyword score
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bm25_score: Option<f32>,
+    /// 1-based rank in the BM25 results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bm25_rank: Option<usize>,
+    /// Reciprocal Rank Fusion score
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fusion_score: Option<f32>,
+    /// Rank before reranking
+    pub retrieval_rank: usize,
+    /// Positions moved by the reranker (positive means promoted)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rerank_delta: Option<i64>,
+    /// Query terms found in the result
+    #[serde(default)]
+    pub matched_terms: Vec<String>,
+}
+
+/// Search response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SearchResponse {
+    /// Search results
+    pub results: Vec<SearchResult>,
+    /// Query processing info
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_info: Option<QueryInfo>,
+    /// Total time in milliseconds
+    pub duration_ms: u64,
+}
+
+/// Query processing information
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QueryInfo {
+    /// Normalized query
+    pub normalized: String,
+    /// Detected intent
+    pub intent: String,
+    /// Confidence score
+    pub confidence: f32,
+    /// Whether the query was expanded using session context
+    #[serde(default)]
+    pub follow_up: bool,
+}
+
+/// Search configuration
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SearchConfigResponse {
+    /// Embedding provider
+    pub embedding_provider: String,
+    /// Embedding model
+    pub embedding_model: String,
+    /// Vector dimensions
+    pub dimensions: usize,
+    /// Vector store backend
+    pub vector_backend: String,
+    /// Whether hybrid search is enabled
+    pub hybrid_search_enabled: bool,
+    /// Whether reranking is enabled
+    pub reranking_enabled: bool,
+    /// Number of indexed documents
+    pub indexed_documents: usize,
+}
+
+/// Update search configuration request
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateSearchConfigRequest {
+    /// Embedding provider (fastembed, openai, ollama)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding_provider: Option<String>,
+    /// Embedding model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding_model: Option<String>,
+    /// Vector backend (inmemory, qdrant)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector_backend: Option<String>,
+    /// Enable/disable hybrid search
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_hybrid: Option<bool>,
+    /// Enable/disable reranking
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_reranking: Option<bool>,
+}
+
+/// Application configuration
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AppConfig {
+    /// Default timeout in seconds
+    pub default_timeout_secs: u64,
+    /// Maximum concurrent executions
+    pub max_concurrent_executions: usize,
+    /// Whether to enable execution history
+    pub enable_history: bool,
+    /// Maximum history entries to keep
+    pub max_history_entries: usize,
+    /// Search configuration
+    pub search: SearchConfigResponse,
+    /// Failure-rate and latency anomaly alerts
+    #[serde(default)]
+    pub alerts: AlertConfig,
+}
+
+/// When to alert on anomalous executions, and where to send the alert
+///
+/// Each check compares a skill's executions in the last `window_minutes`
+/// with its executions over the `baseline_days` before that.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct AlertConfig {
+    /// Whether the analyzer runs
+    pub enabled: bool,
+    /// URL that receives the anomaly as a JSON POST
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    /// Slack incoming webhook URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slack_webhook_url: Option<String>,
+    /// Length of the window checked for anomalies
+    pub window_minutes: u64,
+    /// Length of the baseline the window is compared with
+    pub baseline_days: u32,
+    /// Fewest executions in the window (and baseline, for latency) to judge a skill
+    pub min_executions: usize,
+    /// Failure rate in the window (0.0-1.0) at which to alert...
+    pub failure_rate: f32,
+    /// ...provided it is at least this much above the baseline's
+    pub failure_rate_increase: f32,
+    /// Alert when p95 latency in the window is this many times the baseline's...
+    pub latency_factor: f64,
+    /// ...and at least this many milliseconds
+    pub min_latency_ms: u64,
+    /// Minimum time between alerts of the same kind for a skill
+    pub cooldown_minutes: u64,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            webhook_url: None,
+            slack_webhook_url: None,
+            window_minutes: 60,
+            baseline_days: 7,
+            min_executions: 5,
+            failure_rate: 0.5,
+            failure_rate_increase: 0.25,
+            latency_factor: 3.0,
+            min_latency_ms: 500,
+            cooldown_minutes: 60,
+        }
+    }
+}
+
+/// Update application configuration request
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateAppConfigRequest {
+    /// Default timeout in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_timeout_secs: Option<u64>,
+    /// Maximum concurrent executions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_executions: Option<usize>,
+    /// Whether to enable execution history
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_history: Option<bool>,
+    /// Maximum history entries to keep
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_history_entries: Option<usize>,
+    /// Replace the anomaly alert configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alerts: Option<AlertConfig>,
+}
+
+/// Health check response
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct HealthResponse {
+    /// Overall status
+    pub status: String,
+    /// Whether all components are healthy
+    pub healthy: bool,
+    /// Component statuses
+    pub components: HashMap<String, ComponentHealth>,
+    /// Server version
+    pub version: String,
+    /// Uptime in seconds
+    pub uptime_secs: u64,
+    /// Load on the execution limiter
+    #[serde(default)]
+    pub executions: ExecutionUtilization,
+}
+
+/// How many tool executions are running and queued, against the limits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionUtilization {
+    /// Executions running now
+    pub running: usize,
+    /// Executions waiting for a slot
+    pub queued: usize,
+    /// Executions allowed to run at once
+    pub max_concurrent: usize,
+    /// Executions allowed to wait before requests are refused
+    pub max_queued: usize,
+}
+
+impl ExecutionUtilization {
+    /// Whether a new execution would be refused
+    pub fn is_saturated(&self) -> bool {
+        self.running >= self.max_concurrent && self.queued >= self.max_queued
+    }
+}
+
+/// Component health status
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ComponentHealth {
+    /// Component name
+    pub name: String,
+    /// Whether component is healthy
+    pub healthy: bool,
+    /// Optional status message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Version information
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct VersionResponse {
+    /// Server version
+    pub version: String,
+    /// Build info
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build: Option<String>,
+    /// Git commit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Rust version used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rust_version: Option<String>,
+    /// Wasmtime version
+    pub wasmtime_version: String,
+}
+
+/// API error response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ApiError {
+    /// Error code
+    pub code: String,
+    /// Human-readable message
+    pub message: String,
+    /// Additional details
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl ApiError {
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn not_found(resource: &str) -> Self {
+        Self::new("NOT_FOUND", format!("{} not found", resource))
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new("BAD_REQUEST", message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new("INTERNAL_ERROR", message)
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new("FORBIDDEN", message)
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new("VALIDATION_ERROR", message)
+    }
+}
+
+/// Pagination parameters
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaginationParams {
+    /// Page number (1-indexed)
+    #[serde(default = "default_page")]
+    pub page: usize,
+    /// Items per page
+    #[serde(default = "default_per_page")]
+    pub per_page: usize,
+}
+
+fn default_page() -> usize {
+    1
+}
+
+fn default_per_page() -> usize {
+    20
+}
+
+/// Paginated response wrapper
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaginatedResponse<T> {
+    /// Items for this page
+    pub items: Vec<T>,
+    /// Total number of items
+    pub total: usize,
+    /// Current page
+    pub page: usize,
+    /// Items per page
+    pub per_page: usize,
+    /// Total pages
+    pub total_pages: usize,
+}
+
+impl<T> PaginatedResponse<T> {
+    pub fn new(items: Vec<T>, total: usize, page: usize, per_page: usize) -> Self {
+        let total_pages = total.div_ceil(per_page);
+        Self {
+            items,
+            total,
+            page,
+            per_page,
+            total_pages,
+        }
+    }
+}
+
+// =============================================================================
+// Manifest Import Types
+// =============================================================================
+
+/// Request to import a manifest configuration
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportManifestRequest {
+    /// The manifest content (TOML format)
+    pub content: String,
+    /// Whether to merge with existing skills or replace
+    #[serde(default)]
+    pub merge: bool,
+    /// Whether to install skills immediately or just validate
+    #[serde(default)]
+    pub install: bool,
+    /// Lockfile (`.skill-engine.lock`) content pinning the skills' revisions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lockfile: Option<String>,
+    /// Write the content to the server's manifest file first, so the
+    /// change survives a restart
+    #[serde(default)]
+    pub save: bool,
+}
+
+/// Parsed skill from manifest
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ParsedSkill {
+    /// Skill name
+    pub name: String,
+    /// Source location
+    pub source: String,
+    /// Runtime type
+    pub runtime: String,
+    /// Description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Instances defined
+    pub instances: Vec<ParsedInstance>,
+    /// Docker configuration if applicable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub docker_config: Option<DockerConfig>,
+}
+
+/// Parsed instance from manifest
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ParsedInstance {
+    /// Instance name
+    pub name: String,
+    /// Configuration keys (values hidden)
+    pub config_keys: Vec<String>,
+    /// Environment variable keys
+    pub env_keys: Vec<String>,
+    /// Whether it's the default instance
+    pub is_default: bool,
+}
+
+/// Docker runtime configuration
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DockerConfig {
+    /// Docker image
+    pub image: String,
+    /// Entrypoint command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entrypoint: Option<String>,
+    /// Volume mounts
+    #[serde(default)]
+    pub volumes: Vec<String>,
+    /// Working directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    /// Memory limit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<String>,
+    /// CPU limit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpus: Option<String>,
+    /// Network mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+}
+
+/// Response from parsing/importing a manifest
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportManifestResponse {
+    /// Whether the import was successful
+    pub success: bool,
+    /// Parsed skills from the manifest
+    pub skills: Vec<ParsedSkill>,
+    /// Number of skills found
+    pub skills_count: usize,
+    /// Number of skills installed or queued for installation (if install=true)
+    pub installed_count: usize,
+    /// Install jobs started for the manifest's WASM skills, in dependency
+    /// order; poll `GET /api/installs/{id}` for each
+    #[serde(default)]
+    pub installs: Vec<InstallJob>,
+    /// Validation warnings
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Errors encountered
+    #[serde(default)]
+    pub errors: Vec<String>,
+    /// Manifest file the content was written to, with `save`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub saved_path: Option<String>,
+}
+
+/// Request to validate manifest content
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ValidateManifestRequest {
+    /// The manifest content (TOML format)
+    pub content: String,
+}
+
+/// Response from validating a manifest
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ValidateManifestResponse {
+    /// Whether the manifest is valid
+    pub valid: bool,
+    /// Parsed skills (if valid)
+    pub skills: Vec<ParsedSkill>,
+    /// Validation errors
+    #[serde(default)]
+    pub errors: Vec<String>,
+    /// Validation warnings
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Errors and warnings located by line and column, for editors
+    #[serde(default)]
+    pub diagnostics: Vec<ManifestDiagnostic>,
+}
+
+/// How serious a manifest diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    /// The manifest can't be loaded
+    Error,
+    /// The manifest loads, but probably not as intended
+    Warning,
+}
+
+/// A problem found in a manifest
+///
+/// Lines and columns are 1-based; they're missing when the problem can't be
+/// tied to a place in the file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ManifestDiagnostic {
+    /// Error or warning
+    pub severity: DiagnosticSeverity,
+    /// What's wrong
+    pub message: String,
+    /// Line the problem starts on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Column the problem starts at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    /// Line the problem ends on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    /// Column the problem ends at, exclusive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+}
+
+/// The manifest file the server loaded its skills from
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ManifestSourceResponse {
+    /// Path of the manifest file; `None` when the server runs without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// File content, empty without a manifest
+    pub content: String,
+}
+
+/// Request to export current configuration as manifest
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportManifestRequest {
+    /// Format to export (toml, json)
+    #[serde(default = "default_export_format")]
+    pub format: String,
+    /// Whether to include sensitive values (default: false)
+    #[serde(default)]
+    pub include_secrets: bool,
+}
+
+fn default_export_format() -> String {
+    "toml".to_string()
+}
+
+/// Response with exported manifest
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportManifestResponse {
+    /// The manifest content
+    pub content: String,
+    /// Format used
+    pub format: String,
+    /// Number of skills included
+    pub skills_count: usize,
+}
+
+// =============================================================================
+// System Service Types
+// =============================================================================
+
+/// Status of a system service
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ServiceStatus {
+    /// Service name
+    pub name: String,
+    /// Whether the service is running
+    pub running: bool,
+    /// Process ID if running
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    /// Port the service is listening on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    /// URL to access the service
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Error message if failed to start
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Whether the process was started by this server
+    #[serde(default)]
+    pub managed: bool,
+    /// Times the process was restarted after exiting
+    #[serde(default)]
+    pub restarts: u32,
+}
+
+/// Response listing all system services
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ServicesStatusResponse {
+    /// List of service statuses
+    pub services: Vec<ServiceStatus>,
+}
+
+/// Request to start a service
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StartServiceRequest {
+    /// Service name to start
+    pub service: String,
+    /// Optional port to use
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+}
+
+/// Response from starting a service
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StartServiceResponse {
+    /// Whether the service was started successfully
+    pub success: bool,
+    /// Service status after starting
+    pub status: ServiceStatus,
+    /// Message about the operation
+    pub message: String,
+}
+
+/// Request to stop a service
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StopServiceRequest {
+    /// Service name to stop
+    pub service: String,
+}
+
+// =============================================================================
+// Auth Status Types
+// =============================================================================
+
+/// A credential stored with `skill auth login` (metadata only, never secrets)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CredentialStatusInfo {
+    /// Auth provider ID
+    pub provider_id: String,
+    /// Provider display name
+    pub display_name: String,
+    /// Authentication type
+    pub auth_type: String,
+    /// Associated skill (global if absent)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill: Option<String>,
+    /// Associated instance
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Granted scopes
+    pub scopes: Vec<String>,
+    /// Expiry time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Whether the credential renews itself with a refresh token
+    pub has_refresh_token: bool,
+    /// State: active, refreshable, expiring_soon or expired
+    pub state: String,
+    /// Warning shown before executions fail
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+/// Response listing stored credentials
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuthStatusResponse {
+    /// Stored credentials
+    pub credentials: Vec<CredentialStatusInfo>,
+    /// Warnings for expired or expiring credentials
+    pub warnings: Vec<String>,
+}
+
+// =============================================================================
+// Vector DB Testing Types
+// =============================================================================
+
+/// Request to test search connection (quick validation)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TestConnectionRequest {
+    /// Embedding provider (fastembed, openai, ollama)
+    pub embedding_provider: String,
+    /// Embedding model name
+    pub embedding_model: String,
+    /// Vector backend (inmemory, qdrant)
+    pub vector_backend: String,
+    /// Qdrant URL (if using Qdrant backend)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qdrant_url: Option<String>,
+    /// Ollama URL (if using Ollama provider)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ollama_url: Option<String>,
+}
+
+/// Response from testing search connection
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TestConnectionResponse {
+    /// Whether the test was successful
+    pub success: bool,
+    /// Embedding provider health status
+    pub embedding_provider_status: ComponentHealth,
+    /// Vector backend health status
+    pub vector_backend_status: ComponentHealth,
+    /// Test duration in milliseconds
+    pub duration_ms: u128,
+    /// Overall status message
+    pub message: String,
+}
+
+/// Request to test full search pipeline (indexing + search)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TestPipelineRequest {
+    /// Embedding provider (fastembed, openai, ollama)
+    pub embedding_provider: String,
+    /// Embedding model name
+    pub embedding_model: String,
+    /// Vector backend (inmemory, qdrant)
+    pub vector_backend: String,
+    /// Enable hybrid search (dense + sparse)
+    pub enable_hybrid: bool,
+    /// Enable cross-encoder reranking
+    pub enable_reranking: bool,
+    /// Qdrant URL (if using Qdrant backend)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qdrant_url: Option<String>,
+    /// Query to search for (default: "kubernetes pods")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// Number of results to return (default: 3)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<usize>,
+    /// Index the loaded skills' tools instead of the built-in sample documents
+    #[serde(default)]
+    pub use_skill_catalog: bool,
+    /// Named configurations to run the same query against, side by side;
+    /// each overrides the settings above
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub configs: Vec<PipelineConfigOverride>,
+}
+
+/// A named pipeline configuration for comparing search settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct PipelineConfigOverride {
+    /// Name shown for this configuration's results
+    pub name: String,
+    /// Embedding provider (fastembed, openai, ollama)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_provider: Option<String>,
+    /// Embedding model name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_model: Option<String>,
+    /// Vector backend (inmemory, qdrant)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_backend: Option<String>,
+    /// Enable hybrid search (dense + sparse)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_hybrid: Option<bool>,
+    /// Enable cross-encoder reranking
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_reranking: Option<bool>,
+    /// Qdrant URL (if using Qdrant backend)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qdrant_url: Option<String>,
+}
+
+/// Response from testing search pipeline
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TestPipelineResponse {
+    /// Whether the test was successful
+    pub success: bool,
+    /// Indexing statistics
+    pub index_stats: PipelineIndexStats,
+    /// Search results from test query
+    pub search_results: Vec<PipelineSearchResult>,
+    /// Test duration in milliseconds
+    pub duration_ms: u128,
+    /// Overall status message
+    pub message: String,
+    /// One run per named configuration, in request order; the fields above
+    /// report the first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runs: Vec<PipelineRun>,
+}
+
+/// Results of running the test query with one named configuration
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PipelineRun {
+    /// Configuration name
+    pub name: String,
+    /// Whether the pipeline could be built, indexed and searched
+    pub success: bool,
+    /// Indexing statistics
+    pub index_stats: PipelineIndexStats,
+    /// Search results, best first
+    pub search_results: Vec<PipelineSearchResult>,
+    /// Time the search itself took in milliseconds
+    pub search_duration_ms: u64,
+    /// Time the whole run took, including building the pipeline, in milliseconds
+    pub duration_ms: u64,
+    /// Why the run failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Indexing statistics
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct PipelineIndexStats {
+    /// Number of documents indexed
+    pub documents_indexed: usize,
+    /// Indexing duration in milliseconds
+    pub indexing_duration_ms: u64,
+    /// Embedding generation duration in milliseconds
+    pub embedding_duration_ms: u64,
+}
+
+/// Search result from pipeline
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PipelineSearchResult {
+    /// Document ID
+    pub id: String,
+    /// Document content
+    pub content: String,
+    /// Similarity score (0.0 - 1.0)
+    pub score: f32,
+    /// Rerank score if reranking was enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rerank_score: Option<f32>,
+    /// Document metadata
+    pub metadata: DocumentMetadata,
+}
+
+/// Document metadata for search results
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DocumentMetadata {
+    /// Skill name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skill_name: Option<String>,
+    /// Tool name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+    /// Tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+// =============================================================================
+// Agent Configuration Types
+// =============================================================================
+
+/// Agent runtime configuration
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AgentConfig {
+    /// Agent runtime type
+    pub runtime: AgentRuntime,
+    /// Model configuration for the agent
+    pub model_config: AgentModelConfig,
+    /// Execution timeout in seconds
+    pub timeout_secs: u64,
+    /// Claude Code path (auto-detected if None)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claude_code_path: Option<String>,
+}
+
+/// Agent runtime type
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum AgentRuntime {
+    /// Claude Code (Anthropic) - uses system installation
+    ClaudeCode,
+    /// Google Gemini
+    Gemini,
+    /// OpenAI GPT
+    #[serde(rename = "openai", alias = "open-ai")]
+    OpenAI,
+    /// Custom agent implementation
+    Custom,
+}
+
+/// Model configuration for agent
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AgentModelConfig {
+    /// LLM provider (anthropic, openai, google)
+    pub provider: String,
+    /// Model name (e.g., claude-sonnet-4, gpt-4o, gemini-pro)
+    pub model: String,
+    /// Temperature (0.0 - 2.0)
+    pub temperature: f32,
+    /// Maximum tokens for responses
+    pub max_tokens: usize,
+}
+
+impl Default for AgentConfig {
+    fn default() -> Self {
+        Self {
+            runtime: AgentRuntime::ClaudeCode,
+            model_config: AgentModelConfig {
+                provider: "anthropic".to_string(),
+                model: "claude-sonnet-4".to_string(),
+                temperature: 0.7,
+                max_tokens: 4096,
+            },
+            timeout_secs: 300,
+            claude_code_path: None, // Auto-detect on first use
+        }
+    }
+}
+
+/// Response with agent configuration and available options
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GetAgentConfigResponse {
+    /// Current agent configuration
+    pub config: AgentConfig,
+    /// Available agent runtimes
+    pub available_runtimes: Vec<RuntimeInfo>,
+    /// Available models by provider
+    pub available_models: HashMap<String, Vec<ModelInfo>>,
+    /// Whether Claude Code is detected on system
+    pub claude_code_detected: bool,
+    /// Detected Claude Code version if available
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claude_code_version: Option<String>,
+}
+
+/// Information about an agent runtime
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RuntimeInfo {
+    /// Runtime type
+    pub runtime: AgentRuntime,
+    /// Display name
+    pub name: String,
+    /// Description
+    pub description: String,
+    /// Supported LLM providers
+    pub supported_providers: Vec<String>,
+    /// Whether this runtime is available on the system
+    pub available: bool,
+}
+
+/// Information about an LLM model
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ModelInfo {
+    /// Model ID (e.g., claude-sonnet-4)
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Maximum context tokens
+    pub max_tokens: usize,
+    /// Whether the model supports tool use
+    pub supports_tools: bool,
+}
+
+/// Request to update agent configuration
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateAgentConfigRequest {
+    /// Agent runtime (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<AgentRuntime>,
+    /// Model configuration (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_config: Option<AgentModelConfig>,
+    /// Timeout in seconds (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Claude Code path override (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claude_code_path: Option<String>,
+}
+
+/// Response from indexing operation
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IndexResponse {
+    /// Whether indexing was successful
+    pub success: bool,
+    /// Number of documents indexed
+    pub documents_indexed: usize,
+    /// Duration in milliseconds
+    pub duration_ms: u64,
+    /// Status message
+    pub message: String,
+    /// Indexing statistics
+    pub stats: IndexStats,
+}
+
+/// Indexing statistics
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IndexStats {
+    /// Number of documents added
+    pub documents_added: usize,
+    /// Number of documents updated
+    pub documents_updated: usize,
+    /// Total documents in index
+    pub total_documents: usize,
+    /// Index size in bytes (approximate)
+    pub index_size_bytes: Option<usize>,
+}
+
+// ============================================================================
+// Feedback API Types
+// ============================================================================
+
+/// Submit feedback request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitFeedbackRequest {
+    /// The search query that produced this result
+    pub query: String,
+    /// The result ID that feedback is for
+    pub result_id: String,
+    /// The score of the result
+    pub score: f32,
+    /// The rank position in results (0-based)
+    pub rank: usize,
+    /// Type of feedback
+    pub feedback_type: String, // "positive" or "negative"
+    /// Optional reason for the feedback
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Optional comment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Client type (optional, defaults to "http")
+    #[serde(default = "default_client_type")]
+    pub client_type: String,
+}
+
+fn default_client_type() -> String {
+    "http".to_string()
+}
+
+/// Submit feedback response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitFeedbackResponse {
+    /// Success status
+    pub success: bool,
+    /// Feedback ID
+    pub feedback_id: String,
+    /// Message
+    pub message: String,
+}
+
+/// Get feedback request (query parameters)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetFeedbackRequest {
+    /// Filter by query text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// Filter by result ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result_id: Option<String>,
+    /// Filter by feedback type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feedback_type: Option<String>,
+    /// Limit results
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    /// Offset for pagination
+    #[serde(default)]
+    pub offset: usize,
+}
+
+fn default_limit() -> usize {
+    100
+}
+
+/// Get feedback response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetFeedbackResponse {
+    /// Feedback entries
+    pub feedback: Vec<FeedbackEntry>,
+    /// Total count (before pagination)
+    pub total_count: usize,
+    /// Limit used
+    pub limit: usize,
+    /// Offset used
+    pub offset: usize,
+}
+
+/// Feedback entry for API responses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackEntry {
+    /// Feedback ID
+    pub id: String,
+    /// Query text
+    pub query: String,
+    /// Result ID
+    pub result_id: String,
+    /// Result score
+    pub score: f32,
+    /// Result rank
+    pub rank: usize,
+    /// Feedback type
+    pub feedback_type: String,
+    /// Reason
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Comment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Client type
+    pub client_type: String,
+    /// Timestamp
+    pub timestamp: DateTime<Utc>,
+}
+
+// Analytics Dashboard Types
+
+/// Analytics overview response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyticsOverviewResponse {
+    /// Total number of searches
+    pub total_searches: usize,
+    /// Total feedback submissions
+    pub total_feedback: usize,
+    /// Positive feedback count
+    pub positive_feedback: usize,
+    /// Negative feedback count
+    pub negative_feedback: usize,
+    /// Average search latency in milliseconds
+    pub avg_latency_ms: f64,
+    /// Average results per search
+    pub avg_results: f64,
+    /// Most recent searches
+    pub recent_searches: Vec<SearchHistorySummary>,
+}
+
+/// Search history summary for dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHistorySummary {
+    pub query: String,
+    pub results_count: usize,
+    pub duration_ms: u64,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Top queries response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopQueriesResponse {
+    pub queries: Vec<QueryStats>,
+}
+
+/// Query statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryStats {
+    pub query: String,
+    pub count: usize,
+    pub avg_results: f64,
+    pub avg_latency_ms: f64,
+    pub positive_feedback: usize,
+    pub negative_feedback: usize,
+}
+
+/// Feedback statistics response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackStatsResponse {
+    /// Feedback by type
+    pub by_type: Vec<FeedbackTypeCount>,
+    /// Top positively rated results
+    pub top_positive: Vec<ResultFeedbackSummary>,
+    /// Top negatively rated results
+    pub top_negative: Vec<ResultFeedbackSummary>,
+}
+
+/// Feedback count by type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackTypeCount {
+    pub feedback_type: String,
+    pub count: usize,
+}
+
+/// Result feedback summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultFeedbackSummary {
+    pub result_id: String,
+    pub positive_count: usize,
+    pub negative_count: usize,
+    pub total_count: usize,
+}
+
+/// Search timeline data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchTimelineResponse {
+    pub timeline: Vec<TimelineDataPoint>,
+}
+
+/// Timeline data point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineDataPoint {
+    pub timestamp: DateTime<Utc>,
+    pub search_count: usize,
+    pub avg_latency_ms: f64,
+}
+
+/// Example quality evaluations over time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExampleQualityResponse {
+    pub evaluations: Vec<ExampleQualityPoint>,
+}
+
+/// One `skill enhance --evaluate` result for a skill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExampleQualityPoint {
+    pub skill: String,
+    pub timestamp: DateTime<Utc>,
+    pub sampled: usize,
+    /// Share of sampled examples that parsed (0.0-1.0)
+    pub parse_rate: f32,
+    /// Share of sampled examples that passed the dry run (0.0-1.0)
+    pub pass_rate: f32,
+    pub avg_score: f32,
+    pub quality: f32,
+}
+
+/// Usage of every skill executed in the window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillUsageResponse {
+    pub days: u32,
+    /// Most used first
+    pub skills: Vec<SkillUsage>,
+}
+
+/// Execution count, success rate and latency of a group of executions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageStats {
+    pub executions: usize,
+    pub failures: usize,
+    /// Fraction of executions that succeeded (0.0 - 1.0)
+    pub success_rate: f32,
+    pub avg_ms: f64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub last_run: Option<DateTime<Utc>>,
+}
+
+/// Usage of one skill
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillUsage {
+    pub skill: String,
+    #[serde(flatten)]
+    pub stats: UsageStats,
+}
+
+/// Usage of one tool of a skill
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolUsage {
+    pub tool: String,
+    #[serde(flatten)]
+    pub stats: UsageStats,
+}
+
+/// Executions on one day (UTC)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyUsage {
+    pub date: NaiveDate,
+    pub executions: usize,
+    pub failures: usize,
+}
+
+/// Usage of one skill, broken down by tool and day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillAnalyticsResponse {
+    pub skill: String,
+    pub days: u32,
+    #[serde(flatten)]
+    pub stats: UsageStats,
+    /// Most used first
+    pub tools: Vec<ToolUsage>,
+    /// Oldest first; days without executions are omitted
+    pub daily: Vec<DailyUsage>,
+}
+
+// Enhance Progress Types
+
+/// Request to generate examples for a skill
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EnhanceSkillRequest {
+    /// Regenerate examples even if cached ones are fresh, ignoring AI budget limits
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Progress of the most recent `skill enhance` run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnhanceStatusResponse {
+    /// The run, or `None` if enhance was never run
+    pub run: Option<serde_json::Value>,
+    /// Tools processed so far
+    pub processed: usize,
+    /// Tools planned in the run
+    pub total: usize,
+}
@@ -4,10 +4,13 @@
 
 use crate::auth::provider::{AuthProvider, AuthType};
 use crate::auth::token_store::TokenStore;
-use anyhow::{anyhow, Result};
+use crate::output::OutputFormat;
+use anyhow::{anyhow, bail, Result};
 use chrono::Utc;
 use colored::Colorize;
+use skill_runtime::{CredentialIndex, CredentialState, CredentialSummary};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 
 /// Registry of available authentication providers.
@@ -50,6 +53,12 @@ impl ProviderRegistry {
             self.providers.insert("aws".to_string(), Arc::new(provider));
         }
 
+        // Kubernetes kubeconfig contexts (always available)
+        if let Ok(provider) = crate::auth::providers::kubernetes::kubernetes_provider(None, None) {
+            self.providers
+                .insert("kubernetes".to_string(), Arc::new(provider));
+        }
+
         // OpenAI (always available)
         if let Ok(provider) = crate::auth::providers::api_key::openai_provider() {
             self.providers.insert("openai".to_string(), Arc::new(provider));
@@ -59,6 +68,23 @@ impl ProviderRegistry {
         if let Ok(provider) = crate::auth::providers::api_key::anthropic_provider() {
             self.providers.insert("anthropic".to_string(), Arc::new(provider));
         }
+
+        // OIDC issuers from ~/.skill-engine/auth.toml
+        match crate::auth::providers::oidc::AuthConfigFile::load() {
+            Ok(file) => {
+                for (id, settings) in file.providers {
+                    match crate::auth::providers::oidc::OidcProvider::new(&id, settings) {
+                        Ok(provider) => {
+                            self.providers.insert(id, Arc::new(provider));
+                        }
+                        Err(e) => {
+                            tracing::warn!(provider = %id, error = %e, "Skipping auth provider")
+                        }
+                    }
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to load auth provider config"),
+        }
     }
 
     /// Get a provider by ID.
@@ -88,21 +114,34 @@ impl Default for ProviderRegistry {
 }
 
 /// Execute the `skill auth login` command.
+///
+/// `kubeconfig` and `context` pin the `kubernetes` provider to a cluster.
 pub async fn login(
     provider_id: &str,
     skill: Option<&str>,
     instance: Option<&str>,
     scopes: Option<Vec<String>>,
+    kubeconfig: Option<&Path>,
+    context: Option<&str>,
 ) -> Result<()> {
     let registry = ProviderRegistry::new();
     let token_store = TokenStore::new();
 
-    let provider = registry.get(provider_id).ok_or_else(|| {
-        anyhow!(
-            "Unknown provider '{}'. Run 'skill auth providers' to see available providers.",
-            provider_id
-        )
-    })?;
+    let provider: Arc<dyn AuthProvider> = if kubeconfig.is_some() || context.is_some() {
+        if provider_id != "kubernetes" {
+            bail!("--kubeconfig and --context only apply to the kubernetes provider");
+        }
+        Arc::new(crate::auth::providers::kubernetes::kubernetes_provider(
+            kubeconfig, context,
+        )?)
+    } else {
+        registry.get(provider_id).ok_or_else(|| {
+            anyhow!(
+                "Unknown provider '{}'. Run 'skill auth providers' to see available providers.",
+                provider_id
+            )
+        })?
+    };
 
     println!();
     println!(
@@ -115,7 +154,7 @@ pub async fn login(
     let result = provider.authenticate(scopes).await?;
 
     // Store credentials
-    token_store.store(provider_id, skill, instance, &result).await?;
+    token_store.store(provider.as_ref(), skill, instance, &result).await?;
 
     println!();
     println!(
@@ -151,76 +190,123 @@ pub async fn login(
 }
 
 /// Execute the `skill auth status` command.
-pub async fn status(provider_filter: Option<&str>) -> Result<()> {
+///
+/// Lists every credential in the credential index: global, per-skill and
+/// per-instance. Global logins stored before the index existed are read from
+/// the keyring and added to it.
+pub async fn status(
+    provider_filter: Option<&str>,
+    json: bool,
+    output: Option<OutputFormat>,
+) -> Result<()> {
     let registry = ProviderRegistry::new();
     let token_store = TokenStore::new();
+    let mut index = CredentialIndex::load()?;
 
-    println!();
-    println!("{}", "Authentication Status".bold());
-    println!("{}", "─".repeat(50));
+    let mut backfilled = false;
+    for (provider_id, provider) in registry.list() {
+        let indexed = index
+            .credentials
+            .iter()
+            .any(|c| c.provider_id == provider_id && c.skill.is_none() && c.instance.is_none());
+        if indexed {
+            continue;
+        }
+        if let Ok(Some((creds, refresh_token))) = token_store.load(provider_id, None, None).await {
+            index.upsert(CredentialSummary {
+                provider_id: provider_id.to_string(),
+                display_name: provider.display_name().to_string(),
+                auth_type: provider.auth_type().to_string(),
+                skill: None,
+                instance: None,
+                scopes: creds.scopes.clone(),
+                expires_at: creds.expires_at,
+                has_refresh_token: refresh_token.is_some(),
+                updated_at: Utc::now(),
+            });
+            backfilled = true;
+        }
+    }
+    if backfilled {
+        if let Err(e) = index.save() {
+            tracing::warn!(error = %e, "Failed to update credential index");
+        }
+    }
 
-    let mut found_any = false;
+    let statuses = index.statuses(provider_filter);
 
-    for (provider_id, provider) in registry.list() {
-        if let Some(filter) = provider_filter {
-            if provider_id != filter {
-                continue;
-            }
+    let structured = OutputFormat::structured(output);
+    if structured.is_some() || (json && output.is_none()) {
+        let warnings: Vec<&str> = statuses
+            .iter()
+            .filter_map(|status| status.warning.as_deref())
+            .collect();
+        let data = serde_json::json!({
+            "credentials": statuses,
+            "warnings": warnings,
+        });
+        if let Some(output) = structured {
+            return output.print("auth.status", &data);
         }
+        println!("{}", serde_json::to_string_pretty(&data)?);
+        return Ok(());
+    }
 
-        // Try to load credentials
-        if let Ok(Some((creds, _))) = token_store.load(provider_id, None, None).await {
-            found_any = true;
+    println!();
+    println!("{}", "Authentication Status".bold());
+    println!("{}", "─".repeat(50));
 
-            let status_icon = if creds.is_expired() {
-                "✗".red()
-            } else if creds.needs_refresh() {
-                "⚠".yellow()
-            } else {
-                "✓".green()
-            };
+    for status in &statuses {
+        let creds = &status.summary;
+        let (status_icon, state) = match status.state {
+            CredentialState::Active => ("✓".green(), "Active".green()),
+            CredentialState::Refreshable => {
+                ("✓".green(), "Active (refreshes automatically)".green())
+            }
+            CredentialState::ExpiringSoon => ("⚠".yellow(), "Expiring soon".yellow()),
+            CredentialState::Expired => ("✗".red(), "Expired".red()),
+        };
 
-            println!();
-            println!(
-                "{} {} ({})",
-                status_icon,
-                provider.display_name().bold(),
-                provider.auth_type()
-            );
+        println!();
+        println!(
+            "{} {} ({})",
+            status_icon,
+            creds.display_name.bold(),
+            creds.auth_type
+        );
+        println!("  Status: {}", state);
 
-            if creds.is_expired() {
-                println!("  Status: {}", "Expired".red());
-            } else if creds.needs_refresh() {
-                println!("  Status: {}", "Expiring soon".yellow());
-            } else {
-                println!("  Status: {}", "Active".green());
+        if let Some(skill) = &creds.skill {
+            match &creds.instance {
+                Some(instance) => println!("  Skill: {}@{}", skill.cyan(), instance),
+                None => println!("  Skill: {}", skill.cyan()),
             }
+        }
 
-            if let Some(expires) = creds.expires_at {
-                let now = Utc::now();
-                if expires > now {
-                    let duration = expires - now;
-                    if duration.num_days() > 0 {
-                        println!("  Expires: in {} days", duration.num_days());
-                    } else if duration.num_hours() > 0 {
-                        println!("  Expires: in {} hours", duration.num_hours());
-                    } else {
-                        println!("  Expires: in {} minutes", duration.num_minutes());
-                    }
+        if let Some(expires) = creds.expires_at {
+            let now = Utc::now();
+            if expires > now {
+                let duration = expires - now;
+                if duration.num_days() > 0 {
+                    println!("  Expires: in {} days", duration.num_days());
+                } else if duration.num_hours() > 0 {
+                    println!("  Expires: in {} hours", duration.num_hours());
                 } else {
-                    println!("  Expired: {}", expires.format("%Y-%m-%d %H:%M UTC"));
+                    println!("  Expires: in {} minutes", duration.num_minutes());
                 }
             } else {
-                println!("  Expires: Never");
+                println!("  Expired: {}", expires.format("%Y-%m-%d %H:%M UTC"));
             }
+        } else {
+            println!("  Expires: Never");
+        }
 
-            if !creds.scopes.is_empty() {
-                println!("  Scopes: {}", creds.scopes.join(", "));
-            }
+        if !creds.scopes.is_empty() {
+            println!("  Scopes: {}", creds.scopes.join(", "));
         }
     }
 
-    if !found_any {
+    if statuses.is_empty() {
         println!();
         println!("  No active authentication sessions found.");
         println!();
@@ -230,6 +316,17 @@ pub async fn status(provider_filter: Option<&str>) -> Result<()> {
         );
     }
 
+    let warnings: Vec<&str> = statuses
+        .iter()
+        .filter_map(|status| status.warning.as_deref())
+        .collect();
+    if !warnings.is_empty() {
+        println!();
+        for warning in warnings {
+            println!("{} {}", "⚠".yellow(), warning);
+        }
+    }
+
     println!();
 
     Ok(())
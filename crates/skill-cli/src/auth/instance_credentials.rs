@@ -0,0 +1,240 @@
+//! Resolve `keyring:<provider>` references in instance configuration.
+//!
+//! Manifests and instance configs can point at credentials obtained with
+//! `skill auth login` instead of holding them in plaintext:
+//!
+//! ```toml
+//! [skills.github.instances.default]
+//! config.token = "keyring:github"                      # primary secret
+//! env.AWS_SECRET_ACCESS_KEY = "keyring:aws/AWS_SECRET_ACCESS_KEY"
+//! ```
+//!
+//! References are resolved right before execution through the
+//! [`CredentialRefresher`], so OAuth2 tokens that are about to expire are
+//! refreshed first. The resolved values only live in memory. Credentials that
+//! are expired or about to expire without a refresh token are reported
+//! before the execution runs.
+
+use crate::auth::refresh::CredentialRefresher;
+use anyhow::{bail, Result};
+use skill_runtime::instance::ConfigValue;
+use skill_runtime::{CredentialIndex, InstanceConfig};
+use std::collections::HashMap;
+
+/// Prefix marking a value as an auth keyring reference
+pub const CREDENTIAL_REF_PREFIX: &str = "keyring:";
+
+/// Fields used when a reference doesn't name one
+const PRIMARY_FIELDS: &[&str] = &["ACCESS_TOKEN", "API_KEY", "access_token", "api_key"];
+
+/// A parsed `keyring:<provider>[/<field>]` reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CredentialRef {
+    /// Auth provider ID (github, aws, openai, ...)
+    pub provider: String,
+    /// Skill config key or credential field; the primary secret if absent
+    pub field: Option<String>,
+}
+
+/// Parse a `keyring:<provider>[/<field>]` reference.
+///
+/// Returns `None` for ordinary values and for `keyring://` instance secret
+/// references, which are handled by the instance config itself.
+pub fn parse_credential_ref(value: &str) -> Option<CredentialRef> {
+    let rest = value.strip_prefix(CREDENTIAL_REF_PREFIX)?;
+    if rest.starts_with("//") {
+        return None;
+    }
+    let (provider, field) = match rest.split_once('/') {
+        Some((provider, field)) => (provider, Some(field.to_string())),
+        None => (rest, None),
+    };
+    if provider.is_empty() || field.as_deref() == Some("") {
+        return None;
+    }
+    Some(CredentialRef {
+        provider: provider.to_string(),
+        field,
+    })
+}
+
+/// Inject skill credentials and replace credential references.
+///
+/// Providers logged in for the skill (`skill auth login <provider> --skill`)
+/// contribute their skill config (e.g. `GITHUB_TOKEN`) unless the instance
+/// already sets that key. `keyring:` references are then resolved, looking
+/// for the instance's credentials first, then the skill's, then global ones.
+/// Expiring OAuth2 tokens are refreshed on the way. Returns the number of
+/// references resolved.
+pub async fn resolve_instance_credentials(config: &mut InstanceConfig) -> Result<usize> {
+    let skill = config.metadata.skill_name.clone();
+    let instance = config.metadata.instance_name.clone();
+    let refresher = CredentialRefresher::new();
+
+    for (key, value) in refresher.skill_config(&skill, &instance).await? {
+        config.config.entry(key).or_insert(ConfigValue {
+            value,
+            secret: false,
+        });
+    }
+
+    let mut fields_by_provider: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut resolved = 0;
+
+    let values = config
+        .config
+        .values_mut()
+        .filter(|value| !value.secret)
+        .map(|value| &mut value.value)
+        .chain(config.environment.values_mut());
+    for value in values {
+        let Some(reference) = parse_credential_ref(value) else {
+            continue;
+        };
+        if !fields_by_provider.contains_key(&reference.provider) {
+            let fields =
+                credential_fields(&refresher, &reference.provider, &skill, &instance).await?;
+            fields_by_provider.insert(reference.provider.clone(), fields);
+        }
+        let fields = &fields_by_provider[&reference.provider];
+        *value = select_field(fields, &reference)?;
+        resolved += 1;
+    }
+
+    warn_expiring(&skill, &instance, &fields_by_provider);
+
+    if resolved > 0 {
+        tracing::debug!(
+            skill = %skill,
+            instance = %instance,
+            count = resolved,
+            "Resolved credential references"
+        );
+    }
+    Ok(resolved)
+}
+
+/// Warn about expired or expiring credentials this execution relies on:
+/// those logged in for the skill, and global ones it references.
+fn warn_expiring<V>(skill: &str, instance: &str, referenced: &HashMap<String, V>) {
+    let Ok(index) = CredentialIndex::load() else {
+        return;
+    };
+    for credential in &index.credentials {
+        let relevant = match (&credential.skill, &credential.instance) {
+            (Some(s), Some(i)) => s == skill && i == instance,
+            (Some(s), None) => s == skill,
+            (None, _) => referenced.contains_key(&credential.provider_id),
+        };
+        if let Some(warning) = credential.warning().filter(|_| relevant) {
+            eprintln!("Warning: {}", warning);
+        }
+    }
+}
+
+/// Load a provider's credentials and flatten them into named fields.
+async fn credential_fields(
+    refresher: &CredentialRefresher,
+    provider_id: &str,
+    skill: &str,
+    instance: &str,
+) -> Result<HashMap<String, String>> {
+    let Some(credentials) = refresher
+        .credentials(provider_id, Some(skill), Some(instance))
+        .await?
+    else {
+        bail!(
+            "No '{}' credentials for {}@{}. Run 'skill auth login {} --skill {} --instance {}'.",
+            provider_id,
+            skill,
+            instance,
+            provider_id,
+            skill,
+            instance
+        );
+    };
+
+    let mut fields = credentials.data.clone();
+    if let Some(provider) = refresher
+        .provider(provider_id, Some(skill), Some(instance))
+        .await
+    {
+        fields.extend(provider.to_skill_config(&credentials));
+    }
+    Ok(fields)
+}
+
+fn select_field(fields: &HashMap<String, String>, reference: &CredentialRef) -> Result<String> {
+    let found = match &reference.field {
+        Some(field) => fields.get(field),
+        None => PRIMARY_FIELDS.iter().find_map(|field| fields.get(*field)),
+    };
+    if let Some(value) = found {
+        return Ok(value.clone());
+    }
+
+    let mut available: Vec<&str> = fields.keys().map(String::as_str).collect();
+    available.sort();
+    match &reference.field {
+        Some(field) => bail!(
+            "Credentials for '{}' have no field '{}' (available: {})",
+            reference.provider,
+            field,
+            available.join(", ")
+        ),
+        None => bail!(
+            "Credentials for '{}' have no primary secret; use keyring:{}/<field> (available: {})",
+            reference.provider,
+            reference.provider,
+            available.join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_credential_ref() {
+        assert_eq!(
+            parse_credential_ref("keyring:github"),
+            Some(CredentialRef {
+                provider: "github".to_string(),
+                field: None,
+            })
+        );
+        assert_eq!(
+            parse_credential_ref("keyring:aws/AWS_SECRET_ACCESS_KEY"),
+            Some(CredentialRef {
+                provider: "aws".to_string(),
+                field: Some("AWS_SECRET_ACCESS_KEY".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_credential_ref("keyring://skill-engine/aws/prod/token"),
+            None
+        );
+        assert_eq!(parse_credential_ref("keyring:"), None);
+        assert_eq!(parse_credential_ref("keyring:aws/"), None);
+        assert_eq!(parse_credential_ref("ghp_plaintext"), None);
+    }
+
+    #[test]
+    fn test_select_field() {
+        let fields = HashMap::from([
+            ("access_token".to_string(), "gho_abc".to_string()),
+            ("GITHUB_TOKEN".to_string(), "gho_abc".to_string()),
+        ]);
+        let github = parse_credential_ref("keyring:github").unwrap();
+        assert_eq!(select_field(&fields, &github).unwrap(), "gho_abc");
+
+        let missing = parse_credential_ref("keyring:github/refresh_token").unwrap();
+        let err = select_field(&fields, &missing).unwrap_err().to_string();
+        assert!(err.contains("available: GITHUB_TOKEN, access_token"));
+
+        let aws = HashMap::from([("AWS_REGION".to_string(), "us-east-1".to_string())]);
+        let primary = parse_credential_ref("keyring:aws").unwrap();
+        assert!(select_field(&aws, &primary).is_err());
+    }
+}
@@ -2,8 +2,10 @@
 //!
 //! This module provides a pluggable authentication framework supporting:
 //! - OAuth2 Device Flow (GitHub, Google, Azure AD)
+//! - OpenID Connect issuers configured in `~/.skill-engine/auth.toml`
 //! - API Key authentication (OpenAI, Anthropic, etc.)
 //! - AWS IAM credentials
+//! - Kubernetes kubeconfig/context selection
 //! - Custom authentication providers
 //!
 //! # Architecture
@@ -30,6 +32,17 @@
 //! skill auth logout github
 //! ```
 //!
+//! # Instance Credentials
+//!
+//! Instance config and env values of the form `keyring:<provider>[/<field>]`
+//! are replaced with stored credentials at execution time, so manifests never
+//! hold plaintext secrets:
+//!
+//! ```toml
+//! [skills.github.instances.default]
+//! config.token = "keyring:github"
+//! ```
+//!
 //! # For Skill Developers
 //!
 //! Skills receive credentials via the `get-config()` function:
@@ -46,6 +59,8 @@ pub mod provider;
 pub mod providers;
 pub mod token_store;
 pub mod commands;
+pub mod instance_credentials;
+pub mod refresh;
 
 // These re-exports are part of the public API - allow unused for now
 #[allow(unused_imports)]
@@ -54,3 +69,5 @@ pub use provider::{AuthProvider, AuthResult, AuthType, Credentials, ProviderConf
 pub use token_store::TokenStore;
 #[allow(unused_imports)]
 pub use commands::{login, logout, providers, status, ProviderRegistry};
+pub use instance_credentials::resolve_instance_credentials;
+pub use refresh::CredentialRefresher;
@@ -370,6 +370,10 @@ pub struct TokenResponse {
 
     /// Granted scopes (space-separated)
     pub scope: Option<String>,
+
+    /// OpenID Connect ID token
+    #[serde(default)]
+    pub id_token: Option<String>,
 }
 
 /// Error response from OAuth2 endpoints.
@@ -0,0 +1,430 @@
+//! Kubernetes kubeconfig/context provider.
+//!
+//! Pins an instance to a cluster instead of whatever `current-context` the
+//! user's kubeconfig points at:
+//!
+//! ```bash
+//! skill auth login kubernetes --context prod-cluster --skill kubernetes --instance prod
+//! ```
+//!
+//! No secret is stored: the credentials record the kubeconfig path and
+//! context, and kubectl keeps using the kubeconfig's own users. Before a
+//! native `kubectl`/`helm` command runs, the context is checked against the
+//! kubeconfig and passed explicitly (`KUBECONFIG` plus `--context`).
+
+use crate::auth::provider::{
+    AuthProvider, AuthResult, AuthType, CredentialType, Credentials, ProviderConfig,
+};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use dialoguer::{theme::ColorfulTheme, Select};
+use secrecy::SecretString;
+use serde::Deserialize;
+use skill_runtime::InstanceConfig;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Skill config key holding the kubeconfig path
+pub const KUBECONFIG_KEY: &str = "KUBECONFIG";
+
+/// Skill config key holding the pinned context
+pub const KUBE_CONTEXT_KEY: &str = "KUBE_CONTEXT";
+
+/// The parts of a kubeconfig file needed to select a context.
+#[derive(Debug, Default, Deserialize)]
+pub struct Kubeconfig {
+    /// Context kubectl uses when none is given
+    #[serde(rename = "current-context", default)]
+    pub current_context: Option<String>,
+
+    /// Named contexts
+    #[serde(default)]
+    pub contexts: Vec<NamedContext>,
+}
+
+/// A `contexts[]` entry.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NamedContext {
+    /// Context name
+    pub name: String,
+    /// Cluster, user and namespace
+    pub context: KubeContext,
+}
+
+/// Cluster, user and namespace of a context.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct KubeContext {
+    /// Cluster name
+    #[serde(default)]
+    pub cluster: String,
+    /// User name
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Default namespace
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+impl Kubeconfig {
+    /// Parse a kubeconfig file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read kubeconfig {}", path.display()))?;
+        serde_yaml::from_str(&content)
+            .with_context(|| format!("Invalid kubeconfig {}", path.display()))
+    }
+
+    /// Look up a context, listing the available ones if it's missing.
+    pub fn context(&self, name: &str) -> Result<&KubeContext> {
+        match self.contexts.iter().find(|context| context.name == name) {
+            Some(context) => Ok(&context.context),
+            None => bail!(
+                "Context '{}' not found (available: {})",
+                name,
+                self.context_names().join(", ")
+            ),
+        }
+    }
+
+    /// Names of all contexts.
+    pub fn context_names(&self) -> Vec<&str> {
+        self.contexts
+            .iter()
+            .map(|context| context.name.as_str())
+            .collect()
+    }
+}
+
+/// Kubeconfig kubectl would use: the first `$KUBECONFIG` entry, else `~/.kube/config`.
+pub fn default_kubeconfig() -> Option<PathBuf> {
+    if let Some(paths) = std::env::var_os("KUBECONFIG") {
+        if let Some(first) = std::env::split_paths(&paths).find(|p| !p.as_os_str().is_empty()) {
+            return Some(first);
+        }
+    }
+    dirs::home_dir().map(|home| home.join(".kube").join("config"))
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Cluster an instance is pinned to.
+///
+/// Read from the instance config: `KUBECONFIG`/`KUBE_CONTEXT` as injected by
+/// `skill auth login kubernetes`, or the `kubeconfig`/`context` keys used in
+/// manifests.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KubernetesTarget {
+    /// Kubeconfig path
+    pub kubeconfig: Option<PathBuf>,
+    /// Context name
+    pub context: Option<String>,
+}
+
+impl KubernetesTarget {
+    /// Target configured for an instance, if any.
+    pub fn from_config(config: &InstanceConfig) -> Self {
+        let get = |keys: [&str; 2]| {
+            keys.iter()
+                .find_map(|key| config.get_config(key))
+                .filter(|value| !value.is_empty())
+        };
+        Self {
+            kubeconfig: get([KUBECONFIG_KEY, "kubeconfig"]).map(|path| expand_home(&path)),
+            context: get([KUBE_CONTEXT_KEY, "context"]),
+        }
+    }
+
+    /// Whether the instance pins neither a kubeconfig nor a context.
+    pub fn is_empty(&self) -> bool {
+        self.kubeconfig.is_none() && self.context.is_none()
+    }
+
+    /// Check that the kubeconfig exists and contains the context.
+    pub fn validate(&self) -> Result<()> {
+        let Some(path) = self.kubeconfig.clone().or_else(default_kubeconfig) else {
+            bail!("No kubeconfig found; set KUBECONFIG or run 'skill auth login kubernetes'");
+        };
+        let kubeconfig = Kubeconfig::load(&path)?;
+        if let Some(context) = &self.context {
+            kubeconfig
+                .context(context)
+                .with_context(|| format!("Invalid Kubernetes target in {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Validate the target and point a `kubectl` or `helm` command at it.
+    ///
+    /// Other programs are left untouched.
+    pub fn apply(&self, program: &str, command: &mut tokio::process::Command) -> Result<()> {
+        let context_flag = match program {
+            "kubectl" => "--context",
+            "helm" => "--kube-context",
+            _ => return Ok(()),
+        };
+        if self.is_empty() {
+            return Ok(());
+        }
+        self.validate()?;
+
+        if let Some(kubeconfig) = &self.kubeconfig {
+            command.env("KUBECONFIG", kubeconfig);
+        }
+        if let Some(context) = &self.context {
+            command.arg(context_flag).arg(context);
+        }
+        Ok(())
+    }
+}
+
+/// Kubernetes context provider.
+pub struct KubernetesProvider {
+    config: ProviderConfig,
+}
+
+impl KubernetesProvider {
+    /// Create a new Kubernetes provider with the given configuration.
+    ///
+    /// `custom.kubeconfig` and `custom.context` preselect the kubeconfig and
+    /// context; otherwise the default kubeconfig is used and the context is
+    /// chosen interactively.
+    pub fn new(config: ProviderConfig) -> Result<Self> {
+        Ok(Self { config })
+    }
+
+    fn kubeconfig_path(&self) -> Result<PathBuf> {
+        match self.config.custom.get("kubeconfig") {
+            Some(path) => Ok(expand_home(path)),
+            None => default_kubeconfig().context("Could not determine the kubeconfig path"),
+        }
+    }
+
+    fn select_context(&self, kubeconfig: &Kubeconfig) -> Result<String> {
+        if let Some(context) = self.config.custom.get("context") {
+            return Ok(context.clone());
+        }
+        let names = kubeconfig.context_names();
+        match names.as_slice() {
+            [] => bail!("The kubeconfig has no contexts"),
+            [only] => Ok(only.to_string()),
+            _ => {
+                let default = kubeconfig
+                    .current_context
+                    .as_deref()
+                    .and_then(|current| names.iter().position(|name| *name == current))
+                    .unwrap_or(0);
+                let selection = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Kubernetes context")
+                    .items(&names)
+                    .default(default)
+                    .interact()?;
+                Ok(names[selection].to_string())
+            }
+        }
+    }
+
+    fn target(credentials: &Credentials) -> KubernetesTarget {
+        KubernetesTarget {
+            kubeconfig: credentials.data.get("kubeconfig").map(PathBuf::from),
+            context: credentials.data.get("context").cloned(),
+        }
+    }
+}
+
+#[async_trait]
+impl AuthProvider for KubernetesProvider {
+    fn id(&self) -> &str {
+        &self.config.id
+    }
+
+    fn display_name(&self) -> &str {
+        &self.config.display_name
+    }
+
+    fn auth_type(&self) -> AuthType {
+        AuthType::Custom
+    }
+
+    fn config(&self) -> &ProviderConfig {
+        &self.config
+    }
+
+    async fn authenticate(&self, _scopes: Option<Vec<String>>) -> Result<AuthResult> {
+        let path = self.kubeconfig_path()?;
+        let kubeconfig = Kubeconfig::load(&path)?;
+        let context_name = self.select_context(&kubeconfig)?;
+        let context = kubeconfig.context(&context_name)?;
+        let path = path.canonicalize().unwrap_or(path);
+
+        let mut data = HashMap::from([
+            ("kubeconfig".to_string(), path.display().to_string()),
+            ("context".to_string(), context_name.clone()),
+            ("cluster".to_string(), context.cluster.clone()),
+        ]);
+        if let Some(namespace) = &context.namespace {
+            data.insert("namespace".to_string(), namespace.clone());
+        }
+
+        let credentials = Credentials {
+            provider_id: self.config.id.clone(),
+            credential_type: CredentialType::Secret,
+            expires_at: None, // kubectl handles the cluster credentials themselves
+            scopes: vec![],
+            data,
+            metadata: HashMap::from([("cluster".to_string(), context.cluster.clone())]),
+        };
+
+        Ok(AuthResult {
+            credentials,
+            expires_at: None,
+            refresh_token: None,
+            scopes: vec![],
+            metadata: HashMap::new(),
+        })
+    }
+
+    async fn refresh(
+        &self,
+        credentials: &Credentials,
+        _refresh_token: &SecretString,
+    ) -> Result<AuthResult> {
+        // Nothing to refresh - the stored value is only a pointer into the kubeconfig
+        Ok(AuthResult {
+            credentials: credentials.clone(),
+            expires_at: None,
+            refresh_token: None,
+            scopes: vec![],
+            metadata: HashMap::new(),
+        })
+    }
+
+    async fn validate(&self, credentials: &Credentials) -> Result<bool> {
+        // Errors (rather than `false`) so the missing file or context is reported
+        Self::target(credentials)
+            .validate()
+            .with_context(|| format!("Stale '{}' login", self.config.id))?;
+        Ok(true)
+    }
+
+    async fn revoke(&self, _credentials: &Credentials) -> Result<()> {
+        // Nothing to revoke - the kubeconfig is left untouched
+        Ok(())
+    }
+
+    fn to_skill_config(&self, credentials: &Credentials) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+
+        if let Some(kubeconfig) = credentials.data.get("kubeconfig") {
+            config.insert(KUBECONFIG_KEY.to_string(), kubeconfig.clone());
+        }
+
+        if let Some(context) = credentials.data.get("context") {
+            config.insert(KUBE_CONTEXT_KEY.to_string(), context.clone());
+        }
+
+        if let Some(namespace) = credentials.data.get("namespace") {
+            config.insert("KUBE_NAMESPACE".to_string(), namespace.clone());
+        }
+
+        config
+    }
+
+    fn secret_keys(&self) -> Vec<&str> {
+        vec![]
+    }
+}
+
+/// Create the Kubernetes provider, optionally pinned to a kubeconfig and context.
+pub fn kubernetes_provider(
+    kubeconfig: Option<&Path>,
+    context: Option<&str>,
+) -> Result<KubernetesProvider> {
+    let mut custom = HashMap::new();
+    if let Some(kubeconfig) = kubeconfig {
+        custom.insert("kubeconfig".to_string(), kubeconfig.display().to_string());
+    }
+    if let Some(context) = context {
+        custom.insert("context".to_string(), context.to_string());
+    }
+
+    let config = ProviderConfig {
+        id: "kubernetes".to_string(),
+        display_name: "Kubernetes".to_string(),
+        auth_type: AuthType::Custom,
+        oauth2: None,
+        api_key: None,
+        aws: None,
+        custom,
+    };
+
+    KubernetesProvider::new(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KUBECONFIG: &str = r#"
+apiVersion: v1
+kind: Config
+current-context: dev
+clusters:
+  - name: dev-cluster
+    cluster:
+      server: https://dev.example.com
+contexts:
+  - name: dev
+    context:
+      cluster: dev-cluster
+      user: dev-user
+  - name: prod-cluster
+    context:
+      cluster: prod
+      user: prod-user
+      namespace: payments
+users: []
+"#;
+
+    #[test]
+    fn test_kubeconfig_contexts() {
+        let kubeconfig: Kubeconfig = serde_yaml::from_str(KUBECONFIG).unwrap();
+        assert_eq!(kubeconfig.current_context.as_deref(), Some("dev"));
+        assert_eq!(kubeconfig.context_names(), vec!["dev", "prod-cluster"]);
+
+        let prod = kubeconfig.context("prod-cluster").unwrap();
+        assert_eq!(prod.cluster, "prod");
+        assert_eq!(prod.namespace.as_deref(), Some("payments"));
+
+        let err = kubeconfig.context("staging").unwrap_err().to_string();
+        assert!(err.contains("available: dev, prod-cluster"));
+    }
+
+    #[test]
+    fn test_target_apply() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config");
+        std::fs::write(&path, KUBECONFIG).unwrap();
+
+        let target = KubernetesTarget {
+            kubeconfig: Some(path.clone()),
+            context: Some("prod-cluster".to_string()),
+        };
+        let mut command = tokio::process::Command::new("kubectl");
+        target.apply("kubectl", &mut command).unwrap();
+        let args: Vec<_> = command.as_std().get_args().collect();
+        assert_eq!(args, ["--context", "prod-cluster"]);
+
+        let missing = KubernetesTarget {
+            kubeconfig: Some(path),
+            context: Some("staging".to_string()),
+        };
+        let mut command = tokio::process::Command::new("kubectl");
+        assert!(missing.apply("kubectl", &mut command).is_err());
+        assert!(missing.apply("terraform", &mut command).is_ok());
+    }
+}
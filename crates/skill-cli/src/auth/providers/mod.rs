@@ -3,6 +3,8 @@
 pub mod oauth2;
 pub mod api_key;
 pub mod aws;
+pub mod kubernetes;
+pub mod oidc;
 
 // These re-exports are part of the public API
 #[allow(unused_imports)]
@@ -11,3 +13,7 @@ pub use oauth2::OAuth2Provider;
 pub use api_key::ApiKeyProvider;
 #[allow(unused_imports)]
 pub use aws::AwsProvider;
+#[allow(unused_imports)]
+pub use kubernetes::KubernetesProvider;
+#[allow(unused_imports)]
+pub use oidc::OidcProvider;
@@ -5,6 +5,10 @@
 //! - User authenticates in their browser
 //! - CLI polls for completion
 //!
+//! Providers configured with `AuthType::OAuth2AuthorizationCode` use the
+//! authorization code flow with PKCE (RFC 7636) instead, receiving the
+//! redirect on a short-lived loopback server.
+//!
 //! Supported providers: GitHub, Google, Azure AD, and any OAuth2 provider
 //! that implements the Device Authorization Grant.
 
@@ -15,13 +19,21 @@ use crate::auth::provider::{
 };
 use anyhow::{anyhow, bail, Context, Result};
 use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
 use chrono::{Duration, Utc};
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
 use reqwest::Client;
+use ring::rand::{SecureRandom, SystemRandom};
 use secrecy::{ExposeSecret, SecretString};
 use std::collections::HashMap;
 use std::time::Duration as StdDuration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// How long to wait for the browser redirect in the PKCE flow
+const PKCE_CALLBACK_TIMEOUT: StdDuration = StdDuration::from_secs(300);
 
 /// OAuth2 provider implementing Device Authorization Grant (RFC 8628).
 pub struct OAuth2Provider {
@@ -169,8 +181,115 @@ impl OAuth2Provider {
         }
     }
 
+    /// Run the device flow: show the user code, then poll for the token.
+    async fn device_flow(&self, scopes: &[String]) -> Result<TokenResponse> {
+        // Step 1: Request device code
+        let device_response = self.request_device_code(scopes).await?;
+
+        // Step 2: Display instructions to user
+        println!();
+        println!("{} Open this URL in your browser:", "->".cyan().bold());
+        println!("   {}", device_response.verification_uri.yellow());
+        println!();
+        println!("{} Enter code: {}", "->".cyan().bold(),
+                 device_response.user_code.green().bold());
+
+        if let Some(uri) = &device_response.verification_uri_complete {
+            println!();
+            println!("   Or open: {}", uri.dimmed());
+        }
+        println!();
+
+        // Step 3: Poll for token
+        self.poll_for_token(&device_response).await
+    }
+
+    /// Run the authorization code flow with PKCE via a loopback redirect.
+    async fn authorization_code_flow(&self, scopes: &[String]) -> Result<TokenResponse> {
+        let oauth2 = self.oauth2_config();
+        let endpoint = oauth2
+            .authorization_endpoint
+            .as_ref()
+            .ok_or_else(|| anyhow!("Authorization endpoint not configured"))?;
+
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .context("Failed to start local callback server")?;
+        let redirect_uri = format!(
+            "http://127.0.0.1:{}/callback",
+            listener.local_addr()?.port()
+        );
+        let verifier = random_token(32)?;
+        let state = random_token(16)?;
+
+        let mut url = url::Url::parse(endpoint).context("Invalid authorization endpoint")?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &oauth2.client_id)
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("scope", &scopes.join(" "))
+            .append_pair("state", &state)
+            .append_pair("code_challenge", &pkce_challenge(&verifier))
+            .append_pair("code_challenge_method", "S256");
+        if let Some(audience) = &oauth2.audience {
+            url.query_pairs_mut().append_pair("audience", audience);
+        }
+
+        println!();
+        println!("{} Open this URL in your browser:", "->".cyan().bold());
+        println!("   {}", url.as_str().yellow());
+        println!();
+
+        let pb = ProgressBar::new_spinner();
+        pb.set_style(
+            ProgressStyle::default_spinner()
+                .template("{spinner:.cyan} {msg}")
+                .unwrap(),
+        );
+        pb.set_message("Waiting for browser login...");
+        pb.enable_steady_tick(StdDuration::from_millis(100));
+        let code =
+            tokio::time::timeout(PKCE_CALLBACK_TIMEOUT, wait_for_callback(&listener, &state)).await;
+        pb.finish_and_clear();
+        let code = code.map_err(|_| anyhow!("Authorization timed out. Please try again."))??;
+
+        let mut params = vec![
+            ("client_id", oauth2.client_id.clone()),
+            ("grant_type", "authorization_code".to_string()),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+            ("code_verifier", verifier),
+        ];
+
+        if let Some(secret) = &oauth2.client_secret {
+            params.push(("client_secret", secret.clone()));
+        }
+
+        let response = self
+            .client
+            .post(&oauth2.token_endpoint)
+            .form(&params)
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .context("Failed to exchange authorization code")?;
+
+        if !response.status().is_success() {
+            let error: OAuth2Error = response.json().await.unwrap_or_else(|_| OAuth2Error {
+                error: "unknown_error".to_string(),
+                error_description: Some("Failed to exchange authorization code".to_string()),
+                error_uri: None,
+            });
+            bail!("Authorization failed: {}", error);
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse token response")
+    }
+
     /// Refresh an access token using a refresh token.
-    #[allow(dead_code)]
     async fn refresh_token(&self, refresh_token: &str) -> Result<TokenResponse> {
         let oauth2 = self.oauth2_config();
 
@@ -266,25 +385,10 @@ impl AuthProvider for OAuth2Provider {
         let oauth2 = self.oauth2_config();
         let scopes = scopes.unwrap_or_else(|| oauth2.scopes.clone());
 
-        // Step 1: Request device code
-        let device_response = self.request_device_code(&scopes).await?;
-
-        // Step 2: Display instructions to user
-        println!();
-        println!("{} Open this URL in your browser:", "->".cyan().bold());
-        println!("   {}", device_response.verification_uri.yellow());
-        println!();
-        println!("{} Enter code: {}", "->".cyan().bold(),
-                 device_response.user_code.green().bold());
-
-        if let Some(uri) = &device_response.verification_uri_complete {
-            println!();
-            println!("   Or open: {}", uri.dimmed());
-        }
-        println!();
-
-        // Step 3: Poll for token
-        let token_response = self.poll_for_token(&device_response).await?;
+        let token_response = match self.config.auth_type {
+            AuthType::OAuth2AuthorizationCode => self.authorization_code_flow(&scopes).await?,
+            _ => self.device_flow(&scopes).await?,
+        };
 
         // Step 4: Build credentials
         let expires_at = token_response.expires_in
@@ -300,9 +404,7 @@ impl AuthProvider for OAuth2Provider {
             credential_type: CredentialType::OAuth2AccessToken,
             expires_at,
             scopes: granted_scopes.clone(),
-            data: HashMap::from([
-                ("access_token".to_string(), token_response.access_token.clone()),
-            ]),
+            data: token_data(&token_response),
             metadata: HashMap::from([
                 ("token_type".to_string(), token_response.token_type),
             ]),
@@ -333,9 +435,7 @@ impl AuthProvider for OAuth2Provider {
             credential_type: CredentialType::OAuth2AccessToken,
             expires_at,
             scopes: granted_scopes.clone(),
-            data: HashMap::from([
-                ("access_token".to_string(), token_response.access_token.clone()),
-            ]),
+            data: token_data(&token_response),
             metadata: HashMap::from([
                 ("token_type".to_string(), token_response.token_type),
             ]),
@@ -394,6 +494,99 @@ impl AuthProvider for OAuth2Provider {
     }
 }
 
+/// Credential data from a token response (access token, plus ID token for OIDC).
+fn token_data(token_response: &TokenResponse) -> HashMap<String, String> {
+    let mut data = HashMap::from([(
+        "access_token".to_string(),
+        token_response.access_token.clone(),
+    )]);
+    if let Some(id_token) = &token_response.id_token {
+        data.insert("id_token".to_string(), id_token.clone());
+    }
+    data
+}
+
+/// Random URL-safe string with `bytes` bytes of entropy.
+fn random_token(bytes: usize) -> Result<String> {
+    let mut buf = vec![0u8; bytes];
+    SystemRandom::new()
+        .fill(&mut buf)
+        .map_err(|_| anyhow!("Failed to generate random bytes"))?;
+    Ok(URL_SAFE_NO_PAD.encode(buf))
+}
+
+/// S256 code challenge for a PKCE verifier (RFC 7636 section 4.2).
+fn pkce_challenge(verifier: &str) -> String {
+    let digest = ring::digest::digest(&ring::digest::SHA256, verifier.as_bytes());
+    URL_SAFE_NO_PAD.encode(digest.as_ref())
+}
+
+/// Extract the authorization code from a loopback request target.
+///
+/// Returns `Ok(None)` for requests that aren't the redirect (e.g. favicon).
+fn parse_callback(target: &str, expected_state: &str) -> Result<Option<String>> {
+    let url = url::Url::parse(&format!("http://127.0.0.1{}", target))
+        .context("Invalid callback request")?;
+    if url.path() != "/callback" {
+        return Ok(None);
+    }
+
+    let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+    if let Some(error) = params.get("error") {
+        let error = OAuth2Error {
+            error: error.clone(),
+            error_description: params.get("error_description").cloned(),
+            error_uri: params.get("error_uri").cloned(),
+        };
+        bail!("Authorization failed: {}", error);
+    }
+    if params.get("state").map(String::as_str) != Some(expected_state) {
+        bail!("Authorization failed: state mismatch");
+    }
+    params
+        .get("code")
+        .cloned()
+        .map(Some)
+        .ok_or_else(|| anyhow!("Authorization failed: no code in redirect"))
+}
+
+/// Serve loopback requests until the authorization redirect arrives.
+async fn wait_for_callback(listener: &TcpListener, state: &str) -> Result<String> {
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let mut buf = vec![0u8; 8192];
+        let n = stream.read(&mut buf).await?;
+        let request = String::from_utf8_lossy(&buf[..n]);
+        let target = request
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .unwrap_or("/");
+
+        let result = parse_callback(target, state);
+        let (status, message) = match &result {
+            Ok(Some(_)) => ("200 OK", "Login complete. You can close this window."),
+            Ok(None) => ("404 Not Found", "Not found"),
+            Err(_) => (
+                "400 Bad Request",
+                "Login failed. Return to the terminal for details.",
+            ),
+        };
+        let body = format!("<html><body><p>{}</p></body></html>", message);
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes()).await;
+
+        if let Some(code) = result? {
+            return Ok(code);
+        }
+    }
+}
+
 /// Create a GitHub OAuth2 provider with default configuration.
 pub fn github_provider(client_id: String) -> Result<OAuth2Provider> {
     let config = ProviderConfig {
@@ -446,6 +639,36 @@ pub fn google_provider(client_id: String, client_secret: Option<String>) -> Resu
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pkce_challenge() {
+        // RFC 7636 appendix B
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        assert_eq!(random_token(32).unwrap().len(), 43);
+    }
+
+    #[test]
+    fn test_parse_callback() {
+        assert_eq!(
+            parse_callback("/callback?code=abc&state=xyz", "xyz").unwrap(),
+            Some("abc".to_string())
+        );
+        assert_eq!(parse_callback("/favicon.ico", "xyz").unwrap(), None);
+        assert!(parse_callback("/callback?code=abc&state=other", "xyz").is_err());
+
+        let err = parse_callback(
+            "/callback?error=access_denied&error_description=User%20cancelled&state=xyz",
+            "xyz",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Authorization failed: access_denied: User cancelled"
+        );
+    }
+
     #[test]
     fn test_credentials_expiry() {
         let mut creds = Credentials {
@@ -0,0 +1,403 @@
+//! Generic OpenID Connect provider for any issuer (Okta, Auth0, Keycloak, ...).
+//!
+//! Providers are declared in `~/.skill-engine/auth.toml`:
+//!
+//! ```toml
+//! [providers.okta]
+//! type = "oidc"
+//! issuer = "https://dev-123456.okta.com/oauth2/default"
+//! client_id = "0oa1b2c3d4"
+//! scopes = ["openid", "profile", "offline_access"]
+//! flow = "pkce"          # "device" (default when the issuer supports it) or "pkce"
+//! ```
+//!
+//! Endpoints come from the issuer's discovery document
+//! (`/.well-known/openid-configuration`), fetched on first use. The flows
+//! themselves are handled by [`OAuth2Provider`].
+
+use crate::auth::provider::{
+    AuthProvider, AuthResult, AuthType, Credentials, OAuth2Config, ProviderConfig,
+};
+use crate::auth::providers::oauth2::OAuth2Provider;
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use secrecy::SecretString;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tokio::sync::OnceCell;
+
+/// Provider type in `auth.toml` handled by this module
+pub const OIDC_PROVIDER_TYPE: &str = "oidc";
+
+/// Path of the auth provider configuration (`~/.skill-engine/auth.toml`).
+pub fn auth_config_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".skill-engine").join("auth.toml"))
+}
+
+/// `auth.toml` contents.
+#[derive(Debug, Default, Deserialize)]
+pub struct AuthConfigFile {
+    /// Providers keyed by ID (the name used with `skill auth login <id>`)
+    #[serde(default)]
+    pub providers: HashMap<String, OidcSettings>,
+}
+
+impl AuthConfigFile {
+    /// Load `auth.toml`, or an empty config if it doesn't exist.
+    pub fn load() -> Result<Self> {
+        let Some(path) = auth_config_path().filter(|path| path.exists()) else {
+            return Ok(Self::default());
+        };
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Invalid {}", path.display()))
+    }
+}
+
+/// Which OAuth2 flow to run against the issuer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OidcFlow {
+    /// Device Authorization Grant (RFC 8628)
+    Device,
+    /// Authorization code with PKCE and a loopback redirect
+    Pkce,
+}
+
+/// One `[providers.<id>]` entry of type `oidc`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OidcSettings {
+    /// Provider type; only `oidc` is supported
+    #[serde(rename = "type")]
+    pub provider_type: String,
+
+    /// Issuer URL; discovery is read from `<issuer>/.well-known/openid-configuration`
+    pub issuer: String,
+
+    /// OAuth2 client ID registered with the issuer
+    pub client_id: String,
+
+    /// Client secret, for confidential clients
+    #[serde(default)]
+    pub client_secret: Option<String>,
+
+    /// Scopes to request
+    #[serde(default = "default_scopes")]
+    pub scopes: Vec<String>,
+
+    /// Flow to use; defaults to device flow when the issuer supports it
+    #[serde(default)]
+    pub flow: Option<OidcFlow>,
+
+    /// Audience parameter (Auth0)
+    #[serde(default)]
+    pub audience: Option<String>,
+
+    /// Name shown in `skill auth` output
+    #[serde(default)]
+    pub display_name: Option<String>,
+}
+
+fn default_scopes() -> Vec<String> {
+    vec![
+        "openid".to_string(),
+        "profile".to_string(),
+        "email".to_string(),
+        "offline_access".to_string(),
+    ]
+}
+
+/// The parts of an OpenID Provider discovery document we use.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OidcDiscovery {
+    /// Issuer identifier; must match the configured issuer
+    pub issuer: String,
+    /// Authorization endpoint (PKCE flow)
+    pub authorization_endpoint: Option<String>,
+    /// Token endpoint
+    pub token_endpoint: String,
+    /// Device authorization endpoint (device flow)
+    pub device_authorization_endpoint: Option<String>,
+    /// Token revocation endpoint
+    pub revocation_endpoint: Option<String>,
+}
+
+/// URL of an issuer's discovery document.
+pub fn discovery_url(issuer: &str) -> String {
+    format!(
+        "{}/.well-known/openid-configuration",
+        issuer.trim_end_matches('/')
+    )
+}
+
+/// Fetch and check an issuer's discovery document.
+pub async fn discover(issuer: &str) -> Result<OidcDiscovery> {
+    let url = discovery_url(issuer);
+    let response = reqwest::Client::new()
+        .get(&url)
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch {}", url))?;
+    if !response.status().is_success() {
+        bail!(
+            "OIDC discovery failed: {} returned {}",
+            url,
+            response.status()
+        );
+    }
+    let discovery: OidcDiscovery = response
+        .json()
+        .await
+        .with_context(|| format!("Invalid discovery document at {}", url))?;
+    if discovery.issuer.trim_end_matches('/') != issuer.trim_end_matches('/') {
+        bail!(
+            "OIDC discovery issuer mismatch: expected {}, got {}",
+            issuer,
+            discovery.issuer
+        );
+    }
+    Ok(discovery)
+}
+
+/// Build the OAuth2 provider configuration from settings and discovery.
+pub fn provider_config(
+    id: &str,
+    settings: &OidcSettings,
+    discovery: &OidcDiscovery,
+) -> Result<ProviderConfig> {
+    let flow = match settings.flow {
+        Some(flow) => flow,
+        None if discovery.device_authorization_endpoint.is_some() => OidcFlow::Device,
+        None => OidcFlow::Pkce,
+    };
+    let auth_type = match flow {
+        OidcFlow::Device if discovery.device_authorization_endpoint.is_none() => {
+            bail!(
+                "{} does not support the device flow; set flow = \"pkce\"",
+                settings.issuer
+            )
+        }
+        OidcFlow::Device => AuthType::OAuth2DeviceFlow,
+        OidcFlow::Pkce if discovery.authorization_endpoint.is_none() => {
+            bail!(
+                "{} has no authorization endpoint; set flow = \"device\"",
+                settings.issuer
+            )
+        }
+        OidcFlow::Pkce => AuthType::OAuth2AuthorizationCode,
+    };
+
+    Ok(ProviderConfig {
+        id: id.to_string(),
+        display_name: settings
+            .display_name
+            .clone()
+            .unwrap_or_else(|| id.to_string()),
+        auth_type,
+        oauth2: Some(OAuth2Config {
+            device_authorization_endpoint: discovery.device_authorization_endpoint.clone(),
+            authorization_endpoint: discovery.authorization_endpoint.clone(),
+            token_endpoint: discovery.token_endpoint.clone(),
+            revocation_endpoint: discovery.revocation_endpoint.clone(),
+            client_id: settings.client_id.clone(),
+            client_secret: settings.client_secret.clone(),
+            scopes: settings.scopes.clone(),
+            audience: settings.audience.clone(),
+        }),
+        api_key: None,
+        aws: None,
+        custom: HashMap::from([("issuer".to_string(), settings.issuer.clone())]),
+    })
+}
+
+/// OIDC provider that discovers its endpoints on first use.
+pub struct OidcProvider {
+    /// Configuration before discovery (no endpoints yet)
+    config: ProviderConfig,
+    settings: OidcSettings,
+    inner: OnceCell<OAuth2Provider>,
+}
+
+impl OidcProvider {
+    /// Create a provider from an `auth.toml` entry.
+    pub fn new(id: &str, settings: OidcSettings) -> Result<Self> {
+        if settings.provider_type != OIDC_PROVIDER_TYPE {
+            bail!(
+                "Unsupported provider type '{}' for '{}' (expected \"{}\")",
+                settings.provider_type,
+                id,
+                OIDC_PROVIDER_TYPE
+            );
+        }
+        let config = ProviderConfig {
+            id: id.to_string(),
+            display_name: settings
+                .display_name
+                .clone()
+                .unwrap_or_else(|| id.to_string()),
+            auth_type: match settings.flow {
+                Some(OidcFlow::Pkce) => AuthType::OAuth2AuthorizationCode,
+                _ => AuthType::OAuth2DeviceFlow,
+            },
+            oauth2: None,
+            api_key: None,
+            aws: None,
+            custom: HashMap::from([("issuer".to_string(), settings.issuer.clone())]),
+        };
+        Ok(Self {
+            config,
+            settings,
+            inner: OnceCell::new(),
+        })
+    }
+
+    async fn inner(&self) -> Result<&OAuth2Provider> {
+        self.inner
+            .get_or_try_init(|| async {
+                let discovery = discover(&self.settings.issuer).await?;
+                OAuth2Provider::new(provider_config(
+                    &self.config.id,
+                    &self.settings,
+                    &discovery,
+                )?)
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl AuthProvider for OidcProvider {
+    fn id(&self) -> &str {
+        &self.config.id
+    }
+
+    fn display_name(&self) -> &str {
+        &self.config.display_name
+    }
+
+    fn auth_type(&self) -> AuthType {
+        self.config().auth_type
+    }
+
+    /// Discovered configuration once available, so stored credentials carry
+    /// the token endpoint needed for refresh.
+    fn config(&self) -> &ProviderConfig {
+        match self.inner.get() {
+            Some(inner) => inner.config(),
+            None => &self.config,
+        }
+    }
+
+    async fn authenticate(&self, scopes: Option<Vec<String>>) -> Result<AuthResult> {
+        self.inner().await?.authenticate(scopes).await
+    }
+
+    async fn refresh(
+        &self,
+        credentials: &Credentials,
+        refresh_token: &SecretString,
+    ) -> Result<AuthResult> {
+        self.inner()
+            .await?
+            .refresh(credentials, refresh_token)
+            .await
+    }
+
+    async fn validate(&self, credentials: &Credentials) -> Result<bool> {
+        Ok(!credentials.is_expired())
+    }
+
+    async fn revoke(&self, credentials: &Credentials) -> Result<()> {
+        self.inner().await?.revoke(credentials).await
+    }
+
+    fn to_skill_config(&self, credentials: &Credentials) -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        let prefix = self.config.id.to_uppercase().replace('-', "_");
+
+        if let Some(token) = credentials.data.get("access_token") {
+            config.insert(format!("{}_TOKEN", prefix), token.clone());
+            config.insert("ACCESS_TOKEN".to_string(), token.clone());
+        }
+        if let Some(id_token) = credentials.data.get("id_token") {
+            config.insert(format!("{}_ID_TOKEN", prefix), id_token.clone());
+            config.insert("ID_TOKEN".to_string(), id_token.clone());
+        }
+
+        config
+    }
+
+    fn secret_keys(&self) -> Vec<&str> {
+        vec!["ACCESS_TOKEN", "ID_TOKEN"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn discovery(device: bool) -> OidcDiscovery {
+        OidcDiscovery {
+            issuer: "https://auth.example.com/realms/dev".to_string(),
+            authorization_endpoint: Some(
+                "https://auth.example.com/realms/dev/protocol/openid-connect/auth".to_string(),
+            ),
+            token_endpoint: "https://auth.example.com/realms/dev/protocol/openid-connect/token"
+                .to_string(),
+            device_authorization_endpoint: device.then(|| {
+                "https://auth.example.com/realms/dev/protocol/openid-connect/auth/device"
+                    .to_string()
+            }),
+            revocation_endpoint: None,
+        }
+    }
+
+    #[test]
+    fn test_auth_config_file() {
+        let file: AuthConfigFile = toml::from_str(
+            r#"
+[providers.keycloak]
+type = "oidc"
+issuer = "https://auth.example.com/realms/dev/"
+client_id = "skill-cli"
+flow = "pkce"
+"#,
+        )
+        .unwrap();
+        let settings = &file.providers["keycloak"];
+        assert_eq!(settings.flow, Some(OidcFlow::Pkce));
+        assert!(settings.scopes.contains(&"openid".to_string()));
+        assert_eq!(
+            discovery_url(&settings.issuer),
+            "https://auth.example.com/realms/dev/.well-known/openid-configuration"
+        );
+    }
+
+    #[test]
+    fn test_provider_config_flow_selection() {
+        let mut settings = OidcSettings {
+            provider_type: "oidc".to_string(),
+            issuer: "https://auth.example.com/realms/dev".to_string(),
+            client_id: "skill-cli".to_string(),
+            client_secret: None,
+            scopes: default_scopes(),
+            flow: None,
+            audience: None,
+            display_name: None,
+        };
+
+        let config = provider_config("keycloak", &settings, &discovery(true)).unwrap();
+        assert_eq!(config.auth_type, AuthType::OAuth2DeviceFlow);
+        let config = provider_config("keycloak", &settings, &discovery(false)).unwrap();
+        assert_eq!(config.auth_type, AuthType::OAuth2AuthorizationCode);
+        assert_eq!(
+            config.oauth2.unwrap().token_endpoint,
+            "https://auth.example.com/realms/dev/protocol/openid-connect/token"
+        );
+
+        settings.flow = Some(OidcFlow::Device);
+        assert!(provider_config("keycloak", &settings, &discovery(false)).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+//! Credential refresh layer for skill executions.
+//!
+//! Every path that hands credentials to a skill goes through
+//! [`CredentialRefresher`], which checks expiry first and refreshes OAuth2
+//! tokens through the provider's token endpoint, writing the new tokens back
+//! to the keyring. Refreshes are serialized per credential so concurrent
+//! executions don't race to spend the same refresh token. Credentials are
+//! then validated by their provider (e.g. a pinned Kubernetes context must
+//! still exist in its kubeconfig).
+
+use crate::auth::commands::ProviderRegistry;
+use crate::auth::provider::{AuthProvider, Credentials};
+use crate::auth::providers::OAuth2Provider;
+use crate::auth::token_store::TokenStore;
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Loads credentials for executions, refreshing them when they expire.
+pub struct CredentialRefresher {
+    registry: ProviderRegistry,
+    store: TokenStore,
+    locks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+}
+
+impl CredentialRefresher {
+    /// Create a refresher over the default providers and keyring.
+    pub fn new() -> Self {
+        Self {
+            registry: ProviderRegistry::new(),
+            store: TokenStore::new(),
+            locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Fresh credentials for a provider, most specific scope first.
+    ///
+    /// Looks for credentials stored for `skill`+`instance`, then `skill`,
+    /// then globally.
+    pub async fn credentials(
+        &self,
+        provider_id: &str,
+        skill: Option<&str>,
+        instance: Option<&str>,
+    ) -> Result<Option<Credentials>> {
+        let mut scopes = Vec::new();
+        if let Some(skill) = skill {
+            if instance.is_some() {
+                scopes.push((Some(skill), instance));
+            }
+            scopes.push((Some(skill), None));
+        }
+        scopes.push((None, None));
+
+        for (skill_scope, instance_scope) in scopes {
+            if let Some(credentials) = self
+                .scoped_credentials(provider_id, skill_scope, instance_scope)
+                .await?
+            {
+                self.validate(provider_id, &credentials).await?;
+                return Ok(Some(credentials));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Skill config for every provider logged in for this skill.
+    ///
+    /// Only credentials stored with `skill auth login <provider> --skill`
+    /// are included; global logins must be referenced explicitly with
+    /// `keyring:<provider>`. Providers whose credentials can't be read are
+    /// skipped, but credentials that fail validation are an error: running
+    /// without them could target the wrong account or cluster.
+    pub async fn skill_config(
+        &self,
+        skill: &str,
+        instance: &str,
+    ) -> Result<HashMap<String, String>> {
+        let mut config = HashMap::new();
+        let mut provider_ids: Vec<&str> =
+            self.registry.list().into_iter().map(|(id, _)| id).collect();
+        provider_ids.sort();
+
+        for provider_id in provider_ids {
+            for instance_scope in [Some(instance), None] {
+                let credentials = match self
+                    .scoped_credentials(provider_id, Some(skill), instance_scope)
+                    .await
+                {
+                    Ok(Some(credentials)) => credentials,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        tracing::debug!(
+                            provider = %provider_id,
+                            skill = %skill,
+                            error = %e,
+                            "Skipping credentials"
+                        );
+                        break;
+                    }
+                };
+                self.validate(provider_id, &credentials).await?;
+                if let Some(provider) = self.registry.get(provider_id) {
+                    config.extend(provider.to_skill_config(&credentials));
+                }
+                break;
+            }
+        }
+        Ok(config)
+    }
+
+    /// Provider used to refresh credentials.
+    ///
+    /// Falls back to the OAuth2 settings saved at login when the provider
+    /// isn't configured in this environment (e.g. no client ID variable).
+    pub async fn provider(
+        &self,
+        provider_id: &str,
+        skill: Option<&str>,
+        instance: Option<&str>,
+    ) -> Option<Arc<dyn AuthProvider>> {
+        if let Some(provider) = self.registry.get(provider_id) {
+            return Some(provider);
+        }
+        let config = self
+            .store
+            .load_provider_config(provider_id, skill, instance)
+            .await
+            .ok()
+            .flatten()?;
+        OAuth2Provider::new(config)
+            .ok()
+            .map(|provider| Arc::new(provider) as Arc<dyn AuthProvider>)
+    }
+
+    /// Check credentials with their provider before they're used.
+    async fn validate(&self, provider_id: &str, credentials: &Credentials) -> Result<()> {
+        let Some(provider) = self.registry.get(provider_id) else {
+            return Ok(());
+        };
+        if !provider.validate(credentials).await? {
+            bail!(
+                "Credentials for '{}' are no longer valid. Please run 'skill auth login {}'.",
+                provider_id,
+                provider_id
+            );
+        }
+        Ok(())
+    }
+
+    async fn scoped_credentials(
+        &self,
+        provider_id: &str,
+        skill: Option<&str>,
+        instance: Option<&str>,
+    ) -> Result<Option<Credentials>> {
+        let lock = self.lock_for(provider_id, skill, instance).await;
+        let _guard = lock.lock().await;
+
+        match self.provider(provider_id, skill, instance).await {
+            Some(provider) => {
+                self.store
+                    .get_credentials(provider.as_ref(), skill, instance)
+                    .await
+            }
+            None => match self.store.load(provider_id, skill, instance).await? {
+                Some((credentials, _)) if credentials.is_expired() => bail!(
+                    "Credentials for '{}' expired and can't be refreshed. Please run 'skill auth login {}'.",
+                    provider_id,
+                    provider_id
+                ),
+                found => Ok(found.map(|(credentials, _)| credentials)),
+            },
+        }
+    }
+
+    async fn lock_for(
+        &self,
+        provider_id: &str,
+        skill: Option<&str>,
+        instance: Option<&str>,
+    ) -> Arc<Mutex<()>> {
+        let key = format!(
+            "{}:{}:{}",
+            provider_id,
+            skill.unwrap_or("global"),
+            instance.unwrap_or("default")
+        );
+        self.locks.lock().await.entry(key).or_default().clone()
+    }
+}
+
+impl Default for CredentialRefresher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -4,13 +4,15 @@
 //! - In-memory caching with TTL
 //! - Automatic refresh when credentials expire
 //! - Thread-safe access
+//! - A metadata index of stored credentials (keyrings can't be listed)
 
-use crate::auth::provider::{AuthProvider, AuthResult, Credentials};
+use crate::auth::provider::{AuthProvider, AuthResult, Credentials, ProviderConfig};
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
 use keyring::Entry;
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
+use skill_runtime::{CredentialIndex, CredentialSummary};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -31,6 +33,10 @@ struct CachedCredentials {
 struct StoredCredentials {
     credentials: Credentials,
     refresh_token_key: Option<String>,
+    /// OAuth2 provider settings, so tokens can be refreshed even when the
+    /// provider isn't configured in the current environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    provider: Option<ProviderConfig>,
 }
 
 /// Token store for managing authentication credentials.
@@ -60,12 +66,12 @@ impl TokenStore {
     /// Store credentials in keyring.
     pub async fn store(
         &self,
-        provider_id: &str,
+        provider: &dyn AuthProvider,
         skill: Option<&str>,
         instance: Option<&str>,
         result: &AuthResult,
     ) -> Result<()> {
-        let key = Self::storage_key(provider_id, skill, instance);
+        let key = Self::storage_key(provider.id(), skill, instance);
 
         // Store refresh token separately if present
         let refresh_token_key = if let Some(refresh_token) = &result.refresh_token {
@@ -81,12 +87,31 @@ impl TokenStore {
         let stored = StoredCredentials {
             credentials: result.credentials.clone(),
             refresh_token_key,
+            provider: provider
+                .config()
+                .oauth2
+                .is_some()
+                .then(|| provider.config().clone()),
         };
 
         let json = serde_json::to_string(&stored)?;
         let entry = Entry::new(SERVICE_NAME, &key)?;
         entry.set_password(&json)?;
 
+        Self::update_index(|index| {
+            index.upsert(CredentialSummary {
+                provider_id: provider.id().to_string(),
+                display_name: provider.display_name().to_string(),
+                auth_type: provider.auth_type().to_string(),
+                skill: skill.map(str::to_string),
+                instance: instance.map(str::to_string),
+                scopes: result.credentials.scopes.clone(),
+                expires_at: result.credentials.expires_at,
+                has_refresh_token: result.refresh_token.is_some(),
+                updated_at: Utc::now(),
+            })
+        });
+
         // Update cache
         let cached = CachedCredentials {
             credentials: result.credentials.clone(),
@@ -147,7 +172,6 @@ impl TokenStore {
     }
 
     /// Get credentials, refreshing if necessary.
-    #[allow(dead_code)]
     pub async fn get_credentials(
         &self,
         provider: &dyn AuthProvider,
@@ -169,7 +193,7 @@ impl TokenStore {
                 match provider.refresh(&credentials, &refresh_token).await {
                     Ok(result) => {
                         // Store refreshed credentials
-                        self.store(provider_id, skill, instance, &result).await?;
+                        self.store(provider, skill, instance, &result).await?;
                         return Ok(Some(result.credentials));
                     }
                     Err(e) => {
@@ -197,6 +221,25 @@ impl TokenStore {
         Ok(Some(credentials))
     }
 
+    /// Load the OAuth2 provider settings saved alongside credentials, if any.
+    pub async fn load_provider_config(
+        &self,
+        provider_id: &str,
+        skill: Option<&str>,
+        instance: Option<&str>,
+    ) -> Result<Option<ProviderConfig>> {
+        let key = Self::storage_key(provider_id, skill, instance);
+        let entry = Entry::new(SERVICE_NAME, &key)?;
+        let json = match entry.get_password() {
+            Ok(json) => json,
+            Err(keyring::Error::NoEntry) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let stored: StoredCredentials =
+            serde_json::from_str(&json).context("Failed to parse stored credentials")?;
+        Ok(stored.provider)
+    }
+
     /// Delete credentials from keyring.
     pub async fn delete(
         &self,
@@ -220,6 +263,7 @@ impl TokenStore {
 
         // Delete main credentials
         let _ = entry.delete_credential();
+        Self::update_index(|index| index.remove(provider_id, skill, instance));
 
         // Remove from cache
         self.cache.write().await.remove(&key);
@@ -227,29 +271,23 @@ impl TokenStore {
         Ok(())
     }
 
-    /// List all stored credentials.
-    #[allow(dead_code)]
-    pub async fn list(&self) -> Result<Vec<CredentialInfo>> {
-        // This is a simplified implementation - keyring doesn't support listing
-        // In practice, we'd need to maintain a separate index of stored credentials
-        // For now, return cached entries
-        let cache = self.cache.read().await;
-        let mut infos = Vec::new();
-
-        for (key, cached) in cache.iter() {
-            let parts: Vec<&str> = key.split(':').collect();
-            if parts.len() >= 3 {
-                infos.push(CredentialInfo {
-                    provider_id: parts[0].to_string(),
-                    skill: if parts[1] == "global" { None } else { Some(parts[1].to_string()) },
-                    instance: if parts[2] == "default" { None } else { Some(parts[2].to_string()) },
-                    expires_at: cached.credentials.expires_at,
-                    has_refresh_token: cached.refresh_token.is_some(),
-                });
-            }
-        }
+    /// List all stored credentials from the credential index.
+    pub async fn list(&self) -> Result<Vec<CredentialSummary>> {
+        Ok(CredentialIndex::load()?.credentials)
+    }
 
-        Ok(infos)
+    /// Apply a change to the credential index.
+    ///
+    /// The index is informational, so failures are logged rather than
+    /// failing the keyring operation.
+    fn update_index(change: impl FnOnce(&mut CredentialIndex)) {
+        let result = CredentialIndex::load().and_then(|mut index| {
+            change(&mut index);
+            index.save()
+        });
+        if let Err(e) = result {
+            tracing::warn!(error = %e, "Failed to update credential index");
+        }
     }
 
     /// Clear the in-memory cache.
@@ -265,50 +303,6 @@ impl Default for TokenStore {
     }
 }
 
-/// Information about stored credentials.
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
-pub struct CredentialInfo {
-    pub provider_id: String,
-    pub skill: Option<String>,
-    pub instance: Option<String>,
-    pub expires_at: Option<DateTime<Utc>>,
-    pub has_refresh_token: bool,
-}
-
-#[allow(dead_code)]
-impl CredentialInfo {
-    /// Check if these credentials are expired.
-    pub fn is_expired(&self) -> bool {
-        match self.expires_at {
-            Some(expires) => Utc::now() >= expires,
-            None => false,
-        }
-    }
-
-    /// Get a human-readable expiry description.
-    pub fn expiry_description(&self) -> String {
-        match self.expires_at {
-            Some(expires) => {
-                let now = Utc::now();
-                if expires <= now {
-                    "Expired".to_string()
-                } else {
-                    let duration = expires - now;
-                    if duration.num_days() > 0 {
-                        format!("Expires in {} days", duration.num_days())
-                    } else if duration.num_hours() > 0 {
-                        format!("Expires in {} hours", duration.num_hours())
-                    } else {
-                        format!("Expires in {} minutes", duration.num_minutes())
-                    }
-                }
-            }
-            None => "Never expires".to_string(),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -328,4 +322,21 @@ mod tests {
             "github:global:default"
         );
     }
+
+    #[test]
+    fn test_stored_credentials_without_provider() {
+        // Entries written before provider settings were saved still load
+        let json = r#"{
+            "credentials": {
+                "provider_id": "github",
+                "credential_type": "o_auth2_access_token",
+                "expires_at": null,
+                "data": {"access_token": "gho_abc"}
+            },
+            "refresh_token_key": "github:global:default_refresh"
+        }"#;
+        let stored: StoredCredentials = serde_json::from_str(json).unwrap();
+        assert!(stored.provider.is_none());
+        assert_eq!(stored.credentials.data["access_token"], "gho_abc");
+    }
 }
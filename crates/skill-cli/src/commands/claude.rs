@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::commands::claude_bridge::{self, GenerateOptions};
+use crate::commands::claude_bridge::{self, GenerateOptions, ScriptFormat};
 
 /// MCP server configuration for Claude Code
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -273,26 +273,28 @@ fn find_skill_binary() -> Result<PathBuf> {
     }
 
     // Second try: Check ~/.skill-engine/bin/skill
-    let home = std::env::var("HOME").context("HOME environment variable not set")?;
-    let install_path = PathBuf::from(&home).join(".skill-engine/bin/skill");
+    let install_path = home_dir()?
+        .join(".skill-engine")
+        .join("bin")
+        .join(format!("skill{}", std::env::consts::EXE_SUFFIX));
     if install_path.exists() {
         return Ok(install_path);
     }
 
     // Third try: Check PATH
-    if let Ok(output) = std::process::Command::new("which").arg("skill").output() {
-        if output.status.success() {
-            let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !path.is_empty() {
-                return Ok(PathBuf::from(path));
-            }
-        }
+    if let Some(path) = skill_runtime::find_program("skill") {
+        return Ok(path);
     }
 
     // Default to install path (may not exist)
     Ok(install_path)
 }
 
+/// The user's home directory (`%USERPROFILE%` on Windows)
+fn home_dir() -> Result<PathBuf> {
+    dirs::home_dir().context("Unable to determine the home directory")
+}
+
 /// Get project-level .mcp.json path
 fn get_project_mcp_config_path() -> Result<PathBuf> {
     let cwd = std::env::current_dir().context("Unable to get current directory")?;
@@ -301,20 +303,20 @@ fn get_project_mcp_config_path() -> Result<PathBuf> {
 
 /// Get global Claude Code config path
 fn get_global_mcp_config_path() -> Result<PathBuf> {
-    let home = std::env::var("HOME").context("HOME environment variable not set")?;
+    let home = home_dir()?;
 
     // Claude Code uses different paths on different platforms
     #[cfg(target_os = "macos")]
-    let config_dir = PathBuf::from(&home).join(".config/claude");
+    let config_dir = home.join(".config/claude");
 
     #[cfg(target_os = "linux")]
-    let config_dir = PathBuf::from(&home).join(".config/claude");
+    let config_dir = home.join(".config/claude");
 
     #[cfg(target_os = "windows")]
-    let config_dir = PathBuf::from(&home).join("AppData/Roaming/claude");
+    let config_dir = home.join("AppData").join("Roaming").join("claude");
 
     #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
-    let config_dir = PathBuf::from(&home).join(".config/claude");
+    let config_dir = home.join(".config/claude");
 
     Ok(config_dir.join("mcp.json"))
 }
@@ -356,6 +358,7 @@ pub async fn generate(
     force: bool,
     dry_run: bool,
     no_scripts: bool,
+    script_format: ScriptFormat,
     project: bool,
 ) -> Result<()> {
     // Determine output directory
@@ -364,8 +367,7 @@ pub async fn generate(
     } else if project {
         std::env::current_dir()?.join(".claude").join("skills")
     } else {
-        let home = std::env::var("HOME").context("HOME environment variable not set")?;
-        PathBuf::from(home).join(".claude").join("skills")
+        home_dir()?.join(".claude").join("skills")
     };
 
     println!();
@@ -395,6 +397,7 @@ pub async fn generate(
         force,
         dry_run,
         no_scripts,
+        script_format,
         project,
     };
 
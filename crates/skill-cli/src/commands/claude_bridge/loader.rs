@@ -18,8 +18,13 @@ pub struct Loader {
 
 impl Loader {
     /// Create a new loader, optionally with a specific manifest path
+    ///
+    /// Without a path the layered global + project manifest is used.
     pub fn new(manifest_path: Option<&Path>) -> Result<Self> {
-        let manifest = crate::commands::manifest::load_manifest(manifest_path)?;
+        let manifest = match manifest_path {
+            Some(path) => Some(SkillManifest::load(path)?),
+            None => crate::commands::manifest::load_manifest::<PathBuf>(&[])?,
+        };
         Ok(Self { manifest })
     }
 
@@ -21,6 +21,9 @@
 //!     ├── describe.sh
 //!     └── ...
 //! ```
+//!
+//! On Windows the scripts are `.cmd` batch files instead (`scripts\get.cmd`),
+//! selected with [`ScriptFormat`].
 
 mod loader;
 mod renderer;
@@ -82,8 +85,10 @@ pub async fn generate(options: GenerateOptions) -> Result<GenerateResult> {
         let claude_skill = transformer.transform(validated)?;
 
         // Render output
-        let renderer = Renderer::new(&options.output_dir)?;
-        let script_gen = ScriptGenerator::new(&claude_skill.name);
+        let renderer =
+            Renderer::new(&options.output_dir)?.with_script_format(options.script_format);
+        let script_gen =
+            ScriptGenerator::new(&claude_skill.name).with_format(options.script_format);
 
         if options.dry_run {
             result.dry_run_output.push(format!(
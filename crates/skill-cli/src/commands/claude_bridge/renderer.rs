@@ -7,11 +7,12 @@ use anyhow::{Context, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use super::types::ClaudeSkill;
+use super::types::{ClaudeSkill, ScriptFormat};
 
 /// Renders Claude Agent Skills markdown files
 pub struct Renderer {
     output_dir: PathBuf,
+    script_format: ScriptFormat,
 }
 
 impl Renderer {
@@ -22,9 +23,16 @@ impl Renderer {
 
         Ok(Self {
             output_dir: output_dir.to_path_buf(),
+            script_format: ScriptFormat::Bash,
         })
     }
 
+    /// Document scripts generated in `format`
+    pub fn with_script_format(mut self, format: ScriptFormat) -> Self {
+        self.script_format = format;
+        self
+    }
+
     /// Render all files for a Claude Skill
     pub fn render(&self, skill: &ClaudeSkill) -> Result<()> {
         let skill_dir = self.output_dir.join(&skill.name);
@@ -88,8 +96,11 @@ impl Renderer {
 
         md.push_str("### Method 2: Scripts (Fallback / claude.ai)\n\n");
         md.push_str("When MCP is not available, use the shell scripts:\n\n");
-        md.push_str("```bash\n");
-        md.push_str("./scripts/<tool_name>.sh arg1=value1 arg2=value2\n");
+        md.push_str(&format!("```{}\n", self.script_format.code_language()));
+        md.push_str(&format!(
+            "{} arg1=value1 arg2=value2\n",
+            self.script_format.invocation("<tool_name>")
+        ));
         md.push_str("```\n\n");
 
         // Quick reference table
@@ -142,8 +153,8 @@ impl Renderer {
                         self.format_example_args(&tool.parameters)
                     ));
                     md.push_str(&format!(
-                        "# Script\n./scripts/{}.sh {}\n",
-                        tool.name,
+                        "# Script\n{} {}\n",
+                        self.script_format.invocation(&tool.name),
                         self.format_script_args(&tool.parameters)
                     ));
                     md.push_str("```\n\n");
@@ -155,7 +166,11 @@ impl Renderer {
         md.push_str("## Available Scripts\n\n");
         md.push_str("All scripts are in the `scripts/` directory:\n\n");
         for tool in &skill.tools {
-            md.push_str(&format!("- `scripts/{}.sh` - {}\n", tool.name, tool.description));
+            md.push_str(&format!(
+                "- `{}` - {}\n",
+                self.script_format.path(&tool.name),
+                tool.description
+            ));
         }
         md.push('\n');
 
@@ -218,10 +233,13 @@ impl Renderer {
             ));
             md.push_str("```\n\n");
 
-            md.push_str("**Script:**\n```bash\n");
             md.push_str(&format!(
-                "./scripts/{}.sh {}\n",
-                tool.name,
+                "**Script:**\n```{}\n",
+                self.script_format.code_language()
+            ));
+            md.push_str(&format!(
+                "{} {}\n",
+                self.script_format.invocation(&tool.name),
                 self.format_script_args(&tool.parameters)
             ));
             md.push_str("```\n\n");
@@ -536,6 +554,42 @@ mod tests {
 
     // === TOOLS.md Rendering Tests ===
 
+    #[test]
+    fn test_render_cmd_scripts() {
+        let temp = TempDir::new().unwrap();
+        let renderer = Renderer::new(temp.path())
+            .unwrap()
+            .with_script_format(ScriptFormat::Cmd);
+
+        let mut categories = HashMap::new();
+        categories.insert("Read Operations".to_string(), vec!["get_pods".to_string()]);
+
+        let skill = ClaudeSkill {
+            name: "kubernetes".to_string(),
+            description: "Kubernetes management".to_string(),
+            tools: vec![ClaudeTool {
+                name: "get_pods".to_string(),
+                description: "Get pods from cluster".to_string(),
+                parameters: vec![],
+                examples: vec![],
+                category: Some("Read Operations".to_string()),
+                streaming: false,
+            }],
+            categories,
+            when_to_use: vec![],
+            runtime: SkillRuntimeType::Native,
+        };
+
+        let skill_md = renderer.render_skill_md(&skill);
+        assert!(skill_md.contains("```bat\nscripts\\<tool_name>.cmd arg1=value1"));
+        assert!(skill_md.contains("# Script\nscripts\\get_pods.cmd"));
+        assert!(skill_md.contains("- `scripts\\get_pods.cmd` - Get pods from cluster"));
+        assert!(!skill_md.contains(".sh"));
+
+        let tools_md = renderer.render_tools_md(&skill);
+        assert!(tools_md.contains("**Script:**\n```bat\nscripts\\get_pods.cmd"));
+    }
+
     #[test]
     fn test_render_tools_md_basic() {
         let temp = TempDir::new().unwrap();
@@ -1,29 +1,37 @@
 //! Script Generator - Generate executable wrapper scripts
 //!
-//! Generates bash scripts that wrap `skill run` for each tool.
+//! Generates scripts that wrap `skill run` for each tool: bash scripts, or
+//! `.cmd` batch files for Windows (see [`ScriptFormat`]).
 //! These scripts provide a fallback execution method when MCP is not available
 //! (e.g., on claude.ai).
 
 use anyhow::{Context, Result};
 use std::fs;
-use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 
-use super::types::{ClaudeSkill, ClaudeTool};
+use super::types::{ClaudeSkill, ClaudeTool, ScriptFormat};
 
 /// Generates wrapper scripts for skill tools
 pub struct ScriptGenerator {
     skill_name: String,
+    format: ScriptFormat,
 }
 
 impl ScriptGenerator {
-    /// Create a new script generator for a skill
+    /// Create a new script generator for a skill, writing bash scripts
     pub fn new(skill_name: &str) -> Self {
         Self {
             skill_name: skill_name.to_string(),
+            format: ScriptFormat::Bash,
         }
     }
 
+    /// Write scripts in `format` instead
+    pub fn with_format(mut self, format: ScriptFormat) -> Self {
+        self.format = format;
+        self
+    }
+
     /// Generate all scripts for a skill
     pub fn generate(&self, skill: &ClaudeSkill, output_dir: &Path) -> Result<Vec<String>> {
         let scripts_dir = output_dir.join(&skill.name).join("scripts");
@@ -33,16 +41,24 @@ impl ScriptGenerator {
         let mut generated = Vec::new();
 
         for tool in &skill.tools {
-            let script_path = scripts_dir.join(format!("{}.sh", tool.name));
-            let script_content = self.generate_tool_script(tool);
+            let script_path = scripts_dir.join(self.format.file_name(&tool.name));
+            let script_content = match self.format {
+                ScriptFormat::Bash => self.generate_tool_script(tool),
+                // cmd.exe wants CRLF line endings
+                ScriptFormat::Cmd => self.generate_cmd_script(tool).replace('\n', "\r\n"),
+            };
 
             fs::write(&script_path, &script_content)
                 .with_context(|| format!("Failed to write script: {}", script_path.display()))?;
 
-            // Make executable
-            let mut perms = fs::metadata(&script_path)?.permissions();
-            perms.set_mode(0o755);
-            fs::set_permissions(&script_path, perms)?;
+            // Make executable (Windows runs .cmd files by their extension)
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mut perms = fs::metadata(&script_path)?.permissions();
+                perms.set_mode(0o755);
+                fs::set_permissions(&script_path, perms)?;
+            }
 
             generated.push(tool.name.clone());
         }
@@ -50,7 +66,7 @@ impl ScriptGenerator {
         Ok(generated)
     }
 
-    /// Generate a script for a single tool
+    /// Generate a bash script for a single tool
     fn generate_tool_script(&self, tool: &ClaudeTool) -> String {
         let mut script = String::new();
 
@@ -133,6 +149,83 @@ impl ScriptGenerator {
         script
     }
 
+    /// Generate a batch file for a single tool
+    fn generate_cmd_script(&self, tool: &ClaudeTool) -> String {
+        let usage = format!(
+            "{} {}",
+            self.format.path(&tool.name),
+            self.format_usage_args(&tool.parameters)
+        );
+        let mut script = String::new();
+
+        script.push_str("@echo off\n");
+
+        // Header comment
+        script.push_str(&format!(
+            "rem {}/{} - Generated by Skill Engine\n",
+            self.skill_name,
+            self.format.path(&tool.name)
+        ));
+        script.push_str("rem\n");
+        script.push_str(&format!("rem {}\n", tool.description));
+        script.push_str("rem\n");
+        script.push_str("rem Usage:\n");
+        script.push_str(&format!("rem   {}\n", usage));
+        script.push_str("rem\n");
+
+        if !tool.parameters.is_empty() {
+            script.push_str("rem Parameters:\n");
+            for param in &tool.parameters {
+                let required = if param.required { "(required)" } else { "(optional)" };
+                let default = param
+                    .default_value
+                    .as_ref()
+                    .map(|v| format!(" [default: {}]", v))
+                    .unwrap_or_default();
+
+                script.push_str(&format!(
+                    "rem   {:<15} - {} {}{}\n",
+                    param.name, param.description, required, default
+                ));
+            }
+            script.push_str("rem\n");
+        }
+
+        script.push_str("\nsetlocal\n\n");
+
+        script.push_str("if \"%~1\"==\"-h\" goto help\n");
+        script.push_str("if \"%~1\"==\"--help\" goto help\n\n");
+
+        // Execute skill run
+        script.push_str(&format!("skill run {} {} %*\n", self.skill_name, tool.name));
+        script.push_str("exit /b %ERRORLEVEL%\n\n");
+
+        // Help output
+        script.push_str(":help\n");
+        script.push_str(&format!("echo Usage: {}\n", escape_cmd(&usage)));
+        script.push_str("echo.\n");
+        script.push_str(&format!("echo {}\n", escape_cmd(&tool.description)));
+
+        if !tool.parameters.is_empty() {
+            script.push_str("echo.\n");
+            script.push_str("echo Parameters:\n");
+            for param in &tool.parameters {
+                let required = if param.required { "(required)" } else { "(optional)" };
+                script.push_str(&format!(
+                    "echo   {}\n",
+                    escape_cmd(&format!(
+                        "{:<15} {} {}",
+                        param.name, param.description, required
+                    ))
+                ));
+            }
+        }
+
+        script.push_str("exit /b 0\n");
+
+        script
+    }
+
     /// Format usage arguments for documentation
     fn format_usage_args(&self, parameters: &[super::types::ClaudeToolParameter]) -> String {
         if parameters.is_empty() {
@@ -153,6 +246,23 @@ impl ScriptGenerator {
     }
 }
 
+/// Escape text for `echo` in a batch file
+fn escape_cmd(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '^' | '&' | '|' | '<' | '>' | '(' | ')' => {
+                escaped.push('^');
+                escaped.push(c);
+            }
+            '%' => escaped.push_str("%%"),
+            '\n' | '\r' => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -338,6 +448,63 @@ mod tests {
         assert!(script.contains("exec skill run myskill mytool \"$@\""));
     }
 
+    #[test]
+    fn test_generate_cmd_script() {
+        let gen = ScriptGenerator::new("kubernetes").with_format(ScriptFormat::Cmd);
+
+        let tool = ClaudeTool {
+            name: "logs".to_string(),
+            description: "Show logs (100% of them) | filtered & <sorted>".to_string(),
+            parameters: vec![ClaudeToolParameter {
+                name: "pod".to_string(),
+                param_type: "string".to_string(),
+                description: "Pod name".to_string(),
+                required: true,
+                default_value: None,
+                enum_values: None,
+            }],
+            examples: vec![],
+            category: None,
+            streaming: false,
+        };
+
+        let script = gen.generate_cmd_script(&tool);
+
+        assert!(script.starts_with("@echo off\n"));
+        assert!(script.contains("rem kubernetes/scripts\\logs.cmd - Generated by Skill Engine"));
+        assert!(script.contains("if \"%~1\"==\"--help\" goto help"));
+        assert!(script.contains("skill run kubernetes logs %*\nexit /b %ERRORLEVEL%"));
+        assert!(script.contains("echo Usage: scripts\\logs.cmd pod=^<value^>"));
+        assert!(script.contains(
+            "echo Show logs ^(100%% of them^) ^| filtered ^& ^<sorted^>"
+        ));
+        assert!(!script.contains("set -euo pipefail"));
+    }
+
+    #[test]
+    fn test_escape_cmd() {
+        assert_eq!(escape_cmd("plain text"), "plain text");
+        assert_eq!(escape_cmd("a & b | c"), "a ^& b ^| c");
+        assert_eq!(escape_cmd("50% ^ (x)"), "50%% ^^ ^(x^)");
+        assert_eq!(escape_cmd("two\nlines"), "two lines");
+    }
+
+    #[test]
+    fn test_script_format_paths() {
+        assert_eq!(ScriptFormat::Bash.invocation("get"), "./scripts/get.sh");
+        assert_eq!(ScriptFormat::Cmd.invocation("get"), "scripts\\get.cmd");
+        assert_eq!(ScriptFormat::Cmd.file_name("get"), "get.cmd");
+        assert_eq!(ScriptFormat::Bash.code_language(), "bash");
+        assert_eq!(
+            ScriptFormat::native(),
+            if cfg!(windows) {
+                ScriptFormat::Cmd
+            } else {
+                ScriptFormat::Bash
+            }
+        );
+    }
+
     // === Usage Args Formatting Tests ===
 
     #[test]
@@ -501,8 +668,11 @@ mod tests {
         assert!(scripts_dir.join("tool2.sh").exists());
     }
 
+    #[cfg(unix)]
     #[test]
     fn test_generate_scripts_are_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
         let temp = TempDir::new().unwrap();
         let gen = ScriptGenerator::new("test");
 
@@ -576,6 +746,37 @@ mod tests {
         assert!(script_content.contains("Get some data"));
     }
 
+    #[test]
+    fn test_generate_cmd_files() {
+        let temp = TempDir::new().unwrap();
+        let gen = ScriptGenerator::new("myskill").with_format(ScriptFormat::Cmd);
+
+        let skill = ClaudeSkill {
+            name: "myskill".to_string(),
+            description: "My skill".to_string(),
+            tools: vec![ClaudeTool {
+                name: "get_data".to_string(),
+                description: "Get some data".to_string(),
+                parameters: vec![],
+                examples: vec![],
+                category: None,
+                streaming: false,
+            }],
+            categories: HashMap::new(),
+            when_to_use: vec![],
+            runtime: SkillRuntimeType::Native,
+        };
+
+        gen.generate(&skill, temp.path()).unwrap();
+
+        let scripts_dir = temp.path().join("myskill/scripts");
+        assert!(!scripts_dir.join("get_data.sh").exists());
+        let content = fs::read_to_string(scripts_dir.join("get_data.cmd")).unwrap();
+        assert!(content.starts_with("@echo off\r\n"));
+        assert!(content.contains("skill run myskill get_data %*\r\n"));
+        assert!(!content.replace("\r\n", "").contains('\n'));
+    }
+
     #[test]
     fn test_generate_returns_tool_names() {
         let temp = TempDir::new().unwrap();
@@ -28,11 +28,73 @@ pub struct GenerateOptions {
     /// Skip generating scripts
     pub no_scripts: bool,
 
+    /// Kind of wrapper scripts to generate
+    pub script_format: ScriptFormat,
+
     /// Generate for project-level Claude Code config
     #[allow(dead_code)]
     pub project: bool,
 }
 
+/// Kind of wrapper scripts generated in `scripts/`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ScriptFormat {
+    /// `scripts/<tool>.sh` bash scripts
+    #[default]
+    Bash,
+    /// `scripts\<tool>.cmd` batch files, which run from cmd.exe and
+    /// PowerShell alike, without an execution policy getting in the way
+    Cmd,
+}
+
+impl ScriptFormat {
+    /// Format for the platform this runs on: `cmd` on Windows, else `bash`
+    pub fn native() -> Self {
+        if cfg!(windows) {
+            Self::Cmd
+        } else {
+            Self::Bash
+        }
+    }
+
+    /// File extension of the scripts, without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Bash => "sh",
+            Self::Cmd => "cmd",
+        }
+    }
+
+    /// Script file name for a tool
+    pub fn file_name(self, tool: &str) -> String {
+        format!("{}.{}", tool, self.extension())
+    }
+
+    /// Path of a tool's script relative to the skill directory
+    pub fn path(self, tool: &str) -> String {
+        match self {
+            Self::Bash => format!("scripts/{}", self.file_name(tool)),
+            Self::Cmd => format!("scripts\\{}", self.file_name(tool)),
+        }
+    }
+
+    /// Command running a tool's script from the skill directory
+    pub fn invocation(self, tool: &str) -> String {
+        match self {
+            Self::Bash => format!("./{}", self.path(tool)),
+            Self::Cmd => self.path(tool),
+        }
+    }
+
+    /// Language of markdown code blocks showing script calls
+    pub fn code_language(self) -> &'static str {
+        match self {
+            Self::Bash => "bash",
+            Self::Cmd => "bat",
+        }
+    }
+}
+
 /// Result of generation
 #[derive(Debug, Clone, Default)]
 pub struct GenerateResult {
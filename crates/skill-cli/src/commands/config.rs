@@ -5,6 +5,8 @@ use dialoguer::{Input, Password, Select};
 use skill_runtime::InstanceManager;
 use skill_runtime::instance::ConfigValue;
 
+use crate::output::OutputFormat;
+
 /// Configuration subcommands for skill instances.
 #[derive(Subcommand)]
 pub enum ConfigAction {
@@ -23,6 +25,27 @@ pub enum ConfigAction {
         /// Configuration key
         key: String,
     },
+
+    /// Copy an instance's configuration (including secrets) to a new instance
+    ///
+    /// Example: skill config aws clone --from prod --to staging --set region=us-west-2
+    Clone {
+        /// Instance to copy (default: --instance, or "default")
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Name of the new instance
+        #[arg(long)]
+        to: String,
+
+        /// Key=value pairs to change on the new instance
+        #[arg(long = "set", value_parser = parse_key_val)]
+        pairs: Vec<(String, String)>,
+
+        /// Overwrite the target instance if it exists
+        #[arg(short, long)]
+        force: bool,
+    },
 }
 
 fn parse_key_val(s: &str) -> Result<(String, String), String> {
@@ -36,18 +59,30 @@ pub async fn execute(
     skill: &str,
     instance: Option<&str>,
     action: Option<ConfigAction>,
+    output: Option<OutputFormat>,
 ) -> Result<()> {
     let instance_name = instance.unwrap_or("default");
     let instance_manager = InstanceManager::new()?;
 
     match action {
-        Some(ConfigAction::Show) => show_config(skill, instance_name, &instance_manager).await,
+        Some(ConfigAction::Show) => {
+            show_config(skill, instance_name, &instance_manager, output).await
+        }
         Some(ConfigAction::Set { pairs }) => {
             set_config(skill, instance_name, &instance_manager, pairs).await
         }
         Some(ConfigAction::Get { key }) => {
             get_config(skill, instance_name, &instance_manager, &key).await
         }
+        Some(ConfigAction::Clone {
+            from,
+            to,
+            pairs,
+            force,
+        }) => {
+            let from = from.as_deref().unwrap_or(instance_name);
+            clone_config(skill, from, &to, &instance_manager, pairs, force).await
+        }
         None => interactive_config(skill, instance_name, &instance_manager).await,
     }
 }
@@ -56,11 +91,19 @@ async fn show_config(
     skill: &str,
     instance: &str,
     manager: &InstanceManager,
+    output: Option<OutputFormat>,
 ) -> Result<()> {
-    let config = manager
+    let mut config = manager
         .load_instance(skill, instance)
         .with_context(|| format!("Instance '{}' not found", instance))?;
 
+    if let Some(output) = OutputFormat::structured(output) {
+        for value in config.config.values_mut().filter(|value| value.secret) {
+            value.value = "[REDACTED]".to_string();
+        }
+        return output.print("config.show", &config);
+    }
+
     println!();
     println!(
         "{} Configuration for {}@{}",
@@ -111,6 +154,21 @@ async fn show_config(
             "Disabled".red()
         }
     );
+    if let Some(network) = &config.capabilities.network {
+        let allowed = network.allowed_hosts.clone().unwrap_or_default();
+        println!(
+            "  {} {}",
+            "Allowed Hosts:".bold(),
+            if !network.enabled || allowed.is_empty() {
+                "none (deny all)".red().to_string()
+            } else {
+                allowed.join(", ").green().to_string()
+            }
+        );
+        if let Some(blocked) = network.blocked_hosts.as_ref().filter(|b| !b.is_empty()) {
+            println!("  {} {}", "Blocked Hosts:".bold(), blocked.join(", ").yellow());
+        }
+    }
     println!(
         "  {} {}",
         "Max Concurrent:".bold(),
@@ -172,6 +230,34 @@ async fn set_config(
     Ok(())
 }
 
+async fn clone_config(
+    skill: &str,
+    from: &str,
+    to: &str,
+    manager: &InstanceManager,
+    pairs: Vec<(String, String)>,
+    force: bool,
+) -> Result<()> {
+    let config = manager.clone_instance(skill, from, to, force)?;
+    let secrets = config.config.values().filter(|v| v.secret).count();
+    println!(
+        "{} Cloned {}@{} to {}@{} ({} keys, {} secrets)",
+        "✓".green(),
+        skill.yellow(),
+        from.cyan(),
+        skill.yellow(),
+        to.cyan(),
+        config.config.len(),
+        secrets
+    );
+
+    if !pairs.is_empty() {
+        println!();
+        set_config(skill, to, manager, pairs).await?;
+    }
+    Ok(())
+}
+
 async fn get_config(
     skill: &str,
     instance: &str,
@@ -255,7 +341,7 @@ async fn interactive_config(
             }
             1 => {
                 // View configuration
-                show_config(skill, instance, manager).await?;
+                show_config(skill, instance, manager, None).await?;
             }
             _ => {
                 // Exit
@@ -0,0 +1,145 @@
+//! Background daemon keeping one engine warm for CLI commands
+//!
+//! `skill daemon start` runs `skill daemon start --foreground` detached from
+//! the terminal, logging to `~/.skill-engine/daemon.log`, and waits until it
+//! answers on its socket. While it runs, `skill find` searches its index and
+//! `skill run` executes manifest skills in it when it was started with the
+//! same manifest, directory and environment (see [`skill_http::daemon`]).
+
+use anyhow::{bail, Context, Result};
+use colored::*;
+use skill_http::daemon::{socket_path, DaemonClient};
+use skill_http::{HttpServer, HttpServerConfig, DEFAULT_DRAIN_TIMEOUT};
+use std::os::unix::process::CommandExt;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long `start` waits for the daemon to answer
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time between checks while waiting for the daemon to start or stop
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Start the daemon in the background, or in this process with `foreground`
+pub async fn start(foreground: bool) -> Result<()> {
+    let socket = socket_path();
+    let client = DaemonClient::new(&socket);
+    if let Ok(status) = client.status().await {
+        println!(
+            "{} Daemon already running (pid {})",
+            "✓".green(),
+            status.pid
+        );
+        return Ok(());
+    }
+
+    if foreground {
+        return run(socket).await;
+    }
+
+    let log_path = log_path();
+    if let Some(parent) = log_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let log = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .with_context(|| format!("Failed to open {}", log_path.display()))?;
+
+    // Its own process group, so Ctrl+C in this terminal doesn't reach it
+    let mut child = Command::new(std::env::current_exe()?)
+        .args(["daemon", "start", "--foreground"])
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        .process_group(0)
+        .spawn()
+        .context("Failed to start the daemon")?;
+
+    let started = Instant::now();
+    while started.elapsed() < STARTUP_TIMEOUT {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        if let Ok(status) = client.status().await {
+            println!(
+                "{} Daemon started (pid {}) on {}",
+                "✓".green(),
+                status.pid,
+                socket.display().to_string().cyan()
+            );
+            println!("  Logs: {}", log_path.display().to_string().dimmed());
+            return Ok(());
+        }
+        if let Some(exit) = child.try_wait()? {
+            bail!("Daemon exited with {}; see {}", exit, log_path.display());
+        }
+    }
+    bail!(
+        "Daemon didn't answer within {}s; see {}",
+        STARTUP_TIMEOUT.as_secs(),
+        log_path.display()
+    )
+}
+
+/// Stop the daemon, letting in-flight executions finish
+pub async fn stop() -> Result<()> {
+    let socket = socket_path();
+    let client = DaemonClient::new(&socket);
+    let Ok(status) = client.status().await else {
+        println!("{} Daemon is not running", "ℹ".blue());
+        return Ok(());
+    };
+
+    client.stop().await?;
+    let deadline = Instant::now() + DEFAULT_DRAIN_TIMEOUT + Duration::from_secs(5);
+    while socket.exists() && Instant::now() < deadline {
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    if socket.exists() {
+        bail!("Daemon (pid {}) is still shutting down", status.pid);
+    }
+    println!("{} Daemon stopped (pid {})", "✓".green(), status.pid);
+    Ok(())
+}
+
+/// Show whether the daemon is running and what it has loaded
+pub async fn status() -> Result<()> {
+    let socket = socket_path();
+    let Ok(status) = DaemonClient::new(&socket).status().await else {
+        println!("{} Daemon is not running", "ℹ".blue());
+        println!("  Start it with: {}", "skill daemon start".cyan());
+        return Ok(());
+    };
+
+    println!("{} Daemon running", "✓".green());
+    println!("  PID:     {}", status.pid);
+    println!("  Version: {}", status.version);
+    println!("  Uptime:  {}s", status.uptime_secs);
+    println!("  Socket:  {}", socket.display());
+    println!("  Skills:  {}", status.skills);
+    println!("  Index:   {}", status.index);
+    Ok(())
+}
+
+/// Serve the REST API on the socket until stopped
+async fn run(socket: PathBuf) -> Result<()> {
+    println!("Skill Engine daemon listening on {}", socket.display());
+
+    // Load the manifest from the home directory, not wherever the daemon
+    // happened to be started
+    let config = HttpServerConfig {
+        working_dir: dirs::home_dir(),
+        ..Default::default()
+    };
+    HttpServer::with_config(config)?
+        .start()
+        .await?
+        .serve_unix(&socket)
+        .await
+}
+
+/// `~/.skill-engine/daemon.log`, next to the socket
+fn log_path() -> PathBuf {
+    socket_path().with_file_name("daemon.log")
+}
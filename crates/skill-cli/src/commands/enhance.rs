@@ -1,17 +1,29 @@
 //! AI-enhanced example generation command
 //!
 //! Generates synthetic usage examples for installed skills using LLMs,
-//! with real-time streaming progress display.
+//! with real-time streaming progress display. `--evaluate` dry-runs a
+//! sample of the cached examples instead and records their quality in the
+//! analytics database.
 
 use anyhow::{Context, Result};
 use colored::*;
+use skill_runtime::generation::{AccuracyEvaluator, SkillEvaluation};
+use skill_runtime::ExampleCache;
+use skill_http::analytics::{ExampleQualityEntry, SearchAnalyticsDb};
 
 #[cfg(feature = "ai-ingestion")]
 use skill_runtime::{
     SearchConfig, SearchPipeline, GenerationEvent, ToolDocumentation,
-    IndexDocument, DocumentMetadata,
+    IndexDocument, DocumentMetadata, AI_GENERATED_TAG,
+    GeneratedExample, GenerationRecorder,
+    EnhanceProgressDb, ToolProgress,
 };
 
+#[cfg(feature = "ai-ingestion")]
+use indicatif::{ProgressBar, ProgressStyle};
+
+use std::collections::HashMap;
+
 #[cfg(feature = "ai-ingestion")]
 use tokio_stream::StreamExt;
 
@@ -19,12 +31,22 @@ use tokio_stream::StreamExt;
 use std::path::PathBuf;
 
 /// Execute the enhance command
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     skill_name: Option<&str>,
     all: bool,
     stream: bool,
     examples_per_tool: usize,
+    only_failed: bool,
+    force: bool,
+    max_age_days: Option<u64>,
+    evaluate: bool,
+    sample: usize,
 ) -> Result<()> {
+    if evaluate {
+        return execute_evaluation(skill_name, all, sample).await;
+    }
+
     #[cfg(not(feature = "ai-ingestion"))]
     {
         eprintln!(
@@ -35,13 +57,13 @@ pub async fn execute(
             "{}",
             "Rebuild with: cargo build --features ai-ingestion".dimmed()
         );
-        let _ = (skill_name, all, stream, examples_per_tool); // Suppress unused warnings
+        let _ = (skill_name, all, stream, examples_per_tool, only_failed, force, max_age_days); // Suppress unused warnings
         Ok(())
     }
 
     #[cfg(feature = "ai-ingestion")]
     {
-        execute_with_ai(skill_name, all, stream, examples_per_tool).await
+        execute_with_ai(skill_name, all, stream, examples_per_tool, only_failed, force, max_age_days).await
     }
 }
 
@@ -51,9 +73,13 @@ async fn execute_with_ai(
     all: bool,
     stream: bool,
     _examples_per_tool: usize,
+    only_failed: bool,
+    force: bool,
+    max_age_days: Option<u64>,
 ) -> Result<()> {
     // Load search config
-    let config = load_config()?;
+    let mut config = load_config()?;
+    config.ai_ingestion.budget.force = force;
 
     // Check if AI ingestion is enabled
     if !config.ai_ingestion.enabled {
@@ -113,6 +139,32 @@ async fn execute_with_ai(
         return Ok(());
     }
 
+    // Fail fast when the budget is already spent
+    pipeline.budget().check(1)?;
+
+    // Load examples cached by earlier runs
+    let cache_path = ExampleCache::default_path()?;
+    let mut cache = ExampleCache::load(&cache_path).unwrap_or_else(|e| {
+        eprintln!("{} {}", "⚠".yellow(), format!("Ignoring unreadable example cache: {}", e).dimmed());
+        ExampleCache::default()
+    });
+    let reuse_cached = config.ai_ingestion.cache_examples && !force;
+    let max_age = max_age_days.map(|days| chrono::Duration::days(days as i64));
+
+    // Record progress so an interrupted run picks up where it stopped
+    let progress = EnhanceProgressDb::open(&EnhanceProgressDb::default_path()?)
+        .await
+        .context("Failed to open enhance progress database")?;
+    let run = progress.start_or_resume(&skills).await?;
+    if run.resumed {
+        println!(
+            "{} interrupted run from {}",
+            "Resuming:".dimmed(),
+            run.started_at.format("%Y-%m-%d %H:%M").to_string().cyan()
+        );
+        println!();
+    }
+
     // Process each skill
     for skill_name in &skills {
         println!(
@@ -122,24 +174,84 @@ async fn execute_with_ai(
         );
 
         // Load tools for this skill
-        let tools = load_skill_tools(skill_name)?;
+        let tools = load_skill_tools(&pipeline, skill_name).await?;
 
         if tools.is_empty() {
             println!("   {} No tools found", "⚠".yellow());
             continue;
         }
 
+        if tools.iter().any(|t| t.ai_generated) {
+            println!(
+                "   {} No SKILL.md, drafted docs with {}",
+                "AI-generated:".yellow(),
+                config.ai_ingestion.model.cyan()
+            );
+        }
+
+        // Only regenerate tools whose cached examples are missing, stale or
+        // failed, and that this run hasn't processed yet
+        let processed = progress.processed_tools(&run.id, skill_name).await?;
+        let (pending, cached): (Vec<ToolDocumentation>, Vec<ToolDocumentation>) =
+            tools.iter().cloned().partition(|t| {
+                if processed.contains(&t.name) {
+                    false
+                } else if only_failed {
+                    cache.is_failed(skill_name, &t.name)
+                } else {
+                    !reuse_cached || !cache.is_fresh(skill_name, t, max_age)
+                }
+            });
+
+        let pending_names: Vec<String> = pending.iter().map(|t| t.name.clone()).collect();
+        progress.plan_tools(&run.id, skill_name, &pending_names).await?;
+
         println!(
-            "   {} {} tools",
+            "   {} {} tools, {} to generate, {} cached",
             "Found:".dimmed(),
-            tools.len()
+            tools.len(),
+            pending.len(),
+            cached.len()
         );
 
+        // Convert to IndexDocument for pipeline
+        let documents: Vec<IndexDocument> = tools
+            .iter()
+            .flat_map(|t| {
+                let doc = IndexDocument {
+                    id: format!("{}:{}", skill_name, t.name),
+                    content: build_embedding_text(t),
+                    metadata: DocumentMetadata {
+                        skill_name: Some(skill_name.clone()),
+                        tool_name: Some(t.name.clone()),
+                        tags: if t.ai_generated {
+                            vec![AI_GENERATED_TAG.to_string()]
+                        } else {
+                            Vec::new()
+                        },
+                        ..Default::default()
+                    },
+                };
+                let examples = doc.example_documents(t.examples.iter().map(|e| &e.code));
+                std::iter::once(doc).chain(examples)
+            })
+            .collect();
+
+        // Reuse cached examples for tools that aren't regenerated
+        let cached_examples: HashMap<String, Vec<GeneratedExample>> = cached
+            .iter()
+            .filter_map(|t| {
+                let entry = cache.get(skill_name, &t.name)?;
+                Some((t.name.clone(), entry.examples.clone()))
+            })
+            .collect();
+        let documents = SearchPipeline::with_generated_examples(documents, &cached_examples);
+
         // Create progress bar
         let pb = if stream {
             None
         } else {
-            let bar = ProgressBar::new(tools.len() as u64);
+            let bar = ProgressBar::new(pending.len() as u64);
             bar.set_style(
                 ProgressStyle::default_bar()
                     .template("   {bar:30.cyan/blue} {pos}/{len} tools [{elapsed}]")
@@ -149,55 +261,62 @@ async fn execute_with_ai(
             Some(bar)
         };
 
-        // Convert to IndexDocument for pipeline
-        let documents: Vec<IndexDocument> = tools
-            .iter()
-            .map(|t| IndexDocument {
-                id: format!("{}:{}", skill_name, t.name),
-                content: build_embedding_text(t),
-                metadata: DocumentMetadata {
-                    skill_name: Some(skill_name.clone()),
-                    tool_name: Some(t.name.clone()),
-                    ..Default::default()
-                },
-            })
-            .collect();
+        // Track each tool's run so it can be cached
+        let mut recorder = GenerationRecorder::new();
+        let mut examples_count = 0;
+        let mut valid_count = 0;
+        let mut failed = 0;
+
+        let mut event_stream = Box::pin(pipeline.index_documents_stream(documents, pending.clone()));
 
-        if stream {
-            // Stream events to terminal
-            let mut event_stream = Box::pin(pipeline.index_documents_stream(documents, tools.clone()));
-            let mut examples_count = 0;
-            let mut valid_count = 0;
+        while let Some(event) = event_stream.next().await {
+            let finished_tool = recorder.observe(&event);
 
-            while let Some(event) = event_stream.next().await {
-                match event {
-                    GenerationEvent::Started { tool_name, .. } => {
+            match event {
+                GenerationEvent::Started { tool_name, .. } => {
+                    if stream {
                         println!("   {} {}", "▸".blue(), tool_name.dimmed());
                     }
-                    GenerationEvent::Thinking { thought } => {
+                }
+                GenerationEvent::Thinking { thought } => {
+                    if stream {
                         println!("     {} {}", "💭".dimmed(), thought.dimmed());
                     }
-                    GenerationEvent::Example { example } => {
-                        examples_count += 1;
+                }
+                GenerationEvent::Example { example } => {
+                    examples_count += 1;
+                    if stream {
                         println!(
-                            "     {} {}",
+                            "     {} {} {}",
                             "✓".green(),
-                            truncate_command(&example.command, 60).green()
+                            truncate_command(&example.command, 60).green(),
+                            format!("({:.2})", example.confidence).dimmed()
                         );
                     }
-                    GenerationEvent::Validation { valid, errors, example_index } => {
-                        if valid {
-                            valid_count += 1;
-                        } else {
-                            println!(
-                                "     {} Example {}: {}",
-                                "⚠".yellow(),
-                                example_index,
-                                errors.join(", ").dimmed()
-                            );
-                        }
+                }
+                GenerationEvent::Validation { valid, errors, score, example_index } => {
+                    if valid {
+                        valid_count += 1;
                     }
-                    GenerationEvent::ToolCompleted { tool_name, examples_generated, valid_examples, duration_ms } => {
+                    if stream && !valid {
+                        println!(
+                            "     {} Example {}: {}",
+                            "⚠".yellow(),
+                            example_index,
+                            errors.join(", ").dimmed()
+                        );
+                    } else if stream && score < config.ai_ingestion.min_example_score {
+                        println!(
+                            "     {} Example {}: score {:.2} below {:.2}",
+                            "⚠".yellow(),
+                            example_index,
+                            score,
+                            config.ai_ingestion.min_example_score
+                        );
+                    }
+                }
+                GenerationEvent::ToolCompleted { tool_name, examples_generated, valid_examples, duration_ms } => {
+                    if stream {
                         println!(
                             "   {} {} ({}/{} valid, {}ms)",
                             "✓".green(),
@@ -207,11 +326,23 @@ async fn execute_with_ai(
                             duration_ms
                         );
                     }
-                    GenerationEvent::Error { message, tool_name, .. } => {
-                        let prefix = tool_name.map(|n| format!(" [{}]", n)).unwrap_or_default();
+                    if let Some(bar) = &pb {
+                        bar.inc(1);
+                    }
+                }
+                GenerationEvent::Error { message, tool_name, .. } => {
+                    let prefix = tool_name.as_ref().map(|n| format!(" [{}]", n)).unwrap_or_default();
+                    if let Some(bar) = &pb {
+                        bar.println(format!("   {}{} {}", "✗".red(), prefix, message.red()));
+                        if tool_name.is_some() {
+                            bar.inc(1);
+                        }
+                    } else {
                         eprintln!("   {}{} {}", "✗".red(), prefix, message.red());
                     }
-                    GenerationEvent::Completed { total_examples, total_valid, total_tools, duration_ms } => {
+                }
+                GenerationEvent::Completed { total_examples, total_valid, total_tools, duration_ms } => {
+                    if stream {
                         println!();
                         println!(
                             "   {} {} examples generated ({} valid) from {} tools in {}ms",
@@ -222,40 +353,54 @@ async fn execute_with_ai(
                             duration_ms
                         );
                     }
-                    _ => {}
                 }
+                _ => {}
             }
 
-            println!(
-                "   {} Generated {} examples ({} valid)",
-                "Summary:".dimmed(),
-                examples_count,
-                valid_count
-            );
-        } else {
-            // Non-streaming: use index_documents_with_generation
-            let (stats, examples) = pipeline
-                .index_documents_with_generation(documents, tools.clone())
-                .await
-                .context("Failed to generate examples")?;
-
-            if let Some(bar) = &pb {
-                bar.finish_with_message("complete");
+            // Cache the tool's examples and record its progress right away,
+            // so an interruption loses at most the tool in flight
+            let Some(tool) = finished_tool.and_then(|name| pending.iter().find(|t| t.name == name)) else {
+                continue;
+            };
+            let entry = recorder.finish(tool, config.ai_ingestion.get_model());
+            let state = if entry.failed {
+                failed += 1;
+                ToolProgress::Failed
+            } else {
+                ToolProgress::Completed
+            };
+            let kept_count = entry.examples.len();
+            cache.insert(skill_name, &tool.name, entry);
+            if let Err(e) = cache.save(&cache_path) {
+                eprintln!("   {} {}", "⚠".yellow(), format!("Failed to save example cache: {}", e).dimmed());
             }
+            progress.record_tool(&run.id, skill_name, &tool.name, state, kept_count).await?;
+        }
+
+        if let Some(bar) = &pb {
+            bar.finish_and_clear();
+        }
 
-            let valid_count = examples.iter().filter(|e| e.validated).count();
+        println!(
+            "   {} {} examples kept ({} valid), {} tools failed",
+            "Summary:".dimmed(),
+            examples_count,
+            valid_count,
+            failed
+        );
+        if failed > 0 {
             println!(
-                "   {} {} examples ({} valid), {} documents indexed",
-                "✓".green(),
-                examples.len(),
-                valid_count,
-                stats.total_documents
+                "   {} Retry with {}",
+                "→".dimmed(),
+                format!("skill enhance {} --only-failed", skill_name).cyan()
             );
         }
 
         println!();
     }
 
+    progress.finish_run(&run.id).await?;
+
     // Summary
     println!("{}", "━".repeat(40));
     println!(
@@ -263,13 +408,151 @@ async fn execute_with_ai(
         "✓".green().bold(),
         skills.len()
     );
+    let budget = pipeline.budget();
+    if config.ai_ingestion.budget.cost_per_1k_tokens > 0.0 {
+        println!(
+            "{} ~{} tokens, ~{:.4} estimated cost",
+            "AI usage:".dimmed(),
+            budget.run_tokens(),
+            budget.run_cost()
+        );
+    } else {
+        println!("{} ~{} tokens", "AI usage:".dimmed(), budget.run_tokens());
+    }
     println!();
 
     Ok(())
 }
 
+/// Dry-run a sample of each skill's cached examples and record the results
+///
+/// Nothing is generated or executed: sampled commands are resolved against
+/// the skill's SKILL.md the way `skill run` would before invoking a tool.
+async fn execute_evaluation(skill_name: Option<&str>, all: bool, sample: usize) -> Result<()> {
+    use skill_runtime::parse_skill_md;
+
+    let skills = get_skills_to_enhance(skill_name, all)?;
+    if skills.is_empty() {
+        eprintln!("{}", "No skills found to evaluate.".yellow());
+        return Ok(());
+    }
+
+    let cache = ExampleCache::load(&ExampleCache::default_path()?)?;
+    let home = dirs::home_dir().context("Failed to get home directory")?;
+    let analytics_path = skill_http::namespaces::namespace_data_dir(None).join("analytics.db");
+    let analytics = match SearchAnalyticsDb::new(&analytics_path.to_string_lossy()).await {
+        Ok(db) => Some(db),
+        Err(e) => {
+            eprintln!("{} {}", "⚠".yellow(), format!("Results won't be recorded: {}", e).dimmed());
+            None
+        }
+    };
+    let evaluator = AccuracyEvaluator::new();
+
+    println!();
+    println!("{}", "🧪 Generated Example Evaluation".bold());
+    println!("{}", "━".repeat(40));
+    println!();
+
+    for skill_name in &skills {
+        let skill_md_path = home.join(".skill-engine").join("registry").join(skill_name).join("SKILL.md");
+        if !skill_md_path.exists() {
+            println!("{} {} {}", "○".dimmed(), skill_name.bold(), "(no SKILL.md, skipped)".dimmed());
+            continue;
+        }
+        let tools: Vec<_> = parse_skill_md(&skill_md_path)
+            .context(format!("Failed to parse SKILL.md for '{}'", skill_name))?
+            .tool_docs
+            .into_values()
+            .collect();
+
+        let examples_by_tool: HashMap<_, _> = tools
+            .iter()
+            .filter_map(|tool| {
+                cache
+                    .get(skill_name, &tool.name)
+                    .map(|cached| (tool.name.clone(), cached.examples.clone()))
+            })
+            .collect();
+        if examples_by_tool.is_empty() {
+            println!(
+                "{} {} {}",
+                "○".dimmed(),
+                skill_name.bold(),
+                format!("(no generated examples; run skill enhance {})", skill_name).dimmed()
+            );
+            continue;
+        }
+
+        let evaluation = evaluator.evaluate_skill(skill_name, &tools, &examples_by_tool, sample);
+        let previous = match &analytics {
+            Some(db) => db.get_example_quality(Some(skill_name), 365).await?.pop(),
+            None => None,
+        };
+        print_evaluation(&evaluation, previous.as_ref());
+
+        if let Some(db) = &analytics {
+            db.log_example_quality(&ExampleQualityEntry::from(&evaluation)).await?;
+        }
+    }
+
+    if let Some(db) = analytics {
+        db.close().await;
+    }
+    Ok(())
+}
+
+/// Print one skill's evaluation, with the change since the last one
+fn print_evaluation(evaluation: &SkillEvaluation, previous: Option<&ExampleQualityEntry>) {
+    let pass_rate = evaluation.pass_rate();
+    let icon = if pass_rate >= 0.8 {
+        "✓".green()
+    } else if pass_rate >= 0.5 {
+        "⚠".yellow()
+    } else {
+        "✗".red()
+    };
+    let trend = previous
+        .filter(|p| p.sampled > 0)
+        .map(|p| {
+            let delta = (pass_rate - p.valid as f32 / p.sampled as f32) * 100.0;
+            format!(" ({:+.0} pts since {})", delta, p.timestamp.format("%Y-%m-%d"))
+        })
+        .unwrap_or_default();
+
+    println!("{} {}", icon, evaluation.skill.bold());
+    println!(
+        "   {} {} examples from {} tools",
+        "Sampled:".dimmed(),
+        evaluation.sampled,
+        evaluation.tools
+    );
+    println!(
+        "   {} parsed {:.0}%, passed {:.0}%{}",
+        "Dry run:".dimmed(),
+        evaluation.parse_rate() * 100.0,
+        pass_rate * 100.0,
+        trend.dimmed()
+    );
+    println!(
+        "   {} score {:.2}, quality {:.2}",
+        "Examples:".dimmed(),
+        evaluation.avg_score,
+        evaluation.quality
+    );
+    for failure in evaluation.failures.iter().take(5) {
+        println!("   {} {}", "✗".red(), failure.command.dimmed());
+        for issue in &failure.issues {
+            println!("       {}", issue);
+        }
+    }
+    if evaluation.failures.len() > 5 {
+        println!("   {} {} more failed", "…".dimmed(), evaluation.failures.len() - 5);
+    }
+    println!();
+}
+
 /// Get list of skill names to enhance
-#[allow(dead_code)]
 fn get_skills_to_enhance(skill_name: Option<&str>, all: bool) -> Result<Vec<String>> {
     if let Some(name) = skill_name {
         return Ok(vec![name.to_string()]);
@@ -305,8 +588,14 @@ fn get_skills_to_enhance(skill_name: Option<&str>, all: bool) -> Result<Vec<Stri
 }
 
 /// Load tools for a skill
+///
+/// Skills without a SKILL.md get their documentation drafted by the LLM
+/// from the WASM tool signatures.
 #[cfg(feature = "ai-ingestion")]
-fn load_skill_tools(skill_name: &str) -> Result<Vec<ToolDocumentation>> {
+async fn load_skill_tools(
+    pipeline: &SearchPipeline,
+    skill_name: &str,
+) -> Result<Vec<ToolDocumentation>> {
     use skill_runtime::parse_skill_md;
 
     // Get skill directory from registry
@@ -321,8 +610,7 @@ fn load_skill_tools(skill_name: &str) -> Result<Vec<ToolDocumentation>> {
     let skill_md_path = skill_dir.join("SKILL.md");
 
     if !skill_md_path.exists() {
-        // No SKILL.md, return empty
-        return Ok(Vec::new());
+        return draft_skill_tools(pipeline, skill_name, &skill_dir).await;
     }
 
     // Parse SKILL.md
@@ -338,6 +626,47 @@ fn load_skill_tools(skill_name: &str) -> Result<Vec<ToolDocumentation>> {
     Ok(tools)
 }
 
+/// Draft documentation for a skill's WASM tools
+#[cfg(feature = "ai-ingestion")]
+async fn draft_skill_tools(
+    pipeline: &SearchPipeline,
+    skill_name: &str,
+    skill_dir: &std::path::Path,
+) -> Result<Vec<ToolDocumentation>> {
+    use skill_runtime::{InstanceManager, SkillEngine, SkillExecutor};
+    use std::sync::Arc;
+
+    let skill_path = skill_dir.join(format!("{}.wasm", skill_name));
+    if !skill_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let instance_manager = InstanceManager::new()?;
+    let instance_name = instance_manager
+        .list_instances(skill_name)
+        .unwrap_or_default()
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| "default".to_string());
+    let config = instance_manager
+        .load_instance(skill_name, &instance_name)
+        .unwrap_or_default();
+
+    let engine = Arc::new(SkillEngine::new()?);
+    let executor = SkillExecutor::load(
+        engine,
+        &skill_path,
+        skill_name.to_string(),
+        instance_name,
+        config,
+    )
+    .await
+    .with_context(|| format!("Failed to load WASM tools for '{}'", skill_name))?;
+
+    let tools = executor.get_tools().await?;
+    Ok(pipeline.draft_tool_docs(skill_name, &tools).await)
+}
+
 /// Build embedding text for a tool
 #[cfg(feature = "ai-ingestion")]
 fn build_embedding_text(tool: &ToolDocumentation) -> String {
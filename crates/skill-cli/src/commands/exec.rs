@@ -1,8 +1,8 @@
 use anyhow::{Context, Result};
 use colored::*;
 use skill_runtime::{
-    instance::ConfigValue, parse_git_url, GitSkillLoader, InstanceManager, SkillEngine,
-    SkillExecutor, SkillManifest,
+    instance::ConfigValue, is_git_url, GitSkillLoader, InstanceManager, SkillEngine, SkillExecutor,
+    SkillManifest, StdinSource,
 };
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -19,6 +19,7 @@ pub async fn execute(
     skill_spec: &str,
     config_overrides: &[(String, String)],
     args: &[String],
+    stdin: &StdinSource,
     manifest: Option<&SkillManifest>,
 ) -> Result<()> {
     let start = Instant::now();
@@ -30,7 +31,7 @@ pub async fn execute(
         || skill_spec.starts_with('~');
 
     if is_local_path {
-        return execute_local_skill(skill_spec, config_overrides, args, start).await;
+        return execute_local_skill(skill_spec, config_overrides, args, stdin, start).await;
     }
 
     // Parse skill[@instance] (no tool - exec uses special "exec" tool)
@@ -45,6 +46,7 @@ pub async fn execute(
                 Some(&instance_name),
                 config_overrides,
                 args,
+                stdin,
                 start,
             )
             .await;
@@ -91,6 +93,7 @@ pub async fn execute(
                 instance_name, skill_name, skill_name, instance_name
             )
         })?;
+    crate::auth::resolve_instance_credentials(&mut instance_config).await?;
 
     // Apply config overrides
     if !config_overrides.is_empty() {
@@ -122,6 +125,7 @@ pub async fn execute(
     )
     .await
     .context("Failed to load skill")?;
+    let executor = super::run::attach_stdin(executor, stdin);
 
     // Get available tools to find exec/cli tool
     let tools = executor.get_tools().await?;
@@ -201,12 +205,14 @@ async fn execute_manifest_skill(
     instance_name: Option<&str>,
     config_overrides: &[(String, String)],
     args: &[String],
+    stdin: &StdinSource,
     start: Instant,
 ) -> Result<()> {
     // Resolve instance from manifest
-    let resolved = manifest
+    let mut resolved = manifest
         .resolve_instance(skill_name, instance_name)
         .context("Failed to resolve skill from manifest")?;
+    crate::auth::resolve_instance_credentials(&mut resolved.config).await?;
 
     println!(
         "{} Executing {}@{} (from manifest, pass-through mode)",
@@ -221,7 +227,7 @@ async fn execute_manifest_skill(
     );
 
     // Apply config overrides
-    let mut instance_config = resolved.config;
+    let mut instance_config = resolved.config.clone();
     if !config_overrides.is_empty() {
         for (key, value) in config_overrides {
             instance_config.config.insert(
@@ -253,10 +259,10 @@ async fn execute_manifest_skill(
         )
         .await
         .context("Failed to load local skill from manifest")?
-    } else if is_git_url_spec(&resolved.source) {
+    } else if is_git_url(&resolved.source) {
         // Git source
-        let loader = GitSkillLoader::new()?;
-        let git_source = parse_git_url(&resolved.source)?;
+        let loader = GitSkillLoader::new()?.with_config(resolved.git.clone());
+        let git_source = resolved.git_source()?;
 
         println!("{} Fetching from Git...", "→".dimmed());
         let cloned = loader.clone_skill(&git_source, false).await?;
@@ -299,6 +305,7 @@ async fn execute_manifest_skill(
         .await
         .context("Failed to load installed skill from manifest")?
     };
+    let executor = super::run::attach_stdin(executor, stdin);
 
     // Get available tools to find exec/cli tool
     let tools = executor.get_tools().await?;
@@ -358,21 +365,12 @@ async fn execute_manifest_skill(
     Ok(())
 }
 
-/// Check if a spec looks like a Git URL
-fn is_git_url_spec(spec: &str) -> bool {
-    spec.starts_with("github:")
-        || spec.starts_with("gitlab:")
-        || spec.starts_with("bitbucket:")
-        || spec.starts_with("git@")
-        || spec.starts_with("https://github.com")
-        || spec.starts_with("https://gitlab.com")
-}
-
 /// Execute a local skill in pass-through mode
 async fn execute_local_skill(
     path: &str,
     config_overrides: &[(String, String)],
     args: &[String],
+    stdin: &StdinSource,
     start: Instant,
 ) -> Result<()> {
     // Expand ~ to home directory
@@ -431,6 +429,7 @@ async fn execute_local_skill(
     )
     .await
     .context("Failed to load skill")?;
+    let executor = super::run::attach_stdin(executor, stdin);
 
     // Get available tools
     let tools = executor.get_tools().await?;
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use indicatif::{ProgressBar, ProgressStyle};
 use skill_runtime::{
     InstanceManager, SearchPipeline, IndexDocument, SearchConfig,
-    DocumentMetadata,
+    DocumentMetadata, ScoreExplanation,
 };
 use std::collections::HashMap;
 use std::fs;
@@ -15,6 +15,8 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::SystemTime;
 
+use crate::output::OutputFormat;
+
 /// Embedding cache entry for faster repeated searches
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct EmbeddingCacheEntry {
@@ -793,7 +795,7 @@ fn display_rich_result(rank: usize, similarity_score: f64, tool: &ToolDocument)
 }
 
 /// Get the path to the search configuration file
-fn get_config_path() -> Result<PathBuf> {
+pub(crate) fn get_config_path() -> Result<PathBuf> {
     let home = dirs::home_dir().context("Failed to get home directory")?;
     Ok(home.join(".skill-engine").join("search.toml"))
 }
@@ -894,8 +896,17 @@ pub async fn execute(
     provider: &str,
     model: Option<&str>,
     format: &str,
+    explain: bool,
+    output: Option<OutputFormat>,
 ) -> Result<()> {
     let top_k = top_k.unwrap_or(5);
+    // `--output` takes precedence over `--format`
+    let format = match output {
+        Some(OutputFormat::Table) => "rich",
+        Some(_) => "json",
+        None => format,
+    };
+    let output = OutputFormat::structured(output);
     let is_json = format == "json";
 
     if !is_json {
@@ -903,11 +914,30 @@ pub async fn execute(
         println!("{} Searching for: {}", "→".cyan(), query.yellow());
     }
 
+    // A running daemon has the model loaded and every tool indexed already
+    #[cfg(unix)]
+    if provider.eq_ignore_ascii_case("fastembed") && model.is_none() {
+        if let Some((results, explanations)) = daemon_search(query, top_k, explain).await {
+            if !is_json {
+                println!("{} Searched the daemon's index", "✓".green());
+            }
+            let (config, _) = load_or_create_config(provider, model, is_json)?;
+            match format {
+                "json" => display_json_results(&results, &explanations, output)?,
+                "compact" => display_compact_results(&results, &explanations),
+                _ => display_rich_results(&results, &explanations, top_k, config.retrieval.rrf_k),
+            }
+            return Ok(());
+        }
+    }
+
     // Load all skills and their tools
     let tool_documents = load_all_tools().await?;
 
     if tool_documents.is_empty() {
-        if is_json {
+        if let Some(output) = output {
+            return output.print("find", &Vec::<serde_json::Value>::new());
+        } else if is_json {
             println!("{{\"results\": [], \"error\": \"No skills installed\"}}");
         } else {
             println!("{} No skills installed yet. Install a skill first with: skill install <source>", "!".yellow());
@@ -959,25 +989,8 @@ pub async fn execute(
         pb.finish_with_message(format!("{} Model ready", "✓".green()));
     }
 
-    // Convert ToolDocuments to IndexDocuments
-    let index_docs: Vec<IndexDocument> = tool_documents
-        .iter()
-        .map(|tool| IndexDocument {
-            id: tool.id.clone(),
-            content: tool.full_text.clone(),
-            metadata: DocumentMetadata {
-                skill_name: Some(tool.skill_name.clone()),
-                instance_name: Some(tool.instance_name.clone()),
-                tool_name: Some(tool.tool_name.clone()),
-                category: tool.category.clone(),
-                tags: tool.tags.clone(),
-                custom: HashMap::new(),
-            },
-        })
-        .collect();
-
     // Index documents
-    pipeline.index_documents(index_docs).await
+    pipeline.index_documents(to_index_documents(&tool_documents)).await
         .context("Failed to index tools")?;
 
     if !is_json {
@@ -989,28 +1002,137 @@ pub async fn execute(
         .context("Failed to perform semantic search")?;
 
     // Convert search results back to the expected format (f64, String, ToolDocument)
+    let mut explanations: Vec<Option<ScoreExplanation>> = Vec::new();
     let results: Vec<(f64, String, ToolDocument)> = search_results
         .into_iter()
         .filter_map(|result| {
             // Find the original ToolDocument by ID
-            tool_documents.iter()
-                .find(|tool| tool.id == result.id)
-                .map(|tool| (result.score as f64, result.id.clone(), tool.clone()))
+            let tool = tool_documents.iter().find(|tool| tool.id == result.id)?;
+            if explain {
+                explanations.push(result.explanation);
+            }
+            Some((result.score as f64, result.id, tool.clone()))
         })
         .collect();
 
+    let rrf_k = pipeline.config().retrieval.rrf_k;
+
     // Output based on format
     match format {
-        "json" => display_json_results(&results)?,
-        "compact" => display_compact_results(&results),
-        _ => display_rich_results(&results, top_k),
+        "json" => display_json_results(&results, &explanations, output)?,
+        "compact" => display_compact_results(&results, &explanations),
+        _ => display_rich_results(&results, &explanations, top_k, rrf_k),
     }
 
     Ok(())
 }
 
+/// Search results from the running daemon, unless there is none or its index
+/// is still being built
+///
+/// The daemon returns skill and tool names; the rest of each result comes
+/// from the skill's SKILL.md, like results of an in-process search.
+#[cfg(unix)]
+async fn daemon_search(
+    query: &str,
+    top_k: usize,
+    explain: bool,
+) -> Option<(Vec<(f64, String, ToolDocument)>, Vec<Option<ScoreExplanation>>)> {
+    let (daemon, status) = skill_http::DaemonClient::connect().await?;
+    if !status.index_ready {
+        return None;
+    }
+    let request = skill_http::SearchRequest {
+        query: query.to_string(),
+        top_k,
+        skill_filter: None,
+        include_examples: false,
+        session_id: None,
+        filters: None,
+        explain,
+    };
+    let response = match daemon.search(&request).await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::warn!(error = %e, "Daemon search failed; searching in-process");
+            return None;
+        }
+    };
+
+    let registry_dir = dirs::home_dir()?.join(".skill-engine").join("registry");
+    let mut skill_docs: HashMap<String, Option<SkillMdContent>> = HashMap::new();
+    let mut results: Vec<(f64, String, ToolDocument)> = Vec::new();
+    let mut explanations = Vec::new();
+    for result in response.results {
+        let skill_md = skill_docs
+            .entry(result.skill.clone())
+            .or_insert_with(|| read_skill_md(&registry_dir.join(&result.skill)));
+        let tool = ToolDocument::with_skill_md(
+            result.skill.clone(),
+            "default".to_string(),
+            result.tool.clone(),
+            indexed_description(&result.content).to_string(),
+            skill_md.as_ref(),
+        );
+        // Usage examples are indexed as documents of their own
+        if results.iter().any(|(_, id, _)| *id == tool.id) {
+            continue;
+        }
+        if explain {
+            explanations.push(result.explanation.and_then(|explanation| {
+                let mut explanation: ScoreExplanation =
+                    serde_json::from_value(serde_json::to_value(explanation).ok()?).ok()?;
+                explanation.rerank_score = result.rerank_score;
+                Some(explanation)
+            }));
+        }
+        results.push((result.score as f64, tool.id.clone(), tool));
+    }
+    Some((results, explanations))
+}
+
+/// Tool description in the text the server indexes:
+/// `<skill> - <tool> | <description> | Parameters: ...`
+#[cfg(unix)]
+fn indexed_description(content: &str) -> &str {
+    content.split(" | ").nth(1).unwrap_or_default()
+}
+
+/// Convert ToolDocuments to IndexDocuments for the search pipeline, with a
+/// document per usage example linked to its tool
+pub(crate) fn to_index_documents(tool_documents: &[ToolDocument]) -> Vec<IndexDocument> {
+    tool_documents
+        .iter()
+        .flat_map(|tool| {
+            let doc = IndexDocument {
+                id: tool.id.clone(),
+                content: tool.full_text.clone(),
+                metadata: DocumentMetadata {
+                    skill_name: Some(tool.skill_name.clone()),
+                    instance_name: Some(tool.instance_name.clone()),
+                    tool_name: Some(tool.tool_name.clone()),
+                    category: tool.category.clone(),
+                    runtime: None,
+                    tags: tool.tags.clone(),
+                    custom: HashMap::new(),
+                },
+            };
+            let examples = doc.example_documents(&tool.usage_examples);
+            std::iter::once(doc).chain(examples)
+        })
+        .collect()
+}
+
 /// Display results as JSON (for programmatic consumption and MCP integration)
-fn display_json_results(results: &[(f64, String, ToolDocument)]) -> Result<()> {
+///
+/// `explanations` is empty unless `--explain` was passed, in which case it
+/// holds one entry per result. With `--output json|yaml` the results are
+/// printed in the versioned envelope instead.
+fn display_json_results(
+    results: &[(f64, String, ToolDocument)],
+    explanations: &[Option<ScoreExplanation>],
+    output: Option<OutputFormat>,
+) -> Result<()> {
     #[derive(Serialize)]
     struct JsonParameter {
         name: String,
@@ -1062,10 +1184,15 @@ fn display_json_results(results: &[(f64, String, ToolDocument)]) -> Result<()> {
         category: Option<String>,
         tags: Vec<String>,
         version: Option<String>,
+
+        // Scoring breakdown (--explain)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        explanation: Option<ScoreExplanation>,
     }
 
     let json_results: Vec<JsonResult> = results.iter()
-        .map(|(score, _, tool)| {
+        .enumerate()
+        .map(|(idx, (score, _, tool))| {
             let score_pct = (*score * 100.0) as u32;
             let relevance = match score_pct {
                 80..=100 => "excellent",
@@ -1129,17 +1256,24 @@ fn display_json_results(results: &[(f64, String, ToolDocument)]) -> Result<()> {
                 category: tool.category.clone(),
                 tags: tool.tags.clone(),
                 version: tool.skill_version.clone(),
+                explanation: explanations.get(idx).cloned().flatten(),
             }
         })
         .collect();
 
+    if let Some(output) = output {
+        return output.print("find", &json_results);
+    }
     println!("{}", serde_json::to_string_pretty(&json_results)?);
     Ok(())
 }
 
 /// Display results in compact format (one line per result)
-fn display_compact_results(results: &[(f64, String, ToolDocument)]) {
-    for (score, _, tool) in results {
+fn display_compact_results(
+    results: &[(f64, String, ToolDocument)],
+    explanations: &[Option<ScoreExplanation>],
+) {
+    for (idx, (score, _, tool)) in results.iter().enumerate() {
         let score_pct = (score * 100.0) as u32;
         println!(
             "[{:3}%] {}@{}:{} - {}",
@@ -1149,11 +1283,98 @@ fn display_compact_results(results: &[(f64, String, ToolDocument)]) {
             tool.tool_name,
             tool.description
         );
+        if let Some(Some(explanation)) = explanations.get(idx) {
+            println!("       {}", format_explanation_summary(explanation).dimmed());
+        }
+    }
+}
+
+/// One-line summary of a score explanation
+fn format_explanation_summary(explanation: &ScoreExplanation) -> String {
+    let mut parts = Vec::new();
+    if let Some(score) = explanation.dense_score {
+        parts.push(format!("dense={:.3}", score));
+    }
+    if let Some(score) = explanation.bm25_score {
+        parts.push(format!("bm25={:.3}", score));
+    }
+    if let Some(score) = explanation.fusion_score {
+        parts.push(format!("rrf={:.4}", score));
+    }
+    if let Some(delta) = explanation.rerank_delta {
+        parts.push(format!("rerank={:+}", delta));
+    }
+    if !explanation.matched_terms.is_empty() {
+        parts.push(format!("terms={}", explanation.matched_terms.join(",")));
+    }
+    parts.join(" ")
+}
+
+/// Display a score explanation beneath a rich result
+fn display_explanation(explanation: &ScoreExplanation, rrf_k: f32) {
+    println!("   {} Score breakdown:", "🔍".bold());
+
+    let rank = |rank: Option<usize>| {
+        rank.map(|r| format!(" (rank {})", r)).unwrap_or_default()
+    };
+
+    match explanation.dense_score {
+        Some(score) => println!(
+            "      • dense:  {:.4}{}",
+            score,
+            rank(explanation.dense_rank).dimmed()
+        ),
+        None => println!("      • dense:  {}", "not retrieved".dimmed()),
+    }
+
+    if explanation.fusion_score.is_some() {
+        match explanation.bm25_score {
+            Some(score) => println!(
+                "      • bm25:   {:.4}{}",
+                score,
+                rank(explanation.bm25_rank).dimmed()
+            ),
+            None => println!("      • bm25:   {}", "no keyword match".dimmed()),
+        }
+
+        let contribution = |c: Option<f32>| c.map(|c| format!("{:.4}", c)).unwrap_or_else(|| "0".to_string());
+        println!(
+            "      • fusion: {:.4} {}",
+            explanation.fusion_score.unwrap_or_default(),
+            format!(
+                "(dense {} + bm25 {}, k={})",
+                contribution(explanation.dense_contribution(rrf_k)),
+                contribution(explanation.bm25_contribution(rrf_k)),
+                rrf_k
+            )
+            .dimmed()
+        );
     }
+
+    if let (Some(score), Some(delta)) = (explanation.rerank_score, explanation.rerank_delta) {
+        let movement = match delta {
+            d if d > 0 => format!("moved up {} from #{}", d, explanation.retrieval_rank).green(),
+            d if d < 0 => format!("moved down {} from #{}", -d, explanation.retrieval_rank).red(),
+            _ => "unchanged".dimmed(),
+        };
+        println!("      • rerank: {:.4} ({})", score, movement);
+    }
+
+    if explanation.matched_terms.is_empty() {
+        println!("      • terms:  {}", "no query terms matched".dimmed());
+    } else {
+        println!("      • terms:  {}", explanation.matched_terms.join(", ").yellow());
+    }
+    println!();
 }
 
 /// Display results with rich formatting
-fn display_rich_results(results: &[(f64, String, ToolDocument)], top_k: usize) {
+fn display_rich_results(
+    results: &[(f64, String, ToolDocument)],
+    explanations: &[Option<ScoreExplanation>],
+    top_k: usize,
+    rrf_k: f32,
+) {
     println!();
     println!("{}", "━".repeat(80).dimmed());
     println!();
@@ -1163,6 +1384,9 @@ fn display_rich_results(results: &[(f64, String, ToolDocument)], top_k: usize) {
     // Display results with rich formatting
     for (idx, (similarity_score, _doc_id, tool)) in results.iter().enumerate() {
         display_rich_result(idx + 1, *similarity_score, tool);
+        if let Some(Some(explanation)) = explanations.get(idx) {
+            display_explanation(explanation, rrf_k);
+        }
     }
 
     println!("{}", "━".repeat(80).dimmed());
@@ -1176,7 +1400,7 @@ fn display_rich_results(results: &[(f64, String, ToolDocument)], top_k: usize) {
 }
 
 /// Load all tools from all installed skills
-async fn load_all_tools() -> Result<Vec<ToolDocument>> {
+pub(crate) async fn load_all_tools() -> Result<Vec<ToolDocument>> {
     let instance_manager = InstanceManager::new()?;
     let mut tool_documents = Vec::new();
 
@@ -1220,6 +1444,20 @@ async fn load_all_tools() -> Result<Vec<ToolDocument>> {
     Ok(tool_documents)
 }
 
+/// A skill's SKILL.md, preferring the variant for SKILL_LOCALE; read
+/// silently, with no debug output
+fn read_skill_md(skill_dir: &std::path::Path) -> Option<SkillMdContent> {
+    let skill_md_path = skill_runtime::preferred_locale()
+        .and_then(|locale| skill_runtime::find_skill_md_variant(skill_dir, &locale))
+        .unwrap_or_else(|| skill_dir.join("SKILL.md"));
+    if !skill_md_path.exists() {
+        return None;
+    }
+    fs::read_to_string(&skill_md_path)
+        .ok()
+        .and_then(|content| parse_skill_md(&content).ok())
+}
+
 /// Load tools from a specific skill instance
 async fn load_skill_tools(
     skill_name: &str,
@@ -1242,15 +1480,7 @@ async fn load_skill_tools(
         return Ok(tool_documents);
     }
 
-    // Try to load SKILL.md if it exists (silently, no debug output)
-    let skill_md_path = skill_dir.join("SKILL.md");
-    let skill_md_content = if skill_md_path.exists() {
-        fs::read_to_string(&skill_md_path)
-            .ok()
-            .and_then(|content| parse_skill_md(&content).ok())
-    } else {
-        None
-    };
+    let skill_md_content = read_skill_md(&skill_dir);
 
     // Load instance config
     let instance_manager = InstanceManager::new()?;
@@ -1465,4 +1695,25 @@ mod tests {
         // Hash is 16 chars (half of blake3 hex)
         assert_eq!(hash1.len(), 16);
     }
+
+    #[test]
+    fn test_format_explanation_summary() {
+        let explanation = ScoreExplanation {
+            dense_score: Some(0.8123),
+            dense_rank: Some(2),
+            bm25_score: Some(4.5),
+            bm25_rank: Some(1),
+            fusion_score: Some(0.0325),
+            retrieval_rank: 1,
+            rerank_score: Some(0.9),
+            rerank_delta: Some(1),
+            matched_terms: vec!["pods".to_string(), "cluster".to_string()],
+        };
+
+        assert_eq!(
+            format_explanation_summary(&explanation),
+            "dense=0.812 bm25=4.500 rrf=0.0325 rerank=+1 terms=pods,cluster"
+        );
+        assert_eq!(format_explanation_summary(&ScoreExplanation::default()), "");
+    }
 }
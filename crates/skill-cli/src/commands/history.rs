@@ -0,0 +1,248 @@
+//! Execution history - list past executions and re-run them
+//!
+//! Executions made through the HTTP server and web UI are recorded in
+//! ~/.skill-engine/execution-history.db. `prune` applies the manifest's
+//! `[history]` retention to it and to the search analytics database.
+
+use anyhow::{anyhow, bail, Context, Result};
+use colored::*;
+use skill_http::analytics::SearchAnalyticsDb;
+use skill_http::execution_history::{default_db_path, ExecutionHistoryDb};
+use skill_http::namespaces::namespace_data_dir;
+use skill_http::{AppState, ExecutionStatus, HttpServerConfig};
+use skill_runtime::{format_bytes, HistoryRetention, OutputDiff, ResourceUsage, SkillManifest};
+use std::sync::Arc;
+
+use crate::output::OutputFormat;
+
+/// List recent executions, newest first.
+pub async fn list(limit: usize, skill: Option<&str>, output: Option<OutputFormat>) -> Result<()> {
+    let db = ExecutionHistoryDb::new(&default_db_path().to_string_lossy()).await?;
+    let entries = match skill {
+        Some(skill) => db.list_by_skill(skill, limit).await?,
+        None => db.list_executions(limit, 0).await?,
+    };
+
+    if let Some(output) = OutputFormat::structured(output) {
+        return output.print("history", &entries);
+    }
+
+    if entries.is_empty() {
+        println!("{} No executions recorded", "!".yellow());
+        println!();
+        println!(
+            "Executions run through {} or {} are recorded here",
+            "skill serve --http".cyan(),
+            "skill web".cyan()
+        );
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "  {:<36} {:<30} {:<10} {:>8}  {}",
+        "ID".bold(),
+        "TOOL".bold(),
+        "STATUS".bold(),
+        "TIME".bold(),
+        "STARTED".bold()
+    );
+    println!("  {}", "─".repeat(100).dimmed());
+    for entry in &entries {
+        println!(
+            "  {:<36} {:<30} {:<10} {:>8}  {}",
+            entry.id.dimmed(),
+            format!("{}@{}:{}", entry.skill, entry.instance, entry.tool).cyan(),
+            status_label(&entry.status),
+            format!("{}ms", entry.duration_ms),
+            entry.started_at.format("%Y-%m-%d %H:%M:%S")
+        );
+    }
+    println!();
+    println!("  {} skill history rerun <id> --diff", "Re-run:".dimmed());
+    println!();
+
+    Ok(())
+}
+
+/// Show one execution in full, including the resources it used.
+pub async fn show(id: &str, output: Option<OutputFormat>) -> Result<()> {
+    let db = ExecutionHistoryDb::new(&default_db_path().to_string_lossy()).await?;
+    let entry = db
+        .get_execution(id)
+        .await?
+        .ok_or_else(|| anyhow!("Execution '{}' not found in history", id))?;
+
+    if let Some(output) = OutputFormat::structured(output) {
+        return output.print("history.show", &entry);
+    }
+
+    println!();
+    println!("  {:<12} {}", "ID:".bold(), entry.id);
+    println!(
+        "  {:<12} {}",
+        "Tool:".bold(),
+        format!("{}@{}:{}", entry.skill, entry.instance, entry.tool).cyan()
+    );
+    println!("  {:<12} {}", "Status:".bold(), status_label(&entry.status));
+    println!(
+        "  {:<12} {}",
+        "Started:".bold(),
+        entry.started_at.format("%Y-%m-%d %H:%M:%S")
+    );
+    println!("  {:<12} {}ms", "Duration:".bold(), entry.duration_ms);
+    let resources = match &entry.resource_usage {
+        Some(usage) => ResourceUsage {
+            cpu_time_ms: usage.cpu_time_ms,
+            peak_memory_kb: usage.peak_memory_kb,
+            output_bytes: usage.output_bytes,
+        }
+        .to_string(),
+        None => "not recorded".dimmed().to_string(),
+    };
+    println!("  {:<12} {}", "Resources:".bold(), resources);
+
+    if !entry.args.is_empty() {
+        let mut args: Vec<_> = entry.args.iter().collect();
+        args.sort_by(|a, b| a.0.cmp(b.0));
+        println!("  {}", "Arguments:".bold());
+        for (key, value) in args {
+            println!("    {} = {}", key.cyan(), value);
+        }
+    }
+    if let Some(environment) = &entry.environment {
+        if let Some(context) = &environment.context {
+            println!("  {:<12} {}", "Context:".bold(), context);
+        }
+        for (binary, version) in &environment.binaries {
+            println!("  {:<12} {} {}", "Binary:".bold(), binary, version.dimmed());
+        }
+    }
+    if let Some(error) = &entry.error {
+        println!("  {:<12} {}", "Error:".bold(), error.red());
+    }
+
+    if let Some(output) = entry.output.as_deref().filter(|output| !output.is_empty()) {
+        println!("{}", "─".repeat(60).dimmed());
+        println!("{}", output);
+        println!("{}", "─".repeat(60).dimmed());
+    }
+    println!();
+    Ok(())
+}
+
+/// Re-run a past execution with the same skill, tool, instance and arguments.
+///
+/// With `diff`, prints how the output changed instead of the output itself,
+/// and fails if it changed so drift checks can gate CI.
+pub async fn rerun(
+    id: &str,
+    diff: bool,
+    output: Option<OutputFormat>,
+    manifest: Option<&SkillManifest>,
+) -> Result<()> {
+    let state = Arc::new(AppState::new(HttpServerConfig::default())?);
+    state
+        .initialize_execution_history_db()
+        .await
+        .context("Failed to open execution history")?;
+    if let Some(manifest) = manifest {
+        *state.manifest.write().await = Some(manifest.clone());
+    }
+    state.load_skills_from_manifest().await?;
+
+    let rerun = skill_http::handlers::rerun(state, id.to_string())
+        .await
+        .map_err(|(_, error)| anyhow!(error.0.message))?;
+    let execution = &rerun.execution;
+    let changes: OutputDiff =
+        serde_json::from_value(rerun.diff.clone()).context("Unreadable output diff")?;
+
+    if let Some(output) = OutputFormat::structured(output) {
+        output.print("history.rerun", &rerun)?;
+    } else {
+        println!(
+            "{} Re-ran {} as {} ({}ms)",
+            "→".cyan(),
+            rerun.original_id.yellow(),
+            execution.id.yellow(),
+            execution.duration_ms
+        );
+        println!("{}", "─".repeat(60).dimmed());
+        if !diff {
+            println!("{}", execution.output);
+        } else if changes.is_empty() {
+            println!("{} Output unchanged", "✓".green());
+        } else {
+            for line in changes.to_string().lines() {
+                match line.chars().next() {
+                    Some('-') => println!("{}", line.red()),
+                    Some('+') => println!("{}", line.green()),
+                    _ => println!("{}", line.yellow()),
+                }
+            }
+        }
+        println!("{}", "─".repeat(60).dimmed());
+    }
+
+    if execution.status != ExecutionStatus::Success {
+        bail!(
+            "Execution failed: {}",
+            execution.error.as_deref().unwrap_or("unknown error")
+        );
+    }
+    if diff && !changes.is_empty() {
+        bail!("Output differs from execution {}", rerun.original_id);
+    }
+    Ok(())
+}
+
+/// Apply a retention policy to the execution history and search analytics
+/// databases now, then compact them.
+pub async fn prune(retention: HistoryRetention, output: Option<OutputFormat>) -> Result<()> {
+    let history = ExecutionHistoryDb::new(&default_db_path().to_string_lossy()).await?;
+    let executions = history.apply_retention(&retention).await?;
+    history.close().await;
+
+    let analytics_path = namespace_data_dir(None).join("analytics.db");
+    let analytics = SearchAnalyticsDb::new(&analytics_path.to_string_lossy()).await?;
+    let searches = analytics.apply_retention(&retention).await?;
+    analytics.close().await;
+
+    if let Some(output) = OutputFormat::structured(output) {
+        let report = serde_json::json!({ "execution_history": executions, "analytics": searches });
+        return output.print("history.prune", &report);
+    }
+
+    println!();
+    for (name, report) in [("Execution history", executions), ("Search analytics", searches)] {
+        println!(
+            "  {:<18} {} rows deleted, {} → {}",
+            name.bold(),
+            report.deleted,
+            format_bytes(report.size_before),
+            format_bytes(report.size_after).green()
+        );
+    }
+    println!();
+    println!(
+        "  {} max {} rows, {} days, {} MB per database (0 = no limit)",
+        "Retention:".dimmed(),
+        retention.max_rows,
+        retention.max_age_days,
+        retention.max_db_size_mb
+    );
+    println!();
+    Ok(())
+}
+
+fn status_label(status: &ExecutionStatus) -> ColoredString {
+    match status {
+        ExecutionStatus::Success => "success".green(),
+        ExecutionStatus::Failed => "failed".red(),
+        ExecutionStatus::Timeout => "timeout".red(),
+        ExecutionStatus::Cancelled => "cancelled".yellow(),
+        ExecutionStatus::Pending => "pending".dimmed(),
+        ExecutionStatus::Running => "running".cyan(),
+    }
+}
@@ -0,0 +1,84 @@
+//! Search index snapshots
+//!
+//! `skill index snapshot save` embeds every installed tool once and writes
+//! the result to a file; servers started with `SKILL_INDEX_SNAPSHOT` (or
+//! `[index] snapshot` in search.toml) pointing at it restore the index
+//! instead of re-embedding, which keeps CI and ephemeral containers fast.
+
+use anyhow::{Context, Result};
+use colored::*;
+use skill_runtime::search_config::BackendType;
+use skill_runtime::{IndexSnapshot, SearchConfig, SearchPipeline};
+use std::path::Path;
+
+use super::find::{get_config_path, load_all_tools, to_index_documents};
+
+/// Index every installed tool and save the index to `path`
+pub async fn snapshot_save(path: &Path) -> Result<()> {
+    let tool_documents = load_all_tools().await?;
+    if tool_documents.is_empty() {
+        anyhow::bail!("No skills installed. Install skills before saving an index snapshot.");
+    }
+
+    let config = load_config()?;
+    println!(
+        "{} Indexing {} tools with {}...",
+        "→".cyan(),
+        tool_documents.len(),
+        config.embedding.model.yellow()
+    );
+    let pipeline = SearchPipeline::from_config(config)
+        .await
+        .context("Failed to initialize search pipeline")?;
+    pipeline
+        .index_documents(to_index_documents(&tool_documents))
+        .await
+        .context("Failed to index tools")?;
+
+    let snapshot = pipeline.export_snapshot().await?;
+    snapshot.save(path)?;
+    let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    println!(
+        "{} Saved {} documents to {} ({:.1} KB)",
+        "✓".green(),
+        snapshot.documents.len(),
+        path.display(),
+        size as f64 / 1024.0
+    );
+    Ok(())
+}
+
+/// Restore a saved index into the configured vector store
+pub async fn snapshot_load(path: &Path) -> Result<()> {
+    let snapshot = IndexSnapshot::load(path)?;
+    let mut config = load_config()?;
+    snapshot.check_compatible(&config.embedding.model, config.embedding.dimensions)?;
+
+    // Restore once, from the file given here
+    config.index.snapshot = None;
+    let backend = config.backend.backend_type;
+    let pipeline = SearchPipeline::from_config(config)
+        .await
+        .context("Failed to initialize search pipeline")?;
+    let count = pipeline.restore_snapshot(snapshot).await?;
+    println!("{} Restored {} documents from {}", "✓".green(), count, path.display());
+
+    if backend == BackendType::InMemory {
+        println!(
+            "{} The in-memory backend doesn't persist; set SKILL_INDEX_SNAPSHOT={} so servers restore it on startup",
+            "!".yellow(),
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+fn load_config() -> Result<SearchConfig> {
+    let path = get_config_path()?;
+    let config = if path.exists() {
+        SearchConfig::from_toml_file(&path)?
+    } else {
+        SearchConfig::default()
+    };
+    Ok(config.with_env_overrides())
+}
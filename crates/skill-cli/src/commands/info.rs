@@ -1,26 +1,51 @@
 //! Skill info command - shows SKILL.md documentation and metadata
 use anyhow::{Context, Result};
 use colored::*;
-use skill_runtime::{find_skill_md, parse_skill_md, SkillManifest, LocalSkillLoader};
+use skill_runtime::{
+    find_skill_md, parse_skill_md, LocalSkillLoader, RequirementProbe, RequirementStatus,
+    SkillManifest, SkillRequirements,
+};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-pub async fn execute(skill_name: &str, manifest: Option<&SkillManifest>) -> Result<()> {
+use crate::output::OutputFormat;
+
+pub async fn execute(
+    skill_name: &str,
+    output: Option<OutputFormat>,
+    manifest: Option<&SkillManifest>,
+) -> Result<()> {
     // Find the skill path
     let skill_path = find_skill_path(skill_name, manifest)?;
 
     // Load SKILL.md
     let loader = LocalSkillLoader::new()?;
-    let skill_md = loader.load_skill_md(&skill_path);
-
-    if let Some(md) = skill_md {
-        print_skill_info(&md, &skill_path);
-    } else {
+    let skill_md = match loader.load_skill_md(&skill_path) {
+        Some(md) => Some(md),
         // Try to find SKILL.md directly
-        if let Some(skill_md_path) = find_skill_md(&skill_path) {
-            let md = parse_skill_md(&skill_md_path)
-                .with_context(|| format!("Failed to parse SKILL.md: {}", skill_md_path.display()))?;
-            print_skill_info(&md, &skill_path);
-        } else {
+        None => match find_skill_md(&skill_path) {
+            Some(skill_md_path) => Some(parse_skill_md(&skill_md_path).with_context(|| {
+                format!("Failed to parse SKILL.md: {}", skill_md_path.display())
+            })?),
+            None => None,
+        },
+    };
+    let requirements = check_requirements(skill_name, &skill_path, manifest).await;
+
+    if let Some(output) = OutputFormat::structured(output) {
+        let md = skill_md.with_context(|| {
+            format!(
+                "No SKILL.md found for '{}' in {}",
+                skill_name,
+                skill_path.display()
+            )
+        })?;
+        return output.print("info", &info_data(&md, &skill_path, &requirements));
+    }
+
+    match skill_md {
+        Some(md) => print_skill_info(&md, &skill_path, &requirements),
+        None => {
             println!("{} No SKILL.md found for '{}'", "!".yellow(), skill_name);
             println!();
             println!("  Searched in: {}", skill_path.display());
@@ -38,6 +63,89 @@ pub async fn execute(skill_name: &str, manifest: Option<&SkillManifest>) -> Resu
     Ok(())
 }
 
+/// Host requirements of the skill and of individual tools, probed now
+struct RequirementReport {
+    skill: Vec<RequirementStatus>,
+    tools: HashMap<String, Vec<RequirementStatus>>,
+}
+
+impl RequirementReport {
+    /// Whether the host can run `tool`
+    fn tool_available(&self, tool: &str) -> bool {
+        self.skill
+            .iter()
+            .chain(self.tools.get(tool).into_iter().flatten())
+            .all(|status| status.met)
+    }
+}
+
+async fn check_requirements(
+    skill_name: &str,
+    skill_path: &std::path::Path,
+    manifest: Option<&SkillManifest>,
+) -> RequirementReport {
+    let manifest_requires = manifest
+        .and_then(|manifest| manifest.get_skill(skill_name))
+        .map(|skill| skill.requires.clone())
+        .unwrap_or_default();
+    let requirements = SkillRequirements::load(&manifest_requires, skill_path);
+    let probe = RequirementProbe::default();
+
+    let skill = probe.check(&requirements.skill).await;
+    let mut tools = HashMap::new();
+    for (tool, requires) in &requirements.tools {
+        let own: Vec<_> = requires
+            .iter()
+            .filter(|requirement| !requirements.skill.contains(requirement))
+            .cloned()
+            .collect();
+        tools.insert(tool.clone(), probe.check(&own).await);
+    }
+    RequirementReport { skill, tools }
+}
+
+/// Skill metadata for `--output json|yaml`
+fn info_data(
+    md: &skill_runtime::SkillMdContent,
+    path: &std::path::Path,
+    requirements: &RequirementReport,
+) -> serde_json::Value {
+    let mut tools: Vec<_> = md.tool_docs.iter().collect();
+    tools.sort_by_key(|(name, _)| *name);
+
+    serde_json::json!({
+        "name": skill_name(md, path),
+        "description": md.frontmatter.description,
+        "allowed_tools": md.frontmatter.allowed_tools,
+        "when_to_use": md.when_to_use,
+        "tools": tools
+            .into_iter()
+            .map(|(name, tool)| serde_json::json!({
+                "name": name,
+                "description": tool.description,
+                "examples": tool.examples.len(),
+                "available": requirements.tool_available(name),
+                "requirements": requirements.tools.get(name).cloned().unwrap_or_default(),
+            }))
+            .collect::<Vec<_>>(),
+        "examples": md.examples.len(),
+        "configuration": md.configuration,
+        "requirements": requirements.skill,
+        "path": path,
+    })
+}
+
+/// Name from the frontmatter, or the skill directory name
+fn skill_name(md: &skill_runtime::SkillMdContent, path: &std::path::Path) -> String {
+    if md.frontmatter.name.is_empty() {
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    } else {
+        md.frontmatter.name.clone()
+    }
+}
+
 fn find_skill_path(skill_name: &str, manifest: Option<&SkillManifest>) -> Result<PathBuf> {
     // Check manifest first
     if let Some(manifest) = manifest {
@@ -78,21 +186,13 @@ fn find_skill_path(skill_name: &str, manifest: Option<&SkillManifest>) -> Result
     )
 }
 
-fn print_skill_info(md: &skill_runtime::SkillMdContent, path: &std::path::Path) {
+fn print_skill_info(
+    md: &skill_runtime::SkillMdContent,
+    path: &std::path::Path,
+    requirements: &RequirementReport,
+) {
     println!();
-    println!(
-        "{} {}",
-        "Skill:".bold(),
-        if md.frontmatter.name.is_empty() {
-            path.file_name()
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_else(|| "unknown".to_string())
-        } else {
-            md.frontmatter.name.clone()
-        }
-        .cyan()
-        .bold()
-    );
+    println!("{} {}", "Skill:".bold(), skill_name(md, path).cyan().bold());
     println!("{}", "─".repeat(70).dimmed());
 
     // Description
@@ -107,6 +207,15 @@ fn print_skill_info(md: &skill_runtime::SkillMdContent, path: &std::path::Path)
         println!("{}: {}", "Allowed Tools".bold(), tools.yellow());
     }
 
+    // Host requirements
+    if !requirements.skill.is_empty() {
+        println!();
+        println!("{}", "Requirements".bold());
+        for status in &requirements.skill {
+            print_requirement(status, "  ");
+        }
+    }
+
     // When to use
     if let Some(ref when_to_use) = md.when_to_use {
         println!();
@@ -126,10 +235,17 @@ fn print_skill_info(md: &skill_runtime::SkillMdContent, path: &std::path::Path)
         tools.sort_by_key(|(name, _)| *name);
 
         for (name, tool) in tools {
-            println!("  {} {}", "•".cyan(), name.cyan().bold());
+            if requirements.tool_available(name) {
+                println!("  {} {}", "•".cyan(), name.cyan().bold());
+            } else {
+                println!("  {} {} {}", "•".yellow(), name.yellow().bold(), "(unavailable)".yellow());
+            }
             if !tool.description.is_empty() {
                 println!("    {}", tool.description.dimmed());
             }
+            for status in requirements.tools.get(name).into_iter().flatten() {
+                print_requirement(status, "    ");
+            }
             if !tool.examples.is_empty() {
                 println!(
                     "    {} {} example(s)",
@@ -167,3 +283,16 @@ fn print_skill_info(md: &skill_runtime::SkillMdContent, path: &std::path::Path)
     println!("{}: {}", "Path".dimmed(), path.display().to_string().dimmed());
     println!();
 }
+
+fn print_requirement(status: &RequirementStatus, indent: &str) {
+    let detail = status
+        .detail
+        .as_deref()
+        .map(|detail| format!(" ({})", detail))
+        .unwrap_or_default();
+    if status.met {
+        println!("{}{} {}{}", indent, "✓".green(), status.requirement, detail.dimmed());
+    } else {
+        println!("{}{} {}{}", indent, "✗".red(), status.requirement, detail.red());
+    }
+}
@@ -1,23 +1,344 @@
 use anyhow::{Context, Result};
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
+use skill_runtime::dependencies::{is_installed, read_declared_dependencies};
 use skill_runtime::{
-    is_git_url, parse_git_url, GitSkillLoader, InstanceConfig, InstanceManager, SkillEngine,
+    is_archive_url, is_git_url, is_oci_reference, parse_archive_url, parse_git_url,
+    parse_oci_reference, ArchiveFormat, ArchiveSkillLoader, GitRef, GitSkillLoader,
+    GitSourceConfig, InstanceConfig, InstanceManager, LockedSkill, Lockfile, OciSkillLoader,
+    SkillDependency, SkillEngine, SkillManifest, SkillRuntime,
 };
+use std::collections::HashSet;
+use std::future::Future;
 use std::path::PathBuf;
-use std::time::Instant;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A skill artifact fetched from its source, ready to install
+struct FetchedSkill {
+    wasm_path: PathBuf,
+    skill_name: String,
+    version: Option<String>,
+    dependencies: Vec<SkillDependency>,
+    /// The revision the source resolved to, for the lockfile
+    resolved: LockedSkill,
+}
 
-pub async fn execute(source: &str, instance: Option<&str>, force: bool, enhance: bool) -> Result<()> {
-    println!("{} Installing skill from: {}", "→".cyan(), source.yellow());
+/// A skill installed from a source
+struct InstalledSkill {
+    name: String,
+    resolved: LockedSkill,
+}
 
-    let start = Instant::now();
+/// Outcome of installing one manifest skill
+enum ManifestOutcome {
+    Installed { skills: Vec<String>, locked: bool },
+    Skipped(String),
+    Failed(String),
+}
+
+pub async fn execute(
+    source: &str,
+    instance: Option<&str>,
+    force: bool,
+    enhance: bool,
+    require_signed: bool,
+    git: GitSourceConfig,
+) -> Result<()> {
+    let mut installing = Vec::new();
+    install(
+        source,
+        None,
+        None,
+        instance,
+        force,
+        enhance,
+        require_signed,
+        &git,
+        &mut installing,
+    )
+    .await?;
+    Ok(())
+}
 
-    // Determine source type and get WASM path + skill name
-    let (wasm_path, skill_name, version) = if is_git_url(source) {
-        install_from_git(source, force).await?
+/// Install every skill the manifest declares, honoring its lockfile
+///
+/// Skills are installed in dependency order, up to `jobs` at a time, and
+/// summarized in a table at the end. Git sources are checked out at their
+/// locked commit, OCI and archive sources fetched at their locked digest;
+/// `update` ignores the lockfile and resolves every source afresh. The
+/// lockfile is then updated with what was installed.
+pub async fn from_manifest(
+    manifest: Option<&SkillManifest>,
+    jobs: usize,
+    update: bool,
+    force: bool,
+    require_signed: bool,
+) -> Result<()> {
+    let manifest = manifest.context(
+        "No manifest found. Create .skill-engine.toml or pass one with --manifest",
+    )?;
+    let lock_path = Lockfile::path_for(manifest);
+    let mut lockfile = Lockfile::load(&lock_path)?;
+    // Skills that fail to update keep their previous entry
+    let pins = if update {
+        Lockfile::default()
     } else {
-        install_from_local(source)?
+        lockfile.clone()
     };
+    let waves = manifest.install_waves()?;
+    let total: usize = waves.iter().map(Vec::len).sum();
+    if total == 0 {
+        println!("{} The manifest declares no skills", "→".cyan());
+        return Ok(());
+    }
+    println!(
+        "{} Installing {} skill(s) from the manifest, {} at a time",
+        "→".cyan(),
+        total,
+        jobs.max(1)
+    );
+
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(jobs.max(1)));
+    let mut results: Vec<(String, ManifestOutcome, Duration)> = Vec::new();
+    let mut failed: HashSet<String> = HashSet::new();
+    for wave in waves {
+        let mut tasks = Vec::new();
+        for name in wave {
+            let skill = manifest
+                .get_skill(&name)
+                .with_context(|| format!("Skill '{}' not found in manifest", name))?;
+            let skipped = match skill.runtime {
+                SkillRuntime::Wasm => None,
+                SkillRuntime::Docker => Some("docker runtime, the image is pulled on first run"),
+                SkillRuntime::Native => Some("native runtime, nothing to install"),
+            };
+            if let Some(reason) = skipped {
+                results.push((name, ManifestOutcome::Skipped(reason.to_string()), Duration::ZERO));
+                continue;
+            }
+            if let Some(dep) = skill.depends_on.iter().find(|dep| failed.contains(dep.name())) {
+                let reason = format!("dependency '{}' failed", dep.name());
+                failed.insert(name.clone());
+                results.push((name, ManifestOutcome::Failed(reason), Duration::ZERO));
+                continue;
+            }
+
+            let target = pins.install_source(manifest, &name)?;
+            let git = skill.git.clone().unwrap_or_default();
+            let semaphore = semaphore.clone();
+            let runtime = tokio::runtime::Handle::current();
+            // Git clones and builds block, so each install gets its own thread
+            tasks.push(tokio::task::spawn_blocking(move || {
+                let _permit = runtime.block_on(semaphore.acquire_owned());
+                let start = Instant::now();
+                let mut installing = Vec::new();
+                let installed = runtime.block_on(install(
+                    &target.source,
+                    Some(name.as_str()),
+                    target.git_ref.as_deref(),
+                    None,
+                    force,
+                    false,
+                    require_signed,
+                    &git,
+                    &mut installing,
+                ));
+                (name, target.locked, installed, start.elapsed())
+            }));
+        }
+
+        for task in tasks {
+            let (name, locked, installed, elapsed) = task.await.context("Install task failed")?;
+            let outcome = match installed {
+                Ok(installed) => {
+                    if let Some(skill) = installed.iter().find(|skill| skill.name == name) {
+                        lockfile.lock(manifest, &name, skill.resolved.clone());
+                    }
+                    let skills = installed.into_iter().map(|skill| skill.name).collect();
+                    ManifestOutcome::Installed { skills, locked }
+                }
+                Err(e) => {
+                    failed.insert(name.clone());
+                    ManifestOutcome::Failed(format!("{:#}", e))
+                }
+            };
+            results.push((name, outcome, elapsed));
+        }
+    }
+
+    lockfile.retain_manifest(manifest);
+    lockfile.save(&lock_path)?;
+
+    print_manifest_summary(&results);
+    println!("  {} {}", "Lockfile:".bold(), lock_path.display());
+    println!();
+    if !failed.is_empty() {
+        anyhow::bail!("{} of {} skill(s) failed to install", failed.len(), total);
+    }
+    Ok(())
+}
+
+fn print_manifest_summary(results: &[(String, ManifestOutcome, Duration)]) {
+    println!();
+    println!("{}", "Manifest install summary".bold());
+    println!();
+    println!("  {:<24} {:<10} {:>8}  DETAILS", "SKILL", "STATUS", "TIME");
+    for (name, outcome, elapsed) in results {
+        let (status, details) = match outcome {
+            ManifestOutcome::Installed { skills, locked } => {
+                let mut details = if skills.len() == 1 && skills[0] == *name {
+                    String::new()
+                } else {
+                    skills.join(", ")
+                };
+                if *locked {
+                    if !details.is_empty() {
+                        details.push_str("; ");
+                    }
+                    details.push_str("from lockfile");
+                }
+                (format!("{:<10}", "installed").green(), details)
+            }
+            ManifestOutcome::Skipped(reason) => (format!("{:<10}", "skipped").yellow(), reason.clone()),
+            ManifestOutcome::Failed(error) => (format!("{:<10}", "failed").red(), error.clone()),
+        };
+        println!(
+            "  {:<24} {} {:>7.1}s  {}",
+            name,
+            status,
+            elapsed.as_secs_f64(),
+            details
+        );
+    }
+    println!();
+}
+
+/// Install every skill provided by a source
+///
+/// `name` renames the skill of a single-skill source and `git_ref` overrides
+/// the ref of a Git source. `installing` holds the skills currently being
+/// installed, to detect cycles.
+#[allow(clippy::too_many_arguments)]
+fn install<'a>(
+    source: &'a str,
+    name: Option<&'a str>,
+    git_ref: Option<&'a str>,
+    instance: Option<&'a str>,
+    force: bool,
+    enhance: bool,
+    require_signed: bool,
+    git: &'a GitSourceConfig,
+    installing: &'a mut Vec<String>,
+) -> Pin<Box<dyn Future<Output = Result<Vec<InstalledSkill>>> + 'a>> {
+    Box::pin(async move {
+        println!("{} Installing skill from: {}", "→".cyan(), source.yellow());
+
+        // Determine source type and get WASM path + skill name per skill
+        // Archive URLs are checked first: release download links on GitHub
+        // would otherwise be taken for repository URLs
+        let mut fetched = if is_oci_reference(source) {
+            vec![install_from_oci(source).await?]
+        } else if is_archive_url(source) {
+            vec![install_from_archive(source).await?]
+        } else if is_git_url(source) {
+            install_from_git(source, git_ref, force, git).await?
+        } else {
+            vec![install_from_local(source)?]
+        };
+        if let (Some(name), [skill]) = (name, fetched.as_mut_slice()) {
+            skill.skill_name = name.to_string();
+        }
+
+        let mut installed = Vec::new();
+        for skill in fetched {
+            installed
+                .push(install_fetched(skill, instance, enhance, require_signed, installing).await?);
+        }
+        Ok(installed)
+    })
+}
+
+/// Install one fetched skill after its declared dependencies
+async fn install_fetched(
+    fetched: FetchedSkill,
+    instance: Option<&str>,
+    enhance: bool,
+    require_signed: bool,
+    installing: &mut Vec<String>,
+) -> Result<InstalledSkill> {
+    let start = Instant::now();
+    let FetchedSkill {
+        wasm_path,
+        skill_name,
+        version,
+        dependencies,
+        resolved,
+    } = fetched;
+
+    // Verify the publisher signature (skill.sig next to the WASM artifact)
+    let artifact_dir = wasm_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| std::path::Path::new("."));
+    super::trust::verify_for_install(artifact_dir, &wasm_path, require_signed)?;
+
+    // Install dependencies first so the skill is runnable once installed
+    if installing.contains(&skill_name) {
+        installing.push(skill_name.clone());
+        anyhow::bail!("Dependency cycle: {}", installing.join(" -> "));
+    }
+    installing.push(skill_name.clone());
+    for dep in &dependencies {
+        if is_installed(dep.name()) {
+            println!(
+                "{} Dependency {} already installed",
+                "✓".green(),
+                dep.name().cyan()
+            );
+            continue;
+        }
+
+        let dep_source = dep.source().with_context(|| {
+            format!(
+                "Dependency '{}' of '{}' is not installed and declares no source. \
+                 Install it first: skill install <source>",
+                dep.name(),
+                skill_name
+            )
+        })?;
+        println!(
+            "{} Installing dependency {} of {}",
+            "→".cyan(),
+            dep.name().cyan(),
+            skill_name.yellow()
+        );
+        let default_git = GitSourceConfig::default();
+        let installed = install(
+            dep_source,
+            None,
+            None,
+            None,
+            false,
+            false,
+            require_signed,
+            &default_git,
+            installing,
+        )
+        .await?;
+        if !installed.iter().any(|skill| skill.name == dep.name()) {
+            let names: Vec<&str> = installed.iter().map(|skill| skill.name.as_str()).collect();
+            anyhow::bail!(
+                "Dependency '{}' of '{}' not provided by {} (installed: {})",
+                dep.name(),
+                skill_name,
+                dep_source,
+                names.join(", ")
+            );
+        }
+    }
+    installing.pop();
 
     // Create progress spinner
     let pb = ProgressBar::new_spinner();
@@ -61,10 +382,14 @@ pub async fn execute(source: &str, instance: Option<&str>, force: bool, enhance:
 
     let mut config = InstanceConfig::default();
     config.metadata.skill_name = skill_name.clone();
-    config.metadata.skill_version = version.unwrap_or_else(|| "0.1.0".to_string());
+    config.metadata.skill_version = version.clone().unwrap_or_else(|| "0.1.0".to_string());
     config.metadata.instance_name = instance_name.to_string();
     config.metadata.created_at = chrono::Utc::now();
     config.metadata.updated_at = chrono::Utc::now();
+    config.metadata.dependencies = dependencies
+        .iter()
+        .map(|dep| dep.name().to_string())
+        .collect();
 
     // Create instance with empty secrets (will be configured later)
     instance_manager
@@ -90,6 +415,10 @@ pub async fn execute(source: &str, instance: Option<&str>, force: bool, enhance:
     println!("  {} {}", "Skill:".bold(), skill_name.cyan());
     println!("  {} {}", "Instance:".bold(), instance_name.yellow());
     println!("  {} {}", "Location:".bold(), dest_path.display());
+    if !dependencies.is_empty() {
+        let names: Vec<&str> = dependencies.iter().map(|dep| dep.name()).collect();
+        println!("  {} {}", "Requires:".bold(), names.join(", "));
+    }
     println!(
         "  {} {:.2}s",
         "Duration:".bold(),
@@ -111,7 +440,10 @@ pub async fn execute(source: &str, instance: Option<&str>, force: bool, enhance:
     }
     println!();
 
-    Ok(())
+    Ok(InstalledSkill {
+        name: skill_name,
+        resolved: LockedSkill { version, ..resolved },
+    })
 }
 
 /// Enhance skill with AI-generated examples
@@ -192,14 +524,18 @@ async fn enhance_skill(_skill_name: &str, skill_dir: &PathBuf) -> Result<()> {
         // Build documents
         let documents: Vec<IndexDocument> = tools
             .iter()
-            .map(|t| IndexDocument {
-                id: format!("{}:{}", skill_name, t.name),
-                content: build_tool_content(t),
-                metadata: DocumentMetadata {
-                    skill_name: Some(skill_name.to_string()),
-                    tool_name: Some(t.name.clone()),
-                    ..Default::default()
-                },
+            .flat_map(|t| {
+                let doc = IndexDocument {
+                    id: format!("{}:{}", skill_name, t.name),
+                    content: build_tool_content(t),
+                    metadata: DocumentMetadata {
+                        skill_name: Some(skill_name.to_string()),
+                        tool_name: Some(t.name.clone()),
+                        ..Default::default()
+                    },
+                };
+                let examples = doc.example_documents(t.examples.iter().map(|e| &e.code));
+                std::iter::once(doc).chain(examples)
             })
             .collect();
 
@@ -297,9 +633,20 @@ fn load_search_config() -> Result<skill_runtime::SearchConfig> {
     }
 }
 
-/// Install skill from a Git URL
-async fn install_from_git(source: &str, force: bool) -> Result<(PathBuf, String, Option<String>)> {
-    let git_source = parse_git_url(source)?;
+/// Install skill(s) from a Git URL
+///
+/// A repository with several skill directories (or a `#subpath` holding
+/// them) yields one entry per skill.
+async fn install_from_git(
+    source: &str,
+    git_ref: Option<&str>,
+    force: bool,
+    git: &GitSourceConfig,
+) -> Result<Vec<FetchedSkill>> {
+    let mut git_source = parse_git_url(source)?;
+    if let Some(git_ref) = git_ref.filter(|git_ref| !git_ref.is_empty()) {
+        git_source.git_ref = GitRef::parse(git_ref);
+    }
 
     println!(
         "{} Detected Git source: {}",
@@ -307,33 +654,127 @@ async fn install_from_git(source: &str, force: bool) -> Result<(PathBuf, String,
         git_source.display_name().cyan()
     );
 
-    let loader = GitSkillLoader::new()?;
+    let loader = GitSkillLoader::new()?.with_config(git.clone());
 
     // Clone/update repository
     println!("{} Cloning repository...", "→".dimmed());
-    let cloned = loader.clone_skill(&git_source, force).await?;
+    let skills = loader.clone_skills(&git_source, force).await?;
+
+    if skills.len() > 1 {
+        let names: Vec<&str> = skills.iter().map(|s| s.skill_name.as_str()).collect();
+        println!(
+            "{} Found {} skills: {}",
+            "→".dimmed(),
+            skills.len(),
+            names.join(", ").cyan()
+        );
+    }
+
+    let mut fetched = Vec::new();
+    for cloned in skills {
+        println!(
+            "{} Detected skill type for {}: {}",
+            "→".dimmed(),
+            cloned.skill_name.cyan(),
+            format!("{}", cloned.skill_type).yellow()
+        );
+
+        // Build if needed
+        println!("{} Building skill...", "→".dimmed());
+        let wasm_path = loader.build_skill(&cloned).await?;
+
+        println!(
+            "{} Build complete: {}",
+            "✓".green(),
+            wasm_path.display()
+        );
+
+        fetched.push(FetchedSkill {
+            wasm_path,
+            skill_name: cloned.skill_name,
+            version: cloned.version,
+            dependencies: cloned.dependencies,
+            resolved: LockedSkill {
+                commit: cloned.commit,
+                ..Default::default()
+            },
+        });
+    }
+
+    Ok(fetched)
+}
+
+/// Install a skill from an OCI registry (`oci://registry/repo:tag`)
+async fn install_from_oci(source: &str) -> Result<FetchedSkill> {
+    let reference = parse_oci_reference(source)?;
+
+    println!(
+        "{} Pulling OCI artifact: {}",
+        "→".dimmed(),
+        reference.to_string().cyan()
+    );
+
+    let loader = OciSkillLoader::new()?;
+    let skill = loader.pull(&reference).await?;
+
+    println!(
+        "{} Pulled {} ({})",
+        "✓".green(),
+        skill.skill_name.cyan(),
+        skill.manifest_digest.dimmed()
+    );
+
+    Ok(FetchedSkill {
+        wasm_path: skill.wasm_path,
+        skill_name: skill.skill_name,
+        version: skill.version,
+        dependencies: skill.dependencies,
+        resolved: LockedSkill {
+            digest: Some(skill.manifest_digest),
+            ..Default::default()
+        },
+    })
+}
+
+/// Install a pre-built skill from a .tar.gz/.tgz/.tar/.zip URL
+async fn install_from_archive(source: &str) -> Result<FetchedSkill> {
+    let archive_source = parse_archive_url(source)?;
 
     println!(
-        "{} Detected skill type: {}",
+        "{} Downloading archive: {}",
         "→".dimmed(),
-        format!("{}", cloned.skill_type).yellow()
+        archive_source.url.cyan()
     );
 
-    // Build if needed
-    println!("{} Building skill...", "→".dimmed());
-    let wasm_path = loader.build_skill(&cloned).await?;
+    let loader = ArchiveSkillLoader::new()?;
+    let skill = loader.fetch(&archive_source).await?;
 
+    if archive_source.sha256.is_some() {
+        println!("{} Checksum verified: sha256:{}", "✓".green(), skill.sha256);
+    } else {
+        println!("{} sha256:{}", "→".dimmed(), skill.sha256.dimmed());
+    }
     println!(
-        "{} Build complete: {}",
+        "{} Extracted {} to {}",
         "✓".green(),
-        wasm_path.display()
+        skill.skill_name.cyan(),
+        skill.local_path.display()
     );
 
-    Ok((wasm_path, cloned.skill_name, cloned.version))
+    Ok(FetchedSkill {
+        wasm_path: skill.wasm_path,
+        skill_name: skill.skill_name,
+        version: skill.version,
+        dependencies: skill.dependencies,
+        resolved: LockedSkill {
+            sha256: Some(skill.sha256),
+            ..Default::default()
+        },
+    })
 }
 
 /// Install skill from a local file
-fn install_from_local(source: &str) -> Result<(PathBuf, String, Option<String>)> {
+fn install_from_local(source: &str) -> Result<FetchedSkill> {
     let source_path = PathBuf::from(source);
 
     if !source_path.exists() {
@@ -346,9 +787,27 @@ fn install_from_local(source: &str) -> Result<(PathBuf, String, Option<String>)>
         );
     }
 
+    // Packages from `skill package` and other local archives
+    if ArchiveFormat::from_path(source).is_some() {
+        let skill = ArchiveSkillLoader::new()?.open_file(&source_path)?;
+        println!(
+            "{} Extracted {} to {}",
+            "✓".green(),
+            skill.skill_name.cyan(),
+            skill.local_path.display()
+        );
+        return Ok(FetchedSkill {
+            wasm_path: skill.wasm_path,
+            skill_name: skill.skill_name,
+            version: skill.version,
+            dependencies: skill.dependencies,
+            resolved: LockedSkill::default(),
+        });
+    }
+
     if !source_path.extension().is_some_and(|ext| ext == "wasm") {
         anyhow::bail!(
-            "Invalid file type. Expected .wasm file.\n\
+            "Invalid file type. Expected a .wasm file or .skillpkg package.\n\
              \n\
              For source directories, use:\n\
              skill install github:user/repo\n\
@@ -362,5 +821,17 @@ fn install_from_local(source: &str) -> Result<(PathBuf, String, Option<String>)>
         .ok_or_else(|| anyhow::anyhow!("Invalid filename"))?
         .to_string();
 
-    Ok((source_path, skill_name, None))
+    // Dependencies are declared in skill.yaml or SKILL.md next to the WASM file
+    let dependencies = match source_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => read_declared_dependencies(dir)?,
+        _ => read_declared_dependencies(std::path::Path::new("."))?,
+    };
+
+    Ok(FetchedSkill {
+        wasm_path: source_path,
+        skill_name,
+        version: None,
+        dependencies,
+        resolved: LockedSkill::default(),
+    })
 }
@@ -3,7 +3,13 @@ use colored::*;
 use skill_runtime::{InstanceManager, SkillManifest};
 use std::fs;
 
-pub async fn execute(format: &str, manifest: Option<&SkillManifest>) -> Result<()> {
+use crate::output::OutputFormat;
+
+pub async fn execute(
+    format: &str,
+    output: Option<OutputFormat>,
+    manifest: Option<&SkillManifest>,
+) -> Result<()> {
     let home = dirs::home_dir().context("Failed to get home directory")?;
     let registry_dir = home.join(".skill-engine").join("registry");
 
@@ -26,6 +32,10 @@ pub async fn execute(format: &str, manifest: Option<&SkillManifest>) -> Result<(
     // Get manifest skills
     let manifest_skills = manifest.map(|m| m.list_skills()).unwrap_or_default();
 
+    if let Some(output) = OutputFormat::structured(output) {
+        return output.print("list", &list_data(&installed_skills, &manifest_skills)?);
+    }
+
     // Check if we have anything to show
     if installed_skills.is_empty() && manifest_skills.is_empty() {
         println!("{} No skills found", "!".yellow());
@@ -39,7 +49,7 @@ pub async fn execute(format: &str, manifest: Option<&SkillManifest>) -> Result<(
     }
 
     match format {
-        "json" => list_json(&installed_skills, &manifest_skills).await,
+        "json" if output.is_none() => list_json(&installed_skills, &manifest_skills).await,
         _ => list_table(&installed_skills, &manifest_skills).await,
     }
 }
@@ -192,6 +202,16 @@ async fn list_json(
     installed_skills: &[String],
     manifest_skills: &[skill_runtime::manifest::SkillInfo],
 ) -> Result<()> {
+    let output = list_data(installed_skills, manifest_skills)?;
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+/// Installed and manifest-only skills, as printed by `--format json`
+fn list_data(
+    installed_skills: &[String],
+    manifest_skills: &[skill_runtime::manifest::SkillInfo],
+) -> Result<serde_json::Value> {
     use serde_json::json;
 
     let instance_manager = InstanceManager::new()?;
@@ -240,11 +260,8 @@ async fn list_json(
         })
         .collect();
 
-    let output = json!({
+    Ok(json!({
         "installed": installed_list,
         "manifest": manifest_list
-    });
-
-    println!("{}", serde_json::to_string_pretty(&output)?);
-    Ok(())
+    }))
 }
@@ -1,25 +1,108 @@
 //! Manifest loading and utilities for the CLI.
 
 use anyhow::Result;
-use skill_runtime::SkillManifest;
-use std::path::Path;
+use colored::*;
+use skill_runtime::{global_manifest_path, SkillManifest};
+use std::path::{Path, PathBuf};
 
-/// Load manifest from path or auto-detect
-pub fn load_manifest(path: Option<&Path>) -> Result<Option<SkillManifest>> {
-    if let Some(path) = path {
-        // Explicit path provided
-        let manifest = SkillManifest::load(path)?;
-        tracing::info!("Loaded manifest from {}", path.display());
-        return Ok(Some(manifest));
+/// Load the effective manifest
+///
+/// Layers, lowest precedence first:
+/// 1. `~/.skill-engine/global.toml` (or `$SKILL_GLOBAL_MANIFEST`)
+/// 2. `.skill-engine.toml` auto-detected from the current directory
+/// 3. each `--manifest` path, in the order given
+pub fn load_manifest<P: AsRef<Path>>(overrides: &[P]) -> Result<Option<SkillManifest>> {
+    let layers = manifest_layers(overrides)?;
+    if layers.is_empty() {
+        return Ok(None);
+    }
+
+    let manifest = SkillManifest::load_layered(&layers)?;
+    for layer in &layers {
+        tracing::info!("Loaded manifest layer {}", layer.display());
+    }
+    Ok(Some(manifest))
+}
+
+/// Manifest files that make up the effective manifest, lowest precedence first
+pub fn manifest_layers<P: AsRef<Path>>(overrides: &[P]) -> Result<Vec<PathBuf>> {
+    let mut candidates = Vec::new();
+    if let Some(global) = global_manifest_path().filter(|path| path.is_file()) {
+        candidates.push(global);
     }
 
     // Try to auto-detect manifest in current directory or parents
     let cwd = std::env::current_dir()?;
-    if let Some(manifest_path) = SkillManifest::find(&cwd) {
-        let manifest = SkillManifest::load(&manifest_path)?;
-        tracing::info!("Auto-detected manifest at {}", manifest_path.display());
-        return Ok(Some(manifest));
+    if let Some(project) = SkillManifest::find(&cwd) {
+        candidates.push(project);
+    }
+
+    for path in overrides {
+        let path = path.as_ref();
+        if !path.exists() {
+            anyhow::bail!("Manifest not found: {}", path.display());
+        }
+        candidates.push(path.to_path_buf());
     }
 
-    Ok(None)
+    // A file listed twice keeps its highest-precedence position
+    let mut layers: Vec<PathBuf> = Vec::new();
+    let mut seen: Vec<PathBuf> = Vec::new();
+    for path in candidates.into_iter().rev() {
+        let key = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if !seen.contains(&key) {
+            seen.push(key);
+            layers.push(path);
+        }
+    }
+    layers.reverse();
+    Ok(layers)
+}
+
+/// Print the merged manifest (`skill manifest effective`)
+pub async fn effective(manifest: Option<&SkillManifest>, format: &str) -> Result<()> {
+    let Some(manifest) = manifest else {
+        anyhow::bail!(
+            "No manifest found. Create .skill-engine.toml, ~/.skill-engine/global.toml, or pass --manifest."
+        );
+    };
+
+    if format == "json" {
+        let origins: std::collections::BTreeMap<_, _> = manifest
+            .skill_origins
+            .iter()
+            .map(|(name, path)| (name.clone(), path.display().to_string()))
+            .collect();
+        let json = serde_json::json!({
+            "layers": manifest.layers.iter().map(|p| p.display().to_string()).collect::<Vec<_>>(),
+            "origins": origins,
+            "manifest": manifest,
+        });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        "# Effective manifest (later layers take precedence)".dimmed()
+    );
+    for (index, layer) in manifest.layers.iter().enumerate() {
+        println!(
+            "{}",
+            format!("#   {}. {}", index + 1, layer.display()).dimmed()
+        );
+    }
+    let mut names: Vec<&String> = manifest.skills.keys().collect();
+    names.sort();
+    for name in names {
+        if let Some(origin) = manifest.skill_origins.get(name) {
+            println!(
+                "{}",
+                format!("#   skills.{} <- {}", name, origin.display()).dimmed()
+            );
+        }
+    }
+    println!();
+    print!("{}", toml::to_string_pretty(manifest)?);
+    Ok(())
 }
@@ -1,19 +1,28 @@
 pub mod claude;
 pub mod claude_bridge;
 pub mod config;
+#[cfg(unix)]
+pub mod daemon;
 pub mod enhance;
 pub mod exec;
 pub mod find;
+pub mod history;
+pub mod index;
 pub mod info;
 pub mod init;
 pub mod init_skill;
 pub mod install;
 pub mod list;
 pub mod manifest;
+pub mod models;
+pub mod package;
 pub mod remove;
 pub mod run;
 pub mod search;
 pub mod serve;
 pub mod setup;
+pub mod trust;
 pub mod upgrade;
+pub mod validate;
 pub mod web;
+pub mod workflow;
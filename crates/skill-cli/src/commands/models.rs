@@ -0,0 +1,205 @@
+//! Local embedding and reranker model management
+//!
+//! Pre-downloads FastEmbed models into ~/.skill-engine/models so semantic
+//! search works without network access (see `SKILL_OFFLINE`).
+
+use anyhow::{Context, Result};
+use colored::*;
+use indicatif::{ProgressBar, ProgressStyle};
+use skill_runtime::embeddings::{offline_mode, ModelSpec, ModelStore, MODEL_CATALOG, OFFLINE_ENV};
+use skill_runtime::format_bytes;
+
+/// List catalog models and whether they are downloaded
+pub async fn list(format: &str) -> Result<()> {
+    let store = ModelStore::open_default()?;
+    let models = store.list()?;
+
+    if format == "json" {
+        let json: Vec<serde_json::Value> = models
+            .iter()
+            .map(|(spec, installed)| {
+                serde_json::json!({
+                    "name": spec.name,
+                    "kind": spec.kind,
+                    "repo": spec.repo,
+                    "installed": installed.is_some(),
+                    "revision": installed.as_ref().map(|m| m.revision.clone()),
+                    "size_bytes": installed.as_ref().map(|m| m.total_size()),
+                    "downloaded_at": installed.as_ref().map(|m| m.downloaded_at),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    println!();
+    println!("{} Models in {}", "→".cyan(), store.root().display());
+    if offline_mode() {
+        println!(
+            "{} Offline mode is enabled ({}=1)",
+            "!".yellow(),
+            OFFLINE_ENV
+        );
+    }
+    println!();
+
+    for (spec, installed) in &models {
+        let status = match installed {
+            Some(model) => format!("✓ {}", format_bytes(model.total_size())).green(),
+            None => "not downloaded".dimmed(),
+        };
+        println!(
+            "  {:<36} {:<10} {}",
+            spec.name.cyan(),
+            spec.kind.to_string(),
+            status
+        );
+        println!("  {}", spec.repo.dimmed());
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Download one or more models (or every catalog model with `all`)
+pub async fn download(names: &[String], all: bool, force: bool) -> Result<()> {
+    let specs = resolve_specs(names, all)?;
+    let store = ModelStore::open_default()?;
+
+    for spec in specs {
+        if !force && store.installed(spec)?.is_some() {
+            println!("{} {} already downloaded", "✓".green(), spec.name.cyan());
+            continue;
+        }
+
+        println!(
+            "{} Downloading {} ({})",
+            "→".cyan(),
+            spec.name.cyan(),
+            spec.repo.dimmed()
+        );
+
+        let pb = ProgressBar::new(0);
+        pb.set_style(
+            ProgressStyle::default_bar()
+                .template("  {msg:32} [{bar:30.cyan}] {bytes}/{total_bytes}")
+                .unwrap()
+                .progress_chars("=> "),
+        );
+
+        let model = store
+            .download_with_progress(spec, force, |progress| {
+                pb.set_message(progress.file.clone());
+                if let Some(total) = progress.total {
+                    pb.set_length(total);
+                }
+                pb.set_position(progress.downloaded);
+            })
+            .await
+            .with_context(|| format!("Failed to download model '{}'", spec.name))?;
+        pb.finish_and_clear();
+
+        println!(
+            "{} {} ({}, {} files, sha256 verified)",
+            "✓".green(),
+            spec.name.cyan(),
+            format_bytes(model.total_size()),
+            model.files.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Remove downloaded models
+pub async fn remove(names: &[String], all: bool) -> Result<()> {
+    let specs = resolve_specs(names, all)?;
+    let store = ModelStore::open_default()?;
+
+    for spec in specs {
+        if store.remove(spec)? {
+            println!("{} Removed {}", "✓".green(), spec.name.cyan());
+        } else if !all {
+            println!("{} {} is not downloaded", "!".yellow(), spec.name);
+        }
+    }
+
+    Ok(())
+}
+
+/// Re-hash downloaded models against their recorded checksums
+pub async fn verify(names: &[String]) -> Result<()> {
+    let store = ModelStore::open_default()?;
+    let specs = if names.is_empty() {
+        // Default to everything that is downloaded
+        store
+            .list()?
+            .into_iter()
+            .filter(|(_, installed)| installed.is_some())
+            .map(|(spec, _)| spec)
+            .collect()
+    } else {
+        resolve_specs(names, false)?
+    };
+
+    let mut failed = 0;
+    for spec in specs {
+        match store.verify(spec) {
+            Ok(model) => println!(
+                "{} {} ({} files)",
+                "✓".green(),
+                spec.name.cyan(),
+                model.files.len()
+            ),
+            Err(e) => {
+                failed += 1;
+                println!("{} {}: {}", "✗".red(), spec.name.cyan(), e);
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!(
+            "{} model(s) failed verification; re-download with 'skill models download --force'",
+            failed
+        );
+    }
+    Ok(())
+}
+
+fn resolve_specs(names: &[String], all: bool) -> Result<Vec<&'static ModelSpec>> {
+    if all {
+        return Ok(MODEL_CATALOG.iter().collect());
+    }
+    if names.is_empty() {
+        anyhow::bail!("Specify one or more model names, or --all. See 'skill models list'.");
+    }
+
+    names
+        .iter()
+        .map(|name| {
+            ModelSpec::find(name).with_context(|| {
+                format!(
+                    "Unknown model '{}'. See 'skill models list' for available models.",
+                    name
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_specs() {
+        assert_eq!(resolve_specs(&[], true).unwrap().len(), MODEL_CATALOG.len());
+        assert!(resolve_specs(&[], false).is_err());
+        assert!(resolve_specs(&["nope".to_string()], false).is_err());
+
+        let specs = resolve_specs(&["minilm".to_string()], false).unwrap();
+        assert_eq!(specs[0].name, "all-minilm");
+    }
+}
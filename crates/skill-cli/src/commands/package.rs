@@ -0,0 +1,52 @@
+//! Build distributable `.skillpkg` bundles
+
+use anyhow::Result;
+use colored::*;
+use skill_runtime::build_package;
+use std::path::Path;
+
+/// Package a built skill directory
+pub async fn execute(path: &Path, output: &Path) -> Result<()> {
+    if !path.is_dir() {
+        anyhow::bail!("Not a skill directory: {}", path.display());
+    }
+
+    println!("{} Packaging {}", "→".cyan(), path.display());
+    let package = build_package(path, output)?;
+    let integrity = &package.integrity;
+
+    println!(
+        "{} Created {}",
+        "✓".green(),
+        package.path.display().to_string().cyan()
+    );
+    println!();
+    println!("  {} {}", "Skill:".bold(), integrity.name);
+    if let Some(version) = &integrity.skill_version {
+        println!("  {} {}", "Version:".bold(), version);
+    }
+    println!("  {} {}", "SHA-256:".bold(), package.sha256);
+    println!("  {}", "Files:".bold());
+    for file in integrity.files.keys() {
+        println!("    • {}", file);
+    }
+    println!();
+    println!(
+        "{} Install with: {} install {}",
+        "→".cyan(),
+        "skill".cyan(),
+        package.path.display()
+    );
+    println!(
+        "  or publish it and pin the checksum: {} install https://…/{}#sha256={}",
+        "skill".cyan(),
+        package
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy())
+            .unwrap_or_default(),
+        package.sha256
+    );
+
+    Ok(())
+}
@@ -1,9 +1,15 @@
+use crate::auth::providers::kubernetes::KubernetesTarget;
 use anyhow::{Context, Result};
 use colored::*;
+use skill_runtime::dependencies::installed_dependencies;
 use skill_runtime::{
-    instance::ConfigValue, parse_git_url, DockerRuntime, GitSkillLoader, InstanceManager,
-    LocalSkillLoader, SkillEngine, SkillExecutor, SkillManifest, SkillRuntime,
+    build_native_command, documented_params, find_skill_md, instance::ConfigValue, is_git_url,
+    parse_git_url, parse_skill_md, validate_args, ArtifactRetention, ArtifactStore,
+    CommandAllowlist, DockerRuntime, GitSkillLoader, InstanceManager, LocalSkillLoader,
+    NativeCommand, NativeSecurityPolicy, ResourceUsage, SkillEngine, SkillExecutor, SkillManifest,
+    SkillRuntime, StdinSource, OUTPUT_DIR_ENV,
 };
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Instant;
@@ -61,15 +67,134 @@ fn parse_cli_args(args: &[String]) -> Vec<(String, String)> {
     parsed
 }
 
+/// Reject arguments that don't match the tool's parameters documented in the
+/// skill's SKILL.md, before anything runs
+fn check_args(skill_dir: &Path, tool_name: &str, args: &[String]) -> Result<()> {
+    let params = documented_params(skill_dir, tool_name);
+    let args: HashMap<String, serde_json::Value> = parse_cli_args(args)
+        .into_iter()
+        .map(|(key, value)| (key, serde_json::Value::String(value)))
+        .collect();
+    validate_args(tool_name, &params, &args)?;
+    Ok(())
+}
+
+/// Stdin for the tool: `--stdin-file` contents, or this process's stdin when
+/// data is piped in (`cat deploy.yaml | skill run kubernetes apply -f -`)
+pub fn stdin_source(stdin_file: Option<&Path>) -> Result<StdinSource> {
+    use std::io::{IsTerminal, Read};
+
+    match stdin_file {
+        Some(path) if path == Path::new("-") => {
+            let mut data = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut data)
+                .context("Failed to read stdin")?;
+            Ok(StdinSource::Data(data))
+        }
+        Some(path) => std::fs::read(path)
+            .map(StdinSource::Data)
+            .with_context(|| format!("Failed to read stdin file {}", path.display())),
+        None if std::io::stdin().is_terminal() => Ok(StdinSource::Null),
+        None => Ok(StdinSource::Inherit),
+    }
+}
+
+/// Feed `--stdin-file` data to a WASM skill; piped stdin is inherited by the
+/// sandbox already
+pub(crate) fn attach_stdin(executor: SkillExecutor, stdin: &StdinSource) -> SkillExecutor {
+    match stdin {
+        StdinSource::Data(data) => executor.with_stdin(data.clone()),
+        StdinSource::Null | StdinSource::Inherit => executor,
+    }
+}
+
+/// Capture of the files a manifest skill declares in `outputs`
+struct ArtifactCapture {
+    store: ArtifactStore,
+    execution_id: String,
+    output_dir: PathBuf,
+    patterns: Vec<String>,
+    retention: ArtifactRetention,
+}
+
+impl ArtifactCapture {
+    /// Create the execution's output directory if the skill declares outputs
+    fn prepare(
+        manifest: &SkillManifest,
+        resolved: &skill_runtime::ResolvedInstance,
+    ) -> Result<Option<Self>> {
+        if resolved.outputs.is_empty() {
+            return Ok(None);
+        }
+        let store = ArtifactStore::new()?;
+        let execution_id = uuid::Uuid::new_v4().to_string();
+        let output_dir = store.output_dir(&execution_id)?;
+        Ok(Some(Self {
+            store,
+            execution_id,
+            output_dir,
+            patterns: resolved.outputs.clone(),
+            retention: manifest.artifacts.clone(),
+        }))
+    }
+
+    /// Keep the matching files and report where they were saved
+    fn finish(self) -> Result<()> {
+        let artifacts = self
+            .store
+            .capture(&self.execution_id, &self.patterns)
+            .context("Failed to capture artifacts")?;
+        if let Err(e) = self.store.prune(&self.retention) {
+            tracing::warn!(error = %e, "Failed to prune old artifacts");
+        }
+        if artifacts.is_empty() {
+            return Ok(());
+        }
+
+        println!(
+            "{} Saved {} artifact(s) to {}",
+            "→".cyan(),
+            artifacts.len(),
+            self.store
+                .root()
+                .join(&self.execution_id)
+                .display()
+                .to_string()
+                .dimmed()
+        );
+        for artifact in &artifacts {
+            println!("  {} ({} bytes)", artifact.name.yellow(), artifact.size);
+        }
+        Ok(())
+    }
+}
+
+/// Apply the `--jq` filter to successful output before it's printed
+fn filter_output(output: &str, jq: Option<&str>) -> Result<String> {
+    match jq {
+        Some(filter) => skill_runtime::apply_jq(output, filter)
+            .with_context(|| format!("Failed to apply jq filter '{}'", filter)),
+        None => Ok(output.to_string()),
+    }
+}
+
 pub async fn execute(
     skill_spec: &str,
     tool: Option<&str>,
     config_overrides: &[(String, String)],
     args: &[String],
+    jq: Option<&str>,
+    stdin: &StdinSource,
     manifest: Option<&SkillManifest>,
 ) -> Result<()> {
     let start = Instant::now();
 
+    // Reject a bad filter before running anything
+    if let Some(filter) = jq {
+        skill_runtime::validate_jq(filter)?;
+    }
+
     // Check if skill_spec is a local path (starts with ./ or / or ~)
     let is_local_path = skill_spec.starts_with("./")
         || skill_spec.starts_with("../")
@@ -78,13 +203,15 @@ pub async fn execute(
 
     if is_local_path {
         // Local skill execution
-        return execute_local_skill(skill_spec, tool, config_overrides, args, start).await;
+        return execute_local_skill(skill_spec, tool, config_overrides, args, jq, stdin, start)
+            .await;
     }
 
     // Check if skill_spec is a Git URL (ephemeral execution without install)
     // Supports: github:user/repo:tool, https://github.com/user/repo:tool
-    if is_git_url_spec(skill_spec) {
-        return execute_git_skill(skill_spec, tool, config_overrides, args, start).await;
+    if is_git_url(skill_spec) {
+        return execute_git_skill(skill_spec, tool, config_overrides, args, jq, stdin, start)
+            .await;
     }
 
     // Parse skill[@instance]:tool or skill[@instance] tool
@@ -100,6 +227,8 @@ pub async fn execute(
                 &tool_name,
                 config_overrides,
                 args,
+                jq,
+                stdin,
                 start,
             )
             .await;
@@ -129,6 +258,14 @@ pub async fn execute(
         );
     }
 
+    // Every skill this one depends on must be installed
+    skill_runtime::resolve_dependencies(&skill_name, installed_dependencies)
+        .context("Unsatisfied skill dependencies")?;
+
+    if let Some(skill_dir) = skill_path.parent() {
+        check_args(skill_dir, &tool_name, args)?;
+    }
+
     // Load instance configuration
     let instance_manager = InstanceManager::new()?;
     let mut instance_config = instance_manager
@@ -139,6 +276,7 @@ pub async fn execute(
                 instance_name, skill_name, skill_name, instance_name
             )
         })?;
+    crate::auth::resolve_instance_credentials(&mut instance_config).await?;
 
     // Apply config overrides from command line
     if !config_overrides.is_empty() {
@@ -170,6 +308,7 @@ pub async fn execute(
     )
     .await
     .context("Failed to load skill")?;
+    let executor = attach_stdin(executor, stdin);
 
     // Parse arguments (supports key=value, --key value, --key=value, --flag, -k value, -k)
     let parsed_args = parse_cli_args(args);
@@ -181,12 +320,46 @@ pub async fn execute(
         .await
         .context("Tool execution failed")?;
 
+    report_result(result.success, &result.output, result.error_message, jq, start)
+}
+
+/// A running daemon that would run a skill from `manifest` the way this
+/// process would, with the tool's stdin as the daemon takes it
+///
+/// The daemon resolves skills from the manifest, working directory and
+/// environment it started with, so any difference means running in-process.
+#[cfg(unix)]
+async fn matching_daemon(
+    manifest: &SkillManifest,
+    stdin: &StdinSource,
+) -> Option<(skill_http::DaemonClient, Option<String>)> {
+    let daemon_stdin = match stdin {
+        StdinSource::Null => None,
+        StdinSource::Data(data) => Some(String::from_utf8(data.clone()).ok()?),
+        StdinSource::Inherit => return None,
+    };
+    let (daemon, status) = skill_http::DaemonClient::connect().await?;
+    if !status.matches(Some(manifest)) {
+        tracing::debug!("Daemon setup differs from this process; running in-process");
+        return None;
+    }
+    Some((daemon, daemon_stdin))
+}
+
+/// Print a skill's result, exiting with 1 if the tool failed
+fn report_result(
+    success: bool,
+    output: &str,
+    error: Option<String>,
+    jq: Option<&str>,
+    start: Instant,
+) -> Result<()> {
     let duration = start.elapsed();
 
     println!();
-    if result.success {
+    if success {
         println!("{}", "─".repeat(60).dimmed());
-        println!("{}", result.output);
+        println!("{}", filter_output(output, jq)?);
         println!("{}", "─".repeat(60).dimmed());
         println!();
         println!(
@@ -196,7 +369,7 @@ pub async fn execute(
         );
     } else {
         println!("{} Tool execution failed", "✗".red().bold());
-        if let Some(error) = result.error_message {
+        if let Some(error) = error {
             println!();
             println!("{} {}", "Error:".red().bold(), error);
         }
@@ -208,6 +381,7 @@ pub async fn execute(
 }
 
 /// Execute a skill from manifest definition
+#[allow(clippy::too_many_arguments)]
 async fn execute_manifest_skill(
     manifest: &SkillManifest,
     skill_name: &str,
@@ -215,12 +389,57 @@ async fn execute_manifest_skill(
     tool_name: &str,
     config_overrides: &[(String, String)],
     args: &[String],
+    jq: Option<&str>,
+    stdin: &StdinSource,
     start: Instant,
 ) -> Result<()> {
     // Resolve instance from manifest
-    let resolved = manifest
+    let mut resolved = manifest
         .resolve_instance(skill_name, instance_name)
         .context("Failed to resolve skill from manifest")?;
+    crate::auth::resolve_instance_credentials(&mut resolved.config).await?;
+
+    // Dependencies must be defined in the manifest or installed
+    manifest
+        .resolve_dependencies(skill_name)
+        .context("Unsatisfied skill dependencies")?;
+
+    check_args(Path::new(&resolved.source), tool_name, args)?;
+
+    // A running daemon has the skill compiled already; piped stdin, config
+    // overrides and declared outputs only work in-process
+    #[cfg(unix)]
+    if config_overrides.is_empty() && resolved.outputs.is_empty() {
+        if let Some((daemon, daemon_stdin)) = matching_daemon(manifest, stdin).await {
+            let request = skill_http::ExecutionRequest {
+                skill: skill_name.to_string(),
+                tool: tool_name.to_string(),
+                instance: Some(resolved.instance_name.clone()),
+                args: parse_cli_args(args)
+                    .into_iter()
+                    .map(|(key, value)| (key, serde_json::Value::String(value)))
+                    .collect(),
+                stdin: daemon_stdin,
+                no_cache: true,
+                ..Default::default()
+            };
+            println!();
+            let response = daemon
+                .execute(&request)
+                .await
+                .context("Tool execution failed")?;
+            return report_result(
+                response.status == skill_http::ExecutionStatus::Success,
+                &response.output,
+                response.error,
+                jq,
+                start,
+            );
+        }
+    }
+
+    // Scratch directory for declared output files
+    let artifacts = ArtifactCapture::prepare(manifest, &resolved)?;
 
     // Display runtime type
     let runtime_str = match resolved.runtime {
@@ -245,16 +464,27 @@ async fn execute_manifest_skill(
 
     // Handle Docker runtime separately (before moving config)
     if resolved.runtime == SkillRuntime::Docker {
-        return execute_docker_skill(&resolved, tool_name, args, start).await;
+        return execute_docker_skill(&resolved, tool_name, args, jq, stdin, artifacts, start)
+            .await;
     }
 
+    // Native commands are checked against [security] allowed-commands,
+    // narrowed by the skill's SKILL.md allowed-tools
+    let allowlist = skill_command_allowlist(manifest, &resolved)?;
+
+    // Kubernetes context pinned for this instance, applied to kubectl/helm
+    let kube_target = KubernetesTarget::from_config(&resolved.config);
+
     // Handle Native runtime - execute CLI commands directly
     if resolved.runtime == SkillRuntime::Native {
-        return execute_native_manifest_skill(&resolved, tool_name, args, start).await;
+        return execute_native_manifest_skill(
+            &resolved, &allowlist, tool_name, args, jq, stdin, artifacts, start,
+        )
+        .await;
     }
 
     // Apply config overrides
-    let mut instance_config = resolved.config;
+    let mut instance_config = resolved.config.clone();
     if !config_overrides.is_empty() {
         println!(
             "{} Applying {} config override(s)",
@@ -302,10 +532,10 @@ async fn execute_manifest_skill(
         )
         .await
         .context("Failed to create executor for local skill")?
-    } else if is_git_url_spec(&resolved.source) {
+    } else if is_git_url(&resolved.source) {
         // Git source - clone and build
-        let loader = GitSkillLoader::new()?;
-        let git_source = parse_git_url(&resolved.source)?;
+        let loader = GitSkillLoader::new()?.with_config(resolved.git.clone());
+        let git_source = resolved.git_source()?;
 
         println!("{} Fetching from Git...", "→".dimmed());
         let cloned = loader.clone_skill(&git_source, false).await?;
@@ -348,6 +578,11 @@ async fn execute_manifest_skill(
         .await
         .context("Failed to load installed skill from manifest")?
     };
+    let executor = match &artifacts {
+        Some(capture) => executor.with_output_dir(capture.output_dir.clone()),
+        None => executor,
+    };
+    let executor = attach_stdin(executor.with_limits(resolved.limits.clone()), stdin);
 
     // Parse arguments (supports key=value, --key value, --key=value, --flag, -k value, -k)
     let parsed_args = parse_cli_args(args);
@@ -365,17 +600,30 @@ async fn execute_manifest_skill(
     // Check if the result contains a command that should be executed natively
     let final_result = if result.success && result.output.starts_with("Command: ") {
         // Extract and execute the kubectl command natively
-        execute_native_command(&result.output, start).await?
+        execute_native_command(
+            &result.output,
+            &allowlist,
+            &resolved.native,
+            &kube_target,
+            stdin,
+            resolved.limits.max_output_bytes(),
+            start,
+        )
+        .await?
     } else {
         result
     };
 
+    if let Some(artifacts) = artifacts {
+        artifacts.finish()?;
+    }
+
     let duration = start.elapsed();
 
     println!();
     if final_result.success {
         println!("{}", "─".repeat(60).dimmed());
-        println!("{}", final_result.output);
+        println!("{}", filter_output(&final_result.output, jq)?);
         println!("{}", "─".repeat(60).dimmed());
         println!();
         println!(
@@ -389,98 +637,70 @@ async fn execute_manifest_skill(
             println!();
             println!("{} {}", "Error:".red().bold(), error);
         }
+        if let Some(code) = final_result.exit_code {
+            println!("{} {}", "Exit code:".dimmed(), code);
+        }
         std::process::exit(1);
     }
 
     Ok(())
 }
 
+/// Command allowlist for a manifest skill, including its SKILL.md `allowed-tools`
+fn skill_command_allowlist(
+    manifest: &SkillManifest,
+    resolved: &skill_runtime::ResolvedInstance,
+) -> Result<CommandAllowlist> {
+    let allowed_tools = find_skill_md(Path::new(&resolved.source))
+        .and_then(|path| parse_skill_md(&path).ok())
+        .and_then(|skill_md| skill_md.frontmatter.allowed_tools);
+
+    manifest
+        .command_allowlist()
+        .for_skill(&resolved.skill_name, allowed_tools.as_deref())
+}
+
 /// Execute a native command from skill output
 async fn execute_native_command(
     output: &str,
+    allowlist: &CommandAllowlist,
+    policy: &NativeSecurityPolicy,
+    kube_target: &KubernetesTarget,
+    stdin: &StdinSource,
+    max_output: Option<u64>,
     _start: Instant,
 ) -> Result<skill_runtime::ExecutionResult> {
-    use std::process::Stdio;
-    use tokio::process::Command;
-
     // Extract the command from "Command: kubectl ..."
     let first_line = output.lines().next().unwrap_or("");
     let command_str = first_line.strip_prefix("Command: ").unwrap_or(first_line);
 
-    // Parse the command
-    let parts: Vec<&str> = command_str.split_whitespace().collect();
-    if parts.is_empty() {
-        return Ok(skill_runtime::ExecutionResult {
-            success: false,
-            output: String::new(),
-            error_message: Some("Empty command".to_string()),
-            metadata: None,
-        });
-    }
-
-    let program = parts[0];
-    let args = &parts[1..];
-
-    // Security check: Only allow specific commands
-    let allowed_commands = ["kubectl", "helm", "git", "curl", "jq", "aws", "gcloud", "az", "docker", "terraform"];
-    if !allowed_commands.contains(&program) {
-        return Ok(skill_runtime::ExecutionResult {
-            success: false,
-            output: String::new(),
-            error_message: Some(format!(
-                "Command '{}' not allowed. Allowed: {}",
-                program,
-                allowed_commands.join(", ")
-            )),
-            metadata: None,
-        });
-    }
+    // Parse the command into argv (quotes are honoured, nothing is expanded)
+    let native = match NativeCommand::parse(command_str)
+        .and_then(|native| allowlist.check(native.program()).map(|_| native))
+    {
+        Ok(native) => native,
+        Err(e) => return Ok(skill_runtime::ExecutionResult::failure(e.to_string())),
+    };
 
-    println!("{} Executing: {}", "→".cyan(), command_str.yellow());
+    println!("{} Executing: {}", "→".cyan(), native.to_string().yellow());
 
     // Execute the command
-    let result = Command::new(program)
-        .args(args)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .await;
+    let mut command = native.to_command();
+    if let Err(e) = kube_target.apply(native.program(), &mut command) {
+        return Ok(skill_runtime::ExecutionResult::failure(format!("{:#}", e)));
+    }
+    policy.apply(&mut command)?;
+    let started = Instant::now();
+    let (result, usage) = ResourceUsage::measure(stdin.output(&mut command, max_output)).await;
 
     match result {
-        Ok(output) => {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-            if output.status.success() {
-                Ok(skill_runtime::ExecutionResult {
-                    success: true,
-                    output: stdout,
-                    error_message: if stderr.is_empty() {
-                        None
-                    } else {
-                        Some(stderr)
-                    },
-                    metadata: None,
-                })
-            } else {
-                Ok(skill_runtime::ExecutionResult {
-                    success: false,
-                    output: stdout,
-                    error_message: Some(if stderr.is_empty() {
-                        format!("Command exited with status: {}", output.status)
-                    } else {
-                        stderr
-                    }),
-                    metadata: None,
-                })
-            }
-        }
-        Err(e) => Ok(skill_runtime::ExecutionResult {
-            success: false,
-            output: String::new(),
-            error_message: Some(format!("Failed to execute command: {}", e)),
-            metadata: None,
-        }),
+        Ok(output) => Ok(skill_runtime::ExecutionResult::from_process_output(&output)
+            .with_duration(started.elapsed())
+            .with_resource_usage(usage)),
+        Err(e) => Ok(skill_runtime::ExecutionResult::failure(format!(
+            "Failed to execute command: {}",
+            e
+        ))),
     }
 }
 
@@ -489,12 +709,19 @@ async fn execute_docker_skill(
     resolved: &skill_runtime::ResolvedInstance,
     tool_name: &str,
     args: &[String],
+    jq: Option<&str>,
+    stdin: &StdinSource,
+    artifacts: Option<ArtifactCapture>,
     start: Instant,
 ) -> Result<()> {
     let docker_config = resolved
         .docker
         .as_ref()
         .ok_or_else(|| anyhow::anyhow!("Docker runtime requires docker configuration in manifest"))?;
+    let docker_config = match &artifacts {
+        Some(capture) => docker_config.clone().with_output_dir(&capture.output_dir),
+        None => docker_config.clone(),
+    };
 
     println!(
         "{} Docker image: {}",
@@ -510,7 +737,7 @@ async fn execute_docker_skill(
         );
     }
 
-    let runtime = DockerRuntime::new();
+    let runtime = DockerRuntime::new().with_max_output(resolved.limits.max_output_bytes());
 
     // Ensure image exists (pull if needed)
     println!("{} Ensuring Docker image is available...", "→".dimmed());
@@ -518,6 +745,11 @@ async fn execute_docker_skill(
         .ensure_image(&docker_config.image)
         .context("Failed to ensure Docker image")?;
 
+    if !docker_config.sidecars.is_empty() {
+        let names: Vec<&str> = docker_config.sidecars.iter().map(|s| s.name.as_str()).collect();
+        println!("{} Starting sidecars: {}", "→".dimmed(), names.join(", ").cyan());
+    }
+
     // Build tool arguments
     // Format: tool_name followed by args in key=value format
     let mut tool_args = vec![tool_name.to_string()];
@@ -532,15 +764,19 @@ async fn execute_docker_skill(
 
     // Execute in Docker container
     let output = runtime
-        .execute(docker_config, &tool_args)
+        .execute_with_stdin(&docker_config, &tool_args, stdin)
         .context("Failed to execute Docker container")?;
 
+    if let Some(artifacts) = artifacts {
+        artifacts.finish()?;
+    }
+
     let duration = start.elapsed();
 
     println!();
     if output.success {
         println!("{}", "─".repeat(60).dimmed());
-        println!("{}", output.stdout);
+        println!("{}", filter_output(&output.stdout, jq)?);
         if !output.stderr.is_empty() {
             eprintln!("{}", output.stderr.dimmed());
         }
@@ -573,63 +809,65 @@ async fn execute_docker_skill(
 }
 
 /// Execute a native skill (CLI commands like kubectl, docker, git, terraform)
+#[allow(clippy::too_many_arguments)]
 async fn execute_native_manifest_skill(
     resolved: &skill_runtime::ResolvedInstance,
+    allowlist: &CommandAllowlist,
     tool_name: &str,
     args: &[String],
+    jq: Option<&str>,
+    stdin: &StdinSource,
+    artifacts: Option<ArtifactCapture>,
     start: Instant,
 ) -> Result<()> {
-    use std::process::Stdio;
-    use tokio::process::Command;
-
     let skill_name = &resolved.skill_name;
 
     // Parse arguments (supports key=value, --key value, --key=value, --flag, -k value, -k)
     let parsed_args = parse_cli_args(args);
 
-    // Build the native command
-    let command_str = build_native_command(skill_name, tool_name, &parsed_args)?;
+    // Build the native command as an argv array (no shell involved)
+    let native = build_native_command(skill_name, tool_name, &parsed_args)?;
 
     println!(
         "{} Executing: {}",
         "→".cyan(),
-        command_str.yellow()
+        native.to_string().yellow()
     );
     println!();
 
-    // Parse the command
-    let parts: Vec<&str> = command_str.split_whitespace().collect();
-    if parts.is_empty() {
-        anyhow::bail!("Empty command generated");
-    }
+    let program = native.program();
 
-    let program = parts[0];
-    let cmd_args = &parts[1..];
+    // Security check: Only allow configured commands
+    allowlist.check(program)?;
 
-    // Security check: Only allow specific commands
-    let allowed_commands = ["kubectl", "helm", "git", "curl", "jq", "aws", "gcloud", "az", "docker", "terraform", "psql"];
-    if !allowed_commands.contains(&program) {
-        anyhow::bail!(
-            "Command '{}' not allowed. Allowed: {}",
-            program,
-            allowed_commands.join(", ")
-        );
+    // Execute the command with the skill's hardening policy, against the
+    // instance's pinned Kubernetes context (if any)
+    let mut command = native.to_command();
+    KubernetesTarget::from_config(&resolved.config).apply(program, &mut command)?;
+    if let Some(capture) = &artifacts {
+        command.env(OUTPUT_DIR_ENV, &capture.output_dir);
     }
-
-    // Execute the command
-    let result = Command::new(program)
-        .args(cmd_args)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
+    resolved
+        .native
+        .apply(&mut command)
+        .context("Failed to apply native security policy")?;
+    let result = stdin
+        .output(&mut command, resolved.limits.max_output_bytes())
         .await;
 
+    if let Some(artifacts) = artifacts {
+        artifacts.finish()?;
+    }
+
     let duration = start.elapsed();
 
     match result {
         Ok(output) => {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+            let mut stdout = String::from_utf8_lossy(&output.stdout).to_string();
             let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+            if output.status.success() {
+                stdout = filter_output(&stdout, jq)?;
+            }
 
             println!("{}", "─".repeat(60).dimmed());
             if !stdout.is_empty() {
@@ -668,71 +906,14 @@ async fn execute_native_manifest_skill(
     Ok(())
 }
 
-/// Build a native command from skill name, tool name, and arguments
-/// Uses generic passthrough: base_command + tool_name + args
-/// Args convention:
-///   - "arg" key or empty key: positional argument (value only)
-///   - single char key: short flag (-k value)
-///   - multi char key: long flag (--key value)
-///   - value "true" with flag key: boolean flag (--key or -k without value)
-fn build_native_command(
-    skill_name: &str,
-    tool_name: &str,
-    args: &[(String, String)],
-) -> Result<String> {
-    // Map skill name to base CLI command
-    let base_command = match skill_name {
-        "kubernetes" => "kubectl",
-        "aws" => "aws",
-        "docker" => "docker",
-        "terraform" => "terraform",
-        "helm" => "helm",
-        "git" => "git",
-        "postgres-native" => "psql",
-        _ => skill_name,
-    };
-
-    let mut cmd_parts = vec![base_command.to_string()];
-
-    // Add tool name as subcommand
-    cmd_parts.push(tool_name.to_string());
-
-    // Process arguments generically
-    for (key, value) in args {
-        if key == "arg" || key == "resource" || key.is_empty() {
-            // Positional argument - just add the value
-            // Note: "resource" is special-cased for kubectl which expects resource type as positional
-            cmd_parts.push(value.clone());
-        } else if value == "true" {
-            // Boolean flag
-            if key.len() == 1 {
-                cmd_parts.push(format!("-{}", key));
-            } else {
-                cmd_parts.push(format!("--{}", key));
-            }
-        } else if value == "false" {
-            // Skip false boolean flags
-            continue;
-        } else if key.len() == 1 {
-            // Short flag: -n value
-            cmd_parts.push(format!("-{}", key));
-            cmd_parts.push(value.clone());
-        } else {
-            // Long flag: --namespace value
-            cmd_parts.push(format!("--{}", key));
-            cmd_parts.push(value.clone());
-        }
-    }
-
-    Ok(cmd_parts.join(" "))
-}
-
 /// Execute a skill from a local path (directory or file)
 async fn execute_local_skill(
     path: &str,
     tool: Option<&str>,
     config_overrides: &[(String, String)],
     args: &[String],
+    jq: Option<&str>,
+    stdin: &StdinSource,
     start: Instant,
 ) -> Result<()> {
     let tool_name = tool.ok_or_else(|| anyhow::anyhow!("Tool name required for local skills"))?;
@@ -753,6 +934,10 @@ async fn execute_local_skill(
     );
     println!();
 
+    if expanded_path.is_dir() {
+        check_args(&expanded_path, tool_name, args)?;
+    }
+
     // Create engine and loader
     let engine = Arc::new(SkillEngine::new().context("Failed to create skill engine")?);
     let loader = LocalSkillLoader::new()?;
@@ -807,6 +992,7 @@ async fn execute_local_skill(
     )
     .await
     .context("Failed to create skill executor")?;
+    let executor = attach_stdin(executor, stdin);
 
     // Parse arguments (supports key=value, --key value, --key=value, --flag, -k value, -k)
     let parsed_args = parse_cli_args(args);
@@ -828,7 +1014,7 @@ async fn execute_local_skill(
     println!();
     if result.success {
         println!("{}", "─".repeat(60).dimmed());
-        println!("{}", result.output);
+        println!("{}", filter_output(&result.output, jq)?);
         println!("{}", "─".repeat(60).dimmed());
         println!();
         println!(
@@ -889,18 +1075,6 @@ fn parse_skill_spec(
     }
 }
 
-/// Check if a skill spec looks like a Git URL (for ephemeral execution)
-/// Handles: github:user/repo:tool, github:user/repo tool, https://github.com/user/repo:tool
-fn is_git_url_spec(spec: &str) -> bool {
-    // Check for Git URL prefixes
-    spec.starts_with("github:")
-        || spec.starts_with("gitlab:")
-        || spec.starts_with("bitbucket:")
-        || spec.starts_with("git@")
-        || spec.starts_with("https://github.com")
-        || spec.starts_with("https://gitlab.com")
-}
-
 /// Find the WASM file in a path (handles both files and directories)
 fn find_wasm_in_path(path: &Path) -> Result<PathBuf> {
     // If it's a direct wasm file, return it
@@ -931,6 +1105,8 @@ async fn execute_git_skill(
     tool: Option<&str>,
     config_overrides: &[(String, String)],
     args: &[String],
+    jq: Option<&str>,
+    stdin: &StdinSource,
     start: Instant,
 ) -> Result<()> {
     // Parse: github:user/repo:tool_name or github:user/repo[@ref]:tool_name
@@ -958,6 +1134,8 @@ async fn execute_git_skill(
         format!("{}", cloned.skill_type).cyan()
     );
 
+    check_args(&cloned.local_path, &tool_name, args)?;
+
     println!("{} Building...", "→".dimmed());
     let wasm_path = loader.build_skill(&cloned).await?;
 
@@ -995,6 +1173,7 @@ async fn execute_git_skill(
     )
     .await
     .context("Failed to load skill")?;
+    let executor = attach_stdin(executor, stdin);
 
     // Parse arguments (supports key=value, --key value, --key=value, --flag, -k value, -k)
     let parsed_args = parse_cli_args(args);
@@ -1016,7 +1195,7 @@ async fn execute_git_skill(
     println!();
     if result.success {
         println!("{}", "─".repeat(60).dimmed());
-        println!("{}", result.output);
+        println!("{}", filter_output(&result.output, jq)?);
         println!("{}", "─".repeat(60).dimmed());
         println!();
         println!(
@@ -1141,6 +1320,13 @@ mod tests {
         assert_eq!(url, "github:user/repo@v1.0.0");
         assert_eq!(tool, "hello");
 
+        // gitlab:group/subgroup/project//subdir@ref:tool
+        let (url, tool) =
+            parse_git_tool_spec("gitlab:group/sub/project//skills/aws@v1.0.0:hello", None)
+                .unwrap();
+        assert_eq!(url, "gitlab:group/sub/project//skills/aws@v1.0.0");
+        assert_eq!(tool, "hello");
+
         // With separate tool argument
         let (url, tool) = parse_git_tool_spec("github:user/repo", Some("hello")).unwrap();
         assert_eq!(url, "github:user/repo");
@@ -1149,11 +1335,11 @@ mod tests {
 
     #[test]
     fn test_is_git_url_spec() {
-        assert!(is_git_url_spec("github:user/repo"));
-        assert!(is_git_url_spec("github:user/repo:tool"));
-        assert!(is_git_url_spec("https://github.com/user/repo"));
-        assert!(!is_git_url_spec("aws:s3-list"));  // This is skill:tool, not Git
-        assert!(!is_git_url_spec("my-skill"));
+        assert!(is_git_url("github:user/repo"));
+        assert!(is_git_url("github:user/repo:tool"));
+        assert!(is_git_url("https://github.com/user/repo"));
+        assert!(!is_git_url("aws:s3-list"));  // This is skill:tool, not Git
+        assert!(!is_git_url("my-skill"));
     }
 
     #[test]
@@ -1257,7 +1443,7 @@ mod tests {
         // kubectl get pods --all-namespaces
         // Note: "resource" is special-cased as positional for kubectl compatibility
         let cmd = build_native_command("kubernetes", "get", &parsed).unwrap();
-        assert_eq!(cmd, "kubectl get pods --all-namespaces");
+        assert_eq!(cmd.to_string(), "kubectl get pods --all-namespaces");
     }
 
     #[test]
@@ -1268,6 +1454,20 @@ mod tests {
             ("all-namespaces".to_string(), "true".to_string()),
         ];
         let cmd = build_native_command("kubernetes", "get", &parsed).unwrap();
-        assert_eq!(cmd, "kubectl get pods --all-namespaces");
+        assert_eq!(cmd.to_string(), "kubectl get pods --all-namespaces");
+    }
+
+    #[test]
+    fn test_build_native_command_keeps_spaced_values() {
+        // skill run kubernetes get pods -l "app in (web, api)"
+        let args = vec![
+            "pods".to_string(),
+            "-l".to_string(),
+            "app in (web, api)".to_string(),
+        ];
+        let parsed = parse_cli_args(&args);
+        let cmd = build_native_command("kubernetes", "get", &parsed).unwrap();
+        assert_eq!(cmd.args(), &["get", "pods", "-l", "app in (web, api)"]);
+        assert_eq!(cmd.to_string(), "kubectl get pods -l 'app in (web, api)'");
     }
 }
@@ -1,6 +1,205 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use colored::*;
+use skill_runtime::search::{evaluate, EvalMetrics, EvalReport, EvalSuite};
+use skill_runtime::{SearchConfig, SearchPipeline};
+use std::path::Path;
+
+use crate::output::OutputFormat;
+
+use super::find::{get_config_path, load_all_tools, to_index_documents, ToolDocument};
 
 pub async fn execute(_: &str) -> Result<()> {
     println!("TODO: Implement command");
     Ok(())
 }
+
+/// Run a labeled query suite against one or two search configurations
+///
+/// Without `--config`, the saved configuration (~/.skill-engine/search.toml)
+/// is used as the baseline. With `--compare`, the suite is also run against
+/// a second configuration and metric deltas are reported.
+pub async fn bench(
+    suite_path: &Path,
+    k: usize,
+    config_path: Option<&Path>,
+    compare_path: Option<&Path>,
+    format: &str,
+    output: Option<OutputFormat>,
+) -> Result<()> {
+    // `--output` takes precedence over `--format`
+    let is_json = output.map_or(format == "json", |output| output != OutputFormat::Table);
+    let output = OutputFormat::structured(output);
+    let suite = EvalSuite::from_file(suite_path)?;
+
+    let tool_documents = load_all_tools().await?;
+    if tool_documents.is_empty() {
+        anyhow::bail!("No skills installed. Install skills before running a search benchmark.");
+    }
+
+    if !is_json {
+        println!();
+        println!(
+            "{} Benchmarking {} queries against {} tools (k={})",
+            "→".cyan(),
+            suite.cases.len(),
+            tool_documents.len(),
+            k
+        );
+    }
+
+    let baseline_config = match config_path {
+        Some(path) => SearchConfig::from_toml_file(path)?,
+        None => load_saved_config()?,
+    };
+    let baseline_label = config_path
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "saved config".to_string());
+
+    let mut reports = vec![
+        run_config(
+            baseline_config,
+            &tool_documents,
+            &suite,
+            k,
+            baseline_label,
+            is_json,
+        )
+        .await?,
+    ];
+
+    if let Some(path) = compare_path {
+        let config = SearchConfig::from_toml_file(path)?;
+        let label = path.display().to_string();
+        reports.push(run_config(config, &tool_documents, &suite, k, label, is_json).await?);
+    }
+
+    if is_json {
+        #[derive(serde::Serialize)]
+        struct JsonBench<'a> {
+            reports: &'a [EvalReport],
+            #[serde(skip_serializing_if = "Option::is_none")]
+            delta: Option<EvalMetrics>,
+        }
+
+        let delta = match reports.as_slice() {
+            [a, b] => Some(a.metrics.delta(&b.metrics)),
+            _ => None,
+        };
+        let bench = JsonBench {
+            reports: &reports,
+            delta,
+        };
+        if let Some(output) = output {
+            return output.print("search.bench", &bench);
+        }
+        println!("{}", serde_json::to_string_pretty(&bench)?);
+        return Ok(());
+    }
+
+    for report in &reports {
+        display_report(report);
+    }
+
+    if let [baseline, candidate] = reports.as_slice() {
+        display_comparison(baseline, candidate);
+    }
+
+    Ok(())
+}
+
+fn load_saved_config() -> Result<SearchConfig> {
+    let path = get_config_path()?;
+    if path.exists() {
+        SearchConfig::from_toml_file(&path)
+    } else {
+        Ok(SearchConfig::default())
+    }
+}
+
+async fn run_config(
+    config: SearchConfig,
+    tool_documents: &[ToolDocument],
+    suite: &EvalSuite,
+    k: usize,
+    label: String,
+    is_json: bool,
+) -> Result<EvalReport> {
+    if !is_json {
+        println!("{} Indexing with {}...", "→".cyan(), label.yellow());
+    }
+
+    let pipeline = SearchPipeline::from_config(config)
+        .await
+        .with_context(|| format!("Failed to initialize search pipeline for {}", label))?;
+    pipeline
+        .index_documents(to_index_documents(tool_documents))
+        .await
+        .context("Failed to index tools")?;
+
+    evaluate(&pipeline, suite, k, label).await
+}
+
+fn display_report(report: &EvalReport) {
+    let m = &report.metrics;
+
+    println!();
+    println!("{}", "━".repeat(80).dimmed());
+    println!("{} {}", "Configuration:".bold(), report.label.cyan());
+    println!();
+    println!("   Recall@{:<3} {:.3}", report.k, m.recall);
+    println!("   MRR        {:.3}", m.mrr);
+    println!("   NDCG@{:<5} {:.3}", report.k, m.ndcg);
+    println!("   Latency    {:.1} ms/query", m.mean_latency_ms);
+
+    let misses: Vec<_> = report.misses().collect();
+    if !misses.is_empty() {
+        println!();
+        println!("   {} {} queries missed:", "!".yellow(), misses.len());
+        for miss in misses {
+            println!(
+                "      • {} {}",
+                miss.query,
+                format!(
+                    "(expected {}, got {})",
+                    miss.expected.join(", "),
+                    miss.returned.join(", ")
+                )
+                .dimmed()
+            );
+        }
+    }
+}
+
+fn display_comparison(baseline: &EvalReport, candidate: &EvalReport) {
+    let delta = baseline.metrics.delta(&candidate.metrics);
+
+    // Higher is better for quality metrics, lower is better for latency
+    let fmt = |value: f64, higher_is_better: bool, precision: usize| {
+        let text = format!("{:+.*}", precision, value);
+        if value == 0.0 {
+            text.dimmed()
+        } else if (value > 0.0) == higher_is_better {
+            text.green()
+        } else {
+            text.red()
+        }
+    };
+
+    println!();
+    println!("{}", "━".repeat(80).dimmed());
+    println!(
+        "{} {} → {}",
+        "Comparison:".bold(),
+        baseline.label.cyan(),
+        candidate.label.cyan()
+    );
+    println!();
+    println!("   Recall@{:<3} {}", baseline.k, fmt(delta.recall, true, 3));
+    println!("   MRR        {}", fmt(delta.mrr, true, 3));
+    println!("   NDCG@{:<5} {}", baseline.k, fmt(delta.ndcg, true, 3));
+    println!(
+        "   Latency    {} ms/query",
+        fmt(delta.mean_latency_ms, false, 1)
+    );
+    println!();
+}
@@ -1,41 +1,53 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use colored::*;
-use skill_mcp::McpServer;
-use skill_runtime::SkillManifest;
+use skill_http::{HttpServer, HttpServerConfig, ShutdownController, TlsConfig, DEFAULT_DRAIN_TIMEOUT};
+use skill_mcp::{A2aGateway, McpServer};
+use skill_runtime::{SkillEngine, SkillManifest};
 use std::process::{Child, Command};
+use std::sync::Arc;
+
+/// What `skill serve` serves; everything over HTTP shares one port
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Transports {
+    /// MCP on stdio, next to the HTTP transports
+    pub stdio: bool,
+    /// MCP streamable HTTP at `/mcp`
+    pub http: bool,
+    /// A2A gateway at `/a2a`
+    pub a2a: bool,
+    /// REST API and web UI
+    pub api: bool,
+}
+
+impl Transports {
+    /// Whether anything is served over HTTP
+    fn listens(&self) -> bool {
+        self.http || self.a2a || self.api
+    }
+}
+
+pub async fn execute(
+    skill: Option<&str>,
+    host: &str,
+    port: u16,
+    transports: Transports,
+    with_web: bool,
+    tls: &super::web::TlsArgs,
+    manifest: Option<SkillManifest>,
+) -> Result<()> {
+    let tls = tls.config()?;
+    if tls.is_some() && !transports.listens() {
+        bail!("TLS options only apply to the HTTP transports; add --http, --a2a or --api");
+    }
 
-pub async fn execute(skill: Option<&str>, host: &str, port: u16, http: bool, with_web: bool) -> Result<()> {
     // Start trunk serve if --with-web flag is set
     let mut trunk_process: Option<Child> = None;
     if with_web {
         trunk_process = start_trunk_serve()?;
     }
-    // Load manifest if available
-    let manifest = load_manifest_for_serve()?;
-
-    if http {
-        // HTTP streaming mode
-        println!("{} Starting Skill Engine MCP Server (HTTP Streaming)...", "🚀".green());
-        println!();
-        println!("Server will be available at: {}", format!("http://{}:{}/mcp", host, port).cyan());
-        println!();
-        println!("This endpoint supports:");
-        println!("  • Server-Sent Events (SSE) for real-time streaming");
-        println!("  • Session-based connections for stateful interactions");
-        println!("  • Standard MCP protocol over HTTP");
-        println!();
-
-        if let Some(ref m) = manifest {
-            println!("{} Loaded manifest with {} skills", "✓".green(), m.skill_names().len());
-        } else {
-            println!("{} No manifest found, using installed skills only", "ℹ".blue());
-        }
 
-        println!();
-        println!("{} MCP HTTP server starting...", "✓".green());
-
-        // Run HTTP server
-        McpServer::run_http(host, port, manifest).await?;
+    if transports.listens() {
+        serve_http(host, port, transports, tls, manifest).await?;
     } else {
         // Stdio mode (default for Claude Code)
         if skill.is_some() {
@@ -84,26 +96,113 @@ pub async fn execute(skill: Option<&str>, host: &str, port: u16, http: bool, wit
     Ok(())
 }
 
-/// Load manifest from current directory or parent directories
-fn load_manifest_for_serve() -> Result<Option<SkillManifest>> {
-    let cwd = std::env::current_dir()?;
-
-    // Check common manifest locations
-    let candidates = vec![
-        cwd.join(".skill-engine.toml"),
-        cwd.join("skill-engine.toml"),
-    ];
-
-    for path in candidates {
-        if path.exists() {
-            let content = std::fs::read_to_string(&path)?;
-            let mut manifest: SkillManifest = toml::from_str(&content)?;
-            manifest.base_dir = path.parent().unwrap_or(&cwd).to_path_buf();
-            return Ok(Some(manifest));
-        }
+/// Serve the HTTP transports on one port, and MCP on stdio if asked, all
+/// running skills on one engine
+async fn serve_http(
+    host: &str,
+    port: u16,
+    transports: Transports,
+    tls: Option<TlsConfig>,
+    manifest: Option<SkillManifest>,
+) -> Result<()> {
+    // With --stdio, stdout carries MCP messages, so status goes to stderr
+    let scheme = if tls.is_some() { "https" } else { "http" };
+    let base = format!("{}://{}:{}", scheme, host, port);
+    eprintln!("{} Starting Skill Engine server...", "🚀".green());
+    eprintln!();
+    if transports.stdio {
+        eprintln!("  MCP (stdio): this process's stdin/stdout");
     }
+    if transports.http {
+        eprintln!("  MCP (HTTP):  {}", format!("{}/mcp", base).cyan());
+    }
+    if transports.a2a {
+        eprintln!("  A2A card:    {}", format!("{}/.well-known/agent.json", base).cyan());
+        eprintln!("  A2A:         {}", format!("{}/a2a", base).cyan());
+    }
+    if transports.api {
+        eprintln!("  REST API:    {}", format!("{}/api/...", base).cyan());
+        eprintln!("  Web UI:      {}", format!("{}/", base).cyan());
+    }
+    eprintln!();
 
-    Ok(None)
+    if let Some(ref m) = manifest {
+        eprintln!("{} Loaded manifest with {} skills", "✓".green(), m.skill_names().len());
+    } else {
+        eprintln!("{} No manifest found, using installed skills only", "ℹ".blue());
+    }
+
+    // The REST API brings its own state; MCP runs skills on its engine
+    let rest = if transports.api {
+        let config = HttpServerConfig {
+            host: host.to_string(),
+            port,
+            enable_web_ui: true,
+            tls: tls.clone(),
+            ..Default::default()
+        };
+        Some(HttpServer::with_config(config)?.start().await?)
+    } else {
+        None
+    };
+    let engine = match &rest {
+        Some(rest) => rest.state.engine.clone(),
+        None => Arc::new(SkillEngine::new()?),
+    };
+    let sessions = Arc::new(McpServer::with_engine(engine, manifest)?.start().await?);
+
+    let mut router = axum::Router::new();
+    if transports.http {
+        router = router.merge(sessions.http_router());
+    }
+    if transports.a2a {
+        let gateway = A2aGateway::new(sessions.server().clone()).with_tls(tls.is_some());
+        router = router.merge(gateway.router());
+    }
+
+    let shutdown = match &rest {
+        Some(rest) => rest.state.shutdown.clone(),
+        None => ShutdownController::new(),
+    };
+
+    // The stdio client usually started this process, so stop serving
+    // everything once it disconnects
+    let stdio = transports.stdio.then(|| {
+        let sessions = sessions.clone();
+        let shutdown = shutdown.clone();
+        tokio::spawn(async move {
+            if let Err(e) = sessions.serve_stdio().await {
+                tracing::error!(error = %e, "MCP stdio session failed");
+            }
+            shutdown.trigger();
+        })
+    });
+
+    eprintln!("{} Server ready", "✓".green());
+    let served = if let Some(mut rest) = rest {
+        rest.app = rest.app.merge(router);
+        rest.serve().await
+    } else {
+        let addr = format!("{}:{}", host, port);
+        let listener = tokio::net::TcpListener::bind(&addr)
+            .await
+            .with_context(|| format!("Failed to bind to {}", addr))?;
+        shutdown.listen_for_signals();
+        if let Some(tls) = tls {
+            skill_http::tls::serve(listener, router, &tls, &shutdown, DEFAULT_DRAIN_TIMEOUT).await
+        } else {
+            let triggered = shutdown.clone();
+            axum::serve(listener, router)
+                .with_graceful_shutdown(async move { triggered.triggered().await })
+                .await
+                .context("HTTP server error")
+        }
+    };
+
+    if let Some(stdio) = stdio {
+        stdio.abort();
+    }
+    served
 }
 
 /// Start trunk serve for the web interface
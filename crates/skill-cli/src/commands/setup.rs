@@ -1,13 +1,15 @@
 //! Interactive setup wizard for search and RAG configuration
 //!
 //! Provides a user-friendly way to configure embedding providers,
-//! search settings, and model management.
+//! search settings, and model management. Execution and history settings
+//! go to `~/.skill-engine/config.toml`, which running servers pick up
+//! without a restart.
 
 use anyhow::{Context, Result};
 use colored::*;
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use indicatif::{ProgressBar, ProgressStyle};
-use skill_runtime::SearchConfig;
+use skill_runtime::{AppSettings, ConfigStore, SearchConfig};
 use std::fs;
 use std::path::PathBuf;
 
@@ -104,6 +106,61 @@ fn save_config(config: &SearchConfig) -> Result<()> {
     Ok(())
 }
 
+/// Execution and history settings given on the command line
+#[derive(Debug, Default)]
+pub struct SettingsOptions {
+    /// Default tool execution timeout in seconds
+    pub timeout: Option<u64>,
+    /// Tool executions a server runs at once
+    pub max_concurrent: Option<usize>,
+    /// Whether executions are recorded in history
+    pub history: Option<bool>,
+    /// Executions a server keeps in its in-memory history
+    pub max_history: Option<usize>,
+}
+
+impl SettingsOptions {
+    fn is_empty(&self) -> bool {
+        self.timeout.is_none()
+            && self.max_concurrent.is_none()
+            && self.history.is_none()
+            && self.max_history.is_none()
+    }
+
+    fn apply(&self, settings: &mut AppSettings) {
+        if let Some(timeout) = self.timeout {
+            settings.default_timeout_secs = timeout;
+        }
+        if let Some(max_concurrent) = self.max_concurrent {
+            settings.max_concurrent_executions = Some(max_concurrent);
+        }
+        if let Some(history) = self.history {
+            settings.enable_history = history;
+        }
+        if let Some(max_history) = self.max_history {
+            settings.max_history_entries = max_history;
+        }
+    }
+}
+
+/// Display the execution and history settings
+fn show_settings(store: &ConfigStore) {
+    let settings = store.get();
+    println!("{}", "Execution".bold());
+    if store.path().exists() {
+        println!("  {} {}", "Settings file:".dimmed(), store.path().display());
+    }
+    println!("  {} {}s", "Default Timeout:".cyan(), settings.default_timeout_secs);
+    match settings.max_concurrent_executions {
+        Some(max) => println!("  {} {}", "Max Concurrent:".cyan(), max),
+        None => println!("  {} {}", "Max Concurrent:".cyan(), "server default".dimmed()),
+    }
+    println!("  {} {}", "History:".cyan(),
+        if settings.enable_history { "enabled".green() } else { "disabled".dimmed() });
+    println!("  {} {}", "Max History Entries:".cyan(), settings.max_history_entries);
+    println!();
+}
+
 /// Display current configuration
 fn show_config(config: &SearchConfig) -> Result<()> {
     let config_path = get_config_path()?;
@@ -466,20 +523,29 @@ pub async fn execute(
     model: Option<&str>,
     hybrid: Option<bool>,
     rerank: Option<bool>,
+    settings: SettingsOptions,
 ) -> Result<()> {
     // Load existing config or default
     let mut config = load_config()?;
+    let store = ConfigStore::open_default()?;
 
     // Handle --show flag
     if show {
-        return show_config(&config);
+        show_config(&config)?;
+        show_settings(&store);
+        return Ok(());
     }
 
     // Handle --reset flag
     if reset {
-        let config_path = get_config_path()?;
-        if config_path.exists() {
-            fs::remove_file(&config_path)?;
+        let mut removed = false;
+        for path in [get_config_path()?, store.path().to_path_buf()] {
+            if path.exists() {
+                fs::remove_file(&path)?;
+                removed = true;
+            }
+        }
+        if removed {
             println!("{} Configuration reset to defaults", "✓".green());
         } else {
             println!("{} No configuration file to reset", "!".yellow());
@@ -487,8 +553,17 @@ pub async fn execute(
         return Ok(());
     }
 
+    // Execution and history settings are saved on their own
+    if !settings.is_empty() {
+        store.update(|current| settings.apply(current))?;
+        println!("{} Settings saved to {}", "✓".green(), store.path().display());
+    }
+
     // Handle non-interactive options
     let has_options = provider.is_some() || model.is_some() || hybrid.is_some() || rerank.is_some();
+    if !has_options && !settings.is_empty() {
+        return Ok(());
+    }
 
     if has_options {
         // Non-interactive mode
@@ -0,0 +1,169 @@
+//! Skill signing and trusted publisher keys
+//!
+//! Publishers create a key with `skill trust keygen` and sign a skill
+//! directory with `skill sign`; users trust the publisher's public key with
+//! `skill trust add` so `skill install` can verify it.
+
+use anyhow::{Context, Result};
+use colored::*;
+use skill_runtime::{
+    verify_skill_dir, PublicKey, SignatureStatus, SigningKey, SkillSignature, TrustStore,
+};
+use std::path::Path;
+
+/// Generate a publisher signing key
+pub async fn keygen(output: &Path, force: bool) -> Result<()> {
+    if output.exists() && !force {
+        anyhow::bail!(
+            "{} already exists (use --force to overwrite)",
+            output.display()
+        );
+    }
+
+    let key = SigningKey::generate()?;
+    key.save(output)?;
+    let public_key = key.public_key();
+
+    println!(
+        "{} Signing key written to {}",
+        "✓".green(),
+        output.display().to_string().cyan()
+    );
+    println!();
+    println!("  {} {}", "Public key:".bold(), public_key);
+    println!("  {} {}", "Fingerprint:".bold(), public_key.fingerprint());
+    println!();
+    println!(
+        "{} Share the public key; users trust it with: {} trust add <name> {}",
+        "→".cyan(),
+        "skill".cyan(),
+        public_key
+    );
+
+    Ok(())
+}
+
+/// Sign every file in a skill directory
+pub async fn sign(dir: &Path, key_path: &Path) -> Result<()> {
+    if !dir.is_dir() {
+        anyhow::bail!("Not a skill directory: {}", dir.display());
+    }
+
+    let key = SigningKey::load(key_path)?;
+    let signature = SkillSignature::sign_dir(dir, &key)?;
+    let path = signature.save(dir)?;
+
+    println!(
+        "{} Signed {} file(s) with key {}",
+        "✓".green(),
+        signature.files.len(),
+        key.public_key().fingerprint().cyan()
+    );
+    for file in signature.files.keys() {
+        println!("  • {}", file);
+    }
+    println!("  {} {}", "Signature:".bold(), path.display());
+
+    Ok(())
+}
+
+/// Trust a publisher key (hex string or path to a file containing it)
+pub async fn add(name: &str, key: &str) -> Result<()> {
+    let key = parse_key_arg(key)?;
+    let store = TrustStore::open_default()?;
+    let path = store.add(name, &key)?;
+
+    println!(
+        "{} Trusted {} ({})",
+        "✓".green(),
+        name.cyan(),
+        key.fingerprint()
+    );
+    println!("  {}", path.display().to_string().dimmed());
+    Ok(())
+}
+
+/// Stop trusting a key by name or fingerprint
+pub async fn remove(name: &str) -> Result<()> {
+    let store = TrustStore::open_default()?;
+    if !store.remove(name)? {
+        anyhow::bail!("No trusted key named '{}'. See 'skill trust list'.", name);
+    }
+    println!("{} Removed trusted key {}", "✓".green(), name.cyan());
+    Ok(())
+}
+
+/// List trusted keys
+pub async fn list(format: &str) -> Result<()> {
+    let store = TrustStore::open_default()?;
+    let keys = store.list()?;
+
+    if format == "json" {
+        let json: Vec<serde_json::Value> = keys
+            .iter()
+            .map(|trusted| {
+                serde_json::json!({
+                    "name": trusted.name,
+                    "fingerprint": trusted.key.fingerprint(),
+                    "public_key": trusted.key.to_hex(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    println!();
+    println!("{} Trusted keys in {}", "→".cyan(), store.dir().display());
+    println!();
+    if keys.is_empty() {
+        println!("  {}", "No trusted keys".dimmed());
+    }
+    for trusted in &keys {
+        println!(
+            "  {:<24} {}",
+            trusted.name.cyan(),
+            trusted.key.fingerprint().dimmed()
+        );
+    }
+    println!();
+    Ok(())
+}
+
+/// Verify a skill directory before install and apply the `--require-signed` policy
+pub fn verify_for_install(dir: &Path, wasm_file: &Path, require_signed: bool) -> Result<()> {
+    let store = TrustStore::open_default()?;
+    // Every *.wasm in the directory (including the one being installed) must
+    // match the signature, so a valid result covers the installed artifact
+    let status = verify_skill_dir(dir, &store)
+        .with_context(|| format!("Refusing to install {}", wasm_file.display()))?;
+    status.enforce(require_signed)?;
+
+    match &status {
+        SignatureStatus::Trusted(trusted) => println!(
+            "{} Signature verified: signed by {} ({})",
+            "✓".green(),
+            trusted.name.cyan(),
+            trusted.key.fingerprint()
+        ),
+        SignatureStatus::Untrusted(key) => println!(
+            "{} Signed by untrusted key {}; trust it with: skill trust add <name> {}",
+            "!".yellow(),
+            key.fingerprint(),
+            key
+        ),
+        SignatureStatus::Unsigned => println!("{} Skill is not signed", "→".dimmed()),
+    }
+
+    Ok(())
+}
+
+fn parse_key_arg(key: &str) -> Result<PublicKey> {
+    let path = Path::new(key);
+    if path.is_file() {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        return PublicKey::from_hex(&contents);
+    }
+    PublicKey::from_hex(key)
+}
@@ -1,24 +1,297 @@
 //! Self-upgrade command for the skill CLI
 //!
-//! Downloads and installs the latest version of the skill binary
-//! from Vercel Blob storage.
+//! Installs a release from GitHub in place of the running binary, after
+//! checking it against the SHA-256 checksum published with the release and,
+//! once the release key is trusted (`skill trust add skill-engine <key>`),
+//! its ed25519 signature. Releases come from a channel: `stable` follows the
+//! latest release, `beta` also takes prereleases, and `nightly` the rolling
+//! `nightly` build. `--version` installs one release, newer or older.
+//!
+//! Binaries installed by Homebrew, Scoop or cargo are upgraded through them
+//! instead, so the package manager keeps track of what is installed.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use colored::*;
+use serde::Deserialize;
+use skill_runtime::checksum::sha256_hex;
+use skill_runtime::{find_program, TrustStore};
+use std::cmp::Ordering;
 use std::env;
+use std::fmt;
 use std::fs;
-use std::os::unix::fs::PermissionsExt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const GITHUB_API_URL: &str = "https://api.github.com/repos/kubiyabot/skill";
+
+/// Tag of the rolling release the nightly workflow replaces
+const NIGHTLY_TAG: &str = "nightly";
+
+/// Name release signatures are checked against in the trust store
+const RELEASE_KEY_NAME: &str = "skill-engine";
+
+/// Releases `skill upgrade` picks from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Channel {
+    /// The latest release
+    #[default]
+    Stable,
+    /// The newest release, prereleases included
+    Beta,
+    /// The build of the main branch published every night
+    Nightly,
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Channel::Stable => write!(f, "stable"),
+            Channel::Beta => write!(f, "beta"),
+            Channel::Nightly => write!(f, "nightly"),
+        }
+    }
+}
+
+/// A release version: `major.minor.patch`, optionally with a prerelease
+/// such as `-beta.2`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Vec<String>,
+}
+
+impl Version {
+    /// Parse `1.2.3`, `v1.2.3` or `1.2.3-beta.2`; build metadata is ignored
+    fn parse(version: &str) -> Result<Self> {
+        let trimmed = version.trim();
+        let trimmed = trimmed.strip_prefix('v').unwrap_or(trimmed);
+        let trimmed = trimmed.split('+').next().unwrap_or_default();
+        let (core, pre) = match trimmed.split_once('-') {
+            Some((core, pre)) => (core, pre.split('.').map(str::to_string).collect()),
+            None => (trimmed, Vec::new()),
+        };
+
+        let parts = core
+            .split('.')
+            .map(|part| part.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .ok()
+            .filter(|parts| parts.len() == 3);
+        let Some(parts) = parts else {
+            bail!("Invalid version '{}': expected x.y.z", version);
+        };
+        if pre.iter().any(String::is_empty) {
+            bail!("Invalid prerelease in version '{}'", version);
+        }
+
+        Ok(Self {
+            major: parts[0],
+            minor: parts[1],
+            patch: parts[2],
+            pre,
+        })
+    }
+
+    fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                // A release ranks above its prereleases
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => compare_prerelease(&self.pre, &other.pre),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}.{}.{}", self.major, self.minor, self.patch)?;
+        if self.is_prerelease() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+/// Semver precedence of prerelease identifiers: numbers compare numerically
+/// and below words, and a shorter list ranks below a longer one it starts
+fn compare_prerelease(a: &[String], b: &[String]) -> Ordering {
+    for (a, b) in a.iter().zip(b) {
+        let ordering = match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => a.cmp(b),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+/// How the running binary was installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InstallMethod {
+    Homebrew,
+    Scoop,
+    Cargo,
+    /// `install.sh`, a release archive, or anything else we can replace
+    Standalone,
+}
+
+impl InstallMethod {
+    /// Tell from where the binary lives, with symlinks resolved
+    fn detect(exe: &Path) -> Self {
+        let path = exe.to_string_lossy().replace('\\', "/").to_lowercase();
+        let cargo_bin = env::var_os("CARGO_HOME").map(|home| {
+            let bin = PathBuf::from(home).join("bin");
+            format!(
+                "{}/",
+                bin.to_string_lossy().replace('\\', "/").to_lowercase()
+            )
+        });
+
+        if path.contains("/cellar/") || path.contains("/homebrew/") || path.contains("/linuxbrew/")
+        {
+            InstallMethod::Homebrew
+        } else if path.contains("/scoop/apps/") || path.contains("/scoop/shims/") {
+            InstallMethod::Scoop
+        } else if path.contains("/.cargo/bin/")
+            || cargo_bin.is_some_and(|bin| path.starts_with(&bin))
+        {
+            InstallMethod::Cargo
+        } else {
+            InstallMethod::Standalone
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            InstallMethod::Homebrew => "Homebrew",
+            InstallMethod::Scoop => "Scoop",
+            InstallMethod::Cargo => "cargo",
+            InstallMethod::Standalone => "standalone",
+        }
+    }
+
+    /// Command upgrading the package, or why the package manager can't
+    /// install what was asked for
+    fn upgrade_command(
+        self,
+        channel: Channel,
+        version: Option<&Version>,
+        force: bool,
+    ) -> Result<Vec<String>> {
+        let command: &[&str] = match self {
+            InstallMethod::Homebrew | InstallMethod::Scoop => {
+                if version.is_some() || channel != Channel::Stable {
+                    bail!(
+                        "{} installs follow stable releases. To install {}, remove skill \
+                         with {} and reinstall it with install.sh",
+                        self.name(),
+                        version
+                            .map_or_else(|| format!("the {} channel", channel), |v| v.to_string()),
+                        self.uninstall_command()
+                    );
+                }
+                match (self, force) {
+                    (InstallMethod::Homebrew, false) => &["brew", "upgrade", "skill"],
+                    (InstallMethod::Homebrew, true) => &["brew", "reinstall", "skill"],
+                    (_, false) => &["scoop", "update", "skill"],
+                    (_, true) => &["scoop", "update", "skill", "--force"],
+                }
+            }
+            InstallMethod::Cargo => {
+                if channel == Channel::Nightly {
+                    bail!(
+                        "Nightly builds aren't published to crates.io; \
+                         use `cargo install --git https://github.com/kubiyabot/skill skill-cli`"
+                    );
+                }
+                if channel == Channel::Beta && version.is_none() {
+                    bail!(
+                        "cargo only installs prereleases by version; \
+                         use `skill upgrade --version <x.y.z-beta.n>`"
+                    );
+                }
+                &["cargo", "install", "skill-cli", "--locked"]
+            }
+            InstallMethod::Standalone => bail!("Standalone installs upgrade themselves"),
+        };
+
+        let mut command: Vec<String> = command.iter().map(|arg| arg.to_string()).collect();
+        if self == InstallMethod::Cargo {
+            if let Some(version) = version {
+                command.push("--version".to_string());
+                command.push(version.to_string().trim_start_matches('v').to_string());
+            }
+            if force {
+                command.push("--force".to_string());
+            }
+        }
+        Ok(command)
+    }
+
+    fn uninstall_command(self) -> &'static str {
+        match self {
+            InstallMethod::Homebrew => "`brew uninstall skill`",
+            InstallMethod::Scoop => "`scoop uninstall skill`",
+            InstallMethod::Cargo => "`cargo uninstall skill-cli`",
+            InstallMethod::Standalone => "`rm $(which skill)`",
+        }
+    }
+}
+
+/// A GitHub release
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    assets: Vec<Asset>,
+}
 
-const BLOB_BASE_URL: &str = "https://dqkbk9o7ynwhxfjx.public.blob.vercel-storage.com";
-const VERSION_URL: &str = "https://dqkbk9o7ynwhxfjx.public.blob.vercel-storage.com/latest-version.txt";
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+impl Release {
+    /// Version of a tagged release; `None` for nightly builds
+    fn version(&self) -> Option<Version> {
+        Version::parse(&self.tag_name).ok()
+    }
+
+    fn asset(&self, name: &str) -> Option<&Asset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
 
 /// Get the current version from Cargo.toml
 fn current_version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
 
-/// Get the target triple for the current platform
+/// Get the target triple of the release archive for the current platform
 fn get_target() -> Result<&'static str> {
     #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
     return Ok("aarch64-apple-darwin");
@@ -26,8 +299,9 @@ fn get_target() -> Result<&'static str> {
     #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
     return Ok("x86_64-apple-darwin");
 
+    // Releases ship a static musl build, which runs on any distribution
     #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
-    return Ok("x86_64-unknown-linux-gnu");
+    return Ok("x86_64-unknown-linux-musl");
 
     #[cfg(all(target_os = "linux", target_arch = "aarch64"))]
     return Ok("aarch64-unknown-linux-gnu");
@@ -41,54 +315,93 @@ fn get_target() -> Result<&'static str> {
     anyhow::bail!("Unsupported platform. Please build from source.");
 }
 
-/// Fetch the latest version from Vercel Blob
-async fn get_latest_version() -> Result<String> {
-    let client = reqwest::Client::new();
+/// HTTP client for the GitHub API, authenticated with `GITHUB_TOKEN` when
+/// set to avoid the anonymous rate limit
+fn github_client() -> Result<reqwest::Client> {
+    let mut headers = reqwest::header::HeaderMap::new();
+    if let Ok(token) = env::var("GITHUB_TOKEN") {
+        if !token.is_empty() {
+            headers.insert(
+                reqwest::header::AUTHORIZATION,
+                format!("Bearer {}", token).parse()?,
+            );
+        }
+    }
+    reqwest::Client::builder()
+        .user_agent(concat!("skill-cli/", env!("CARGO_PKG_VERSION")))
+        .default_headers(headers)
+        .build()
+        .context("Failed to create HTTP client")
+}
+
+/// Fetch JSON from the GitHub API
+async fn get_json<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    path: &str,
+) -> Result<T> {
+    let url = format!("{}{}", GITHUB_API_URL, path);
     let response = client
-        .get(VERSION_URL)
+        .get(&url)
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
         .send()
         .await
-        .context("Failed to fetch latest version")?;
+        .context("Failed to fetch release information")?;
 
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        bail!("Release not found: {}", url);
+    }
     if !response.status().is_success() {
-        anyhow::bail!(
-            "Failed to fetch version info: {}",
-            response.status()
+        bail!(
+            "Failed to fetch release information: {} - {}",
+            response.status(),
+            url
         );
     }
-
-    let version = response
-        .text()
+    response
+        .json()
         .await
-        .context("Failed to read version")?
-        .trim()
-        .to_string();
-
-    Ok(version)
+        .context("Failed to parse release information")
 }
 
-/// Download the binary for the current platform
-async fn download_binary(version: &str, target: &str) -> Result<Vec<u8>> {
-    let version_num = version.strip_prefix('v').unwrap_or(version);
-    let url = format!(
-        "{}/skill-{}-{}.tar.gz",
-        BLOB_BASE_URL, version_num, target
-    );
+/// The release to install: `version` when given, else the newest on `channel`
+async fn find_release(
+    client: &reqwest::Client,
+    channel: Channel,
+    version: Option<&Version>,
+) -> Result<Release> {
+    if let Some(version) = version {
+        return get_json(client, &format!("/releases/tags/{}", version)).await;
+    }
 
-    eprintln!("  {} {}", "Downloading:".dimmed(), url);
+    match channel {
+        Channel::Stable => get_json(client, "/releases/latest").await,
+        Channel::Nightly => get_json(client, &format!("/releases/tags/{}", NIGHTLY_TAG)).await,
+        Channel::Beta => {
+            let releases: Vec<Release> = get_json(client, "/releases?per_page=50").await?;
+            releases
+                .into_iter()
+                .filter(|release| !release.draft)
+                .filter_map(|release| release.version().map(|version| (version, release)))
+                .max_by(|(a, _), (b, _)| a.cmp(b))
+                .map(|(_, release)| release)
+                .context("No releases found")
+        }
+    }
+}
 
-    let client = reqwest::Client::new();
+/// Download a release asset
+async fn download(client: &reqwest::Client, asset: &Asset) -> Result<Vec<u8>> {
     let response = client
-        .get(&url)
+        .get(&asset.browser_download_url)
         .send()
         .await
-        .context("Failed to download binary")?;
+        .with_context(|| format!("Failed to download {}", asset.name))?;
 
     if !response.status().is_success() {
-        anyhow::bail!(
+        bail!(
             "Download failed: {} - {}",
             response.status(),
-            url
+            asset.browser_download_url
         );
     }
 
@@ -96,14 +409,125 @@ async fn download_binary(version: &str, target: &str) -> Result<Vec<u8>> {
         .bytes()
         .await
         .map(|b| b.to_vec())
-        .context("Failed to read binary data")
+        .with_context(|| format!("Failed to read {}", asset.name))
+}
+
+/// Expected SHA-256 of `file` from `shasum -a 256` output: a single
+/// `<hash>  <file>` line or a `checksums.txt` listing several files
+fn parse_checksum(checksums: &str, file: &str) -> Result<String> {
+    for line in checksums.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(hash), name) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        // shasum marks binary mode with a leading '*'
+        let matches = name.map_or(true, |name| name.trim_start_matches('*') == file);
+        if matches {
+            if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                bail!("Invalid SHA-256 checksum for {}: {}", file, hash);
+            }
+            return Ok(hash.to_lowercase());
+        }
+    }
+    bail!("No checksum for {}", file)
+}
+
+/// Fail unless `data` hashes to `expected`
+fn verify_checksum(data: &[u8], expected: &str, file: &str) -> Result<()> {
+    let actual = sha256_hex(data);
+    if actual != expected {
+        bail!(
+            "Checksum mismatch for {}: expected {}, got {}. Refusing to install.",
+            file,
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+/// Check the downloaded archive against the release's checksum
+async fn check_checksum(
+    client: &reqwest::Client,
+    release: &Release,
+    archive: &str,
+    tarball: &[u8],
+) -> Result<()> {
+    let checksum_asset = release
+        .asset(&format!("{}.sha256", archive))
+        .or_else(|| release.asset("checksums.txt"))
+        .with_context(|| {
+            format!(
+                "Release {} publishes no checksum for {}. Refusing to install.",
+                release.tag_name, archive
+            )
+        })?;
+    let checksums = download(client, checksum_asset).await?;
+    let expected = parse_checksum(&String::from_utf8_lossy(&checksums), archive)?;
+    verify_checksum(tarball, &expected, archive)?;
+    println!(
+        "  {} {}",
+        "Checksum:".dimmed(),
+        "verified (SHA-256)".green()
+    );
+    Ok(())
+}
+
+/// Check the archive's signature against the trusted release key
+///
+/// Without a trusted key the signature can't be checked and only a notice
+/// is printed; once the key is trusted, unsigned releases are refused.
+async fn check_signature(
+    client: &reqwest::Client,
+    release: &Release,
+    archive: &str,
+    tarball: &[u8],
+) -> Result<()> {
+    let trusted = TrustStore::open_default()?
+        .list()?
+        .into_iter()
+        .find(|trusted| trusted.name == RELEASE_KEY_NAME);
+    let signature_asset = release.asset(&format!("{}.sig", archive));
+
+    match (trusted, signature_asset) {
+        (Some(trusted), Some(asset)) => {
+            let signature = download(client, asset).await?;
+            trusted
+                .key
+                .verify_hex(tarball, &String::from_utf8_lossy(&signature))
+                .with_context(|| {
+                    format!("Invalid signature on {}. Refusing to install.", archive)
+                })?;
+            println!(
+                "  {} {}",
+                "Signature:".dimmed(),
+                format!("verified (key {})", trusted.key.fingerprint()).green()
+            );
+        }
+        (Some(_), None) => bail!(
+            "Release {} is not signed, but the '{}' key is trusted. Refusing to install.",
+            release.tag_name,
+            RELEASE_KEY_NAME
+        ),
+        (None, Some(_)) => println!(
+            "  {} {}",
+            "Signature:".dimmed(),
+            format!(
+                "not checked; trust the release key with `skill trust add {} <key>`",
+                RELEASE_KEY_NAME
+            )
+            .yellow()
+        ),
+        (None, None) => println!("  {} {}", "Signature:".dimmed(), "none".dimmed()),
+    }
+    Ok(())
 }
 
 /// Extract the binary from the tarball
 fn extract_binary(tarball: &[u8]) -> Result<Vec<u8>> {
     use flate2::read::GzDecoder;
-    use tar::Archive;
     use std::io::Read;
+    use tar::Archive;
 
     let decoder = GzDecoder::new(tarball);
     let mut archive = Archive::new(decoder);
@@ -123,9 +547,10 @@ fn extract_binary(tarball: &[u8]) -> Result<Vec<u8>> {
     anyhow::bail!("Binary 'skill' not found in archive")
 }
 
-/// Get the path to the current executable
+/// Get the path to the current executable, with symlinks resolved
 fn get_current_exe() -> Result<PathBuf> {
-    env::current_exe().context("Failed to get current executable path")
+    let exe = env::current_exe().context("Failed to get current executable path")?;
+    Ok(fs::canonicalize(&exe).unwrap_or(exe))
 }
 
 /// Replace the current binary with the new one
@@ -135,18 +560,20 @@ fn replace_binary(new_binary: &[u8], exe_path: &PathBuf) -> Result<()> {
     let backup_path = exe_path.with_extension("backup");
 
     // Write new binary to temp file
-    fs::write(&temp_path, new_binary)
-        .context("Failed to write new binary")?;
+    fs::write(&temp_path, new_binary).context("Failed to write new binary")?;
 
     // Make it executable
-    let mut perms = fs::metadata(&temp_path)?.permissions();
-    perms.set_mode(0o755);
-    fs::set_permissions(&temp_path, perms)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&temp_path)?.permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&temp_path, perms)?;
+    }
 
     // Backup current binary
     if exe_path.exists() {
-        fs::rename(exe_path, &backup_path)
-            .context("Failed to backup current binary")?;
+        fs::rename(exe_path, &backup_path).context("Failed to backup current binary")?;
     }
 
     // Move new binary into place
@@ -164,83 +591,145 @@ fn replace_binary(new_binary: &[u8], exe_path: &PathBuf) -> Result<()> {
     Ok(())
 }
 
-/// Compare versions (simple semver comparison)
-fn is_newer_version(current: &str, latest: &str) -> bool {
-    let current = current.strip_prefix('v').unwrap_or(current);
-    let latest = latest.strip_prefix('v').unwrap_or(latest);
+/// Upgrade through the package manager that installed the binary
+fn delegate(
+    method: InstallMethod,
+    channel: Channel,
+    version: Option<&Version>,
+    force: bool,
+    check_only: bool,
+) -> Result<()> {
+    let command = method.upgrade_command(channel, version, force)?;
+    println!("  {} {}", "Installed with:".dimmed(), method.name().cyan());
+    println!();
 
-    let parse_version = |v: &str| -> (u32, u32, u32) {
-        let parts: Vec<u32> = v
-            .split('.')
-            .filter_map(|s| s.parse().ok())
-            .collect();
-        (
-            parts.first().copied().unwrap_or(0),
-            parts.get(1).copied().unwrap_or(0),
-            parts.get(2).copied().unwrap_or(0),
-        )
-    };
+    if check_only {
+        println!("Run {} to upgrade.", command.join(" ").cyan());
+        return Ok(());
+    }
 
-    parse_version(latest) > parse_version(current)
+    println!("{} Running {}", "→".blue().bold(), command.join(" ").cyan());
+    println!();
+    let program = find_program(&command[0]).unwrap_or_else(|| PathBuf::from(&command[0]));
+    let status = Command::new(program)
+        .args(&command[1..])
+        .status()
+        .with_context(|| format!("Failed to run {}", command[0]))?;
+    if !status.success() {
+        bail!("`{}` failed with {}", command.join(" "), status);
+    }
+    Ok(())
 }
 
 /// Execute the upgrade command
-pub async fn execute(force: bool, check_only: bool) -> Result<()> {
-    let current = current_version();
+///
+/// Installs `version` when given, else the newest release on `channel`.
+pub async fn execute(
+    force: bool,
+    check_only: bool,
+    channel: Channel,
+    version: Option<String>,
+) -> Result<()> {
+    let current = Version::parse(current_version())?;
+    let requested = version.as_deref().map(Version::parse).transpose()?;
 
     println!();
     println!("{}", "Skill Engine Upgrade".bold());
     println!("{}", "─".repeat(50));
     println!();
-    println!("  {} {}", "Current version:".dimmed(), format!("v{}", current).cyan());
+    println!(
+        "  {} {}",
+        "Current version:".dimmed(),
+        current.to_string().cyan()
+    );
+    println!("  {} {}", "Channel:".dimmed(), channel.to_string().cyan());
 
-    // Fetch latest version from Vercel Blob
+    let exe_path = get_current_exe()?;
+    let method = InstallMethod::detect(&exe_path);
+    if method != InstallMethod::Standalone {
+        return delegate(method, channel, requested.as_ref(), force, check_only);
+    }
+
+    // Fetch the release from GitHub
     print!("  {} ", "Checking for updates...".dimmed());
-    let latest = get_latest_version().await?;
+    let client = github_client()?;
+    let release = find_release(&client, channel, requested.as_ref()).await?;
     println!("{}", "done".green());
 
-    println!("  {} {}", "Latest version:".dimmed(), format!("v{}", latest).cyan());
+    let target_version = release.version();
+    let label = target_version
+        .as_ref()
+        .map_or_else(|| release.tag_name.clone(), |v| v.to_string());
+    let heading = if requested.is_some() {
+        "Requested version:"
+    } else {
+        "Latest version:"
+    };
+    println!("  {} {}", heading.dimmed(), label.cyan());
     println!();
 
-    // Check if upgrade is needed
-    let needs_upgrade = is_newer_version(current, &latest);
+    // Nightly builds share the version of the last release; always reinstall
+    let ordering = target_version.as_ref().map(|target| target.cmp(&current));
+    let needs_install = match ordering {
+        Some(Ordering::Greater) => true,
+        Some(Ordering::Less) => requested.is_some(),
+        Some(Ordering::Equal) => false,
+        None => true,
+    };
 
-    if !needs_upgrade && !force {
-        println!(
-            "{} You're already running the latest version!",
-            "✓".green().bold()
-        );
+    if !needs_install && !force {
+        if ordering == Some(Ordering::Equal) {
+            println!("{} You're already running {}!", "✓".green().bold(), label);
+        } else {
+            println!(
+                "{} You're running a newer version than the latest {} release. \
+                 Use {} to downgrade.",
+                "✓".green().bold(),
+                channel,
+                format!("skill upgrade --version {}", label).cyan()
+            );
+        }
         println!();
         return Ok(());
     }
 
     if check_only {
-        if needs_upgrade {
-            println!(
-                "{} A new version is available: {} → {}",
-                "ℹ".blue().bold(),
-                format!("v{}", current).yellow(),
-                format!("v{}", latest).green()
-            );
-            println!();
-            println!("Run {} to upgrade.", "skill upgrade".cyan());
+        println!(
+            "{} {} is available: {} → {}",
+            "ℹ".blue().bold(),
+            label,
+            current.to_string().yellow(),
+            label.green()
+        );
+        println!();
+        let mut command = "skill upgrade".to_string();
+        if let Some(version) = &version {
+            command.push_str(&format!(" --version {}", version));
+        } else if channel != Channel::Stable {
+            command.push_str(&format!(" --channel {}", channel));
         }
+        println!("Run {} to install it.", command.cyan());
         return Ok(());
     }
 
-    if !needs_upgrade && force {
-        println!(
+    match ordering {
+        Some(Ordering::Equal) => println!(
             "{} Forcing reinstall of version {}",
             "⚠".yellow().bold(),
-            latest
-        );
-    } else {
-        println!(
+            label
+        ),
+        Some(Ordering::Less) => println!(
+            "{} Downgrading: {} → {}",
+            "↓".yellow().bold(),
+            current.to_string().yellow(),
+            label.green()
+        ),
+        _ => println!(
             "{} Upgrading: {} → {}",
             "→".blue().bold(),
-            format!("v{}", current).yellow(),
-            format!("v{}", latest).green()
-        );
+            current.to_string().yellow(),
+            label.green()
+        ),
     }
     println!();
 
@@ -248,21 +737,32 @@ pub async fn execute(force: bool, check_only: bool) -> Result<()> {
     let target = get_target()?;
     println!("  {} {}", "Platform:".dimmed(), target);
 
-    // Download binary
-    println!("  {} downloading...", "Status:".dimmed());
-    let tarball = download_binary(&latest, target).await?;
-    println!("  {} extracting...", "Status:".dimmed());
+    let archive = format!("skill-{}.tar.gz", target);
+    let asset = release.asset(&archive).with_context(|| {
+        format!(
+            "Release {} has no build for {}; install with `cargo install skill-cli` instead",
+            release.tag_name, target
+        )
+    })?;
+
+    // Download and verify the archive
+    println!(
+        "  {} {}",
+        "Downloading:".dimmed(),
+        asset.browser_download_url
+    );
+    let tarball = download(&client, asset).await?;
+    check_checksum(&client, &release, &archive, &tarball).await?;
+    check_signature(&client, &release, &archive, &tarball).await?;
 
     // Extract binary
+    println!("  {} extracting...", "Status:".dimmed());
     let binary = extract_binary(&tarball)?;
     println!(
         "  {} {} bytes",
         "Binary size:".dimmed(),
         binary.len().to_string().cyan()
     );
-
-    // Get current exe path
-    let exe_path = get_current_exe()?;
     println!("  {} {}", "Install path:".dimmed(), exe_path.display());
 
     // Replace binary
@@ -271,9 +771,9 @@ pub async fn execute(force: bool, check_only: bool) -> Result<()> {
 
     println!();
     println!(
-        "{} Successfully upgraded to v{}!",
+        "{} Successfully installed {}!",
         "✓".green().bold(),
-        latest.green()
+        label.green()
     );
     println!();
 
@@ -283,13 +783,17 @@ pub async fn execute(force: bool, check_only: bool) -> Result<()> {
 /// Check for updates without installing
 #[allow(dead_code)]
 pub async fn check() -> Result<()> {
-    execute(false, true).await
+    execute(false, true, Channel::default(), None).await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn is_newer_version(current: &str, latest: &str) -> bool {
+        Version::parse(latest).unwrap() > Version::parse(current).unwrap()
+    }
+
     #[test]
     fn test_version_comparison() {
         assert!(is_newer_version("0.1.0", "0.2.0"));
@@ -301,4 +805,154 @@ mod tests {
         assert!(!is_newer_version("0.2.0", "0.1.0"));
         assert!(!is_newer_version("1.0.0", "0.9.9"));
     }
+
+    #[test]
+    fn test_prerelease_ordering() {
+        assert!(is_newer_version("0.4.0-beta.1", "0.4.0"));
+        assert!(!is_newer_version("0.4.0", "0.4.0-beta.1"));
+        assert!(is_newer_version("0.3.4", "0.4.0-beta.1"));
+        assert!(is_newer_version("0.4.0-beta.2", "0.4.0-beta.10"));
+        assert!(is_newer_version("0.4.0-alpha", "0.4.0-alpha.1"));
+        assert!(is_newer_version("0.4.0-alpha.1", "0.4.0-beta"));
+        assert!(is_newer_version("0.4.0-1", "0.4.0-alpha"));
+        assert_eq!(
+            Version::parse("v1.2.3+build.7").unwrap(),
+            Version::parse("1.2.3").unwrap()
+        );
+        assert_eq!(
+            Version::parse("1.2.3-rc.1").unwrap().to_string(),
+            "v1.2.3-rc.1"
+        );
+
+        assert!(Version::parse("nightly").is_err());
+        assert!(Version::parse("1.2").is_err());
+        assert!(Version::parse("1.2.3-").is_err());
+    }
+
+    #[test]
+    fn test_install_method_detection() {
+        let detect = |path: &str| InstallMethod::detect(Path::new(path));
+        assert_eq!(
+            detect("/opt/homebrew/Cellar/skill/0.3.4/bin/skill"),
+            InstallMethod::Homebrew
+        );
+        assert_eq!(
+            detect("/home/linuxbrew/.linuxbrew/bin/skill"),
+            InstallMethod::Homebrew
+        );
+        assert_eq!(
+            detect(r"C:\Users\me\scoop\apps\skill\current\skill.exe"),
+            InstallMethod::Scoop
+        );
+        assert_eq!(detect("/home/me/.cargo/bin/skill"), InstallMethod::Cargo);
+        assert_eq!(
+            detect("/home/me/.local/bin/skill"),
+            InstallMethod::Standalone
+        );
+        assert_eq!(detect("/usr/local/bin/skill"), InstallMethod::Standalone);
+    }
+
+    #[test]
+    fn test_package_manager_commands() {
+        let beta = Version::parse("0.4.0-beta.1").unwrap();
+
+        assert_eq!(
+            InstallMethod::Homebrew
+                .upgrade_command(Channel::Stable, None, false)
+                .unwrap(),
+            ["brew", "upgrade", "skill"]
+        );
+        assert_eq!(
+            InstallMethod::Scoop
+                .upgrade_command(Channel::Stable, None, true)
+                .unwrap(),
+            ["scoop", "update", "skill", "--force"]
+        );
+        assert_eq!(
+            InstallMethod::Cargo
+                .upgrade_command(Channel::Beta, Some(&beta), false)
+                .unwrap(),
+            [
+                "cargo",
+                "install",
+                "skill-cli",
+                "--locked",
+                "--version",
+                "0.4.0-beta.1"
+            ]
+        );
+
+        let error = InstallMethod::Homebrew
+            .upgrade_command(Channel::Nightly, None, false)
+            .unwrap_err();
+        assert!(error.to_string().contains("brew uninstall skill"));
+        assert!(InstallMethod::Scoop
+            .upgrade_command(Channel::Stable, Some(&beta), false)
+            .is_err());
+        assert!(InstallMethod::Cargo
+            .upgrade_command(Channel::Beta, None, false)
+            .is_err());
+        assert!(InstallMethod::Cargo
+            .upgrade_command(Channel::Nightly, None, false)
+            .is_err());
+    }
+
+    #[test]
+    fn test_checksums() {
+        let data = b"skill release";
+        let hash = "e3d2c4d5b1b8b3f5c4a8cbb0d70b7f7e0a2c7f2fd0bde1d1a5d1f6c6f0d6e8a1";
+        let single = format!("{}  skill-aarch64-apple-darwin.tar.gz\n", hash);
+        assert_eq!(
+            parse_checksum(&single, "skill-aarch64-apple-darwin.tar.gz").unwrap(),
+            hash
+        );
+
+        let listing = format!(
+            "{}  skill-x86_64-unknown-linux-musl.tar.gz\n{} *skill-aarch64-apple-darwin.tar.gz\n",
+            "0".repeat(64),
+            hash.to_uppercase()
+        );
+        assert_eq!(
+            parse_checksum(&listing, "skill-aarch64-apple-darwin.tar.gz").unwrap(),
+            hash
+        );
+        assert!(parse_checksum(&listing, "skill-x86_64-pc-windows-msvc.tar.gz").is_err());
+        assert!(parse_checksum("abc123  skill.tar.gz", "skill.tar.gz").is_err());
+
+        let actual = sha256_hex(data);
+        verify_checksum(data, &actual, "skill.tar.gz").unwrap();
+        let error = verify_checksum(b"tampered", &actual, "skill.tar.gz").unwrap_err();
+        assert!(error.to_string().contains("Checksum mismatch"));
+    }
+
+    #[test]
+    fn test_release_assets() {
+        let release: Release = serde_json::from_str(
+            r#"{
+                "tag_name": "v0.4.0-beta.1",
+                "draft": false,
+                "prerelease": true,
+                "assets": [{
+                    "name": "skill-aarch64-apple-darwin.tar.gz",
+                    "browser_download_url": "https://example.com/skill-aarch64-apple-darwin.tar.gz"
+                }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            release.version(),
+            Some(Version::parse("0.4.0-beta.1").unwrap())
+        );
+        assert!(release.asset("skill-aarch64-apple-darwin.tar.gz").is_some());
+        assert!(release
+            .asset("skill-aarch64-apple-darwin.tar.gz.sig")
+            .is_none());
+
+        let nightly = Release {
+            tag_name: NIGHTLY_TAG.to_string(),
+            draft: false,
+            assets: Vec::new(),
+        };
+        assert_eq!(nightly.version(), None);
+    }
 }
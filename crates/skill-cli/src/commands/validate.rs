@@ -0,0 +1,181 @@
+//! Lint skill directories and manifests (`skill validate`)
+
+use anyhow::{Context, Result};
+use colored::*;
+use skill_runtime::validation::{validate_manifest, validate_skill_md, validate_tool_exports};
+use skill_runtime::{
+    find_skill_md, parse_skill_md_in, Severity, SkillEngine, SkillExecutor, ValidationReport,
+};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Validate a skill directory, a SKILL.md file, or a manifest
+///
+/// Fails if any errors are found, or any issue at all with `strict`.
+pub async fn execute(
+    path: &Path,
+    manifest: Option<&Path>,
+    format: &str,
+    strict: bool,
+) -> Result<()> {
+    let mut report = ValidationReport::default();
+    let mut checked = Vec::new();
+
+    let is_manifest_file = path.is_file() && path.extension().is_some_and(|ext| ext == "toml");
+    let (skill_md, manifest_path) = if is_manifest_file {
+        (None, Some(path.to_path_buf()))
+    } else if path.is_file() {
+        (Some(path.to_path_buf()), manifest.map(Path::to_path_buf))
+    } else if path.is_dir() {
+        let manifest_path = manifest.map(Path::to_path_buf).or_else(|| {
+            [".skill-engine.toml", "skill-engine.toml"]
+                .iter()
+                .map(|name| path.join(name))
+                .find(|p| p.exists())
+        });
+        (find_skill_md(path), manifest_path)
+    } else {
+        anyhow::bail!("Path not found: {}", path.display());
+    };
+
+    if skill_md.is_none() && manifest_path.is_none() {
+        anyhow::bail!(
+            "Nothing to validate in {}: expected SKILL.md or .skill-engine.toml",
+            path.display()
+        );
+    }
+
+    if let Some(skill_md) = &skill_md {
+        validate_skill(skill_md, &mut report).await?;
+        checked.push(skill_md.display().to_string());
+    }
+
+    if let Some(manifest_path) = &manifest_path {
+        let content = std::fs::read_to_string(manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+        let base_dir = manifest_path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        validate_manifest(
+            &content,
+            &manifest_path.display().to_string(),
+            base_dir,
+            &mut report,
+        );
+        checked.push(manifest_path.display().to_string());
+    }
+
+    let failed = report.is_failure(strict);
+    if format == "json" {
+        let json = serde_json::json!({
+            "valid": !failed,
+            "checked": checked,
+            "errors": report.error_count(),
+            "warnings": report.warning_count(),
+            "issues": report.issues,
+        });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+    } else {
+        print_report(&report, &checked);
+    }
+
+    if failed {
+        anyhow::bail!(
+            "Validation failed: {} error(s), {} warning(s){}",
+            report.error_count(),
+            report.warning_count(),
+            if strict { " (strict)" } else { "" }
+        );
+    }
+    Ok(())
+}
+
+/// Lint SKILL.md and, when a built component sits next to it, its exported tools
+async fn validate_skill(skill_md: &Path, report: &mut ValidationReport) -> Result<()> {
+    let location = skill_md.display().to_string();
+    let content = std::fs::read_to_string(skill_md)
+        .with_context(|| format!("Failed to read {}", skill_md.display()))?;
+    let Some(mut skill) = validate_skill_md(&content, &location, report) else {
+        return Ok(());
+    };
+
+    let dir = skill_md.parent().unwrap_or(Path::new("."));
+    if !skill.frontmatter.include.is_empty() {
+        match parse_skill_md_in(&content, dir) {
+            Ok(merged) => skill = merged,
+            Err(e) => {
+                report.error("include-invalid", &location, format!("{:#}", e));
+                return Ok(());
+            }
+        }
+    }
+    let Some(wasm) = find_component(dir) else {
+        return Ok(());
+    };
+
+    let engine = Arc::new(SkillEngine::new()?);
+    let tools = match SkillExecutor::load(
+        engine,
+        &wasm,
+        skill.frontmatter.name.clone(),
+        "default".to_string(),
+        Default::default(),
+    )
+    .await
+    {
+        Ok(executor) => executor.get_tools().await,
+        Err(e) => Err(e),
+    };
+
+    match tools {
+        Ok(tools) => validate_tool_exports(&skill.tool_docs, &tools, &location, report),
+        Err(e) => report.error(
+            "wasm-load",
+            wasm.display().to_string(),
+            format!("Failed to load component to list its tools: {:#}", e),
+        ),
+    }
+    Ok(())
+}
+
+fn find_component(dir: &Path) -> Option<PathBuf> {
+    ["skill.wasm", "dist/skill.wasm", "build/skill.wasm"]
+        .iter()
+        .map(|candidate| dir.join(candidate))
+        .find(|path| path.is_file())
+}
+
+fn print_report(report: &ValidationReport, checked: &[String]) {
+    println!();
+    for path in checked {
+        println!("{} Checked {}", "→".cyan(), path);
+    }
+    println!();
+
+    for issue in &report.issues {
+        let label = match issue.severity {
+            Severity::Error => format!("error[{}]", issue.code).red().bold(),
+            Severity::Warning => format!("warning[{}]", issue.code).yellow().bold(),
+        };
+        println!("  {} {}", label, issue.location.dimmed());
+        println!("      {}", issue.message);
+    }
+
+    if report.issues.is_empty() {
+        println!("{} No issues found", "✓".green());
+    } else {
+        println!();
+        println!(
+            "{} {} error(s), {} warning(s)",
+            if report.error_count() > 0 {
+                "✗".red()
+            } else {
+                "!".yellow()
+            },
+            report.error_count(),
+            report.warning_count()
+        );
+    }
+    println!();
+}
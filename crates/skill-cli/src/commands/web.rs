@@ -3,12 +3,72 @@
 //! This command starts an HTTP server that serves both the REST API
 //! and an embedded web UI built with Yew/WASM.
 
-use anyhow::Result;
+use anyhow::{bail, Result};
+use clap::Args;
 use colored::*;
+use skill_http::{HttpServer, HttpServerConfig, NamespaceConfig, TlsConfig};
+use std::path::PathBuf;
+
+/// HTTPS options, shared with `skill serve --http`
+#[derive(Args, Debug, Clone, Default)]
+pub struct TlsArgs {
+    /// PEM certificate chain to serve HTTPS with
+    #[arg(long, requires = "tls_key", conflicts_with = "acme_domain")]
+    pub tls_cert: Option<PathBuf>,
+
+    /// PEM private key for --tls-cert
+    #[arg(long, requires = "tls_cert")]
+    pub tls_key: Option<PathBuf>,
+
+    /// Get a certificate for this domain from Let's Encrypt (repeatable; needs port 443)
+    #[arg(long)]
+    pub acme_domain: Vec<String>,
+
+    /// Contact email for the Let's Encrypt account
+    #[arg(long, requires = "acme_domain")]
+    pub acme_email: Option<String>,
+
+    /// Require client certificates signed by this CA bundle (mTLS)
+    #[arg(long)]
+    pub tls_client_ca: Option<PathBuf>,
+
+    /// With --tls-client-ca, still accept clients without a certificate
+    #[arg(long, requires = "tls_client_ca")]
+    pub tls_client_optional: bool,
+}
+
+impl TlsArgs {
+    /// TLS configuration, or `None` to serve plain HTTP
+    pub fn config(&self) -> Result<Option<TlsConfig>> {
+        let mut tls = match (&self.tls_cert, &self.tls_key) {
+            (Some(cert), Some(key)) => TlsConfig::from_files(cert, key),
+            _ if !self.acme_domain.is_empty() => {
+                TlsConfig::acme(self.acme_domain.clone(), self.acme_email.clone())
+            }
+            _ if self.tls_client_ca.is_some() => {
+                bail!("--tls-client-ca needs --tls-cert/--tls-key or --acme-domain")
+            }
+            _ => return Ok(None),
+        };
+        if let Some(ca) = &self.tls_client_ca {
+            tls = tls.with_client_ca(ca);
+        }
+        tls.client_auth_optional = self.tls_client_optional;
+        Ok(Some(tls))
+    }
+}
 
 /// Execute the web command
-pub async fn execute(host: &str, port: u16, open_browser: bool) -> Result<()> {
-    let url = format!("http://{}:{}", host, port);
+pub async fn execute(
+    host: &str,
+    port: u16,
+    open_browser: bool,
+    namespaces: Vec<NamespaceConfig>,
+    tls: &TlsArgs,
+) -> Result<()> {
+    let tls = tls.config()?;
+    let scheme = if tls.is_some() { "https" } else { "http" };
+    let url = format!("{}://{}:{}", scheme, host, port);
 
     // Print startup banner
     println!();
@@ -28,12 +88,29 @@ pub async fn execute(host: &str, port: u16, open_browser: bool) -> Result<()> {
     println!();
     println!("  {} {}/", "Web UI:".dimmed(), url);
     println!("  {} {}/api/...", "API:".dimmed(), url);
+    for namespace in &namespaces {
+        println!(
+            "  {} {}/ns/{}/api/... ({})",
+            "Namespace:".dimmed(),
+            url,
+            namespace.name,
+            namespace.working_dir.display()
+        );
+    }
     println!();
     println!("{}", "Press Ctrl+C to stop".dimmed());
     println!();
 
     // Start the server with web UI enabled
-    skill_http::serve_with_ui(host, port).await
+    let config = HttpServerConfig {
+        host: host.to_string(),
+        port,
+        enable_web_ui: true,
+        tls,
+        namespaces,
+        ..Default::default()
+    };
+    HttpServer::with_config(config)?.run().await
 }
 
 /// Open a URL in the default browser
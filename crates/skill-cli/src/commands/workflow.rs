@@ -0,0 +1,355 @@
+//! Workflows - run multi-step tool pipelines and inspect past runs
+//!
+//! Runs are recorded in the job queue (`jobs.db`) with per-step status, so
+//! `skill workflow status` can show them after the fact.
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use colored::*;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use skill_mcp::McpServer;
+use skill_runtime::jobs::{create_storage, Job, JobConfig, JobFilter, JobStatus, JobType};
+use skill_runtime::{
+    discover_workflows, resolve_workflow, RunObserver, RunStatus, SkillManifest, Workflow,
+    WorkflowRun,
+};
+use std::sync::Mutex;
+
+use crate::output::OutputFormat;
+
+/// Run a workflow file or named workflow.
+pub async fn run(
+    workflow: &str,
+    inputs: Vec<(String, String)>,
+    output: Option<OutputFormat>,
+    manifest: Option<&SkillManifest>,
+) -> Result<()> {
+    let path = resolve_workflow(workflow)?;
+    let workflow = Workflow::load(&path)?;
+    let server = match manifest {
+        Some(manifest) => McpServer::with_manifest(manifest.clone())?,
+        None => McpServer::new()?,
+    };
+    let inputs: Map<String, Value> = inputs
+        .into_iter()
+        .map(|(name, value)| {
+            let value = workflow.parse_input(&name, &value);
+            (name, value)
+        })
+        .collect();
+
+    let structured = OutputFormat::structured(output);
+    if structured.is_none() {
+        println!(
+            "{} Running workflow {} ({} steps)",
+            "→".cyan(),
+            workflow.name.yellow(),
+            workflow.steps.len()
+        );
+        println!();
+    }
+
+    let progress = Progress::default();
+    let observer = structured
+        .is_none()
+        .then_some(&progress as &dyn RunObserver);
+    let (run, job_id) = workflow.run_recorded(inputs, &server, observer).await?;
+
+    if let Some(output) = structured {
+        output.print(
+            "workflow.run",
+            &RunRecord {
+                job_id: job_id.map(|id| id.to_string()),
+                run: Some(&run),
+            },
+        )?;
+    } else {
+        println!();
+        if let Some(last) = run
+            .steps
+            .iter()
+            .rev()
+            .find_map(|step| step.output.as_deref())
+        {
+            println!("{}", "─".repeat(60).dimmed());
+            println!("{}", last.trim_end());
+            println!("{}", "─".repeat(60).dimmed());
+        }
+        if let Some(job_id) = job_id {
+            println!("  {} skill workflow status {}", "Details:".dimmed(), job_id);
+        }
+        if run.status == RunStatus::Succeeded {
+            println!("{} Workflow {} succeeded", "✓".green(), run.workflow);
+        }
+    }
+
+    if let Some(step) = run.failed_step() {
+        bail!(
+            "Workflow '{}' failed at step '{}': {}",
+            run.workflow,
+            step.id,
+            step.error.as_deref().unwrap_or("unknown error")
+        );
+    }
+    Ok(())
+}
+
+/// List workflows found in ./workflows and ~/.skill-engine/workflows.
+pub async fn list(output: Option<OutputFormat>) -> Result<()> {
+    let workflows = discover_workflows();
+
+    if let Some(output) = OutputFormat::structured(output) {
+        let workflows: Vec<_> = workflows
+            .iter()
+            .map(|(path, workflow)| {
+                serde_json::json!({
+                    "name": workflow.name,
+                    "description": workflow.description,
+                    "path": path,
+                    "inputs": workflow.inputs,
+                    "steps": workflow.steps.len(),
+                })
+            })
+            .collect();
+        return output.print("workflow.list", &workflows);
+    }
+
+    if workflows.is_empty() {
+        println!("{} No workflows found", "!".yellow());
+        println!();
+        println!("Add workflow files to ./workflows or ~/.skill-engine/workflows");
+        return Ok(());
+    }
+
+    println!();
+    for (path, workflow) in &workflows {
+        println!(
+            "  {} {}",
+            workflow.name.cyan().bold(),
+            format!("({} steps)", workflow.steps.len()).dimmed()
+        );
+        if let Some(description) = &workflow.description {
+            println!("    {}", description);
+        }
+        if !workflow.inputs.is_empty() {
+            let inputs: Vec<&str> = workflow.inputs.keys().map(String::as_str).collect();
+            println!("    {} {}", "inputs:".dimmed(), inputs.join(", "));
+        }
+        println!("    {}", path.display().to_string().dimmed());
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Show one recorded run step by step, or the most recent runs.
+pub async fn status(id: Option<&str>, limit: usize, output: Option<OutputFormat>) -> Result<()> {
+    let storage = create_storage(&JobConfig::sqlite_default())
+        .await
+        .context("Failed to open the job queue")?;
+
+    let Some(id) = id else {
+        let filter = JobFilter {
+            job_type: Some("workflow".to_string()),
+            limit: Some(limit),
+            descending: true,
+            ..Default::default()
+        };
+        let jobs = storage.list(filter).await?;
+
+        if let Some(output) = OutputFormat::structured(output) {
+            let runs: Vec<RunSummary> = jobs.iter().filter_map(RunSummary::from_job).collect();
+            return output.print("workflow.runs", &runs);
+        }
+
+        if jobs.is_empty() {
+            println!("{} No workflow runs recorded", "!".yellow());
+            return Ok(());
+        }
+        println!();
+        println!(
+            "  {:<36} {:<24} {:<10}  {}",
+            "ID".bold(),
+            "WORKFLOW".bold(),
+            "STATUS".bold(),
+            "STARTED".bold()
+        );
+        println!("  {}", "─".repeat(90).dimmed());
+        for run in jobs.iter().filter_map(RunSummary::from_job) {
+            println!(
+                "  {:<36} {:<24} {:<10}  {}",
+                run.job_id.dimmed(),
+                run.workflow.cyan(),
+                job_status_label(run.status),
+                run.started_at
+            );
+        }
+        println!();
+        return Ok(());
+    };
+
+    let job_id = id
+        .parse()
+        .with_context(|| format!("Invalid run ID '{}'", id))?;
+    let job = storage
+        .get(job_id)
+        .await?
+        .filter(|job| matches!(job.job_type, JobType::Workflow { .. }))
+        .with_context(|| format!("No workflow run '{}'", id))?;
+    let run: Option<WorkflowRun> = job
+        .result
+        .clone()
+        .and_then(|result| serde_json::from_value(result).ok());
+
+    if let Some(output) = OutputFormat::structured(output) {
+        return output.print(
+            "workflow.status",
+            &RunRecord {
+                job_id: Some(job.id.to_string()),
+                run: run.as_ref(),
+            },
+        );
+    }
+
+    let Some(run) = run else {
+        println!(
+            "{} Run {} has no recorded steps ({})",
+            "!".yellow(),
+            id,
+            job_status_label(job.status)
+        );
+        return Ok(());
+    };
+    println!();
+    println!(
+        "  {} {}  {}",
+        run.workflow.cyan().bold(),
+        run_status_label(run.status),
+        run.started_at
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+            .dimmed()
+    );
+    if !run.inputs.is_empty() {
+        println!(
+            "  {} {}",
+            "inputs:".dimmed(),
+            Value::Object(run.inputs.clone())
+        );
+    }
+    println!();
+    for step in &run.steps {
+        println!(
+            "  {:<10} {:<20} {:<30} {:>8}",
+            run_status_label(step.status),
+            step.id,
+            step.tool.dimmed(),
+            format!("{}ms", step.duration_ms)
+        );
+        if step.attempts > 1 {
+            println!("             {} attempts", step.attempts);
+        }
+        if let Some(error) = &step.error {
+            println!("             {}", error.red());
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Result of `workflow run` and `workflow status <id>`
+#[derive(Serialize)]
+struct RunRecord<'a> {
+    job_id: Option<String>,
+    run: Option<&'a WorkflowRun>,
+}
+
+/// One line of `workflow status`
+#[derive(Serialize)]
+struct RunSummary {
+    job_id: String,
+    workflow: String,
+    status: JobStatus,
+    started_at: String,
+}
+
+impl RunSummary {
+    fn from_job(job: &Job) -> Option<Self> {
+        let JobType::Workflow { name, .. } = &job.job_type else {
+            return None;
+        };
+        Some(Self {
+            job_id: job.id.to_string(),
+            workflow: name.clone(),
+            status: job.status,
+            started_at: job
+                .started_at
+                .unwrap_or(job.created_at)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+        })
+    }
+}
+
+/// Prints each step as it starts and finishes
+#[derive(Default)]
+struct Progress {
+    seen: Mutex<Vec<RunStatus>>,
+}
+
+#[async_trait]
+impl RunObserver for Progress {
+    async fn on_update(&self, run: &WorkflowRun) {
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        seen.resize(run.steps.len(), RunStatus::Pending);
+        for (step, last) in run.steps.iter().zip(seen.iter_mut()) {
+            if step.status == *last {
+                continue;
+            }
+            *last = step.status;
+            match step.status {
+                RunStatus::Running => {
+                    println!("  {} {} {}", "▸".cyan(), step.id, step.tool.dimmed())
+                }
+                RunStatus::Succeeded => println!(
+                    "  {} {} {}",
+                    "✓".green(),
+                    step.id,
+                    format!("({}ms)", step.duration_ms).dimmed()
+                ),
+                RunStatus::Failed => println!(
+                    "  {} {} {}",
+                    "✗".red(),
+                    step.id,
+                    step.error.as_deref().unwrap_or_default().red()
+                ),
+                RunStatus::Skipped => {
+                    println!("  {} {} {}", "○".dimmed(), step.id, "skipped".dimmed())
+                }
+                RunStatus::Pending => {}
+            }
+        }
+    }
+}
+
+fn run_status_label(status: RunStatus) -> ColoredString {
+    match status {
+        RunStatus::Succeeded => "succeeded".green(),
+        RunStatus::Failed => "failed".red(),
+        RunStatus::Skipped => "skipped".dimmed(),
+        RunStatus::Running => "running".cyan(),
+        RunStatus::Pending => "pending".dimmed(),
+    }
+}
+
+fn job_status_label(status: JobStatus) -> ColoredString {
+    match status {
+        JobStatus::Completed => "succeeded".green(),
+        JobStatus::Failed | JobStatus::Dead => "failed".red(),
+        JobStatus::Cancelled => "cancelled".yellow(),
+        JobStatus::Running => "running".cyan(),
+        JobStatus::Pending => "pending".dimmed(),
+    }
+}
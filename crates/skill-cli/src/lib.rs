@@ -6,3 +6,4 @@
 pub mod auth;
 pub mod commands;
 pub mod config;
+pub mod output;
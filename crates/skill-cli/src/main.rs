@@ -1,7 +1,10 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use colored::*;
+use skill_cli::commands::claude_bridge::ScriptFormat;
 use skill_cli::commands::config::ConfigAction;
+use skill_cli::commands::upgrade::Channel;
+use skill_cli::output::OutputFormat;
 use skill_cli::{auth, commands};
 
 #[derive(Parser)]
@@ -9,9 +12,20 @@ use skill_cli::{auth, commands};
 #[command(about = "Skill Engine - Universal WASM plugin system", long_about = None)]
 #[command(version)]
 struct Cli {
-    /// Path to skill manifest file (default: auto-detect .skill-engine.toml)
+    /// Manifest to layer over ~/.skill-engine/global.toml and the
+    /// auto-detected .skill-engine.toml (repeatable; later files win)
     #[arg(short = 'm', long = "manifest", global = true)]
-    manifest: Option<std::path::PathBuf>,
+    manifest: Vec<std::path::PathBuf>,
+
+    /// Output format for list, info, find, search bench, history, auth status
+    /// and config show; json and yaml wrap results in a versioned envelope
+    #[arg(long, value_enum, value_name = "FORMAT", env = "SKILL_OUTPUT")]
+    output: Option<OutputFormat>,
+
+    /// Locale for skill and tool descriptions (reads SKILL.<locale>.md
+    /// variants, falling back to SKILL.md) in info, find, serve and web
+    #[arg(long, global = true, value_name = "LOCALE", env = "SKILL_LOCALE")]
+    locale: Option<String>,
 
     #[command(subcommand)]
     command: Commands,
@@ -26,9 +40,24 @@ enum Commands {
     ///   skill install github:user/repo          # GitHub shorthand
     ///   skill install github:user/repo@v1.0.0   # Specific version
     ///   skill install https://github.com/u/r   # Full URL
+    ///   skill install --from-manifest           # Every skill in .skill-engine.toml
     Install {
         /// Skill source: local path, Git URL, or shorthand (github:user/repo)
-        source: String,
+        #[arg(required_unless_present = "from_manifest", conflicts_with = "from_manifest")]
+        source: Option<String>,
+
+        /// Install every skill the manifest declares, at the revisions in
+        /// its lockfile (.skill-engine.lock)
+        #[arg(long)]
+        from_manifest: bool,
+
+        /// Skills installed at once with --from-manifest
+        #[arg(short = 'j', long, default_value = "4", requires = "from_manifest")]
+        jobs: usize,
+
+        /// Ignore the lockfile and resolve every source again (--from-manifest)
+        #[arg(long, requires = "from_manifest")]
+        update: bool,
 
         /// Instance name for this installation
         #[arg(short = 'i', long)]
@@ -41,6 +70,22 @@ enum Commands {
         /// Generate AI-powered examples after installation
         #[arg(long)]
         enhance: bool,
+
+        /// Refuse skills that are not signed by a trusted key (see `skill trust`)
+        #[arg(long, env = "SKILL_REQUIRE_SIGNED")]
+        require_signed: bool,
+
+        /// SSH private key (deploy key) for private Git sources; implies SSH cloning
+        #[arg(long)]
+        ssh_key: Option<std::path::PathBuf>,
+
+        /// Shallow clone with this many commits of history
+        #[arg(long)]
+        depth: Option<u32>,
+
+        /// Skip initializing Git submodules
+        #[arg(long)]
+        no_submodules: bool,
     },
 
     /// Run a skill tool
@@ -51,6 +96,7 @@ enum Commands {
     ///   skill run ./path/to/skill.wasm hello name=World
     ///   skill run github:user/repo:tool arg=value
     ///   skill run aws:list --config region=eu-west-1
+    ///   skill run k8s:get resource=pods --jq '.items[] | select(.status.phase == "Running") | .metadata.name'
     Run {
         /// Skill name or name@instance:tool
         skill: String,
@@ -62,6 +108,14 @@ enum Commands {
         #[arg(short = 'c', long = "config", value_parser = parse_key_val)]
         config: Vec<(String, String)>,
 
+        /// jq filter applied to JSON output (pipes, select(), map(), ...)
+        #[arg(long)]
+        jq: Option<String>,
+
+        /// Read the tool's stdin from a file (`-` reads all of stdin up front)
+        #[arg(long, value_name = "PATH")]
+        stdin_file: Option<std::path::PathBuf>,
+
         /// Tool arguments
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
@@ -84,6 +138,10 @@ enum Commands {
         #[arg(short = 'c', long = "config", value_parser = parse_key_val)]
         config: Vec<(String, String)>,
 
+        /// Read the skill's stdin from a file (`-` reads all of stdin up front)
+        #[arg(long, value_name = "PATH")]
+        stdin_file: Option<std::path::PathBuf>,
+
         /// Arguments to pass to the skill (after --)
         #[arg(last = true)]
         args: Vec<String>,
@@ -159,6 +217,24 @@ enum Commands {
         /// Start web interface (trunk serve on port 8080)
         #[arg(long)]
         with_web: bool,
+
+        /// Also serve skills to A2A (Agent2Agent) clients: agent card at
+        /// /.well-known/agent.json, JSON-RPC at /a2a (implies --http)
+        #[arg(long)]
+        a2a: bool,
+
+        /// Also serve the REST API and web UI on the same port, sharing the
+        /// skill engine with MCP
+        #[arg(long)]
+        api: bool,
+
+        /// Keep serving MCP on stdio alongside --http, --a2a or --api; the
+        /// process exits when the stdio client disconnects
+        #[arg(long)]
+        stdio: bool,
+
+        #[command(flatten)]
+        tls: commands::web::TlsArgs,
     },
 
     /// Show skill information
@@ -168,9 +244,19 @@ enum Commands {
     },
 
     /// Search for skills in registry
+    ///
+    /// Examples:
+    ///   skill search kubernetes
+    ///   skill search bench queries.toml                      # Evaluate search quality
+    ///   skill search bench queries.toml --compare new.toml   # A/B two search configs
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Search {
         /// Search query
-        query: String,
+        #[arg(required = true)]
+        query: Option<String>,
+
+        #[command(subcommand)]
+        action: Option<SearchAction>,
     },
 
     /// Find tools semantically using AI-powered vector search
@@ -193,6 +279,10 @@ enum Commands {
         /// Output format (rich, json, compact)
         #[arg(short = 'f', long, default_value = "rich")]
         format: String,
+
+        /// Show how each result was scored (dense, BM25, fusion, rerank, matched terms)
+        #[arg(long)]
+        explain: bool,
     },
 
     /// Enhance skills with AI-generated examples
@@ -204,6 +294,11 @@ enum Commands {
     ///   skill enhance kubernetes        # Enhance a specific skill
     ///   skill enhance --all             # Enhance all installed skills
     ///   skill enhance --all --stream    # With streaming progress
+    ///   skill enhance --all --only-failed  # Retry tools whose examples failed
+    ///   skill enhance --all --evaluate     # Dry-run a sample and report quality
+    ///
+    /// Tools whose examples were generated from unchanged documentation are
+    /// skipped unless --force is given.
     Enhance {
         /// Skill name to enhance
         skill: Option<String>,
@@ -219,6 +314,27 @@ enum Commands {
         /// Number of examples to generate per tool
         #[arg(short = 'n', long, default_value = "5")]
         examples: usize,
+
+        /// Only regenerate tools whose last run failed validation
+        #[arg(long, conflicts_with = "force")]
+        only_failed: bool,
+
+        /// Regenerate examples even if cached ones are fresh, ignoring AI budget limits
+        #[arg(long)]
+        force: bool,
+
+        /// Regenerate cached examples older than this many days
+        #[arg(long, value_name = "DAYS")]
+        max_age: Option<u64>,
+
+        /// Dry-run a sample of generated examples and record their quality
+        /// instead of generating
+        #[arg(long, conflicts_with_all = ["only_failed", "force", "max_age"])]
+        evaluate: bool,
+
+        /// Examples to dry-run per tool with --evaluate
+        #[arg(long, default_value = "3", requires = "evaluate")]
+        sample: usize,
     },
 
     /// Configure search and RAG settings
@@ -255,6 +371,22 @@ enum Commands {
         /// Enable reranking for better precision
         #[arg(long)]
         rerank: Option<bool>,
+
+        /// Default tool execution timeout in seconds
+        #[arg(long)]
+        timeout: Option<u64>,
+
+        /// Tool executions a server runs at once
+        #[arg(long)]
+        max_concurrent: Option<usize>,
+
+        /// Record executions in history
+        #[arg(long)]
+        history: Option<bool>,
+
+        /// Executions a server keeps in its in-memory history
+        #[arg(long)]
+        max_history: Option<usize>,
     },
 
     /// Generate SKILL.md documentation template
@@ -293,12 +425,16 @@ enum Commands {
 
     /// Upgrade skill CLI to the latest version
     ///
-    /// Downloads and installs the latest release from GitHub.
+    /// Downloads the latest release from GitHub, verifies its checksum (and
+    /// signature, once the release key is trusted) and installs it. Homebrew,
+    /// Scoop and cargo installs are upgraded through the package manager.
     ///
     /// Examples:
     ///   skill upgrade                   # Upgrade to latest version
     ///   skill upgrade --check           # Check for updates without installing
     ///   skill upgrade --force           # Force reinstall current version
+    ///   skill upgrade --channel beta    # Include prereleases
+    ///   skill upgrade --version 0.3.2   # Install a specific version, even older
     Upgrade {
         /// Check for updates without installing
         #[arg(short = 'c', long)]
@@ -307,6 +443,14 @@ enum Commands {
         /// Force reinstall even if already on latest version
         #[arg(short = 'f', long)]
         force: bool,
+
+        /// Release channel to follow
+        #[arg(long, value_enum, default_value_t = Channel::Stable)]
+        channel: Channel,
+
+        /// Install this version (x.y.z) instead of the latest, downgrading if older
+        #[arg(long, value_name = "VERSION", conflicts_with = "channel")]
+        version: Option<String>,
     },
 
     /// Start the web interface with embedded UI
@@ -319,6 +463,9 @@ enum Commands {
     ///   skill web --port 8080           # Custom port
     ///   skill web --open                # Open browser automatically
     ///   skill web --host 0.0.0.0        # Listen on all interfaces
+    ///   skill web --host 0.0.0.0 --tls-cert cert.pem --tls-key key.pem
+    ///   skill web --host 0.0.0.0 --acme-domain skills.example.com --port 443
+    ///   skill web --namespace team-a=./teams/a --namespace team-b=./teams/b
     Web {
         /// Port to run the web server on
         #[arg(short, long, default_value = "3000")]
@@ -331,6 +478,37 @@ enum Commands {
         /// Open browser automatically after starting
         #[arg(short, long)]
         open: bool,
+
+        /// Serve an isolated namespace from a directory (NAME=DIR, repeatable)
+        #[arg(long = "namespace", value_name = "NAME=DIR")]
+        namespaces: Vec<skill_http::NamespaceConfig>,
+
+        #[command(flatten)]
+        tls: commands::web::TlsArgs,
+    },
+
+    /// Show past executions and re-run them
+    ///
+    /// Executions made through the HTTP server and web UI are recorded.
+    ///
+    /// Examples:
+    ///   skill history                       # Recent executions
+    ///   skill history --skill kubernetes    # Executions of one skill
+    ///   skill history show <id>             # Details and resource usage of one
+    ///   skill history rerun <id> --diff     # Re-run and compare output
+    ///   skill history prune --max-age-days 30  # Delete old history now
+    #[command(args_conflicts_with_subcommands = true)]
+    History {
+        /// Number of executions to show
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: usize,
+
+        /// Only show executions of this skill
+        #[arg(short = 's', long)]
+        skill: Option<String>,
+
+        #[command(subcommand)]
+        action: Option<HistoryAction>,
     },
 
     /// Authenticate with external services (OAuth2, API keys, etc.)
@@ -339,12 +517,313 @@ enum Commands {
     ///   skill auth login github           # OAuth2 Device Flow
     ///   skill auth login aws              # AWS IAM credentials
     ///   skill auth login openai           # API key
+    ///   skill auth login kubernetes --context prod-cluster --skill kubernetes --instance prod
     ///   skill auth status                 # Check auth status
     ///   skill auth logout github          # Remove credentials
     Auth {
         #[command(subcommand)]
         action: AuthAction,
     },
+
+    /// Manage local embedding and reranker models
+    ///
+    /// Pre-downloads FastEmbed models to ~/.skill-engine/models for offline
+    /// and air-gapped use. Set SKILL_OFFLINE=1 to refuse network downloads.
+    ///
+    /// Examples:
+    ///   skill models list                       # Show models and status
+    ///   skill models download all-minilm        # Download a model
+    ///   skill models download --all             # Download every model
+    ///   skill models verify                     # Re-check checksums
+    ///   skill models remove bge-large           # Delete a model
+    Models {
+        #[command(subcommand)]
+        action: ModelsAction,
+    },
+
+    /// Manage the semantic search index
+    ///
+    /// Examples:
+    ///   skill index snapshot save index.snapshot   # Embed all tools and save
+    ///   skill index snapshot load index.snapshot   # Restore into the vector store
+    Index {
+        #[command(subcommand)]
+        action: IndexAction,
+    },
+
+    /// Run a background engine that CLI commands share
+    ///
+    /// Keeps the embedding model, search index and compiled skills loaded,
+    /// so `skill find` and `skill run` skip the cold start.
+    ///
+    /// Examples:
+    ///   skill daemon start                      # Start in the background
+    ///   skill daemon status                     # Show pid, uptime and index
+    ///   skill daemon stop                       # Stop after in-flight runs
+    Daemon {
+        #[command(subcommand)]
+        action: DaemonAction,
+    },
+
+    /// Sign every file in a skill directory with an ed25519 key
+    ///
+    /// Writes skill.sig into the skill directory. Create a key with
+    /// `skill trust keygen`.
+    ///
+    /// Examples:
+    ///   skill sign ./my-skill --key ~/.skill-engine/publisher.key
+    Sign {
+        /// Skill directory containing the artifacts to sign
+        path: std::path::PathBuf,
+
+        /// Private key file
+        #[arg(short, long)]
+        key: std::path::PathBuf,
+    },
+
+    /// Lint a skill directory or manifest
+    ///
+    /// Checks SKILL.md frontmatter, allowed-tools, documented tools against
+    /// the tools the built WASM component exports, manifest keys and types,
+    /// and dangling instance/dependency references. Exits non-zero on errors.
+    ///
+    /// Examples:
+    ///   skill validate                          # SKILL.md and manifest in .
+    ///   skill validate ./my-skill
+    ///   skill validate .skill-engine.toml --format json
+    ///   skill validate --strict                 # Fail on warnings too
+    Validate {
+        /// Skill directory, SKILL.md, or manifest file
+        #[arg(default_value = ".")]
+        path: std::path::PathBuf,
+
+        /// Output format (text, json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Treat warnings as errors
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Package a built skill into a distributable .skillpkg bundle
+    ///
+    /// Bundles skill.wasm, SKILL.md, skill.yaml, skill.sig, scripts/ and a
+    /// manifest fragment with an integrity manifest (integrity.json). Install
+    /// the result with `skill install <file-or-url>`.
+    ///
+    /// Examples:
+    ///   skill package ./my-skill                  # Writes ./my-skill-1.0.0.skillpkg
+    ///   skill package ./my-skill --output dist/
+    Package {
+        /// Skill directory (must contain a built .wasm)
+        #[arg(default_value = ".")]
+        path: std::path::PathBuf,
+
+        /// Directory to write the package to
+        #[arg(short, long, default_value = ".")]
+        output: std::path::PathBuf,
+    },
+
+    /// Manage trusted publisher keys for signed skills
+    ///
+    /// Keys live in ~/.skill-engine/trust. `skill install` verifies skill.sig
+    /// against them; use --require-signed to reject unsigned skills.
+    ///
+    /// Examples:
+    ///   skill trust keygen --output publisher.key   # Create a signing key
+    ///   skill trust add acme <public-key>           # Trust a publisher
+    ///   skill trust list                            # Show trusted keys
+    ///   skill trust remove acme                     # Revoke trust
+    Trust {
+        #[command(subcommand)]
+        action: TrustAction,
+    },
+
+    /// Inspect the merged manifest
+    ///
+    /// Manifests are layered, later ones taking precedence:
+    /// ~/.skill-engine/global.toml, then the project .skill-engine.toml, then
+    /// each --manifest in order.
+    ///
+    /// Examples:
+    ///   skill manifest effective                    # Merged view with origins
+    ///   skill -m ci.toml manifest effective -f json
+    Manifest {
+        #[command(subcommand)]
+        action: ManifestAction,
+    },
+
+    /// Run multi-step workflows that chain skill tools
+    ///
+    /// Workflow files (YAML or TOML) are looked up by path, or by name in
+    /// ./workflows and ~/.skill-engine/workflows.
+    ///
+    /// Examples:
+    ///   skill workflow run deploy.yaml -i namespace=prod
+    ///   skill workflow list                 # Available workflows
+    ///   skill workflow status               # Recent runs
+    ///   skill workflow status <id>          # Per-step status of a run
+    Workflow {
+        #[command(subcommand)]
+        action: WorkflowAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum ManifestAction {
+    /// Print the merged manifest and which file each skill came from
+    Effective {
+        /// Output format (toml, json)
+        #[arg(short, long, default_value = "toml")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum WorkflowAction {
+    /// Run a workflow
+    Run {
+        /// Workflow file or name
+        workflow: String,
+
+        /// Workflow inputs (key=value; parsed as JSON unless the input is a string)
+        #[arg(short = 'i', long = "input", value_parser = parse_key_val)]
+        inputs: Vec<(String, String)>,
+    },
+
+    /// List available workflows
+    List,
+
+    /// Show recent runs, or one run's steps
+    Status {
+        /// Run ID (printed by `skill workflow run`)
+        id: Option<String>,
+
+        /// Number of runs to show
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum TrustAction {
+    /// Trust a publisher's public key
+    Add {
+        /// Local name for the key
+        name: String,
+
+        /// Hex-encoded public key, or a file containing it
+        key: String,
+    },
+
+    /// Remove a trusted key by name or fingerprint
+    #[command(alias = "rm")]
+    Remove {
+        /// Key name or fingerprint
+        name: String,
+    },
+
+    /// List trusted keys
+    #[command(alias = "ls")]
+    List {
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+
+    /// Generate an ed25519 signing key for publishing skills
+    Keygen {
+        /// Where to write the private key
+        #[arg(short, long, default_value = "skill-signing.key")]
+        output: std::path::PathBuf,
+
+        /// Overwrite an existing key file
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ModelsAction {
+    /// List available models and whether they are downloaded
+    #[command(alias = "ls")]
+    List {
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+
+    /// Download models with checksum verification
+    Download {
+        /// Model names (e.g. all-minilm, bge-small, bge-reranker-base)
+        names: Vec<String>,
+
+        /// Download every model in the catalog
+        #[arg(long)]
+        all: bool,
+
+        /// Re-download even if already present
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Remove downloaded models
+    #[command(alias = "rm")]
+    Remove {
+        /// Model names
+        names: Vec<String>,
+
+        /// Remove every downloaded model
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Verify downloaded model files against their checksums
+    Verify {
+        /// Model names (default: all downloaded models)
+        names: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum IndexAction {
+    /// Save or restore a pre-built index, e.g. for fast startup in CI
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum SnapshotAction {
+    /// Index every installed tool and write the index to a file
+    Save {
+        /// Snapshot file to write
+        path: std::path::PathBuf,
+    },
+
+    /// Restore a snapshot into the configured vector store
+    Load {
+        /// Snapshot file to read
+        path: std::path::PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum DaemonAction {
+    /// Start the daemon
+    Start {
+        /// Run in this process instead of in the background
+        #[arg(long)]
+        foreground: bool,
+    },
+
+    /// Stop the daemon
+    Stop,
+
+    /// Show whether the daemon is running
+    Status,
 }
 
 #[derive(Subcommand)]
@@ -410,12 +889,80 @@ enum ClaudeAction {
         #[arg(long)]
         no_scripts: bool,
 
+        /// Kind of scripts to generate (default: cmd on Windows, bash elsewhere)
+        #[arg(long, value_enum)]
+        script_format: Option<ScriptFormat>,
+
         /// Generate for project-level Claude config (current directory)
         #[arg(short = 'p', long)]
         project: bool,
     },
 }
 
+#[derive(Subcommand)]
+enum SearchAction {
+    /// Benchmark search quality against a labeled query set
+    ///
+    /// Reports recall@k, MRR and NDCG@k. The suite is a TOML or JSON file of
+    /// queries and the tools they should return (`skill:tool`).
+    Bench {
+        /// Path to the labeled query suite (.toml or .json)
+        suite: std::path::PathBuf,
+
+        /// Cutoff for recall and NDCG
+        #[arg(short = 'k', long, default_value = "5")]
+        k: usize,
+
+        /// Search config to evaluate (default: ~/.skill-engine/search.toml)
+        #[arg(short = 'c', long)]
+        config: Option<std::path::PathBuf>,
+
+        /// Second search config to A/B against the first
+        #[arg(long)]
+        compare: Option<std::path::PathBuf>,
+
+        /// Output format (table, json)
+        #[arg(short = 'f', long, default_value = "table")]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum HistoryAction {
+    /// Show one execution: arguments, environment, resource usage and output
+    Show {
+        /// Execution ID (see `skill history`)
+        id: String,
+    },
+
+    /// Re-run a past execution with the same arguments
+    Rerun {
+        /// Execution ID (see `skill history`)
+        id: String,
+
+        /// Show how the output changed from the original run; exits 1 if it did
+        #[arg(long)]
+        diff: bool,
+    },
+
+    /// Apply the retention policy now and compact the databases
+    ///
+    /// Limits default to the manifest's [history] section; 0 disables one.
+    Prune {
+        /// Keep at most this many rows per table
+        #[arg(long)]
+        max_rows: Option<u64>,
+
+        /// Delete rows older than this many days
+        #[arg(long)]
+        max_age_days: Option<u64>,
+
+        /// Shrink each database to at most this many megabytes
+        #[arg(long)]
+        max_db_size_mb: Option<u64>,
+    },
+}
+
 #[derive(Subcommand)]
 enum AuthAction {
     /// Login to an authentication provider
@@ -434,12 +981,24 @@ enum AuthAction {
         /// OAuth2 scopes to request (comma-separated)
         #[arg(long, value_delimiter = ',')]
         scopes: Option<Vec<String>>,
+
+        /// Kubeconfig to pin (kubernetes provider; default: $KUBECONFIG or ~/.kube/config)
+        #[arg(long)]
+        kubeconfig: Option<std::path::PathBuf>,
+
+        /// Kubeconfig context to pin (kubernetes provider)
+        #[arg(long)]
+        context: Option<String>,
     },
 
     /// Show authentication status
     Status {
         /// Filter by provider
         provider: Option<String>,
+
+        /// Print machine-readable JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Logout from a provider (revoke credentials)
@@ -471,54 +1030,97 @@ fn parse_key_val(s: &str) -> Result<(String, String), String> {
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    // The runtime reads the locale from the environment wherever it loads
+    // SKILL.md, including the per-session MCP servers `serve --http` builds
+    if let Some(locale) = &cli.locale {
+        std::env::set_var(skill_runtime::LOCALE_ENV, locale);
+    }
+
     // Initialize tracing - IMPORTANT: Write to stderr for MCP stdio compatibility
-    // For MCP stdio mode, we must never write to stdout as it's reserved for JSON-RPC
-    tracing_subscriber::fmt()
-        .with_writer(std::io::stderr)
-        .init();
+    // For MCP stdio mode, we must never write to stdout as it's reserved for JSON-RPC.
+    // Events of MCP tool calls also go to the calling client, down to the
+    // level it asks for
+    {
+        use tracing_subscriber::{filter::LevelFilter, prelude::*};
+
+        tracing_subscriber::registry()
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_writer(std::io::stderr)
+                    .with_filter(LevelFilter::INFO),
+            )
+            .with(skill_mcp::McpLogLayer.with_filter(LevelFilter::DEBUG))
+            .init();
+    }
 
-    // Load manifest if specified or auto-detect
-    let manifest = commands::manifest::load_manifest(cli.manifest.as_deref())?;
+    // Load manifest if specified or auto-detect (`validate` reports parse errors itself)
+    let manifest = if matches!(cli.command, Commands::Validate { .. }) {
+        None
+    } else {
+        commands::manifest::load_manifest(&cli.manifest)?
+    };
 
     let result = match cli.command {
-        Commands::Install { source, instance, force, enhance } => {
-            commands::install::execute(&source, instance.as_deref(), force, enhance).await
+        Commands::Install { source, from_manifest, jobs, update, instance, force, enhance, require_signed, ssh_key, depth, no_submodules } => {
+            match source {
+                Some(source) if !from_manifest => {
+                    let git = skill_runtime::GitSourceConfig {
+                        ssh_key,
+                        depth,
+                        submodules: !no_submodules,
+                        ..Default::default()
+                    };
+                    commands::install::execute(&source, instance.as_deref(), force, enhance, require_signed, git).await
+                }
+                _ => commands::install::from_manifest(manifest.as_ref(), jobs, update, force, require_signed).await,
+            }
         }
-        Commands::Run { skill, tool, config, args } => {
-            commands::run::execute(&skill, tool.as_deref(), &config, &args, manifest.as_ref()).await
+        Commands::Run { skill, tool, config, jq, stdin_file, args } => {
+            let stdin = commands::run::stdin_source(stdin_file.as_deref())?;
+            commands::run::execute(&skill, tool.as_deref(), &config, &args, jq.as_deref(), &stdin, manifest.as_ref()).await
         }
-        Commands::Exec { skill, config, args } => {
-            commands::exec::execute(&skill, &config, &args, manifest.as_ref()).await
+        Commands::Exec { skill, config, stdin_file, args } => {
+            let stdin = commands::run::stdin_source(stdin_file.as_deref())?;
+            commands::exec::execute(&skill, &config, &args, &stdin, manifest.as_ref()).await
         }
         Commands::List { format } => {
-            commands::list::execute(&format, manifest.as_ref()).await
+            commands::list::execute(&format, cli.output, manifest.as_ref()).await
         }
         Commands::Remove { skill, instance, force } => {
             commands::remove::execute(&skill, instance.as_deref(), force).await
         }
         Commands::Config { skill, instance, action } => {
-            commands::config::execute(&skill, instance.as_deref(), action).await
+            commands::config::execute(&skill, instance.as_deref(), action, cli.output).await
         }
         Commands::Init { name, template, list } => {
             commands::init::execute(name.as_deref(), template.as_deref(), list).await
         }
-        Commands::Serve { skill, port, host, http, with_web } => {
-            commands::serve::execute(skill.as_deref(), &host, port, http, with_web).await
+        Commands::Serve { skill, port, host, http, with_web, a2a, api, stdio, tls } => {
+            let transports = commands::serve::Transports { stdio, http, a2a, api };
+            commands::serve::execute(skill.as_deref(), &host, port, transports, with_web, &tls, manifest).await
         }
         Commands::Info { skill } => {
-            commands::info::execute(&skill, manifest.as_ref()).await
+            commands::info::execute(&skill, cli.output, manifest.as_ref()).await
         }
-        Commands::Search { query } => {
-            commands::search::execute(&query).await
+        Commands::Search { query, action } => {
+            match action {
+                Some(SearchAction::Bench { suite, k, config, compare, format }) => {
+                    commands::search::bench(&suite, k, config.as_deref(), compare.as_deref(), &format, cli.output).await
+                }
+                None => {
+                    commands::search::execute(query.as_deref().unwrap_or_default()).await
+                }
+            }
         }
-        Commands::Find { query, top_k, provider, model, format } => {
-            commands::find::execute(&query, top_k, &provider, model.as_deref(), &format).await
+        Commands::Find { query, top_k, provider, model, format, explain } => {
+            commands::find::execute(&query, top_k, &provider, model.as_deref(), &format, explain, cli.output).await
         }
-        Commands::Enhance { skill, all, stream, examples } => {
-            commands::enhance::execute(skill.as_deref(), all, stream, examples).await
+        Commands::Enhance { skill, all, stream, examples, only_failed, force, max_age, evaluate, sample } => {
+            commands::enhance::execute(skill.as_deref(), all, stream, examples, only_failed, force, max_age, evaluate, sample).await
         }
-        Commands::Setup { show, reset, provider, model, hybrid, rerank } => {
-            commands::setup::execute(show, reset, provider.as_deref(), model.as_deref(), hybrid, rerank).await
+        Commands::Setup { show, reset, provider, model, hybrid, rerank, timeout, max_concurrent, history, max_history } => {
+            let settings = commands::setup::SettingsOptions { timeout, max_concurrent, history, max_history };
+            commands::setup::execute(show, reset, provider.as_deref(), model.as_deref(), hybrid, rerank, settings).await
         }
         Commands::InitSkill { skill_name, output, tools, from_installed } => {
             if from_installed {
@@ -538,21 +1140,69 @@ async fn main() -> Result<()> {
                 ClaudeAction::Remove { global, name } => {
                     commands::claude::remove(global, name.as_deref()).await
                 }
-                ClaudeAction::Generate { skill, output, force, dry_run, no_scripts, project } => {
-                    commands::claude::generate(skill, output, force, dry_run, no_scripts, project).await
+                ClaudeAction::Generate {
+                    skill,
+                    output,
+                    force,
+                    dry_run,
+                    no_scripts,
+                    script_format,
+                    project,
+                } => {
+                    commands::claude::generate(
+                        skill,
+                        output,
+                        force,
+                        dry_run,
+                        no_scripts,
+                        script_format.unwrap_or_else(ScriptFormat::native),
+                        project,
+                    )
+                    .await
                 }
             }
         }
-        Commands::Upgrade { check, force } => {
-            commands::upgrade::execute(force, check).await
+        Commands::Upgrade {
+            check,
+            force,
+            channel,
+            version,
+        } => commands::upgrade::execute(force, check, channel, version).await,
+        Commands::History { limit, skill, action } => {
+            match action {
+                Some(HistoryAction::Show { id }) => {
+                    commands::history::show(&id, cli.output).await
+                }
+                Some(HistoryAction::Rerun { id, diff }) => {
+                    commands::history::rerun(&id, diff, cli.output, manifest.as_ref()).await
+                }
+                Some(HistoryAction::Prune { max_rows, max_age_days, max_db_size_mb }) => {
+                    let mut retention = manifest.as_ref().map(|m| m.history).unwrap_or_default();
+                    retention.max_rows = max_rows.unwrap_or(retention.max_rows);
+                    retention.max_age_days = max_age_days.unwrap_or(retention.max_age_days);
+                    retention.max_db_size_mb = max_db_size_mb.unwrap_or(retention.max_db_size_mb);
+                    commands::history::prune(retention, cli.output).await
+                }
+                None => {
+                    commands::history::list(limit, skill.as_deref(), cli.output).await
+                }
+            }
         }
         Commands::Auth { action } => {
             match action {
-                AuthAction::Login { provider, skill, instance, scopes } => {
-                    auth::login(&provider, skill.as_deref(), instance.as_deref(), scopes).await
+                AuthAction::Login { provider, skill, instance, scopes, kubeconfig, context } => {
+                    auth::login(
+                        &provider,
+                        skill.as_deref(),
+                        instance.as_deref(),
+                        scopes,
+                        kubeconfig.as_deref(),
+                        context.as_deref(),
+                    )
+                    .await
                 }
-                AuthAction::Status { provider } => {
-                    auth::status(provider.as_deref()).await
+                AuthAction::Status { provider, json } => {
+                    auth::status(provider.as_deref(), json, cli.output).await
                 }
                 AuthAction::Logout { provider, skill, instance } => {
                     auth::logout(&provider, skill.as_deref(), instance.as_deref()).await
@@ -562,8 +1212,96 @@ async fn main() -> Result<()> {
                 }
             }
         }
-        Commands::Web { port, host, open } => {
-            commands::web::execute(&host, port, open).await
+        Commands::Models { action } => {
+            match action {
+                ModelsAction::List { format } => {
+                    commands::models::list(&format).await
+                }
+                ModelsAction::Download { names, all, force } => {
+                    commands::models::download(&names, all, force).await
+                }
+                ModelsAction::Remove { names, all } => {
+                    commands::models::remove(&names, all).await
+                }
+                ModelsAction::Verify { names } => {
+                    commands::models::verify(&names).await
+                }
+            }
+        }
+        Commands::Index { action } => {
+            match action {
+                IndexAction::Snapshot { action: SnapshotAction::Save { path } } => {
+                    commands::index::snapshot_save(&path).await
+                }
+                IndexAction::Snapshot { action: SnapshotAction::Load { path } } => {
+                    commands::index::snapshot_load(&path).await
+                }
+            }
+        }
+        #[cfg(unix)]
+        Commands::Daemon { action } => {
+            match action {
+                DaemonAction::Start { foreground } => {
+                    commands::daemon::start(foreground).await
+                }
+                DaemonAction::Stop => {
+                    commands::daemon::stop().await
+                }
+                DaemonAction::Status => {
+                    commands::daemon::status().await
+                }
+            }
+        }
+        #[cfg(not(unix))]
+        Commands::Daemon { .. } => {
+            anyhow::bail!("skill daemon needs Unix domain sockets, which this platform doesn't have")
+        }
+        Commands::Sign { path, key } => {
+            commands::trust::sign(&path, &key).await
+        }
+        Commands::Validate { path, format, strict } => {
+            let manifest_path = cli.manifest.last().map(std::path::PathBuf::as_path);
+            commands::validate::execute(&path, manifest_path, &format, strict).await
+        }
+        Commands::Package { path, output } => {
+            commands::package::execute(&path, &output).await
+        }
+        Commands::Trust { action } => {
+            match action {
+                TrustAction::Add { name, key } => {
+                    commands::trust::add(&name, &key).await
+                }
+                TrustAction::Remove { name } => {
+                    commands::trust::remove(&name).await
+                }
+                TrustAction::List { format } => {
+                    commands::trust::list(&format).await
+                }
+                TrustAction::Keygen { output, force } => {
+                    commands::trust::keygen(&output, force).await
+                }
+            }
+        }
+        Commands::Manifest { action } => {
+            match action {
+                ManifestAction::Effective { format } => {
+                    commands::manifest::effective(manifest.as_ref(), &format).await
+                }
+            }
+        }
+        Commands::Workflow { action } => {
+            match action {
+                WorkflowAction::Run { workflow, inputs } => {
+                    commands::workflow::run(&workflow, inputs, cli.output, manifest.as_ref()).await
+                }
+                WorkflowAction::List => commands::workflow::list(cli.output).await,
+                WorkflowAction::Status { id, limit } => {
+                    commands::workflow::status(id.as_deref(), limit, cli.output).await
+                }
+            }
+        }
+        Commands::Web { port, host, open, namespaces, tls } => {
+            commands::web::execute(&host, port, open, namespaces, &tls).await
         }
     };
 
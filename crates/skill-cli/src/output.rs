@@ -0,0 +1,98 @@
+//! Machine-readable command output.
+//!
+//! `skill --output json|yaml <command>` prints a command's result wrapped in
+//! a versioned envelope, so scripts and CI can rely on its shape:
+//!
+//! ```json
+//! { "schema_version": 1, "kind": "list", "data": { ... } }
+//! ```
+//!
+//! `schema_version` is bumped whenever a command's `data` changes
+//! incompatibly. `--output table` (the default) keeps the human-readable
+//! output.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// Version of the `--output json|yaml` envelope and payloads
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Output format selected with `--output`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable tables and text
+    #[default]
+    Table,
+    /// JSON envelope
+    Json,
+    /// YAML envelope
+    Yaml,
+}
+
+/// Versioned wrapper around a command's result.
+#[derive(Debug, Serialize)]
+pub struct Envelope<'a, T: Serialize> {
+    /// [`SCHEMA_VERSION`]
+    pub schema_version: u32,
+    /// Command that produced the data, e.g. `list` or `auth.status`
+    pub kind: &'a str,
+    /// Command result
+    pub data: &'a T,
+}
+
+impl OutputFormat {
+    /// The format to print with, if `output` asks for JSON or YAML.
+    pub fn structured(output: Option<Self>) -> Option<Self> {
+        output.filter(|format| *format != Self::Table)
+    }
+
+    /// Render `data` as a `kind` envelope.
+    pub fn render<T: Serialize>(self, kind: &str, data: &T) -> Result<String> {
+        let envelope = Envelope {
+            schema_version: SCHEMA_VERSION,
+            kind,
+            data,
+        };
+        Ok(match self {
+            Self::Yaml => serde_yaml::to_string(&envelope)?,
+            Self::Json | Self::Table => serde_json::to_string_pretty(&envelope)? + "\n",
+        })
+    }
+
+    /// Print `data` as a `kind` envelope to stdout.
+    pub fn print<T: Serialize>(self, kind: &str, data: &T) -> Result<()> {
+        print!("{}", self.render(kind, data)?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_envelope() {
+        let data = serde_json::json!({ "skills": ["kubernetes"] });
+
+        let json: serde_json::Value =
+            serde_json::from_str(&OutputFormat::Json.render("list", &data).unwrap()).unwrap();
+        assert_eq!(json["schema_version"], SCHEMA_VERSION);
+        assert_eq!(json["kind"], "list");
+        assert_eq!(json["data"]["skills"][0], "kubernetes");
+
+        let yaml: serde_json::Value =
+            serde_yaml::from_str(&OutputFormat::Yaml.render("list", &data).unwrap()).unwrap();
+        assert_eq!(yaml, json);
+    }
+
+    #[test]
+    fn test_structured() {
+        assert_eq!(OutputFormat::structured(None), None);
+        assert_eq!(OutputFormat::structured(Some(OutputFormat::Table)), None);
+        assert_eq!(
+            OutputFormat::structured(Some(OutputFormat::Yaml)),
+            Some(OutputFormat::Yaml)
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//! Windows Integration Tests
+//!
+//! Run by the Windows CI job:
+//! - `skill claude generate` writes `.cmd` wrapper scripts that cmd.exe runs
+//! - Native commands find CLIs installed as `.cmd` shims through `PATHEXT`
+//! - Credentials round-trip through Windows Credential Manager
+
+#![cfg(windows)]
+#![allow(deprecated)] // cargo_bin is deprecated but still functional
+
+use assert_cmd::Command;
+use skill_runtime::{find_program, CredentialStore, NativeCommand};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const SKILL_MD: &str = r#"---
+name: hello
+description: Simple hello world skill for testing basic functionality
+---
+
+# Hello Skill
+
+## Available Tools
+
+### greet
+
+Greet a user by name
+
+**Parameters:**
+- `name` (string, required): Name of the person to greet
+"#;
+
+/// Test helper: A manifest with one native skill documented in SKILL.md
+fn create_skill(dir: &Path) {
+    fs::write(
+        dir.join(".skill-engine.toml"),
+        r#"
+[skills.hello]
+source = "./hello"
+runtime = "native"
+description = "Simple hello world skill"
+"#,
+    )
+    .unwrap();
+    fs::create_dir_all(dir.join("hello")).unwrap();
+    fs::write(dir.join("hello").join("SKILL.md"), SKILL_MD).unwrap();
+}
+
+#[test]
+fn test_generate_writes_cmd_scripts() {
+    let temp = TempDir::new().unwrap();
+    create_skill(temp.path());
+    let output_dir = temp.path().join("skills");
+
+    Command::cargo_bin("skill")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("claude")
+        .arg("generate")
+        .arg("--output")
+        .arg(&output_dir)
+        .assert()
+        .success();
+
+    let scripts = output_dir.join("hello").join("scripts");
+    assert!(scripts.join("greet.cmd").exists());
+    assert!(!scripts.join("greet.sh").exists());
+
+    let skill_md = fs::read_to_string(output_dir.join("hello").join("SKILL.md")).unwrap();
+    assert!(skill_md.contains(r"scripts\greet.cmd name=<value>"));
+
+    // Help is answered by the script itself, without calling skill
+    let output = std::process::Command::new("cmd")
+        .arg("/C")
+        .arg(scripts.join("greet.cmd"))
+        .arg("--help")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(r"Usage: scripts\greet.cmd name=<value>"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("Greet a user by name"), "{}", stdout);
+}
+
+#[test]
+fn test_generate_bash_scripts_on_request() {
+    let temp = TempDir::new().unwrap();
+    create_skill(temp.path());
+    let output_dir = temp.path().join("skills");
+
+    Command::cargo_bin("skill")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("claude")
+        .arg("generate")
+        .arg("--script-format")
+        .arg("bash")
+        .arg("--output")
+        .arg(&output_dir)
+        .assert()
+        .success();
+
+    let script = fs::read_to_string(output_dir.join("hello/scripts/greet.sh")).unwrap();
+    assert!(script.starts_with("#!/bin/bash\n"));
+}
+
+#[tokio::test]
+async fn test_native_command_runs_cmd_shim() {
+    let bin = TempDir::new().unwrap();
+    fs::write(
+        bin.path().join("skill-test-shim.cmd"),
+        "@echo off\r\necho shim %*\r\n",
+    )
+    .unwrap();
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    let path = std::env::join_paths(
+        std::iter::once(bin.path().to_path_buf()).chain(std::env::split_paths(&path)),
+    )
+    .unwrap();
+    std::env::set_var("PATH", &path);
+
+    let found = find_program("skill-test-shim").unwrap();
+    assert!(found
+        .to_string_lossy()
+        .to_lowercase()
+        .ends_with("skill-test-shim.cmd"));
+
+    let command = NativeCommand::parse("skill-test-shim get pods").unwrap();
+    let output = command.to_command().output().await.unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        "shim get pods"
+    );
+}
+
+#[test]
+fn test_credentials_in_credential_manager() {
+    let store = CredentialStore::with_service_name("skill-engine-test".to_string());
+    let key = format!("token-{}", std::process::id());
+
+    store
+        .store_credential("windows-test", "default", &key, "s3cr3t value")
+        .unwrap();
+    assert!(store.has_credential("windows-test", "default", &key));
+    assert_eq!(
+        store
+            .get_credential("windows-test", "default", &key)
+            .unwrap()
+            .as_str(),
+        "s3cr3t value"
+    );
+
+    store
+        .delete_credential("windows-test", "default", &key)
+        .unwrap();
+    assert!(!store.has_credential("windows-test", "default", &key));
+}
@@ -0,0 +1,254 @@
+//! The client, its error type, and the request helpers the endpoint
+//! modules build on
+
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::types::ApiError;
+
+/// Header that selects the namespace of a request
+const NAMESPACE_HEADER: &str = "x-skill-namespace";
+
+/// Errors returned by [`SkillClient`]
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    /// The request couldn't be sent or its response couldn't be read
+    #[error("Request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// The server answered with an error status
+    #[error("{status}: {} ({})", error.message, error.code)]
+    Api {
+        /// HTTP status
+        status: StatusCode,
+        /// Error body, or the status and raw body if it wasn't an API error
+        error: ApiError,
+    },
+
+    /// The response body didn't match the expected type
+    #[error("Invalid response: {0}")]
+    Decode(#[from] serde_json::Error),
+}
+
+impl ClientError {
+    /// HTTP status of an API error
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ClientError::Api { status, .. } => Some(*status),
+            ClientError::Http(e) => e.status(),
+            ClientError::Decode(_) => None,
+        }
+    }
+
+    /// Error code of an API error, like `NOT_FOUND`
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            ClientError::Api { error, .. } => Some(&error.code),
+            _ => None,
+        }
+    }
+}
+
+/// Result type for client calls
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Typed client for the Skill HTTP API
+#[derive(Debug, Clone)]
+pub struct SkillClient {
+    http: reqwest::Client,
+    base_url: String,
+    namespace: Option<String>,
+}
+
+impl SkillClient {
+    /// Client for the server at `base_url`, like `http://127.0.0.1:3000`
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_http_client(base_url, reqwest::Client::new())
+    }
+
+    /// Client sending requests through a preconfigured `reqwest` client,
+    /// e.g. one with timeouts, default headers or client certificates
+    pub fn with_http_client(base_url: impl Into<String>, http: reqwest::Client) -> Self {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        Self {
+            http,
+            base_url,
+            namespace: None,
+        }
+    }
+
+    /// Send every request to a namespace of the server
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Server URL the client was created with
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Start a request to an API path (relative to `/api`)
+    pub(crate) fn request(&self, method: reqwest::Method, path: &str) -> RequestBuilder {
+        let request = self
+            .http
+            .request(method, format!("{}/api{}", self.base_url, path));
+        match &self.namespace {
+            Some(namespace) => request.header(NAMESPACE_HEADER, namespace),
+            None => request,
+        }
+    }
+
+    pub(crate) async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        json(send(self.request(reqwest::Method::GET, path)).await?).await
+    }
+
+    pub(crate) async fn get_query<T: DeserializeOwned, Q: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> Result<T> {
+        json(send(self.request(reqwest::Method::GET, path).query(query)).await?).await
+    }
+
+    pub(crate) async fn post<T: DeserializeOwned, B: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        json(send(self.request(reqwest::Method::POST, path).json(body)).await?).await
+    }
+
+    pub(crate) async fn post_empty<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        json(send(self.request(reqwest::Method::POST, path)).await?).await
+    }
+
+    pub(crate) async fn put<T: DeserializeOwned, B: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T> {
+        json(send(self.request(reqwest::Method::PUT, path).json(body)).await?).await
+    }
+
+    pub(crate) async fn delete(&self, path: &str) -> Result<()> {
+        send(self.request(reqwest::Method::DELETE, path)).await?;
+        Ok(())
+    }
+
+    pub(crate) async fn delete_json<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        json(send(self.request(reqwest::Method::DELETE, path)).await?).await
+    }
+}
+
+/// Send a request, turning error statuses into [`ClientError::Api`]
+pub(crate) async fn send(request: RequestBuilder) -> Result<Response> {
+    let response = request.send().await?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body = response.text().await?;
+    let error = serde_json::from_str(&body).unwrap_or_else(|_| ApiError {
+        code: format!("HTTP_{}", status.as_u16()),
+        message: if body.is_empty() {
+            status.canonical_reason().unwrap_or("Error").to_string()
+        } else {
+            body
+        },
+        details: None,
+    });
+    Err(ClientError::Api { status, error })
+}
+
+async fn json<T: DeserializeOwned>(response: Response) -> Result<T> {
+    let body = response.bytes().await?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// The events of a Server-Sent Events response, decoded from their `data`
+pub(crate) fn events<T: DeserializeOwned + Send + 'static>(
+    response: Response,
+) -> BoxStream<'static, Result<T>> {
+    let state = (response.bytes_stream().boxed(), Vec::new());
+    futures::stream::unfold(state, |(mut body, mut buffer)| async move {
+        loop {
+            // Events end with a blank line; keep partial events
+            // buffered until the rest arrives
+            if let Some(end) = buffer.windows(2).position(|w| w == b"\n\n") {
+                let event: Vec<u8> = buffer.drain(..end + 2).collect();
+                match parse_event(&event) {
+                    Some(event) => return Some((event, (body, buffer))),
+                    None => continue,
+                }
+            }
+            match body.next().await? {
+                Ok(chunk) => buffer.extend(chunk.iter().filter(|&&b| b != b'\r')),
+                Err(e) => return Some((Err(ClientError::Http(e)), (body, buffer))),
+            }
+        }
+    })
+    .boxed()
+}
+
+/// The event in one SSE message, or `None` for keep-alive comments
+fn parse_event<T: DeserializeOwned>(message: &[u8]) -> Option<Result<T>> {
+    let message = String::from_utf8_lossy(message);
+    let data: Vec<&str> = message
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect();
+    if data.is_empty() {
+        return None;
+    }
+    Some(serde_json::from_str(&data.join("\n")).map_err(ClientError::from))
+}
+
+/// Percent-encode a value used as one path segment
+pub(crate) fn segment(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::WorkflowEvent;
+
+    #[test]
+    fn test_segment() {
+        assert_eq!(segment("kubernetes"), "kubernetes");
+        assert_eq!(segment("my skill/v1"), "my%20skill%2Fv1");
+    }
+
+    #[test]
+    fn test_parse_event() {
+        let event: WorkflowEvent = parse_event(
+            b"event: step_started\ndata: {\"event\":\"step_started\",\"step\":\"list\",\"tool\":\"kubernetes:get\"}\n\n",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(event.name(), "step_started");
+
+        assert!(parse_event::<WorkflowEvent>(b": keep-alive\n\n").is_none());
+        assert!(parse_event::<WorkflowEvent>(b"data: {}\n\n").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_base_url() {
+        let client = SkillClient::new("http://127.0.0.1:3000/");
+        assert_eq!(client.base_url(), "http://127.0.0.1:3000");
+    }
+}
@@ -0,0 +1,37 @@
+//! Example generation endpoints
+
+use futures::stream::BoxStream;
+use reqwest::{header::ACCEPT, Method};
+
+use crate::client::{events, segment, send, Result, SkillClient};
+use crate::types::{EnhanceSkillRequest, EnhanceStatusResponse};
+
+impl SkillClient {
+    /// `POST /api/skills/{name}/enhance` - generate examples for a skill's
+    /// tools, streaming generation events as they happen
+    ///
+    /// Each event is the JSON of a generation event, tagged by `type`
+    /// (`started`, `thinking`, `example`, `validation`, `progress`,
+    /// `tool_completed`, `completed`, `error`). Generation keeps going on
+    /// the server if the stream is dropped.
+    pub async fn enhance_skill_stream(
+        &self,
+        name: &str,
+        request: &EnhanceSkillRequest,
+    ) -> Result<BoxStream<'static, Result<serde_json::Value>>> {
+        let response = send(
+            self.request(Method::POST, &format!("/skills/{}/enhance", segment(name)))
+                .header(ACCEPT, "text/event-stream")
+                .json(request),
+        )
+        .await?;
+
+        Ok(events(response))
+    }
+
+    /// `GET /api/enhance/status` - progress of the most recent
+    /// `skill enhance` run
+    pub async fn enhance_status(&self) -> Result<EnhanceStatusResponse> {
+        self.get("/enhance/status").await
+    }
+}
@@ -0,0 +1,108 @@
+//! Execution endpoints
+
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use reqwest::{header::ACCEPT, Method};
+
+use crate::client::{events, segment, send, Result, SkillClient};
+use crate::types::{
+    ExecutionArtifact, ExecutionHistoryEntry, ExecutionOutputPage, ExecutionOutputQuery,
+    ExecutionRequest, ExecutionResponse, ExecutionStreamEvent, ExecutionSearchQuery, ExecutionSearchResponse, ExecutionSummaryResponse,
+    PaginatedResponse, PaginationParams, RerunResponse,
+};
+
+impl SkillClient {
+    /// `POST /api/execute` - run a tool and wait for its result
+    pub async fn execute(&self, request: &ExecutionRequest) -> Result<ExecutionResponse> {
+        self.post("/execute", request).await
+    }
+
+    /// `POST /api/execute/stream` - run a tool, streaming its output as it
+    /// is written; the stream ends after the `completed` or `error` event
+    pub async fn execute_stream(
+        &self,
+        request: &ExecutionRequest,
+    ) -> Result<BoxStream<'static, Result<ExecutionStreamEvent>>> {
+        let response = send(
+            self.request(Method::POST, "/execute/stream")
+                .header(ACCEPT, "text/event-stream")
+                .json(request),
+        )
+        .await?;
+
+        Ok(events(response))
+    }
+
+    /// `GET /api/executions` - one page of execution history, newest first
+    pub async fn list_executions(
+        &self,
+        page: usize,
+        per_page: usize,
+    ) -> Result<PaginatedResponse<ExecutionHistoryEntry>> {
+        self.get_query("/executions", &PaginationParams { page, per_page })
+            .await
+    }
+
+    /// `DELETE /api/executions` - clear the execution history
+    pub async fn clear_executions(&self) -> Result<()> {
+        self.delete("/executions").await
+    }
+
+    /// `GET /api/executions/search` - executions whose output or error
+    /// contains every word of the query, newest first
+    pub async fn search_executions(
+        &self,
+        query: &ExecutionSearchQuery,
+    ) -> Result<ExecutionSearchResponse> {
+        self.get_query("/executions/search", query).await
+    }
+
+    /// `GET /api/executions/{id}` - one execution
+    pub async fn get_execution(&self, id: &str) -> Result<ExecutionHistoryEntry> {
+        self.get(&format!("/executions/{}", segment(id))).await
+    }
+
+    /// `GET /api/executions/{id}/output` - a page of an execution's full
+    /// output; `range` is `<start>-<end>` or `<start>-`
+    pub async fn get_execution_output(
+        &self,
+        id: &str,
+        range: Option<&str>,
+    ) -> Result<ExecutionOutputPage> {
+        let query = ExecutionOutputQuery {
+            range: range.map(str::to_string),
+        };
+        self.get_query(&format!("/executions/{}/output", segment(id)), &query)
+            .await
+    }
+
+    /// `POST /api/executions/{id}/rerun` - run an execution again with the
+    /// same arguments and compare the output
+    pub async fn rerun_execution(&self, id: &str) -> Result<RerunResponse> {
+        self.post_empty(&format!("/executions/{}/rerun", segment(id)))
+            .await
+    }
+
+    /// `POST /api/executions/{id}/summarize` - ask the configured agent to
+    /// explain an execution
+    pub async fn summarize_execution(&self, id: &str) -> Result<ExecutionSummaryResponse> {
+        self.post_empty(&format!("/executions/{}/summarize", segment(id)))
+            .await
+    }
+
+    /// `GET /api/executions/{id}/artifacts` - files the execution produced
+    pub async fn list_execution_artifacts(&self, id: &str) -> Result<Vec<ExecutionArtifact>> {
+        self.get(&format!("/executions/{}/artifacts", segment(id)))
+            .await
+    }
+
+    /// `GET /api/executions/{id}/artifacts/{name}` - an artifact's contents
+    pub async fn download_execution_artifact(&self, id: &str, name: &str) -> Result<Bytes> {
+        let name: Vec<String> = name.split('/').map(segment).collect();
+        let path = format!("/executions/{}/artifacts/{}", segment(id), name.join("/"));
+        Ok(send(self.request(Method::GET, &path))
+            .await?
+            .bytes()
+            .await?)
+    }
+}
@@ -0,0 +1,42 @@
+//! Skill Client - typed async client for the Skill HTTP API
+//!
+//! Every REST endpoint served by `skill-http` (`skill serve --http` or
+//! `skill web`) has a method on [`SkillClient`], taking and returning the
+//! payloads in [`types`], which are the server's own types from `skill-api`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use skill_client::{types::ExecutionRequest, SkillClient};
+//!
+//! # async fn run() -> skill_client::Result<()> {
+//! let client = SkillClient::new("http://127.0.0.1:3000");
+//!
+//! for skill in client.list_skills(1, 20).await?.items {
+//!     println!("{} ({} tools)", skill.name, skill.tools_count);
+//! }
+//!
+//! let result = client
+//!     .execute(&ExecutionRequest {
+//!         skill: "kubernetes".to_string(),
+//!         tool: "get".to_string(),
+//!         args: [("resource".to_string(), "pods".into())].into_iter().collect(),
+//!         ..Default::default()
+//!     })
+//!     .await?;
+//! println!("{}", result.output);
+//! # Ok(())
+//! # }
+//! ```
+
+mod client;
+mod enhance;
+mod executions;
+mod presets;
+mod search;
+mod skills;
+mod system;
+mod workflows;
+
+pub use client::{ClientError, Result, SkillClient};
+pub use skill_api::types;
@@ -0,0 +1,36 @@
+//! Saved run endpoints
+
+use crate::client::{segment, Result, SkillClient};
+use crate::types::{CreatePresetRequest, ExecutionResponse, RunPreset, UpdatePresetRequest};
+
+impl SkillClient {
+    /// `GET /api/presets` - saved runs, pinned ones first
+    pub async fn list_presets(&self) -> Result<Vec<RunPreset>> {
+        self.get("/presets").await
+    }
+
+    /// `POST /api/presets` - save a run under a name
+    pub async fn create_preset(&self, request: &CreatePresetRequest) -> Result<RunPreset> {
+        self.post("/presets", request).await
+    }
+
+    /// `PUT /api/presets/{id}` - rename, edit or pin a saved run
+    pub async fn update_preset(
+        &self,
+        id: &str,
+        request: &UpdatePresetRequest,
+    ) -> Result<RunPreset> {
+        self.put(&format!("/presets/{}", segment(id)), request).await
+    }
+
+    /// `DELETE /api/presets/{id}` - delete a saved run
+    pub async fn delete_preset(&self, id: &str) -> Result<()> {
+        self.delete(&format!("/presets/{}", segment(id))).await
+    }
+
+    /// `POST /api/presets/{id}/run` - run a saved run and wait for its result
+    pub async fn run_preset(&self, id: &str) -> Result<ExecutionResponse> {
+        self.post_empty(&format!("/presets/{}/run", segment(id)))
+            .await
+    }
+}
@@ -0,0 +1,135 @@
+//! Search, feedback and analytics endpoints
+
+use crate::client::{Result, SkillClient};
+use crate::types::{
+    AnalyticsOverviewResponse, ExampleQualityResponse, FeedbackStatsResponse, GetFeedbackRequest, GetFeedbackResponse,
+    IndexResponse, SearchConfigResponse, SearchRequest, SearchResponse, SearchTimelineResponse,
+    SkillAnalyticsResponse, SkillUsageResponse, SubmitFeedbackRequest, SubmitFeedbackResponse,
+    TestConnectionRequest, TestConnectionResponse, TestPipelineRequest, TestPipelineResponse,
+    TopQueriesResponse, UpdateSearchConfigRequest,
+};
+
+impl SkillClient {
+    /// `POST /api/search` - semantic search over the installed skills' tools
+    pub async fn search(&self, request: &SearchRequest) -> Result<SearchResponse> {
+        self.post("/search", request).await
+    }
+
+    /// `GET /api/search/config` - the search pipeline configuration
+    pub async fn get_search_config(&self) -> Result<SearchConfigResponse> {
+        self.get("/search/config").await
+    }
+
+    /// `PUT /api/search/config` - update the search pipeline configuration
+    pub async fn update_search_config(
+        &self,
+        request: &UpdateSearchConfigRequest,
+    ) -> Result<SearchConfigResponse> {
+        self.put("/search/config", request).await
+    }
+
+    /// `POST /api/search/index` - reindex the installed skills
+    pub async fn index_skills(&self) -> Result<IndexResponse> {
+        self.post_empty("/search/index").await
+    }
+
+    /// `POST /api/search/test-connection` - check an embedding provider or
+    /// vector store is reachable
+    pub async fn test_search_connection(
+        &self,
+        request: &TestConnectionRequest,
+    ) -> Result<TestConnectionResponse> {
+        self.post("/search/test-connection", request).await
+    }
+
+    /// `POST /api/search/test-pipeline` - run a query through a search
+    /// pipeline configuration without saving it
+    pub async fn test_search_pipeline(
+        &self,
+        request: &TestPipelineRequest,
+    ) -> Result<TestPipelineResponse> {
+        self.post("/search/test-pipeline", request).await
+    }
+
+    /// `POST /api/feedback` - rate a search result
+    pub async fn submit_feedback(
+        &self,
+        request: &SubmitFeedbackRequest,
+    ) -> Result<SubmitFeedbackResponse> {
+        self.post("/feedback", request).await
+    }
+
+    /// `GET /api/feedback` - recorded search feedback
+    pub async fn get_feedback(&self, request: &GetFeedbackRequest) -> Result<GetFeedbackResponse> {
+        self.get_query("/feedback", request).await
+    }
+
+    /// `GET /api/analytics/overview` - search statistics for the last `days`
+    pub async fn analytics_overview(&self, days: u32) -> Result<AnalyticsOverviewResponse> {
+        self.get_query("/analytics/overview", &[("days", days)])
+            .await
+    }
+
+    /// `GET /api/analytics/top-queries` - the `limit` most frequent queries
+    /// of the last `days`
+    pub async fn top_queries(&self, limit: usize, days: u32) -> Result<TopQueriesResponse> {
+        self.get_query(
+            "/analytics/top-queries",
+            &[("limit", limit.to_string()), ("days", days.to_string())],
+        )
+        .await
+    }
+
+    /// `GET /api/analytics/feedback-stats` - feedback statistics for the
+    /// last `days`
+    pub async fn feedback_stats(&self, days: u32) -> Result<FeedbackStatsResponse> {
+        self.get_query("/analytics/feedback-stats", &[("days", days)])
+            .await
+    }
+
+    /// `GET /api/analytics/timeline` - searches over the last `days`, in
+    /// buckets of `interval_hours`
+    pub async fn search_timeline(
+        &self,
+        days: u32,
+        interval_hours: u32,
+    ) -> Result<SearchTimelineResponse> {
+        self.get_query(
+            "/analytics/timeline",
+            &[("days", days), ("interval_hours", interval_hours)],
+        )
+        .await
+    }
+
+    /// `GET /api/analytics/skills` - execution count, success rate and
+    /// latency percentiles per skill over the last `days`
+    pub async fn skill_usage(&self, days: u32) -> Result<SkillUsageResponse> {
+        self.get_query("/analytics/skills", &[("days", days)])
+            .await
+    }
+
+    /// `GET /api/analytics/skills/{name}` - usage of one skill over the
+    /// last `days`, per tool and per day
+    pub async fn skill_analytics(&self, name: &str, days: u32) -> Result<SkillAnalyticsResponse> {
+        self.get_query(
+            &format!("/analytics/skills/{}", crate::client::segment(name)),
+            &[("days", days)],
+        )
+        .await
+    }
+
+    /// `GET /api/analytics/example-quality` - `skill enhance --evaluate`
+    /// results over the last `days`, optionally for one skill
+    pub async fn example_quality(
+        &self,
+        skill: Option<&str>,
+        days: u32,
+    ) -> Result<ExampleQualityResponse> {
+        let mut query = vec![("days", days.to_string())];
+        if let Some(skill) = skill {
+            query.push(("skill", skill.to_string()));
+        }
+        self.get_query("/analytics/example-quality", &query)
+            .await
+    }
+}
@@ -0,0 +1,50 @@
+//! Skills endpoints
+
+use crate::client::{segment, Result, SkillClient};
+use crate::types::{
+    InstallJob, InstallSkillRequest, PaginatedResponse, PaginationParams, SkillDetail,
+    SkillDocsResponse, SkillSummary,
+};
+
+impl SkillClient {
+    /// `GET /api/skills` - one page of installed skills
+    pub async fn list_skills(
+        &self,
+        page: usize,
+        per_page: usize,
+    ) -> Result<PaginatedResponse<SkillSummary>> {
+        self.get_query("/skills", &PaginationParams { page, per_page })
+            .await
+    }
+
+    /// `GET /api/skills/{name}` - a skill with its tools and instances
+    pub async fn get_skill(&self, name: &str) -> Result<SkillDetail> {
+        self.get(&format!("/skills/{}", segment(name))).await
+    }
+
+    /// `GET /api/skills/{name}/docs` - a skill's SKILL.md or README
+    pub async fn get_skill_docs(&self, name: &str) -> Result<SkillDocsResponse> {
+        self.get(&format!("/skills/{}/docs", segment(name))).await
+    }
+
+    /// `POST /api/skills` - start installing a skill in the background
+    pub async fn install_skill(&self, request: &InstallSkillRequest) -> Result<InstallJob> {
+        self.post("/skills", request).await
+    }
+
+    /// `GET /api/installs/{id}` - progress of a skill install
+    pub async fn get_install(&self, id: &str) -> Result<InstallJob> {
+        self.get(&format!("/installs/{}", segment(id))).await
+    }
+
+    /// `DELETE /api/installs/{id}` - cancel a skill install
+    pub async fn cancel_install(&self, id: &str) -> Result<InstallJob> {
+        self.delete_json(&format!("/installs/{}", segment(id)))
+            .await
+    }
+
+    /// `DELETE /api/skills/{name}` - uninstall a skill
+    pub async fn uninstall_skill(&self, name: &str) -> Result<()> {
+        self.delete(&format!("/skills/{}", segment(name))).await
+    }
+}
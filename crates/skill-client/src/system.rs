@@ -0,0 +1,99 @@
+//! Configuration, manifest, service, auth and health endpoints
+
+use crate::client::{Result, SkillClient};
+use crate::types::{
+    AgentConfig, AppConfig, AuthStatusResponse, ExportManifestRequest, ExportManifestResponse,
+    GetAgentConfigResponse, HealthResponse, ImportManifestRequest, ImportManifestResponse,
+    ManifestSourceResponse,
+    ServicesStatusResponse, StartServiceRequest, StartServiceResponse, StopServiceRequest,
+    UpdateAgentConfigRequest, UpdateAppConfigRequest, ValidateManifestRequest,
+    ValidateManifestResponse, VersionResponse,
+};
+
+impl SkillClient {
+    /// `GET /api/config` - server configuration
+    pub async fn get_config(&self) -> Result<AppConfig> {
+        self.get("/config").await
+    }
+
+    /// `PUT /api/config` - update the server configuration
+    pub async fn update_config(&self, request: &UpdateAppConfigRequest) -> Result<AppConfig> {
+        self.put("/config", request).await
+    }
+
+    /// `GET /api/agent/config` - agent configuration and available runtimes
+    pub async fn get_agent_config(&self) -> Result<GetAgentConfigResponse> {
+        self.get("/agent/config").await
+    }
+
+    /// `PUT /api/agent/config` - update the agent configuration
+    pub async fn update_agent_config(
+        &self,
+        request: &UpdateAgentConfigRequest,
+    ) -> Result<AgentConfig> {
+        self.put("/agent/config", request).await
+    }
+
+    /// `GET /api/manifest` - the manifest file the server loaded its skills
+    /// from
+    pub async fn get_manifest(&self) -> Result<ManifestSourceResponse> {
+        self.get("/manifest").await
+    }
+
+    /// `POST /api/manifest/validate` - check a manifest without applying it
+    pub async fn validate_manifest(
+        &self,
+        request: &ValidateManifestRequest,
+    ) -> Result<ValidateManifestResponse> {
+        self.post("/manifest/validate", request).await
+    }
+
+    /// `POST /api/manifest/import` - install the skills a manifest declares
+    pub async fn import_manifest(
+        &self,
+        request: &ImportManifestRequest,
+    ) -> Result<ImportManifestResponse> {
+        self.post("/manifest/import", request).await
+    }
+
+    /// `POST /api/manifest/export` - the installed skills as a manifest
+    pub async fn export_manifest(
+        &self,
+        request: &ExportManifestRequest,
+    ) -> Result<ExportManifestResponse> {
+        self.post("/manifest/export", request).await
+    }
+
+    /// `GET /api/services` - system services and their status
+    pub async fn list_services(&self) -> Result<ServicesStatusResponse> {
+        self.get("/services").await
+    }
+
+    /// `POST /api/services/start` - start a system service
+    pub async fn start_service(
+        &self,
+        request: &StartServiceRequest,
+    ) -> Result<StartServiceResponse> {
+        self.post("/services/start", request).await
+    }
+
+    /// `POST /api/services/stop` - stop a system service
+    pub async fn stop_service(&self, request: &StopServiceRequest) -> Result<StartServiceResponse> {
+        self.post("/services/stop", request).await
+    }
+
+    /// `GET /api/auth/status` - which credentials are configured
+    pub async fn auth_status(&self) -> Result<AuthStatusResponse> {
+        self.get("/auth/status").await
+    }
+
+    /// `GET /api/health` - server and component health
+    pub async fn health(&self) -> Result<HealthResponse> {
+        self.get("/health").await
+    }
+
+    /// `GET /api/version` - server version information
+    pub async fn version(&self) -> Result<VersionResponse> {
+        self.get("/version").await
+    }
+}
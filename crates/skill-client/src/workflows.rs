@@ -0,0 +1,42 @@
+//! Workflow endpoints
+
+use futures::stream::BoxStream;
+use reqwest::{header::ACCEPT, Method};
+
+use crate::client::{events, segment, send, Result, SkillClient};
+use crate::types::{RunWorkflowRequest, WorkflowEvent, WorkflowRunResponse, WorkflowSummary};
+
+impl SkillClient {
+    /// `GET /api/workflows` - workflows the server can run
+    pub async fn list_workflows(&self) -> Result<Vec<WorkflowSummary>> {
+        self.get("/workflows").await
+    }
+
+    /// `POST /api/workflows/{name}/run` - run a workflow and wait for it
+    pub async fn run_workflow(
+        &self,
+        name: &str,
+        request: &RunWorkflowRequest,
+    ) -> Result<WorkflowRunResponse> {
+        self.post(&format!("/workflows/{}/run", segment(name)), request)
+            .await
+    }
+
+    /// `POST /api/workflows/{name}/run` - run a workflow, streaming its
+    /// progress as it happens; the stream ends after the `finished` or
+    /// `error` event
+    pub async fn run_workflow_stream(
+        &self,
+        name: &str,
+        request: &RunWorkflowRequest,
+    ) -> Result<BoxStream<'static, Result<WorkflowEvent>>> {
+        let response = send(
+            self.request(Method::POST, &format!("/workflows/{}/run", segment(name)))
+                .header(ACCEPT, "text/event-stream")
+                .json(request),
+        )
+        .await?;
+
+        Ok(events(response))
+    }
+}
@@ -0,0 +1,177 @@
+//! Runs the client against a real skill-http server, so a route or payload
+//! the client no longer matches fails here
+
+use futures::StreamExt;
+use skill_client::{
+    types::{EnhanceSkillRequest, ExecutionRequest, GetFeedbackRequest, SubmitFeedbackRequest},
+    SkillClient,
+};
+use skill_http::{types::SkillSummary, AppState, HttpServerConfig};
+use std::sync::Arc;
+
+async fn serve() -> (SkillClient, tempfile::TempDir) {
+    let (client, dir, _) = serve_with_state().await;
+    (client, dir)
+}
+
+async fn serve_with_state() -> (SkillClient, tempfile::TempDir, Arc<AppState>) {
+    let dir = tempfile::TempDir::new().unwrap();
+    let config = HttpServerConfig {
+        host: "127.0.0.1".to_string(),
+        port: 0,
+        enable_cors: false,
+        enable_tracing: false,
+        enable_web_ui: false,
+        working_dir: Some(dir.path().to_path_buf()),
+        drain_timeout: skill_http::DEFAULT_DRAIN_TIMEOUT,
+        tls: None,
+        namespaces: Vec::new(),
+        max_concurrent_executions: skill_http::DEFAULT_MAX_CONCURRENT_EXECUTIONS,
+        max_queued_executions: skill_http::DEFAULT_MAX_QUEUED_EXECUTIONS,
+        result_cache_ttl: skill_http::DEFAULT_RESULT_CACHE_TTL,
+    };
+    let state = Arc::new(AppState::new(config).unwrap());
+    state.skills.write().await.insert(
+        "test-skill".to_string(),
+        SkillSummary {
+            name: "test-skill".to_string(),
+            version: "0.1.0".to_string(),
+            description: "A test skill".to_string(),
+            source: "local:./test-skill".to_string(),
+            runtime: "wasm".to_string(),
+            tools_count: 3,
+            instances_count: 1,
+            execution_count: 0,
+            last_used: None,
+            required_services: vec![],
+        },
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let app = skill_http::routes::create_app(state.clone());
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    (SkillClient::new(url), dir, state)
+}
+
+#[tokio::test]
+async fn test_skills() {
+    let (client, _dir) = serve().await;
+
+    let page = client.list_skills(1, 20).await.unwrap();
+    assert_eq!(page.total, 1);
+    assert_eq!(page.items[0].name, "test-skill");
+    assert_eq!(page.items[0].tools_count, 3);
+
+    let err = client.get_skill("missing").await.unwrap_err();
+    assert_eq!(err.status(), Some(reqwest::StatusCode::NOT_FOUND));
+    assert_eq!(err.code(), Some("NOT_FOUND"));
+}
+
+#[tokio::test]
+async fn test_system_endpoints() {
+    let (client, _dir) = serve().await;
+
+    let version = client.version().await.unwrap();
+    assert!(!version.version.is_empty());
+    client.health().await.unwrap();
+    client.get_config().await.unwrap();
+    client.list_services().await.unwrap();
+    client.auth_status().await.unwrap();
+    client.list_workflows().await.unwrap();
+    client.list_executions(1, 20).await.unwrap();
+    client.enhance_status().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_execute_unknown_skill() {
+    let (client, _dir) = serve().await;
+
+    let err = client
+        .execute(&ExecutionRequest {
+            skill: "missing".to_string(),
+            tool: "run".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert!(err.status().is_some_and(|status| status.is_client_error()));
+}
+
+#[tokio::test]
+async fn test_execute_stream_unknown_skill() {
+    let (client, _dir) = serve().await;
+
+    let events: Vec<_> = client
+        .execute_stream(&ExecutionRequest {
+            skill: "missing".to_string(),
+            tool: "run".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    let names: Vec<_> = events.iter().map(|e| e.as_ref().unwrap().name()).collect();
+    assert_eq!(names, ["started", "error"]);
+}
+
+#[tokio::test]
+async fn test_enhance_unknown_skill() {
+    let (client, _dir) = serve().await;
+
+    let err = client
+        .enhance_skill_stream("missing", &EnhanceSkillRequest::default())
+        .await
+        .err()
+        .unwrap();
+    // 501 when skill-http is built without the ai-ingestion feature
+    assert!(matches!(
+        err.code(),
+        Some("NOT_FOUND" | "AI_INGESTION_UNAVAILABLE")
+    ));
+}
+
+#[tokio::test]
+async fn test_feedback_and_analytics() {
+    let (client, _dir, state) = serve_with_state().await;
+    *state.analytics_db.write().await = Some(Arc::new(
+        skill_http::analytics::SearchAnalyticsDb::new(":memory:").await.unwrap(),
+    ));
+
+    let submitted = client
+        .submit_feedback(&SubmitFeedbackRequest {
+            query: "list pods".to_string(),
+            result_id: "kubernetes@default/get".to_string(),
+            score: 0.9,
+            rank: 0,
+            feedback_type: "positive".to_string(),
+            reason: None,
+            comment: None,
+            client_type: "http".to_string(),
+        })
+        .await
+        .unwrap();
+    assert!(submitted.success);
+
+    let feedback = client
+        .get_feedback(&GetFeedbackRequest {
+            query: None,
+            result_id: None,
+            feedback_type: None,
+            limit: 10,
+            offset: 0,
+        })
+        .await
+        .unwrap();
+    assert_eq!(feedback.total_count, 1);
+    assert_eq!(feedback.feedback[0].query, "list pods");
+
+    let overview = client.analytics_overview(7).await.unwrap();
+    assert_eq!(overview.total_feedback, 1);
+    client.top_queries(10, 7).await.unwrap();
+    client.feedback_stats(7).await.unwrap();
+    client.search_timeline(7, 24).await.unwrap();
+    client.example_quality(Some("kubernetes"), 30).await.unwrap();
+}
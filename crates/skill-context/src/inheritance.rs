@@ -327,6 +327,15 @@ fn merge_execution_limits(
         timeout_seconds: child.timeout_seconds.or(parent.timeout_seconds),
         max_concurrent: child.max_concurrent.or(parent.max_concurrent),
         rate_limit: child.rate_limit.clone().or_else(|| parent.rate_limit.clone()),
+        max_output_size: child
+            .max_output_size
+            .clone()
+            .or_else(|| parent.max_output_size.clone()),
+        max_files_written: child.max_files_written.or(parent.max_files_written),
+        max_write_size: child
+            .max_write_size
+            .clone()
+            .or_else(|| parent.max_write_size.clone()),
     }
 }
 
@@ -166,6 +166,16 @@ impl Mount {
         PathBuf::from(&self.target)
     }
 
+    /// Render the contents of a config file mount.
+    ///
+    /// Returns `None` for other mount types.
+    pub fn render_config(&self) -> Option<String> {
+        match &self.mount_type {
+            MountType::ConfigFile { template } => Some(expand_env_vars(template)),
+            _ => None,
+        }
+    }
+
     /// Check if this mount requires a source path to exist.
     pub fn requires_source(&self) -> bool {
         matches!(
@@ -328,6 +338,23 @@ key = "${API_KEY}"
         }
     }
 
+    #[test]
+    fn test_render_config() {
+        std::env::set_var("SKILL_CONTEXT_TEST_ENDPOINT", "https://api.example.com");
+        let mount = Mount::config_file(
+            "api-config",
+            "endpoint = \"${SKILL_CONTEXT_TEST_ENDPOINT}\"",
+            "/etc/app/config.toml",
+        );
+
+        assert_eq!(
+            mount.render_config().unwrap(),
+            "endpoint = \"https://api.example.com\""
+        );
+        assert!(Mount::tmpfs("temp", "/tmp", 10).render_config().is_none());
+        std::env::remove_var("SKILL_CONTEXT_TEST_ENDPOINT");
+    }
+
     #[test]
     fn test_env_var_expansion() {
         std::env::set_var("TEST_VAR", "test_value");
@@ -381,6 +381,19 @@ pub struct ExecutionLimits {
     /// Rate limiting.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rate_limit: Option<RateLimit>,
+
+    /// Maximum tool output (e.g. "1mb"); the tool is stopped and its
+    /// output truncated when it produces more.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_size: Option<String>,
+
+    /// Maximum number of files created or modified in writable mounts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_files_written: Option<u64>,
+
+    /// Maximum total bytes written to writable mounts (e.g. "100mb").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_write_size: Option<String>,
 }
 
 impl ExecutionLimits {
@@ -410,10 +423,43 @@ impl ExecutionLimits {
         self
     }
 
+    /// Set maximum output size.
+    pub fn with_max_output_size(mut self, size: impl Into<String>) -> Self {
+        self.max_output_size = Some(size.into());
+        self
+    }
+
+    /// Set maximum number of files written.
+    pub fn with_max_files_written(mut self, max: u64) -> Self {
+        self.max_files_written = Some(max);
+        self
+    }
+
+    /// Set maximum total bytes written.
+    pub fn with_max_write_size(mut self, size: impl Into<String>) -> Self {
+        self.max_write_size = Some(size.into());
+        self
+    }
+
     /// Get timeout as Duration.
     pub fn timeout(&self) -> Option<Duration> {
         self.timeout_seconds.map(Duration::from_secs)
     }
+
+    /// Parse max output size as bytes.
+    pub fn max_output_bytes(&self) -> Option<u64> {
+        self.max_output_size.as_ref().and_then(|s| parse_size(s))
+    }
+
+    /// Parse max write size as bytes.
+    pub fn max_write_bytes(&self) -> Option<u64> {
+        self.max_write_size.as_ref().and_then(|s| parse_size(s))
+    }
+
+    /// Whether a file write limit is set.
+    pub fn limits_writes(&self) -> bool {
+        self.max_files_written.is_some() || self.max_write_bytes().is_some()
+    }
 }
 
 /// Rate limiting configuration.
@@ -571,6 +617,15 @@ mod tests {
 
         let rate = limits.rate_limit.unwrap();
         assert_eq!(rate.requests_per_second(), 100.0 / 60.0);
+
+        let limits = ExecutionLimits::new()
+            .with_max_output_size("1mb")
+            .with_max_files_written(50)
+            .with_max_write_size("100m");
+        assert_eq!(limits.max_output_bytes(), Some(1024 * 1024));
+        assert_eq!(limits.max_write_bytes(), Some(100 * 1024 * 1024));
+        assert!(limits.limits_writes());
+        assert!(!ExecutionLimits::new().with_max_output_size("1k").limits_writes());
     }
 
     #[test]
@@ -0,0 +1,7 @@
+// Generate the gRPC service from the protobuf definitions (grpc feature)
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=proto");
+    #[cfg(feature = "grpc")]
+    tonic_build::compile_protos("proto/skill.proto")?;
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//! Agent - the LLM runtime the web UI drives enhance and summarize with
+//!
+//! The [`AgentConfig`] edited through `PUT /api/agent/config` is kept in
+//! `agent.json` next to the state's databases. Once saved, it decides who
+//! answers: `POST /api/skills/{name}/enhance` generates examples with it
+//! instead of the `[ai_ingestion]` provider, and
+//! `POST /api/executions/{id}/summarize` asks it to explain an execution.
+//! [`runner`] maps the configuration onto a [`skill_runtime::AgentRunner`].
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use skill_runtime::{AgentKind, AgentRunner, CompletionRequest};
+use tracing::warn;
+
+use crate::namespaces::namespace_data_dir;
+use crate::types::{AgentConfig, AgentRuntime, ExecutionHistoryEntry};
+
+/// File the agent configuration is kept in
+pub const AGENT_FILE: &str = "agent.json";
+
+/// Execution output and errors sent for summarizing are cut to this many
+/// characters
+const MAX_SUMMARY_INPUT_CHARS: usize = 12_000;
+
+const SUMMARY_SYSTEM_PROMPT: &str = "You summarize tool executions for an operator. \
+In a few sentences, say what the tool did and whether it worked. For a failure, name the \
+likely cause and the next thing to check. Don't repeat the output verbatim.";
+
+/// Where a namespace's agent configuration is saved
+pub fn config_path(namespace: Option<&str>) -> PathBuf {
+    namespace_data_dir(namespace).join(AGENT_FILE)
+}
+
+/// Load the agent configuration, falling back to the default (Claude Code)
+pub fn load_config(path: &Path) -> AgentConfig {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return AgentConfig::default();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        warn!("Ignoring invalid agent configuration {}: {}", path.display(), e);
+        AgentConfig::default()
+    })
+}
+
+/// Save the agent configuration
+pub fn save_config(path: &Path, config: &AgentConfig) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).context("Failed to create agent configuration directory")?;
+    }
+    let content = serde_json::to_string_pretty(config).context("Failed to serialize agent configuration")?;
+    std::fs::write(path, content).context("Failed to write agent configuration")
+}
+
+/// Check that a configuration can be run
+pub fn validate(config: &AgentConfig) -> Result<()> {
+    if config.runtime == AgentRuntime::Custom {
+        anyhow::bail!("Custom agent runtimes can't be run by the server; choose claude-code, openai or gemini");
+    }
+    if config.model_config.model.trim().is_empty() {
+        anyhow::bail!("model_config.model must not be empty");
+    }
+    if !(0.0..=2.0).contains(&config.model_config.temperature) {
+        anyhow::bail!("model_config.temperature must be between 0.0 and 2.0");
+    }
+    if config.timeout_secs == 0 {
+        anyhow::bail!("timeout_secs must be at least 1");
+    }
+    Ok(())
+}
+
+/// The runtime kind an API runtime maps onto, `None` for custom runtimes
+pub fn agent_kind(runtime: &AgentRuntime) -> Option<AgentKind> {
+    match runtime {
+        AgentRuntime::ClaudeCode => Some(AgentKind::ClaudeCode),
+        AgentRuntime::OpenAI => Some(AgentKind::OpenAi),
+        AgentRuntime::Gemini => Some(AgentKind::Gemini),
+        AgentRuntime::Custom => None,
+    }
+}
+
+/// The runner for a configuration
+pub fn runner(config: &AgentConfig) -> Result<AgentRunner> {
+    validate(config)?;
+    let kind = agent_kind(&config.runtime).context("Custom agent runtimes can't be run by the server")?;
+    let runner = AgentRunner::new(kind, &config.model_config.model)
+        .with_timeout(Duration::from_secs(config.timeout_secs));
+    Ok(match &config.claude_code_path {
+        Some(path) => runner.with_claude_code_path(path),
+        None => runner,
+    })
+}
+
+/// The prompt asking the agent to summarize an execution
+pub fn summary_request(entry: &ExecutionHistoryEntry, config: &AgentConfig) -> CompletionRequest {
+    let args = serde_json::to_string(&entry.args).unwrap_or_default();
+    let mut prompt = format!(
+        "Tool: {}:{} (instance {})\nArguments: {}\nStatus: {:?}, {}ms\n",
+        entry.skill, entry.tool, entry.instance, args, entry.status, entry.duration_ms
+    );
+    if let Some(error) = entry.error.as_deref().filter(|error| !error.is_empty()) {
+        prompt.push_str(&format!("\nError:\n{}\n", tail(error, MAX_SUMMARY_INPUT_CHARS / 4)));
+    }
+    if let Some(output) = entry.output.as_deref().filter(|output| !output.is_empty()) {
+        prompt.push_str(&format!("\nOutput:\n{}\n", tail(output, MAX_SUMMARY_INPUT_CHARS)));
+    }
+    CompletionRequest::with_system(SUMMARY_SYSTEM_PROMPT, prompt)
+        .temperature(config.model_config.temperature)
+        .max_tokens(config.model_config.max_tokens.min(u32::MAX as usize) as u32)
+}
+
+/// The last `max_chars` characters of `text`, where failures usually show
+fn tail(text: &str, max_chars: usize) -> String {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text.to_string();
+    }
+    let kept: String = text.chars().skip(count - max_chars).collect();
+    format!("[{} earlier characters omitted]\n{}", count - max_chars, kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_round_trip_and_validation() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(AGENT_FILE);
+        assert_eq!(load_config(&path).runtime, AgentRuntime::ClaudeCode);
+
+        let mut config = AgentConfig {
+            runtime: AgentRuntime::Gemini,
+            ..Default::default()
+        };
+        config.model_config.model = "gemini-1.5-pro".to_string();
+        save_config(&path, &config).unwrap();
+        let loaded = load_config(&path);
+        assert_eq!(loaded.runtime, AgentRuntime::Gemini);
+        assert_eq!(runner(&loaded).unwrap().kind, AgentKind::Gemini);
+
+        config.runtime = AgentRuntime::Custom;
+        assert!(runner(&config).is_err());
+        assert_eq!(tail("abcdef", 2), "[4 earlier characters omitted]\nef");
+    }
+}
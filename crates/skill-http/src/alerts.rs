@@ -0,0 +1,382 @@
+//! Alerts - notifications when a skill's failure rate or latency spikes
+//!
+//! [`AnomalyAnalyzer`] periodically compares each skill's executions in the
+//! last `window_minutes` with its own baseline over the `baseline_days`
+//! before that, and POSTs any anomaly, with the latest failing outputs
+//! attached, to the webhook and/or Slack URL of the [`AlertConfig`]. The
+//! configuration is edited through `PUT /api/config` and kept in
+//! `alerts.json` next to the state's databases.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::analytics::{usage_stats, ExecutionSample, UsageStats};
+use crate::namespaces::namespace_data_dir;
+use crate::server::AppState;
+use crate::types::{AlertConfig, ExecutionHistoryEntry};
+
+/// How often the server checks for anomalies
+pub const DEFAULT_ANALYZER_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// File the alert configuration is kept in
+pub const ALERTS_FILE: &str = "alerts.json";
+
+/// Failing executions attached to an alert
+const MAX_FAILURES: usize = 3;
+
+/// Output and errors of attached executions are cut to this many characters
+const MAX_OUTPUT_CHARS: usize = 2000;
+
+/// What was anomalous about a skill's recent executions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    /// Failure rate well above the baseline
+    FailureRate,
+    /// p95 latency well above the baseline
+    Latency,
+}
+
+/// An anomaly in one skill's executions
+#[derive(Debug, Clone, Serialize)]
+pub struct Anomaly {
+    /// Skill name
+    pub skill: String,
+    /// What was anomalous
+    pub kind: AnomalyKind,
+    /// One-line description
+    pub message: String,
+    /// Executions in the window
+    pub recent: UsageStats,
+    /// Executions in the baseline before the window
+    pub baseline: UsageStats,
+}
+
+/// Find the skills whose executions since `window_start` are anomalous
+/// compared with their earlier `samples`
+pub fn detect(samples: &[ExecutionSample], window_start: DateTime<Utc>, config: &AlertConfig) -> Vec<Anomaly> {
+    let mut by_skill: BTreeMap<&str, (Vec<&ExecutionSample>, Vec<&ExecutionSample>)> = BTreeMap::new();
+    for sample in samples {
+        let (recent, baseline) = by_skill.entry(sample.skill.as_str()).or_default();
+        if sample.started_at >= window_start {
+            recent.push(sample);
+        } else {
+            baseline.push(sample);
+        }
+    }
+
+    let mut anomalies = Vec::new();
+    for (skill, (recent, baseline)) in by_skill {
+        let recent = usage_stats(recent);
+        let baseline = usage_stats(baseline);
+        if recent.executions < config.min_executions {
+            continue;
+        }
+
+        let failure_rate = recent.failures as f32 / recent.executions as f32;
+        let baseline_failure_rate = if baseline.executions > 0 {
+            baseline.failures as f32 / baseline.executions as f32
+        } else {
+            0.0
+        };
+        if failure_rate >= config.failure_rate
+            && failure_rate - baseline_failure_rate >= config.failure_rate_increase
+        {
+            anomalies.push(Anomaly {
+                skill: skill.to_string(),
+                kind: AnomalyKind::FailureRate,
+                message: format!(
+                    "{}: {:.0}% of {} executions failed in the last {} min (baseline {:.0}%)",
+                    skill,
+                    failure_rate * 100.0,
+                    recent.executions,
+                    config.window_minutes,
+                    baseline_failure_rate * 100.0
+                ),
+                recent: recent.clone(),
+                baseline: baseline.clone(),
+            });
+        }
+
+        // Latency needs a baseline to compare with
+        if baseline.executions >= config.min_executions
+            && recent.p95_ms >= config.min_latency_ms
+            && recent.p95_ms as f64 >= baseline.p95_ms as f64 * config.latency_factor
+        {
+            anomalies.push(Anomaly {
+                skill: skill.to_string(),
+                kind: AnomalyKind::Latency,
+                message: format!(
+                    "{}: p95 latency {}ms in the last {} min, {:.1}x the baseline {}ms",
+                    skill,
+                    recent.p95_ms,
+                    config.window_minutes,
+                    recent.p95_ms as f64 / baseline.p95_ms.max(1) as f64,
+                    baseline.p95_ms
+                ),
+                recent,
+                baseline,
+            });
+        }
+    }
+    anomalies
+}
+
+/// Where a state's alert configuration is kept
+pub fn config_path(namespace: Option<&str>) -> PathBuf {
+    namespace_data_dir(namespace).join(ALERTS_FILE)
+}
+
+/// Load the alert configuration, falling back to the (disabled) default
+pub fn load_config(path: &Path) -> AlertConfig {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return AlertConfig::default();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        warn!("Ignoring invalid alert configuration {}: {}", path.display(), e);
+        AlertConfig::default()
+    })
+}
+
+/// Save the alert configuration
+pub fn save_config(path: &Path, config: &AlertConfig) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).context("Failed to create alert configuration directory")?;
+    }
+    let content = serde_json::to_string_pretty(config).context("Failed to serialize alert configuration")?;
+    std::fs::write(path, content).context("Failed to write alert configuration")
+}
+
+/// A failing execution attached to an alert
+#[derive(Debug, Serialize)]
+struct FailureSample {
+    id: String,
+    tool: String,
+    started_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+}
+
+impl From<ExecutionHistoryEntry> for FailureSample {
+    fn from(entry: ExecutionHistoryEntry) -> Self {
+        let cut = |text: String| text.chars().take(MAX_OUTPUT_CHARS).collect::<String>();
+        Self {
+            id: entry.id,
+            tool: entry.tool,
+            started_at: entry.started_at,
+            error: entry.error.map(cut),
+            output: entry.output.filter(|output| !output.is_empty()).map(cut),
+        }
+    }
+}
+
+/// Body POSTed to the generic webhook
+#[derive(Serialize)]
+struct AlertPayload<'a> {
+    event: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    namespace: Option<&'a str>,
+    #[serde(flatten)]
+    anomaly: &'a Anomaly,
+    failures: &'a [FailureSample],
+}
+
+/// Slack message for an anomaly
+fn slack_message(anomaly: &Anomaly, namespace: Option<&str>, failures: &[FailureSample]) -> serde_json::Value {
+    let mut text = match namespace {
+        Some(namespace) => format!(":rotating_light: *[{}] {}*", namespace, anomaly.message),
+        None => format!(":rotating_light: *{}*", anomaly.message),
+    };
+    for failure in failures {
+        text.push_str(&format!("\n\n`{}` at {}", failure.tool, failure.started_at.format("%Y-%m-%d %H:%M:%S UTC")));
+        if let Some(detail) = failure.error.as_ref().or(failure.output.as_ref()) {
+            // Slack truncates long messages, so keep each excerpt short
+            let excerpt: String = detail.chars().take(500).collect();
+            text.push_str(&format!("\n```{}```", excerpt));
+        }
+    }
+    serde_json::json!({ "text": text })
+}
+
+/// Background task that watches execution history for anomalies and
+/// sends alerts
+pub struct AnomalyAnalyzer {
+    state: Arc<AppState>,
+    interval: Duration,
+    client: reqwest::Client,
+    /// When each (skill, kind) was last alerted on
+    last_alerted: Mutex<HashMap<(String, AnomalyKind), DateTime<Utc>>>,
+}
+
+impl AnomalyAnalyzer {
+    /// Create an analyzer for a state, running every [`DEFAULT_ANALYZER_INTERVAL`]
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self {
+            state,
+            interval: DEFAULT_ANALYZER_INTERVAL,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            last_alerted: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set how often the analyzer runs
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Check for anomalies once and send alerts for those not in cooldown
+    ///
+    /// Returns the anomalies that were alerted on.
+    pub async fn run_once(&self) -> Result<Vec<Anomaly>> {
+        let config = self.state.alerts.read().await.clone();
+        if !config.enabled || (config.webhook_url.is_none() && config.slack_webhook_url.is_none()) {
+            return Ok(Vec::new());
+        }
+        let Some(db) = self.state.execution_history_db.read().await.clone() else {
+            return Ok(Vec::new());
+        };
+
+        let now = Utc::now();
+        let window_start = now - chrono::Duration::minutes(config.window_minutes as i64);
+        let since = window_start - chrono::Duration::days(config.baseline_days as i64);
+        let samples = db.samples(since, None).await?;
+
+        let mut alerted = Vec::new();
+        for anomaly in detect(&samples, window_start, &config) {
+            let key = (anomaly.skill.clone(), anomaly.kind);
+            let cooldown = chrono::Duration::minutes(config.cooldown_minutes as i64);
+            if self.last_alerted.lock().unwrap().get(&key).is_some_and(|at| now - *at < cooldown) {
+                continue;
+            }
+
+            let failures: Vec<FailureSample> = db
+                .recent_failures(&anomaly.skill, window_start, MAX_FAILURES)
+                .await?
+                .into_iter()
+                .map(FailureSample::from)
+                .collect();
+            if let Err(e) = self.notify(&config, &anomaly, &failures).await {
+                warn!(skill = %anomaly.skill, "Failed to send anomaly alert: {:#}", e);
+                continue;
+            }
+            info!(skill = %anomaly.skill, kind = ?anomaly.kind, "{}", anomaly.message);
+            self.last_alerted.lock().unwrap().insert(key, now);
+            alerted.push(anomaly);
+        }
+        Ok(alerted)
+    }
+
+    async fn notify(&self, config: &AlertConfig, anomaly: &Anomaly, failures: &[FailureSample]) -> Result<()> {
+        let namespace = self.state.namespace.as_deref();
+        if let Some(url) = &config.webhook_url {
+            let payload = AlertPayload { event: "skill.anomaly", namespace, anomaly, failures };
+            self.client.post(url).json(&payload).send().await
+                .and_then(|response| response.error_for_status())
+                .context("Webhook request failed")?;
+        }
+        if let Some(url) = &config.slack_webhook_url {
+            self.client.post(url).json(&slack_message(anomaly, namespace, failures)).send().await
+                .and_then(|response| response.error_for_status())
+                .context("Slack request failed")?;
+        }
+        Ok(())
+    }
+
+    /// Run the analyzer until the server shuts down
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let shutdown = self.state.shutdown.clone();
+            let mut ticker = tokio::time::interval(self.interval);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = shutdown.triggered() => break,
+                }
+                if let Err(e) = self.run_once().await {
+                    warn!("Anomaly analysis failed: {:#}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(skill: &str, success: bool, duration_ms: u64, minutes_ago: i64) -> ExecutionSample {
+        ExecutionSample {
+            skill: skill.to_string(),
+            tool: "get".to_string(),
+            success,
+            duration_ms,
+            started_at: Utc::now() - chrono::Duration::minutes(minutes_ago),
+        }
+    }
+
+    #[test]
+    fn test_detect_failure_rate_and_latency_spikes() {
+        let config = AlertConfig::default();
+        let window_start = Utc::now() - chrono::Duration::minutes(60);
+
+        let mut samples = Vec::new();
+        for i in 0..20 {
+            // A day of healthy, fast baseline for both skills
+            samples.push(sample("kubernetes", true, 100, 120 + i));
+            samples.push(sample("docker", true, 100, 120 + i));
+        }
+        for i in 0..6 {
+            // kubernetes starts failing; docker slows down
+            samples.push(sample("kubernetes", i % 3 == 0, 100, i));
+            samples.push(sample("docker", true, 2000, i));
+        }
+        // Too few recent executions to judge
+        samples.push(sample("git", false, 100, 1));
+
+        let anomalies = detect(&samples, window_start, &config);
+        let found: Vec<(&str, AnomalyKind)> = anomalies.iter().map(|a| (a.skill.as_str(), a.kind)).collect();
+        assert_eq!(found, vec![("docker", AnomalyKind::Latency), ("kubernetes", AnomalyKind::FailureRate)]);
+        assert!(anomalies[1].message.contains("67% of 6 executions failed"));
+    }
+
+    #[test]
+    fn test_detect_ignores_steady_failure_rate() {
+        let config = AlertConfig::default();
+        let window_start = Utc::now() - chrono::Duration::minutes(60);
+
+        // Flaky all week: no change, no alert
+        let samples: Vec<ExecutionSample> = (0..40)
+            .map(|i| sample("flaky", i % 3 != 0, 100, i * 10))
+            .collect();
+        assert!(detect(&samples, window_start, &config).is_empty());
+    }
+
+    #[test]
+    fn test_config_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(ALERTS_FILE);
+        assert_eq!(load_config(&path), AlertConfig::default());
+
+        let config = AlertConfig {
+            enabled: true,
+            slack_webhook_url: Some("https://hooks.slack.com/services/T/B/X".to_string()),
+            failure_rate: 0.3,
+            ..Default::default()
+        };
+        save_config(&path, &config).unwrap();
+        assert_eq!(load_config(&path), config);
+    }
+}
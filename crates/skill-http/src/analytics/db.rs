@@ -3,9 +3,14 @@
 use std::path::Path;
 use anyhow::{Context, Result};
 use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use skill_runtime::HistoryRetention;
 use uuid::Uuid;
 
 use super::types::*;
+use crate::maintenance::{self, PruneReport, RetainedTable};
+
+/// id, skill, tools, sampled, parsed, valid, avg_score, quality, timestamp
+type ExampleQualityRow = (String, String, i64, i64, i64, i64, f32, f32, String);
 
 /// SQLite-based search analytics database
 pub struct SearchAnalyticsDb {
@@ -63,6 +68,11 @@ impl SearchAnalyticsDb {
         &self.pool
     }
 
+    /// Wait for open connections to finish and close them
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+
     /// Initialize database schema with tables and indexes
     async fn setup(&self) -> Result<()> {
         // Create search_history table
@@ -149,6 +159,36 @@ impl SearchAnalyticsDb {
         .await
         .context("Failed to create search_feedback timestamp index")?;
 
+        // Create example_quality table
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS example_quality (
+                id TEXT PRIMARY KEY,
+                skill TEXT NOT NULL,
+                tools INTEGER NOT NULL,
+                sampled INTEGER NOT NULL,
+                parsed INTEGER NOT NULL,
+                valid INTEGER NOT NULL,
+                avg_score REAL NOT NULL,
+                quality REAL NOT NULL,
+                timestamp TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create example_quality table")?;
+
+        sqlx::query(
+            r#"
+            CREATE INDEX IF NOT EXISTS idx_example_quality_skill
+            ON example_quality(skill, timestamp);
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create example_quality skill index")?;
+
         Ok(())
     }
 
@@ -206,6 +246,86 @@ impl SearchAnalyticsDb {
         Ok(())
     }
 
+    /// Record an example quality evaluation
+    pub async fn log_example_quality(&self, entry: &ExampleQualityEntry) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO example_quality
+            (id, skill, tools, sampled, parsed, valid, avg_score, quality, timestamp)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(entry.id.to_string())
+        .bind(&entry.skill)
+        .bind(entry.tools as i64)
+        .bind(entry.sampled as i64)
+        .bind(entry.parsed as i64)
+        .bind(entry.valid as i64)
+        .bind(entry.avg_score)
+        .bind(entry.quality)
+        .bind(entry.timestamp.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert example quality entry")?;
+
+        Ok(())
+    }
+
+    /// Get example quality evaluations, oldest first
+    pub async fn get_example_quality(
+        &self,
+        skill: Option<&str>,
+        days: u32,
+    ) -> Result<Vec<ExampleQualityEntry>> {
+        let from_date = chrono::Utc::now() - chrono::Duration::days(days as i64);
+
+        let rows: Vec<ExampleQualityRow> = sqlx::query_as(
+            r#"
+            SELECT id, skill, tools, sampled, parsed, valid, avg_score, quality, timestamp
+            FROM example_quality
+            WHERE timestamp >= ?1 AND (?2 IS NULL OR skill = ?2)
+            ORDER BY timestamp ASC
+            "#
+        )
+        .bind(from_date.to_rfc3339())
+        .bind(skill)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to get example quality")?;
+
+        let entries = rows
+            .into_iter()
+            .filter_map(|(id, skill, tools, sampled, parsed, valid, avg_score, quality, timestamp)| {
+                Some(ExampleQualityEntry {
+                    id: Uuid::parse_str(&id).ok()?,
+                    skill,
+                    tools: tools as usize,
+                    sampled: sampled as usize,
+                    parsed: parsed as usize,
+                    valid: valid as usize,
+                    avg_score,
+                    quality,
+                    timestamp: chrono::DateTime::parse_from_rfc3339(&timestamp)
+                        .ok()?
+                        .with_timezone(&chrono::Utc),
+                })
+            })
+            .collect();
+
+        Ok(entries)
+    }
+
+    /// Apply a `[history]` retention policy to searches, feedback and
+    /// example quality, then compact the database
+    pub async fn apply_retention(&self, retention: &HistoryRetention) -> Result<PruneReport> {
+        let tables = [
+            RetainedTable { name: "search_history", time_column: "timestamp" },
+            RetainedTable { name: "search_feedback", time_column: "timestamp" },
+            RetainedTable { name: "example_quality", time_column: "timestamp" },
+        ];
+        maintenance::apply_retention(&self.pool, &tables, retention).await
+    }
+
     /// Get search history with optional filtering
     pub async fn get_history(&self, filter: &SearchHistoryFilter) -> Result<Vec<SearchHistoryEntry>> {
         // Build query dynamically based on filters
@@ -641,4 +761,30 @@ mod tests {
         assert_eq!(history.len(), 1);
         assert_eq!(history[0].query, "test query");
     }
+
+    #[tokio::test]
+    async fn test_example_quality_history() {
+        let db = SearchAnalyticsDb::new(":memory:").await.unwrap();
+
+        for (skill, valid) in [("kubernetes", 6), ("kubernetes", 9), ("docker", 4)] {
+            let entry = ExampleQualityEntry {
+                id: Uuid::new_v4(),
+                skill: skill.to_string(),
+                tools: 3,
+                sampled: 10,
+                parsed: 10,
+                valid,
+                avg_score: 0.8,
+                quality: 0.75,
+                timestamp: chrono::Utc::now(),
+            };
+            db.log_example_quality(&entry).await.unwrap();
+        }
+
+        let kubernetes = db.get_example_quality(Some("kubernetes"), 30).await.unwrap();
+        assert_eq!(kubernetes.len(), 2);
+        assert_eq!(kubernetes[1].valid, 9);
+
+        assert_eq!(db.get_example_quality(None, 30).await.unwrap().len(), 3);
+    }
 }
@@ -4,9 +4,15 @@
 //! - Search history with client metadata
 //! - User feedback on search results
 //! - Query performance metrics
+//!
+//! and per-skill usage (counts, success rates, latency percentiles)
+//! aggregated from execution history.
 
 pub mod db;
 pub mod types;
+pub mod usage;
 
 pub use db::SearchAnalyticsDb;
 pub use types::*;
+pub use skill_api::{DailyUsage, SkillUsage, ToolUsage, UsageStats};
+pub use usage::{usage_stats, ExecutionSample};
@@ -59,6 +59,36 @@ impl FeedbackType {
     }
 }
 
+/// Example quality of one skill, recorded by `skill enhance --evaluate`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExampleQualityEntry {
+    pub id: Uuid,
+    pub skill: String,
+    pub tools: usize,
+    pub sampled: usize,
+    pub parsed: usize,
+    pub valid: usize,
+    pub avg_score: f32,
+    pub quality: f32,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl From<&skill_runtime::generation::SkillEvaluation> for ExampleQualityEntry {
+    fn from(evaluation: &skill_runtime::generation::SkillEvaluation) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            skill: evaluation.skill.clone(),
+            tools: evaluation.tools,
+            sampled: evaluation.sampled,
+            parsed: evaluation.parsed,
+            valid: evaluation.valid,
+            avg_score: evaluation.avg_score,
+            quality: evaluation.quality,
+            timestamp: evaluation.evaluated_at,
+        }
+    }
+}
+
 /// Filter for querying search history
 #[derive(Debug, Clone, Default)]
 pub struct SearchHistoryFilter {
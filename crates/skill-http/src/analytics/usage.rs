@@ -0,0 +1,164 @@
+//! Per-skill and per-tool usage aggregated from execution history
+//!
+//! SQLite has no percentile functions, so the execution history database
+//! hands over one [`ExecutionSample`] per finished execution and the
+//! counts, success rates and latency percentiles are computed here.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use skill_api::{DailyUsage, SkillUsage, ToolUsage, UsageStats};
+
+/// One finished execution, reduced to what usage analytics needs
+#[derive(Debug, Clone)]
+pub struct ExecutionSample {
+    pub skill: String,
+    pub tool: String,
+    pub success: bool,
+    pub duration_ms: u64,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Aggregate a group of samples
+pub fn usage_stats<'a>(samples: impl IntoIterator<Item = &'a ExecutionSample>) -> UsageStats {
+    let mut durations = Vec::new();
+    let mut failures = 0;
+    let mut last_run = None;
+    for sample in samples {
+        durations.push(sample.duration_ms);
+        if !sample.success {
+            failures += 1;
+        }
+        last_run = last_run.max(Some(sample.started_at));
+    }
+    if durations.is_empty() {
+        return UsageStats::default();
+    }
+
+    durations.sort_unstable();
+    let executions = durations.len();
+    UsageStats {
+        executions,
+        failures,
+        success_rate: (executions - failures) as f32 / executions as f32,
+        avg_ms: durations.iter().sum::<u64>() as f64 / executions as f64,
+        p50_ms: percentile(&durations, 0.50),
+        p95_ms: percentile(&durations, 0.95),
+        last_run,
+    }
+}
+
+/// Usage per skill, most used first
+pub fn usage_by_skill(samples: &[ExecutionSample]) -> Vec<SkillUsage> {
+    let mut usage: Vec<SkillUsage> = group_by(samples, |sample| &sample.skill)
+        .into_iter()
+        .map(|(skill, group)| SkillUsage {
+            skill: skill.to_string(),
+            stats: usage_stats(group),
+        })
+        .collect();
+    usage.sort_by(|a, b| b.stats.executions.cmp(&a.stats.executions).then_with(|| a.skill.cmp(&b.skill)));
+    usage
+}
+
+/// Usage per tool, most used first
+pub fn usage_by_tool(samples: &[ExecutionSample]) -> Vec<ToolUsage> {
+    let mut usage: Vec<ToolUsage> = group_by(samples, |sample| &sample.tool)
+        .into_iter()
+        .map(|(tool, group)| ToolUsage {
+            tool: tool.to_string(),
+            stats: usage_stats(group),
+        })
+        .collect();
+    usage.sort_by(|a, b| b.stats.executions.cmp(&a.stats.executions).then_with(|| a.tool.cmp(&b.tool)));
+    usage
+}
+
+/// Executions per day, oldest first; days without executions are omitted
+pub fn daily_usage(samples: &[ExecutionSample]) -> Vec<DailyUsage> {
+    let mut days: BTreeMap<NaiveDate, DailyUsage> = BTreeMap::new();
+    for sample in samples {
+        let date = sample.started_at.date_naive();
+        let day = days.entry(date).or_insert(DailyUsage { date, executions: 0, failures: 0 });
+        day.executions += 1;
+        if !sample.success {
+            day.failures += 1;
+        }
+    }
+    days.into_values().collect()
+}
+
+fn group_by<'a>(
+    samples: &'a [ExecutionSample],
+    key: impl Fn(&'a ExecutionSample) -> &'a str,
+) -> HashMap<&'a str, Vec<&'a ExecutionSample>> {
+    let mut groups: HashMap<&str, Vec<&ExecutionSample>> = HashMap::new();
+    for sample in samples {
+        groups.entry(key(sample)).or_default().push(sample);
+    }
+    groups
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[u64], p: f64) -> u64 {
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(skill: &str, tool: &str, success: bool, duration_ms: u64, days_ago: i64) -> ExecutionSample {
+        ExecutionSample {
+            skill: skill.to_string(),
+            tool: tool.to_string(),
+            success,
+            duration_ms,
+            started_at: Utc::now() - chrono::Duration::days(days_ago),
+        }
+    }
+
+    #[test]
+    fn test_usage_stats_percentiles() {
+        let samples: Vec<ExecutionSample> = (1..=20)
+            .map(|i| sample("kubernetes", "get", i % 5 != 0, i * 10, 0))
+            .collect();
+
+        let stats = usage_stats(&samples);
+        assert_eq!(stats.executions, 20);
+        assert_eq!(stats.failures, 4);
+        assert!((stats.success_rate - 0.8).abs() < 1e-6);
+        assert_eq!(stats.p50_ms, 100);
+        assert_eq!(stats.p95_ms, 190);
+        assert!((stats.avg_ms - 105.0).abs() < 1e-9);
+
+        assert_eq!(usage_stats(&[] as &[ExecutionSample]), UsageStats::default());
+    }
+
+    #[test]
+    fn test_usage_by_skill_tool_and_day() {
+        let samples = vec![
+            sample("kubernetes", "get", true, 100, 1),
+            sample("kubernetes", "logs", false, 300, 1),
+            sample("kubernetes", "get", true, 200, 0),
+            sample("docker", "ps", true, 50, 0),
+        ];
+
+        let skills = usage_by_skill(&samples);
+        assert_eq!(skills[0].skill, "kubernetes");
+        assert_eq!(skills[0].stats.executions, 3);
+        assert_eq!(skills[0].stats.failures, 1);
+        assert_eq!(skills[1].skill, "docker");
+
+        let tools = usage_by_tool(&samples[..3]);
+        assert_eq!(tools[0].tool, "get");
+        assert_eq!(tools[0].stats.p95_ms, 200);
+        assert_eq!(tools[1].stats.success_rate, 0.0);
+
+        let daily = daily_usage(&samples);
+        assert_eq!(daily.len(), 2);
+        assert_eq!((daily[0].executions, daily[0].failures), (2, 1));
+        assert_eq!((daily[1].executions, daily[1].failures), (2, 0));
+    }
+}
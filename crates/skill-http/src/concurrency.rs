@@ -0,0 +1,220 @@
+//! Concurrency - limits how many tool executions run at once
+//!
+//! Every execution takes a permit from the [`ExecutionLimiter`] before it
+//! starts. When all permits are taken, requests wait in a bounded queue;
+//! once the queue is full too, new requests are refused (`429`) with the
+//! limiter's [`ExecutionUtilization`] attached so clients can back off. The
+//! limit can be changed at runtime through `PUT /api/config`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::types::ExecutionUtilization;
+
+/// Executions that may run at once unless configured otherwise
+pub const DEFAULT_MAX_CONCURRENT_EXECUTIONS: usize = 10;
+
+/// Executions that may wait for a permit unless configured otherwise
+pub const DEFAULT_MAX_QUEUED_EXECUTIONS: usize = 100;
+
+/// A request was refused because the queue is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Saturated {
+    /// Where the request would have been in the queue
+    pub queue_position: usize,
+    /// Utilization when it was refused
+    pub utilization: ExecutionUtilization,
+}
+
+struct Inner {
+    semaphore: Arc<Semaphore>,
+    max_concurrent: AtomicUsize,
+    max_queued: AtomicUsize,
+    running: AtomicUsize,
+    queued: AtomicUsize,
+    /// Permits to retire as they are released, after the limit was lowered
+    /// while they were in use
+    excess: Mutex<usize>,
+}
+
+/// Semaphore plus bounded wait queue for tool executions
+#[derive(Clone)]
+pub struct ExecutionLimiter {
+    inner: Arc<Inner>,
+}
+
+impl ExecutionLimiter {
+    /// Create a limiter allowing `max_concurrent` executions with up to
+    /// `max_queued` more waiting
+    pub fn new(max_concurrent: usize, max_queued: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                semaphore: Arc::new(Semaphore::new(max_concurrent)),
+                max_concurrent: AtomicUsize::new(max_concurrent),
+                max_queued: AtomicUsize::new(max_queued),
+                running: AtomicUsize::new(0),
+                queued: AtomicUsize::new(0),
+                excess: Mutex::new(0),
+            }),
+        }
+    }
+
+    /// Wait for a permit to run an execution, or fail at once if the queue
+    /// is full
+    pub async fn acquire(&self) -> Result<ExecutionPermit, Saturated> {
+        let inner = &self.inner;
+        let permit = match inner.semaphore.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                let queue_position = inner.queued.fetch_add(1, Ordering::SeqCst) + 1;
+                let _queued = QueueSlot(&inner.queued);
+                if queue_position > inner.max_queued.load(Ordering::SeqCst) {
+                    return Err(Saturated {
+                        queue_position,
+                        utilization: self.utilization(),
+                    });
+                }
+                inner
+                    .semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("execution semaphore is never closed")
+            }
+        };
+        inner.running.fetch_add(1, Ordering::SeqCst);
+        Ok(ExecutionPermit {
+            permit: Some(permit),
+            inner: inner.clone(),
+        })
+    }
+
+    /// Change how many executions may run at once
+    ///
+    /// Raising the limit lets queued executions start immediately; lowering
+    /// it takes effect as running executions finish.
+    pub fn set_max_concurrent(&self, max_concurrent: usize) {
+        let inner = &self.inner;
+        let mut excess = inner.excess.lock().unwrap();
+        let previous = inner.max_concurrent.swap(max_concurrent, Ordering::SeqCst);
+        if max_concurrent > previous {
+            let mut added = max_concurrent - previous;
+            // Permits still due for retirement cover part of the increase
+            let kept = added.min(*excess);
+            *excess -= kept;
+            added -= kept;
+            inner.semaphore.add_permits(added);
+        } else {
+            let removed = previous - max_concurrent;
+            let forgotten = inner.semaphore.forget_permits(removed);
+            *excess += removed - forgotten;
+        }
+    }
+
+    /// Change how many executions may wait for a permit
+    pub fn set_max_queued(&self, max_queued: usize) {
+        self.inner.max_queued.store(max_queued, Ordering::SeqCst);
+    }
+
+    /// Current load
+    pub fn utilization(&self) -> ExecutionUtilization {
+        let inner = &self.inner;
+        ExecutionUtilization {
+            running: inner.running.load(Ordering::SeqCst),
+            queued: inner.queued.load(Ordering::SeqCst),
+            max_concurrent: inner.max_concurrent.load(Ordering::SeqCst),
+            max_queued: inner.max_queued.load(Ordering::SeqCst),
+        }
+    }
+}
+
+impl Default for ExecutionLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_EXECUTIONS, DEFAULT_MAX_QUEUED_EXECUTIONS)
+    }
+}
+
+/// Leaves the queue when dropped, including when the waiting request is
+/// cancelled
+struct QueueSlot<'a>(&'a AtomicUsize);
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Permission to run one execution; released when dropped
+pub struct ExecutionPermit {
+    permit: Option<OwnedSemaphorePermit>,
+    inner: Arc<Inner>,
+}
+
+impl Drop for ExecutionPermit {
+    fn drop(&mut self) {
+        self.inner.running.fetch_sub(1, Ordering::SeqCst);
+        let mut excess = self.inner.excess.lock().unwrap();
+        if *excess > 0 {
+            *excess -= 1;
+            if let Some(permit) = self.permit.take() {
+                permit.forget();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_queue_then_refuse() {
+        let limiter = ExecutionLimiter::new(1, 1);
+        let running = limiter.acquire().await.unwrap();
+
+        // The second request waits in the queue
+        let waiting = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire().await.map(|_| ()) }
+        });
+        while limiter.utilization().queued == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        // The third is refused with its would-be position
+        let refused = limiter.acquire().await.err().unwrap();
+        assert_eq!(refused.queue_position, 2);
+        assert!(refused.utilization.is_saturated());
+        assert_eq!(limiter.utilization().queued, 1);
+
+        drop(running);
+        waiting.await.unwrap().unwrap();
+        assert_eq!(
+            limiter.utilization(),
+            ExecutionUtilization { running: 0, queued: 0, max_concurrent: 1, max_queued: 1 }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resize_while_running() {
+        let limiter = ExecutionLimiter::new(2, 0);
+        let first = limiter.acquire().await.unwrap();
+        let second = limiter.acquire().await.unwrap();
+
+        // Lowering the limit retires a permit once one is released
+        limiter.set_max_concurrent(1);
+        drop(first);
+        assert!(limiter.acquire().await.is_err());
+        drop(second);
+        let third = limiter.acquire().await.unwrap();
+        assert!(limiter.acquire().await.is_err());
+
+        limiter.set_max_concurrent(3);
+        let _fourth = limiter.acquire().await.unwrap();
+        let _fifth = limiter.acquire().await.unwrap();
+        assert!(limiter.acquire().await.is_err());
+        drop(third);
+    }
+}
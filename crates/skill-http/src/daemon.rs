@@ -0,0 +1,545 @@
+//! Daemon - the server on a local Unix socket, and the client CLI commands use
+//!
+//! `skill daemon start` keeps one engine warm in the background: compiled
+//! WASM modules, the embedding model and search index, and autostarted
+//! services. It serves the REST API on `~/.skill-engine/daemon.sock`, plus
+//! `GET /daemon/status` and `POST /daemon/stop`. Commands such as
+//! `skill find` and `skill run` send their work there through
+//! [`DaemonClient`] when it's running, instead of loading models and
+//! compiling skills on every invocation. Set `SKILL_NO_DAEMON=1` to always
+//! run commands in-process.
+//!
+//! The daemon resolves skills from its own manifest, environment and working
+//! directory, fixed when it started. A command only hands work over when
+//! [`DaemonStatus::matches`] says it would run the same way in-process.
+
+use anyhow::{bail, Context, Result};
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{header, Method, Request, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use hyper_util::rt::TokioIo;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use skill_runtime::SkillManifest;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::{UnixListener, UnixStream};
+use tower::Service;
+use tracing::{info, warn};
+
+use crate::indexing::IndexStatus;
+use crate::namespaces::namespace_data_dir;
+use crate::server::{AppState, RunningServer};
+use crate::shutdown::ShutdownController;
+use crate::types::{ApiError, ExecutionRequest, ExecutionResponse, SearchRequest, SearchResponse};
+
+/// Set to run CLI commands in-process even when a daemon is running
+pub const NO_DAEMON_ENV: &str = "SKILL_NO_DAEMON";
+
+/// Responses larger than this are refused
+const MAX_RESPONSE_BYTES: usize = 64 * 1024 * 1024;
+
+/// How long a status check waits before the daemon counts as not running
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Variables that change how skills resolve whatever the manifest says:
+/// where skills and commands are found, and the engine's own settings
+/// (`SKILL_*`, matched by prefix)
+const RESOLUTION_ENV: &[&str] = &["HOME", "PATH"];
+const ENGINE_ENV_PREFIX: &str = "SKILL_";
+
+/// Socket the daemon listens on: `~/.skill-engine/daemon.sock`
+pub fn socket_path() -> PathBuf {
+    namespace_data_dir(None).join("daemon.sock")
+}
+
+/// What `GET /daemon/status` reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    /// Process ID of the daemon
+    pub pid: u32,
+    /// Version of the daemon binary
+    pub version: String,
+    /// Seconds since the daemon started
+    pub uptime_secs: u64,
+    /// Skills loaded
+    pub skills: usize,
+    /// Whether the search index has every loaded skill
+    pub index_ready: bool,
+    /// State of the search index build
+    pub index: String,
+    /// Manifest files the daemon resolves skills from, lowest precedence first
+    #[serde(default)]
+    pub manifest: Vec<PathBuf>,
+    /// Directory native commands run in
+    #[serde(default)]
+    pub working_dir: PathBuf,
+    /// Fingerprint of the daemon's environment (see [`environment_fingerprint`])
+    #[serde(default)]
+    pub environment: String,
+}
+
+impl DaemonStatus {
+    /// Whether the daemon would run a skill from `manifest` the way this
+    /// process would: same manifest files, working directory and environment
+    pub fn matches(&self, manifest: Option<&SkillManifest>) -> bool {
+        let layers = manifest.map_or(&[][..], |manifest| manifest.layers.as_slice());
+        let working_dir = std::env::current_dir().unwrap_or_default();
+        same_files(&self.manifest, layers)
+            && same_file(&self.working_dir, &working_dir)
+            && self.environment == environment_fingerprint(layers)
+    }
+}
+
+fn same_files(a: &[PathBuf], b: &[PathBuf]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_file(a, b))
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// BLAKE3 fingerprint of the environment variables that can change how
+/// skills from `manifest_files` resolve
+///
+/// That's [`RESOLUTION_ENV`], `SKILL_*`, and every variable the manifests
+/// reference through `${...}`. Terminal-specific variables such as
+/// `TERM_SESSION_ID` or `SSH_AUTH_SOCK` are left out, so they don't keep a
+/// command from using the daemon.
+pub fn environment_fingerprint(manifest_files: &[PathBuf]) -> String {
+    let text: String = manifest_files
+        .iter()
+        .filter_map(|path| std::fs::read_to_string(path).ok())
+        .collect::<Vec<_>>()
+        .join("\n");
+    fingerprint_env(std::env::vars_os(), &EnvReferences::scan(&text))
+}
+
+/// Variables named in `${...}` placeholders
+#[derive(Debug, Default)]
+struct EnvReferences {
+    names: BTreeSet<String>,
+    /// Leading parts of nested names like `${URL_${ENV}}`, which can resolve
+    /// to any variable starting with them
+    prefixes: BTreeSet<String>,
+}
+
+impl EnvReferences {
+    fn scan(text: &str) -> Self {
+        let mut references = Self::default();
+        for (start, _) in text.match_indices("${") {
+            let rest = &text[start + 2..];
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let name = &rest[..end];
+            if rest[end..].starts_with("${") {
+                references.prefixes.insert(name.to_string());
+            } else if !name.is_empty() {
+                references.names.insert(name.to_string());
+            }
+        }
+        references
+    }
+
+    fn affects_resolution(&self, key: &str) -> bool {
+        RESOLUTION_ENV.contains(&key)
+            || key.starts_with(ENGINE_ENV_PREFIX)
+            || self.names.contains(key)
+            || self.prefixes.iter().any(|prefix| key.starts_with(prefix.as_str()))
+    }
+}
+
+fn fingerprint_env(
+    vars: impl IntoIterator<Item = (OsString, OsString)>,
+    references: &EnvReferences,
+) -> String {
+    let vars: BTreeMap<OsString, OsString> = vars
+        .into_iter()
+        .filter(|(key, _)| key.to_str().is_some_and(|key| references.affects_resolution(key)))
+        .collect();
+    let mut hasher = blake3::Hasher::new();
+    for (key, value) in &vars {
+        hasher.update(key.as_encoded_bytes());
+        hasher.update(b"=");
+        hasher.update(value.as_encoded_bytes());
+        hasher.update(b"\0");
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
+/// Routes controlling the daemon itself
+fn control_routes(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/daemon/status", get(daemon_status))
+        .route("/daemon/stop", post(daemon_stop))
+        .with_state(state)
+}
+
+async fn daemon_status(State(state): State<Arc<AppState>>) -> Json<DaemonStatus> {
+    let index_status = state.index_status.read().await.clone();
+    let manifest = state
+        .manifest
+        .read()
+        .await
+        .as_ref()
+        .map(|manifest| manifest.layers.clone())
+        .unwrap_or_default();
+    Json(DaemonStatus {
+        pid: std::process::id(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        uptime_secs: state.started_at.elapsed().as_secs(),
+        skills: state.skills.read().await.len(),
+        index_ready: matches!(index_status, IndexStatus::Ready { .. }),
+        index: index_status.health().message.unwrap_or_default(),
+        working_dir: std::env::current_dir().unwrap_or_default(),
+        environment: environment_fingerprint(&manifest),
+        manifest,
+    })
+}
+
+async fn daemon_stop(State(state): State<Arc<AppState>>) -> StatusCode {
+    info!("Daemon stop requested");
+    state.shutdown.trigger();
+    StatusCode::ACCEPTED
+}
+
+impl RunningServer {
+    /// Serve [`RunningServer::app`] and the daemon routes on a Unix socket
+    /// until stopped, then close the state and remove the socket
+    ///
+    /// A socket left behind by a daemon that died is replaced; callers check
+    /// that no daemon answers on it first.
+    pub async fn serve_unix(mut self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        if path.exists() {
+            std::fs::remove_file(path)
+                .with_context(|| format!("Failed to remove stale socket {}", path.display()))?;
+        }
+        let listener = UnixListener::bind(path)
+            .with_context(|| format!("Failed to bind to {}", path.display()))?;
+        // Only the user running the daemon may talk to it
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        info!(socket = %path.display(), "Daemon listening");
+
+        let app = std::mem::take(&mut self.app).merge(control_routes(self.state.clone()));
+        let shutdown = self.state.shutdown.clone();
+        shutdown.listen_for_signals();
+        serve_socket(listener, app, &shutdown).await;
+
+        let _ = std::fs::remove_file(path);
+        self.close().await;
+        Ok(())
+    }
+}
+
+/// Serve `app` on `listener` until `shutdown` is triggered
+async fn serve_socket(listener: UnixListener, app: Router, shutdown: &ShutdownController) {
+    loop {
+        let stream = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    warn!(error = %e, "Failed to accept daemon connection");
+                    continue;
+                }
+            },
+            _ = shutdown.triggered() => return,
+        };
+
+        let app = app.clone();
+        tokio::spawn(async move {
+            let service =
+                hyper::service::service_fn(move |request: Request<hyper::body::Incoming>| {
+                    app.clone().call(request)
+                });
+            if let Err(e) = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                warn!(error = %e, "Daemon connection failed");
+            }
+        });
+    }
+}
+
+/// Client for a running daemon
+#[derive(Debug, Clone)]
+pub struct DaemonClient {
+    socket: PathBuf,
+}
+
+impl DaemonClient {
+    /// Client for the daemon on `socket`, whether or not one is running
+    pub fn new(socket: impl Into<PathBuf>) -> Self {
+        Self {
+            socket: socket.into(),
+        }
+    }
+
+    /// The running daemon and its status, unless there is none or
+    /// `SKILL_NO_DAEMON` is set
+    pub async fn connect() -> Option<(Self, DaemonStatus)> {
+        if std::env::var_os(NO_DAEMON_ENV).is_some_and(|value| !value.is_empty() && value != "0") {
+            return None;
+        }
+        let client = Self::new(socket_path());
+        if !client.socket.exists() {
+            return None;
+        }
+        match tokio::time::timeout(CONNECT_TIMEOUT, client.status()).await {
+            Ok(Ok(status)) => Some((client, status)),
+            _ => None,
+        }
+    }
+
+    /// `GET /daemon/status`
+    pub async fn status(&self) -> Result<DaemonStatus> {
+        self.request(Method::GET, "/daemon/status", None::<&()>)
+            .await
+    }
+
+    /// Ask the daemon to shut down; it finishes in-flight executions first
+    pub async fn stop(&self) -> Result<()> {
+        let response = self.send(Method::POST, "/daemon/stop", None::<&()>).await?;
+        if !response.0.is_success() {
+            bail!("Daemon refused to stop: {}", response.0);
+        }
+        Ok(())
+    }
+
+    /// Search the daemon's index (`POST /api/search`)
+    pub async fn search(&self, request: &SearchRequest) -> Result<SearchResponse> {
+        self.request(Method::POST, "/api/search", Some(request))
+            .await
+    }
+
+    /// Execute a tool in the daemon (`POST /api/execute`)
+    pub async fn execute(&self, request: &ExecutionRequest) -> Result<ExecutionResponse> {
+        self.request(Method::POST, "/api/execute", Some(request))
+            .await
+    }
+
+    /// Send a request, parsing a JSON response or the API error
+    async fn request<B: Serialize, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+    ) -> Result<T> {
+        let (status, bytes) = self.send(method, path, body).await?;
+        if !status.is_success() {
+            let message = serde_json::from_slice::<ApiError>(&bytes)
+                .map(|error| error.message)
+                .unwrap_or_else(|_| String::from_utf8_lossy(&bytes).to_string());
+            bail!("Daemon returned {}: {}", status, message);
+        }
+        serde_json::from_slice(&bytes)
+            .with_context(|| format!("Invalid response from daemon for {}", path))
+    }
+
+    async fn send<B: Serialize>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+    ) -> Result<(StatusCode, bytes::Bytes)> {
+        let stream = UnixStream::connect(&self.socket)
+            .await
+            .with_context(|| format!("Failed to connect to daemon at {}", self.socket.display()))?;
+        let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .context("Failed to connect to daemon")?;
+        tokio::spawn(async move {
+            let _ = connection.await;
+        });
+
+        let body = match body {
+            Some(body) => Body::from(serde_json::to_vec(body)?),
+            None => Body::empty(),
+        };
+        let request = Request::builder()
+            .method(method)
+            .uri(path)
+            .header(header::HOST, "localhost")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(body)?;
+        let response = sender
+            .send_request(request)
+            .await
+            .context("Daemon closed the connection")?;
+        let status = response.status();
+        let bytes = axum::body::to_bytes(Body::new(response.into_body()), MAX_RESPONSE_BYTES)
+            .await
+            .context("Failed to read daemon response")?;
+        Ok((status, bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_client_over_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("daemon.sock");
+        let shutdown = ShutdownController::new();
+        let app = Router::new()
+            .route(
+                "/daemon/status",
+                get(|| async {
+                    Json(DaemonStatus {
+                        pid: 42,
+                        version: "1.0.0".to_string(),
+                        uptime_secs: 5,
+                        skills: 3,
+                        index_ready: true,
+                        index: "Ready".to_string(),
+                        manifest: Vec::new(),
+                        working_dir: PathBuf::from("/"),
+                        environment: String::new(),
+                    })
+                }),
+            )
+            .route(
+                "/daemon/stop",
+                post({
+                    let shutdown = shutdown.clone();
+                    move || async move {
+                        shutdown.trigger();
+                        StatusCode::ACCEPTED
+                    }
+                }),
+            );
+        let listener = UnixListener::bind(&socket).unwrap();
+        let serving = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { serve_socket(listener, app, &shutdown).await }
+        });
+
+        let client = DaemonClient::new(&socket);
+        let status = client.status().await.unwrap();
+        assert_eq!(status.pid, 42);
+        assert_eq!(status.skills, 3);
+
+        let request = SearchRequest {
+            query: "list pods".to_string(),
+            top_k: 5,
+            skill_filter: None,
+            include_examples: false,
+            session_id: None,
+            filters: None,
+            explain: false,
+        };
+        let error = client.search(&request).await.unwrap_err();
+        assert!(error.to_string().contains("404"));
+
+        client.stop().await.unwrap();
+        serving.await.unwrap();
+    }
+
+    #[test]
+    fn test_status_matches_caller() {
+        let dir = tempfile::tempdir().unwrap();
+        let daemon_manifest = dir.path().join("daemon.toml");
+        let caller_manifest = dir.path().join("caller.toml");
+        std::fs::write(&daemon_manifest, "version = \"1\"\n").unwrap();
+        std::fs::write(&caller_manifest, "version = \"1\"\n").unwrap();
+
+        let status = DaemonStatus {
+            pid: 42,
+            version: "1.0.0".to_string(),
+            uptime_secs: 5,
+            skills: 3,
+            index_ready: true,
+            index: "Ready".to_string(),
+            manifest: vec![daemon_manifest.clone()],
+            working_dir: std::env::current_dir().unwrap(),
+            environment: environment_fingerprint(std::slice::from_ref(&daemon_manifest)),
+        };
+        let same = SkillManifest::load(&daemon_manifest).unwrap();
+        assert!(status.matches(Some(&same)));
+
+        // The caller's manifest differs, or it has none
+        let other = SkillManifest::load(&caller_manifest).unwrap();
+        assert!(!status.matches(Some(&other)));
+        assert!(!status.matches(None));
+
+        // Started elsewhere, or with another environment
+        let elsewhere = DaemonStatus {
+            working_dir: dir.path().to_path_buf(),
+            ..status.clone()
+        };
+        assert!(!elsewhere.matches(Some(&same)));
+        let other_env = DaemonStatus {
+            environment: fingerprint_env(
+                [(OsString::from("HOME"), OsString::from("/tmp"))],
+                &EnvReferences::default(),
+            ),
+            ..status
+        };
+        assert!(!other_env.matches(Some(&same)));
+    }
+
+    #[test]
+    fn test_environment_fingerprint() {
+        let references = EnvReferences::scan(
+            "config.kubeconfig = \"${KUBECONFIG}\"\nconfig.url = \"${API_URL_${STAGE:-dev}}\"\n",
+        );
+        let fingerprint = |vars: &[(&str, &str)]| {
+            fingerprint_env(
+                vars.iter()
+                    .map(|(k, v)| (OsString::from(k), OsString::from(v))),
+                &references,
+            )
+        };
+        let base = fingerprint(&[("HOME", "/home/dev"), ("KUBECONFIG", "/home/dev/kube")]);
+
+        // Order, shell bookkeeping and terminal-specific variables don't matter
+        let reordered = fingerprint(&[
+            ("KUBECONFIG", "/home/dev/kube"),
+            ("PWD", "/tmp"),
+            ("HOME", "/home/dev"),
+            ("SHLVL", "3"),
+            ("TERM_SESSION_ID", "w0t0p0"),
+            ("SSH_AUTH_SOCK", "/tmp/agent.sock"),
+            ("COLUMNS", "120"),
+        ]);
+        assert_eq!(reordered, base);
+
+        // Nor do variables the manifest never references
+        let unreferenced = fingerprint(&[
+            ("HOME", "/home/dev"),
+            ("KUBECONFIG", "/home/dev/kube"),
+            ("AWS_PROFILE", "prod"),
+        ]);
+        assert_eq!(unreferenced, base);
+
+        // Referenced values, nested names and engine settings do
+        let changed = fingerprint(&[("HOME", "/home/dev"), ("KUBECONFIG", "/tmp")]);
+        assert_ne!(changed, base);
+        for extra in [("STAGE", "prod"), ("API_URL_prod", "https://api"), ("SKILL_STRICT_ENV", "1")] {
+            let with_extra = fingerprint(&[
+                ("HOME", "/home/dev"),
+                ("KUBECONFIG", "/home/dev/kube"),
+                extra,
+            ]);
+            assert_ne!(with_extra, base, "{} should count", extra.0);
+        }
+    }
+}
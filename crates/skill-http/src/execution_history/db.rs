@@ -5,6 +5,10 @@ use anyhow::{Context, Result};
 use sqlx::{SqlitePool, sqlite::SqlitePoolOptions, Row};
 use chrono::{DateTime, Utc};
 
+use skill_runtime::HistoryRetention;
+
+use crate::analytics::ExecutionSample;
+use crate::maintenance::{self, PruneReport, RetainedTable};
 use crate::types::{ExecutionHistoryEntry, ExecutionStatus};
 
 /// SQLite-based execution history database
@@ -63,6 +67,11 @@ impl ExecutionHistoryDb {
         &self.pool
     }
 
+    /// Wait for open connections to finish and close them
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+
     /// Initialize database schema with tables and indexes
     async fn setup(&self) -> Result<()> {
         // Create execution_history table
@@ -77,7 +86,10 @@ impl ExecutionHistoryDb {
                 duration_ms INTEGER NOT NULL,
                 started_at TEXT NOT NULL,
                 error TEXT,
-                output TEXT
+                output TEXT,
+                args TEXT,
+                environment TEXT,
+                resource_usage TEXT
             )
             "#,
         )
@@ -85,6 +97,25 @@ impl ExecutionHistoryDb {
         .await
         .context("Failed to create execution_history table")?;
 
+        // Databases created before arguments, environments and resource
+        // usage were recorded lack those columns
+        for column in ["args", "environment", "resource_usage"] {
+            let exists: i64 = sqlx::query(
+                "SELECT COUNT(*) AS count FROM pragma_table_info('execution_history') WHERE name = ?",
+            )
+            .bind(column)
+            .fetch_one(&self.pool)
+            .await
+            .context("Failed to inspect execution_history table")?
+            .get("count");
+            if exists == 0 {
+                sqlx::query(&format!("ALTER TABLE execution_history ADD COLUMN {} TEXT", column))
+                    .execute(&self.pool)
+                    .await
+                    .with_context(|| format!("Failed to add {} column to execution_history", column))?;
+            }
+        }
+
         // Create indexes for common queries
         sqlx::query(
             r#"
@@ -116,6 +147,60 @@ impl ExecutionHistoryDb {
         .await
         .context("Failed to create status index")?;
 
+        // Full-text index over output and errors, kept in sync by triggers.
+        // Existing rows are indexed the first time the table is created.
+        let fts_exists: i64 = sqlx::query(
+            "SELECT COUNT(*) AS count FROM sqlite_master WHERE type = 'table' AND name = 'execution_history_fts'",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to inspect execution history search index")?
+        .get("count");
+
+        sqlx::query(
+            r#"
+            CREATE VIRTUAL TABLE IF NOT EXISTS execution_history_fts USING fts5(
+                output, error, content='execution_history', content_rowid='rowid'
+            );
+
+            CREATE TRIGGER IF NOT EXISTS execution_history_fts_insert
+            AFTER INSERT ON execution_history BEGIN
+                INSERT INTO execution_history_fts(rowid, output, error)
+                VALUES (new.rowid, new.output, new.error);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS execution_history_fts_delete
+            AFTER DELETE ON execution_history BEGIN
+                INSERT INTO execution_history_fts(execution_history_fts, rowid, output, error)
+                VALUES ('delete', old.rowid, old.output, old.error);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS execution_history_fts_update
+            AFTER UPDATE ON execution_history BEGIN
+                INSERT INTO execution_history_fts(execution_history_fts, rowid, output, error)
+                VALUES ('delete', old.rowid, old.output, old.error);
+                INSERT INTO execution_history_fts(rowid, output, error)
+                VALUES (new.rowid, new.output, new.error);
+            END;
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create execution history search index")?;
+
+        if fts_exists == 0 {
+            self.rebuild_search_index().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Re-index all rows for full-text search
+    async fn rebuild_search_index(&self) -> Result<()> {
+        sqlx::query("INSERT INTO execution_history_fts(execution_history_fts) VALUES ('rebuild')")
+            .execute(&self.pool)
+            .await
+            .context("Failed to rebuild execution history search index")?;
         Ok(())
     }
 
@@ -130,12 +215,28 @@ impl ExecutionHistoryDb {
             ExecutionStatus::Cancelled => "cancelled",
         };
 
+        let args = serde_json::to_string(&entry.args)
+            .context("Failed to serialize execution arguments")?;
+        let environment = entry
+            .environment
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .context("Failed to serialize execution environment")?;
+        let resource_usage = entry
+            .resource_usage
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .context("Failed to serialize execution resource usage")?;
+
         sqlx::query(
             r#"
             INSERT INTO execution_history (
-                id, skill, tool, instance, status, duration_ms, started_at, error, output
+                id, skill, tool, instance, status, duration_ms, started_at, error, output, args,
+                environment, resource_usage
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&entry.id)
@@ -147,6 +248,9 @@ impl ExecutionHistoryDb {
         .bind(entry.started_at.to_rfc3339())
         .bind(&entry.error)
         .bind(&entry.output)
+        .bind(args)
+        .bind(environment)
+        .bind(resource_usage)
         .execute(&self.pool)
         .await
         .context("Failed to insert execution history entry")?;
@@ -158,7 +262,8 @@ impl ExecutionHistoryDb {
     pub async fn get_execution(&self, id: &str) -> Result<Option<ExecutionHistoryEntry>> {
         let row = sqlx::query(
             r#"
-            SELECT id, skill, tool, instance, status, duration_ms, started_at, error, output
+            SELECT id, skill, tool, instance, status, duration_ms, started_at, error, output, args,
+                   environment, resource_usage
             FROM execution_history
             WHERE id = ?
             "#,
@@ -182,7 +287,8 @@ impl ExecutionHistoryDb {
     ) -> Result<Vec<ExecutionHistoryEntry>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, skill, tool, instance, status, duration_ms, started_at, error, output
+            SELECT id, skill, tool, instance, status, duration_ms, started_at, error, output, args,
+                   environment, resource_usage
             FROM execution_history
             ORDER BY started_at DESC
             LIMIT ? OFFSET ?
@@ -207,7 +313,8 @@ impl ExecutionHistoryDb {
     ) -> Result<Vec<ExecutionHistoryEntry>> {
         let rows = sqlx::query(
             r#"
-            SELECT id, skill, tool, instance, status, duration_ms, started_at, error, output
+            SELECT id, skill, tool, instance, status, duration_ms, started_at, error, output, args,
+                   environment, resource_usage
             FROM execution_history
             WHERE skill = ?
             ORDER BY started_at DESC
@@ -242,7 +349,8 @@ impl ExecutionHistoryDb {
 
         let rows = sqlx::query(
             r#"
-            SELECT id, skill, tool, instance, status, duration_ms, started_at, error, output
+            SELECT id, skill, tool, instance, status, duration_ms, started_at, error, output, args,
+                   environment, resource_usage
             FROM execution_history
             WHERE status = ?
             ORDER BY started_at DESC
@@ -260,6 +368,122 @@ impl ExecutionHistoryDb {
             .collect::<Result<Vec<_>>>()
     }
 
+    /// Search execution output and errors, newest first
+    ///
+    /// Every word of `query` must appear; words are matched literally, so
+    /// FTS5 operators and punctuation need no escaping. Each match comes with
+    /// a snippet of the matching text, with hits wrapped in
+    /// [`SNIPPET_HIGHLIGHT`] markers.
+    pub async fn search(
+        &self,
+        query: &str,
+        since: Option<DateTime<Utc>>,
+        limit: usize,
+    ) -> Result<Vec<(ExecutionHistoryEntry, String)>> {
+        let Some(expression) = match_expression(query) else {
+            return Ok(Vec::new());
+        };
+
+        let rows = sqlx::query(
+            r#"
+            SELECT h.id, h.skill, h.tool, h.instance, h.status, h.duration_ms, h.started_at,
+                   h.error, h.output, h.args, h.environment, h.resource_usage,
+                   snippet(execution_history_fts, -1, ?, ?, '…', 16) AS snippet
+            FROM execution_history_fts
+            JOIN execution_history h ON h.rowid = execution_history_fts.rowid
+            WHERE execution_history_fts MATCH ?
+              AND (? IS NULL OR h.started_at >= ?)
+            ORDER BY h.started_at DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(SNIPPET_HIGHLIGHT.0)
+        .bind(SNIPPET_HIGHLIGHT.1)
+        .bind(expression)
+        .bind(since.map(|since| since.to_rfc3339()))
+        .bind(since.map(|since| since.to_rfc3339()))
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to search execution history")?;
+
+        rows.into_iter()
+            .map(|row| {
+                let snippet: String = row.get("snippet");
+                Ok((row_to_entry(row)?, snippet))
+            })
+            .collect::<Result<Vec<_>>>()
+    }
+
+    /// Finished executions since a point in time, optionally of one skill,
+    /// for usage analytics
+    pub async fn samples(
+        &self,
+        since: DateTime<Utc>,
+        skill: Option<&str>,
+    ) -> Result<Vec<ExecutionSample>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT skill, tool, status, duration_ms, started_at
+            FROM execution_history
+            WHERE started_at >= ?
+              AND status NOT IN ('pending', 'running')
+              AND (? IS NULL OR skill = ?)
+            "#,
+        )
+        .bind(since.to_rfc3339())
+        .bind(skill)
+        .bind(skill)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load execution samples")?;
+
+        rows.into_iter()
+            .map(|row| {
+                let started_at: String = row.get("started_at");
+                Ok(ExecutionSample {
+                    skill: row.get("skill"),
+                    tool: row.get("tool"),
+                    success: row.get::<String, _>("status") == "success",
+                    duration_ms: row.get::<i64, _>("duration_ms") as u64,
+                    started_at: DateTime::parse_from_rfc3339(&started_at)
+                        .context("Failed to parse started_at timestamp")?
+                        .with_timezone(&Utc),
+                })
+            })
+            .collect()
+    }
+
+    /// Most recent failed or timed out executions of a skill since a point
+    /// in time
+    pub async fn recent_failures(
+        &self,
+        skill: &str,
+        since: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<ExecutionHistoryEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, skill, tool, instance, status, duration_ms, started_at, error, output, args,
+                   environment, resource_usage
+            FROM execution_history
+            WHERE skill = ? AND started_at >= ? AND status IN ('failed', 'timeout')
+            ORDER BY started_at DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(skill)
+        .bind(since.to_rfc3339())
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list recent failures")?;
+
+        rows.into_iter()
+            .map(row_to_entry)
+            .collect::<Result<Vec<_>>>()
+    }
+
     /// Get total count of executions
     pub async fn count(&self) -> Result<i64> {
         let row = sqlx::query("SELECT COUNT(*) as count FROM execution_history")
@@ -311,6 +535,17 @@ impl ExecutionHistoryDb {
         Ok(result.rows_affected() as usize)
     }
 
+    /// Apply a `[history]` retention policy, then compact the database
+    pub async fn apply_retention(&self, retention: &HistoryRetention) -> Result<PruneReport> {
+        let tables = [RetainedTable { name: "execution_history", time_column: "started_at" }];
+        let report = maintenance::apply_retention(&self.pool, &tables, retention).await?;
+        // VACUUM may renumber rowids, which the search index refers to
+        if report.deleted > 0 {
+            self.rebuild_search_index().await?;
+        }
+        Ok(report)
+    }
+
     /// Get statistics
     pub async fn get_stats(&self) -> Result<ExecutionStats> {
         let row = sqlx::query(
@@ -336,6 +571,18 @@ impl ExecutionHistoryDb {
     }
 }
 
+/// Markers placed around matching words in search snippets
+pub const SNIPPET_HIGHLIGHT: (&str, &str) = ("«", "»");
+
+/// Turn free text into an FTS5 query matching rows that contain every word
+fn match_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
 /// Convert database row to ExecutionHistoryEntry
 fn row_to_entry(row: sqlx::sqlite::SqliteRow) -> Result<ExecutionHistoryEntry> {
     let status_str: String = row.get("status");
@@ -364,6 +611,16 @@ fn row_to_entry(row: sqlx::sqlite::SqliteRow) -> Result<ExecutionHistoryEntry> {
         started_at,
         error: row.get("error"),
         output: row.get("output"),
+        args: row
+            .get::<Option<String>, _>("args")
+            .and_then(|args| serde_json::from_str(&args).ok())
+            .unwrap_or_default(),
+        environment: row
+            .get::<Option<String>, _>("environment")
+            .and_then(|environment| serde_json::from_str(&environment).ok()),
+        resource_usage: row
+            .get::<Option<String>, _>("resource_usage")
+            .and_then(|usage| serde_json::from_str(&usage).ok()),
     })
 }
 
@@ -379,7 +636,9 @@ pub struct ExecutionStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{ExecutionEnvironment, ResourceUsage};
     use chrono::Utc;
+    use std::collections::HashMap;
 
     #[tokio::test]
     async fn test_execution_history_crud() -> Result<()> {
@@ -396,6 +655,18 @@ mod tests {
             started_at: Utc::now(),
             error: None,
             output: Some("test output".to_string()),
+            args: HashMap::from([("namespace".to_string(), serde_json::json!("prod"))]),
+            environment: Some(ExecutionEnvironment {
+                config_keys: vec!["KUBE_CONTEXT".to_string()],
+                context: Some("prod-cluster".to_string()),
+                binaries: [("kubectl".to_string(), "1.29.2".to_string())].into(),
+                ..Default::default()
+            }),
+            resource_usage: Some(ResourceUsage {
+                cpu_time_ms: Some(40),
+                peak_memory_kb: Some(51_200),
+                output_bytes: Some(11),
+            }),
         };
 
         // Add
@@ -403,8 +674,11 @@ mod tests {
 
         // Get by ID
         let retrieved = db.get_execution("test-123").await?;
-        assert!(retrieved.is_some());
-        assert_eq!(retrieved.unwrap().id, "test-123");
+        let retrieved = retrieved.expect("entry was added");
+        assert_eq!(retrieved.id, "test-123");
+        assert_eq!(retrieved.args, entry.args);
+        assert_eq!(retrieved.environment, entry.environment);
+        assert_eq!(retrieved.resource_usage, entry.resource_usage);
 
         // List
         let list = db.list_executions(10, 0).await?;
@@ -417,4 +691,46 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_search_output_and_errors() -> Result<()> {
+        let db = ExecutionHistoryDb::new(":memory:").await?;
+        let entry = |id: &str, days_ago: i64, output: &str, error: Option<&str>| ExecutionHistoryEntry {
+            id: id.to_string(),
+            skill: "kubernetes".to_string(),
+            tool: "get".to_string(),
+            instance: "default".to_string(),
+            status: if error.is_some() { ExecutionStatus::Failed } else { ExecutionStatus::Success },
+            duration_ms: 10,
+            started_at: Utc::now() - chrono::Duration::days(days_ago),
+            error: error.map(str::to_string),
+            output: Some(output.to_string()),
+            args: HashMap::new(),
+            environment: None,
+            resource_usage: None,
+        };
+
+        db.add_execution(&entry("old", 30, "api-7d9f  0/1  CrashLoopBackOff", None)).await?;
+        db.add_execution(&entry("recent", 2, "web-5c4b  1/1  Running", Some("pod web-5c4b in CrashLoopBackOff"))).await?;
+        db.add_execution(&entry("other", 1, "all pods Running", None)).await?;
+
+        let hits = db.search("crashloopbackoff", None, 10).await?;
+        let ids: Vec<&str> = hits.iter().map(|(entry, _)| entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["recent", "old"]);
+        assert!(hits[0].1.contains("«CrashLoopBackOff»"));
+
+        let last_week = db.search("CrashLoopBackOff", Some(Utc::now() - chrono::Duration::days(7)), 10).await?;
+        assert_eq!(last_week.len(), 1);
+        assert_eq!(last_week[0].0.id, "recent");
+
+        // Every word must match, and operators are taken literally
+        assert_eq!(db.search("running web-5c4b", None, 10).await?.len(), 1);
+        assert!(db.search("\"NOT", None, 10).await?.is_empty());
+        assert!(db.search("  ", None, 10).await?.is_empty());
+
+        db.delete_execution("recent").await?;
+        assert_eq!(db.search("CrashLoopBackOff", None, 10).await?.len(), 1);
+
+        Ok(())
+    }
 }
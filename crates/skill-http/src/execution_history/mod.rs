@@ -8,3 +8,12 @@
 pub mod db;
 
 pub use db::ExecutionHistoryDb;
+
+use std::path::PathBuf;
+
+/// Where the HTTP server records executions: ~/.skill-engine/execution-history.db
+pub fn default_db_path() -> PathBuf {
+    dirs::home_dir()
+        .map(|p| p.join(".skill-engine/execution-history.db"))
+        .unwrap_or_else(|| PathBuf::from(".skill-engine/execution-history.db"))
+}
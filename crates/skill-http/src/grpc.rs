@@ -0,0 +1,396 @@
+//! gRPC API - the REST API's skills, execution and search over tonic
+//!
+//! The service is defined in `proto/skill.proto` and served on the same port
+//! as the REST API. Each RPC goes through the REST handler it mirrors, so
+//! both APIs validate, record history and report errors the same way;
+//! `ExecuteTool` shares `POST /api/execute/stream`'s live output.
+
+use axum::http::StatusCode;
+use axum::{extract::Query, extract::State, Json, Router};
+use futures::{Stream, StreamExt};
+use std::pin::Pin;
+use std::sync::Arc;
+use tonic::server::NamedService;
+use tonic::{Request, Response, Status};
+
+use crate::handlers;
+use crate::server::AppState;
+use crate::types::{
+    ApiError, ExecutionRequest, ExecutionStatus, ExecutionStreamEvent, OutputStream,
+    PaginationParams,
+};
+
+/// Types generated from `proto/skill.proto`
+pub mod proto {
+    tonic::include_proto!("skill.v1");
+}
+
+use proto::skill_service_server::{SkillService, SkillServiceServer};
+
+/// Router serving the gRPC service under `/skill.v1.SkillService/`
+pub fn routes(state: Arc<AppState>) -> Router {
+    let server = SkillServiceServer::new(GrpcService { state });
+    Router::new().route_service(
+        &format!("/{}/*rpc", SkillServiceServer::<GrpcService>::NAME),
+        server,
+    )
+}
+
+/// [`SkillService`] backed by the HTTP server's state
+pub struct GrpcService {
+    state: Arc<AppState>,
+}
+
+#[tonic::async_trait]
+impl SkillService for GrpcService {
+    async fn list_skills(
+        &self,
+        request: Request<proto::ListSkillsRequest>,
+    ) -> Result<Response<proto::ListSkillsResponse>, Status> {
+        let request = request.into_inner();
+        let pagination = PaginationParams {
+            page: if request.page == 0 {
+                1
+            } else {
+                request.page as usize
+            },
+            per_page: if request.per_page == 0 {
+                20
+            } else {
+                request.per_page as usize
+            },
+        };
+        let Json(page) = handlers::list_skills(State(self.state.clone()), Query(pagination))
+            .await
+            .map_err(status)?;
+
+        Ok(Response::new(proto::ListSkillsResponse {
+            skills: page
+                .items
+                .into_iter()
+                .map(|skill| proto::Skill {
+                    name: skill.name,
+                    version: skill.version,
+                    description: skill.description,
+                    source: skill.source,
+                    runtime: skill.runtime,
+                    tools_count: skill.tools_count as u32,
+                    instances_count: skill.instances_count as u32,
+                    execution_count: skill.execution_count,
+                    last_used: skill.last_used.map(|at| at.to_rfc3339()),
+                })
+                .collect(),
+            total: page.total as u32,
+            page: page.page as u32,
+            per_page: page.per_page as u32,
+        }))
+    }
+
+    type ExecuteToolStream =
+        Pin<Box<dyn Stream<Item = Result<proto::ExecuteToolEvent, Status>> + Send>>;
+
+    async fn execute_tool(
+        &self,
+        request: Request<proto::ExecuteToolRequest>,
+    ) -> Result<Response<Self::ExecuteToolStream>, Status> {
+        use proto::execute_tool_event::Event;
+
+        let request = request.into_inner();
+        let args = request
+            .args
+            .map(|args| {
+                args.fields
+                    .into_iter()
+                    .map(|(key, value)| (key, json_from_proto(value)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let request = ExecutionRequest {
+            skill: request.skill,
+            tool: request.tool,
+            instance: request.instance,
+            args,
+            timeout_secs: request.timeout_secs,
+            max_output: request.max_output.map(|max| max as usize),
+            grep: request.grep,
+            jq: request.jq,
+            stdin: request.stdin,
+            ..Default::default()
+        };
+        let events = handlers::stream_execution(self.state.clone(), request).map_err(status)?;
+
+        // Forward events as they come, so long-running tools show progress
+        let stream = events.filter_map(|event| {
+            let event = match event {
+                ExecutionStreamEvent::Started { .. } => return std::future::ready(None),
+                ExecutionStreamEvent::Output { stream, text } => {
+                    Event::Output(proto::OutputChunk {
+                        text,
+                        stream: output_stream(stream) as i32,
+                    })
+                }
+                ExecutionStreamEvent::Completed { execution } => {
+                    Event::Result(proto::ExecutionResult {
+                        id: execution.id,
+                        status: execution_status(&execution.status) as i32,
+                        error: execution.error,
+                        duration_ms: execution.duration_ms,
+                        metadata: execution.metadata,
+                    })
+                }
+                ExecutionStreamEvent::Error {
+                    status: code,
+                    error,
+                } => {
+                    let code =
+                        StatusCode::from_u16(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                    return std::future::ready(Some(Err(status((code, Json(error))))));
+                }
+            };
+            std::future::ready(Some(Ok(proto::ExecuteToolEvent { event: Some(event) })))
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn search(
+        &self,
+        request: Request<proto::SearchRequest>,
+    ) -> Result<Response<proto::SearchResponse>, Status> {
+        let request = request.into_inner();
+        let request = crate::types::SearchRequest {
+            query: request.query,
+            top_k: if request.top_k == 0 {
+                5
+            } else {
+                request.top_k as usize
+            },
+            skill_filter: request.skill_filter,
+            include_examples: false,
+            session_id: request.session_id,
+            filters: None,
+            explain: false,
+        };
+        let Json(response) = handlers::semantic_search(State(self.state.clone()), Json(request))
+            .await
+            .map_err(status)?;
+
+        Ok(Response::new(proto::SearchResponse {
+            results: response
+                .results
+                .into_iter()
+                .map(|result| proto::SearchResult {
+                    id: result.id,
+                    skill: result.skill,
+                    tool: result.tool,
+                    content: result.content,
+                    score: result.score,
+                    rerank_score: result.rerank_score,
+                })
+                .collect(),
+            duration_ms: response.duration_ms,
+        }))
+    }
+}
+
+/// gRPC status for a REST error, keeping its code in the `error-code` metadata
+fn status((code, Json(error)): (StatusCode, Json<ApiError>)) -> Status {
+    let grpc_code = match code {
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => tonic::Code::InvalidArgument,
+        StatusCode::NOT_FOUND => tonic::Code::NotFound,
+        StatusCode::FORBIDDEN => tonic::Code::PermissionDenied,
+        StatusCode::UNAUTHORIZED => tonic::Code::Unauthenticated,
+        StatusCode::PRECONDITION_FAILED => tonic::Code::FailedPrecondition,
+        StatusCode::SERVICE_UNAVAILABLE => tonic::Code::Unavailable,
+        StatusCode::TOO_MANY_REQUESTS => tonic::Code::ResourceExhausted,
+        StatusCode::REQUEST_TIMEOUT | StatusCode::GATEWAY_TIMEOUT => tonic::Code::DeadlineExceeded,
+        _ => tonic::Code::Internal,
+    };
+    let mut status = Status::new(grpc_code, error.message);
+    if let Ok(value) = error.code.parse() {
+        status.metadata_mut().insert("error-code", value);
+    }
+    status
+}
+
+fn execution_status(status: &ExecutionStatus) -> proto::ExecutionStatus {
+    match status {
+        ExecutionStatus::Pending => proto::ExecutionStatus::Pending,
+        ExecutionStatus::Running => proto::ExecutionStatus::Running,
+        ExecutionStatus::Success => proto::ExecutionStatus::Success,
+        ExecutionStatus::Failed => proto::ExecutionStatus::Failed,
+        ExecutionStatus::Timeout => proto::ExecutionStatus::Timeout,
+        ExecutionStatus::Cancelled => proto::ExecutionStatus::Cancelled,
+    }
+}
+
+fn output_stream(stream: OutputStream) -> proto::OutputStream {
+    match stream {
+        OutputStream::Stdout => proto::OutputStream::Stdout,
+        OutputStream::Stderr => proto::OutputStream::Stderr,
+    }
+}
+
+/// JSON for a protobuf `Value`; whole numbers become integers
+fn json_from_proto(value: prost_types::Value) -> serde_json::Value {
+    use prost_types::value::Kind;
+    use serde_json::Value;
+
+    match value.kind {
+        None | Some(Kind::NullValue(_)) => Value::Null,
+        Some(Kind::BoolValue(b)) => Value::Bool(b),
+        Some(Kind::NumberValue(n)) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => {
+            Value::from(n as i64)
+        }
+        Some(Kind::NumberValue(n)) => serde_json::Number::from_f64(n)
+            .map(Value::Number)
+            .unwrap_or(Value::Null),
+        Some(Kind::StringValue(s)) => Value::String(s),
+        Some(Kind::ListValue(list)) => {
+            Value::Array(list.values.into_iter().map(json_from_proto).collect())
+        }
+        Some(Kind::StructValue(object)) => Value::Object(
+            object
+                .fields
+                .into_iter()
+                .map(|(key, value)| (key, json_from_proto(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_types::value::Kind;
+
+    fn value(kind: Kind) -> prost_types::Value {
+        prost_types::Value { kind: Some(kind) }
+    }
+
+    #[test]
+    fn test_json_from_proto() {
+        let object = prost_types::Struct {
+            fields: [
+                ("replicas".to_string(), value(Kind::NumberValue(3.0))),
+                ("ratio".to_string(), value(Kind::NumberValue(0.5))),
+                ("name".to_string(), value(Kind::StringValue("web".into()))),
+                (
+                    "labels".to_string(),
+                    value(Kind::ListValue(prost_types::ListValue {
+                        values: vec![value(Kind::BoolValue(true)), value(Kind::NullValue(0))],
+                    })),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        assert_eq!(
+            json_from_proto(value(Kind::StructValue(object))),
+            serde_json::json!({"replicas": 3, "ratio": 0.5, "name": "web", "labels": [true, null]})
+        );
+    }
+
+    async fn next_event(
+        stream: &mut <GrpcService as SkillService>::ExecuteToolStream,
+    ) -> proto::execute_tool_event::Event {
+        let next = tokio::time::timeout(std::time::Duration::from_secs(10), stream.next());
+        next.await.unwrap().unwrap().unwrap().event.unwrap()
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_execute_tool_streams_before_exit() {
+        use proto::execute_tool_event::Event;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let release = dir.path().join("release");
+        let state = Arc::new(
+            AppState::new(crate::server::HttpServerConfig {
+                working_dir: Some(dir.path().to_path_buf()),
+                ..Default::default()
+            })
+            .unwrap(),
+        );
+        *state.manifest.write().await = Some(
+            skill_runtime::SkillManifest::parse(&format!(
+                "[security]\nallowed-commands = [\"sh\"]\n\n\
+                 [skills.sh]\nsource = \"{}\"\nruntime = \"native\"\n",
+                dir.path().display()
+            ))
+            .unwrap(),
+        );
+        state.skills.write().await.insert(
+            "sh".to_string(),
+            crate::types::SkillSummary {
+                name: "sh".to_string(),
+                version: "0.1.0".to_string(),
+                description: String::new(),
+                source: dir.path().display().to_string(),
+                runtime: "native".to_string(),
+                tools_count: 1,
+                instances_count: 1,
+                execution_count: 0,
+                last_used: None,
+                required_services: vec![],
+            },
+        );
+
+        // `sh -c <script>`: prints, then waits until the test releases it
+        let script = format!(
+            "echo first; while [ ! -e '{}' ]; do sleep 0.05; done; echo second",
+            release.display()
+        );
+        let args = prost_types::Struct {
+            fields: [("arg".to_string(), value(Kind::StringValue(script)))]
+                .into_iter()
+                .collect(),
+        };
+        let service = GrpcService { state };
+        let mut stream = service
+            .execute_tool(Request::new(proto::ExecuteToolRequest {
+                skill: "sh".to_string(),
+                tool: "-c".to_string(),
+                args: Some(args),
+                timeout_secs: Some(30),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        match next_event(&mut stream).await {
+            Event::Output(chunk) => {
+                assert_eq!(chunk.text, "first\n");
+                assert_eq!(chunk.stream, proto::OutputStream::Stdout as i32);
+            }
+            other => panic!("expected output while the tool runs, got {:?}", other),
+        }
+
+        std::fs::write(&release, "").unwrap();
+        let mut output = String::new();
+        let result = loop {
+            match next_event(&mut stream).await {
+                Event::Output(chunk) => output.push_str(&chunk.text),
+                Event::Result(result) => break result,
+            }
+        };
+        assert_eq!(output, "second\n");
+        assert_eq!(result.status, proto::ExecutionStatus::Success as i32);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[test]
+    fn test_status_mapping() {
+        let error = status((
+            StatusCode::PRECONDITION_FAILED,
+            Json(ApiError::new("REQUIREMENTS_UNMET", "kubectl not on PATH")),
+        ));
+        assert_eq!(error.code(), tonic::Code::FailedPrecondition);
+        assert_eq!(error.message(), "kubectl not on PATH");
+        assert_eq!(
+            error.metadata().get("error-code").unwrap(),
+            "REQUIREMENTS_UNMET"
+        );
+    }
+}
@@ -7,13 +7,18 @@ use axum::{
     Json,
 };
 use chrono::Utc;
-use skill_runtime::{instance::InstanceConfig, SkillExecutor};
+use skill_runtime::{
+    build_native_command, documented_params, find_skill_md, instance::InstanceConfig,
+    parse_skill_md, validate_args, ArtifactStore, CommandAllowlist, EnhanceProgressDb, InstallSource, Lockfile, ResourceUsage, SkillExecutor, SkillManifest, SkillRequirements, SkillRuntime, StdinSource, OUTPUT_DIR_ENV,
+};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use crate::concurrency::ExecutionPermit;
+use crate::indexing::{IndexStatus, IndexTask};
 use crate::types::*;
 use crate::AppState;
 
@@ -58,6 +63,7 @@ pub async fn get_skill(
     let mut tools = Vec::new();
     let mut instances = Vec::new();
     let mut required_services = Vec::new();
+    let mut requirements = Vec::new();
 
     // Check manifest for skill definition
     let manifest = state.manifest.read().await;
@@ -81,6 +87,7 @@ pub async fn get_skill(
                     description: service_req.description.clone(),
                     optional: service_req.optional,
                     default_port: service_req.default_port,
+                    start_on_demand: service_req.start_on_demand,
                     status,
                 });
             }
@@ -90,16 +97,15 @@ pub async fn get_skill(
                 state.working_dir.join(&skill_def.source)
             } else {
                 // For non-local sources, check if installed
-                let home = dirs::home_dir().unwrap_or_default();
-                home.join(".skill-engine").join("registry").join(&name)
+                state.registry_dir().join(&name)
             };
 
             // Try to load tools from SKILL.md first (works for all skill types)
             if source_path.exists() {
-                use skill_runtime::skill_md::{parse_skill_md, find_skill_md};
+                use skill_runtime::skill_md::{find_skill_md, parse_skill_md_localized, preferred_locale};
 
                 if let Some(skill_md_path) = find_skill_md(&source_path) {
-                    if let Ok(skill_content) = parse_skill_md(&skill_md_path) {
+                    if let Ok(skill_content) = parse_skill_md_localized(&skill_md_path, preferred_locale().as_deref()) {
                         tools = skill_content.tool_docs.into_iter().map(|(tool_name, tool_doc)| ToolInfo {
                             name: tool_name,
                             description: tool_doc.description,
@@ -109,8 +115,14 @@ pub async fn get_skill(
                                 description: p.description,
                                 required: p.required,
                                 default_value: p.default,
+                                allowed_values: p.allowed_values,
+                                pattern: p.pattern,
+                                minimum: p.minimum,
+                                maximum: p.maximum,
                             }).collect(),
                             streaming: false,
+                            available: true,
+                            unmet_requirements: Vec::new(),
                         }).collect();
                     }
                 }
@@ -126,7 +138,10 @@ pub async fn get_skill(
                             name.clone(),
                             "default".to_string(),
                             instance_config,
-                        ) {
+                        )
+                        .map(|executor| {
+                            executor.with_instance_dir(state.instance_manager.instance_dir(&name, "default"))
+                        }) {
                             if let Ok(tool_defs) = executor.get_tools().await {
                                 tools = tool_defs.into_iter().map(|t| ToolInfo {
                                     name: t.name,
@@ -137,14 +152,32 @@ pub async fn get_skill(
                                         description: p.description,
                                         required: p.required,
                                         default_value: p.default_value,
+                                        allowed_values: Vec::new(),
+                                        pattern: None,
+                                        minimum: None,
+                                        maximum: None,
                                     }).collect(),
                                     streaming: t.streaming,
+                                    available: true,
+                                    unmet_requirements: Vec::new(),
                                 }).collect();
                             }
                         }
                     }
                 }
             }
+
+            // Mark tools the host can't run instead of letting them fail later
+            let skill_requirements = SkillRequirements::load(&skill_def.requires, &source_path);
+            requirements = state.requirements.check(&skill_requirements.skill).await;
+            for tool in &mut tools {
+                let unmet = state
+                    .requirements
+                    .unmet(&skill_requirements.for_tool(&tool.name))
+                    .await;
+                tool.available = unmet.is_empty();
+                tool.unmet_requirements = unmet.iter().map(ToString::to_string).collect();
+            }
         }
     }
     drop(manifest);
@@ -182,6 +215,7 @@ pub async fn get_skill(
         license: None,
         tools,
         instances,
+        requirements: requirements.iter().map(api_json).collect(),
     };
 
     Ok(Json(detail))
@@ -189,86 +223,101 @@ pub async fn get_skill(
 
 /// Get the current status of a service
 async fn get_service_status(state: &Arc<AppState>, service_name: &str, default_port: Option<u16>) -> ServiceStatus {
-    let services = state.services.read().await;
-
-    if let Some(service) = services.get(service_name) {
-        // Service is tracked by the server
-        let running = service.process.is_some() || check_port_in_use(service.port);
-        ServiceStatus {
-            name: service_name.to_string(),
-            running,
-            pid: None,
-            port: Some(service.port),
-            url: if running { Some(format!("http://127.0.0.1:{}", service.port)) } else { None },
-            error: None,
-        }
-    } else if let Some(port) = default_port {
-        // Check if service is running externally on default port
-        let running = check_port_in_use(port);
-        ServiceStatus {
-            name: service_name.to_string(),
-            running,
-            pid: None,
-            port: if running { Some(port) } else { None },
-            url: if running { Some(format!("http://127.0.0.1:{}", port)) } else { None },
-            error: None,
-        }
-    } else {
-        // Unknown service, not running
-        ServiceStatus {
-            name: service_name.to_string(),
-            running: false,
-            pid: None,
-            port: None,
-            url: None,
-            error: None,
+    api_service_status(state.services.status(service_name, default_port).await)
+}
+
+/// README names the docs endpoint falls back to when a skill has no SKILL.md
+const README_NAMES: [&str; 3] = ["README.md", "readme.md", "README"];
+
+/// Get a skill's usage documentation: its SKILL.md body in the preferred
+/// locale, else its README
+pub async fn get_skill_docs(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<Json<SkillDocsResponse>, (StatusCode, Json<ApiError>)> {
+    if !state.skills.read().await.contains_key(&name) {
+        return Err((StatusCode::NOT_FOUND, Json(ApiError::not_found(&format!("Skill '{}'", name)))));
+    }
+    let internal = |e: anyhow::Error| {
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiError::internal(format!("{:#}", e))))
+    };
+
+    let skill_dir = state.skill_dir(&name).await;
+    if let Some(path) = find_skill_md(&skill_dir) {
+        let skill_md = skill_runtime::skill_md::parse_skill_md_localized(
+            &path,
+            skill_runtime::preferred_locale().as_deref(),
+        )
+        .map_err(internal)?;
+        return Ok(Json(SkillDocsResponse {
+            name,
+            file: "SKILL.md".to_string(),
+            content: skill_md.body,
+        }));
+    }
+
+    for file in README_NAMES {
+        let path = skill_dir.join(file);
+        if path.is_file() {
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| internal(anyhow::Error::new(e).context(format!("Failed to read {}", path.display()))))?;
+            return Ok(Json(SkillDocsResponse {
+                name,
+                file: file.to_string(),
+                content,
+            }));
         }
     }
+
+    Err((
+        StatusCode::NOT_FOUND,
+        Json(ApiError::not_found(&format!("Documentation for skill '{}'", name))),
+    ))
 }
 
-/// Install a new skill
+/// Install a skill in the background
+///
+/// Answers `202 Accepted` with the install job; follow it with
+/// `GET /api/installs/{id}`.
 pub async fn install_skill(
     State(state): State<Arc<AppState>>,
     Json(request): Json<InstallSkillRequest>,
-) -> Result<Json<InstallSkillResponse>, (StatusCode, Json<ApiError>)> {
-    info!("Installing skill from: {}", request.source);
-
-    // TODO: Implement actual skill installation using GitSkillLoader
-    // For now, return a mock response
-
-    let name = request.name.clone().unwrap_or_else(|| {
-        // Extract name from source
-        request.source
-            .split('/')
-            .next_back()
-            .unwrap_or("unknown")
-            .trim_end_matches(".git")
-            .to_string()
-    });
+) -> Result<(StatusCode, Json<InstallJob>), (StatusCode, Json<ApiError>)> {
+    if request.source.trim().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, Json(ApiError::validation("source must not be empty"))));
+    }
+    refuse_during_shutdown(&state)?;
 
-    // Add to skills list
-    let skill = SkillSummary {
-        name: name.clone(),
-        version: "0.1.0".to_string(),
-        description: format!("Installed from {}", request.source),
-        source: request.source.clone(),
-        runtime: "wasm".to_string(),
-        tools_count: 0,
-        instances_count: 1,
-        last_used: None,
-        execution_count: 0,
-        required_services: Vec::new(),
-    };
+    let job = state.installs.start(state.clone(), request).await;
+    info!(install = %job.id, source = %job.source, "Queued skill install");
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
 
-    state.skills.write().await.insert(name.clone(), skill);
+/// Progress of a skill install
+pub async fn get_install(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<InstallJob>, (StatusCode, Json<ApiError>)> {
+    state.installs.get(&id).await.map(Json).ok_or_else(|| {
+        (StatusCode::NOT_FOUND, Json(ApiError::not_found(&format!("Install '{}'", id))))
+    })
+}
 
-    Ok(Json(InstallSkillResponse {
-        success: true,
-        name: Some(name),
-        version: Some("0.1.0".to_string()),
-        error: None,
-        tools_count: 0,
-    }))
+/// Cancel a skill install
+pub async fn cancel_install(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<InstallJob>, (StatusCode, Json<ApiError>)> {
+    let job = state.installs.cancel(&id).await.ok_or_else(|| {
+        (StatusCode::NOT_FOUND, Json(ApiError::not_found(&format!("Running install '{}'", id))))
+    })?;
+    if job.stage.is_finished() {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiError::new("INSTALL_FINISHED", format!("Install '{}' has already finished", id))),
+        ));
+    }
+    Ok(Json(job))
 }
 
 /// Uninstall a skill
@@ -287,16 +336,19 @@ pub async fn uninstall_skill(
 }
 
 /// Execute a native skill (CLI commands like kubectl, docker, git, terraform)
+#[allow(clippy::too_many_arguments)]
 async fn execute_native_skill(
     state: Arc<AppState>,
     skill_name: &str,
     tool_name: &str,
     instance_name: String,
     args: &HashMap<String, serde_json::Value>,
+    stdin: &StdinSource,
+    mut environment: ExecutionEnvironment,
     start: Instant,
+    timeout: std::time::Duration,
+    live: Option<&LiveOutput>,
 ) -> Result<Json<ExecutionResponse>, (StatusCode, Json<ApiError>)> {
-    use tokio::process::Command;
-
     let execution_id = Uuid::new_v4().to_string();
 
     // Convert JSON args to Vec<(String, String)>
@@ -312,32 +364,100 @@ async fn execute_native_skill(
         })
         .collect();
 
-    // Build the native command
-    let command_str = build_native_command(skill_name, tool_name, &parsed_args)
+    // Build the native command as an argv array (no shell involved)
+    let native = build_native_command(skill_name, tool_name, &parsed_args)
         .map_err(|e| {
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiError::internal(format!("Failed to build command: {}", e))))
+            (StatusCode::BAD_REQUEST, Json(ApiError::bad_request(format!("Failed to build command: {}", e))))
         })?;
+    let program = native.program();
 
-    // Parse the command
-    let parts: Vec<&str> = command_str.split_whitespace().collect();
-    if parts.is_empty() {
-        return Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiError::internal("Empty command")),
-        ));
-    }
+    // Resolve the command allowlist, hardening policy, limits, declared outputs and services from the manifest
+    let (allowlist, policy, limits, outputs, services) = {
+        let manifest = state.manifest.read().await;
+        let allowed_tools = manifest
+            .as_ref()
+            .and_then(|manifest| manifest.resolve_instance(skill_name, None).ok())
+            .and_then(|resolved| find_skill_md(std::path::Path::new(&resolved.source)))
+            .and_then(|path| parse_skill_md(&path).ok())
+            .and_then(|skill_md| skill_md.frontmatter.allowed_tools);
+        let allowlist = manifest
+            .as_ref()
+            .map(|manifest| manifest.command_allowlist())
+            .unwrap_or_default()
+            .for_skill(skill_name, allowed_tools.as_deref());
+        let policy = manifest
+            .as_ref()
+            .map(|manifest| manifest.native_policy(skill_name))
+            .unwrap_or_default();
+        let limits = manifest
+            .as_ref()
+            .map(|manifest| manifest.execution_limits(skill_name))
+            .unwrap_or_default();
+        let (outputs, services) = manifest
+            .as_ref()
+            .and_then(|manifest| manifest.get_skill(skill_name))
+            .map(|skill| (skill.outputs.clone(), skill.services.clone()))
+            .unwrap_or_default();
+        (allowlist, policy, limits, outputs, services)
+    };
 
-    let program = parts[0];
-    let args = &parts[1..];
+    // Security check: Only allow configured commands
+    allowlist
+        .and_then(|allowlist: CommandAllowlist| allowlist.check(program))
+        .map_err(|e| (StatusCode::FORBIDDEN, Json(ApiError::forbidden(e.to_string()))))?;
+    if let Some(version) = state.requirements.binary_version(program).await {
+        environment.binaries.insert(program.to_string(), version);
+    }
 
-    // Execute the command
-    let output = Command::new(program)
-        .args(args)
-        .output()
-        .await
+    // Execute the command with the skill's hardening policy
+    let mut command = native.to_command();
+    let output_dir = artifact_output_dir(&execution_id, &outputs);
+    if let Some(dir) = &output_dir {
+        command.env(OUTPUT_DIR_ENV, dir);
+    }
+    for (key, url) in state.services.injected_env(&services).await {
+        command.env(key, url);
+    }
+    policy.apply(&mut command).map_err(|e| {
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiError::internal(format!("Failed to apply native security policy: {}", e))))
+    })?;
+    // The command is killed if it outlives the timeout or writes too much
+    command.kill_on_drop(true);
+    let max_output = limits.max_output_bytes();
+    let run = async {
+        match live {
+            Some(live) => {
+                stdin
+                    .output_streaming(&mut command, max_output, |stream, bytes| {
+                        live.write(stream, bytes)
+                    })
+                    .await
+            }
+            None => stdin.output(&mut command, max_output).await,
+        }
+    };
+    let (output, resource_usage) =
+        ResourceUsage::measure(tokio::time::timeout(timeout, run)).await;
+    let output = output
+        .map_err(|_| {
+            (
+                StatusCode::GATEWAY_TIMEOUT,
+                Json(ApiError::new(
+                    "EXECUTION_TIMEOUT",
+                    format!("Command timed out after {}s", timeout.as_secs()),
+                )),
+            )
+        })?
         .map_err(|e| {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiError::internal(format!("Failed to execute command: {}", e))))
         })?;
+    let resource_usage = resource_usage
+        .unwrap_or_default()
+        .with_output_bytes((output.stdout.len() + output.stderr.len()) as u64);
+    let artifacts = match output_dir {
+        Some(_) => capture_artifacts(&state, &execution_id, &outputs).await,
+        None => Vec::new(),
+    };
 
     let duration_ms = start.elapsed().as_millis() as u64;
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
@@ -361,21 +481,14 @@ async fn execute_native_skill(
         started_at: Utc::now(),
         error: error_msg.clone(),
         output: Some(stdout.clone()),
+        args: args.clone(),
+        environment: Some(environment),
+        resource_usage: Some(api_resource_usage(resource_usage)),
     };
+    state.metrics.record_execution(success);
+    state.metrics.record_resource_usage(&resource_usage);
 
-    // Save to in-memory cache
-    state.execution_history.write().await.push(history_entry.clone());
-
-    // Save to database (non-blocking)
-    if let Some(db) = state.execution_history_db.read().await.as_ref() {
-        let db = db.clone();
-        let entry = history_entry.clone();
-        tokio::spawn(async move {
-            if let Err(e) = db.add_execution(&entry).await {
-                tracing::warn!("Failed to save execution to database: {}", e);
-            }
-        });
-    }
+    state.record_history(history_entry).await;
 
     // Update skill's last_used and execution_count
     let mut skills = state.skills.write().await;
@@ -385,87 +498,227 @@ async fn execute_native_skill(
     }
     drop(skills);
 
-    let response = if success {
-        ExecutionResponse {
-            id: execution_id,
-            status,
-            output: stdout,
-            error: None,
-            duration_ms,
-            metadata: HashMap::new(),
-        }
-    } else {
-        ExecutionResponse {
-            id: execution_id,
-            status,
-            output: stdout,
-            error: error_msg,
-            duration_ms,
-            metadata: HashMap::new(),
-        }
+    let response = ExecutionResponse {
+        id: execution_id,
+        status,
+        output: stdout,
+        error: error_msg,
+        duration_ms,
+        metadata: HashMap::new(),
+        artifacts,
+        exit_code: output.status.code(),
+        stderr,
+        resource_usage: Some(api_resource_usage(resource_usage)),
+        truncated: false,
+        cache: None,
     };
 
     Ok(Json(response))
 }
 
-/// Build a native command from skill name, tool name, and arguments
-fn build_native_command(
-    skill_name: &str,
-    tool_name: &str,
-    args: &[(String, String)],
-) -> anyhow::Result<String> {
-    // Map skill name to base CLI command
-    let base_command = match skill_name {
-        "kubernetes" => "kubectl",
-        "aws" => "aws",
-        "docker" => "docker",
-        "terraform" => "terraform",
-        "helm" => "helm",
-        "git" => "git",
-        "postgres-native" => "psql",
-        _ => skill_name,
-    };
+/// Execute a tool
+pub async fn execute_tool(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ExecutionRequest>,
+) -> Result<Json<ExecutionResponse>, (StatusCode, Json<ApiError>)> {
+    refuse_during_shutdown(&state)?;
+    run_execution(state, request).await
+}
 
-    let mut cmd_parts = vec![base_command.to_string()];
+/// Execute a tool, streaming its output as Server-Sent Events
+///
+/// Emits `started`, then `output` events as the tool writes to stdout and
+/// stderr, then `completed` with the response `POST /execute` would return,
+/// or `error` with the status it would have failed with. Native skills
+/// stream as they run; WASM skills and cached results hand over their output
+/// in chunks once it's complete. The execution keeps going if the client
+/// disconnects.
+pub async fn execute_tool_stream(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ExecutionRequest>,
+) -> Result<axum::response::Response, (StatusCode, Json<ApiError>)> {
+    use axum::response::sse::{Event, KeepAlive, Sse};
+    use futures::StreamExt;
 
-    // Add tool name as subcommand
-    cmd_parts.push(tool_name.to_string());
+    let stream = stream_execution(state, request)?
+        .map(|event| Event::default().event(event.name()).json_data(&event));
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()).into_response())
+}
 
-    // Process arguments generically
-    for (key, value) in args {
-        if key == "arg" || key == "resource" || key.is_empty() {
-            // Positional argument - just add the value
-            cmd_parts.push(value.clone());
-        } else if value == "true" {
-            // Boolean flag
-            if key.len() == 1 {
-                cmd_parts.push(format!("-{}", key));
-            } else {
-                cmd_parts.push(format!("--{}", key));
+/// Start an execution in the background and return its events: `started`,
+/// `output` as the tool writes (native skills) or in chunks once it's done
+/// (WASM skills, cached results), then `completed` or `error`
+///
+/// The execution keeps going if the receiver is dropped.
+pub(crate) fn stream_execution(
+    state: Arc<AppState>,
+    request: ExecutionRequest,
+) -> Result<
+    futures::channel::mpsc::UnboundedReceiver<ExecutionStreamEvent>,
+    (StatusCode, Json<ApiError>),
+> {
+    refuse_during_shutdown(&state)?;
+
+    let (events, receiver) = futures::channel::mpsc::unbounded();
+    tokio::spawn(async move {
+        let _ = events.unbounded_send(ExecutionStreamEvent::Started {
+            skill: request.skill.clone(),
+            tool: request.tool.clone(),
+            instance: request.instance.clone().unwrap_or_else(|| "default".to_string()),
+        });
+        let live = LiveOutput::new(events.clone());
+        let finished = match run_execution_live(state, request, Some(&live)).await {
+            Ok(Json(execution)) => {
+                live.flush();
+                if !live.streamed() {
+                    for chunk in output_chunks(&execution.output) {
+                        live.send(skill_runtime::OutputStream::Stdout, chunk);
+                    }
+                }
+                ExecutionStreamEvent::Completed { execution }
             }
-        } else if value == "false" {
-            // Skip false boolean flags
-            continue;
-        } else if key.len() == 1 {
-            // Short flag: -n value
-            cmd_parts.push(format!("-{}", key));
-            cmd_parts.push(value.clone());
-        } else {
-            // Long flag: --namespace value
-            cmd_parts.push(format!("--{}", key));
-            cmd_parts.push(value.clone());
+            Err((status, Json(error))) => ExecutionStreamEvent::Error {
+                status: status.as_u16(),
+                error,
+            },
+        };
+        let _ = events.unbounded_send(finished);
+    });
+    Ok(receiver)
+}
+
+/// Forwards a running tool's output to an execution stream, decoding it as
+/// UTF-8 without splitting characters across events
+struct LiveOutput {
+    events: futures::channel::mpsc::UnboundedSender<ExecutionStreamEvent>,
+    /// Undecoded trailing bytes of stdout and stderr
+    pending: std::sync::Mutex<[Vec<u8>; 2]>,
+    streamed: std::sync::atomic::AtomicBool,
+}
+
+impl LiveOutput {
+    fn new(events: futures::channel::mpsc::UnboundedSender<ExecutionStreamEvent>) -> Self {
+        Self {
+            events,
+            pending: Default::default(),
+            streamed: Default::default(),
         }
     }
 
-    Ok(cmd_parts.join(" "))
+    fn index(stream: skill_runtime::OutputStream) -> usize {
+        match stream {
+            skill_runtime::OutputStream::Stdout => 0,
+            skill_runtime::OutputStream::Stderr => 1,
+        }
+    }
+
+    /// Send raw output, holding back an incomplete trailing character
+    fn write(&self, stream: skill_runtime::OutputStream, bytes: &[u8]) {
+        let text = {
+            let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            let buffer = &mut pending[Self::index(stream)];
+            buffer.extend_from_slice(bytes);
+            let complete = match std::str::from_utf8(buffer) {
+                Ok(_) => buffer.len(),
+                // Only an unfinished character at the end is worth waiting for
+                Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                Err(_) => buffer.len(),
+            };
+            let chunk: Vec<u8> = buffer.drain(..complete).collect();
+            String::from_utf8_lossy(&chunk).into_owned()
+        };
+        if !text.is_empty() {
+            self.send(stream, text);
+        }
+    }
+
+    /// Send whatever is still held back
+    fn flush(&self) {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap_or_else(|e| e.into_inner()));
+        for (stream, bytes) in [
+            (skill_runtime::OutputStream::Stdout, &pending[0]),
+            (skill_runtime::OutputStream::Stderr, &pending[1]),
+        ] {
+            if !bytes.is_empty() {
+                self.send(stream, String::from_utf8_lossy(bytes).into_owned());
+            }
+        }
+    }
+
+    fn send(&self, stream: skill_runtime::OutputStream, text: String) {
+        self.streamed.store(true, std::sync::atomic::Ordering::Relaxed);
+        let _ = self.events.unbounded_send(ExecutionStreamEvent::Output {
+            stream: api_output_stream(stream),
+            text,
+        });
+    }
+
+    /// Whether any output went out while the tool ran
+    fn streamed(&self) -> bool {
+        self.streamed.load(std::sync::atomic::Ordering::Relaxed)
+    }
 }
 
-/// Execute a tool
-pub async fn execute_tool(
-    State(state): State<Arc<AppState>>,
-    Json(request): Json<ExecutionRequest>,
+/// Refuse new work once the server has started shutting down
+fn refuse_during_shutdown(state: &AppState) -> Result<(), (StatusCode, Json<ApiError>)> {
+    if state.shutdown.is_triggered() {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiError::new("SHUTTING_DOWN", "Server is shutting down")),
+        ));
+    }
+    Ok(())
+}
+
+/// Wait for a slot under `max_concurrent_executions`, or refuse with `429`
+/// and the queue position when the wait queue is full too
+async fn acquire_execution_slot(
+    state: &AppState,
+) -> Result<ExecutionPermit, (StatusCode, Json<ApiError>)> {
+    state.executions.acquire().await.map_err(|saturated| {
+        let utilization = saturated.utilization;
+        warn!(
+            running = utilization.running,
+            queued = utilization.queued,
+            "Execution queue is full, refusing request"
+        );
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(
+                ApiError::new(
+                    "EXECUTION_QUEUE_FULL",
+                    format!(
+                        "{} executions are running and {} are queued; retry later",
+                        utilization.running, utilization.queued
+                    ),
+                )
+                .with_details(serde_json::json!({
+                    "queue_position": saturated.queue_position,
+                    "running": utilization.running,
+                    "queued": utilization.queued,
+                    "max_concurrent": utilization.max_concurrent,
+                    "max_queued": utilization.max_queued,
+                })),
+            ),
+        )
+    })
+}
+
+/// Run a tool execution, recording it in history; shutdown waits for it
+async fn run_execution(
+    state: Arc<AppState>,
+    request: ExecutionRequest,
 ) -> Result<Json<ExecutionResponse>, (StatusCode, Json<ApiError>)> {
-    let start = Instant::now();
+    run_execution_live(state, request, None).await
+}
+
+/// [`run_execution`], forwarding a native tool's output as it runs
+async fn run_execution_live(
+    state: Arc<AppState>,
+    request: ExecutionRequest,
+    live: Option<&LiveOutput>,
+) -> Result<Json<ExecutionResponse>, (StatusCode, Json<ApiError>)> {
+    let _in_flight = state.shutdown.track();
     let execution_id = Uuid::new_v4().to_string();
     let instance_name = request.instance.clone().unwrap_or_else(|| "default".to_string());
 
@@ -477,6 +730,11 @@ pub async fn execute_tool(
         "Executing tool"
     );
 
+    let output_options = output_options(&request);
+    output_options.validate().map_err(|e| {
+        (StatusCode::BAD_REQUEST, Json(ApiError::bad_request(format!("{:#}", e))))
+    })?;
+
     // Verify skill exists
     let skills = state.skills.read().await;
     if !skills.contains_key(&request.skill) {
@@ -506,26 +764,123 @@ pub async fn execute_tool(
             (StatusCode::NOT_FOUND, Json(ApiError::not_found(&format!("Skill '{}' not in manifest", request.skill))))
         })?
         .clone();
-    drop(manifest);
 
-    // Check if this is a native skill
-    use skill_runtime::SkillRuntime;
-    debug!("Skill runtime: {:?}, checking if Native", skill_def.runtime);
-    if skill_def.runtime == SkillRuntime::Native {
-        debug!("Routing to native skill execution");
-        return execute_native_skill(state.clone(), &request.skill, &request.tool, instance_name, &request.args, start).await;
+    // Every dependency must be defined in the manifest or installed
+    if let Some(m) = manifest.as_ref() {
+        m.resolve_dependencies(&request.skill).map_err(|e| {
+            (
+                StatusCode::FAILED_DEPENDENCY,
+                Json(ApiError::new("DEPENDENCY_ERROR", format!("{:#}", e))),
+            )
+        })?;
     }
+    drop(manifest);
 
     // Determine source path
     let source_path = if skill_def.source.starts_with("./") || skill_def.source.starts_with('/') {
         state.working_dir.join(&skill_def.source)
     } else {
-        let home = dirs::home_dir().unwrap_or_default();
-        home.join(".skill-engine").join("registry").join(&request.skill)
+        state.registry_dir().join(&request.skill)
     };
 
+    // Fail up front, naming what's missing, if the host can't run the tool
+    let requires = SkillRequirements::load(&skill_def.requires, &source_path).for_tool(&request.tool);
+    let unmet = state.requirements.unmet(&requires).await;
+    if !unmet.is_empty() {
+        let unmet: Vec<String> = unmet.iter().map(ToString::to_string).collect();
+        return Err((
+            StatusCode::PRECONDITION_FAILED,
+            Json(
+                ApiError::new(
+                    "REQUIREMENTS_UNMET",
+                    format!(
+                        "Tool '{}:{}' is unavailable on this host; unmet requirements: {}",
+                        request.skill,
+                        request.tool,
+                        unmet.join(", ")
+                    ),
+                )
+                .with_details(serde_json::json!({ "unmet_requirements": unmet })),
+            ),
+        ));
+    }
+
+    // Reject arguments that don't match the parameters documented in SKILL.md
+    let params = documented_params(&source_path, &request.tool);
+    if let Err(e) = validate_args(&request.tool, &params, &request.args) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(
+                ApiError::validation(e.to_string())
+                    .with_details(serde_json::json!({ "issues": e.issues })),
+            ),
+        ));
+    }
+
+    // Reuse a recent result of a read-only tool instead of executing it again
+    let cache_key = state
+        .result_cache
+        .key(&request, &instance_name)
+        .filter(|_| skill_def.outputs.is_empty() && is_read_only(&source_path, &request.tool));
+    if let Some(response) = cache_key.and_then(|key| state.result_cache.get(key)) {
+        debug!(skill = %request.skill, tool = %request.tool, execution_id = %response.id, "Serving cached result");
+        return Ok(Json(shape_output(response, &output_options)));
+    }
+
+    let _slot = acquire_execution_slot(&state).await?;
+    let start = Instant::now();
+    let timeout = std::time::Duration::from_secs(
+        request.timeout_secs.unwrap_or_else(|| state.settings.get().default_timeout_secs),
+    );
+
+    // Start services marked start_on_demand so their URLs can be injected
+    match state.services.ensure_running(&skill_def.services).await {
+        Ok(started) if !started.is_empty() => {
+            info!(skill = %request.skill, services = ?started, "Started services on demand");
+        }
+        Ok(_) => {}
+        Err(e) => {
+            return Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ApiError::new("SERVICE_UNAVAILABLE", format!("{:#}", e))),
+            ));
+        }
+    }
+
+    // Both runtimes get the service URLs and, with declared outputs, the output directory
+    let mut env_vars: Vec<String> = state
+        .services
+        .injected_env(&skill_def.services)
+        .await
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+    if !skill_def.outputs.is_empty() {
+        env_vars.push(OUTPUT_DIR_ENV.to_string());
+    }
+    let binaries = requires
+        .iter()
+        .filter_map(|requirement| match requirement {
+            skill_runtime::Requirement::Binary { name, .. } => Some(name.clone()),
+            _ => None,
+        })
+        .collect();
+    let environment =
+        execution_environment(&state, &request.skill, &instance_name, env_vars, binaries).await;
+
+    // Check if this is a native skill
+    debug!("Skill runtime: {:?}, checking if Native", skill_def.runtime);
+    if skill_def.runtime == SkillRuntime::Native {
+        debug!("Routing to native skill execution");
+        let Json(response) = execute_native_skill(state.clone(), &request.skill, &request.tool, instance_name, &request.args, &stdin_source(&request), environment, start, timeout, live).await?;
+        return Ok(Json(shape_output(cache_result(&state, cache_key, response), &output_options)));
+    }
+
+    // Scratch directory for the skill's declared outputs, mounted at /outputs
+    let output_dir = artifact_output_dir(&execution_id, &skill_def.outputs);
+
     // Load and execute the WASM skill
-    let result = async {
+    let run = async {
         // load_skill returns a Component directly
         let component = state.local_loader.load_skill(&source_path, &state.engine).await
             .map_err(|e| format!("Failed to load skill: {}", e))?;
@@ -535,21 +890,15 @@ pub async fn execute_tool(
 
         // Get service requirements from skill definition and inject running service URLs
         let mut service_urls: Vec<(String, String)> = Vec::new();
-        for service_req in &skill_def.services {
-            let status = get_service_status(&state, &service_req.name, service_req.default_port).await;
-            if status.running {
-                if let Some(url) = &status.url {
-                    // Add to environment (for WASI-compatible access)
-                    let env_key = format!("{}_URL", service_req.name.to_uppercase().replace("-", "_"));
-                    instance_config.environment.insert(env_key.clone(), url.clone());
-
-                    // Also prepare for argument injection (for JS WASM components)
-                    let arg_key = format!("_{}_url", service_req.name.replace("-", "_"));
-                    service_urls.push((arg_key, url.clone()));
-
-                    debug!("Passing {} to skill execution", env_key);
-                }
-            }
+        for (env_key, url) in state.services.injected_env(&skill_def.services).await {
+            // Add to environment (for WASI-compatible access)
+            instance_config.environment.insert(env_key.clone(), url.clone());
+
+            // Also prepare for argument injection (for JS WASM components)
+            let arg_key = format!("_{}", env_key.to_lowercase());
+            service_urls.push((arg_key, url));
+
+            debug!("Passing {} to skill execution", env_key);
         }
 
         let executor = SkillExecutor::from_component(
@@ -558,7 +907,20 @@ pub async fn execute_tool(
             request.skill.clone(),
             instance_name.clone(),
             instance_config,
-        ).map_err(|e| format!("Failed to create executor: {}", e))?;
+        ).map_err(|e| format!("Failed to create executor: {}", e))?
+        .with_instance_dir(state.instance_manager.instance_dir(&request.skill, &instance_name));
+        let executor = match &skill_def.limits {
+            Some(limits) => executor.with_limits(limits.clone()),
+            None => executor,
+        };
+        let executor = match &output_dir {
+            Some(dir) => executor.with_output_dir(dir.clone()),
+            None => executor,
+        };
+        let executor = match &request.stdin {
+            Some(data) => executor.with_stdin(data.clone().into_bytes()),
+            None => executor,
+        };
 
         // Convert args to Vec<(String, String)>
         let mut args: Vec<(String, String)> = request.args.iter()
@@ -581,23 +943,24 @@ pub async fn execute_tool(
             .map_err(|e| format!("Execution failed: {}", e))?;
 
         Ok::<_, String>(exec_result)
-    }.await;
+    };
+    let result = tokio::time::timeout(timeout, run)
+        .await
+        .unwrap_or_else(|_| Err(format!("Execution timed out after {}s", timeout.as_secs())));
 
+    let artifacts = match output_dir {
+        Some(_) => capture_artifacts(&state, &execution_id, &skill_def.outputs).await,
+        None => Vec::new(),
+    };
     let duration_ms = start.elapsed().as_millis() as u64;
 
-    let (status, output, error) = match result {
-        Ok(exec_result) => {
-            if exec_result.success {
-                (ExecutionStatus::Success, exec_result.output, None)
-            } else {
-                (ExecutionStatus::Failed, exec_result.output, exec_result.error_message)
-            }
-        }
-        Err(e) => {
-            warn!(error = %e, "Tool execution failed");
-            (ExecutionStatus::Failed, String::new(), Some(e))
-        }
-    };
+    let exec_result = result.unwrap_or_else(|e| {
+        warn!(error = %e, "Tool execution failed");
+        skill_runtime::ExecutionResult::failure(e)
+    });
+    let status = if exec_result.success { ExecutionStatus::Success } else { ExecutionStatus::Failed };
+    let output = exec_result.output;
+    let error = exec_result.error_message.filter(|_| !exec_result.success);
 
     // Record in history
     let history_entry = ExecutionHistoryEntry {
@@ -610,22 +973,17 @@ pub async fn execute_tool(
         started_at: Utc::now(),
         error: error.clone(),
         output: Some(output.clone()),
+        args: request.args.clone(),
+        environment: Some(environment),
+        resource_usage: exec_result.resource_usage.map(api_resource_usage),
     };
-
-    // Save to in-memory cache
-    state.execution_history.write().await.push(history_entry.clone());
-
-    // Save to database (non-blocking)
-    if let Some(db) = state.execution_history_db.read().await.as_ref() {
-        let db = db.clone();
-        let entry = history_entry.clone();
-        tokio::spawn(async move {
-            if let Err(e) = db.add_execution(&entry).await {
-                tracing::warn!("Failed to save execution to database: {}", e);
-            }
-        });
+    state.metrics.record_execution(exec_result.success);
+    if let Some(usage) = &exec_result.resource_usage {
+        state.metrics.record_resource_usage(usage);
     }
 
+    state.record_history(history_entry).await;
+
     // Update skill's last_used and execution_count
     let mut skills = state.skills.write().await;
     if let Some(skill) = skills.get_mut(&request.skill) {
@@ -633,14 +991,167 @@ pub async fn execute_tool(
         skill.execution_count += 1;
     }
 
-    Ok(Json(ExecutionResponse {
+    drop(skills);
+
+    let response = ExecutionResponse {
         id: execution_id,
         status,
         output,
         error,
         duration_ms,
         metadata: HashMap::new(),
-    }))
+        artifacts,
+        exit_code: exec_result.exit_code,
+        stderr: exec_result.stderr,
+        resource_usage: exec_result.resource_usage.map(api_resource_usage),
+        truncated: false,
+        cache: None,
+    };
+    Ok(Json(shape_output(cache_result(&state, cache_key, response), &output_options)))
+}
+
+/// Whether a tool's SKILL.md annotations, or failing that its name, mark it
+/// read-only
+fn is_read_only(skill_dir: &std::path::Path, tool: &str) -> bool {
+    let hints = find_skill_md(skill_dir)
+        .and_then(|path| parse_skill_md(&path).ok())
+        .and_then(|content| content.tool_docs.get(tool).map(|doc| doc.hints))
+        .unwrap_or_default();
+    hints.resolve(tool).read_only == Some(true)
+}
+
+/// Keep a fresh result of a cacheable execution for later identical requests
+fn cache_result(state: &AppState, key: Option<u64>, mut response: ExecutionResponse) -> ExecutionResponse {
+    if let Some(key) = key {
+        state.result_cache.insert(key, &response);
+        if response.status == ExecutionStatus::Success {
+            response.cache = Some(state.result_cache.miss_info());
+        }
+    }
+    response
+}
+
+/// Snapshot of what an execution runs with, recorded in its history entry
+///
+/// Config keys come from the resolved instance (the manifest's, or an
+/// installed one); values are left out. `binaries` are probed for their
+/// versions through the requirement cache.
+async fn execution_environment(
+    state: &AppState,
+    skill: &str,
+    instance: &str,
+    mut env_vars: Vec<String>,
+    binaries: Vec<String>,
+) -> ExecutionEnvironment {
+    let config = state
+        .manifest
+        .read()
+        .await
+        .as_ref()
+        .and_then(|manifest| manifest.resolve_instance(skill, Some(instance)).ok())
+        .map(|resolved| resolved.config)
+        .or_else(|| state.instance_manager.load_instance(skill, instance).ok())
+        .unwrap_or_default();
+
+    let mut config_keys: Vec<String> = config.config.keys().cloned().collect();
+    config_keys.sort();
+    env_vars.sort();
+    env_vars.dedup();
+    let context = ["KUBE_CONTEXT", "context"]
+        .iter()
+        .find_map(|key| config.get_config(key))
+        .filter(|value| !value.is_empty());
+
+    let mut versions = std::collections::BTreeMap::new();
+    for name in binaries {
+        if let Some(version) = state.requirements.binary_version(&name).await {
+            versions.insert(name, version);
+        }
+    }
+
+    ExecutionEnvironment {
+        config_keys,
+        env_vars,
+        context,
+        binaries: versions,
+    }
+}
+
+/// Scratch directory for an execution's declared outputs, if the skill has any
+fn artifact_output_dir(execution_id: &str, outputs: &[String]) -> Option<std::path::PathBuf> {
+    if outputs.is_empty() {
+        return None;
+    }
+    match ArtifactStore::new().and_then(|store| store.output_dir(execution_id)) {
+        Ok(dir) => Some(dir),
+        Err(e) => {
+            warn!(error = %e, "Failed to create artifact output directory");
+            None
+        }
+    }
+}
+
+/// Keep the files matching a skill's declared outputs, then prune artifacts
+/// outside the manifest's retention policy
+///
+/// Capture failures are logged rather than failing the execution.
+async fn capture_artifacts(state: &AppState, execution_id: &str, outputs: &[String]) -> Vec<ExecutionArtifact> {
+    let retention = state.manifest.read().await
+        .as_ref()
+        .map(|manifest| manifest.artifacts.clone())
+        .unwrap_or_default();
+    let store = match ArtifactStore::new() {
+        Ok(store) => store,
+        Err(e) => {
+            warn!(error = %e, "Failed to open artifact store");
+            return Vec::new();
+        }
+    };
+
+    let artifacts = store.capture(execution_id, outputs).unwrap_or_else(|e| {
+        warn!(execution_id = %execution_id, error = %e, "Failed to capture artifacts");
+        Vec::new()
+    });
+    if let Err(e) = store.prune(&retention) {
+        warn!(error = %e, "Failed to prune old artifacts");
+    }
+    artifacts.into_iter().map(api_artifact).collect()
+}
+
+/// Apply the request's output options (grep, head/tail, jq, format,
+/// max_output) to a successful execution's output.
+///
+/// History keeps the unprocessed output; what was applied is reported in the
+/// response metadata. A jq filter that fails at runtime (e.g. the output isn't
+/// JSON) fails the response but leaves the output intact.
+fn shape_output(mut response: ExecutionResponse, options: &skill_runtime::OutputOptions) -> ExecutionResponse {
+    if options.is_empty() || response.status != ExecutionStatus::Success {
+        return response;
+    }
+    match options.apply(&response.output) {
+        Ok(processed) => {
+            response.metadata.insert("original_length".to_string(), processed.original_length.to_string());
+            response.metadata.insert("truncated".to_string(), processed.truncated.to_string());
+            response.truncated = processed.truncated;
+            if let Some(matches) = processed.grep_matches {
+                response.metadata.insert("grep_matches".to_string(), matches.to_string());
+            }
+            response.metadata.insert("processing".to_string(), processed.processing.join(" → "));
+            if processed.truncated {
+                // History keeps the full output; point the client at it
+                response.metadata.insert(
+                    "full_output".to_string(),
+                    format!("/api/executions/{}/output?range=0-{}", response.id, skill_runtime::DEFAULT_PAGE_SIZE),
+                );
+            }
+            response.output = processed.content;
+        }
+        Err(e) => {
+            response.status = ExecutionStatus::Failed;
+            response.error = Some(format!("{:#}", e));
+        }
+    }
+    response
 }
 
 /// List execution history
@@ -689,6 +1200,589 @@ pub async fn get_execution(
     Err((StatusCode::NOT_FOUND, Json(ApiError::not_found(&format!("Execution '{}'", id)))))
 }
 
+/// Full-text search over execution output and errors
+///
+/// Query parameters: `q` (required), `days` to only search recent
+/// executions, and `limit` (default 50).
+pub async fn search_executions(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<ExecutionSearchResponse>, (StatusCode, Json<ApiError>)> {
+    let query = params.get("q").map(|q| q.trim()).unwrap_or_default().to_string();
+    if query.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiError::bad_request("Query parameter 'q' is required")),
+        ));
+    }
+    debug!("Searching executions: {}", query);
+
+    let db = state
+        .execution_history_db
+        .read()
+        .await
+        .as_ref()
+        .ok_or_else(|| {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ApiError::internal("Execution history database not available")),
+            )
+        })?
+        .clone();
+
+    let since = params
+        .get("days")
+        .and_then(|d| d.parse::<i64>().ok())
+        .map(|days| Utc::now() - chrono::Duration::days(days));
+    let limit = params
+        .get("limit")
+        .and_then(|l| l.parse::<usize>().ok())
+        .unwrap_or(50);
+
+    let hits = db.search(&query, since, limit).await.map_err(|e| {
+        error!(error = %e, "Failed to search execution history");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError::internal("Failed to search execution history")),
+        )
+    })?;
+
+    let results = hits
+        .into_iter()
+        .map(|(execution, snippet)| ExecutionSearchHit { execution, snippet })
+        .collect();
+
+    Ok(Json(ExecutionSearchResponse { query, results }))
+}
+
+/// Re-run a past execution and diff its output against the original
+///
+/// Useful for drift detection (`kubectl get`, `terraform show`) and regression
+/// checks. The new run is recorded in history as its own execution.
+pub async fn rerun_execution(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<RerunResponse>, (StatusCode, Json<ApiError>)> {
+    refuse_during_shutdown(&state)?;
+    rerun(state, id).await.map(Json)
+}
+
+/// Repeat an execution with the same skill, tool, instance and arguments.
+///
+/// Executions recorded before arguments were kept are repeated without
+/// arguments; stdin is never recorded, so it isn't replayed.
+pub async fn rerun(
+    state: Arc<AppState>,
+    id: String,
+) -> Result<RerunResponse, (StatusCode, Json<ApiError>)> {
+    let Json(original) = get_execution(State(state.clone()), Path(id)).await?;
+    info!(execution_id = %original.id, "Re-running execution");
+
+    let request = ExecutionRequest {
+        skill: original.skill.clone(),
+        tool: original.tool.clone(),
+        instance: Some(original.instance.clone()),
+        args: original.args.clone(),
+        no_cache: true,
+        ..Default::default()
+    };
+    let Json(execution) = run_execution(state, request).await?;
+    let diff = skill_runtime::OutputDiff::between(
+        original.output.as_deref().unwrap_or_default(),
+        &execution.output,
+    );
+
+    Ok(RerunResponse {
+        original_id: original.id,
+        execution,
+        diff: api_json(&diff),
+    })
+}
+
+/// Ask the configured agent to summarize an execution
+///
+/// The agent gets the tool, arguments, status, error and the end of the
+/// output, and answers in a few sentences; for failures it names the likely
+/// cause.
+pub async fn summarize_execution(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<ExecutionSummaryResponse>, (StatusCode, Json<ApiError>)> {
+    refuse_during_shutdown(&state)?;
+    let Json(entry) = get_execution(State(state.clone()), Path(id)).await?;
+    let config = state.agent_config.read().await.clone();
+    let agent_unavailable = |e: anyhow::Error| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiError::new("AGENT_UNAVAILABLE", format!("{:#}", e))),
+        )
+    };
+    let llm = crate::agent::runner(&config)
+        .and_then(|runner| runner.provider())
+        .map_err(agent_unavailable)?;
+
+    info!(execution_id = %entry.id, runtime = ?config.runtime, model = %llm.model(), "Summarizing execution");
+    let _in_flight = state.shutdown.track();
+    let response = llm
+        .complete(&crate::agent::summary_request(&entry, &config))
+        .await
+        .map_err(|e| {
+            warn!(error = %e, "Agent failed to summarize execution");
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(ApiError::new("AGENT_FAILED", format!("{:#}", e))),
+            )
+        })?;
+
+    Ok(Json(ExecutionSummaryResponse {
+        id: entry.id,
+        summary: response.content.trim().to_string(),
+        runtime: config.runtime,
+        model: llm.model().to_string(),
+    }))
+}
+
+/// The saved runs database, or `503` when it couldn't be opened
+async fn presets_db(
+    state: &AppState,
+) -> Result<Arc<crate::presets::PresetsDb>, (StatusCode, Json<ApiError>)> {
+    state.presets_db.read().await.clone().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiError::internal("Presets database not available")),
+        )
+    })
+}
+
+fn preset_db_error(e: anyhow::Error) -> (StatusCode, Json<ApiError>) {
+    error!(error = %e, "Run presets database error");
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiError::internal("Failed to access run presets")),
+    )
+}
+
+fn preset_not_found(id: &str) -> (StatusCode, Json<ApiError>) {
+    (StatusCode::NOT_FOUND, Json(ApiError::not_found(&format!("Preset '{}'", id))))
+}
+
+/// List saved runs, pinned ones first
+pub async fn list_presets(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<RunPreset>>, (StatusCode, Json<ApiError>)> {
+    let db = presets_db(&state).await?;
+    db.list_presets().await.map(Json).map_err(preset_db_error)
+}
+
+/// Save a run as a named preset
+pub async fn create_preset(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<CreatePresetRequest>,
+) -> Result<(StatusCode, Json<RunPreset>), (StatusCode, Json<ApiError>)> {
+    let name = request.name.trim();
+    if name.is_empty() || request.skill.is_empty() || request.tool.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiError::validation("Preset name, skill and tool are required")),
+        ));
+    }
+    let db = presets_db(&state).await?;
+
+    let preset = RunPreset {
+        id: Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        skill: request.skill,
+        tool: request.tool,
+        instance: request.instance.unwrap_or_else(|| "default".to_string()),
+        args: request.args,
+        pinned: request.pinned,
+        created_at: Utc::now(),
+        last_run_at: None,
+    };
+    info!(preset = %preset.name, skill = %preset.skill, tool = %preset.tool, "Saving run preset");
+    db.add_preset(&preset).await.map_err(preset_db_error)?;
+
+    Ok((StatusCode::CREATED, Json(preset)))
+}
+
+/// Rename, edit or pin a saved run
+pub async fn update_preset(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Json(mut request): Json<UpdatePresetRequest>,
+) -> Result<Json<RunPreset>, (StatusCode, Json<ApiError>)> {
+    if let Some(name) = request.name.as_mut() {
+        *name = name.trim().to_string();
+        if name.is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ApiError::validation("Preset name can't be empty")),
+            ));
+        }
+    }
+    let db = presets_db(&state).await?;
+    db.update_preset(&id, &request)
+        .await
+        .map_err(preset_db_error)?
+        .map(Json)
+        .ok_or_else(|| preset_not_found(&id))
+}
+
+/// Delete a saved run
+pub async fn delete_preset(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, Json<ApiError>)> {
+    let db = presets_db(&state).await?;
+    if db.delete_preset(&id).await.map_err(preset_db_error)? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(preset_not_found(&id))
+    }
+}
+
+/// Run a saved run with its skill, tool, instance and arguments
+///
+/// The execution is recorded in history like any other.
+pub async fn run_preset(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<ExecutionResponse>, (StatusCode, Json<ApiError>)> {
+    refuse_during_shutdown(&state)?;
+    let db = presets_db(&state).await?;
+    let preset = db
+        .get_preset(&id)
+        .await
+        .map_err(preset_db_error)?
+        .ok_or_else(|| preset_not_found(&id))?;
+    info!(preset = %preset.name, "Running preset");
+
+    let request = ExecutionRequest {
+        skill: preset.skill,
+        tool: preset.tool,
+        instance: Some(preset.instance),
+        args: preset.args,
+        ..Default::default()
+    };
+    let execution = run_execution(state, request).await?;
+    if let Err(e) = db.mark_run(&id, Utc::now()).await {
+        warn!(error = %e, "Failed to record preset run");
+    }
+    Ok(execution)
+}
+
+/// List workflows in ./workflows and ~/.skill-engine/workflows, or in a
+/// namespace's own `workflows` directory
+pub async fn list_workflows(State(state): State<Arc<AppState>>) -> Json<Vec<WorkflowSummary>> {
+    let workflows = skill_runtime::discover_workflows_in(&state.workflow_dirs())
+        .into_iter()
+        .map(|(_, workflow)| workflow_summary(&workflow))
+        .collect();
+    Json(workflows)
+}
+
+fn workflow_summary(workflow: &skill_runtime::Workflow) -> WorkflowSummary {
+    WorkflowSummary {
+        name: workflow.name.clone(),
+        description: workflow.description.clone(),
+        inputs: workflow.input_schema(),
+        steps: workflow
+            .steps
+            .iter()
+            .map(|step| WorkflowStepInfo {
+                id: step.id.clone(),
+                tool: step.tool.clone(),
+                when: step.when.clone(),
+                depends_on: step.depends_on().into_iter().collect(),
+                retries: step.retries,
+                continue_on_error: step.continue_on_error,
+            })
+            .collect(),
+    }
+}
+
+/// Run a workflow by name
+///
+/// Returns the finished run, or with `Accept: text/event-stream` streams
+/// per-step events (`started`, `step_started`, `step_output`,
+/// `step_completed`, `step_failed`, `step_skipped`, `finished`) as the run
+/// progresses. A streamed run keeps going if the client disconnects. Steps
+/// execute like `POST /execute` and are recorded in execution history.
+pub async fn run_workflow(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    headers: axum::http::HeaderMap,
+    Json(request): Json<RunWorkflowRequest>,
+) -> Result<axum::response::Response, (StatusCode, Json<ApiError>)> {
+    use axum::response::sse::{Event, KeepAlive, Sse};
+    use futures::StreamExt;
+
+    // Only discovered workflows by name, never arbitrary paths
+    let (_, workflow) = skill_runtime::discover_workflows_in(&state.workflow_dirs())
+        .into_iter()
+        .find(|(_, workflow)| workflow.name == name)
+        .ok_or_else(|| {
+            (StatusCode::NOT_FOUND, Json(ApiError::not_found(&format!("Workflow '{}'", name))))
+        })?;
+    workflow.resolve_inputs(request.inputs.clone()).map_err(|e| {
+        (StatusCode::BAD_REQUEST, Json(ApiError::bad_request(format!("{:#}", e))))
+    })?;
+    refuse_during_shutdown(&state)?;
+    info!(workflow = %name, "Running workflow");
+
+    let in_flight = state.shutdown.track();
+    let executor = WorkflowStepExecutor(state);
+    let stream = headers
+        .get(axum::http::header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/event-stream"));
+
+    if !stream {
+        let (run, job_id) = workflow
+            .run_recorded(request.inputs, &executor, None)
+            .await
+            .map_err(|e| {
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiError::internal(format!("{:#}", e))))
+            })?;
+        let response = WorkflowRunResponse {
+            job_id: job_id.map(|id| id.to_string()),
+            run: api_json(&run),
+        };
+        return Ok(Json(response).into_response());
+    }
+
+    let (events, receiver) = futures::channel::mpsc::unbounded();
+    tokio::spawn(async move {
+        let _ = events.unbounded_send(WorkflowEvent::Started {
+            workflow: workflow.name.clone(),
+            steps: workflow.steps.iter().map(|step| step.id.clone()).collect(),
+        });
+        let observer = WorkflowEventObserver {
+            events: events.clone(),
+            seen: std::sync::Mutex::new(Vec::new()),
+        };
+        let finished = match workflow
+            .run_recorded(request.inputs, &executor, Some(&observer))
+            .await
+        {
+            Ok((run, job_id)) => WorkflowEvent::Finished {
+                job_id: job_id.map(|id| id.to_string()),
+                run: api_json(&run),
+            },
+            Err(e) => WorkflowEvent::Error {
+                message: format!("{:#}", e),
+            },
+        };
+        let _ = events.unbounded_send(finished);
+        drop(in_flight);
+    });
+
+    let stream = receiver.map(|event| Event::default().event(event.name()).json_data(&event));
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()).into_response())
+}
+
+/// Runs workflow steps through [`execute_tool`]
+struct WorkflowStepExecutor(Arc<AppState>);
+
+#[async_trait::async_trait]
+impl skill_runtime::StepExecutor for WorkflowStepExecutor {
+    async fn execute(
+        &self,
+        skill: &str,
+        instance: &str,
+        tool: &str,
+        args: HashMap<String, serde_json::Value>,
+    ) -> anyhow::Result<skill_runtime::ExecutionResult> {
+        let request = ExecutionRequest {
+            skill: skill.to_string(),
+            tool: tool.to_string(),
+            instance: Some(instance.to_string()),
+            args,
+            ..Default::default()
+        };
+        let Json(response) = run_execution(self.0.clone(), request)
+            .await
+            .map_err(|(_, error)| anyhow::anyhow!(error.0.message))?;
+        Ok(skill_runtime::ExecutionResult {
+            success: response.status == ExecutionStatus::Success,
+            output: response.output,
+            error_message: response.error,
+            metadata: None,
+            exit_code: response.exit_code,
+            stderr: response.stderr,
+            duration_ms: Some(response.duration_ms),
+            resource_usage: response.resource_usage.map(runtime_resource_usage),
+            truncated: response.truncated,
+        })
+    }
+}
+
+/// Output is sent in chunks of about this many bytes, split at line ends
+const WORKFLOW_OUTPUT_CHUNK: usize = 8 * 1024;
+
+/// Turns workflow run updates into per-step [`WorkflowEvent`]s
+struct WorkflowEventObserver {
+    events: futures::channel::mpsc::UnboundedSender<WorkflowEvent>,
+    /// Step statuses already reported
+    seen: std::sync::Mutex<Vec<skill_runtime::RunStatus>>,
+}
+
+#[async_trait::async_trait]
+impl skill_runtime::RunObserver for WorkflowEventObserver {
+    async fn on_update(&self, run: &skill_runtime::WorkflowRun) {
+        use skill_runtime::RunStatus;
+
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        seen.resize(run.steps.len(), RunStatus::Pending);
+        for (step, last) in run.steps.iter().zip(seen.iter_mut()) {
+            if step.status == *last {
+                continue;
+            }
+            *last = step.status;
+
+            let mut events = Vec::new();
+            if matches!(step.status, RunStatus::Succeeded | RunStatus::Failed) {
+                let output = step.output.as_deref().unwrap_or_default();
+                events.extend(output_chunks(output).into_iter().map(|chunk| {
+                    WorkflowEvent::StepOutput {
+                        step: step.id.clone(),
+                        chunk,
+                    }
+                }));
+            }
+            events.push(match step.status {
+                RunStatus::Running => WorkflowEvent::StepStarted {
+                    step: step.id.clone(),
+                    tool: step.tool.clone(),
+                },
+                RunStatus::Succeeded => WorkflowEvent::StepCompleted {
+                    step: step.id.clone(),
+                    duration_ms: step.duration_ms,
+                    outputs: step.outputs.clone(),
+                },
+                RunStatus::Failed => WorkflowEvent::StepFailed {
+                    step: step.id.clone(),
+                    error: step.error.clone().unwrap_or_default(),
+                    attempts: step.attempts,
+                },
+                RunStatus::Skipped => WorkflowEvent::StepSkipped {
+                    step: step.id.clone(),
+                },
+                RunStatus::Pending => continue,
+            });
+            for event in events {
+                let _ = self.events.unbounded_send(event);
+            }
+        }
+    }
+}
+
+pub(crate) fn output_chunks(output: &str) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut chunk = String::new();
+    for line in output.split_inclusive('\n') {
+        if !chunk.is_empty() && chunk.len() + line.len() > WORKFLOW_OUTPUT_CHUNK {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+        chunk.push_str(line);
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+/// Page through an execution's full output
+///
+/// Responses truncated by `max_output` (or other output options) point here so
+/// clients can read the rest without re-running the tool. `range` is
+/// `<start>-<end>` (end exclusive) or `<start>-` for a default-sized page.
+pub async fn get_execution_output(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<ExecutionOutputQuery>,
+) -> Result<Json<ExecutionOutputPage>, (StatusCode, Json<ApiError>)> {
+    let range = match query.range.as_deref() {
+        Some(range) => skill_runtime::OutputRange::parse(range).map_err(|e| {
+            (StatusCode::BAD_REQUEST, Json(ApiError::bad_request(e.to_string())))
+        })?,
+        None => skill_runtime::OutputRange::default(),
+    };
+
+    let cached = state.execution_history.read().await
+        .iter()
+        .find(|e| e.id == id)
+        .map(|e| e.output.clone());
+    let output = match cached {
+        Some(output) => output,
+        None => match state.execution_history_db.read().await.as_ref() {
+            Some(db) => db.get_execution(&id).await.ok().flatten().map(|e| e.output),
+            None => None,
+        }
+        .ok_or_else(|| {
+            (StatusCode::NOT_FOUND, Json(ApiError::not_found(&format!("Execution '{}'", id))))
+        })?,
+    };
+
+    let page = range.page(output.as_deref().unwrap_or_default());
+    Ok(Json(ExecutionOutputPage {
+        execution_id: id,
+        content: page.content,
+        offset: page.offset,
+        next_offset: page.next_offset,
+        total_length: page.total_length,
+    }))
+}
+
+/// List the files an execution produced
+///
+/// Only skills that declare `outputs` in the manifest produce artifacts; other
+/// executions have none.
+pub async fn list_execution_artifacts(
+    Path(id): Path<String>,
+) -> Result<Json<Vec<ExecutionArtifact>>, (StatusCode, Json<ApiError>)> {
+    let store = ArtifactStore::new().map_err(|e| {
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiError::internal(e.to_string())))
+    })?;
+    let artifacts = store.list(&id).map_err(|e| {
+        (StatusCode::BAD_REQUEST, Json(ApiError::bad_request(e.to_string())))
+    })?;
+    Ok(Json(artifacts.into_iter().map(api_artifact).collect()))
+}
+
+/// Download an artifact an execution produced
+pub async fn download_execution_artifact(
+    Path((id, name)): Path<(String, String)>,
+) -> Result<impl IntoResponse, (StatusCode, Json<ApiError>)> {
+    use axum::http::header;
+
+    let store = ArtifactStore::new().map_err(|e| {
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiError::internal(e.to_string())))
+    })?;
+    let path = store.path(&id, &name).map_err(|_| {
+        (StatusCode::NOT_FOUND, Json(ApiError::not_found(&format!("Artifact '{}'", name))))
+    })?;
+    let contents = tokio::fs::read(&path).await.map_err(|e| {
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiError::internal(format!("Failed to read artifact: {}", e))))
+    })?;
+
+    // Header values must be visible ASCII
+    let file_name: String = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if (c.is_ascii_graphic() && c != '"') || c == ' ' { c } else { '_' })
+        .collect();
+    Ok((
+        [
+            (header::CONTENT_TYPE, skill_runtime::artifacts::content_type(&name).to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", file_name)),
+        ],
+        contents,
+    ))
+}
+
 /// Clear all execution history
 ///
 /// Permanently deletes all execution history entries from both memory and persistent storage.
@@ -738,6 +1832,7 @@ pub async fn semantic_search(
                         normalized: request.query.clone(),
                         intent: "search".to_string(),
                         confidence: 0.0,
+                        follow_up: false,
                     }),
                     duration_ms: 0,
                 }));
@@ -745,17 +1840,52 @@ pub async fn semantic_search(
         }
     };
 
+    // Understand the query (expanding follow-ups within the session)
+    let processed = match request.session_id.as_deref() {
+        Some(session_id) => pipeline.process_query_in_session(&request.query, session_id),
+        None => pipeline.process_query(&request.query),
+    };
+
+    // Build structured filter (pushed down into vector store and BM25)
+    let mut filter = skill_runtime::Filter::new();
+    if let Some(ref skill) = request.skill_filter {
+        filter = filter.skill(skill.clone());
+    }
+    if let Some(ref filters) = request.filters {
+        if let Some(ref runtime) = filters.runtime {
+            filter = filter.runtime(runtime.clone());
+        }
+        if let Some(ref instance) = filters.instance {
+            filter = filter.instance(instance.clone());
+        }
+        if let Some(ref category) = filters.category {
+            filter = filter.category(category.clone());
+        }
+        if !filters.tags.is_empty() {
+            filter = filter.tags(filters.tags.clone());
+        }
+    }
+
     // Perform search
-    let search_results = pipeline.search(&request.query, request.top_k).await.map_err(|e| {
-        warn!("Search failed: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiError::internal(format!("Search failed: {}", e))),
+    let search_results = pipeline
+        .search_filtered(
+            &request.query,
+            request.top_k,
+            request.session_id.as_deref(),
+            Some(filter),
         )
-    })?;
+        .await
+        .map_err(|e| {
+            warn!("Search failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError::internal(format!("Search failed: {}", e))),
+            )
+        })?;
 
     // Convert results
-    let mut results: Vec<SearchResult> = search_results
+    let explain = request.explain;
+    let results: Vec<SearchResult> = search_results
         .into_iter()
         .map(|r| SearchResult {
             id: r.id,
@@ -764,20 +1894,26 @@ pub async fn semantic_search(
             content: r.content,
             score: r.score,
             rerank_score: r.rerank_score,
+            explanation: r.explanation.filter(|_| explain).map(|e| ScoreExplanation {
+                dense_score: e.dense_score,
+                dense_rank: e.dense_rank,
+                bm25_score: e.bm25_score,
+                bm25_rank: e.bm25_rank,
+                fusion_score: e.fusion_score,
+                retrieval_rank: e.retrieval_rank,
+                rerank_delta: e.rerank_delta,
+                matched_terms: e.matched_terms,
+            }),
         })
         .collect();
 
-    // If filtering by skill, filter results
-    if let Some(ref skill_filter) = request.skill_filter {
-        results.retain(|r| r.skill == *skill_filter);
-    }
-
     let duration_ms = start.elapsed().as_millis() as u64;
 
     // Log search to analytics database (non-blocking)
     let analytics_db = state.analytics_db.read().await.clone();
     if let Some(db) = analytics_db {
         let query = request.query.clone();
+        let session_id = request.session_id.clone();
         let results_count = results.len();
         let avg_score = if !results.is_empty() {
             Some(results.iter().map(|r| r.score).sum::<f32>() / results.len() as f32)
@@ -787,7 +1923,7 @@ pub async fn semantic_search(
         let duration = duration_ms;
 
         // Spawn async task to log search
-        tokio::spawn(async move {
+        state.shutdown.spawn(async move {
             use crate::analytics::{SearchHistoryEntry};
             use chrono::Utc;
             use uuid::Uuid;
@@ -801,7 +1937,7 @@ pub async fn semantic_search(
                 duration_ms: duration,
                 client_type: "http".to_string(),
                 client_id: None,
-                session_id: None,
+                session_id,
                 timestamp: Utc::now(),
             };
 
@@ -814,9 +1950,10 @@ pub async fn semantic_search(
     Ok(Json(SearchResponse {
         results,
         query_info: Some(QueryInfo {
-            normalized: request.query.clone(),
-            intent: "search".to_string(),
-            confidence: 0.9,
+            normalized: processed.normalized,
+            intent: format!("{:?}", processed.intent),
+            confidence: processed.intent_confidence,
+            follow_up: processed.is_follow_up,
         }),
         duration_ms,
     }))
@@ -826,6 +1963,11 @@ pub async fn semantic_search(
 pub async fn get_search_config(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<SearchConfigResponse>, (StatusCode, Json<ApiError>)> {
+    Ok(Json(search_config_response(&state).await))
+}
+
+/// The search configuration in effect, with the indexed document count
+async fn search_config_response(state: &AppState) -> SearchConfigResponse {
     // Get indexed document count from search pipeline if available
     let indexed_documents = if let Some(ref pipeline) = *state.search_pipeline.read().await {
         pipeline.document_count().await.unwrap_or(0)
@@ -833,90 +1975,158 @@ pub async fn get_search_config(
         0
     };
 
-    // Return current search configuration
-    Ok(Json(SearchConfigResponse {
-        embedding_provider: "fastembed".to_string(),
-        embedding_model: "all-minilm".to_string(),
-        dimensions: 384,
-        vector_backend: "file".to_string(),
-        hybrid_search_enabled: true,
-        reranking_enabled: false,
+    let config = state.effective_search_config().await;
+    SearchConfigResponse {
+        embedding_provider: config.embedding.provider,
+        embedding_model: config.embedding.model,
+        dimensions: config.embedding.dimensions,
+        vector_backend: config.backend.backend_type.to_string(),
+        hybrid_search_enabled: config.retrieval.enable_hybrid,
+        reranking_enabled: config.reranker.enabled,
         indexed_documents,
-    }))
+    }
 }
 
 /// Update search configuration
+///
+/// The change is saved to `search.toml` and applied live: a new pipeline is
+/// built and indexes every skill in the background, replacing the current
+/// one when it's done. Searches keep running on the current pipeline
+/// meanwhile. `SKILL_*` environment variables still take precedence.
 pub async fn update_search_config(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Json(request): Json<UpdateSearchConfigRequest>,
 ) -> Result<Json<SearchConfigResponse>, (StatusCode, Json<ApiError>)> {
     info!("Updating search configuration: {:?}", request);
+    let invalid = |e: anyhow::Error| {
+        (StatusCode::BAD_REQUEST, Json(ApiError::validation(format!("{:#}", e))))
+    };
 
-    // TODO: Actually update the search configuration
-    // For now, just return the updated config
-
-    Ok(Json(SearchConfigResponse {
-        embedding_provider: request.embedding_provider.unwrap_or_else(|| "fastembed".to_string()),
-        embedding_model: request.embedding_model.unwrap_or_else(|| "BAAI/bge-small-en-v1.5".to_string()),
-        dimensions: 384,
-        vector_backend: request.vector_backend.unwrap_or_else(|| "inmemory".to_string()),
-        hybrid_search_enabled: request.enable_hybrid.unwrap_or(false),
-        reranking_enabled: request.enable_reranking.unwrap_or(false),
-        indexed_documents: 0,
-    }))
+    let mut config = state.search_config.read().await.clone();
+    if let Some(provider) = request.embedding_provider {
+        provider
+            .parse::<skill_runtime::EmbeddingProviderType>()
+            .map_err(invalid)?;
+        config.embedding.provider = provider;
+        // Dimensions follow the model as the new provider knows it
+        let model = config.embedding.model.clone();
+        config.set_embedding_model(model);
+    }
+    if let Some(model) = request.embedding_model {
+        config.set_embedding_model(model);
+    }
+    if let Some(backend) = request.vector_backend {
+        config.backend.backend_type = backend.parse().map_err(invalid)?;
+    }
+    if let Some(enable_hybrid) = request.enable_hybrid {
+        config.retrieval.enable_hybrid = enable_hybrid;
+    }
+    if let Some(enable_reranking) = request.enable_reranking {
+        config.reranker.enabled = enable_reranking;
+    }
+    config.validate().map_err(invalid)?;
+    refuse_during_shutdown(&state)?;
+
+    // Build the pipeline before saving, so a configuration that can't be
+    // used (unknown model, unreachable backend) is refused
+    let pipeline = skill_runtime::SearchPipeline::from_config(config.clone().with_env_overrides())
+        .await
+        .map_err(|e| invalid(e.context("Search configuration can't be applied")))?;
+
+    config.to_toml_file(&state.search_config_path()).map_err(|e| {
+        error!(error = %e, "Failed to save search configuration");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError::internal("Failed to save search configuration")),
+        )
+    })?;
+    *state.search_config.write().await = config;
+    IndexTask::new(state.clone()).spawn_replacement(pipeline);
+
+    Ok(Json(search_config_response(&state).await))
 }
 
 /// Get application configuration
 pub async fn get_config(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<AppConfig>, (StatusCode, Json<ApiError>)> {
-    // Get indexed document count from search pipeline if available
-    let indexed_documents = if let Some(ref pipeline) = *state.search_pipeline.read().await {
-        pipeline.document_count().await.unwrap_or(0)
-    } else {
-        0
-    };
+    Ok(Json(app_config_response(&state).await))
+}
 
-    Ok(Json(AppConfig {
-        default_timeout_secs: 30,
-        max_concurrent_executions: 10,
-        enable_history: true,
-        max_history_entries: 1000,
-        search: SearchConfigResponse {
-            embedding_provider: "fastembed".to_string(),
-            embedding_model: "all-minilm".to_string(),
-            dimensions: 384,
-            vector_backend: "file".to_string(),
-            hybrid_search_enabled: true,
-            reranking_enabled: false,
-            indexed_documents,
-        },
-    }))
+/// The application configuration in effect
+async fn app_config_response(state: &AppState) -> AppConfig {
+    let settings = state.settings.get();
+    AppConfig {
+        default_timeout_secs: settings.default_timeout_secs,
+        max_concurrent_executions: state.executions.utilization().max_concurrent,
+        enable_history: settings.enable_history,
+        max_history_entries: settings.max_history_entries,
+        search: search_config_response(state).await,
+        alerts: state.alerts.read().await.clone(),
+    }
 }
 
 /// Update application configuration
+///
+/// Settings are saved to `~/.skill-engine/config.toml`, shared with
+/// `skill setup`, and apply without a restart.
 pub async fn update_config(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Json(request): Json<UpdateAppConfigRequest>,
 ) -> Result<Json<AppConfig>, (StatusCode, Json<ApiError>)> {
     info!("Updating app configuration: {:?}", request);
+    let mut settings = state.settings.get();
+    if let Some(timeout) = request.default_timeout_secs {
+        settings.default_timeout_secs = timeout;
+    }
+    if let Some(max_concurrent) = request.max_concurrent_executions {
+        settings.max_concurrent_executions = Some(max_concurrent);
+    }
+    if let Some(enable_history) = request.enable_history {
+        settings.enable_history = enable_history;
+    }
+    if let Some(max_entries) = request.max_history_entries {
+        settings.max_history_entries = max_entries;
+    }
+    settings.validate().map_err(|e| {
+        (StatusCode::BAD_REQUEST, Json(ApiError::bad_request(format!("{:#}", e))))
+    })?;
 
-    // TODO: Actually persist configuration changes
-    Ok(Json(AppConfig {
-        default_timeout_secs: request.default_timeout_secs.unwrap_or(30),
-        max_concurrent_executions: request.max_concurrent_executions.unwrap_or(10),
-        enable_history: request.enable_history.unwrap_or(true),
-        max_history_entries: request.max_history_entries.unwrap_or(1000),
-        search: SearchConfigResponse {
-            embedding_provider: "fastembed".to_string(),
-            embedding_model: "BAAI/bge-small-en-v1.5".to_string(),
-            dimensions: 384,
-            vector_backend: "inmemory".to_string(),
-            hybrid_search_enabled: false,
-            reranking_enabled: false,
-            indexed_documents: 0,
-        },
-    }))
+    // Alert settings take effect on the analyzer's next run and are kept
+    // across restarts
+    if let Some(alerts) = request.alerts {
+        for url in [&alerts.webhook_url, &alerts.slack_webhook_url].into_iter().flatten() {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiError::bad_request(format!("Alert URL must be http(s): {}", url))),
+                ));
+            }
+        }
+        let path = crate::alerts::config_path(state.namespace.as_deref());
+        crate::alerts::save_config(&path, &alerts).map_err(|e| {
+            error!(error = %e, "Failed to save alert configuration");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError::internal("Failed to save alert configuration")),
+            )
+        })?;
+        *state.alerts.write().await = alerts;
+    }
+
+    let settings = state.settings.update(|current| *current = settings).map_err(|e| {
+        error!(error = %e, "Failed to save settings");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError::internal("Failed to save settings")),
+        )
+    })?;
+
+    // The execution limit applies at once; queued executions start if it
+    // rose. Other namespaces follow through the settings task.
+    state.apply_settings(&settings).await;
+
+    Ok(Json(app_config_response(&state).await))
 }
 
 /// Health check endpoint
@@ -947,6 +2157,26 @@ pub async fn health_check(
         },
     );
 
+    // Background index build; search results are partial until it's ready
+    components.insert(
+        "search_index".to_string(),
+        state.index_status.read().await.health(),
+    );
+
+    // Executions are degraded once new requests would be refused
+    let executions = state.executions.utilization();
+    components.insert(
+        "executions".to_string(),
+        ComponentHealth {
+            name: "Executions".to_string(),
+            healthy: !executions.is_saturated(),
+            message: Some(format!(
+                "{}/{} running, {}/{} queued",
+                executions.running, executions.max_concurrent, executions.queued, executions.max_queued
+            )),
+        },
+    );
+
     let all_healthy = components.values().all(|c| c.healthy);
 
     Ok(Json(HealthResponse {
@@ -955,6 +2185,7 @@ pub async fn health_check(
         components,
         version: env!("CARGO_PKG_VERSION").to_string(),
         uptime_secs: uptime,
+        executions,
     }))
 }
 
@@ -995,11 +2226,53 @@ pub async fn validate_manifest(
         Ok(value) => {
             let (skills, warnings) = parse_manifest_skills(&value);
 
+            // Warnings name the skill they're about; point at its table
+            let skill_names: Vec<&String> = value
+                .get("skills")
+                .and_then(|v| v.as_table())
+                .map(|table| table.keys().collect())
+                .unwrap_or_default();
+            let mut diagnostics: Vec<ManifestDiagnostic> = warnings
+                .iter()
+                .map(|warning| {
+                    let line = skill_names
+                        .iter()
+                        .find(|name| warning.contains(&format!("'{}'", name)))
+                        .and_then(|name| skill_table_line(&request.content, name));
+                    ManifestDiagnostic {
+                        severity: DiagnosticSeverity::Warning,
+                        message: warning.clone(),
+                        line,
+                        column: line.map(|_| 1),
+                        end_line: None,
+                        end_column: None,
+                    }
+                })
+                .collect();
+
+            // Then check it against the manifest schema, as the server loads it
+            let mut errors = vec![];
+            if let Err(e) = SkillManifest::parse(&request.content) {
+                diagnostics.insert(0, match e.downcast_ref::<toml::de::Error>() {
+                    Some(toml_error) => toml_diagnostic(&request.content, toml_error),
+                    None => ManifestDiagnostic {
+                        severity: DiagnosticSeverity::Error,
+                        message: format!("{:#}", e),
+                        line: None,
+                        column: None,
+                        end_line: None,
+                        end_column: None,
+                    },
+                });
+                errors.push(format!("{:#}", e));
+            }
+
             Ok(Json(ValidateManifestResponse {
-                valid: true,
+                valid: errors.is_empty(),
                 skills,
-                errors: vec![],
+                errors,
                 warnings,
+                diagnostics,
             }))
         }
         Err(e) => {
@@ -1008,12 +2281,87 @@ pub async fn validate_manifest(
                 skills: vec![],
                 errors: vec![format!("TOML parse error: {}", e)],
                 warnings: vec![],
+                diagnostics: vec![toml_diagnostic(&request.content, &e)],
             }))
         }
     }
 }
 
+/// A TOML syntax or schema error, located by its span
+fn toml_diagnostic(content: &str, error: &toml::de::Error) -> ManifestDiagnostic {
+    let start = error.span().map(|span| line_column(content, span.start));
+    let end = error.span().map(|span| line_column(content, span.end));
+    ManifestDiagnostic {
+        severity: DiagnosticSeverity::Error,
+        message: error.message().to_string(),
+        line: start.map(|(line, _)| line),
+        column: start.map(|(_, column)| column),
+        end_line: end.map(|(line, _)| line),
+        end_column: end.map(|(_, column)| column),
+    }
+}
+
+/// 1-based line and column (in characters) of a byte offset
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let mut offset = offset.min(content.len());
+    while !content.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// 1-based line of a skill's `[skills.<name>]` table, or its first subtable
+fn skill_table_line(content: &str, name: &str) -> Option<usize> {
+    let headers = [format!("[skills.{}", name), format!("[skills.\"{}\"", name)];
+    content
+        .lines()
+        .position(|line| {
+            let line = line.trim_start();
+            headers.iter().any(|header| {
+                line.strip_prefix(header.as_str())
+                    .is_some_and(|rest| rest.starts_with(']') || rest.starts_with('.'))
+            })
+        })
+        .map(|index| index + 1)
+}
+
+/// Get the manifest file the server loaded its skills from
+///
+/// With layered manifests this is the most specific layer, the one
+/// `save` on import writes to.
+pub async fn get_manifest(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<ManifestSourceResponse>, (StatusCode, Json<ApiError>)> {
+    if state.manifest.read().await.is_none() {
+        return Ok(Json(ManifestSourceResponse { path: None, content: String::new() }));
+    }
+    let path = state.manifest_path().await;
+    let content = std::fs::read_to_string(&path).map_err(|e| {
+        error!(path = %path.display(), error = %e, "Failed to read manifest");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError::internal(format!("Failed to read manifest {}", path.display()))),
+        )
+    })?;
+
+    Ok(Json(ManifestSourceResponse {
+        path: Some(path.display().to_string()),
+        content,
+    }))
+}
+
 /// Import a manifest configuration
+///
+/// With `save`, the content is first written to the server's manifest file
+/// (see [`get_manifest`]) and skills are reloaded from it; content the server
+/// couldn't load is refused. With `install`, every WASM skill is queued as an install job in
+/// dependency order, at the revisions pinned by the request's lockfile.
+/// Docker and native skills need no install and are listed right away.
 pub async fn import_manifest(
     State(state): State<Arc<AppState>>,
     Json(request): Json<ImportManifestRequest>,
@@ -1034,9 +2382,52 @@ pub async fn import_manifest(
             let skills_count = skills.len();
             let mut installed_count = 0;
             let mut errors: Vec<String> = vec![];
+            let mut installs = Vec::new();
+
+            let mut saved_path = None;
+            if request.save {
+                refuse_during_shutdown(&state)?;
+                // Never write a manifest the server couldn't load back
+                let saved = match SkillManifest::parse(&request.content) {
+                    Ok(_) => state.save_manifest(&request.content).await,
+                    Err(e) => Err(e),
+                };
+                match saved {
+                    Ok(path) => saved_path = Some(path.display().to_string()),
+                    Err(e) => {
+                        return Ok(Json(ImportManifestResponse {
+                            success: false,
+                            skills,
+                            skills_count,
+                            installed_count: 0,
+                            installs: vec![],
+                            warnings,
+                            errors: vec![format!("{:#}", e)],
+                            saved_path: None,
+                        }));
+                    }
+                }
+            }
 
             if request.install {
-                // Add skills to the state
+                refuse_during_shutdown(&state)?;
+                let plan = match manifest_install_plan(&request.content, request.lockfile.as_deref()) {
+                    Ok(plan) => plan,
+                    Err(e) => {
+                        return Ok(Json(ImportManifestResponse {
+                            success: false,
+                            skills,
+                            skills_count,
+                            installed_count: 0,
+                            installs: vec![],
+                            warnings,
+                            errors: vec![format!("{:#}", e)],
+                            saved_path,
+                        }));
+                    }
+                };
+
+                // Add skills that run without an install to the state
                 let mut state_skills = state.skills.write().await;
 
                 if !request.merge {
@@ -1054,6 +2445,9 @@ pub async fn import_manifest(
                         errors.push(format!("Skill '{}' has empty source, skipping", skill.name));
                         continue;
                     }
+                    if plan.iter().any(|(name, _)| *name == skill.name) {
+                        continue;
+                    }
 
                     let skill_summary = SkillSummary {
                         name: skill.name.clone(),
@@ -1071,6 +2465,28 @@ pub async fn import_manifest(
                     state_skills.insert(skill.name.clone(), skill_summary);
                     installed_count += 1;
                 }
+                drop(state_skills);
+
+                // Installs run one at a time in the order they're queued,
+                // so dependencies finish before the skills needing them
+                for (name, target) in plan {
+                    let job = state
+                        .installs
+                        .start(
+                            state.clone(),
+                            InstallSkillRequest {
+                                source: target.source,
+                                name: Some(name.clone()),
+                                git_ref: target.git_ref,
+                                instance: None,
+                                force: false,
+                            },
+                        )
+                        .await;
+                    info!(install = %job.id, skill = %name, locked = target.locked, "Queued manifest skill install");
+                    installs.push(job);
+                }
+                installed_count += installs.len();
             }
 
             Ok(Json(ImportManifestResponse {
@@ -1078,8 +2494,10 @@ pub async fn import_manifest(
                 skills,
                 skills_count,
                 installed_count,
+                installs,
                 warnings,
                 errors,
+                saved_path,
             }))
         }
         Err(e) => {
@@ -1088,8 +2506,10 @@ pub async fn import_manifest(
                 skills: vec![],
                 skills_count: 0,
                 installed_count: 0,
+                installs: vec![],
                 warnings: vec![],
                 errors: vec![format!("TOML parse error: {}", e)],
+                saved_path: None,
             }))
         }
     }
@@ -1153,6 +2573,24 @@ pub async fn export_manifest(
 }
 
 /// Parse skills from a TOML manifest value
+/// Install sources of a manifest's WASM skills in dependency order, pinned
+/// by the lockfile
+fn manifest_install_plan(content: &str, lockfile: Option<&str>) -> anyhow::Result<Vec<(String, InstallSource)>> {
+    let manifest = SkillManifest::parse(content)?;
+    let lockfile = match lockfile {
+        Some(content) => Lockfile::parse(content)?,
+        None => Lockfile::default(),
+    };
+    let mut plan = Vec::new();
+    for name in manifest.install_waves()?.into_iter().flatten() {
+        if manifest.get_skill(&name).is_some_and(|skill| skill.runtime == SkillRuntime::Wasm) {
+            let target = lockfile.install_source(&manifest, &name)?;
+            plan.push((name, target));
+        }
+    }
+    Ok(plan)
+}
+
 fn parse_manifest_skills(value: &toml::Value) -> (Vec<ParsedSkill>, Vec<String>) {
     let mut skills = vec![];
     let mut warnings = vec![];
@@ -1262,43 +2700,15 @@ use crate::types::{
     ServiceStatus, ServicesStatusResponse, StartServiceRequest, StartServiceResponse,
     StopServiceRequest,
 };
-use crate::server::TrackedService;
 
 /// List all system services and their status
 pub async fn list_services(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<ServicesStatusResponse>, (axum::http::StatusCode, Json<ApiError>)> {
-    let services = state.services.read().await;
-
-    let mut service_statuses = vec![];
-
-    // Check kubectl proxy status
-    let kubectl_proxy_status = if let Some(service) = services.get("kubectl-proxy") {
-        ServiceStatus {
-            name: "kubectl-proxy".to_string(),
-            running: service.process.is_some(),
-            pid: None, // We don't track PID in Child in a clean way
-            port: Some(service.port),
-            url: Some(format!("http://127.0.0.1:{}", service.port)),
-            error: None,
-        }
-    } else {
-        // Check if kubectl proxy is running externally
-        let port = 8001u16;
-        let running = check_port_in_use(port);
-        ServiceStatus {
-            name: "kubectl-proxy".to_string(),
-            running,
-            pid: None,
-            port: if running { Some(port) } else { None },
-            url: if running { Some(format!("http://127.0.0.1:{}", port)) } else { None },
-            error: None,
-        }
-    };
-    service_statuses.push(kubectl_proxy_status);
+    let services = state.services.list().await;
 
     Ok(Json(ServicesStatusResponse {
-        services: service_statuses,
+        services: services.into_iter().map(api_service_status).collect(),
     }))
 }
 
@@ -1307,187 +2717,59 @@ pub async fn start_service(
     State(state): State<Arc<AppState>>,
     Json(request): Json<StartServiceRequest>,
 ) -> Result<Json<StartServiceResponse>, (axum::http::StatusCode, Json<ApiError>)> {
-    match request.service.as_str() {
-        "kubectl-proxy" => start_kubectl_proxy(state, request.port.unwrap_or(8001)).await,
-        _ => Err((
-            axum::http::StatusCode::BAD_REQUEST,
-            Json(ApiError::bad_request(format!("Unknown service: {}", request.service))),
-        )),
-    }
-}
-
-/// Stop a system service
-pub async fn stop_service(
-    State(state): State<Arc<AppState>>,
-    Json(request): Json<StopServiceRequest>,
-) -> Result<Json<StartServiceResponse>, (axum::http::StatusCode, Json<ApiError>)> {
-    match request.service.as_str() {
-        "kubectl-proxy" => stop_kubectl_proxy(state).await,
-        _ => Err((
+    let name = request.service.as_str();
+    if state.services.spec(name).is_none() {
+        return Err((
             axum::http::StatusCode::BAD_REQUEST,
-            Json(ApiError::bad_request(format!("Unknown service: {}", request.service))),
-        )),
-    }
-}
-
-/// Start kubectl proxy service
-async fn start_kubectl_proxy(
-    state: Arc<AppState>,
-    port: u16,
-) -> Result<Json<StartServiceResponse>, (axum::http::StatusCode, Json<ApiError>)> {
-    // Check if already running
-    {
-        let services = state.services.read().await;
-        if let Some(service) = services.get("kubectl-proxy") {
-            if service.process.is_some() {
-                return Ok(Json(StartServiceResponse {
-                    success: true,
-                    status: ServiceStatus {
-                        name: "kubectl-proxy".to_string(),
-                        running: true,
-                        pid: None,
-                        port: Some(service.port),
-                        url: Some(format!("http://127.0.0.1:{}", service.port)),
-                        error: None,
-                    },
-                    message: "kubectl proxy is already running".to_string(),
-                }));
-            }
-        }
-    }
-
-    // Check if port is already in use (external kubectl proxy)
-    if check_port_in_use(port) {
-        return Ok(Json(StartServiceResponse {
-            success: true,
-            status: ServiceStatus {
-                name: "kubectl-proxy".to_string(),
-                running: true,
-                pid: None,
-                port: Some(port),
-                url: Some(format!("http://127.0.0.1:{}", port)),
-                error: None,
-            },
-            message: format!("kubectl proxy already running externally on port {}", port),
-        }));
+            Json(ApiError::bad_request(format!("Unknown service: {}", name))),
+        ));
     }
 
-    // Start kubectl proxy
-    let result = std::process::Command::new("kubectl")
-        .args(["proxy", "--port", &port.to_string()])
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .spawn();
-
-    match result {
-        Ok(child) => {
-            // Wait a moment for the proxy to start
-            tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-
-            // Check if it's actually running
-            let running = check_port_in_use(port);
-
-            if running {
-                let mut services = state.services.write().await;
-                services.insert("kubectl-proxy".to_string(), TrackedService {
-                    name: "kubectl-proxy".to_string(),
-                    process: Some(child),
-                    port,
-                });
-
-                // Set environment variable for skills
-                std::env::set_var("KUBECTL_PROXY_URL", format!("http://127.0.0.1:{}", port));
-
-                Ok(Json(StartServiceResponse {
-                    success: true,
-                    status: ServiceStatus {
-                        name: "kubectl-proxy".to_string(),
-                        running: true,
-                        pid: None,
-                        port: Some(port),
-                        url: Some(format!("http://127.0.0.1:{}", port)),
-                        error: None,
-                    },
-                    message: format!("kubectl proxy started on port {}", port),
-                }))
+    match state.services.start(name, request.port).await {
+        Ok(info) => {
+            let port = info.port.map(|port| format!(" on port {}", port)).unwrap_or_default();
+            let message = if info.managed {
+                format!("{} running{}", name, port)
             } else {
-                Ok(Json(StartServiceResponse {
-                    success: false,
-                    status: ServiceStatus {
-                        name: "kubectl-proxy".to_string(),
-                        running: false,
-                        pid: None,
-                        port: None,
-                        url: None,
-                        error: Some("kubectl proxy failed to start. Is kubectl installed and configured?".to_string()),
-                    },
-                    message: "kubectl proxy failed to start".to_string(),
-                }))
-            }
+                format!("{} already running externally{}", name, port)
+            };
+            Ok(Json(StartServiceResponse {
+                success: true,
+                status: api_service_status(info),
+                message,
+            }))
         }
         Err(e) => {
+            let mut status = api_service_status(state.services.status(name, request.port).await);
+            status.error = Some(format!("{:#}", e));
             Ok(Json(StartServiceResponse {
                 success: false,
-                status: ServiceStatus {
-                    name: "kubectl-proxy".to_string(),
-                    running: false,
-                    pid: None,
-                    port: None,
-                    url: None,
-                    error: Some(format!("Failed to start kubectl proxy: {}. Is kubectl installed?", e)),
-                },
-                message: format!("Failed to start kubectl proxy: {}", e),
+                status,
+                message: format!("Failed to start {}: {:#}", name, e),
             }))
         }
     }
 }
 
-/// Stop kubectl proxy service
-async fn stop_kubectl_proxy(
-    state: Arc<AppState>,
+/// Stop a system service
+pub async fn stop_service(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<StopServiceRequest>,
 ) -> Result<Json<StartServiceResponse>, (axum::http::StatusCode, Json<ApiError>)> {
-    let mut services = state.services.write().await;
-
-    if let Some(mut service) = services.remove("kubectl-proxy") {
-        if let Some(mut child) = service.process.take() {
-            let _ = child.kill();
-            let _ = child.wait();
-        }
-
-        // Unset environment variable
-        std::env::remove_var("KUBECTL_PROXY_URL");
-
-        Ok(Json(StartServiceResponse {
-            success: true,
-            status: ServiceStatus {
-                name: "kubectl-proxy".to_string(),
-                running: false,
-                pid: None,
-                port: None,
-                url: None,
-                error: None,
-            },
-            message: "kubectl proxy stopped".to_string(),
-        }))
-    } else {
-        Ok(Json(StartServiceResponse {
-            success: true,
-            status: ServiceStatus {
-                name: "kubectl-proxy".to_string(),
-                running: false,
-                pid: None,
-                port: None,
-                url: None,
-                error: None,
-            },
-            message: "kubectl proxy was not running".to_string(),
-        }))
-    }
-}
+    let name = request.service.as_str();
+    let was_running = state.services.stop(name).await.map_err(|e| {
+        (axum::http::StatusCode::BAD_REQUEST, Json(ApiError::bad_request(e.to_string())))
+    })?;
 
-/// Check if a port is in use
-fn check_port_in_use(port: u16) -> bool {
-    std::net::TcpStream::connect(format!("127.0.0.1:{}", port)).is_ok()
+    Ok(Json(StartServiceResponse {
+        success: true,
+        status: api_service_status(state.services.status(name, None).await),
+        message: if was_running {
+            format!("{} stopped", name)
+        } else {
+            format!("{} was not running", name)
+        },
+    }))
 }
 
 // =============================================================================
@@ -1671,18 +2953,162 @@ async fn test_vector_backend(backend: &str, _qdrant_url: Option<&str>) -> Compon
     }
 }
 
+/// Query the pipeline test searches for unless the request names one
+const TEST_PIPELINE_QUERY: &str = "kubernetes pods";
+
+/// Most named configurations one pipeline test compares
+const MAX_PIPELINE_CONFIGS: usize = 4;
+
 /// Test full search pipeline (indexing + search)
+///
+/// With `configs`, the same query runs against each named configuration in
+/// turn, so settings such as hybrid search or reranking can be compared side
+/// by side. A configuration that fails is reported in its run rather than
+/// failing the request.
 pub async fn test_search_pipeline(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Json(request): Json<TestPipelineRequest>,
 ) -> Result<Json<TestPipelineResponse>, (StatusCode, Json<ApiError>)> {
     info!(
-        "Testing full search pipeline: provider={}, backend={}, hybrid={}, reranking={}",
-        request.embedding_provider, request.vector_backend, request.enable_hybrid, request.enable_reranking
+        "Testing full search pipeline: provider={}, backend={}, hybrid={}, reranking={}, configs={}",
+        request.embedding_provider,
+        request.vector_backend,
+        request.enable_hybrid,
+        request.enable_reranking,
+        request.configs.len()
     );
 
+    if request.configs.len() > MAX_PIPELINE_CONFIGS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiError::validation(format!(
+                "At most {} configurations can be compared",
+                MAX_PIPELINE_CONFIGS
+            ))),
+        ));
+    }
+    for (i, config) in request.configs.iter().enumerate() {
+        if config.name.trim().is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ApiError::validation("Every configuration needs a name")),
+            ));
+        }
+        if request.configs[..i].iter().any(|other| other.name == config.name) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ApiError::validation(format!(
+                    "Configuration name '{}' is used twice",
+                    config.name
+                ))),
+            ));
+        }
+    }
+
     let start = Instant::now();
+    let query = request
+        .query
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+        .unwrap_or(TEST_PIPELINE_QUERY)
+        .to_string();
+    let top_k = request.top_k.unwrap_or(3).clamp(1, 50);
+    let documents = if request.use_skill_catalog {
+        state.skill_index_documents().await
+    } else {
+        sample_pipeline_documents()
+    };
+
+    if request.configs.is_empty() {
+        let run = run_test_pipeline(&request, documents, &query, top_k)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiError::internal(e))))?;
+        let results_count = run.search_results.len();
+        let documents_indexed = run.index_stats.documents_indexed;
+
+        return Ok(Json(TestPipelineResponse {
+            success: true,
+            index_stats: run.index_stats,
+            search_results: run.search_results,
+            duration_ms: start.elapsed().as_millis(),
+            message: format!(
+                "Pipeline test completed: indexed {} documents, found {} results",
+                documents_indexed, results_count
+            ),
+            runs: vec![],
+        }));
+    }
+
+    let mut runs = Vec::with_capacity(request.configs.len());
+    for config in &request.configs {
+        let settings = TestPipelineRequest {
+            embedding_provider: config
+                .embedding_provider
+                .clone()
+                .unwrap_or_else(|| request.embedding_provider.clone()),
+            embedding_model: config
+                .embedding_model
+                .clone()
+                .unwrap_or_else(|| request.embedding_model.clone()),
+            vector_backend: config
+                .vector_backend
+                .clone()
+                .unwrap_or_else(|| request.vector_backend.clone()),
+            enable_hybrid: config.enable_hybrid.unwrap_or(request.enable_hybrid),
+            enable_reranking: config.enable_reranking.unwrap_or(request.enable_reranking),
+            qdrant_url: config.qdrant_url.clone().or_else(|| request.qdrant_url.clone()),
+            query: None,
+            top_k: None,
+            use_skill_catalog: false,
+            configs: vec![],
+        };
+
+        let run_start = Instant::now();
+        let run = match run_test_pipeline(&settings, documents.clone(), &query, top_k).await {
+            Ok(run) => run,
+            Err(e) => PipelineRun {
+                name: String::new(),
+                success: false,
+                index_stats: PipelineIndexStats::default(),
+                search_results: vec![],
+                search_duration_ms: 0,
+                duration_ms: 0,
+                error: Some(e),
+            },
+        };
+        runs.push(PipelineRun {
+            name: config.name.clone(),
+            duration_ms: run_start.elapsed().as_millis() as u64,
+            ..run
+        });
+    }
+
+    let failed = runs.iter().filter(|run| !run.success).count();
+    let first = &runs[0];
+
+    Ok(Json(TestPipelineResponse {
+        success: failed == 0,
+        index_stats: first.index_stats.clone(),
+        search_results: first.search_results.clone(),
+        duration_ms: start.elapsed().as_millis(),
+        message: if failed == 0 {
+            format!("Compared {} configurations for '{}'", runs.len(), query)
+        } else {
+            format!("{} of {} configurations failed", failed, runs.len())
+        },
+        runs,
+    }))
+}
 
+/// Build a temporary pipeline from test settings, index the documents and
+/// search them once
+async fn run_test_pipeline(
+    settings: &TestPipelineRequest,
+    documents: Vec<skill_runtime::search::IndexDocument>,
+    query: &str,
+    top_k: usize,
+) -> Result<PipelineRun, String> {
     // Build search configuration
     use skill_runtime::search_config::{
         SearchConfig, BackendConfig, EmbeddingConfig as RuntimeEmbeddingConfig,
@@ -1690,33 +3116,37 @@ pub async fn test_search_pipeline(
         BackendType, IndexConfig, AiIngestionConfig,
     };
 
-    let backend_type: BackendType = request.vector_backend.parse().unwrap_or(BackendType::InMemory);
+    let backend_type: BackendType = settings.vector_backend.parse().unwrap_or(BackendType::InMemory);
 
     let config = SearchConfig {
         backend: BackendConfig {
             backend_type,
         },
         embedding: RuntimeEmbeddingConfig {
-            provider: request.embedding_provider.clone(),
-            model: request.embedding_model.clone(),
+            provider: settings.embedding_provider.clone(),
+            model: settings.embedding_model.clone(),
             dimensions: 384, // Default for most models
             batch_size: 100,
             openai_api_key: std::env::var("OPENAI_API_KEY").ok(),
             ollama_host: None,
+            offline: false,
         },
         retrieval: RetrievalConfig {
-            enable_hybrid: request.enable_hybrid,
+            enable_hybrid: settings.enable_hybrid,
             ..Default::default()
         },
         reranker: RerankerConfig {
-            enabled: request.enable_reranking,
+            enabled: settings.enable_reranking,
             ..Default::default()
         },
         context: ContextConfig::default(),
         file: None, // Use default file config
         qdrant: if backend_type == BackendType::Qdrant {
             Some(RuntimeQdrantConfig {
-                url: request.qdrant_url.unwrap_or_else(|| "http://localhost:6334".to_string()),
+                url: settings
+                    .qdrant_url
+                    .clone()
+                    .unwrap_or_else(|| "http://localhost:6334".to_string()),
                 api_key: std::env::var("QDRANT_API_KEY").ok(),
                 collection: "skill_test_pipeline".to_string(),
                 ..Default::default()
@@ -1724,27 +3154,73 @@ pub async fn test_search_pipeline(
         } else {
             None
         },
-        index: IndexConfig::default(),
-        ai_ingestion: AiIngestionConfig::default(),
-    };
+        index: IndexConfig::default(),
+        ai_ingestion: AiIngestionConfig::default(),
+    };
+
+    // Create temporary pipeline
+    let pipeline = skill_runtime::search::SearchPipeline::from_config(config)
+        .await
+        .map_err(|e| {
+            warn!("Failed to create search pipeline: {}", e);
+            format!("Failed to create pipeline: {}", e)
+        })?;
+
+    let indexing_start = Instant::now();
+
+    // Index documents
+    let index_stats = pipeline.index_documents(documents).await.map_err(|e| {
+        warn!("Failed to index documents: {}", e);
+        format!("Failed to index documents: {}", e)
+    })?;
+
+    let indexing_duration_ms = indexing_start.elapsed().as_millis() as u64;
+
+    // Perform test search
+    let search_start = Instant::now();
+    let search_results = pipeline.search(query, top_k).await.map_err(|e| {
+        warn!("Search failed: {}", e);
+        format!("Search failed: {}", e)
+    })?;
+    let search_duration_ms = search_start.elapsed().as_millis() as u64;
+
+    // Convert results to response format
+    let search_results = search_results
+        .into_iter()
+        .map(|r| PipelineSearchResult {
+            id: r.id,
+            content: r.content,
+            score: r.score,
+            rerank_score: r.rerank_score,
+            metadata: DocumentMetadata {
+                skill_name: r.metadata.skill_name,
+                tool_name: r.metadata.tool_name,
+                tags: r.metadata.tags,
+            },
+        })
+        .collect();
+
+    Ok(PipelineRun {
+        name: String::new(),
+        success: true,
+        index_stats: PipelineIndexStats {
+            documents_indexed: index_stats.documents_added,
+            indexing_duration_ms,
+            embedding_duration_ms: indexing_duration_ms, // Approximation
+        },
+        search_results,
+        search_duration_ms,
+        duration_ms: 0,
+        error: None,
+    })
+}
 
-    // Create temporary pipeline
-    use skill_runtime::search::{SearchPipeline, IndexDocument};
+/// Sample tool documents the pipeline test indexes by default
+fn sample_pipeline_documents() -> Vec<skill_runtime::search::IndexDocument> {
+    use skill_runtime::search::IndexDocument;
     use skill_runtime::vector_store::DocumentMetadata as RuntimeDocMetadata;
 
-    let pipeline = match SearchPipeline::from_config(config).await {
-        Ok(p) => p,
-        Err(e) => {
-            warn!("Failed to create search pipeline: {}", e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiError::internal(format!("Failed to create pipeline: {}", e))),
-            ));
-        }
-    };
-
-    // Create sample test documents
-    let sample_docs = vec![
+    vec![
         IndexDocument {
             id: "test-1".to_string(),
             content: "List all Kubernetes pods in the default namespace".to_string(),
@@ -1790,71 +3266,7 @@ pub async fn test_search_pipeline(
                 ..Default::default()
             },
         },
-    ];
-
-    let indexing_start = Instant::now();
-
-    // Index documents
-    let index_stats = match pipeline.index_documents(sample_docs).await {
-        Ok(stats) => stats,
-        Err(e) => {
-            warn!("Failed to index documents: {}", e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiError::internal(format!("Failed to index documents: {}", e))),
-            ));
-        }
-    };
-
-    let indexing_duration_ms = indexing_start.elapsed().as_millis() as u64;
-
-    // Perform test search
-    let search_results = match pipeline.search("kubernetes pods", 3).await {
-        Ok(results) => results,
-        Err(e) => {
-            warn!("Search failed: {}", e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiError::internal(format!("Search failed: {}", e))),
-            ));
-        }
-    };
-
-    let duration_ms = start.elapsed().as_millis();
-
-    // Convert results to response format
-    let pipeline_results: Vec<PipelineSearchResult> = search_results
-        .into_iter()
-        .map(|r| PipelineSearchResult {
-            id: r.id,
-            content: r.content,
-            score: r.score,
-            rerank_score: r.rerank_score,
-            metadata: DocumentMetadata {
-                skill_name: r.metadata.skill_name,
-                tool_name: r.metadata.tool_name,
-                tags: r.metadata.tags,
-            },
-        })
-        .collect();
-
-    let results_count = pipeline_results.len();
-
-    Ok(Json(TestPipelineResponse {
-        success: true,
-        index_stats: PipelineIndexStats {
-            documents_indexed: index_stats.documents_added,
-            indexing_duration_ms,
-            embedding_duration_ms: indexing_duration_ms, // Approximation
-        },
-        search_results: pipeline_results,
-        duration_ms,
-        message: format!(
-            "Pipeline test completed: indexed {} documents, found {} results",
-            index_stats.documents_added,
-            results_count
-        ),
-    }))
+    ]
 }
 
 // =============================================================================
@@ -1863,15 +3275,14 @@ pub async fn test_search_pipeline(
 
 /// Get agent configuration
 pub async fn get_agent_config(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
 ) -> Result<Json<GetAgentConfigResponse>, (StatusCode, Json<ApiError>)> {
     info!("Getting agent configuration");
 
     // Detect Claude Code on system
     let (claude_code_detected, claude_code_version) = detect_claude_code().await;
 
-    // Build configuration response
-    let config = AgentConfig::default();
+    let config = state.agent_config.read().await.clone();
 
     let available_runtimes = vec![
         RuntimeInfo {
@@ -1886,14 +3297,14 @@ pub async fn get_agent_config(
             name: "Google Gemini".to_string(),
             description: "Google's multimodal AI with code execution".to_string(),
             supported_providers: vec!["google".to_string()],
-            available: false, // Not yet implemented
+            available: skill_runtime::AgentRunner::is_available(skill_runtime::AgentKind::Gemini),
         },
         RuntimeInfo {
             runtime: AgentRuntime::OpenAI,
             name: "OpenAI GPT".to_string(),
             description: "OpenAI's GPT models with function calling".to_string(),
             supported_providers: vec!["openai".to_string()],
-            available: false, // Not yet implemented
+            available: skill_runtime::AgentRunner::is_available(skill_runtime::AgentKind::OpenAi),
         },
     ];
 
@@ -1909,13 +3320,16 @@ pub async fn get_agent_config(
 }
 
 /// Update agent configuration
+///
+/// The configuration is saved to `agent.json`; from then on the agent
+/// enhances skills and summarizes executions.
 pub async fn update_agent_config(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Json(request): Json<UpdateAgentConfigRequest>,
 ) -> Result<Json<AgentConfig>, (StatusCode, Json<ApiError>)> {
     info!("Updating agent configuration");
 
-    let mut config = AgentConfig::default();
+    let mut config = state.agent_config.read().await.clone();
 
     // Apply updates
     if let Some(runtime) = request.runtime {
@@ -1931,10 +3345,21 @@ pub async fn update_agent_config(
     }
 
     if let Some(claude_code_path) = request.claude_code_path {
-        config.claude_code_path = Some(claude_code_path);
+        config.claude_code_path = Some(claude_code_path).filter(|path| !path.is_empty());
     }
 
-    // TODO: Persist configuration to disk
+    crate::agent::validate(&config).map_err(|e| {
+        (StatusCode::BAD_REQUEST, Json(ApiError::validation(format!("{:#}", e))))
+    })?;
+    let path = crate::agent::config_path(state.namespace.as_deref());
+    crate::agent::save_config(&path, &config).map_err(|e| {
+        error!(error = %e, "Failed to save agent configuration");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError::internal("Failed to save agent configuration")),
+        )
+    })?;
+    *state.agent_config.write().await = config.clone();
 
     Ok(Json(config))
 }
@@ -2093,9 +3518,7 @@ pub async fn index_skills(
         }
     }
 
-    // Get skills
-    let skills = state.skills.read().await;
-    if skills.is_empty() {
+    if state.skills.read().await.is_empty() {
         return Err((
             StatusCode::BAD_REQUEST,
             Json(ApiError::validation("No skills loaded. Load skills first before indexing.")),
@@ -2103,69 +3526,8 @@ pub async fn index_skills(
     }
 
     // Build documents from skills by loading tool information
-    use skill_runtime::search::IndexDocument;
-    use skill_runtime::vector_store::DocumentMetadata as RuntimeDocMetadata;
-
-    let mut documents = Vec::new();
-    let mut _loaded_tools = 0;
-
-    // For each skill, we need to load the actual tools
-    for (skill_name, skill_summary) in skills.iter() {
-        // Try to load skill details to get tools
-        let manifest = state.manifest.read().await;
-        if let Some(manifest) = manifest.as_ref() {
-            if let Some(skill_def) = manifest.skills.get(skill_name) {
-                // Build source path
-                let source_path = if skill_def.source.starts_with("./") || skill_def.source.starts_with('/') {
-                    manifest.base_dir.join(&skill_def.source)
-                } else {
-                    let home = dirs::home_dir().unwrap_or_default();
-                    home.join(".skill-engine").join("registry").join(skill_name)
-                };
-
-                // Load tools from SKILL.md if available
-                if source_path.exists() {
-                    use skill_runtime::skill_md::find_skill_md;
-                    if let Some(skill_md_path) = find_skill_md(&source_path) {
-                        if let Ok(skill_content) = skill_runtime::skill_md::parse_skill_md(&skill_md_path) {
-                            // Create documents from tools (tool_docs is HashMap<String, ToolDocumentation>)
-                            for (_tool_name, tool_doc) in skill_content.tool_docs {
-                                let params_text = tool_doc.parameters.iter()
-                                    .map(|p| format!("{}: {}", p.name, p.description))
-                                    .collect::<Vec<_>>()
-                                    .join(", ");
-
-                                let content = format!(
-                                    "{} - {} | {} | Parameters: {}",
-                                    skill_name,
-                                    tool_doc.name,
-                                    tool_doc.description,
-                                    params_text
-                                );
-
-                                documents.push(IndexDocument {
-                                    id: format!("{}:{}", skill_name, tool_doc.name),
-                                    content,
-                                    metadata: RuntimeDocMetadata {
-                                        skill_name: Some(skill_name.clone()),
-                                        tool_name: Some(tool_doc.name.clone()),
-                                        instance_name: None,
-                                        category: Some(skill_summary.runtime.clone()),
-                                        tags: vec![],
-                                        custom: std::collections::HashMap::new(),
-                                    },
-                                });
-                                _loaded_tools += 1;
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
+    let documents = state.skill_index_documents().await;
     let doc_count = documents.len();
-    drop(skills);
 
     if doc_count == 0 {
         return Err((
@@ -2197,6 +3559,10 @@ pub async fn index_skills(
         "Indexed {} documents in {}ms",
         doc_count, duration_ms
     );
+    *state.index_status.write().await = IndexStatus::Ready {
+        documents: doc_count,
+        duration_ms,
+    };
 
     // Convert PipelineIndexStats to IndexStats
     use crate::types::IndexStats;
@@ -2621,6 +3987,361 @@ pub async fn get_search_timeline(
     Ok(Json(SearchTimelineResponse { timeline }))
 }
 
+/// Get example quality over time, as recorded by `skill enhance --evaluate`
+pub async fn get_example_quality(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
+) -> Result<Json<ExampleQualityResponse>, (StatusCode, Json<ApiError>)> {
+    info!("Getting example quality");
+
+    let db = state
+        .analytics_db
+        .read()
+        .await
+        .as_ref()
+        .ok_or_else(|| {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ApiError::internal("Analytics database not available")),
+            )
+        })?
+        .clone();
+
+    let days = params
+        .get("days")
+        .and_then(|d| d.parse::<u32>().ok())
+        .unwrap_or(90);
+    let skill = params.get("skill").map(String::as_str);
+
+    let entries = db.get_example_quality(skill, days).await.map_err(|e| {
+        error!(error = %e, "Failed to get example quality");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError::internal("Failed to get example quality")),
+        )
+    })?;
+
+    let rate = |count: usize, total: usize| if total == 0 { 0.0 } else { count as f32 / total as f32 };
+    let evaluations = entries
+        .into_iter()
+        .map(|e| ExampleQualityPoint {
+            parse_rate: rate(e.parsed, e.sampled),
+            pass_rate: rate(e.valid, e.sampled),
+            skill: e.skill,
+            timestamp: e.timestamp,
+            sampled: e.sampled,
+            avg_score: e.avg_score,
+            quality: e.quality,
+        })
+        .collect();
+
+    Ok(Json(ExampleQualityResponse { evaluations }))
+}
+
+/// Load finished executions of the last `days` for usage analytics
+async fn usage_samples(
+    state: &AppState,
+    days: u32,
+    skill: Option<&str>,
+) -> Result<Vec<crate::analytics::ExecutionSample>, (StatusCode, Json<ApiError>)> {
+    let db = state
+        .execution_history_db
+        .read()
+        .await
+        .as_ref()
+        .ok_or_else(|| {
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(ApiError::internal("Execution history database not available")),
+            )
+        })?
+        .clone();
+
+    let since = Utc::now() - chrono::Duration::days(days as i64);
+    db.samples(since, skill).await.map_err(|e| {
+        error!(error = %e, "Failed to load execution samples");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError::internal("Failed to get skill usage")),
+        )
+    })
+}
+
+/// Get execution count, success rate and latency per skill
+pub async fn get_skill_usage(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
+) -> Result<Json<SkillUsageResponse>, (StatusCode, Json<ApiError>)> {
+    info!("Getting skill usage");
+
+    let days = params
+        .get("days")
+        .and_then(|d| d.parse::<u32>().ok())
+        .unwrap_or(30);
+
+    let samples = usage_samples(&state, days, None).await?;
+    let skills = crate::analytics::usage::usage_by_skill(&samples);
+
+    Ok(Json(SkillUsageResponse { days, skills }))
+}
+
+/// Get usage of one skill, per tool and per day
+pub async fn get_skill_analytics(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
+) -> Result<Json<SkillAnalyticsResponse>, (StatusCode, Json<ApiError>)> {
+    info!("Getting analytics for skill: {}", name);
+
+    let days = params
+        .get("days")
+        .and_then(|d| d.parse::<u32>().ok())
+        .unwrap_or(30);
+
+    let samples = usage_samples(&state, days, Some(&name)).await?;
+
+    Ok(Json(SkillAnalyticsResponse {
+        stats: crate::analytics::usage_stats(&samples),
+        tools: crate::analytics::usage::usage_by_tool(&samples),
+        daily: crate::analytics::usage::daily_usage(&samples),
+        skill: name,
+        days,
+    }))
+}
+
+// =============================================================================
+// Enhance Handlers
+// =============================================================================
+
+/// Generate examples for a skill's tools with the configured LLM
+///
+/// Works like `skill enhance <name>`: AI ingestion must be enabled in the
+/// namespace's live search configuration, and tools whose cached examples are still
+/// fresh are skipped and `[ai_ingestion.budget]` limits are enforced unless
+/// `force` is set. Streams generation events
+/// (`started`, `thinking`, `example`, `validation`, `progress`,
+/// `tool_completed`, `completed`, `error`) as SSE while examples are
+/// generated and indexed. Generation keeps going if the client disconnects.
+#[cfg(feature = "ai-ingestion")]
+pub async fn enhance_skill(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    request: Option<Json<EnhanceSkillRequest>>,
+) -> Result<axum::response::Response, (StatusCode, Json<ApiError>)> {
+    use axum::response::sse::{Event, KeepAlive, Sse};
+    use futures::StreamExt;
+    use skill_runtime::{
+        ExampleCache, GenerationRecorder, SearchPipeline, ToolProgress,
+    };
+
+    let force = request.map(|Json(request)| request.force).unwrap_or_default();
+    let internal = |e: anyhow::Error| {
+        (StatusCode::INTERNAL_SERVER_ERROR, Json(ApiError::internal(format!("{:#}", e))))
+    };
+
+    let (tools, documents) = state.skill_tool_documents(&name).await.ok_or_else(|| {
+        (StatusCode::NOT_FOUND, Json(ApiError::not_found(&format!("Documented skill '{}'", name))))
+    })?;
+
+    let mut config = state.effective_search_config().await;
+    config.ai_ingestion.budget.force = force;
+
+    // A saved agent configuration takes over from [ai_ingestion]
+    let agent = if crate::agent::config_path(state.namespace.as_deref()).exists() {
+        let agent_config = state.agent_config.read().await.clone();
+        let llm = crate::agent::runner(&agent_config)
+            .and_then(|runner| runner.provider())
+            .map_err(|e| {
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Json(ApiError::new("AGENT_UNAVAILABLE", format!("{:#}", e))),
+                )
+            })?;
+        Some(llm)
+    } else {
+        None
+    };
+    if !config.ai_ingestion.enabled && agent.is_none() {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiError::new(
+                "AI_INGESTION_DISABLED",
+                format!(
+                    "AI ingestion is not enabled; set [ai_ingestion] enabled = true in {} or configure an agent",
+                    state.search_config_path().display()
+                ),
+            )),
+        ));
+    }
+    refuse_during_shutdown(&state)?;
+
+    let mut pipeline = SearchPipeline::from_config(config.clone()).await.map_err(internal)?;
+    if let Some(llm) = &agent {
+        pipeline = pipeline.with_llm_provider(llm.clone());
+    }
+    if !pipeline.has_example_generator() {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiError::new("LLM_UNAVAILABLE", "Failed to initialize the LLM provider")),
+        ));
+    }
+    // Refuse up front when the budget is already spent
+    if let Err(e) = pipeline.budget().check(1) {
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            Json(ApiError::new("AI_BUDGET_EXCEEDED", e.to_string())),
+        ));
+    }
+
+    // Reuse fresh cached examples, like the CLI
+    let cache_path = ExampleCache::default_path().map_err(internal)?;
+    let mut cache = ExampleCache::load(&cache_path).unwrap_or_default();
+    let reuse_cached = config.ai_ingestion.cache_examples && !force;
+    let (pending, cached): (Vec<_>, Vec<_>) = tools
+        .into_iter()
+        .partition(|t| !reuse_cached || !cache.is_fresh(&name, t, None));
+    let cached_examples: HashMap<_, _> = cached
+        .iter()
+        .filter_map(|t| Some((t.name.clone(), cache.get(&name, &t.name)?.examples.clone())))
+        .collect();
+    let documents = SearchPipeline::with_generated_examples(documents, &cached_examples);
+
+    // Progress is shared with the CLI and GET /enhance/status
+    let progress_path = EnhanceProgressDb::default_path().map_err(internal)?;
+    let progress = EnhanceProgressDb::open(&progress_path).await.map_err(internal)?;
+    let run = progress.start_or_resume(std::slice::from_ref(&name)).await.map_err(internal)?;
+    let pending_names: Vec<String> = pending.iter().map(|t| t.name.clone()).collect();
+    progress.plan_tools(&run.id, &name, &pending_names).await.map_err(internal)?;
+
+    info!(skill = %name, tools = pending.len(), "Enhancing skill");
+    let in_flight = state.shutdown.track();
+    let model = match &agent {
+        Some(llm) => llm.model().to_string(),
+        None => config.ai_ingestion.get_model().to_string(),
+    };
+    let (events, receiver) = futures::channel::mpsc::unbounded();
+    tokio::spawn(async move {
+        let mut recorder = GenerationRecorder::new();
+        let mut stream = Box::pin(pipeline.index_documents_stream(documents, pending.clone()));
+
+        while let Some(event) = stream.next().await {
+            let finished = recorder.observe(&event);
+            let _ = events.unbounded_send(event);
+
+            let Some(tool) = finished.and_then(|tool| pending.iter().find(|t| t.name == tool)) else {
+                continue;
+            };
+            let entry = recorder.finish(tool, &model);
+            let outcome = if entry.failed { ToolProgress::Failed } else { ToolProgress::Completed };
+            let kept = entry.examples.len();
+            cache.insert(&name, &tool.name, entry);
+            if let Err(e) = cache.save(&cache_path) {
+                warn!(error = %e, "Failed to save example cache");
+            }
+            if let Err(e) = progress.record_tool(&run.id, &name, &tool.name, outcome, kept).await {
+                warn!(error = %e, "Failed to record enhance progress");
+            }
+        }
+
+        if let Err(e) = progress.finish_run(&run.id).await {
+            warn!(error = %e, "Failed to finish enhance run");
+        }
+        drop(in_flight);
+    });
+
+    let stream = receiver.map(|event| Event::default().event(event.event_type()).json_data(&event));
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()).into_response())
+}
+
+/// Without the `ai-ingestion` feature, enhancement isn't available
+#[cfg(not(feature = "ai-ingestion"))]
+pub async fn enhance_skill(
+    Path(_name): Path<String>,
+) -> Result<axum::response::Response, (StatusCode, Json<ApiError>)> {
+    Err((
+        StatusCode::NOT_IMPLEMENTED,
+        Json(ApiError::new(
+            "AI_INGESTION_UNAVAILABLE",
+            "Server was built without the ai-ingestion feature",
+        )),
+    ))
+}
+
+/// Progress of the most recent `skill enhance` run
+pub async fn get_enhance_status(
+) -> Result<Json<EnhanceStatusResponse>, (StatusCode, Json<ApiError>)> {
+    let internal = |e: anyhow::Error| {
+        error!(error = %e, "Failed to read enhance progress");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError::internal("Failed to read enhance progress")),
+        )
+    };
+
+    // The CLI creates the database on its first run
+    let path = EnhanceProgressDb::default_path().map_err(internal)?;
+    if !path.exists() {
+        return Ok(Json(EnhanceStatusResponse { run: None, processed: 0, total: 0 }));
+    }
+
+    let db = EnhanceProgressDb::open(&path).await.map_err(internal)?;
+    let run = db.latest_status().await.map_err(internal)?;
+
+    Ok(Json(EnhanceStatusResponse {
+        processed: run.as_ref().map_or(0, |r| r.processed()),
+        total: run.as_ref().map_or(0, |r| r.total()),
+        run: run.as_ref().map(api_json),
+    }))
+}
+
+// =============================================================================
+// Auth Status Handlers
+// =============================================================================
+
+/// List credentials stored with `skill auth login`, with expiry warnings
+///
+/// Reads the credential index, which holds metadata only; secrets stay in the keyring.
+pub async fn get_auth_status(
+    State(_state): State<Arc<AppState>>,
+) -> Result<Json<AuthStatusResponse>, (StatusCode, Json<ApiError>)> {
+    let index = skill_runtime::CredentialIndex::load().map_err(|e| {
+        error!(error = %e, "Failed to load credential index");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError::internal("Failed to load credential index")),
+        )
+    })?;
+
+    let credentials: Vec<CredentialStatusInfo> = index
+        .statuses(None)
+        .into_iter()
+        .map(|status| CredentialStatusInfo {
+            provider_id: status.summary.provider_id,
+            display_name: status.summary.display_name,
+            auth_type: status.summary.auth_type,
+            skill: status.summary.skill,
+            instance: status.summary.instance,
+            scopes: status.summary.scopes,
+            expires_at: status.summary.expires_at,
+            has_refresh_token: status.summary.has_refresh_token,
+            state: serde_json::to_value(status.state)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            warning: status.warning,
+        })
+        .collect();
+    let warnings = credentials
+        .iter()
+        .filter_map(|c| c.warning.clone())
+        .collect();
+
+    Ok(Json(AuthStatusResponse {
+        credentials,
+        warnings,
+    }))
+}
+
 /// Serve the OpenAPI specification as JSON
 pub async fn openapi_spec() -> impl IntoResponse {
     use axum::http::header;
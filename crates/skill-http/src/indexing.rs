@@ -0,0 +1,219 @@
+//! Indexing - building the search index in the background on startup
+//!
+//! Without an index, `/api/search` has nothing to return until someone calls
+//! `/api/search/index`. [`IndexTask`] creates the search pipeline once the
+//! server is up and indexes every loaded skill one at a time, so search
+//! fills in while the server already answers requests. Progress is kept in
+//! [`AppState::index_status`] and reported as the `search_index` component
+//! of `/api/health`.
+//!
+//! A pipeline restored from an index snapshot (`SKILL_INDEX_SNAPSHOT`)
+//! reuses the stored embeddings, so only skills that changed since the
+//! snapshot are embedded. Set `SKILL_INDEX_ON_STARTUP=false` to skip the
+//! build.
+//!
+//! Changing the search configuration through `/api/search/config` builds a
+//! new pipeline the same way and swaps it in once it has indexed every
+//! skill; searches keep using the old one until then.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use skill_runtime::search::SearchPipeline;
+use tracing::{info, warn};
+
+use crate::server::AppState;
+use crate::types::ComponentHealth;
+
+/// Progress of the search index build
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum IndexStatus {
+    /// No index has been built yet
+    #[default]
+    NotStarted,
+    /// Indexing on startup is turned off
+    Disabled,
+    /// Skills are being indexed
+    Building {
+        /// Skills indexed so far
+        indexed_skills: usize,
+        /// Skills to index
+        total_skills: usize,
+        /// Documents indexed so far
+        documents: usize,
+    },
+    /// Every loaded skill is indexed
+    Ready {
+        /// Documents indexed
+        documents: usize,
+        /// How long the build took
+        duration_ms: u64,
+    },
+    /// The build failed; search may be missing skills
+    Failed {
+        /// What went wrong
+        error: String,
+    },
+}
+
+impl IndexStatus {
+    /// The `search_index` component of `/api/health`
+    ///
+    /// Only a failed build is unhealthy; a build in progress is reported
+    /// but doesn't degrade the server.
+    pub fn health(&self) -> ComponentHealth {
+        let message = match self {
+            Self::NotStarted => "Not built yet".to_string(),
+            Self::Disabled => "Indexing on startup is disabled".to_string(),
+            Self::Building { indexed_skills, total_skills, documents } => format!(
+                "Building: {}/{} skills, {} documents",
+                indexed_skills, total_skills, documents
+            ),
+            Self::Ready { documents, duration_ms } => {
+                format!("Ready: {} documents indexed in {}ms", documents, duration_ms)
+            }
+            Self::Failed { error } => format!("Build failed: {}", error),
+        };
+        ComponentHealth {
+            name: "Search Index".to_string(),
+            healthy: !matches!(self, Self::Failed { .. }),
+            message: Some(message),
+        }
+    }
+}
+
+/// Background task building a state's search index
+pub struct IndexTask {
+    state: Arc<AppState>,
+}
+
+impl IndexTask {
+    /// Create a task for a state
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self { state }
+    }
+
+    /// Create the search pipeline if needed and index every loaded skill
+    ///
+    /// Returns the number of documents indexed.
+    pub async fn run_once(&self) -> Result<usize> {
+        if self.state.search_pipeline.read().await.is_none() {
+            self.state
+                .initialize_search_pipeline()
+                .await
+                .context("Failed to initialize search pipeline")?;
+        }
+        let pipeline = self
+            .state
+            .search_pipeline
+            .read()
+            .await
+            .clone()
+            .context("Search pipeline not initialized")?;
+        self.index_into(&pipeline).await
+    }
+
+    /// Index every loaded skill into `pipeline`, reporting progress
+    async fn index_into(&self, pipeline: &SearchPipeline) -> Result<usize> {
+        let mut skills: Vec<String> = self.state.skills.read().await.keys().cloned().collect();
+        skills.sort();
+
+        let start = Instant::now();
+        let total_skills = skills.len();
+        let mut documents = 0;
+        self.set_status(IndexStatus::Building { indexed_skills: 0, total_skills, documents })
+            .await;
+        for (idx, skill) in skills.iter().enumerate() {
+            if self.state.shutdown.is_triggered() {
+                anyhow::bail!("Server shut down while indexing");
+            }
+            if let Some((_, skill_documents)) = self.state.skill_tool_documents(skill).await {
+                documents += skill_documents.len();
+                pipeline
+                    .index_documents(skill_documents)
+                    .await
+                    .with_context(|| format!("Failed to index skill '{}'", skill))?;
+            }
+            self.set_status(IndexStatus::Building {
+                indexed_skills: idx + 1,
+                total_skills,
+                documents,
+            })
+            .await;
+        }
+
+        self.set_status(IndexStatus::Ready {
+            documents,
+            duration_ms: start.elapsed().as_millis() as u64,
+        })
+        .await;
+        Ok(documents)
+    }
+
+    async fn set_status(&self, status: IndexStatus) {
+        *self.state.index_status.write().await = status;
+    }
+
+    /// Index every loaded skill into a new pipeline in the background, then
+    /// put it in place of the current one
+    ///
+    /// Searches already running finish on the pipeline they started with,
+    /// which is dropped once the last of them is done. A pipeline whose
+    /// configuration was replaced again while it was indexing is discarded.
+    pub fn spawn_replacement(self, pipeline: SearchPipeline) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let documents = match self.index_into(&pipeline).await {
+                Ok(documents) => documents,
+                Err(e) => {
+                    warn!("Failed to index with the new search configuration: {:#}", e);
+                    self.set_status(IndexStatus::Failed { error: format!("{:#}", e) }).await;
+                    return;
+                }
+            };
+
+            let current = self.state.effective_search_config().await;
+            if serde_json::to_value(&current).ok() != serde_json::to_value(pipeline.config()).ok() {
+                info!("Search configuration changed while indexing; discarding the rebuilt pipeline");
+                return;
+            }
+            *self.state.search_pipeline.write().await = Some(Arc::new(pipeline));
+            info!(documents, "Applied new search configuration");
+        })
+    }
+
+    /// Build the index in the background, unless `index_on_startup` (or
+    /// `SKILL_INDEX_ON_STARTUP`) turns it off
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            if !self.state.effective_search_config().await.index.index_on_startup {
+                self.set_status(IndexStatus::Disabled).await;
+                return;
+            }
+            match self.run_once().await {
+                Ok(documents) => info!(documents, "Search index built"),
+                Err(e) => {
+                    warn!("Failed to build search index: {:#}", e);
+                    self.set_status(IndexStatus::Failed { error: format!("{:#}", e) }).await;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_health() {
+        let building = IndexStatus::Building { indexed_skills: 2, total_skills: 5, documents: 14 };
+        let health = building.health();
+        assert!(health.healthy);
+        assert_eq!(health.message.as_deref(), Some("Building: 2/5 skills, 14 documents"));
+
+        let failed = IndexStatus::Failed { error: "model download failed".to_string() };
+        assert!(!failed.health().healthy);
+        assert!(IndexStatus::default().health().healthy);
+    }
+}
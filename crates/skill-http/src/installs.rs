@@ -0,0 +1,536 @@
+//! Installs - installing skills in the background
+//!
+//! Cloning a repository or building a skill from source can take minutes, so
+//! `POST /api/skills` doesn't wait for it: it queues the install and answers
+//! with an [`InstallJob`] right away. Installs run one at a time. Each one
+//! fetches the source, builds and verifies every skill it provides, copies
+//! them to the registry with a default instance and re-indexes search,
+//! keeping the job's stage and progress (clone percentage included) up to
+//! date for `GET /api/installs/{id}`. `DELETE /api/installs/{id}` cancels an
+//! install: a clone stops at once, other stages when they finish. Skills
+//! installed before the cancellation stay installed.
+//!
+//! Installs are recorded as `skill_install` jobs in the job queue
+//! (`~/.skill-engine/jobs.db`), so finished ones can still be looked up
+//! after they've left memory or the server restarted.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use skill_runtime::dependencies::{is_installed_in, read_declared_dependencies};
+use skill_runtime::jobs::{create_storage, Job, JobConfig, JobStatus, JobStorage, JobType};
+use skill_runtime::{
+    is_archive_url, is_git_url, is_oci_reference, parse_archive_url, parse_git_url,
+    parse_oci_reference, verify_skill_dir, ArchiveFormat, ArchiveSkillLoader, GitRef,
+    GitSkillLoader, InstanceConfig, OciSkillLoader, SkillChanged, SkillDependency, TrustStore,
+};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::server::AppState;
+use crate::types::{InstallJob, InstallSkillRequest, InstallStage, SkillSummary};
+
+/// Finished installs are kept in memory this long; older ones are read back
+/// from the job queue
+pub const INSTALL_RETENTION: Duration = Duration::from_secs(60 * 60);
+
+/// Share of the overall progress taken by fetching the source
+const FETCH_PROGRESS: usize = 40;
+
+/// Share of the overall progress taken by installing the fetched skills
+const INSTALL_PROGRESS: usize = 50;
+
+/// Skill installs of one state, running and recently finished
+#[derive(Default)]
+pub struct Installs {
+    jobs: RwLock<HashMap<String, Arc<InstallHandle>>>,
+    /// Held by the running install, so installs don't clone or write the
+    /// registry over each other
+    running: tokio::sync::Mutex<()>,
+}
+
+impl Installs {
+    /// Queue an install and return its job
+    pub async fn start(&self, state: Arc<AppState>, request: InstallSkillRequest) -> InstallJob {
+        let storage = match create_storage(&JobConfig::sqlite_default()).await {
+            Ok(storage) => Some(storage),
+            Err(e) => {
+                warn!("Not recording skill install: {}", e);
+                None
+            }
+        };
+        let record = Job::skill_install(&request.source).with_max_attempts(1);
+        let id = record.id;
+        // The server runs the install; keep queue workers from picking it up
+        let record = match &storage {
+            Some(storage) => match storage.enqueue(record.clone()).await {
+                Ok(_) => Some(record),
+                Err(e) => {
+                    warn!("Not recording skill install: {}", e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let handle = Arc::new(InstallHandle {
+            job: Mutex::new(InstallJob {
+                id: id.to_string(),
+                source: request.source.clone(),
+                stage: InstallStage::Queued,
+                progress: 0,
+                message: "Waiting for other installs to finish".to_string(),
+                installed: Vec::new(),
+                tools_count: 0,
+                error: None,
+                started_at: Utc::now(),
+                finished_at: None,
+            }),
+            cancelled: AtomicBool::new(false),
+            record: storage.zip(record).map(|(storage, job)| (storage, tokio::sync::Mutex::new(job))),
+        });
+        let job = handle.snapshot();
+
+        let mut jobs = self.jobs.write().await;
+        let cutoff = Utc::now() - chrono::Duration::from_std(INSTALL_RETENTION).unwrap_or_default();
+        jobs.retain(|_, handle| handle.snapshot().finished_at.map_or(true, |at| at > cutoff));
+        jobs.insert(job.id.clone(), handle.clone());
+        drop(jobs);
+
+        tokio::spawn(async move {
+            let installs = &state.installs;
+            let _running = installs.running.lock().await;
+            handle.run(&state, request).await;
+        });
+        job
+    }
+
+    /// An install by ID, from memory or the job queue
+    pub async fn get(&self, id: &str) -> Option<InstallJob> {
+        if let Some(handle) = self.jobs.read().await.get(id) {
+            return Some(handle.snapshot());
+        }
+
+        let id = Uuid::parse_str(id).ok()?;
+        let storage = create_storage(&JobConfig::sqlite_default()).await.ok()?;
+        let job = storage.get(id).await.ok()??;
+        if !matches!(job.job_type, JobType::SkillInstall { .. }) {
+            return None;
+        }
+        let mut install: InstallJob = serde_json::from_value(job.result?).ok()?;
+        if !install.stage.is_finished() {
+            // The server stopped before the install finished
+            install.stage = InstallStage::Failed;
+            install.error = Some("Interrupted by a server restart".to_string());
+        }
+        Some(install)
+    }
+
+    /// Cancel a running or queued install
+    ///
+    /// Returns the job, or `None` if this server isn't running it.
+    pub async fn cancel(&self, id: &str) -> Option<InstallJob> {
+        let handle = self.jobs.read().await.get(id)?.clone();
+        if !handle.snapshot().stage.is_finished() {
+            handle.cancelled.store(true, Ordering::SeqCst);
+            info!(install = %id, "Cancelling skill install");
+        }
+        Some(handle.snapshot())
+    }
+}
+
+/// A skill fetched from its source, ready to install
+struct FetchedSkill {
+    wasm_path: PathBuf,
+    skill_name: String,
+    version: Option<String>,
+    dependencies: Vec<SkillDependency>,
+}
+
+/// One install's progress, cancellation flag and job queue record
+struct InstallHandle {
+    job: Mutex<InstallJob>,
+    cancelled: AtomicBool,
+    record: Option<(Arc<dyn JobStorage>, tokio::sync::Mutex<Job>)>,
+}
+
+impl InstallHandle {
+    fn snapshot(&self) -> InstallJob {
+        self.job.lock().unwrap().clone()
+    }
+
+    fn is_cancelled(&self, state: &AppState) -> bool {
+        self.cancelled.load(Ordering::SeqCst) || state.shutdown.is_triggered()
+    }
+
+    fn check_cancelled(&self, state: &AppState) -> Result<()> {
+        if self.is_cancelled(state) {
+            anyhow::bail!("Install cancelled");
+        }
+        Ok(())
+    }
+
+    /// Update the in-memory progress only, for frequent updates like clone
+    /// progress
+    fn report(&self, progress: usize, message: impl Into<String>) {
+        let mut job = self.job.lock().unwrap();
+        job.progress = progress.min(100) as u8;
+        job.message = message.into();
+    }
+
+    /// Move to another stage and record it in the job queue
+    async fn set_stage(&self, stage: InstallStage, progress: usize, message: impl Into<String>) {
+        {
+            let mut job = self.job.lock().unwrap();
+            job.stage = stage;
+            if stage.is_finished() {
+                job.finished_at = Some(Utc::now());
+            }
+        }
+        self.report(progress, message);
+        self.persist().await;
+    }
+
+    async fn persist(&self) {
+        let Some((storage, record)) = &self.record else {
+            return;
+        };
+        let install = self.snapshot();
+        let mut job = record.lock().await;
+        job.result = serde_json::to_value(&install).ok();
+        job.error = install.error.clone();
+        job.status = match install.stage {
+            InstallStage::Queued => JobStatus::Pending,
+            InstallStage::Completed => JobStatus::Completed,
+            InstallStage::Failed => JobStatus::Failed,
+            InstallStage::Cancelled => JobStatus::Cancelled,
+            _ => JobStatus::Running,
+        };
+        if job.status == JobStatus::Running && job.started_at.is_none() {
+            job.started_at = Some(Utc::now());
+            job.attempts = 1;
+        }
+        job.completed_at = install.finished_at;
+        if let Err(e) = storage.update(&job).await {
+            warn!(install = %install.id, "Failed to record install progress: {}", e);
+        }
+    }
+
+    async fn run(self: &Arc<Self>, state: &Arc<AppState>, request: InstallSkillRequest) {
+        info!(source = %request.source, "Installing skill");
+        match self.install(state, &request).await {
+            Ok(()) => {
+                let job = self.snapshot();
+                info!(source = %request.source, skills = ?job.installed, "Installed skill");
+                let message = format!("Installed {}", job.installed.join(", "));
+                self.set_stage(InstallStage::Completed, 100, message).await;
+            }
+            Err(e) if self.is_cancelled(state) => {
+                info!(source = %request.source, "Skill install cancelled: {:#}", e);
+                let progress = self.snapshot().progress as usize;
+                self.set_stage(InstallStage::Cancelled, progress, "Cancelled").await;
+            }
+            Err(e) => {
+                warn!(source = %request.source, "Failed to install skill: {:#}", e);
+                let progress = self.snapshot().progress as usize;
+                self.job.lock().unwrap().error = Some(format!("{:#}", e));
+                self.set_stage(InstallStage::Failed, progress, "Failed").await;
+            }
+        }
+    }
+
+    async fn install(self: &Arc<Self>, state: &Arc<AppState>, request: &InstallSkillRequest) -> Result<()> {
+        self.check_cancelled(state)?;
+        let source = request.source.strip_prefix("local:").unwrap_or(&request.source);
+        self.set_stage(InstallStage::Fetching, 0, format!("Fetching {}", source)).await;
+        let mut fetched = self
+            .fetch(state, source, request.git_ref.as_deref(), request.force)
+            .await?;
+        if let (Some(name), [skill]) = (&request.name, fetched.as_mut_slice()) {
+            skill.skill_name = name.clone();
+        }
+
+        let instance = request.instance.as_deref().unwrap_or("default");
+        let total = fetched.len().max(1);
+        let mut installing = Vec::new();
+        for (idx, skill) in fetched.into_iter().enumerate() {
+            let base = FETCH_PROGRESS + idx * INSTALL_PROGRESS / total;
+            self.install_fetched(state, skill, instance, base, &mut installing)
+                .await?;
+        }
+
+        let installed = self.snapshot().installed;
+        self.set_stage(InstallStage::Indexing, FETCH_PROGRESS + INSTALL_PROGRESS, "Updating the search index")
+            .await;
+        for name in &installed {
+            if let Err(e) = state.apply_skill_change(&SkillChanged::Updated(name.clone())).await {
+                warn!(skill = %name, "Failed to index installed skill: {:#}", e);
+            }
+        }
+        let skills = state.skills.read().await;
+        let tools_count = installed
+            .iter()
+            .filter_map(|name| skills.get(name))
+            .map(|skill| skill.tools_count)
+            .sum();
+        self.job.lock().unwrap().tools_count = tools_count;
+        Ok(())
+    }
+
+    /// Fetch every skill a source provides
+    async fn fetch(
+        self: &Arc<Self>,
+        state: &Arc<AppState>,
+        source: &str,
+        git_ref: Option<&str>,
+        force: bool,
+    ) -> Result<Vec<FetchedSkill>> {
+        // Archive URLs first: release download links on GitHub would
+        // otherwise be taken for repository URLs
+        if is_oci_reference(source) {
+            let skill = OciSkillLoader::new()?.pull(&parse_oci_reference(source)?).await?;
+            return Ok(vec![FetchedSkill {
+                wasm_path: skill.wasm_path,
+                skill_name: skill.skill_name,
+                version: skill.version,
+                dependencies: skill.dependencies,
+            }]);
+        }
+        if is_archive_url(source) {
+            self.report(FETCH_PROGRESS / 2, format!("Downloading {}", source));
+            let skill = ArchiveSkillLoader::new()?.fetch(&parse_archive_url(source)?).await?;
+            return Ok(vec![FetchedSkill {
+                wasm_path: skill.wasm_path,
+                skill_name: skill.skill_name,
+                version: skill.version,
+                dependencies: skill.dependencies,
+            }]);
+        }
+        if is_git_url(source) {
+            return self.fetch_git(state, source, git_ref, force).await;
+        }
+        fetch_local(source).map(|skill| vec![skill])
+    }
+
+    /// Clone a repository and build its skills, off the async runtime
+    async fn fetch_git(
+        self: &Arc<Self>,
+        state: &Arc<AppState>,
+        source: &str,
+        git_ref: Option<&str>,
+        force: bool,
+    ) -> Result<Vec<FetchedSkill>> {
+        let mut git_source = parse_git_url(source)?;
+        if let Some(git_ref) = git_ref.filter(|git_ref| !git_ref.is_empty()) {
+            git_source.git_ref = GitRef::parse(git_ref);
+        }
+
+        let handle = self.clone();
+        let cancel_state = state.clone();
+        let loader = GitSkillLoader::new()?.with_progress(Arc::new(move |received, total| {
+            if let Some(done) = (received * FETCH_PROGRESS).checked_div(total) {
+                handle.report(done, format!("Receiving objects {}/{}", received, total));
+            }
+            !handle.is_cancelled(&cancel_state)
+        }));
+
+        // git2 and the skill builds block, so they get their own thread
+        let runtime = tokio::runtime::Handle::current();
+        let (loader, cloned) = tokio::task::spawn_blocking(move || {
+            let cloned = runtime.block_on(loader.clone_skills(&git_source, force));
+            cloned.map(|cloned| (loader, cloned))
+        })
+        .await
+        .context("Clone task failed")??;
+        self.check_cancelled(state)?;
+
+        let loader = Arc::new(loader);
+        let mut fetched = Vec::new();
+        for cloned in cloned {
+            self.set_stage(
+                InstallStage::Building,
+                FETCH_PROGRESS,
+                format!("Building {} ({})", cloned.skill_name, cloned.skill_type),
+            )
+            .await;
+            let runtime = tokio::runtime::Handle::current();
+            let builder = loader.clone();
+            let (cloned, wasm_path) = tokio::task::spawn_blocking(move || {
+                let wasm_path = runtime.block_on(builder.build_skill(&cloned));
+                wasm_path.map(|wasm_path| (cloned, wasm_path))
+            })
+            .await
+            .context("Build task failed")??;
+            self.check_cancelled(state)?;
+
+            fetched.push(FetchedSkill {
+                wasm_path,
+                skill_name: cloned.skill_name,
+                version: cloned.version,
+                dependencies: cloned.dependencies,
+            });
+        }
+        Ok(fetched)
+    }
+
+    /// Install one fetched skill after its missing dependencies
+    ///
+    /// `installing` holds the skills being installed, to detect cycles.
+    fn install_fetched<'a>(
+        self: &'a Arc<Self>,
+        state: &'a Arc<AppState>,
+        fetched: FetchedSkill,
+        instance: &'a str,
+        progress: usize,
+        installing: &'a mut Vec<String>,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            let FetchedSkill { wasm_path, skill_name, version, dependencies } = fetched;
+
+            self.set_stage(InstallStage::Verifying, progress, format!("Verifying {}", skill_name))
+                .await;
+            let artifact_dir = wasm_path
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .unwrap_or_else(|| Path::new("."));
+            verify_skill_dir(artifact_dir, &TrustStore::open_default()?)
+                .with_context(|| format!("Refusing to install {}", wasm_path.display()))?
+                .enforce(false)?;
+            let component = state
+                .engine
+                .load_component(&wasm_path)
+                .await
+                .context("Failed to load WASM component")?;
+            state
+                .engine
+                .validate_component(&component)
+                .await
+                .context("Component validation failed")?;
+            self.check_cancelled(state)?;
+
+            if installing.contains(&skill_name) {
+                installing.push(skill_name.clone());
+                anyhow::bail!("Dependency cycle: {}", installing.join(" -> "));
+            }
+            installing.push(skill_name.clone());
+            let registry_dir = state.registry_dir();
+            for dep in dependencies
+                .iter()
+                .filter(|dep| !is_installed_in(&registry_dir, dep.name()))
+            {
+                let dep_source = dep.source().with_context(|| {
+                    format!(
+                        "Dependency '{}' of '{}' is not installed and declares no source",
+                        dep.name(),
+                        skill_name
+                    )
+                })?;
+                self.set_stage(
+                    InstallStage::Fetching,
+                    progress,
+                    format!("Fetching dependency {} of {}", dep.name(), skill_name),
+                )
+                .await;
+                let fetched = self.fetch(state, dep_source, None, false).await?;
+                if !fetched.iter().any(|skill| skill.skill_name == dep.name()) {
+                    anyhow::bail!(
+                        "Dependency '{}' of '{}' not provided by {}",
+                        dep.name(),
+                        skill_name,
+                        dep_source
+                    );
+                }
+                for skill in fetched {
+                    self.install_fetched(state, skill, "default", progress, installing)
+                        .await?;
+                }
+            }
+            installing.pop();
+
+            self.set_stage(InstallStage::Installing, progress, format!("Installing {}", skill_name))
+                .await;
+            let skill_dir = registry_dir.join(&skill_name);
+            std::fs::create_dir_all(&skill_dir).with_context(|| {
+                format!("Failed to create registry directory: {}", skill_dir.display())
+            })?;
+            let dest_path = skill_dir.join(format!("{}.wasm", skill_name));
+            std::fs::copy(&wasm_path, &dest_path).with_context(|| {
+                format!("Failed to copy skill binary to: {}", dest_path.display())
+            })?;
+
+            let mut config = InstanceConfig::default();
+            config.metadata.skill_name = skill_name.clone();
+            config.metadata.skill_version = version.clone().unwrap_or_else(|| "0.1.0".to_string());
+            config.metadata.instance_name = instance.to_string();
+            config.metadata.created_at = Utc::now();
+            config.metadata.updated_at = Utc::now();
+            config.metadata.dependencies =
+                dependencies.iter().map(|dep| dep.name().to_string()).collect();
+            state
+                .instance_manager
+                .create_instance(&skill_name, instance, config, HashMap::new())
+                .context("Failed to create instance")?;
+
+            let source = self.snapshot().source;
+            let mut skills = state.skills.write().await;
+            let previous = skills.get(&skill_name);
+            let summary = SkillSummary {
+                name: skill_name.clone(),
+                version: version.unwrap_or_else(|| "0.1.0".to_string()),
+                description: format!("Installed from {}", source),
+                source,
+                runtime: "wasm".to_string(),
+                tools_count: previous.map_or(0, |skill| skill.tools_count),
+                instances_count: 1,
+                last_used: previous.and_then(|skill| skill.last_used),
+                execution_count: previous.map_or(0, |skill| skill.execution_count),
+                required_services: Vec::new(),
+            };
+            skills.insert(skill_name.clone(), summary);
+            drop(skills);
+
+            self.job.lock().unwrap().installed.push(skill_name);
+            Ok(())
+        })
+    }
+}
+
+/// A `.wasm` file or skill package on the server's filesystem
+fn fetch_local(source: &str) -> Result<FetchedSkill> {
+    let source_path = PathBuf::from(source);
+    if !source_path.exists() {
+        anyhow::bail!("Skill file not found: {}", source);
+    }
+
+    if ArchiveFormat::from_path(source).is_some() {
+        let skill = ArchiveSkillLoader::new()?.open_file(&source_path)?;
+        return Ok(FetchedSkill {
+            wasm_path: skill.wasm_path,
+            skill_name: skill.skill_name,
+            version: skill.version,
+            dependencies: skill.dependencies,
+        });
+    }
+    if !source_path.extension().is_some_and(|ext| ext == "wasm") {
+        anyhow::bail!("Invalid file type: expected a .wasm file or .skillpkg package");
+    }
+
+    let skill_name = source_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .context("Invalid filename")?
+        .to_string();
+    let dependencies = match source_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => read_declared_dependencies(dir)?,
+        _ => read_declared_dependencies(Path::new("."))?,
+    };
+    Ok(FetchedSkill { wasm_path: source_path, skill_name, version: None, dependencies })
+}
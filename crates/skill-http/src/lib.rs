@@ -9,6 +9,21 @@
 //! - **Search**: Semantic search across skills and tools
 //! - **Configuration**: Runtime configuration management
 //! - **Health Checks**: Monitor server and component health
+//! - **TLS**: HTTPS with certificate files or ACME, and optional mTLS
+//! - **gRPC**: `ListSkills`, streaming `ExecuteTool` and `Search` on the same
+//!   port (`grpc` feature; see `proto/skill.proto`)
+//! - **Namespaces**: Isolated skills, history and search per team, selected
+//!   with the `X-Skill-Namespace` header or a `/ns/{namespace}` prefix
+//! - **Retention**: History and analytics are pruned and compacted in the
+//!   background according to the manifest's `[history]` section
+//! - **Concurrency limits**: At most `max_concurrent_executions` tools run at
+//!   once; further requests queue, and get `429` once the queue is full
+//! - **Result cache**: Results of read-only tools are reused for a few
+//!   seconds; `no_cache` on a request bypasses it
+//! - **Background indexing**: The search index is built on startup, with
+//!   progress in the `search_index` component of `/api/health`
+//! - **Persistent settings**: Timeouts, execution and history limits are
+//!   kept in `~/.skill-engine/config.toml` and applied without a restart
 //!
 //! ## API Endpoints
 //!
@@ -16,13 +31,22 @@
 //! - `GET /api/skills` - List all installed skills
 //! - `POST /api/skills` - Install a new skill
 //! - `GET /api/skills/{name}` - Get skill details
+//! - `GET /api/skills/{name}/docs` - Get a skill's SKILL.md or README
 //! - `DELETE /api/skills/{name}` - Uninstall a skill
 //!
 //! ### Execution
 //! - `POST /api/execute` - Execute a tool
+//! - `POST /api/execute/stream` - Execute a tool, streaming its output as SSE
 //! - `GET /api/executions` - List execution history
 //! - `GET /api/executions/{id}` - Get execution details
 //!
+//! ### Presets
+//! - `GET /api/presets` - List saved runs
+//! - `POST /api/presets` - Save a run
+//! - `PUT /api/presets/{id}` - Rename, edit or pin a saved run
+//! - `DELETE /api/presets/{id}` - Delete a saved run
+//! - `POST /api/presets/{id}/run` - Run a saved run
+//!
 //! ### Search
 //! - `POST /api/search` - Semantic search for skills/tools
 //! - `GET /api/search/config` - Get search configuration
@@ -31,6 +55,9 @@
 //! ### Configuration
 //! - `GET /api/config` - Get application configuration
 //! - `PUT /api/config` - Update application configuration
+//! - `GET /api/manifest` - Get the manifest skills were loaded from
+//! - `POST /api/manifest/validate` - Validate a manifest, with located diagnostics
+//! - `POST /api/manifest/import` - Import a manifest, optionally saving it
 //!
 //! ### Health
 //! - `GET /api/health` - Health check
@@ -48,6 +75,7 @@
 //!         port: 3000,
 //!         enable_cors: true,
 //!         enable_tracing: true,
+//!         ..Default::default()
 //!     };
 //!
 //!     let server = HttpServer::with_config(config)?;
@@ -55,17 +83,47 @@
 //! }
 //! ```
 
+pub mod agent;
+pub mod alerts;
 pub mod analytics;
+pub mod concurrency;
+#[cfg(unix)]
+pub mod daemon;
 pub mod embedded;
 pub mod execution_history;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod handlers;
+pub mod indexing;
+pub mod installs;
+pub mod maintenance;
 pub mod middleware;
+pub mod namespaces;
 pub mod openapi;
+pub mod presets;
+pub mod result_cache;
 pub mod routes;
 pub mod server;
+pub mod settings;
+pub mod shutdown;
+pub mod tls;
 pub mod types;
 
-pub use server::{AppState, HttpServer, HttpServerConfig};
+pub use alerts::{Anomaly, AnomalyAnalyzer, AnomalyKind};
+#[cfg(unix)]
+pub use daemon::{DaemonClient, DaemonStatus};
+pub use concurrency::{
+    ExecutionLimiter, ExecutionPermit, DEFAULT_MAX_CONCURRENT_EXECUTIONS, DEFAULT_MAX_QUEUED_EXECUTIONS,
+};
+pub use indexing::{IndexStatus, IndexTask};
+pub use installs::{Installs, INSTALL_RETENTION};
+pub use maintenance::{MaintenanceTask, PruneReport};
+pub use namespaces::{NamespaceConfig, DEFAULT_NAMESPACE, NAMESPACE_HEADER};
+pub use result_cache::{ResultCache, DEFAULT_RESULT_CACHE_TTL};
+pub use server::{AppState, HttpServer, HttpServerConfig, RunningServer};
+pub use settings::{SettingsTask, SETTINGS_RELOAD_INTERVAL};
+pub use shutdown::{ShutdownController, DEFAULT_DRAIN_TIMEOUT};
+pub use tls::{CertificateSource, TlsConfig};
 pub use types::*;
 
 use anyhow::Result;
@@ -79,6 +137,12 @@ pub async fn serve(host: &str, port: u16) -> Result<()> {
         enable_tracing: true,
         enable_web_ui: false,
         working_dir: None,
+        drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+        tls: None,
+        namespaces: Vec::new(),
+        max_concurrent_executions: DEFAULT_MAX_CONCURRENT_EXECUTIONS,
+        max_queued_executions: DEFAULT_MAX_QUEUED_EXECUTIONS,
+        result_cache_ttl: DEFAULT_RESULT_CACHE_TTL,
     };
     let server = HttpServer::with_config(config)?;
     server.run().await
@@ -93,6 +157,12 @@ pub async fn serve_with_ui(host: &str, port: u16) -> Result<()> {
         enable_tracing: true,
         enable_web_ui: true,
         working_dir: None,
+        drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+        tls: None,
+        namespaces: Vec::new(),
+        max_concurrent_executions: DEFAULT_MAX_CONCURRENT_EXECUTIONS,
+        max_queued_executions: DEFAULT_MAX_QUEUED_EXECUTIONS,
+        result_cache_ttl: DEFAULT_RESULT_CACHE_TTL,
     };
     let server = HttpServer::with_config(config)?;
     server.run().await
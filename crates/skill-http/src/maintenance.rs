@@ -0,0 +1,293 @@
+//! Maintenance - retention and compaction of the history databases
+//!
+//! The `[history]` section of the manifest limits how many rows the
+//! execution history and search analytics databases keep, for how long, and
+//! how large each database file may grow. [`MaintenanceTask`] applies it in
+//! the background while the server runs; `skill history prune` applies it on
+//! demand. Deleted rows only give space back to the filesystem once the
+//! database is vacuumed, so every pass that deletes anything ends with a
+//! `VACUUM`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Serialize;
+use skill_runtime::HistoryRetention;
+use sqlx::{Row, SqlitePool};
+use tracing::{info, warn};
+
+use crate::server::{trim_history, AppState};
+
+/// How often the server applies the retention policy
+pub const DEFAULT_MAINTENANCE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// When a database is over its size limit, delete enough rows to bring it
+/// this far under, so the next pass doesn't immediately have to prune again
+const SIZE_HEADROOM: f64 = 0.9;
+
+/// What a retention pass removed from one database
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PruneReport {
+    /// Rows deleted across all tables
+    pub deleted: u64,
+    /// Database size in bytes before the pass
+    pub size_before: u64,
+    /// Database size in bytes after the pass (and `VACUUM`)
+    pub size_after: u64,
+}
+
+impl PruneReport {
+    /// Bytes given back to the filesystem
+    pub fn reclaimed(&self) -> u64 {
+        self.size_before.saturating_sub(self.size_after)
+    }
+}
+
+/// A table subject to retention, and the column holding its RFC 3339 timestamps
+pub(crate) struct RetainedTable {
+    pub name: &'static str,
+    pub time_column: &'static str,
+}
+
+/// Apply a retention policy to the tables of one database, then vacuum it
+/// if anything was deleted
+pub(crate) async fn apply_retention(
+    pool: &SqlitePool,
+    tables: &[RetainedTable],
+    retention: &HistoryRetention,
+) -> Result<PruneReport> {
+    let size_before = db_size(pool).await?;
+    let mut deleted = 0;
+
+    if retention.max_age_days > 0 {
+        let cutoff = (Utc::now() - chrono::Duration::days(retention.max_age_days as i64)).to_rfc3339();
+        for table in tables {
+            deleted += sqlx::query(&format!("DELETE FROM {} WHERE {} < ?", table.name, table.time_column))
+                .bind(&cutoff)
+                .execute(pool)
+                .await
+                .with_context(|| format!("Failed to prune old rows from {}", table.name))?
+                .rows_affected();
+        }
+    }
+
+    if retention.max_rows > 0 {
+        for table in tables {
+            deleted += keep_newest(pool, table, retention.max_rows).await?;
+        }
+    }
+
+    // Deleted rows leave free pages behind, so compare what's in use
+    let max_bytes = retention.max_db_size_mb * 1024 * 1024;
+    let used = used_size(pool).await?;
+    if max_bytes > 0 && used > max_bytes {
+        let keep_fraction = max_bytes as f64 / used as f64 * SIZE_HEADROOM;
+        for table in tables {
+            let count: i64 = sqlx::query(&format!("SELECT COUNT(*) AS count FROM {}", table.name))
+                .fetch_one(pool)
+                .await
+                .with_context(|| format!("Failed to count rows in {}", table.name))?
+                .get("count");
+            let keep = (count as f64 * keep_fraction).floor() as u64;
+            deleted += keep_newest(pool, table, keep).await?;
+        }
+    }
+
+    if deleted > 0 {
+        sqlx::query("VACUUM")
+            .execute(pool)
+            .await
+            .context("Failed to vacuum database")?;
+    }
+
+    Ok(PruneReport {
+        deleted,
+        size_before,
+        size_after: db_size(pool).await?,
+    })
+}
+
+/// Delete all but the newest `keep` rows of a table
+async fn keep_newest(pool: &SqlitePool, table: &RetainedTable, keep: u64) -> Result<u64> {
+    let result = sqlx::query(&format!(
+        "DELETE FROM {table} WHERE rowid NOT IN (SELECT rowid FROM {table} ORDER BY {time} DESC LIMIT ?)",
+        table = table.name,
+        time = table.time_column,
+    ))
+    .bind(keep as i64)
+    .execute(pool)
+    .await
+    .with_context(|| format!("Failed to prune {}", table.name))?;
+    Ok(result.rows_affected())
+}
+
+/// Size of the database file in bytes
+async fn db_size(pool: &SqlitePool) -> Result<u64> {
+    Ok(pragma(pool, "page_count").await? * pragma(pool, "page_size").await?)
+}
+
+/// Bytes of the database in use, not counting free pages
+async fn used_size(pool: &SqlitePool) -> Result<u64> {
+    let pages = pragma(pool, "page_count").await? - pragma(pool, "freelist_count").await?;
+    Ok(pages * pragma(pool, "page_size").await?)
+}
+
+async fn pragma(pool: &SqlitePool, name: &str) -> Result<u64> {
+    let value: i64 = sqlx::query(&format!("PRAGMA {}", name))
+        .fetch_one(pool)
+        .await
+        .with_context(|| format!("Failed to read PRAGMA {}", name))?
+        .get(0);
+    Ok(value.max(0) as u64)
+}
+
+/// Background task applying the manifest's `[history]` retention to a
+/// state's databases
+pub struct MaintenanceTask {
+    state: Arc<AppState>,
+    interval: Duration,
+}
+
+impl MaintenanceTask {
+    /// Create a task for a state, running every [`DEFAULT_MAINTENANCE_INTERVAL`]
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self {
+            state,
+            interval: DEFAULT_MAINTENANCE_INTERVAL,
+        }
+    }
+
+    /// Set how often the task runs
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Apply the retention policy once to both databases
+    ///
+    /// Returns the reports for the execution history and analytics
+    /// databases, `None` for one that isn't open.
+    pub async fn run_once(&self) -> Result<(Option<PruneReport>, Option<PruneReport>)> {
+        let retention = self.state.manifest.read().await
+            .as_ref()
+            .map(|manifest| manifest.history)
+            .unwrap_or_default();
+
+        let history_db = self.state.execution_history_db.read().await.clone();
+        let executions = match history_db {
+            Some(db) => Some(db.apply_retention(&retention).await?),
+            None => None,
+        };
+        if executions.is_some_and(|report| report.deleted > 0) {
+            self.trim_cache(&retention).await;
+        }
+
+        let analytics_db = self.state.analytics_db.read().await.clone();
+        let analytics = match analytics_db {
+            Some(db) => Some(db.apply_retention(&retention).await?),
+            None => None,
+        };
+
+        Ok((executions, analytics))
+    }
+
+    /// Keep the in-memory history cache within the same limits
+    async fn trim_cache(&self, retention: &HistoryRetention) {
+        let mut history = self.state.execution_history.write().await;
+        if retention.max_age_days > 0 {
+            let cutoff = Utc::now() - chrono::Duration::days(retention.max_age_days as i64);
+            history.retain(|entry| entry.started_at >= cutoff);
+        }
+        if retention.max_rows > 0 {
+            trim_history(&mut history, retention.max_rows as usize);
+        }
+    }
+
+    /// Run the task until the server shuts down
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let shutdown = self.state.shutdown.clone();
+            let mut ticker = tokio::time::interval(self.interval);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = shutdown.triggered() => break,
+                }
+                match self.run_once().await {
+                    Ok((executions, analytics)) => {
+                        for (name, report) in [("execution history", executions), ("analytics", analytics)] {
+                            if let Some(report) = report.filter(|report| report.deleted > 0) {
+                                info!(
+                                    database = name,
+                                    deleted = report.deleted,
+                                    reclaimed_bytes = report.reclaimed(),
+                                    "Applied history retention"
+                                );
+                            }
+                        }
+                    }
+                    Err(e) => warn!("History maintenance failed: {:#}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_history::ExecutionHistoryDb;
+    use crate::types::{ExecutionHistoryEntry, ExecutionStatus};
+
+    fn entry(id: usize, days_ago: i64) -> ExecutionHistoryEntry {
+        ExecutionHistoryEntry {
+            id: format!("exec-{}", id),
+            skill: "kubernetes".to_string(),
+            tool: "get".to_string(),
+            instance: "default".to_string(),
+            status: ExecutionStatus::Success,
+            duration_ms: 10,
+            started_at: Utc::now() - chrono::Duration::days(days_ago),
+            error: None,
+            output: Some("x".repeat(4096)),
+            args: Default::default(),
+            environment: None,
+            resource_usage: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retention_by_age_and_rows() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let db = ExecutionHistoryDb::new(&dir.path().join("history.db").to_string_lossy()).await?;
+        for (id, days_ago) in [(0, 100), (1, 5), (2, 3), (3, 1), (4, 0)] {
+            db.add_execution(&entry(id, days_ago)).await?;
+        }
+
+        let retention = HistoryRetention { max_rows: 3, max_age_days: 30, max_db_size_mb: 0 };
+        let report = db.apply_retention(&retention).await?;
+        assert_eq!(report.deleted, 2);
+
+        let remaining: Vec<String> = db.list_executions(10, 0).await?.into_iter().map(|e| e.id).collect();
+        assert_eq!(remaining, vec!["exec-4", "exec-3", "exec-2"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retention_by_size_vacuums() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let db = ExecutionHistoryDb::new(&dir.path().join("history.db").to_string_lossy()).await?;
+        for id in 0..600 {
+            db.add_execution(&entry(id, 0)).await?;
+        }
+
+        let retention = HistoryRetention { max_rows: 0, max_age_days: 0, max_db_size_mb: 1 };
+        let report = db.apply_retention(&retention).await?;
+        assert!(report.deleted > 0);
+        assert!(report.size_before > 1024 * 1024);
+        assert!(report.size_after <= 1024 * 1024);
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+//! Namespaces - isolated tenants served from one server
+//!
+//! Each namespace has its own working directory, and so its own manifest,
+//! skills, instances and workflows, plus its own execution history, search
+//! index and analytics. A request picks a namespace with the
+//! `X-Skill-Namespace` header or the `/ns/{namespace}` path prefix
+//! (`/ns/team-a/api/skills`). Requests with neither use the default
+//! namespace: the server's own working directory.
+
+use anyhow::{bail, Context, Result};
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json, Router,
+};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+use crate::routes::create_app;
+use crate::server::AppState;
+use crate::types::ApiError;
+
+/// Header that selects the namespace of a request
+pub const NAMESPACE_HEADER: &str = "x-skill-namespace";
+
+/// Name of the namespace requests use when they don't pick one
+pub const DEFAULT_NAMESPACE: &str = "default";
+
+/// A namespace and the directory its manifest and workflows live in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamespaceConfig {
+    /// Name used in the header and path prefix
+    pub name: String,
+    /// Working directory holding the namespace's `.skill-engine.toml`
+    pub working_dir: PathBuf,
+}
+
+impl NamespaceConfig {
+    /// Validate the name: lowercase letters, digits, `-` and `_`
+    pub fn new(name: impl Into<String>, working_dir: impl Into<PathBuf>) -> Result<Self> {
+        let name = name.into();
+        let valid = !name.is_empty()
+            && name.len() <= 64
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if !valid {
+            bail!(
+                "Invalid namespace '{}': use lowercase letters, digits, '-' and '_'",
+                name
+            );
+        }
+        if name == DEFAULT_NAMESPACE {
+            bail!("'{}' is the server's own namespace", DEFAULT_NAMESPACE);
+        }
+        Ok(Self {
+            name,
+            working_dir: working_dir.into(),
+        })
+    }
+
+    /// Where the namespace keeps its execution history and analytics
+    pub fn data_dir(&self) -> PathBuf {
+        namespace_data_dir(Some(&self.name))
+    }
+}
+
+/// `team-a=./teams/a`
+impl FromStr for NamespaceConfig {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, dir) = s
+            .split_once('=')
+            .with_context(|| format!("Invalid namespace '{}': expected <name>=<dir>", s))?;
+        Self::new(name.trim(), dir.trim())
+    }
+}
+
+/// Data directory of a namespace, or of the default namespace for `None`
+pub fn namespace_data_dir(namespace: Option<&str>) -> PathBuf {
+    let root = dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".skill-engine");
+    match namespace {
+        Some(name) => root.join("namespaces").join(name),
+        None => root,
+    }
+}
+
+/// Serve every namespace's API next to the default one: under its
+/// `/ns/{namespace}` prefix, and on any path when the request carries the
+/// namespace header
+pub fn namespaced(app: Router, namespaces: &[Arc<AppState>]) -> Router {
+    if namespaces.is_empty() {
+        return app;
+    }
+
+    let mut routers = HashMap::new();
+    let mut app = app;
+    for state in namespaces {
+        let Some(name) = state.namespace.clone() else {
+            continue;
+        };
+        let router = create_app(state.clone());
+        app = app.nest(&format!("/ns/{}", name), router.clone());
+        routers.insert(name, router);
+    }
+    app.route(
+        "/ns/:namespace/*path",
+        axum::routing::any(unknown_namespace),
+    )
+    .layer(axum::middleware::from_fn_with_state(
+        Arc::new(routers),
+        route_by_header,
+    ))
+}
+
+/// Hand requests carrying the namespace header to that namespace's router
+async fn route_by_header(
+    State(routers): State<Arc<HashMap<String, Router>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let namespace = request
+        .headers()
+        .get(NAMESPACE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .unwrap_or_default()
+        .to_string();
+    // The path prefix wins over the header
+    if namespace.is_empty()
+        || namespace == DEFAULT_NAMESPACE
+        || request.uri().path().starts_with("/ns/")
+    {
+        return next.run(request).await;
+    }
+
+    match routers.get(&namespace) {
+        Some(router) => match router.clone().oneshot(request).await {
+            Ok(response) => response,
+            Err(infallible) => match infallible {},
+        },
+        None => namespace_not_found(&namespace),
+    }
+}
+
+async fn unknown_namespace(
+    axum::extract::Path((namespace, _)): axum::extract::Path<(String, String)>,
+) -> Response {
+    namespace_not_found(&namespace)
+}
+
+fn namespace_not_found(namespace: &str) -> Response {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ApiError::new(
+            "NAMESPACE_NOT_FOUND",
+            format!("Namespace '{}' not found", namespace),
+        )),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_namespace() {
+        let namespace: NamespaceConfig = "team-a=./teams/a".parse().unwrap();
+        assert_eq!(namespace.name, "team-a");
+        assert_eq!(namespace.working_dir, PathBuf::from("./teams/a"));
+        assert!(namespace.data_dir().ends_with("namespaces/team-a"));
+
+        assert!("team-a".parse::<NamespaceConfig>().is_err());
+        assert!("Team A=./a".parse::<NamespaceConfig>().is_err());
+        assert!("../a=./a".parse::<NamespaceConfig>().is_err());
+        assert!("default=./a".parse::<NamespaceConfig>().is_err());
+    }
+}
@@ -30,25 +30,49 @@ use crate::types::*;
         schemas(
             SkillSummary,
             SkillDetail,
+            SkillDocsResponse,
             ToolInfo,
             ParameterInfo,
             InstanceInfo,
             InstallSkillRequest,
-            InstallSkillResponse,
+            InstallStage,
+            InstallJob,
             ExecutionRequest,
             ExecutionResponse,
+            ResourceUsage,
+            ExecutionCacheInfo,
             ExecutionStatus,
             ExecutionHistoryEntry,
+            ExecutionEnvironment,
+            ExecutionOutputPage,
+            RerunResponse,
+            RunPreset,
+            CreatePresetRequest,
+            UpdatePresetRequest,
+            ExecutionSearchHit,
+            ExecutionSearchResponse,
+            WorkflowSummary,
+            WorkflowStepInfo,
+            RunWorkflowRequest,
+            WorkflowRunResponse,
+            WorkflowEvent,
+            ExecutionStreamEvent,
+            OutputStream,
+            ExecutionArtifact,
             SearchRequest,
+            SearchFilters,
             SearchResult,
+            ScoreExplanation,
             SearchResponse,
             QueryInfo,
             SearchConfigResponse,
             UpdateSearchConfigRequest,
             AppConfig,
+            AlertConfig,
             UpdateAppConfigRequest,
             HealthResponse,
             ComponentHealth,
+            ExecutionUtilization,
             VersionResponse,
             ApiError,
             PaginationParams,
@@ -56,6 +80,9 @@ use crate::types::*;
             ImportManifestResponse,
             ValidateManifestRequest,
             ValidateManifestResponse,
+            DiagnosticSeverity,
+            ManifestDiagnostic,
+            ManifestSourceResponse,
             ExportManifestRequest,
             ExportManifestResponse,
             ServiceStatus,
@@ -63,6 +90,8 @@ use crate::types::*;
             StartServiceRequest,
             StartServiceResponse,
             StopServiceRequest,
+            CredentialStatusInfo,
+            AuthStatusResponse,
             SkillServiceRequirement,
             ParsedSkill,
             ParsedInstance,
@@ -72,6 +101,7 @@ use crate::types::*;
     tags(
         (name = "skills", description = "Skill management operations"),
         (name = "execution", description = "Tool execution operations"),
+        (name = "workflows", description = "Multi-step workflow runs"),
         (name = "search", description = "Semantic search operations"),
         (name = "config", description = "Configuration management"),
         (name = "services", description = "System service management"),
@@ -0,0 +1,294 @@
+//! Database implementation for run presets
+
+use std::path::Path;
+use anyhow::{Context, Result};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions, Row};
+use chrono::{DateTime, Utc};
+
+use crate::types::{RunPreset, UpdatePresetRequest};
+
+/// SQLite-based run preset database
+pub struct PresetsDb {
+    pool: SqlitePool,
+}
+
+impl PresetsDb {
+    /// Create a new run preset database
+    ///
+    /// # Arguments
+    /// * `db_path` - Path to the SQLite database file
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use skill_http::presets::PresetsDb;
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let db = PresetsDb::new("~/.skill-engine/presets.db").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn new(db_path: &str) -> Result<Self> {
+        // Expand home directory
+        let db_path = shellexpand::tilde(db_path).to_string();
+
+        // Ensure parent directory exists
+        if let Some(parent) = Path::new(&db_path).parent() {
+            std::fs::create_dir_all(parent)
+                .context("Failed to create presets database directory")?;
+        }
+
+        // Build connection URL
+        let url = if db_path == ":memory:" {
+            "sqlite::memory:".to_string()
+        } else {
+            format!("sqlite:{}?mode=rwc", db_path)
+        };
+
+        // Create connection pool
+        let pool = SqlitePoolOptions::new()
+            .max_connections(10)
+            .connect(&url)
+            .await
+            .context("Failed to connect to presets database")?;
+
+        let db = Self { pool };
+
+        // Initialize database schema
+        db.setup().await?;
+
+        Ok(db)
+    }
+
+    /// Wait for open connections to finish and close them
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+
+    /// Initialize database schema
+    async fn setup(&self) -> Result<()> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS run_presets (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                skill TEXT NOT NULL,
+                tool TEXT NOT NULL,
+                instance TEXT NOT NULL,
+                args TEXT NOT NULL,
+                pinned INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL,
+                last_run_at TEXT
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create run_presets table")?;
+
+        Ok(())
+    }
+
+    /// Save a new preset
+    pub async fn add_preset(&self, preset: &RunPreset) -> Result<()> {
+        let args = serde_json::to_string(&preset.args)
+            .context("Failed to serialize preset arguments")?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO run_presets (id, name, skill, tool, instance, args, pinned, created_at, last_run_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&preset.id)
+        .bind(&preset.name)
+        .bind(&preset.skill)
+        .bind(&preset.tool)
+        .bind(&preset.instance)
+        .bind(args)
+        .bind(preset.pinned)
+        .bind(preset.created_at.to_rfc3339())
+        .bind(preset.last_run_at.map(|at| at.to_rfc3339()))
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert run preset")?;
+
+        Ok(())
+    }
+
+    /// Get a preset by ID
+    pub async fn get_preset(&self, id: &str) -> Result<Option<RunPreset>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, name, skill, tool, instance, args, pinned, created_at, last_run_at
+            FROM run_presets
+            WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to query run presets")?;
+
+        row.map(row_to_preset).transpose()
+    }
+
+    /// List presets, pinned ones first, then by name
+    pub async fn list_presets(&self) -> Result<Vec<RunPreset>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, skill, tool, instance, args, pinned, created_at, last_run_at
+            FROM run_presets
+            ORDER BY pinned DESC, name COLLATE NOCASE, created_at
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list run presets")?;
+
+        rows.into_iter().map(row_to_preset).collect()
+    }
+
+    /// Apply changes to a preset, returning it as updated, or `None` if it
+    /// doesn't exist
+    pub async fn update_preset(
+        &self,
+        id: &str,
+        changes: &UpdatePresetRequest,
+    ) -> Result<Option<RunPreset>> {
+        let Some(mut preset) = self.get_preset(id).await? else {
+            return Ok(None);
+        };
+        if let Some(name) = &changes.name {
+            preset.name = name.clone();
+        }
+        if let Some(instance) = &changes.instance {
+            preset.instance = instance.clone();
+        }
+        if let Some(args) = &changes.args {
+            preset.args = args.clone();
+        }
+        if let Some(pinned) = changes.pinned {
+            preset.pinned = pinned;
+        }
+
+        let args = serde_json::to_string(&preset.args)
+            .context("Failed to serialize preset arguments")?;
+        sqlx::query("UPDATE run_presets SET name = ?, instance = ?, args = ?, pinned = ? WHERE id = ?")
+            .bind(&preset.name)
+            .bind(&preset.instance)
+            .bind(args)
+            .bind(preset.pinned)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to update run preset")?;
+
+        Ok(Some(preset))
+    }
+
+    /// Record that a preset was just run
+    pub async fn mark_run(&self, id: &str, at: DateTime<Utc>) -> Result<()> {
+        sqlx::query("UPDATE run_presets SET last_run_at = ? WHERE id = ?")
+            .bind(at.to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to update run preset")?;
+
+        Ok(())
+    }
+
+    /// Delete a preset, returning whether it existed
+    pub async fn delete_preset(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM run_presets WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to delete run preset")?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn row_to_preset(row: sqlx::sqlite::SqliteRow) -> Result<RunPreset> {
+    let parse_time = |value: &str| {
+        DateTime::parse_from_rfc3339(value)
+            .map(|at| at.with_timezone(&Utc))
+            .context("Failed to parse run preset timestamp")
+    };
+
+    Ok(RunPreset {
+        id: row.get("id"),
+        name: row.get("name"),
+        skill: row.get("skill"),
+        tool: row.get("tool"),
+        instance: row.get("instance"),
+        args: serde_json::from_str(&row.get::<String, _>("args")).unwrap_or_default(),
+        pinned: row.get("pinned"),
+        created_at: parse_time(&row.get::<String, _>("created_at"))?,
+        last_run_at: row
+            .get::<Option<String>, _>("last_run_at")
+            .as_deref()
+            .map(parse_time)
+            .transpose()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn preset(id: &str, name: &str, pinned: bool) -> RunPreset {
+        RunPreset {
+            id: id.to_string(),
+            name: name.to_string(),
+            skill: "kubernetes".to_string(),
+            tool: "get".to_string(),
+            instance: "default".to_string(),
+            args: HashMap::from([("resource".to_string(), serde_json::json!("pods"))]),
+            pinned,
+            created_at: Utc::now(),
+            last_run_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_presets_crud() -> Result<()> {
+        let db = PresetsDb::new(":memory:").await?;
+
+        db.add_preset(&preset("a", "staging pods", false)).await?;
+        db.add_preset(&preset("b", "Prod pods", false)).await?;
+        db.add_preset(&preset("c", "nodes", true)).await?;
+
+        // Pinned first, then case-insensitively by name
+        let names: Vec<_> = db.list_presets().await?.into_iter().map(|p| p.name).collect();
+        assert_eq!(names, ["nodes", "Prod pods", "staging pods"]);
+
+        let updated = db
+            .update_preset(
+                "a",
+                &UpdatePresetRequest {
+                    pinned: Some(true),
+                    args: Some(HashMap::new()),
+                    ..Default::default()
+                },
+            )
+            .await?
+            .expect("preset was added");
+        assert!(updated.pinned);
+        assert!(updated.args.is_empty());
+        assert_eq!(db.get_preset("a").await?, Some(updated));
+
+        let ran_at = Utc::now();
+        db.mark_run("b", ran_at).await?;
+        let ran = db.get_preset("b").await?.expect("preset was added");
+        assert_eq!(ran.last_run_at.map(|at| at.timestamp()), Some(ran_at.timestamp()));
+
+        assert!(db.delete_preset("c").await?);
+        assert!(!db.delete_preset("c").await?);
+        assert!(db.update_preset("c", &UpdatePresetRequest::default()).await?.is_none());
+        assert_eq!(db.list_presets().await?.len(), 2);
+
+        Ok(())
+    }
+}
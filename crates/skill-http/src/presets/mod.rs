@@ -0,0 +1,9 @@
+//! Saved runs for the web UI
+//!
+//! Provides SQLite-based storage for named presets of a skill, tool,
+//! instance and arguments that can be run again in one click or pinned to
+//! the sidebar.
+
+pub mod db;
+
+pub use db::PresetsDb;
@@ -0,0 +1,192 @@
+//! Result cache - short-lived memoization of read-only tool results
+//!
+//! Agents tend to call the same read-only tool (`kubernetes get pods`)
+//! several times within seconds. Successful results of tools whose SKILL.md
+//! hints resolve to read-only are kept for a short TTL, keyed by skill, tool,
+//! instance and arguments, and returned without executing the tool again.
+//! Callers that need a fresh result set `no_cache` on the request.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::types::{ExecutionCacheInfo, ExecutionRequest, ExecutionResponse, ExecutionStatus};
+
+/// How long results are reused unless configured otherwise
+pub const DEFAULT_RESULT_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Most results kept at once; expired ones are dropped first
+const MAX_ENTRIES: usize = 1000;
+
+struct CachedResult {
+    stored_at: Instant,
+    response: ExecutionResponse,
+}
+
+/// Recent results of read-only tools
+pub struct ResultCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<u64, CachedResult>>,
+}
+
+impl ResultCache {
+    /// Create a cache keeping results for `ttl`; a zero TTL disables it
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether results are cached at all
+    pub fn is_enabled(&self) -> bool {
+        !self.ttl.is_zero()
+    }
+
+    /// Cache key for a request, or `None` if its result mustn't be reused
+    ///
+    /// Requests feeding standard input are never cached, and neither are
+    /// requests that ask to bypass the cache. Output shaping options aren't
+    /// part of the key: the unshaped result is cached and shaped per request.
+    pub fn key(&self, request: &ExecutionRequest, instance: &str) -> Option<u64> {
+        if !self.is_enabled() || request.no_cache || request.stdin.is_some() {
+            return None;
+        }
+        // Sorted so argument order doesn't matter
+        let args: BTreeMap<&String, String> =
+            request.args.iter().map(|(key, value)| (key, value.to_string())).collect();
+        let mut hasher = DefaultHasher::new();
+        (&request.skill, &request.tool, instance, args).hash(&mut hasher);
+        Some(hasher.finish())
+    }
+
+    /// A fresh cached result, with `cache` metadata describing its age
+    pub fn get(&self, key: u64) -> Option<ExecutionResponse> {
+        let entries = self.entries.lock().unwrap();
+        let cached = entries.get(&key)?;
+        let age = cached.stored_at.elapsed();
+        if age >= self.ttl {
+            return None;
+        }
+        let mut response = cached.response.clone();
+        response.cache = Some(ExecutionCacheInfo {
+            hit: true,
+            age_ms: age.as_millis() as u64,
+            ttl_ms: self.ttl.as_millis() as u64,
+        });
+        Some(response)
+    }
+
+    /// Keep a successful result; failures are always retried
+    pub fn insert(&self, key: u64, response: &ExecutionResponse) {
+        if response.status != ExecutionStatus::Success {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_ENTRIES {
+            entries.retain(|_, cached| cached.stored_at.elapsed() < self.ttl);
+            if entries.len() >= MAX_ENTRIES {
+                // Still full of fresh results: make room by dropping the oldest
+                if let Some(oldest) = entries
+                    .iter()
+                    .min_by_key(|(_, cached)| cached.stored_at)
+                    .map(|(key, _)| *key)
+                {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(
+            key,
+            CachedResult {
+                stored_at: Instant::now(),
+                response: response.clone(),
+            },
+        );
+    }
+
+    /// Metadata for a freshly executed result that was stored in the cache
+    pub fn miss_info(&self) -> ExecutionCacheInfo {
+        ExecutionCacheInfo {
+            hit: false,
+            age_ms: 0,
+            ttl_ms: self.ttl.as_millis() as u64,
+        }
+    }
+}
+
+impl Default for ResultCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_RESULT_CACHE_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(args: serde_json::Value) -> ExecutionRequest {
+        ExecutionRequest {
+            skill: "kubernetes".to_string(),
+            tool: "get".to_string(),
+            args: serde_json::from_value(args).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    fn response(status: ExecutionStatus) -> ExecutionResponse {
+        ExecutionResponse {
+            id: "exec-1".to_string(),
+            status,
+            output: "pod-a\npod-b".to_string(),
+            error: None,
+            duration_ms: 120,
+            metadata: HashMap::new(),
+            artifacts: vec![],
+            exit_code: Some(0),
+            stderr: String::new(),
+            resource_usage: None,
+            truncated: false,
+            cache: None,
+        }
+    }
+
+    #[test]
+    fn test_hit_within_ttl() {
+        let cache = ResultCache::new(Duration::from_secs(60));
+        let key = cache.key(&request(serde_json::json!({"resource": "pods", "namespace": "default"})), "default").unwrap();
+        assert!(cache.get(key).is_none());
+
+        cache.insert(key, &response(ExecutionStatus::Success));
+        let reordered = request(serde_json::json!({"namespace": "default", "resource": "pods"}));
+        let hit = cache.get(cache.key(&reordered, "default").unwrap()).unwrap();
+        assert_eq!(hit.id, "exec-1");
+        assert!(hit.cache.unwrap().hit);
+
+        // Other arguments, another instance, or failures don't share the entry
+        let other = request(serde_json::json!({"resource": "services"}));
+        assert!(cache.get(cache.key(&other, "default").unwrap()).is_none());
+        assert!(cache.get(cache.key(&reordered, "prod").unwrap()).is_none());
+        let failed = cache.key(&other, "default").unwrap();
+        cache.insert(failed, &response(ExecutionStatus::Failed));
+        assert!(cache.get(failed).is_none());
+    }
+
+    #[test]
+    fn test_bypass_and_expiry() {
+        let cache = ResultCache::new(Duration::from_millis(1));
+        let mut bypass = request(serde_json::json!({}));
+        bypass.no_cache = true;
+        assert!(cache.key(&bypass, "default").is_none());
+
+        let key = cache.key(&request(serde_json::json!({})), "default").unwrap();
+        cache.insert(key, &response(ExecutionStatus::Success));
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(cache.get(key).is_none());
+
+        let disabled = ResultCache::new(Duration::ZERO);
+        assert!(disabled.key(&request(serde_json::json!({})), "default").is_none());
+    }
+}
@@ -21,11 +21,31 @@ pub fn api_routes(state: Arc<AppState>) -> Router {
         .route("/skills", post(handlers::install_skill))
         .route("/skills/:name", get(handlers::get_skill))
         .route("/skills/:name", delete(handlers::uninstall_skill))
+        .route("/skills/:name/docs", get(handlers::get_skill_docs))
+        .route("/skills/:name/enhance", post(handlers::enhance_skill))
+        .route("/installs/:id", get(handlers::get_install))
+        .route("/installs/:id", delete(handlers::cancel_install))
         // Execution endpoints
         .route("/execute", post(handlers::execute_tool))
+        .route("/execute/stream", post(handlers::execute_tool_stream))
         .route("/executions", get(handlers::list_executions))
         .route("/executions", delete(handlers::clear_execution_history))
+        .route("/executions/search", get(handlers::search_executions))
         .route("/executions/:id", get(handlers::get_execution))
+        .route("/executions/:id/output", get(handlers::get_execution_output))
+        .route("/executions/:id/rerun", post(handlers::rerun_execution))
+        .route("/executions/:id/summarize", post(handlers::summarize_execution))
+        .route("/executions/:id/artifacts", get(handlers::list_execution_artifacts))
+        .route("/executions/:id/artifacts/*name", get(handlers::download_execution_artifact))
+        // Saved run endpoints
+        .route("/presets", get(handlers::list_presets))
+        .route("/presets", post(handlers::create_preset))
+        .route("/presets/:id", put(handlers::update_preset))
+        .route("/presets/:id", delete(handlers::delete_preset))
+        .route("/presets/:id/run", post(handlers::run_preset))
+        // Workflow endpoints
+        .route("/workflows", get(handlers::list_workflows))
+        .route("/workflows/:name/run", post(handlers::run_workflow))
         // Search endpoints
         .route("/search", post(handlers::semantic_search))
         .route("/search/config", get(handlers::get_search_config))
@@ -41,6 +61,11 @@ pub fn api_routes(state: Arc<AppState>) -> Router {
         .route("/analytics/top-queries", get(handlers::get_top_queries))
         .route("/analytics/feedback-stats", get(handlers::get_feedback_statistics))
         .route("/analytics/timeline", get(handlers::get_search_timeline))
+        .route("/analytics/example-quality", get(handlers::get_example_quality))
+        .route("/analytics/skills", get(handlers::get_skill_usage))
+        .route("/analytics/skills/:name", get(handlers::get_skill_analytics))
+        // Enhance progress endpoint
+        .route("/enhance/status", get(handlers::get_enhance_status))
         // Agent configuration endpoints
         .route("/agent/config", get(handlers::get_agent_config))
         .route("/agent/config", put(handlers::update_agent_config))
@@ -48,6 +73,7 @@ pub fn api_routes(state: Arc<AppState>) -> Router {
         .route("/config", get(handlers::get_config))
         .route("/config", put(handlers::update_config))
         // Manifest import/export endpoints
+        .route("/manifest", get(handlers::get_manifest))
         .route("/manifest/validate", post(handlers::validate_manifest))
         .route("/manifest/import", post(handlers::import_manifest))
         .route("/manifest/export", post(handlers::export_manifest))
@@ -55,6 +81,8 @@ pub fn api_routes(state: Arc<AppState>) -> Router {
         .route("/services", get(handlers::list_services))
         .route("/services/start", post(handlers::start_service))
         .route("/services/stop", post(handlers::stop_service))
+        // Auth status endpoint
+        .route("/auth/status", get(handlers::get_auth_status))
         // Health and version
         .route("/health", get(handlers::health_check))
         .route("/version", get(handlers::version_info))
@@ -66,6 +94,7 @@ pub fn api_routes(state: Arc<AppState>) -> Router {
 pub fn create_app(state: Arc<AppState>) -> Router {
     Router::new()
         .merge(SwaggerUi::new("/docs/api").url("/api/openapi.json", ApiDoc::openapi()))
+        .merge(grpc_routes(&state))
         .nest("/api", api_routes(state))
         .fallback(handlers::not_found)
 }
@@ -74,15 +103,28 @@ pub fn create_app(state: Arc<AppState>) -> Router {
 ///
 /// This router serves:
 /// - `/api/*` - REST API endpoints
+/// - `/skill.v1.SkillService/*` - gRPC service (`grpc` feature)
 /// - `/docs/api` - Swagger UI for API documentation
 /// - `/*` - Embedded web UI (SPA with client-side routing)
 pub fn create_app_with_ui(state: Arc<AppState>) -> Router {
     Router::new()
         .merge(SwaggerUi::new("/docs/api").url("/api/openapi.json", ApiDoc::openapi()))
+        .merge(grpc_routes(&state))
         .nest("/api", api_routes(state))
         .fallback(serve_static_handler)
 }
 
+/// gRPC service routes, when built with the `grpc` feature
+#[cfg(feature = "grpc")]
+fn grpc_routes(state: &Arc<AppState>) -> Router {
+    crate::grpc::routes(state.clone())
+}
+
+#[cfg(not(feature = "grpc"))]
+fn grpc_routes(_state: &Arc<AppState>) -> Router {
+    Router::new()
+}
+
 /// Handler for serving static assets from embedded files
 async fn serve_static_handler(
     uri: axum::http::Uri,
@@ -1,21 +1,38 @@
 //! HTTP Server implementation - REST API for skill invocation
 
-use anyhow::Result;
-use skill_runtime::{InstanceManager, LocalSkillLoader, SkillEngine, SkillManifest};
-use skill_runtime::search::SearchPipeline;
+use anyhow::{Context, Result};
+use skill_runtime::{AppSettings, ConfigStore, ExecutionMetrics, InstanceManager, LocalSkillLoader, RequirementProbe, ServiceManager, SkillChanged, SkillEngine, SkillManifest, SkillWatcher, ToolDocumentation};
+use skill_runtime::search::{IndexDocument, SearchPipeline};
+use skill_runtime::search_config::SearchConfig;
+use skill_runtime::vector_store::DocumentMetadata;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Instant;
+use std::future::IntoFuture;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use tracing::info;
 
+use crate::agent;
+use crate::alerts::{self, AnomalyAnalyzer};
 use crate::analytics::SearchAnalyticsDb;
-use crate::execution_history::ExecutionHistoryDb;
+use crate::execution_history::{default_db_path, ExecutionHistoryDb};
+use crate::indexing::{IndexStatus, IndexTask};
+use crate::installs::Installs;
+use crate::presets::PresetsDb;
+use crate::namespaces::{namespace_data_dir, namespaced, NamespaceConfig};
+use crate::maintenance::MaintenanceTask;
 use crate::routes::{create_app, create_app_with_ui};
-use crate::types::{ExecutionHistoryEntry, ServiceStatus, SkillServiceRequirement, SkillSummary};
+use crate::concurrency::{
+    ExecutionLimiter, DEFAULT_MAX_CONCURRENT_EXECUTIONS, DEFAULT_MAX_QUEUED_EXECUTIONS,
+};
+use crate::result_cache::{ResultCache, DEFAULT_RESULT_CACHE_TTL};
+use crate::settings::SettingsTask;
+use crate::shutdown::{ShutdownController, DEFAULT_DRAIN_TIMEOUT};
+use crate::tls::TlsConfig;
+use crate::types::{AgentConfig, AlertConfig, ExecutionHistoryEntry, ServiceStatus, SkillServiceRequirement, SkillSummary};
 
 /// HTTP Server configuration
 #[derive(Debug, Clone)]
@@ -32,6 +49,18 @@ pub struct HttpServerConfig {
     pub enable_web_ui: bool,
     /// Working directory for skills
     pub working_dir: Option<PathBuf>,
+    /// How long in-flight executions get to finish on shutdown
+    pub drain_timeout: Duration,
+    /// Serve HTTPS instead of plain HTTP
+    pub tls: Option<TlsConfig>,
+    /// Isolated namespaces served next to the default one
+    pub namespaces: Vec<NamespaceConfig>,
+    /// Tool executions allowed to run at once, across all namespaces
+    pub max_concurrent_executions: usize,
+    /// Executions allowed to wait for a slot before requests get `429`
+    pub max_queued_executions: usize,
+    /// How long results of read-only tools are reused (zero disables caching)
+    pub result_cache_ttl: Duration,
 }
 
 impl Default for HttpServerConfig {
@@ -43,20 +72,16 @@ impl Default for HttpServerConfig {
             enable_tracing: true,
             enable_web_ui: false,
             working_dir: None,
+            drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+            tls: None,
+            namespaces: Vec::new(),
+            max_concurrent_executions: DEFAULT_MAX_CONCURRENT_EXECUTIONS,
+            max_queued_executions: DEFAULT_MAX_QUEUED_EXECUTIONS,
+            result_cache_ttl: DEFAULT_RESULT_CACHE_TTL,
         }
     }
 }
 
-/// Tracked system service (like kubectl proxy)
-pub struct TrackedService {
-    /// Service name
-    pub name: String,
-    /// Process handle
-    pub process: Option<std::process::Child>,
-    /// Port being used
-    pub port: u16,
-}
-
 /// Shared application state
 pub struct AppState {
     /// Server start time for uptime tracking
@@ -79,22 +104,98 @@ pub struct AppState {
     pub local_loader: LocalSkillLoader,
     /// Working directory
     pub working_dir: PathBuf,
-    /// Tracked background services (kubectl proxy, etc.)
-    pub services: RwLock<HashMap<String, TrackedService>>,
+    /// Background services declared by skills (kubectl proxy, etc.)
+    pub services: ServiceManager,
     /// Search pipeline for semantic search
     pub search_pipeline: RwLock<Option<Arc<SearchPipeline>>>,
+    /// Search settings saved in `search.toml`, before environment overrides
+    pub search_config: RwLock<SearchConfig>,
+    /// Progress of the background search index build
+    pub index_status: RwLock<IndexStatus>,
     /// Analytics database for search history and feedback
     pub analytics_db: RwLock<Option<Arc<SearchAnalyticsDb>>>,
+    /// Saved runs shown on the dashboard and pinned to the sidebar
+    pub presets_db: RwLock<Option<Arc<PresetsDb>>>,
+    /// Host requirement checks for skill tools (results cached briefly)
+    pub requirements: RequirementProbe,
+    /// Tracks in-flight work so shutdown can drain it
+    pub shutdown: ShutdownController,
+    /// Namespace this state serves, `None` for the default one
+    pub namespace: Option<String>,
+    /// Failure-rate and latency alerting, editable through `/api/config`
+    pub alerts: RwLock<AlertConfig>,
+    /// Agent that enhances skills and summarizes executions, editable
+    /// through `/api/agent/config`
+    pub agent_config: RwLock<AgentConfig>,
+    /// Skill installs running in the background and recently finished
+    pub installs: Installs,
+    /// Limits concurrent tool executions; shared by all namespaces
+    pub executions: ExecutionLimiter,
+    /// Recent results of read-only tools
+    pub result_cache: ResultCache,
+    /// Execution counts and resource totals for this namespace
+    pub metrics: ExecutionMetrics,
+    /// Timeouts and history limits from `~/.skill-engine/config.toml`;
+    /// shared by all namespaces
+    pub settings: Arc<ConfigStore>,
 }
 
 impl AppState {
     /// Create new application state
     pub fn new(config: HttpServerConfig) -> Result<Self> {
+        let path = namespace_data_dir(None).join(skill_runtime::APP_CONFIG_FILE);
+        let settings = ConfigStore::open(&path).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring invalid settings: {:#}", e);
+            ConfigStore::with_settings(path, AppSettings::default())
+        });
+        let max_concurrent = settings
+            .get()
+            .max_concurrent_executions
+            .unwrap_or(config.max_concurrent_executions);
+        let executions = ExecutionLimiter::new(max_concurrent, config.max_queued_executions);
+        Self::build(
+            config,
+            None,
+            Arc::new(SkillEngine::new()?),
+            ShutdownController::new(),
+            executions,
+            Arc::new(settings),
+        )
+    }
+
+    /// State for a namespace, sharing this state's engine, shutdown and
+    /// execution limits
+    pub fn for_namespace(&self, namespace: &NamespaceConfig) -> Result<Self> {
+        let config = HttpServerConfig {
+            working_dir: Some(namespace.working_dir.clone()),
+            namespaces: Vec::new(),
+            ..self.config.clone()
+        };
+        Self::build(
+            config,
+            Some(namespace.name.clone()),
+            self.engine.clone(),
+            self.shutdown.clone(),
+            self.executions.clone(),
+            self.settings.clone(),
+        )
+    }
+
+    fn build(
+        config: HttpServerConfig,
+        namespace: Option<String>,
+        engine: Arc<SkillEngine>,
+        shutdown: ShutdownController,
+        executions: ExecutionLimiter,
+        settings: Arc<ConfigStore>,
+    ) -> Result<Self> {
         let working_dir = config.working_dir.clone()
             .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
 
-        let engine = Arc::new(SkillEngine::new()?);
-        let instance_manager = InstanceManager::new()?;
+        let instance_manager = match namespace.as_deref() {
+            Some(name) => InstanceManager::in_data_dir(namespace_data_dir(Some(name)), name)?,
+            None => InstanceManager::new()?,
+        };
         let local_loader = LocalSkillLoader::new()?;
 
         // Try to load manifest from working directory - find() searches for .skill-engine.toml
@@ -103,6 +204,8 @@ impl AppState {
 
         Ok(Self {
             started_at: Instant::now(),
+            result_cache: ResultCache::new(config.result_cache_ttl),
+            metrics: ExecutionMetrics::new(),
             skills: RwLock::new(HashMap::new()),
             execution_history: RwLock::new(Vec::new()),
             execution_history_db: RwLock::new(None),
@@ -112,17 +215,53 @@ impl AppState {
             instance_manager,
             local_loader,
             working_dir,
-            services: RwLock::new(HashMap::new()),
+            services: ServiceManager::new(),
             search_pipeline: RwLock::new(None),
+            search_config: RwLock::new(load_search_config(&search_config_path(namespace.as_deref()))),
+            index_status: RwLock::new(IndexStatus::default()),
             analytics_db: RwLock::new(None),
+            presets_db: RwLock::new(None),
+            requirements: RequirementProbe::default(),
+            shutdown,
+            alerts: RwLock::new(alerts::load_config(&alerts::config_path(namespace.as_deref()))),
+            agent_config: RwLock::new(agent::load_config(&agent::config_path(namespace.as_deref()))),
+            installs: Installs::default(),
+            namespace,
+            executions,
+            settings,
         })
     }
 
-    /// Initialize search pipeline with default configuration
-    pub async fn initialize_search_pipeline(&self) -> Result<()> {
-        use skill_runtime::search_config::SearchConfig;
+    /// Open the databases, load skills and start autostart services
+    pub async fn initialize(&self) -> Result<()> {
+        if let Err(e) = self.initialize_execution_history_db().await {
+            tracing::warn!("Failed to initialize execution history database: {}", e);
+        }
+        if let Err(e) = self.initialize_analytics_db().await {
+            tracing::warn!("Failed to initialize analytics database: {}", e);
+        }
+        if let Err(e) = self.initialize_presets_db().await {
+            tracing::warn!("Failed to initialize presets database: {}", e);
+        }
+        self.load_skills_from_manifest().await?;
+        self.services.start_autostart().await;
+        Ok(())
+    }
 
-        let config = SearchConfig::default();
+    /// Directories workflows are discovered in; a namespace only sees its own
+    pub fn workflow_dirs(&self) -> Vec<PathBuf> {
+        match self.namespace {
+            Some(_) => vec![self.working_dir.join("workflows")],
+            None => skill_runtime::workflow_dirs(),
+        }
+    }
+
+    /// Initialize search pipeline from the saved search configuration
+    ///
+    /// `SKILL_*` search environment variables apply, so `SKILL_INDEX_SNAPSHOT`
+    /// restores a pre-built index instead of embedding every tool.
+    pub async fn initialize_search_pipeline(&self) -> Result<()> {
+        let config = self.effective_search_config().await;
         let pipeline = SearchPipeline::from_config(config).await?;
 
         let mut search_pipeline = self.search_pipeline.write().await;
@@ -131,11 +270,63 @@ impl AppState {
         Ok(())
     }
 
+    /// The saved search configuration with environment overrides applied,
+    /// as the pipeline uses it
+    pub async fn effective_search_config(&self) -> SearchConfig {
+        self.search_config.read().await.clone().with_env_overrides()
+    }
+
+    /// Where this state's installed skills live: `registry` in its
+    /// namespace's data directory
+    pub fn registry_dir(&self) -> PathBuf {
+        namespace_data_dir(self.namespace.as_deref()).join("registry")
+    }
+
+        /// Where this state's search configuration is saved
+    pub fn search_config_path(&self) -> PathBuf {
+        search_config_path(self.namespace.as_deref())
+    }
+
+    /// Record a finished execution in history, unless history is disabled
+    ///
+    /// The in-memory cache keeps the newest `max_history_entries`; the
+    /// database write happens in the background.
+    pub async fn record_history(&self, entry: ExecutionHistoryEntry) {
+        let settings = self.settings.get();
+        if !settings.enable_history {
+            return;
+        }
+        {
+            let mut history = self.execution_history.write().await;
+            history.push(entry.clone());
+            trim_history(&mut history, settings.max_history_entries);
+        }
+
+        // Save to database (non-blocking)
+        if let Some(db) = self.execution_history_db.read().await.as_ref() {
+            let db = db.clone();
+            self.shutdown.spawn(async move {
+                if let Err(e) = db.add_execution(&entry).await {
+                    tracing::warn!("Failed to save execution to database: {}", e);
+                }
+            });
+        }
+    }
+
+    /// Apply changed settings to the execution limit and history cache
+    pub async fn apply_settings(&self, settings: &AppSettings) {
+        self.executions.set_max_concurrent(
+            settings
+                .max_concurrent_executions
+                .unwrap_or(self.config.max_concurrent_executions),
+        );
+        trim_history(&mut *self.execution_history.write().await, settings.max_history_entries);
+    }
+
     /// Initialize analytics database
     pub async fn initialize_analytics_db(&self) -> Result<()> {
-        let db_path = dirs::home_dir()
-            .map(|p| p.join(".skill-engine/analytics.db"))
-            .unwrap_or_else(|| PathBuf::from(".skill-engine/analytics.db"))
+        let db_path = namespace_data_dir(self.namespace.as_deref())
+            .join("analytics.db")
             .to_string_lossy()
             .to_string();
 
@@ -149,18 +340,37 @@ impl AppState {
         Ok(())
     }
 
-    /// Initialize execution history database
-    pub async fn initialize_execution_history_db(&self) -> Result<()> {
-        let db_path = dirs::home_dir()
-            .map(|p| p.join(".skill-engine/execution-history.db"))
-            .unwrap_or_else(|| PathBuf::from(".skill-engine/execution-history.db"))
+    /// Initialize the saved runs database
+    pub async fn initialize_presets_db(&self) -> Result<()> {
+        let db_path = namespace_data_dir(self.namespace.as_deref())
+            .join("presets.db")
             .to_string_lossy()
             .to_string();
 
+        let db = PresetsDb::new(&db_path).await?;
+
+        let mut presets_db = self.presets_db.write().await;
+        *presets_db = Some(Arc::new(db));
+
+        info!("Presets database initialized at: {}", db_path);
+
+        Ok(())
+    }
+
+    /// Initialize execution history database
+    pub async fn initialize_execution_history_db(&self) -> Result<()> {
+        let db_path = match &self.namespace {
+            Some(namespace) => namespace_data_dir(Some(namespace)).join("execution-history.db"),
+            None => default_db_path(),
+        };
+        let db_path = db_path.to_string_lossy().to_string();
+
         let db = ExecutionHistoryDb::new(&db_path).await?;
 
-        // Load recent history into memory cache (for fast access)
-        let recent_history = db.list_executions(1000, 0).await?;
+        // Load recent history into memory cache (for fast access), oldest first
+        let max_entries = self.settings.get().max_history_entries;
+        let mut recent_history = db.list_executions(max_entries, 0).await?;
+        recent_history.reverse();
         let mut history = self.execution_history.write().await;
         *history = recent_history;
 
@@ -175,9 +385,11 @@ impl AppState {
     /// Initialize skills from manifest - loads all tools at startup
     pub async fn load_skills_from_manifest(&self) -> Result<()> {
         // First, collect skill info from manifest
+        let registry_dir = self.registry_dir();
         let skill_infos: Vec<_> = {
             let manifest = self.manifest.read().await;
             if let Some(manifest) = manifest.as_ref() {
+                self.services.register_manifest(manifest);
                 manifest.skills.iter().map(|(name, skill_def)| {
                     let instances_count = if skill_def.instances.is_empty() { 1 } else { skill_def.instances.len() };
                     let runtime_str = match skill_def.runtime {
@@ -188,8 +400,7 @@ impl AppState {
                     let source_path = if skill_def.source.starts_with("./") || skill_def.source.starts_with('/') {
                         manifest.base_dir.join(&skill_def.source)
                     } else {
-                        let home = dirs::home_dir().unwrap_or_default();
-                        home.join(".skill-engine").join("registry").join(name)
+                        registry_dir.join(name)
                     };
                     (
                         name.clone(),
@@ -241,6 +452,7 @@ impl AppState {
                     description: s.description.clone(),
                     optional: s.optional,
                     default_port: s.default_port,
+                    start_on_demand: s.start_on_demand,
                     status: ServiceStatus {
                         name: s.name.clone(),
                         running: false,
@@ -248,6 +460,8 @@ impl AppState {
                         port: s.default_port,
                         url: None,
                         error: None,
+                        managed: false,
+                        restarts: 0,
                     },
                 }
             }).collect();
@@ -267,9 +481,13 @@ impl AppState {
             skills_to_insert.push((name, skill_summary));
         }
 
-        // Insert all skills
+        // Insert all skills, keeping usage stats of skills being reloaded
         let mut skills = self.skills.write().await;
-        for (name, summary) in skills_to_insert {
+        for (name, mut summary) in skills_to_insert {
+            if let Some(existing) = skills.get(&name) {
+                summary.last_used = existing.last_used;
+                summary.execution_count = existing.execution_count;
+            }
             skills.insert(name, summary);
         }
         info!("Loaded {} skills from manifest", skills.len());
@@ -277,6 +495,231 @@ impl AppState {
         Ok(())
     }
 
+    /// Drain in-flight work, then stop worker pools and services and close
+    /// the databases
+    pub async fn graceful_shutdown(&self) {
+        self.shutdown.trigger();
+        let deadline = self.shutdown.triggered_at().unwrap_or_else(Instant::now) + self.config.drain_timeout;
+
+        if !self.shutdown.drain(deadline.saturating_duration_since(Instant::now())).await {
+            tracing::warn!(
+                "{} executions still running after {}s; stopping anyway",
+                self.shutdown.in_flight(),
+                self.config.drain_timeout.as_secs()
+            );
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now()).max(Duration::from_secs(1));
+        for pool in self.shutdown.worker_pools() {
+            if let Err(e) = pool.shutdown(remaining).await {
+                tracing::warn!("Failed to shut down worker pool: {}", e);
+            }
+        }
+
+        self.close().await;
+        info!("Shutdown complete");
+    }
+
+    /// Stop this state's services and close its databases
+    pub async fn close(&self) {
+        self.services.stop_all().await;
+
+        if let Some(db) = self.execution_history_db.read().await.as_ref() {
+            db.close().await;
+        }
+        if let Some(db) = self.analytics_db.read().await.as_ref() {
+            db.close().await;
+        }
+        if let Some(db) = self.presets_db.read().await.as_ref() {
+            db.close().await;
+        }
+    }
+
+    /// Search index documents for the tools of every loaded manifest skill
+    pub async fn skill_index_documents(&self) -> Vec<IndexDocument> {
+        let skill_names: Vec<String> = self.skills.read().await.keys().cloned().collect();
+
+        let mut documents = Vec::new();
+        for skill_name in &skill_names {
+            if let Some((_, skill_documents)) = self.skill_tool_documents(skill_name).await {
+                documents.extend(skill_documents);
+            }
+        }
+        documents
+    }
+
+    /// Directory a skill's files live in: its local source when the manifest
+    /// declares one, else its registry install
+    pub async fn skill_dir(&self, skill_name: &str) -> PathBuf {
+        skill_dir_in(self.manifest.read().await.as_ref(), &self.registry_dir(), skill_name)
+    }
+
+    /// Tool documentation of a loaded manifest skill with its search index
+    /// documents, or `None` if the skill has no readable SKILL.md
+    pub async fn skill_tool_documents(
+        &self,
+        skill_name: &str,
+    ) -> Option<(Vec<ToolDocumentation>, Vec<IndexDocument>)> {
+        let skills = self.skills.read().await;
+        let manifest = self.manifest.read().await;
+        let skill_summary = skills.get(skill_name)?;
+        let manifest = manifest.as_ref()?;
+        let skill_def = manifest.skills.get(skill_name)?;
+        let source_path = skill_dir_in(Some(manifest), &self.registry_dir(), skill_name);
+
+        // Load tools from SKILL.md if available
+        let skill_md_path = skill_runtime::skill_md::find_skill_md(&source_path)?;
+        let skill_content = skill_runtime::skill_md::parse_skill_md_localized(
+            &skill_md_path,
+            skill_runtime::preferred_locale().as_deref(),
+        )
+        .ok()?;
+
+        let tools: Vec<ToolDocumentation> = skill_content.tool_docs.into_values().collect();
+        let mut documents = Vec::new();
+        for tool_doc in &tools {
+            let params_text = tool_doc.parameters.iter()
+                .map(|p| format!("{}: {}", p.name, p.description))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let content = format!(
+                "{} - {} | {} | Parameters: {}",
+                skill_name,
+                tool_doc.name,
+                tool_doc.description,
+                params_text
+            );
+
+            let doc = IndexDocument {
+                id: format!("{}:{}", skill_name, tool_doc.name),
+                content,
+                metadata: DocumentMetadata {
+                    skill_name: Some(skill_name.to_string()),
+                    tool_name: Some(tool_doc.name.clone()),
+                    instance_name: None,
+                    category: Some(skill_summary.runtime.clone()),
+                    runtime: Some(skill_summary.runtime.clone()),
+                    tags: skill_def.tags.clone(),
+                    custom: HashMap::new(),
+                },
+            };
+            documents.extend(doc.example_documents(tool_doc.examples.iter().map(|e| &e.code)));
+            documents.push(doc);
+        }
+        Some((tools, documents))
+    }
+
+    /// Apply a registry or manifest change reported by the [`SkillWatcher`]
+    ///
+    /// Manifest changes reload the manifest and its skills; registry changes
+    /// refresh the affected skill. Once skills have been indexed for search,
+    /// the index is updated so results match the current tools.
+    pub async fn apply_skill_change(&self, change: &SkillChanged) -> Result<()> {
+        let pipeline = self.search_pipeline.read().await.clone();
+        let indexed_before: Vec<String> = match pipeline {
+            Some(_) => self.skill_index_documents().await.into_iter().map(|doc| doc.id).collect(),
+            None => Vec::new(),
+        };
+
+        match change {
+            SkillChanged::Manifest(_) | SkillChanged::Rescan => self.reload_manifest().await?,
+            SkillChanged::Updated(name) | SkillChanged::Removed(name) => {
+                let in_manifest = self.manifest.read().await
+                    .as_ref()
+                    .is_some_and(|manifest| manifest.skills.contains_key(name));
+                if in_manifest {
+                    self.load_skills_from_manifest().await?;
+                } else if matches!(change, SkillChanged::Removed(_)) {
+                    self.skills.write().await.remove(name);
+                } else {
+                    self.refresh_registry_skill(name).await;
+                }
+            }
+        }
+        info!(change = ?change, "Reloaded skills");
+
+        if let Some(pipeline) = pipeline {
+            let documents = self.skill_index_documents().await;
+            let stale: Vec<String> = indexed_before
+                .into_iter()
+                .filter(|id| !documents.iter().any(|doc| &doc.id == id))
+                .collect();
+            pipeline.remove_documents(stale).await?;
+            let count = documents.len();
+            pipeline.index_documents(documents).await?;
+            info!("Re-indexed {} tools after skill change", count);
+        }
+        Ok(())
+    }
+
+    /// The manifest file edits are saved to: the most specific layer of the
+    /// loaded manifest, else `.skill-engine.toml` in the working directory
+    pub async fn manifest_path(&self) -> PathBuf {
+        self.manifest
+            .read()
+            .await
+            .as_ref()
+            .and_then(|manifest| manifest.layers.last().cloned())
+            .unwrap_or_else(|| self.working_dir.join(".skill-engine.toml"))
+    }
+
+    /// Write the manifest file and reload skills from it
+    pub async fn save_manifest(&self, content: &str) -> Result<PathBuf> {
+        let path = self.manifest_path().await;
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write manifest {}", path.display()))?;
+
+        if self.manifest.read().await.is_some() {
+            self.reload_manifest().await?;
+        } else {
+            *self.manifest.write().await = Some(SkillManifest::load(&path)?);
+            self.load_skills_from_manifest().await?;
+        }
+        info!("Saved manifest to {}", path.display());
+        Ok(path)
+    }
+
+    /// Re-read the manifest files, dropping skills that were removed from them
+    async fn reload_manifest(&self) -> Result<()> {
+        let mut manifest = self.manifest.write().await;
+        let Some(current) = manifest.as_ref() else {
+            return Ok(());
+        };
+        let reloaded = current.reload().context("Failed to reload manifest")?;
+        let removed: Vec<String> = current.skills.keys()
+            .filter(|name| !reloaded.skills.contains_key(*name))
+            .cloned()
+            .collect();
+        *manifest = Some(reloaded);
+        drop(manifest);
+
+        let mut skills = self.skills.write().await;
+        for name in &removed {
+            skills.remove(name);
+        }
+        drop(skills);
+
+        self.load_skills_from_manifest().await
+    }
+
+    /// Update the tools count of a tracked skill installed in the registry
+    async fn refresh_registry_skill(&self, name: &str) {
+        if !self.skills.read().await.contains_key(name) {
+            return;
+        }
+        let skill_path = self.registry_dir().join(name);
+        let tools_count = match skill_runtime::skill_md::find_skill_md(&skill_path) {
+            Some(path) => skill_runtime::skill_md::parse_skill_md(&path)
+                .map(|content| content.tool_docs.len())
+                .unwrap_or(0),
+            None => self.load_skill_tools_count(name, &skill_path).await,
+        };
+        if let Some(skill) = self.skills.write().await.get_mut(name) {
+            skill.tools_count = tools_count;
+        }
+    }
+
     /// Load tools count for a skill
     async fn load_skill_tools_count(&self, name: &str, source_path: &PathBuf) -> usize {
         match self.local_loader.load_skill(source_path, &self.engine).await {
@@ -288,7 +731,10 @@ impl AppState {
                     name.to_string(),
                     "default".to_string(),
                     instance_config,
-                ) {
+                )
+                .map(|executor| {
+                    executor.with_instance_dir(self.instance_manager.instance_dir(name, "default"))
+                }) {
                     Ok(executor) => {
                         match executor.get_tools().await {
                             Ok(tools) => {
@@ -315,6 +761,15 @@ impl AppState {
     }
 }
 
+/// Keep the newest `max_entries` of the history cache, oldest first
+pub(crate) fn trim_history(history: &mut Vec<ExecutionHistoryEntry>, max_entries: usize) {
+    if history.len() > max_entries {
+        let excess = history.len() - max_entries;
+        history.sort_by_key(|entry| entry.started_at);
+        history.drain(..excess);
+    }
+}
+
 /// HTTP Server that exposes skills via REST API
 pub struct HttpServer {
     config: HttpServerConfig,
@@ -335,29 +790,83 @@ impl HttpServer {
 
     /// Run the HTTP server
     pub async fn run(&self) -> Result<()> {
+        let server = self.start().await?;
+
+        let addr = format!("{}:{}", self.config.host, self.config.port);
+        let scheme = if self.config.tls.is_some() { "https" } else { "http" };
+        if self.config.enable_web_ui {
+            println!("Skill Engine Web UI available at {}://{}", scheme, addr);
+            println!("  Web interface: {}://{}/", scheme, addr);
+            println!("  API endpoints: {}://{}/api/...", scheme, addr);
+        } else {
+            println!("Skill Engine HTTP API listening on {}://{}", scheme, addr);
+            println!("  API endpoints: {}://{}/api/...", scheme, addr);
+            println!("  Health check:  {}://{}/api/health", scheme, addr);
+        }
+
+        server.serve().await
+    }
+
+    /// Load skills and start the background tasks, returning the application
+    /// to serve
+    ///
+    /// Callers can add routes of their own to [`RunningServer::app`] (e.g. MCP
+    /// at `/mcp`) before calling [`RunningServer::serve`].
+    pub async fn start(&self) -> Result<RunningServer> {
         // Create application state
         let state = Arc::new(AppState::new(self.config.clone())?);
 
-        // Initialize execution history database
-        if let Err(e) = state.initialize_execution_history_db().await {
-            tracing::warn!("Failed to initialize execution history database: {}", e);
+        // Open databases, load skills from manifest and start autostart services
+        state.initialize().await?;
+
+        // Each namespace gets its own state, isolated from the others
+        let mut namespaces = Vec::new();
+        for namespace in &self.config.namespaces {
+            let namespace_state = Arc::new(state.for_namespace(namespace)?);
+            namespace_state.initialize().await.with_context(|| {
+                format!("Failed to initialize namespace '{}'", namespace.name)
+            })?;
+            info!(namespace = %namespace.name, dir = %namespace.working_dir.display(), "Namespace loaded");
+            namespaces.push(namespace_state);
         }
 
-        // Initialize analytics database
-        if let Err(e) = state.initialize_analytics_db().await {
-            tracing::warn!("Failed to initialize analytics database: {}", e);
+        // Prune and compact history, and watch it for anomalies, in the
+        // background until shutdown
+        MaintenanceTask::new(state.clone()).spawn();
+        AnomalyAnalyzer::new(state.clone()).spawn();
+        for namespace_state in &namespaces {
+            MaintenanceTask::new(namespace_state.clone()).spawn();
+            AnomalyAnalyzer::new(namespace_state.clone()).spawn();
         }
 
-        // Load skills from manifest
-        state.load_skills_from_manifest().await?;
+        // Apply settings changed through /api/config or `skill setup`
+        let mut all_states = vec![state.clone()];
+        all_states.extend(namespaces.iter().cloned());
+        SettingsTask::new(all_states).spawn();
+
+        // Build the search index while the server starts answering requests
+        IndexTask::new(state.clone()).spawn();
+        for namespace_state in &namespaces {
+            IndexTask::new(namespace_state.clone()).spawn();
+        }
+
+        // Reload skills when the registry or manifest changes
+        let mut watchers = Vec::new();
+        watchers.extend(watch_skills(state.clone()).await);
+        for namespace_state in &namespaces {
+            watchers.extend(watch_skills(namespace_state.clone()).await);
+        }
 
         // Build the application router based on mode
         let mut app = if self.config.enable_web_ui {
-            create_app_with_ui(state)
+            create_app_with_ui(state.clone())
         } else {
-            create_app(state)
+            create_app(state.clone())
         };
 
+        // Serve namespaces under /ns/{namespace} and by header
+        app = namespaced(app, &namespaces);
+
         // Add CORS middleware if enabled
         if self.config.enable_cors {
             let cors = CorsLayer::new()
@@ -372,32 +881,133 @@ impl HttpServer {
             app = app.layer(TraceLayer::new_for_http());
         }
 
-        // Bind and serve
-        let addr = format!("{}:{}", self.config.host, self.config.port);
-        let listener = tokio::net::TcpListener::bind(&addr).await?;
+        Ok(RunningServer {
+            app,
+            state,
+            namespaces,
+            _watchers: watchers,
+        })
+    }
+}
+
+/// An initialized server, with its background tasks running
+pub struct RunningServer {
+    /// REST API (and web UI) routes
+    pub app: axum::Router,
+    /// State of the default namespace
+    pub state: Arc<AppState>,
+    namespaces: Vec<Arc<AppState>>,
+    /// Skill hot reload, stopped when dropped
+    _watchers: Vec<SkillWatcher>,
+}
+
+impl RunningServer {
+    /// Bind and serve [`RunningServer::app`] until SIGINT/SIGTERM or
+    /// [`ShutdownController::trigger`], then close the state
+    pub async fn serve(mut self) -> Result<()> {
+        let app = std::mem::take(&mut self.app);
+        let config = &self.state.config;
+        let addr = format!("{}:{}", config.host, config.port);
+        let listener = tokio::net::TcpListener::bind(&addr)
+            .await
+            .with_context(|| format!("Failed to bind to {}", addr))?;
 
         info!(
             address = %addr,
-            cors = self.config.enable_cors,
-            tracing = self.config.enable_tracing,
-            web_ui = self.config.enable_web_ui,
+            cors = config.enable_cors,
+            tracing = config.enable_tracing,
+            web_ui = config.enable_web_ui,
+            tls = config.tls.is_some(),
+            mtls = config.tls.as_ref().is_some_and(|tls| tls.client_ca.is_some()),
             "HTTP server starting"
         );
 
-        if self.config.enable_web_ui {
-            println!("Skill Engine Web UI available at http://{}", addr);
-            println!("  Web interface: http://{}/", addr);
-            println!("  API endpoints: http://{}/api/...", addr);
+        // Stop accepting connections on SIGINT/SIGTERM, giving open requests
+        // until the drain deadline
+        let state = &self.state;
+        state.shutdown.listen_for_signals();
+        let drain_timeout = config.drain_timeout;
+        if let Some(tls) = &config.tls {
+            crate::tls::serve(listener, app, tls, &state.shutdown, drain_timeout).await?;
         } else {
-            println!("Skill Engine HTTP API listening on http://{}", addr);
-            println!("  API endpoints: http://{}/api/...", addr);
-            println!("  Health check:  http://{}/api/health", addr);
+            let shutdown = state.shutdown.clone();
+            let serve = axum::serve(listener, app)
+                .with_graceful_shutdown(async move { shutdown.triggered().await })
+                .into_future();
+            let deadline = async {
+                state.shutdown.triggered().await;
+                tokio::time::sleep(drain_timeout).await;
+            };
+            tokio::select! {
+                result = serve => result?,
+                _ = deadline => tracing::warn!("Requests still open after {}s; closing them", drain_timeout.as_secs()),
+            }
         }
 
-        axum::serve(listener, app).await?;
-
+        self.close().await;
         Ok(())
     }
+
+    /// Drain in-flight executions and close every namespace's state
+    pub(crate) async fn close(&self) {
+        self.state.graceful_shutdown().await;
+        for namespace_state in &self.namespaces {
+            namespace_state.close().await;
+        }
+    }
+}
+
+/// A skill's directory: its local manifest source, else its install in `registry_dir`
+fn skill_dir_in(manifest: Option<&SkillManifest>, registry_dir: &Path, skill_name: &str) -> PathBuf {
+    let local_source = manifest.and_then(|manifest| {
+        let skill_def = manifest.skills.get(skill_name)?;
+        (skill_def.source.starts_with("./") || skill_def.source.starts_with('/'))
+            .then(|| manifest.base_dir.join(&skill_def.source))
+    });
+    local_source.unwrap_or_else(|| registry_dir.join(skill_name))
+}
+
+/// `search.toml` in a namespace's data directory; `~/.skill-engine/search.toml`
+/// for the default namespace, shared with the CLI
+fn search_config_path(namespace: Option<&str>) -> PathBuf {
+    namespace_data_dir(namespace).join("search.toml")
+}
+
+/// Load a saved search configuration, falling back to the default
+fn load_search_config(path: &std::path::Path) -> SearchConfig {
+    if !path.exists() {
+        return SearchConfig::default();
+    }
+    SearchConfig::from_toml_file(path).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring invalid search configuration {}: {:#}", path.display(), e);
+        SearchConfig::default()
+    })
+}
+
+/// Watch the registry and manifest files, applying changes to the state
+/// until the returned watcher is dropped
+async fn watch_skills(state: Arc<AppState>) -> Option<SkillWatcher> {
+    let manifest_paths = state.manifest.read().await
+        .as_ref()
+        .map(|manifest| manifest.layers.clone())
+        .unwrap_or_default();
+    let watcher = match SkillWatcher::new(&manifest_paths) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            tracing::warn!("Skill hot reload disabled: {}", e);
+            return None;
+        }
+    };
+
+    let mut changes = watcher.subscribe();
+    tokio::spawn(async move {
+        while let Some(change) = changes.recv().await {
+            if let Err(e) = state.apply_skill_change(&change).await {
+                tracing::warn!("Failed to reload skills: {}", e);
+            }
+        }
+    });
+    Some(watcher)
 }
 
 impl Default for HttpServer {
@@ -0,0 +1,63 @@
+//! Settings - applying `~/.skill-engine/config.toml` while the server runs
+//!
+//! The default timeout and history settings are read on every execution, so
+//! they take effect as soon as they change. [`SettingsTask`] handles the
+//! rest: on every change it resizes the execution limit and trims each
+//! namespace's history cache. Changes made through `PUT /api/config` arrive
+//! at once; edits by `skill setup` or by hand are picked up by re-reading
+//! the file every [`SETTINGS_RELOAD_INTERVAL`].
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+use crate::server::AppState;
+
+/// How often the settings file is checked for outside edits
+pub const SETTINGS_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Background task applying settings changes to every state sharing them
+pub struct SettingsTask {
+    states: Vec<Arc<AppState>>,
+}
+
+impl SettingsTask {
+    /// Create a task for states sharing one settings store; the first
+    /// state's store is watched
+    pub fn new(states: Vec<Arc<AppState>>) -> Self {
+        Self { states }
+    }
+
+    /// Apply every change until shutdown
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let Some(first) = self.states.first().cloned() else {
+                return;
+            };
+            let shutdown = first.shutdown.clone();
+            let mut changes = first.settings.subscribe();
+            let mut ticker = tokio::time::interval(SETTINGS_RELOAD_INTERVAL);
+            loop {
+                tokio::select! {
+                    changed = changes.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                        let settings = changes.borrow_and_update().clone();
+                        for state in &self.states {
+                            state.apply_settings(&settings).await;
+                        }
+                        info!(?settings, "Applied settings");
+                    }
+                    _ = ticker.tick() => {
+                        if let Err(e) = first.settings.reload() {
+                            warn!("Failed to reload settings: {:#}", e);
+                        }
+                    }
+                    _ = shutdown.triggered() => break,
+                }
+            }
+        })
+    }
+}
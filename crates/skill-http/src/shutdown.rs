@@ -0,0 +1,218 @@
+//! Graceful shutdown - trap signals, drain in-flight work, then clean up
+//!
+//! On SIGINT or SIGTERM the server stops accepting connections, new
+//! executions are refused, and in-flight executions and pending history
+//! writes get until the drain deadline to finish. Worker pools and managed
+//! services are stopped afterwards, and the databases are closed, so no
+//! child process (such as a `kubectl proxy`) outlives the server.
+
+use skill_runtime::WorkerPool;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{watch, Notify};
+use tracing::info;
+
+/// Default time in-flight executions get to finish after a shutdown signal
+pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Coordinates a graceful shutdown across handlers and background tasks
+#[derive(Clone)]
+pub struct ShutdownController {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    triggered: watch::Sender<Option<Instant>>,
+    in_flight: AtomicUsize,
+    idle: Notify,
+    worker_pools: Mutex<Vec<Arc<WorkerPool>>>,
+}
+
+impl Default for ShutdownController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShutdownController {
+    /// Create a controller that hasn't been triggered
+    pub fn new() -> Self {
+        let (triggered, _) = watch::channel(None);
+        Self {
+            inner: Arc::new(Inner {
+                triggered,
+                in_flight: AtomicUsize::new(0),
+                idle: Notify::new(),
+                worker_pools: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Trigger the shutdown when the process receives Ctrl+C, or SIGTERM on Unix
+    pub fn listen_for_signals(&self) {
+        let controller = self.clone();
+        tokio::spawn(async move {
+            signal().await;
+            info!("Shutdown signal received, draining in-flight executions");
+            controller.trigger();
+        });
+    }
+
+    /// Start shutting down
+    pub fn trigger(&self) {
+        self.inner.triggered.send_if_modified(|triggered| {
+            let first = triggered.is_none();
+            triggered.get_or_insert_with(Instant::now);
+            first
+        });
+    }
+
+    /// Whether shutdown has started
+    pub fn is_triggered(&self) -> bool {
+        self.triggered_at().is_some()
+    }
+
+    /// When shutdown started
+    pub fn triggered_at(&self) -> Option<Instant> {
+        *self.inner.triggered.borrow()
+    }
+
+    /// Resolves once shutdown has started
+    pub async fn triggered(&self) {
+        let mut triggered = self.inner.triggered.subscribe();
+        let _ = triggered.wait_for(Option::is_some).await;
+    }
+
+    /// Mark an execution as in flight until the guard is dropped
+    pub fn track(&self) -> InFlight {
+        self.inner.in_flight.fetch_add(1, Ordering::SeqCst);
+        InFlight {
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// Spawn a background task (like a history write) that shutdown waits for
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let guard = self.track();
+        tokio::spawn(async move {
+            task.await;
+            drop(guard);
+        });
+    }
+
+    /// Number of in-flight executions and background tasks
+    pub fn in_flight(&self) -> usize {
+        self.inner.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Wait until nothing is in flight, or the timeout passes
+    ///
+    /// Returns whether everything finished.
+    pub async fn drain(&self, timeout: Duration) -> bool {
+        let drained = async {
+            loop {
+                let idle = self.inner.idle.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        };
+        tokio::time::timeout(timeout, drained).await.is_ok()
+    }
+
+    /// Shut a worker pool down with the server
+    pub fn register_worker_pool(&self, pool: Arc<WorkerPool>) {
+        self.inner
+            .worker_pools
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(pool);
+    }
+
+    /// Worker pools to shut down
+    pub(crate) fn worker_pools(&self) -> Vec<Arc<WorkerPool>> {
+        self.inner
+            .worker_pools
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+/// An in-flight execution; shutdown waits for it until dropped
+pub struct InFlight {
+    inner: Arc<Inner>,
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if self.inner.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.inner.idle.notify_waiters();
+        }
+    }
+}
+
+/// Resolves on Ctrl+C, or SIGTERM on Unix
+async fn signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_waits_for_in_flight() {
+        let controller = ShutdownController::new();
+        let execution = controller.track();
+        controller.spawn(async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        });
+        assert_eq!(controller.in_flight(), 2);
+
+        assert!(!controller.drain(Duration::from_millis(100)).await);
+
+        drop(execution);
+        assert!(controller.drain(Duration::from_secs(1)).await);
+        assert_eq!(controller.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_trigger() {
+        let controller = ShutdownController::new();
+        assert!(!controller.is_triggered());
+
+        let waiter = tokio::spawn({
+            let controller = controller.clone();
+            async move { controller.triggered().await }
+        });
+        controller.trigger();
+        waiter.await.unwrap();
+        assert!(controller.is_triggered());
+    }
+}
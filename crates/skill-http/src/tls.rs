@@ -0,0 +1,296 @@
+//! TLS termination - serve HTTPS, optionally requiring client certificates
+//!
+//! Certificates come from PEM files or, with the `acme` feature, are
+//! obtained and renewed from Let's Encrypt over TLS-ALPN-01 (the server has
+//! to be reachable on port 443 under each domain). Setting a client CA
+//! turns on mTLS: clients must present a certificate that chains to it.
+
+use crate::shutdown::ShutdownController;
+use anyhow::{Context, Result};
+use axum::Router;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::{danger::ClientCertVerifier, WebPkiClientVerifier};
+use rustls::{RootCertStore, ServerConfig};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// TLS settings for the HTTP server
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    /// Where the server certificate comes from
+    pub certificate: CertificateSource,
+    /// CA bundle (PEM) client certificates must chain to; enables mTLS
+    pub client_ca: Option<PathBuf>,
+    /// Let clients connect without a certificate; ones that present a
+    /// certificate are still verified against `client_ca`
+    pub client_auth_optional: bool,
+}
+
+/// Where the server certificate comes from
+#[derive(Debug, Clone)]
+pub enum CertificateSource {
+    /// PEM certificate chain and private key
+    Files {
+        /// Certificate chain, leaf first
+        cert: PathBuf,
+        /// PKCS#8, PKCS#1 or SEC1 private key
+        key: PathBuf,
+    },
+    /// Obtained and renewed automatically from an ACME directory
+    Acme {
+        /// Domains the certificate covers
+        domains: Vec<String>,
+        /// Contact email for the ACME account
+        contact: Option<String>,
+        /// Where the account and certificates are cached between runs
+        cache_dir: PathBuf,
+        /// Use the Let's Encrypt staging directory
+        staging: bool,
+    },
+}
+
+impl TlsConfig {
+    /// Serve a certificate and key read from PEM files
+    pub fn from_files(cert: impl Into<PathBuf>, key: impl Into<PathBuf>) -> Self {
+        Self {
+            certificate: CertificateSource::Files {
+                cert: cert.into(),
+                key: key.into(),
+            },
+            client_ca: None,
+            client_auth_optional: false,
+        }
+    }
+
+    /// Obtain certificates for `domains` from Let's Encrypt, caching them
+    /// in `~/.skill-engine/acme`
+    pub fn acme(domains: Vec<String>, contact: Option<String>) -> Self {
+        let cache_dir = dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(".skill-engine")
+            .join("acme");
+        Self {
+            certificate: CertificateSource::Acme {
+                domains,
+                contact,
+                cache_dir,
+                staging: false,
+            },
+            client_ca: None,
+            client_auth_optional: false,
+        }
+    }
+
+    /// Require client certificates that chain to the CA bundle at `path`
+    pub fn with_client_ca(mut self, path: impl Into<PathBuf>) -> Self {
+        self.client_ca = Some(path.into());
+        self
+    }
+
+    /// rustls configuration for a certificate read from files
+    pub fn server_config(&self) -> Result<ServerConfig> {
+        let CertificateSource::Files { cert, key } = &self.certificate else {
+            anyhow::bail!("ACME certificates are resolved at runtime, not loaded from files");
+        };
+        let certs = load_certs(cert)?;
+        let key = load_key(key)?;
+        let mut config = self
+            .builder()?
+            .with_single_cert(certs, key)
+            .context("Invalid TLS certificate or key")?;
+        config.alpn_protocols = alpn_protocols();
+        Ok(config)
+    }
+
+    fn builder(
+        &self,
+    ) -> Result<rustls::ConfigBuilder<ServerConfig, rustls::server::WantsServerCert>> {
+        let builder = ServerConfig::builder_with_provider(crypto_provider())
+            .with_safe_default_protocol_versions()
+            .context("TLS provider doesn't support the default protocol versions")?;
+        Ok(match &self.client_ca {
+            Some(ca) => builder.with_client_cert_verifier(self.client_verifier(ca)?),
+            None => builder.with_no_client_auth(),
+        })
+    }
+
+    fn client_verifier(&self, ca: &Path) -> Result<Arc<dyn ClientCertVerifier>> {
+        let mut roots = RootCertStore::empty();
+        for cert in load_certs(ca)? {
+            roots
+                .add(cert)
+                .with_context(|| format!("Invalid client CA certificate in {}", ca.display()))?;
+        }
+        let builder = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), crypto_provider());
+        let builder = if self.client_auth_optional {
+            builder.allow_unauthenticated()
+        } else {
+            builder
+        };
+        builder
+            .build()
+            .with_context(|| format!("Invalid client CA {}", ca.display()))
+    }
+}
+
+/// Crypto backend for every TLS configuration; set explicitly because the
+/// dependency graph enables both of rustls's providers, so it can't pick one
+fn crypto_provider() -> Arc<rustls::crypto::CryptoProvider> {
+    Arc::new(rustls::crypto::aws_lc_rs::default_provider())
+}
+
+/// Serve `app` over TLS on `listener` until `shutdown` is triggered, giving
+/// open connections `drain_timeout` to finish
+pub async fn serve(
+    listener: tokio::net::TcpListener,
+    app: Router,
+    tls: &TlsConfig,
+    shutdown: &ShutdownController,
+    drain_timeout: Duration,
+) -> Result<()> {
+    let listener = listener.into_std()?;
+    let handle = axum_server::Handle::new();
+    tokio::spawn({
+        let handle = handle.clone();
+        let shutdown = shutdown.clone();
+        async move {
+            shutdown.triggered().await;
+            handle.graceful_shutdown(Some(drain_timeout));
+        }
+    });
+
+    match &tls.certificate {
+        CertificateSource::Files { .. } => {
+            let config =
+                axum_server::tls_rustls::RustlsConfig::from_config(Arc::new(tls.server_config()?));
+            axum_server::from_tcp_rustls(listener, config)
+                .handle(handle)
+                .serve(app.into_make_service())
+                .await?;
+        }
+        #[cfg(feature = "acme")]
+        CertificateSource::Acme {
+            domains,
+            contact,
+            cache_dir,
+            staging,
+        } => {
+            use futures::StreamExt;
+            use rustls_acme::{caches::DirCache, AcmeConfig};
+
+            // The ACME client builds its own rustls configuration from the
+            // process default; an error means one is installed already
+            let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+            let mut state = AcmeConfig::new(domains.clone())
+                .contact(contact.iter().map(|email| format!("mailto:{}", email)))
+                .cache(DirCache::new(cache_dir.clone()))
+                .directory_lets_encrypt(!staging)
+                .state();
+            let mut config = tls.builder()?.with_cert_resolver(state.resolver());
+            config.alpn_protocols = alpn_protocols();
+            config.alpn_protocols.push(b"acme-tls/1".to_vec());
+            let acceptor = state.axum_acceptor(Arc::new(config));
+
+            tokio::spawn(async move {
+                while let Some(event) = state.next().await {
+                    match event {
+                        Ok(event) => tracing::info!(?event, "ACME"),
+                        Err(e) => tracing::warn!(error = ?e, "ACME certificate request failed"),
+                    }
+                }
+            });
+
+            axum_server::from_tcp(listener)
+                .acceptor(acceptor)
+                .handle(handle)
+                .serve(app.into_make_service())
+                .await?;
+        }
+        #[cfg(not(feature = "acme"))]
+        CertificateSource::Acme { .. } => {
+            anyhow::bail!("ACME certificates need skill-http built with the `acme` feature")
+        }
+    }
+    Ok(())
+}
+
+fn alpn_protocols() -> Vec<Vec<u8>> {
+    vec![b"h2".to_vec(), b"http/1.1".to_vec()]
+}
+
+/// Certificates in a PEM file
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open certificate {}", path.display()))?;
+    let certs = rustls_pemfile::certs(&mut std::io::BufReader::new(file))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .with_context(|| format!("Invalid PEM in {}", path.display()))?;
+    if certs.is_empty() {
+        anyhow::bail!("No certificates found in {}", path.display());
+    }
+    Ok(certs)
+}
+
+/// First private key in a PEM file
+fn load_key(path: &Path) -> Result<PrivateKeyDer<'static>> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open private key {}", path.display()))?;
+    rustls_pemfile::private_key(&mut std::io::BufReader::new(file))
+        .with_context(|| format!("Invalid PEM in {}", path.display()))?
+        .with_context(|| format!("No private key found in {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_self_signed(dir: &Path) -> (PathBuf, PathBuf) {
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let cert = dir.join("cert.pem");
+        let key = dir.join("key.pem");
+        std::fs::write(&cert, certified.cert.pem()).unwrap();
+        std::fs::write(&key, certified.key_pair.serialize_pem()).unwrap();
+        (cert, key)
+    }
+
+    #[test]
+    fn test_server_config_from_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let (cert, key) = write_self_signed(dir.path());
+
+        let config = TlsConfig::from_files(&cert, &key).server_config().unwrap();
+        assert_eq!(config.alpn_protocols, alpn_protocols());
+
+        // The same certificate doubles as the client CA
+        TlsConfig::from_files(&cert, &key)
+            .with_client_ca(&cert)
+            .server_config()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_server_config_errors() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let (cert, _) = write_self_signed(dir.path());
+
+        let err = TlsConfig::from_files(&cert, dir.path().join("missing.pem"))
+            .server_config()
+            .unwrap_err();
+        assert!(err.to_string().contains("missing.pem"));
+
+        // A certificate file holds no private key
+        let err = TlsConfig::from_files(&cert, &cert)
+            .server_config()
+            .unwrap_err();
+        assert!(err.to_string().contains("No private key"));
+
+        let empty = dir.path().join("empty.pem");
+        std::fs::write(&empty, "").unwrap();
+        let err = TlsConfig::from_files(&cert, dir.path().join("key.pem"))
+            .with_client_ca(&empty)
+            .server_config()
+            .unwrap_err();
+        assert!(err.to_string().contains("No certificates"));
+    }
+}
@@ -5,6 +5,7 @@ mod common;
 use axum::http::StatusCode;
 use common::TestApp;
 use serde_json::json;
+use skill_http::types::{DiagnosticSeverity, ValidateManifestResponse};
 
 // ============================================================================
 // Get Config Tests
@@ -177,6 +178,26 @@ invalid toml [[[
     assert!(!response["errors"].as_array().unwrap().is_empty());
 }
 
+#[tokio::test]
+async fn test_validate_manifest_locates_schema_errors() {
+    let app = TestApp::new().await;
+    let body = json!({
+        "content": "version = \"1\"\n\n[skills.test]\nsource = \"github:test/skill\"\nruntime = \"python\"\n"
+    }).to_string();
+
+    let req = TestApp::post_request("/api/manifest/validate", &body);
+    let (status, resp_body) = app.request(req).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Valid TOML, but not a runtime the server knows
+    let response: ValidateManifestResponse = TestApp::parse_json(&resp_body);
+    assert!(!response.valid);
+    let diagnostic = &response.diagnostics[0];
+    assert_eq!(diagnostic.severity, DiagnosticSeverity::Error);
+    assert!(diagnostic.message.contains("python"), "{}", diagnostic.message);
+    assert_eq!(diagnostic.line, Some(5));
+}
+
 // ============================================================================
 // Manifest Import Tests
 // ============================================================================
@@ -205,6 +226,55 @@ runtime = "wasm"
     assert!(response["skills_count"].as_u64().unwrap() >= 1);
 }
 
+#[tokio::test]
+async fn test_import_manifest_install_queues_jobs_in_dependency_order() {
+    let app = TestApp::new().await;
+    let body = json!({
+        "content": r#"
+[skills.app]
+source = "/nonexistent/app.wasm"
+depends_on = ["base"]
+
+[skills.base]
+source = "/nonexistent/base.wasm"
+
+[skills.container]
+source = "docker:alpine"
+runtime = "docker"
+
+[skills.container.docker]
+image = "alpine:3.19"
+        "#,
+        "install": true,
+        "merge": true
+    }).to_string();
+
+    let req = TestApp::post_request("/api/manifest/import", &body);
+    let (status, resp_body) = app.request(req).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: serde_json::Value = TestApp::parse_json(&resp_body);
+    assert_eq!(response["success"], true);
+    // The Docker skill needs no install; the WASM skills are queued, dependency first
+    let installs = response["installs"].as_array().unwrap();
+    let sources: Vec<&str> = installs.iter().map(|job| job["source"].as_str().unwrap()).collect();
+    assert_eq!(sources, vec!["/nonexistent/base.wasm", "/nonexistent/app.wasm"]);
+    assert_eq!(response["installed_count"], 3);
+
+    // A dependency cycle is reported instead of installed
+    let body = json!({
+        "content": "[skills.a]\nsource = \"./a.wasm\"\ndepends_on = [\"b\"]\n\n[skills.b]\nsource = \"./b.wasm\"\ndepends_on = [\"a\"]\n",
+        "install": true,
+        "merge": true
+    }).to_string();
+    let req = TestApp::post_request("/api/manifest/import", &body);
+    let (status, resp_body) = app.request(req).await;
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = TestApp::parse_json(&resp_body);
+    assert_eq!(response["success"], false);
+    assert!(response["errors"][0].as_str().unwrap().contains("cycle"));
+}
+
 // ============================================================================
 // Manifest Export Tests
 // ============================================================================
@@ -223,6 +293,35 @@ async fn test_export_manifest_returns_toml() {
     }
 }
 
+// ============================================================================
+// Search Pipeline Test Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_pipeline_comparison_rejects_duplicate_config_names() {
+    let app = TestApp::new().await;
+    let body = json!({
+        "embedding_provider": "fastembed",
+        "embedding_model": "all-minilm",
+        "vector_backend": "inmemory",
+        "enable_hybrid": true,
+        "enable_reranking": false,
+        "query": "list pods",
+        "configs": [
+            { "name": "A", "enable_hybrid": true },
+            { "name": "A", "enable_hybrid": false }
+        ]
+    })
+    .to_string();
+
+    let req = TestApp::post_request("/api/search/test-pipeline", &body);
+    let (status, body) = app.request(req).await;
+
+    // Rejected before any pipeline is built
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(String::from_utf8_lossy(&body).contains("used twice"));
+}
+
 // ============================================================================
 // Error Handling Tests
 // ============================================================================
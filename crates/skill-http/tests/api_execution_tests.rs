@@ -54,6 +54,36 @@ async fn test_execute_tool_missing_skill() {
     assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
+#[tokio::test]
+async fn test_execute_stream_reports_errors_as_events() {
+    let app = TestApp::new().await;
+    let body = json!({
+        "skill": "nonexistent-skill",
+        "tool": "some-tool",
+        "args": {}
+    }).to_string();
+
+    let req = TestApp::post_request("/api/execute/stream", &body);
+    let (status, resp_body) = app.request(req).await;
+
+    // The stream opens before the execution runs, so failures arrive as events
+    assert_eq!(status, StatusCode::OK);
+    let events: Vec<ExecutionStreamEvent> = String::from_utf8(resp_body)
+        .unwrap()
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .map(|data| serde_json::from_str(data).unwrap())
+        .collect();
+    assert!(matches!(
+        events.first(),
+        Some(ExecutionStreamEvent::Started { skill, .. }) if skill == "nonexistent-skill"
+    ));
+    assert!(matches!(
+        events.last(),
+        Some(ExecutionStreamEvent::Error { status: 404, .. })
+    ));
+}
+
 #[tokio::test]
 async fn test_execute_tool_missing_tool() {
     let app = TestApp::new().await;
@@ -378,6 +408,63 @@ async fn test_execute_with_invalid_parameter_type() {
     );
 }
 
+#[tokio::test]
+async fn test_execute_rejects_args_not_matching_skill_md() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("SKILL.md"),
+        r#"---
+name: scaler
+description: Scales deployments
+---
+
+## Tools Provided
+
+### scale
+Scale a deployment.
+
+**Parameters**:
+- `deployment` (required, string): Deployment name
+- `replicas` (required, integer): Desired replica count
+- `output` (optional, enum: json|yaml): Output format
+"#,
+    )
+    .unwrap();
+
+    let app = TestApp::new().await;
+    let manifest = skill_runtime::SkillManifest::parse(&format!(
+        "[skills.scaler]\nsource = \"{}\"\n",
+        dir.path().display()
+    ))
+    .unwrap();
+    *app.state.manifest.write().await = Some(manifest);
+    app.state
+        .skills
+        .write()
+        .await
+        .insert("scaler".to_string(), common::mock_skill_summary("scaler"));
+
+    let body = json!({
+        "skill": "scaler",
+        "tool": "scale",
+        "args": { "replicas": "three", "output": "yml" }
+    }).to_string();
+    let (status, resp_body) = app.request(TestApp::post_request("/api/execute", &body)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let error: ApiError = TestApp::parse_json(&resp_body);
+    assert_eq!(error.code, "VALIDATION_ERROR");
+    let issues = error.details.unwrap()["issues"].clone();
+    let params: Vec<&str> = issues
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["param"].as_str().unwrap())
+        .collect();
+    assert_eq!(params, vec!["deployment", "replicas", "output"]);
+    assert_eq!(issues[2]["suggestion"], "did you mean 'yaml'?");
+}
+
 #[tokio::test]
 async fn test_execute_empty_args() {
     let app = TestApp::new().await;
@@ -418,3 +505,61 @@ async fn test_execute_null_args() {
         || status == StatusCode::NOT_FOUND
     );
 }
+
+// ============================================================================
+// Run Preset Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_presets_unavailable_without_database() {
+    let app = TestApp::new().await;
+
+    let (status, _) = app.request(TestApp::get_request("/api/presets")).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn test_presets_save_pin_and_delete() {
+    let app = TestApp::new().await;
+    let db = skill_http::presets::PresetsDb::new(":memory:").await.unwrap();
+    *app.state.presets_db.write().await = Some(std::sync::Arc::new(db));
+
+    let body = json!({ "name": " ", "skill": "test-skill", "tool": "echo" }).to_string();
+    let (status, _) = app.request(TestApp::post_request("/api/presets", &body)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let body = json!({
+        "name": "Say hello",
+        "skill": "test-skill",
+        "tool": "echo",
+        "args": { "message": "hello" }
+    }).to_string();
+    let (status, resp_body) = app.request(TestApp::post_request("/api/presets", &body)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let preset: RunPreset = TestApp::parse_json(&resp_body);
+    assert_eq!(preset.instance, "default");
+    assert!(!preset.pinned);
+
+    let path = format!("/api/presets/{}", preset.id);
+    let (status, resp_body) = app
+        .request(TestApp::put_request(&path, &json!({ "pinned": true }).to_string()))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let pinned: RunPreset = TestApp::parse_json(&resp_body);
+    assert!(pinned.pinned);
+    assert_eq!(pinned.args, preset.args);
+
+    let (status, resp_body) = app.request(TestApp::get_request("/api/presets")).await;
+    assert_eq!(status, StatusCode::OK);
+    let presets: Vec<RunPreset> = TestApp::parse_json(&resp_body);
+    assert_eq!(presets, vec![pinned]);
+
+    let (status, _) = app.request(TestApp::delete_request(&path)).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = app.request(TestApp::delete_request(&path)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = app
+        .request(TestApp::post_request(&format!("{}/run", path), "{}"))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
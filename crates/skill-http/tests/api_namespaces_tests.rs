@@ -0,0 +1,130 @@
+//! Integration tests for namespaced requests
+
+mod common;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use common::{mock_skill_summary, TestApp};
+use skill_http::namespaces::namespaced;
+use skill_http::types::*;
+use skill_http::{NamespaceConfig, NAMESPACE_HEADER};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+/// Default app with a `team-a` namespace holding one skill
+async fn namespaced_app(dir: &tempfile::TempDir) -> axum::Router {
+    let app = TestApp::new().await;
+    let namespace = NamespaceConfig::new("team-a", dir.path()).unwrap();
+    let team_a = Arc::new(app.state.for_namespace(&namespace).unwrap());
+    team_a
+        .skills
+        .write()
+        .await
+        .insert("team-skill".to_string(), mock_skill_summary("team-skill"));
+
+    namespaced(skill_http::routes::create_app(app.state.clone()), &[team_a])
+}
+
+async fn skill_names(app: axum::Router, request: Request<Body>) -> (StatusCode, Vec<String>) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    if status != StatusCode::OK {
+        return (status, Vec::new());
+    }
+    let skills: PaginatedResponse<SkillSummary> = TestApp::parse_json(&body);
+    let mut names: Vec<String> = skills.items.into_iter().map(|skill| skill.name).collect();
+    names.sort();
+    (status, names)
+}
+
+#[tokio::test]
+async fn test_default_namespace_is_isolated() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let app = namespaced_app(&dir).await;
+
+    let (status, names) = skill_names(app, TestApp::get_request("/api/skills")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!names.contains(&"team-skill".to_string()));
+    assert!(names.contains(&"test-skill".to_string()));
+}
+
+#[tokio::test]
+async fn test_namespace_by_path_prefix() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let app = namespaced_app(&dir).await;
+
+    let (status, names) = skill_names(app, TestApp::get_request("/ns/team-a/api/skills")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(names, vec!["team-skill".to_string()]);
+}
+
+#[tokio::test]
+async fn test_namespace_by_header() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let app = namespaced_app(&dir).await;
+
+    let request = Request::builder()
+        .uri("/api/skills")
+        .header(NAMESPACE_HEADER, "team-a")
+        .body(Body::empty())
+        .unwrap();
+    let (status, names) = skill_names(app, request).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(names, vec!["team-skill".to_string()]);
+}
+
+#[tokio::test]
+async fn test_unknown_namespace_returns_404() {
+    let dir = tempfile::TempDir::new().unwrap();
+
+    let request = Request::builder()
+        .uri("/api/skills")
+        .header(NAMESPACE_HEADER, "team-b")
+        .body(Body::empty())
+        .unwrap();
+    let response = namespaced_app(&dir).await.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let error: ApiError = TestApp::parse_json(&body);
+    assert_eq!(error.code, "NAMESPACE_NOT_FOUND");
+
+    let response = namespaced_app(&dir)
+        .await
+        .oneshot(TestApp::get_request("/ns/team-b/api/skills"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_namespace_installs_are_isolated() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let name = format!("isolation-{}", std::process::id());
+    let app = TestApp::new().await;
+    let namespace = NamespaceConfig::new(&name, dir.path()).unwrap();
+    let team = app.state.for_namespace(&namespace).unwrap();
+    let data_dir = skill_http::namespaces::namespace_data_dir(Some(&name));
+
+    let registry = team.registry_dir().join("github");
+    std::fs::create_dir_all(&registry).unwrap();
+    std::fs::write(registry.join("github.wasm"), b"\0asm").unwrap();
+    let mut config = skill_runtime::InstanceConfig::default();
+    config.set_config("org".to_string(), "team".to_string(), false);
+    team.instance_manager
+        .create_instance("github", &name, config, Default::default())
+        .unwrap();
+
+    let installed = skill_runtime::dependencies::is_installed_in(&team.registry_dir(), "github");
+    let loaded = team.instance_manager.load_instance("github", &name).unwrap();
+    let default_instances = app.state.instance_manager.list_instances("github").unwrap();
+    let default_loaded = app.state.instance_manager.load_instance("github", &name);
+    std::fs::remove_dir_all(&data_dir).unwrap();
+
+    assert!(team.registry_dir().starts_with(&data_dir));
+    assert!(installed);
+    assert_ne!(app.state.registry_dir(), team.registry_dir());
+    assert_eq!(loaded.get_config("org"), Some("team".to_string()));
+    assert!(!default_instances.contains(&name));
+    assert!(default_loaded.is_err());
+}
@@ -184,3 +184,28 @@ async fn test_service_status_has_name_and_running() {
         }
     }
 }
+
+#[tokio::test]
+async fn test_list_services_includes_builtin_kubectl_proxy() {
+    let app = TestApp::new().await;
+    let req = TestApp::get_request("/api/services");
+    let (status, body) = app.request(req).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let response: serde_json::Value = TestApp::parse_json(&body);
+    let services = response["services"].as_array().unwrap();
+    assert!(services.iter().any(|service| service["name"] == "kubectl-proxy"));
+}
+
+#[tokio::test]
+async fn test_stop_unknown_service_returns_400() {
+    let app = TestApp::new().await;
+    let body = json!({
+        "service": "no-such-service"
+    }).to_string();
+
+    let req = TestApp::post_request("/api/services/stop", &body);
+    let (status, _) = app.request(req).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
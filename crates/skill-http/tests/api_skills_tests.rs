@@ -148,35 +148,92 @@ async fn test_get_skill_with_service_requirements() {
     assert!(detail.summary.required_services.is_empty() || !detail.summary.required_services.is_empty());
 }
 
+// ============================================================================
+// Skill Docs Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_get_skill_docs_returns_skill_md_body() {
+    let app = TestApp::new().await;
+    let dir = tempfile::TempDir::new().unwrap();
+    let skill_dir = dir.path().join("docs-skill");
+    std::fs::create_dir_all(&skill_dir).unwrap();
+    std::fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: docs-skill\ndescription: Documented skill\n---\n\n# Docs Skill\n\n```bash\nskill run docs-skill:hello\n```\n",
+    )
+    .unwrap();
+    let readme_dir = dir.path().join("readme-skill");
+    std::fs::create_dir_all(&readme_dir).unwrap();
+    std::fs::write(readme_dir.join("README.md"), "# Readme Skill\n").unwrap();
+    let manifest_path = dir.path().join(".skill-engine.toml");
+    std::fs::write(
+        &manifest_path,
+        "[skills.docs-skill]\nsource = \"./docs-skill\"\n\n[skills.readme-skill]\nsource = \"./readme-skill\"\n",
+    )
+    .unwrap();
+    *app.state.manifest.write().await = Some(skill_runtime::SkillManifest::load(&manifest_path).unwrap());
+    {
+        let mut skills = app.state.skills.write().await;
+        for name in ["docs-skill", "readme-skill"] {
+            skills.insert(name.to_string(), common::mock_skill_summary(name));
+        }
+    }
+
+    let (status, body) = app.request(TestApp::get_request("/api/skills/docs-skill/docs")).await;
+    assert_eq!(status, StatusCode::OK);
+    let docs: SkillDocsResponse = TestApp::parse_json(&body);
+    assert_eq!(docs.file, "SKILL.md");
+    assert!(docs.content.contains("# Docs Skill"));
+    assert!(docs.content.contains("```bash"));
+    assert!(!docs.content.contains("description: Documented skill"));
+
+    let (status, body) = app.request(TestApp::get_request("/api/skills/readme-skill/docs")).await;
+    assert_eq!(status, StatusCode::OK);
+    let docs: SkillDocsResponse = TestApp::parse_json(&body);
+    assert_eq!(docs.file, "README.md");
+
+    let (status, _) = app.request(TestApp::get_request("/api/skills/nonexistent-skill/docs")).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
 // ============================================================================
 // Install Skill Tests
 // ============================================================================
 
 #[tokio::test]
-async fn test_install_skill_success() {
+async fn test_install_skill_runs_as_job() {
     let app = TestApp::new().await;
-    let body = r#"{
-        "source": "github:test/new-skill",
-        "name": "new-skill",
-        "version": null,
-        "instance_name": null,
-        "force": false
-    }"#;
+    let body = r#"{ "source": "./missing/new-skill.wasm", "force": false }"#;
     let req = TestApp::post_request("/api/skills", body);
     let (status, resp_body) = app.request(req).await;
 
-    // Installation might not be fully implemented, so accept either OK or error
-    assert!(
-        status == StatusCode::OK
-            || status == StatusCode::BAD_REQUEST
-            || status == StatusCode::INTERNAL_SERVER_ERROR
-    );
-
-    if status == StatusCode::OK {
-        let response: serde_json::Value = TestApp::parse_json(&resp_body);
-        // Check response structure if successful
-        assert!(response.is_object());
+    assert_eq!(status, StatusCode::ACCEPTED);
+    let mut job: InstallJob = TestApp::parse_json(&resp_body);
+    assert_eq!(job.source, "./missing/new-skill.wasm");
+
+    // The missing file fails the install in the background
+    for _ in 0..100 {
+        let req = TestApp::get_request(&format!("/api/installs/{}", job.id));
+        let (status, body) = app.request(req).await;
+        assert_eq!(status, StatusCode::OK);
+        job = TestApp::parse_json(&body);
+        if job.stage.is_finished() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
     }
+    assert_eq!(job.stage, InstallStage::Failed);
+    assert!(job.error.unwrap().contains("not found"));
+
+    // Finished installs can't be cancelled
+    let req = TestApp::delete_request(&format!("/api/installs/{}", job.id));
+    let (status, _) = app.request(req).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let req = TestApp::get_request("/api/installs/unknown");
+    let (status, _) = app.request(req).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
 #[tokio::test]
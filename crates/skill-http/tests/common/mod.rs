@@ -2,7 +2,7 @@
 
 #![allow(dead_code)] // Test helpers that may be used in future tests
 
-use axum::body::{Body, to_bytes};
+use axum::body::{to_bytes, Body};
 use axum::http::{Request, StatusCode};
 use chrono::Utc;
 use skill_http::{types::*, AppState, HttpServerConfig};
@@ -26,6 +26,12 @@ impl TestApp {
             enable_tracing: false,
             enable_web_ui: false,
             working_dir: Some(test_fixtures_dir()),
+            drain_timeout: skill_http::DEFAULT_DRAIN_TIMEOUT,
+            tls: None,
+            namespaces: Vec::new(),
+            max_concurrent_executions: skill_http::DEFAULT_MAX_CONCURRENT_EXECUTIONS,
+            max_queued_executions: skill_http::DEFAULT_MAX_QUEUED_EXECUTIONS,
+            result_cache_ttl: skill_http::DEFAULT_RESULT_CACHE_TTL,
         };
 
         let state = Arc::new(AppState::new(config).unwrap());
@@ -41,9 +47,7 @@ impl TestApp {
         let app = skill_http::routes::create_app(self.state.clone());
         let response = app.oneshot(req).await.unwrap();
         let status = response.status();
-        let body = to_bytes(response.into_body(), usize::MAX)
-            .await
-            .unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
         (status, body.to_vec())
     }
 
@@ -154,6 +158,7 @@ async fn load_test_skills(state: &Arc<AppState>) {
                 description: Some("Kubernetes API proxy".to_string()),
                 optional: false,
                 default_port: Some(8001),
+                start_on_demand: false,
                 status: ServiceStatus {
                     name: "kubectl-proxy".to_string(),
                     running: false,
@@ -161,6 +166,8 @@ async fn load_test_skills(state: &Arc<AppState>) {
                     port: None,
                     url: None,
                     error: None,
+                    managed: false,
+                    restarts: 0,
                 },
             }],
         },
@@ -192,5 +199,11 @@ pub fn mock_execution_response() -> ExecutionResponse {
         error: None,
         duration_ms: 42,
         metadata: HashMap::new(),
+        artifacts: vec![],
+        exit_code: None,
+        stderr: String::new(),
+        resource_usage: None,
+        truncated: false,
+        cache: None,
     }
 }
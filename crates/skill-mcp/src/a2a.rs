@@ -0,0 +1,671 @@
+//! A2A gateway - installed skills as an Agent2Agent (A2A) agent
+//!
+//! Serves an agent card at `/.well-known/agent.json` that advertises each
+//! installed skill as an agent skill, and a JSON-RPC endpoint at `/a2a`
+//! where `message/send` runs a tool and returns the finished task. The
+//! message names the tool either with a data part
+//! (`{"skill": "kubernetes", "tool": "get", "args": {"resource": "pods"}}`)
+//! or with a text part (`kubernetes:get resource=pods`, or
+//! `kubernetes@prod:get ...` for a specific instance).
+
+use anyhow::{bail, Context, Result};
+use axum::{extract::State, http::HeaderMap, routing::get, routing::post, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::server::{DiscoveredTool, McpServer};
+
+/// A2A protocol version the gateway implements
+pub const PROTOCOL_VERSION: &str = "0.2.5";
+
+/// Finished tasks kept for `tasks/get`
+const MAX_TASKS: usize = 1000;
+
+// JSON-RPC and A2A error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const TASK_NOT_FOUND: i64 = -32001;
+const TASK_NOT_CANCELABLE: i64 = -32002;
+const UNSUPPORTED_OPERATION: i64 = -32004;
+
+/// Agent card served at `/.well-known/agent.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentCard {
+    pub protocol_version: String,
+    pub name: String,
+    pub description: String,
+    /// JSON-RPC endpoint
+    pub url: String,
+    pub preferred_transport: String,
+    pub version: String,
+    pub capabilities: AgentCapabilities,
+    pub default_input_modes: Vec<String>,
+    pub default_output_modes: Vec<String>,
+    pub skills: Vec<AgentSkill>,
+}
+
+/// Optional protocol features the agent supports
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentCapabilities {
+    pub streaming: bool,
+    pub push_notifications: bool,
+    pub state_transition_history: bool,
+}
+
+/// One installed skill, advertised as a capability of the agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentSkill {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    /// One request per tool, in the text form `message/send` accepts
+    pub examples: Vec<String>,
+}
+
+/// A message between the client and the agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Message {
+    pub role: MessageRole,
+    pub parts: Vec<Part>,
+    pub message_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_id: Option<String>,
+    #[serde(default = "message_kind")]
+    pub kind: String,
+}
+
+fn message_kind() -> String {
+    "message".to_string()
+}
+
+/// Who sent a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageRole {
+    User,
+    Agent,
+}
+
+/// Content of a message or artifact
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Part {
+    Text { text: String },
+    Data { data: serde_json::Value },
+    File { file: serde_json::Value },
+}
+
+/// A tool execution requested over A2A
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Task {
+    pub id: String,
+    pub context_id: String,
+    pub status: TaskStatus,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<Artifact>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<Message>,
+    pub kind: String,
+}
+
+/// State of a task, with the agent's message for failed tasks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskStatus {
+    pub state: TaskState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<Message>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Lifecycle state of a task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TaskState {
+    Submitted,
+    Working,
+    InputRequired,
+    Completed,
+    Canceled,
+    Failed,
+    Rejected,
+    Unknown,
+}
+
+/// Output of a task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Artifact {
+    pub artifact_id: String,
+    pub name: String,
+    pub parts: Vec<Part>,
+}
+
+/// Tool a message asks for
+#[derive(Debug, Clone, PartialEq)]
+struct ToolCall {
+    skill: String,
+    instance: Option<String>,
+    tool: String,
+    args: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    #[serde(default)]
+    id: serde_json::Value,
+    method: String,
+    #[serde(default)]
+    params: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SendMessageParams {
+    message: Message,
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskIdParams {
+    id: String,
+}
+
+/// Serves installed skills over the A2A protocol
+#[derive(Clone)]
+pub struct A2aGateway {
+    server: McpServer,
+    public_url: Option<String>,
+    tls: bool,
+    tasks: Arc<RwLock<TaskStore>>,
+}
+
+#[derive(Default)]
+struct TaskStore {
+    tasks: HashMap<String, Task>,
+    order: VecDeque<String>,
+}
+
+impl A2aGateway {
+    /// Gateway running tools through `server`
+    pub fn new(server: McpServer) -> Self {
+        Self {
+            server,
+            public_url: None,
+            tls: false,
+            tasks: Arc::new(RwLock::new(TaskStore::default())),
+        }
+    }
+
+    /// URL clients reach the server at (like `https://agents.example.com`),
+    /// for the agent card; defaults to the request's `Host`
+    pub fn with_public_url(mut self, url: impl Into<String>) -> Self {
+        self.public_url = Some(url.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Advertise `https://` URLs, for a gateway served over TLS
+    pub fn with_tls(mut self, tls: bool) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    /// Router serving the agent card and the JSON-RPC endpoint
+    pub fn router(self) -> axum::Router {
+        axum::Router::new()
+            .route("/.well-known/agent.json", get(agent_card))
+            .route("/.well-known/agent-card.json", get(agent_card))
+            .route("/a2a", post(json_rpc))
+            .with_state(self)
+    }
+
+    /// Agent card advertising every installed skill
+    pub async fn agent_card(&self, base_url: &str) -> Result<AgentCard> {
+        let tools = self.server.discover_tools().await?;
+        Ok(AgentCard {
+            protocol_version: PROTOCOL_VERSION.to_string(),
+            name: "Skill Engine".to_string(),
+            description: "Runs the tools of installed skills. Send a data part \
+                          {\"skill\", \"tool\", \"args\"} or text like \
+                          `kubernetes:get resource=pods`."
+                .to_string(),
+            url: format!("{}/a2a", base_url),
+            preferred_transport: "JSONRPC".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            capabilities: AgentCapabilities::default(),
+            default_input_modes: vec!["text/plain".to_string(), "application/json".to_string()],
+            default_output_modes: vec!["text/plain".to_string()],
+            skills: agent_skills(&tools),
+        })
+    }
+
+    /// Run the tool a message asks for and record the finished task
+    pub async fn send_message(&self, message: Message) -> Result<Task> {
+        let call = tool_call(&message)?;
+        let tools = self.server.discover_tools().await?;
+        let tool = tools
+            .iter()
+            .find(|tool| {
+                tool.skill_name == call.skill
+                    && tool.tool_name == call.tool
+                    && call
+                        .instance
+                        .as_ref()
+                        .map_or(true, |instance| &tool.instance_name == instance)
+            })
+            .with_context(|| format!("Unknown tool '{}:{}'", call.skill, call.tool))?;
+        let instance = call
+            .instance
+            .clone()
+            .unwrap_or_else(|| tool.instance_name.clone());
+
+        tracing::info!(skill = %call.skill, tool = %call.tool, "A2A task");
+        let result = self
+            .server
+            .execute_skill_tool(&call.skill, &instance, &call.tool, call.args)
+            .await;
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let context_id = message
+            .context_id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let mut request = message;
+        request.task_id = Some(id.clone());
+        request.context_id = Some(context_id.clone());
+
+        let (state, artifacts, error) = match result {
+            Ok(result) if result.success => (
+                TaskState::Completed,
+                vec![Artifact {
+                    artifact_id: uuid::Uuid::new_v4().to_string(),
+                    name: "output".to_string(),
+                    parts: vec![Part::Text {
+                        text: result.output,
+                    }],
+                }],
+                None,
+            ),
+            Ok(result) => (
+                TaskState::Failed,
+                Vec::new(),
+                Some(
+                    result
+                        .error_message
+                        .unwrap_or_else(|| "Tool execution failed".to_string()),
+                ),
+            ),
+            Err(e) => (TaskState::Failed, Vec::new(), Some(format!("{:#}", e))),
+        };
+        let task = Task {
+            status: TaskStatus {
+                state,
+                message: error.map(|text| Message {
+                    role: MessageRole::Agent,
+                    parts: vec![Part::Text { text }],
+                    message_id: uuid::Uuid::new_v4().to_string(),
+                    task_id: Some(id.clone()),
+                    context_id: Some(context_id.clone()),
+                    kind: message_kind(),
+                }),
+                timestamp: Utc::now(),
+            },
+            id,
+            context_id,
+            artifacts,
+            history: vec![request],
+            kind: "task".to_string(),
+        };
+
+        let mut store = self.tasks.write().await;
+        store.order.push_back(task.id.clone());
+        store.tasks.insert(task.id.clone(), task.clone());
+        while store.order.len() > MAX_TASKS {
+            if let Some(oldest) = store.order.pop_front() {
+                store.tasks.remove(&oldest);
+            }
+        }
+        Ok(task)
+    }
+
+    /// A task run by this gateway
+    pub async fn get_task(&self, id: &str) -> Option<Task> {
+        self.tasks.read().await.tasks.get(id).cloned()
+    }
+
+    async fn handle(&self, request: JsonRpcRequest) -> Result<serde_json::Value, JsonRpcError> {
+        match request.method.as_str() {
+            "message/send" => {
+                let params: SendMessageParams = params(request.params)?;
+                let task = self
+                    .send_message(params.message)
+                    .await
+                    .map_err(|e| rpc_error(INVALID_PARAMS, format!("{:#}", e)))?;
+                Ok(serde_json::to_value(task).unwrap_or_default())
+            }
+            "tasks/get" => {
+                let params: TaskIdParams = params(request.params)?;
+                let task = self
+                    .get_task(&params.id)
+                    .await
+                    .ok_or_else(|| rpc_error(TASK_NOT_FOUND, "Task not found"))?;
+                Ok(serde_json::to_value(task).unwrap_or_default())
+            }
+            // Tasks finish before `message/send` returns
+            "tasks/cancel" => {
+                let params: TaskIdParams = params(request.params)?;
+                match self.get_task(&params.id).await {
+                    Some(_) => Err(rpc_error(TASK_NOT_CANCELABLE, "Task cannot be canceled")),
+                    None => Err(rpc_error(TASK_NOT_FOUND, "Task not found")),
+                }
+            }
+            "message/stream"
+            | "tasks/resubscribe"
+            | "tasks/pushNotificationConfig/set"
+            | "tasks/pushNotificationConfig/get" => Err(rpc_error(
+                UNSUPPORTED_OPERATION,
+                "This operation is not supported",
+            )),
+            method => Err(rpc_error(
+                METHOD_NOT_FOUND,
+                format!("Method not found: {}", method),
+            )),
+        }
+    }
+}
+
+async fn agent_card(
+    State(gateway): State<A2aGateway>,
+    headers: HeaderMap,
+) -> Result<Json<AgentCard>, (axum::http::StatusCode, String)> {
+    let base_url = match &gateway.public_url {
+        Some(url) => url.clone(),
+        None => {
+            let host = headers
+                .get(axum::http::header::HOST)
+                .and_then(|host| host.to_str().ok())
+                .unwrap_or("localhost");
+            let scheme = if gateway.tls { "https" } else { "http" };
+            format!("{}://{}", scheme, host)
+        }
+    };
+    gateway.agent_card(&base_url).await.map(Json).map_err(|e| {
+        (
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            format!("{:#}", e),
+        )
+    })
+}
+
+async fn json_rpc(State(gateway): State<A2aGateway>, body: String) -> Json<serde_json::Value> {
+    let request: JsonRpcRequest = match serde_json::from_str(&body) {
+        Ok(request) => request,
+        Err(e) => {
+            return Json(rpc_response(
+                serde_json::Value::Null,
+                Err(rpc_error(
+                    PARSE_ERROR,
+                    format!("Invalid JSON-RPC request: {}", e),
+                )),
+            ))
+        }
+    };
+    let id = request.id.clone();
+    Json(rpc_response(id, gateway.handle(request).await))
+}
+
+fn rpc_response(
+    id: serde_json::Value,
+    result: Result<serde_json::Value, JsonRpcError>,
+) -> serde_json::Value {
+    match result {
+        Ok(result) => serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => serde_json::json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    }
+}
+
+fn rpc_error(code: i64, message: impl Into<String>) -> JsonRpcError {
+    JsonRpcError {
+        code,
+        message: message.into(),
+    }
+}
+
+fn params<T: serde::de::DeserializeOwned>(params: serde_json::Value) -> Result<T, JsonRpcError> {
+    serde_json::from_value(params)
+        .map_err(|e| rpc_error(INVALID_PARAMS, format!("Invalid params: {}", e)))
+}
+
+/// One agent skill per installed skill, listing its tools
+fn agent_skills(tools: &[DiscoveredTool]) -> Vec<AgentSkill> {
+    let mut skills: BTreeMap<&str, Vec<&DiscoveredTool>> = BTreeMap::new();
+    for tool in tools {
+        skills
+            .entry(tool.skill_name.as_str())
+            .or_default()
+            .push(tool);
+    }
+
+    skills
+        .into_iter()
+        .map(|(skill, mut tools)| {
+            tools.sort_by(|a, b| a.tool_name.cmp(&b.tool_name));
+            tools.dedup_by(|a, b| a.tool_name == b.tool_name);
+            let description = tools
+                .iter()
+                .map(|tool| format!("{}: {}", tool.tool_name, tool.description))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let examples = tools
+                .iter()
+                .map(|tool| {
+                    let mut example = format!("{}:{}", skill, tool.tool_name);
+                    for param in tool.parameters.iter().filter(|param| param.required) {
+                        example.push_str(&format!(" {}=<{}>", param.name, param.name));
+                    }
+                    example
+                })
+                .collect();
+            let mut tags = vec![skill.to_string()];
+            if tools.iter().all(|tool| tool.hints.read_only == Some(true)) {
+                tags.push("read-only".to_string());
+            }
+            AgentSkill {
+                id: skill.to_string(),
+                name: skill.to_string(),
+                description,
+                tags,
+                examples,
+            }
+        })
+        .collect()
+}
+
+/// The tool a message asks for: from its first data part naming a skill
+/// and tool, otherwise from its text
+fn tool_call(message: &Message) -> Result<ToolCall> {
+    for part in &message.parts {
+        if let Part::Data { data } = part {
+            if let (Some(skill), Some(tool)) = (
+                data.get("skill").and_then(|v| v.as_str()),
+                data.get("tool").and_then(|v| v.as_str()),
+            ) {
+                let args = match data.get("args") {
+                    Some(serde_json::Value::Object(args)) => args.clone().into_iter().collect(),
+                    Some(serde_json::Value::Null) | None => HashMap::new(),
+                    Some(_) => bail!("'args' must be an object"),
+                };
+                return Ok(ToolCall {
+                    skill: skill.to_string(),
+                    instance: data
+                        .get("instance")
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string),
+                    tool: tool.to_string(),
+                    args,
+                });
+            }
+        }
+    }
+
+    let text: Vec<&str> = message
+        .parts
+        .iter()
+        .filter_map(|part| match part {
+            Part::Text { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    parse_command(&text.join(" "))
+}
+
+/// `skill[@instance]:tool key=value ...`, with quoted values
+fn parse_command(text: &str) -> Result<ToolCall> {
+    let mut words = split_words(text).into_iter();
+    let target = words
+        .next()
+        .context("Empty request: expected `skill:tool key=value ...` or a data part")?;
+    let (skill, tool) = target
+        .split_once(':')
+        .with_context(|| format!("Expected `skill:tool`, got '{}'", target))?;
+    let (skill, instance) = match skill.split_once('@') {
+        Some((skill, instance)) => (skill, Some(instance.to_string())),
+        None => (skill, None),
+    };
+    if skill.is_empty() || tool.is_empty() {
+        bail!("Expected `skill:tool`, got '{}'", target);
+    }
+
+    let mut args = HashMap::new();
+    for word in words {
+        let (key, value) = word
+            .split_once('=')
+            .with_context(|| format!("Expected `key=value`, got '{}'", word))?;
+        args.insert(
+            key.to_string(),
+            serde_json::Value::String(value.to_string()),
+        );
+    }
+    Ok(ToolCall {
+        skill: skill.to_string(),
+        instance,
+        tool: tool.to_string(),
+        args,
+    })
+}
+
+/// Split on whitespace, keeping single- or double-quoted runs together
+fn split_words(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    for c in text.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(parts: Vec<Part>) -> Message {
+        Message {
+            role: MessageRole::User,
+            parts,
+            message_id: "m1".to_string(),
+            task_id: None,
+            context_id: None,
+            kind: message_kind(),
+        }
+    }
+
+    #[test]
+    fn test_tool_call_from_text() {
+        let call = tool_call(&message(vec![Part::Text {
+            text: "kubernetes@prod:get resource=pods selector='app=web tier'".to_string(),
+        }]))
+        .unwrap();
+        assert_eq!(call.skill, "kubernetes");
+        assert_eq!(call.instance.as_deref(), Some("prod"));
+        assert_eq!(call.tool, "get");
+        assert_eq!(call.args["resource"], "pods");
+        assert_eq!(call.args["selector"], "app=web tier");
+
+        assert!(parse_command("kubernetes get").is_err());
+        assert!(parse_command("kubernetes:get pods").is_err());
+        assert!(parse_command("").is_err());
+    }
+
+    #[test]
+    fn test_tool_call_from_data() {
+        let call = tool_call(&message(vec![
+            Part::Text {
+                text: "list the pods".to_string(),
+            },
+            Part::Data {
+                data: serde_json::json!({
+                    "skill": "kubernetes",
+                    "tool": "get",
+                    "args": {"resource": "pods", "all_namespaces": true}
+                }),
+            },
+        ]))
+        .unwrap();
+        assert_eq!(call.skill, "kubernetes");
+        assert_eq!(call.instance, None);
+        assert_eq!(call.args["all_namespaces"], true);
+    }
+
+    #[test]
+    fn test_message_wire_format() {
+        let message: Message = serde_json::from_value(serde_json::json!({
+            "role": "user",
+            "parts": [{"kind": "text", "text": "kubernetes:get resource=pods"}],
+            "messageId": "m1",
+            "kind": "message"
+        }))
+        .unwrap();
+        assert_eq!(message.role, MessageRole::User);
+        assert_eq!(
+            serde_json::to_value(TaskState::InputRequired).unwrap(),
+            "input-required"
+        );
+    }
+}
@@ -0,0 +1,404 @@
+//! Flat tool mode - each skill tool as an MCP tool of its own
+//!
+//! Some MCP clients handle one tool per operation (`kubernetes__get`) much
+//! better than the generic `execute` dispatcher. The `[mcp]` section of the
+//! manifest picks the mode and can alias tools to shorter names; generated
+//! names that clash with a built-in tool, a workflow or another tool get a
+//! numeric suffix.
+
+use rmcp::{handler::server::router::tool::ToolRoute, model::Tool};
+use skill_runtime::McpConfig;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::instances::{instances_label, SkillInstance};
+use crate::server::{execute_request, DiscoveredTool, ExecuteSkillRequest, McpServer};
+
+/// MCP tool name length limit
+const MAX_TOOL_NAME: usize = 64;
+
+/// Output options flat tools accept next to the tool's own parameters
+const OUTPUT_OPTIONS: &[(&str, &str, &str)] = &[
+    (
+        "max_output",
+        "integer",
+        "Maximum characters in output to prevent context overflow",
+    ),
+    (
+        "grep",
+        "string",
+        "Regex pattern; only matching output lines are returned",
+    ),
+    ("head", "integer", "Return only first N lines of output"),
+    ("tail", "integer", "Return only last N lines of output"),
+    ("jq", "string", "jq filter applied to JSON output"),
+];
+
+/// One MCP tool route per skill tool
+///
+/// `reserved` holds the names already taken by built-in and workflow tools;
+/// `instance_hints` the instances of skills that have several.
+pub(crate) fn flat_tool_routes(
+    tools: &[DiscoveredTool],
+    config: &McpConfig,
+    reserved: &HashSet<String>,
+    instance_hints: &BTreeMap<String, Vec<SkillInstance>>,
+) -> Vec<ToolRoute<McpServer>> {
+    flat_tool_names(tools, &config.aliases, reserved)
+        .into_iter()
+        .map(|(name, tool)| {
+            let instances = instance_hints
+                .get(&tool.skill_name)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            flat_tool_route(name, tool, instances)
+        })
+        .collect()
+}
+
+/// Collision-free MCP tool names: the alias configured for `skill:tool`,
+/// otherwise `skill__tool`
+fn flat_tool_names<'a>(
+    tools: &'a [DiscoveredTool],
+    aliases: &HashMap<String, String>,
+    reserved: &HashSet<String>,
+) -> Vec<(String, &'a DiscoveredTool)> {
+    // A skill in both the registry and the manifest is discovered twice
+    let mut seen = HashSet::new();
+    let mut tools: Vec<&DiscoveredTool> = tools
+        .iter()
+        .filter(|tool| seen.insert((tool.skill_name.as_str(), tool.tool_name.as_str())))
+        .collect();
+    tools.sort_by(|a, b| (&a.skill_name, &a.tool_name).cmp(&(&b.skill_name, &b.tool_name)));
+
+    let mut taken = reserved.clone();
+    let mut names: Vec<Option<String>> = vec![None; tools.len()];
+
+    // Aliases claim their names first
+    for (i, tool) in tools.iter().enumerate() {
+        let key = format!("{}:{}", tool.skill_name, tool.tool_name);
+        let Some(alias) = aliases.get(&key) else {
+            continue;
+        };
+        if !crate::server::is_valid_tool_name(alias) || taken.contains(alias) {
+            tracing::warn!(
+                "MCP alias '{}' for '{}' is taken or not a valid tool name; using a generated name",
+                alias,
+                key
+            );
+            continue;
+        }
+        taken.insert(alias.clone());
+        names[i] = Some(alias.clone());
+    }
+
+    tools
+        .into_iter()
+        .zip(names)
+        .map(|(tool, name)| {
+            let name = name.unwrap_or_else(|| {
+                let base = sanitize(&format!("{}__{}", tool.skill_name, tool.tool_name));
+                let name = unique_name(&base, &taken);
+                taken.insert(name.clone());
+                name
+            });
+            (name, tool)
+        })
+        .collect()
+}
+
+/// Replace characters MCP tool names can't hold, and cut to the length limit
+pub(crate) fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_TOOL_NAME)
+        .collect()
+}
+
+/// `base`, or `base_2`, `base_3`... if taken, within the length limit
+pub(crate) fn unique_name(base: &str, taken: &HashSet<String>) -> String {
+    if !taken.contains(base) {
+        return base.to_string();
+    }
+    (2..)
+        .map(|n| {
+            let suffix = format!("_{}", n);
+            let keep = base.len().min(MAX_TOOL_NAME - suffix.len());
+            format!("{}{}", &base[..keep], suffix)
+        })
+        .find(|name| !taken.contains(name))
+        .expect("unbounded suffixes")
+}
+
+/// JSON schema type for a discovered parameter type
+fn schema_type(param_type: &str) -> Option<&'static str> {
+    match param_type.to_ascii_lowercase().as_str() {
+        "string" | "file" => Some("string"),
+        "integer" => Some("integer"),
+        "number" => Some("number"),
+        "boolean" => Some("boolean"),
+        "array" => Some("array"),
+        "object" | "json" => Some("object"),
+        _ => None,
+    }
+}
+
+/// A default from SKILL.md as a JSON value of the parameter's type
+fn typed_value(value: &str, param_type: &str) -> serde_json::Value {
+    let typed = match schema_type(param_type) {
+        Some("integer") => value.parse::<i64>().ok().map(Into::into),
+        Some("number") => value.parse::<f64>().ok().map(Into::into),
+        Some("boolean") => value.parse::<bool>().ok().map(Into::into),
+        Some("array") | Some("object") => serde_json::from_str(value).ok(),
+        _ => None,
+    };
+    typed.unwrap_or_else(|| value.into())
+}
+
+fn input_schema(
+    tool: &DiscoveredTool,
+    instances: &[SkillInstance],
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+    for param in &tool.parameters {
+        let mut property = serde_json::json!({ "description": param.description });
+        if let Some(kind) = schema_type(&param.param_type) {
+            property["type"] = kind.into();
+        }
+        if !param.allowed_values.is_empty() {
+            property["enum"] = param.allowed_values.clone().into();
+        }
+        if let Some(pattern) = &param.pattern {
+            property["pattern"] = pattern.clone().into();
+        }
+        if let Some(minimum) = param.minimum {
+            property["minimum"] = minimum.into();
+        }
+        if let Some(maximum) = param.maximum {
+            property["maximum"] = maximum.into();
+        }
+        if let Some(default) = &param.default {
+            property["default"] = typed_value(default, &param.param_type);
+        }
+        properties.insert(param.name.clone(), property);
+        if param.required {
+            required.push(param.name.clone());
+        }
+    }
+
+    // Options never shadow the tool's own parameters
+    if !properties.contains_key("instance") {
+        let instance = if instances.len() > 1 {
+            serde_json::json!({
+                "type": "string",
+                "enum": instances.iter().map(|i| i.name.as_str()).collect::<Vec<_>>(),
+                "description": format!("Instance to run: {}", instances_label(instances)),
+            })
+        } else {
+            serde_json::json!({
+                "type": "string",
+                "description": format!("Instance to run (default: '{}')", tool.instance_name),
+            })
+        };
+        properties.insert("instance".to_string(), instance);
+    }
+    for (name, kind, description) in OUTPUT_OPTIONS {
+        if !properties.contains_key(*name) {
+            properties.insert(
+                name.to_string(),
+                serde_json::json!({ "type": kind, "description": description }),
+            );
+        }
+    }
+
+    let mut schema = serde_json::Map::new();
+    schema.insert("type".to_string(), "object".into());
+    schema.insert("properties".to_string(), properties.into());
+    schema.insert("required".to_string(), required.into());
+    schema
+}
+
+/// Split a flat tool call into an `execute` request
+fn execute_request_for(
+    tool: &DiscoveredTool,
+    mut arguments: serde_json::Map<String, serde_json::Value>,
+) -> Result<ExecuteSkillRequest, serde_json::Error> {
+    let is_param = |name: &str| tool.parameters.iter().any(|param| param.name == name);
+
+    let mut request = serde_json::Map::new();
+    for name in std::iter::once("instance").chain(OUTPUT_OPTIONS.iter().map(|(name, _, _)| *name)) {
+        if !is_param(name) {
+            if let Some(value) = arguments.remove(name) {
+                request.insert(name.to_string(), value);
+            }
+        }
+    }
+    request
+        .entry("instance")
+        .or_insert_with(|| tool.instance_name.clone().into());
+    request.insert("skill".to_string(), tool.skill_name.clone().into());
+    request.insert("tool".to_string(), tool.tool_name.clone().into());
+    request.insert("args".to_string(), arguments.into());
+    serde_json::from_value(serde_json::Value::Object(request))
+}
+
+fn flat_tool_route(
+    name: String,
+    discovered: &DiscoveredTool,
+    instances: &[SkillInstance],
+) -> ToolRoute<McpServer> {
+    use futures::FutureExt;
+    use rmcp::handler::server::tool::ToolCallContext;
+    use rmcp::ErrorData as McpError;
+
+    let mut description = format!(
+        "{} (skill '{}', tool '{}')",
+        discovered.description, discovered.skill_name, discovered.tool_name
+    );
+    if !discovered.is_available() {
+        description.push_str(&format!(
+            "\n\nUnavailable on this host: {}",
+            discovered.unmet_label()
+        ));
+    }
+
+    let tool = Tool {
+        name: Cow::Owned(name),
+        title: None,
+        description: Some(Cow::Owned(description)),
+        input_schema: Arc::new(input_schema(discovered, instances)),
+        output_schema: None,
+        annotations: Some(discovered.annotations()),
+        icons: None,
+        meta: None,
+    };
+
+    let discovered = discovered.clone();
+    ToolRoute::new_dyn(tool, move |ctx: ToolCallContext<'_, McpServer>| {
+        let discovered = discovered.clone();
+        async move {
+            let arguments = ctx.arguments.clone().unwrap_or_default();
+            let request = execute_request_for(&discovered, arguments).map_err(|e| {
+                McpError::invalid_params(format!("Invalid parameters: {}", e), None)
+            })?;
+            execute_request(ctx.service, &ctx.request_context, request).await
+        }
+        .boxed()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::ToolParameter;
+
+    fn tool(skill: &str, name: &str, params: &[&str]) -> DiscoveredTool {
+        DiscoveredTool {
+            skill_name: skill.to_string(),
+            instance_name: "default".to_string(),
+            tool_name: name.to_string(),
+            description: format!("{} {}", skill, name),
+            parameters: params
+                .iter()
+                .map(|param| ToolParameter {
+                    name: param.to_string(),
+                    param_type: "String".to_string(),
+                    description: String::new(),
+                    required: true,
+                    allowed_values: Vec::new(),
+                    pattern: None,
+                    minimum: None,
+                    maximum: None,
+                    default: None,
+                })
+                .collect(),
+            source_path: None,
+            hints: Default::default(),
+            requires: Vec::new(),
+            unmet: Vec::new(),
+            examples: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_flat_tool_names() {
+        let tools = vec![
+            tool("kubernetes", "get", &[]),
+            tool("kubernetes", "get", &[]),
+            tool("kubernetes", "logs", &[]),
+            tool("aws", "s3.ls", &[]),
+            tool("aws", "s3_ls", &[]),
+            tool("team", "run", &[]),
+        ];
+        let aliases = HashMap::from([
+            ("kubernetes:logs".to_string(), "logs".to_string()),
+            ("team:run".to_string(), "execute".to_string()),
+        ]);
+        let reserved = HashSet::from(["execute".to_string()]);
+
+        let names: Vec<String> = flat_tool_names(&tools, &aliases, &reserved)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "aws__s3_ls",
+                "aws__s3_ls_2",
+                "kubernetes__get",
+                "logs",
+                "team__run"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unique_name_length() {
+        let base = "a".repeat(MAX_TOOL_NAME);
+        let taken = HashSet::from([base.clone()]);
+        let name = unique_name(&base, &taken);
+        assert_eq!(name.len(), MAX_TOOL_NAME);
+        assert!(name.ends_with("_2"));
+    }
+
+    #[test]
+    fn test_execute_request_for() {
+        // `head` is the tool's own parameter here, not the output option
+        let tool = tool("logs", "tail", &["file", "head"]);
+        let arguments = serde_json::json!({"file": "app.log", "head": 5, "grep": "ERROR"});
+        let request = execute_request_for(&tool, arguments.as_object().unwrap().clone()).unwrap();
+
+        assert_eq!(request.skill, "logs");
+        assert_eq!(request.instance, "default");
+        assert_eq!(request.grep.as_deref(), Some("ERROR"));
+        assert_eq!(request.head, None);
+        assert_eq!(request.args["head"], 5);
+        assert_eq!(request.args["file"], "app.log");
+
+        let schema = input_schema(&tool, &[]);
+        assert_eq!(schema["required"], serde_json::json!(["file", "head"]));
+        assert_eq!(schema["properties"]["file"]["type"], "string");
+        assert!(schema["properties"]["jq"].is_object());
+        assert_eq!(typed_value("3", "integer"), serde_json::json!(3));
+        assert_eq!(typed_value("yes", "boolean"), serde_json::json!("yes"));
+
+        let instances = ["dev", "prod"].map(|name| SkillInstance {
+            name: name.to_string(),
+            description: None,
+            is_default: name == "dev",
+        });
+        let schema = input_schema(&tool, &instances);
+        assert_eq!(
+            schema["properties"]["instance"]["enum"],
+            serde_json::json!(["dev", "prod"])
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! Instance routing hints - tell agents which instances a skill has
+//!
+//! A skill with several instances (`dev`, `staging`, `prod`) lists them in
+//! `list_skills`, in the `execute` and flat tool descriptions, and through
+//! argument completion, so agents pick the right one instead of always
+//! running the default.
+
+use skill_runtime::manifest::SkillDefinition;
+use std::collections::BTreeSet;
+
+/// Most values returned for one completion request
+pub(crate) const MAX_COMPLETIONS: usize = 100;
+
+/// An instance a skill can run as
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillInstance {
+    /// Instance name, passed as `instance`
+    pub name: String,
+    /// Description from the manifest
+    pub description: Option<String>,
+    /// Used when a call doesn't name an instance
+    pub is_default: bool,
+}
+
+/// Instances declared in the manifest and created under
+/// `~/.skill-engine/instances`, default first
+pub(crate) fn collect_instances(
+    definition: Option<&SkillDefinition>,
+    installed: Vec<String>,
+) -> Vec<SkillInstance> {
+    let default = definition
+        .map(|definition| definition.default_instance.as_str())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("default");
+
+    let mut names: BTreeSet<String> = installed.into_iter().collect();
+    if let Some(definition) = definition {
+        names.extend(definition.instances.keys().cloned());
+    }
+
+    let mut instances: Vec<SkillInstance> = names
+        .into_iter()
+        .map(|name| SkillInstance {
+            description: definition
+                .and_then(|definition| definition.instances.get(&name))
+                .and_then(|instance| instance.description.clone()),
+            is_default: name == default,
+            name,
+        })
+        .collect();
+    instances.sort_by_key(|instance| !instance.is_default);
+    instances
+}
+
+/// `dev (default), staging: Staging cluster, prod: Production cluster`
+pub(crate) fn instances_label(instances: &[SkillInstance]) -> String {
+    instances
+        .iter()
+        .map(|instance| {
+            let mut label = instance.name.clone();
+            if instance.is_default {
+                label.push_str(" (default)");
+            }
+            if let Some(description) = &instance.description {
+                label.push_str(&format!(": {}", description));
+            }
+            label
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Candidates starting with `prefix`, sorted and deduplicated, capped at
+/// [`MAX_COMPLETIONS`], with the number of matches before the cap
+pub(crate) fn complete_values(
+    candidates: impl IntoIterator<Item = String>,
+    prefix: &str,
+) -> (Vec<String>, usize) {
+    let matches: BTreeSet<String> = candidates
+        .into_iter()
+        .filter(|candidate| candidate.starts_with(prefix))
+        .collect();
+    let total = matches.len();
+    (matches.into_iter().take(MAX_COMPLETIONS).collect(), total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_instances() {
+        let manifest = skill_runtime::SkillManifest::parse(
+            r#"
+            [skills.kubernetes]
+            source = "./kubernetes"
+            default_instance = "dev"
+
+            [skills.kubernetes.instances.dev]
+            [skills.kubernetes.instances.prod]
+            description = "Production cluster"
+        "#,
+        )
+        .unwrap();
+
+        let instances = collect_instances(
+            manifest.get_skill("kubernetes"),
+            vec!["staging".to_string(), "prod".to_string()],
+        );
+        let names: Vec<&str> = instances.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["dev", "prod", "staging"]);
+        assert_eq!(
+            instances_label(&instances),
+            "dev (default), prod: Production cluster, staging"
+        );
+
+        let installed = collect_instances(None, vec!["default".to_string()]);
+        assert!(installed[0].is_default);
+    }
+
+    #[test]
+    fn test_complete_values() {
+        let (values, total) = complete_values(
+            ["prod", "dev", "prod-eu", "staging"].map(String::from),
+            "pro",
+        );
+        assert_eq!(values, vec!["prod", "prod-eu"]);
+        assert_eq!(total, 2);
+    }
+}
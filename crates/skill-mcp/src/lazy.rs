@@ -0,0 +1,394 @@
+//! Lazy tool discovery - skill tools are listed once a skill is loaded
+//!
+//! Installations with hundreds of tools fill an agent's context before it
+//! does anything. With `[mcp] discovery = "lazy"`, `tools/list` returns the
+//! built-in tools plus one meta-tool per skill; calling a skill's meta-tool
+//! or `load_skill_tools(skill)` adds the skill's tools (named as in flat
+//! mode) and tells the client the tool list changed.
+
+use rmcp::{
+    handler::server::{
+        router::tool::{ToolRoute, ToolRouter},
+        tool::ToolCallContext,
+        ServerHandler,
+    },
+    model::{
+        CallToolRequestParam, CallToolResult, CompleteRequestParam, CompleteResult, Content,
+        ListToolsResult, PaginatedRequestParam, ServerCapabilities, ServerInfo,
+        SetLevelRequestParam, Tool, ToolAnnotations,
+    },
+    service::{NotificationContext, RequestContext},
+    ErrorData as McpError, Peer, RoleServer,
+};
+use skill_runtime::McpConfig;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::flat::{flat_tool_routes, sanitize, unique_name};
+use crate::server::{DiscoveredTool, McpServer};
+
+/// Name of the tool that loads a skill's tools
+const LOAD_SKILL_TOOLS: &str = "load_skill_tools";
+
+/// Tool names a skill's meta-tool description lists before "and N more"
+const MAX_LISTED_TOOLS: usize = 12;
+
+/// MCP service listing skill tools only after their skill is loaded
+pub(crate) struct LazyRouter {
+    server: McpServer,
+    config: McpConfig,
+    /// Built-in and workflow tools, always listed
+    base: ToolRouter<McpServer>,
+    /// Names meta-tools and loaded tools can't take
+    reserved: HashSet<String>,
+    /// Tools of the loaded skills, by skill
+    loaded: RwLock<BTreeMap<String, ToolRouter<McpServer>>>,
+}
+
+impl LazyRouter {
+    pub(crate) fn new(
+        server: McpServer,
+        routes: Vec<ToolRoute<McpServer>>,
+        config: McpConfig,
+    ) -> Self {
+        let mut base = ToolRouter::new();
+        for route in routes {
+            base.add_route(route);
+        }
+        let mut reserved: HashSet<String> = base
+            .list_all()
+            .into_iter()
+            .map(|tool| tool.name.into_owned())
+            .collect();
+        reserved.insert(LOAD_SKILL_TOOLS.to_string());
+
+        Self {
+            server,
+            config,
+            base,
+            reserved,
+            loaded: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Add a skill's tools, replacing them if it was loaded before, and
+    /// notify the client
+    async fn load_skill(
+        &self,
+        skill: &str,
+        peer: &Peer<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let skills = self.server.tools_by_skill().await;
+        let Some(tools) = skills.get(skill) else {
+            let known: Vec<&str> = skills.keys().map(String::as_str).collect();
+            return Err(McpError::invalid_params(
+                format!("Unknown skill '{}'. Skills: {}", skill, known.join(", ")),
+                None,
+            ));
+        };
+
+        let names = {
+            let mut loaded = self.loaded.write().await;
+            let mut reserved = self.reserved.clone();
+            reserved.extend(meta_tool_names(&skills, &self.reserved).into_keys());
+            for (name, router) in loaded.iter() {
+                if name != skill {
+                    reserved.extend(
+                        router
+                            .list_all()
+                            .into_iter()
+                            .map(|tool| tool.name.into_owned()),
+                    );
+                }
+            }
+
+            let hints = self.server.instance_hints(tools);
+            let mut router = ToolRouter::new();
+            for route in flat_tool_routes(tools, &self.config, &reserved, &hints) {
+                router.add_route(route);
+            }
+            let names: Vec<String> = router
+                .list_all()
+                .into_iter()
+                .map(|tool| tool.name.into_owned())
+                .collect();
+            loaded.insert(skill.to_string(), router);
+            names
+        };
+
+        if let Err(e) = peer.notify_tool_list_changed().await {
+            tracing::warn!(error = %e, "Failed to notify the client of new tools");
+        }
+
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "Loaded {} tools from skill '{}': {}",
+            names.len(),
+            skill,
+            names.join(", ")
+        ))]))
+    }
+}
+
+impl ServerHandler for LazyRouter {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_tool_list_changed()
+                .enable_completions()
+                .enable_logging()
+                .build(),
+            ..self.server.get_info()
+        }
+    }
+
+    fn complete(
+        &self,
+        request: CompleteRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> impl std::future::Future<Output = Result<CompleteResult, McpError>> + Send + '_ {
+        self.server.complete(request, context)
+    }
+
+    fn on_initialized(
+        &self,
+        context: NotificationContext<RoleServer>,
+    ) -> impl std::future::Future<Output = ()> + Send + '_ {
+        self.server.on_initialized(context)
+    }
+
+    fn set_level(
+        &self,
+        request: SetLevelRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> impl std::future::Future<Output = Result<(), McpError>> + Send + '_ {
+        self.server.set_level(request, context)
+    }
+
+    fn on_roots_list_changed(
+        &self,
+        context: NotificationContext<RoleServer>,
+    ) -> impl std::future::Future<Output = ()> + Send + '_ {
+        self.server.on_roots_list_changed(context)
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        let skills = self.server.tools_by_skill().await;
+        let loaded = self.loaded.read().await;
+
+        let mut tools = self.base.list_all();
+        tools.push(load_skill_tools_tool(&skills));
+        for (name, skill) in meta_tool_names(&skills, &self.reserved) {
+            if !loaded.contains_key(&skill) {
+                tools.push(meta_tool(name, &skill, &skills[&skill]));
+            }
+        }
+        // Skills removed since they were loaded drop out of the list
+        for (skill, router) in loaded.iter() {
+            if skills.contains_key(skill) {
+                tools.extend(router.list_all());
+            }
+        }
+
+        Ok(ListToolsResult::with_all_items(tools))
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        if request.name == LOAD_SKILL_TOOLS {
+            let skill = request
+                .arguments
+                .as_ref()
+                .and_then(|arguments| arguments.get("skill"))
+                .and_then(|skill| skill.as_str())
+                .ok_or_else(|| McpError::invalid_params("Missing 'skill' argument", None))?
+                .to_string();
+            return self.load_skill(&skill, &context.peer).await;
+        }
+        if self.base.has_route(&request.name) {
+            return self
+                .base
+                .call(ToolCallContext::new(&self.server, request, context))
+                .await;
+        }
+
+        // Clone the router out so long executions don't hold the lock
+        let router = {
+            let loaded = self.loaded.read().await;
+            loaded
+                .values()
+                .find(|router| router.has_route(&request.name))
+                .cloned()
+        };
+        if let Some(router) = router {
+            return router
+                .call(ToolCallContext::new(&self.server, request, context))
+                .await;
+        }
+
+        let skills = self.server.tools_by_skill().await;
+        match meta_tool_names(&skills, &self.reserved).remove(request.name.as_ref()) {
+            Some(skill) => self.load_skill(&skill, &context.peer).await,
+            None => Err(McpError::invalid_params(
+                format!("Unknown tool '{}'", request.name),
+                None,
+            )),
+        }
+    }
+}
+
+/// Meta-tool name for each skill (its name, made valid and unique), to the skill
+fn meta_tool_names(
+    skills: &BTreeMap<String, Vec<DiscoveredTool>>,
+    reserved: &HashSet<String>,
+) -> BTreeMap<String, String> {
+    let mut taken = reserved.clone();
+    skills
+        .keys()
+        .map(|skill| {
+            let name = unique_name(&sanitize(skill), &taken);
+            taken.insert(name.clone());
+            (name, skill.clone())
+        })
+        .collect()
+}
+
+/// Distinct tool names of a skill discovered under several instances or sources
+fn tool_names(tools: &[DiscoveredTool]) -> Vec<&str> {
+    let mut names: Vec<&str> = tools.iter().map(|tool| tool.tool_name.as_str()).collect();
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// Skill meta-tool, which loads the skill's tools when called
+fn meta_tool(name: String, skill: &str, tools: &[DiscoveredTool]) -> Tool {
+    let names = tool_names(tools);
+    let mut listed = names
+        .iter()
+        .take(MAX_LISTED_TOOLS)
+        .copied()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if names.len() > MAX_LISTED_TOOLS {
+        listed.push_str(&format!(" and {} more", names.len() - MAX_LISTED_TOOLS));
+    }
+
+    let schema: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_value(serde_json::json!({ "type": "object", "properties": {} })).unwrap();
+
+    Tool {
+        name: Cow::Owned(name),
+        title: None,
+        description: Some(Cow::Owned(format!(
+            "Skill '{}' with {} tools: {}. Call this to load them as tools of their own.",
+            skill,
+            names.len(),
+            listed
+        ))),
+        input_schema: Arc::new(schema),
+        output_schema: None,
+        annotations: Some(load_annotations()),
+        icons: None,
+        meta: None,
+    }
+}
+
+/// The load_skill_tools tool, with the discovered skills as its choices
+fn load_skill_tools_tool(skills: &BTreeMap<String, Vec<DiscoveredTool>>) -> Tool {
+    let schema: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_value(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "skill": {
+                    "type": "string",
+                    "enum": skills.keys().collect::<Vec<_>>(),
+                    "description": "Skill whose tools to load"
+                }
+            },
+            "required": ["skill"]
+        }))
+        .unwrap();
+
+    Tool {
+        name: Cow::Borrowed(LOAD_SKILL_TOOLS),
+        title: None,
+        description: Some(Cow::Borrowed(
+            "Load a skill's tools so they are listed as tools of their own. \
+             Use find_tools first if you don't know which skill you need. \
+             Example: load_skill_tools(skill='kubernetes')",
+        )),
+        input_schema: Arc::new(schema),
+        output_schema: None,
+        annotations: Some(load_annotations()),
+        icons: None,
+        meta: None,
+    }
+}
+
+fn load_annotations() -> ToolAnnotations {
+    ToolAnnotations {
+        title: None,
+        read_only_hint: Some(true),
+        destructive_hint: Some(false),
+        idempotent_hint: Some(true),
+        open_world_hint: Some(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(skill: &str, name: &str) -> DiscoveredTool {
+        DiscoveredTool {
+            skill_name: skill.to_string(),
+            instance_name: "default".to_string(),
+            tool_name: name.to_string(),
+            description: String::new(),
+            parameters: Vec::new(),
+            source_path: None,
+            hints: Default::default(),
+            requires: Vec::new(),
+            unmet: Vec::new(),
+            examples: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_meta_tool_names() {
+        let skills = BTreeMap::from([
+            ("execute".to_string(), vec![tool("execute", "run")]),
+            ("my.skill".to_string(), vec![tool("my.skill", "get")]),
+            ("kubernetes".to_string(), vec![tool("kubernetes", "get")]),
+        ]);
+        let reserved = HashSet::from(["execute".to_string()]);
+
+        let names = meta_tool_names(&skills, &reserved);
+        assert_eq!(names["execute_2"], "execute");
+        assert_eq!(names["kubernetes"], "kubernetes");
+        assert_eq!(names["my_skill"], "my.skill");
+    }
+
+    #[test]
+    fn test_meta_tool_description() {
+        let mut tools: Vec<DiscoveredTool> = (0..15)
+            .map(|i| tool("big", &format!("t{:02}", i)))
+            .collect();
+        tools.push(tool("big", "t00"));
+
+        let meta = meta_tool("big".to_string(), "big", &tools);
+        let description = meta.description.unwrap();
+        assert!(description.contains("15 tools"));
+        assert!(description.contains("t11 and 3 more"));
+    }
+}
@@ -9,6 +9,9 @@
 //! - **SKILL.md Integration**: Uses SKILL.md documentation for rich tool descriptions
 //! - **Manifest Support**: Works with `.skill-engine.toml` declarative manifests
 //! - **Stdio Transport**: Uses stdio for direct Claude Code integration
+//! - **A2A Gateway**: Serves skills to Agent2Agent (A2A) clients (see [`a2a`])
+//! - **Client Logging**: Sends what tool calls trace to the client as MCP log
+//!   messages (see [`McpLogLayer`])
 //!
 //! # Usage
 //!
@@ -22,9 +25,19 @@
 //! server.run().await?;
 //! ```
 
+pub mod a2a;
+mod flat;
+mod instances;
+mod lazy;
+mod logging;
+mod progress;
+mod roots;
 pub mod server;
 
-pub use server::{DiscoveredTool, McpServer, ToolParameter};
+pub use a2a::A2aGateway;
+pub use instances::SkillInstance;
+pub use logging::McpLogLayer;
+pub use server::{DiscoveredTool, McpServer, McpSessions, ToolParameter};
 
 use anyhow::Result;
 use skill_runtime::SkillManifest;
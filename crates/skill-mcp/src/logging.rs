@@ -0,0 +1,183 @@
+//! Tracing events of a tool call, forwarded as MCP log messages
+//!
+//! Executor logs, the native command line and truncation notices are traced
+//! like anything else and end up on stderr, where an agent never sees them.
+//! [`McpLogLayer`] also sends the events a tool call emits to the client
+//! that made it, as `notifications/message`, at or above the level the
+//! client picked with `logging/setLevel` (`info` until it does).
+
+use rmcp::{
+    model::{LoggingLevel, LoggingMessageNotificationParam},
+    Peer, RoleServer,
+};
+use std::future::Future;
+use tokio::sync::mpsc;
+use tracing::field::{Field, Visit};
+use tracing_subscriber::layer::{Context, Layer};
+
+tokio::task_local! {
+    /// Where events of the tool call being run on this task go
+    static SINK: LogSink;
+}
+
+struct LogSink {
+    level: LoggingLevel,
+    sender: mpsc::UnboundedSender<LoggingMessageNotificationParam>,
+}
+
+/// Tracing layer forwarding the events of MCP tool calls to their client
+///
+/// Add it to the subscriber of a process serving MCP:
+///
+/// ```ignore
+/// use tracing_subscriber::prelude::*;
+///
+/// tracing_subscriber::registry()
+///     .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+///     .with(skill_mcp::McpLogLayer)
+///     .init();
+/// ```
+///
+/// Only events from Skill Engine's own crates are forwarded.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct McpLogLayer;
+
+impl<S: tracing::Subscriber> Layer<S> for McpLogLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if !metadata.target().starts_with("skill") {
+            return;
+        }
+        let _ = SINK.try_with(|sink| {
+            let level = logging_level(metadata.level());
+            if severity(&level) < severity(&sink.level) {
+                return;
+            }
+            let mut fields = FieldVisitor::default();
+            event.record(&mut fields);
+            let _ = sink.sender.send(LoggingMessageNotificationParam {
+                level,
+                logger: Some(metadata.target().to_string()),
+                data: serde_json::Value::Object(fields.0),
+            });
+        });
+    }
+}
+
+/// Run a tool call, sending the events it emits at `level` or above to the
+/// client before returning
+pub(crate) async fn forward<F: Future>(
+    peer: &Peer<RoleServer>,
+    level: LoggingLevel,
+    call: F,
+) -> F::Output {
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let peer = peer.clone();
+    let forwarder = tokio::spawn(async move {
+        while let Some(message) = receiver.recv().await {
+            if peer.notify_logging_message(message).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let output = SINK.scope(LogSink { level, sender }, call).await;
+    // The sender went with the scope; wait for the messages still queued
+    let _ = forwarder.await;
+    output
+}
+
+fn logging_level(level: &tracing::Level) -> LoggingLevel {
+    match *level {
+        tracing::Level::ERROR => LoggingLevel::Error,
+        tracing::Level::WARN => LoggingLevel::Warning,
+        tracing::Level::INFO => LoggingLevel::Info,
+        _ => LoggingLevel::Debug,
+    }
+}
+
+/// Rank of a level, lowest for `debug`
+fn severity(level: &LoggingLevel) -> u8 {
+    match level {
+        LoggingLevel::Debug => 0,
+        LoggingLevel::Info => 1,
+        LoggingLevel::Notice => 2,
+        LoggingLevel::Warning => 3,
+        LoggingLevel::Error => 4,
+        LoggingLevel::Critical => 5,
+        LoggingLevel::Alert => 6,
+        LoggingLevel::Emergency => 7,
+    }
+}
+
+/// An event's fields as JSON, with its text under `message`
+#[derive(Default)]
+struct FieldVisitor(serde_json::Map<String, serde_json::Value>);
+
+impl Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value).into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity_order() {
+        assert_eq!(logging_level(&tracing::Level::TRACE), LoggingLevel::Debug);
+        assert!(
+            severity(&logging_level(&tracing::Level::WARN)) >= severity(&LoggingLevel::Warning)
+        );
+        assert!(severity(&LoggingLevel::Info) < severity(&LoggingLevel::Notice));
+    }
+
+    #[test]
+    fn test_field_visitor() {
+        use tracing_subscriber::prelude::*;
+
+        #[derive(Default)]
+        struct Capture(std::sync::Arc<std::sync::Mutex<Vec<serde_json::Value>>>);
+
+        impl<S: tracing::Subscriber> Layer<S> for Capture {
+            fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+                let mut fields = FieldVisitor::default();
+                event.record(&mut fields);
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(serde_json::Value::Object(fields.0));
+            }
+        }
+
+        let capture = Capture::default();
+        let events = capture.0.clone();
+        let subscriber = tracing_subscriber::registry().with(capture);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(command = %"kubectl get pods", exit_code = 0u64, "Executing native command");
+        });
+
+        let events = events.lock().unwrap();
+        assert_eq!(events[0]["message"], "Executing native command");
+        assert_eq!(events[0]["command"], "kubectl get pods");
+        assert_eq!(events[0]["exit_code"], 0);
+    }
+}
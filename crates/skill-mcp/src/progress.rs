@@ -0,0 +1,174 @@
+//! Progress notifications for long tool calls
+//!
+//! When a `tools/call` request carries a `progressToken`, the server sends
+//! `notifications/progress` every few seconds until the call returns, with
+//! the elapsed time, the bytes of output native commands have written so far
+//! and, for workflows, the step being run. Clients can show it and apply
+//! their own timeouts knowing the call is still alive.
+
+use async_trait::async_trait;
+use rmcp::{
+    model::{ProgressNotificationParam, ProgressToken},
+    service::RequestContext,
+    Peer, RoleServer,
+};
+use skill_runtime::{format_bytes, RunObserver, RunStatus, StdinSource, WorkflowRun};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+/// Time between progress notifications
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(2);
+
+tokio::task_local! {
+    /// Progress of the call being run on this task
+    static CURRENT: Arc<ProgressState>;
+}
+
+/// What a running call has done so far
+#[derive(Default)]
+struct ProgressState {
+    output_bytes: AtomicU64,
+    /// Workflow step being run, as `step 2/5: deploy (kubernetes:apply)`
+    step: Mutex<Option<String>>,
+}
+
+impl ProgressState {
+    fn message(&self, elapsed: Duration) -> String {
+        let mut message = format!(
+            "{}s elapsed, {} output",
+            elapsed.as_secs(),
+            format_bytes(self.output_bytes.load(Ordering::Relaxed))
+        );
+        if let Some(step) = self.step.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            message.push_str(", ");
+            message.push_str(step);
+        }
+        message
+    }
+}
+
+/// Progress of one tool call, reported while it's alive if the client asked
+pub(crate) struct Progress {
+    state: Arc<ProgressState>,
+    ticker: Option<JoinHandle<()>>,
+}
+
+impl Progress {
+    /// Start reporting to the client if the request has a progress token
+    pub(crate) fn start(context: &RequestContext<RoleServer>) -> Self {
+        let state = Arc::new(ProgressState::default());
+        let ticker = context
+            .meta
+            .get_progress_token()
+            .map(|token| tokio::spawn(report(context.peer.clone(), token, state.clone())));
+        Self { state, ticker }
+    }
+
+    /// Run a call, counting the output of native commands it runs
+    pub(crate) async fn track<F: Future>(&self, call: F) -> F::Output {
+        CURRENT.scope(self.state.clone(), call).await
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if let Some(ticker) = &self.ticker {
+            ticker.abort();
+        }
+    }
+}
+
+#[async_trait]
+impl RunObserver for Progress {
+    async fn on_update(&self, run: &WorkflowRun) {
+        let step = run
+            .steps
+            .iter()
+            .position(|step| step.status == RunStatus::Running)
+            .map(|index| {
+                let step = &run.steps[index];
+                format!(
+                    "step {}/{}: {} ({})",
+                    index + 1,
+                    run.steps.len(),
+                    step.id,
+                    step.tool
+                )
+            });
+        *self.state.step.lock().unwrap_or_else(|e| e.into_inner()) = step;
+    }
+}
+
+/// Notify the client every [`PROGRESS_INTERVAL`] until aborted or the
+/// client goes away
+async fn report(peer: Peer<RoleServer>, token: ProgressToken, state: Arc<ProgressState>) {
+    let started = Instant::now();
+    let mut interval = tokio::time::interval(PROGRESS_INTERVAL);
+    // The first tick completes immediately
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        let elapsed = started.elapsed();
+        let notification = ProgressNotificationParam {
+            progress_token: token.clone(),
+            // Must grow with every notification; the total isn't known
+            progress: elapsed.as_secs_f64(),
+            total: None,
+            message: Some(state.message(elapsed)),
+        };
+        if peer.notify_progress(notification).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Run a command to completion like `Command::output`, counting its output
+/// towards the progress of the current call
+///
+/// The command is killed once stdout or stderr goes over `max_output` bytes.
+pub(crate) async fn output(
+    command: &mut tokio::process::Command,
+    max_output: Option<u64>,
+) -> std::io::Result<std::process::Output> {
+    // Never hand the child the server's stdin, which carries MCP messages
+    StdinSource::Null
+        .output_streaming(command, max_output, |_, bytes| {
+            let _ = CURRENT
+                .try_with(|state| state.output_bytes.fetch_add(bytes.len() as u64, Ordering::Relaxed));
+        })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_output_counts_bytes() {
+        let state = Arc::new(ProgressState::default());
+        let mut command = tokio::process::Command::new("sh");
+        command
+            .args(["-c", "printf hello; printf oops >&2"])
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped());
+
+        let output = CURRENT
+            .scope(state.clone(), output(&mut command, None))
+            .await
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"hello");
+        assert_eq!(output.stderr, b"oops");
+        assert_eq!(state.output_bytes.load(Ordering::Relaxed), 9);
+
+        *state.step.lock().unwrap() = Some("step 1/2: build (docker:build)".to_string());
+        assert_eq!(
+            state.message(Duration::from_secs(3)),
+            "3s elapsed, 9 B output, step 1/2: build (docker:build)"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! Client workspace roots, handed to skills
+//!
+//! MCP clients with the `roots` capability tell the server which directories
+//! the user is working in. When the manifest opts in with `[mcp] roots`, the
+//! server asks for them once the session is initialized and again whenever
+//! the client says they changed. WASM skills get each root mounted at
+//! `/roots/<name>` (writable only with `roots = "read-write"`) with the mount
+//! points in `MCP_ROOTS`; native commands run in the first root.
+
+use rmcp::{model::Root, Peer, RoleServer};
+use skill_context::Mount;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Guest directory the roots are mounted under
+const ROOTS_DIR: &str = "/roots";
+
+/// A client root on the local filesystem
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct WorkspaceRoot {
+    /// Unique name, used for the mount point
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl WorkspaceRoot {
+    /// Mount point inside WASM skills
+    pub fn target(&self) -> String {
+        format!("{}/{}", ROOTS_DIR, self.name)
+    }
+}
+
+/// The client's roots, or none when it doesn't declare the capability or
+/// the request fails
+pub(crate) async fn fetch(peer: &Peer<RoleServer>) -> Vec<WorkspaceRoot> {
+    let supports_roots = peer
+        .peer_info()
+        .is_some_and(|info| info.capabilities.roots.is_some());
+    if !supports_roots {
+        return Vec::new();
+    }
+
+    match peer.list_roots().await {
+        Ok(result) => workspace_roots(&result.roots),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to list the client's roots");
+            Vec::new()
+        }
+    }
+}
+
+/// Local directories among the roots, with unique mount names
+///
+/// Roots that aren't `file://` URIs are skipped.
+fn workspace_roots(roots: &[Root]) -> Vec<WorkspaceRoot> {
+    let mut taken = HashSet::new();
+    roots
+        .iter()
+        .filter_map(|root| {
+            let path = url::Url::parse(&root.uri).ok()?.to_file_path().ok()?;
+            let base = root
+                .name
+                .clone()
+                .or_else(|| {
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                })
+                .map(|name| sanitize(&name))
+                .filter(|name| !name.is_empty() && name != "." && name != "..")
+                .unwrap_or_else(|| "root".to_string());
+            let name = (1..)
+                .map(|n| {
+                    if n == 1 {
+                        base.clone()
+                    } else {
+                        format!("{}-{}", base, n)
+                    }
+                })
+                .find(|name| !taken.contains(name))
+                .expect("unbounded suffixes");
+            taken.insert(name.clone());
+            Some(WorkspaceRoot { name, path })
+        })
+        .collect()
+}
+
+/// Keep letters, digits, `-`, `_` and `.`; replace the rest with `_`
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Directory mounts for the roots
+pub(crate) fn mounts(roots: &[WorkspaceRoot], read_only: bool) -> Vec<Mount> {
+    roots
+        .iter()
+        .map(|root| {
+            let mount = Mount::directory(
+                format!("root-{}", root.name),
+                root.path.to_string_lossy().to_string(),
+                root.target(),
+            );
+            if read_only {
+                mount.as_read_only()
+            } else {
+                mount
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root(uri: &str, name: Option<&str>) -> Root {
+        Root {
+            uri: uri.to_string(),
+            name: name.map(str::to_string),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_workspace_roots() {
+        let roots = workspace_roots(&[
+            root("file:///home/dev/infra", None),
+            root("file:///home/dev/other/infra", None),
+            root("file:///home/dev/My%20App", Some("My App")),
+            root("https://example.com/repo", Some("remote")),
+            root("file:///", None),
+        ]);
+
+        let names: Vec<&str> = roots.iter().map(|root| root.name.as_str()).collect();
+        assert_eq!(names, vec!["infra", "infra-2", "My_App", "root"]);
+        assert_eq!(roots[2].path, PathBuf::from("/home/dev/My App"));
+        assert_eq!(roots[1].target(), "/roots/infra-2");
+
+        let mounts = mounts(&roots[..1], true);
+        assert_eq!(mounts[0].source, "/home/dev/infra");
+        assert_eq!(mounts[0].target, "/roots/infra");
+        assert!(mounts[0].read_only);
+    }
+}
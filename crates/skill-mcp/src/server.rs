@@ -12,24 +12,38 @@ use rmcp::{
         ServerHandler,
     },
     model::{
-        CallToolResult, Content, Implementation, ProtocolVersion,
-        ServerCapabilities, ServerInfo, Tool,
+        CallToolResult, CompleteRequestParam, CompleteResult, CompletionInfo, Content,
+        CreateMessageRequestParam, Implementation, LoggingLevel, ProtocolVersion, Role,
+        SamplingMessage, ServerCapabilities, ServerInfo, SetLevelRequestParam, Tool,
+        ToolAnnotations,
     },
-    ServiceExt,
+    service::{DynService, NotificationContext, RequestContext},
+    Peer, RoleServer, ServiceExt,
     transport::stdio,
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use skill_runtime::{
-    InstanceManager, LocalSkillLoader, SkillEngine, SkillExecutor, SkillManifest,
-    SearchPipeline, IndexDocument, SearchConfig, DocumentMetadata,
+    CommandAllowlist, InstanceManager, NativeCommand, LocalSkillLoader, SkillEngine, SkillExecutor, SkillManifest,
+    SearchPipeline, IndexDocument, SearchConfig, DocumentMetadata, SelectedTool, ToolHints,
+    SkillChanged, SkillWatcher, OutputOptions, ProcessedOutput, ResourceUsage, smart_truncate,
+    OutputRange, OutputStore, DEFAULT_PAGE_SIZE,
+    RunStatus, StepExecutor, Workflow, WorkflowRun, discover_workflows, resolve_workflow,
+    Requirement, RequirementProbe, RequirementStatus, SkillRequirements, McpDiscovery, McpRoots,
+    ArgValidationError, ParamSpec, validate_args,
+    ContextCompressor, CompressionConfig, CompressionResult, CompressionStrategy, ToolDocument,
+    ToolParameterInput, PipelineSearchResult, ProcessedQuery, check_positional,
 };
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+use crate::progress::Progress;
+use crate::roots::WorkspaceRoot;
+use crate::instances::{collect_instances, complete_values, instances_label, SkillInstance};
+
 /// Discovered skill tool information
 #[derive(Debug, Clone)]
 pub struct DiscoveredTool {
@@ -39,6 +53,57 @@ pub struct DiscoveredTool {
     pub description: String,
     pub parameters: Vec<ToolParameter>,
     pub source_path: Option<PathBuf>,
+    /// Behavior hints from SKILL.md, with undeclared ones inferred from the tool name
+    pub hints: ToolHints,
+    /// Host requirements of the skill and the tool
+    pub requires: Vec<Requirement>,
+    /// Requirements the host didn't meet when the tool was discovered
+    pub unmet: Vec<RequirementStatus>,
+    /// Code examples from SKILL.md, indexed for search one by one
+    pub examples: Vec<String>,
+}
+
+impl DiscoveredTool {
+    /// Cache and search index key (`skill@instance:tool`)
+    pub fn key(&self) -> String {
+        format!("{}@{}:{}", self.skill_name, self.instance_name, self.tool_name)
+    }
+
+    /// MCP tool annotations for this tool
+    pub fn annotations(&self) -> ToolAnnotations {
+        ToolAnnotations {
+            title: Some(format!("{}:{}", self.skill_name, self.tool_name)),
+            read_only_hint: self.hints.read_only,
+            destructive_hint: self.hints.destructive,
+            idempotent_hint: self.hints.idempotent,
+            open_world_hint: self.hints.open_world,
+        }
+    }
+
+    /// Whether the host met every requirement when the tool was discovered
+    pub fn is_available(&self) -> bool {
+        self.unmet.is_empty()
+    }
+
+    /// Unmet requirements as a comma-separated list
+    pub fn unmet_label(&self) -> String {
+        self.unmet
+            .iter()
+            .map(|status| status.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Short label for listings ("read-only", "destructive"), if any
+    pub fn hint_label(&self) -> Option<&'static str> {
+        if self.hints.read_only == Some(true) {
+            Some("read-only")
+        } else if self.hints.destructive == Some(true) {
+            Some("destructive")
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -47,6 +112,26 @@ pub struct ToolParameter {
     pub param_type: String,
     pub description: String,
     pub required: bool,
+    /// Values the parameter accepts (`enum:` in SKILL.md); any if empty
+    pub allowed_values: Vec<String>,
+    /// Regex the value must match
+    pub pattern: Option<String>,
+    /// Smallest allowed value of a numeric parameter
+    pub minimum: Option<f64>,
+    /// Largest allowed value of a numeric parameter
+    pub maximum: Option<f64>,
+    /// Value used when the parameter is left out
+    pub default: Option<String>,
+}
+
+impl ToolParameter {
+    /// The parameter as arguments are validated against it
+    pub fn spec(&self) -> ParamSpec {
+        ParamSpec::new(self.name.clone(), &self.param_type, self.required)
+            .with_allowed_values(self.allowed_values.clone())
+            .with_pattern(self.pattern.clone())
+            .with_range(self.minimum, self.maximum)
+    }
 }
 
 /// Request to execute a skill tool with context engineering features
@@ -105,332 +190,118 @@ pub struct ExecuteSkillRequest {
 
     /// Output format transformation
     #[serde(default)]
-    #[schemars(description = "Transform output: 'json' (parse as JSON), 'lines' (split into array), 'count' (line count only), 'summary' (AI summary)")]
+    #[schemars(description = "Transform output: 'json' (parse as JSON), 'lines' (split into array), 'count' (line count only), 'compact' (remove whitespace), 'summary' (summarize via the client's LLM when longer than max_output)")]
     pub format: Option<String>,
 
-    /// JSON path to extract (when output is JSON)
+    /// jq filter to apply (when output is JSON)
     #[serde(default)]
-    #[schemars(description = "JSONPath expression to extract specific data from JSON output. Example: '.items[].metadata.name'")]
+    #[schemars(description = "jq filter applied to JSON output (pipes, select(), map() supported). Example: '.items[] | select(.status.phase == \"Running\") | .metadata.name'")]
     pub jq: Option<String>,
 
     /// Include metadata about the execution
     #[serde(default)]
     #[schemars(description = "Include execution metadata (timing, truncation info, etc.)")]
     pub include_metadata: Option<bool>,
+
+    /// Search session this execution belongs to
+    #[serde(default)]
+    #[schemars(description = "Optional search session ID. Records this tool as selected so follow-up searches keep its context.")]
+    pub session_id: Option<String>,
 }
 
 fn default_instance() -> String {
     "default".to_string()
 }
 
-/// Output processing result with context engineering metadata
-#[derive(Debug, Serialize)]
-struct ProcessedOutput {
-    /// The processed output content
-    content: String,
-    /// Whether output was truncated
-    truncated: bool,
-    /// Original length before processing
-    original_length: usize,
-    /// Final length after processing
-    final_length: usize,
-    /// Number of lines matched by grep (if used)
-    grep_matches: Option<usize>,
-    /// Processing applied
-    processing: Vec<String>,
-}
-
-/// Process tool output with context engineering transformations
-#[allow(clippy::too_many_arguments)]
-fn process_output(
-    output: &str,
-    max_output: Option<usize>,
-    truncate_strategy: Option<&str>,
-    grep_pattern: Option<&str>,
-    grep_invert: bool,
-    head_lines: Option<usize>,
-    tail_lines: Option<usize>,
-    format: Option<&str>,
-    jq_path: Option<&str>,
-) -> ProcessedOutput {
-    let original_length = output.len();
-    let mut content = output.to_string();
-    let mut processing = Vec::new();
-    let mut truncated = false;
-    let mut grep_matches = None;
-
-    // Step 1: Apply grep filter first (most selective)
-    if let Some(pattern) = grep_pattern {
-        if let Ok(regex) = regex::Regex::new(pattern) {
-            let lines: Vec<&str> = content.lines().collect();
-            let filtered: Vec<&str> = lines
-                .into_iter()
-                .filter(|line| {
-                    let matches = regex.is_match(line);
-                    if grep_invert { !matches } else { matches }
-                })
-                .collect();
-            grep_matches = Some(filtered.len());
-            content = filtered.join("\n");
-            processing.push(format!("grep(pattern='{}', invert={}, matches={})",
-                pattern, grep_invert, grep_matches.unwrap_or(0)));
-        }
-    }
-
-    // Step 2: Apply head/tail line limits
-    if let Some(n) = head_lines {
-        let lines: Vec<&str> = content.lines().take(n).collect();
-        if content.lines().count() > n {
-            truncated = true;
-        }
-        content = lines.join("\n");
-        processing.push(format!("head({})", n));
-    } else if let Some(n) = tail_lines {
-        let all_lines: Vec<&str> = content.lines().collect();
-        if all_lines.len() > n {
-            truncated = true;
-            content = all_lines[all_lines.len().saturating_sub(n)..].join("\n");
-        }
-        processing.push(format!("tail({})", n));
-    }
-
-    // Step 3: Apply jq path extraction for JSON
-    if let Some(path) = jq_path {
-        if let Ok(json_val) = serde_json::from_str::<serde_json::Value>(&content) {
-            content = extract_json_path(&json_val, path);
-            processing.push(format!("jq('{}')", path));
-        }
-    }
-
-    // Step 4: Apply format transformation
-    if let Some(fmt) = format {
-        match fmt {
-            "json" => {
-                // Try to parse and pretty-print JSON
-                if let Ok(json_val) = serde_json::from_str::<serde_json::Value>(&content) {
-                    content = serde_json::to_string_pretty(&json_val).unwrap_or(content);
-                    processing.push("format(json)".to_string());
-                }
-            }
-            "lines" => {
-                // Convert to JSON array of lines
-                let lines: Vec<&str> = content.lines().collect();
-                content = serde_json::to_string(&lines).unwrap_or(content);
-                processing.push("format(lines)".to_string());
-            }
-            "count" => {
-                // Just return line count
-                let count = content.lines().count();
-                content = format!("{} lines", count);
-                processing.push("format(count)".to_string());
-            }
-            "compact" => {
-                // Remove empty lines and extra whitespace
-                let lines: Vec<&str> = content.lines()
-                    .map(|l| l.trim())
-                    .filter(|l| !l.is_empty())
-                    .collect();
-                content = lines.join("\n");
-                processing.push("format(compact)".to_string());
-            }
-            _ => {}
-        }
-    }
-
-    // Step 5: Apply max_output truncation last
-    if let Some(max) = max_output {
-        if content.len() > max {
-            truncated = true;
-            let strategy = truncate_strategy.unwrap_or("smart");
-            content = truncate_content(&content, max, strategy);
-            processing.push(format!("truncate({}, strategy='{}')", max, strategy));
-        }
-    }
+/// Output longer than this is summarized when `max_output` isn't set
+const DEFAULT_SUMMARY_THRESHOLD: usize = 4000;
 
-    ProcessedOutput {
-        final_length: content.len(),
-        content,
-        truncated,
-        original_length,
-        grep_matches,
-        processing,
-    }
-}
+/// Most output sent to the client's model for summarization
+const MAX_SUMMARY_INPUT: usize = 100_000;
 
-/// Truncate content with different strategies
-fn truncate_content(content: &str, max_len: usize, strategy: &str) -> String {
-    if content.len() <= max_len {
-        return content.to_string();
-    }
+/// Search results `find_tools` compresses before cutting to the budget
+const FIND_TOOLS_CANDIDATES: usize = 20;
 
-    match strategy {
-        "head" => {
-            // Keep the beginning
-            let truncated = &content[..max_len.saturating_sub(50)];
-            format!("{}\n\n... [TRUNCATED: {} more characters]", truncated, content.len() - truncated.len())
-        }
-        "tail" => {
-            // Keep the end
-            let start = content.len().saturating_sub(max_len.saturating_sub(50));
-            let truncated = &content[start..];
-            format!("[TRUNCATED: {} characters omitted] ...\n\n{}", start, truncated)
-        }
-        "middle" => {
-            // Keep both beginning and end
-            let half = (max_len.saturating_sub(100)) / 2;
-            let head = &content[..half];
-            let tail = &content[content.len().saturating_sub(half)..];
-            let omitted = content.len() - (head.len() + tail.len());
-            format!("{}\n\n... [TRUNCATED: {} characters in middle] ...\n\n{}", head, omitted, tail)
-        }
-        _ => {
-            // Smart truncation (default): try to preserve structure (complete lines, JSON structure)
-            smart_truncate(content, max_len)
-        }
-    }
-}
+/// Token budget `find_tools` uses when the caller doesn't set one
+const DEFAULT_TOKEN_BUDGET: usize = 800;
 
-/// Smart truncation that preserves structure
-fn smart_truncate(content: &str, max_len: usize) -> String {
-    // Check if it's JSON
-    if content.trim().starts_with('{') || content.trim().starts_with('[') {
-        return smart_truncate_json(content, max_len);
-    }
+/// Smallest and largest token budgets `find_tools` accepts
+const TOKEN_BUDGET_RANGE: std::ops::RangeInclusive<usize> = 100..=8000;
 
-    // For plain text, truncate at line boundaries
-    let mut result = String::new();
-    let remaining = max_len.saturating_sub(100); // Reserve space for truncation message
-    let lines: Vec<&str> = content.lines().collect();
-    let total_lines = lines.len();
-    let mut included_lines = 0;
+/// Tokens any one tool may take, however small the budget
+const MIN_TOKENS_PER_TOOL: usize = 60;
 
-    for line in lines {
-        if result.len() + line.len() + 1 > remaining {
-            break;
-        }
-        if !result.is_empty() {
-            result.push('\n');
-        }
-        result.push_str(line);
-        included_lines += 1;
-    }
+/// kubectl argument keys whose values are passed as positional arguments
+const POSITIONAL_KEYS: &[&str] = &[
+    "resource", "name", "pod", "subcommand", "context", "action", "labels", "annotations",
+    "node", "taint",
+];
 
-    if included_lines < total_lines {
-        let omitted_lines = total_lines - included_lines;
-        let omitted_chars = content.len() - result.len();
-        result.push_str(&format!(
-            "\n\n... [TRUNCATED: {} more lines, {} characters]\n\
-             💡 Tip: Use grep='<pattern>' to filter, or head=N/tail=N to limit lines",
-            omitted_lines, omitted_chars
-        ));
+/// Apply `format: "summary"`: ask the client's LLM to summarize oversized
+/// output through MCP sampling (`sampling/createMessage`)
+///
+/// Falls back to smart truncation when the client doesn't support sampling
+/// or the request fails, so the output always fits the requested size.
+async fn summarize_output(
+    peer: &Peer<RoleServer>,
+    processed: &mut ProcessedOutput,
+    max_output: Option<usize>,
+    tool_label: &str,
+) {
+    let limit = max_output.unwrap_or(DEFAULT_SUMMARY_THRESHOLD);
+    if processed.content.len() <= limit {
+        return;
     }
 
-    result
-}
-
-/// Smart truncation for JSON content
-fn smart_truncate_json(content: &str, max_len: usize) -> String {
-    if let Ok(json) = serde_json::from_str::<serde_json::Value>(content) {
-        // For arrays, truncate to fewer elements
-        if let serde_json::Value::Array(arr) = &json {
-            let total = arr.len();
-            let mut truncated_arr = Vec::new();
-            let mut current_len = 2; // For []
-
-            for (idx, item) in arr.iter().enumerate() {
-                let item_str = serde_json::to_string(item).unwrap_or_default();
-                if current_len + item_str.len() + 2 > max_len.saturating_sub(150) {
-                    // Add truncation notice
-                    let notice = serde_json::json!({
-                        "_truncated": true,
-                        "_message": format!("... {} more items", total - idx),
-                        "_total_items": total,
-                        "_shown_items": idx,
-                        "_tip": "Use jq='.items[0:10]' to select specific range, or grep to filter"
-                    });
-                    truncated_arr.push(notice);
-                    break;
-                }
-                truncated_arr.push(item.clone());
-                current_len += item_str.len() + 2;
-            }
-
-            return serde_json::to_string_pretty(&truncated_arr)
-                .unwrap_or_else(|_| content[..max_len].to_string());
-        }
-
-        // For objects, try to pretty-print with truncation
-        if let Ok(pretty) = serde_json::to_string_pretty(&json) {
-            if pretty.len() <= max_len {
-                return pretty;
+    let supports_sampling = peer
+        .peer_info()
+        .is_some_and(|info| info.capabilities.sampling.is_some());
+
+    let summary = if supports_sampling {
+        let input = smart_truncate(&processed.content, MAX_SUMMARY_INPUT);
+        let prompt = format!(
+            "Summarize the output of the `{}` tool below for an AI agent. \
+             Keep identifiers, names, counts, errors and warnings; drop repetition. \
+             Answer in plain text in under {} characters.\n\n{}",
+            tool_label, limit, input
+        );
+        let request = CreateMessageRequestParam {
+            messages: vec![SamplingMessage {
+                role: Role::User,
+                content: Content::text(prompt),
+            }],
+            model_preferences: None,
+            system_prompt: Some("You summarize command output concisely and accurately.".to_string()),
+            include_context: None,
+            temperature: Some(0.0),
+            // Roughly four characters per token
+            max_tokens: (limit / 4).clamp(256, 4096) as u32,
+            stop_sequences: None,
+            metadata: None,
+        };
+        match peer.create_message(request).await {
+            Ok(result) => result.message.content.as_text().map(|text| text.text.clone()),
+            Err(e) => {
+                tracing::warn!(error = %e, "Sampling request failed, truncating output instead");
+                None
             }
         }
-    }
-
-    // Fallback: simple truncation
-    truncate_content(content, max_len, "head")
-}
-
-/// Extract data from JSON using a simple path expression
-fn extract_json_path(json: &serde_json::Value, path: &str) -> String {
-    let path = path.trim_start_matches('.');
-    let parts: Vec<&str> = path.split('.').collect();
+    } else {
+        None
+    };
 
-    let mut current = json.clone();
-    for part in parts {
-        if part.is_empty() {
-            continue;
+    match summary {
+        Some(summary) => {
+            processed.content = summary;
+            processed.processing.push("summary(sampling)".to_string());
         }
-
-        // Handle array access like "items[]" or "items[0]"
-        if part.contains('[') {
-            let (field, bracket) = part.split_once('[').unwrap_or((part, ""));
-
-            // First get the field
-            if !field.is_empty() {
-                current = current.get(field).cloned().unwrap_or(serde_json::Value::Null);
-            }
-
-            // Then handle array access
-            if bracket.starts_with(']') {
-                // items[] - extract all items
-                if let serde_json::Value::Array(arr) = current {
-                    let extracted: Vec<String> = arr.iter()
-                        .map(|v| match v {
-                            serde_json::Value::String(s) => s.clone(),
-                            other => serde_json::to_string(other).unwrap_or_default(),
-                        })
-                        .collect();
-                    return extracted.join("\n");
-                }
-            } else if let Some(idx_str) = bracket.strip_suffix(']') {
-                // items[0] or items[0:5]
-                if idx_str.contains(':') {
-                    // Range access items[0:5]
-                    let range_parts: Vec<&str> = idx_str.split(':').collect();
-                    if let (Ok(start), Ok(end)) = (
-                        range_parts.first().unwrap_or(&"0").parse::<usize>(),
-                        range_parts.get(1).unwrap_or(&"").parse::<usize>()
-                    ) {
-                        if let serde_json::Value::Array(arr) = current {
-                            let sliced: Vec<_> = arr.iter().skip(start).take(end - start).cloned().collect();
-                            return serde_json::to_string_pretty(&sliced).unwrap_or_default();
-                        }
-                    }
-                } else if let Ok(idx) = idx_str.parse::<usize>() {
-                    current = current.get(idx).cloned().unwrap_or(serde_json::Value::Null);
-                }
-            }
-        } else {
-            current = current.get(part).cloned().unwrap_or(serde_json::Value::Null);
+        None => {
+            processed.content = smart_truncate(&processed.content, limit);
+            processed.processing.push(format!("summary(unavailable, truncate({}, strategy='smart'))", limit));
         }
     }
-
-    match current {
-        serde_json::Value::String(s) => s,
-        serde_json::Value::Null => "null".to_string(),
-        other => serde_json::to_string_pretty(&other).unwrap_or_default(),
-    }
+    processed.truncated = true;
+    processed.final_length = processed.content.len();
 }
 
 /// Request to list available tools
@@ -472,16 +343,34 @@ pub struct McpServer {
     local_loader: Arc<LocalSkillLoader>,
     /// Discovered tools cache
     tools: Arc<RwLock<HashMap<String, DiscoveredTool>>>,
-    /// Optional manifest for declarative skills
-    manifest: Option<SkillManifest>,
+    /// Optional manifest for declarative skills (replaced when its files change)
+    manifest: Arc<std::sync::RwLock<Option<Arc<SkillManifest>>>>,
     /// Search pipeline for semantic search (lazy initialized)
     search_pipeline: Arc<RwLock<Option<SearchPipeline>>>,
+    /// Full output of truncated executions, paged with get_execution_output
+    outputs: Arc<OutputStore>,
+    /// Host requirement checks, shared by discovery and execution
+    requirements: Arc<RequirementProbe>,
+    /// Workspace roots the client declared
+    roots: Arc<std::sync::RwLock<Vec<WorkspaceRoot>>>,
+    /// Lowest level of the log messages sent to the client
+    log_level: Arc<std::sync::RwLock<LoggingLevel>>,
 }
 
 impl McpServer {
     /// Create a new MCP server
     pub fn new() -> Result<Self> {
-        let engine = Arc::new(SkillEngine::new()?);
+        Self::with_engine(Arc::new(SkillEngine::new()?), None)
+    }
+
+    /// Create a new MCP server with a manifest
+    pub fn with_manifest(manifest: SkillManifest) -> Result<Self> {
+        Self::with_engine(Arc::new(SkillEngine::new()?), Some(manifest))
+    }
+
+    /// Create a new MCP server running skills on an existing engine, e.g.
+    /// the REST API's when both are served from one process
+    pub fn with_engine(engine: Arc<SkillEngine>, manifest: Option<SkillManifest>) -> Result<Self> {
         let instance_manager = Arc::new(InstanceManager::new()?);
         let local_loader = Arc::new(LocalSkillLoader::new()?);
 
@@ -490,16 +379,71 @@ impl McpServer {
             instance_manager,
             local_loader,
             tools: Arc::new(RwLock::new(HashMap::new())),
-            manifest: None,
+            manifest: Arc::new(std::sync::RwLock::new(manifest.map(Arc::new))),
             search_pipeline: Arc::new(RwLock::new(None)),
+            outputs: Arc::new(OutputStore::default()),
+            requirements: Arc::new(RequirementProbe::default()),
+            roots: Arc::new(std::sync::RwLock::new(Vec::new())),
+            log_level: Arc::new(std::sync::RwLock::new(LoggingLevel::Info)),
         })
     }
 
-    /// Create a new MCP server with a manifest
-    pub fn with_manifest(manifest: SkillManifest) -> Result<Self> {
-        let mut server = Self::new()?;
-        server.manifest = Some(manifest);
-        Ok(server)
+    /// Current manifest, if any
+    fn manifest(&self) -> Option<Arc<SkillManifest>> {
+        self.manifest.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// How skills get the client's roots (`[mcp] roots`)
+    fn roots_mode(&self) -> McpRoots {
+        self.manifest()
+            .map(|manifest| manifest.mcp.roots)
+            .unwrap_or_default()
+    }
+
+    /// Ask the client for its workspace roots again
+    pub(crate) fn refresh_roots(&self, peer: Peer<RoleServer>) {
+        if self.roots_mode() == McpRoots::Off {
+            return;
+        }
+        // Listing roots is a request to the client, so don't make the
+        // notification that triggered it wait for the answer
+        let server = self.clone();
+        tokio::spawn(async move {
+            let roots = crate::roots::fetch(&peer).await;
+            tracing::info!(roots = roots.len(), "Workspace roots updated");
+            *server.roots.write().unwrap_or_else(|e| e.into_inner()) = roots;
+        });
+    }
+
+    /// Lowest level of the log messages sent to the client
+    pub(crate) fn log_level(&self) -> LoggingLevel {
+        *self.log_level.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Current workspace roots, unless `[mcp] roots = "off"`
+    fn workspace_roots(&self) -> Vec<WorkspaceRoot> {
+        if self.roots_mode() == McpRoots::Off {
+            return Vec::new();
+        }
+        self.roots.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Mount the workspace roots into a WASM skill's instance and list the
+    /// mount points in `MCP_ROOTS`
+    fn grant_roots(&self, config: &mut skill_runtime::InstanceConfig) {
+        let roots = self.workspace_roots();
+        if roots.is_empty() {
+            return;
+        }
+        let read_only = self.roots_mode() != McpRoots::ReadWrite;
+        config
+            .capabilities
+            .mounts
+            .extend(crate::roots::mounts(&roots, read_only));
+        let targets: Vec<String> = roots.iter().map(WorkspaceRoot::target).collect();
+        config
+            .environment
+            .insert("MCP_ROOTS".to_string(), targets.join(":"));
     }
 
     /// Discover all available tools from installed skills and manifest
@@ -523,7 +467,7 @@ impl McpServer {
         }
 
         // Discover from manifest
-        if let Some(ref manifest) = self.manifest {
+        if let Some(manifest) = self.manifest() {
             for skill_name in manifest.skill_names() {
                 if let Ok(resolved) = manifest.resolve_instance(skill_name, None) {
                     if let Ok(tools) = self
@@ -540,16 +484,98 @@ impl McpServer {
             }
         }
 
-        // Update cache
+        // Replace cache
         let mut cache = self.tools.write().await;
-        for tool in &discovered {
-            let key = format!("{}@{}:{}", tool.skill_name, tool.instance_name, tool.tool_name);
-            cache.insert(key, tool.clone());
-        }
+        *cache = discovered
+            .iter()
+            .map(|tool| (tool.key(), tool.clone()))
+            .collect();
 
         Ok(discovered)
     }
 
+    /// Refresh tools after a registry or manifest change reported by the
+    /// [`SkillWatcher`]
+    ///
+    /// Registry changes only rediscover the affected skill; manifest changes
+    /// reload the manifest and rediscover everything. Removed tools are
+    /// dropped from the search index.
+    pub async fn apply_skill_change(&self, change: &SkillChanged) -> Result<()> {
+        let keys_before: Vec<String> = self.tools.read().await.keys().cloned().collect();
+
+        match change {
+            SkillChanged::Updated(skill_name) | SkillChanged::Removed(skill_name) => {
+                let home = dirs::home_dir().context("Failed to get home directory")?;
+                let skill_path = home
+                    .join(".skill-engine")
+                    .join("registry")
+                    .join(skill_name);
+                let tools = match change {
+                    SkillChanged::Updated(_) => self.discover_skill_tools(skill_name, None).await?,
+                    _ => Vec::new(),
+                };
+
+                let mut cache = self.tools.write().await;
+                cache.retain(|_, tool| tool.source_path.as_ref() != Some(&skill_path));
+                for tool in tools {
+                    cache.insert(tool.key(), tool);
+                }
+                tracing::info!(skill = %skill_name, "Reloaded skill tools");
+            }
+            SkillChanged::Manifest(_) | SkillChanged::Rescan => {
+                if let Some(manifest) = self.manifest() {
+                    let reloaded = manifest.reload().context("Failed to reload manifest")?;
+                    *self.manifest.write().unwrap_or_else(|e| e.into_inner()) =
+                        Some(Arc::new(reloaded));
+                }
+                let discovered = self.discover_tools().await?;
+                tracing::info!("Reloaded {} tools from skills", discovered.len());
+            }
+        }
+
+        // Drop removed tools from the search index; the next search
+        // re-indexes the current ones
+        if let Some(pipeline) = self.search_pipeline.read().await.as_ref() {
+            let tools = self.tools.read().await;
+            let stale: Vec<String> = keys_before
+                .into_iter()
+                .filter(|key| !tools.contains_key(key))
+                .collect();
+            pipeline
+                .remove_documents(stale)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to update search index: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Watch the registry and manifest, applying changes until the returned
+    /// watcher is dropped
+    fn watch_skills(&self) -> Option<SkillWatcher> {
+        let manifest_paths = self
+            .manifest()
+            .map(|manifest| manifest.layers.clone())
+            .unwrap_or_default();
+        let watcher = match SkillWatcher::new(&manifest_paths) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                tracing::warn!(error = %e, "Skill hot reload disabled");
+                return None;
+            }
+        };
+
+        let mut changes = watcher.subscribe();
+        let server = self.clone();
+        tokio::spawn(async move {
+            while let Some(change) = changes.recv().await {
+                if let Err(e) = server.apply_skill_change(&change).await {
+                    tracing::warn!(error = %e, "Failed to reload skills");
+                }
+            }
+        });
+        Some(watcher)
+    }
+
     /// Discover tools from an installed skill
     async fn discover_skill_tools(
         &self,
@@ -600,6 +626,11 @@ impl McpServer {
         skill_path: &PathBuf,
     ) -> Result<Vec<DiscoveredTool>> {
         let mut tools = Vec::new();
+        let manifest_requires = self
+            .manifest()
+            .and_then(|manifest| manifest.get_skill(skill_name).map(|skill| skill.requires.clone()))
+            .unwrap_or_default();
+        let requirements = SkillRequirements::load(&manifest_requires, skill_path);
 
         // Try to load SKILL.md for rich documentation
         if let Some(skill_md) = self.local_loader.load_skill_md(skill_path) {
@@ -609,19 +640,29 @@ impl McpServer {
                     .iter()
                     .map(|p| ToolParameter {
                         name: p.name.clone(),
-                        param_type: "string".to_string(),
+                        param_type: p.param_type.to_string(),
                         description: p.description.clone(),
-                        required: p.required,
+                        required: p.required && p.default.is_none(),
+                        allowed_values: p.allowed_values.clone(),
+                        pattern: p.pattern.clone(),
+                        minimum: p.minimum,
+                        maximum: p.maximum,
+                        default: p.default.clone(),
                     })
                     .collect();
 
+                let requires = requirements.for_tool(&tool_name);
                 tools.push(DiscoveredTool {
                     skill_name: skill_name.to_string(),
                     instance_name: instance_name.to_string(),
+                    hints: tool_doc.hints.resolve(&tool_name),
+                    unmet: self.requirements.unmet(&requires).await,
+                    requires,
                     tool_name,
                     description: tool_doc.description,
                     parameters,
                     source_path: Some(skill_path.clone()),
+                    examples: tool_doc.examples.into_iter().map(|e| e.code).collect(),
                 });
             }
         }
@@ -654,17 +695,27 @@ impl McpServer {
                                     name: p.name.clone(),
                                     param_type: format!("{:?}", p.param_type),
                                     description: p.description.clone(),
-                                    required: p.required,
+                                    required: p.required && p.default_value.is_none(),
+                                    allowed_values: Vec::new(),
+                                    pattern: None,
+                                    minimum: None,
+                                    maximum: None,
+                                    default: p.default_value.clone(),
                                 })
                                 .collect();
 
+                            let requires = requirements.for_tool(&tool.name);
                             tools.push(DiscoveredTool {
                                 skill_name: skill_name.to_string(),
                                 instance_name: instance_name.to_string(),
+                                hints: ToolHints::infer(&tool.name),
+                                unmet: self.requirements.unmet(&requires).await,
+                                requires,
                                 tool_name: tool.name,
                                 description: tool.description,
                                 parameters,
                                 source_path: Some(skill_path.clone()),
+                                examples: Vec::new(),
                             });
                         }
                     }
@@ -683,8 +734,38 @@ impl McpServer {
         tool_name: &str,
         args: HashMap<String, serde_json::Value>,
     ) -> Result<skill_runtime::ExecutionResult> {
+        if let Some(manifest) = self.manifest() {
+            manifest
+                .resolve_dependencies(skill_name)
+                .context("Unsatisfied skill dependencies")?;
+        }
+
+        // Fail early, and clearly, if the host can't run the tool or the
+        // arguments don't match its parameters
+        let key = format!("{}@{}:{}", skill_name, instance_name, tool_name);
+        let (requires, params) = match self.tools.read().await.get(&key) {
+            Some(tool) => (
+                Some(tool.requires.clone()),
+                tool.parameters.iter().map(ToolParameter::spec).collect(),
+            ),
+            None => (None, Vec::new()),
+        };
+        validate_args(tool_name, &params, &args)?;
+        if let Some(requires) = requires {
+            let unmet = self.requirements.unmet(&requires).await;
+            if !unmet.is_empty() {
+                let unmet: Vec<String> = unmet.iter().map(|status| status.to_string()).collect();
+                anyhow::bail!(
+                    "Tool '{}:{}' is unavailable on this host; unmet requirements: {}",
+                    skill_name,
+                    tool_name,
+                    unmet.join(", ")
+                );
+            }
+        }
+
         // Find skill path
-        let skill_path = if let Some(ref manifest) = self.manifest {
+        let skill_path = if let Some(manifest) = self.manifest() {
             if let Some(skill) = manifest.get_skill(skill_name) {
                 let source = &skill.source;
                 if source.starts_with("./") || source.starts_with("../") {
@@ -723,10 +804,11 @@ impl McpServer {
 
         if let Ok(wasm_file) = wasm_path {
             // WASM skill - execute via runtime
-            let config = self
+            let mut config = self
                 .instance_manager
                 .load_instance(skill_name, instance_name)
                 .unwrap_or_default();
+            self.grant_roots(&mut config);
 
             let executor = SkillExecutor::load(
                 self.engine.clone(),
@@ -741,7 +823,9 @@ impl McpServer {
 
             // Check if the WASM skill returns a native command to execute
             if result.success && result.output.starts_with("Command: ") {
-                return self.execute_native_command(&result.output).await;
+                return self
+                    .execute_native_command(skill_name, &skill_path, &result.output)
+                    .await;
             }
 
             Ok(result)
@@ -783,93 +867,74 @@ impl McpServer {
         args: Vec<(String, String)>,
         skill_path: &PathBuf,
     ) -> Result<skill_runtime::ExecutionResult> {
-        use std::process::Stdio;
-        use tokio::process::Command;
-
         // Load SKILL.md to understand the tool's command pattern
         let skill_md = self.local_loader.load_skill_md(skill_path)
             .ok_or_else(|| anyhow::anyhow!("No SKILL.md found for native skill: {}", skill_name))?;
 
-        // Build the command based on skill name and tool
-        let command_str = self.build_native_command(skill_name, tool_name, &args, &skill_md)?;
+        let allowlist =
+            self.command_allowlist(skill_name, skill_md.frontmatter.allowed_tools.as_deref())?;
 
-        tracing::info!(command = %command_str, "Executing native command");
+        // Build the command based on skill name and tool (argv, no shell involved)
+        let native = self.build_native_command(skill_name, tool_name, &args, &skill_md)?;
 
-        // Parse the command
-        let parts: Vec<&str> = command_str.split_whitespace().collect();
-        if parts.is_empty() {
-            return Ok(skill_runtime::ExecutionResult {
-                success: false,
-                output: String::new(),
-                error_message: Some("Empty command".to_string()),
-                metadata: None,
-            });
-        }
+        tracing::info!(command = %native, "Executing native command");
 
-        let program = parts[0];
-        let cmd_args = &parts[1..];
-
-        // Security check: Only allow specific commands
-        let allowed_commands = ["kubectl", "helm", "git", "curl", "jq", "aws", "gcloud", "az", "docker", "terraform"];
-        if !allowed_commands.contains(&program) {
-            return Ok(skill_runtime::ExecutionResult {
-                success: false,
-                output: String::new(),
-                error_message: Some(format!(
-                    "Command '{}' not allowed. Allowed: {}",
-                    program,
-                    allowed_commands.join(", ")
-                )),
-                metadata: None,
-            });
+        let program = native.program();
+
+        // Security check: Only allow configured commands
+        if let Err(e) = allowlist.check(program) {
+            return Ok(skill_runtime::ExecutionResult::failure(e.to_string()));
         }
 
         // Execute the command
-        let result = Command::new(program)
-            .args(cmd_args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await;
+        let mut command = native.to_command();
+        self.native_policy(skill_name).apply(&mut command)?;
+        if let Some(root) = self.workspace_roots().first() {
+            command.current_dir(&root.path);
+        }
+        let started = std::time::Instant::now();
+        let max_output = self.execution_limits(skill_name).max_output_bytes();
+        let (result, usage) =
+            ResourceUsage::measure(crate::progress::output(&mut command, max_output)).await;
 
         match result {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-                if output.status.success() {
-                    Ok(skill_runtime::ExecutionResult {
-                        success: true,
-                        output: stdout,
-                        error_message: if stderr.is_empty() {
-                            None
-                        } else {
-                            Some(stderr)
-                        },
-                        metadata: None,
-                    })
-                } else {
-                    Ok(skill_runtime::ExecutionResult {
-                        success: false,
-                        output: stdout,
-                        error_message: Some(if stderr.is_empty() {
-                            format!("Command exited with status: {}", output.status)
-                        } else {
-                            stderr
-                        }),
-                        metadata: None,
-                    })
-                }
-            }
-            Err(e) => Ok(skill_runtime::ExecutionResult {
-                success: false,
-                output: String::new(),
-                error_message: Some(format!("Failed to execute command: {}", e)),
-                metadata: None,
-            }),
+            Ok(output) => Ok(skill_runtime::ExecutionResult::from_process_output(&output)
+                .with_duration(started.elapsed())
+                .with_resource_usage(usage)),
+            Err(e) => Ok(skill_runtime::ExecutionResult::failure(format!(
+                "Failed to execute command: {}",
+                e
+            ))),
         }
     }
 
+    /// Command allowlist for a skill: the manifest's `[security]` section (or
+    /// the built-in defaults), narrowed by the skill's `allowed-tools`
+    fn command_allowlist(
+        &self,
+        skill_name: &str,
+        allowed_tools: Option<&str>,
+    ) -> Result<CommandAllowlist> {
+        self.manifest()
+            .map(|manifest| manifest.command_allowlist())
+            .unwrap_or_default()
+            .for_skill(skill_name, allowed_tools)
+    }
+
+    /// Native execution hardening for a skill (from the manifest, if any)
+    fn native_policy(&self, skill_name: &str) -> skill_runtime::NativeSecurityPolicy {
+        self.manifest()
+            .map(|manifest| manifest.native_policy(skill_name))
+            .unwrap_or_default()
+    }
+
+    /// Output and write limits for a skill (from the manifest, if any)
+    fn execution_limits(&self, skill_name: &str) -> skill_context::ExecutionLimits {
+        self.manifest()
+            .map(|manifest| manifest.execution_limits(skill_name))
+            .unwrap_or_default()
+    }
+
     /// Build a native command from skill definition and arguments
     fn build_native_command(
         &self,
@@ -877,8 +942,9 @@ impl McpServer {
         tool_name: &str,
         args: &[(String, String)],
         skill_md: &skill_runtime::SkillMdContent,
-    ) -> Result<String> {
+    ) -> Result<NativeCommand> {
         // Get the base command from the skill's allowed-tools or infer from name
+        let declared: Option<String>;
         let base_command = match skill_name {
             "kubernetes" => "kubectl",
             "aws" => "aws",
@@ -886,15 +952,27 @@ impl McpServer {
             "terraform" => "terraform",
             "helm" => "helm",
             _ => {
-                // Try to get from allowed-tools in SKILL.md
-                if let Some(ref allowed) = skill_md.frontmatter.allowed_tools {
-                    allowed.split(',').next().unwrap_or(skill_name).trim()
-                } else {
-                    skill_name
-                }
+                // Use the first binary declared in allowed-tools in SKILL.md
+                declared = skill_md
+                    .frontmatter
+                    .allowed_tools
+                    .as_deref()
+                    .and_then(|allowed| {
+                        skill_runtime::command_allowlist::declared_commands(allowed)
+                            .into_iter()
+                            .next()
+                    });
+                declared.as_deref().unwrap_or(skill_name)
             }
         };
 
+        // Values that land in positional slots must not be parsed as options
+        for (key, value) in args {
+            if POSITIONAL_KEYS.contains(&key.as_str()) {
+                check_positional(value)?;
+            }
+        }
+
         // Build the command based on tool name and args
         let mut cmd_parts = vec![base_command.to_string()];
 
@@ -1117,7 +1195,9 @@ impl McpServer {
                         cmd_parts.push(container);
                     }
                     cmd_parts.push("--".to_string());
-                    cmd_parts.extend(command.split_whitespace().map(|s| s.to_string()));
+                    if !command.trim().is_empty() {
+                        cmd_parts.extend(NativeCommand::parse(&command)?.argv());
+                    }
                 }
                 "label" => {
                     cmd_parts.push("label".to_string());
@@ -1205,7 +1285,7 @@ impl McpServer {
                     cmd_parts.clear();
                     for (key, value) in args {
                         if key == "command" {
-                            return Ok(value.clone());
+                            return NativeCommand::parse(value);
                         }
                     }
                     return Err(anyhow::anyhow!("raw tool requires 'command' argument"));
@@ -1214,6 +1294,7 @@ impl McpServer {
                     // Generic passthrough
                     cmd_parts.push(tool_name.to_string());
                     for (_, value) in args {
+                        check_positional(value)?;
                         cmd_parts.push(value.clone());
                     }
                 }
@@ -1222,100 +1303,110 @@ impl McpServer {
             // For other skills, just pass tool name and args
             cmd_parts.push(tool_name.to_string());
             for (_, value) in args {
+                check_positional(value)?;
                 cmd_parts.push(value.clone());
             }
         }
 
-        Ok(cmd_parts.join(" "))
+        NativeCommand::from_argv(cmd_parts)
     }
 
     /// Execute a native command from skill output
     async fn execute_native_command(
         &self,
+        skill_name: &str,
+        skill_path: &std::path::Path,
         output: &str,
     ) -> Result<skill_runtime::ExecutionResult> {
-        use std::process::Stdio;
-        use tokio::process::Command;
+        let allowed_tools = self
+            .local_loader
+            .load_skill_md(skill_path)
+            .and_then(|skill_md| skill_md.frontmatter.allowed_tools);
+        let allowlist = self.command_allowlist(skill_name, allowed_tools.as_deref())?;
 
         // Extract the command from "Command: kubectl ..."
         let first_line = output.lines().next().unwrap_or("");
         let command_str = first_line.strip_prefix("Command: ").unwrap_or(first_line);
 
-        // Parse the command
-        let parts: Vec<&str> = command_str.split_whitespace().collect();
-        if parts.is_empty() {
-            return Ok(skill_runtime::ExecutionResult {
-                success: false,
-                output: String::new(),
-                error_message: Some("Empty command".to_string()),
-                metadata: None,
-            });
-        }
-
-        let program = parts[0];
-        let cmd_args = &parts[1..];
-
-        // Security check: Only allow specific commands
-        let allowed_commands = ["kubectl", "helm", "git", "curl", "jq", "aws", "gcloud", "az", "docker", "terraform"];
-        if !allowed_commands.contains(&program) {
-            return Ok(skill_runtime::ExecutionResult {
-                success: false,
-                output: String::new(),
-                error_message: Some(format!(
-                    "Command '{}' not allowed. Allowed: {}",
-                    program,
-                    allowed_commands.join(", ")
-                )),
-                metadata: None,
-            });
-        }
+        // Parse the command into argv (quotes are honoured, nothing is expanded)
+        let native = match NativeCommand::parse(command_str)
+            .and_then(|native| allowlist.check(native.program()).map(|_| native))
+        {
+            Ok(native) => native,
+            Err(e) => return Ok(skill_runtime::ExecutionResult::failure(e.to_string())),
+        };
 
-        tracing::info!(command = %command_str, "Executing native command");
+        tracing::info!(command = %native, "Executing native command");
 
         // Execute the command
-        let result = Command::new(program)
-            .args(cmd_args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
-            .await;
+        let mut command = native.to_command();
+        self.native_policy(skill_name).apply(&mut command)?;
+        if let Some(root) = self.workspace_roots().first() {
+            command.current_dir(&root.path);
+        }
+        let started = std::time::Instant::now();
+        let max_output = self.execution_limits(skill_name).max_output_bytes();
+        let (result, usage) =
+            ResourceUsage::measure(crate::progress::output(&mut command, max_output)).await;
 
         match result {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-                if output.status.success() {
-                    Ok(skill_runtime::ExecutionResult {
-                        success: true,
-                        output: stdout,
-                        error_message: if stderr.is_empty() {
-                            None
-                        } else {
-                            Some(stderr)
-                        },
-                        metadata: None,
-                    })
-                } else {
-                    Ok(skill_runtime::ExecutionResult {
-                        success: false,
-                        output: stdout,
-                        error_message: Some(if stderr.is_empty() {
-                            format!("Command exited with status: {}", output.status)
-                        } else {
-                            stderr
-                        }),
-                        metadata: None,
-                    })
-                }
+            Ok(output) => Ok(skill_runtime::ExecutionResult::from_process_output(&output)
+                .with_duration(started.elapsed())
+                .with_resource_usage(usage)),
+            Err(e) => Ok(skill_runtime::ExecutionResult::failure(format!(
+                "Failed to execute command: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Instances a skill can run as - declared in the manifest or created
+    /// with `skill config` - default first
+    pub fn skill_instances(&self, skill_name: &str) -> Vec<SkillInstance> {
+        let manifest = self.manifest();
+        let installed = self
+            .instance_manager
+            .list_instances(skill_name)
+            .unwrap_or_default();
+        collect_instances(
+            manifest.as_ref().and_then(|m| m.get_skill(skill_name)),
+            installed,
+        )
+    }
+
+    /// Instances of every discovered skill that has more than one
+    pub(crate) fn instance_hints(
+        &self,
+        tools: &[DiscoveredTool],
+    ) -> BTreeMap<String, Vec<SkillInstance>> {
+        let mut hints = BTreeMap::new();
+        for tool in tools {
+            if hints.contains_key(&tool.skill_name) {
+                continue;
+            }
+            let instances = self.skill_instances(&tool.skill_name);
+            if instances.len() > 1 {
+                hints.insert(tool.skill_name.clone(), instances);
             }
-            Err(e) => Ok(skill_runtime::ExecutionResult {
-                success: false,
-                output: String::new(),
-                error_message: Some(format!("Failed to execute command: {}", e)),
-                metadata: None,
-            }),
         }
+        hints
+    }
+
+    /// Discovered tools grouped by skill
+    pub(crate) async fn tools_by_skill(&self) -> BTreeMap<String, Vec<DiscoveredTool>> {
+        let tools = self.tools.read().await;
+        let mut skills: BTreeMap<String, Vec<DiscoveredTool>> = BTreeMap::new();
+        for tool in tools.values() {
+            skills.entry(tool.skill_name.clone()).or_default().push(tool.clone());
+        }
+        skills
+    }
+
+    /// Names of the discovered skills
+    async fn skill_names(&self) -> Vec<String> {
+        let tools = self.tools.read().await;
+        let names: HashSet<&String> = tools.values().map(|tool| &tool.skill_name).collect();
+        names.into_iter().cloned().collect()
     }
 
     /// Get tools for list_skills response with optional pagination
@@ -1392,8 +1483,27 @@ impl McpServer {
         for skill_name in skill_names {
             let skill_tools = grouped.get(&skill_name).unwrap();
             output.push_str(&format!("## {}\n", skill_name));
+            let instances = self.skill_instances(&skill_name);
+            if instances.len() > 1 {
+                output.push_str(&format!("  Instances: {}\n", instances_label(&instances)));
+            }
             for tool in skill_tools {
-                output.push_str(&format!("  - **{}**: {}\n", tool.tool_name, tool.description));
+                if !tool.is_available() {
+                    output.push_str(&format!(
+                        "  - **{}** [unavailable: {}]: {}\n",
+                        tool.tool_name,
+                        tool.unmet_label(),
+                        tool.description
+                    ));
+                    continue;
+                }
+                match tool.hint_label() {
+                    Some(label) => output.push_str(&format!(
+                        "  - **{}** [{}]: {}\n",
+                        tool.tool_name, label, tool.description
+                    )),
+                    None => output.push_str(&format!("  - **{}**: {}\n", tool.tool_name, tool.description)),
+                }
                 if !tool.parameters.is_empty() {
                     for param in &tool.parameters {
                         let req = if param.required { " (required)" } else { "" };
@@ -1408,76 +1518,30 @@ impl McpServer {
     }
 
     /// Search for skills using semantic vector search via SearchPipeline
-    pub async fn search_skills(&self, query: &str, top_k: usize) -> Result<String> {
+    pub async fn search_skills(&self, query: &str, top_k: usize, session_id: Option<&str>) -> Result<String> {
         let tools = self.tools.read().await;
 
         if tools.is_empty() {
             return Ok("No skills installed. Install skills with `skill install <source>`".to_string());
         }
 
-        // Initialize SearchPipeline lazily
-        let mut pipeline_lock = self.search_pipeline.write().await;
-        if pipeline_lock.is_none() {
-            let config = SearchConfig::default();
-            let pipeline = SearchPipeline::from_config(config).await
-                .map_err(|e| anyhow::anyhow!("Failed to create search pipeline: {}", e))?;
-            *pipeline_lock = Some(pipeline);
-        }
-        let pipeline = pipeline_lock.as_ref().unwrap();
+        let (processed, results) = self.search_tools(&tools, query, top_k, session_id).await?;
 
-        // Build index documents from discovered tools with rich context
-        let index_docs: Vec<IndexDocument> = tools.values().map(|t| {
-            // Build rich text for better semantic matching
-            let param_text = t.parameters.iter()
-                .map(|p| {
-                    let req = if p.required { "required" } else { "optional" };
-                    format!("{} ({}, {}): {}", p.name, p.param_type, req, p.description)
-                })
-                .collect::<Vec<_>>()
-                .join("; ");
+        // Format rich results for AI consumption
+        let mut output = String::new();
+        output.push_str(&format!("## 🔍 Search Results for: \"{}\"\n\n", query));
+        if processed.is_follow_up {
+            output.push_str(&format!("_Interpreted as follow-up: \"{}\"_\n\n", processed.normalized));
+        }
 
-            let full_text = format!(
-                "Tool: {} | Description: {} | Skill: {} | Parameters: {}",
-                t.tool_name,
-                t.description,
-                t.skill_name,
-                if param_text.is_empty() { "none".to_string() } else { param_text }
-            );
-
-            IndexDocument {
-                id: format!("{}@{}:{}", t.skill_name, t.instance_name, t.tool_name),
-                content: full_text,
-                metadata: DocumentMetadata {
-                    skill_name: Some(t.skill_name.clone()),
-                    instance_name: Some(t.instance_name.clone()),
-                    tool_name: Some(t.tool_name.clone()),
-                    category: None,
-                    tags: Vec::new(),
-                    custom: HashMap::new(),
-                },
-            }
-        }).collect();
-
-        // Index documents
-        pipeline.index_documents(index_docs).await
-            .map_err(|e| anyhow::anyhow!("Failed to index tools: {}", e))?;
-
-        // Search
-        let results = pipeline.search(query, top_k).await
-            .map_err(|e| anyhow::anyhow!("Search failed: {}", e))?;
-
-        // Format rich results for AI consumption
-        let mut output = String::new();
-        output.push_str(&format!("## 🔍 Search Results for: \"{}\"\n\n", query));
-
-        if results.is_empty() {
-            output.push_str("No matching tools found for your query.\n\n");
-            output.push_str("**Suggestions:**\n");
-            output.push_str("- Try different keywords\n");
-            output.push_str("- Use `list_skills` to see all available tools\n");
-            output.push_str("- Install more skills with `skill install <source>`\n");
-        } else {
-            output.push_str(&format!("Found **{}** relevant tools:\n\n", results.len()));
+        if results.is_empty() {
+            output.push_str("No matching tools found for your query.\n\n");
+            output.push_str("**Suggestions:**\n");
+            output.push_str("- Try different keywords\n");
+            output.push_str("- Use `list_skills` to see all available tools\n");
+            output.push_str("- Install more skills with `skill install <source>`\n");
+        } else {
+            output.push_str(&format!("Found **{}** relevant tools:\n\n", results.len()));
 
             for (i, result) in results.iter().enumerate() {
                 let score_pct = (result.score * 100.0) as u32;
@@ -1501,6 +1565,9 @@ impl McpServer {
                 // Description
                 if let Some(t) = tool_info {
                     output.push_str(&format!("**Description:** {}\n\n", t.description));
+                    if let Some(label) = t.hint_label() {
+                        output.push_str(&format!("**Behavior:** {}\n\n", label));
+                    }
 
                     // Parameters section
                     if !t.parameters.is_empty() {
@@ -1564,6 +1631,144 @@ impl McpServer {
         Ok(output)
     }
 
+    /// Index the available tools and search them, expanding follow-up
+    /// queries against the session
+    async fn search_tools(
+        &self,
+        tools: &HashMap<String, DiscoveredTool>,
+        query: &str,
+        top_k: usize,
+        session_id: Option<&str>,
+    ) -> Result<(ProcessedQuery, Vec<PipelineSearchResult>)> {
+        // Initialize SearchPipeline lazily, restoring SKILL_INDEX_SNAPSHOT if set
+        let mut pipeline_lock = self.search_pipeline.write().await;
+        if pipeline_lock.is_none() {
+            let config = SearchConfig::default().with_env_overrides();
+            let pipeline = SearchPipeline::from_config(config).await
+                .map_err(|e| anyhow::anyhow!("Failed to create search pipeline: {}", e))?;
+            *pipeline_lock = Some(pipeline);
+        }
+        let pipeline = pipeline_lock.as_ref().unwrap();
+
+        // Build index documents from discovered tools with rich context, plus
+        // one per SKILL.md example; tools the host can't run are left out of search
+        let index_docs: Vec<IndexDocument> = tools.values().filter(|t| t.is_available()).flat_map(|t| {
+            // Build rich text for better semantic matching
+            let param_text = t.parameters.iter()
+                .map(|p| {
+                    let req = if p.required { "required" } else { "optional" };
+                    format!("{} ({}, {}): {}", p.name, p.param_type, req, p.description)
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+
+            let full_text = format!(
+                "Tool: {} | Description: {} | Skill: {} | Parameters: {}",
+                t.tool_name,
+                t.description,
+                t.skill_name,
+                if param_text.is_empty() { "none".to_string() } else { param_text }
+            );
+
+            let doc = IndexDocument {
+                id: format!("{}@{}:{}", t.skill_name, t.instance_name, t.tool_name),
+                content: full_text,
+                metadata: DocumentMetadata {
+                    skill_name: Some(t.skill_name.clone()),
+                    instance_name: Some(t.instance_name.clone()),
+                    tool_name: Some(t.tool_name.clone()),
+                    category: None,
+                    runtime: None,
+                    tags: Vec::new(),
+                    custom: HashMap::new(),
+                },
+            };
+            let examples = doc.example_documents(&t.examples);
+            std::iter::once(doc).chain(examples)
+        }).collect();
+
+        // Index documents
+        pipeline.index_documents(index_docs).await
+            .map_err(|e| anyhow::anyhow!("Failed to index tools: {}", e))?;
+
+        // Expand follow-up queries against the session before searching
+        let processed = match session_id {
+            Some(id) => pipeline.process_query_in_session(query, id),
+            None => pipeline.process_query(query),
+        };
+
+        // Search, dropping tools indexed before they became unavailable
+        let mut results = pipeline.search_in_session(query, top_k, session_id).await
+            .map_err(|e| anyhow::anyhow!("Search failed: {}", e))?;
+        results.retain(|result| tools.get(&result.id).map_or(true, |t| t.is_available()));
+
+        Ok((processed, results))
+    }
+
+    /// Find tools for a query and return compressed contexts that fit in
+    /// `token_budget` tokens, best match first
+    pub async fn find_tools(
+        &self,
+        query: &str,
+        token_budget: usize,
+        strategy: CompressionStrategy,
+        session_id: Option<&str>,
+    ) -> Result<String> {
+        let tools = self.tools.read().await;
+
+        if tools.is_empty() {
+            return Ok("No skills installed. Install skills with `skill install <source>`".to_string());
+        }
+
+        let (_, results) = self.search_tools(&tools, query, FIND_TOOLS_CANDIDATES, session_id).await?;
+
+        let documents: Vec<ToolDocument> = results
+            .iter()
+            .filter_map(|result| {
+                let tool = tools.get(&result.id)?;
+                Some(ToolDocument {
+                    tool_id: format!("{}@{}/{}", tool.skill_name, tool.instance_name, tool.tool_name),
+                    name: tool.tool_name.clone(),
+                    description: tool.description.clone(),
+                    parameters: tool
+                        .parameters
+                        .iter()
+                        .map(|p| ToolParameterInput {
+                            name: p.name.clone(),
+                            param_type: p.param_type.clone(),
+                            required: p.required,
+                            description: p.description.clone(),
+                        })
+                        .collect(),
+                    example: tool.examples.first().cloned(),
+                    relevance_score: result.rerank_score.unwrap_or(result.score),
+                })
+            })
+            .collect();
+
+        // A single tool may take up to a third of the budget, so a detailed
+        // top match can't crowd out the rest
+        let config = CompressionConfig::with_strategy(strategy)
+            .max_total(token_budget)
+            .max_per_tool((token_budget / 3).max(MIN_TOKENS_PER_TOOL));
+        let compressor = ContextCompressor::with_config(config)
+            .context("Failed to load the tokenizer for context compression")?;
+        let compressed = compressor.compress(documents);
+
+        Ok(format_compressed_tools(query, &compressed, results.len()))
+    }
+
+    /// Record a tool selection in a search session (no-op until search is initialized)
+    pub async fn record_session_selection(&self, session_id: &str, skill: &str, tool: &str, instance: &str) {
+        if let Some(pipeline) = self.search_pipeline.read().await.as_ref() {
+            pipeline.record_session_selection(session_id, SelectedTool {
+                skill: skill.to_string(),
+                tool: tool.to_string(),
+                instance: Some(instance.to_string()),
+            });
+        }
+    }
+
     /// Generate AI-powered examples for a skill's tools
     #[cfg(feature = "ai-ingestion")]
     pub async fn generate_examples(
@@ -1629,21 +1834,25 @@ impl McpServer {
 
         // Build documents
         let documents: Vec<IndexDocument> = tools.iter()
-            .map(|t| IndexDocument {
-                id: format!("{}:{}", skill_name, t.name),
-                content: format!(
-                    "Tool: {}\nDescription: {}\nParameters: {}",
-                    t.name, t.description,
-                    t.parameters.iter()
-                        .map(|p| format!("{} ({})", p.name, p.param_type))
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                ),
-                metadata: DocumentMetadata {
-                    skill_name: Some(skill_name.to_string()),
-                    tool_name: Some(t.name.clone()),
-                    ..Default::default()
-                },
+            .flat_map(|t| {
+                let doc = IndexDocument {
+                    id: format!("{}:{}", skill_name, t.name),
+                    content: format!(
+                        "Tool: {}\nDescription: {}\nParameters: {}",
+                        t.name, t.description,
+                        t.parameters.iter()
+                            .map(|p| format!("{} ({})", p.name, p.param_type))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    metadata: DocumentMetadata {
+                        skill_name: Some(skill_name.to_string()),
+                        tool_name: Some(t.name.clone()),
+                        ..Default::default()
+                    },
+                };
+                let examples = doc.example_documents(t.examples.iter().map(|e| &e.code));
+                std::iter::once(doc).chain(examples)
             })
             .collect();
 
@@ -1707,35 +1916,69 @@ impl McpServer {
     pub async fn run(self) -> Result<()> {
         tracing::info!("Starting MCP server with stdio transport");
 
-        // Discover tools first
+        self.start().await?.serve_stdio().await
+    }
+
+    /// Discover tools and watch skills for changes, returning the server
+    /// ready to take sessions over any transport
+    pub async fn start(self) -> Result<McpSessions> {
         let discovered = self.discover_tools().await?;
         tracing::info!("Discovered {} tools from skills", discovered.len());
 
-        // Create the router with our tools
-        let router = Router::new(self)
-            .with_tool(execute_tool_route())
-            .with_tool(list_skills_tool_route())
-            .with_tool(search_skills_tool_route())
-            .with_tool(generate_examples_tool_route());
+        // Pick up installed, removed and edited skills while running
+        let watcher = self.watch_skills();
 
-        // Run with stdio transport
-        // Note: Don't await the serve call, just await the waiting()
-        router.serve(stdio())
-            .await?
-            .waiting()
-            .await?;
+        Ok(McpSessions {
+            server: self,
+            discovered,
+            _watcher: watcher,
+        })
+    }
 
-        Ok(())
+    /// This server for another client: everything is shared but the
+    /// client's roots and log level
+    fn for_session(&self) -> Self {
+        Self {
+            roots: Arc::new(std::sync::RwLock::new(Vec::new())),
+            log_level: Arc::new(std::sync::RwLock::new(LoggingLevel::Info)),
+            ..self.clone()
+        }
     }
 
     /// Run the MCP server using HTTP streaming transport (SSE)
     pub async fn run_http(host: &str, port: u16, manifest: Option<SkillManifest>) -> Result<()> {
+        tracing::info!("Starting MCP server with HTTP streaming at {}:{}", host, port);
+
+        Self::serve_http(host, port, Self::http_router(manifest)).await
+    }
+
+    /// Serve a router built from [`McpServer::http_router`] (plus any routes
+    /// merged into it, like the [`A2aGateway`](crate::A2aGateway)'s) until Ctrl+C
+    pub async fn serve_http(host: &str, port: u16, router: axum::Router) -> Result<()> {
+        // Bind and serve
+        let addr = format!("{}:{}", host, port);
+        let tcp_listener = tokio::net::TcpListener::bind(&addr).await
+            .map_err(|e| anyhow::anyhow!("Failed to bind to {}: {}", addr, e))?;
+
+        tracing::info!("MCP HTTP server ready at http://{}/mcp", addr);
+
+        axum::serve(tcp_listener, router)
+            .with_graceful_shutdown(async {
+                tokio::signal::ctrl_c().await.ok();
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!("HTTP server error: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Router serving the streamable HTTP transport at `/mcp`, for callers
+    /// that bind and serve it themselves (e.g. behind TLS)
+    pub fn http_router(manifest: Option<SkillManifest>) -> axum::Router {
         use rmcp::transport::streamable_http_server::{
             StreamableHttpService, session::local::LocalSessionManager,
         };
 
-        tracing::info!("Starting MCP server with HTTP streaming at {}:{}", host, port);
-
         // Create factory function that creates a new server instance per session
         let manifest_clone = manifest.clone();
         let server_factory = move || -> std::result::Result<McpServer, std::io::Error> {
@@ -1757,25 +2000,120 @@ impl McpServer {
             Default::default(),
         );
 
-        // Create axum router
-        let router = axum::Router::new().nest_service("/mcp", service);
-
-        // Bind and serve
-        let addr = format!("{}:{}", host, port);
-        let tcp_listener = tokio::net::TcpListener::bind(&addr).await
-            .map_err(|e| anyhow::anyhow!("Failed to bind to {}: {}", addr, e))?;
+        axum::Router::new().nest_service("/mcp", service)
+    }
+}
 
-        tracing::info!("MCP HTTP server ready at http://{}/mcp", addr);
+/// A started MCP server, taking sessions over any number of transports
+///
+/// Sessions share the server's engine, tool cache, search index and stored
+/// outputs, so a client on stdio and another over HTTP see the same skills.
+/// Skill changes are picked up until this is dropped.
+pub struct McpSessions {
+    server: McpServer,
+    discovered: Vec<DiscoveredTool>,
+    _watcher: Option<SkillWatcher>,
+}
 
-        axum::serve(tcp_listener, router)
-            .with_graceful_shutdown(async {
-                tokio::signal::ctrl_c().await.ok();
-            })
-            .await
-            .map_err(|e| anyhow::anyhow!("HTTP server error: {}", e))?;
+impl McpSessions {
+    /// The server sessions are served from
+    pub fn server(&self) -> &McpServer {
+        &self.server
+    }
 
+    /// Serve one session over stdio, until the client disconnects
+    pub async fn serve_stdio(&self) -> Result<()> {
+        // Note: Don't await the serve call, just await the waiting()
+        session_handler(&self.server, &self.discovered)
+            .serve(stdio())
+            .await?
+            .waiting()
+            .await?;
         Ok(())
     }
+
+    /// Router serving the streamable HTTP transport at `/mcp`, a session per
+    /// client
+    pub fn http_router(&self) -> axum::Router {
+        use rmcp::transport::streamable_http_server::{
+            StreamableHttpService, session::local::LocalSessionManager,
+        };
+
+        let server = self.server.clone();
+        let discovered = self.discovered.clone();
+        let service = StreamableHttpService::new(
+            move || Ok(session_handler(&server, &discovered)),
+            LocalSessionManager::default().into(),
+            Default::default(),
+        );
+
+        axum::Router::new().nest_service("/mcp", service)
+    }
+}
+
+/// Handler for a new session, with the tools `[mcp]` asks for
+fn session_handler(server: &McpServer, discovered: &[DiscoveredTool]) -> Box<dyn DynService<RoleServer>> {
+    // Create the router with our tools
+    let workflows = discover_workflows();
+    let server = server.for_session();
+    let mcp = server
+        .manifest()
+        .map(|manifest| manifest.mcp.clone())
+        .unwrap_or_default();
+    let mut routes = vec![
+        list_skills_tool_route(),
+        search_skills_tool_route(),
+        find_tools_tool_route(),
+        generate_examples_tool_route(),
+        get_execution_output_tool_route(),
+        run_workflow_tool_route(&workflows),
+    ];
+    let instance_hints = server.instance_hints(discovered);
+    if mcp.tool_mode.dispatcher() {
+        routes.push(execute_tool_route(&instance_hints));
+    }
+
+    // Each saved workflow is also a tool of its own
+    let mut reserved: HashSet<String> = BUILTIN_TOOLS.iter().map(|name| name.to_string()).collect();
+    for (path, workflow) in workflows {
+        if BUILTIN_TOOLS.contains(&workflow.name.as_str()) || !is_valid_tool_name(&workflow.name) {
+            tracing::warn!(
+                "Workflow '{}' is not exposed as a tool: name is taken or not a valid tool name",
+                workflow.name
+            );
+            continue;
+        }
+        reserved.insert(workflow.name.clone());
+        routes.push(workflow_tool_route(path, &workflow));
+    }
+
+    // With lazy discovery, skill tools are listed once they're loaded
+    if mcp.discovery == McpDiscovery::Lazy {
+        return crate::lazy::LazyRouter::new(server, routes, mcp).into_dyn();
+    }
+
+    // And, in flat mode, each skill tool (`kubernetes__get`)
+    if mcp.tool_mode.flat() {
+        routes.extend(crate::flat::flat_tool_routes(discovered, &mcp, &reserved, &instance_hints));
+    }
+    let mut router = Router::new(server);
+    for route in routes {
+        router = router.with_tool(route);
+    }
+    router.into_dyn()
+}
+
+#[async_trait::async_trait]
+impl StepExecutor for McpServer {
+    async fn execute(
+        &self,
+        skill: &str,
+        instance: &str,
+        tool: &str,
+        args: HashMap<String, serde_json::Value>,
+    ) -> Result<skill_runtime::ExecutionResult> {
+        self.execute_skill_tool(skill, instance, tool, args).await
+    }
 }
 
 // ServerHandler implementation
@@ -1783,22 +2121,110 @@ impl ServerHandler for McpServer {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
             protocol_version: ProtocolVersion::V_2024_11_05,
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_completions()
+                .enable_logging()
+                .build(),
             server_info: Implementation::from_build_env(),
-            instructions: Some(
+            instructions: Some(if self.manifest().is_some_and(|m| m.mcp.discovery == McpDiscovery::Lazy) {
+                "Skill Engine MCP Server - Execute installed skills and their tools. \
+                 Skill tools aren't listed until loaded: call a skill's tool or `load_skill_tools(skill)` \
+                 to add them, or `find_tools` to find the right skill first. \
+                 Page through truncated output with `get_execution_output`. \
+                 Saved workflows are tools of their own; `run_workflow` runs any workflow by name or path."
+                    .to_string()
+            } else if self.manifest().is_some_and(|m| !m.mcp.tool_mode.dispatcher()) {
+                "Skill Engine MCP Server - Execute installed skills and their tools. \
+                 Each skill tool is a tool of its own, named `<skill>__<tool>` unless aliased. \
+                 Use `list_skills` to discover available skills. \
+                 Page through truncated output with `get_execution_output`. \
+                 Saved workflows are tools of their own; `run_workflow` runs any workflow by name or path."
+                    .to_string()
+            } else {
                 "Skill Engine MCP Server - Execute installed skills and their tools. \
                  Use `list_skills` to discover available skills, then `execute` to run tools. \
+                 Page through truncated output with `get_execution_output`. \
+                 Saved workflows are tools of their own; `run_workflow` runs any workflow by name or path. \
                  Example: execute(skill='kubernetes', tool='get', args={resource: 'pods'})"
-                    .to_string(),
-            ),
+                    .to_string()
+            }),
         }
     }
+
+    fn on_initialized(
+        &self,
+        context: NotificationContext<RoleServer>,
+    ) -> impl std::future::Future<Output = ()> + Send + '_ {
+        self.refresh_roots(context.peer);
+        std::future::ready(())
+    }
+
+    /// Set the lowest level of the log messages tool calls send
+    fn set_level(
+        &self,
+        request: SetLevelRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> impl std::future::Future<Output = Result<(), McpError>> + Send + '_ {
+        *self.log_level.write().unwrap_or_else(|e| e.into_inner()) = request.level;
+        std::future::ready(Ok(()))
+    }
+
+    fn on_roots_list_changed(
+        &self,
+        context: NotificationContext<RoleServer>,
+    ) -> impl std::future::Future<Output = ()> + Send + '_ {
+        self.refresh_roots(context.peer);
+        std::future::ready(())
+    }
+
+    /// Complete `skill` and `instance` arguments; instances are those of the
+    /// `skill` argument when it's already filled in, else of every skill
+    async fn complete(
+        &self,
+        request: CompleteRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CompleteResult, McpError> {
+        let candidates = match request.argument.name.as_str() {
+            "skill" => self.skill_names().await,
+            "instance" => {
+                let skill = request
+                    .context
+                    .as_ref()
+                    .and_then(|context| context.arguments.as_ref())
+                    .and_then(|arguments| arguments.get("skill"))
+                    .cloned();
+                let skills = match skill {
+                    Some(skill) => vec![skill],
+                    None => self.skill_names().await,
+                };
+                skills
+                    .iter()
+                    .flat_map(|skill| self.skill_instances(skill))
+                    .map(|instance| instance.name)
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+
+        let (values, total) = complete_values(candidates, &request.argument.value);
+        Ok(CompleteResult {
+            completion: CompletionInfo {
+                has_more: Some(total > values.len()),
+                total: Some(total as u32),
+                values,
+            },
+        })
+    }
 }
 
 // Tool route definitions
 
 /// Create the execute tool route with context engineering features
-fn execute_tool_route() -> ToolRoute<McpServer> {
+///
+/// Skills with several instances are listed in its description, so agents
+/// pass `instance` instead of always running the default.
+fn execute_tool_route(instance_hints: &BTreeMap<String, Vec<SkillInstance>>) -> ToolRoute<McpServer> {
     use futures::FutureExt;
     use rmcp::handler::server::tool::ToolCallContext;
 
@@ -1851,119 +2277,225 @@ fn execute_tool_route() -> ToolRoute<McpServer> {
             },
             "format": {
                 "type": "string",
-                "enum": ["json", "lines", "count", "compact"],
-                "description": "Transform output: 'json' (pretty-print), 'lines' (array), 'count' (line count), 'compact' (remove whitespace)"
+                "enum": ["json", "lines", "count", "compact", "summary"],
+                "description": "Transform output: 'json' (pretty-print), 'lines' (array), 'count' (line count), 'compact' (remove whitespace), 'summary' (summarize output longer than max_output, default 4000, with the client's LLM; truncates if the client doesn't support sampling)"
             },
             "jq": {
                 "type": "string",
-                "description": "JSONPath to extract from JSON output. Examples: '.items[].name', '.metadata', '.items[0:5]'"
+                "description": "jq filter applied to JSON output. Examples: '.items[].name', '.items[0:5]', '.items[] | select(.status == \"Running\") | .name', '.items | map(.name)'"
             },
             "include_metadata": {
                 "type": "boolean",
                 "description": "Include execution metadata (timing, truncation info, original size)"
+            },
+            "session_id": {
+                "type": "string",
+                "description": "Optional search session ID (as used with search_skills). Records this tool as selected for follow-up searches."
             }
         },
         "required": ["skill", "tool"]
     })).unwrap();
 
-    let tool = Tool {
-        name: Cow::Borrowed("execute"),
-        title: None,
-        description: Some(Cow::Borrowed(
+    let mut description = String::from(
             "Execute a skill tool with context engineering features. \
              Use max_output to limit response size, grep to filter, jq to extract JSON fields. \
              Examples:\n\
              - Basic: execute(skill='k8s', tool='get', args={resource:'pods'})\n\
              - With filter: execute(skill='k8s', tool='get', args={...}, grep='Running', head=10)\n\
              - JSON extract: execute(skill='k8s', tool='get', args={...}, jq='.items[].metadata.name')\n\
-             - Size limit: execute(skill='k8s', tool='logs', args={...}, max_output=4000, truncate='tail')"
-        )),
+             - jq filter: execute(skill='k8s', tool='get', args={...}, jq='.items[] | select(.status.phase == \"Running\") | .metadata.name')\n\
+             - Size limit: execute(skill='k8s', tool='logs', args={...}, max_output=4000, truncate='tail')\n\
+             - Summary: execute(skill='k8s', tool='logs', args={...}, format='summary', max_output=2000)"
+    );
+    if !instance_hints.is_empty() {
+        description.push_str("\n\nSkills with several instances (pass `instance` to pick one):");
+        for (skill, instances) in instance_hints {
+            description.push_str(&format!("\n- {}: {}", skill, instances_label(instances)));
+        }
+    }
+
+    let tool = Tool {
+        name: Cow::Borrowed("execute"),
+        title: None,
+        description: Some(Cow::Owned(description)),
         input_schema: Arc::new(execute_schema),
         output_schema: None,
-        annotations: None,
+        annotations: Some(ToolAnnotations {
+            title: None,
+            read_only_hint: Some(false),
+            destructive_hint: Some(true),
+            idempotent_hint: Some(false),
+            open_world_hint: Some(true),
+        }),
         icons: None,
         meta: None,
     };
 
     ToolRoute::new_dyn(tool, |ctx: ToolCallContext<'_, McpServer>| {
         async move {
-            let start_time = std::time::Instant::now();
             let args = ctx.arguments.clone().unwrap_or_default();
             let request: ExecuteSkillRequest = serde_json::from_value(serde_json::Value::Object(args))
                 .map_err(|e| McpError::invalid_params(format!("Invalid parameters: {}", e), None))?;
+            execute_request(ctx.service, &ctx.request_context, request).await
+        }.boxed()
+    })
+}
 
-            // Execute the skill tool
-            let result = ctx.service
-                .execute_skill_tool(&request.skill, &request.instance, &request.tool, request.args)
-                .await
-                .map_err(|e| McpError::internal_error(format!("Skill execution failed: {}", e), None))?;
-
-            let elapsed = start_time.elapsed();
-
-            if result.success {
-                // Apply context engineering transformations
-                let processed = process_output(
-                    &result.output,
-                    request.max_output,
-                    request.truncate.as_deref(),
-                    request.grep.as_deref(),
-                    request.grep_invert.unwrap_or(false),
-                    request.head,
-                    request.tail,
-                    request.format.as_deref(),
-                    request.jq.as_deref(),
-                );
+/// Run a tool for `execute` (or a flat tool) and shape its output, sending
+/// what it traces to the client as log messages
+pub(crate) async fn execute_request(
+    server: &McpServer,
+    context: &RequestContext<RoleServer>,
+    request: ExecuteSkillRequest,
+) -> std::result::Result<CallToolResult, McpError> {
+    let level = server.log_level();
+    crate::logging::forward(&context.peer, level, execute_and_shape(server, context, request)).await
+}
 
-                // Build response
-                let output = if request.include_metadata.unwrap_or(false) {
-                    // Include rich metadata for debugging/transparency
-                    let mut response = String::new();
+async fn execute_and_shape(
+    server: &McpServer,
+    context: &RequestContext<RoleServer>,
+    request: ExecuteSkillRequest,
+) -> std::result::Result<CallToolResult, McpError> {
+    let start_time = std::time::Instant::now();
+    let progress = Progress::start(context);
 
-                    if processed.truncated || !processed.processing.is_empty() {
-                        response.push_str("📊 **Execution Metadata**\n");
-                        response.push_str(&format!("- Execution time: {:?}\n", elapsed));
-                        response.push_str(&format!("- Original size: {} chars\n", processed.original_length));
-                        response.push_str(&format!("- Final size: {} chars\n", processed.final_length));
+    if let Some(ref session_id) = request.session_id {
+        server.record_session_selection(session_id, &request.skill, &request.tool, &request.instance).await;
+    }
 
-                        if processed.truncated {
-                            response.push_str("- ⚠️ Output was truncated\n");
-                        }
+    // Execute the skill tool
+    let mut result = match progress
+        .track(server.execute_skill_tool(&request.skill, &request.instance, &request.tool, request.args))
+        .await
+    {
+        Ok(result) => result,
+        // A tool error rather than a protocol error, so the agent sees the
+        // suggestions and can retry with fixed arguments
+        Err(e) if e.is::<ArgValidationError>() => {
+            return Ok(CallToolResult::error(vec![Content::text(e.to_string())]));
+        }
+        Err(e) => {
+            return Err(McpError::internal_error(format!("Skill execution failed: {}", e), None));
+        }
+    };
 
-                        if let Some(matches) = processed.grep_matches {
-                            response.push_str(&format!("- Grep matches: {} lines\n", matches));
-                        }
+    let elapsed = start_time.elapsed();
+    if result.duration_ms.is_none() {
+        result = result.with_duration(elapsed);
+    }
 
-                        if !processed.processing.is_empty() {
-                            response.push_str(&format!("- Processing: {}\n", processed.processing.join(" → ")));
-                        }
+    if result.success {
+        // Summaries replace max_output truncation
+        let summarize = request.format.as_deref() == Some("summary");
+
+        // Apply context engineering transformations
+        let options = OutputOptions {
+            max_output: if summarize { None } else { request.max_output },
+            truncate: request.truncate.clone(),
+            grep: request.grep.clone(),
+            grep_invert: request.grep_invert.unwrap_or(false),
+            head: request.head,
+            tail: request.tail,
+            format: request.format.clone(),
+            jq: request.jq.clone(),
+        };
+        let mut processed = match options.apply(&result.output) {
+            Ok(processed) => processed,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "❌ **Output Processing Failed**\n\n**Error:** {:#}\n\n\
+                     💡 **Tip:** jq filters only apply to JSON output",
+                    e
+                ))]));
+            }
+        };
 
-                        response.push_str("\n---\n\n");
-                    }
+        if summarize {
+            let tool_label = format!("{}:{}", request.skill, request.tool);
+            summarize_output(&context.peer, &mut processed, request.max_output, &tool_label).await;
+        }
 
-                    response.push_str(&processed.content);
-                    response
-                } else {
-                    processed.content
-                };
+        // Keep the full output so the agent can page through it
+        let output_id = processed.truncated.then(|| server.outputs.insert(result.output.as_str()));
 
-                Ok(CallToolResult::success(vec![Content::text(output)]))
-            } else {
-                // Error response with helpful context
-                let error_msg = result.error_message.unwrap_or_else(|| "Unknown error".to_string());
-                let error_output = format!(
-                    "❌ **Execution Failed**\n\n\
-                     **Skill:** {} | **Tool:** {} | **Instance:** {}\n\n\
-                     **Error:** {}\n\n\
-                     💡 **Tips:**\n\
-                     - Use `list_skills` to verify the skill/tool exists\n\
-                     - Use `search_skills` to find the right tool for your task\n\
-                     - Check that required arguments are provided",
-                    request.skill, request.tool, request.instance, error_msg
-                );
-                Ok(CallToolResult::error(vec![Content::text(error_output)]))
+        // Structured clients get the shaped stdout with stderr kept apart
+        let structured = skill_runtime::ExecutionResult {
+            output: processed.content.clone(),
+            truncated: processed.truncated,
+            ..result
+        }
+        .structured();
+
+        if let Some(output_id) = output_id {
+            tracing::info!(
+                skill = %request.skill,
+                tool = %request.tool,
+                output_id = %output_id,
+                shown = processed.final_length,
+                total = processed.original_length,
+                "Output truncated"
+            );
+            processed.content.push_str(&format!(
+                "\n\n📄 Output truncated ({} of {} characters shown). Page through the full output with \
+                 get_execution_output(id='{}', offset=0, limit={}) instead of re-running the tool.",
+                processed.final_length, processed.original_length, output_id, DEFAULT_PAGE_SIZE
+            ));
+        }
+
+        // Build response
+        let output = if request.include_metadata.unwrap_or(false) {
+            // Include rich metadata for debugging/transparency
+            let mut response = String::new();
+
+            if processed.truncated || !processed.processing.is_empty() {
+                response.push_str("📊 **Execution Metadata**\n");
+                response.push_str(&format!("- Execution time: {:?}\n", elapsed));
+                response.push_str(&format!("- Original size: {} chars\n", processed.original_length));
+                response.push_str(&format!("- Final size: {} chars\n", processed.final_length));
+
+                if processed.truncated {
+                    response.push_str("- ⚠️ Output was truncated\n");
+                }
+
+                if let Some(matches) = processed.grep_matches {
+                    response.push_str(&format!("- Grep matches: {} lines\n", matches));
+                }
+
+                if !processed.processing.is_empty() {
+                    response.push_str(&format!("- Processing: {}\n", processed.processing.join(" → ")));
+                }
+
+                response.push_str("\n---\n\n");
             }
-        }.boxed()
-    })
+
+            response.push_str(&processed.content);
+            response
+        } else {
+            processed.content
+        };
+
+        let mut call_result = CallToolResult::success(vec![Content::text(output)]);
+        call_result.structured_content = Some(structured);
+        Ok(call_result)
+    } else {
+        // Error response with helpful context
+        let structured = result.structured();
+        let error_msg = result.error_message.unwrap_or_else(|| "Unknown error".to_string());
+        let error_output = format!(
+            "❌ **Execution Failed**\n\n\
+             **Skill:** {} | **Tool:** {} | **Instance:** {}\n\n\
+             **Error:** {}\n\n\
+             💡 **Tips:**\n\
+             - Use `list_skills` to verify the skill/tool exists\n\
+             - Use `search_skills` to find the right tool for your task\n\
+             - Check that required arguments are provided",
+            request.skill, request.tool, request.instance, error_msg
+        );
+        let mut call_result = CallToolResult::error(vec![Content::text(error_output)]);
+        call_result.structured_content = Some(structured);
+        Ok(call_result)
+    }
 }
 
 /// Create the list_skills tool route
@@ -1997,7 +2529,13 @@ fn list_skills_tool_route() -> ToolRoute<McpServer> {
         description: Some(Cow::Borrowed("List all available skills and their tools. Supports pagination with offset/limit parameters.")),
         input_schema: Arc::new(list_schema),
         output_schema: None,
-        annotations: None,
+        annotations: Some(ToolAnnotations {
+            title: None,
+            read_only_hint: Some(true),
+            destructive_hint: Some(false),
+            idempotent_hint: Some(true),
+            open_world_hint: Some(false),
+        }),
         icons: None,
         meta: None,
     };
@@ -2028,6 +2566,11 @@ pub struct SearchSkillsRequest {
     #[serde(default = "default_top_k")]
     #[schemars(description = "Maximum number of results to return (default: 5)")]
     pub top_k: usize,
+
+    /// Optional conversational session ID
+    #[serde(default)]
+    #[schemars(description = "Optional session ID. Follow-up queries like 'now for staging' are expanded using earlier queries in the same session.")]
+    pub session_id: Option<String>,
 }
 
 fn default_top_k() -> usize {
@@ -2071,6 +2614,10 @@ fn search_skills_tool_route() -> ToolRoute<McpServer> {
                 "type": "integer",
                 "description": "Maximum number of results to return (default: 5)",
                 "default": 5
+            },
+            "session_id": {
+                "type": "string",
+                "description": "Optional session ID. Reuse it across related searches so follow-ups like 'now for staging' or 'same but delete' are expanded using earlier queries."
             }
         },
         "required": ["query"]
@@ -2082,7 +2629,13 @@ fn search_skills_tool_route() -> ToolRoute<McpServer> {
         description: Some(Cow::Borrowed("Search for relevant skills and tools using natural language. Uses semantic vector search to find the best matching tools for your task.")),
         input_schema: Arc::new(search_schema),
         output_schema: None,
-        annotations: None,
+        annotations: Some(ToolAnnotations {
+            title: None,
+            read_only_hint: Some(true),
+            destructive_hint: Some(false),
+            idempotent_hint: Some(true),
+            open_world_hint: Some(false),
+        }),
         icons: None,
         meta: None,
     };
@@ -2093,7 +2646,9 @@ fn search_skills_tool_route() -> ToolRoute<McpServer> {
             let request: SearchSkillsRequest = serde_json::from_value(serde_json::Value::Object(args))
                 .map_err(|e| McpError::invalid_params(format!("Invalid parameters: {}", e), None))?;
 
-            let output = ctx.service.search_skills(&request.query, request.top_k).await
+            let output = ctx.service
+                .search_skills(&request.query, request.top_k, request.session_id.as_deref())
+                .await
                 .map_err(|e| McpError::internal_error(format!("Search failed: {}", e), None))?;
 
             Ok(CallToolResult::success(vec![Content::text(output)]))
@@ -2101,6 +2656,187 @@ fn search_skills_tool_route() -> ToolRoute<McpServer> {
     })
 }
 
+/// Request to find tools within a token budget
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct FindToolsRequest {
+    /// Natural language description of the task
+    #[schemars(description = "Natural language description of the task (e.g., 'restart a deployment')")]
+    pub query: String,
+
+    /// Most tokens the result may take
+    #[serde(default = "default_token_budget")]
+    #[schemars(description = "Most tokens the tool descriptions may take (default: 800)")]
+    pub token_budget: usize,
+
+    /// How much detail each tool gets
+    #[serde(default)]
+    #[schemars(description = "progressive (default), template or extractive")]
+    pub strategy: Option<String>,
+
+    /// Optional conversational session ID
+    #[serde(default)]
+    #[schemars(description = "Optional session ID, shared with search_skills, for expanding follow-up queries")]
+    pub session_id: Option<String>,
+}
+
+fn default_token_budget() -> usize {
+    DEFAULT_TOKEN_BUDGET
+}
+
+/// Create the find_tools tool route
+fn find_tools_tool_route() -> ToolRoute<McpServer> {
+    use futures::FutureExt;
+    use rmcp::handler::server::tool::ToolCallContext;
+
+    let schema: serde_json::Map<String, serde_json::Value> = serde_json::from_value(serde_json::json!({
+        "type": "object",
+        "properties": {
+            "query": {
+                "type": "string",
+                "description": "Natural language description of the task (e.g., 'restart a deployment', 'read a file from S3')"
+            },
+            "token_budget": {
+                "type": "integer",
+                "description": "Most tokens the tool descriptions may take (default: 800). Tools are dropped from the end when they don't fit.",
+                "minimum": TOKEN_BUDGET_RANGE.start(),
+                "maximum": TOKEN_BUDGET_RANGE.end(),
+                "default": DEFAULT_TOKEN_BUDGET
+            },
+            "strategy": {
+                "type": "string",
+                "enum": ["progressive", "template", "extractive"],
+                "description": "How much detail each tool gets: progressive gives the best match its full parameters and lower ranks less (default); template gives every tool a summary and parameters; extractive gives the first sentence and parameters",
+                "default": "progressive"
+            },
+            "session_id": {
+                "type": "string",
+                "description": "Optional session ID, shared with search_skills, so follow-ups like 'now for staging' are expanded using earlier queries."
+            }
+        },
+        "required": ["query"]
+    })).unwrap();
+
+    let tool = Tool {
+        name: Cow::Borrowed("find_tools"),
+        title: None,
+        description: Some(Cow::Borrowed(
+            "Find the tools for a task mid-conversation, as compact descriptions sized to a token budget. \
+             Cheaper than list_skills or search_skills when you only need enough to call the right tool. \
+             Example: find_tools(query='scale a deployment', token_budget=400)"
+        )),
+        input_schema: Arc::new(schema),
+        output_schema: None,
+        annotations: Some(ToolAnnotations {
+            title: None,
+            read_only_hint: Some(true),
+            destructive_hint: Some(false),
+            idempotent_hint: Some(true),
+            open_world_hint: Some(false),
+        }),
+        icons: None,
+        meta: None,
+    };
+
+    ToolRoute::new_dyn(tool, |ctx: ToolCallContext<'_, McpServer>| {
+        async move {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let request: FindToolsRequest = serde_json::from_value(serde_json::Value::Object(args))
+                .map_err(|e| McpError::invalid_params(format!("Invalid parameters: {}", e), None))?;
+
+            let strategy = match request.strategy.as_deref() {
+                None => CompressionStrategy::Progressive,
+                Some(name) => match name.parse() {
+                    Ok(CompressionStrategy::None) | Err(_) => {
+                        return Err(McpError::invalid_params(
+                            format!("Unknown strategy '{}'. Options: progressive, template, extractive", name),
+                            None,
+                        ));
+                    }
+                    Ok(strategy) => strategy,
+                },
+            };
+            let budget = request
+                .token_budget
+                .clamp(*TOKEN_BUDGET_RANGE.start(), *TOKEN_BUDGET_RANGE.end());
+
+            let output = ctx.service
+                .find_tools(&request.query, budget, strategy, request.session_id.as_deref())
+                .await
+                .map_err(|e| McpError::internal_error(format!("Search failed: {:#}", e), None))?;
+
+            Ok(CallToolResult::success(vec![Content::text(output)]))
+        }.boxed()
+    })
+}
+
+/// Compressed tool contexts as a compact list, with how much of the budget
+/// they used
+fn format_compressed_tools(query: &str, result: &CompressionResult, matches: usize) -> String {
+    let mut output = format!("## Tools for: \"{}\"\n\n", query);
+
+    if matches == 0 {
+        output.push_str("No matching tools found. Try different keywords or `list_skills`.\n");
+        return output;
+    }
+    if result.tools.is_empty() {
+        output.push_str("No tool fits in this token budget; call again with a larger `token_budget`.\n");
+        return output;
+    }
+
+    output.push_str(&format!(
+        "{} of {} matches, {} tokens.\n\n",
+        result.tools.len(),
+        matches,
+        result.total_tokens
+    ));
+
+    for tool in &result.tools {
+        output.push_str(&format!(
+            "{}. `{}` ({}%) - {}\n",
+            tool.rank,
+            tool.tool_id,
+            (tool.relevance_score * 100.0) as u32,
+            tool.summary
+        ));
+        if let Some(hint) = &tool.execution_hint {
+            output.push_str(&format!("   {}\n", hint));
+        }
+        for param in &tool.parameters {
+            let required = if param.required { ", required" } else { "" };
+            output.push_str(&format!("   - `{}` ({}{})", param.name, param.param_type, required));
+            if !param.description.is_empty() {
+                output.push_str(&format!(": {}", param.description));
+            }
+            output.push('\n');
+        }
+    }
+
+    output.push_str("\nIDs are `skill@instance/tool`; run one with `execute`.\n");
+    output
+}
+
+/// Request to page through a truncated execution's full output
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetExecutionOutputRequest {
+    /// Output ID returned by a truncated execute call
+    #[schemars(description = "Output ID from a truncated execute result")]
+    pub id: String,
+
+    /// Offset to start reading from
+    #[serde(default)]
+    #[schemars(description = "Character offset to start from (default: 0)")]
+    pub offset: usize,
+
+    /// Maximum characters to return
+    #[serde(default = "default_page_size")]
+    #[schemars(description = "Maximum characters to return (default: 8000)")]
+    pub limit: usize,
+}
+
+fn default_page_size() -> usize {
+    DEFAULT_PAGE_SIZE
+}
+
 /// Create the generate_examples tool route
 fn generate_examples_tool_route() -> ToolRoute<McpServer> {
     use futures::FutureExt;
@@ -2136,7 +2872,13 @@ fn generate_examples_tool_route() -> ToolRoute<McpServer> {
         )),
         input_schema: Arc::new(schema),
         output_schema: None,
-        annotations: None,
+        annotations: Some(ToolAnnotations {
+            title: None,
+            read_only_hint: Some(true),
+            destructive_hint: Some(false),
+            idempotent_hint: Some(false),
+            open_world_hint: Some(true),
+        }),
         icons: None,
         meta: None,
     };
@@ -2158,3 +2900,287 @@ fn generate_examples_tool_route() -> ToolRoute<McpServer> {
         }.boxed()
     })
 }
+
+/// Create the get_execution_output tool route
+fn get_execution_output_tool_route() -> ToolRoute<McpServer> {
+    use futures::FutureExt;
+    use rmcp::handler::server::tool::ToolCallContext;
+
+    let schema: serde_json::Map<String, serde_json::Value> = serde_json::from_value(serde_json::json!({
+        "type": "object",
+        "properties": {
+            "id": {
+                "type": "string",
+                "description": "Output ID from a truncated execute result"
+            },
+            "offset": {
+                "type": "integer",
+                "description": "Character offset to start from (default: 0). Use the next offset from the previous page to continue.",
+                "minimum": 0,
+                "default": 0
+            },
+            "limit": {
+                "type": "integer",
+                "description": "Maximum characters to return (default: 8000)",
+                "minimum": 1,
+                "default": DEFAULT_PAGE_SIZE
+            }
+        },
+        "required": ["id"]
+    })).unwrap();
+
+    let tool = Tool {
+        name: Cow::Borrowed("get_execution_output"),
+        title: None,
+        description: Some(Cow::Borrowed(
+            "Page through the full output of an execute call that was truncated, without re-running the tool. \
+             Truncated results end with the output ID to use. \
+             Example: get_execution_output(id='...', offset=8000, limit=8000)"
+        )),
+        input_schema: Arc::new(schema),
+        output_schema: None,
+        annotations: Some(ToolAnnotations {
+            title: None,
+            read_only_hint: Some(true),
+            destructive_hint: Some(false),
+            idempotent_hint: Some(true),
+            open_world_hint: Some(false),
+        }),
+        icons: None,
+        meta: None,
+    };
+
+    ToolRoute::new_dyn(tool, |ctx: ToolCallContext<'_, McpServer>| {
+        async move {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let request: GetExecutionOutputRequest = serde_json::from_value(serde_json::Value::Object(args))
+                .map_err(|e| McpError::invalid_params(format!("Invalid parameters: {}", e), None))?;
+
+            let range = OutputRange { offset: request.offset, limit: request.limit };
+            let Some(page) = ctx.service.outputs.page(&request.id, range) else {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "❌ No stored output '{}'. Only recent truncated outputs are kept; re-run the tool if it has expired.",
+                    request.id
+                ))]));
+            };
+
+            let end = page.offset + page.content.len();
+            let mut output = format!(
+                "📄 Characters {}–{} of {}\n\n{}",
+                page.offset, end, page.total_length, page.content
+            );
+            match page.next_offset {
+                Some(next) => output.push_str(&format!(
+                    "\n\n... more output: get_execution_output(id='{}', offset={}, limit={})",
+                    request.id, next, request.limit
+                )),
+                None => output.push_str("\n\n(end of output)"),
+            }
+
+            Ok(CallToolResult::success(vec![Content::text(output)]))
+        }.boxed()
+    })
+}
+
+/// Names of the built-in tools, which workflows can't shadow
+const BUILTIN_TOOLS: &[&str] = &[
+    "execute",
+    "list_skills",
+    "search_skills",
+    "find_tools",
+    "generate_examples",
+    "get_execution_output",
+    "run_workflow",
+    "load_skill_tools",
+];
+
+/// MCP tool names: 1-64 letters, digits, `_` or `-`
+pub(crate) fn is_valid_tool_name(name: &str) -> bool {
+    (1..=64).contains(&name.len())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Request to run a workflow
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RunWorkflowRequest {
+    /// Workflow name or file path
+    #[schemars(description = "Workflow name (from ./workflows or ~/.skill-engine/workflows) or file path")]
+    pub workflow: String,
+
+    /// Workflow inputs
+    #[serde(default)]
+    #[schemars(description = "Workflow inputs by name")]
+    pub inputs: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Create the run_workflow tool route
+///
+/// The description lists the workflows found when the server starts.
+fn run_workflow_tool_route(workflows: &[(PathBuf, Workflow)]) -> ToolRoute<McpServer> {
+    use futures::FutureExt;
+    use rmcp::handler::server::tool::ToolCallContext;
+
+    let schema: serde_json::Map<String, serde_json::Value> = serde_json::from_value(serde_json::json!({
+        "type": "object",
+        "properties": {
+            "workflow": {
+                "type": "string",
+                "description": "Workflow name (from ./workflows or ~/.skill-engine/workflows) or file path"
+            },
+            "inputs": {
+                "type": "object",
+                "description": "Workflow inputs by name",
+                "additionalProperties": true
+            }
+        },
+        "required": ["workflow"]
+    })).unwrap();
+
+    let mut description = String::from(
+        "Run a multi-step workflow that chains skill tools, passing results between steps, \
+         as a single call. Returns each step's status and the last step's output. \
+         Example: run_workflow(workflow='deploy', inputs={namespace: 'staging'})",
+    );
+    if !workflows.is_empty() {
+        description.push_str("\n\nAvailable workflows:");
+        for (_, workflow) in workflows {
+            description.push_str(&format!("\n- {}", workflow.name));
+            if let Some(summary) = &workflow.description {
+                description.push_str(&format!(": {}", summary));
+            }
+            if !workflow.inputs.is_empty() {
+                let inputs: Vec<&str> = workflow.inputs.keys().map(String::as_str).collect();
+                description.push_str(&format!(" (inputs: {})", inputs.join(", ")));
+            }
+        }
+    }
+
+    let tool = Tool {
+        name: Cow::Borrowed("run_workflow"),
+        title: None,
+        description: Some(Cow::Owned(description)),
+        input_schema: Arc::new(schema),
+        output_schema: None,
+        annotations: Some(ToolAnnotations {
+            title: None,
+            read_only_hint: Some(false),
+            destructive_hint: Some(true),
+            idempotent_hint: Some(false),
+            open_world_hint: Some(true),
+        }),
+        icons: None,
+        meta: None,
+    };
+
+    ToolRoute::new_dyn(tool, |ctx: ToolCallContext<'_, McpServer>| {
+        async move {
+            let args = ctx.arguments.clone().unwrap_or_default();
+            let request: RunWorkflowRequest = serde_json::from_value(serde_json::Value::Object(args))
+                .map_err(|e| McpError::invalid_params(format!("Invalid parameters: {}", e), None))?;
+
+            let path = resolve_workflow(&request.workflow)
+                .map_err(|e| McpError::invalid_params(format!("{:#}", e), None))?;
+            run_workflow(ctx.service, &ctx.request_context, &path, request.inputs).await
+        }.boxed()
+    })
+}
+
+/// Create the tool for one saved workflow, with its inputs as parameters
+fn workflow_tool_route(path: PathBuf, workflow: &Workflow) -> ToolRoute<McpServer> {
+    use futures::FutureExt;
+    use rmcp::handler::server::tool::ToolCallContext;
+
+    let mut description = workflow
+        .description
+        .clone()
+        .unwrap_or_else(|| format!("Run the '{}' workflow", workflow.name));
+    let steps: Vec<&str> = workflow.steps.iter().map(|step| step.tool.as_str()).collect();
+    description.push_str(&format!("\n\nWorkflow steps: {}", steps.join(" → ")));
+
+    let tool = Tool {
+        name: Cow::Owned(workflow.name.clone()),
+        title: None,
+        description: Some(Cow::Owned(description)),
+        input_schema: Arc::new(workflow.input_schema()),
+        output_schema: None,
+        annotations: Some(ToolAnnotations {
+            title: Some(format!("workflow:{}", workflow.name)),
+            read_only_hint: Some(false),
+            destructive_hint: Some(true),
+            idempotent_hint: Some(false),
+            open_world_hint: Some(true),
+        }),
+        icons: None,
+        meta: None,
+    };
+
+    ToolRoute::new_dyn(tool, move |ctx: ToolCallContext<'_, McpServer>| {
+        let path = path.clone();
+        async move {
+            let inputs = ctx.arguments.clone().unwrap_or_default();
+            run_workflow(ctx.service, &ctx.request_context, &path, inputs).await
+        }.boxed()
+    })
+}
+
+/// Run a workflow file, reloaded so edits apply without a restart
+async fn run_workflow(
+    server: &McpServer,
+    context: &RequestContext<RoleServer>,
+    path: &std::path::Path,
+    inputs: serde_json::Map<String, serde_json::Value>,
+) -> std::result::Result<CallToolResult, McpError> {
+    let workflow = Workflow::load(path)
+        .map_err(|e| McpError::invalid_params(format!("{:#}", e), None))?;
+
+    let progress = Progress::start(context);
+    let run = progress.track(workflow.run_recorded(inputs, server, Some(&progress)));
+    let (run, job_id) = crate::logging::forward(&context.peer, server.log_level(), run)
+        .await
+        .map_err(|e| McpError::invalid_params(format!("{:#}", e), None))?;
+
+    let mut output = format_workflow_run(&run);
+    if let Some(job_id) = job_id {
+        output.push_str(&format!("\n\nRecorded as job {}", job_id));
+    }
+
+    if run.status == RunStatus::Succeeded {
+        Ok(CallToolResult::success(vec![Content::text(output)]))
+    } else {
+        Ok(CallToolResult::error(vec![Content::text(output)]))
+    }
+}
+
+/// Per-step summary of a workflow run, followed by the last output
+fn format_workflow_run(run: &WorkflowRun) -> String {
+    let mut output = match run.status {
+        RunStatus::Succeeded => format!("✅ **Workflow '{}' succeeded**\n", run.workflow),
+        _ => format!("❌ **Workflow '{}' failed**\n", run.workflow),
+    };
+
+    for step in &run.steps {
+        let icon = match step.status {
+            RunStatus::Succeeded => "✓",
+            RunStatus::Failed => "✗",
+            RunStatus::Skipped => "○",
+            RunStatus::Pending | RunStatus::Running => "…",
+        };
+        output.push_str(&format!("\n{} {} ({}) - {}", icon, step.id, step.tool, step.status));
+        if step.attempts > 1 {
+            output.push_str(&format!(", {} attempts", step.attempts));
+        }
+        if let Some(error) = &step.error {
+            output.push_str(&format!("\n  **Error:** {}", error));
+        }
+    }
+
+    let last_output = run
+        .steps
+        .iter()
+        .rev()
+        .find_map(|step| step.output.as_deref().filter(|o| !o.is_empty()));
+    if let Some(last_output) = last_output {
+        output.push_str(&format!("\n\n---\n\n{}", last_output));
+    }
+    output
+}
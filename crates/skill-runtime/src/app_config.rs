@@ -0,0 +1,199 @@
+//! App config - execution and history settings in `~/.skill-engine/config.toml`
+//!
+//! `skill setup`, the HTTP server and its `/api/config` endpoint all read
+//! and write these settings through a [`ConfigStore`]:
+//!
+//! ```toml
+//! default_timeout_secs = 30          # tool executions without their own timeout
+//! max_concurrent_executions = 8      # unset: the server's default
+//! enable_history = true
+//! max_history_entries = 1000         # executions kept in memory per namespace
+//! ```
+//!
+//! Every change is saved and published to [`ConfigStore::subscribe`], so
+//! components holding on to a setting apply it without a restart. Edits made
+//! to the file by another process are picked up by [`ConfigStore::reload`].
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+
+/// Name of the settings file in `~/.skill-engine`
+pub const APP_CONFIG_FILE: &str = "config.toml";
+
+/// Settings shared by the CLI and the HTTP server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Timeout for tool executions that don't set their own, in seconds
+    pub default_timeout_secs: u64,
+    /// Tool executions allowed to run at once; the server's default if unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_executions: Option<usize>,
+    /// Whether executions are recorded in history
+    pub enable_history: bool,
+    /// Executions kept in the in-memory history
+    pub max_history_entries: usize,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            default_timeout_secs: 30,
+            max_concurrent_executions: None,
+            enable_history: true,
+            max_history_entries: 1000,
+        }
+    }
+}
+
+impl AppSettings {
+    /// Check that every setting is usable
+    pub fn validate(&self) -> Result<()> {
+        if self.default_timeout_secs == 0 {
+            anyhow::bail!("default_timeout_secs must be at least 1");
+        }
+        if self.max_concurrent_executions == Some(0) {
+            anyhow::bail!("max_concurrent_executions must be at least 1");
+        }
+        if self.max_history_entries == 0 {
+            anyhow::bail!("max_history_entries must be at least 1");
+        }
+        Ok(())
+    }
+
+    /// Load settings from a TOML file; a missing file gives the defaults
+    pub fn from_toml_file(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read settings from {}", path.display()))?;
+        let settings: Self = toml::from_str(&content)
+            .with_context(|| format!("Invalid settings in {}", path.display()))?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    /// Save settings to a TOML file, creating its directory
+    pub fn to_toml_file(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create settings directory")?;
+        }
+        let content = toml::to_string_pretty(self).context("Failed to serialize settings")?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write settings to {}", path.display()))
+    }
+}
+
+/// The settings file and the current settings, with change notifications
+pub struct ConfigStore {
+    path: PathBuf,
+    settings: watch::Sender<AppSettings>,
+}
+
+impl ConfigStore {
+    /// `~/.skill-engine/config.toml`
+    pub fn default_path() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Failed to get home directory")?;
+        Ok(home.join(".skill-engine").join(APP_CONFIG_FILE))
+    }
+
+    /// Open the settings file at `path`; a missing file gives the defaults
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let settings = AppSettings::from_toml_file(&path)?;
+        Ok(Self::with_settings(path, settings))
+    }
+
+    /// Open `~/.skill-engine/config.toml`
+    pub fn open_default() -> Result<Self> {
+        Self::open(Self::default_path()?)
+    }
+
+    /// A store saving to `path` that starts from `settings` instead of
+    /// reading the file
+    pub fn with_settings(path: impl Into<PathBuf>, settings: AppSettings) -> Self {
+        let (settings, _) = watch::channel(settings);
+        Self { path: path.into(), settings }
+    }
+
+    /// Where the settings are saved
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The current settings
+    pub fn get(&self) -> AppSettings {
+        self.settings.borrow().clone()
+    }
+
+    /// Change the settings, save them and notify subscribers
+    ///
+    /// Nothing is saved if the changed settings are invalid or the file
+    /// can't be written. Returns the new settings.
+    pub fn update(&self, change: impl FnOnce(&mut AppSettings)) -> Result<AppSettings> {
+        let mut settings = self.get();
+        change(&mut settings);
+        settings.validate()?;
+        settings.to_toml_file(&self.path)?;
+        self.publish(settings.clone());
+        Ok(settings)
+    }
+
+    /// Re-read the file, notifying subscribers if it changed
+    ///
+    /// Returns whether the settings changed.
+    pub fn reload(&self) -> Result<bool> {
+        let settings = AppSettings::from_toml_file(&self.path)?;
+        Ok(self.publish(settings))
+    }
+
+    /// Receive the settings after every change
+    pub fn subscribe(&self) -> watch::Receiver<AppSettings> {
+        self.settings.subscribe()
+    }
+
+    fn publish(&self, settings: AppSettings) -> bool {
+        self.settings.send_if_modified(|current| {
+            if *current == settings {
+                return false;
+            }
+            *current = settings;
+            true
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_saves_and_notifies() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(APP_CONFIG_FILE);
+        let store = ConfigStore::open(&path).unwrap();
+        assert_eq!(store.get(), AppSettings::default());
+
+        let mut changes = store.subscribe();
+        store.update(|settings| settings.max_history_entries = 50).unwrap();
+        assert!(changes.has_changed().unwrap());
+        assert_eq!(changes.borrow_and_update().max_history_entries, 50);
+        assert_eq!(ConfigStore::open(&path).unwrap().get().max_history_entries, 50);
+
+        // Invalid settings are neither saved nor published
+        assert!(store.update(|settings| settings.default_timeout_secs = 0).is_err());
+        assert_eq!(store.get().default_timeout_secs, 30);
+        assert!(!changes.has_changed().unwrap());
+
+        // Another process edits the file
+        std::fs::write(&path, "default_timeout_secs = 90\n").unwrap();
+        assert!(store.reload().unwrap());
+        assert!(!store.reload().unwrap());
+        assert_eq!(store.get().default_timeout_secs, 90);
+        assert_eq!(store.get().max_history_entries, 1000);
+    }
+}
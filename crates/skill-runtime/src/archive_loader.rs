@@ -0,0 +1,591 @@
+//! Archive Skill Loader - Download skills packaged as .tar.gz, .tar or .zip
+//!
+//! Archives are fetched over HTTP(S), checked against a SHA-256 checksum and
+//! extracted under `~/.skill-engine/archives`. The checksum comes from the
+//! URL fragment (`https://example.com/skill-v1.2.0.tar.gz#sha256=<hex>`) or a
+//! `<url>.sha256` file published next to the archive; without either the
+//! computed digest is reported but not verified.
+//!
+//! A published `<url>.sha256` comes from the same origin as the archive, so it
+//! only catches truncated or corrupted downloads: whoever can replace the
+//! archive can replace its checksum too. Only a `#sha256=` pin obtained out of
+//! band authenticates the archive.
+//!
+//! Downloads are capped at [`MAX_ARCHIVE_SIZE`] bytes and extraction at
+//! [`MAX_UNPACKED_SIZE`] bytes, so a hostile archive cannot fill the disk.
+//!
+//! Archives carry a pre-built component (`skill.wasm`, `dist/skill.wasm`,
+//! `build/skill.wasm`, or a single `*.wasm`), so no git or build toolchain is
+//! needed. `.skillpkg` files from `skill package` are gzipped tarballs whose
+//! `integrity.json` is verified after extraction.
+
+use anyhow::{Context, Result};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use crate::checksum::sha256_hex;
+use crate::dependencies::{read_declared_dependencies, SkillDependency};
+use crate::git_loader::extract_metadata;
+use crate::package::PackageIntegrity;
+
+/// Largest archive that will be downloaded or opened (256 MiB)
+pub const MAX_ARCHIVE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Largest total size of the files extracted from one archive (1 GiB)
+pub const MAX_UNPACKED_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Supported archive formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// Gzip-compressed tarball (`.tar.gz`, `.tgz`)
+    TarGz,
+    /// Uncompressed tarball (`.tar`)
+    Tar,
+    /// Zip archive (`.zip`)
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Detect the format from a file name or URL path
+    pub fn from_path(path: &str) -> Option<Self> {
+        split_extension(path).map(|(_, format)| format)
+    }
+}
+
+/// Split a file name into its stem and archive format
+fn split_extension(file_name: &str) -> Option<(&str, ArchiveFormat)> {
+    const EXTENSIONS: &[(&str, ArchiveFormat)] = &[
+        (".tar.gz", ArchiveFormat::TarGz),
+        (".tgz", ArchiveFormat::TarGz),
+        (".skillpkg", ArchiveFormat::TarGz),
+        (".tar", ArchiveFormat::Tar),
+        (".zip", ArchiveFormat::Zip),
+    ];
+    let lower = file_name.to_ascii_lowercase();
+    EXTENSIONS.iter().find_map(|(ext, format)| {
+        lower
+            .ends_with(ext)
+            .then(|| (&file_name[..file_name.len() - ext.len()], *format))
+    })
+}
+
+/// A parsed archive URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveSource {
+    /// Download URL (without the checksum fragment)
+    pub url: String,
+    /// Archive format
+    pub format: ArchiveFormat,
+    /// Expected SHA-256 digest (lowercase hex), if pinned in the URL
+    pub sha256: Option<String>,
+    /// Archive file name without extension (e.g. `skill-v1.2.0`)
+    pub stem: String,
+}
+
+/// Check if a source is an HTTP(S) URL pointing at a supported archive
+pub fn is_archive_url(input: &str) -> bool {
+    parse_archive_url(input).is_ok()
+}
+
+/// Parse an archive URL, including an optional `#sha256=<hex>` fragment
+pub fn parse_archive_url(input: &str) -> Result<ArchiveSource> {
+    let mut url = url::Url::parse(input).context("Invalid URL")?;
+    if url.scheme() != "https" && url.scheme() != "http" {
+        anyhow::bail!("Archive sources must be http(s) URLs: {}", input);
+    }
+
+    let file_name = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .unwrap_or_default()
+        .to_string();
+    let (stem, format) = split_extension(&file_name).with_context(|| {
+        format!(
+            "Not a .tar.gz, .tgz, .tar, .zip or .skillpkg URL: {}",
+            input
+        )
+    })?;
+
+    let sha256 = match url.fragment() {
+        Some(fragment) => {
+            let hex = fragment
+                .strip_prefix("sha256=")
+                .with_context(|| format!("Unsupported URL fragment '{}'", fragment))?;
+            Some(normalize_digest(hex)?)
+        }
+        None => None,
+    };
+    url.set_fragment(None);
+
+    Ok(ArchiveSource {
+        url: url.to_string(),
+        format,
+        sha256,
+        stem: stem.to_string(),
+    })
+}
+
+/// A skill extracted from an archive
+#[derive(Debug, Clone)]
+pub struct ArchiveSkill {
+    /// Archive the skill came from
+    pub source: ArchiveSource,
+    /// Directory the archive was extracted to
+    pub local_path: PathBuf,
+    /// Pre-built WASM component
+    pub wasm_path: PathBuf,
+    /// Skill name (from skill.yaml/SKILL.md, else the archive name)
+    pub skill_name: String,
+    /// Skill version (from metadata, else parsed from the archive name)
+    pub version: Option<String>,
+    /// Skills this skill depends on
+    pub dependencies: Vec<SkillDependency>,
+    /// SHA-256 digest of the downloaded archive
+    pub sha256: String,
+}
+
+/// Downloads and extracts skill archives
+pub struct ArchiveSkillLoader {
+    /// Directory for extracted archives
+    archives_dir: PathBuf,
+}
+
+impl ArchiveSkillLoader {
+    /// Create a new ArchiveSkillLoader
+    pub fn new() -> Result<Self> {
+        let home = dirs::home_dir().context("Failed to get home directory")?;
+        let archives_dir = home.join(".skill-engine").join("archives");
+        std::fs::create_dir_all(&archives_dir).with_context(|| {
+            format!(
+                "Failed to create archives directory: {}",
+                archives_dir.display()
+            )
+        })?;
+        Ok(Self { archives_dir })
+    }
+
+    /// Download, verify and extract an archive
+    pub async fn fetch(&self, source: &ArchiveSource) -> Result<ArchiveSkill> {
+        info!(url = %source.url, "Downloading skill archive");
+        let client = reqwest::Client::new();
+        let mut response = client
+            .get(&source.url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .with_context(|| format!("Failed to download {}", source.url))?;
+        if let Some(len) = response.content_length() {
+            check_archive_size(&source.url, len)?;
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .with_context(|| format!("Failed to read {}", source.url))?
+        {
+            bytes.extend_from_slice(&chunk);
+            check_archive_size(&source.url, bytes.len() as u64)?;
+        }
+
+        let sha256 = sha256_hex(&bytes);
+        if let Some(pinned) = &source.sha256 {
+            verify_digest(&source.url, pinned, &sha256)?;
+            info!(sha256 = %sha256, "Archive checksum verified");
+        } else if let Some(published) = fetch_published_digest(&client, &source.url).await {
+            // Same origin as the archive: detects corruption, not tampering
+            verify_digest(&source.url, &published, &sha256)?;
+            warn!(
+                sha256 = %sha256,
+                "Archive matches the checksum published next to it, which does not \
+                 authenticate it; pin it with #sha256=<hex>"
+            );
+        } else {
+            warn!(
+                sha256 = %sha256,
+                "No checksum published for archive; pin it with #sha256=<hex>"
+            );
+        }
+
+        let dest = self.extract_cached(source, &bytes, &sha256)?;
+        self.load_extracted(source, dest, sha256)
+    }
+
+    /// Extract a local archive or `.skillpkg` file
+    pub fn open_file(&self, path: &Path) -> Result<ArchiveSkill> {
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .with_context(|| format!("Invalid archive path: {}", path.display()))?;
+        let (stem, format) = split_extension(file_name)
+            .with_context(|| format!("Not a supported archive: {}", path.display()))?;
+        let source = ArchiveSource {
+            url: path.display().to_string(),
+            format,
+            sha256: None,
+            stem: stem.to_string(),
+        };
+
+        let len = std::fs::metadata(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+            .len();
+        check_archive_size(&source.url, len)?;
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let sha256 = sha256_hex(&bytes);
+        let dest = self.extract_cached(&source, &bytes, &sha256)?;
+        self.load_extracted(&source, dest, sha256)
+    }
+
+    /// Extract into `archives/<stem>-<sha>`, reusing an earlier extraction
+    fn extract_cached(
+        &self,
+        source: &ArchiveSource,
+        bytes: &[u8],
+        sha256: &str,
+    ) -> Result<PathBuf> {
+        let dest = self
+            .archives_dir
+            .join(format!("{}-{}", source.stem, &sha256[..12]));
+        if !dest.exists() {
+            let staging = self
+                .archives_dir
+                .join(format!(".{}.partial", &sha256[..12]));
+            if staging.exists() {
+                std::fs::remove_dir_all(&staging)?;
+            }
+            extract_archive(bytes, source.format, &staging)
+                .with_context(|| format!("Failed to extract {}", source.url))?;
+            std::fs::rename(&staging, &dest)?;
+        }
+        Ok(dest)
+    }
+
+    fn load_extracted(
+        &self,
+        source: &ArchiveSource,
+        extracted: PathBuf,
+        sha256: String,
+    ) -> Result<ArchiveSkill> {
+        let skill_dir = unwrap_single_dir(&extracted)?;
+        if let Some(integrity) = PackageIntegrity::load(&skill_dir)? {
+            integrity
+                .verify(&skill_dir)
+                .with_context(|| format!("Integrity check failed for {}", source.url))?;
+            info!(files = integrity.files.len(), "Package integrity verified");
+        }
+        let wasm_path = find_wasm(&skill_dir)?;
+
+        let (default_name, default_version) = split_name_version(&source.stem);
+        let (skill_name, version) = extract_metadata(&skill_dir, &default_name)?;
+        let dependencies = read_declared_dependencies(&skill_dir)?;
+
+        Ok(ArchiveSkill {
+            source: source.clone(),
+            local_path: skill_dir,
+            wasm_path,
+            skill_name,
+            version: version.or(default_version),
+            dependencies,
+            sha256,
+        })
+    }
+}
+
+/// Look for `<url>.sha256` next to the archive (first word is the digest)
+async fn fetch_published_digest(client: &reqwest::Client, url: &str) -> Option<String> {
+    let response = client.get(format!("{}.sha256", url)).send().await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let text = response.text().await.ok()?;
+    normalize_digest(text.split_whitespace().next()?).ok()
+}
+
+fn verify_digest(url: &str, expected: &str, actual: &str) -> Result<()> {
+    if expected != actual {
+        anyhow::bail!(
+            "Checksum mismatch for {}: expected sha256 {}, got {}",
+            url,
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+fn check_archive_size(url: &str, len: u64) -> Result<()> {
+    if len > MAX_ARCHIVE_SIZE {
+        anyhow::bail!(
+            "Archive {} is larger than the {} byte limit",
+            url,
+            MAX_ARCHIVE_SIZE
+        );
+    }
+    Ok(())
+}
+
+fn normalize_digest(hex: &str) -> Result<String> {
+    let hex = hex.trim().to_ascii_lowercase();
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!("Invalid sha256 digest '{}'", hex);
+    }
+    Ok(hex)
+}
+
+/// Extract an archive into `dest`, rejecting entries that escape it and
+/// stopping once [`MAX_UNPACKED_SIZE`] bytes have been written
+pub(crate) fn extract_archive(bytes: &[u8], format: ArchiveFormat, dest: &Path) -> Result<()> {
+    extract_archive_limited(bytes, format, dest, MAX_UNPACKED_SIZE)
+}
+
+fn extract_archive_limited(
+    bytes: &[u8],
+    format: ArchiveFormat,
+    dest: &Path,
+    limit: u64,
+) -> Result<()> {
+    std::fs::create_dir_all(dest)?;
+    match format {
+        ArchiveFormat::TarGz => unpack_tar(flate2::read::GzDecoder::new(bytes), dest, limit),
+        ArchiveFormat::Tar => unpack_tar(bytes, dest, limit),
+        ArchiveFormat::Zip => unpack_zip(bytes, dest, limit),
+    }
+}
+
+/// Charge `size` extracted bytes against the remaining budget
+fn take_unpacked(remaining: &mut u64, size: u64, limit: u64) -> Result<()> {
+    *remaining = remaining
+        .checked_sub(size)
+        .with_context(|| format!("Archive unpacks to more than the {} byte limit", limit))?;
+    Ok(())
+}
+
+fn unpack_tar<R: Read>(reader: R, dest: &Path, limit: u64) -> Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    let mut remaining = limit;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        // `unpack_in` would strip a leading `/`; refuse it like zip does
+        if entry.path()?.has_root() {
+            anyhow::bail!(
+                "Archive entry escapes destination: {}",
+                entry.path()?.display()
+            );
+        }
+        // The entry size is exactly what `unpack_in` reads from the stream
+        take_unpacked(&mut remaining, entry.size(), limit)?;
+        // `unpack_in` refuses paths outside `dest` (returns false)
+        if !entry.unpack_in(dest)? {
+            anyhow::bail!(
+                "Archive entry escapes destination: {}",
+                entry.path()?.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn unpack_zip(bytes: &[u8], dest: &Path, limit: u64) -> Result<()> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))?;
+    let mut remaining = limit;
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        let relative = file
+            .enclosed_name()
+            .map(Path::to_path_buf)
+            .with_context(|| format!("Archive entry escapes destination: {}", file.name()))?;
+        let path = dest.join(relative);
+
+        if file.is_dir() {
+            std::fs::create_dir_all(&path)?;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // The declared size can lie, so count what is actually inflated
+        let mut out = std::fs::File::create(&path)?;
+        let written = std::io::copy(&mut (&mut file).take(remaining + 1), &mut out)?;
+        take_unpacked(&mut remaining, written, limit)?;
+    }
+    Ok(())
+}
+
+/// Archives often wrap everything in one top-level directory
+fn unwrap_single_dir(dir: &Path) -> Result<PathBuf> {
+    let entries: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .map_or(true, |n| !n.starts_with('.'))
+        })
+        .collect();
+    match entries.as_slice() {
+        [only] if only.is_dir() => Ok(only.clone()),
+        _ => Ok(dir.to_path_buf()),
+    }
+}
+
+/// Find the skill's pre-built component
+pub(crate) fn find_wasm(dir: &Path) -> Result<PathBuf> {
+    for candidate in ["skill.wasm", "dist/skill.wasm", "build/skill.wasm"] {
+        let path = dir.join(candidate);
+        if path.is_file() {
+            return Ok(path);
+        }
+    }
+
+    let wasm_files: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "wasm"))
+        .collect();
+    match wasm_files.as_slice() {
+        [only] => Ok(only.clone()),
+        [] => anyhow::bail!(
+            "No pre-built skill.wasm found in archive. \
+             Archives must contain a compiled component"
+        ),
+        _ => anyhow::bail!(
+            "Archive contains several .wasm files; name the skill component skill.wasm"
+        ),
+    }
+}
+
+/// Split `name-v1.2.0` / `name-1.2.0` into name and version
+fn split_name_version(stem: &str) -> (String, Option<String>) {
+    if let Some((name, version)) = stem.rsplit_once('-') {
+        let digits = version.strip_prefix('v').unwrap_or(version);
+        if !name.is_empty() && digits.starts_with(|c: char| c.is_ascii_digit()) {
+            return (name.to_string(), Some(digits.to_string()));
+        }
+    }
+    (stem.to_string(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_archive_url() {
+        let digest = "a".repeat(64);
+        let source = parse_archive_url(&format!(
+            "https://example.com/dl/aws-skill-v1.2.0.tar.gz#sha256={}",
+            digest.to_uppercase()
+        ))
+        .unwrap();
+        assert_eq!(source.url, "https://example.com/dl/aws-skill-v1.2.0.tar.gz");
+        assert_eq!(source.format, ArchiveFormat::TarGz);
+        assert_eq!(source.sha256, Some(digest));
+        assert_eq!(source.stem, "aws-skill-v1.2.0");
+        assert_eq!(
+            split_name_version(&source.stem),
+            ("aws-skill".to_string(), Some("1.2.0".to_string()))
+        );
+
+        let source = parse_archive_url("https://example.com/aws-1.0.0.tgz").unwrap();
+        assert_eq!(source.stem, "aws-1.0.0");
+        assert_eq!(source.sha256, None);
+
+        assert!(is_archive_url("https://example.com/skill.zip?token=x"));
+        assert!(is_archive_url("https://example.com/aws-1.0.0.skillpkg"));
+        assert!(!is_archive_url("https://github.com/org/repo"));
+        assert!(!is_archive_url("./skill.tar.gz"));
+        assert!(parse_archive_url("https://example.com/s.tgz#sha256=abc").is_err());
+    }
+
+    #[test]
+    fn test_extract_tar_gz_with_wrapper_dir() {
+        let mut tarball = Vec::new();
+        {
+            let encoder =
+                flate2::write::GzEncoder::new(&mut tarball, flate2::Compression::default());
+            let mut builder = tar::Builder::new(encoder);
+            for (path, contents) in [
+                ("skill-v1.0.0/skill.wasm", &b"\0asm"[..]),
+                ("skill-v1.0.0/SKILL.md", &b"---\nname: demo\n---\n"[..]),
+            ] {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(contents.len() as u64);
+                header.set_mode(0o644);
+                header.set_cksum();
+                builder.append_data(&mut header, path, contents).unwrap();
+            }
+            builder.into_inner().unwrap().finish().unwrap();
+        }
+
+        let dir = tempfile::TempDir::new().unwrap();
+        extract_archive(&tarball, ArchiveFormat::TarGz, dir.path()).unwrap();
+
+        let skill_dir = unwrap_single_dir(dir.path()).unwrap();
+        assert_eq!(skill_dir, dir.path().join("skill-v1.0.0"));
+        assert_eq!(find_wasm(&skill_dir).unwrap(), skill_dir.join("skill.wasm"));
+        assert_eq!(extract_metadata(&skill_dir, "fallback").unwrap().0, "demo");
+    }
+
+    /// Build a tarball without the path checks `tar::Builder` applies
+    fn raw_tar(path: &str, contents: &[u8]) -> Vec<u8> {
+        let mut header = tar::Header::new_gnu();
+        header.as_gnu_mut().unwrap().name[..path.len()].copy_from_slice(path.as_bytes());
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        let mut builder = tar::Builder::new(Vec::new());
+        builder.append(&header, contents).unwrap();
+        builder.into_inner().unwrap()
+    }
+
+    fn zip_with(path: &str, contents: &[u8]) -> Vec<u8> {
+        use std::io::Write;
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        writer
+            .start_file(path, zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(contents).unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_extract_rejects_escaping_entries() {
+        for path in ["../evil.txt", "skill/../../evil.txt", "/tmp/evil.txt"] {
+            let root = tempfile::TempDir::new().unwrap();
+            let dest = root.path().join("dest");
+
+            let err = extract_archive(&raw_tar(path, b"x"), ArchiveFormat::Tar, &dest).unwrap_err();
+            assert!(
+                err.to_string().contains("escapes destination"),
+                "{path}: {err}"
+            );
+
+            let err =
+                extract_archive(&zip_with(path, b"x"), ArchiveFormat::Zip, &dest).unwrap_err();
+            assert!(
+                err.to_string().contains("escapes destination"),
+                "{path}: {err}"
+            );
+
+            assert!(!root.path().join("evil.txt").exists());
+        }
+    }
+
+    #[test]
+    fn test_extract_stops_at_unpacked_limit() {
+        let contents = vec![0u8; 4096];
+        for (bytes, format) in [
+            (raw_tar("big.bin", &contents), ArchiveFormat::Tar),
+            (zip_with("big.bin", &contents), ArchiveFormat::Zip),
+        ] {
+            let dir = tempfile::TempDir::new().unwrap();
+            let err = extract_archive_limited(&bytes, format, dir.path(), 1024).unwrap_err();
+            assert!(err.to_string().contains("byte limit"), "{err}");
+
+            let dir = tempfile::TempDir::new().unwrap();
+            extract_archive_limited(&bytes, format, dir.path(), 4096).unwrap();
+            assert_eq!(std::fs::read(dir.path().join("big.bin")).unwrap(), contents);
+        }
+        assert!(check_archive_size("a.tgz", MAX_ARCHIVE_SIZE).is_ok());
+        assert!(check_archive_size("a.tgz", MAX_ARCHIVE_SIZE + 1).is_err());
+    }
+}
@@ -0,0 +1,478 @@
+//! Checking tool arguments against declared parameters before execution
+//!
+//! Arguments are checked against the parameters a tool declares in SKILL.md
+//! or its WASM definition: required parameters must be present, values must
+//! fit the parameter type, and `enum:` values, `pattern:` regexes and
+//! `min:`/`max:` bounds are enforced. Every problem is reported at once, with a suggestion where one
+//! helps, instead of handing a bad flag to `kubectl` or a WASM component.
+//!
+//! Values may be JSON (MCP, HTTP) or strings (`skill run key=value`), so
+//! strings are accepted for numeric, boolean and object parameters when they
+//! parse as one. Skills often accept flags they don't document, so an
+//! undeclared argument is only rejected when it looks like a typo of a
+//! declared parameter.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use crate::skill_md::{find_skill_md, parse_skill_md, ParameterDoc, ParameterType};
+
+/// Key of positional arguments from `skill run`
+const POSITIONAL_ARG: &str = "arg";
+
+/// A parameter that arguments are checked against
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamSpec {
+    /// Parameter name
+    pub name: String,
+    /// Expected type
+    pub param_type: ParameterType,
+    /// Whether a value must be provided
+    pub required: bool,
+    /// Values the parameter accepts; any value if empty
+    pub allowed_values: Vec<String>,
+    /// Regex the value must match (anywhere, as in JSON Schema)
+    pub pattern: Option<String>,
+    /// Smallest allowed value of a numeric parameter
+    pub minimum: Option<f64>,
+    /// Largest allowed value of a numeric parameter
+    pub maximum: Option<f64>,
+}
+
+impl ParamSpec {
+    /// Parameter with a type given by name, like `integer` or `String`;
+    /// unknown names are treated as strings
+    pub fn new(name: impl Into<String>, param_type: &str, required: bool) -> Self {
+        Self {
+            name: name.into(),
+            param_type: ParameterType::from_name(param_type),
+            required,
+            allowed_values: Vec::new(),
+            pattern: None,
+            minimum: None,
+            maximum: None,
+        }
+    }
+
+    /// Only accept these values
+    pub fn with_allowed_values(mut self, allowed_values: Vec<String>) -> Self {
+        self.allowed_values = allowed_values;
+        self
+    }
+
+    /// Only accept values matching this regex
+    pub fn with_pattern(mut self, pattern: Option<String>) -> Self {
+        self.pattern = pattern;
+        self
+    }
+
+    /// Only accept numbers within these bounds
+    pub fn with_range(mut self, minimum: Option<f64>, maximum: Option<f64>) -> Self {
+        self.minimum = minimum;
+        self.maximum = maximum;
+        self
+    }
+}
+
+impl From<&ParameterDoc> for ParamSpec {
+    fn from(doc: &ParameterDoc) -> Self {
+        Self {
+            name: doc.name.clone(),
+            param_type: doc.param_type.clone(),
+            // A documented default fills in a missing value
+            required: doc.required && doc.default.is_none(),
+            allowed_values: doc.allowed_values.clone(),
+            pattern: doc.pattern.clone(),
+            minimum: doc.minimum,
+            maximum: doc.maximum,
+        }
+    }
+}
+
+impl From<&crate::types::Parameter> for ParamSpec {
+    fn from(param: &crate::types::Parameter) -> Self {
+        use crate::types::ParameterType as WasmType;
+
+        Self {
+            name: param.name.clone(),
+            param_type: match param.param_type {
+                WasmType::String | WasmType::File => ParameterType::String,
+                WasmType::Number => ParameterType::Number,
+                WasmType::Boolean => ParameterType::Boolean,
+                WasmType::Json => ParameterType::Object,
+                WasmType::Array => ParameterType::Array,
+            },
+            required: param.required && param.default_value.is_none(),
+            allowed_values: Vec::new(),
+            pattern: None,
+            minimum: None,
+            maximum: None,
+        }
+    }
+}
+
+/// Parameters of `tool` as documented in the SKILL.md of `skill_dir`; empty
+/// if the skill has no SKILL.md or doesn't document the tool
+pub fn documented_params(skill_dir: &Path, tool: &str) -> Vec<ParamSpec> {
+    find_skill_md(skill_dir)
+        .and_then(|path| parse_skill_md(&path).ok())
+        .and_then(|content| {
+            content
+                .tool_docs
+                .get(tool)
+                .map(|doc| doc.parameters.iter().map(ParamSpec::from).collect())
+        })
+        .unwrap_or_default()
+}
+
+/// What's wrong with an argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArgIssueKind {
+    /// A required parameter has no value
+    Missing,
+    /// The value doesn't fit the parameter type
+    InvalidType,
+    /// The value isn't one of the parameter's allowed values
+    InvalidValue,
+    /// The argument isn't a declared parameter
+    Unknown,
+}
+
+/// One problem with the arguments of a call
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArgIssue {
+    /// Parameter (or undeclared argument) name
+    pub param: String,
+    /// Kind of problem
+    pub kind: ArgIssueKind,
+    /// What's wrong
+    pub message: String,
+    /// How to fix it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+/// Arguments that don't match a tool's parameters
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ArgValidationError {
+    /// Tool name
+    pub tool: String,
+    /// Every problem found
+    pub issues: Vec<ArgIssue>,
+}
+
+impl fmt::Display for ArgValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid arguments for tool '{}':", self.tool)?;
+        for issue in &self.issues {
+            write!(f, "\n  - {}", issue.message)?;
+            if let Some(suggestion) = &issue.suggestion {
+                write!(f, " ({})", suggestion)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ArgValidationError {}
+
+/// Check `args` against the parameters of `tool`
+///
+/// Tools that declare no parameters accept anything.
+pub fn validate_args(
+    tool: &str,
+    params: &[ParamSpec],
+    args: &HashMap<String, serde_json::Value>,
+) -> std::result::Result<(), ArgValidationError> {
+    if params.is_empty() {
+        return Ok(());
+    }
+
+    let mut issues = Vec::new();
+    for param in params {
+        match args.get(&param.name) {
+            None | Some(serde_json::Value::Null) => {
+                if param.required {
+                    issues.push(ArgIssue {
+                        param: param.name.clone(),
+                        kind: ArgIssueKind::Missing,
+                        message: format!("Missing required parameter '{}'", param.name),
+                        suggestion: Some(format!("pass {}=<{}>", param.name, param.param_type)),
+                    });
+                }
+            }
+            Some(value) => issues.extend(check_value(param, value)),
+        }
+    }
+
+    let mut undeclared: Vec<&String> = args
+        .keys()
+        .filter(|name| name.as_str() != POSITIONAL_ARG)
+        .filter(|name| !name.starts_with('_'))
+        .filter(|name| !params.iter().any(|param| &param.name == *name))
+        .collect();
+    undeclared.sort();
+    for name in undeclared {
+        let declared = params.iter().map(|param| param.name.as_str());
+        if let Some(closest) = closest_match(name, declared) {
+            issues.push(ArgIssue {
+                param: name.clone(),
+                kind: ArgIssueKind::Unknown,
+                message: format!("Unknown parameter '{}'", name),
+                suggestion: Some(format!("did you mean '{}'?", closest)),
+            });
+        }
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(ArgValidationError {
+            tool: tool.to_string(),
+            issues,
+        })
+    }
+}
+
+fn check_value(param: &ParamSpec, value: &serde_json::Value) -> Option<ArgIssue> {
+    use serde_json::Value;
+
+    let text = match value {
+        Value::String(s) => Some(s.as_str()),
+        _ => None,
+    };
+    let fits = match param.param_type {
+        ParameterType::String => !matches!(value, Value::Array(_) | Value::Object(_)),
+        ParameterType::Integer => {
+            value.is_i64()
+                || value.is_u64()
+                || text.is_some_and(|s| s.trim().parse::<i64>().is_ok())
+        }
+        ParameterType::Number => {
+            value.is_number() || text.is_some_and(|s| s.trim().parse::<f64>().is_ok())
+        }
+        ParameterType::Boolean => {
+            value.is_boolean()
+                || text.is_some_and(|s| {
+                    s.eq_ignore_ascii_case("true") || s.eq_ignore_ascii_case("false")
+                })
+        }
+        // Lists are often passed comma-separated
+        ParameterType::Array => value.is_array() || text.is_some(),
+        ParameterType::Object => {
+            value.is_object()
+                || text.is_some_and(|s| {
+                    serde_json::from_str::<Value>(s).is_ok_and(|parsed| parsed.is_object())
+                })
+        }
+    };
+    if !fits {
+        return Some(ArgIssue {
+            param: param.name.clone(),
+            kind: ArgIssueKind::InvalidType,
+            message: format!(
+                "Parameter '{}' expects {}, got {}",
+                param.name, param.param_type, value
+            ),
+            suggestion: None,
+        });
+    }
+
+    let value = text
+        .map(str::to_string)
+        .unwrap_or_else(|| value.to_string());
+    let invalid = |message: String, suggestion: Option<String>| ArgIssue {
+        param: param.name.clone(),
+        kind: ArgIssueKind::InvalidValue,
+        message,
+        suggestion,
+    };
+
+    if !param.allowed_values.is_empty() && !param.allowed_values.contains(&value) {
+        let allowed = param.allowed_values.iter().map(String::as_str);
+        let suggestion = match closest_match(&value, allowed) {
+            Some(closest) => format!("did you mean '{}'?", closest),
+            None => format!("one of: {}", param.allowed_values.join(", ")),
+        };
+        return Some(invalid(
+            format!("Parameter '{}' doesn't accept '{}'", param.name, value),
+            Some(suggestion),
+        ));
+    }
+
+    if matches!(
+        param.param_type,
+        ParameterType::Integer | ParameterType::Number
+    ) {
+        let number = value.trim().parse::<f64>().ok();
+        if let (Some(number), Some(minimum)) = (number, param.minimum) {
+            if number < minimum {
+                return Some(invalid(
+                    format!("Parameter '{}' must be at least {}", param.name, minimum),
+                    None,
+                ));
+            }
+        }
+        if let (Some(number), Some(maximum)) = (number, param.maximum) {
+            if number > maximum {
+                return Some(invalid(
+                    format!("Parameter '{}' must be at most {}", param.name, maximum),
+                    None,
+                ));
+            }
+        }
+    }
+
+    if let Some(pattern) = &param.pattern {
+        match regex::Regex::new(pattern) {
+            Ok(regex) if !regex.is_match(&value) => {
+                return Some(invalid(
+                    format!(
+                        "Parameter '{}' doesn't match the pattern `{}`",
+                        param.name, pattern
+                    ),
+                    None,
+                ));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!("Ignoring invalid pattern for '{}': {}", param.name, e);
+            }
+        }
+    }
+
+    None
+}
+
+/// The candidate `name` is most likely a typo of, if any is close enough
+fn closest_match<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let normalize = |s: &str| s.to_ascii_lowercase().replace('-', "_");
+    let name = normalize(name);
+    let max_distance = (name.chars().count() / 3).clamp(1, 3);
+
+    candidates
+        .map(|candidate| (edit_distance(&name, &normalize(candidate)), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn params() -> Vec<ParamSpec> {
+        vec![
+            ParamSpec::new("resource", "string", true),
+            ParamSpec::new("namespace", "string", false),
+            ParamSpec::new("replicas", "integer", false).with_range(Some(1.0), Some(10.0)),
+            ParamSpec::new("label", "string", false).with_pattern(Some("^[a-z]+$".to_string())),
+            ParamSpec::new("output", "string", false).with_allowed_values(vec![
+                "json".to_string(),
+                "yaml".to_string(),
+                "wide".to_string(),
+            ]),
+        ]
+    }
+
+    fn args(value: serde_json::Value) -> HashMap<String, serde_json::Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_valid_args() {
+        let args = args(json!({
+            "resource": "pods",
+            "replicas": "3",
+            "output": "yaml",
+            "selector": "app=web",
+            "arg": "extra"
+        }));
+        assert!(validate_args("get", &params(), &args).is_ok());
+        assert!(validate_args("get", &[], &args).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_args() {
+        let args = args(json!({
+            "namespce": "default",
+            "replicas": "three",
+            "output": "yml"
+        }));
+        let error = validate_args("get", &params(), &args).unwrap_err();
+        let kinds: Vec<(&str, ArgIssueKind)> = error
+            .issues
+            .iter()
+            .map(|issue| (issue.param.as_str(), issue.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("resource", ArgIssueKind::Missing),
+                ("replicas", ArgIssueKind::InvalidType),
+                ("output", ArgIssueKind::InvalidValue),
+                ("namespce", ArgIssueKind::Unknown),
+            ]
+        );
+        assert_eq!(
+            error.issues[2].suggestion.as_deref(),
+            Some("did you mean 'yaml'?")
+        );
+        assert_eq!(
+            error.issues[3].suggestion.as_deref(),
+            Some("did you mean 'namespace'?")
+        );
+        assert!(error
+            .to_string()
+            .starts_with("Invalid arguments for tool 'get':"));
+    }
+
+    #[test]
+    fn test_constraints() {
+        let error = validate_args(
+            "get",
+            &params(),
+            &args(json!({"resource": "pods", "replicas": 20, "label": "Web"})),
+        )
+        .unwrap_err();
+        let messages: Vec<&str> = error.issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Parameter 'replicas' must be at most 10",
+                "Parameter 'label' doesn't match the pattern `^[a-z]+$`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("namespace", "namespace"), 0);
+        assert_eq!(edit_distance("namespce", "namespace"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(
+            closest_match("dry-run", ["dry_run"].into_iter()),
+            Some("dry_run")
+        );
+    }
+}
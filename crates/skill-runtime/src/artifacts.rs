@@ -0,0 +1,440 @@
+//! Files produced by tool executions.
+//!
+//! Skills that declare `outputs` in the manifest get a scratch directory for
+//! each execution, passed as `SKILL_OUTPUT_DIR` (mounted at `/outputs` for
+//! WASM and Docker skills). When the tool exits, files matching the declared
+//! patterns are moved into `~/.skill-engine/artifacts/<execution-id>/` and
+//! kept until the manifest's `[artifacts]` retention policy prunes them.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+/// Environment variable holding the directory a tool writes artifacts to
+pub const OUTPUT_DIR_ENV: &str = "SKILL_OUTPUT_DIR";
+
+/// Where the output directory is mounted inside WASM sandboxes and containers
+pub const GUEST_OUTPUT_DIR: &str = "/outputs";
+
+/// Scratch directories for running executions, inside the store root
+const STAGING_DIR: &str = ".staging";
+
+/// `[artifacts]` section of the manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ArtifactRetention {
+    /// Delete artifacts older than this many days (0 keeps them forever)
+    #[serde(default = "default_max_age_days")]
+    pub max_age_days: u64,
+
+    /// Delete the oldest executions' artifacts once all artifacts exceed this
+    /// many megabytes (0 for no limit)
+    #[serde(default = "default_max_size_mb")]
+    pub max_size_mb: u64,
+}
+
+fn default_max_age_days() -> u64 {
+    7
+}
+
+fn default_max_size_mb() -> u64 {
+    1024
+}
+
+impl Default for ArtifactRetention {
+    fn default() -> Self {
+        Self {
+            max_age_days: default_max_age_days(),
+            max_size_mb: default_max_size_mb(),
+        }
+    }
+}
+
+/// A file captured from an execution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactInfo {
+    /// Path relative to the output directory, `/`-separated
+    pub name: String,
+    /// Size in bytes
+    pub size: u64,
+    /// MIME type guessed from the extension
+    pub content_type: String,
+    /// When the artifact was captured
+    pub created_at: DateTime<Utc>,
+}
+
+/// Artifacts stored per execution under a root directory.
+#[derive(Debug, Clone)]
+pub struct ArtifactStore {
+    root: PathBuf,
+}
+
+impl ArtifactStore {
+    /// Default store location (`~/.skill-engine/artifacts`).
+    pub fn default_dir() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Failed to get home directory")?;
+        Ok(home.join(".skill-engine").join("artifacts"))
+    }
+
+    /// Store in the default location.
+    pub fn new() -> Result<Self> {
+        Ok(Self::at(Self::default_dir()?))
+    }
+
+    /// Store rooted at `root`.
+    pub fn at(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Root directory of the store.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Create the scratch directory an execution writes its outputs to.
+    pub fn output_dir(&self, execution_id: &str) -> Result<PathBuf> {
+        let dir = self
+            .root
+            .join(STAGING_DIR)
+            .join(file_component(execution_id)?);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create output directory {}", dir.display()))?;
+        Ok(dir)
+    }
+
+    /// Move files matching `patterns` from an execution's output directory
+    /// into the store, then remove the output directory.
+    ///
+    /// Patterns are globs relative to the output directory: `*` and `?`
+    /// stay within a path segment, `**` spans directories. Symlinks are
+    /// never captured.
+    pub fn capture(&self, execution_id: &str, patterns: &[String]) -> Result<Vec<ArtifactInfo>> {
+        let staging = self.output_dir(execution_id)?;
+        let matchers = patterns
+            .iter()
+            .map(|pattern| glob_regex(pattern))
+            .collect::<Result<Vec<_>>>()?;
+        let dest = self.root.join(file_component(execution_id)?);
+
+        let result = move_matching(&staging, &dest, &matchers);
+        let _ = std::fs::remove_dir_all(&staging);
+        result?;
+        self.list(execution_id)
+    }
+
+    /// Artifacts captured for an execution (empty if there are none).
+    pub fn list(&self, execution_id: &str) -> Result<Vec<ArtifactInfo>> {
+        let dir = self.root.join(file_component(execution_id)?);
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut artifacts = Vec::new();
+        for entry in WalkDir::new(&dir).follow_links(false) {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Some(name) = relative_name(&dir, entry.path()) else {
+                continue;
+            };
+            let metadata = entry.metadata()?;
+            artifacts.push(ArtifactInfo {
+                content_type: content_type(&name).to_string(),
+                name,
+                size: metadata.len(),
+                created_at: metadata
+                    .modified()
+                    .map(DateTime::<Utc>::from)
+                    .unwrap_or_else(|_| Utc::now()),
+            });
+        }
+        artifacts.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(artifacts)
+    }
+
+    /// Path of a stored artifact.
+    ///
+    /// Rejects names that would escape the execution's directory.
+    pub fn path(&self, execution_id: &str, name: &str) -> Result<PathBuf> {
+        let relative = Path::new(name);
+        if name.is_empty()
+            || !relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+        {
+            bail!("Invalid artifact name '{}'", name);
+        }
+
+        let path = self.root.join(file_component(execution_id)?).join(relative);
+        let is_file = std::fs::symlink_metadata(&path)
+            .map(|metadata| metadata.is_file())
+            .unwrap_or(false);
+        if !is_file {
+            bail!(
+                "Artifact '{}' not found for execution {}",
+                name,
+                execution_id
+            );
+        }
+        Ok(path)
+    }
+
+    /// Delete artifacts outside the retention policy, returning how many
+    /// executions' artifacts were removed.
+    ///
+    /// Executions older than `max_age_days` are removed first, then the
+    /// oldest remaining ones until the store fits in `max_size_mb`.
+    pub fn prune(&self, retention: &ArtifactRetention) -> Result<usize> {
+        if !self.root.is_dir() {
+            return Ok(0);
+        }
+
+        let max_age = (retention.max_age_days > 0)
+            .then(|| Duration::from_secs(retention.max_age_days * 24 * 60 * 60));
+        let now = SystemTime::now();
+        let is_expired = |modified: SystemTime| {
+            max_age
+                .is_some_and(|max_age| now.duration_since(modified).unwrap_or_default() > max_age)
+        };
+
+        // (modified, size, path) of every execution directory
+        let mut executions = Vec::new();
+        for entry in std::fs::read_dir(&self.root)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let modified = entry.metadata()?.modified()?;
+            if entry.file_name() == STAGING_DIR {
+                // Scratch directories left behind by interrupted executions
+                for staged in std::fs::read_dir(entry.path())?.flatten() {
+                    let stale = staged
+                        .metadata()
+                        .and_then(|metadata| metadata.modified())
+                        .is_ok_and(is_expired);
+                    if stale {
+                        let _ = std::fs::remove_dir_all(staged.path());
+                    }
+                }
+                continue;
+            }
+            executions.push((modified, dir_size(&entry.path()), entry.path()));
+        }
+        executions.sort_by_key(|(modified, _, _)| *modified);
+
+        let max_bytes = retention.max_size_mb.saturating_mul(1024 * 1024);
+        let mut total: u64 = executions.iter().map(|(_, size, _)| size).sum();
+        let mut removed = 0;
+        for (modified, size, path) in executions {
+            let over_size = retention.max_size_mb > 0 && total > max_bytes;
+            if !is_expired(modified) && !over_size {
+                continue;
+            }
+            std::fs::remove_dir_all(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            total -= size;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+}
+
+/// Move files under `from` whose relative name matches into `to`
+fn move_matching(from: &Path, to: &Path, matchers: &[Regex]) -> Result<()> {
+    for entry in WalkDir::new(from).follow_links(false) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Some(name) = relative_name(from, entry.path()) else {
+            continue;
+        };
+        if !matchers.iter().any(|matcher| matcher.is_match(&name)) {
+            continue;
+        }
+
+        let target = to.join(&name);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        if std::fs::rename(entry.path(), &target).is_err() {
+            std::fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to capture artifact '{}'", name))?;
+        }
+    }
+    Ok(())
+}
+
+/// MIME type for an artifact name, from its extension.
+pub fn content_type(name: &str) -> &'static str {
+    let extension = Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("json") => "application/json",
+        Some("yaml" | "yml") => "application/yaml",
+        Some("txt" | "log" | "out") => "text/plain; charset=utf-8",
+        Some("md") => "text/markdown; charset=utf-8",
+        Some("csv") => "text/csv",
+        Some("html" | "htm") => "text/html; charset=utf-8",
+        Some("xml") => "application/xml",
+        Some("pdf") => "application/pdf",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("svg") => "image/svg+xml",
+        Some("zip") => "application/zip",
+        Some("gz" | "tgz") => "application/gzip",
+        Some("tar") => "application/x-tar",
+        _ => "application/octet-stream",
+    }
+}
+
+/// An ID used as a single directory name
+fn file_component(id: &str) -> Result<&str> {
+    let mut components = Path::new(id).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) if !id.starts_with('.') => Ok(id),
+        _ => bail!("Invalid execution ID '{}'", id),
+    }
+}
+
+/// `/`-separated path of `path` relative to `base`
+fn relative_name(base: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(base).ok()?;
+    let parts: Vec<&str> = relative
+        .components()
+        .map(|component| match component {
+            Component::Normal(part) => part.to_str(),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    Some(parts.join("/"))
+}
+
+/// Compile an output glob into an anchored regex
+fn glob_regex(pattern: &str) -> Result<Regex> {
+    let mut regex = String::from("^");
+    let mut rest = pattern.trim_start_matches("./");
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix("**/") {
+            regex.push_str("(?:.*/)?");
+            rest = after;
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("**") {
+            regex.push_str(".*");
+            rest = after;
+            continue;
+        }
+        match c {
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    regex.push('$');
+    Regex::new(&regex).with_context(|| format!("Invalid output pattern '{}'", pattern))
+}
+
+/// Total size of the files under a directory
+fn dir_size(dir: &Path) -> u64 {
+    WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_regex() {
+        let report = glob_regex("*.json").unwrap();
+        assert!(report.is_match("report.json"));
+        assert!(!report.is_match("nested/report.json"));
+
+        let nested = glob_regex("**/*.tfplan").unwrap();
+        assert!(nested.is_match("plan.tfplan"));
+        assert!(nested.is_match("envs/prod/plan.tfplan"));
+        assert!(!nested.is_match("plan.tfplan.bak"));
+
+        assert!(glob_regex("./kubeconfig").unwrap().is_match("kubeconfig"));
+        assert!(glob_regex("out-?.txt").unwrap().is_match("out-1.txt"));
+        assert!(glob_regex("**").unwrap().is_match("a/b/c"));
+    }
+
+    #[test]
+    fn test_capture_and_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ArtifactStore::at(dir.path());
+
+        let output_dir = store.output_dir("exec-1").unwrap();
+        std::fs::write(output_dir.join("report.json"), "{}").unwrap();
+        std::fs::write(output_dir.join("scratch.tmp"), "x").unwrap();
+        std::fs::create_dir_all(output_dir.join("plans")).unwrap();
+        std::fs::write(output_dir.join("plans").join("prod.tfplan"), "plan").unwrap();
+
+        let artifacts = store
+            .capture("exec-1", &["*.json".to_string(), "**/*.tfplan".to_string()])
+            .unwrap();
+        let names: Vec<&str> = artifacts.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["plans/prod.tfplan", "report.json"]);
+        assert_eq!(artifacts[1].content_type, "application/json");
+        assert_eq!(artifacts[1].size, 2);
+
+        // The scratch directory is gone; unmatched files went with it
+        assert!(!output_dir.exists());
+        assert_eq!(store.list("exec-1").unwrap(), artifacts);
+        assert!(store.list("exec-2").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_path_rejects_traversal() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ArtifactStore::at(dir.path());
+        std::fs::write(store.output_dir("exec-1").unwrap().join("a.txt"), "a").unwrap();
+        store.capture("exec-1", &["*".to_string()]).unwrap();
+
+        assert!(store.path("exec-1", "a.txt").unwrap().is_file());
+        assert!(store.path("exec-1", "missing.txt").is_err());
+        assert!(store.path("exec-1", "../exec-1/a.txt").is_err());
+        assert!(store.path("exec-1", "/etc/passwd").is_err());
+        assert!(store.path("..", "a.txt").is_err());
+        assert!(store.path(".staging", "a.txt").is_err());
+    }
+
+    #[test]
+    fn test_prune_by_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ArtifactStore::at(dir.path());
+        for id in ["old", "new"] {
+            let output_dir = store.output_dir(id).unwrap();
+            std::fs::write(output_dir.join("big.bin"), vec![0u8; 768 * 1024]).unwrap();
+            store.capture(id, &["*.bin".to_string()]).unwrap();
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        let retention = ArtifactRetention {
+            max_age_days: 0,
+            max_size_mb: 1,
+        };
+        assert_eq!(store.prune(&retention).unwrap(), 1);
+        assert!(store.list("old").unwrap().is_empty());
+        assert_eq!(store.list("new").unwrap().len(), 1);
+
+        // Nothing left to prune
+        assert_eq!(store.prune(&retention).unwrap(), 0);
+    }
+}
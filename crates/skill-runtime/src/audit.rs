@@ -6,6 +6,8 @@ use std::io::Write;
 use std::path::PathBuf;
 use std::sync::Mutex;
 
+use crate::http_proxy::NetworkRequestRecord;
+
 /// Audit event types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -24,6 +26,10 @@ pub enum AuditEventType {
     ConfigLoad,
     /// Configuration was updated
     ConfigUpdate,
+    /// Outbound network request was blocked by the sandbox policy
+    NetworkBlocked,
+    /// Outbound HTTP request was sent through the host proxy
+    NetworkRequest,
 }
 
 /// Audit log entry
@@ -176,6 +182,56 @@ impl AuditLogger {
         self.log(entry)
     }
 
+    /// Log an outbound connection blocked by the network policy
+    pub fn log_network_blocked(
+        &self,
+        skill_name: &str,
+        instance_name: &str,
+        host: &str,
+        port: u16,
+        interface: &str,
+    ) -> Result<()> {
+        let entry = AuditEntry::new(
+            AuditEventType::NetworkBlocked,
+            skill_name.to_string(),
+            instance_name.to_string(),
+        )
+        .with_details(format!("Blocked outbound {} request to {}:{}", interface, host, port))
+        .with_metadata(serde_json::json!({
+            "host": host,
+            "port": port,
+            "interface": interface,
+        }));
+
+        self.log(entry)
+    }
+
+    /// Log an outbound HTTP request sent through the host proxy
+    pub fn log_network_request(
+        &self,
+        skill_name: &str,
+        instance_name: &str,
+        request: &NetworkRequestRecord,
+    ) -> Result<()> {
+        let outcome = match (&request.status, &request.error) {
+            (Some(status), _) => status.to_string(),
+            (None, Some(error)) => format!("failed: {}", error),
+            (None, None) => "no response".to_string(),
+        };
+        let entry = AuditEntry::new(
+            AuditEventType::NetworkRequest,
+            skill_name.to_string(),
+            instance_name.to_string(),
+        )
+        .with_details(format!(
+            "{} {}://{}:{}{} -> {}",
+            request.method, request.scheme, request.host, request.port, request.path, outcome
+        ))
+        .with_metadata(serde_json::to_value(request)?);
+
+        self.log(entry)
+    }
+
     /// Get the audit log path
     pub fn log_path(&self) -> &PathBuf {
         &self.log_path
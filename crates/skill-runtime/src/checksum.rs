@@ -0,0 +1,28 @@
+//! Hex encoding and SHA-256 digests
+//!
+//! Archive, OCI and package checksums, signatures and model downloads all
+//! report digests as lowercase hex through these helpers.
+
+/// Lowercase hex encoding of `bytes`
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// SHA-256 digest of `bytes` as lowercase hex
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    to_hex(ring::digest::digest(&ring::digest::SHA256, bytes).as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(to_hex(&[0x00, 0xab, 0xff]), "00abff");
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
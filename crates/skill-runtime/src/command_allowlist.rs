@@ -0,0 +1,315 @@
+//! Allowlist of host binaries that native skills may execute
+//!
+//! The global list comes from the manifest's `[security]` section (falling
+//! back to [`DEFAULT_ALLOWED_COMMANDS`]); each skill can narrow it further by
+//! naming binaries in its SKILL.md `allowed-tools` frontmatter:
+//!
+//! ```toml
+//! [security]
+//! allowed-commands = ["kubectl", "helm", "terraform*", "/opt/tools/bin/*"]
+//! strict = true
+//! ```
+//!
+//! Patterns without a `/` match bare program names resolved via `PATH`;
+//! patterns with a `/` match explicit program paths. `*` matches any run of
+//! characters. On Windows names match case-insensitively, with either path
+//! separator and with or without an `.exe`, `.cmd`, `.bat` or `.com`
+//! extension, so `kubectl` also allows `KUBECTL.EXE`. In strict mode a skill whose `allowed-tools` names a binary
+//! outside the global list is rejected instead of having it silently dropped.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::network::glob_match;
+
+/// Binaries allowed when the manifest has no `[security] allowed-commands`
+pub const DEFAULT_ALLOWED_COMMANDS: &[&str] = &[
+    "kubectl",
+    "helm",
+    "git",
+    "curl",
+    "jq",
+    "aws",
+    "gcloud",
+    "az",
+    "docker",
+    "terraform",
+    "psql",
+];
+
+/// Agent tool names that may appear in `allowed-tools` but are not binaries
+const AGENT_TOOLS: &[&str] = &[
+    "bash",
+    "edit",
+    "glob",
+    "grep",
+    "multiedit",
+    "notebookedit",
+    "read",
+    "skill-run",
+    "task",
+    "todowrite",
+    "webfetch",
+    "websearch",
+    "write",
+];
+
+/// `[security]` section of the manifest
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SecurityConfig {
+    /// Binaries native skills may run (globs allowed); defaults to the built-in list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_commands: Option<Vec<String>>,
+
+    /// Reject skills whose `allowed-tools` name binaries outside `allowed-commands`
+    #[serde(default)]
+    pub strict: bool,
+}
+
+/// Compiled command allowlist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandAllowlist {
+    patterns: Vec<String>,
+    strict: bool,
+}
+
+impl Default for CommandAllowlist {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_ALLOWED_COMMANDS.iter().map(|s| s.to_string()),
+            false,
+        )
+    }
+}
+
+impl CommandAllowlist {
+    /// Create an allowlist from glob patterns
+    pub fn new(patterns: impl IntoIterator<Item = String>, strict: bool) -> Self {
+        Self {
+            patterns: patterns.into_iter().collect(),
+            strict,
+        }
+    }
+
+    /// Build the allowlist for a manifest `[security]` section
+    pub fn from_config(config: &SecurityConfig) -> Self {
+        match &config.allowed_commands {
+            Some(patterns) => Self::new(patterns.iter().cloned(), config.strict),
+            None => Self {
+                strict: config.strict,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Allowed command patterns
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Whether strict mode is enabled
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Check whether a program (bare name or path) is allowed
+    pub fn is_allowed(&self, program: &str) -> bool {
+        if program.is_empty() {
+            return false;
+        }
+        let is_path = program.contains('/') || program.contains('\\');
+        let program = platform_name(program);
+        self.patterns.iter().any(|pattern| {
+            let pattern_is_path = pattern.contains('/') || pattern.contains('\\');
+            pattern_is_path == is_path && glob_match(&platform_name(pattern), &program)
+        })
+    }
+
+    /// Fail with a descriptive error if a program is not allowed
+    pub fn check(&self, program: &str) -> Result<()> {
+        if !self.is_allowed(program) {
+            anyhow::bail!(
+                "Command '{}' not allowed. Allowed: {}",
+                program,
+                self.patterns.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Narrow the allowlist to the binaries a skill declares in `allowed-tools`
+    ///
+    /// Skills that only list agent tools (e.g. `Bash, skill-run`) keep the
+    /// full allowlist. Declared binaries outside the allowlist are dropped,
+    /// or rejected in strict mode.
+    pub fn for_skill(&self, skill_name: &str, allowed_tools: Option<&str>) -> Result<Self> {
+        let declared = allowed_tools.map(declared_commands).unwrap_or_default();
+        if declared.is_empty() {
+            return Ok(self.clone());
+        }
+
+        let (allowed, rejected): (Vec<String>, Vec<String>) = declared
+            .into_iter()
+            .partition(|command| self.is_allowed(command));
+
+        if !rejected.is_empty() {
+            if self.strict {
+                anyhow::bail!(
+                    "Skill '{}' requests commands not in [security] allowed-commands: {}",
+                    skill_name,
+                    rejected.join(", ")
+                );
+            }
+            tracing::warn!(
+                skill = %skill_name,
+                commands = %rejected.join(", "),
+                "Ignoring allowed-tools entries that are not in the command allowlist"
+            );
+        }
+
+        Ok(Self::new(allowed, self.strict))
+    }
+}
+
+/// A program name or pattern in the form this platform compares them
+fn platform_name(name: &str) -> String {
+    if cfg!(windows) {
+        windows_name(name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Lowercased, with `/` separators and no executable extension
+fn windows_name(name: &str) -> String {
+    let mut name = name.replace('\\', "/").to_lowercase();
+    if let Some(ext) = [".exe", ".cmd", ".bat", ".com"]
+        .into_iter()
+        .find(|ext| name.ends_with(ext))
+    {
+        name.truncate(name.len() - ext.len());
+    }
+    name
+}
+
+/// Binaries named in a SKILL.md `allowed-tools` value (agent tools are skipped)
+///
+/// Accepts a comma or space separated list as well as a YAML/JSON list such
+/// as `["kubectl", "helm"]`.
+pub fn declared_commands(allowed_tools: &str) -> Vec<String> {
+    allowed_tools
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|tool| tool.trim_matches(|c: char| matches!(c, '[' | ']' | '"' | '\'')))
+        .filter(|tool| !tool.is_empty())
+        .filter(|tool| !AGENT_TOOLS.contains(&tool.to_lowercase().as_str()))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_allowlist() {
+        let allowlist = CommandAllowlist::default();
+        assert!(allowlist.is_allowed("kubectl"));
+        assert!(allowlist.is_allowed("psql"));
+        assert!(!allowlist.is_allowed("rm"));
+        assert!(!allowlist.is_allowed(""));
+        assert!(allowlist.check("sh").is_err());
+    }
+
+    #[test]
+    fn test_globs_and_paths() {
+        let allowlist = CommandAllowlist::new(
+            vec!["terraform*".to_string(), "/opt/tools/bin/*".to_string()],
+            false,
+        );
+
+        assert!(allowlist.is_allowed("terraform"));
+        assert!(allowlist.is_allowed("terraform-1.7"));
+        assert!(allowlist.is_allowed("/opt/tools/bin/kubectl"));
+        // Bare-name patterns never match explicit paths, and vice versa
+        assert!(!allowlist.is_allowed("/tmp/terraform"));
+        assert!(!allowlist.is_allowed("kubectl"));
+    }
+
+    #[test]
+    fn test_windows_names() {
+        assert_eq!(windows_name("KUBECTL.EXE"), "kubectl");
+        assert_eq!(windows_name("az.cmd"), "az");
+        assert_eq!(windows_name("terraform-1.7"), "terraform-1.7");
+        assert_eq!(windows_name(r"C:\Tools\bin\helm.exe"), "c:/tools/bin/helm");
+        assert!(glob_match(
+            &windows_name(r"C:\Tools\bin\*"),
+            &windows_name(r"c:\tools\bin\kubectl.exe")
+        ));
+    }
+
+    #[test]
+    fn test_security_config_from_toml() {
+        let config: SecurityConfig = toml::from_str(
+            r#"
+            allowed-commands = ["kubectl", "helm"]
+            strict = true
+            "#,
+        )
+        .unwrap();
+
+        let allowlist = CommandAllowlist::from_config(&config);
+        assert!(allowlist.is_strict());
+        assert_eq!(allowlist.patterns(), &["kubectl", "helm"]);
+
+        let allowlist = CommandAllowlist::from_config(&SecurityConfig::default());
+        assert_eq!(allowlist, CommandAllowlist::default());
+    }
+
+    #[test]
+    fn test_declared_commands() {
+        assert!(declared_commands("Bash, skill-run").is_empty());
+        assert_eq!(
+            declared_commands("Bash, kubectl, helm"),
+            vec!["kubectl", "helm"]
+        );
+
+        // List form, as in the example skills
+        assert_eq!(
+            declared_commands(r#"["Bash", "kubectl", "helm"]"#),
+            vec!["kubectl", "helm"]
+        );
+        assert_eq!(declared_commands("['kubectl']"), vec!["kubectl"]);
+        assert!(declared_commands("[]").is_empty());
+        assert!(CommandAllowlist::new(vec!["kubectl".to_string()], true)
+            .for_skill("kubernetes", Some(r#"["kubectl"]"#))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_for_skill() {
+        let allowlist =
+            CommandAllowlist::new(vec!["kubectl".to_string(), "helm".to_string()], false);
+
+        // Only agent tools: keep the full allowlist
+        let skill = allowlist
+            .for_skill("kubernetes", Some("Bash, skill-run"))
+            .unwrap();
+        assert_eq!(skill, allowlist);
+
+        // Declared binaries narrow the list; unlisted ones are dropped
+        let skill = allowlist
+            .for_skill("kubernetes", Some("kubectl, rm"))
+            .unwrap();
+        assert!(skill.is_allowed("kubectl"));
+        assert!(!skill.is_allowed("helm"));
+        assert!(!skill.is_allowed("rm"));
+
+        // Strict mode rejects the skill instead
+        let strict = CommandAllowlist::new(vec!["kubectl".to_string()], true);
+        let err = strict
+            .for_skill("kubernetes", Some("kubectl, rm"))
+            .unwrap_err();
+        assert!(err.to_string().contains("rm"));
+    }
+}
@@ -0,0 +1,300 @@
+//! Index of credentials stored by `skill auth login`.
+//!
+//! The system keyring can't be enumerated, so the auth token store records
+//! what it saves in `~/.skill-engine/auth-index.json`. The index holds
+//! metadata only (provider, scope, scopes, expiry) and never secrets, which
+//! lets `skill auth status` and the HTTP API report every credential without
+//! touching the keyring.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Credentials expiring within this window (and not refreshable) are flagged
+pub const EXPIRY_WARNING_HOURS: i64 = 24;
+
+/// Health of a stored credential.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialState {
+    /// Valid and not close to expiring
+    Active,
+    /// The access token is expired or expiring, but a refresh token will renew it
+    Refreshable,
+    /// Expires within [`EXPIRY_WARNING_HOURS`] and can't be refreshed
+    ExpiringSoon,
+    /// Expired and can't be refreshed; executions using it will fail
+    Expired,
+}
+
+/// Metadata about one stored credential.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredentialSummary {
+    /// Auth provider ID
+    pub provider_id: String,
+    /// Provider display name
+    pub display_name: String,
+    /// Provider authentication type (e.g. "OAuth2 Device Flow")
+    pub auth_type: String,
+    /// Skill the credential is associated with (global if absent)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skill: Option<String>,
+    /// Instance the credential is associated with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Granted scopes
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// When the credential expires
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Whether a refresh token is stored
+    #[serde(default)]
+    pub has_refresh_token: bool,
+    /// When the credential was last stored or refreshed
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CredentialSummary {
+    /// State of the credential at `now`.
+    pub fn state_at(&self, now: DateTime<Utc>) -> CredentialState {
+        let Some(expires_at) = self.expires_at else {
+            return CredentialState::Active;
+        };
+        if expires_at - now > Duration::hours(EXPIRY_WARNING_HOURS) {
+            CredentialState::Active
+        } else if self.has_refresh_token {
+            CredentialState::Refreshable
+        } else if expires_at <= now {
+            CredentialState::Expired
+        } else {
+            CredentialState::ExpiringSoon
+        }
+    }
+
+    /// Current state of the credential.
+    pub fn state(&self) -> CredentialState {
+        self.state_at(Utc::now())
+    }
+
+    /// `provider` or `provider@skill[:instance]`.
+    pub fn label(&self) -> String {
+        match (&self.skill, &self.instance) {
+            (Some(skill), Some(instance)) => {
+                format!("{}@{}:{}", self.provider_id, skill, instance)
+            }
+            (Some(skill), None) => format!("{}@{}", self.provider_id, skill),
+            (None, _) => self.provider_id.clone(),
+        }
+    }
+
+    /// Warning to show before an execution fails, if any.
+    pub fn warning(&self) -> Option<String> {
+        let expires_at = self.expires_at?;
+        match self.state() {
+            CredentialState::Expired => Some(format!(
+                "{} credentials expired at {}; run 'skill auth login {}'",
+                self.label(),
+                expires_at.format("%Y-%m-%d %H:%M UTC"),
+                self.provider_id
+            )),
+            CredentialState::ExpiringSoon => Some(format!(
+                "{} credentials expire in {} minutes and can't be refreshed; run 'skill auth login {}'",
+                self.label(),
+                (expires_at - Utc::now()).num_minutes(),
+                self.provider_id
+            )),
+            CredentialState::Active | CredentialState::Refreshable => None,
+        }
+    }
+
+    fn same_scope(&self, other: &Self) -> bool {
+        self.provider_id == other.provider_id
+            && self.skill == other.skill
+            && self.instance == other.instance
+    }
+}
+
+/// A credential with its current state, as reported by
+/// `skill auth status --json` and the HTTP API.
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialStatus {
+    /// Stored metadata
+    #[serde(flatten)]
+    pub summary: CredentialSummary,
+    /// Current state
+    pub state: CredentialState,
+    /// Warning for expired or expiring credentials
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+impl From<&CredentialSummary> for CredentialStatus {
+    fn from(summary: &CredentialSummary) -> Self {
+        Self {
+            summary: summary.clone(),
+            state: summary.state(),
+            warning: summary.warning(),
+        }
+    }
+}
+
+/// The credential index file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CredentialIndex {
+    /// Stored credentials
+    #[serde(default)]
+    pub credentials: Vec<CredentialSummary>,
+}
+
+impl CredentialIndex {
+    /// Default index location (`~/.skill-engine/auth-index.json`).
+    pub fn default_path() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Failed to get home directory")?;
+        Ok(home.join(".skill-engine").join("auth-index.json"))
+    }
+
+    /// Load the index from the default location.
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::default_path()?)
+    }
+
+    /// Load an index file; a missing file is an empty index.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Invalid {}", path.display()))
+    }
+
+    /// Save the index to the default location.
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::default_path()?)
+    }
+
+    /// Save the index to a file.
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Add or replace the entry for a credential's provider and scope.
+    pub fn upsert(&mut self, summary: CredentialSummary) {
+        match self.credentials.iter_mut().find(|c| c.same_scope(&summary)) {
+            Some(existing) => *existing = summary,
+            None => self.credentials.push(summary),
+        }
+    }
+
+    /// Remove the entry for a provider and scope.
+    pub fn remove(&mut self, provider_id: &str, skill: Option<&str>, instance: Option<&str>) {
+        self.credentials.retain(|c| {
+            !(c.provider_id == provider_id
+                && c.skill.as_deref() == skill
+                && c.instance.as_deref() == instance)
+        });
+    }
+
+    /// Credentials sorted by provider, then skill and instance.
+    pub fn sorted(&self) -> Vec<&CredentialSummary> {
+        let mut credentials: Vec<_> = self.credentials.iter().collect();
+        credentials.sort_by(|a, b| {
+            (&a.provider_id, &a.skill, &a.instance).cmp(&(&b.provider_id, &b.skill, &b.instance))
+        });
+        credentials
+    }
+
+    /// Status of every credential, optionally for one provider only.
+    pub fn statuses(&self, provider_id: Option<&str>) -> Vec<CredentialStatus> {
+        self.sorted()
+            .into_iter()
+            .filter(|c| provider_id.map_or(true, |id| c.provider_id == id))
+            .map(CredentialStatus::from)
+            .collect()
+    }
+
+    /// Warnings for expired or expiring credentials.
+    pub fn warnings(&self) -> Vec<String> {
+        self.sorted()
+            .into_iter()
+            .filter_map(CredentialSummary::warning)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(expires_in: Option<Duration>, refreshable: bool) -> CredentialSummary {
+        CredentialSummary {
+            provider_id: "github".to_string(),
+            display_name: "GitHub".to_string(),
+            auth_type: "OAuth2 Device Flow".to_string(),
+            skill: Some("github".to_string()),
+            instance: None,
+            scopes: vec!["repo".to_string()],
+            expires_at: expires_in.map(|d| Utc::now() + d),
+            has_refresh_token: refreshable,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_credential_state() {
+        assert_eq!(summary(None, false).state(), CredentialState::Active);
+        assert_eq!(
+            summary(Some(Duration::days(3)), false).state(),
+            CredentialState::Active
+        );
+        assert_eq!(
+            summary(Some(Duration::hours(2)), false).state(),
+            CredentialState::ExpiringSoon
+        );
+        assert_eq!(
+            summary(Some(Duration::hours(-2)), false).state(),
+            CredentialState::Expired
+        );
+        assert_eq!(
+            summary(Some(Duration::hours(-2)), true).state(),
+            CredentialState::Refreshable
+        );
+
+        let expired = summary(Some(Duration::hours(-2)), false);
+        assert!(expired
+            .warning()
+            .unwrap()
+            .starts_with("github@github credentials expired"));
+        assert!(summary(Some(Duration::hours(-2)), true).warning().is_none());
+    }
+
+    #[test]
+    fn test_index_upsert_and_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("auth-index.json");
+
+        let mut index = CredentialIndex::default();
+        index.upsert(summary(Some(Duration::hours(-1)), false));
+        index.upsert(summary(Some(Duration::days(30)), false));
+        assert_eq!(index.credentials.len(), 1);
+        assert!(index.warnings().is_empty());
+
+        index.save_to(&path).unwrap();
+        let mut loaded = CredentialIndex::load_from(&path).unwrap();
+        assert_eq!(loaded.credentials, index.credentials);
+
+        loaded.remove("github", Some("github"), None);
+        assert!(loaded.credentials.is_empty());
+        assert!(CredentialIndex::load_from(&dir.path().join("missing.json"))
+            .unwrap()
+            .credentials
+            .is_empty());
+    }
+}
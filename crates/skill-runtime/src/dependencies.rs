@@ -0,0 +1,275 @@
+//! Dependencies between skills
+//!
+//! A skill can require other skills, either in the project manifest:
+//!
+//! ```toml
+//! [skills.deploy]
+//! source = "./skills/deploy"
+//! depends_on = ["kubernetes", { name = "helm", source = "github:org/helm-skill" }]
+//! ```
+//!
+//! or in its own `skill.yaml` / SKILL.md frontmatter (`dependencies:`), which
+//! `skill install` reads to install dependencies transitively. Before
+//! execution the graph is resolved and every dependency must be available.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::instance::InstanceManager;
+
+/// A dependency on another skill
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SkillDependency {
+    /// Dependency by name (defined in the manifest or already installed)
+    Name(String),
+    /// Dependency with a source it can be installed from
+    Source {
+        /// Skill name
+        name: String,
+        /// Install source (local path, git URL, or shorthand)
+        source: String,
+    },
+}
+
+impl SkillDependency {
+    /// Name of the required skill
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Name(name) => name,
+            Self::Source { name, .. } => name,
+        }
+    }
+
+    /// Install source, if declared
+    pub fn source(&self) -> Option<&str> {
+        match self {
+            Self::Name(_) => None,
+            Self::Source { source, .. } => Some(source),
+        }
+    }
+}
+
+impl fmt::Display for SkillDependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name(name) => write!(f, "{}", name),
+            Self::Source { name, source } => write!(f, "{} ({})", name, source),
+        }
+    }
+}
+
+/// Resolve the transitive dependencies of `root` in dependency order
+///
+/// `lookup` returns the direct dependencies of an available skill, or `None`
+/// if the skill is not available. The result lists every dependency before
+/// the skills that need it and excludes `root`. Missing skills and cycles are
+/// errors.
+pub fn resolve_dependencies<F>(root: &str, mut lookup: F) -> Result<Vec<String>>
+where
+    F: FnMut(&str) -> Result<Option<Vec<String>>>,
+{
+    fn visit<F>(
+        skill: &str,
+        lookup: &mut F,
+        path: &mut Vec<String>,
+        done: &mut HashSet<String>,
+        order: &mut Vec<String>,
+    ) -> Result<()>
+    where
+        F: FnMut(&str) -> Result<Option<Vec<String>>>,
+    {
+        if done.contains(skill) {
+            return Ok(());
+        }
+        if let Some(start) = path.iter().position(|s| s == skill) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(skill.to_string());
+            anyhow::bail!("Dependency cycle: {}", cycle.join(" -> "));
+        }
+
+        let deps = match lookup(skill)? {
+            Some(deps) => deps,
+            None => match path.last() {
+                Some(parent) => anyhow::bail!(
+                    "Skill '{}' depends on '{}', which is not installed or defined in the manifest",
+                    parent,
+                    skill
+                ),
+                None => anyhow::bail!("Skill '{}' not found", skill),
+            },
+        };
+
+        path.push(skill.to_string());
+        for dep in &deps {
+            visit(dep, lookup, path, done, order)?;
+        }
+        path.pop();
+
+        done.insert(skill.to_string());
+        order.push(skill.to_string());
+        Ok(())
+    }
+
+    let mut order = Vec::new();
+    visit(
+        root,
+        &mut lookup,
+        &mut Vec::new(),
+        &mut HashSet::new(),
+        &mut order,
+    )?;
+    order.pop(); // root is always last
+    Ok(order)
+}
+
+/// Registry directory for installed skills (`~/.skill-engine/registry`)
+pub fn registry_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Failed to get home directory")?;
+    Ok(home.join(".skill-engine").join("registry"))
+}
+
+/// Whether a skill is installed in the registry
+pub fn is_installed(skill_name: &str) -> bool {
+    registry_dir()
+        .map(|dir| is_installed_in(&dir, skill_name))
+        .unwrap_or(false)
+}
+
+/// Whether a skill is installed in the registry at `registry_dir`
+pub fn is_installed_in(registry_dir: &Path, skill_name: &str) -> bool {
+    registry_dir
+        .join(skill_name)
+        .join(format!("{}.wasm", skill_name))
+        .exists()
+}
+
+/// Dependencies recorded for an installed skill, or `None` if it is not installed
+pub fn installed_dependencies(skill_name: &str) -> Result<Option<Vec<String>>> {
+    if !is_installed(skill_name) {
+        return Ok(None);
+    }
+
+    let manager = InstanceManager::new()?;
+    let mut deps = Vec::new();
+    for instance in manager.list_instances(skill_name)? {
+        if let Ok(config) = manager.load_instance(skill_name, &instance) {
+            for dep in config.metadata.dependencies {
+                if !deps.contains(&dep) {
+                    deps.push(dep);
+                }
+            }
+        }
+    }
+    Ok(Some(deps))
+}
+
+/// Dependencies declared by a skill directory (`skill.yaml`, then SKILL.md frontmatter)
+pub fn read_declared_dependencies(dir: &Path) -> Result<Vec<SkillDependency>> {
+    #[derive(Deserialize)]
+    struct Declared {
+        #[serde(default)]
+        dependencies: Vec<SkillDependency>,
+    }
+
+    let skill_yaml = dir.join("skill.yaml");
+    if skill_yaml.exists() {
+        let contents = std::fs::read_to_string(&skill_yaml)?;
+        let declared: Declared = serde_yaml::from_str(&contents)
+            .with_context(|| format!("Invalid dependencies in {}", skill_yaml.display()))?;
+        return Ok(declared.dependencies);
+    }
+
+    let skill_md = dir.join("SKILL.md");
+    if skill_md.exists() {
+        let contents = std::fs::read_to_string(&skill_md)?;
+        if let Some(frontmatter) = contents
+            .strip_prefix("---")
+            .and_then(|rest| rest.split_once("\n---").map(|(fm, _)| fm))
+        {
+            let declared: Declared = serde_yaml::from_str(frontmatter)
+                .with_context(|| format!("Invalid dependencies in {}", skill_md.display()))?;
+            return Ok(declared.dependencies);
+        }
+    }
+
+    Ok(Vec::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn graph(edges: &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+        edges
+            .iter()
+            .map(|(skill, deps)| {
+                (
+                    skill.to_string(),
+                    deps.iter().map(|d| d.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn resolve(graph: &HashMap<String, Vec<String>>, root: &str) -> Result<Vec<String>> {
+        resolve_dependencies(root, |skill| Ok(graph.get(skill).cloned()))
+    }
+
+    #[test]
+    fn test_resolve_order() {
+        let graph = graph(&[
+            ("deploy", &["kubernetes", "helm"]),
+            ("helm", &["kubernetes"]),
+            ("kubernetes", &[]),
+        ]);
+
+        assert_eq!(
+            resolve(&graph, "deploy").unwrap(),
+            vec!["kubernetes", "helm"]
+        );
+        assert!(resolve(&graph, "kubernetes").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_missing_and_cycles() {
+        let graph = graph(&[("deploy", &["helm"]), ("a", &["b"]), ("b", &["a"])]);
+
+        let err = resolve(&graph, "deploy").unwrap_err().to_string();
+        assert!(err.contains("'deploy' depends on 'helm'"), "{}", err);
+
+        let err = resolve(&graph, "a").unwrap_err().to_string();
+        assert_eq!(err, "Dependency cycle: a -> b -> a");
+
+        assert!(resolve(&graph, "nope").is_err());
+    }
+
+    #[test]
+    fn test_read_declared_dependencies() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(read_declared_dependencies(dir.path()).unwrap().is_empty());
+
+        std::fs::write(
+            dir.path().join("SKILL.md"),
+            "---\nname: deploy\ndescription: Deploy apps\ndependencies:\n  - kubernetes\n  - name: helm\n    source: github:org/helm-skill\n---\n# Deploy\n",
+        )
+        .unwrap();
+
+        let deps = read_declared_dependencies(dir.path()).unwrap();
+        assert_eq!(
+            deps,
+            vec![
+                SkillDependency::Name("kubernetes".to_string()),
+                SkillDependency::Source {
+                    name: "helm".to_string(),
+                    source: "github:org/helm-skill".to_string(),
+                },
+            ]
+        );
+        assert_eq!(deps[1].source(), Some("github:org/helm-skill"));
+    }
+}
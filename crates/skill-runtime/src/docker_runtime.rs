@@ -20,12 +20,39 @@
 //! network = "none"
 //! rm = true
 //! ```
+//!
+//! # Keep-warm mode
+//!
+//! With `keep_warm = true` the first tool call starts a long-lived container
+//! (running `keepalive_command` instead of the entrypoint) and every call
+//! runs the tool in it with `docker exec`, skipping container startup.
+//! Containers are named after a hash of the configuration, so each skill
+//! instance gets its own and a changed configuration gets a fresh one.
+//! Before each call the container is checked to be running and not
+//! unhealthy, and recreated otherwise. Containers unused for
+//! `idle_timeout_secs` are removed by the next Docker tool call, along with
+//! their sidecars (see [`crate::docker_sidecars`]).
 
 use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
 use std::process::Command;
+use std::time::{Duration, SystemTime};
 use tracing::{debug, info, warn};
 
+use crate::docker_sidecars::SidecarGroup;
+use crate::docker_usage::{ContainerRef, UsageSampler};
 use crate::manifest::DockerRuntimeConfig;
+use crate::stdin::StdinSource;
+use crate::types::ResourceUsage;
+
+/// Label marking containers kept warm by [`DockerRuntime`]
+const WARM_LABEL: &str = "skill-engine.warm";
+
+/// Label holding a warm container's idle timeout in seconds
+const IDLE_TIMEOUT_LABEL: &str = "skill-engine.idle-timeout";
 
 /// Security constraints for Docker execution
 pub struct DockerSecurityPolicy {
@@ -61,19 +88,42 @@ impl Default for DockerSecurityPolicy {
 /// Docker runtime executor
 pub struct DockerRuntime {
     policy: DockerSecurityPolicy,
+    /// Where warm containers' last use is recorded (file modification times)
+    warm_state_dir: PathBuf,
+    /// Bytes of stdout or stderr after which the tool is stopped
+    max_output: Option<u64>,
 }
 
 impl DockerRuntime {
     /// Create a new Docker runtime with default security policy
     pub fn new() -> Self {
-        Self {
-            policy: DockerSecurityPolicy::default(),
-        }
+        Self::with_policy(DockerSecurityPolicy::default())
     }
 
     /// Create with custom security policy
     pub fn with_policy(policy: DockerSecurityPolicy) -> Self {
-        Self { policy }
+        let warm_state_dir = dirs::home_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join(".skill-engine")
+            .join("docker-warm");
+        Self {
+            policy,
+            warm_state_dir,
+            max_output: None,
+        }
+    }
+
+    /// Record warm containers' last use under `dir` instead of
+    /// `~/.skill-engine/docker-warm`
+    pub fn with_warm_state_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.warm_state_dir = dir.into();
+        self
+    }
+
+    /// Stop tools whose stdout or stderr goes over `max_output` bytes
+    pub fn with_max_output(mut self, max_output: Option<u64>) -> Self {
+        self.max_output = max_output;
+        self
     }
 
     /// Check if Docker is available
@@ -148,6 +198,31 @@ impl DockerRuntime {
             args.push("--rm".to_string());
         }
 
+        self.push_container_options(config, &mut args);
+
+        // Entrypoint override
+        if let Some(ref entrypoint) = config.entrypoint {
+            args.push("--entrypoint".to_string());
+            args.push(entrypoint.clone());
+        }
+
+        // Image
+        args.push(config.image.clone());
+
+        // Command/args
+        if let Some(ref cmd) = config.command {
+            args.extend(cmd.iter().cloned());
+        }
+
+        // Additional tool arguments
+        args.extend(tool_args.iter().cloned());
+
+        Ok(args)
+    }
+
+    /// Resource, mount, environment and security options shared by cold
+    /// and warm containers
+    fn push_container_options(&self, config: &DockerRuntimeConfig, args: &mut Vec<String>) {
         // Network mode (default: none for isolation)
         args.push("--network".to_string());
         args.push(config.network.clone());
@@ -209,41 +284,151 @@ impl DockerRuntime {
         for extra in &config.extra_args {
             args.push(extra.clone());
         }
+    }
 
-        // Entrypoint override
-        if let Some(ref entrypoint) = config.entrypoint {
-            args.push("--entrypoint".to_string());
-            args.push(entrypoint.clone());
-        }
-
-        // Image
+    /// Build the `docker run` arguments starting a warm container
+    ///
+    /// The keepalive command replaces the entrypoint; the container removes
+    /// itself if that command ever exits.
+    pub fn build_warm_start_command(&self, config: &DockerRuntimeConfig, name: &str) -> Result<Vec<String>> {
+        self.validate_config(config)?;
+        let (keepalive, keepalive_args) = config
+            .keepalive_command
+            .split_first()
+            .ok_or_else(|| anyhow!("keepalive_command must not be empty"))?;
+
+        let mut args = vec![
+            "run".to_string(),
+            "-d".to_string(),
+            "--rm".to_string(),
+            "--name".to_string(),
+            name.to_string(),
+            "--label".to_string(),
+            format!("{}=true", WARM_LABEL),
+            "--label".to_string(),
+            format!("{}={}", IDLE_TIMEOUT_LABEL, config.idle_timeout_secs),
+        ];
+        self.push_container_options(config, &mut args);
+        args.push("--entrypoint".to_string());
+        args.push(keepalive.clone());
         args.push(config.image.clone());
+        args.extend(keepalive_args.iter().cloned());
+        Ok(args)
+    }
 
-        // Command/args
-        if let Some(ref cmd) = config.command {
-            args.extend(cmd.iter().cloned());
+    /// Execute a Docker container and capture output
+    pub fn execute(
+        &self,
+        config: &DockerRuntimeConfig,
+        tool_args: &[String],
+    ) -> Result<DockerOutput> {
+        self.execute_with_stdin(config, tool_args, &StdinSource::Null)
+    }
+
+    /// Execute a Docker container with the given stdin and capture output
+    ///
+    /// Any input runs the container with `-i` so it reaches the tool.
+    pub fn execute_with_stdin(
+        &self,
+        config: &DockerRuntimeConfig,
+        tool_args: &[String],
+        stdin: &StdinSource,
+    ) -> Result<DockerOutput> {
+        if config.keep_warm {
+            return self.execute_warm(config, tool_args, stdin);
+        }
+        if config.sidecars.is_empty() {
+            return self.execute_cold(config, tool_args, stdin);
         }
 
-        // Additional tool arguments
-        args.extend(tool_args.iter().cloned());
+        // Sidecars live exactly as long as this one container
+        let group = SidecarGroup::new(format!("skill-run-{}", uuid::Uuid::new_v4().simple()), config)?;
+        let result = group
+            .up()
+            .and_then(|_| self.execute_cold(&group.attach(config), tool_args, stdin));
+        group.down();
+        result
+    }
 
-        Ok(args)
+    /// Execute a tool in a new container
+    fn execute_cold(
+        &self,
+        config: &DockerRuntimeConfig,
+        tool_args: &[String],
+        stdin: &StdinSource,
+    ) -> Result<DockerOutput> {
+        let mut args = self.build_command(config, tool_args)?;
+        if !stdin.is_null() {
+            args.insert(1, "-i".to_string());
+        }
+        // The container ID, for metering its cgroup while it runs
+        let cidfile = std::env::temp_dir().join(format!("skill-cid-{}", uuid::Uuid::new_v4().simple()));
+        args.insert(1, format!("--cidfile={}", cidfile.display()));
+        let output = self.run_tool(&args, stdin, ContainerRef::CidFile(cidfile.clone()));
+        let _ = std::fs::remove_file(&cidfile);
+        output
     }
 
-    /// Execute a Docker container and capture output
-    pub fn execute(
+    /// Execute a tool with `docker exec` in the configuration's warm
+    /// container, starting (or replacing) the container when needed
+    fn execute_warm(
         &self,
         config: &DockerRuntimeConfig,
         tool_args: &[String],
+        stdin: &StdinSource,
     ) -> Result<DockerOutput> {
-        let args = self.build_command(config, tool_args)?;
+        self.validate_config(config)?;
+        if let Err(e) = self.reap_idle_containers() {
+            warn!("Failed to remove idle warm containers: {:#}", e);
+        }
+
+        let name = warm_container_name(config);
+        // Sidecars are checked on every call and share the container's lifetime
+        let group = SidecarGroup::new(name.clone(), config)?;
+        group.up()?;
+        let attached;
+        let config = if config.sidecars.is_empty() {
+            config
+        } else {
+            attached = group.attach(config);
+            &attached
+        };
+        self.ensure_warm_container(config, &name)?;
 
+        let image = if config.entrypoint.is_some() && (config.command.is_some() || !tool_args.is_empty()) {
+            ImageConfig::default()
+        } else {
+            self.inspect_image(&config.image)?
+        };
+        let argv = exec_argv(config, tool_args, &image)?;
+
+        let mut args = vec!["exec".to_string()];
+        if !stdin.is_null() {
+            args.push("-i".to_string());
+        }
+        args.push(name.clone());
+        args.extend(argv);
+
+        self.touch_warm_container(&name);
+        let output = self.run_tool(&args, stdin, ContainerRef::Running(name.clone()));
+        self.touch_warm_container(&name);
+        output
+    }
+
+    /// Run a docker command and collect the tool's output, metering the
+    /// container it runs in
+    fn run_tool(&self, args: &[String], stdin: &StdinSource, container: ContainerRef) -> Result<DockerOutput> {
         debug!("Docker command: docker {}", args.join(" "));
 
-        let output = Command::new("docker")
-            .args(&args)
-            .output()
-            .context("Failed to execute docker command")?;
+        let sampler = UsageSampler::start(container);
+        let output = stdin.output_blocking(Command::new("docker").args(args), self.max_output);
+        let usage = sampler.finish();
+        let output = output.context("Failed to execute docker command")?;
+        let resource_usage = Some(
+            usage
+                .unwrap_or_default()
+                .with_output_bytes((output.stdout.len() + output.stderr.len()) as u64),
+        );
 
         let stdout = String::from_utf8_lossy(&output.stdout).to_string();
         let stderr = String::from_utf8_lossy(&output.stderr).to_string();
@@ -255,6 +440,7 @@ impl DockerRuntime {
                 stdout,
                 stderr,
                 exit_code: output.status.code().unwrap_or(0),
+                resource_usage,
             })
         } else {
             let exit_code = output.status.code().unwrap_or(-1);
@@ -264,10 +450,141 @@ impl DockerRuntime {
                 stdout,
                 stderr,
                 exit_code,
+                resource_usage,
             })
         }
     }
 
+    /// Make sure a warm container is running and not unhealthy, replacing
+    /// it otherwise
+    fn ensure_warm_container(&self, config: &DockerRuntimeConfig, name: &str) -> Result<()> {
+        match self.warm_container_state(name) {
+            WarmState::Ready => return Ok(()),
+            WarmState::Unhealthy => {
+                warn!(container = %name, "Warm container is unhealthy, replacing it");
+                self.remove_container(name);
+            }
+            WarmState::Missing => {}
+        }
+
+        info!(container = %name, image = %config.image, "Starting warm container");
+        let args = self.build_warm_start_command(config, name)?;
+        let output = Command::new("docker")
+            .args(&args)
+            .output()
+            .context("Failed to start warm container")?;
+        if !output.status.success() {
+            // Another invocation may have started it in the meantime
+            if self.warm_container_state(name) == WarmState::Ready {
+                return Ok(());
+            }
+            return Err(anyhow!(
+                "Failed to start warm container {}: {}",
+                name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Health of a warm container: running, and not failing the image's
+    /// `HEALTHCHECK` if it has one
+    fn warm_container_state(&self, name: &str) -> WarmState {
+        let output = Command::new("docker")
+            .args([
+                "inspect",
+                "--format",
+                "{{.State.Running}} {{if .State.Health}}{{.State.Health.Status}}{{end}}",
+                name,
+            ])
+            .output();
+        match output {
+            Ok(output) if output.status.success() => {
+                parse_warm_state(&String::from_utf8_lossy(&output.stdout))
+            }
+            _ => WarmState::Missing,
+        }
+    }
+
+    /// Remove warm containers that have been unused for longer than their
+    /// idle timeout; returns how many were removed
+    pub fn reap_idle_containers(&self) -> Result<usize> {
+        let output = Command::new("docker")
+            .args([
+                "ps",
+                "--all",
+                "--filter",
+                &format!("label={}", WARM_LABEL),
+                "--format",
+                &format!("{{{{.Names}}}} {{{{.Label \"{}\"}}}}", IDLE_TIMEOUT_LABEL),
+            ])
+            .output()
+            .context("Failed to list warm containers")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "Failed to list warm containers: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let mut removed = 0;
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let mut fields = line.split_whitespace();
+            let (Some(name), timeout) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let timeout = timeout.and_then(|t| t.parse().ok()).unwrap_or_else(default_idle_timeout);
+            let idle = self.idle_time(name);
+            if !idle.is_some_and(|idle| idle <= Duration::from_secs(timeout)) {
+                info!(container = %name, "Removing idle warm container");
+                self.remove_container(name);
+                SidecarGroup::remove(name);
+                let _ = std::fs::remove_file(self.warm_state_dir.join(name));
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Time since a warm container was last used, if it was ever recorded
+    fn idle_time(&self, name: &str) -> Option<Duration> {
+        let modified = std::fs::metadata(self.warm_state_dir.join(name)).ok()?.modified().ok()?;
+        Some(SystemTime::now().duration_since(modified).unwrap_or_default())
+    }
+
+    /// Record that a warm container was just used
+    fn touch_warm_container(&self, name: &str) {
+        let path = self.warm_state_dir.join(name);
+        let result = std::fs::create_dir_all(&self.warm_state_dir)
+            .and_then(|_| std::fs::write(&path, b""));
+        if let Err(e) = result {
+            warn!("Failed to record use of warm container {}: {}", name, e);
+        }
+    }
+
+    fn remove_container(&self, name: &str) {
+        if let Err(e) = Command::new("docker").args(["rm", "-f", name]).output() {
+            warn!("Failed to remove container {}: {}", name, e);
+        }
+    }
+
+    /// The image's own entrypoint and command, used when the configuration
+    /// doesn't override them
+    fn inspect_image(&self, image: &str) -> Result<ImageConfig> {
+        let output = Command::new("docker")
+            .args(["image", "inspect", "--format", "{{json .Config}}", image])
+            .output()
+            .context("Failed to inspect docker image")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "Failed to inspect image {}: {}",
+                image,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        serde_json::from_slice(&output.stdout).context("Failed to parse docker image config")
+    }
+
     /// Pull an image if not already present
     pub fn ensure_image(&self, image: &str) -> Result<()> {
         info!("Ensuring Docker image: {}", image);
@@ -305,6 +622,71 @@ impl Default for DockerRuntime {
     }
 }
 
+/// Entrypoint and command baked into an image
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ImageConfig {
+    #[serde(default)]
+    entrypoint: Option<Vec<String>>,
+    #[serde(default)]
+    cmd: Option<Vec<String>>,
+}
+
+/// Condition of a warm container
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WarmState {
+    /// Running, and healthy or without a health check
+    Ready,
+    /// Stopped, or failing its health check
+    Unhealthy,
+    /// No such container
+    Missing,
+}
+
+fn parse_warm_state(inspect: &str) -> WarmState {
+    let mut fields = inspect.split_whitespace();
+    let running = fields.next() == Some("true");
+    let health = fields.next();
+    if running && health != Some("unhealthy") {
+        WarmState::Ready
+    } else {
+        WarmState::Unhealthy
+    }
+}
+
+fn default_idle_timeout() -> u64 {
+    DockerRuntimeConfig::default().idle_timeout_secs
+}
+
+/// Name of the warm container for a configuration
+///
+/// Every option that shapes the container is hashed, so instances with
+/// different images, mounts or environment never share a container.
+pub fn warm_container_name(config: &DockerRuntimeConfig) -> String {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(config).unwrap_or_default().hash(&mut hasher);
+    format!("skill-warm-{:016x}", hasher.finish())
+}
+
+/// The argv `docker exec` runs for a tool call: the same program and
+/// arguments `docker run` would have started
+fn exec_argv(config: &DockerRuntimeConfig, tool_args: &[String], image: &ImageConfig) -> Result<Vec<String>> {
+    let mut argv = match &config.entrypoint {
+        Some(entrypoint) => vec![entrypoint.clone()],
+        None => image.entrypoint.clone().unwrap_or_default(),
+    };
+    match &config.command {
+        Some(command) => argv.extend(command.iter().chain(tool_args).cloned()),
+        // Arguments after the image replace its command
+        None if tool_args.is_empty() => argv.extend(image.cmd.clone().unwrap_or_default()),
+        None => argv.extend(tool_args.iter().cloned()),
+    }
+    if argv.is_empty() {
+        return Err(anyhow!("Image {} has no entrypoint or command to run", config.image));
+    }
+    Ok(argv)
+}
+
 /// Output from Docker container execution
 #[derive(Debug, Clone)]
 pub struct DockerOutput {
@@ -316,11 +698,15 @@ pub struct DockerOutput {
     pub stderr: String,
     /// Exit code returned by the container
     pub exit_code: i32,
+    /// CPU time and peak memory sampled from the container's cgroup, and
+    /// the output size
+    pub resource_usage: Option<ResourceUsage>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::Path;
 
     #[test]
     fn test_docker_runtime_creation() {
@@ -455,6 +841,87 @@ mod tests {
         assert!(args.contains(&"PORT=3000".to_string()));
     }
 
+    #[test]
+    fn test_warm_start_command() {
+        let runtime = DockerRuntime::new();
+        let config = DockerRuntimeConfig {
+            image: "python:3.11".to_string(),
+            entrypoint: Some("python".to_string()),
+            memory: Some("512m".to_string()),
+            keep_warm: true,
+            idle_timeout_secs: 120,
+            ..Default::default()
+        };
+
+        let name = warm_container_name(&config);
+        let args = runtime.build_warm_start_command(&config, &name).unwrap();
+        assert_eq!(&args[..5], ["run", "-d", "--rm", "--name", name.as_str()]);
+        assert!(args.contains(&"skill-engine.idle-timeout=120".to_string()));
+        assert!(args.contains(&"512m".to_string()));
+        // The keepalive command replaces the configured entrypoint
+        assert!(!args.contains(&"python".to_string()));
+        assert_eq!(&args[args.len() - 4..], ["--entrypoint", "sleep", "python:3.11", "infinity"]);
+
+        // Instances with another configuration get their own container
+        let other = DockerRuntimeConfig {
+            environment: vec!["ENV=prod".to_string()],
+            ..config.clone()
+        };
+        assert_ne!(warm_container_name(&other), name);
+        assert_eq!(warm_container_name(&config.clone()), name);
+    }
+
+    #[test]
+    fn test_exec_argv_matches_docker_run() {
+        let image = ImageConfig {
+            entrypoint: Some(vec!["/entrypoint.sh".to_string()]),
+            cmd: Some(vec!["--help".to_string()]),
+        };
+        let tool_args = vec!["convert".to_string(), "in.mp4".to_string()];
+
+        let config = DockerRuntimeConfig {
+            image: "ffmpeg".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(exec_argv(&config, &tool_args, &image).unwrap(), ["/entrypoint.sh", "convert", "in.mp4"]);
+        assert_eq!(exec_argv(&config, &[], &image).unwrap(), ["/entrypoint.sh", "--help"]);
+
+        let config = DockerRuntimeConfig {
+            image: "ffmpeg".to_string(),
+            entrypoint: Some("ffmpeg".to_string()),
+            command: Some(vec!["-y".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(exec_argv(&config, &tool_args, &image).unwrap(), ["ffmpeg", "-y", "convert", "in.mp4"]);
+
+        let bare = DockerRuntimeConfig { image: "scratch".to_string(), ..Default::default() };
+        assert!(exec_argv(&bare, &[], &ImageConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_warm_state_and_idle_tracking() {
+        assert_eq!(parse_warm_state("true \n"), WarmState::Ready);
+        assert_eq!(parse_warm_state("true healthy\n"), WarmState::Ready);
+        assert_eq!(parse_warm_state("true unhealthy\n"), WarmState::Unhealthy);
+        assert_eq!(parse_warm_state("false \n"), WarmState::Unhealthy);
+
+        let dir = tempfile::tempdir().unwrap();
+        let runtime = DockerRuntime::new().with_warm_state_dir(dir.path());
+        assert!(runtime.idle_time("skill-warm-0").is_none());
+        runtime.touch_warm_container("skill-warm-0");
+        assert!(runtime.idle_time("skill-warm-0").unwrap() < Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_output_dir_disables_keep_warm() {
+        let config = DockerRuntimeConfig {
+            image: "alpine".to_string(),
+            keep_warm: true,
+            ..Default::default()
+        };
+        assert!(!config.with_output_dir(Path::new("/tmp/out")).keep_warm);
+    }
+
     #[test]
     fn test_custom_security_policy() {
         let policy = DockerSecurityPolicy {
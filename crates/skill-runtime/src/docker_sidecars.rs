@@ -0,0 +1,393 @@
+//! Docker sidecars - containers a Docker skill needs running next to it
+//!
+//! A Docker skill can declare sidecars, such as a localstack container the
+//! aws skill's tests talk to:
+//!
+//! ```toml
+//! [[skills.aws.docker.sidecars]]
+//! name = "localstack"
+//! image = "localstack/localstack:3"
+//! port = 4566
+//! environment = ["SERVICES=s3,sqs"]
+//! health_check = ["curl", "-fs", "http://localhost:4566/_localstack/health"]
+//! inject_env = "AWS_ENDPOINT_URL"
+//! ```
+//!
+//! Before the skill's container starts, a [`SidecarGroup`] creates a network
+//! for it, starts each sidecar on that network and waits until it is ready.
+//! The skill's container joins the network and gets each sidecar's URL
+//! (`http://<name>:<port>`) in `inject_env`. The group is torn down with the
+//! skill: after the call for a cold container, or when an idle warm
+//! container is removed.
+
+use anyhow::{anyhow, Context, Result};
+use std::process::Command;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::manifest::{DockerRuntimeConfig, DockerSidecar};
+use crate::services::default_env_var;
+
+/// Label tying a sidecar to its group
+const SIDECAR_LABEL: &str = "skill-engine.sidecar-of";
+
+/// How long a sidecar gets to become ready unless it says otherwise
+const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Pause between readiness checks while a sidecar starts
+const READY_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The sidecars of one skill container, and the network they share
+pub struct SidecarGroup {
+    /// Group name; also the name of the network
+    name: String,
+    sidecars: Vec<DockerSidecar>,
+    /// Block traffic leaving the network, for skills with `network = "none"`
+    internal: bool,
+}
+
+impl SidecarGroup {
+    /// Group for a skill configuration, named `name`
+    pub fn new(name: impl Into<String>, config: &DockerRuntimeConfig) -> Result<Self> {
+        for sidecar in &config.sidecars {
+            let valid = !sidecar.name.is_empty()
+                && sidecar
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid {
+                return Err(anyhow!(
+                    "Invalid sidecar name '{}': use letters, digits, '-' and '_'",
+                    sidecar.name
+                ));
+            }
+            if sidecar.image.is_empty() {
+                return Err(anyhow!("Sidecar '{}' has no image", sidecar.name));
+            }
+        }
+        Ok(Self {
+            name: name.into(),
+            sidecars: config.sidecars.clone(),
+            internal: config.network == "none",
+        })
+    }
+
+    /// Name of the shared network
+    pub fn network(&self) -> &str {
+        &self.name
+    }
+
+    /// Container name of a sidecar
+    fn container_name(&self, sidecar: &DockerSidecar) -> String {
+        format!("{}-{}", self.name, sidecar.name)
+    }
+
+    /// The skill configuration joined to the network, with the sidecar URLs
+    /// in its environment
+    pub fn attach(&self, config: &DockerRuntimeConfig) -> DockerRuntimeConfig {
+        let mut config = config.clone();
+        config.network = self.name.clone();
+        config.environment.extend(self.injected_env());
+        config
+    }
+
+    /// `KEY=url` entries passing each sidecar's URL to the skill
+    pub fn injected_env(&self) -> Vec<String> {
+        self.sidecars
+            .iter()
+            .map(|sidecar| {
+                let key = sidecar
+                    .inject_env
+                    .clone()
+                    .unwrap_or_else(|| default_env_var(&sidecar.name));
+                let url = match sidecar.port {
+                    Some(port) => format!("http://{}:{}", sidecar.name, port),
+                    None => format!("http://{}", sidecar.name),
+                };
+                format!("{}={}", key, url)
+            })
+            .collect()
+    }
+
+    /// `docker run` arguments starting a sidecar
+    pub fn run_args(&self, sidecar: &DockerSidecar) -> Vec<String> {
+        let mut args = vec![
+            "run".to_string(),
+            "-d".to_string(),
+            "--rm".to_string(),
+            "--name".to_string(),
+            self.container_name(sidecar),
+            "--label".to_string(),
+            format!("{}={}", SIDECAR_LABEL, self.name),
+            "--network".to_string(),
+            self.name.clone(),
+            "--network-alias".to_string(),
+            sidecar.name.clone(),
+        ];
+        for env_var in &sidecar.environment {
+            args.push("-e".to_string());
+            args.push(env_var.clone());
+        }
+        args.push(sidecar.image.clone());
+        if let Some(ref command) = sidecar.command {
+            args.extend(command.iter().cloned());
+        }
+        args
+    }
+
+    /// Create the network and start every sidecar that isn't running, then
+    /// wait until all are ready
+    ///
+    /// Running sidecars are left alone, so calling this again for a warm
+    /// container only replaces sidecars that died.
+    pub fn up(&self) -> Result<()> {
+        if self.sidecars.is_empty() {
+            return Ok(());
+        }
+        self.create_network()?;
+
+        for sidecar in &self.sidecars {
+            let container = self.container_name(sidecar);
+            if container_running(&container) {
+                continue;
+            }
+            // A stopped leftover would keep the name taken
+            remove_container(&container);
+
+            info!(sidecar = %sidecar.name, image = %sidecar.image, "Starting sidecar");
+            let output = Command::new("docker")
+                .args(self.run_args(sidecar))
+                .output()
+                .context("Failed to start sidecar")?;
+            if !output.status.success() && !container_running(&container) {
+                return Err(anyhow!(
+                    "Failed to start sidecar '{}': {}",
+                    sidecar.name,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+        }
+
+        for sidecar in &self.sidecars {
+            self.wait_ready(sidecar)?;
+        }
+        Ok(())
+    }
+
+    fn create_network(&self) -> Result<()> {
+        let exists = Command::new("docker")
+            .args(["network", "inspect", &self.name])
+            .output()
+            .is_ok_and(|output| output.status.success());
+        if exists {
+            return Ok(());
+        }
+
+        let mut args = vec!["network", "create"];
+        if self.internal {
+            args.push("--internal");
+        }
+        args.push(&self.name);
+        let output = Command::new("docker")
+            .args(&args)
+            .output()
+            .context("Failed to create sidecar network")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // Another invocation may have created it in the meantime
+            if !stderr.contains("already exists") {
+                return Err(anyhow!("Failed to create network {}: {}", self.name, stderr.trim()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Wait until a sidecar passes its health check
+    fn wait_ready(&self, sidecar: &DockerSidecar) -> Result<()> {
+        let container = self.container_name(sidecar);
+        let timeout = sidecar
+            .startup_timeout_secs
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_STARTUP_TIMEOUT);
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.readiness(sidecar, &container) {
+                Readiness::Ready => return Ok(()),
+                Readiness::Stopped => {
+                    return Err(anyhow!(
+                        "Sidecar '{}' exited while starting; see 'docker logs {}'",
+                        sidecar.name,
+                        container
+                    ))
+                }
+                Readiness::Starting => {}
+            }
+            if Instant::now() >= deadline {
+                return Err(anyhow!(
+                    "Sidecar '{}' wasn't ready within {}s",
+                    sidecar.name,
+                    timeout.as_secs()
+                ));
+            }
+            std::thread::sleep(READY_INTERVAL);
+        }
+    }
+
+    fn readiness(&self, sidecar: &DockerSidecar, container: &str) -> Readiness {
+        let state = Command::new("docker")
+            .args([
+                "inspect",
+                "--format",
+                "{{.State.Running}} {{if .State.Health}}{{.State.Health.Status}}{{end}}",
+                container,
+            ])
+            .output();
+        let state = match state {
+            Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout).to_string(),
+            _ => return Readiness::Stopped,
+        };
+        let readiness = parse_readiness(&state);
+        if readiness != Readiness::Ready || sidecar.health_check.is_empty() {
+            return readiness;
+        }
+
+        let passed = Command::new("docker")
+            .arg("exec")
+            .arg(container)
+            .args(&sidecar.health_check)
+            .output()
+            .is_ok_and(|output| output.status.success());
+        if passed {
+            Readiness::Ready
+        } else {
+            debug!(sidecar = %sidecar.name, "Sidecar health check not passing yet");
+            Readiness::Starting
+        }
+    }
+
+    /// Remove the sidecars and the network
+    pub fn down(&self) {
+        Self::remove(&self.name);
+    }
+
+    /// Remove every sidecar of the group named `name`, and its network
+    pub fn remove(name: &str) {
+        let listed = Command::new("docker")
+            .args(["ps", "--all", "--quiet", "--filter", &format!("label={}={}", SIDECAR_LABEL, name)])
+            .output();
+        let Ok(listed) = listed else {
+            return;
+        };
+        let ids: Vec<String> = String::from_utf8_lossy(&listed.stdout)
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+        if ids.is_empty() {
+            return;
+        }
+
+        info!(group = %name, sidecars = ids.len(), "Removing sidecars");
+        for id in &ids {
+            remove_container(id);
+        }
+        let removed = Command::new("docker").args(["network", "rm", name]).output();
+        if !removed.is_ok_and(|output| output.status.success()) {
+            warn!("Failed to remove sidecar network {}", name);
+        }
+    }
+}
+
+/// How far a sidecar has come
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Readiness {
+    Ready,
+    Starting,
+    Stopped,
+}
+
+/// Readiness from `docker inspect` output: `<running> <health status>`
+fn parse_readiness(inspect: &str) -> Readiness {
+    let mut fields = inspect.split_whitespace();
+    if fields.next() != Some("true") {
+        return Readiness::Stopped;
+    }
+    match fields.next() {
+        Some("starting") => Readiness::Starting,
+        Some("unhealthy") => Readiness::Stopped,
+        _ => Readiness::Ready,
+    }
+}
+
+fn container_running(name: &str) -> bool {
+    Command::new("docker")
+        .args(["inspect", "--format", "{{.State.Running}}", name])
+        .output()
+        .is_ok_and(|output| output.status.success() && output.stdout.starts_with(b"true"))
+}
+
+fn remove_container(name: &str) {
+    if let Err(e) = Command::new("docker").args(["rm", "-f", name]).output() {
+        warn!("Failed to remove container {}: {}", name, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> DockerRuntimeConfig {
+        DockerRuntimeConfig {
+            image: "amazon/aws-cli".to_string(),
+            environment: vec!["AWS_REGION=us-east-1".to_string()],
+            sidecars: vec![DockerSidecar {
+                name: "localstack".to_string(),
+                image: "localstack/localstack:3".to_string(),
+                environment: vec!["SERVICES=s3".to_string()],
+                port: Some(4566),
+                inject_env: Some("AWS_ENDPOINT_URL".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_attach_wires_network_and_urls() {
+        let group = SidecarGroup::new("skill-run-1", &config()).unwrap();
+        assert!(group.internal);
+
+        let attached = group.attach(&config());
+        assert_eq!(attached.network, "skill-run-1");
+        assert_eq!(
+            attached.environment,
+            ["AWS_REGION=us-east-1", "AWS_ENDPOINT_URL=http://localstack:4566"]
+        );
+
+        let args = group.run_args(&config().sidecars[0]);
+        assert_eq!(&args[..5], ["run", "-d", "--rm", "--name", "skill-run-1-localstack"]);
+        assert!(args.windows(2).any(|w| w == ["--network-alias", "localstack"]));
+        assert!(args.windows(2).any(|w| w == ["-e", "SERVICES=s3"]));
+        assert_eq!(args.last().unwrap(), "localstack/localstack:3");
+    }
+
+    #[test]
+    fn test_default_env_var_and_validation() {
+        let mut config = config();
+        config.sidecars[0].inject_env = None;
+        config.sidecars[0].port = None;
+        let group = SidecarGroup::new("g", &config).unwrap();
+        assert_eq!(group.injected_env(), ["LOCALSTACK_URL=http://localstack"]);
+
+        config.sidecars[0].name = "local stack".to_string();
+        assert!(SidecarGroup::new("g", &config).is_err());
+    }
+
+    #[test]
+    fn test_parse_readiness() {
+        assert_eq!(parse_readiness("true \n"), Readiness::Ready);
+        assert_eq!(parse_readiness("true healthy\n"), Readiness::Ready);
+        assert_eq!(parse_readiness("true starting\n"), Readiness::Starting);
+        assert_eq!(parse_readiness("true unhealthy\n"), Readiness::Stopped);
+        assert_eq!(parse_readiness("false \n"), Readiness::Stopped);
+    }
+}
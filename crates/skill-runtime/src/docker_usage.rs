@@ -0,0 +1,181 @@
+//! Docker usage - CPU and memory metering for containers via cgroups
+//!
+//! A container's cgroup disappears as soon as it exits, so usage is sampled
+//! while the docker command runs: a background thread reads the
+//! container's `cpu.stat` and memory files every [`SAMPLE_INTERVAL`] and
+//! keeps the latest CPU counter and the highest memory reading. Work done in
+//! the last interval before exit can be missed, so short runs may
+//! under-report.
+//!
+//! Both cgroup v2 (`system.slice/docker-<id>.scope` or `docker/<id>`) and
+//! v1 (`cpuacct`/`memory` hierarchies) layouts are read. Where neither is
+//! visible (Docker Desktop, rootless Docker, remote daemons) nothing is
+//! reported.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::types::ResourceUsage;
+
+/// How often a running container's cgroup is read
+pub const SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// The container whose usage is sampled
+#[derive(Debug, Clone)]
+pub enum ContainerRef {
+    /// A container being started with `docker run --cidfile <path>`; its
+    /// whole lifetime is the execution
+    CidFile(PathBuf),
+    /// An already-running container (keep-warm); only usage after sampling
+    /// started counts
+    Running(String),
+}
+
+/// One reading of a container's cgroup
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct CgroupReading {
+    cpu_usec: Option<u64>,
+    memory_bytes: Option<u64>,
+}
+
+/// Samples a container's cgroup until [`UsageSampler::finish`] is called
+pub struct UsageSampler {
+    stop: mpsc::Sender<()>,
+    handle: JoinHandle<Option<ResourceUsage>>,
+}
+
+impl UsageSampler {
+    /// Start sampling in a background thread
+    pub fn start(container: ContainerRef) -> Self {
+        let (stop, stopped) = mpsc::channel();
+        let handle = std::thread::spawn(move || sample(container, stopped));
+        Self { stop, handle }
+    }
+
+    /// Stop sampling and return what was measured
+    pub fn finish(self) -> Option<ResourceUsage> {
+        let _ = self.stop.send(());
+        self.handle.join().ok().flatten()
+    }
+}
+
+fn sample(container: ContainerRef, stopped: mpsc::Receiver<()>) -> Option<ResourceUsage> {
+    let mut id = match &container {
+        ContainerRef::CidFile(_) => None,
+        ContainerRef::Running(name) => container_id(name),
+    };
+    let mut first_cpu = None;
+    let mut last_cpu = None;
+    let mut peak_memory = None;
+
+    loop {
+        if id.is_none() {
+            if let ContainerRef::CidFile(path) = &container {
+                id = std::fs::read_to_string(path)
+                    .ok()
+                    .map(|id| id.trim().to_string())
+                    .filter(|id| !id.is_empty());
+            }
+        }
+        if let Some(reading) = id.as_deref().and_then(read_cgroup) {
+            if reading.cpu_usec.is_some() {
+                first_cpu = first_cpu.or(reading.cpu_usec);
+                last_cpu = reading.cpu_usec;
+            }
+            peak_memory = peak_memory.max(reading.memory_bytes);
+        }
+        match stopped.recv_timeout(SAMPLE_INTERVAL) {
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            _ => break,
+        }
+    }
+
+    // A fresh container's counter starts at zero; a warm one's counts from
+    // the first reading
+    let cpu_usec = match container {
+        ContainerRef::CidFile(_) => last_cpu,
+        ContainerRef::Running(_) => last_cpu.zip(first_cpu).map(|(last, first)| last.saturating_sub(first)),
+    };
+    let usage = ResourceUsage {
+        cpu_time_ms: cpu_usec.map(|usec| usec / 1000),
+        peak_memory_kb: peak_memory.map(|bytes| bytes / 1024),
+        output_bytes: None,
+    };
+    (!usage.is_empty()).then_some(usage)
+}
+
+/// Full ID of a running container
+fn container_id(name: &str) -> Option<String> {
+    let output = Command::new("docker")
+        .args(["inspect", "--format", "{{.Id}}", name])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|id| !id.is_empty())
+}
+
+/// Read a container's CPU and memory counters from whichever cgroup layout
+/// the host uses
+fn read_cgroup(id: &str) -> Option<CgroupReading> {
+    let root = Path::new(CGROUP_ROOT);
+    for dir in [
+        root.join("system.slice").join(format!("docker-{}.scope", id)),
+        root.join("docker").join(id),
+    ] {
+        if let Ok(stat) = std::fs::read_to_string(dir.join("cpu.stat")) {
+            // memory.peak needs Linux 5.19; fall back to the current size
+            let memory = read_u64(&dir.join("memory.peak")).or_else(|| read_u64(&dir.join("memory.current")));
+            return Some(CgroupReading {
+                cpu_usec: parse_cpu_stat(&stat),
+                memory_bytes: memory,
+            });
+        }
+    }
+
+    // cgroup v1
+    let cpu_ns = read_u64(&root.join("cpuacct/docker").join(id).join("cpuacct.usage"));
+    let memory = read_u64(&root.join("memory/docker").join(id).join("memory.max_usage_in_bytes"));
+    (cpu_ns.is_some() || memory.is_some()).then(|| CgroupReading {
+        cpu_usec: cpu_ns.map(|ns| ns / 1000),
+        memory_bytes: memory,
+    })
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// `usage_usec` from a cgroup v2 `cpu.stat` file
+fn parse_cpu_stat(stat: &str) -> Option<u64> {
+    stat.lines()
+        .find_map(|line| line.strip_prefix("usage_usec "))
+        .and_then(|value| value.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpu_stat() {
+        let stat = "usage_usec 1523400\nuser_usec 1200000\nsystem_usec 323400\nnr_periods 0\n";
+        assert_eq!(parse_cpu_stat(stat), Some(1523400));
+        assert_eq!(parse_cpu_stat("nr_periods 0\n"), None);
+    }
+
+    #[test]
+    fn test_missing_container_reports_nothing() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let sampler = UsageSampler::start(ContainerRef::CidFile(dir.path().join("cid")));
+        std::thread::sleep(SAMPLE_INTERVAL / 2);
+        assert_eq!(sampler.finish(), None);
+    }
+}
@@ -40,12 +40,17 @@ mod fastembed;
 mod openai;
 mod ollama;
 mod factory;
+mod models;
 
 pub use types::*;
 pub use fastembed::FastEmbedProvider;
 pub use openai::OpenAIEmbedProvider;
 pub use ollama::OllamaProvider;
 pub use factory::{EmbeddingProviderFactory, create_provider};
+pub use models::{
+    ModelStore, ModelSpec, ModelKind, InstalledModel, ModelFileRecord, DownloadProgress,
+    MODEL_CATALOG, OFFLINE_ENV, offline_mode, prepare_fastembed_model,
+};
 
 use async_trait::async_trait;
 use anyhow::Result;
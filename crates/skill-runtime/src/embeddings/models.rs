@@ -0,0 +1,671 @@
+//! Local model management for FastEmbed
+//!
+//! Pre-downloads embedding and reranker models into `~/.skill-engine/models`
+//! so semantic search works on air-gapped machines. Files are laid out like
+//! the Hugging Face hub cache (`models--org--name/snapshots/<rev>/...`), which
+//! is what FastEmbed reads, and every file is checked against the SHA-256
+//! published by the hub before it is moved into place.
+//!
+//! Offline mode (`SKILL_OFFLINE=1`, or `offline = true` under `[embedding]` in
+//! search.toml) makes the search pipeline fail fast when a required model is
+//! missing, instead of letting FastEmbed download it.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use ring::digest;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::FastEmbedModel;
+use crate::checksum::to_hex;
+
+/// Environment variable that enables offline mode
+pub const OFFLINE_ENV: &str = "SKILL_OFFLINE";
+
+/// Environment variable overriding the Hugging Face endpoint (for mirrors)
+const HF_ENDPOINT_ENV: &str = "HF_ENDPOINT";
+
+/// Default Hugging Face endpoint
+const DEFAULT_HF_ENDPOINT: &str = "https://huggingface.co";
+
+/// Environment variable FastEmbed reads its cache directory from
+const FASTEMBED_CACHE_ENV: &str = "FASTEMBED_CACHE_PATH";
+
+/// Record of a downloaded model, stored next to the model files
+const MANIFEST_FILE: &str = "skill-model.json";
+
+/// Tokenizer files FastEmbed loads alongside the ONNX model
+const REQUIRED_TOKENIZER_FILES: &[&str] = &["tokenizer.json", "config.json"];
+const OPTIONAL_TOKENIZER_FILES: &[&str] = &["tokenizer_config.json", "special_tokens_map.json"];
+
+/// What a model is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelKind {
+    /// Text embedding model (dense retrieval)
+    Embedding,
+    /// Cross-encoder reranker
+    Reranker,
+}
+
+impl std::fmt::Display for ModelKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Embedding => write!(f, "embedding"),
+            Self::Reranker => write!(f, "reranker"),
+        }
+    }
+}
+
+/// A model FastEmbed can load, and where it comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelSpec {
+    /// Short name used in search.toml and on the command line
+    pub name: &'static str,
+    /// What the model is used for
+    pub kind: ModelKind,
+    /// Hugging Face repository
+    pub repo: &'static str,
+    /// Path of the ONNX model inside the repository
+    pub model_file: &'static str,
+}
+
+/// Models that can be managed locally
+pub const MODEL_CATALOG: &[ModelSpec] = &[
+    ModelSpec {
+        name: "all-minilm",
+        kind: ModelKind::Embedding,
+        repo: "Xenova/all-MiniLM-L6-v2",
+        model_file: "onnx/model_quantized.onnx",
+    },
+    ModelSpec {
+        name: "bge-small",
+        kind: ModelKind::Embedding,
+        repo: "Qdrant/bge-small-en-v1.5-onnx-Q",
+        model_file: "model_optimized.onnx",
+    },
+    ModelSpec {
+        name: "bge-base",
+        kind: ModelKind::Embedding,
+        repo: "Xenova/bge-base-en-v1.5",
+        model_file: "onnx/model.onnx",
+    },
+    ModelSpec {
+        name: "bge-large",
+        kind: ModelKind::Embedding,
+        repo: "Xenova/bge-large-en-v1.5",
+        model_file: "onnx/model.onnx",
+    },
+    ModelSpec {
+        name: "bge-reranker-base",
+        kind: ModelKind::Reranker,
+        repo: "BAAI/bge-reranker-base",
+        model_file: "onnx/model.onnx",
+    },
+    ModelSpec {
+        name: "bge-reranker-v2-m3",
+        kind: ModelKind::Reranker,
+        repo: "rozgo/bge-reranker-v2-m3",
+        model_file: "model.onnx",
+    },
+    ModelSpec {
+        name: "jina-reranker-v1-turbo-en",
+        kind: ModelKind::Reranker,
+        repo: "jinaai/jina-reranker-v1-turbo-en",
+        model_file: "onnx/model.onnx",
+    },
+    ModelSpec {
+        name: "jina-reranker-v2-base-multilingual",
+        kind: ModelKind::Reranker,
+        repo: "jinaai/jina-reranker-v2-base-multilingual",
+        model_file: "onnx/model.onnx",
+    },
+];
+
+impl ModelSpec {
+    /// Look up a model by short name, alias, or Hugging Face repository
+    pub fn find(name: &str) -> Option<&'static ModelSpec> {
+        let lower = name.to_lowercase();
+        if let Some(spec) = MODEL_CATALOG
+            .iter()
+            .find(|s| s.name == lower || s.repo.to_lowercase() == lower)
+        {
+            return Some(spec);
+        }
+
+        // Embedding aliases (e.g. "minilm", "bge-small-en")
+        if let Ok(model) = lower.parse::<FastEmbedModel>() {
+            return Some(Self::for_embedding(model));
+        }
+
+        // Reranker aliases, matching the names accepted in search.toml
+        let reranker = match lower.as_str() {
+            "bge-v2-m3" | "baai/bge-reranker-v2-m3" => "bge-reranker-v2-m3",
+            "jina-turbo" | "jina-v1-turbo" => "jina-reranker-v1-turbo-en",
+            "jina-base" | "jina-v2-base" => "jina-reranker-v2-base-multilingual",
+            _ => return None,
+        };
+        MODEL_CATALOG.iter().find(|s| s.name == reranker)
+    }
+
+    /// Look up a reranker by the model name used in search.toml
+    pub fn for_reranker(name: &str) -> Option<&'static ModelSpec> {
+        let lower = name.to_lowercase();
+        // "bge-base" is ambiguous between the embedding and reranker catalogs
+        let lower = if lower == "bge-base" {
+            "bge-reranker-base".to_string()
+        } else {
+            lower
+        };
+        Self::find(&lower).filter(|s| s.kind == ModelKind::Reranker)
+    }
+
+    /// The catalog entry for a FastEmbed embedding model
+    pub fn for_embedding(model: FastEmbedModel) -> &'static ModelSpec {
+        let name = model.to_string();
+        MODEL_CATALOG
+            .iter()
+            .find(|s| s.name == name)
+            .expect("every FastEmbedModel has a catalog entry")
+    }
+
+    /// Directory name used by the Hugging Face cache layout
+    fn cache_dir_name(&self) -> String {
+        format!("models--{}", self.repo.replace('/', "--"))
+    }
+}
+
+/// A file belonging to a downloaded model
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelFileRecord {
+    /// Path relative to the model snapshot
+    pub path: String,
+    /// Size in bytes
+    pub size: u64,
+    /// Hex-encoded SHA-256
+    pub sha256: String,
+}
+
+/// A model that has been downloaded into the store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledModel {
+    /// Short model name
+    pub name: String,
+    /// Model kind
+    pub kind: ModelKind,
+    /// Hugging Face repository
+    pub repo: String,
+    /// Repository revision (commit) the files were downloaded from
+    pub revision: String,
+    /// Downloaded files
+    pub files: Vec<ModelFileRecord>,
+    /// When the model was downloaded
+    pub downloaded_at: DateTime<Utc>,
+}
+
+impl InstalledModel {
+    /// Total size of all files in bytes
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|f| f.size).sum()
+    }
+}
+
+/// Progress event emitted while downloading a model
+#[derive(Debug, Clone)]
+pub struct DownloadProgress {
+    /// File being downloaded
+    pub file: String,
+    /// Bytes downloaded so far for this file
+    pub downloaded: u64,
+    /// Expected size of the file, if known
+    pub total: Option<u64>,
+}
+
+/// Entry in the Hugging Face tree listing
+#[derive(Debug, Deserialize)]
+struct HubTreeEntry {
+    path: String,
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    lfs: Option<HubLfsInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HubLfsInfo {
+    /// SHA-256 of the file contents
+    oid: String,
+    size: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct HubRevision {
+    sha: String,
+}
+
+/// Whether offline mode is enabled through the environment
+pub fn offline_mode() -> bool {
+    std::env::var(OFFLINE_ENV)
+        .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+        .unwrap_or(false)
+}
+
+/// Local store of FastEmbed models
+#[derive(Debug, Clone)]
+pub struct ModelStore {
+    root: PathBuf,
+}
+
+impl ModelStore {
+    /// Default store location (`~/.skill-engine/models`)
+    pub fn default_root() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Failed to get home directory")?;
+        Ok(home.join(".skill-engine").join("models"))
+    }
+
+    /// Open the store at the default location
+    pub fn open_default() -> Result<Self> {
+        Ok(Self::new(Self::default_root()?))
+    }
+
+    /// Open a store rooted at `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Store root directory (usable as FastEmbed's cache directory)
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn repo_dir(&self, spec: &ModelSpec) -> PathBuf {
+        self.root.join(spec.cache_dir_name())
+    }
+
+    fn snapshot_dir(&self, spec: &ModelSpec, revision: &str) -> PathBuf {
+        self.repo_dir(spec).join("snapshots").join(revision)
+    }
+
+    /// Get the installed record for a model, if it has been downloaded
+    pub fn installed(&self, spec: &ModelSpec) -> Result<Option<InstalledModel>> {
+        let path = self.repo_dir(spec).join(MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let model = serde_json::from_str(&content)
+            .with_context(|| format!("Corrupt model record: {}", path.display()))?;
+        Ok(Some(model))
+    }
+
+    /// All catalog models with their install state
+    pub fn list(&self) -> Result<Vec<(&'static ModelSpec, Option<InstalledModel>)>> {
+        MODEL_CATALOG
+            .iter()
+            .map(|spec| Ok((spec, self.installed(spec)?)))
+            .collect()
+    }
+
+    /// Require a model to be present, for use in offline mode
+    pub fn require(&self, spec: &ModelSpec) -> Result<InstalledModel> {
+        self.installed(spec)?.with_context(|| {
+            format!(
+                "Model '{}' is not downloaded and offline mode is enabled. \
+                 Run 'skill models download {}' on a connected machine and copy {} to this host.",
+                spec.name,
+                spec.name,
+                self.root.display()
+            )
+        })
+    }
+
+    /// Re-hash every file of an installed model against its recorded checksum
+    pub fn verify(&self, spec: &ModelSpec) -> Result<InstalledModel> {
+        let model = self
+            .installed(spec)?
+            .with_context(|| format!("Model '{}' is not downloaded", spec.name))?;
+        let snapshot = self.snapshot_dir(spec, &model.revision);
+
+        for file in &model.files {
+            let path = snapshot.join(&file.path);
+            let actual = sha256_file(&path)
+                .with_context(|| format!("Missing model file: {}", path.display()))?;
+            if actual != file.sha256 {
+                anyhow::bail!(
+                    "Checksum mismatch for {} (expected {}, found {})",
+                    path.display(),
+                    file.sha256,
+                    actual
+                );
+            }
+        }
+
+        Ok(model)
+    }
+
+    /// Delete a downloaded model
+    pub fn remove(&self, spec: &ModelSpec) -> Result<bool> {
+        let dir = self.repo_dir(spec);
+        if !dir.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_dir_all(&dir)
+            .with_context(|| format!("Failed to remove {}", dir.display()))?;
+        Ok(true)
+    }
+
+    /// Download a model, verifying each file against the hub's SHA-256
+    pub async fn download(&self, spec: &ModelSpec, force: bool) -> Result<InstalledModel> {
+        self.download_with_progress(spec, force, |_| {}).await
+    }
+
+    /// Download a model, reporting per-file progress
+    pub async fn download_with_progress(
+        &self,
+        spec: &ModelSpec,
+        force: bool,
+        mut on_progress: impl FnMut(&DownloadProgress),
+    ) -> Result<InstalledModel> {
+        if offline_mode() {
+            anyhow::bail!(
+                "Refusing to download '{}' because offline mode is enabled ({}=1)",
+                spec.name,
+                OFFLINE_ENV
+            );
+        }
+
+        if !force {
+            if let Some(model) = self.installed(spec)? {
+                return Ok(model);
+            }
+        }
+
+        let endpoint =
+            std::env::var(HF_ENDPOINT_ENV).unwrap_or_else(|_| DEFAULT_HF_ENDPOINT.to_string());
+        let endpoint = endpoint.trim_end_matches('/');
+        let client = reqwest::Client::builder()
+            .user_agent(concat!("skill-engine/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+
+        // Pin the revision so every file comes from the same commit
+        let revision: HubRevision = client
+            .get(format!(
+                "{}/api/models/{}/revision/main",
+                endpoint, spec.repo
+            ))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .with_context(|| format!("Failed to resolve revision for {}", spec.repo))?
+            .json()
+            .await?;
+
+        let tree: Vec<HubTreeEntry> = client
+            .get(format!(
+                "{}/api/models/{}/tree/{}?recursive=true",
+                endpoint, spec.repo, revision.sha
+            ))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .with_context(|| format!("Failed to list files for {}", spec.repo))?
+            .json()
+            .await?;
+
+        let mut wanted: Vec<(&str, bool)> = vec![(spec.model_file, true)];
+        wanted.extend(REQUIRED_TOKENIZER_FILES.iter().map(|f| (*f, true)));
+        wanted.extend(OPTIONAL_TOKENIZER_FILES.iter().map(|f| (*f, false)));
+
+        let snapshot = self.snapshot_dir(spec, &revision.sha);
+        let mut files = Vec::new();
+
+        for (path, required) in wanted {
+            let Some(entry) = tree.iter().find(|e| e.path == path) else {
+                if required {
+                    anyhow::bail!("{} not found in {}@{}", path, spec.repo, revision.sha);
+                }
+                continue;
+            };
+
+            let url = format!(
+                "{}/{}/resolve/{}/{}",
+                endpoint, spec.repo, revision.sha, path
+            );
+            let expected_size = entry.lfs.as_ref().map_or(entry.size, |l| l.size);
+            let record = download_file(
+                &client,
+                &url,
+                &snapshot.join(path),
+                path,
+                expected_size,
+                entry.lfs.as_ref().map(|l| l.oid.as_str()),
+                &mut on_progress,
+            )
+            .await?;
+            files.push(record);
+        }
+
+        // refs/main lets FastEmbed's hub cache resolve the snapshot offline
+        let repo_dir = self.repo_dir(spec);
+        std::fs::create_dir_all(repo_dir.join("refs"))?;
+        std::fs::write(repo_dir.join("refs").join("main"), &revision.sha)?;
+
+        let model = InstalledModel {
+            name: spec.name.to_string(),
+            kind: spec.kind,
+            repo: spec.repo.to_string(),
+            revision: revision.sha,
+            files,
+            downloaded_at: Utc::now(),
+        };
+        std::fs::write(
+            repo_dir.join(MANIFEST_FILE),
+            serde_json::to_string_pretty(&model)?,
+        )?;
+
+        Ok(model)
+    }
+}
+
+/// Check offline mode for a FastEmbed model and point FastEmbed at the store
+///
+/// Returns the store root when the model is available locally. In offline
+/// mode a missing model is an error rather than a silent download.
+pub fn prepare_fastembed_model(spec: &ModelSpec, offline: bool) -> Result<Option<PathBuf>> {
+    let store = ModelStore::open_default()?;
+    let offline = offline || offline_mode();
+
+    let installed = if offline {
+        Some(store.require(spec)?)
+    } else {
+        store.installed(spec)?
+    };
+
+    if installed.is_none() {
+        return Ok(None);
+    }
+
+    // rig-fastembed does not expose FastEmbed's cache options, so use the
+    // environment variable FastEmbed falls back to (unless the user set it)
+    if std::env::var_os(FASTEMBED_CACHE_ENV).is_none() {
+        std::env::set_var(FASTEMBED_CACHE_ENV, store.root());
+    }
+
+    Ok(Some(store.root().to_path_buf()))
+}
+
+async fn download_file(
+    client: &reqwest::Client,
+    url: &str,
+    dest: &Path,
+    name: &str,
+    expected_size: u64,
+    expected_sha256: Option<&str>,
+    on_progress: &mut impl FnMut(&DownloadProgress),
+) -> Result<ModelFileRecord> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut partial = dest.as_os_str().to_owned();
+    partial.push(".part");
+    let partial = PathBuf::from(partial);
+
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .with_context(|| format!("Failed to download {}", url))?;
+
+    let total = response
+        .content_length()
+        .or(Some(expected_size).filter(|s| *s > 0));
+    let mut out = std::fs::File::create(&partial)
+        .with_context(|| format!("Failed to create {}", partial.display()))?;
+    let mut hasher = digest::Context::new(&digest::SHA256);
+    let mut downloaded = 0u64;
+
+    while let Some(chunk) = response.chunk().await? {
+        out.write_all(&chunk)?;
+        hasher.update(&chunk);
+        downloaded += chunk.len() as u64;
+        on_progress(&DownloadProgress {
+            file: name.to_string(),
+            downloaded,
+            total,
+        });
+    }
+    out.flush()?;
+    drop(out);
+
+    let sha256 = to_hex(hasher.finish().as_ref());
+    let verified = match expected_sha256 {
+        Some(expected) => expected.eq_ignore_ascii_case(&sha256),
+        // Small non-LFS files have no published SHA-256; fall back to size
+        None => expected_size == 0 || expected_size == downloaded,
+    };
+    if !verified {
+        let _ = std::fs::remove_file(&partial);
+        anyhow::bail!("Checksum verification failed for {}", name);
+    }
+
+    std::fs::rename(&partial, dest)
+        .with_context(|| format!("Failed to move {} into place", dest.display()))?;
+
+    Ok(ModelFileRecord {
+        path: name.to_string(),
+        size: downloaded,
+        sha256,
+    })
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = digest::Context::new(&digest::SHA256);
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = std::io::Read::read(&mut file, &mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(to_hex(hasher.finish().as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn install_fake(store: &ModelStore, spec: &ModelSpec, content: &[u8]) {
+        let snapshot = store.snapshot_dir(spec, "abc123");
+        let path = snapshot.join(spec.model_file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content).unwrap();
+
+        let model = InstalledModel {
+            name: spec.name.to_string(),
+            kind: spec.kind,
+            repo: spec.repo.to_string(),
+            revision: "abc123".to_string(),
+            files: vec![ModelFileRecord {
+                path: spec.model_file.to_string(),
+                size: content.len() as u64,
+                sha256: sha256_file(&path).unwrap(),
+            }],
+            downloaded_at: Utc::now(),
+        };
+        std::fs::write(
+            store.repo_dir(spec).join(MANIFEST_FILE),
+            serde_json::to_string(&model).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_catalog_covers_every_embedding_model() {
+        for model in [
+            FastEmbedModel::AllMiniLM,
+            FastEmbedModel::BGESmallEN,
+            FastEmbedModel::BGEBaseEN,
+            FastEmbedModel::BGELargeEN,
+        ] {
+            let spec = ModelSpec::for_embedding(model);
+            assert_eq!(spec.kind, ModelKind::Embedding);
+        }
+    }
+
+    #[test]
+    fn test_find_by_name_alias_and_repo() {
+        assert_eq!(
+            ModelSpec::find("all-minilm").unwrap().repo,
+            "Xenova/all-MiniLM-L6-v2"
+        );
+        assert_eq!(ModelSpec::find("minilm").unwrap().name, "all-minilm");
+        assert_eq!(
+            ModelSpec::find("BAAI/bge-reranker-base").unwrap().name,
+            "bge-reranker-base"
+        );
+        assert_eq!(
+            ModelSpec::for_reranker("bge-base").unwrap().name,
+            "bge-reranker-base"
+        );
+        assert_eq!(
+            ModelSpec::for_reranker("jina-turbo").unwrap().name,
+            "jina-reranker-v1-turbo-en"
+        );
+        assert!(ModelSpec::find("does-not-exist").is_none());
+    }
+
+    #[test]
+    fn test_hf_cache_layout() {
+        let spec = ModelSpec::find("bge-small").unwrap();
+        assert_eq!(
+            spec.cache_dir_name(),
+            "models--Qdrant--bge-small-en-v1.5-onnx-Q"
+        );
+    }
+
+    #[test]
+    fn test_verify_and_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ModelStore::new(dir.path());
+        let spec = ModelSpec::find("all-minilm").unwrap();
+
+        assert!(store.installed(spec).unwrap().is_none());
+        assert!(store.require(spec).is_err());
+
+        install_fake(&store, spec, b"onnx bytes");
+        assert_eq!(store.verify(spec).unwrap().total_size(), 10);
+
+        // Tamper with the file
+        let path = store.snapshot_dir(spec, "abc123").join(spec.model_file);
+        std::fs::write(&path, b"tampered!!").unwrap();
+        assert!(store.verify(spec).is_err());
+
+        assert!(store.remove(spec).unwrap());
+        assert!(!store.remove(spec).unwrap());
+    }
+}
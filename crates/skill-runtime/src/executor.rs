@@ -1,5 +1,5 @@
 use anyhow::{Context, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Instant;
 use wasmtime::{
@@ -7,10 +7,15 @@ use wasmtime::{
     Store,
 };
 
+use skill_context::{ExecutionContext, ExecutionLimits, Mount};
+
+use crate::artifacts::{GUEST_OUTPUT_DIR, OUTPUT_DIR_ENV};
+use crate::audit::AuditLogger;
 use crate::engine::SkillEngine;
 use crate::instance::InstanceConfig;
-use crate::sandbox::SandboxBuilder;
-use crate::types::{ExecutionResult, SkillMetadata, ToolDefinition, Parameter, ParameterType};
+use crate::limits::{cap_output, LimitExceeded, WriteTracker};
+use crate::sandbox::{HostState, SandboxBuilder};
+use crate::types::{ExecutionResult, Parameter, ParameterType, ResourceUsage, SkillMetadata, ToolDefinition};
 
 // Generate WIT bindings for the skill interface
 // TODO: Add host function imports for configuration access
@@ -39,6 +44,12 @@ pub struct SkillExecutor {
     instance_name: String,
     config: InstanceConfig,
     component: Component,
+    audit_logger: Option<Arc<AuditLogger>>,
+    context: Option<ExecutionContext>,
+    output_dir: Option<PathBuf>,
+    stdin: Option<Vec<u8>>,
+    instance_dir: Option<PathBuf>,
+    limits: Option<ExecutionLimits>,
 }
 
 impl SkillExecutor {
@@ -79,6 +90,12 @@ impl SkillExecutor {
             instance_name,
             config,
             component,
+            audit_logger: AuditLogger::new().ok().map(Arc::new),
+            context: None,
+            output_dir: None,
+            stdin: None,
+            instance_dir: None,
+            limits: None,
         })
     }
 
@@ -96,23 +113,109 @@ impl SkillExecutor {
             instance_name,
             config,
             component,
+            audit_logger: AuditLogger::new().ok().map(Arc::new),
+            context: None,
+            output_dir: None,
+            stdin: None,
+            instance_dir: None,
+            limits: None,
         })
     }
 
+    /// Run with an execution context's mounts and network policy
+    ///
+    /// Context mounts are granted in addition to the instance's own mounts;
+    /// the context's network settings replace the instance policy.
+    pub fn with_execution_context(mut self, context: ExecutionContext) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Mount a host directory read-write at `/outputs` for the tool's
+    /// artifacts, exposed as `SKILL_OUTPUT_DIR`
+    pub fn with_output_dir(mut self, output_dir: PathBuf) -> Self {
+        self.output_dir = Some(output_dir);
+        self
+    }
+
+    /// Give the tool fixed data as its WASI stdin stream
+    ///
+    /// Without it the sandbox inherits the host process's stdin.
+    pub fn with_stdin(mut self, data: Vec<u8>) -> Self {
+        self.stdin = Some(data);
+        self
+    }
+
+    /// Enforce `limits` instead of the execution context's
+    pub fn with_limits(mut self, limits: ExecutionLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Use `instance_dir` as the sandbox's instance directory
+    ///
+    /// Without it the directory under `~/.skill-engine/instances` is used.
+    pub fn with_instance_dir(mut self, instance_dir: PathBuf) -> Self {
+        self.instance_dir = Some(instance_dir);
+        self
+    }
+
+    /// The sandbox's instance directory
+    fn instance_dir(&self) -> Result<PathBuf> {
+        match &self.instance_dir {
+            Some(dir) => Ok(dir.clone()),
+            None => InstanceConfig::instance_dir(&self.skill_name, &self.instance_name),
+        }
+    }
+
+    /// Sandbox builder with this instance's environment, mounts and network policy
+    fn sandbox(&self, instance_dir: std::path::PathBuf) -> SandboxBuilder {
+        let mut builder = SandboxBuilder::new(&self.instance_name, instance_dir)
+            .skill_name(&self.skill_name)
+            .env_from_config(&self.config);
+        if let Some(context) = &self.context {
+            builder = builder.execution_context(context);
+        }
+        if let Some(output_dir) = &self.output_dir {
+            builder = builder
+                .mount(Mount::directory(
+                    "outputs",
+                    output_dir.to_string_lossy(),
+                    GUEST_OUTPUT_DIR,
+                ))
+                .env(OUTPUT_DIR_ENV, GUEST_OUTPUT_DIR);
+        }
+        if let Some(data) = &self.stdin {
+            builder = builder.stdin(data.clone());
+        }
+
+        match &self.audit_logger {
+            Some(logger) => builder.audit_logger(logger.clone()),
+            None => builder,
+        }
+    }
+
+    /// Linker with WASI and policy-checked WASI HTTP imports
+    fn linker(&self) -> Result<Linker<HostState>> {
+        let mut linker = Linker::new(self.engine.wasmtime_engine());
+        wasmtime_wasi::add_to_linker_async(&mut linker)?;
+        wasmtime_wasi_http::add_only_http_to_linker_async(&mut linker)?;
+        Ok(linker)
+    }
+
     /// Get skill metadata
     pub async fn get_metadata(&self) -> Result<SkillMetadata> {
         // Create a store for this execution
-        let instance_dir = InstanceConfig::instance_dir(&self.skill_name, &self.instance_name)?;
+        let instance_dir = self.instance_dir()?;
 
-        let sandbox = SandboxBuilder::new(&self.instance_name, instance_dir)
-            .env_from_config(&self.config)
+        let sandbox = self
+            .sandbox(instance_dir)
             .build()?;
 
         let mut store = Store::new(self.engine.wasmtime_engine(), sandbox);
 
         // Create linker and instantiate component
-        let mut linker = Linker::new(self.engine.wasmtime_engine());
-        wasmtime_wasi::add_to_linker_async(&mut linker)?;
+        let linker = self.linker()?;
 
         let skill = Skill::instantiate_async(&mut store, &self.component, &linker).await?;
 
@@ -136,17 +239,16 @@ impl SkillExecutor {
     /// Get list of tools provided by this skill
     pub async fn get_tools(&self) -> Result<Vec<ToolDefinition>> {
         // Create a store for this execution
-        let instance_dir = InstanceConfig::instance_dir(&self.skill_name, &self.instance_name)?;
+        let instance_dir = self.instance_dir()?;
 
-        let sandbox = SandboxBuilder::new(&self.instance_name, instance_dir)
-            .env_from_config(&self.config)
+        let sandbox = self
+            .sandbox(instance_dir)
             .build()?;
 
         let mut store = Store::new(self.engine.wasmtime_engine(), sandbox);
 
         // Create linker and instantiate component
-        let mut linker = Linker::new(self.engine.wasmtime_engine());
-        wasmtime_wasi::add_to_linker_async(&mut linker)?;
+        let linker = self.linker()?;
 
         let skill = Skill::instantiate_async(&mut store, &self.component, &linker).await?;
 
@@ -212,18 +314,18 @@ impl SkillExecutor {
         );
 
         // Create sandbox environment
-        let instance_dir = InstanceConfig::instance_dir(&self.skill_name, &self.instance_name)?;
+        let instance_dir = self.instance_dir()?;
 
-        let sandbox = SandboxBuilder::new(&self.instance_name, instance_dir)
-            .env_from_config(&self.config)
+        let sandbox = self
+            .sandbox(instance_dir)
             .args(vec![tool_name.to_string()])
             .build()?;
 
         let mut store = Store::new(self.engine.wasmtime_engine(), sandbox);
+        store.limiter(|state| &mut state.memory);
 
         // Create linker and instantiate component
-        let mut linker = Linker::new(self.engine.wasmtime_engine());
-        wasmtime_wasi::add_to_linker_async(&mut linker)?;
+        let linker = self.linker()?;
 
         let skill = Skill::instantiate_async(&mut store, &self.component, &linker).await?;
 
@@ -232,8 +334,49 @@ impl SkillExecutor {
             args.into_iter().map(|(k, v)| (k, serde_json::Value::String(v)))
         ))?;
 
-        // Call execute-tool export
-        let result_json = skill.call_execute_tool(&mut store, tool_name, args_json.as_str()).await?;
+        // The skill's (or else the context's) output and write limits
+        let limits = self
+            .limits
+            .clone()
+            .or_else(|| {
+                self.context
+                    .as_ref()
+                    .map(|context| context.resources.execution.clone())
+            })
+            .unwrap_or_default();
+        let writable = store
+            .data()
+            .mounts
+            .iter()
+            .filter(|mount| !mount.read_only)
+            .map(|mount| mount.host_path.clone())
+            .collect();
+        let writes = WriteTracker::snapshot(writable, &limits).await;
+
+        // Call execute-tool export, metering the guest's CPU time and
+        // stopping it if it writes too much
+        let call = ResourceUsage::measure_cpu(
+            skill.call_execute_tool(&mut store, tool_name, args_json.as_str()),
+        );
+        let (result_json, cpu_time_ms) = match &writes {
+            Some(writes) => tokio::select! {
+                finished = call => finished,
+                exceeded = writes.exceeded() => return Ok(self.stopped(tool_name, exceeded, start)),
+            },
+            None => call.await,
+        };
+        // Writes made after the last check
+        if let Some(writes) = &writes {
+            if let Err(exceeded) = writes.check().await {
+                return Ok(self.stopped(tool_name, exceeded, start));
+            }
+        }
+        let result_json = result_json?;
+        let usage = ResourceUsage {
+            cpu_time_ms,
+            peak_memory_kb: Some(store.data().memory.peak_kb()),
+            output_bytes: None,
+        };
 
         // Parse JSON result
         let result_value: serde_json::Value = serde_json::from_str(&result_json)
@@ -245,53 +388,62 @@ impl SkillExecutor {
                 success: ok["success"].as_bool().unwrap_or(true),
                 output: ok["output"].as_str().unwrap_or("").to_string(),
                 error_message: ok["errorMessage"].as_str().map(|s| s.to_string()),
-                metadata: None,
+                ..Default::default()
             }
         } else if let Some(err) = result_value.get("err") {
             // Error case
-            ExecutionResult {
-                success: false,
-                output: String::new(),
-                error_message: Some(err.as_str().unwrap_or("Unknown error").to_string()),
-                metadata: None,
-            }
+            ExecutionResult::failure(err.as_str().unwrap_or("Unknown error"))
         } else {
             // Fallback
-            ExecutionResult {
-                success: false,
-                output: String::new(),
-                error_message: Some("Invalid result format".to_string()),
-                metadata: None,
-            }
+            ExecutionResult::failure("Invalid result format")
         };
 
         let duration = start.elapsed();
+        let mut result = result
+            .with_duration(duration)
+            .with_resource_usage(Some(usage))
+            .with_output_metered();
+        if let Some(limit) = limits.max_output_bytes() {
+            result.truncated = cap_output(&mut result.output, limit);
+        }
         tracing::info!(
             skill = %self.skill_name,
             instance = %self.instance_name,
             tool = %tool_name,
             success = result.success,
             duration_ms = duration.as_millis(),
+            cpu_time_ms = ?cpu_time_ms,
             "Tool execution completed"
         );
 
         Ok(result)
     }
 
+    /// Result of a tool stopped for going over an execution limit
+    fn stopped(&self, tool_name: &str, exceeded: LimitExceeded, start: Instant) -> ExecutionResult {
+        tracing::warn!(
+            skill = %self.skill_name,
+            instance = %self.instance_name,
+            tool = %tool_name,
+            limit = %exceeded,
+            "Stopped tool execution over its limits"
+        );
+        ExecutionResult::failure(format!("Execution stopped: {}", exceeded)).with_duration(start.elapsed())
+    }
+
     /// Validate configuration
     pub async fn validate_config(&self) -> Result<()> {
         // Create a store for this execution
-        let instance_dir = InstanceConfig::instance_dir(&self.skill_name, &self.instance_name)?;
+        let instance_dir = self.instance_dir()?;
 
-        let sandbox = SandboxBuilder::new(&self.instance_name, instance_dir)
-            .env_from_config(&self.config)
+        let sandbox = self
+            .sandbox(instance_dir)
             .build()?;
 
         let mut store = Store::new(self.engine.wasmtime_engine(), sandbox);
 
         // Create linker and instantiate component
-        let mut linker = Linker::new(self.engine.wasmtime_engine());
-        wasmtime_wasi::add_to_linker_async(&mut linker)?;
+        let linker = self.linker()?;
 
         let skill = Skill::instantiate_async(&mut store, &self.component, &linker).await?;
 
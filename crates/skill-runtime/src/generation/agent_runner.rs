@@ -0,0 +1,392 @@
+//! Agent Runner - LLM access through the agent picked in the web UI
+//!
+//! The agent settings choose a runtime and a model; [`AgentRunner`] turns
+//! that choice into an [`LlmProvider`], so example generation and
+//! documentation drafting run on it like on any configured provider:
+//!
+//! - **Claude Code** runs the `claude` CLI in print mode with the user's own
+//!   login (`claude -p --output-format json --model <model>`). The CLI has
+//!   no temperature or token settings, so those are ignored.
+//! - **OpenAI** uses chat completions with `OPENAI_API_KEY` (needs the
+//!   `openai` feature).
+//! - **Gemini** calls `generateContent` with `GEMINI_API_KEY` or
+//!   `GOOGLE_API_KEY`.
+//!
+//! Every runner streams by returning the whole completion as one chunk.
+
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use futures_util::Stream;
+use serde_json::{json, Value};
+
+use super::llm_provider::{CompletionRequest, LlmChunk, LlmProvider, LlmResponse, TokenUsage};
+
+/// Default time an agent gets to answer one prompt
+pub const DEFAULT_AGENT_TIMEOUT: Duration = Duration::from_secs(300);
+
+const GEMINI_API_URL: &str = "https://generativelanguage.googleapis.com/v1beta/models";
+
+/// Agent runtimes a runner can drive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentKind {
+    /// The `claude` CLI
+    ClaudeCode,
+    /// OpenAI chat completions
+    OpenAi,
+    /// Google Gemini
+    Gemini,
+}
+
+/// An agent runtime with the model and limits to run prompts with
+#[derive(Debug, Clone)]
+pub struct AgentRunner {
+    /// Runtime that answers prompts
+    pub kind: AgentKind,
+    /// Model to ask
+    pub model: String,
+    /// Time one prompt may take
+    pub timeout: Duration,
+    /// `claude` binary to run; looked up on `PATH` if unset
+    pub claude_code_path: Option<PathBuf>,
+}
+
+impl AgentRunner {
+    /// Create a runner for a runtime and model
+    pub fn new(kind: AgentKind, model: impl Into<String>) -> Self {
+        Self {
+            kind,
+            model: model.into(),
+            timeout: DEFAULT_AGENT_TIMEOUT,
+            claude_code_path: None,
+        }
+    }
+
+    /// Set how long one prompt may take
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Run a specific `claude` binary
+    pub fn with_claude_code_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.claude_code_path = Some(path.into());
+        self
+    }
+
+    /// Whether the runtime can be used here: the CLI is installed or the
+    /// API key is set
+    pub fn is_available(kind: AgentKind) -> bool {
+        match kind {
+            AgentKind::ClaudeCode => crate::requirements::find_on_path("claude").is_some(),
+            AgentKind::OpenAi => cfg!(feature = "openai") && std::env::var("OPENAI_API_KEY").is_ok(),
+            AgentKind::Gemini => gemini_api_key().is_some(),
+        }
+    }
+
+    /// The provider that sends prompts to this runner's agent
+    pub fn provider(&self) -> Result<Arc<dyn LlmProvider>> {
+        match self.kind {
+            AgentKind::ClaudeCode => Ok(Arc::new(ClaudeCodeProvider {
+                program: self.claude_code_path.clone().unwrap_or_else(|| PathBuf::from("claude")),
+                model: self.model.clone(),
+                timeout: self.timeout,
+            })),
+            #[cfg(feature = "openai")]
+            AgentKind::OpenAi => Ok(Arc::new(super::llm_provider::openai::OpenAIProvider::new(&self.model)?)),
+            #[cfg(not(feature = "openai"))]
+            AgentKind::OpenAi => {
+                anyhow::bail!("OpenAI support not enabled. Rebuild with --features openai")
+            }
+            AgentKind::Gemini => {
+                let api_key = gemini_api_key().context("Set GEMINI_API_KEY or GOOGLE_API_KEY to use Gemini")?;
+                let client = reqwest::Client::builder()
+                    .timeout(self.timeout)
+                    .build()
+                    .context("Failed to create HTTP client")?;
+                Ok(Arc::new(GeminiProvider {
+                    client,
+                    api_key,
+                    model: self.model.clone(),
+                }))
+            }
+        }
+    }
+}
+
+fn gemini_api_key() -> Option<String> {
+    std::env::var("GEMINI_API_KEY")
+        .or_else(|_| std::env::var("GOOGLE_API_KEY"))
+        .ok()
+        .filter(|key| !key.is_empty())
+}
+
+/// Answers prompts by running the `claude` CLI
+pub struct ClaudeCodeProvider {
+    program: PathBuf,
+    model: String,
+    timeout: Duration,
+}
+
+#[async_trait]
+impl LlmProvider for ClaudeCodeProvider {
+    fn name(&self) -> &str {
+        "claude-code"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<LlmResponse> {
+        use tokio::io::AsyncWriteExt;
+
+        let (system, prompt) = claude_prompt(request);
+        let mut command = tokio::process::Command::new(&self.program);
+        command
+            .args(["-p", "--output-format", "json", "--model", claude_model(&self.model)])
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(system) = system {
+            command.args(["--append-system-prompt", &system]);
+        }
+
+        let mut child = command
+            .spawn()
+            .with_context(|| format!("Failed to run {}; is Claude Code installed?", self.program.display()))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(prompt.as_bytes()).await.context("Failed to send prompt to Claude Code")?;
+        }
+        let output = tokio::time::timeout(self.timeout, child.wait_with_output())
+            .await
+            .with_context(|| format!("Claude Code didn't answer within {}s", self.timeout.as_secs()))?
+            .context("Failed to run Claude Code")?;
+        if !output.status.success() && output.stdout.is_empty() {
+            anyhow::bail!("Claude Code failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        parse_claude_output(&output.stdout, &self.model)
+    }
+
+    async fn complete_stream(
+        &self,
+        request: &CompletionRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<LlmChunk>> + Send>>> {
+        single_chunk(self.complete(request).await?)
+    }
+}
+
+/// Split a request into the system prompt and the prompt sent on stdin
+fn claude_prompt(request: &CompletionRequest) -> (Option<String>, String) {
+    let system: Vec<&str> = request
+        .messages
+        .iter()
+        .filter(|m| m.role == "system")
+        .map(|m| m.content.as_str())
+        .collect();
+    let turns: Vec<&_> = request.messages.iter().filter(|m| m.role != "system").collect();
+    let prompt = match turns.as_slice() {
+        [only] => only.content.clone(),
+        _ => turns
+            .iter()
+            .map(|m| format!("{}: {}", if m.role == "assistant" { "Assistant" } else { "User" }, m.content))
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    };
+    ((!system.is_empty()).then(|| system.join("\n\n")), prompt)
+}
+
+/// The CLI's alias for the model IDs offered in the agent settings; other
+/// names are passed through
+fn claude_model(model: &str) -> &str {
+    match model {
+        "claude-sonnet-4" => "sonnet",
+        "claude-opus-4" => "opus",
+        "claude-haiku-3.5" => "haiku",
+        other => other,
+    }
+}
+
+/// Read the JSON result `claude -p --output-format json` prints
+fn parse_claude_output(stdout: &[u8], model: &str) -> Result<LlmResponse> {
+    let result: Value = serde_json::from_slice(stdout).context("Claude Code returned invalid JSON")?;
+    let content = result["result"].as_str().unwrap_or_default().to_string();
+    if result["is_error"].as_bool().unwrap_or(false) {
+        anyhow::bail!("Claude Code failed: {}", content);
+    }
+    let usage = result.get("usage").map(|usage| {
+        let prompt_tokens = usage["input_tokens"].as_u64().unwrap_or(0) as u32;
+        let completion_tokens = usage["output_tokens"].as_u64().unwrap_or(0) as u32;
+        TokenUsage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+        }
+    });
+    Ok(LlmResponse {
+        content,
+        model: model.to_string(),
+        usage,
+        finish_reason: result["subtype"].as_str().map(str::to_string),
+    })
+}
+
+/// Answers prompts with the Gemini API
+pub struct GeminiProvider {
+    client: reqwest::Client,
+    api_key: String,
+    model: String,
+}
+
+#[async_trait]
+impl LlmProvider for GeminiProvider {
+    fn name(&self) -> &str {
+        "gemini"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<LlmResponse> {
+        let response = self
+            .client
+            .post(format!("{}/{}:generateContent", GEMINI_API_URL, self.model))
+            .header("x-goog-api-key", &self.api_key)
+            .json(&gemini_body(request))
+            .send()
+            .await
+            .context("Failed to reach the Gemini API")?;
+        let status = response.status();
+        let body: Value = response.json().await.context("Gemini returned invalid JSON")?;
+        if !status.is_success() {
+            anyhow::bail!(
+                "Gemini request failed ({}): {}",
+                status,
+                body["error"]["message"].as_str().unwrap_or("unknown error")
+            );
+        }
+        parse_gemini_response(&body, &self.model)
+    }
+
+    async fn complete_stream(
+        &self,
+        request: &CompletionRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<LlmChunk>> + Send>>> {
+        single_chunk(self.complete(request).await?)
+    }
+}
+
+/// A `generateContent` request body
+fn gemini_body(request: &CompletionRequest) -> Value {
+    let system: Vec<Value> = request
+        .messages
+        .iter()
+        .filter(|m| m.role == "system")
+        .map(|m| json!({ "text": m.content }))
+        .collect();
+    let contents: Vec<Value> = request
+        .messages
+        .iter()
+        .filter(|m| m.role != "system")
+        .map(|m| {
+            let role = if m.role == "assistant" { "model" } else { "user" };
+            json!({ "role": role, "parts": [{ "text": m.content }] })
+        })
+        .collect();
+
+    let mut generation = serde_json::Map::new();
+    if let Some(temperature) = request.temperature {
+        generation.insert("temperature".to_string(), json!(temperature));
+    }
+    if let Some(max_tokens) = request.max_tokens {
+        generation.insert("maxOutputTokens".to_string(), json!(max_tokens));
+    }
+    if let Some(stop) = &request.stop {
+        generation.insert("stopSequences".to_string(), json!(stop));
+    }
+
+    let mut body = json!({ "contents": contents, "generationConfig": generation });
+    if !system.is_empty() {
+        body["systemInstruction"] = json!({ "parts": system });
+    }
+    body
+}
+
+fn parse_gemini_response(body: &Value, model: &str) -> Result<LlmResponse> {
+    let candidate = body["candidates"]
+        .get(0)
+        .context("Gemini returned no candidates")?;
+    let content = candidate["content"]["parts"]
+        .as_array()
+        .map(|parts| parts.iter().filter_map(|part| part["text"].as_str()).collect::<String>())
+        .unwrap_or_default();
+    let usage = body.get("usageMetadata").map(|usage| TokenUsage {
+        prompt_tokens: usage["promptTokenCount"].as_u64().unwrap_or(0) as u32,
+        completion_tokens: usage["candidatesTokenCount"].as_u64().unwrap_or(0) as u32,
+        total_tokens: usage["totalTokenCount"].as_u64().unwrap_or(0) as u32,
+    });
+    Ok(LlmResponse {
+        content,
+        model: body["modelVersion"].as_str().unwrap_or(model).to_string(),
+        usage,
+        finish_reason: candidate["finishReason"].as_str().map(str::to_string),
+    })
+}
+
+fn single_chunk(response: LlmResponse) -> Result<Pin<Box<dyn Stream<Item = Result<LlmChunk>> + Send>>> {
+    let chunk = LlmChunk {
+        delta: response.content,
+        is_final: true,
+    };
+    Ok(Box::pin(futures_util::stream::iter([Ok(chunk)])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claude_code_request_and_output() {
+        let request = CompletionRequest::with_system("Answer briefly", "List pods");
+        let (system, prompt) = claude_prompt(&request);
+        assert_eq!(system.as_deref(), Some("Answer briefly"));
+        assert_eq!(prompt, "List pods");
+        assert_eq!(claude_model("claude-opus-4"), "opus");
+        assert_eq!(claude_model("claude-sonnet-4-20250514"), "claude-sonnet-4-20250514");
+
+        let stdout = br#"{"type":"result","subtype":"success","is_error":false,"result":"kubectl get pods","usage":{"input_tokens":12,"output_tokens":4}}"#;
+        let response = parse_claude_output(stdout, "claude-sonnet-4").unwrap();
+        assert_eq!(response.content, "kubectl get pods");
+        assert_eq!(response.usage.unwrap().total_tokens, 16);
+
+        let failed = br#"{"type":"result","subtype":"success","is_error":true,"result":"Invalid API key"}"#;
+        assert!(parse_claude_output(failed, "sonnet").is_err());
+    }
+
+    #[test]
+    fn test_gemini_request_and_response() {
+        let request = CompletionRequest::with_system("Answer briefly", "List pods").max_tokens(100);
+        let body = gemini_body(&request);
+        assert_eq!(body["systemInstruction"]["parts"][0]["text"], "Answer briefly");
+        assert_eq!(body["contents"][0]["role"], "user");
+        assert_eq!(body["generationConfig"]["maxOutputTokens"], 100);
+
+        let response = json!({
+            "candidates": [{
+                "content": { "parts": [{ "text": "kubectl " }, { "text": "get pods" }] },
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": { "promptTokenCount": 10, "candidatesTokenCount": 3, "totalTokenCount": 13 }
+        });
+        let response = parse_gemini_response(&response, "gemini-1.5-pro").unwrap();
+        assert_eq!(response.content, "kubectl get pods");
+        assert_eq!(response.finish_reason.as_deref(), Some("STOP"));
+        assert!(parse_gemini_response(&json!({ "candidates": [] }), "gemini-1.5-pro").is_err());
+    }
+}
@@ -0,0 +1,341 @@
+//! Budget Guard - token and cost limits for AI operations
+//!
+//! Every LLM call, and every embedding call to a metered provider, is
+//! checked against `[ai_ingestion.budget]` before the request is sent, so a
+//! run that would go over a limit stops immediately instead of after the
+//! money is spent. Daily token usage is kept in `~/.skill-engine/ai-usage.json`
+//! so the daily limit holds across runs; cost is tracked per run.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+
+use crate::search_config::AiBudgetConfig;
+use super::llm_provider::{CompletionRequest, LlmChunk, LlmProvider, LlmResponse};
+
+/// Embedding providers that bill per token
+const METERED_EMBEDDING_PROVIDERS: &[&str] = &["openai"];
+
+/// An operation was refused because it would exceed a budget limit
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum BudgetExceeded {
+    /// The daily token limit would be exceeded
+    #[error(
+        "AI budget exceeded: {requested} more tokens would bring today's usage to {} of {limit} \
+         (ai_ingestion.budget.max_tokens_per_day). Raise the limit or rerun with --force",
+        used + requested
+    )]
+    DailyTokens {
+        /// Tokens used today
+        used: u64,
+        /// Tokens the operation is estimated to use
+        requested: u64,
+        /// Configured daily limit
+        limit: u64,
+    },
+
+    /// The per-run cost limit would be exceeded
+    #[error(
+        "AI budget exceeded: this run would cost an estimated {:.4}, over the limit of {limit:.4} \
+         (ai_ingestion.budget.max_cost_per_run). Raise the limit or rerun with --force",
+        spent + estimated
+    )]
+    RunCost {
+        /// Estimated cost of the run so far
+        spent: f64,
+        /// Estimated cost of the operation
+        estimated: f64,
+        /// Configured per-run limit
+        limit: f64,
+    },
+}
+
+/// Token usage for one day, persisted between runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct DailyUsage {
+    day: Option<NaiveDate>,
+    tokens: u64,
+}
+
+impl DailyUsage {
+    fn tokens_on(&self, today: NaiveDate) -> u64 {
+        if self.day == Some(today) { self.tokens } else { 0 }
+    }
+}
+
+/// Enforces an [`AiBudgetConfig`] across the LLM and embedding calls of a run
+#[derive(Debug)]
+pub struct BudgetGuard {
+    config: AiBudgetConfig,
+    /// Where daily usage is persisted (in memory only when `None`)
+    usage_path: Option<PathBuf>,
+    /// Tokens used today, including earlier runs
+    daily: Mutex<DailyUsage>,
+    /// Tokens used by this run
+    run_tokens: Mutex<u64>,
+}
+
+impl BudgetGuard {
+    /// Usage file name under `~/.skill-engine`
+    pub const USAGE_FILE: &'static str = "ai-usage.json";
+
+    /// Create a guard that persists daily usage at the default location
+    pub fn from_config(config: &AiBudgetConfig) -> Self {
+        let path = dirs::home_dir().map(|home| home.join(".skill-engine").join(Self::USAGE_FILE));
+        Self::new(config.clone(), path)
+    }
+
+    /// Create a guard, loading today's usage from `usage_path` if given
+    pub fn new(config: AiBudgetConfig, usage_path: Option<PathBuf>) -> Self {
+        let daily = usage_path
+            .as_deref()
+            .and_then(|path| load_usage(path).ok())
+            .unwrap_or_default();
+        Self {
+            config,
+            usage_path,
+            daily: Mutex::new(daily),
+            run_tokens: Mutex::new(0),
+        }
+    }
+
+    /// A guard with no limits
+    pub fn unlimited() -> Self {
+        Self::new(AiBudgetConfig::default(), None)
+    }
+
+    /// Whether embedding calls to a provider count against the budget
+    pub fn is_metered_embedding(provider: &str) -> bool {
+        METERED_EMBEDDING_PROVIDERS.contains(&provider)
+    }
+
+    /// Check that an operation estimated at `tokens` fits within the budget
+    pub fn check(&self, tokens: u64) -> Result<(), BudgetExceeded> {
+        if self.config.force {
+            return Ok(());
+        }
+
+        if let Some(limit) = self.config.max_tokens_per_day {
+            let used = self.daily.lock().unwrap().tokens_on(today());
+            if used + tokens > limit {
+                return Err(BudgetExceeded::DailyTokens { used, requested: tokens, limit });
+            }
+        }
+
+        if let Some(limit) = self.config.max_cost_per_run {
+            let spent = self.cost(*self.run_tokens.lock().unwrap());
+            let estimated = self.cost(tokens);
+            if spent + estimated > limit {
+                return Err(BudgetExceeded::RunCost { spent, estimated, limit });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record tokens used by a completed operation
+    pub fn record(&self, tokens: u64) {
+        *self.run_tokens.lock().unwrap() += tokens;
+
+        let today = today();
+        let mut daily = self.daily.lock().unwrap();
+        // Pick up usage recorded by other runs since this one started
+        if let Some(on_disk) = self.usage_path.as_deref().and_then(|path| load_usage(path).ok()) {
+            *daily = on_disk;
+        }
+        daily.tokens = daily.tokens_on(today) + tokens;
+        daily.day = Some(today);
+
+        // Only limited budgets write the usage file
+        if let Some(path) = self.usage_path.as_deref().filter(|_| self.config.is_limited()) {
+            if let Err(e) = save_usage(path, &daily) {
+                tracing::warn!("Failed to save AI usage: {}", e);
+            }
+        }
+    }
+
+    /// Tokens used by this run
+    pub fn run_tokens(&self) -> u64 {
+        *self.run_tokens.lock().unwrap()
+    }
+
+    /// Estimated cost of this run
+    pub fn run_cost(&self) -> f64 {
+        self.cost(self.run_tokens())
+    }
+
+    fn cost(&self, tokens: u64) -> f64 {
+        tokens as f64 / 1000.0 * self.config.cost_per_1k_tokens
+    }
+}
+
+/// Rough token count for text (about four characters per token)
+pub fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64).div_ceil(4)
+}
+
+fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+fn load_usage(path: &Path) -> Result<DailyUsage> {
+    let content = fs::read_to_string(path).context("Failed to read AI usage")?;
+    serde_json::from_str(&content).context("Failed to parse AI usage")
+}
+
+fn save_usage(path: &Path, usage: &DailyUsage) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).context("Failed to create usage directory")?;
+    }
+    let content = serde_json::to_string_pretty(usage).context("Failed to serialize AI usage")?;
+    fs::write(path, content).context("Failed to write AI usage")
+}
+
+/// LLM provider that checks every call against a [`BudgetGuard`]
+pub struct BudgetedProvider {
+    inner: Arc<dyn LlmProvider>,
+    guard: Arc<BudgetGuard>,
+}
+
+impl BudgetedProvider {
+    /// Wrap a provider with a budget guard
+    pub fn new(inner: Arc<dyn LlmProvider>, guard: Arc<BudgetGuard>) -> Self {
+        Self { inner, guard }
+    }
+
+    /// Prompt tokens plus the most the model may generate
+    fn estimate(request: &CompletionRequest) -> u64 {
+        let prompt: u64 = request.messages.iter().map(|m| estimate_tokens(&m.content)).sum();
+        prompt + request.max_tokens.unwrap_or(0) as u64
+    }
+}
+
+#[async_trait]
+impl LlmProvider for BudgetedProvider {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<LlmResponse> {
+        self.guard.check(Self::estimate(request))?;
+        let response = self.inner.complete(request).await?;
+
+        let used = match response.usage {
+            Some(ref usage) => usage.total_tokens as u64,
+            None => {
+                let prompt: u64 = request.messages.iter().map(|m| estimate_tokens(&m.content)).sum();
+                prompt + estimate_tokens(&response.content)
+            }
+        };
+        self.guard.record(used);
+        Ok(response)
+    }
+
+    async fn complete_stream(
+        &self,
+        request: &CompletionRequest,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<LlmChunk>> + Send>>> {
+        // Streams don't report usage, so the estimate is charged up front
+        let estimated = Self::estimate(request);
+        self.guard.check(estimated)?;
+        let stream = self.inner.complete_stream(request).await?;
+        self.guard.record(estimated);
+        Ok(stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::llm_provider::TokenUsage;
+
+    struct MockProvider;
+
+    #[async_trait]
+    impl LlmProvider for MockProvider {
+        fn name(&self) -> &str { "mock" }
+        fn model(&self) -> &str { "test" }
+        async fn complete(&self, _: &CompletionRequest) -> Result<LlmResponse> {
+            Ok(LlmResponse {
+                content: "[]".to_string(),
+                model: "test".to_string(),
+                usage: Some(TokenUsage { prompt_tokens: 80, completion_tokens: 20, total_tokens: 100 }),
+                finish_reason: None,
+            })
+        }
+        async fn complete_stream(&self, _: &CompletionRequest) -> Result<Pin<Box<dyn Stream<Item = Result<LlmChunk>> + Send>>> {
+            unimplemented!()
+        }
+    }
+
+    fn budget(max_tokens_per_day: Option<u64>, max_cost_per_run: Option<f64>) -> AiBudgetConfig {
+        AiBudgetConfig { max_tokens_per_day, max_cost_per_run, cost_per_1k_tokens: 1.0, force: false }
+    }
+
+    #[test]
+    fn test_daily_limit_persists_across_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(BudgetGuard::USAGE_FILE);
+
+        let guard = BudgetGuard::new(budget(Some(1000), None), Some(path.clone()));
+        assert!(guard.check(600).is_ok());
+        guard.record(600);
+
+        let next_run = BudgetGuard::new(budget(Some(1000), None), Some(path));
+        assert_eq!(next_run.run_tokens(), 0);
+        assert_eq!(
+            next_run.check(500),
+            Err(BudgetExceeded::DailyTokens { used: 600, requested: 500, limit: 1000 })
+        );
+        assert!(next_run.check(400).is_ok());
+    }
+
+    #[test]
+    fn test_run_cost_limit_and_force() {
+        let guard = BudgetGuard::new(budget(None, Some(0.5)), None);
+        guard.record(400);
+        assert!((guard.run_cost() - 0.4).abs() < 1e-9);
+
+        let err = guard.check(200).unwrap_err();
+        assert!(matches!(err, BudgetExceeded::RunCost { .. }));
+        assert!(err.to_string().contains("--force"));
+
+        let mut forced = budget(None, Some(0.5));
+        forced.force = true;
+        let guard = BudgetGuard::new(forced, None);
+        guard.record(400);
+        assert!(guard.check(200).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_budgeted_provider_fails_before_calling() {
+        let guard = Arc::new(BudgetGuard::new(budget(Some(150), None), None));
+        let provider = BudgetedProvider::new(Arc::new(MockProvider), guard.clone());
+        let request = CompletionRequest::new("List pods").max_tokens(60);
+
+        provider.complete(&request).await.unwrap();
+        assert_eq!(guard.run_tokens(), 100);
+
+        let err = provider.complete(&request).await.unwrap_err();
+        assert!(err.downcast_ref::<BudgetExceeded>().is_some());
+        assert_eq!(guard.run_tokens(), 100);
+    }
+
+    #[test]
+    fn test_metered_embeddings() {
+        assert!(BudgetGuard::is_metered_embedding("openai"));
+        assert!(!BudgetGuard::is_metered_embedding("fastembed"));
+        assert_eq!(estimate_tokens("abcdefgh"), 2);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+}
@@ -0,0 +1,283 @@
+//! Documentation Drafter - AI-written docs for tools without a SKILL.md
+//!
+//! WASM skills that ship without a SKILL.md only expose tool signatures,
+//! which are nearly impossible to find through semantic search. The drafter
+//! asks the configured LLM for a description and per-parameter docs, keeping
+//! names, types and required flags from the signature. Drafted docs are
+//! flagged as AI-generated so they can be told apart from authored ones.
+
+use std::sync::Arc;
+use anyhow::{Context, Result};
+
+use crate::skill_md::{ParameterDoc, ParameterType, ToolDocumentation};
+use crate::types::{self, ToolDefinition};
+use super::llm_provider::{LlmProvider, CompletionRequest};
+use super::example_generator::GeneratorConfig;
+use super::budget::BudgetExceeded;
+
+/// Tag put on index documents built from drafted documentation
+pub const AI_GENERATED_TAG: &str = "ai-generated";
+
+/// Drafts tool documentation from tool signatures using an LLM
+pub struct DocDrafter {
+    /// LLM provider for drafting
+    llm: Arc<dyn LlmProvider>,
+    /// Shared generation settings (retries, tokens)
+    config: GeneratorConfig,
+}
+
+impl DocDrafter {
+    /// Create a new documentation drafter
+    pub fn new(llm: Arc<dyn LlmProvider>, config: GeneratorConfig) -> Self {
+        Self { llm, config }
+    }
+
+    /// Draft documentation for a tool from its signature
+    pub async fn draft(&self, skill_name: &str, tool: &ToolDefinition) -> Result<ToolDocumentation> {
+        let request = CompletionRequest::with_system(SYSTEM_PROMPT, self.build_prompt(skill_name, tool))
+            .temperature(0.2)
+            .max_tokens(self.config.max_tokens);
+
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let result = match self.llm.complete(&request).await {
+                Ok(response) => self.parse_draft(&response.content, tool),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(doc) => return Ok(doc),
+                Err(e) if e.downcast_ref::<BudgetExceeded>().is_some() => return Err(e),
+                Err(e) if attempts >= self.config.max_retries.max(1) => {
+                    return Err(e.context(format!(
+                        "Failed to draft documentation for '{}' after {} attempts",
+                        tool.name, attempts
+                    )));
+                }
+                Err(_) => continue,
+            }
+        }
+    }
+
+    /// Build the prompt describing the tool signature
+    fn build_prompt(&self, skill_name: &str, tool: &ToolDefinition) -> String {
+        let parameters = if tool.parameters.is_empty() {
+            "No parameters.".to_string()
+        } else {
+            tool.parameters
+                .iter()
+                .map(|p| {
+                    let required = if p.required { " (required)" } else { "" };
+                    let default = p.default_value.as_ref()
+                        .map(|d| format!(" [default: {}]", d))
+                        .unwrap_or_default();
+                    let hint = if p.description.is_empty() {
+                        String::new()
+                    } else {
+                        format!(": {}", p.description)
+                    };
+                    format!(
+                        "- `{}` ({}){}{}{}",
+                        p.name,
+                        format!("{:?}", p.param_type).to_lowercase(),
+                        required,
+                        default,
+                        hint
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let existing = if tool.description.is_empty() {
+            "(none)"
+        } else {
+            tool.description.as_str()
+        };
+
+        format!(
+            r#"Document the tool `{name}` from the `{skill}` skill.
+
+## Signature
+- **Tool**: {name}
+- **Existing description**: {existing}
+
+## Parameters
+{parameters}
+
+## Output Format
+Return a JSON object:
+```json
+{{"description": "One or two sentences on what the tool does", "parameters": {{"param_name": "What the parameter controls"}}}}
+```"#,
+            name = tool.name,
+            skill = skill_name,
+            existing = existing,
+            parameters = parameters,
+        )
+    }
+
+    /// Parse the LLM response into documentation for the tool
+    fn parse_draft(&self, response: &str, tool: &ToolDefinition) -> Result<ToolDocumentation> {
+        let start = response.find('{').context("Could not find JSON object in response")?;
+        let end = response.rfind('}').context("Could not find JSON object in response")?;
+        anyhow::ensure!(end > start, "Could not find JSON object in response");
+
+        let parsed: serde_json::Value = serde_json::from_str(&response[start..=end])
+            .context("Failed to parse drafted documentation")?;
+
+        let description = parsed
+            .get("description")
+            .and_then(|d| d.as_str())
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .context("Drafted documentation has no description")?;
+        let param_docs = parsed.get("parameters").and_then(|p| p.as_object());
+
+        // Only descriptions come from the model; the signature stays authoritative
+        let parameters = tool
+            .parameters
+            .iter()
+            .map(|p| {
+                let drafted = param_docs
+                    .and_then(|docs| docs.get(&p.name))
+                    .and_then(|d| d.as_str())
+                    .map(str::trim)
+                    .filter(|d| !d.is_empty());
+                ParameterDoc {
+                    name: p.name.clone(),
+                    required: p.required,
+                    param_type: parameter_type(&p.param_type),
+                    description: drafted.unwrap_or(&p.description).to_string(),
+                    default: p.default_value.clone(),
+                    allowed_values: Vec::new(),
+                    pattern: None,
+                    minimum: None,
+                    maximum: None,
+                }
+            })
+            .collect();
+
+        Ok(ToolDocumentation {
+            name: tool.name.clone(),
+            description: description.to_string(),
+            parameters,
+            ai_generated: true,
+            ..Default::default()
+        })
+    }
+
+    /// Get the LLM provider name
+    pub fn provider_name(&self) -> &str {
+        self.llm.name()
+    }
+
+    /// Get the model name
+    pub fn model_name(&self) -> &str {
+        self.llm.model()
+    }
+}
+
+/// Map a runtime parameter type onto its SKILL.md equivalent
+fn parameter_type(param_type: &types::ParameterType) -> ParameterType {
+    match param_type {
+        types::ParameterType::String | types::ParameterType::File => ParameterType::String,
+        types::ParameterType::Number => ParameterType::Number,
+        types::ParameterType::Boolean => ParameterType::Boolean,
+        types::ParameterType::Json => ParameterType::Object,
+        types::ParameterType::Array => ParameterType::Array,
+    }
+}
+
+/// System prompt for documentation drafting
+const SYSTEM_PROMPT: &str = r#"You are a technical writer documenting command-line tools from their signatures.
+
+Guidelines:
+- Describe what the tool does in plain, specific language
+- Infer intent from the tool name, skill name and parameter names
+- Do not invent parameters; document only the ones listed
+- Keep each parameter description to one sentence
+
+Output a single JSON object with "description" and "parameters" fields."#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::llm_provider::{LlmChunk, LlmResponse};
+    use crate::types::Parameter;
+    use futures_util::Stream;
+    use std::pin::Pin;
+
+    struct MockProvider(&'static str);
+
+    #[async_trait::async_trait]
+    impl LlmProvider for MockProvider {
+        fn name(&self) -> &str { "mock" }
+        fn model(&self) -> &str { "test" }
+        async fn complete(&self, _: &CompletionRequest) -> Result<LlmResponse> {
+            Ok(LlmResponse {
+                content: self.0.to_string(),
+                model: "test".to_string(),
+                usage: None,
+                finish_reason: None,
+            })
+        }
+        async fn complete_stream(&self, _: &CompletionRequest) -> Result<Pin<Box<dyn Stream<Item = Result<LlmChunk>> + Send>>> {
+            unimplemented!()
+        }
+    }
+
+    fn scale_tool() -> ToolDefinition {
+        ToolDefinition {
+            name: "scale".to_string(),
+            description: String::new(),
+            parameters: vec![
+                Parameter {
+                    name: "replicas".to_string(),
+                    param_type: types::ParameterType::Number,
+                    description: String::new(),
+                    required: true,
+                    default_value: None,
+                },
+                Parameter {
+                    name: "wait".to_string(),
+                    param_type: types::ParameterType::Boolean,
+                    description: "Block until ready".to_string(),
+                    required: false,
+                    default_value: Some("false".to_string()),
+                },
+            ],
+            streaming: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_draft_keeps_signature_and_flags_doc() {
+        let response = r#"Sure:
+```json
+{"description": "Scale a deployment to a number of replicas", "parameters": {"replicas": "Desired replica count", "extra": "Not a real parameter"}}
+```"#;
+        let drafter = DocDrafter::new(Arc::new(MockProvider(response)), GeneratorConfig::default());
+
+        let doc = drafter.draft("kubernetes", &scale_tool()).await.unwrap();
+
+        assert!(doc.ai_generated);
+        assert_eq!(doc.description, "Scale a deployment to a number of replicas");
+        assert_eq!(doc.parameters.len(), 2);
+        assert_eq!(doc.parameters[0].description, "Desired replica count");
+        assert_eq!(doc.parameters[0].param_type, ParameterType::Number);
+        assert!(doc.parameters[0].required);
+        // Parameters the model skipped keep the signature's description
+        assert_eq!(doc.parameters[1].description, "Block until ready");
+        assert_eq!(doc.parameters[1].default.as_deref(), Some("false"));
+    }
+
+    #[tokio::test]
+    async fn test_draft_rejects_missing_description() {
+        let drafter = DocDrafter::new(
+            Arc::new(MockProvider(r#"{"parameters": {}}"#)),
+            GeneratorConfig::default(),
+        );
+
+        assert!(drafter.draft("kubernetes", &scale_tool()).await.is_err());
+    }
+}
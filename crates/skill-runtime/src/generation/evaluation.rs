@@ -1,9 +1,13 @@
 //! Accuracy evaluation metrics for AI-generated examples
 //!
 //! Provides metrics for measuring the quality, accuracy, and diversity
-//! of generated examples against tool schemas.
+//! of generated examples against tool schemas, and dry runs that resolve
+//! examples the way `skill run` would without executing them.
 
 use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::arg_validation::{validate_args, ParamSpec};
 use crate::skill_md::ToolDocumentation;
 use super::streaming::GeneratedExample;
 use super::validator::ExampleValidator;
@@ -339,6 +343,197 @@ fn categorize_error(error: &str) -> String {
     }
 }
 
+// =============================================================================
+// Dry-Run Evaluation
+// =============================================================================
+
+/// Outcome of resolving an example as `skill run` would, without running it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunResult {
+    /// Tool the example belongs to
+    pub tool: String,
+    /// Example command
+    pub command: String,
+    /// Command parsed and targets the skill's tool
+    pub parsed: bool,
+    /// Arguments passed the checks run before execution
+    pub valid: bool,
+    /// Why the example would be rejected
+    pub issues: Vec<String>,
+}
+
+/// Quality of a sample of one skill's generated examples
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillEvaluation {
+    /// Skill name
+    pub skill: String,
+    /// When the evaluation ran
+    pub evaluated_at: DateTime<Utc>,
+    /// Tools that had examples to sample
+    pub tools: usize,
+    /// Examples dry-run
+    pub sampled: usize,
+    /// Examples that parsed and targeted the right tool
+    pub parsed: usize,
+    /// Examples whose arguments passed validation
+    pub valid: usize,
+    /// Mean validation score of the sampled examples
+    pub avg_score: f32,
+    /// Weighted quality of the sample (see [`AccuracyMetrics::overall_quality`])
+    pub quality: f32,
+    /// Dry runs that failed
+    pub failures: Vec<DryRunResult>,
+}
+
+impl SkillEvaluation {
+    /// Share of sampled examples that parsed (0.0-1.0)
+    pub fn parse_rate(&self) -> f32 {
+        if self.sampled == 0 {
+            return 0.0;
+        }
+        self.parsed as f32 / self.sampled as f32
+    }
+
+    /// Share of sampled examples that passed the dry run (0.0-1.0)
+    pub fn pass_rate(&self) -> f32 {
+        if self.sampled == 0 {
+            return 0.0;
+        }
+        self.valid as f32 / self.sampled as f32
+    }
+}
+
+impl AccuracyEvaluator {
+    /// Resolve an example against its tool as `skill run` would
+    ///
+    /// The command must name the skill's tool, and its arguments go through
+    /// the same checks a real run makes before invoking the tool. Nothing is
+    /// executed.
+    pub fn dry_run(
+        &self,
+        skill_name: &str,
+        tool: &ToolDocumentation,
+        example: &GeneratedExample,
+    ) -> DryRunResult {
+        let mut result = DryRunResult {
+            tool: tool.name.clone(),
+            command: example.command.clone(),
+            parsed: false,
+            valid: false,
+            issues: Vec::new(),
+        };
+
+        let parsed = match self.validator.parse_command(&example.command) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                result.issues.push(format!("Failed to parse command: {}", e));
+                return result;
+            }
+        };
+        if parsed.skill.as_deref().is_some_and(|skill| skill != skill_name) {
+            result.issues.push(format!(
+                "Targets skill '{}' instead of '{}'",
+                parsed.skill.as_deref().unwrap_or_default(),
+                skill_name
+            ));
+        }
+        if parsed.tool.as_deref() != Some(tool.name.as_str()) {
+            result.issues.push(format!(
+                "Targets tool '{}' instead of '{}'",
+                parsed.tool.as_deref().unwrap_or_default(),
+                tool.name
+            ));
+        }
+        result.parsed = result.issues.is_empty();
+        if !result.parsed {
+            return result;
+        }
+
+        // Build arguments the way `skill run` passes them on
+        let mut args: HashMap<String, serde_json::Value> = parsed
+            .parameters
+            .into_iter()
+            .map(|(name, value)| (name, serde_json::Value::String(value)))
+            .collect();
+        for flag in parsed.flags {
+            args.insert(flag, serde_json::Value::String("true".to_string()));
+        }
+        if let Some(positional) = parsed.positional.first() {
+            args.insert("arg".to_string(), serde_json::Value::String(positional.clone()));
+        }
+
+        let params: Vec<ParamSpec> = tool.parameters.iter().map(ParamSpec::from).collect();
+        match validate_args(&tool.name, &params, &args) {
+            Ok(()) => result.valid = true,
+            Err(e) => result.issues.extend(e.issues.into_iter().map(|issue| issue.message)),
+        }
+        result
+    }
+
+    /// Dry-run up to `sample_per_tool` examples of each tool and summarize
+    /// the skill's example quality
+    ///
+    /// Samples are spread evenly over each tool's examples.
+    pub fn evaluate_skill(
+        &self,
+        skill_name: &str,
+        tools: &[ToolDocumentation],
+        examples_by_tool: &HashMap<String, Vec<GeneratedExample>>,
+        sample_per_tool: usize,
+    ) -> SkillEvaluation {
+        let mut evaluation = SkillEvaluation {
+            skill: skill_name.to_string(),
+            evaluated_at: Utc::now(),
+            tools: 0,
+            sampled: 0,
+            parsed: 0,
+            valid: 0,
+            avg_score: 0.0,
+            quality: 0.0,
+            failures: Vec::new(),
+        };
+
+        let mut sampled_by_tool = HashMap::new();
+        let mut total_score = 0.0;
+        for tool in tools {
+            let Some(examples) = examples_by_tool.get(&tool.name).filter(|e| !e.is_empty()) else {
+                continue;
+            };
+            let sample = sample_evenly(examples, sample_per_tool);
+            evaluation.tools += 1;
+
+            for example in &sample {
+                let result = self.dry_run(skill_name, tool, example);
+                evaluation.sampled += 1;
+                total_score += example.confidence;
+                if result.parsed {
+                    evaluation.parsed += 1;
+                }
+                if result.valid {
+                    evaluation.valid += 1;
+                } else {
+                    evaluation.failures.push(result);
+                }
+            }
+            sampled_by_tool.insert(tool.name.clone(), sample);
+        }
+
+        if evaluation.sampled > 0 {
+            evaluation.avg_score = total_score / evaluation.sampled as f32;
+            evaluation.quality = self.evaluate_batch(tools, &sampled_by_tool).overall_quality();
+        }
+        evaluation
+    }
+}
+
+/// Up to `n` examples spread evenly over `examples`
+fn sample_evenly(examples: &[GeneratedExample], n: usize) -> Vec<GeneratedExample> {
+    if examples.len() <= n {
+        return examples.to_vec();
+    }
+    (0..n).map(|i| examples[i * examples.len() / n].clone()).collect()
+}
+
 // =============================================================================
 // Performance Metrics
 // =============================================================================
@@ -525,6 +720,65 @@ mod tests {
         assert!(!metrics.meets_latency_threshold(1500));
     }
 
+    #[test]
+    fn test_dry_run() {
+        let evaluator = AccuracyEvaluator::new();
+        let tool = kubernetes_apply_tool();
+
+        let ok = evaluator.dry_run(
+            "kubernetes",
+            &tool,
+            &GeneratedExample::new("skill run kubernetes:apply --file=deploy.yaml", "Apply"),
+        );
+        assert!(ok.parsed && ok.valid, "{:?}", ok.issues);
+
+        let missing = evaluator.dry_run(
+            "kubernetes",
+            &tool,
+            &GeneratedExample::new("skill run kubernetes:apply --namespace=prod", "No file"),
+        );
+        assert!(missing.parsed);
+        assert!(!missing.valid);
+
+        let wrong_tool = evaluator.dry_run(
+            "kubernetes",
+            &tool,
+            &GeneratedExample::new("skill run kubernetes:delete --file=deploy.yaml", "Delete"),
+        );
+        assert!(!wrong_tool.parsed);
+        assert!(wrong_tool.issues[0].contains("delete"));
+    }
+
+    #[test]
+    fn test_evaluate_skill_samples_each_tool() {
+        let evaluator = AccuracyEvaluator::new();
+        let tools = vec![kubernetes_apply_tool()];
+
+        let mut examples_by_tool = HashMap::new();
+        examples_by_tool.insert(
+            "apply".to_string(),
+            (0..10)
+                .map(|i| {
+                    let command = if i % 2 == 0 {
+                        format!("skill run kubernetes:apply --file=app-{}.yaml", i)
+                    } else {
+                        "skill run kubernetes:apply".to_string()
+                    };
+                    GeneratedExample::new(command, "Apply").with_confidence(0.8)
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        let evaluation = evaluator.evaluate_skill("kubernetes", &tools, &examples_by_tool, 4);
+
+        assert_eq!(evaluation.tools, 1);
+        assert_eq!(evaluation.sampled, 4);
+        assert_eq!(evaluation.parsed, 4);
+        assert_eq!(evaluation.valid + evaluation.failures.len(), 4);
+        assert!(evaluation.pass_rate() < 1.0);
+        assert!((evaluation.avg_score - 0.8).abs() < 1e-6);
+    }
+
     #[test]
     fn test_batch_evaluation() {
         let evaluator = AccuracyEvaluator::new();
@@ -0,0 +1,297 @@
+//! Example Cache - generated examples kept between enhance runs
+//!
+//! Each tool's examples are stored with a checksum of the documentation they
+//! were generated from, so unchanged tools can be skipped on the next run,
+//! and with their validation scores, so runs that produced mostly rejected
+//! examples can be found and regenerated.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::skill_md::ToolDocumentation;
+use super::streaming::{GeneratedExample, GenerationEvent};
+
+/// Cached generation result for one tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedExamples {
+    /// Checksum of the tool documentation the examples were generated from
+    pub checksum: String,
+    /// Model that generated the examples
+    pub model: String,
+    /// When the examples were generated
+    pub generated_at: DateTime<Utc>,
+    /// Number of examples the model returned
+    pub generated: usize,
+    /// Examples that were kept; `confidence` holds the validation score
+    pub examples: Vec<GeneratedExample>,
+    /// Generation errored, or fewer than half of the examples were kept
+    pub failed: bool,
+}
+
+impl CachedExamples {
+    /// Record a generation run
+    pub fn new(
+        checksum: impl Into<String>,
+        model: impl Into<String>,
+        generated: usize,
+        examples: Vec<GeneratedExample>,
+        errored: bool,
+    ) -> Self {
+        let failed = errored || examples.is_empty() || examples.len() * 2 < generated;
+        Self {
+            checksum: checksum.into(),
+            model: model.into(),
+            generated_at: Utc::now(),
+            generated,
+            examples,
+            failed,
+        }
+    }
+
+    /// Mean validation score of the kept examples
+    pub fn score(&self) -> f32 {
+        if self.examples.is_empty() {
+            return 0.0;
+        }
+        self.examples.iter().map(|e| e.confidence).sum::<f32>() / self.examples.len() as f32
+    }
+}
+
+/// Generated examples for all enhanced tools, keyed by `skill:tool`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExampleCache {
+    #[serde(default)]
+    tools: BTreeMap<String, CachedExamples>,
+}
+
+impl ExampleCache {
+    /// Cache file name under `~/.skill-engine`
+    pub const CACHE_FILE: &'static str = "example-cache.json";
+
+    /// Default cache location
+    pub fn default_path() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Failed to get home directory")?;
+        Ok(home.join(".skill-engine").join(Self::CACHE_FILE))
+    }
+
+    /// Load the cache, or an empty one if the file doesn't exist
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .context("Failed to read example cache")?;
+        serde_json::from_str(&content)
+            .context("Failed to parse example cache")
+    }
+
+    /// Save the cache to disk
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .context("Failed to create cache directory")?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .context("Failed to serialize example cache")?;
+        fs::write(path, content)
+            .context("Failed to write example cache")?;
+
+        Ok(())
+    }
+
+    /// Checksum of the documentation examples are generated from
+    pub fn checksum(tool: &ToolDocumentation) -> String {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(tool.name.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(tool.description.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(tool.usage.as_deref().unwrap_or_default().as_bytes());
+        for param in &tool.parameters {
+            hasher.update(b"\0");
+            hasher.update(
+                format!(
+                    "{}|{}|{}|{}|{}|{}",
+                    param.name,
+                    param.param_type,
+                    param.required,
+                    param.description,
+                    param.default.as_deref().unwrap_or_default(),
+                    param.allowed_values.join(","),
+                )
+                .as_bytes(),
+            );
+        }
+        hasher.finalize().to_hex().to_string()
+    }
+
+    /// Cached result for a tool
+    pub fn get(&self, skill_name: &str, tool_name: &str) -> Option<&CachedExamples> {
+        self.tools.get(&cache_key(skill_name, tool_name))
+    }
+
+    /// Whether a tool's cached examples can be reused
+    ///
+    /// They can when the last run didn't fail, the documentation hasn't
+    /// changed since, and they're younger than `max_age` (if given).
+    pub fn is_fresh(
+        &self,
+        skill_name: &str,
+        tool: &ToolDocumentation,
+        max_age: Option<Duration>,
+    ) -> bool {
+        self.get(skill_name, &tool.name).is_some_and(|cached| {
+            !cached.failed
+                && cached.checksum == Self::checksum(tool)
+                && max_age.map_or(true, |age| Utc::now() - cached.generated_at <= age)
+        })
+    }
+
+    /// Whether the last run for a tool failed
+    pub fn is_failed(&self, skill_name: &str, tool_name: &str) -> bool {
+        self.get(skill_name, tool_name).is_some_and(|cached| cached.failed)
+    }
+
+    /// Record the result of a generation run
+    pub fn insert(&mut self, skill_name: &str, tool_name: &str, examples: CachedExamples) {
+        self.tools.insert(cache_key(skill_name, tool_name), examples);
+    }
+}
+
+/// Follows a generation stream and builds each tool's cache entry
+#[derive(Debug, Default)]
+pub struct GenerationRecorder {
+    current_tool: String,
+    kept: HashMap<String, Vec<GeneratedExample>>,
+    generated: HashMap<String, usize>,
+    errored: HashSet<String>,
+}
+
+impl GenerationRecorder {
+    /// Create an empty recorder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Observe an event, returning the tool's name when the event ends it
+    pub fn observe(&mut self, event: &GenerationEvent) -> Option<String> {
+        match event {
+            GenerationEvent::Started { tool_name, .. } => {
+                self.current_tool = tool_name.clone();
+                None
+            }
+            GenerationEvent::Example { example } => {
+                self.kept.entry(self.current_tool.clone()).or_default().push(example.clone());
+                None
+            }
+            GenerationEvent::ToolCompleted { tool_name, examples_generated, .. } => {
+                self.generated.insert(tool_name.clone(), *examples_generated);
+                Some(tool_name.clone())
+            }
+            GenerationEvent::Error { tool_name: Some(tool_name), .. } => {
+                self.errored.insert(tool_name.clone());
+                Some(tool_name.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Take the cache entry for a tool that has ended
+    pub fn finish(&mut self, tool: &ToolDocumentation, model: &str) -> CachedExamples {
+        CachedExamples::new(
+            ExampleCache::checksum(tool),
+            model,
+            self.generated.remove(&tool.name).unwrap_or(0),
+            self.kept.remove(&tool.name).unwrap_or_default(),
+            self.errored.remove(&tool.name),
+        )
+    }
+}
+
+fn cache_key(skill_name: &str, tool_name: &str) -> String {
+    format!("{}:{}", skill_name, tool_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::fixtures::simple_tool;
+
+    fn scored(score: f32) -> GeneratedExample {
+        let mut example = GeneratedExample::new("skill run list --type=pods", "List pods");
+        example.validated = true;
+        example.confidence = score;
+        example
+    }
+
+    #[test]
+    fn test_freshness_follows_checksum_age_and_failure() {
+        let tool = simple_tool();
+        let mut cache = ExampleCache::default();
+        assert!(!cache.is_fresh("k8s", &tool, None));
+
+        let checksum = ExampleCache::checksum(&tool);
+        cache.insert("k8s", &tool.name, CachedExamples::new(&checksum, "test", 2, vec![scored(1.0), scored(0.8)], false));
+        assert!(cache.is_fresh("k8s", &tool, None));
+        assert!((cache.get("k8s", &tool.name).unwrap().score() - 0.9).abs() < 1e-6);
+
+        // Expired
+        assert!(!cache.is_fresh("k8s", &tool, Some(Duration::seconds(-1))));
+
+        // Documentation changed
+        let mut changed = tool.clone();
+        changed.description.push_str(" in a namespace");
+        assert!(!cache.is_fresh("k8s", &changed, None));
+
+        // Most examples rejected
+        cache.insert("k8s", &tool.name, CachedExamples::new(&checksum, "test", 5, vec![scored(0.9)], false));
+        assert!(cache.is_failed("k8s", &tool.name));
+        assert!(!cache.is_fresh("k8s", &tool, None));
+    }
+
+    #[test]
+    fn test_recorder_builds_entries_from_events() {
+        let tool = simple_tool();
+        let builder = super::super::streaming::GenerationStreamBuilder::new(&tool.name, 1, 1);
+        let mut recorder = GenerationRecorder::new();
+
+        assert_eq!(recorder.observe(&builder.started()), None);
+        recorder.observe(&builder.example(scored(0.9)));
+        recorder.observe(&builder.example(scored(0.7)));
+        let ended = recorder.observe(&builder.tool_completed(3, 2, std::time::Duration::from_millis(5)));
+        assert_eq!(ended.as_deref(), Some("list"));
+
+        let entry = recorder.finish(&tool, "test");
+        assert_eq!(entry.generated, 3);
+        assert_eq!(entry.examples.len(), 2);
+        assert!(!entry.failed);
+        assert_eq!(entry.checksum, ExampleCache::checksum(&tool));
+
+        // An error ends the tool as failed
+        recorder.observe(&builder.started());
+        assert_eq!(recorder.observe(&builder.error("timeout", false)).as_deref(), Some("list"));
+        assert!(recorder.finish(&tool, "test").failed);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(ExampleCache::CACHE_FILE);
+        assert!(ExampleCache::load(&path).unwrap().get("k8s", "list").is_none());
+
+        let mut cache = ExampleCache::default();
+        cache.insert("k8s", "list", CachedExamples::new("abc", "test", 1, vec![scored(0.7)], false));
+        cache.save(&path).unwrap();
+
+        let loaded = ExampleCache::load(&path).unwrap();
+        let cached = loaded.get("k8s", "list").unwrap();
+        assert_eq!(cached.checksum, "abc");
+        assert_eq!(cached.examples[0].confidence, 0.7);
+    }
+}
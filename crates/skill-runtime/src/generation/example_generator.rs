@@ -14,6 +14,7 @@ use crate::search_config::AiIngestionConfig;
 use super::llm_provider::{LlmProvider, CompletionRequest};
 use super::validator::ExampleValidator;
 use super::streaming::{GenerationEvent, GeneratedExample, GenerationStreamBuilder};
+use super::budget::BudgetExceeded;
 
 /// Configuration for the example generator
 #[derive(Debug, Clone)]
@@ -22,6 +23,8 @@ pub struct GeneratorConfig {
     pub examples_per_tool: usize,
     /// Whether to validate generated examples
     pub validate_examples: bool,
+    /// Minimum validation score for an example to be kept
+    pub min_score: f32,
     /// Maximum retries for failed generation
     pub max_retries: usize,
     /// Timeout for generation
@@ -37,6 +40,7 @@ impl Default for GeneratorConfig {
         Self {
             examples_per_tool: 5,
             validate_examples: true,
+            min_score: 0.5,
             max_retries: 2,
             timeout: Duration::from_secs(30),
             temperature: 0.7,
@@ -50,6 +54,7 @@ impl From<&AiIngestionConfig> for GeneratorConfig {
         Self {
             examples_per_tool: config.examples_per_tool,
             validate_examples: config.validate_examples,
+            min_score: config.min_example_score,
             max_retries: 2,
             timeout: Duration::from_secs(config.timeout_secs),
             temperature: 0.7,
@@ -170,6 +175,11 @@ impl ExampleGenerator {
                             }
                         }
                     }
+                    Err(e) if e.downcast_ref::<BudgetExceeded>().is_some() => {
+                        // Retrying can't help until the budget changes
+                        yield builder.error(e.to_string(), false);
+                        return;
+                    }
                     Err(e) => {
                         if attempts >= self.config.max_retries {
                             yield builder.error(
@@ -195,10 +205,11 @@ impl ExampleGenerator {
                     yield builder.validation(
                         validation.valid,
                         validation.errors.clone(),
+                        validation.confidence,
                         idx,
                     );
 
-                    if validation.valid {
+                    if validation.valid && validation.confidence >= self.config.min_score {
                         example.validated = true;
                         example.confidence = validation.confidence;
                         valid_count += 1;
@@ -432,6 +443,9 @@ mod tests {
                     required: true,
                     default: None,
                     allowed_values: vec![],
+                    pattern: None,
+                    minimum: None,
+                    maximum: None,
                 },
                 ParameterDoc {
                     name: "namespace".to_string(),
@@ -440,9 +454,15 @@ mod tests {
                     required: false,
                     default: Some("default".to_string()),
                     allowed_values: vec![],
+                    pattern: None,
+                    minimum: None,
+                    maximum: None,
                 },
             ],
             examples: vec![],
+            hints: Default::default(),
+            requires: Vec::new(),
+            ai_generated: false,
         }
     }
 
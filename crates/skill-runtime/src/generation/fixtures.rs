@@ -30,6 +30,9 @@ pub fn kubernetes_apply_tool() -> ToolDocumentation {
                 required: true,
                 default: None,
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
             ParameterDoc {
                 name: "namespace".to_string(),
@@ -38,6 +41,9 @@ pub fn kubernetes_apply_tool() -> ToolDocumentation {
                 required: false,
                 default: Some("default".to_string()),
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
             ParameterDoc {
                 name: "dry-run".to_string(),
@@ -46,6 +52,9 @@ pub fn kubernetes_apply_tool() -> ToolDocumentation {
                 required: false,
                 default: None,
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
             ParameterDoc {
                 name: "output".to_string(),
@@ -54,6 +63,9 @@ pub fn kubernetes_apply_tool() -> ToolDocumentation {
                 required: false,
                 default: None,
                 allowed_values: vec!["json".to_string(), "yaml".to_string(), "wide".to_string()],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
             ParameterDoc {
                 name: "wait".to_string(),
@@ -62,6 +74,9 @@ pub fn kubernetes_apply_tool() -> ToolDocumentation {
                 required: false,
                 default: None,
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
             ParameterDoc {
                 name: "timeout".to_string(),
@@ -70,6 +85,9 @@ pub fn kubernetes_apply_tool() -> ToolDocumentation {
                 required: false,
                 default: Some("300".to_string()),
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
         ],
         examples: vec![
@@ -79,6 +97,9 @@ pub fn kubernetes_apply_tool() -> ToolDocumentation {
                 description: Some("Apply a deployment manifest".to_string()),
             },
         ],
+        hints: Default::default(),
+        requires: Vec::new(),
+        ai_generated: false,
     }
 }
 
@@ -96,9 +117,15 @@ pub fn simple_tool() -> ToolDocumentation {
                 required: true,
                 default: None,
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
         ],
         examples: vec![],
+        hints: Default::default(),
+        requires: Vec::new(),
+        ai_generated: false,
     }
 }
 
@@ -122,6 +149,9 @@ pub fn tool_with_constraints() -> ToolDocumentation {
                     "configmaps".to_string(),
                     "secrets".to_string(),
                 ],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
             ParameterDoc {
                 name: "output".to_string(),
@@ -130,6 +160,9 @@ pub fn tool_with_constraints() -> ToolDocumentation {
                 required: false,
                 default: None,
                 allowed_values: vec!["json".to_string(), "yaml".to_string(), "wide".to_string()],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
             ParameterDoc {
                 name: "all-namespaces".to_string(),
@@ -138,9 +171,15 @@ pub fn tool_with_constraints() -> ToolDocumentation {
                 required: false,
                 default: None,
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
         ],
         examples: vec![],
+        hints: Default::default(),
+        requires: Vec::new(),
+        ai_generated: false,
     }
 }
 
@@ -158,6 +197,9 @@ pub fn aws_s3_tool() -> ToolDocumentation {
                 required: true,
                 default: None,
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
             ParameterDoc {
                 name: "destination".to_string(),
@@ -166,6 +208,9 @@ pub fn aws_s3_tool() -> ToolDocumentation {
                 required: true,
                 default: None,
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
             ParameterDoc {
                 name: "recursive".to_string(),
@@ -174,6 +219,9 @@ pub fn aws_s3_tool() -> ToolDocumentation {
                 required: false,
                 default: None,
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
             ParameterDoc {
                 name: "region".to_string(),
@@ -182,9 +230,15 @@ pub fn aws_s3_tool() -> ToolDocumentation {
                 required: false,
                 default: Some("us-east-1".to_string()),
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
         ],
         examples: vec![],
+        hints: Default::default(),
+        requires: Vec::new(),
+        ai_generated: false,
     }
 }
 
@@ -202,6 +256,9 @@ pub fn docker_build_tool() -> ToolDocumentation {
                 required: true,
                 default: None,
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
             ParameterDoc {
                 name: "tag".to_string(),
@@ -210,6 +267,9 @@ pub fn docker_build_tool() -> ToolDocumentation {
                 required: false,
                 default: None,
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
             ParameterDoc {
                 name: "file".to_string(),
@@ -218,6 +278,9 @@ pub fn docker_build_tool() -> ToolDocumentation {
                 required: false,
                 default: Some("Dockerfile".to_string()),
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
             ParameterDoc {
                 name: "no-cache".to_string(),
@@ -226,9 +289,15 @@ pub fn docker_build_tool() -> ToolDocumentation {
                 required: false,
                 default: None,
                 allowed_values: vec![],
+                pattern: None,
+                minimum: None,
+                maximum: None,
             },
         ],
         examples: vec![],
+        hints: Default::default(),
+        requires: Vec::new(),
+        ai_generated: false,
     }
 }
 
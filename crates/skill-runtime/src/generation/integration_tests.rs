@@ -130,7 +130,7 @@ async fn test_validation_integration() {
     // Count validation events
     let validation_events: Vec<_> = events.iter()
         .filter_map(|e| {
-            if let GenerationEvent::Validation { valid, errors, example_index } = e {
+            if let GenerationEvent::Validation { valid, errors, example_index, .. } = e {
                 Some((valid, errors, example_index))
             } else {
                 None
@@ -423,6 +423,9 @@ async fn test_empty_tool_parameters() {
         usage: None,
         parameters: vec![],
         examples: vec![],
+        hints: Default::default(),
+        requires: Vec::new(),
+        ai_generated: false,
     };
 
     // Should still generate examples
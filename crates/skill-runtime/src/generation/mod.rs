@@ -5,8 +5,14 @@
 
 mod streaming;
 mod llm_provider;
+mod agent_runner;
 mod validator;
 mod example_generator;
+mod doc_drafter;
+mod example_cache;
+mod budget;
+#[cfg(feature = "sqlite-storage")]
+mod progress;
 mod evaluation;
 mod fixtures;
 
@@ -23,8 +29,16 @@ pub use llm_provider::{
     LlmProvider, LlmResponse, LlmChunk, TokenUsage,
     ChatMessage, CompletionRequest, create_llm_provider,
 };
+pub use agent_runner::{
+    AgentKind, AgentRunner, ClaudeCodeProvider, GeminiProvider, DEFAULT_AGENT_TIMEOUT,
+};
 pub use validator::{ExampleValidator, ValidationResult, ParsedCommand};
 pub use example_generator::{ExampleGenerator, GeneratorConfig};
+pub use doc_drafter::{DocDrafter, AI_GENERATED_TAG};
+pub use example_cache::{ExampleCache, CachedExamples, GenerationRecorder};
+pub use budget::{BudgetGuard, BudgetedProvider, BudgetExceeded, estimate_tokens};
+#[cfg(feature = "sqlite-storage")]
+pub use progress::{EnhanceProgressDb, EnhanceRun, EnhanceStatus, SkillProgress, ToolProgress};
 
 #[cfg(feature = "ollama")]
 pub use llm_provider::ollama::OllamaProvider;
@@ -0,0 +1,412 @@
+//! Enhance Progress - resumable example generation runs
+//!
+//! `skill enhance` records every tool it works through in SQLite. When a run
+//! is interrupted, the next run over the same skills picks it up and skips
+//! the tools that were already processed. The same database backs the
+//! progress reported to the web UI.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{Row, SqlitePool, sqlite::SqlitePoolOptions};
+
+/// Processing state of a tool within a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolProgress {
+    /// Not processed yet
+    Pending,
+    /// Examples generated and kept
+    Completed,
+    /// Generation errored or its examples were rejected
+    Failed,
+}
+
+impl ToolProgress {
+    /// Database representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ToolProgress::Pending => "pending",
+            ToolProgress::Completed => "completed",
+            ToolProgress::Failed => "failed",
+        }
+    }
+}
+
+/// An enhance run, either new or resumed
+#[derive(Debug, Clone)]
+pub struct EnhanceRun {
+    /// Run identifier
+    pub id: String,
+    /// Skills covered by the run
+    pub skills: Vec<String>,
+    /// When the run first started
+    pub started_at: DateTime<Utc>,
+    /// Whether an interrupted run was picked up
+    pub resumed: bool,
+}
+
+/// Progress of one skill within a run
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillProgress {
+    /// Skill name
+    pub skill: String,
+    /// Tools planned for generation
+    pub total: usize,
+    /// Tools completed
+    pub completed: usize,
+    /// Tools that failed
+    pub failed: usize,
+    /// Tools still to process
+    pub pending: usize,
+}
+
+/// Progress of the most recent enhance run
+#[derive(Debug, Clone, Serialize)]
+pub struct EnhanceStatus {
+    /// Run identifier
+    pub run_id: String,
+    /// Whether the run is still going (or was interrupted)
+    pub running: bool,
+    /// When the run started
+    pub started_at: DateTime<Utc>,
+    /// Last recorded activity
+    pub updated_at: DateTime<Utc>,
+    /// When the run finished
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Per-skill progress, in the order skills were planned
+    pub skills: Vec<SkillProgress>,
+}
+
+impl EnhanceStatus {
+    /// Tools planned across all skills
+    pub fn total(&self) -> usize {
+        self.skills.iter().map(|s| s.total).sum()
+    }
+
+    /// Tools processed across all skills
+    pub fn processed(&self) -> usize {
+        self.skills.iter().map(|s| s.completed + s.failed).sum()
+    }
+}
+
+/// SQLite store for enhance progress
+pub struct EnhanceProgressDb {
+    pool: SqlitePool,
+}
+
+impl EnhanceProgressDb {
+    /// Database file name under `~/.skill-engine`
+    pub const DB_FILE: &'static str = "enhance.db";
+
+    /// Default database location
+    pub fn default_path() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Failed to get home directory")?;
+        Ok(home.join(".skill-engine").join(Self::DB_FILE))
+    }
+
+    /// Open (or create) the progress database; `:memory:` is supported
+    pub async fn open(path: &Path) -> Result<Self> {
+        let in_memory = path == Path::new(":memory:");
+        let url = if in_memory {
+            "sqlite::memory:".to_string()
+        } else {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .context("Failed to create enhance progress directory")?;
+            }
+            format!("sqlite:{}?mode=rwc", path.display())
+        };
+
+        // Every connection to an in-memory database sees its own database
+        let pool = SqlitePoolOptions::new()
+            .max_connections(if in_memory { 1 } else { 2 })
+            .connect(&url)
+            .await
+            .context("Failed to connect to enhance progress database")?;
+
+        let db = Self { pool };
+        db.setup().await?;
+        Ok(db)
+    }
+
+    /// Initialize database schema
+    async fn setup(&self) -> Result<()> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS enhance_runs (
+                id TEXT PRIMARY KEY,
+                skills TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                finished_at TEXT
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create enhance_runs table")?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS enhance_tools (
+                run_id TEXT NOT NULL,
+                skill TEXT NOT NULL,
+                tool TEXT NOT NULL,
+                position INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                examples INTEGER NOT NULL DEFAULT 0,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (run_id, skill, tool)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .context("Failed to create enhance_tools table")?;
+
+        Ok(())
+    }
+
+    /// Resume the unfinished run over the same skills, or start a new one
+    pub async fn start_or_resume(&self, skills: &[String]) -> Result<EnhanceRun> {
+        let mut sorted = skills.to_vec();
+        sorted.sort();
+        let key = serde_json::to_string(&sorted)?;
+
+        let unfinished = sqlx::query(
+            "SELECT id, started_at FROM enhance_runs
+             WHERE skills = ? AND finished_at IS NULL
+             ORDER BY started_at DESC LIMIT 1",
+        )
+        .bind(&key)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to look up unfinished enhance run")?;
+
+        if let Some(row) = unfinished {
+            return Ok(EnhanceRun {
+                id: row.get("id"),
+                skills: sorted,
+                started_at: parse_time(row.get("started_at"))?,
+                resumed: true,
+            });
+        }
+
+        let now = Utc::now();
+        let id = uuid::Uuid::new_v4().to_string();
+        sqlx::query(
+            "INSERT INTO enhance_runs (id, skills, started_at, updated_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(&id)
+        .bind(&key)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to record enhance run")?;
+
+        Ok(EnhanceRun {
+            id,
+            skills: sorted,
+            started_at: now,
+            resumed: false,
+        })
+    }
+
+    /// Plan the tools of a skill; tools already recorded keep their state
+    pub async fn plan_tools(&self, run_id: &str, skill: &str, tools: &[String]) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let base: i64 = sqlx::query("SELECT COUNT(*) AS count FROM enhance_tools WHERE run_id = ?")
+            .bind(run_id)
+            .fetch_one(&self.pool)
+            .await?
+            .get("count");
+
+        for (offset, tool) in tools.iter().enumerate() {
+            sqlx::query(
+                "INSERT OR IGNORE INTO enhance_tools (run_id, skill, tool, position, status, updated_at)
+                 VALUES (?, ?, ?, ?, 'pending', ?)",
+            )
+            .bind(run_id)
+            .bind(skill)
+            .bind(tool)
+            .bind(base + offset as i64)
+            .bind(&now)
+            .execute(&self.pool)
+            .await
+            .context("Failed to plan enhance tools")?;
+        }
+        self.touch(run_id, &now).await
+    }
+
+    /// Record the outcome for a tool
+    pub async fn record_tool(
+        &self,
+        run_id: &str,
+        skill: &str,
+        tool: &str,
+        progress: ToolProgress,
+        examples: usize,
+    ) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            "UPDATE enhance_tools SET status = ?, examples = ?, updated_at = ?
+             WHERE run_id = ? AND skill = ? AND tool = ?",
+        )
+        .bind(progress.as_str())
+        .bind(examples as i64)
+        .bind(&now)
+        .bind(run_id)
+        .bind(skill)
+        .bind(tool)
+        .execute(&self.pool)
+        .await
+        .context("Failed to record enhance progress")?;
+        self.touch(run_id, &now).await
+    }
+
+    /// Tools of a skill this run has already processed
+    pub async fn processed_tools(&self, run_id: &str, skill: &str) -> Result<HashSet<String>> {
+        let rows = sqlx::query(
+            "SELECT tool FROM enhance_tools WHERE run_id = ? AND skill = ? AND status != 'pending'",
+        )
+        .bind(run_id)
+        .bind(skill)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load enhance progress")?;
+
+        Ok(rows.iter().map(|row| row.get("tool")).collect())
+    }
+
+    /// Mark a run as finished so it isn't resumed
+    pub async fn finish_run(&self, run_id: &str) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        sqlx::query("UPDATE enhance_runs SET finished_at = ?, updated_at = ? WHERE id = ?")
+            .bind(&now)
+            .bind(&now)
+            .bind(run_id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to finish enhance run")?;
+        Ok(())
+    }
+
+    /// Progress of the most recently active run
+    pub async fn latest_status(&self) -> Result<Option<EnhanceStatus>> {
+        let Some(run) = sqlx::query(
+            "SELECT id, started_at, updated_at, finished_at FROM enhance_runs
+             ORDER BY updated_at DESC LIMIT 1",
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to load enhance run")?
+        else {
+            return Ok(None);
+        };
+        let run_id: String = run.get("id");
+
+        let rows = sqlx::query(
+            "SELECT skill, status, COUNT(*) AS count, MIN(position) AS first_position
+             FROM enhance_tools WHERE run_id = ?
+             GROUP BY skill, status ORDER BY first_position",
+        )
+        .bind(&run_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to load enhance progress")?;
+
+        let mut skills: Vec<SkillProgress> = Vec::new();
+        for row in &rows {
+            let skill: String = row.get("skill");
+            let count = row.get::<i64, _>("count") as usize;
+            let index = match skills.iter().position(|s| s.skill == skill) {
+                Some(index) => index,
+                None => {
+                    skills.push(SkillProgress { skill, total: 0, completed: 0, failed: 0, pending: 0 });
+                    skills.len() - 1
+                }
+            };
+            let progress = &mut skills[index];
+            progress.total += count;
+            match row.get::<String, _>("status").as_str() {
+                "completed" => progress.completed += count,
+                "failed" => progress.failed += count,
+                _ => progress.pending += count,
+            }
+        }
+
+        let finished_at: Option<String> = run.get("finished_at");
+        Ok(Some(EnhanceStatus {
+            run_id,
+            running: finished_at.is_none(),
+            started_at: parse_time(run.get("started_at"))?,
+            updated_at: parse_time(run.get("updated_at"))?,
+            finished_at: finished_at.map(parse_time).transpose()?,
+            skills,
+        }))
+    }
+
+    async fn touch(&self, run_id: &str, now: &str) -> Result<()> {
+        sqlx::query("UPDATE enhance_runs SET updated_at = ? WHERE id = ?")
+            .bind(now)
+            .bind(run_id)
+            .execute(&self.pool)
+            .await
+            .context("Failed to update enhance run")?;
+        Ok(())
+    }
+}
+
+fn parse_time(value: String) -> Result<DateTime<Utc>> {
+    Ok(DateTime::parse_from_rfc3339(&value)
+        .with_context(|| format!("Invalid timestamp '{}'", value))?
+        .with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_run_is_resumed() {
+        let db = EnhanceProgressDb::open(Path::new(":memory:")).await.unwrap();
+        let skills = names(&["kubernetes", "git"]);
+
+        let run = db.start_or_resume(&skills).await.unwrap();
+        assert!(!run.resumed);
+        db.plan_tools(&run.id, "git", &names(&["status", "log"])).await.unwrap();
+        db.plan_tools(&run.id, "kubernetes", &names(&["get", "apply"])).await.unwrap();
+        db.record_tool(&run.id, "git", "status", ToolProgress::Completed, 5).await.unwrap();
+        db.record_tool(&run.id, "git", "log", ToolProgress::Failed, 0).await.unwrap();
+
+        // Same skills in another order pick up the unfinished run
+        let resumed = db.start_or_resume(&names(&["git", "kubernetes"])).await.unwrap();
+        assert!(resumed.resumed);
+        assert_eq!(resumed.id, run.id);
+        assert_eq!(db.processed_tools(&run.id, "git").await.unwrap().len(), 2);
+        assert!(db.processed_tools(&run.id, "kubernetes").await.unwrap().is_empty());
+
+        // Re-planning keeps recorded progress
+        db.plan_tools(&run.id, "git", &names(&["status", "log"])).await.unwrap();
+        let status = db.latest_status().await.unwrap().unwrap();
+        assert!(status.running);
+        assert_eq!(status.skills[0].skill, "git");
+        assert_eq!(status.skills[0].completed, 1);
+        assert_eq!(status.skills[0].failed, 1);
+        assert_eq!(status.skills[1].pending, 2);
+        assert_eq!((status.processed(), status.total()), (2, 4));
+
+        db.finish_run(&run.id).await.unwrap();
+        assert!(!db.latest_status().await.unwrap().unwrap().running);
+        assert!(!db.start_or_resume(&skills).await.unwrap().resumed);
+    }
+}
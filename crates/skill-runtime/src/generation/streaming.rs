@@ -59,6 +59,9 @@ pub enum GenerationEvent {
         valid: bool,
         /// Validation errors (if any)
         errors: Vec<String>,
+        /// Validation score (0.0 - 1.0, 0.0 when invalid)
+        #[serde(default)]
+        score: f32,
         /// Index of the example being validated
         example_index: usize,
     },
@@ -181,9 +184,9 @@ impl GenerationEvent {
         format!("data: {}\n\n", serde_json::to_string(self).unwrap_or_default())
     }
 
-    /// Format as SSE with event type
-    pub fn to_sse(&self) -> String {
-        let event_type = match self {
+    /// SSE event type for this event
+    pub fn event_type(&self) -> &'static str {
+        match self {
             Self::Started { .. } => "started",
             Self::Thinking { .. } => "thinking",
             Self::Searching { .. } => "searching",
@@ -195,10 +198,14 @@ impl GenerationEvent {
             Self::Completed { .. } => "completed",
             Self::Error { .. } => "error",
             Self::AgentStep { .. } => "agent_step",
-        };
+        }
+    }
+
+    /// Format as SSE with event type
+    pub fn to_sse(&self) -> String {
         format!(
             "event: {}\ndata: {}\n\n",
-            event_type,
+            self.event_type(),
             serde_json::to_string(self).unwrap_or_default()
         )
     }
@@ -387,10 +394,11 @@ impl GenerationStreamBuilder {
     }
 
     /// Create a validation event
-    pub fn validation(&self, valid: bool, errors: Vec<String>, example_index: usize) -> GenerationEvent {
+    pub fn validation(&self, valid: bool, errors: Vec<String>, score: f32, example_index: usize) -> GenerationEvent {
         GenerationEvent::Validation {
             valid,
             errors,
+            score,
             example_index,
         }
     }
@@ -181,10 +181,12 @@ impl ExampleValidator {
         // Calculate final validity
         let valid = errors.is_empty() && (!self.strict || warnings.is_empty());
 
-        // Adjust confidence based on warnings
+        // Adjust confidence based on warnings, starting from full confidence
+        // when the model didn't report one
         let confidence = if valid {
+            let base = if example.confidence > 0.0 { example.confidence } else { 1.0 };
             let warning_penalty = 0.1 * warnings.len() as f32;
-            (example.confidence - warning_penalty).max(0.1)
+            (base - warning_penalty).max(0.1)
         } else {
             0.0
         };
@@ -425,6 +427,9 @@ mod tests {
                     required: true,
                     default: None,
                     allowed_values: vec![],
+                    pattern: None,
+                    minimum: None,
+                    maximum: None,
                 },
                 ParameterDoc {
                     name: "namespace".to_string(),
@@ -433,6 +438,9 @@ mod tests {
                     required: false,
                     default: Some("default".to_string()),
                     allowed_values: vec![],
+                    pattern: None,
+                    minimum: None,
+                    maximum: None,
                 },
                 ParameterDoc {
                     name: "dry-run".to_string(),
@@ -441,9 +449,15 @@ mod tests {
                     required: false,
                     default: None,
                     allowed_values: vec![],
+                    pattern: None,
+                    minimum: None,
+                    maximum: None,
                 },
             ],
             examples: vec![],
+            hints: Default::default(),
+            requires: Vec::new(),
+            ai_generated: false,
         }
     }
 
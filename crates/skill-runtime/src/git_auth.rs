@@ -0,0 +1,298 @@
+//! Authentication and clone options for Git skill sources
+//!
+//! Private repositories are reached with, in order of preference:
+//!
+//! - an SSH deploy key (`ssh_key`) or keys from the running SSH agent when the
+//!   source is cloned over SSH (`git@host:org/repo.git` or `ssh = true`)
+//! - a token for HTTPS, taken from `token` (a `keyring://` reference, a
+//!   `${ENV_VAR}`, or a literal), then `SKILL_GIT_TOKEN`, then the host's usual
+//!   variable (`GITHUB_TOKEN`/`GH_TOKEN`, `GITLAB_TOKEN`, `BITBUCKET_TOKEN`),
+//!   then the keyring entry `keyring://skill-engine/git/<host>/token`
+//!
+//! ```toml
+//! [skills.internal]
+//! source = "github:acme/internal-skills"
+//! git = { ssh_key = "~/.ssh/deploy_internal", depth = 1 }
+//! ```
+
+use anyhow::{Context, Result};
+use git2::{Cred, CredentialType, FetchOptions, RemoteCallbacks};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::debug;
+use zeroize::Zeroizing;
+
+use crate::credentials::{parse_keyring_reference, CredentialStore};
+
+/// Maximum credential attempts before giving up (libgit2 retries on failure)
+const MAX_AUTH_ATTEMPTS: usize = 3;
+
+/// Per-source clone and authentication settings (`git` table of a manifest skill)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitSourceConfig {
+    /// Private key file used for SSH (deploy key); defaults to the SSH agent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssh_key: Option<PathBuf>,
+
+    /// Passphrase for `ssh_key` (`keyring://` reference or `${ENV_VAR}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssh_key_passphrase: Option<String>,
+
+    /// Clone over SSH even when the source is an HTTPS URL or shorthand
+    pub ssh: bool,
+
+    /// HTTPS token (`keyring://` reference, `${ENV_VAR}`, or literal)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+
+    /// Username sent with the token (defaults to the host's convention)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    /// Shallow clone depth (full history when unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth: Option<u32>,
+
+    /// Initialize and update submodules after checkout
+    pub submodules: bool,
+}
+
+impl Default for GitSourceConfig {
+    fn default() -> Self {
+        Self {
+            ssh_key: None,
+            ssh_key_passphrase: None,
+            ssh: false,
+            token: None,
+            username: None,
+            depth: None,
+            submodules: true,
+        }
+    }
+}
+
+impl GitSourceConfig {
+    /// Whether the source should be cloned over SSH
+    pub fn uses_ssh(&self) -> bool {
+        self.ssh || self.ssh_key.is_some()
+    }
+}
+
+/// Called with the objects received and the objects to receive while a
+/// repository is fetched; returning `false` aborts the fetch
+pub type TransferProgress = Arc<dyn Fn(usize, usize) -> bool + Send + Sync>;
+
+/// Credentials resolved for one host
+pub struct GitAuth {
+    ssh_key: Option<PathBuf>,
+    passphrase: Option<Zeroizing<String>>,
+    token: Option<Zeroizing<String>>,
+    username: String,
+    depth: Option<u32>,
+    progress: Option<TransferProgress>,
+}
+
+impl GitAuth {
+    /// Resolve credentials for `host` from the source config, environment and keyring
+    pub fn resolve(config: &GitSourceConfig, host: &str) -> Result<Self> {
+        let token = match &config.token {
+            Some(token) => Some(resolve_secret(token).context("Failed to resolve git token")?),
+            None => default_token(host),
+        };
+        let passphrase = config
+            .ssh_key_passphrase
+            .as_deref()
+            .map(resolve_secret)
+            .transpose()
+            .context("Failed to resolve SSH key passphrase")?;
+
+        Ok(Self {
+            ssh_key: config.ssh_key.as_deref().map(expand_home),
+            passphrase,
+            token,
+            username: config
+                .username
+                .clone()
+                .unwrap_or_else(|| default_username(host).to_string()),
+            depth: config.depth,
+            progress: None,
+        })
+    }
+
+    /// Report fetch progress to `progress`
+    pub fn with_progress(mut self, progress: Option<TransferProgress>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Whether an HTTPS token was found
+    pub fn has_token(&self) -> bool {
+        self.token.is_some()
+    }
+
+    /// Fetch options carrying the credential callback (and depth, if shallow)
+    pub fn fetch_options(&self) -> FetchOptions<'_> {
+        let mut options = FetchOptions::new();
+        options.remote_callbacks(self.callbacks());
+        if let Some(depth) = self.depth {
+            options.depth(depth as i32);
+        }
+        options
+    }
+
+    fn callbacks(&self) -> RemoteCallbacks<'_> {
+        let mut callbacks = RemoteCallbacks::new();
+        callbacks.transfer_progress(|progress| {
+            debug!(
+                "Receiving objects: {}/{}",
+                progress.received_objects(),
+                progress.total_objects()
+            );
+            match &self.progress {
+                Some(report) => report(progress.received_objects(), progress.total_objects()),
+                None => true,
+            }
+        });
+
+        let mut attempts = 0;
+        callbacks.credentials(move |url, username_from_url, allowed| {
+            attempts += 1;
+            if attempts > MAX_AUTH_ATTEMPTS {
+                return Err(git2::Error::from_str(&format!(
+                    "Authentication failed for {}",
+                    url
+                )));
+            }
+
+            if allowed.contains(CredentialType::SSH_KEY) {
+                let user = username_from_url.unwrap_or("git");
+                return match &self.ssh_key {
+                    Some(key) => Cred::ssh_key(
+                        user,
+                        None,
+                        key,
+                        self.passphrase.as_ref().map(|p| p.as_str()),
+                    ),
+                    None => Cred::ssh_key_from_agent(user),
+                };
+            }
+
+            if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+                if let Some(token) = &self.token {
+                    return Cred::userpass_plaintext(&self.username, token.as_str());
+                }
+            }
+
+            if allowed.contains(CredentialType::DEFAULT) {
+                return Cred::default();
+            }
+
+            Err(git2::Error::from_str(&format!(
+                "No credentials for {}: set a token (SKILL_GIT_TOKEN or `git.token`) \
+                 or an SSH key (`git.ssh_key`) for this source",
+                url
+            )))
+        });
+
+        callbacks
+    }
+}
+
+/// Username conventionally paired with an access token on each host
+fn default_username(host: &str) -> &'static str {
+    match host {
+        "github.com" => "x-access-token",
+        "gitlab.com" => "oauth2",
+        "bitbucket.org" => "x-token-auth",
+        _ => "git",
+    }
+}
+
+/// Token from the environment or keyring when the source sets none
+fn default_token(host: &str) -> Option<Zeroizing<String>> {
+    let host_vars: &[&str] = match host {
+        "github.com" => &["GITHUB_TOKEN", "GH_TOKEN"],
+        "gitlab.com" => &["GITLAB_TOKEN"],
+        "bitbucket.org" => &["BITBUCKET_TOKEN"],
+        _ => &[],
+    };
+
+    let from_env = std::iter::once("SKILL_GIT_TOKEN")
+        .chain(host_vars.iter().copied())
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()));
+    if let Some(token) = from_env {
+        return Some(Zeroizing::new(token));
+    }
+
+    let store = CredentialStore::new();
+    if store.has_credential("git", host, "token") {
+        return store.get_credential("git", host, "token").ok();
+    }
+    None
+}
+
+/// Resolve a `keyring://` reference, `${ENV_VAR}`, or literal value
+fn resolve_secret(value: &str) -> Result<Zeroizing<String>> {
+    if value.starts_with("keyring://") {
+        let (skill, instance, key) = parse_keyring_reference(value)?;
+        return CredentialStore::new().get_credential(&skill, &instance, &key);
+    }
+
+    if let Some(var) = value.strip_prefix("${").and_then(|v| v.strip_suffix('}')) {
+        let secret = std::env::var(var)
+            .with_context(|| format!("Environment variable {} is not set", var))?;
+        return Ok(Zeroizing::new(secret));
+    }
+
+    Ok(Zeroizing::new(value.to_string()))
+}
+
+fn expand_home(path: &std::path::Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_source_config() {
+        let config: GitSourceConfig =
+            toml::from_str("ssh_key = \"~/.ssh/deploy\"\ndepth = 1\nsubmodules = false\n").unwrap();
+        assert!(config.uses_ssh());
+        assert_eq!(config.depth, Some(1));
+        assert!(!config.submodules);
+
+        let config: GitSourceConfig = toml::from_str("").unwrap();
+        assert_eq!(config, GitSourceConfig::default());
+        assert!(config.submodules && !config.uses_ssh());
+    }
+
+    #[test]
+    fn test_resolve_secret_and_usernames() {
+        std::env::set_var("SKILL_TEST_GIT_TOKEN", "tok-123");
+        assert_eq!(
+            resolve_secret("${SKILL_TEST_GIT_TOKEN}").unwrap().as_str(),
+            "tok-123"
+        );
+        assert_eq!(resolve_secret("literal").unwrap().as_str(), "literal");
+        assert!(resolve_secret("${SKILL_TEST_GIT_TOKEN_UNSET}").is_err());
+
+        let auth = GitAuth::resolve(
+            &GitSourceConfig {
+                token: Some("${SKILL_TEST_GIT_TOKEN}".to_string()),
+                ..Default::default()
+            },
+            "gitlab.com",
+        )
+        .unwrap();
+        assert!(auth.has_token());
+        assert_eq!(auth.username, "oauth2");
+        assert_eq!(default_username("github.com"), "x-access-token");
+    }
+}
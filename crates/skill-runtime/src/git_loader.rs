@@ -5,15 +5,20 @@
 // - Auto-detection of skill type (Rust, JS/TS, Python, pre-built WASM)
 // - Caching cloned repositories for fast subsequent access
 // - Version pinning via tags, branches, or commits
+// - Monorepos: several skill directories in one repository
+// - Private repositories over SSH (agent or deploy key) or HTTPS tokens,
+//   shallow clones and submodules (see `git_auth`)
 
 use anyhow::{Context, Result};
-use git2::{FetchOptions, RemoteCallbacks, Repository};
+use git2::Repository;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use tracing::{debug, info, warn};
 
-use crate::git_source::GitSource;
+use crate::dependencies::{read_declared_dependencies, SkillDependency};
+use crate::git_auth::{GitAuth, GitSourceConfig, TransferProgress};
+use crate::git_source::{GitRef, GitSource};
 
 /// Skill type detected from repository structure
 #[derive(Debug, Clone, PartialEq)]
@@ -50,7 +55,8 @@ impl std::fmt::Display for SkillType {
 pub struct ClonedSkill {
     /// Original Git source
     pub source: GitSource,
-    /// Local path to cloned repository
+    /// Local path to the skill directory (the clone root unless the skill
+    /// lives in a subdirectory of a monorepo)
     pub local_path: PathBuf,
     /// Detected skill type
     pub skill_type: SkillType,
@@ -58,6 +64,10 @@ pub struct ClonedSkill {
     pub skill_name: String,
     /// Skill version (if found in manifest)
     pub version: Option<String>,
+    /// Skills this skill depends on (from skill.yaml or SKILL.md)
+    pub dependencies: Vec<SkillDependency>,
+    /// Commit checked out, if it could be read
+    pub commit: Option<String>,
 }
 
 /// Cache metadata for tracking cloned repositories
@@ -88,6 +98,10 @@ pub struct GitSkillLoader {
     sources_dir: PathBuf,
     /// Cache file path
     cache_path: PathBuf,
+    /// Authentication and clone options
+    config: GitSourceConfig,
+    /// Receives fetch progress, and can abort the fetch
+    progress: Option<TransferProgress>,
 }
 
 impl GitSkillLoader {
@@ -104,9 +118,24 @@ impl GitSkillLoader {
         Ok(Self {
             sources_dir,
             cache_path,
+            config: GitSourceConfig::default(),
+            progress: None,
         })
     }
 
+    /// Use per-source authentication and clone options
+    pub fn with_config(mut self, config: GitSourceConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Report clone and fetch progress to `progress`; returning `false`
+    /// from it aborts the transfer
+    pub fn with_progress(mut self, progress: TransferProgress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
     /// Get the directory for a cloned repo
     pub fn get_repo_dir(&self, source: &GitSource) -> PathBuf {
         self.sources_dir.join(&source.owner).join(&source.repo)
@@ -118,7 +147,67 @@ impl GitSkillLoader {
     }
 
     /// Clone or update a Git repository and prepare for loading
+    ///
+    /// The source must resolve to a single skill; repositories holding
+    /// several skills need a `#subpath` or [`Self::clone_skills`].
     pub async fn clone_skill(&self, source: &GitSource, force: bool) -> Result<ClonedSkill> {
+        let mut skills = self.clone_skills(source, force).await?;
+        if skills.len() == 1 {
+            return Ok(skills.remove(0));
+        }
+
+        let names: Vec<&str> = skills.iter().map(|s| s.skill_name.as_str()).collect();
+        anyhow::bail!(
+            "{} contains {} skills ({}). Select one with {}#<path>",
+            source.display_name(),
+            skills.len(),
+            names.join(", "),
+            source
+                .original
+                .split('#')
+                .next()
+                .unwrap_or(&source.original)
+        )
+    }
+
+    /// Clone or update a Git repository and prepare every skill it provides
+    ///
+    /// Skills are looked up in the source's `#subpath` (or the repository
+    /// root). If that directory has no SKILL.md or skill.wasm of its own, its
+    /// subdirectories that do are returned as separate skills.
+    pub async fn clone_skills(&self, source: &GitSource, force: bool) -> Result<Vec<ClonedSkill>> {
+        let repo_dir = self.sync_repo(source, force)?;
+
+        let base_dir = match &source.subpath {
+            Some(subpath) => {
+                let dir = repo_dir.join(subpath);
+                if !dir.is_dir() {
+                    anyhow::bail!("Path '{}' not found in {}", subpath, source.display_name());
+                }
+                dir
+            }
+            None => repo_dir.clone(),
+        };
+
+        let mut skill_dirs = Vec::new();
+        if !has_skill_marker(&base_dir) {
+            discover_skill_dirs(&base_dir, 0, &mut skill_dirs)?;
+            skill_dirs.sort();
+        }
+        if skill_dirs.is_empty() {
+            skill_dirs.push(base_dir);
+        } else {
+            info!(count = skill_dirs.len(), "Discovered skills in repository");
+        }
+
+        skill_dirs
+            .into_iter()
+            .map(|dir| self.load_cloned_skill(source, &repo_dir, dir))
+            .collect()
+    }
+
+    /// Clone the repository, or update an existing clone, and return its path
+    fn sync_repo(&self, source: &GitSource, force: bool) -> Result<PathBuf> {
         let repo_dir = self.get_repo_dir(source);
 
         if force && repo_dir.exists() {
@@ -143,22 +232,50 @@ impl GitSkillLoader {
             self.clone_repo(source, &repo_dir)?;
         }
 
+        Ok(repo_dir)
+    }
+
+    /// Prepare the skill in `skill_dir` of a cloned repository
+    fn load_cloned_skill(
+        &self,
+        source: &GitSource,
+        repo_dir: &Path,
+        skill_dir: PathBuf,
+    ) -> Result<ClonedSkill> {
         // Detect skill type
-        let skill_type = self.detect_skill_type(&repo_dir)?;
-        info!(skill_type = %skill_type, "Detected skill type");
+        let skill_type = self.detect_skill_type(&skill_dir)?;
+        info!(skill_type = %skill_type, path = %skill_dir.display(), "Detected skill type");
 
-        // Extract metadata
-        let (skill_name, version) = self.extract_metadata(&repo_dir, source)?;
+        // Extract metadata; skills in subdirectories default to the directory name
+        let default_name = if skill_dir == repo_dir {
+            source.repo.clone()
+        } else {
+            skill_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| source.repo.clone())
+        };
+        let (skill_name, version) = extract_metadata(&skill_dir, &default_name)?;
+        let dependencies = read_declared_dependencies(&skill_dir)?;
+
+        let commit = Repository::open(repo_dir).ok().and_then(|repo| {
+            repo.head()
+                .ok()
+                .and_then(|head| head.peel_to_commit().ok())
+                .map(|commit| commit.id().to_string())
+        });
 
         // Update cache
-        self.update_cache(source, &repo_dir, &skill_name)?;
+        self.update_cache(source, repo_dir, &skill_dir, &skill_name, commit.as_deref())?;
 
         Ok(ClonedSkill {
             source: source.clone(),
-            local_path: repo_dir,
+            local_path: skill_dir,
             skill_type,
             skill_name,
             version,
+            dependencies,
+            commit,
         })
     }
 
@@ -204,68 +321,105 @@ impl GitSkillLoader {
 
     // --- Private methods ---
 
+    /// URL to clone from: SSH for `git@` sources or when configured, else HTTPS
+    fn clone_url(&self, source: &GitSource) -> String {
+        if self.config.uses_ssh() || source.original.starts_with("git@") {
+            source.ssh_url()
+        } else {
+            source.url.clone()
+        }
+    }
+
     fn clone_repo(&self, source: &GitSource, dest: &Path) -> Result<()> {
         std::fs::create_dir_all(dest.parent().unwrap())?;
 
-        // Set up callbacks for progress
-        let mut callbacks = RemoteCallbacks::new();
-        callbacks.transfer_progress(|progress| {
-            debug!(
-                "Receiving objects: {}/{}",
-                progress.received_objects(),
-                progress.total_objects()
-            );
-            true
-        });
-
-        let mut fetch_options = FetchOptions::new();
-        fetch_options.remote_callbacks(callbacks);
+        let auth = GitAuth::resolve(&self.config, source.host())?.with_progress(self.progress.clone());
+        let url = self.clone_url(source);
 
         // Clone the repository
         let mut builder = git2::build::RepoBuilder::new();
-        builder.fetch_options(fetch_options);
+        builder.fetch_options(auth.fetch_options());
+        if let (Some(_), GitRef::Branch(branch)) = (self.config.depth, &source.git_ref) {
+            builder.branch(branch);
+        }
 
         let repo = builder
-            .clone(&source.url, dest)
-            .with_context(|| format!("Failed to clone repository: {}", source.url))?;
+            .clone(&url, dest)
+            .with_context(|| format!("Failed to clone repository: {}", url))?;
 
         // Checkout specific ref if not default branch
         if let Some(refspec) = source.git_ref.as_refspec() {
+            if self.config.depth.is_some() {
+                self.fetch_pinned_ref(&repo, &source.git_ref, &auth)?;
+            }
             self.checkout_ref_in_repo(&repo, refspec)?;
         }
 
+        if self.config.submodules {
+            update_submodules(&repo, &auth)?;
+        }
+
         Ok(())
     }
 
     fn checkout_ref(&self, repo_dir: &Path, source: &GitSource) -> Result<()> {
         let repo = Repository::open(repo_dir)
             .with_context(|| format!("Failed to open repository: {}", repo_dir.display()))?;
+        let auth = GitAuth::resolve(&self.config, source.host())?.with_progress(self.progress.clone());
 
         // Fetch updates if not a pinned ref
         if !source.git_ref.is_pinned() {
             debug!("Fetching updates from origin...");
             let mut remote = repo.find_remote("origin")?;
-            remote.fetch(&["refs/heads/*:refs/heads/*"], None, None)?;
+            remote.fetch(
+                &["refs/heads/*:refs/heads/*"],
+                Some(&mut auth.fetch_options()),
+                None,
+            )?;
         }
 
         if let Some(refspec) = source.git_ref.as_refspec() {
             self.checkout_ref_in_repo(&repo, refspec)?;
         }
 
+        if self.config.submodules {
+            update_submodules(&repo, &auth)?;
+        }
+
+        Ok(())
+    }
+
+    /// A shallow clone only has one branch; fetch a pinned tag or commit explicitly
+    fn fetch_pinned_ref(&self, repo: &Repository, git_ref: &GitRef, auth: &GitAuth) -> Result<()> {
+        let refspec = match git_ref {
+            GitRef::Tag(tag) => format!("+refs/tags/{0}:refs/tags/{0}", tag),
+            GitRef::Commit(sha) => sha.clone(),
+            GitRef::Branch(_) | GitRef::DefaultBranch => return Ok(()),
+        };
+
+        let mut remote = repo.find_remote("origin")?;
+        remote
+            .fetch(&[refspec.as_str()], Some(&mut auth.fetch_options()), None)
+            .with_context(|| format!("Failed to fetch {}", git_ref))?;
         Ok(())
     }
 
     fn checkout_ref_in_repo(&self, repo: &Repository, refspec: &str) -> Result<()> {
         info!(refspec = %refspec, "Checking out ref");
 
-        // Try to find the reference
+        // Try to find the reference, then fall back to a commit SHA
         let reference = repo
             .resolve_reference_from_short_name(refspec)
             .or_else(|_| repo.find_reference(&format!("refs/tags/{}", refspec)))
-            .or_else(|_| repo.find_reference(&format!("refs/heads/{}", refspec)))
-            .with_context(|| format!("Could not find ref: {}", refspec))?;
-
-        let commit = reference.peel_to_commit()?;
+            .or_else(|_| repo.find_reference(&format!("refs/heads/{}", refspec)));
+
+        let commit = match reference {
+            Ok(reference) => reference.peel_to_commit()?,
+            Err(_) => repo
+                .revparse_single(refspec)
+                .and_then(|object| object.peel_to_commit())
+                .with_context(|| format!("Could not find ref: {}", refspec))?,
+        };
 
         // Checkout the commit
         repo.checkout_tree(commit.as_object(), None)?;
@@ -349,100 +503,30 @@ impl GitSkillLoader {
         Ok(SkillType::Unknown)
     }
 
-    fn extract_metadata(
-        &self,
-        repo_dir: &Path,
-        source: &GitSource,
-    ) -> Result<(String, Option<String>)> {
-        // Try to read skill.yaml
-        let skill_yaml_path = repo_dir.join("skill.yaml");
-        if skill_yaml_path.exists() {
-            let contents = std::fs::read_to_string(&skill_yaml_path)?;
-            if let Ok(yaml) = serde_yaml::from_str::<serde_yaml::Value>(&contents) {
-                let name = yaml["name"]
-                    .as_str()
-                    .unwrap_or(&source.repo)
-                    .to_string();
-                let version = yaml["version"].as_str().map(|s| s.to_string());
-                return Ok((name, version));
-            }
-        }
-
-        // Try SKILL.md frontmatter
-        let skill_md_path = repo_dir.join("SKILL.md");
-        if skill_md_path.exists() {
-            let contents = std::fs::read_to_string(&skill_md_path)?;
-            if let Some(frontmatter) = extract_yaml_frontmatter(&contents) {
-                if let Ok(yaml) = serde_yaml::from_str::<serde_yaml::Value>(frontmatter) {
-                    let name = yaml["name"]
-                        .as_str()
-                        .unwrap_or(&source.repo)
-                        .to_string();
-                    let version = yaml["version"].as_str().map(|s| s.to_string());
-                    return Ok((name, version));
-                }
-            }
-        }
-
-        // Try package.json
-        let package_json_path = repo_dir.join("package.json");
-        if package_json_path.exists() {
-            let contents = std::fs::read_to_string(&package_json_path)?;
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&contents) {
-                let name = json["name"]
-                    .as_str()
-                    .unwrap_or(&source.repo)
-                    .to_string();
-                let version = json["version"].as_str().map(|s| s.to_string());
-                return Ok((name, version));
-            }
-        }
-
-        // Try Cargo.toml
-        let cargo_toml_path = repo_dir.join("Cargo.toml");
-        if cargo_toml_path.exists() {
-            let contents = std::fs::read_to_string(&cargo_toml_path)?;
-            if let Ok(toml) = toml::from_str::<toml::Value>(&contents) {
-                if let Some(package) = toml.get("package") {
-                    let name = package["name"]
-                        .as_str()
-                        .unwrap_or(&source.repo)
-                        .to_string();
-                    let version = package["version"].as_str().map(|s| s.to_string());
-                    return Ok((name, version));
-                }
-            }
-        }
-
-        // Fall back to repo name
-        Ok((source.repo.clone(), None))
-    }
-
     fn update_cache(
         &self,
         source: &GitSource,
         repo_dir: &Path,
+        skill_dir: &Path,
         skill_name: &str,
+        commit: Option<&str>,
     ) -> Result<()> {
         let mut cache = self.load_cache();
 
-        // Get current commit
-        let commit = if let Ok(repo) = Repository::open(repo_dir) {
-            repo.head()
-                .ok()
-                .and_then(|h| h.peel_to_commit().ok())
-                .map(|c| c.id().to_string())
-                .unwrap_or_default()
-        } else {
-            String::new()
+        // Skills in a monorepo get one entry each
+        let key = match skill_dir.strip_prefix(repo_dir) {
+            Ok(rel) if !rel.as_os_str().is_empty() => {
+                format!("{}#{}", source.cache_key(), rel.to_string_lossy())
+            }
+            _ => source.cache_key(),
         };
 
         cache.entries.insert(
-            source.cache_key(),
+            key,
             SourceCacheEntry {
                 url: source.url.clone(),
                 git_ref: source.git_ref.to_string(),
-                commit,
+                commit: commit.unwrap_or_default().to_string(),
                 cloned_at: chrono::Utc::now(),
                 skill_name: skill_name.to_string(),
             },
@@ -637,6 +721,113 @@ impl Default for GitSkillLoader {
     }
 }
 
+/// Read a skill's name and version from skill.yaml, SKILL.md frontmatter,
+/// package.json or Cargo.toml, falling back to `default_name`
+pub(crate) fn extract_metadata(
+    repo_dir: &Path,
+    default_name: &str,
+) -> Result<(String, Option<String>)> {
+    // Try to read skill.yaml
+    let skill_yaml_path = repo_dir.join("skill.yaml");
+    if skill_yaml_path.exists() {
+        let contents = std::fs::read_to_string(&skill_yaml_path)?;
+        if let Ok(yaml) = serde_yaml::from_str::<serde_yaml::Value>(&contents) {
+            let name = yaml["name"].as_str().unwrap_or(default_name).to_string();
+            let version = yaml["version"].as_str().map(|s| s.to_string());
+            return Ok((name, version));
+        }
+    }
+
+    // Try SKILL.md frontmatter
+    let skill_md_path = repo_dir.join("SKILL.md");
+    if skill_md_path.exists() {
+        let contents = std::fs::read_to_string(&skill_md_path)?;
+        if let Some(frontmatter) = extract_yaml_frontmatter(&contents) {
+            if let Ok(yaml) = serde_yaml::from_str::<serde_yaml::Value>(frontmatter) {
+                let name = yaml["name"].as_str().unwrap_or(default_name).to_string();
+                let version = yaml["version"].as_str().map(|s| s.to_string());
+                return Ok((name, version));
+            }
+        }
+    }
+
+    // Try package.json
+    let package_json_path = repo_dir.join("package.json");
+    if package_json_path.exists() {
+        let contents = std::fs::read_to_string(&package_json_path)?;
+        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&contents) {
+            let name = json["name"].as_str().unwrap_or(default_name).to_string();
+            let version = json["version"].as_str().map(|s| s.to_string());
+            return Ok((name, version));
+        }
+    }
+
+    // Try Cargo.toml
+    let cargo_toml_path = repo_dir.join("Cargo.toml");
+    if cargo_toml_path.exists() {
+        let contents = std::fs::read_to_string(&cargo_toml_path)?;
+        if let Ok(toml) = toml::from_str::<toml::Value>(&contents) {
+            if let Some(package) = toml.get("package") {
+                let name = package["name"].as_str().unwrap_or(default_name).to_string();
+                let version = package["version"].as_str().map(|s| s.to_string());
+                return Ok((name, version));
+            }
+        }
+    }
+
+    // Fall back to repo (or directory) name
+    Ok((default_name.to_string(), None))
+}
+
+/// Initialize and update submodules recursively
+fn update_submodules(repo: &Repository, auth: &GitAuth) -> Result<()> {
+    for mut submodule in repo.submodules()? {
+        let name = submodule.name().unwrap_or_default().to_string();
+        debug!(submodule = %name, "Updating submodule");
+
+        let mut options = git2::SubmoduleUpdateOptions::new();
+        options.fetch(auth.fetch_options());
+        submodule
+            .update(true, Some(&mut options))
+            .with_context(|| format!("Failed to update submodule: {}", name))?;
+
+        if let Ok(nested) = submodule.open() {
+            update_submodules(&nested, auth)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether a directory declares a skill (SKILL.md or a pre-built skill.wasm)
+fn has_skill_marker(dir: &Path) -> bool {
+    dir.join("SKILL.md").exists() || dir.join("skill.wasm").exists()
+}
+
+/// Find skill directories below `dir` (not descending into skills found)
+fn discover_skill_dirs(dir: &Path, depth: usize, found: &mut Vec<PathBuf>) -> Result<()> {
+    const MAX_DEPTH: usize = 3;
+    const SKIP: &[&str] = &["node_modules", "target", "dist", "build"];
+
+    if depth >= MAX_DEPTH {
+        return Ok(());
+    }
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if !path.is_dir() || name.starts_with('.') || SKIP.contains(&name) {
+            continue;
+        }
+
+        if has_skill_marker(&path) {
+            found.push(path);
+        } else {
+            discover_skill_dirs(&path, depth + 1, found)?;
+        }
+    }
+    Ok(())
+}
+
 fn extract_yaml_frontmatter(content: &str) -> Option<&str> {
     if !content.starts_with("---") {
         return None;
@@ -667,6 +858,34 @@ mod tests {
         assert!(fm.unwrap().contains("name: test"));
     }
 
+    #[test]
+    fn test_discover_skill_dirs() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        for skill in ["skills/aws", "skills/gcp", "tools/nested/helm"] {
+            std::fs::create_dir_all(root.join(skill)).unwrap();
+            std::fs::write(root.join(skill).join("SKILL.md"), "# Skill").unwrap();
+        }
+        // Skills inside a skill and in ignored directories are not separate skills
+        std::fs::create_dir_all(root.join("skills/aws/examples")).unwrap();
+        std::fs::write(root.join("skills/aws/examples/SKILL.md"), "# Example").unwrap();
+        std::fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        std::fs::write(root.join("node_modules/pkg/skill.wasm"), b"").unwrap();
+
+        assert!(!has_skill_marker(root));
+        let mut found = Vec::new();
+        discover_skill_dirs(root, 0, &mut found).unwrap();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                root.join("skills/aws"),
+                root.join("skills/gcp"),
+                root.join("tools/nested/helm"),
+            ]
+        );
+    }
+
     #[test]
     fn test_no_frontmatter() {
         let content = "# Just markdown\n\nNo frontmatter here.";
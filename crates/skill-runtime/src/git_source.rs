@@ -2,9 +2,10 @@
 //
 // Supports various Git URL formats:
 // - HTTPS: https://github.com/user/repo
-// - Shorthand: github:user/repo, gitlab:user/repo
+// - Shorthand: github:user/repo, gitlab:group/subgroup/project, bitbucket:workspace/repo
 // - SSH: git@github.com:user/repo.git
 // - With ref: github:user/repo@v1.0.0, github:user/repo@main
+// - Monorepo subpath: github:org/repo//skills/aws, github:org/repo#skills/aws
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
@@ -20,6 +21,9 @@ pub struct GitSource {
     pub repo: String,
     /// Git reference (branch, tag, or commit)
     pub git_ref: GitRef,
+    /// Directory within the repository holding the skill(s)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subpath: Option<String>,
     /// Original input string for display
     pub original: String,
 }
@@ -39,6 +43,12 @@ pub enum GitRef {
 }
 
 impl GitRef {
+    /// Classify a ref string: `v1.2.3`-style tags, 40-character commit SHAs,
+    /// and branches otherwise
+    pub fn parse(ref_str: &str) -> Self {
+        parse_ref(ref_str)
+    }
+
     /// Get the refspec string for checkout
     pub fn as_refspec(&self) -> Option<&str> {
         match self {
@@ -72,11 +82,29 @@ impl GitSource {
         format!("{}/{}", self.owner, self.repo)
     }
 
+    /// Host name of the repository (e.g. `github.com`)
+    pub fn host(&self) -> &str {
+        self.url
+            .split("://")
+            .last()
+            .and_then(|rest| rest.split('/').next())
+            .unwrap_or_default()
+    }
+
+    /// SSH clone URL (`git@host:owner/repo.git`)
+    pub fn ssh_url(&self) -> String {
+        format!("git@{}:{}/{}.git", self.host(), self.owner, self.repo)
+    }
+
     /// Get display name
     pub fn display_name(&self) -> String {
-        match &self.git_ref {
+        let name = match &self.git_ref {
             GitRef::DefaultBranch => format!("{}/{}", self.owner, self.repo),
             ref_type => format!("{}/{}@{}", self.owner, self.repo, ref_type),
+        };
+        match &self.subpath {
+            Some(subpath) => format!("{}#{}", name, subpath),
+            None => name,
         }
     }
 }
@@ -89,11 +117,29 @@ impl GitSource {
 /// - `github:user/repo`
 /// - `github:user/repo@v1.0.0`
 /// - `git@github.com:user/repo.git`
-/// - `gitlab:user/repo`
-/// - `https://gitlab.com/user/repo`
+/// - `gitlab:group/subgroup/project@main`
+/// - `bitbucket:workspace/repo`
+/// - `https://gitlab.com/group/project`
+/// - `github:org/repo//skills/aws` or `github:org/repo#skills/aws` (skill
+///   directory in a monorepo)
+/// - Web URLs such as `https://github.com/org/repo/tree/v1.0.0/skills/aws`
 pub fn parse_git_url(input: &str) -> Result<GitSource> {
     let original = input.to_string();
 
+    // HTTPS URLs keep the fragment for refs and handle `//` themselves
+    if input.starts_with("https://") || input.starts_with("http://") {
+        return parse_https_url(input, original);
+    }
+
+    let (input, subpath) = split_subpath(input)?;
+    let mut source = parse_repo_url(&input, original)?;
+    if subpath.is_some() {
+        source.subpath = subpath;
+    }
+    Ok(source)
+}
+
+fn parse_repo_url(input: &str, original: String) -> Result<GitSource> {
     // Handle shorthand formats: github:user/repo[@ref]
     if let Some(rest) = input.strip_prefix("github:") {
         return parse_shorthand("github.com", rest, original);
@@ -110,33 +156,69 @@ pub fn parse_git_url(input: &str) -> Result<GitSource> {
         return parse_ssh_url(input, original);
     }
 
-    // Handle HTTPS URLs
-    if input.starts_with("https://") || input.starts_with("http://") {
-        return parse_https_url(input, original);
-    }
-
     anyhow::bail!(
         "Unsupported Git URL format: {}\n\
          Supported formats:\n\
          - github:user/repo\n\
          - github:user/repo@v1.0.0\n\
+         - gitlab:group/project@main\n\
+         - bitbucket:workspace/repo\n\
+         - github:org/repo//path/to/skill\n\
          - https://github.com/user/repo\n\
          - git@github.com:user/repo.git",
         input
     );
 }
 
+/// Split a monorepo subpath (`#path` or `//path`) off a shorthand or SSH source
+///
+/// A ref written after the subpath (`org/repo//path@v1`) stays with the
+/// repository part.
+fn split_subpath(input: &str) -> Result<(String, Option<String>)> {
+    let Some((base, path)) = input.split_once('#').or_else(|| input.split_once("//")) else {
+        return Ok((input.to_string(), None));
+    };
+
+    match path.rsplit_once('@') {
+        Some((path, git_ref)) => Ok((format!("{}@{}", base, git_ref), validate_subpath(path)?)),
+        None => Ok((base.to_string(), validate_subpath(path)?)),
+    }
+}
+
+fn validate_subpath(path: &str) -> Result<Option<String>> {
+    let path = path.trim_matches('/');
+    if path.split('/').any(|part| part == "..") {
+        anyhow::bail!(
+            "Invalid subpath '{}': must stay inside the repository",
+            path
+        );
+    }
+    Ok((!path.is_empty()).then(|| path.to_string()))
+}
+
+/// Split `owner/.../repo` into the owner (which may contain groups) and repo name
+fn split_project(segments: &[&str], input: &str) -> Result<(String, String)> {
+    match segments {
+        [owner @ .., repo] if !owner.is_empty() => {
+            Ok((owner.join("/"), repo.trim_end_matches(".git").to_string()))
+        }
+        _ => anyhow::bail!("Expected owner/repo in Git source: {}", input),
+    }
+}
+
+/// Whether the host nests projects in groups (GitLab); elsewhere paths are owner/repo
+fn allows_nested_groups(host: &str) -> bool {
+    host != "github.com" && host != "bitbucket.org"
+}
+
 fn parse_shorthand(host: &str, rest: &str, original: String) -> Result<GitSource> {
     // Split by @ for ref: user/repo@v1.0.0
-    let (path, git_ref) = if let Some(at_pos) = rest.rfind('@') {
-        let ref_str = &rest[at_pos + 1..];
-        let path = &rest[..at_pos];
-        (path, parse_ref(ref_str))
-    } else {
-        (rest, GitRef::DefaultBranch)
+    let (path, git_ref) = match rest.rsplit_once('@') {
+        Some((path, ref_str)) if !ref_str.is_empty() => (path, parse_ref(ref_str)),
+        _ => (rest, GitRef::DefaultBranch),
     };
 
-    let parts: Vec<&str> = path.split('/').collect();
+    let parts: Vec<&str> = path.split('/').filter(|p| !p.is_empty()).collect();
     if parts.len() < 2 {
         anyhow::bail!(
             "Invalid shorthand format '{}'. Expected: user/repo or user/repo@version",
@@ -144,20 +226,27 @@ fn parse_shorthand(host: &str, rest: &str, original: String) -> Result<GitSource
         );
     }
 
-    let owner = parts[0].to_string();
-    let repo = parts[1].trim_end_matches(".git").to_string();
+    // GitLab paths are group/subgroup/project; on GitHub and Bitbucket any
+    // segments after owner/repo are a subpath
+    let (project, extra) = if allows_nested_groups(host) {
+        (&parts[..], &[][..])
+    } else {
+        parts.split_at(2)
+    };
+    let (owner, repo) = split_project(project, rest)?;
 
     Ok(GitSource {
         url: format!("https://{}/{}/{}.git", host, owner, repo),
         owner,
         repo,
         git_ref,
+        subpath: validate_subpath(&extra.join("/"))?,
         original,
     })
 }
 
 fn parse_ssh_url(input: &str, original: String) -> Result<GitSource> {
-    // git@github.com:user/repo.git
+    // git@github.com:user/repo.git[@ref]
     let without_prefix = input
         .strip_prefix("git@")
         .context("Invalid SSH URL format")?;
@@ -169,16 +258,23 @@ fn parse_ssh_url(input: &str, original: String) -> Result<GitSource> {
     let host = &without_prefix[..colon_pos];
     let path = &without_prefix[colon_pos + 1..];
 
-    let parts: Vec<&str> = path.trim_end_matches(".git").split('/').collect();
+    let (path, git_ref) = match path.rsplit_once('@') {
+        Some((path, ref_str)) if !ref_str.is_empty() => (path, parse_ref(ref_str)),
+        _ => (path, GitRef::DefaultBranch),
+    };
+
+    let parts: Vec<&str> = path.split('/').filter(|p| !p.is_empty()).collect();
     if parts.len() < 2 {
         anyhow::bail!("Invalid SSH URL: expected user/repo format after host");
     }
+    let (owner, repo) = split_project(&parts, input)?;
 
     Ok(GitSource {
-        url: format!("https://{}/{}", host, path),
-        owner: parts[0].to_string(),
-        repo: parts[1].trim_end_matches(".git").to_string(),
-        git_ref: GitRef::DefaultBranch,
+        url: format!("https://{}/{}/{}.git", host, owner, repo),
+        owner,
+        repo,
+        git_ref,
+        subpath: None,
         original,
     })
 }
@@ -187,31 +283,51 @@ fn parse_https_url(input: &str, original: String) -> Result<GitSource> {
     let url = url::Url::parse(input).context("Invalid URL")?;
     let host = url.host_str().context("Missing host in URL")?;
 
-    let path_segments: Vec<&str> = url
-        .path_segments()
-        .context("Invalid URL path")?
-        .filter(|s| !s.is_empty())
-        .collect();
+    // `//` separates the repository from a subpath: https://host/org/repo.git//skills/aws
+    let path = url.path().trim_start_matches('/');
+    let (repo_path, mut subpath) = match path.split_once("//") {
+        Some((repo_path, sub)) => (repo_path, validate_subpath(sub)?),
+        None => (path, None),
+    };
 
-    if path_segments.len() < 2 {
+    let segments: Vec<&str> = repo_path.split('/').filter(|s| !s.is_empty()).collect();
+    if segments.len() < 2 {
         anyhow::bail!("URL must include owner/repo path: {}", input);
     }
 
-    let owner = path_segments[0].to_string();
-    let repo = path_segments[1].trim_end_matches(".git").to_string();
+    // Web URLs: GitLab `group/project/-/tree/<ref>/<path>`, GitHub
+    // `org/repo/tree/<ref>/<path>`, Bitbucket `workspace/repo/src/<ref>/<path>`
+    let (project, web) = if let Some(dash) = segments.iter().position(|s| *s == "-") {
+        (&segments[..dash], &segments[dash + 1..])
+    } else if allows_nested_groups(host) {
+        (&segments[..], &[][..])
+    } else {
+        segments.split_at(2)
+    };
+    let (owner, repo) = split_project(project, input)?;
 
     // Check for ref in URL fragment
-    let git_ref = if let Some(fragment) = url.fragment() {
+    let mut git_ref = if let Some(fragment) = url.fragment() {
         parse_ref(fragment)
     } else {
         GitRef::DefaultBranch
     };
 
+    if let [kind, web_ref, rest @ ..] = web {
+        if matches!(*kind, "tree" | "blob" | "src") {
+            git_ref = parse_ref(web_ref);
+            if subpath.is_none() {
+                subpath = validate_subpath(&rest.join("/"))?;
+            }
+        }
+    }
+
     Ok(GitSource {
         url: format!("https://{}/{}/{}.git", host, owner, repo),
         owner,
         repo,
         git_ref,
+        subpath,
         original,
     })
 }
@@ -280,6 +396,8 @@ mod tests {
         assert_eq!(source.owner, "user");
         assert_eq!(source.repo, "repo");
         assert_eq!(source.url, "https://github.com/user/repo.git");
+        assert_eq!(source.host(), "github.com");
+        assert_eq!(source.ssh_url(), "git@github.com:user/repo.git");
     }
 
     #[test]
@@ -326,6 +444,79 @@ mod tests {
         assert_eq!(source_with_tag.display_name(), "user/repo@v1.0.0");
     }
 
+    #[test]
+    fn test_monorepo_subpath() {
+        let source = parse_git_url("github:org/skills@v1.2.0#skills/aws/").unwrap();
+        assert_eq!(source.repo, "skills");
+        assert_eq!(source.url, "https://github.com/org/skills.git");
+        assert!(matches!(source.git_ref, GitRef::Tag(ref t) if t == "v1.2.0"));
+        assert_eq!(source.subpath.as_deref(), Some("skills/aws"));
+        assert_eq!(source.display_name(), "org/skills@v1.2.0#skills/aws");
+
+        let source = parse_git_url("git@github.com:org/skills.git#aws").unwrap();
+        assert_eq!(source.subpath.as_deref(), Some("aws"));
+
+        assert!(parse_git_url("github:org/skills#../etc").is_err());
+        assert!(parse_git_url("github:org/skills#")
+            .unwrap()
+            .subpath
+            .is_none());
+    }
+
+    #[test]
+    fn test_gitlab_nested_groups() {
+        let source = parse_git_url("gitlab:group/subgroup/project@main").unwrap();
+        assert_eq!(source.owner, "group/subgroup");
+        assert_eq!(source.repo, "project");
+        assert_eq!(source.url, "https://gitlab.com/group/subgroup/project.git");
+        assert_eq!(source.git_ref, GitRef::Branch("main".to_string()));
+
+        let source = parse_git_url("https://gitlab.com/group/sub/project.git").unwrap();
+        assert_eq!(source.owner, "group/sub");
+
+        let source =
+            parse_git_url("https://gitlab.com/group/project/-/tree/v2.0.0/skills/aws").unwrap();
+        assert_eq!(source.owner, "group");
+        assert_eq!(source.repo, "project");
+        assert_eq!(source.git_ref, GitRef::Tag("v2.0.0".to_string()));
+        assert_eq!(source.subpath.as_deref(), Some("skills/aws"));
+
+        let source = parse_git_url("git@gitlab.com:group/sub/project.git@v1.0.0").unwrap();
+        assert_eq!(source.owner, "group/sub");
+        assert_eq!(source.url, "https://gitlab.com/group/sub/project.git");
+        assert_eq!(source.git_ref, GitRef::Tag("v1.0.0".to_string()));
+    }
+
+    #[test]
+    fn test_bitbucket_and_double_slash_subpaths() {
+        let source = parse_git_url("bitbucket:workspace/repo").unwrap();
+        assert_eq!(source.url, "https://bitbucket.org/workspace/repo.git");
+
+        for input in [
+            "github:org/repo//skills/aws@v1.0.0",
+            "github:org/repo@v1.0.0//skills/aws",
+            "github:org/repo@v1.0.0#skills/aws",
+            "https://github.com/org/repo/tree/v1.0.0/skills/aws",
+            "https://github.com/org/repo.git//skills/aws#v1.0.0",
+        ] {
+            let source = parse_git_url(input).unwrap();
+            assert_eq!(source.owner, "org", "{}", input);
+            assert_eq!(source.repo, "repo", "{}", input);
+            assert_eq!(
+                source.git_ref,
+                GitRef::Tag("v1.0.0".to_string()),
+                "{}",
+                input
+            );
+            assert_eq!(source.subpath.as_deref(), Some("skills/aws"), "{}", input);
+        }
+
+        // Segments past owner/repo are a subpath on GitHub
+        let source = parse_git_url("github:org/repo/skills/gcp").unwrap();
+        assert_eq!(source.repo, "repo");
+        assert_eq!(source.subpath.as_deref(), Some("skills/gcp"));
+    }
+
     #[test]
     fn test_commit_sha() {
         let sha = "abc123def456789012345678901234567890abcd";
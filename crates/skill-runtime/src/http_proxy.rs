@@ -0,0 +1,429 @@
+//! Host-side HTTP proxy for WASM skills
+//!
+//! Every outbound `wasi:http` request from a skill is routed through the
+//! host rather than sent directly by the component. On its way out a request
+//! is:
+//!
+//! 1. checked against the instance's [`NetworkPolicy`], by hostname and by
+//!    the addresses it resolves to (denied requests fail with
+//!    `HttpRequestDenied` and are audited as `network_blocked`);
+//! 2. given the credentials the instance declares for that host, read from
+//!    the instance config (or keyring), so tokens never have to be baked into
+//!    the component or passed in its environment;
+//! 3. recorded in the audit log as a `network_request` entry once the
+//!    response headers arrive (method, URL without query, status, duration
+//!    and the names of injected headers — never their values).
+//!
+//! ```toml
+//! [skills.github.instances.default]
+//! config.token = "keyring:github"
+//! capabilities.allowed_hosts = ["api.github.com:443"]
+//!
+//! [[skills.github.instances.default.capabilities.http_credentials]]
+//! host = "api.github.com"
+//! config_key = "token"        # header defaults to Authorization: Bearer {value}
+//! ```
+
+use anyhow::{Context, Result};
+use hyper::header::{HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+use wasmtime_wasi_http::bindings::http::types::ErrorCode;
+use wasmtime_wasi_http::body::HyperOutgoingBody;
+use wasmtime_wasi_http::types::{
+    default_send_request_handler, HostFutureIncomingResponse, OutgoingRequestConfig,
+};
+use wasmtime_wasi_http::HttpResult;
+use zeroize::Zeroizing;
+
+use crate::audit::AuditLogger;
+use crate::instance::InstanceConfig;
+use crate::network::{HostRule, NetworkPolicy};
+
+/// Placeholder in [`HttpCredential::format`] replaced with the secret
+pub const CREDENTIAL_PLACEHOLDER: &str = "{value}";
+
+fn default_header() -> String {
+    "Authorization".to_string()
+}
+
+fn default_format() -> String {
+    format!("Bearer {}", CREDENTIAL_PLACEHOLDER)
+}
+
+/// A credential the host adds to a skill's outbound requests
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpCredential {
+    /// Host rule the credential is sent to (`host[:port]`, `*` globs)
+    pub host: String,
+    /// Instance config key holding the secret
+    pub config_key: String,
+    /// Header to set (default: `Authorization`)
+    #[serde(default = "default_header")]
+    pub header: String,
+    /// Header value, with `{value}` replaced by the secret (default: `Bearer {value}`)
+    #[serde(default = "default_format")]
+    pub format: String,
+}
+
+impl HttpCredential {
+    /// Credential sent as `Authorization: Bearer <config_key>` to `host`
+    pub fn bearer(host: impl Into<String>, config_key: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            config_key: config_key.into(),
+            header: default_header(),
+            format: default_format(),
+        }
+    }
+}
+
+/// A credential with its secret loaded, ready to inject
+struct ResolvedCredential {
+    rule: HostRule,
+    header: HeaderName,
+    value: Zeroizing<String>,
+}
+
+/// Metadata of one proxied request, as written to the audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkRequestRecord {
+    /// HTTP method
+    pub method: String,
+    /// URL scheme (`http` or `https`)
+    pub scheme: String,
+    /// Destination host
+    pub host: String,
+    /// Destination port
+    pub port: u16,
+    /// Request path; the query string is left out as it may carry secrets
+    pub path: String,
+    /// Response status, if a response arrived
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Time until the response headers arrived (or the request failed)
+    pub duration_ms: u64,
+    /// Names of headers the host injected
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub injected_headers: Vec<String>,
+    /// Why the request failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Routes a sandbox's outbound HTTP requests through the host
+pub struct HttpProxy {
+    skill_name: String,
+    instance_id: String,
+    network: Arc<NetworkPolicy>,
+    credentials: Vec<ResolvedCredential>,
+    audit_logger: Option<Arc<AuditLogger>>,
+}
+
+impl HttpProxy {
+    /// Create a proxy enforcing `network` for one skill instance
+    pub fn new(
+        skill_name: impl Into<String>,
+        instance_id: impl Into<String>,
+        network: Arc<NetworkPolicy>,
+    ) -> Self {
+        Self {
+            skill_name: skill_name.into(),
+            instance_id: instance_id.into(),
+            network,
+            credentials: Vec::new(),
+            audit_logger: None,
+        }
+    }
+
+    /// Record requests (allowed and blocked) in the audit log
+    pub fn with_audit_logger(mut self, logger: Option<Arc<AuditLogger>>) -> Self {
+        self.audit_logger = logger;
+        self
+    }
+
+    /// Inject a credential with an already-known secret
+    pub fn with_credential(mut self, credential: &HttpCredential, secret: &str) -> Result<Self> {
+        let rule = HostRule::parse(&credential.host)
+            .with_context(|| format!("Invalid credential host rule '{}'", credential.host))?;
+        let header = HeaderName::from_bytes(credential.header.as_bytes())
+            .with_context(|| format!("Invalid credential header '{}'", credential.header))?;
+        let value = Zeroizing::new(credential.format.replace(CREDENTIAL_PLACEHOLDER, secret));
+        HeaderValue::from_str(&value).with_context(|| {
+            format!(
+                "Credential '{}' is not a valid value for header '{}'",
+                credential.config_key, credential.header
+            )
+        })?;
+        self.credentials.push(ResolvedCredential { rule, header, value });
+        Ok(self)
+    }
+
+    /// Inject the credentials an instance declares, reading their secrets
+    /// from its config (plain values or keyring references)
+    ///
+    /// A declared credential whose config key isn't set is an error, so a
+    /// misconfigured instance fails before the skill runs.
+    pub fn with_instance_credentials(mut self, config: &InstanceConfig) -> Result<Self> {
+        for credential in &config.capabilities.http_credentials {
+            let secret = match config.get_config(&credential.config_key) {
+                Some(value) => Zeroizing::new(value),
+                None => config
+                    .get_secret_config(&credential.config_key)?
+                    .with_context(|| {
+                        format!(
+                            "Credential for '{}' uses config key '{}', which is not set",
+                            credential.host, credential.config_key
+                        )
+                    })?,
+            };
+            self = self.with_credential(credential, &secret)?;
+        }
+        Ok(self)
+    }
+
+    /// Check, decorate, send and audit one request
+    pub fn send(
+        &self,
+        mut request: hyper::Request<HyperOutgoingBody>,
+        config: OutgoingRequestConfig,
+    ) -> HttpResult<HostFutureIncomingResponse> {
+        let host = request.uri().host().unwrap_or_default().to_string();
+        let port = request
+            .uri()
+            .port_u16()
+            .unwrap_or(if config.use_tls { 443 } else { 80 });
+
+        if host.is_empty() || !self.network.is_allowed(&host, port) {
+            record_blocked(
+                self.audit_logger.as_deref(),
+                &self.skill_name,
+                &self.instance_id,
+                &host,
+                port,
+                "wasi:http",
+            );
+            return Err(ErrorCode::HttpRequestDenied.into());
+        }
+
+        let injected_headers = self.inject(&mut request, &host, port, config.use_tls);
+        let mut record = NetworkRequestRecord {
+            method: request.method().to_string(),
+            scheme: if config.use_tls { "https" } else { "http" }.to_string(),
+            host,
+            port,
+            path: request.uri().path().to_string(),
+            status: None,
+            duration_ms: 0,
+            injected_headers,
+            error: None,
+        };
+
+        let network = self.network.clone();
+        let audit_logger = self.audit_logger.clone();
+        let skill_name = self.skill_name.clone();
+        let instance_id = self.instance_id.clone();
+        let handle = wasmtime_wasi::runtime::spawn(async move {
+            if network.resolves_to_blocked(&record.host, port).await {
+                record_blocked(
+                    audit_logger.as_deref(),
+                    &skill_name,
+                    &instance_id,
+                    &record.host,
+                    port,
+                    "wasi:http",
+                );
+                return Ok(Err(ErrorCode::HttpRequestDenied));
+            }
+
+            let start = Instant::now();
+            let result = default_send_request_handler(request, config).await;
+            record.duration_ms = start.elapsed().as_millis() as u64;
+            match &result {
+                Ok(response) => record.status = Some(response.resp.status().as_u16()),
+                Err(e) => record.error = Some(e.to_string()),
+            }
+            record_request(audit_logger.as_deref(), &skill_name, &instance_id, &record);
+            Ok(result)
+        });
+        Ok(HostFutureIncomingResponse::pending(handle))
+    }
+
+    /// Set the headers of every credential matching the destination,
+    /// replacing any value the guest set; returns the injected header names
+    ///
+    /// Credentials are only ever sent over TLS.
+    fn inject<B>(&self, request: &mut hyper::Request<B>, host: &str, port: u16, use_tls: bool) -> Vec<String> {
+        if !use_tls {
+            return Vec::new();
+        }
+        let mut injected = Vec::new();
+        for credential in &self.credentials {
+            if !credential.rule.matches(host, port) {
+                continue;
+            }
+            // Validated when the credential was added
+            let Ok(mut value) = HeaderValue::from_str(&credential.value) else {
+                continue;
+            };
+            value.set_sensitive(true);
+            request.headers_mut().insert(credential.header.clone(), value);
+            injected.push(credential.header.as_str().to_string());
+        }
+        injected
+    }
+}
+
+/// Log (and audit) an outbound connection rejected by the network policy
+pub(crate) fn record_blocked(
+    audit_logger: Option<&AuditLogger>,
+    skill_name: &str,
+    instance_id: &str,
+    host: &str,
+    port: u16,
+    interface: &str,
+) {
+    tracing::warn!(
+        skill = %skill_name,
+        instance = %instance_id,
+        host = %host,
+        port = port,
+        interface = interface,
+        "Blocked outbound network request (not in allowed_hosts)"
+    );
+
+    if let Some(logger) = audit_logger {
+        if let Err(e) = logger.log_network_blocked(skill_name, instance_id, host, port, interface) {
+            tracing::warn!(error = %e, "Failed to write network audit entry");
+        }
+    }
+}
+
+/// Log (and audit) a request that went out through the proxy
+fn record_request(
+    audit_logger: Option<&AuditLogger>,
+    skill_name: &str,
+    instance_id: &str,
+    record: &NetworkRequestRecord,
+) {
+    tracing::debug!(
+        skill = %skill_name,
+        instance = %instance_id,
+        method = %record.method,
+        host = %record.host,
+        path = %record.path,
+        status = ?record.status,
+        duration_ms = record.duration_ms,
+        "Proxied outbound HTTP request"
+    );
+
+    if let Some(logger) = audit_logger {
+        if let Err(e) = logger.log_network_request(skill_name, instance_id, record) {
+            tracing::warn!(error = %e, "Failed to write network audit entry");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proxy() -> HttpProxy {
+        HttpProxy::new("github", "default", Arc::new(NetworkPolicy::allow_all()))
+            .with_credential(&HttpCredential::bearer("api.github.com:443", "token"), "ghp_secret")
+            .unwrap()
+            .with_credential(
+                &HttpCredential {
+                    host: "*.example.com".to_string(),
+                    config_key: "api_key".to_string(),
+                    header: "X-Api-Key".to_string(),
+                    format: CREDENTIAL_PLACEHOLDER.to_string(),
+                },
+                "k-123",
+            )
+            .unwrap()
+    }
+
+    fn request(authorization: Option<&str>) -> hyper::Request<()> {
+        let mut builder = hyper::Request::get("https://api.github.com/user");
+        if let Some(value) = authorization {
+            builder = builder.header("authorization", value);
+        }
+        builder.body(()).unwrap()
+    }
+
+    #[test]
+    fn test_injects_matching_credentials() {
+        let proxy = proxy();
+
+        let mut github = request(Some("Bearer guest-token"));
+        let injected = proxy.inject(&mut github, "api.github.com", 443, true);
+        assert_eq!(injected, vec!["authorization".to_string()]);
+        assert_eq!(github.headers()["authorization"], "Bearer ghp_secret");
+        assert!(github.headers()["authorization"].is_sensitive());
+        assert!(!github.headers().contains_key("x-api-key"));
+
+        let mut example = request(None);
+        assert_eq!(proxy.inject(&mut example, "data.example.com", 8443, true), vec!["x-api-key".to_string()]);
+        assert_eq!(example.headers()["x-api-key"], "k-123");
+    }
+
+    #[test]
+    fn test_no_credentials_for_other_hosts_or_plain_http() {
+        let proxy = proxy();
+
+        let mut other = request(None);
+        assert!(proxy.inject(&mut other, "evil.com", 443, true).is_empty());
+        assert!(other.headers().is_empty());
+
+        // Wrong port, or not over TLS
+        assert!(proxy.inject(&mut request(None), "api.github.com", 8080, true).is_empty());
+        assert!(proxy.inject(&mut request(None), "api.github.com", 443, false).is_empty());
+    }
+
+    #[test]
+    fn test_instance_credentials_from_config() {
+        let mut config = InstanceConfig::default();
+        config
+            .capabilities
+            .http_credentials
+            .push(HttpCredential::bearer("api.github.com", "token"));
+
+        let missing = HttpProxy::new("github", "default", Arc::new(NetworkPolicy::allow_all()))
+            .with_instance_credentials(&config);
+        assert!(missing.err().unwrap().to_string().contains("'token'"));
+
+        config.set_config("token".to_string(), "ghp_secret".to_string(), false);
+        let proxy = HttpProxy::new("github", "default", Arc::new(NetworkPolicy::allow_all()))
+            .with_instance_credentials(&config)
+            .unwrap();
+        let mut github = request(None);
+        proxy.inject(&mut github, "api.github.com", 443, true);
+        assert_eq!(github.headers()["authorization"], "Bearer ghp_secret");
+
+        // Secrets that can't be header values are rejected up front
+        let invalid = HttpProxy::new("github", "default", Arc::new(NetworkPolicy::allow_all()))
+            .with_credential(&HttpCredential::bearer("api.github.com", "token"), "line\nbreak");
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_record_serialization_omits_empty_fields() {
+        let record = NetworkRequestRecord {
+            method: "GET".to_string(),
+            scheme: "https".to_string(),
+            host: "api.github.com".to_string(),
+            port: 443,
+            path: "/user".to_string(),
+            status: Some(200),
+            duration_ms: 42,
+            injected_headers: Vec::new(),
+            error: None,
+        };
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["status"], 200);
+        assert!(json.get("injected_headers").is_none());
+        assert!(json.get("error").is_none());
+    }
+}
@@ -4,7 +4,10 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use zeroize::Zeroizing;
 
+use skill_context::{Mount, NetworkConfig};
+
 use crate::credentials::{parse_keyring_reference, CredentialStore};
+use crate::http_proxy::HttpCredential;
 
 /// Configuration for a skill instance
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +40,9 @@ pub struct InstanceMetadata {
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// Timestamp when the instance was last updated
     pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// Skills this skill requires (recorded at install time)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
 }
 
 impl Default for InstanceMetadata {
@@ -48,6 +54,7 @@ impl Default for InstanceMetadata {
             instance_name: String::new(),
             created_at: now,
             updated_at: now,
+            dependencies: Vec::new(),
         }
     }
 }
@@ -69,10 +76,22 @@ pub struct Capabilities {
     #[serde(default)]
     pub allowed_paths: Vec<PathBuf>,
 
-    /// Network access permission
+    /// Network access permission (allows every host unless `network` is set)
     #[serde(default)]
     pub network_access: bool,
 
+    /// Outbound network policy for WASM skills (host globs and ports)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<NetworkConfig>,
+
+    /// Files and directories preopened for WASM skills
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mounts: Vec<Mount>,
+
+    /// Credentials the host injects into matching outbound HTTP requests
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub http_credentials: Vec<HttpCredential>,
+
     /// Maximum concurrent requests
     #[serde(default = "default_max_concurrent")]
     pub max_concurrent_requests: usize,
@@ -87,11 +106,33 @@ impl Default for Capabilities {
         Self {
             allowed_paths: Vec::new(),
             network_access: false,
+            network: None,
+            mounts: Vec::new(),
+            http_credentials: Vec::new(),
             max_concurrent_requests: default_max_concurrent(),
         }
     }
 }
 
+impl Capabilities {
+    /// Filesystem grants for the sandbox
+    ///
+    /// Declared mounts plus each `allowed_paths` entry as a read-write
+    /// directory at the same path inside the guest.
+    pub fn grants(&self) -> Vec<Mount> {
+        let allowed = self
+            .allowed_paths
+            .iter()
+            .filter(|path| !path.as_os_str().is_empty())
+            .enumerate()
+            .map(|(i, path)| {
+                let path = path.to_string_lossy().to_string();
+                Mount::directory(format!("allowed-path-{}", i), path.clone(), path)
+            });
+        self.mounts.iter().cloned().chain(allowed).collect()
+    }
+}
+
 impl InstanceConfig {
     /// Load instance configuration from TOML file
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
@@ -200,6 +241,7 @@ impl InstanceConfig {
 /// Manager for skill instances
 pub struct InstanceManager {
     instances_root: PathBuf,
+    credential_scope: Option<String>,
     credential_store: CredentialStore,
 }
 
@@ -214,10 +256,40 @@ impl InstanceManager {
 
         Ok(Self {
             instances_root,
+            credential_scope: None,
             credential_store: CredentialStore::new(),
         })
     }
 
+    /// Create an instance manager for another data directory
+    ///
+    /// Instances live in `<data_dir>/instances` and their keyring entries are
+    /// keyed by `scope`, so managers with different scopes never share
+    /// configuration or secrets.
+    pub fn in_data_dir(data_dir: impl AsRef<Path>, scope: &str) -> Result<Self> {
+        let instances_root = data_dir.as_ref().join("instances");
+        std::fs::create_dir_all(&instances_root)?;
+
+        Ok(Self {
+            instances_root,
+            credential_scope: Some(scope.to_string()),
+            credential_store: CredentialStore::new(),
+        })
+    }
+
+    /// Directory holding an instance's configuration
+    pub fn instance_dir(&self, skill_name: &str, instance_name: &str) -> PathBuf {
+        self.instances_root.join(skill_name).join(instance_name)
+    }
+
+    /// Skill segment of this manager's keyring entries
+    fn credential_skill(&self, skill_name: &str) -> String {
+        match &self.credential_scope {
+            Some(scope) => format!("{}:{}", scope, skill_name),
+            None => skill_name.to_string(),
+        }
+    }
+
     /// Create a new instance with configuration and secrets
     pub fn create_instance(
         &self,
@@ -227,18 +299,21 @@ impl InstanceManager {
         secrets: HashMap<String, String>,
     ) -> Result<()> {
         // Create instance directory
-        InstanceConfig::create_instance_dir(skill_name, instance_name)?;
+        let instance_dir = self.instance_dir(skill_name, instance_name);
+        std::fs::create_dir_all(&instance_dir)
+            .with_context(|| format!("Failed to create instance directory: {}", instance_dir.display()))?;
 
         // Store secrets in keyring and update config with references
+        let credential_skill = self.credential_skill(skill_name);
         let mut updated_config = config;
         for (key, value) in secrets {
             // Store in keyring
             self.credential_store
-                .store_credential(skill_name, instance_name, &key, &value)?;
+                .store_credential(&credential_skill, instance_name, &key, &value)?;
 
             // Add keyring reference to config
             let keyring_ref =
-                format!("keyring://skill-engine/{}/{}/{}", skill_name, instance_name, key);
+                format!("keyring://skill-engine/{}/{}/{}", credential_skill, instance_name, key);
             updated_config.config.insert(
                 key,
                 ConfigValue {
@@ -284,14 +359,12 @@ impl InstanceManager {
 
     /// Load instance configuration
     pub fn load_instance(&self, skill_name: &str, instance_name: &str) -> Result<InstanceConfig> {
-        let config_path = InstanceConfig::config_path(skill_name, instance_name)?;
-        InstanceConfig::load(config_path)
+        InstanceConfig::load(self.instance_dir(skill_name, instance_name).join("config.toml"))
     }
 
     /// Save instance configuration
     pub fn save_instance(&self, skill_name: &str, instance_name: &str, config: &InstanceConfig) -> Result<()> {
-        let config_path = InstanceConfig::config_path(skill_name, instance_name)?;
-        config.save(config_path)
+        config.save(self.instance_dir(skill_name, instance_name).join("config.toml"))
     }
 
     /// Delete an instance and all associated credentials
@@ -302,17 +375,17 @@ impl InstanceManager {
             for value in config.config.values() {
                 if value.secret {
                     // Parse keyring reference and delete
-                    if let Ok((_, _, secret_key)) = parse_keyring_reference(&value.value) {
+                    if let Ok((skill, instance, secret_key)) = parse_keyring_reference(&value.value) {
                         let _ = self
                             .credential_store
-                            .delete_credential(skill_name, instance_name, &secret_key);
+                            .delete_credential(&skill, &instance, &secret_key);
                     }
                 }
             }
         }
 
         // Delete instance directory
-        let instance_dir = InstanceConfig::instance_dir(skill_name, instance_name)?;
+        let instance_dir = self.instance_dir(skill_name, instance_name);
         if instance_dir.exists() {
             std::fs::remove_dir_all(&instance_dir)
                 .with_context(|| format!("Failed to delete instance directory: {}", instance_dir.display()))?;
@@ -327,6 +400,59 @@ impl InstanceManager {
         Ok(())
     }
 
+    /// Copy an instance's configuration to a new instance
+    ///
+    /// Secrets are copied into new keyring entries owned by `to`, so the two
+    /// instances can be rotated independently. Fails if `to` already exists
+    /// unless `overwrite` is set.
+    pub fn clone_instance(
+        &self,
+        skill_name: &str,
+        from: &str,
+        to: &str,
+        overwrite: bool,
+    ) -> Result<InstanceConfig> {
+        if from == to {
+            anyhow::bail!("Source and target instance are the same: {}", from);
+        }
+        let source = self
+            .load_instance(skill_name, from)
+            .with_context(|| format!("Instance '{}' not found", from))?;
+        let exists = self
+            .list_instances(skill_name)?
+            .iter()
+            .any(|name| name == to);
+        if exists && !overwrite {
+            anyhow::bail!(
+                "Instance '{}' already exists (use --force to overwrite)",
+                to
+            );
+        }
+
+        let mut config = source.clone();
+        let now = chrono::Utc::now();
+        config.metadata.instance_name = to.to_string();
+        config.metadata.created_at = now;
+        config.metadata.updated_at = now;
+
+        let mut secrets = HashMap::new();
+        for (key, value) in &source.config {
+            if value.secret {
+                let secret = source
+                    .get_secret_config(key)?
+                    .with_context(|| format!("Secret '{}' missing from keyring", key))?;
+                config.config.remove(key);
+                secrets.insert(key.clone(), secret.to_string());
+            }
+        }
+
+        if exists {
+            self.delete_instance(skill_name, to)?;
+        }
+        self.create_instance(skill_name, to, config, secrets)?;
+        self.load_instance(skill_name, to)
+    }
+
     /// Update a secret value for an instance
     pub fn update_secret(
         &self,
@@ -336,7 +462,7 @@ impl InstanceManager {
         value: &str,
     ) -> Result<()> {
         self.credential_store
-            .store_credential(skill_name, instance_name, key, value)?;
+            .store_credential(&self.credential_skill(skill_name), instance_name, key, value)?;
 
         tracing::debug!(
             skill = %skill_name,
@@ -358,7 +484,24 @@ impl Default for InstanceManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
+    #[test]
+    fn test_capability_grants() {
+        let capabilities = Capabilities {
+            allowed_paths: vec![PathBuf::from("/data"), PathBuf::new()],
+            mounts: vec![Mount::directory("logs", "/var/log/app", "/logs").as_read_only()],
+            ..Default::default()
+        };
+
+        let grants = capabilities.grants();
+        assert_eq!(grants.len(), 2);
+        assert_eq!(grants[0].target, "/logs");
+        assert!(grants[0].read_only);
+        assert_eq!(grants[1].source, "/data");
+        assert_eq!(grants[1].target, "/data");
+        assert!(!grants[1].read_only);
+    }
+
 
     #[test]
     fn test_instance_config_serialization() {
@@ -374,6 +517,19 @@ mod tests {
         assert_eq!(deserialized.get_config("key1"), Some("value1".to_string()));
     }
 
+    #[test]
+    fn test_instance_manager_in_data_dir() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let manager = InstanceManager::in_data_dir(dir.path(), "team-a").unwrap();
+
+        assert_eq!(
+            manager.instance_dir("github", "default"),
+            dir.path().join("instances").join("github").join("default")
+        );
+        assert_eq!(manager.credential_skill("github"), "team-a:github");
+        assert!(manager.list_instances("github").unwrap().is_empty());
+    }
+
     #[test]
     fn test_config_value() {
         let mut config = InstanceConfig::default();
@@ -387,6 +387,11 @@ impl JobStorage for SqliteJobStorage {
             params.push(status.to_string());
         }
 
+        if let Some(job_type) = &filter.job_type {
+            query.push_str(" AND job_type LIKE ?");
+            params.push(format!("{{\"type\":\"{}\"%", job_type));
+        }
+
         if let Some(skill_id) = &filter.skill_id {
             query.push_str(" AND job_type LIKE ?");
             params.push(format!("%\"skill_id\":\"{}%", skill_id));
@@ -95,6 +95,21 @@ pub enum JobType {
         task: MaintenanceTask,
     },
 
+    /// Run of a multi-step workflow; per-step status is kept in the job result
+    Workflow {
+        /// Name of the workflow in the manifest
+        name: String,
+        /// Workflow inputs as a JSON object
+        inputs: serde_json::Value,
+    },
+
+    /// Installation of the skills a source provides; progress is kept in the
+    /// job result
+    SkillInstall {
+        /// Install source (git URL, archive URL, OCI reference or path)
+        source: String,
+    },
+
     /// Custom job type for extensibility
     Custom {
         name: String,
@@ -225,6 +240,21 @@ impl Job {
         })
     }
 
+    /// Create a workflow run job
+    pub fn workflow(name: impl Into<String>, inputs: serde_json::Value) -> Self {
+        Self::new(JobType::Workflow {
+            name: name.into(),
+            inputs,
+        })
+    }
+
+    /// Create a skill installation job
+    pub fn skill_install(source: impl Into<String>) -> Self {
+        Self::new(JobType::SkillInstall {
+            source: source.into(),
+        })
+    }
+
     /// Set job priority
     pub fn with_priority(mut self, priority: JobPriority) -> Self {
         self.priority = priority;
@@ -308,11 +308,15 @@ impl WorkerPool {
         // Signal all workers to stop
         let _ = self.shutdown_tx.send(());
 
-        // Small delay to allow workers to see the shutdown signal
-        tokio::time::sleep(Duration::from_millis(10)).await;
-
-        // Wait for timeout (workers should exit on their own by checking state)
-        tokio::time::sleep(timeout.min(Duration::from_millis(100))).await;
+        // Let workers finish their current job, aborting any still busy at the deadline
+        let workers: Vec<JoinHandle<()>> = self.workers.write().await.drain(..).collect();
+        let deadline = tokio::time::Instant::now() + timeout;
+        for mut worker in workers {
+            if tokio::time::timeout_at(deadline, &mut worker).await.is_err() {
+                warn!("Worker still busy at the shutdown deadline, aborting it");
+                worker.abort();
+            }
+        }
 
         // Mark as stopped
         {
@@ -0,0 +1,124 @@
+//! jq filters for skill output.
+//!
+//! Skill output is often JSON that callers only need a slice of. The MCP
+//! `jq` parameter, the HTTP execute endpoint and `skill run --jq` all run
+//! filters through this module, backed by [jaq](https://github.com/01mf02/jaq),
+//! so paths, pipes, `select()`, `map()` and the rest of the jq standard
+//! library behave the same everywhere.
+
+use anyhow::{anyhow, bail, Context, Result};
+use jaq_interpret::{Ctx, Filter, FilterT, ParseCtx, RcIter, Val};
+use serde_json::Value;
+
+/// Check that a filter parses and only uses defined functions.
+pub fn validate_jq(filter: &str) -> Result<()> {
+    compile(filter).map(|_| ())
+}
+
+/// Run a filter over a JSON value, returning every result.
+pub fn run_jq(input: Value, filter: &str) -> Result<Vec<Value>> {
+    let filter = compile(filter)?;
+    let inputs = RcIter::new(core::iter::empty());
+    filter
+        .run((Ctx::new([], &inputs), Val::from(input)))
+        .map(|result| result.map(Value::from).map_err(|e| anyhow!("{}", e)))
+        .collect()
+}
+
+/// Run a filter over JSON text and render the results for display.
+///
+/// Strings are printed raw and other values as pretty JSON, one result per
+/// line, like `jq -r`.
+pub fn apply_jq(output: &str, filter: &str) -> Result<String> {
+    let input: Value = serde_json::from_str(output).context("Output is not valid JSON")?;
+    let lines: Vec<String> = run_jq(input, filter)?
+        .into_iter()
+        .map(|value| match value {
+            Value::String(s) => s,
+            other => serde_json::to_string_pretty(&other).unwrap_or_default(),
+        })
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+fn compile(filter: &str) -> Result<Filter> {
+    let (parsed, errors) = jaq_parse::parse(filter, jaq_parse::main());
+    if let Some(error) = errors.first() {
+        bail!("Invalid jq filter '{}': {}", filter, error);
+    }
+    let parsed = parsed.ok_or_else(|| anyhow!("Invalid jq filter '{}'", filter))?;
+
+    let mut ctx = ParseCtx::new(Vec::new());
+    ctx.insert_natives(jaq_core::core());
+    ctx.insert_defs(jaq_std::std());
+    let compiled = ctx.compile(parsed);
+    if let Some((_, span)) = ctx.errs.first() {
+        bail!(
+            "Invalid jq filter '{}': undefined '{}'",
+            filter,
+            filter.get(span.clone()).unwrap_or_default()
+        );
+    }
+    Ok(compiled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pods() -> Value {
+        json!({
+            "items": [
+                {"metadata": {"name": "web"}, "status": {"phase": "Running"}},
+                {"metadata": {"name": "job"}, "status": {"phase": "Succeeded"}},
+                {"metadata": {"name": "api"}, "status": {"phase": "Running"}}
+            ]
+        })
+    }
+
+    #[test]
+    fn test_run_jq() {
+        assert_eq!(
+            run_jq(pods(), ".items[].metadata.name").unwrap(),
+            vec![json!("web"), json!("job"), json!("api")]
+        );
+        assert_eq!(
+            run_jq(
+                pods(),
+                r#".items[] | select(.status.phase == "Running") | .metadata.name"#
+            )
+            .unwrap(),
+            vec![json!("web"), json!("api")]
+        );
+        assert_eq!(
+            run_jq(pods(), ".items | map(.metadata.name) | length").unwrap(),
+            vec![json!(3)]
+        );
+        assert_eq!(
+            run_jq(pods(), ".items[0:1] | map(.status.phase)").unwrap(),
+            vec![json!(["Running"])]
+        );
+    }
+
+    #[test]
+    fn test_apply_jq() {
+        let output = pods().to_string();
+        assert_eq!(
+            apply_jq(&output, ".items[].metadata.name").unwrap(),
+            "web\njob\napi"
+        );
+        assert_eq!(
+            apply_jq(&output, "[.items[].metadata.name]").unwrap(),
+            "[\n  \"web\",\n  \"job\",\n  \"api\"\n]"
+        );
+        assert!(apply_jq("not json", ".").is_err());
+    }
+
+    #[test]
+    fn test_validate_jq() {
+        assert!(validate_jq(".items[] | select(.x > 1)").is_ok());
+        assert!(validate_jq(".items[").is_err());
+        assert!(validate_jq("undefined_fn(1)").is_err());
+    }
+}
@@ -64,8 +64,10 @@
 //!
 //! Skills execute with capability-based security:
 //!
-//! - **WASI Sandbox**: Network and filesystem access must be explicitly granted
-//! - **Command Allowlist**: Native skills declare allowed commands in `allowed-tools`
+//! - **WASI Sandbox**: Network and filesystem access must be explicitly granted;
+//!   outbound requests are checked against a deny-by-default host allow-list
+//! - **Command Allowlist**: Native commands must match `[security] allowed-commands`,
+//!   narrowed per skill by `allowed-tools`
 //! - **Docker Isolation**: Containerized skills run in separate namespaces
 //! - **Audit Trail**: All executions are logged with timestamps and arguments
 //!
@@ -87,14 +89,34 @@
 
 #![warn(missing_docs)]
 
+/// Execution and history settings shared by the CLI and the server.
+pub mod app_config;
+/// HTTP(S) archive loader for installing pre-built skills from .tar.gz/.zip files.
+pub mod archive_loader;
+/// Checking tool arguments against declared parameters.
+pub mod arg_validation;
+/// Files produced by tool executions and their retention.
+pub mod artifacts;
 /// Audit logging and security event tracking for skill executions.
 pub mod audit;
+/// Hex encoding and SHA-256 digests.
+pub mod checksum;
+/// Configurable allowlist of host binaries for native skills.
+pub mod command_allowlist;
 /// Configuration mapping utilities for skill instances and environments.
 pub mod config_mapper;
+/// Metadata index of credentials stored by `skill auth login`.
+pub mod credential_index;
 /// Credential management and secure storage integration.
 pub mod credentials;
+/// Dependency declarations and resolution between skills.
+pub mod dependencies;
 /// Docker container runtime for executing skills in isolated environments.
 pub mod docker_runtime;
+/// Sidecar containers started next to Docker skills on a shared network.
+pub mod docker_sidecars;
+/// CPU and memory metering for Docker containers from their cgroups.
+pub mod docker_usage;
 /// Core skill execution engine and orchestration logic.
 pub mod engine;
 /// Error types and result handling for the runtime.
@@ -103,26 +125,66 @@ pub mod errors;
 pub mod executor;
 /// AI-powered example generation and validation for skill documentation.
 pub mod generation;
+/// Authentication, shallow clone and submodule options for Git sources.
+pub mod git_auth;
 /// Git repository loader for installing skills from remote sources.
 pub mod git_loader;
 /// Git URL parsing and repository source handling.
 pub mod git_source;
+/// Host-side proxy for WASM skills' outbound HTTP requests.
+pub mod http_proxy;
 /// Multi-instance management for skills with different configurations.
 pub mod instance;
+/// jq filters for extracting data from skill output.
+pub mod jq;
+/// Output and file-write limits enforced on tool executions.
+pub mod limits;
 /// Local filesystem loader for installing skills from directories.
 pub mod local_loader;
+/// Resolved revisions of a manifest's skills (`.skill-engine.lock`).
+pub mod lockfile;
 /// Skill manifest parsing and configuration (`.skill-engine.toml`).
 pub mod manifest;
+/// Shell-free argv construction and parsing for native commands.
+pub mod native_command;
+/// Seccomp, AppArmor and no-new-privileges hardening for native commands.
+pub mod native_security;
 /// Execution metrics collection and performance tracking.
 pub mod metrics;
+/// Outbound network allow-list enforced for WASM skills.
+pub mod network;
+/// OCI registry loader for installing skills distributed as OCI artifacts.
+pub mod oci_loader;
+/// Output shaping (grep, head/tail, jq, format, truncation) for executions.
+pub mod output;
+/// Diffing a tool's output between runs, structurally for JSON.
+pub mod output_diff;
+/// Paging through full execution output kept server-side.
+pub mod output_store;
+/// Distributable `.skillpkg` bundles with an integrity manifest.
+pub mod package;
+/// Host requirements of skills (binaries, environment variables, ports).
+pub mod requirements;
 /// WASM sandbox configuration and capability-based security.
 pub mod sandbox;
+/// Managed host services skills depend on (start, health checks, restarts).
+pub mod services;
+/// Ed25519 signing of skill artifacts and the trusted publisher key store.
+pub mod signing;
 /// SKILL.md parser for native command-based skill definitions.
 pub mod skill_md;
+/// Standard input for native commands, containers and WASM skills.
+pub mod stdin;
 /// Core type definitions shared across the runtime.
 pub mod types;
+/// Linting of SKILL.md files, skill tool exports and manifests.
+pub mod validation;
 /// Vector database abstraction for semantic search.
 pub mod vector_store;
+/// Registry and manifest watcher for hot reloading skills.
+pub mod watcher;
+/// Multi-step workflows chaining skill tools.
+pub mod workflow;
 /// Embedding provider implementations (FastEmbed, OpenAI, Ollama).
 pub mod embeddings;
 /// Hybrid search, reranking, and context compression pipeline.
@@ -133,27 +195,76 @@ pub mod search_config;
 #[cfg(feature = "job-queue")]
 pub mod jobs;
 
+pub use app_config::{AppSettings, ConfigStore, APP_CONFIG_FILE};
+pub use archive_loader::{
+    is_archive_url, parse_archive_url, ArchiveFormat, ArchiveSkill, ArchiveSkillLoader, ArchiveSource,
+};
+pub use arg_validation::{
+    documented_params, validate_args, ArgIssue, ArgIssueKind, ArgValidationError, ParamSpec,
+};
+pub use artifacts::{ArtifactInfo, ArtifactRetention, ArtifactStore, GUEST_OUTPUT_DIR, OUTPUT_DIR_ENV};
 pub use audit::{AuditEntry, AuditEventType, AuditLogger};
+pub use command_allowlist::{CommandAllowlist, SecurityConfig, DEFAULT_ALLOWED_COMMANDS};
 pub use config_mapper::ConfigMapper;
+pub use credential_index::{
+    CredentialIndex, CredentialState, CredentialStatus, CredentialSummary,
+};
 pub use credentials::{parse_keyring_reference, CredentialStore, SecureString};
+pub use dependencies::{resolve_dependencies, SkillDependency};
 pub use engine::SkillEngine;
 pub use errors::{RuntimeError, Result};
 pub use executor::{ComponentCache, SkillExecutor};
+pub use git_auth::{GitSourceConfig, TransferProgress};
 pub use git_loader::{ClonedSkill, GitSkillLoader, SkillType};
 pub use git_source::{is_git_url, parse_git_url, GitRef, GitSource};
+pub use http_proxy::{HttpCredential, HttpProxy, NetworkRequestRecord};
 pub use instance::{InstanceConfig, InstanceManager};
+pub use jq::{apply_jq, run_jq, validate_jq};
 pub use local_loader::LocalSkillLoader;
 pub use docker_runtime::{DockerOutput, DockerRuntime, DockerSecurityPolicy};
+pub use docker_sidecars::SidecarGroup;
+pub use lockfile::{InstallSource, LockedSkill, Lockfile, LOCKFILE_NAME};
 pub use manifest::{
-    DockerRuntimeConfig, ServiceRequirement, SkillManifest, SkillRuntime, ResolvedInstance, SkillInfo, expand_env_vars
+    DockerRuntimeConfig, DockerSidecar, HistoryRetention, McpConfig, McpDiscovery, McpRoots, McpToolMode, ServiceRequirement, SkillManifest, SkillRuntime, ResolvedInstance, SkillInfo, expand_env_vars,
+    expand_env_vars_lenient, global_manifest_path, UnresolvedVar,
 };
 pub use metrics::ExecutionMetrics;
-pub use sandbox::{HostState, SandboxBuilder};
+pub use native_command::{build_native_command, check_positional, find_program, NativeCommand};
+pub use native_security::{NativeSecurityPolicy, SeccompMode, DEFAULT_DENIED_SYSCALLS};
+pub use network::{HostRule, NetworkPolicy};
+pub use oci_loader::{
+    is_oci_reference, parse_oci_reference, OciReference, OciSkill, OciSkillLoader,
+};
+pub use output::{
+    smart_truncate, truncate_content, OutputOptions, ProcessedOutput, OUTPUT_FORMATS,
+    TRUNCATE_STRATEGIES,
+};
+pub use output_diff::{JsonChange, LineChange, LineOp, OutputDiff};
+pub use output_store::{OutputPage, OutputRange, OutputStore, DEFAULT_PAGE_SIZE};
+pub use package::{build_package, BuiltPackage, PackageIntegrity};
+pub use requirements::{Requirement, RequirementProbe, RequirementStatus, SkillRequirements};
+pub use sandbox::{GrantedMount, HostState, SandboxBuilder};
+pub use services::{RestartPolicy, ServiceInfo, ServiceManager, ServiceSpec};
+pub use signing::{
+    verify_skill_dir, PublicKey, SignatureStatus, SigningKey, SkillSignature, TrustStore,
+    TrustedKey, SIGNATURE_FILE,
+};
 pub use skill_md::{
-    parse_skill_md, parse_skill_md_content, find_skill_md,
-    SkillMdContent, SkillMdFrontmatter, ToolDocumentation, CodeExample, ParameterDoc
+    parse_skill_md, parse_skill_md_content, parse_skill_md_in, parse_skill_md_localized,
+    find_skill_md, find_skill_md_variant, preferred_locale, LOCALE_ENV,
+    SkillMdContent, SkillMdFrontmatter, ToolDocumentation, ToolHints, CodeExample, ParameterDoc
 };
+pub use stdin::{OutputStream, StdinSource};
 pub use types::*;
+pub use validation::{Severity, ValidationIssue, ValidationReport};
+pub use watcher::{SkillChanged, SkillChanges, SkillWatcher};
+pub use workflow::{
+    discover_workflows, discover_workflows_in, resolve_workflow, workflow_dirs, InputType,
+    RunObserver, RunStatus, StepExecutor, StepRun, Workflow, WorkflowInput, WorkflowRun,
+    WorkflowStep,
+};
+#[cfg(feature = "job-queue")]
+pub use workflow::JobRecorder;
 pub use vector_store::{
     VectorStore, InMemoryVectorStore,
     EmbeddedDocument, DocumentMetadata, Filter, SearchResult,
@@ -169,6 +280,7 @@ pub use embeddings::{
     OpenAIEmbedProvider, OpenAIEmbeddingModel,
     OllamaProvider,
     EmbeddingProviderFactory, create_provider,
+    ModelStore, ModelSpec, ModelKind, InstalledModel,
 };
 
 pub use search::{FusionMethod, reciprocal_rank_fusion, weighted_sum_fusion};
@@ -182,17 +294,23 @@ pub use search::{Reranker, RerankResult, RerankDocument, FastEmbedReranker, Rera
 #[cfg(feature = "context-compression")]
 pub use search::{
     ContextCompressor, CompressionStrategy, CompressionConfig,
-    CompressedToolContext, ToolParameter, CompressionResult,
+    CompressedToolContext, ToolParameter, CompressionResult, ToolDocument, ToolParameterInput,
 };
 
 pub use search::{
     QueryProcessor, QueryIntent, ExtractedEntity, EntityType,
-    ProcessedQuery, QueryExpansion,
+    ProcessedQuery, QueryExpansion, SuggestedFilter,
 };
 
+pub use search::{SearchSession, SessionStore, SessionQuery, SelectedTool};
+
+pub use search::{ScoreExplanation, matched_terms, rrf_contribution};
+
+pub use search::{EvalCase, EvalSuite, EvalMetrics, EvalReport, QueryOutcome};
+
 pub use search::{
-    IndexManager, IndexMetadata, SkillChecksum,
-    IndexStats, SyncResult,
+    IndexManager, IndexMetadata, IndexSnapshot, SkillChecksum,
+    IndexStats, SyncResult, ChunkConfig, chunk_documents, chunk_text,
 };
 
 pub use search::{
@@ -208,7 +326,7 @@ pub use search_config::{
     IndexConfig as SearchIndexConfig,
     FusionMethod as SearchFusionMethod,
     CompressionStrategy as SearchCompressionStrategy,
-    AiIngestionConfig, AiProvider,
+    AiIngestionConfig, AiProvider, AiBudgetConfig,
     OllamaLlmConfig, OpenAiLlmConfig, AnthropicLlmConfig,
 };
 
@@ -217,10 +335,17 @@ pub use generation::{
     SearchResultRef, GenerationStreamBuilder,
     LlmProvider, LlmResponse, LlmChunk, TokenUsage,
     ChatMessage, CompletionRequest, create_llm_provider,
+    AgentKind, AgentRunner, ClaudeCodeProvider, GeminiProvider, DEFAULT_AGENT_TIMEOUT,
     ExampleValidator, ValidationResult, ParsedCommand,
     ExampleGenerator, GeneratorConfig,
+    DocDrafter, AI_GENERATED_TAG,
+    ExampleCache, CachedExamples, GenerationRecorder,
+    BudgetGuard, BudgetedProvider, BudgetExceeded,
 };
 
+#[cfg(feature = "sqlite-storage")]
+pub use generation::{EnhanceProgressDb, EnhanceRun, EnhanceStatus, SkillProgress, ToolProgress};
+
 #[cfg(feature = "ollama")]
 pub use generation::OllamaProvider;
 
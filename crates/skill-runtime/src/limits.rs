@@ -0,0 +1,255 @@
+//! Output and file-write limits for tool executions
+//!
+//! [`ExecutionLimits`], from an execution context's `resources.execution` or
+//! a manifest skill's `[skills.<name>.limits]`, can cap how much a tool may
+//! produce, protecting the host from runaway tools:
+//!
+//! ```toml
+//! [skills.kubernetes.limits]
+//! max_output_size = "1mb"     # stdout or stderr past this stops the tool
+//! max_files_written = 100     # files created or modified in writable mounts
+//! max_write_size = "50mb"     # bytes added to writable mounts
+//! ```
+//!
+//! Native and Docker commands are killed as soon as stdout or stderr goes
+//! over `max_output_size` (see [`crate::StdinSource::output`]); a WASM tool's
+//! result is truncated with a notice.
+//!
+//! Writes are measured against a snapshot of a WASM tool's writable mounts
+//! taken before it starts, and checked every [`WRITE_CHECK_INTERVAL`] while
+//! it runs. A tool that goes over a write limit is stopped at its next host
+//! call and fails; files it already wrote are left in place for inspection.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use skill_context::ExecutionLimits;
+
+/// How often writable mounts are checked while a tool runs
+pub const WRITE_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A limit the execution went over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitExceeded {
+    /// More output than `max_output_size`
+    Output {
+        /// Configured limit in bytes
+        limit: u64,
+    },
+    /// More files written than `max_files_written`
+    FilesWritten {
+        /// Files created or modified so far
+        written: u64,
+        /// Configured limit
+        limit: u64,
+    },
+    /// More bytes written than `max_write_size`
+    BytesWritten {
+        /// Bytes added so far
+        written: u64,
+        /// Configured limit in bytes
+        limit: u64,
+    },
+}
+
+impl std::fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Output { limit } => write!(f, "output exceeded max_output_size of {} bytes", limit),
+            Self::FilesWritten { written, limit } => write!(
+                f,
+                "wrote {} files to mounted directories, more than max_files_written of {}",
+                written, limit
+            ),
+            Self::BytesWritten { written, limit } => write!(
+                f,
+                "wrote {} bytes to mounted directories, more than max_write_size of {} bytes",
+                written, limit
+            ),
+        }
+    }
+}
+
+/// Files and bytes written since a [`WriteTracker`] snapshot
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WriteUsage {
+    /// Files created or modified
+    pub files: u64,
+    /// Bytes added: the full size of new files plus growth of modified ones
+    pub bytes: u64,
+}
+
+/// Size and modification time of a file in the snapshot
+type FileState = (u64, Option<SystemTime>);
+
+/// Measures what a tool writes to its writable mounts
+///
+/// Mounts are walked on tokio's blocking thread pool, so large mounts don't
+/// stall the runtime the tool runs on.
+#[derive(Clone)]
+pub struct WriteTracker {
+    dirs: Arc<Vec<PathBuf>>,
+    baseline: Arc<HashMap<PathBuf, FileState>>,
+    max_files: Option<u64>,
+    max_bytes: Option<u64>,
+}
+
+impl WriteTracker {
+    /// Snapshot `dirs` before the tool runs, or `None` if `limits` don't
+    /// restrict writes
+    pub async fn snapshot(dirs: Vec<PathBuf>, limits: &ExecutionLimits) -> Option<Self> {
+        if !limits.limits_writes() {
+            return None;
+        }
+        let (dirs, baseline) = tokio::task::spawn_blocking(move || {
+            let mut baseline = HashMap::new();
+            for dir in &dirs {
+                scan(dir, &mut baseline);
+            }
+            (dirs, baseline)
+        })
+        .await
+        .ok()?;
+        Some(Self {
+            dirs: Arc::new(dirs),
+            baseline: Arc::new(baseline),
+            max_files: limits.max_files_written,
+            max_bytes: limits.max_write_bytes(),
+        })
+    }
+
+    /// What has been written since the snapshot
+    ///
+    /// Walks the mounts on the calling thread; async code should use
+    /// [`WriteTracker::check`].
+    pub fn usage(&self) -> WriteUsage {
+        let mut current = HashMap::new();
+        for dir in self.dirs.iter() {
+            scan(dir, &mut current);
+        }
+        let mut usage = WriteUsage::default();
+        for (path, (size, modified)) in &current {
+            match self.baseline.get(path) {
+                None => {
+                    usage.files += 1;
+                    usage.bytes += size;
+                }
+                Some((old_size, old_modified)) if old_size != size || old_modified != modified => {
+                    usage.files += 1;
+                    usage.bytes += size.saturating_sub(*old_size);
+                }
+                Some(_) => {}
+            }
+        }
+        usage
+    }
+
+    /// Fail if the writes so far go over a limit
+    pub async fn check(&self) -> Result<WriteUsage, LimitExceeded> {
+        let tracker = self.clone();
+        match tokio::task::spawn_blocking(move || tracker.check_usage()).await {
+            Ok(checked) => checked,
+            // The scan panicked; count nothing rather than stop the tool
+            Err(_) => Ok(WriteUsage::default()),
+        }
+    }
+
+    fn check_usage(&self) -> Result<WriteUsage, LimitExceeded> {
+        let usage = self.usage();
+        if let Some(limit) = self.max_files.filter(|limit| usage.files > *limit) {
+            return Err(LimitExceeded::FilesWritten { written: usage.files, limit });
+        }
+        if let Some(limit) = self.max_bytes.filter(|limit| usage.bytes > *limit) {
+            return Err(LimitExceeded::BytesWritten { written: usage.bytes, limit });
+        }
+        Ok(usage)
+    }
+
+    /// Resolve once a write limit is exceeded; never resolves otherwise
+    pub async fn exceeded(&self) -> LimitExceeded {
+        loop {
+            tokio::time::sleep(WRITE_CHECK_INTERVAL).await;
+            if let Err(exceeded) = self.check().await {
+                return exceeded;
+            }
+        }
+    }
+}
+
+/// Record every regular file under `dir`; symlinks aren't followed
+fn scan(dir: &Path, files: &mut HashMap<PathBuf, FileState>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            scan(&entry.path(), files);
+        } else if metadata.is_file() {
+            files.insert(entry.path(), (metadata.len(), metadata.modified().ok()));
+        }
+    }
+}
+
+/// Cut `output` to at most `limit` bytes plus a notice saying so
+///
+/// Returns whether the output was cut.
+pub fn cap_output(output: &mut String, limit: u64) -> bool {
+    let limit = usize::try_from(limit).unwrap_or(usize::MAX);
+    if output.len() <= limit {
+        return false;
+    }
+    let mut end = limit;
+    while !output.is_char_boundary(end) {
+        end -= 1;
+    }
+    output.truncate(end);
+    output.push_str(&format!(
+        "\n[output truncated: {}]",
+        LimitExceeded::Output { limit: limit as u64 }
+    ));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cap_output() {
+        let mut output = "héllo world".to_string();
+        assert!(!cap_output(&mut output, 100));
+
+        // Cuts on a character boundary
+        assert!(cap_output(&mut output, 2));
+        assert!(output.starts_with("h\n[output truncated: output exceeded max_output_size of 2 bytes]"));
+    }
+
+    #[tokio::test]
+    async fn test_write_limits() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("existing.txt"), "0123456789").unwrap();
+
+        assert!(WriteTracker::snapshot(vec![dir.path().to_path_buf()], &ExecutionLimits::new()).await.is_none());
+        let limits = ExecutionLimits::new().with_max_files_written(2).with_max_write_size("20b");
+        let tracker = WriteTracker::snapshot(vec![dir.path().to_path_buf()], &limits).await.unwrap();
+        assert_eq!(tracker.check().await, Ok(WriteUsage::default()));
+
+        // A new file and growth of an existing one
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        std::fs::write(dir.path().join("nested/new.txt"), "abc").unwrap();
+        std::fs::write(dir.path().join("existing.txt"), "0123456789abcde").unwrap();
+        assert_eq!(tracker.check().await, Ok(WriteUsage { files: 2, bytes: 8 }));
+
+        std::fs::write(dir.path().join("third.txt"), "x").unwrap();
+        assert_eq!(tracker.check().await, Err(LimitExceeded::FilesWritten { written: 3, limit: 2 }));
+
+        std::fs::remove_file(dir.path().join("third.txt")).unwrap();
+        std::fs::write(dir.path().join("nested/new.txt"), "x".repeat(30)).unwrap();
+        assert_eq!(tracker.check().await, Err(LimitExceeded::BytesWritten { written: 35, limit: 20 }));
+    }
+}
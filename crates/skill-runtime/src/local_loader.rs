@@ -4,7 +4,7 @@ use std::process::Command;
 use wasmtime::component::Component;
 
 use crate::engine::SkillEngine;
-use crate::skill_md::{find_skill_md, parse_skill_md, SkillMdContent};
+use crate::skill_md::{find_skill_md, parse_skill_md_localized, preferred_locale, SkillMdContent};
 
 /// Loads skills from local directories with automatic compilation
 pub struct LocalSkillLoader {
@@ -283,7 +283,8 @@ impl LocalSkillLoader {
         Ok(())
     }
 
-    /// Load SKILL.md from a skill directory if it exists
+    /// Load SKILL.md from a skill directory if it exists, localized for the
+    /// preferred locale (`SKILL_LOCALE`)
     pub fn load_skill_md(&self, skill_path: impl AsRef<Path>) -> Option<SkillMdContent> {
         let skill_path = skill_path.as_ref();
 
@@ -296,7 +297,7 @@ impl LocalSkillLoader {
 
         // Find and parse SKILL.md
         if let Some(skill_md_path) = find_skill_md(&skill_dir) {
-            match parse_skill_md(&skill_md_path) {
+            match parse_skill_md_localized(&skill_md_path, preferred_locale().as_deref()) {
                 Ok(content) => {
                     tracing::info!(
                         path = %skill_md_path.display(),
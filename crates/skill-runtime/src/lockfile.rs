@@ -0,0 +1,308 @@
+//! Lockfile - the revisions a manifest's skills were installed at
+//!
+//! `skill install --from-manifest` writes `.skill-engine.lock` next to the
+//! manifest, recording what each skill's source resolved to:
+//!
+//! ```toml
+//! version = 1
+//!
+//! [skills.github-ops]
+//! source = "github:org/skill-github@v1.0.0"
+//! version = "1.0.0"
+//! commit = "4f2a9c1e0b7d6a5f3e2d1c0b9a8f7e6d5c4b3a29"
+//!
+//! [skills.kubernetes]
+//! source = "oci://ghcr.io/org/kubernetes:1.2.0"
+//! digest = "sha256:9b1f..."
+//! ```
+//!
+//! Installing from the manifest again fetches exactly these revisions: the
+//! commit of a Git source, the manifest digest of an OCI artifact and the
+//! checksum of an archive. An entry only applies while the manifest declares
+//! the same `source` and `ref`; once either changes, the skill is resolved
+//! afresh and its entry replaced.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::archive_loader::{is_archive_url, parse_archive_url};
+use crate::git_source::is_git_url;
+use crate::manifest::SkillManifest;
+use crate::oci_loader::{is_oci_reference, parse_oci_reference};
+
+/// Name of the lockfile, next to `.skill-engine.toml`
+pub const LOCKFILE_NAME: &str = ".skill-engine.lock";
+
+/// Lockfile format version written by this release
+const LOCKFILE_VERSION: u32 = 1;
+
+const LOCKFILE_HEADER: &str =
+    "# Written by `skill install --from-manifest`; commit it next to the manifest.\n\n";
+
+/// Resolved revisions of a manifest's skills
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// Format version
+    pub version: u32,
+    /// Locked skills by name
+    #[serde(default)]
+    pub skills: BTreeMap<String, LockedSkill>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            skills: BTreeMap::new(),
+        }
+    }
+}
+
+/// What a skill's source resolved to when it was installed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedSkill {
+    /// Source as declared in the manifest
+    pub source: String,
+    /// Git ref as declared in the manifest
+    #[serde(rename = "ref", default, skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// Version the skill declared
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Commit of a Git source
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Manifest digest of an OCI artifact
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    /// SHA-256 of a downloaded archive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+/// Where to install a manifest skill from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstallSource {
+    /// Source to fetch, relative paths resolved and OCI and archive sources
+    /// pinned to their locked digest
+    pub source: String,
+    /// Git ref to check out: the locked commit, else the manifest's `ref`
+    pub git_ref: Option<String>,
+    /// Whether the revision comes from the lockfile
+    pub locked: bool,
+}
+
+impl Lockfile {
+    /// The lockfile belonging to a manifest
+    pub fn path_for(manifest: &SkillManifest) -> PathBuf {
+        manifest.base_dir.join(LOCKFILE_NAME)
+    }
+
+    /// Load a lockfile; a missing file gives an empty one
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read lockfile {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid lockfile {}", path.display()))
+    }
+
+    /// Parse lockfile content
+    pub fn parse(content: &str) -> Result<Self> {
+        let lockfile: Self = toml::from_str(content).context("Failed to parse lockfile TOML")?;
+        if lockfile.version > LOCKFILE_VERSION {
+            anyhow::bail!(
+                "Lockfile version {} is newer than this release supports ({}); upgrade skill",
+                lockfile.version,
+                LOCKFILE_VERSION
+            );
+        }
+        Ok(lockfile)
+    }
+
+    /// Save the lockfile
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = toml::to_string_pretty(self).context("Failed to serialize lockfile")?;
+        std::fs::write(path, format!("{}{}", LOCKFILE_HEADER, content))
+            .with_context(|| format!("Failed to write lockfile {}", path.display()))
+    }
+
+    /// A skill's entry, if it was locked for the `source` and `ref` the
+    /// manifest declares now
+    pub fn locked(&self, manifest: &SkillManifest, skill_name: &str) -> Option<&LockedSkill> {
+        let skill = manifest.get_skill(skill_name)?;
+        self.skills
+            .get(skill_name)
+            .filter(|entry| entry.source == skill.source && entry.git_ref == skill.git_ref)
+    }
+
+    /// Where to install a manifest skill from, honoring its lock entry
+    pub fn install_source(
+        &self,
+        manifest: &SkillManifest,
+        skill_name: &str,
+    ) -> Result<InstallSource> {
+        let skill = manifest
+            .get_skill(skill_name)
+            .with_context(|| format!("Skill '{}' not found in manifest", skill_name))?;
+        let mut source = manifest.skill_source(skill_name).unwrap_or_default();
+        let mut git_ref = skill.git_ref.clone();
+        let mut locked = false;
+
+        if let Some(entry) = self.locked(manifest, skill_name) {
+            if is_oci_reference(&source) {
+                if let Some(digest) = &entry.digest {
+                    let mut reference = parse_oci_reference(&source)?;
+                    reference.digest = Some(digest.clone());
+                    source = format!("oci://{}", reference);
+                    locked = true;
+                }
+            } else if is_archive_url(&source) {
+                if let Some(sha256) = &entry.sha256 {
+                    if parse_archive_url(&source)?.sha256.is_none() {
+                        source = format!("{}#sha256={}", source, sha256);
+                    }
+                    locked = true;
+                }
+            } else if is_git_url(&source) {
+                if let Some(commit) = &entry.commit {
+                    git_ref = Some(commit.clone());
+                    locked = true;
+                }
+            }
+        }
+
+        Ok(InstallSource {
+            source,
+            git_ref,
+            locked,
+        })
+    }
+
+    /// Record what a manifest skill was installed at
+    pub fn lock(&mut self, manifest: &SkillManifest, skill_name: &str, mut entry: LockedSkill) {
+        if let Some(skill) = manifest.get_skill(skill_name) {
+            entry.source = skill.source.clone();
+            entry.git_ref = skill.git_ref.clone();
+        }
+        self.version = LOCKFILE_VERSION;
+        self.skills.insert(skill_name.to_string(), entry);
+    }
+
+    /// Drop entries of skills the manifest no longer declares
+    pub fn retain_manifest(&mut self, manifest: &SkillManifest) {
+        self.skills
+            .retain(|name, _| manifest.skills.contains_key(name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+        [skills.github]
+        source = "github:org/skill-github"
+        ref = "v1.0.0"
+
+        [skills.kubernetes]
+        source = "oci://ghcr.io/org/kubernetes:1.2.0"
+
+        [skills.terraform]
+        source = "https://example.com/terraform-skill.tar.gz"
+
+        [skills.local]
+        source = "./skills/local"
+    "#;
+
+    #[test]
+    fn test_install_source_honors_lock_entries() {
+        let manifest = SkillManifest::parse(MANIFEST).unwrap();
+        let mut lockfile = Lockfile::default();
+
+        // Nothing locked yet: the manifest's pins are used as declared
+        let github = lockfile.install_source(&manifest, "github").unwrap();
+        assert_eq!(github.git_ref.as_deref(), Some("v1.0.0"));
+        assert!(!github.locked);
+
+        let commit = "4f2a9c1e0b7d6a5f3e2d1c0b9a8f7e6d5c4b3a29";
+        lockfile.lock(
+            &manifest,
+            "github",
+            LockedSkill {
+                commit: Some(commit.to_string()),
+                ..Default::default()
+            },
+        );
+        lockfile.lock(
+            &manifest,
+            "kubernetes",
+            LockedSkill {
+                digest: Some("sha256:abc".to_string()),
+                ..Default::default()
+            },
+        );
+        lockfile.lock(
+            &manifest,
+            "terraform",
+            LockedSkill {
+                sha256: Some("def".to_string()),
+                ..Default::default()
+            },
+        );
+        lockfile.lock(&manifest, "gone", LockedSkill::default());
+        lockfile.retain_manifest(&manifest);
+        assert!(!lockfile.skills.contains_key("gone"));
+
+        let github = lockfile.install_source(&manifest, "github").unwrap();
+        assert_eq!(github.git_ref.as_deref(), Some(commit));
+        assert!(github.locked);
+        let kubernetes = lockfile.install_source(&manifest, "kubernetes").unwrap();
+        assert_eq!(
+            kubernetes.source,
+            "oci://ghcr.io/org/kubernetes:1.2.0@sha256:abc"
+        );
+        let terraform = lockfile.install_source(&manifest, "terraform").unwrap();
+        assert_eq!(
+            terraform.source,
+            "https://example.com/terraform-skill.tar.gz#sha256=def"
+        );
+        let local = lockfile.install_source(&manifest, "local").unwrap();
+        assert!(local.source.ends_with("skills/local"));
+        assert!(!local.locked);
+
+        // Changing the declared ref releases the lock
+        let bumped = SkillManifest::parse(&MANIFEST.replace("v1.0.0", "v2.0.0")).unwrap();
+        let github = lockfile.install_source(&bumped, "github").unwrap();
+        assert_eq!(github.git_ref.as_deref(), Some("v2.0.0"));
+        assert!(!github.locked);
+    }
+
+    #[test]
+    fn test_lockfile_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(LOCKFILE_NAME);
+        assert_eq!(Lockfile::load(&path).unwrap(), Lockfile::default());
+
+        let manifest = SkillManifest::parse(MANIFEST).unwrap();
+        let mut lockfile = Lockfile::default();
+        lockfile.lock(
+            &manifest,
+            "github",
+            LockedSkill {
+                version: Some("1.0.0".to_string()),
+                ..Default::default()
+            },
+        );
+        lockfile.save(&path).unwrap();
+        let loaded = Lockfile::load(&path).unwrap();
+        assert_eq!(loaded, lockfile);
+        assert_eq!(loaded.skills["github"].git_ref.as_deref(), Some("v1.0.0"));
+
+        assert!(Lockfile::parse("version = 99").is_err());
+    }
+}
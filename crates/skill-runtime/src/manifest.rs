@@ -38,7 +38,8 @@
 //! [skills.aws.instances.dev]
 //! config.region = "us-west-2"
 //! config.profile = "dev"
-//! capabilities.network_access = true
+//! # WASM skills can be limited to specific endpoints (host globs, optional port)
+//! capabilities.allowed_hosts = ["*.amazonaws.com:443"]
 //! ```
 
 use anyhow::{Context, Result};
@@ -46,7 +47,18 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use skill_context::{ExecutionLimits, Mount, NetworkConfig};
+
+use crate::artifacts::{ArtifactRetention, GUEST_OUTPUT_DIR, OUTPUT_DIR_ENV};
+use crate::command_allowlist::{CommandAllowlist, SecurityConfig};
+use crate::dependencies::{installed_dependencies, SkillDependency};
+use crate::git_auth::GitSourceConfig;
+use crate::git_source::{parse_git_url, GitRef, GitSource};
+use crate::http_proxy::HttpCredential;
 use crate::instance::{Capabilities, ConfigValue, InstanceConfig, InstanceMetadata};
+use crate::native_security::NativeSecurityPolicy;
+use crate::requirements::Requirement;
+use crate::services::RestartPolicy;
 
 /// Runtime type for skill execution
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -124,12 +136,79 @@ pub struct DockerRuntimeConfig {
     /// Additional docker run arguments (advanced use)
     #[serde(default)]
     pub extra_args: Vec<String>,
+
+    /// Keep a container running between tool calls and `docker exec` into
+    /// it instead of starting a new container per call (default: false)
+    #[serde(default)]
+    pub keep_warm: bool,
+
+    /// Seconds a warm container may sit unused before it is removed
+    /// (default: 600)
+    #[serde(default = "default_idle_timeout_secs")]
+    pub idle_timeout_secs: u64,
+
+    /// Command that keeps a warm container alive; it replaces the image's
+    /// entrypoint (default: `sleep infinity`)
+    #[serde(default = "default_keepalive_command")]
+    pub keepalive_command: Vec<String>,
+
+    /// Containers started next to the skill's container on a shared
+    /// network (e.g. localstack for the aws skill)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sidecars: Vec<DockerSidecar>,
+}
+
+/// A container a Docker skill needs running next to it
+///
+/// Sidecars are started before the skill's container, on a network shared
+/// with it, and removed with it (see [`crate::docker_sidecars`]).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DockerSidecar {
+    /// Sidecar name; also its hostname on the shared network
+    pub name: String,
+
+    /// Docker image to run (supports ${ENV_VAR} syntax)
+    pub image: String,
+
+    /// Command to run (overrides image CMD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<Vec<String>>,
+
+    /// Environment variables (KEY=value format, supports ${ENV_VAR} syntax)
+    #[serde(default)]
+    pub environment: Vec<String>,
+
+    /// Port the sidecar listens on; its URL is injected into the skill
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+
+    /// Command run inside the sidecar that succeeds once it is ready;
+    /// defaults to the image's HEALTHCHECK, or to the container running
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub health_check: Vec<String>,
+
+    /// Variable that passes the sidecar URL to the skill
+    /// (defaults to `<NAME>_URL`, e.g. `LOCALSTACK_URL`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inject_env: Option<String>,
+
+    /// Seconds the sidecar gets to become ready (default: 60)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub startup_timeout_secs: Option<u64>,
 }
 
 fn default_network() -> String {
     "none".to_string()
 }
 
+fn default_idle_timeout_secs() -> u64 {
+    600
+}
+
+fn default_keepalive_command() -> Vec<String> {
+    vec!["sleep".to_string(), "infinity".to_string()]
+}
+
 fn default_true() -> bool {
     true
 }
@@ -152,10 +231,29 @@ impl Default for DockerRuntimeConfig {
             read_only: false,
             platform: None,
             extra_args: Vec::new(),
+            keep_warm: false,
+            idle_timeout_secs: default_idle_timeout_secs(),
+            keepalive_command: default_keepalive_command(),
+            sidecars: Vec::new(),
         }
     }
 }
 
+impl DockerRuntimeConfig {
+    /// Mount a host output directory at `/outputs` and point
+    /// `SKILL_OUTPUT_DIR` at it
+    ///
+    /// The directory is per execution, so the container can't be kept warm.
+    pub fn with_output_dir(mut self, host_dir: &Path) -> Self {
+        self.keep_warm = false;
+        self.volumes
+            .push(format!("{}:{}", host_dir.display(), GUEST_OUTPUT_DIR));
+        self.environment
+            .push(format!("{}={}", OUTPUT_DIR_ENV, GUEST_OUTPUT_DIR));
+        self
+    }
+}
+
 /// Root manifest structure
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SkillManifest {
@@ -167,13 +265,41 @@ pub struct SkillManifest {
     #[serde(default)]
     pub defaults: ManifestDefaults,
 
+    /// Security settings (command allowlist for native skills)
+    #[serde(default)]
+    pub security: SecurityConfig,
+
+    /// Retention of files captured from executions
+    #[serde(default)]
+    pub artifacts: ArtifactRetention,
+
+    /// Retention of execution history and search analytics
+    #[serde(default)]
+    pub history: HistoryRetention,
+
+    /// How the MCP server exposes skill tools
+    #[serde(default, skip_serializing_if = "McpConfig::is_default")]
+    pub mcp: McpConfig,
+
     /// Skill definitions
     #[serde(default)]
     pub skills: HashMap<String, SkillDefinition>,
 
+    /// Reusable instance templates, referenced with `template = "<name>"`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub templates: HashMap<String, InstanceDefinition>,
+
     /// Base directory for resolving relative paths (set during load)
     #[serde(skip)]
     pub base_dir: PathBuf,
+
+    /// Manifest files merged into this one, lowest precedence first
+    #[serde(skip)]
+    pub layers: Vec<PathBuf>,
+
+    /// Manifest file each skill definition came from
+    #[serde(skip)]
+    pub skill_origins: HashMap<String, PathBuf>,
 }
 
 fn default_version() -> String {
@@ -190,12 +316,140 @@ pub struct ManifestDefaults {
     /// Default environment variables for all instances
     #[serde(default)]
     pub env: HashMap<String, String>,
+
+    /// Fail resolution on any unresolved `${VAR}`, listing all of them
+    /// (also enabled by `SKILL_STRICT_ENV=1`)
+    #[serde(default)]
+    pub strict_env: bool,
+}
+
+/// `[history]` section: retention of the execution history and search
+/// analytics databases
+///
+/// Limits of 0 are disabled. The server applies them periodically;
+/// `skill history prune` applies them on demand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HistoryRetention {
+    /// Keep at most this many rows per table, newest first
+    #[serde(default = "default_history_max_rows")]
+    pub max_rows: u64,
+
+    /// Delete rows older than this many days
+    #[serde(default = "default_history_max_age_days")]
+    pub max_age_days: u64,
+
+    /// Delete the oldest rows until each database fits in this many megabytes
+    #[serde(default = "default_history_max_db_size_mb")]
+    pub max_db_size_mb: u64,
+}
+
+fn default_history_max_rows() -> u64 {
+    100_000
+}
+
+fn default_history_max_age_days() -> u64 {
+    90
+}
+
+fn default_history_max_db_size_mb() -> u64 {
+    256
+}
+
+impl Default for HistoryRetention {
+    fn default() -> Self {
+        Self {
+            max_rows: default_history_max_rows(),
+            max_age_days: default_history_max_age_days(),
+            max_db_size_mb: default_history_max_db_size_mb(),
+        }
+    }
+}
+
+/// `[mcp]` section: how the MCP server exposes skill tools
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct McpConfig {
+    /// Expose tools through the `execute` dispatcher, individually, or both
+    #[serde(default)]
+    pub tool_mode: McpToolMode,
+
+    /// List every tool up front, or only the skills until their tools are loaded
+    #[serde(default)]
+    pub discovery: McpDiscovery,
+
+    /// How skills get the workspace roots the client declares
+    #[serde(default)]
+    pub roots: McpRoots,
+
+    /// MCP tool names for individually exposed tools, keyed by `skill:tool`
+    /// (otherwise `skill__tool`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub aliases: HashMap<String, String>,
+}
+
+impl McpConfig {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How the MCP server exposes skill tools
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpToolMode {
+    /// One generic `execute` tool taking the skill and tool names
+    #[default]
+    Dispatcher,
+    /// One MCP tool per skill tool (`kubernetes__get`), without `execute`
+    Flat,
+    /// Both the individual tools and `execute`
+    Both,
+}
+
+impl McpToolMode {
+    /// Whether the generic `execute` tool is exposed
+    pub fn dispatcher(self) -> bool {
+        matches!(self, Self::Dispatcher | Self::Both)
+    }
+
+    /// Whether each skill tool is exposed as an MCP tool of its own
+    pub fn flat(self) -> bool {
+        matches!(self, Self::Flat | Self::Both)
+    }
+}
+
+/// When the MCP server lists skill tools
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpDiscovery {
+    /// Every tool is listed from the start
+    #[default]
+    Eager,
+    /// One meta-tool per skill; a skill's tools are listed once
+    /// `load_skill_tools` loads them
+    Lazy,
+}
+
+/// How skills get the client's workspace roots
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum McpRoots {
+    /// Not requested from the client, so skills never see the workspace
+    #[default]
+    Off,
+    /// Mounted read-only for WASM skills; native commands run in the first
+    ReadOnly,
+    /// Mounted writable for WASM skills; native commands run in the first
+    ReadWrite,
 }
 
 /// Host service requirement for a skill
 ///
 /// Skills can declare dependencies on host services (like kubectl-proxy)
-/// that must be running for the skill to function properly.
+/// that must be running for the skill to function properly. Services with a
+/// `command` are started, monitored and stopped by the server (see
+/// [`crate::services`]).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceRequirement {
     /// Service name (e.g., "kubectl-proxy")
@@ -213,6 +467,39 @@ pub struct ServiceRequirement {
     /// Default port the service runs on
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_port: Option<u16>,
+
+    /// Program and arguments that start the service; `{{port}}` is replaced
+    /// with the port. Without it the service is only detected on its port
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub command: Vec<String>,
+
+    /// HTTP path (e.g. "/healthz") or URL that answers 2xx when the service
+    /// is up; defaults to the port accepting connections
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<String>,
+
+    /// Environment of the service process (supports ${ENV_VAR} syntax)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+
+    /// Variable that passes the service URL to the skill's tools
+    /// (defaults to `<NAME>_URL`, e.g. `KUBECTL_PROXY_URL`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inject_env: Option<String>,
+
+    /// Whether to restart the process when it exits
+    /// ("on-failure" (default), "always" or "never")
+    #[serde(default)]
+    pub restart: RestartPolicy,
+
+    /// Start the service when the server starts
+    #[serde(default)]
+    pub autostart: bool,
+
+    /// Start the service, and wait for its health check, before running a
+    /// tool of this skill when it isn't already running
+    #[serde(default)]
+    pub start_on_demand: bool,
 }
 
 /// Skill definition in manifest
@@ -235,6 +522,10 @@ pub struct SkillDefinition {
     #[serde(rename = "ref")]
     pub git_ref: Option<String>,
 
+    /// Authentication and clone options - only for git sources
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitSourceConfig>,
+
     /// Description of this skill
     pub description: Option<String>,
 
@@ -242,6 +533,10 @@ pub struct SkillDefinition {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub docker: Option<DockerRuntimeConfig>,
 
+    /// Process hardening for native commands (runtime = "native")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub native: Option<NativeSecurityPolicy>,
+
     /// Instance configurations for this skill
     #[serde(default)]
     pub instances: HashMap<String, InstanceDefinition>,
@@ -253,6 +548,28 @@ pub struct SkillDefinition {
     /// Host services this skill requires (e.g., kubectl-proxy)
     #[serde(default)]
     pub services: Vec<ServiceRequirement>,
+
+    /// Tags used for search filtering (e.g., ["aws", "cloud"])
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Other skills this skill requires (names, or `{ name, source }` tables)
+    #[serde(default, alias = "dependencies", skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<SkillDependency>,
+
+    /// Files the tool writes to `SKILL_OUTPUT_DIR` to keep as artifacts
+    /// (globs relative to the output directory, e.g. `["*.json", "**/*.tfplan"]`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<String>,
+
+    /// What the skill needs from the host
+    /// (e.g. `["binary:kubectl>=1.27", "env:KUBECONFIG", "port:8001"]`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<Requirement>,
+    /// Output and write limits for the skill's tools
+    /// (e.g. `max_output_size = "1mb"`; see [`crate::limits`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<ExecutionLimits>,
 }
 
 fn default_instance_name() -> String {
@@ -276,6 +593,36 @@ pub struct InstanceDefinition {
 
     /// Description of this instance
     pub description: Option<String>,
+
+    /// Template this instance is based on (key of `[templates]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+
+    /// Values for `{{name}}` placeholders in the template
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub vars: HashMap<String, String>,
+}
+
+impl InstanceDefinition {
+    /// Replace `{{name}}` placeholders in every string value
+    fn substitute_vars(&mut self, vars: &HashMap<String, String>) -> Result<()> {
+        for value in self.config.values_mut().chain(self.env.values_mut()) {
+            *value = substitute_template_vars(value, vars)?;
+        }
+        let caps = &mut self.capabilities;
+        for value in caps
+            .allowed_hosts
+            .iter_mut()
+            .chain(caps.blocked_hosts.iter_mut())
+            .chain(caps.allowed_paths.iter_mut())
+        {
+            *value = substitute_template_vars(value, vars)?;
+        }
+        if let Some(description) = &mut self.description {
+            *description = substitute_template_vars(description, vars)?;
+        }
+        Ok(())
+    }
 }
 
 /// Capabilities in manifest format
@@ -285,21 +632,68 @@ pub struct ManifestCapabilities {
     #[serde(default)]
     pub network_access: bool,
 
+    /// Outbound hosts WASM skills may reach (`host[:port]`, `*` globs)
+    #[serde(default, alias = "allowed_domains")]
+    pub allowed_hosts: Vec<String>,
+
+    /// Outbound hosts that are always blocked
+    #[serde(default, alias = "blocked_domains")]
+    pub blocked_hosts: Vec<String>,
+
     /// Allowed filesystem paths
     #[serde(default)]
     pub allowed_paths: Vec<String>,
 
+    /// Files and directories preopened for WASM skills
+    #[serde(default)]
+    pub mounts: Vec<Mount>,
+
+    /// Credentials injected into WASM skills' outbound HTTP requests
+    #[serde(default)]
+    pub http_credentials: Vec<HttpCredential>,
+
     /// Max concurrent requests
     pub max_concurrent_requests: Option<usize>,
 }
 
+impl ManifestCapabilities {
+    /// Layer `other` on top: lists are combined, flags enabled by either side win
+    pub fn extend(&mut self, other: ManifestCapabilities) {
+        self.network_access |= other.network_access;
+        for (list, additions) in [
+            (&mut self.allowed_hosts, other.allowed_hosts),
+            (&mut self.blocked_hosts, other.blocked_hosts),
+            (&mut self.allowed_paths, other.allowed_paths),
+        ] {
+            for item in additions {
+                if !list.contains(&item) {
+                    list.push(item);
+                }
+            }
+        }
+        self.mounts.extend(other.mounts);
+        self.http_credentials.extend(other.http_credentials);
+        if other.max_concurrent_requests.is_some() {
+            self.max_concurrent_requests = other.max_concurrent_requests;
+        }
+    }
+}
+
 impl SkillManifest {
     /// Load manifest from file
     pub fn load(path: &Path) -> Result<Self> {
+        let mut manifest = Self::load_file(path)?;
+        manifest.apply_templates()?;
+        Ok(manifest)
+    }
+
+    /// Read a single manifest file without expanding instance templates
+    fn load_file(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read manifest file: {}", path.display()))?;
 
-        let mut manifest = Self::parse(&content)?;
+        let mut manifest: Self =
+            toml::from_str(&content).context("Failed to parse manifest TOML")?;
 
         // Set base_dir to the manifest file's parent directory
         manifest.base_dir = path
@@ -312,12 +706,178 @@ impl SkillManifest {
             manifest.base_dir = canonical;
         }
 
+        manifest.layers = vec![path.to_path_buf()];
+        manifest.skill_origins = manifest
+            .skills
+            .keys()
+            .map(|name| (name.clone(), path.to_path_buf()))
+            .collect();
+
+        Ok(manifest)
+    }
+
+    /// Load several manifests and merge them, later files taking precedence
+    ///
+    /// Typical layering is the global manifest (`~/.skill-engine/global.toml`),
+    /// then the project `.skill-engine.toml`, then `--manifest` overrides. See
+    /// [`SkillManifest::merge`] for how layers combine.
+    pub fn load_layered(paths: &[PathBuf]) -> Result<Self> {
+        let mut merged: Option<Self> = None;
+        for path in paths {
+            let layer = Self::load_file(path)?;
+            match merged.as_mut() {
+                Some(manifest) => manifest.merge(layer),
+                None => {
+                    let mut manifest = Self::default();
+                    manifest.merge(layer);
+                    merged = Some(manifest);
+                }
+            }
+        }
+        let mut manifest = merged.context("No manifest files to load")?;
+        // Templates may come from a different layer than the instances using them
+        manifest.apply_templates()?;
         Ok(manifest)
     }
 
+    /// Load this manifest again from the file(s) it was read from
+    pub fn reload(&self) -> Result<Self> {
+        match self.layers.as_slice() {
+            [path] => Self::load(path),
+            layers => Self::load_layered(layers),
+        }
+    }
+
+    /// Merge a higher-precedence manifest into this one
+    ///
+    /// - a skill defined in both is taken entirely from `other`
+    /// - `defaults.env` entries from `other` override per variable
+    /// - host, path and mount lists in `defaults.capabilities` are combined;
+    ///   `network_access` and `strict_env` are enabled if either layer enables them
+    /// - `security.allowed-commands` from `other` replaces this one when set
+    /// - `[artifacts]` and `[history]` sections from `other` replace this one
+    /// - `mcp.tool-mode`, `mcp.discovery` and `mcp.roots` from `other`
+    ///   replace this one's when set; aliases are replaced by tool
+    /// - templates are replaced by name
+    ///
+    /// Relative local sources are made absolute against the manifest that
+    /// declared them, so they keep working after the merge.
+    pub fn merge(&mut self, other: SkillManifest) {
+        let origin = other
+            .layers
+            .last()
+            .cloned()
+            .unwrap_or_else(|| other.base_dir.clone());
+
+        self.version = other.version;
+
+        self.defaults.env.extend(other.defaults.env);
+        self.defaults.strict_env |= other.defaults.strict_env;
+        self.defaults
+            .capabilities
+            .extend(other.defaults.capabilities);
+
+        if other.security.allowed_commands.is_some() {
+            self.security.allowed_commands = other.security.allowed_commands;
+        }
+        self.security.strict |= other.security.strict;
+        if other.artifacts != ArtifactRetention::default() {
+            self.artifacts = other.artifacts;
+        }
+        if other.history != HistoryRetention::default() {
+            self.history = other.history;
+        }
+        if other.mcp.tool_mode != McpToolMode::default() {
+            self.mcp.tool_mode = other.mcp.tool_mode;
+        }
+        if other.mcp.discovery != McpDiscovery::default() {
+            self.mcp.discovery = other.mcp.discovery;
+        }
+        if other.mcp.roots != McpRoots::default() {
+            self.mcp.roots = other.mcp.roots;
+        }
+        self.mcp.aliases.extend(other.mcp.aliases);
+        self.templates.extend(other.templates);
+
+        for (name, mut skill) in other.skills {
+            if skill.source.starts_with("./") || skill.source.starts_with("../") {
+                skill.source = other
+                    .base_dir
+                    .join(&skill.source)
+                    .to_string_lossy()
+                    .to_string();
+            }
+            let skill_origin = other
+                .skill_origins
+                .get(&name)
+                .cloned()
+                .unwrap_or_else(|| origin.clone());
+            self.skill_origins.insert(name.clone(), skill_origin);
+            self.skills.insert(name, skill);
+        }
+
+        self.layers.extend(other.layers);
+        self.base_dir = other.base_dir;
+    }
+
     /// Parse manifest from TOML string
     pub fn parse(content: &str) -> Result<Self> {
-        toml::from_str(content).context("Failed to parse manifest TOML")
+        let mut manifest: Self =
+            toml::from_str(content).context("Failed to parse manifest TOML")?;
+        manifest.apply_templates()?;
+        Ok(manifest)
+    }
+
+    /// Expand instances declared with `template = "<name>"`
+    ///
+    /// The template's config, env and capabilities are copied into the
+    /// instance, values set on the instance itself take precedence, and then
+    /// `{{name}}` placeholders are filled from the instance's `vars` plus the
+    /// built-ins `{{skill}}` and `{{instance}}`. `${ENV}` references are left
+    /// for instance resolution.
+    fn apply_templates(&mut self) -> Result<()> {
+        for (skill_name, skill) in &mut self.skills {
+            for (instance_name, instance) in &mut skill.instances {
+                let Some(template_name) = instance.template.take() else {
+                    continue;
+                };
+                let Some(template) = self.templates.get(&template_name) else {
+                    anyhow::bail!(
+                        "Instance '{}' of skill '{}' uses unknown template '{}'",
+                        instance_name,
+                        skill_name,
+                        template_name
+                    );
+                };
+                let mut expanded = template.clone();
+                if expanded.template.is_some() {
+                    anyhow::bail!("Template '{}' cannot itself use a template", template_name);
+                }
+
+                let own = std::mem::take(instance);
+                expanded.config.extend(own.config);
+                expanded.env.extend(own.env);
+                expanded.capabilities.extend(own.capabilities);
+                if own.description.is_some() {
+                    expanded.description = own.description;
+                }
+
+                let mut vars = own.vars.clone();
+                vars.entry("skill".to_string())
+                    .or_insert_with(|| skill_name.clone());
+                vars.entry("instance".to_string())
+                    .or_insert_with(|| instance_name.clone());
+                expanded.substitute_vars(&vars).with_context(|| {
+                    format!(
+                        "Failed to apply template '{}' to {}@{}",
+                        template_name, skill_name, instance_name
+                    )
+                })?;
+                expanded.vars = own.vars;
+                *instance = expanded;
+            }
+        }
+        Ok(())
     }
 
     /// Find manifest file in current or parent directories
@@ -378,13 +938,15 @@ impl SkillManifest {
             .cloned()
             .unwrap_or_default();
 
+        let mut env = EnvResolver::new(self.strict_env());
+
         // Build resolved config
         let mut config = HashMap::new();
         for (key, value) in &instance_def.config {
             config.insert(
                 key.clone(),
                 ConfigValue {
-                    value: expand_env_vars(value)?,
+                    value: env.expand(&format!("config.{}", key), value)?,
                     secret: is_likely_secret(key),
                 },
             );
@@ -395,24 +957,75 @@ impl SkillManifest {
 
         // Add global defaults first
         for (key, value) in &self.defaults.env {
-            environment.insert(key.clone(), expand_env_vars(value)?);
+            environment.insert(
+                key.clone(),
+                env.expand(&format!("defaults.env.{}", key), value)?,
+            );
         }
 
         // Add instance-specific env vars (override defaults)
         for (key, value) in &instance_def.env {
-            environment.insert(key.clone(), expand_env_vars(value)?);
+            environment.insert(key.clone(), env.expand(&format!("env.{}", key), value)?);
         }
 
+        // Build network policy from declared hosts (defaults + instance)
+        let mut collect_hosts = |field: &str, instance: &[String], defaults: &[String]| {
+            instance
+                .iter()
+                .chain(defaults.iter())
+                .map(|h| env.expand_optional(field, h).unwrap_or_else(|| h.clone()))
+                .collect::<Vec<_>>()
+        };
+        let allowed_hosts = collect_hosts(
+            "capabilities.allowed_hosts",
+            &instance_def.capabilities.allowed_hosts,
+            &self.defaults.capabilities.allowed_hosts,
+        );
+        let blocked_hosts = collect_hosts(
+            "capabilities.blocked_hosts",
+            &instance_def.capabilities.blocked_hosts,
+            &self.defaults.capabilities.blocked_hosts,
+        );
+        let network = if allowed_hosts.is_empty() && blocked_hosts.is_empty() {
+            None
+        } else {
+            let mut network = NetworkConfig::enabled().with_allowed_hosts(allowed_hosts);
+            if !blocked_hosts.is_empty() {
+                network = network.with_blocked_hosts(blocked_hosts);
+            }
+            Some(network)
+        };
+
         // Build capabilities
         let capabilities = Capabilities {
             network_access: instance_def.capabilities.network_access
                 || self.defaults.capabilities.network_access,
+            network,
             allowed_paths: instance_def
                 .capabilities
                 .allowed_paths
                 .iter()
                 .chain(self.defaults.capabilities.allowed_paths.iter())
-                .map(|p| PathBuf::from(expand_env_vars(p).unwrap_or_default()))
+                .map(|p| {
+                    PathBuf::from(
+                        env.expand_optional("capabilities.allowed_paths", p)
+                            .unwrap_or_default(),
+                    )
+                })
+                .collect(),
+            mounts: instance_def
+                .capabilities
+                .mounts
+                .iter()
+                .chain(self.defaults.capabilities.mounts.iter())
+                .cloned()
+                .collect(),
+            http_credentials: instance_def
+                .capabilities
+                .http_credentials
+                .iter()
+                .chain(self.defaults.capabilities.http_credentials.iter())
+                .cloned()
                 .collect(),
             max_concurrent_requests: instance_def
                 .capabilities
@@ -421,29 +1034,24 @@ impl SkillManifest {
                 .unwrap_or(10),
         };
 
-        // Resolve relative paths against base_dir
-        let resolved_source = if skill.source.starts_with("./") || skill.source.starts_with("../") {
-            self.base_dir.join(&skill.source).to_string_lossy().to_string()
-        } else {
-            skill.source.clone()
-        };
+        let resolved_source = self.resolve_source(&skill.source);
 
         // Resolve Docker config with env var expansion
         let docker_config = if let Some(ref docker) = skill.docker {
             Some(DockerRuntimeConfig {
-                image: expand_env_vars(&docker.image)?,
+                image: env.expand("docker.image", &docker.image)?,
                 entrypoint: docker.entrypoint.clone(),
                 command: docker.command.clone(),
                 volumes: docker
                     .volumes
                     .iter()
-                    .map(|v| expand_env_vars(v))
+                    .map(|v| env.expand("docker.volumes", v))
                     .collect::<Result<Vec<_>>>()?,
                 working_dir: docker.working_dir.clone(),
                 environment: docker
                     .environment
                     .iter()
-                    .map(|e| expand_env_vars(e))
+                    .map(|e| env.expand("docker.environment", e))
                     .collect::<Result<Vec<_>>>()?,
                 memory: docker.memory.clone(),
                 cpus: docker.cpus.clone(),
@@ -454,16 +1062,37 @@ impl SkillManifest {
                 read_only: docker.read_only,
                 platform: docker.platform.clone(),
                 extra_args: docker.extra_args.clone(),
+                keep_warm: docker.keep_warm,
+                idle_timeout_secs: docker.idle_timeout_secs,
+                keepalive_command: docker.keepalive_command.clone(),
+                sidecars: docker
+                    .sidecars
+                    .iter()
+                    .map(|sidecar| {
+                        Ok(DockerSidecar {
+                            image: env.expand("docker.sidecars.image", &sidecar.image)?,
+                            environment: sidecar
+                                .environment
+                                .iter()
+                                .map(|e| env.expand("docker.sidecars.environment", e))
+                                .collect::<Result<Vec<_>>>()?,
+                            ..sidecar.clone()
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?,
             })
         } else {
             None
         };
 
+        env.finish(skill_name, instance_name)?;
+
         Ok(ResolvedInstance {
             skill_name: skill_name.to_string(),
             instance_name: instance_name.to_string(),
             source: resolved_source,
             git_ref: skill.git_ref.clone(),
+            git: skill.git.clone().unwrap_or_default(),
             config: InstanceConfig {
                 metadata: InstanceMetadata {
                     skill_name: skill_name.to_string(),
@@ -471,6 +1100,11 @@ impl SkillManifest {
                     instance_name: instance_name.to_string(),
                     created_at: chrono::Utc::now(),
                     updated_at: chrono::Utc::now(),
+                    dependencies: skill
+                        .depends_on
+                        .iter()
+                        .map(|dep| dep.name().to_string())
+                        .collect(),
                 },
                 config,
                 environment,
@@ -478,9 +1112,123 @@ impl SkillManifest {
             },
             runtime: skill.runtime.clone(),
             docker: docker_config,
+            native: self.native_policy(skill_name),
+            outputs: skill.outputs.clone(),
+            limits: self.execution_limits(skill_name),
+        })
+    }
+
+    /// Whether unresolved `${VAR}` placeholders fail resolution as a whole
+    pub fn strict_env(&self) -> bool {
+        self.defaults.strict_env
+            || std::env::var("SKILL_STRICT_ENV").is_ok_and(|v| v == "1" || v == "true")
+    }
+
+    /// Resolve a skill's transitive dependencies, dependencies first
+    ///
+    /// Each dependency must be defined in this manifest or installed in the
+    /// registry; missing skills and cycles are errors.
+    pub fn resolve_dependencies(&self, skill_name: &str) -> Result<Vec<String>> {
+        crate::dependencies::resolve_dependencies(skill_name, |name| match self.skills.get(name) {
+            Some(skill) => Ok(Some(
+                skill
+                    .depends_on
+                    .iter()
+                    .map(|dep| dep.name().to_string())
+                    .collect(),
+            )),
+            None => installed_dependencies(name),
         })
     }
 
+    /// Skills grouped into install waves, dependencies first
+    ///
+    /// A skill's dependencies defined in this manifest are all in earlier
+    /// waves, so the skills of one wave can be installed in parallel.
+    /// Dependencies from outside the manifest are left to the installer.
+    /// Cycles are errors.
+    pub fn install_waves(&self) -> Result<Vec<Vec<String>>> {
+        let direct = |name: &str| -> Vec<String> {
+            self.skills
+                .get(name)
+                .map(|skill| {
+                    skill
+                        .depends_on
+                        .iter()
+                        .map(|dep| dep.name().to_string())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let mut names: Vec<&String> = self.skills.keys().collect();
+        names.sort();
+        let mut depth: HashMap<String, usize> = HashMap::new();
+        for name in names {
+            let order =
+                crate::dependencies::resolve_dependencies(name, |skill| Ok(Some(direct(skill))))?;
+            // Dependencies come first, so theirs are known by the time a skill is reached
+            for skill in order.iter().chain(std::iter::once(name)) {
+                if depth.contains_key(skill) || !self.skills.contains_key(skill) {
+                    continue;
+                }
+                let level = direct(skill)
+                    .iter()
+                    .filter_map(|dep| depth.get(dep))
+                    .map(|level| level + 1)
+                    .max()
+                    .unwrap_or(0);
+                depth.insert(skill.clone(), level);
+            }
+        }
+
+        let mut waves = vec![Vec::new(); depth.values().max().map_or(0, |max| max + 1)];
+        for (name, level) in depth {
+            waves[level].push(name);
+        }
+        for wave in &mut waves {
+            wave.sort();
+        }
+        Ok(waves)
+    }
+
+    /// Source a skill is installed from, relative paths resolved against
+    /// the manifest's directory
+    pub fn skill_source(&self, skill_name: &str) -> Option<String> {
+        self.skills
+            .get(skill_name)
+            .map(|skill| self.resolve_source(&skill.source))
+    }
+
+    fn resolve_source(&self, source: &str) -> String {
+        if source.starts_with("./") || source.starts_with("../") {
+            self.base_dir.join(source).to_string_lossy().to_string()
+        } else {
+            source.to_string()
+        }
+    }
+
+    /// Command allowlist for native skills from the `[security]` section
+    pub fn command_allowlist(&self) -> CommandAllowlist {
+        CommandAllowlist::from_config(&self.security)
+    }
+
+    /// Native execution hardening for a skill (defaults when not declared)
+    pub fn native_policy(&self, skill_name: &str) -> NativeSecurityPolicy {
+        self.skills
+            .get(skill_name)
+            .and_then(|skill| skill.native.clone())
+            .unwrap_or_default()
+    }
+
+    /// Output and write limits for a skill's tools (none when not declared)
+    pub fn execution_limits(&self, skill_name: &str) -> ExecutionLimits {
+        self.skills
+            .get(skill_name)
+            .and_then(|skill| skill.limits.clone())
+            .unwrap_or_default()
+    }
+
     /// List all skills with their resolved sources
     pub fn list_skills(&self) -> Vec<SkillInfo> {
         self.skills
@@ -508,12 +1256,32 @@ pub struct ResolvedInstance {
     pub source: String,
     /// Git ref (branch, tag, or commit) if applicable
     pub git_ref: Option<String>,
+    /// Git authentication and clone options
+    pub git: GitSourceConfig,
     /// Instance configuration with expanded values
     pub config: InstanceConfig,
     /// Runtime type (wasm, docker, or native)
     pub runtime: SkillRuntime,
     /// Docker configuration (when runtime = docker)
     pub docker: Option<DockerRuntimeConfig>,
+    /// Native execution hardening (when runtime = native)
+    pub native: NativeSecurityPolicy,
+    /// Output file patterns captured as artifacts
+    pub outputs: Vec<String>,
+    /// Output and write limits for the skill's tools
+    pub limits: ExecutionLimits,
+}
+
+impl ResolvedInstance {
+    /// Git source for this instance, with the manifest's `ref` taking precedence
+    /// over a ref in the source URL
+    pub fn git_source(&self) -> Result<GitSource> {
+        let mut source = parse_git_url(&self.source)?;
+        if let Some(git_ref) = &self.git_ref {
+            source.git_ref = GitRef::parse(git_ref);
+        }
+        Ok(source)
+    }
 }
 
 /// Summary info about a skill
@@ -533,63 +1301,238 @@ pub struct SkillInfo {
     pub runtime: SkillRuntime,
 }
 
+/// Replace `{{name}}` placeholders in a template value
+///
+/// Unknown names are an error; an unterminated `{{` is kept literally.
+fn substitute_template_vars(input: &str, vars: &HashMap<String, String>) -> Result<String> {
+    let mut result = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + 2 + len].trim();
+        let value = vars
+            .get(name)
+            .with_context(|| format!("Unknown template variable '{{{{{}}}}}'", name))?;
+        result.push_str(&rest[..start]);
+        result.push_str(value);
+        rest = &rest[start + 2 + len + 2..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Path of the personal manifest merged beneath project manifests
+///
+/// `SKILL_GLOBAL_MANIFEST` overrides the default `~/.skill-engine/global.toml`.
+pub fn global_manifest_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("SKILL_GLOBAL_MANIFEST") {
+        return Some(PathBuf::from(path));
+    }
+    dirs::home_dir().map(|home| home.join(".skill-engine").join("global.toml"))
+}
+
 /// Expand environment variable references in a string.
 ///
 /// Supports formats:
 /// - `${VAR}` - Required env var, errors if not set
-/// - `${VAR:-default}` - With default value
+/// - `${VAR:-default}` - Default when unset or empty; the default may itself
+///   contain references (`${A:-${B:-fallback}}`)
 /// - `${VAR:?error message}` - Required with custom error
+/// - `${PREFIX_${ENV}}` - Nested references in the variable name
+/// - `$${VAR}` - Escaped, produces the literal text `${VAR}`
+///
+/// The error lists every unresolved placeholder in `input`.
 pub fn expand_env_vars(input: &str) -> Result<String> {
+    let mut unresolved = Vec::new();
+    let expanded = expand_env_vars_lenient(input, &mut unresolved);
+    match unresolved.as_slice() {
+        [] => Ok(expanded),
+        [var] => match &var.message {
+            Some(message) => {
+                anyhow::bail!("Environment variable {} not set: {}", var.name, message)
+            }
+            None => anyhow::bail!("Environment variable {} not set", var.name),
+        },
+        vars => {
+            let names: Vec<&str> = vars.iter().map(|v| v.name.as_str()).collect();
+            anyhow::bail!("Environment variables not set: {}", names.join(", "))
+        }
+    }
+}
+
+/// A required `${VAR}` placeholder whose variable is not set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedVar {
+    /// Variable name
+    pub name: String,
+    /// Message from `${VAR:?message}`
+    pub message: Option<String>,
+}
+
+impl std::fmt::Display for UnresolvedVar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "${{{}}}", self.name)?;
+        if let Some(message) = &self.message {
+            write!(f, " ({})", message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Expand like [`expand_env_vars`], but never fail
+///
+/// Unresolved required placeholders expand to an empty string and are
+/// appended to `unresolved`.
+pub fn expand_env_vars_lenient(input: &str, unresolved: &mut Vec<UnresolvedVar>) -> String {
     let mut result = String::with_capacity(input.len());
-    let mut chars = input.chars().peekable();
-
-    while let Some(c) = chars.next() {
-        if c == '$' && chars.peek() == Some(&'{') {
-            chars.next(); // consume '{'
-
-            let mut var_expr = String::new();
-            let mut depth = 1;
-
-            for c in chars.by_ref() {
-                if c == '{' {
-                    depth += 1;
-                    var_expr.push(c);
-                } else if c == '}' {
-                    depth -= 1;
-                    if depth == 0 {
-                        break;
-                    }
-                    var_expr.push(c);
-                } else {
-                    var_expr.push(c);
+    let mut rest = input;
+
+    while let Some(pos) = rest.find('$') {
+        result.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+
+        if let Some(escaped) = after.strip_prefix("${") {
+            // $${VAR} -> literal ${VAR}
+            result.push_str("${");
+            rest = escaped;
+        } else if let Some(expr_start) = after.strip_prefix('{') {
+            match matching_brace(expr_start) {
+                Some(end) => {
+                    result.push_str(&expand_expression(&expr_start[..end], unresolved));
+                    rest = &expr_start[end + 1..];
+                }
+                None => {
+                    // Unterminated placeholder, keep as written
+                    result.push_str(&rest[pos..]);
+                    rest = "";
+                }
+            }
+        } else {
+            result.push('$');
+            rest = after;
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Index of the `}` closing an expression, accounting for nested `${...}`
+fn matching_brace(expr: &str) -> Option<usize> {
+    let mut depth = 1;
+    for (i, c) in expr.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
                 }
             }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Evaluate the inside of `${...}`
+fn expand_expression(expr: &str, unresolved: &mut Vec<UnresolvedVar>) -> String {
+    // Operator: the first `:-` or `:?` outside nested references
+    let mut depth = 0;
+    let mut operator = None;
+    for (i, c) in expr.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ':' if depth == 0
+                && (expr[i + 1..].starts_with('-') || expr[i + 1..].starts_with('?')) =>
+            {
+                operator = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let (name_expr, op, operand) = match operator {
+        Some(i) => (&expr[..i], &expr[i + 1..i + 2], &expr[i + 2..]),
+        None => (expr, "", ""),
+    };
+    let name = expand_env_vars_lenient(name_expr, unresolved);
+    let value = std::env::var(&name).ok();
+
+    match op {
+        "-" => match value.filter(|v| !v.is_empty()) {
+            Some(value) => value,
+            None => expand_env_vars_lenient(operand, unresolved),
+        },
+        "?" => value.unwrap_or_else(|| {
+            unresolved.push(UnresolvedVar {
+                name,
+                message: Some(operand.to_string()),
+            });
+            String::new()
+        }),
+        _ => value.unwrap_or_else(|| {
+            unresolved.push(UnresolvedVar {
+                name,
+                message: None,
+            });
+            String::new()
+        }),
+    }
+}
+
+/// Expands placeholders while resolving one instance
+///
+/// In strict mode every unresolved placeholder is collected and reported
+/// together by [`EnvResolver::finish`]; otherwise the first failure is
+/// returned as before.
+struct EnvResolver {
+    strict: bool,
+    unresolved: Vec<String>,
+}
+
+impl EnvResolver {
+    fn new(strict: bool) -> Self {
+        Self {
+            strict,
+            unresolved: Vec::new(),
+        }
+    }
+
+    fn expand(&mut self, field: &str, value: &str) -> Result<String> {
+        if !self.strict {
+            return expand_env_vars(value);
+        }
+        let mut missing = Vec::new();
+        let expanded = expand_env_vars_lenient(value, &mut missing);
+        self.unresolved
+            .extend(missing.iter().map(|var| format!("{}: {}", field, var)));
+        Ok(expanded)
+    }
 
-            // Parse the variable expression
-            let value = if let Some(pos) = var_expr.find(":-") {
-                // ${VAR:-default}
-                let var_name = &var_expr[..pos];
-                let default_value = &var_expr[pos + 2..];
-                std::env::var(var_name).unwrap_or_else(|_| default_value.to_string())
-            } else if let Some(pos) = var_expr.find(":?") {
-                // ${VAR:?error}
-                let var_name = &var_expr[..pos];
-                let error_msg = &var_expr[pos + 2..];
-                std::env::var(var_name)
-                    .with_context(|| format!("Environment variable {} not set: {}", var_name, error_msg))?
-            } else {
-                // ${VAR}
-                std::env::var(&var_expr)
-                    .with_context(|| format!("Environment variable {} not set", var_expr))?
-            };
-
-            result.push_str(&value);
+    /// For fields that fall back to a raw value outside strict mode
+    fn expand_optional(&mut self, field: &str, value: &str) -> Option<String> {
+        if self.strict {
+            self.expand(field, value).ok()
         } else {
-            result.push(c);
+            expand_env_vars(value).ok()
         }
     }
 
-    Ok(result)
+    fn finish(self, skill_name: &str, instance_name: &str) -> Result<()> {
+        if self.unresolved.is_empty() {
+            return Ok(());
+        }
+        anyhow::bail!(
+            "Unresolved environment variables for skill '{}' (instance '{}'):\n  {}",
+            skill_name,
+            instance_name,
+            self.unresolved.join("\n  ")
+        )
+    }
 }
 
 /// Check if a config key is likely a secret
@@ -637,6 +1580,285 @@ mod tests {
         assert_eq!(aws.instances.len(), 2);
     }
 
+    #[test]
+    fn test_resolve_network_allow_list() {
+        let toml = r#"
+            [defaults]
+            capabilities.blocked_hosts = ["169.254.169.254"]
+
+            [skills.github]
+            source = "./skills/github"
+
+            [skills.github.instances.default]
+            capabilities.allowed_hosts = ["api.github.com:443"]
+        "#;
+
+        let manifest = SkillManifest::parse(toml).unwrap();
+        let resolved = manifest.resolve_instance("github", Some("default")).unwrap();
+        let network = resolved.config.capabilities.network.as_ref().unwrap();
+
+        assert!(network.enabled);
+        assert_eq!(network.allowed_hosts, Some(vec!["api.github.com:443".to_string()]));
+        assert_eq!(network.blocked_hosts, Some(vec!["169.254.169.254".to_string()]));
+    }
+
+    #[test]
+    fn test_resolve_http_credentials() {
+        let toml = r#"
+            [skills.github]
+            source = "./skills/github"
+
+            [skills.github.instances.default]
+            config.token = "ghp_secret"
+            capabilities.allowed_hosts = ["api.github.com:443", "uploads.github.com:443"]
+
+            [[skills.github.instances.default.capabilities.http_credentials]]
+            host = "api.github.com"
+            config_key = "token"
+
+            [[skills.github.instances.default.capabilities.http_credentials]]
+            host = "uploads.github.com"
+            config_key = "token"
+            header = "X-Upload-Token"
+            format = "{value}"
+        "#;
+
+        let manifest = SkillManifest::parse(toml).unwrap();
+        let resolved = manifest.resolve_instance("github", Some("default")).unwrap();
+        let credentials = &resolved.config.capabilities.http_credentials;
+
+        assert_eq!(credentials[0], HttpCredential::bearer("api.github.com", "token"));
+        assert_eq!(credentials[1].header, "X-Upload-Token");
+        assert_eq!(credentials[1].format, "{value}");
+    }
+
+    #[test]
+    fn test_resolve_manifest_dependencies() {
+        let toml = r#"
+            [skills.deploy]
+            source = "./deploy"
+            depends_on = ["kubernetes", { name = "helm", source = "github:org/helm-skill" }]
+
+            [skills.helm]
+            source = "./helm"
+            dependencies = ["kubernetes"]
+
+            [skills.kubernetes]
+            source = "./kubernetes"
+
+            [skills.broken]
+            source = "./broken"
+            depends_on = ["skill-that-is-not-installed-anywhere"]
+        "#;
+
+        let manifest = SkillManifest::parse(toml).unwrap();
+        let deploy = manifest.get_skill("deploy").unwrap();
+        assert_eq!(deploy.depends_on[1].source(), Some("github:org/helm-skill"));
+
+        assert_eq!(
+            manifest.resolve_dependencies("deploy").unwrap(),
+            vec!["kubernetes", "helm"]
+        );
+        assert!(manifest.resolve_dependencies("broken").is_err());
+
+        let resolved = manifest.resolve_instance("helm", None).unwrap();
+        assert_eq!(resolved.config.metadata.dependencies, vec!["kubernetes"]);
+
+        // Dependencies from outside the manifest are the installer's concern
+        assert_eq!(
+            manifest.install_waves().unwrap(),
+            vec![vec!["broken", "kubernetes"], vec!["helm"], vec!["deploy"]]
+        );
+        let cyclic = SkillManifest::parse(
+            r#"
+            [skills.a]
+            source = "./a"
+            depends_on = ["b"]
+
+            [skills.b]
+            source = "./b"
+            depends_on = ["a"]
+        "#,
+        )
+        .unwrap();
+        assert!(cyclic.install_waves().is_err());
+    }
+
+    #[test]
+    fn test_parse_mcp_config() {
+        let manifest = SkillManifest::parse(
+            r#"
+            [mcp]
+            tool-mode = "both"
+            discovery = "lazy"
+            roots = "read-write"
+
+            [mcp.aliases]
+            "kubernetes:get" = "k8s_get"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.mcp.tool_mode, McpToolMode::Both);
+        assert!(manifest.mcp.tool_mode.flat() && manifest.mcp.tool_mode.dispatcher());
+        assert_eq!(manifest.mcp.aliases["kubernetes:get"], "k8s_get");
+        assert_eq!(manifest.mcp.discovery, McpDiscovery::Lazy);
+        assert_eq!(manifest.mcp.roots, McpRoots::ReadWrite);
+
+        let mut base = SkillManifest::parse("").unwrap();
+        assert_eq!(base.mcp.tool_mode, McpToolMode::Dispatcher);
+        assert_eq!(base.mcp.roots, McpRoots::Off);
+        base.merge(manifest);
+        assert_eq!(base.mcp.tool_mode, McpToolMode::Both);
+        assert_eq!(base.mcp.discovery, McpDiscovery::Lazy);
+        assert_eq!(base.mcp.roots, McpRoots::ReadWrite);
+        assert!(!toml::to_string(&SkillManifest::parse("").unwrap())
+            .unwrap()
+            .contains("[mcp]"));
+    }
+
+    #[test]
+    fn test_parse_history_retention() {
+        let manifest = SkillManifest::parse(
+            r#"
+            [history]
+            max-rows = 5000
+            max-db-size-mb = 0
+        "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.history.max_rows, 5000);
+        assert_eq!(manifest.history.max_db_size_mb, 0);
+        assert_eq!(
+            manifest.history.max_age_days,
+            HistoryRetention::default().max_age_days
+        );
+
+        let mut base = SkillManifest::parse("").unwrap();
+        base.merge(manifest);
+        assert_eq!(base.history.max_rows, 5000);
+    }
+
+    #[test]
+    fn test_parse_artifact_outputs() {
+        let toml = r#"
+            [artifacts]
+            max-age-days = 30
+
+            [skills.terraform]
+            source = "./terraform"
+            runtime = "native"
+            outputs = ["*.tfplan", "reports/**"]
+        "#;
+
+        let manifest = SkillManifest::parse(toml).unwrap();
+        assert_eq!(manifest.artifacts.max_age_days, 30);
+        assert_eq!(
+            manifest.artifacts.max_size_mb,
+            ArtifactRetention::default().max_size_mb
+        );
+        let resolved = manifest.resolve_instance("terraform", None).unwrap();
+        assert_eq!(resolved.outputs, vec!["*.tfplan", "reports/**"]);
+
+        let docker = DockerRuntimeConfig::default().with_output_dir(Path::new("/tmp/out"));
+        assert_eq!(docker.volumes, vec!["/tmp/out:/outputs"]);
+        assert_eq!(docker.environment, vec!["SKILL_OUTPUT_DIR=/outputs"]);
+    }
+
+    #[test]
+    fn test_parse_security_section() {
+        let toml = r#"
+            [security]
+            allowed-commands = ["kubectl", "helm*"]
+            strict = true
+        "#;
+
+        let manifest = SkillManifest::parse(toml).unwrap();
+        let allowlist = manifest.command_allowlist();
+        assert!(allowlist.is_strict());
+        assert!(allowlist.is_allowed("helm3"));
+        assert!(!allowlist.is_allowed("git"));
+
+        // Without a [security] section the built-in list applies
+        let manifest = SkillManifest::parse("").unwrap();
+        assert!(manifest.command_allowlist().is_allowed("git"));
+    }
+
+    #[test]
+    fn test_parse_git_source_config() {
+        let toml = r#"
+            [skills.internal]
+            source = "github:acme/internal-skills"
+            ref = "v1.0.0"
+            git = { ssh_key = "~/.ssh/deploy", depth = 1, submodules = false }
+
+            [skills.public]
+            source = "github:acme/public-skill"
+        "#;
+
+        let manifest = SkillManifest::parse(toml).unwrap();
+        let resolved = manifest.resolve_instance("internal", None).unwrap();
+        assert!(resolved.git.uses_ssh());
+        assert_eq!(resolved.git.depth, Some(1));
+        assert!(!resolved.git.submodules);
+        // The manifest's `ref` is applied to the parsed source
+        assert_eq!(
+            resolved.git_source().unwrap().git_ref,
+            GitRef::Tag("v1.0.0".to_string())
+        );
+
+        let resolved = manifest.resolve_instance("public", None).unwrap();
+        assert_eq!(resolved.git, GitSourceConfig::default());
+        assert_eq!(resolved.git_source().unwrap().git_ref, GitRef::DefaultBranch);
+    }
+
+    #[test]
+    fn test_parse_native_security_policy() {
+        let toml = r#"
+            [skills.kubernetes]
+            source = "./skills/kubernetes"
+            runtime = "native"
+
+            [skills.kubernetes.native]
+            seccomp = "default"
+            apparmor_profile = "skill-kubectl"
+
+            [skills.git]
+            source = "./skills/git"
+            runtime = "native"
+        "#;
+
+        let manifest = SkillManifest::parse(toml).unwrap();
+
+        let resolved = manifest.resolve_instance("kubernetes", None).unwrap();
+        assert!(resolved.native.uses_seccomp());
+        assert!(!resolved.native.no_new_privileges);
+        assert_eq!(resolved.native.apparmor_profile.as_deref(), Some("skill-kubectl"));
+
+        let git = manifest.native_policy("git");
+        assert_eq!(git, NativeSecurityPolicy::default());
+    }
+
+    #[test]
+    fn test_parse_skill_limits() {
+        let toml = r#"
+            [skills.kubernetes]
+            source = "./skills/kubernetes"
+            runtime = "native"
+
+            [skills.kubernetes.limits]
+            max_output_size = "1mb"
+
+            [skills.git]
+            source = "./skills/git"
+            runtime = "native"
+        "#;
+
+        let manifest = SkillManifest::parse(toml).unwrap();
+        let resolved = manifest.resolve_instance("kubernetes", None).unwrap();
+        assert_eq!(resolved.limits.max_output_bytes(), Some(1024 * 1024));
+        assert_eq!(manifest.execution_limits("git").max_output_bytes(), None);
+    }
+
     #[test]
     fn test_expand_env_vars() {
         std::env::set_var("TEST_VAR", "hello");
@@ -650,6 +1872,186 @@ mod tests {
         std::env::remove_var("TEST_VAR");
     }
 
+    #[test]
+    fn test_expand_env_vars_nested_and_escaped() {
+        std::env::set_var("TEST_NESTED_ENV", "prod");
+        std::env::set_var("TEST_NESTED_URL_prod", "https://prod.example.com");
+        std::env::set_var("TEST_NESTED_EMPTY", "");
+
+        assert_eq!(
+            expand_env_vars("${TEST_NESTED_URL_${TEST_NESTED_ENV}}").unwrap(),
+            "https://prod.example.com"
+        );
+        assert_eq!(
+            expand_env_vars("${MISSING_A:-${MISSING_B:-${TEST_NESTED_ENV}}}").unwrap(),
+            "prod"
+        );
+        assert_eq!(
+            expand_env_vars("${TEST_NESTED_EMPTY:-fallback}").unwrap(),
+            "fallback"
+        );
+        assert_eq!(
+            expand_env_vars("cost: $5, $${HOME}").unwrap(),
+            "cost: $5, ${HOME}"
+        );
+        assert_eq!(expand_env_vars("${UNTERMINATED").unwrap(), "${UNTERMINATED");
+
+        let err = expand_env_vars("${MISSING_A}/${MISSING_B:?set it}").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Environment variables not set: MISSING_A, MISSING_B"
+        );
+
+        let mut unresolved = Vec::new();
+        assert_eq!(
+            expand_env_vars_lenient("a${MISSING_A}b${MISSING_B:?set it}", &mut unresolved),
+            "ab"
+        );
+        assert_eq!(unresolved[1].to_string(), "${MISSING_B} (set it)");
+
+        std::env::remove_var("TEST_NESTED_ENV");
+        std::env::remove_var("TEST_NESTED_URL_prod");
+        std::env::remove_var("TEST_NESTED_EMPTY");
+    }
+
+    #[test]
+    fn test_merge_layered_manifests() {
+        let global_dir = tempfile::TempDir::new().unwrap();
+        let project_dir = tempfile::TempDir::new().unwrap();
+        let global = global_dir.path().join("global.toml");
+        let project = project_dir.path().join(".skill-engine.toml");
+        std::fs::write(
+            &global,
+            r#"
+[defaults]
+env.LOG_LEVEL = "debug"
+env.EDITOR = "vim"
+capabilities.allowed_hosts = ["api.github.com"]
+
+[skills.notes]
+source = "./skills/notes"
+
+[skills.aws]
+source = "github:me/aws-skill"
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            &project,
+            r#"
+[defaults]
+env.LOG_LEVEL = "info"
+capabilities.allowed_hosts = ["*.amazonaws.com"]
+
+[skills.aws]
+source = "github:org/aws-skill@v2.0.0"
+"#,
+        )
+        .unwrap();
+
+        let manifest = SkillManifest::load_layered(&[global.clone(), project.clone()]).unwrap();
+
+        assert_eq!(manifest.layers, vec![global.clone(), project.clone()]);
+        assert_eq!(manifest.skills["aws"].source, "github:org/aws-skill@v2.0.0");
+        assert_eq!(manifest.skill_origins["aws"], project);
+        assert_eq!(manifest.skill_origins["notes"], global);
+        assert!(manifest.skills["notes"].source.ends_with("skills/notes"));
+        assert!(Path::new(&manifest.skills["notes"].source).is_absolute());
+        assert_eq!(manifest.defaults.env["LOG_LEVEL"], "info");
+        assert_eq!(manifest.defaults.env["EDITOR"], "vim");
+        assert_eq!(
+            manifest.defaults.capabilities.allowed_hosts,
+            vec!["api.github.com", "*.amazonaws.com"]
+        );
+    }
+
+    #[test]
+    fn test_instance_templates() {
+        let toml = r#"
+[templates.aws-account]
+description = "{{account}} account"
+config.region = "{{region}}"
+config.profile = "{{account}}-{{instance}}"
+config.role_arn = "arn:aws:iam::{{account_id}}:role/skill"
+capabilities.allowed_hosts = ["*.{{region}}.amazonaws.com"]
+
+[skills.aws]
+source = "github:org/aws-skill"
+
+[skills.aws.instances.prod]
+template = "aws-account"
+vars = { account = "prod", region = "us-east-1", account_id = "111111111111" }
+
+[skills.aws.instances.staging]
+template = "aws-account"
+vars = { account = "staging", region = "${AWS_REGION:-us-west-2}", account_id = "222222222222" }
+config.profile = "staging-readonly"
+"#;
+
+        let manifest = SkillManifest::parse(toml).unwrap();
+        let prod = &manifest.skills["aws"].instances["prod"];
+        assert_eq!(prod.config["region"], "us-east-1");
+        assert_eq!(prod.config["profile"], "prod-prod");
+        assert_eq!(
+            prod.config["role_arn"],
+            "arn:aws:iam::111111111111:role/skill"
+        );
+        assert_eq!(prod.description.as_deref(), Some("prod account"));
+        assert_eq!(
+            prod.capabilities.allowed_hosts,
+            vec!["*.us-east-1.amazonaws.com"]
+        );
+        assert!(prod.template.is_none());
+
+        let staging = &manifest.skills["aws"].instances["staging"];
+        assert_eq!(staging.config["profile"], "staging-readonly");
+        assert_eq!(staging.config["region"], "${AWS_REGION:-us-west-2}");
+
+        let err =
+            SkillManifest::parse(&toml.replace(", account_id = \"222222222222\"", "")).unwrap_err();
+        assert!(format!("{:#}", err).contains("Unknown template variable '{{account_id}}'"));
+
+        let err =
+            SkillManifest::parse(&toml.replace("template = \"aws-account\"", "template = \"gcp\""))
+                .unwrap_err();
+        assert!(err.to_string().contains("unknown template 'gcp'"));
+    }
+
+    #[test]
+    fn test_strict_env_reports_all_unresolved() {
+        let manifest = SkillManifest::parse(
+            r#"
+[defaults]
+strict_env = true
+
+[skills.api]
+source = "./api"
+
+[skills.api.instances.default]
+config.token = "${STRICT_MISSING_TOKEN}"
+config.url = "${STRICT_MISSING_URL:-https://api.example.com}"
+env.REGION = "${STRICT_MISSING_REGION:?region is required}"
+"#,
+        )
+        .unwrap();
+
+        let err = manifest
+            .resolve_instance("api", None)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("config.token: ${STRICT_MISSING_TOKEN}"),
+            "{}",
+            err
+        );
+        assert!(
+            err.contains("env.REGION: ${STRICT_MISSING_REGION} (region is required)"),
+            "{}",
+            err
+        );
+        assert!(!err.contains("STRICT_MISSING_URL"), "{}", err);
+    }
+
     #[test]
     fn test_is_likely_secret() {
         assert!(is_likely_secret("api_key"));
@@ -1,5 +1,7 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 
+use crate::types::ResourceUsage;
+
 /// Performance metrics for skill execution
 pub struct ExecutionMetrics {
     /// Duration in milliseconds of the most recent cold start
@@ -10,6 +12,12 @@ pub struct ExecutionMetrics {
     pub total_executions: AtomicU64,
     /// Total number of failed executions
     pub failed_executions: AtomicU64,
+    /// CPU time in milliseconds summed over executions where it was measured
+    pub total_cpu_time_ms: AtomicU64,
+    /// Highest peak memory of any single execution, in kilobytes
+    pub max_peak_memory_kb: AtomicU64,
+    /// Bytes of output summed over all executions
+    pub total_output_bytes: AtomicU64,
 }
 
 impl ExecutionMetrics {
@@ -20,6 +28,9 @@ impl ExecutionMetrics {
             warm_start_ms: AtomicU64::new(0),
             total_executions: AtomicU64::new(0),
             failed_executions: AtomicU64::new(0),
+            total_cpu_time_ms: AtomicU64::new(0),
+            max_peak_memory_kb: AtomicU64::new(0),
+            total_output_bytes: AtomicU64::new(0),
         }
     }
 
@@ -41,6 +52,34 @@ impl ExecutionMetrics {
         }
     }
 
+    /// Adds an execution's measured resources to the totals
+    pub fn record_resource_usage(&self, usage: &ResourceUsage) {
+        if let Some(cpu) = usage.cpu_time_ms {
+            self.total_cpu_time_ms.fetch_add(cpu, Ordering::Relaxed);
+        }
+        if let Some(memory) = usage.peak_memory_kb {
+            self.max_peak_memory_kb.fetch_max(memory, Ordering::Relaxed);
+        }
+        if let Some(bytes) = usage.output_bytes {
+            self.total_output_bytes.fetch_add(bytes, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the CPU time summed over all metered executions in milliseconds
+    pub fn get_total_cpu_time_ms(&self) -> u64 {
+        self.total_cpu_time_ms.load(Ordering::Relaxed)
+    }
+
+    /// Returns the highest peak memory of a single execution in kilobytes
+    pub fn get_max_peak_memory_kb(&self) -> u64 {
+        self.max_peak_memory_kb.load(Ordering::Relaxed)
+    }
+
+    /// Returns the output bytes summed over all executions
+    pub fn get_total_output_bytes(&self) -> u64 {
+        self.total_output_bytes.load(Ordering::Relaxed)
+    }
+
     /// Returns the most recent cold start duration in milliseconds
     pub fn get_cold_start_ms(&self) -> u64 {
         self.cold_start_ms.load(Ordering::Relaxed)
@@ -0,0 +1,525 @@
+//! Shell-free argument handling for native skills
+//!
+//! Native commands are carried as argv arrays from the moment they are built
+//! until they reach `Command::new(program).args(args)`. No shell is involved,
+//! so values containing spaces, quotes or metacharacters (`;`, `|`, `$(..)`)
+//! are passed to the program verbatim as a single argument.
+//!
+//! Two entry points produce a [`NativeCommand`]:
+//!
+//! - [`build_native_command`] maps tool arguments to flags and positionals
+//! - [`NativeCommand::parse`] splits a `Command: ...` line emitted by a WASM
+//!   skill using POSIX-style quoting (`'single'`, `"double"`, `\` escapes),
+//!   without any expansion
+//!
+//! On Windows the program is looked up with `PATHEXT` before spawning (see
+//! [`find_program`]), so CLIs installed as `.cmd` or `.bat` shims run like
+//! they do from a console.
+
+use anyhow::Result;
+use std::ffi::OsStr;
+use std::fmt;
+use std::path::PathBuf;
+
+/// A program and its argument vector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NativeCommand {
+    program: String,
+    args: Vec<String>,
+}
+
+impl NativeCommand {
+    /// Create a command with no arguments
+    pub fn new(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+        }
+    }
+
+    /// Create a command from a full argv (`argv[0]` is the program)
+    pub fn from_argv(argv: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut argv = argv.into_iter();
+        let program = argv
+            .next()
+            .filter(|program| !program.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Empty command"))?;
+
+        let command = Self {
+            program,
+            args: argv.collect(),
+        };
+        command.validate()?;
+        Ok(command)
+    }
+
+    /// Split a command line into argv using POSIX-style quoting
+    ///
+    /// Quotes group words and backslashes escape the next character; nothing
+    /// is expanded and shell operators are kept as literal arguments.
+    pub fn parse(line: &str) -> Result<Self> {
+        Self::from_argv(split_words(line)?)
+    }
+
+    /// Append one argument
+    pub fn arg(&mut self, arg: impl Into<String>) -> &mut Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Program to execute
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    /// Arguments passed to the program
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Full argv including the program
+    pub fn argv(&self) -> Vec<String> {
+        std::iter::once(self.program.clone())
+            .chain(self.args.iter().cloned())
+            .collect()
+    }
+
+    /// Build a `tokio::process::Command` for this argv
+    pub fn to_command(&self) -> tokio::process::Command {
+        let mut command = tokio::process::Command::new(program_path(&self.program));
+        command.args(&self.args);
+        command
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some(arg) = self.argv().iter().find(|arg| arg.contains('\0')) {
+            anyhow::bail!("Argument contains a NUL byte: {:?}", arg);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for NativeCommand {
+    /// Shell-quoted rendering for logs and display (never executed)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let words: Vec<String> = self.argv().iter().map(|word| quote(word)).collect();
+        write!(f, "{}", words.join(" "))
+    }
+}
+
+/// Where `program` is found on `PATH`, like a shell would find it
+///
+/// Names containing a path separator are returned as they are if the file
+/// exists. On Windows each `PATHEXT` extension (`.COM;.EXE;.BAT;.CMD` when
+/// unset) is tried for names without one.
+pub fn find_program(program: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    search_path(program, &path, &executable_extensions())
+}
+
+/// Extensions tried for a bare program name
+fn executable_extensions() -> Vec<String> {
+    if cfg!(windows) {
+        std::env::var("PATHEXT")
+            .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+            .split(';')
+            .filter(|ext| !ext.is_empty())
+            .map(str::to_string)
+            .collect()
+    } else {
+        Vec::new()
+    }
+}
+
+fn search_path(program: &str, path: &OsStr, extensions: &[String]) -> Option<PathBuf> {
+    if program.is_empty() {
+        return None;
+    }
+    let candidates = |base: PathBuf| {
+        let has_extension = base.extension().is_some();
+        std::iter::once(base.clone())
+            .filter(move |_| extensions.is_empty() || has_extension)
+            .chain(extensions.iter().map(move |ext| {
+                let mut name = base.clone().into_os_string();
+                name.push(ext);
+                PathBuf::from(name)
+            }))
+    };
+
+    if program.contains(['/', '\\']) {
+        return candidates(PathBuf::from(program)).find(|candidate| candidate.is_file());
+    }
+    std::env::split_paths(path)
+        .flat_map(|dir| candidates(dir.join(program)))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Path to spawn `program` with
+///
+/// Windows only appends `.exe` when it searches `PATH` itself, so programs
+/// such as `az` (`az.cmd`) or npm-installed CLIs wouldn't be found. Rust
+/// quotes arguments for batch files and refuses ones it can't pass safely.
+#[cfg(windows)]
+pub(crate) fn program_path(program: &str) -> PathBuf {
+    if std::path::Path::new(program).extension().is_none() {
+        if let Some(path) = find_program(program) {
+            return path;
+        }
+    }
+    PathBuf::from(program)
+}
+
+#[cfg(not(windows))]
+pub(crate) fn program_path(program: &str) -> PathBuf {
+    PathBuf::from(program)
+}
+
+/// Map a skill name to its base CLI program
+pub fn base_command(skill_name: &str) -> &str {
+    match skill_name {
+        "kubernetes" => "kubectl",
+        "aws" => "aws",
+        "docker" => "docker",
+        "terraform" => "terraform",
+        "helm" => "helm",
+        "git" => "git",
+        "postgres-native" => "psql",
+        _ => skill_name,
+    }
+}
+
+/// Build a native command from skill name, tool name, and arguments
+///
+/// Uses generic passthrough: base_command + tool_name + args.
+/// Args convention:
+///   - "arg", "resource" or empty key: positional argument (value only);
+///     values starting with `-` are rejected so they can't act as flags
+///   - single char key: short flag (-k value)
+///   - multi char key: long flag (--key value)
+///   - value "true"/"false" with flag key: boolean flag (present/omitted)
+///
+/// Every value becomes exactly one argv element, whatever it contains.
+pub fn build_native_command(
+    skill_name: &str,
+    tool_name: &str,
+    args: &[(String, String)],
+) -> Result<NativeCommand> {
+    let mut command = NativeCommand::new(base_command(skill_name));
+    command.arg(tool_name);
+
+    for (key, value) in args {
+        if key == "arg" || key == "resource" || key.is_empty() {
+            // Positional argument - just add the value
+            // Note: "resource" is special-cased for kubectl which expects resource type as positional
+            check_positional(value)?;
+            command.arg(value.as_str());
+            continue;
+        }
+
+        let flag = flag_name(key)?;
+        match value.as_str() {
+            "true" => {
+                command.arg(flag);
+            }
+            "false" => {}
+            _ => {
+                command.arg(flag).arg(value.as_str());
+            }
+        }
+    }
+
+    command.validate()?;
+    Ok(command)
+}
+
+/// Refuse positional values the target CLI would parse as options, such as
+/// `--kubeconfig=/tmp/x`; a lone `-` (stdin) is fine
+pub fn check_positional(value: &str) -> Result<()> {
+    if value.starts_with('-') && value != "-" {
+        anyhow::bail!(
+            "Positional argument '{}' looks like a flag; pass options by name instead",
+            value
+        );
+    }
+    Ok(())
+}
+
+/// Render an argument key as `-k` or `--key`, rejecting anything that is not a plain flag name
+fn flag_name(key: &str) -> Result<String> {
+    let valid = key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && !key.starts_with('-');
+    if !valid {
+        anyhow::bail!("Invalid argument name '{}'", key);
+    }
+
+    Ok(if key.len() == 1 {
+        format!("-{}", key)
+    } else {
+        format!("--{}", key)
+    })
+}
+
+/// Split a line into words, honouring quotes and backslash escapes
+fn split_words(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => anyhow::bail!("Unterminated single quote in command"),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => anyhow::bail!("Unterminated double quote in command"),
+                        },
+                        Some(c) => word.push(c),
+                        None => anyhow::bail!("Unterminated double quote in command"),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                match chars.next() {
+                    Some('\n') => {}
+                    Some(c) => word.push(c),
+                    None => anyhow::bail!("Trailing backslash in command"),
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Quote a word so that `split_words` (or a POSIX shell) reads it back unchanged
+fn quote(word: &str) -> String {
+    let safe = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=,@%+".contains(c));
+    if safe {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn args(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_build_keeps_values_as_single_arguments() {
+        let command = build_native_command(
+            "kubernetes",
+            "get",
+            &args(&[
+                ("resource", "pods"),
+                ("l", "app in (web, api)"),
+                ("namespace", "default; rm -rf /"),
+                ("watch", "false"),
+                ("A", "true"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(command.program(), "kubectl");
+        assert_eq!(
+            command.args(),
+            &[
+                "get",
+                "pods",
+                "-l",
+                "app in (web, api)",
+                "--namespace",
+                "default; rm -rf /",
+                "-A"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_rejects_invalid_keys() {
+        assert!(build_native_command("git", "log", &args(&[("--exec", "x")])).is_err());
+        assert!(build_native_command("git", "log", &args(&[("a b", "x")])).is_err());
+        assert!(build_native_command("git", "log", &args(&[("arg", "a\0b")])).is_err());
+    }
+
+    #[test]
+    fn test_build_rejects_flag_like_positionals() {
+        for value in ["--kubeconfig=/tmp/x", "-o=yaml", "--", "-A"] {
+            let err = build_native_command("kubernetes", "get", &args(&[("resource", value)]))
+                .unwrap_err();
+            assert!(err.to_string().contains("looks like a flag"), "{}", value);
+        }
+        assert!(build_native_command("git", "log", &args(&[("", "--exec=sh")])).is_err());
+
+        // Stdin and flag values are unaffected
+        let command =
+            build_native_command("kubernetes", "apply", &args(&[("f", "-"), ("arg", "-")]))
+                .unwrap();
+        assert_eq!(command.args(), &["apply", "-f", "-", "-"]);
+    }
+
+    #[test]
+    fn test_parse_quoting() {
+        let command =
+            NativeCommand::parse(r#"kubectl get pods -l 'app in (a, b)' -n "my ns" x\ y"#).unwrap();
+        assert_eq!(command.program(), "kubectl");
+        assert_eq!(
+            command.args(),
+            &["get", "pods", "-l", "app in (a, b)", "-n", "my ns", "x y"]
+        );
+
+        // Shell operators and expansions stay literal
+        let command = NativeCommand::parse("kubectl get pods; rm -rf / $(id) | sh").unwrap();
+        assert_eq!(
+            command.args(),
+            &["get", "pods;", "rm", "-rf", "/", "$(id)", "|", "sh"]
+        );
+
+        assert_eq!(
+            NativeCommand::parse(r#"echo "" ''"#).unwrap().args(),
+            &["", ""]
+        );
+        assert!(NativeCommand::parse("kubectl get 'pods").is_err());
+        assert!(NativeCommand::parse("   ").is_err());
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        let mut command = NativeCommand::new("kubectl");
+        command.arg("get").arg("it's here").arg("").arg("-n");
+        assert_eq!(command.to_string(), r"kubectl get 'it'\''s here' '' -n");
+        assert_eq!(NativeCommand::parse(&command.to_string()).unwrap(), command);
+    }
+
+    #[test]
+    fn test_search_path() {
+        let bin = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        std::fs::write(bin.path().join("kubectl"), "").unwrap();
+        std::fs::write(bin.path().join("az.cmd"), "").unwrap();
+        let path = std::env::join_paths([other.path(), bin.path()]).unwrap();
+
+        // Unix: exact names only
+        assert_eq!(
+            search_path("kubectl", &path, &[]),
+            Some(bin.path().join("kubectl"))
+        );
+        assert_eq!(search_path("az", &path, &[]), None);
+
+        // Windows: PATHEXT extensions for bare names
+        let pathext = vec![".EXE".to_string(), ".cmd".to_string()];
+        assert_eq!(
+            search_path("az", &path, &pathext),
+            Some(bin.path().join("az.cmd"))
+        );
+        assert_eq!(
+            search_path("az.cmd", &path, &pathext),
+            Some(bin.path().join("az.cmd"))
+        );
+        assert_eq!(search_path("kubectl", &path, &pathext), None);
+
+        let explicit = bin.path().join("az");
+        assert_eq!(
+            search_path(explicit.to_str().unwrap(), &path, &pathext),
+            Some(bin.path().join("az.cmd"))
+        );
+        assert_eq!(search_path("", &path, &[]), None);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_values_are_never_split(value in "[^\0]*") {
+            let command = build_native_command(
+                "kubernetes",
+                "get",
+                &args(&[("resource", "pods"), ("namespace", value.as_str())]),
+            )
+            .unwrap();
+            prop_assert_eq!(command.args().len(), 4);
+            prop_assert_eq!(&command.args()[3], &value);
+        }
+
+        #[test]
+        fn prop_parse_inverts_display(
+            argv in proptest::collection::vec("[^\0]*", 0..6),
+            program in "[a-z][a-z0-9-]{0,10}",
+        ) {
+            let command = NativeCommand::from_argv(
+                std::iter::once(program).chain(argv),
+            )
+            .unwrap();
+            prop_assert_eq!(NativeCommand::parse(&command.to_string()).unwrap(), command);
+        }
+
+        #[test]
+        fn prop_injection_payloads_stay_in_one_argument(
+            prefix in "[a-z]{0,8}",
+            op in prop::sample::select(vec![
+                ";", "&&", "||", "|", "`id`", "$(id)", "\n", "'", "\"",
+                " --kubeconfig=/tmp/x", "--kubeconfig=/tmp/x", "-o=yaml", "--",
+            ]),
+            suffix in "[ -~]{0,16}",
+        ) {
+            let payload = format!("{}{}{}", prefix, op, suffix);
+            let result = build_native_command(
+                "kubernetes",
+                "logs",
+                &args(&[("arg", payload.as_str())]),
+            );
+            match result {
+                Ok(command) => {
+                    // Never a flag in a positional slot
+                    prop_assert!(!command.args()[1].starts_with('-') || command.args()[1] == "-");
+                    prop_assert_eq!(command.args(), &["logs".to_string(), payload]);
+                }
+                Err(_) => prop_assert!(payload.starts_with('-')),
+            }
+        }
+    }
+}
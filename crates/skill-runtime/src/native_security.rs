@@ -0,0 +1,525 @@
+//! Process hardening for native skill execution
+//!
+//! Native skills run real host binaries (kubectl, aws, terraform). The command
+//! allowlist decides *which* binaries may run; this module constrains *how*
+//! they run, in the spirit of [`DockerSecurityPolicy`](crate::DockerSecurityPolicy):
+//!
+//! - `no_new_privileges`: setuid/setgid binaries and file capabilities cannot
+//!   raise privileges (Linux `PR_SET_NO_NEW_PRIVS`). Off unless the skill
+//!   asks for it, since tools such as `sudo` and some credential helpers
+//!   rely on setuid; a seccomp filter turns it on regardless.
+//! - `seccomp`: a syscall deny-list installed before `exec`. The default
+//!   profile also refuses `clone` with namespace flags and `clone3` (whose
+//!   flags it can't inspect; libc falls back to `clone`), so no new
+//!   namespaces can be created.
+//! - `apparmor_profile`: switch to a named AppArmor profile on `exec`
+//!
+//! ```toml
+//! [skills.kubernetes.native]
+//! no_new_privileges = true
+//! seccomp = "default"
+//! denied_syscalls = ["personality"]
+//! apparmor_profile = "skill-kubectl"
+//! ```
+//!
+//! All three are Linux-only; on other platforms the policy is logged and
+//! skipped so shared manifests keep working on developer machines.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Seccomp filter mode for native commands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeccompMode {
+    /// No seccomp filter (only `denied_syscalls`, if any, are blocked)
+    #[default]
+    Disabled,
+    /// Block kernel administration, tracing and namespace syscalls,
+    /// including `clone` with `CLONE_NEW*` flags
+    Default,
+}
+
+/// Syscalls blocked by [`SeccompMode::Default`]
+///
+/// None of these are needed by CLI tools that talk to remote APIs.
+pub const DEFAULT_DENIED_SYSCALLS: &[&str] = &[
+    "acct",
+    "add_key",
+    "bpf",
+    "chroot",
+    "clock_settime",
+    "delete_module",
+    "finit_module",
+    "init_module",
+    "kexec_load",
+    "keyctl",
+    "mount",
+    "open_by_handle_at",
+    "perf_event_open",
+    "pivot_root",
+    "process_vm_readv",
+    "process_vm_writev",
+    "ptrace",
+    "quotactl",
+    "reboot",
+    "request_key",
+    "setns",
+    "settimeofday",
+    "swapoff",
+    "swapon",
+    "umount2",
+    "unshare",
+    "userfaultfd",
+];
+
+/// Security policy for native command execution
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NativeSecurityPolicy {
+    /// Prevent the command from gaining privileges via setuid binaries (default: false)
+    #[serde(default)]
+    pub no_new_privileges: bool,
+
+    /// Seccomp filter mode (default: disabled)
+    #[serde(default)]
+    pub seccomp: SeccompMode,
+
+    /// Additional syscalls to block, by name (e.g. "socket" to forbid networking)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub denied_syscalls: Vec<String>,
+
+    /// AppArmor profile to switch to on exec (must already be loaded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apparmor_profile: Option<String>,
+}
+
+impl Default for NativeSecurityPolicy {
+    fn default() -> Self {
+        Self {
+            no_new_privileges: false,
+            seccomp: SeccompMode::Disabled,
+            denied_syscalls: Vec::new(),
+            apparmor_profile: None,
+        }
+    }
+}
+
+impl NativeSecurityPolicy {
+    /// Policy with the built-in seccomp profile enabled
+    pub fn hardened() -> Self {
+        Self {
+            seccomp: SeccompMode::Default,
+            ..Default::default()
+        }
+    }
+
+    /// Names of every syscall this policy blocks
+    pub fn denied_syscall_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = match self.seccomp {
+            SeccompMode::Default => DEFAULT_DENIED_SYSCALLS.to_vec(),
+            SeccompMode::Disabled => Vec::new(),
+        };
+        for name in &self.denied_syscalls {
+            if !names.contains(&name.as_str()) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// Whether the filter refuses `clone` calls creating namespaces
+    fn blocks_namespaces(&self) -> bool {
+        self.seccomp == SeccompMode::Default
+    }
+
+    /// Whether a seccomp filter will be installed
+    pub fn uses_seccomp(&self) -> bool {
+        self.seccomp != SeccompMode::Disabled || !self.denied_syscalls.is_empty()
+    }
+
+    /// Check that the policy can be enforced on this host
+    pub fn validate(&self) -> Result<()> {
+        #[cfg(target_os = "linux")]
+        {
+            if self.uses_seccomp() {
+                linux::build_filter(&self.denied_syscall_names(), self.blocks_namespaces())?;
+            }
+            if let Some(profile) = &self.apparmor_profile {
+                linux::check_apparmor(profile)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the policy to a command before it is spawned
+    pub fn apply(&self, command: &mut tokio::process::Command) -> Result<()> {
+        #[cfg(target_os = "linux")]
+        {
+            linux::apply(self, command)
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = command;
+            if self.uses_seccomp() || self.apparmor_profile.is_some() {
+                tracing::warn!(
+                    "seccomp and AppArmor hardening are only available on Linux; running without them"
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use super::NativeSecurityPolicy;
+    use anyhow::{Context, Result};
+    use std::ffi::CString;
+
+    #[cfg(target_arch = "x86_64")]
+    const AUDIT_ARCH: Option<u32> = Some(0xC000_003E);
+    #[cfg(target_arch = "aarch64")]
+    const AUDIT_ARCH: Option<u32> = Some(0xC000_00B7);
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    const AUDIT_ARCH: Option<u32> = None;
+
+    // Classic BPF opcodes and seccomp return values (linux/filter.h, linux/seccomp.h)
+    const BPF_LD_W_ABS: u16 = 0x20; // BPF_LD | BPF_W | BPF_ABS
+    const BPF_JMP_JEQ_K: u16 = 0x15; // BPF_JMP | BPF_JEQ | BPF_K
+    #[cfg(target_arch = "x86_64")]
+    const BPF_JMP_JGE_K: u16 = 0x35; // BPF_JMP | BPF_JGE | BPF_K
+    const BPF_RET_K: u16 = 0x06; // BPF_RET | BPF_K
+    const SECCOMP_RET_KILL_PROCESS: u32 = 0x8000_0000;
+    const SECCOMP_RET_ERRNO: u32 = 0x0005_0000;
+    const SECCOMP_RET_ALLOW: u32 = 0x7fff_0000;
+    #[cfg(target_arch = "x86_64")]
+    const X32_SYSCALL_BIT: u32 = 0x4000_0000;
+
+    const BPF_JMP_JSET_K: u16 = 0x45; // BPF_JMP | BPF_JSET | BPF_K
+
+    // Offsets into struct seccomp_data
+    const OFFSET_NR: u32 = 0;
+    const OFFSET_ARCH: u32 = 4;
+    /// Low 32 bits of the first argument (both supported arches are little-endian)
+    const OFFSET_ARG0_LOW: u32 = 16;
+
+    /// CLONE_NEWNS | CLONE_NEWCGROUP | CLONE_NEWUTS | CLONE_NEWIPC |
+    /// CLONE_NEWUSER | CLONE_NEWPID | CLONE_NEWNET
+    const CLONE_NAMESPACE_FLAGS: u32 = 0x7E02_0000;
+
+    fn syscall_number(name: &str) -> Option<libc::c_long> {
+        let nr = match name {
+            "acct" => libc::SYS_acct,
+            "add_key" => libc::SYS_add_key,
+            "bind" => libc::SYS_bind,
+            "bpf" => libc::SYS_bpf,
+            "chroot" => libc::SYS_chroot,
+            "clock_settime" => libc::SYS_clock_settime,
+            "connect" => libc::SYS_connect,
+            "delete_module" => libc::SYS_delete_module,
+            "finit_module" => libc::SYS_finit_module,
+            "init_module" => libc::SYS_init_module,
+            "kexec_load" => libc::SYS_kexec_load,
+            "keyctl" => libc::SYS_keyctl,
+            "listen" => libc::SYS_listen,
+            "mount" => libc::SYS_mount,
+            "open_by_handle_at" => libc::SYS_open_by_handle_at,
+            "perf_event_open" => libc::SYS_perf_event_open,
+            "personality" => libc::SYS_personality,
+            "pivot_root" => libc::SYS_pivot_root,
+            "process_vm_readv" => libc::SYS_process_vm_readv,
+            "process_vm_writev" => libc::SYS_process_vm_writev,
+            "ptrace" => libc::SYS_ptrace,
+            "quotactl" => libc::SYS_quotactl,
+            "reboot" => libc::SYS_reboot,
+            "request_key" => libc::SYS_request_key,
+            "setns" => libc::SYS_setns,
+            "settimeofday" => libc::SYS_settimeofday,
+            "socket" => libc::SYS_socket,
+            "swapoff" => libc::SYS_swapoff,
+            "swapon" => libc::SYS_swapon,
+            "umount2" => libc::SYS_umount2,
+            "unshare" => libc::SYS_unshare,
+            "userfaultfd" => libc::SYS_userfaultfd,
+            _ => return None,
+        };
+        Some(nr)
+    }
+
+    fn stmt(code: u16, k: u32) -> libc::sock_filter {
+        libc::sock_filter {
+            code,
+            jt: 0,
+            jf: 0,
+            k,
+        }
+    }
+
+    fn jump(code: u16, k: u32, jt: u8, jf: u8) -> libc::sock_filter {
+        libc::sock_filter { code, jt, jf, k }
+    }
+
+    /// Build a filter that kills foreign-arch syscalls and fails denied ones with EPERM
+    ///
+    /// With `block_namespaces`, `clone` with namespace flags also fails with
+    /// EPERM, and `clone3` with ENOSYS so libc retries with `clone`.
+    pub(super) fn build_filter(
+        denied: &[&str],
+        block_namespaces: bool,
+    ) -> Result<Vec<libc::sock_filter>> {
+        let arch =
+            AUDIT_ARCH.context("seccomp filtering is not supported on this CPU architecture")?;
+        let deny = SECCOMP_RET_ERRNO | (libc::EPERM as u32 & 0xffff);
+
+        let mut filter = vec![
+            stmt(BPF_LD_W_ABS, OFFSET_ARCH),
+            jump(BPF_JMP_JEQ_K, arch, 1, 0),
+            stmt(BPF_RET_K, SECCOMP_RET_KILL_PROCESS),
+            stmt(BPF_LD_W_ABS, OFFSET_NR),
+        ];
+
+        // x32 syscalls share the x86_64 arch value; reject them outright
+        #[cfg(target_arch = "x86_64")]
+        {
+            filter.push(jump(BPF_JMP_JGE_K, X32_SYSCALL_BIT, 0, 1));
+            filter.push(stmt(BPF_RET_K, deny));
+        }
+
+        for name in denied {
+            let nr = syscall_number(name).with_context(|| {
+                format!(
+                    "Unknown or unsupported syscall in seccomp policy: '{}'",
+                    name
+                )
+            })?;
+            filter.push(jump(BPF_JMP_JEQ_K, nr as u32, 0, 1));
+            filter.push(stmt(BPF_RET_K, deny));
+        }
+
+        if block_namespaces {
+            let enosys = SECCOMP_RET_ERRNO | (libc::ENOSYS as u32 & 0xffff);
+            filter.push(jump(BPF_JMP_JEQ_K, libc::SYS_clone3 as u32, 0, 1));
+            filter.push(stmt(BPF_RET_K, enosys));
+
+            // clone: deny when any namespace flag is set, else reload the
+            // syscall number and carry on
+            filter.push(jump(BPF_JMP_JEQ_K, libc::SYS_clone as u32, 0, 4));
+            filter.push(stmt(BPF_LD_W_ABS, OFFSET_ARG0_LOW));
+            filter.push(jump(BPF_JMP_JSET_K, CLONE_NAMESPACE_FLAGS, 0, 1));
+            filter.push(stmt(BPF_RET_K, deny));
+            filter.push(stmt(BPF_LD_W_ABS, OFFSET_NR));
+        }
+
+        filter.push(stmt(BPF_RET_K, SECCOMP_RET_ALLOW));
+        Ok(filter)
+    }
+
+    pub(super) fn check_apparmor(profile: &str) -> Result<()> {
+        let enabled = std::fs::read_to_string("/sys/module/apparmor/parameters/enabled")
+            .map(|s| s.trim() == "Y")
+            .unwrap_or(false);
+        if !enabled {
+            anyhow::bail!(
+                "AppArmor profile '{}' requested but AppArmor is not enabled on this host",
+                profile
+            );
+        }
+        if profile.is_empty() || profile.contains('\0') {
+            anyhow::bail!("Invalid AppArmor profile name: '{}'", profile);
+        }
+        Ok(())
+    }
+
+    pub(super) fn apply(
+        policy: &NativeSecurityPolicy,
+        command: &mut tokio::process::Command,
+    ) -> Result<()> {
+        let no_new_privs = policy.no_new_privileges;
+
+        let filter = if policy.uses_seccomp() {
+            Some(build_filter(
+                &policy.denied_syscall_names(),
+                policy.blocks_namespaces(),
+            )?)
+        } else {
+            None
+        };
+
+        let apparmor = match &policy.apparmor_profile {
+            Some(profile) => {
+                check_apparmor(profile)?;
+                Some((
+                    CString::new(format!("exec {}", profile))?,
+                    CString::new("/proc/self/attr/apparmor/exec")?,
+                    CString::new("/proc/self/attr/exec")?,
+                ))
+            }
+            None => None,
+        };
+
+        if !no_new_privs && filter.is_none() && apparmor.is_none() {
+            return Ok(());
+        }
+
+        // SAFETY: the closure runs in the forked child before exec and only
+        // makes async-signal-safe syscalls on data prepared above.
+        unsafe {
+            command.pre_exec(move || {
+                if let Some((change, attr_path, legacy_path)) = &apparmor {
+                    let mut fd = libc::open(attr_path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+                    if fd < 0 {
+                        fd = libc::open(legacy_path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+                    }
+                    if fd < 0 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                    let bytes = change.as_bytes();
+                    let written =
+                        libc::write(fd, bytes.as_ptr() as *const libc::c_void, bytes.len());
+                    libc::close(fd);
+                    if written < 0 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                }
+
+                // Required for unprivileged seccomp, so set it whenever a filter is used
+                if (no_new_privs || filter.is_some())
+                    && libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0
+                {
+                    return Err(std::io::Error::last_os_error());
+                }
+
+                if let Some(filter) = &filter {
+                    let program = libc::sock_fprog {
+                        len: filter.len() as libc::c_ushort,
+                        filter: filter.as_ptr() as *mut libc::sock_filter,
+                    };
+                    if libc::prctl(
+                        libc::PR_SET_SECCOMP,
+                        libc::SECCOMP_MODE_FILTER,
+                        &program as *const libc::sock_fprog,
+                    ) != 0
+                    {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                }
+
+                Ok(())
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_from_toml() {
+        let policy: NativeSecurityPolicy = toml::from_str(
+            r#"
+            seccomp = "default"
+            denied_syscalls = ["socket", "ptrace"]
+            apparmor_profile = "skill-kubectl"
+            "#,
+        )
+        .unwrap();
+
+        assert!(!policy.no_new_privileges);
+        assert_eq!(policy.seccomp, SeccompMode::Default);
+        assert!(policy.uses_seccomp());
+        assert_eq!(policy.apparmor_profile.as_deref(), Some("skill-kubectl"));
+
+        // Extra syscalls are appended without duplicates
+        let names = policy.denied_syscall_names();
+        assert_eq!(names.len(), DEFAULT_DENIED_SYSCALLS.len() + 1);
+        assert!(names.contains(&"socket"));
+    }
+
+    #[test]
+    fn test_default_policy() {
+        // Opt-in, so setuid helpers such as sudo keep working
+        let policy = NativeSecurityPolicy::default();
+        assert!(!policy.no_new_privileges);
+        assert!(!policy.uses_seccomp());
+        assert!(policy.denied_syscall_names().is_empty());
+        assert!(NativeSecurityPolicy::hardened().uses_seccomp());
+    }
+
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    #[test]
+    fn test_unknown_syscall_is_rejected() {
+        let policy = NativeSecurityPolicy {
+            denied_syscalls: vec!["not_a_syscall".to_string()],
+            ..Default::default()
+        };
+        assert!(policy.validate().is_err());
+        assert!(NativeSecurityPolicy::hardened().validate().is_ok());
+    }
+
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    #[tokio::test]
+    async fn test_seccomp_blocks_denied_syscalls() {
+        // `unshare` is denied by the default profile; `true` is not affected
+        let policy = NativeSecurityPolicy::hardened();
+
+        let mut allowed = tokio::process::Command::new("true");
+        policy.apply(&mut allowed).unwrap();
+        assert!(allowed.status().await.unwrap().success());
+
+        let mut blocked = tokio::process::Command::new("unshare");
+        blocked.arg("--user").arg("true");
+        policy.apply(&mut blocked).unwrap();
+        if let Ok(status) = blocked.status().await {
+            assert!(!status.success());
+        }
+    }
+
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    #[tokio::test]
+    async fn test_seccomp_blocks_namespace_clones() {
+        // Probe from the child, after the filter is installed: clone3 must
+        // report ENOSYS and clone must not create a user namespace. Errors
+        // are raw OS codes, as the forked child must not allocate.
+        let probe = || unsafe {
+            libc::syscall(libc::SYS_clone3, std::ptr::null::<u8>(), 0usize);
+            if std::io::Error::last_os_error().raw_os_error() != Some(libc::ENOSYS) {
+                return Err(std::io::Error::from_raw_os_error(libc::EINVAL));
+            }
+            let pid = libc::syscall(
+                libc::SYS_clone,
+                (libc::CLONE_NEWUSER | libc::SIGCHLD) as libc::c_ulong,
+                0usize,
+                0usize,
+                0usize,
+                0usize,
+            );
+            match pid {
+                0 => libc::_exit(0),
+                pid if pid > 0 => Err(std::io::Error::from_raw_os_error(libc::EEXIST)),
+                _ => Ok(()),
+            }
+        };
+
+        let mut command = tokio::process::Command::new("true");
+        NativeSecurityPolicy::hardened()
+            .apply(&mut command)
+            .unwrap();
+        unsafe {
+            command.pre_exec(probe);
+        }
+        assert!(command.status().await.unwrap().success());
+    }
+}
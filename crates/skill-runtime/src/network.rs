@@ -0,0 +1,372 @@
+//! Outbound network policy for WASM skills
+//!
+//! WASM skills are deny-by-default: a skill can only reach endpoints that its
+//! instance declares in `NetworkConfig::allowed_hosts`. Each rule is a host
+//! glob with an optional port:
+//!
+//! ```text
+//! api.github.com          # any port
+//! api.github.com:443      # HTTPS only
+//! *.amazonaws.com:443     # any subdomain (and the apex)
+//! 10.0.0.*:8080           # IP globs also apply to raw wasi:sockets connects
+//! *                       # everything (equivalent to legacy network_access)
+//! ```
+//!
+//! `blocked_hosts` uses the same syntax and always takes precedence. Hostnames
+//! of `wasi:http` requests are also resolved and checked against the IP rules
+//! in `blocked_hosts`, so a DNS name pointing at a blocked address (say, a
+//! wildcard-DNS name for `169.254.169.254`) is refused as well.
+
+use skill_context::NetworkConfig;
+use std::net::{IpAddr, SocketAddr};
+
+use crate::instance::Capabilities;
+
+/// A single `host[:port]` rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostRule {
+    /// Host glob (`*` matches any run of characters), lowercased
+    pub host: String,
+    /// Port restriction; `None` matches any port
+    pub port: Option<u16>,
+}
+
+impl HostRule {
+    /// Parse a rule such as `*.example.com:443` or `[::1]:8080`
+    pub fn parse(rule: &str) -> Option<Self> {
+        let rule = rule.trim();
+        if rule.is_empty() {
+            return None;
+        }
+
+        // Bracketed IPv6, optionally with a port
+        if let Some(rest) = rule.strip_prefix('[') {
+            let (host, after) = rest.split_once(']')?;
+            let port = match after.strip_prefix(':') {
+                Some(port) => Some(port.parse().ok()?),
+                None if after.is_empty() => None,
+                None => return None,
+            };
+            return Some(Self {
+                host: host.to_lowercase(),
+                port,
+            });
+        }
+
+        // A bare IPv6 address has several colons and no port
+        if rule.matches(':').count() > 1 {
+            return Some(Self {
+                host: rule.to_lowercase(),
+                port: None,
+            });
+        }
+
+        match rule.split_once(':') {
+            Some((host, port)) if !host.is_empty() => Some(Self {
+                host: host.to_lowercase(),
+                port: Some(port.parse().ok()?),
+            }),
+            Some(_) => None,
+            None => Some(Self {
+                host: rule.to_lowercase(),
+                port: None,
+            }),
+        }
+    }
+
+    /// Check whether this rule covers `host` on `port`
+    pub fn matches(&self, host: &str, port: u16) -> bool {
+        if self.port.is_some_and(|p| p != port) {
+            return false;
+        }
+
+        let host = host.trim_end_matches('.').to_lowercase();
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+
+        // `*.example.com` also covers the apex `example.com`
+        if let Some(apex) = self.host.strip_prefix("*.") {
+            if host == apex {
+                return true;
+            }
+        }
+
+        glob_match(&self.host, host)
+    }
+}
+
+impl std::fmt::Display for HostRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let host = if self.host.contains(':') {
+            format!("[{}]", self.host)
+        } else {
+            self.host.clone()
+        };
+        match self.port {
+            Some(port) => write!(f, "{}:{}", host, port),
+            None => write!(f, "{}", host),
+        }
+    }
+}
+
+/// Compiled outbound network policy enforced by the WASI sandbox
+#[derive(Debug, Clone, Default)]
+pub struct NetworkPolicy {
+    allowed: Vec<HostRule>,
+    blocked: Vec<HostRule>,
+}
+
+impl NetworkPolicy {
+    /// Policy that blocks every outbound connection
+    pub fn deny_all() -> Self {
+        Self::default()
+    }
+
+    /// Policy that allows every outbound connection
+    pub fn allow_all() -> Self {
+        Self {
+            allowed: vec![HostRule {
+                host: "*".to_string(),
+                port: None,
+            }],
+            blocked: Vec::new(),
+        }
+    }
+
+    /// Compile a policy from a `NetworkConfig`
+    ///
+    /// Unlike `NetworkConfig::is_host_allowed`, a missing allow-list denies
+    /// everything: WASM skills must declare the endpoints they talk to.
+    pub fn from_config(config: &NetworkConfig) -> Self {
+        if !config.enabled {
+            return Self::deny_all();
+        }
+
+        let parse_all = |rules: &Option<Vec<String>>| -> Vec<HostRule> {
+            rules
+                .iter()
+                .flatten()
+                .filter_map(|rule| {
+                    let parsed = HostRule::parse(rule);
+                    if parsed.is_none() {
+                        tracing::warn!(rule = %rule, "Ignoring invalid network rule");
+                    }
+                    parsed
+                })
+                .collect()
+        };
+
+        Self {
+            allowed: parse_all(&config.allowed_hosts),
+            blocked: parse_all(&config.blocked_hosts),
+        }
+    }
+
+    /// Compile the policy for an instance's capabilities
+    ///
+    /// An explicit `network` section wins; otherwise the legacy
+    /// `network_access = true` flag allows everything.
+    pub fn from_capabilities(capabilities: &Capabilities) -> Self {
+        match &capabilities.network {
+            Some(config) => Self::from_config(config),
+            None if capabilities.network_access => Self::allow_all(),
+            None => Self::deny_all(),
+        }
+    }
+
+    /// Whether any outbound connection could be allowed
+    pub fn allows_any(&self) -> bool {
+        !self.allowed.is_empty()
+    }
+
+    /// Rules in the allow-list
+    pub fn allowed_rules(&self) -> &[HostRule] {
+        &self.allowed
+    }
+
+    /// Check a hostname (or IP literal) and port
+    pub fn is_allowed(&self, host: &str, port: u16) -> bool {
+        if self.blocked.iter().any(|rule| rule.matches(host, port)) {
+            return false;
+        }
+        self.allowed.iter().any(|rule| rule.matches(host, port))
+    }
+
+    /// Check a resolved socket address (used for raw wasi:sockets traffic)
+    pub fn is_addr_allowed(&self, addr: &SocketAddr) -> bool {
+        self.is_allowed(&canonical_ip(addr), addr.port())
+    }
+
+    /// Whether a `blocked_hosts` rule covers a resolved socket address
+    pub fn is_addr_blocked(&self, addr: &SocketAddr) -> bool {
+        let ip = canonical_ip(addr);
+        self.blocked
+            .iter()
+            .any(|rule| rule.matches(&ip, addr.port()))
+    }
+
+    /// Whether `host` resolves to an address `blocked_hosts` covers
+    ///
+    /// A name that doesn't resolve isn't blocked here; the connection fails
+    /// on its own.
+    pub async fn resolves_to_blocked(&self, host: &str, port: u16) -> bool {
+        if self.blocked.is_empty() {
+            return false;
+        }
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        match tokio::net::lookup_host((host, port)).await {
+            Ok(mut addrs) => addrs.any(|addr| self.is_addr_blocked(&addr)),
+            Err(_) => false,
+        }
+    }
+}
+
+/// The address's IP as rules see it, with IPv4-mapped IPv6 unwrapped
+fn canonical_ip(addr: &SocketAddr) -> String {
+    match addr.ip() {
+        IpAddr::V6(v6) => v6
+            .to_ipv4_mapped()
+            .map(IpAddr::V4)
+            .unwrap_or(IpAddr::V6(v6)),
+        ip => ip,
+    }
+    .to_string()
+}
+
+/// Case-sensitive glob match where `*` matches any run of characters
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let (p, t): (Vec<char>, Vec<char>) = (pattern.chars().collect(), text.chars().collect());
+    let (mut pi, mut ti) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ti < t.len() {
+        if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if pi < p.len() && p[pi] == t[ti] {
+            pi += 1;
+            ti += 1;
+        } else if let Some((star_pi, star_ti)) = star {
+            pi = star_pi + 1;
+            ti = star_ti + 1;
+            star = Some((star_pi, star_ti + 1));
+        } else {
+            return false;
+        }
+    }
+
+    p[pi..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allowed: &[&str], blocked: &[&str]) -> NetworkPolicy {
+        NetworkPolicy::from_config(
+            &NetworkConfig::enabled()
+                .with_allowed_hosts(allowed.iter().map(|s| s.to_string()).collect())
+                .with_blocked_hosts(blocked.iter().map(|s| s.to_string()).collect()),
+        )
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        assert_eq!(
+            HostRule::parse("API.github.com:443"),
+            Some(HostRule {
+                host: "api.github.com".to_string(),
+                port: Some(443)
+            })
+        );
+        assert_eq!(HostRule::parse("*.example.com").unwrap().port, None);
+        assert_eq!(HostRule::parse("[::1]:8080").unwrap().host, "::1");
+        assert_eq!(HostRule::parse("fe80::1").unwrap().port, None);
+        assert!(HostRule::parse("example.com:https").is_none());
+        assert!(HostRule::parse(":443").is_none());
+        assert!(HostRule::parse("  ").is_none());
+    }
+
+    #[test]
+    fn test_deny_by_default() {
+        assert!(!NetworkPolicy::deny_all().is_allowed("example.com", 443));
+        assert!(
+            !NetworkPolicy::from_config(&NetworkConfig::enabled()).is_allowed("example.com", 443)
+        );
+        assert!(
+            !NetworkPolicy::from_config(&NetworkConfig::disabled().allow_host("*"))
+                .is_allowed("example.com", 443)
+        );
+
+        let caps = Capabilities::default();
+        assert!(!NetworkPolicy::from_capabilities(&caps).allows_any());
+    }
+
+    #[test]
+    fn test_host_globs_and_ports() {
+        let policy = policy(
+            &["api.github.com:443", "*.amazonaws.com", "10.0.0.*:8080"],
+            &[],
+        );
+
+        assert!(policy.is_allowed("api.github.com", 443));
+        assert!(policy.is_allowed("API.GitHub.com.", 443));
+        assert!(!policy.is_allowed("api.github.com", 80));
+        assert!(!policy.is_allowed("github.com", 443));
+
+        assert!(policy.is_allowed("s3.us-east-1.amazonaws.com", 443));
+        assert!(policy.is_allowed("amazonaws.com", 80));
+        assert!(!policy.is_allowed("evilamazonaws.com", 443));
+
+        assert!(policy.is_addr_allowed(&"10.0.0.12:8080".parse().unwrap()));
+        assert!(!policy.is_addr_allowed(&"10.0.1.12:8080".parse().unwrap()));
+        assert!(policy.is_addr_allowed(&"[::ffff:10.0.0.1]:8080".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_blocked_hosts_take_precedence() {
+        let policy = policy(&["*"], &["metadata.google.internal", "169.254.169.254"]);
+
+        assert!(policy.is_allowed("example.com", 443));
+        assert!(!policy.is_allowed("metadata.google.internal", 80));
+        assert!(!policy.is_addr_allowed(&"169.254.169.254:80".parse().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_names_resolving_to_blocked_ips() {
+        let loopback = policy(&["*"], &["127.0.0.1", "::1"]);
+
+        // The name itself passes; the address it resolves to doesn't
+        assert!(loopback.is_allowed("localhost", 80));
+        assert!(loopback.resolves_to_blocked("localhost", 80).await);
+        assert!(loopback.is_addr_blocked(&"[::ffff:127.0.0.1]:80".parse().unwrap()));
+
+        assert!(
+            !policy(&["*"], &["10.0.0.*"])
+                .resolves_to_blocked("localhost", 80)
+                .await
+        );
+        assert!(
+            !policy(&["*"], &[])
+                .resolves_to_blocked("localhost", 80)
+                .await
+        );
+    }
+
+    #[test]
+    fn test_legacy_network_access() {
+        let caps = Capabilities {
+            network_access: true,
+            ..Default::default()
+        };
+        assert!(NetworkPolicy::from_capabilities(&caps).is_allowed("example.com", 443));
+
+        let caps = Capabilities {
+            network_access: true,
+            network: Some(NetworkConfig::enabled().allow_host("example.com")),
+            ..Default::default()
+        };
+        let policy = NetworkPolicy::from_capabilities(&caps);
+        assert!(policy.is_allowed("example.com", 443));
+        assert!(!policy.is_allowed("other.com", 443));
+    }
+}
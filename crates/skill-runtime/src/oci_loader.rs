@@ -0,0 +1,583 @@
+//! OCI Skill Loader - Pull skills distributed as OCI artifacts
+//!
+//! `skill install oci://ghcr.io/org/skill:1.2.0` pulls an artifact from any
+//! OCI-compliant registry (GHCR, ECR, Harbor, Docker Hub, ...). The artifact
+//! holds a WASM layer and, optionally, metadata layers:
+//!
+//! - WASM: `application/vnd.wasm.content.layer.v1+wasm` or `application/wasm`
+//! - Metadata: layers with an `org.opencontainers.image.title` annotation
+//!   (`SKILL.md`, `skill.yaml`, ...) written under that name, or a
+//!   `application/vnd.skill-engine.metadata.v1.tar+gzip` bundle extracted as-is
+//!
+//! Publishing with `oras`:
+//!
+//! ```text
+//! oras push ghcr.io/org/skill:1.2.0 \
+//!   skill.wasm:application/vnd.wasm.content.layer.v1+wasm \
+//!   SKILL.md:text/markdown
+//! ```
+//!
+//! Registry credentials come from `SKILL_REGISTRY_USERNAME` /
+//! `SKILL_REGISTRY_PASSWORD`, then `~/.docker/config.json` (`auths`), so a
+//! prior `docker login` or `oras login` is reused. Anonymous pulls work for
+//! public artifacts.
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use reqwest::header::{ACCEPT, WWW_AUTHENTICATE};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+use crate::archive_loader::{extract_archive, ArchiveFormat};
+use crate::checksum::sha256_hex;
+use crate::dependencies::{read_declared_dependencies, SkillDependency};
+use crate::git_loader::extract_metadata;
+
+/// Media types accepted for the WASM component layer
+const WASM_MEDIA_TYPES: &[&str] = &[
+    "application/vnd.wasm.content.layer.v1+wasm",
+    "application/vnd.module.wasm.content.layer.v1+wasm",
+    "application/wasm",
+];
+
+/// Media type of a gzipped tarball holding SKILL.md, skill.yaml, scripts, ...
+pub const METADATA_BUNDLE_MEDIA_TYPE: &str = "application/vnd.skill-engine.metadata.v1.tar+gzip";
+
+const TITLE_ANNOTATION: &str = "org.opencontainers.image.title";
+
+const MANIFEST_ACCEPT: &str = "application/vnd.oci.image.manifest.v1+json, \
+     application/vnd.oci.image.index.v1+json, \
+     application/vnd.docker.distribution.manifest.v2+json, \
+     application/vnd.docker.distribution.manifest.list.v2+json";
+
+/// A parsed `oci://registry/repository[:tag|@digest]` reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OciReference {
+    /// Registry host (with port, if any)
+    pub registry: String,
+    /// Repository path within the registry
+    pub repository: String,
+    /// Tag (defaults to `latest` when neither tag nor digest is given)
+    pub tag: Option<String>,
+    /// Pinned manifest digest (`sha256:<hex>`)
+    pub digest: Option<String>,
+}
+
+impl OciReference {
+    /// Tag or digest used to fetch the manifest
+    pub fn reference(&self) -> &str {
+        self.digest
+            .as_deref()
+            .or(self.tag.as_deref())
+            .unwrap_or("latest")
+    }
+
+    /// Base URL of the registry API
+    fn api_base(&self) -> String {
+        let host = match self.registry.as_str() {
+            "docker.io" => "registry-1.docker.io",
+            other => other,
+        };
+        let scheme = if host.starts_with("localhost") || host.starts_with("127.0.0.1") {
+            "http"
+        } else {
+            "https"
+        };
+        format!("{}://{}/v2/{}", scheme, host, self.repository)
+    }
+}
+
+impl std::fmt::Display for OciReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.registry, self.repository)?;
+        if let Some(tag) = &self.tag {
+            write!(f, ":{}", tag)?;
+        }
+        if let Some(digest) = &self.digest {
+            write!(f, "@{}", digest)?;
+        }
+        Ok(())
+    }
+}
+
+/// Check if a source is an `oci://` reference
+pub fn is_oci_reference(input: &str) -> bool {
+    input.starts_with("oci://")
+}
+
+/// Parse `oci://ghcr.io/org/skill:1.2.0` or `oci://ghcr.io/org/skill@sha256:<hex>`
+pub fn parse_oci_reference(input: &str) -> Result<OciReference> {
+    let rest = input
+        .strip_prefix("oci://")
+        .with_context(|| format!("OCI references must start with oci://: {}", input))?;
+
+    let (name, digest) = match rest.split_once('@') {
+        Some((name, digest)) => {
+            let hex = digest
+                .strip_prefix("sha256:")
+                .with_context(|| format!("Unsupported digest '{}'", digest))?;
+            if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                anyhow::bail!("Invalid sha256 digest '{}'", digest);
+            }
+            (name, Some(digest.to_ascii_lowercase()))
+        }
+        None => (rest, None),
+    };
+
+    // A tag follows the last ':' after the last '/', so registry ports are kept
+    let (name, tag) = match name.rsplit_once(':') {
+        Some((repo, tag)) if !tag.contains('/') => (repo, Some(tag.to_string())),
+        _ => (name, None),
+    };
+
+    let (registry, repository) = name
+        .split_once('/')
+        .filter(|(registry, repo)| !registry.is_empty() && !repo.is_empty())
+        .with_context(|| {
+            format!(
+                "Invalid OCI reference '{}'. Expected oci://<registry>/<repository>[:tag]",
+                input
+            )
+        })?;
+
+    if repository
+        .split('/')
+        .any(|segment| segment.is_empty() || segment == "." || segment == "..")
+    {
+        anyhow::bail!("Invalid repository path '{}'", repository);
+    }
+
+    Ok(OciReference {
+        registry: registry.to_string(),
+        repository: repository.to_string(),
+        tag: tag.or_else(|| digest.is_none().then(|| "latest".to_string())),
+        digest,
+    })
+}
+
+/// A skill pulled from an OCI registry
+#[derive(Debug, Clone)]
+pub struct OciSkill {
+    /// Reference the skill was pulled from
+    pub reference: OciReference,
+    /// Manifest digest (`sha256:<hex>`)
+    pub manifest_digest: String,
+    /// Directory holding the WASM and metadata layers
+    pub local_path: PathBuf,
+    /// WASM component
+    pub wasm_path: PathBuf,
+    /// Skill name (from metadata, else the last repository segment)
+    pub skill_name: String,
+    /// Skill version (from metadata, else the tag)
+    pub version: Option<String>,
+    /// Skills this skill depends on
+    pub dependencies: Vec<SkillDependency>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    #[serde(default)]
+    layers: Vec<Descriptor>,
+    #[serde(default)]
+    manifests: Vec<Descriptor>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    media_type: String,
+    digest: String,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    #[serde(default)]
+    token: Option<String>,
+    #[serde(default)]
+    access_token: Option<String>,
+}
+
+/// Pulls skill artifacts from OCI registries
+pub struct OciSkillLoader {
+    /// Directory for pulled artifacts
+    cache_dir: PathBuf,
+    client: reqwest::Client,
+}
+
+impl OciSkillLoader {
+    /// Create a new OciSkillLoader
+    pub fn new() -> Result<Self> {
+        let home = dirs::home_dir().context("Failed to get home directory")?;
+        let cache_dir = home.join(".skill-engine").join("oci");
+        std::fs::create_dir_all(&cache_dir).with_context(|| {
+            format!(
+                "Failed to create OCI cache directory: {}",
+                cache_dir.display()
+            )
+        })?;
+        Ok(Self {
+            cache_dir,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    /// Pull an artifact and lay out its layers as a skill directory
+    ///
+    /// Artifacts are cached by manifest digest, so re-pulling an unchanged tag
+    /// only fetches the manifest.
+    pub async fn pull(&self, reference: &OciReference) -> Result<OciSkill> {
+        info!(reference = %reference, "Pulling OCI skill artifact");
+        let mut session = RegistrySession::new(&self.client, reference);
+
+        let (manifest, manifest_digest) = session.fetch_manifest(reference.reference()).await?;
+        // An index (multi-platform artifact) points at the actual manifests
+        let (manifest, manifest_digest) = if manifest.layers.is_empty() {
+            let first = manifest
+                .manifests
+                .first()
+                .context("OCI index contains no manifests")?;
+            session.fetch_manifest(&first.digest).await?
+        } else {
+            (manifest, manifest_digest)
+        };
+        if let Some(pinned) = &reference.digest {
+            if *pinned != manifest_digest {
+                anyhow::bail!(
+                    "Manifest digest mismatch: expected {}, got {}",
+                    pinned,
+                    manifest_digest
+                );
+            }
+        }
+
+        let dest = self
+            .cache_dir
+            .join(&reference.registry)
+            .join(&reference.repository)
+            .join(manifest_digest.trim_start_matches("sha256:"));
+        if !dest.exists() {
+            let staging = dest.with_extension("partial");
+            if staging.exists() {
+                std::fs::remove_dir_all(&staging)?;
+            }
+            std::fs::create_dir_all(&staging)?;
+            self.download_layers(&mut session, &manifest, &staging)
+                .await?;
+            std::fs::rename(&staging, &dest)?;
+        } else {
+            debug!(path = %dest.display(), "Using cached OCI artifact");
+        }
+
+        let default_name = reference
+            .repository
+            .rsplit('/')
+            .next()
+            .unwrap_or(&reference.repository)
+            .to_string();
+        let (skill_name, version) = extract_metadata(&dest, &default_name)?;
+        let dependencies = read_declared_dependencies(&dest)?;
+
+        Ok(OciSkill {
+            reference: reference.clone(),
+            manifest_digest,
+            wasm_path: dest.join("skill.wasm"),
+            local_path: dest,
+            skill_name,
+            version: version.or_else(|| reference.tag.clone().filter(|t| t != "latest")),
+            dependencies,
+        })
+    }
+
+    async fn download_layers(
+        &self,
+        session: &mut RegistrySession<'_>,
+        manifest: &Manifest,
+        dest: &Path,
+    ) -> Result<()> {
+        let mut has_wasm = false;
+        for layer in &manifest.layers {
+            let title = layer.annotations.get(TITLE_ANNOTATION).map(|t| t.as_str());
+
+            if WASM_MEDIA_TYPES.contains(&layer.media_type.as_str()) {
+                if has_wasm {
+                    anyhow::bail!("Artifact contains more than one WASM layer");
+                }
+                let bytes = session.fetch_blob(&layer.digest).await?;
+                std::fs::write(dest.join("skill.wasm"), bytes)?;
+                has_wasm = true;
+            } else if layer.media_type == METADATA_BUNDLE_MEDIA_TYPE {
+                let bytes = session.fetch_blob(&layer.digest).await?;
+                extract_archive(&bytes, ArchiveFormat::TarGz, dest)
+                    .context("Failed to extract metadata layer")?;
+            } else if let Some(title) = title {
+                // Only plain file names; titles never place files outside `dest`
+                let file_name = Path::new(title)
+                    .file_name()
+                    .filter(|name| *name == std::ffi::OsStr::new(title))
+                    .with_context(|| format!("Invalid layer title '{}'", title))?;
+                let bytes = session.fetch_blob(&layer.digest).await?;
+                std::fs::write(dest.join(file_name), bytes)?;
+            } else {
+                debug!(media_type = %layer.media_type, "Skipping unrecognized layer");
+            }
+        }
+
+        if !has_wasm {
+            anyhow::bail!(
+                "Artifact has no WASM layer (expected media type {})",
+                WASM_MEDIA_TYPES[0]
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Registry requests sharing one bearer token
+struct RegistrySession<'a> {
+    client: &'a reqwest::Client,
+    reference: &'a OciReference,
+    base: String,
+    credentials: Option<(String, String)>,
+    token: Option<String>,
+}
+
+impl<'a> RegistrySession<'a> {
+    fn new(client: &'a reqwest::Client, reference: &'a OciReference) -> Self {
+        Self {
+            client,
+            reference,
+            base: reference.api_base(),
+            credentials: registry_credentials(&reference.registry),
+            token: None,
+        }
+    }
+
+    async fn fetch_manifest(&mut self, reference: &str) -> Result<(Manifest, String)> {
+        let url = format!("{}/manifests/{}", self.base, reference);
+        let bytes = self.get(&url, Some(MANIFEST_ACCEPT)).await?;
+        let digest = format!("sha256:{}", sha256_hex(&bytes));
+        if reference.starts_with("sha256:") && reference != digest {
+            anyhow::bail!("Manifest digest mismatch for {}", reference);
+        }
+        let manifest = serde_json::from_slice(&bytes)
+            .with_context(|| format!("Invalid OCI manifest for {}", self.reference))?;
+        Ok((manifest, digest))
+    }
+
+    async fn fetch_blob(&mut self, digest: &str) -> Result<Vec<u8>> {
+        let expected = digest
+            .strip_prefix("sha256:")
+            .with_context(|| format!("Unsupported layer digest '{}'", digest))?;
+        let url = format!("{}/blobs/{}", self.base, digest);
+        let bytes = self.get(&url, None).await?;
+        if sha256_hex(&bytes) != expected {
+            anyhow::bail!("Layer digest mismatch for {}", digest);
+        }
+        Ok(bytes)
+    }
+
+    /// GET with the current token, authenticating once on a 401 challenge
+    async fn get(&mut self, url: &str, accept: Option<&str>) -> Result<Vec<u8>> {
+        for attempt in 0..2 {
+            let mut request = self.client.get(url);
+            if let Some(accept) = accept {
+                request = request.header(ACCEPT, accept);
+            }
+            if let Some(token) = &self.token {
+                request = request.bearer_auth(token);
+            } else if let Some((user, password)) = &self.credentials {
+                request = request.basic_auth(user, Some(password));
+            }
+
+            let response = request
+                .send()
+                .await
+                .with_context(|| format!("Failed to reach registry {}", self.reference.registry))?;
+
+            if response.status() == StatusCode::UNAUTHORIZED && attempt == 0 {
+                let challenge = response
+                    .headers()
+                    .get(WWW_AUTHENTICATE)
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string();
+                self.authenticate(&challenge).await?;
+                continue;
+            }
+
+            let status = response.status();
+            if !status.is_success() {
+                anyhow::bail!(
+                    "Registry returned {} for {}{}",
+                    status,
+                    self.reference,
+                    if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+                        " (log in with `docker login` or set SKILL_REGISTRY_USERNAME/SKILL_REGISTRY_PASSWORD)"
+                    } else {
+                        ""
+                    }
+                );
+            }
+            return Ok(response.bytes().await?.to_vec());
+        }
+        anyhow::bail!("Authentication to {} failed", self.reference.registry)
+    }
+
+    /// Exchange credentials (or nothing, for anonymous pulls) for a bearer token
+    async fn authenticate(&mut self, challenge: &str) -> Result<()> {
+        let Some(params) = challenge.strip_prefix("Bearer ") else {
+            // Basic challenges are answered by the credentials on the next try
+            if self.credentials.is_none() {
+                anyhow::bail!("Registry {} requires credentials", self.reference.registry);
+            }
+            return Ok(());
+        };
+
+        let params = parse_challenge(params);
+        let realm = params
+            .get("realm")
+            .context("Registry auth challenge has no realm")?;
+        let scope = params
+            .get("scope")
+            .cloned()
+            .unwrap_or_else(|| format!("repository:{}:pull", self.reference.repository));
+
+        let mut query = vec![("scope", scope)];
+        if let Some(service) = params.get("service") {
+            query.push(("service", service.clone()));
+        }
+        let mut request = self.client.get(realm).query(&query);
+        if let Some((user, password)) = &self.credentials {
+            request = request.basic_auth(user, Some(password));
+        }
+
+        let response: TokenResponse = request
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .with_context(|| format!("Failed to get a token from {}", realm))?
+            .json()
+            .await?;
+        self.token = Some(
+            response
+                .token
+                .or(response.access_token)
+                .context("Token endpoint returned no token")?,
+        );
+        Ok(())
+    }
+}
+
+/// Parse `realm="...",service="...",scope="..."`
+fn parse_challenge(params: &str) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    let mut rest = params.trim();
+    while let Some((key, after)) = rest.split_once('=') {
+        let key = key.trim().trim_start_matches(',').trim().to_string();
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => match quoted.split_once('"') {
+                Some((value, remaining)) => (value, remaining),
+                None => (quoted, ""),
+            },
+            None => after.split_once(',').unwrap_or((after, "")),
+        };
+        values.insert(key, value.to_string());
+        rest = remaining.trim_start_matches(',').trim();
+    }
+    values
+}
+
+/// Credentials from the environment, then `~/.docker/config.json`
+fn registry_credentials(registry: &str) -> Option<(String, String)> {
+    if let (Ok(user), Ok(password)) = (
+        std::env::var("SKILL_REGISTRY_USERNAME"),
+        std::env::var("SKILL_REGISTRY_PASSWORD"),
+    ) {
+        return Some((user, password));
+    }
+
+    let config_dir = std::env::var_os("DOCKER_CONFIG")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".docker")))?;
+    let contents = std::fs::read_to_string(config_dir.join("config.json")).ok()?;
+    docker_config_credentials(&contents, registry)
+}
+
+fn docker_config_credentials(contents: &str, registry: &str) -> Option<(String, String)> {
+    let config: serde_json::Value = serde_json::from_str(contents).ok()?;
+    let auths = config.get("auths")?.as_object()?;
+    let entry = auths.iter().find_map(|(host, entry)| {
+        let host = host
+            .trim_start_matches("https://")
+            .trim_start_matches("http://");
+        let host = host.split('/').next().unwrap_or(host);
+        let matches = host == registry || (registry == "docker.io" && host == "index.docker.io");
+        matches.then_some(entry)
+    })?;
+
+    let auth = entry.get("auth")?.as_str()?;
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(auth)
+        .ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let (user, password) = decoded.split_once(':')?;
+    Some((user.to_string(), password.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_oci_reference() {
+        let r = parse_oci_reference("oci://ghcr.io/org/skill:1.2.0").unwrap();
+        assert_eq!(r.registry, "ghcr.io");
+        assert_eq!(r.repository, "org/skill");
+        assert_eq!(r.reference(), "1.2.0");
+        assert_eq!(r.api_base(), "https://ghcr.io/v2/org/skill");
+
+        let r = parse_oci_reference("oci://localhost:5000/team/tools/aws").unwrap();
+        assert_eq!(r.registry, "localhost:5000");
+        assert_eq!(r.repository, "team/tools/aws");
+        assert_eq!(r.reference(), "latest");
+        assert_eq!(r.api_base(), "http://localhost:5000/v2/team/tools/aws");
+
+        let digest = format!("sha256:{}", "a".repeat(64));
+        let r = parse_oci_reference(&format!("oci://docker.io/org/skill@{}", digest)).unwrap();
+        assert_eq!(r.tag, None);
+        assert_eq!(r.reference(), digest);
+        assert_eq!(r.api_base(), "https://registry-1.docker.io/v2/org/skill");
+
+        assert!(parse_oci_reference("oci://ghcr.io").is_err());
+        assert!(parse_oci_reference("oci://ghcr.io/org/skill@sha256:abc").is_err());
+        assert!(parse_oci_reference("oci://ghcr.io/org/../skill").is_err());
+        assert!(!is_oci_reference("github:org/skill"));
+    }
+
+    #[test]
+    fn test_parse_challenge() {
+        let params = parse_challenge(
+            r#"realm="https://ghcr.io/token",service="ghcr.io",scope="repository:org/skill:pull""#,
+        );
+        assert_eq!(params["realm"], "https://ghcr.io/token");
+        assert_eq!(params["service"], "ghcr.io");
+        assert_eq!(params["scope"], "repository:org/skill:pull");
+    }
+
+    #[test]
+    fn test_docker_config_credentials() {
+        // "user:secret"
+        let config = r#"{"auths":{"https://index.docker.io/v1/":{"auth":"dXNlcjpzZWNyZXQ="}}}"#;
+        assert_eq!(
+            docker_config_credentials(config, "docker.io"),
+            Some(("user".to_string(), "secret".to_string()))
+        );
+        assert_eq!(docker_config_credentials(config, "ghcr.io"), None);
+    }
+}
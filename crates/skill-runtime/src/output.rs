@@ -0,0 +1,428 @@
+//! Output shaping for skill executions.
+//!
+//! Tool output can be far larger than a caller needs. [`OutputOptions`]
+//! describes the context-engineering transformations (grep, head/tail, jq,
+//! format and size-limited truncation) that the MCP `execute` tool and the
+//! HTTP execute endpoint apply before returning output, so both produce the
+//! same result for the same options.
+
+use crate::jq::{apply_jq, validate_jq};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Formats accepted by [`OutputOptions::format`]
+pub const OUTPUT_FORMATS: &[&str] = &["json", "lines", "count", "compact"];
+
+/// Strategies accepted by [`OutputOptions::truncate`]
+pub const TRUNCATE_STRATEGIES: &[&str] = &["head", "tail", "middle", "smart"];
+
+/// Transformations applied to tool output, in order: grep, head/tail, jq,
+/// format, then truncation to `max_output`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputOptions {
+    /// Maximum characters in the output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output: Option<usize>,
+    /// Truncation strategy when `max_output` is exceeded (default: smart)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncate: Option<String>,
+    /// Regex keeping only matching lines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grep: Option<String>,
+    /// Keep lines that don't match `grep` instead
+    #[serde(default)]
+    pub grep_invert: bool,
+    /// Keep only the first N lines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<usize>,
+    /// Keep only the last N lines (ignored when `head` is set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tail: Option<usize>,
+    /// Output format transformation (see [`OUTPUT_FORMATS`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// jq filter applied to JSON output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jq: Option<String>,
+}
+
+/// Output after [`OutputOptions::apply`], with what was done to it.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessedOutput {
+    /// The processed output content
+    pub content: String,
+    /// Whether output was truncated
+    pub truncated: bool,
+    /// Original length before processing
+    pub original_length: usize,
+    /// Final length after processing
+    pub final_length: usize,
+    /// Number of lines matched by grep (if used)
+    pub grep_matches: Option<usize>,
+    /// Processing applied
+    pub processing: Vec<String>,
+}
+
+impl OutputOptions {
+    /// Whether no transformation is requested.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check the options before running anything.
+    ///
+    /// Rejects invalid grep patterns and jq filters, and unknown formats or
+    /// truncation strategies.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(pattern) = &self.grep {
+            regex::Regex::new(pattern)
+                .with_context(|| format!("Invalid grep pattern '{}'", pattern))?;
+        }
+        if let Some(filter) = &self.jq {
+            validate_jq(filter)?;
+        }
+        if let Some(format) = &self.format {
+            if !OUTPUT_FORMATS.contains(&format.as_str()) {
+                bail!(
+                    "Unknown format '{}' (expected one of: {})",
+                    format,
+                    OUTPUT_FORMATS.join(", ")
+                );
+            }
+        }
+        if let Some(strategy) = &self.truncate {
+            if !TRUNCATE_STRATEGIES.contains(&strategy.as_str()) {
+                bail!(
+                    "Unknown truncate strategy '{}' (expected one of: {})",
+                    strategy,
+                    TRUNCATE_STRATEGIES.join(", ")
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the transformations to tool output.
+    ///
+    /// Invalid grep patterns and unknown formats are skipped; a jq filter
+    /// that fails (e.g. on output that isn't JSON) is an error.
+    pub fn apply(&self, output: &str) -> Result<ProcessedOutput> {
+        let original_length = output.len();
+        let mut content = output.to_string();
+        let mut processing = Vec::new();
+        let mut truncated = false;
+        let mut grep_matches = None;
+
+        // Step 1: Apply grep filter first (most selective)
+        if let Some(pattern) = &self.grep {
+            if let Ok(regex) = regex::Regex::new(pattern) {
+                let filtered: Vec<&str> = content
+                    .lines()
+                    .filter(|line| regex.is_match(line) != self.grep_invert)
+                    .collect();
+                grep_matches = Some(filtered.len());
+                content = filtered.join("\n");
+                processing.push(format!(
+                    "grep(pattern='{}', invert={}, matches={})",
+                    pattern,
+                    self.grep_invert,
+                    grep_matches.unwrap_or(0)
+                ));
+            }
+        }
+
+        // Step 2: Apply head/tail line limits
+        if let Some(n) = self.head {
+            let lines: Vec<&str> = content.lines().take(n).collect();
+            if content.lines().count() > n {
+                truncated = true;
+            }
+            content = lines.join("\n");
+            processing.push(format!("head({})", n));
+        } else if let Some(n) = self.tail {
+            let all_lines: Vec<&str> = content.lines().collect();
+            if all_lines.len() > n {
+                truncated = true;
+                content = all_lines[all_lines.len().saturating_sub(n)..].join("\n");
+            }
+            processing.push(format!("tail({})", n));
+        }
+
+        // Step 3: Apply jq filter
+        if let Some(filter) = &self.jq {
+            content = apply_jq(&content, filter)
+                .with_context(|| format!("jq filter '{}' failed", filter))?;
+            processing.push(format!("jq('{}')", filter));
+        }
+
+        // Step 4: Apply format transformation
+        match self.format.as_deref() {
+            Some("json") => {
+                // Try to parse and pretty-print JSON
+                if let Ok(json_val) = serde_json::from_str::<serde_json::Value>(&content) {
+                    content = serde_json::to_string_pretty(&json_val).unwrap_or(content);
+                    processing.push("format(json)".to_string());
+                }
+            }
+            Some("lines") => {
+                // Convert to JSON array of lines
+                let lines: Vec<&str> = content.lines().collect();
+                content = serde_json::to_string(&lines).unwrap_or(content);
+                processing.push("format(lines)".to_string());
+            }
+            Some("count") => {
+                // Just return line count
+                let count = content.lines().count();
+                content = format!("{} lines", count);
+                processing.push("format(count)".to_string());
+            }
+            Some("compact") => {
+                // Remove empty lines and extra whitespace
+                let lines: Vec<&str> = content
+                    .lines()
+                    .map(|l| l.trim())
+                    .filter(|l| !l.is_empty())
+                    .collect();
+                content = lines.join("\n");
+                processing.push("format(compact)".to_string());
+            }
+            _ => {}
+        }
+
+        // Step 5: Apply max_output truncation last
+        if let Some(max) = self.max_output {
+            if content.len() > max {
+                truncated = true;
+                let strategy = self.truncate.as_deref().unwrap_or("smart");
+                content = truncate_content(&content, max, strategy);
+                processing.push(format!("truncate({}, strategy='{}')", max, strategy));
+            }
+        }
+
+        Ok(ProcessedOutput {
+            final_length: content.len(),
+            content,
+            truncated,
+            original_length,
+            grep_matches,
+            processing,
+        })
+    }
+}
+
+/// Truncate content with different strategies
+pub fn truncate_content(content: &str, max_len: usize, strategy: &str) -> String {
+    if content.len() <= max_len {
+        return content.to_string();
+    }
+
+    match strategy {
+        "head" => {
+            // Keep the beginning
+            let truncated = &content[..char_boundary(content, max_len.saturating_sub(50))];
+            format!(
+                "{}\n\n... [TRUNCATED: {} more characters]",
+                truncated,
+                content.len() - truncated.len()
+            )
+        }
+        "tail" => {
+            // Keep the end
+            let start = char_boundary(
+                content,
+                content.len().saturating_sub(max_len.saturating_sub(50)),
+            );
+            let truncated = &content[start..];
+            format!(
+                "[TRUNCATED: {} characters omitted] ...\n\n{}",
+                start, truncated
+            )
+        }
+        "middle" => {
+            // Keep both beginning and end
+            let half = (max_len.saturating_sub(100)) / 2;
+            let head = &content[..char_boundary(content, half)];
+            let tail = &content[char_boundary(content, content.len().saturating_sub(half))..];
+            let omitted = content.len() - (head.len() + tail.len());
+            format!(
+                "{}\n\n... [TRUNCATED: {} characters in middle] ...\n\n{}",
+                head, omitted, tail
+            )
+        }
+        _ => {
+            // Smart truncation (default): try to preserve structure (complete lines, JSON structure)
+            smart_truncate(content, max_len)
+        }
+    }
+}
+
+/// Smart truncation that preserves structure
+pub fn smart_truncate(content: &str, max_len: usize) -> String {
+    // Check if it's JSON
+    if content.trim().starts_with('{') || content.trim().starts_with('[') {
+        return smart_truncate_json(content, max_len);
+    }
+
+    // For plain text, truncate at line boundaries
+    let mut result = String::new();
+    let remaining = max_len.saturating_sub(100); // Reserve space for truncation message
+    let lines: Vec<&str> = content.lines().collect();
+    let total_lines = lines.len();
+    let mut included_lines = 0;
+
+    for line in lines {
+        if result.len() + line.len() + 1 > remaining {
+            break;
+        }
+        if !result.is_empty() {
+            result.push('\n');
+        }
+        result.push_str(line);
+        included_lines += 1;
+    }
+
+    if included_lines < total_lines {
+        let omitted_lines = total_lines - included_lines;
+        let omitted_chars = content.len() - result.len();
+        result.push_str(&format!(
+            "\n\n... [TRUNCATED: {} more lines, {} characters]\n\
+             💡 Tip: Use grep='<pattern>' to filter, or head=N/tail=N to limit lines",
+            omitted_lines, omitted_chars
+        ));
+    }
+
+    result
+}
+
+/// Smart truncation for JSON content
+fn smart_truncate_json(content: &str, max_len: usize) -> String {
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(content) {
+        // For arrays, truncate to fewer elements
+        if let serde_json::Value::Array(arr) = &json {
+            let total = arr.len();
+            let mut truncated_arr = Vec::new();
+            let mut current_len = 2; // For []
+
+            for (idx, item) in arr.iter().enumerate() {
+                let item_str = serde_json::to_string(item).unwrap_or_default();
+                if current_len + item_str.len() + 2 > max_len.saturating_sub(150) {
+                    // Add truncation notice
+                    let notice = serde_json::json!({
+                        "_truncated": true,
+                        "_message": format!("... {} more items", total - idx),
+                        "_total_items": total,
+                        "_shown_items": idx,
+                        "_tip": "Use jq='.[0:10]' to select a range, or grep to filter"
+                    });
+                    truncated_arr.push(notice);
+                    break;
+                }
+                truncated_arr.push(item.clone());
+                current_len += item_str.len() + 2;
+            }
+
+            return serde_json::to_string_pretty(&truncated_arr)
+                .unwrap_or_else(|_| content[..char_boundary(content, max_len)].to_string());
+        }
+
+        // For objects, try to pretty-print with truncation
+        if let Ok(pretty) = serde_json::to_string_pretty(&json) {
+            if pretty.len() <= max_len {
+                return pretty;
+            }
+        }
+    }
+
+    // Fallback: simple truncation
+    truncate_content(content, max_len, "head")
+}
+
+/// Largest char boundary at or before `index`
+fn char_boundary(content: &str, index: usize) -> usize {
+    let mut index = index.min(content.len());
+    while !content.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_pipeline() {
+        let output = "web Running\njob Completed\napi Running\ndb Running";
+        let options = OutputOptions {
+            grep: Some("Running".to_string()),
+            head: Some(2),
+            format: Some("lines".to_string()),
+            ..Default::default()
+        };
+        let processed = options.apply(output).unwrap();
+        assert_eq!(processed.content, r#"["web Running","api Running"]"#);
+        assert_eq!(processed.grep_matches, Some(3));
+        assert!(processed.truncated);
+        assert_eq!(processed.processing.len(), 3);
+
+        let inverted = OutputOptions {
+            grep: Some("Running".to_string()),
+            grep_invert: true,
+            format: Some("count".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(inverted.apply(output).unwrap().content, "1 lines");
+    }
+
+    #[test]
+    fn test_apply_jq_and_truncate() {
+        let output = r#"{"items": [{"name": "a"}, {"name": "b"}]}"#;
+        let options = OutputOptions {
+            jq: Some(".items | map(.name)".to_string()),
+            format: Some("compact".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.apply(output).unwrap().content,
+            "[\n\"a\",\n\"b\"\n]"
+        );
+        assert!(options.apply("not json").is_err());
+
+        let long = "é".repeat(500);
+        let truncated = OutputOptions {
+            max_output: Some(200),
+            truncate: Some("middle".to_string()),
+            ..Default::default()
+        };
+        let processed = truncated.apply(&long).unwrap();
+        assert!(processed.truncated);
+        assert!(processed.content.contains("TRUNCATED"));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(OutputOptions::default().validate().is_ok());
+        assert!(OutputOptions::default().is_empty());
+        let bad = [
+            OutputOptions {
+                grep: Some("(".to_string()),
+                ..Default::default()
+            },
+            OutputOptions {
+                jq: Some(".items[".to_string()),
+                ..Default::default()
+            },
+            OutputOptions {
+                format: Some("yaml".to_string()),
+                ..Default::default()
+            },
+            OutputOptions {
+                truncate: Some("random".to_string()),
+                ..Default::default()
+            },
+        ];
+        for options in bad {
+            assert!(options.validate().is_err(), "{:?}", options);
+        }
+    }
+}
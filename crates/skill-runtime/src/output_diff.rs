@@ -0,0 +1,304 @@
+//! Comparing a tool's output between runs.
+//!
+//! Re-running a past execution and diffing the result against the original
+//! catches drift (`kubectl get`, `terraform show`) and regressions. JSON
+//! output is compared structurally, by jq-style path; anything else line by
+//! line.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+/// Larger changed regions are reported as a whole instead of line by line
+const MAX_LINE_DIFF_CELLS: usize = 4_000_000;
+
+/// Differences between an original and a new output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OutputDiff {
+    /// Both outputs are JSON: values that changed, by path
+    Json {
+        /// Changed values
+        changes: Vec<JsonChange>,
+    },
+    /// Lines removed from or added to the output
+    Text {
+        /// Changed lines
+        changes: Vec<LineChange>,
+    },
+}
+
+/// A value that differs between two JSON outputs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonChange {
+    /// jq-style path, e.g. `.items[0].status`
+    pub path: String,
+    /// Original value; absent if the value was added
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<Value>,
+    /// New value; absent if the value was removed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<Value>,
+}
+
+/// A line removed from or added to a text output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineChange {
+    /// Whether the line was removed or added
+    pub op: LineOp,
+    /// 1-based line number in the original (removed) or new (added) output
+    pub line: usize,
+    /// Line content
+    pub text: String,
+}
+
+/// Kind of [`LineChange`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineOp {
+    /// Only in the original output
+    Removed,
+    /// Only in the new output
+    Added,
+}
+
+impl OutputDiff {
+    /// Compare two outputs, structurally if both are JSON.
+    pub fn between(before: &str, after: &str) -> Self {
+        match (
+            serde_json::from_str::<Value>(before),
+            serde_json::from_str::<Value>(after),
+        ) {
+            (Ok(before), Ok(after)) => {
+                let mut changes = Vec::new();
+                diff_json(String::new(), &before, &after, &mut changes);
+                Self::Json { changes }
+            }
+            _ => Self::Text {
+                changes: diff_lines(before, after),
+            },
+        }
+    }
+
+    /// Whether the outputs are identical.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Json { changes } => changes.is_empty(),
+            Self::Text { changes } => changes.is_empty(),
+        }
+    }
+}
+
+impl fmt::Display for OutputDiff {
+    /// `-`/`+`/`~` prefixed lines, one per change.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json { changes } => {
+                for change in changes {
+                    match (&change.before, &change.after) {
+                        (Some(before), Some(after)) => {
+                            writeln!(f, "~ {}: {} -> {}", change.path, before, after)?
+                        }
+                        (Some(before), None) => writeln!(f, "- {}: {}", change.path, before)?,
+                        (None, Some(after)) => writeln!(f, "+ {}: {}", change.path, after)?,
+                        (None, None) => writeln!(f, "~ {}", change.path)?,
+                    }
+                }
+            }
+            Self::Text { changes } => {
+                for change in changes {
+                    let sign = match change.op {
+                        LineOp::Removed => '-',
+                        LineOp::Added => '+',
+                    };
+                    writeln!(f, "{} {:>5} | {}", sign, change.line, change.text)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn diff_json(path: String, before: &Value, after: &Value, changes: &mut Vec<JsonChange>) {
+    let change = |path: String, before: Option<&Value>, after: Option<&Value>| JsonChange {
+        path: if path.is_empty() {
+            ".".to_string()
+        } else {
+            path
+        },
+        before: before.cloned(),
+        after: after.cloned(),
+    };
+
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            for (key, old) in before {
+                let path = key_path(&path, key);
+                match after.get(key) {
+                    Some(new) => diff_json(path, old, new, changes),
+                    None => changes.push(change(path, Some(old), None)),
+                }
+            }
+            for (key, new) in after {
+                if !before.contains_key(key) {
+                    changes.push(change(key_path(&path, key), None, Some(new)));
+                }
+            }
+        }
+        (Value::Array(before), Value::Array(after)) => {
+            for index in 0..before.len().max(after.len()) {
+                let path = format!(
+                    "{}[{}]",
+                    if path.is_empty() { "." } else { path.as_str() },
+                    index
+                );
+                match (before.get(index), after.get(index)) {
+                    (Some(old), Some(new)) => diff_json(path, old, new, changes),
+                    (old, new) => changes.push(change(path, old, new)),
+                }
+            }
+        }
+        _ if before != after => changes.push(change(path, Some(before), Some(after))),
+        _ => {}
+    }
+}
+
+/// `.key`, or `["key"]` for keys that aren't identifiers
+fn key_path(path: &str, key: &str) -> String {
+    let identifier = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if identifier {
+        format!("{}.{}", path, key)
+    } else {
+        let parent = if path.is_empty() { "." } else { path };
+        format!("{}[{}]", parent, Value::String(key.to_string()))
+    }
+}
+
+fn diff_lines(before: &str, after: &str) -> Vec<LineChange> {
+    let before: Vec<&str> = before.lines().collect();
+    let after: Vec<&str> = after.lines().collect();
+
+    // Only the region between the common prefix and suffix can differ
+    let prefix = before
+        .iter()
+        .zip(&after)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = before[prefix..]
+        .iter()
+        .rev()
+        .zip(after[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old = &before[prefix..before.len() - suffix];
+    let new = &after[prefix..after.len() - suffix];
+
+    let removed = |i: usize| LineChange {
+        op: LineOp::Removed,
+        line: prefix + i + 1,
+        text: old[i].to_string(),
+    };
+    let added = |j: usize| LineChange {
+        op: LineOp::Added,
+        line: prefix + j + 1,
+        text: new[j].to_string(),
+    };
+
+    if old.len().saturating_mul(new.len()) > MAX_LINE_DIFF_CELLS {
+        return (0..old.len())
+            .map(removed)
+            .chain((0..new.len()).map(added))
+            .collect();
+    }
+
+    // Longest common subsequence of the remaining lines
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            changes.push(removed(i));
+            i += 1;
+        } else {
+            changes.push(added(j));
+            j += 1;
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_diff() {
+        let before = r#"{"items": [{"name": "web", "ready": true}], "count": 1, "my key": 1}"#;
+        let after = r#"{"items": [{"name": "web", "ready": false}, {"name": "db"}], "count": 1}"#;
+
+        let OutputDiff::Json { changes } = OutputDiff::between(before, after) else {
+            panic!("expected a JSON diff");
+        };
+        assert_eq!(
+            changes,
+            vec![
+                JsonChange {
+                    path: ".items[0].ready".to_string(),
+                    before: Some(json!(true)),
+                    after: Some(json!(false)),
+                },
+                JsonChange {
+                    path: ".items[1]".to_string(),
+                    before: None,
+                    after: Some(json!({"name": "db"})),
+                },
+                JsonChange {
+                    path: r#".["my key"]"#.to_string(),
+                    before: Some(json!(1)),
+                    after: None,
+                },
+            ]
+        );
+        assert!(OutputDiff::between("[1, 2]", " [1,2] ").is_empty());
+    }
+
+    #[test]
+    fn test_text_diff() {
+        let before = "NAME READY\nweb  1/1\ndb   1/1\n";
+        let after = "NAME READY\nweb  0/1\ndb   1/1\ncache 1/1\n";
+
+        let diff = OutputDiff::between(before, after);
+        let OutputDiff::Text { changes } = &diff else {
+            panic!("expected a text diff");
+        };
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.op, c.line, c.text.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (LineOp::Removed, 2, "web  1/1"),
+                (LineOp::Added, 2, "web  0/1"),
+                (LineOp::Added, 4, "cache 1/1"),
+            ]
+        );
+        assert_eq!(diff.to_string().lines().next(), Some("-     2 | web  1/1"));
+        assert!(OutputDiff::between("same\n", "same\n").is_empty());
+    }
+}
@@ -0,0 +1,230 @@
+//! Paging through full execution output.
+//!
+//! When output is truncated to fit an agent's context, the full result is
+//! kept server-side so callers can page through it without re-running the
+//! tool. The HTTP API pages the output stored in its execution history; the
+//! MCP server, which has no database, keeps recent outputs in an
+//! [`OutputStore`].
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Page size when a request doesn't give one
+pub const DEFAULT_PAGE_SIZE: usize = 8000;
+
+/// One page of an execution's output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputPage {
+    /// Page content
+    pub content: String,
+    /// Offset of the page in the full output
+    pub offset: usize,
+    /// Offset of the next page, if there is more output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<usize>,
+    /// Length of the full output
+    pub total_length: usize,
+}
+
+impl OutputPage {
+    /// The page of `output` starting at `offset`, at most `limit` long.
+    ///
+    /// Offsets are byte offsets, moved back to the nearest character
+    /// boundary so pages never split a character.
+    pub fn of(output: &str, offset: usize, limit: usize) -> Self {
+        let start = char_boundary(output, offset);
+        let end = char_boundary(output, start.saturating_add(limit.max(1)));
+        // A limit smaller than the next character still makes progress
+        let end = if end == start && start < output.len() {
+            start + output[start..].chars().next().map_or(0, char::len_utf8)
+        } else {
+            end
+        };
+        Self {
+            content: output[start..end].to_string(),
+            offset: start,
+            next_offset: (end < output.len()).then_some(end),
+            total_length: output.len(),
+        }
+    }
+}
+
+/// A requested slice of output: `<start>-<end>` (end exclusive) or
+/// `<start>-` / `<start>` for a default-sized page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputRange {
+    /// Start offset
+    pub offset: usize,
+    /// Page length
+    pub limit: usize,
+}
+
+impl Default for OutputRange {
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            limit: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+impl OutputRange {
+    /// Parse a `range` parameter like `0-8000`, `8000-` or `8000`.
+    pub fn parse(range: &str) -> Result<Self> {
+        let range = range.trim();
+        let (start, end) = range.split_once('-').unwrap_or((range, ""));
+        let Ok(offset) = start.trim().parse::<usize>() else {
+            bail!("Invalid range '{}': expected <start>-<end>", range);
+        };
+        let limit = match end.trim() {
+            "" => DEFAULT_PAGE_SIZE,
+            end => match end.parse::<usize>() {
+                Ok(end) if end > offset => end - offset,
+                _ => bail!("Invalid range '{}': end must be greater than start", range),
+            },
+        };
+        Ok(Self { offset, limit })
+    }
+
+    /// The page of `output` this range selects.
+    pub fn page(&self, output: &str) -> OutputPage {
+        OutputPage::of(output, self.offset, self.limit)
+    }
+}
+
+/// Bounded in-memory store of full outputs, keyed by output ID.
+///
+/// The oldest outputs are evicted once either the entry or the size limit
+/// is exceeded.
+pub struct OutputStore {
+    entries: Mutex<VecDeque<(String, Arc<str>)>>,
+    max_entries: usize,
+    max_bytes: usize,
+}
+
+impl OutputStore {
+    /// Create a store keeping at most `max_entries` outputs and `max_bytes`
+    /// of output in total.
+    pub fn new(max_entries: usize, max_bytes: usize) -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::new()),
+            max_entries: max_entries.max(1),
+            max_bytes,
+        }
+    }
+
+    /// Store an output, returning its ID.
+    pub fn insert(&self, output: impl Into<Arc<str>>) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.push_back((id.clone(), output.into()));
+
+        let mut total: usize = entries.iter().map(|(_, output)| output.len()).sum();
+        // Always keep the newest output, even if it alone exceeds max_bytes
+        while entries.len() > 1 && (entries.len() > self.max_entries || total > self.max_bytes) {
+            if let Some((_, evicted)) = entries.pop_front() {
+                total -= evicted.len();
+            }
+        }
+        id
+    }
+
+    /// Full output stored under `id`.
+    pub fn get(&self, id: &str) -> Option<Arc<str>> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .iter()
+            .find(|(entry_id, _)| entry_id == id)
+            .map(|(_, output)| output.clone())
+    }
+
+    /// A page of the output stored under `id`.
+    pub fn page(&self, id: &str, range: OutputRange) -> Option<OutputPage> {
+        self.get(id).map(|output| range.page(&output))
+    }
+}
+
+impl Default for OutputStore {
+    /// 32 outputs, 64 MiB in total.
+    fn default() -> Self {
+        Self::new(32, 64 * 1024 * 1024)
+    }
+}
+
+/// Largest char boundary at or before `index`
+fn char_boundary(content: &str, index: usize) -> usize {
+    let mut index = index.min(content.len());
+    while !content.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_page() {
+        let page = OutputPage::of("abcdefghij", 0, 4);
+        assert_eq!(page.content, "abcd");
+        assert_eq!(page.next_offset, Some(4));
+        assert_eq!(page.total_length, 10);
+
+        let last = OutputPage::of("abcdefghij", 8, 4);
+        assert_eq!(last.content, "ij");
+        assert_eq!(last.next_offset, None);
+
+        // Never splits a character
+        let page = OutputPage::of("héllo", 0, 2);
+        assert_eq!(page.content, "h");
+        assert_eq!(OutputPage::of("héllo", 1, 1).content, "é");
+        assert_eq!(OutputPage::of("abc", 10, 4).content, "");
+    }
+
+    #[test]
+    fn test_output_range_parse() {
+        assert_eq!(
+            OutputRange::parse("100-300").unwrap(),
+            OutputRange {
+                offset: 100,
+                limit: 200
+            }
+        );
+        assert_eq!(
+            OutputRange::parse("8000-").unwrap(),
+            OutputRange {
+                offset: 8000,
+                limit: DEFAULT_PAGE_SIZE
+            }
+        );
+        assert_eq!(OutputRange::parse("42").unwrap().offset, 42);
+        assert!(OutputRange::parse("300-100").is_err());
+        assert!(OutputRange::parse("abc").is_err());
+    }
+
+    #[test]
+    fn test_output_store_evicts_oldest() {
+        let store = OutputStore::new(2, 1024);
+        let first = store.insert("one");
+        let second = store.insert("two");
+        let third = store.insert("three");
+        assert!(store.get(&first).is_none());
+        assert_eq!(store.get(&second).as_deref(), Some("two"));
+        assert_eq!(
+            store
+                .page(&third, OutputRange::parse("1-3").unwrap())
+                .unwrap()
+                .content,
+            "hr"
+        );
+
+        let small = OutputStore::new(10, 8);
+        let big = small.insert("0123456789");
+        let newer = small.insert("abcdefghij");
+        assert!(small.get(&big).is_none());
+        assert!(small.get(&newer).is_some());
+    }
+}
@@ -0,0 +1,311 @@
+//! Distributable skill packages (`.skillpkg`)
+//!
+//! A package is a gzipped tarball holding everything needed to install a
+//! skill without building it:
+//!
+//! ```text
+//! skill.wasm          pre-built component
+//! SKILL.md            documentation (optional)
+//! skill.yaml          metadata and dependencies (optional)
+//! skill.sig           signature from `skill sign` (optional)
+//! scripts/...         helper scripts (optional)
+//! manifest.toml       `[skills.<name>]` fragment for .skill-engine.toml
+//! integrity.json      SHA-256 of every other file
+//! ```
+//!
+//! Packages install like any archive (`skill install ./aws-1.2.0.skillpkg` or
+//! an HTTPS URL); `integrity.json` is checked after extraction and any file
+//! that is missing, modified, or not listed aborts the install.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::archive_loader::find_wasm;
+use crate::checksum::sha256_hex;
+use crate::dependencies::{read_declared_dependencies, SkillDependency};
+use crate::git_loader::extract_metadata;
+use crate::signing::SIGNATURE_FILE;
+
+/// File extension of skill packages
+pub const PACKAGE_EXTENSION: &str = "skillpkg";
+
+/// Integrity manifest inside a package
+pub const INTEGRITY_FILE: &str = "integrity.json";
+
+/// Manifest fragment inside a package
+pub const MANIFEST_FRAGMENT_FILE: &str = "manifest.toml";
+
+const INTEGRITY_VERSION: u32 = 1;
+
+/// Optional files copied from the skill directory when present
+const OPTIONAL_FILES: &[&str] = &["SKILL.md", "skill.yaml", "README.md", SIGNATURE_FILE];
+
+/// Optional directories copied recursively when present
+const OPTIONAL_DIRS: &[&str] = &["scripts"];
+
+/// Contents of `integrity.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackageIntegrity {
+    /// Integrity format version
+    pub version: u32,
+    /// Skill name
+    pub name: String,
+    /// Skill version, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skill_version: Option<String>,
+    /// When the package was built (RFC 3339)
+    pub created_at: String,
+    /// SHA-256 (hex) of each packaged file, keyed by relative path
+    pub files: BTreeMap<String, String>,
+}
+
+impl PackageIntegrity {
+    /// Check every file under `dir` against the manifest
+    ///
+    /// Files that are missing, modified, or not listed are errors.
+    pub fn verify(&self, dir: &Path) -> Result<()> {
+        if self.version != INTEGRITY_VERSION {
+            anyhow::bail!("Unsupported package integrity version {}", self.version);
+        }
+
+        for (file, expected) in &self.files {
+            let path = dir.join(file);
+            let bytes =
+                std::fs::read(&path).with_context(|| format!("Package file missing: {}", file))?;
+            if sha256_hex(&bytes) != *expected {
+                anyhow::bail!("Package file modified: {}", file);
+            }
+        }
+
+        for file in package_files(dir)? {
+            if file != INTEGRITY_FILE && !self.files.contains_key(&file) {
+                anyhow::bail!("Package contains unlisted file: {}", file);
+            }
+        }
+        Ok(())
+    }
+
+    /// Read `integrity.json` from an extracted package, if present
+    pub fn load(dir: &Path) -> Result<Option<Self>> {
+        let path = dir.join(INTEGRITY_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(&path)?;
+        let integrity = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid {}", path.display()))?;
+        Ok(Some(integrity))
+    }
+}
+
+/// A package written by [`build_package`]
+#[derive(Debug, Clone)]
+pub struct BuiltPackage {
+    /// Path of the `.skillpkg` file
+    pub path: PathBuf,
+    /// Integrity manifest stored in the package
+    pub integrity: PackageIntegrity,
+    /// SHA-256 of the package file itself (for `#sha256=` pins)
+    pub sha256: String,
+}
+
+/// Assemble a skill directory into `<output_dir>/<name>-<version>.skillpkg`
+///
+/// The skill must already be built: the component is looked up as
+/// `skill.wasm`, `dist/skill.wasm`, `build/skill.wasm`, or a single `*.wasm`,
+/// and is stored as `skill.wasm`.
+pub fn build_package(skill_dir: &Path, output_dir: &Path) -> Result<BuiltPackage> {
+    let default_name = skill_dir
+        .canonicalize()
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "skill".to_string());
+    let (name, version) = extract_metadata(skill_dir, &default_name)?;
+    let wasm = find_wasm(skill_dir).context("Build the skill before packaging it")?;
+
+    // (path in package, path on disk)
+    let mut entries: BTreeMap<String, PathBuf> = BTreeMap::new();
+    entries.insert("skill.wasm".to_string(), wasm);
+    for file in OPTIONAL_FILES {
+        let path = skill_dir.join(file);
+        if path.is_file() {
+            entries.insert(file.to_string(), path);
+        }
+    }
+    for dir in OPTIONAL_DIRS {
+        let root = skill_dir.join(dir);
+        if !root.is_dir() {
+            continue;
+        }
+        for entry in walkdir::WalkDir::new(&root).sort_by_file_name() {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let relative = entry.path().strip_prefix(skill_dir)?;
+            entries.insert(to_package_path(relative), entry.path().to_path_buf());
+        }
+    }
+
+    let package_name = match &version {
+        Some(version) => format!("{}-{}", name, version),
+        None => name.clone(),
+    };
+    let fragment = manifest_fragment(
+        &name,
+        &format!("{}.{}", package_name, PACKAGE_EXTENSION),
+        &read_declared_dependencies(skill_dir)?,
+    );
+
+    let mut files = BTreeMap::new();
+    for (name, path) in &entries {
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        files.insert(name.clone(), sha256_hex(&bytes));
+    }
+    files.insert(
+        MANIFEST_FRAGMENT_FILE.to_string(),
+        sha256_hex(fragment.as_bytes()),
+    );
+
+    let integrity = PackageIntegrity {
+        version: INTEGRITY_VERSION,
+        name: name.clone(),
+        skill_version: version,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        files,
+    };
+    let integrity_json = serde_json::to_string_pretty(&integrity)? + "\n";
+
+    std::fs::create_dir_all(output_dir)?;
+    let path = output_dir.join(format!("{}.{}", package_name, PACKAGE_EXTENSION));
+    let file = std::fs::File::create(&path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        file,
+        flate2::Compression::default(),
+    ));
+    for (name, source) in &entries {
+        builder.append_path_with_name(source, name)?;
+    }
+    append_bytes(&mut builder, MANIFEST_FRAGMENT_FILE, fragment.as_bytes())?;
+    append_bytes(&mut builder, INTEGRITY_FILE, integrity_json.as_bytes())?;
+    builder.into_inner()?.finish()?;
+
+    let sha256 = sha256_hex(&std::fs::read(&path)?);
+    Ok(BuiltPackage {
+        path,
+        integrity,
+        sha256,
+    })
+}
+
+/// `[skills.<name>]` entry for the installed package
+fn manifest_fragment(name: &str, package_file: &str, dependencies: &[SkillDependency]) -> String {
+    let mut fragment = format!(
+        "# Install with `skill install {}`, then add to .skill-engine.toml\n\
+         [skills.{}]\n\
+         source = \"{}\"\n",
+        package_file, name, name
+    );
+    if !dependencies.is_empty() {
+        let names: Vec<String> = dependencies
+            .iter()
+            .map(|dep| format!("\"{}\"", dep.name()))
+            .collect();
+        fragment.push_str(&format!("depends_on = [{}]\n", names.join(", ")));
+    }
+    fragment
+}
+
+fn append_bytes<W: std::io::Write>(
+    builder: &mut tar::Builder<W>,
+    name: &str,
+    bytes: &[u8],
+) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp() as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, name, bytes)?;
+    Ok(())
+}
+
+/// Relative paths of all files under `dir`, with `/` separators
+fn package_files(dir: &Path) -> Result<Vec<String>> {
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(dir) {
+        let entry = entry?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+        files.push(to_package_path(entry.path().strip_prefix(dir)?));
+    }
+    Ok(files)
+}
+
+fn to_package_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archive_loader::{extract_archive, ArchiveFormat};
+
+    #[test]
+    fn test_build_and_verify_package() {
+        let skill = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(skill.path().join("dist")).unwrap();
+        std::fs::create_dir_all(skill.path().join("scripts")).unwrap();
+        std::fs::write(skill.path().join("dist/skill.wasm"), b"\0asm").unwrap();
+        std::fs::write(
+            skill.path().join("SKILL.md"),
+            "---\nname: aws\nversion: 1.2.0\n---\n# AWS\n",
+        )
+        .unwrap();
+        std::fs::write(skill.path().join("scripts/setup.sh"), "echo hi\n").unwrap();
+
+        let out = tempfile::TempDir::new().unwrap();
+        let package = build_package(skill.path(), out.path()).unwrap();
+        assert_eq!(package.path, out.path().join("aws-1.2.0.skillpkg"));
+        assert_eq!(
+            package.integrity.files.keys().collect::<Vec<_>>(),
+            vec![
+                "SKILL.md",
+                "manifest.toml",
+                "scripts/setup.sh",
+                "skill.wasm"
+            ]
+        );
+
+        let extracted = tempfile::TempDir::new().unwrap();
+        let bytes = std::fs::read(&package.path).unwrap();
+        extract_archive(&bytes, ArchiveFormat::TarGz, extracted.path()).unwrap();
+        let integrity = PackageIntegrity::load(extracted.path()).unwrap().unwrap();
+        assert_eq!(integrity, package.integrity);
+        integrity.verify(extracted.path()).unwrap();
+
+        std::fs::write(extracted.path().join("skill.wasm"), b"tampered").unwrap();
+        let err = integrity.verify(extracted.path()).unwrap_err().to_string();
+        assert_eq!(err, "Package file modified: skill.wasm");
+
+        std::fs::write(extracted.path().join("skill.wasm"), b"\0asm").unwrap();
+        std::fs::write(extracted.path().join("extra.sh"), "rm -rf /").unwrap();
+        let err = integrity.verify(extracted.path()).unwrap_err().to_string();
+        assert_eq!(err, "Package contains unlisted file: extra.sh");
+    }
+
+    #[test]
+    fn test_package_requires_wasm() {
+        let skill = tempfile::TempDir::new().unwrap();
+        let out = tempfile::TempDir::new().unwrap();
+        assert!(build_package(skill.path(), out.path()).is_err());
+    }
+}
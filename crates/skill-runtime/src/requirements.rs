@@ -0,0 +1,566 @@
+//! Capability probes - what a skill needs from the host to work
+//!
+//! Skills declare requirements in the project manifest:
+//!
+//! ```toml
+//! [skills.kubernetes]
+//! source = "./skills/kubernetes"
+//! requires = ["binary:kubectl>=1.27", "env:KUBECONFIG", "port:8001"]
+//! ```
+//!
+//! in their own `skill.yaml` / SKILL.md frontmatter (`requires:`), or per
+//! tool with a `**Requires**:` line in the tool's SKILL.md section.
+//!
+//! - `binary:<name>` - on `PATH`; `binary:<name>>=<version>` also checks the
+//!   version it reports
+//! - `env:<NAME>` - environment variable set and non-empty
+//! - `port:<port>` - something listening on localhost
+//!
+//! Discovery probes requirements so unavailable tools can be hidden or
+//! annotated instead of failing when they run.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::skill_md::{find_skill_md, parse_skill_md};
+
+/// How long a binary gets to report its version
+const VERSION_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How long to wait for a port to accept a connection
+const PORT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Something a skill needs from the host
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Requirement {
+    /// An executable on `PATH`, optionally with a minimum version
+    Binary {
+        /// Executable name
+        name: String,
+        /// Minimum version (`1.27`), compared component by component
+        min_version: Option<String>,
+    },
+    /// A non-empty environment variable
+    Env(String),
+    /// A port on localhost accepting connections
+    Port(u16),
+}
+
+impl FromStr for Requirement {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (kind, value) = s
+            .split_once(':')
+            .map(|(kind, value)| (kind.trim(), value.trim()))
+            .with_context(|| format!("Invalid requirement '{}': expected <kind>:<value>", s))?;
+        if value.is_empty() {
+            bail!("Invalid requirement '{}': missing value", s);
+        }
+
+        match kind {
+            "binary" | "bin" => {
+                let (name, min_version) = match value.split_once(">=") {
+                    Some((name, version)) => {
+                        let version = version.trim();
+                        if parse_version(version).is_none() {
+                            bail!("Invalid requirement '{}': bad version '{}'", s, version);
+                        }
+                        (name.trim(), Some(version.to_string()))
+                    }
+                    None => (value, None),
+                };
+                Ok(Self::Binary {
+                    name: name.to_string(),
+                    min_version,
+                })
+            }
+            "env" => Ok(Self::Env(value.to_string())),
+            "port" => value
+                .parse()
+                .map(Self::Port)
+                .with_context(|| format!("Invalid requirement '{}': bad port", s)),
+            _ => bail!(
+                "Invalid requirement '{}': unknown kind '{}' (expected binary, env or port)",
+                s,
+                kind
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Binary {
+                name,
+                min_version: Some(version),
+            } => write!(f, "binary:{}>={}", name, version),
+            Self::Binary { name, .. } => write!(f, "binary:{}", name),
+            Self::Env(name) => write!(f, "env:{}", name),
+            Self::Port(port) => write!(f, "port:{}", port),
+        }
+    }
+}
+
+impl Serialize for Requirement {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Requirement {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|e: anyhow::Error| serde::de::Error::custom(e))
+    }
+}
+
+impl Requirement {
+    /// Parse a comma-separated list such as a `**Requires**:` line,
+    /// skipping (and logging) entries that don't parse
+    pub fn parse_list(value: &str) -> Vec<Self> {
+        value
+            .split(',')
+            .map(|entry| entry.trim().trim_matches('`'))
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|entry| match entry.parse() {
+                Ok(requirement) => Some(requirement),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Ignoring requirement");
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Probe the host for this requirement
+    pub async fn check(&self) -> RequirementStatus {
+        let (met, detail) = match self {
+            Self::Binary { name, min_version } => check_binary(name, min_version.as_deref()).await,
+            Self::Env(name) => match std::env::var_os(name) {
+                Some(value) if !value.is_empty() => (true, None),
+                _ => (false, Some("not set".to_string())),
+            },
+            Self::Port(port) => {
+                let connect = tokio::net::TcpStream::connect(("127.0.0.1", *port));
+                match tokio::time::timeout(PORT_TIMEOUT, connect).await {
+                    Ok(Ok(_)) => (true, None),
+                    _ => (false, Some("nothing listening".to_string())),
+                }
+            }
+        };
+        RequirementStatus {
+            requirement: self.clone(),
+            met,
+            detail,
+        }
+    }
+}
+
+/// Result of probing one requirement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequirementStatus {
+    /// The requirement probed
+    pub requirement: Requirement,
+    /// Whether the host meets it
+    pub met: bool,
+    /// What was found (`found 1.25.3`) or why it isn't met
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl fmt::Display for RequirementStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.detail {
+            Some(detail) => write!(f, "{} ({})", self.requirement, detail),
+            None => write!(f, "{}", self.requirement),
+        }
+    }
+}
+
+/// Probes requirements, remembering results for a while so discovering
+/// many tools of the same skill doesn't rerun the same probes
+pub struct RequirementProbe {
+    ttl: Duration,
+    cache: Mutex<HashMap<Requirement, (Instant, RequirementStatus)>>,
+}
+
+impl Default for RequirementProbe {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(30))
+    }
+}
+
+impl RequirementProbe {
+    /// Create a probe that reuses results for `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Status of every requirement, in order
+    pub async fn check(&self, requirements: &[Requirement]) -> Vec<RequirementStatus> {
+        let mut statuses = Vec::with_capacity(requirements.len());
+        for requirement in requirements {
+            let cached = self
+                .cache
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(requirement)
+                .filter(|(checked_at, _)| checked_at.elapsed() < self.ttl)
+                .map(|(_, status)| status.clone());
+            let status = match cached {
+                Some(status) => status,
+                None => {
+                    let status = requirement.check().await;
+                    self.cache
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .insert(requirement.clone(), (Instant::now(), status.clone()));
+                    status
+                }
+            };
+            statuses.push(status);
+        }
+        statuses
+    }
+
+    /// Requirements the host doesn't meet
+    pub async fn unmet(&self, requirements: &[Requirement]) -> Vec<RequirementStatus> {
+        self.check(requirements)
+            .await
+            .into_iter()
+            .filter(|status| !status.met)
+            .collect()
+    }
+
+    /// Version a binary on `PATH` reports (`1.29.2`), if it reports one
+    pub async fn binary_version(&self, name: &str) -> Option<String> {
+        // Any minimum makes the probe ask the binary for its version
+        let requirement = Requirement::Binary {
+            name: name.to_string(),
+            min_version: Some("0".to_string()),
+        };
+        let status = self.check(std::slice::from_ref(&requirement)).await.pop()?;
+        status.detail?.strip_prefix("found ").map(str::to_string)
+    }
+}
+
+/// Requirements of a skill and of each of its tools
+#[derive(Debug, Clone, Default)]
+pub struct SkillRequirements {
+    /// Requirements of every tool in the skill
+    pub skill: Vec<Requirement>,
+    /// Extra requirements of individual tools (`**Requires**:` lines)
+    pub tools: HashMap<String, Vec<Requirement>>,
+}
+
+impl SkillRequirements {
+    /// Requirements from the manifest plus those the skill directory declares
+    pub fn load(manifest_requires: &[Requirement], dir: &Path) -> Self {
+        let mut skill = manifest_requires.to_vec();
+        match read_declared_requirements(dir) {
+            Ok(declared) => skill.extend(declared),
+            Err(e) => {
+                tracing::warn!(error = %e, path = %dir.display(), "Ignoring declared requirements")
+            }
+        }
+        dedup(&mut skill);
+
+        let tools = find_skill_md(dir)
+            .and_then(|path| parse_skill_md(&path).ok())
+            .map(|md| {
+                md.tool_docs
+                    .into_iter()
+                    .filter(|(_, doc)| !doc.requires.is_empty())
+                    .map(|(name, doc)| (name, doc.requires))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self { skill, tools }
+    }
+
+    /// Everything `tool` needs: the skill's requirements, then its own
+    pub fn for_tool(&self, tool: &str) -> Vec<Requirement> {
+        let mut requirements = self.skill.clone();
+        if let Some(extra) = self.tools.get(tool) {
+            requirements.extend(extra.iter().cloned());
+        }
+        dedup(&mut requirements);
+        requirements
+    }
+}
+
+/// Requirements declared by a skill directory (`skill.yaml`, then SKILL.md frontmatter)
+pub fn read_declared_requirements(dir: &Path) -> Result<Vec<Requirement>> {
+    #[derive(Deserialize)]
+    struct Declared {
+        #[serde(default)]
+        requires: Vec<Requirement>,
+    }
+
+    let skill_yaml = dir.join("skill.yaml");
+    if skill_yaml.exists() {
+        let contents = std::fs::read_to_string(&skill_yaml)?;
+        let declared: Declared = serde_yaml::from_str(&contents)
+            .with_context(|| format!("Invalid requirements in {}", skill_yaml.display()))?;
+        return Ok(declared.requires);
+    }
+
+    let skill_md = dir.join("SKILL.md");
+    if skill_md.exists() {
+        let contents = std::fs::read_to_string(&skill_md)?;
+        if let Some(frontmatter) = contents
+            .strip_prefix("---")
+            .and_then(|rest| rest.split_once("\n---").map(|(fm, _)| fm))
+        {
+            let declared: Declared = serde_yaml::from_str(frontmatter)
+                .with_context(|| format!("Invalid requirements in {}", skill_md.display()))?;
+            return Ok(declared.requires);
+        }
+    }
+
+    Ok(Vec::new())
+}
+
+fn dedup(requirements: &mut Vec<Requirement>) {
+    let mut seen = Vec::new();
+    requirements.retain(|requirement| {
+        if seen.contains(requirement) {
+            false
+        } else {
+            seen.push(requirement.clone());
+            true
+        }
+    });
+}
+
+async fn check_binary(name: &str, min_version: Option<&str>) -> (bool, Option<String>) {
+    let Some(path) = find_on_path(name) else {
+        return (false, Some("not on PATH".to_string()));
+    };
+    let Some(min_version) = min_version else {
+        return (true, None);
+    };
+    let wanted = parse_version(min_version).unwrap_or_default();
+
+    // Tools disagree on how to report their version
+    let attempts: [&[&str]; 3] = [&["--version"], &["version", "--client"], &["version"]];
+    for args in attempts {
+        let output = tokio::process::Command::new(&path)
+            .args(args)
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .output();
+        let Ok(Ok(output)) = tokio::time::timeout(VERSION_TIMEOUT, output).await else {
+            continue;
+        };
+        let text = format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        if let Some(found) = find_version(&text) {
+            let met = compare_versions(&found, &wanted).is_ge();
+            let found = found
+                .iter()
+                .map(u64::to_string)
+                .collect::<Vec<_>>()
+                .join(".");
+            return (met, Some(format!("found {}", found)));
+        }
+    }
+    (false, Some("couldn't determine version".to_string()))
+}
+
+pub(crate) fn find_on_path(name: &str) -> Option<std::path::PathBuf> {
+    if name.contains(std::path::MAIN_SEPARATOR) {
+        let path = std::path::PathBuf::from(name);
+        return path.is_file().then_some(path);
+    }
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths).find_map(|dir| {
+        let candidate = dir.join(name);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        let exe = dir.join(format!("{}.exe", name));
+        (cfg!(windows) && exe.is_file()).then_some(exe)
+    })
+}
+
+/// `1.27.3` -> `[1, 27, 3]`, accepting a leading `v`
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<u64>>>()
+        .filter(|parts| !parts.is_empty())
+}
+
+/// First dotted version number in a `--version` output
+/// (`Client Version: v1.29.0`, `Docker version 24.0.7, build ...`)
+fn find_version(text: &str) -> Option<Vec<u64>> {
+    let bytes = text.as_bytes();
+    let mut start = 0;
+    while start < bytes.len() {
+        if !bytes[start].is_ascii_digit() {
+            start += 1;
+            continue;
+        }
+        let mut end = start;
+        while end < bytes.len() && (bytes[end].is_ascii_digit() || bytes[end] == b'.') {
+            end += 1;
+        }
+        let candidate = text[start..end].trim_end_matches('.');
+        if candidate.contains('.') {
+            if let Some(version) = parse_version(candidate) {
+                return Some(version);
+            }
+        }
+        start = end;
+    }
+    None
+}
+
+fn compare_versions(a: &[u64], b: &[u64]) -> std::cmp::Ordering {
+    let len = a.len().max(b.len());
+    let part = |v: &[u64], i: usize| v.get(i).copied().unwrap_or(0);
+    (0..len)
+        .map(|i| part(a, i).cmp(&part(b, i)))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_requirements() {
+        assert_eq!(
+            "binary:kubectl>=1.27".parse::<Requirement>().unwrap(),
+            Requirement::Binary {
+                name: "kubectl".to_string(),
+                min_version: Some("1.27".to_string()),
+            }
+        );
+        assert_eq!(
+            "env:KUBECONFIG".parse::<Requirement>().unwrap(),
+            Requirement::Env("KUBECONFIG".to_string())
+        );
+        assert_eq!(
+            "port:8001".parse::<Requirement>().unwrap(),
+            Requirement::Port(8001)
+        );
+        for s in ["binary:kubectl>=1.27", "binary:jq", "env:HOME", "port:80"] {
+            assert_eq!(s.parse::<Requirement>().unwrap().to_string(), s);
+        }
+
+        assert!("kubectl".parse::<Requirement>().is_err());
+        assert!("port:http".parse::<Requirement>().is_err());
+        assert!("binary:kubectl>=latest".parse::<Requirement>().is_err());
+        assert!("disk:10G".parse::<Requirement>().is_err());
+
+        assert_eq!(
+            Requirement::parse_list("`binary:helm`, env:KUBECONFIG, nonsense"),
+            vec![
+                Requirement::Binary {
+                    name: "helm".to_string(),
+                    min_version: None,
+                },
+                Requirement::Env("KUBECONFIG".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_versions() {
+        assert_eq!(
+            find_version("Client Version: v1.29.0\nKustomize Version: v5.0.4"),
+            Some(vec![1, 29, 0])
+        );
+        assert_eq!(
+            find_version("Docker version 24.0.7, build afdd53b"),
+            Some(vec![24, 0, 7])
+        );
+        assert_eq!(
+            find_version("version.BuildInfo{Version:\"v3.12.0\"}"),
+            Some(vec![3, 12, 0])
+        );
+        assert_eq!(find_version("jq-1.7.1"), Some(vec![1, 7, 1]));
+        assert_eq!(find_version("no version here 42"), None);
+
+        assert!(compare_versions(&[1, 29, 0], &[1, 27]).is_gt());
+        assert!(compare_versions(&[1, 27], &[1, 27, 0]).is_eq());
+        assert!(compare_versions(&[1, 25, 3], &[1, 27]).is_lt());
+    }
+
+    #[tokio::test]
+    async fn test_check() {
+        let probe = RequirementProbe::default();
+        let requirements = [
+            Requirement::Env("SKILL_TEST_REQUIREMENT_UNSET".to_string()),
+            Requirement::Binary {
+                name: "skill-test-no-such-binary".to_string(),
+                min_version: None,
+            },
+        ];
+        let unmet = probe.unmet(&requirements).await;
+        assert_eq!(unmet.len(), 2);
+        assert_eq!(
+            unmet[0].to_string(),
+            "env:SKILL_TEST_REQUIREMENT_UNSET (not set)"
+        );
+        assert_eq!(
+            unmet[1].to_string(),
+            "binary:skill-test-no-such-binary (not on PATH)"
+        );
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(probe.unmet(&[Requirement::Port(port)]).await.is_empty());
+
+        assert_eq!(probe.binary_version("skill-test-no-such-binary").await, None);
+    }
+
+    #[test]
+    fn test_skill_requirements() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("SKILL.md"),
+            "---\nname: kubernetes\ndescription: Kubernetes\nrequires:\n  - binary:kubectl>=1.27\n---\n# Kubernetes\n\n## Tools\n\n### proxy\n\nTalk to the API through kubectl proxy.\n\n**Requires**: port:8001, binary:kubectl>=1.27\n",
+        )
+        .unwrap();
+
+        let requirements =
+            SkillRequirements::load(&[Requirement::Env("KUBECONFIG".to_string())], dir.path());
+        let kubectl: Requirement = "binary:kubectl>=1.27".parse().unwrap();
+        assert_eq!(
+            requirements.skill,
+            vec![Requirement::Env("KUBECONFIG".to_string()), kubectl.clone()]
+        );
+        assert_eq!(
+            requirements.for_tool("proxy"),
+            vec![
+                Requirement::Env("KUBECONFIG".to_string()),
+                kubectl,
+                Requirement::Port(8001)
+            ]
+        );
+        assert_eq!(requirements.for_tool("get").len(), 2);
+    }
+}
@@ -1,21 +1,150 @@
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use skill_context::{ExecutionContext, Mount, MountType};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use wasmtime_wasi::{
-    ResourceTable, WasiCtx, WasiCtxBuilder, WasiView,
+    pipe::MemoryInputPipe, DirPerms, FilePerms, ResourceTable, SocketAddrUse, WasiCtx,
+    WasiCtxBuilder, WasiView,
 };
+use wasmtime_wasi_http::body::HyperOutgoingBody;
+use wasmtime_wasi_http::types::{HostFutureIncomingResponse, OutgoingRequestConfig};
+use wasmtime_wasi_http::{HttpResult, WasiHttpCtx, WasiHttpView};
 
+use crate::audit::AuditLogger;
+use crate::http_proxy::{record_blocked, HttpProxy};
 use crate::instance::InstanceConfig;
+use crate::network::NetworkPolicy;
 
 /// Host state for WASI context
 pub struct HostState {
     /// WASI context for the sandboxed environment
     pub wasi: WasiCtx,
+    /// WASI HTTP context for outbound requests
+    pub http: WasiHttpCtx,
     /// Resource table for managing WASI resources
     pub table: ResourceTable,
     /// Unique identifier for this skill instance
     pub instance_id: String,
+    /// Name of the skill running in this sandbox (for audit entries)
+    pub skill_name: String,
     /// Configuration key-value pairs passed as environment variables
     pub config: std::collections::HashMap<String, String>,
+    /// Outbound network policy
+    pub network: Arc<NetworkPolicy>,
+    /// Directories preopened for the guest
+    pub mounts: Vec<GrantedMount>,
+    /// Linear memory high-water mark, once installed as the store's limiter
+    pub memory: MemoryMeter,
+    /// Host-side proxy for the guest's wasi:http requests
+    proxy: HttpProxy,
+    /// Scratch space for staged files and tmpfs mounts, removed on drop
+    _scratch: ScratchDir,
+}
+
+/// Per-execution scratch directory, removed with everything in it on drop
+///
+/// Staged config files are rendered templates and may hold secrets, so the
+/// directory is only accessible to the current user and doesn't outlive the
+/// sandbox.
+struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    fn create(path: PathBuf) -> Result<Self> {
+        // Parents are shared with other sandboxes; only this one is private
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut builder = std::fs::DirBuilder::new();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+            builder.mode(0o700);
+        }
+        builder.create(&path)?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_dir_all(&self.0) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!(
+                    path = %self.0.display(),
+                    error = %e,
+                    "Failed to remove sandbox scratch directory"
+                );
+            }
+        }
+    }
+}
+
+/// Write a staged file readable only by the current user
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(contents)
+}
+
+/// Tracks how much linear memory a guest has allocated
+///
+/// Installed with `store.limiter(|state| &mut state.memory)`; it never
+/// refuses growth, it only records it. WASM memories can't shrink, so the
+/// total after execution is also the peak.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryMeter {
+    allocated: usize,
+    peak: usize,
+}
+
+impl MemoryMeter {
+    /// Highest total linear memory size seen, in kilobytes
+    pub fn peak_kb(&self) -> u64 {
+        (self.peak / 1024) as u64
+    }
+}
+
+impl wasmtime::ResourceLimiter for MemoryMeter {
+    fn memory_growing(
+        &mut self,
+        current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        self.allocated = self.allocated.saturating_add(desired.saturating_sub(current));
+        self.peak = self.peak.max(self.allocated);
+        Ok(true)
+    }
+
+    fn table_growing(
+        &mut self,
+        _current: usize,
+        _desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        Ok(true)
+    }
+}
+
+/// A host directory preopened in the sandbox
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrantedMount {
+    /// Mount id (or a generated id for staged files and the scratch dir)
+    pub id: String,
+    /// Directory on the host
+    pub host_path: PathBuf,
+    /// Path the guest sees
+    pub guest_path: String,
+    /// Whether the guest can only read
+    pub read_only: bool,
 }
 
 impl WasiView for HostState {
@@ -28,14 +157,38 @@ impl WasiView for HostState {
     }
 }
 
+impl WasiHttpView for HostState {
+    fn ctx(&mut self) -> &mut WasiHttpCtx {
+        &mut self.http
+    }
+
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+
+    fn send_request(
+        &mut self,
+        request: hyper::Request<HyperOutgoingBody>,
+        config: OutgoingRequestConfig,
+    ) -> HttpResult<HostFutureIncomingResponse> {
+        self.proxy.send(request, config)
+    }
+}
+
 /// Builder for creating sandboxed WASI environments
 pub struct SandboxBuilder {
     instance_id: String,
+    skill_name: String,
     instance_dir: PathBuf,
     temp_dir: PathBuf,
     env_vars: Vec<(String, String)>,
     args: Vec<String>,
     inherit_stdio: bool,
+    stdin: Option<Vec<u8>>,
+    network: NetworkPolicy,
+    mounts: Vec<Mount>,
+    audit_logger: Option<Arc<AuditLogger>>,
+    credentials_from: Option<InstanceConfig>,
 }
 
 impl SandboxBuilder {
@@ -48,14 +201,62 @@ impl SandboxBuilder {
 
         Self {
             instance_id: instance_id.into(),
+            skill_name: String::new(),
             instance_dir,
             temp_dir,
             env_vars: Vec::new(),
             args: Vec::new(),
             inherit_stdio: true,
+            stdin: None,
+            network: NetworkPolicy::deny_all(),
+            mounts: Vec::new(),
+            audit_logger: None,
+            credentials_from: None,
         }
     }
 
+    /// Grant a mount to the guest
+    ///
+    /// Directories are preopened at `target` with read-only or read-write
+    /// permissions. File and config-file mounts are staged read-only into a
+    /// scratch directory preopened at the target's parent; tmpfs mounts get
+    /// an empty per-execution directory. Docker volumes are ignored.
+    pub fn mount(mut self, mount: Mount) -> Self {
+        self.mounts.push(mount);
+        self
+    }
+
+    /// Grant several mounts to the guest
+    pub fn mounts(mut self, mounts: impl IntoIterator<Item = Mount>) -> Self {
+        self.mounts.extend(mounts);
+        self
+    }
+
+    /// Apply an execution context's mounts and network policy
+    pub fn execution_context(mut self, context: &ExecutionContext) -> Self {
+        self.mounts.extend(context.mounts.iter().cloned());
+        self.network = NetworkPolicy::from_config(&context.resources.network);
+        self
+    }
+
+    /// Set the skill name recorded in audit entries
+    pub fn skill_name(mut self, skill_name: impl Into<String>) -> Self {
+        self.skill_name = skill_name.into();
+        self
+    }
+
+    /// Set the outbound network policy (default: deny all)
+    pub fn network_policy(mut self, policy: NetworkPolicy) -> Self {
+        self.network = policy;
+        self
+    }
+
+    /// Record outbound requests and blocked network attempts in the audit log
+    pub fn audit_logger(mut self, logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = Some(logger);
+        self
+    }
+
     /// Add an environment variable to the sandbox
     pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.env_vars.push((key.into(), value.into()));
@@ -63,11 +264,17 @@ impl SandboxBuilder {
     }
 
     /// Add multiple environment variables from configuration
+    ///
+    /// Also applies the instance's network capabilities and the HTTP
+    /// credentials it declares, which are resolved when the sandbox is built.
     pub fn env_from_config(mut self, config: &InstanceConfig) -> Self {
         // Map configuration to environment variables
         for (key, value) in &config.environment {
             self.env_vars.push((key.clone(), value.clone()));
         }
+        self.network = NetworkPolicy::from_capabilities(&config.capabilities);
+        self.mounts.extend(config.capabilities.grants());
+        self.credentials_from = Some(config.clone());
         self
     }
 
@@ -83,10 +290,17 @@ impl SandboxBuilder {
         self
     }
 
+    /// Give the guest fixed data as its stdin stream
+    pub fn stdin(mut self, data: Vec<u8>) -> Self {
+        self.stdin = Some(data);
+        self
+    }
+
     /// Build the sandboxed WASI context with capability restrictions
     pub fn build(self) -> Result<HostState> {
-        // Create temporary directory for this execution
-        std::fs::create_dir_all(&self.temp_dir)
+        // Create temporary directory for this execution; it goes away with
+        // the returned state, or right away if building fails
+        let scratch = ScratchDir::create(self.temp_dir.clone())
             .context("Failed to create temporary sandbox directory")?;
 
         let mut builder = WasiCtxBuilder::new();
@@ -106,11 +320,65 @@ impl SandboxBuilder {
         if self.inherit_stdio {
             builder.inherit_stdio();
         }
+        if let Some(data) = &self.stdin {
+            builder.stdin(MemoryInputPipe::new(data.clone()));
+        }
+
+        // Resolve mounts while `self` is still whole; the network policy
+        // is moved out below
+        let mounts = self.resolve_mounts()?;
+
+        // Raw sockets only see IP addresses, so hostname rules apply to
+        // wasi:http; IP rules (e.g. `10.0.0.*:5432`) also cover sockets.
+        let network = Arc::new(self.network);
+        builder.allow_ip_name_lookup(network.allows_any());
+        {
+            let network = network.clone();
+            let audit_logger = self.audit_logger.clone();
+            let skill_name = self.skill_name.clone();
+            let instance_id = self.instance_id.clone();
+            builder.socket_addr_check(move |addr, addr_use| {
+                let allowed = match addr_use {
+                    SocketAddrUse::TcpBind | SocketAddrUse::UdpBind => network.allows_any(),
+                    _ => network.is_addr_allowed(&addr),
+                };
+                if !allowed {
+                    record_blocked(
+                        audit_logger.as_deref(),
+                        &skill_name,
+                        &instance_id,
+                        &addr.ip().to_string(),
+                        addr.port(),
+                        "wasi:sockets",
+                    );
+                }
+                Box::pin(async move { allowed })
+            });
+        }
 
-        // Pre-open directories - in wasmtime 26, preopened_dir is simpler
-        // Just use the builder's methods directly with paths
-        // Note: The API changed - for now we'll comment this out until we can test properly
-        //  TODO: Fix directory preopen for WASI Preview 2
+        // Pre-open exactly the declared mounts; nothing else on the host is visible
+        for mount in &mounts {
+            let (dir_perms, file_perms) = if mount.read_only {
+                (DirPerms::READ, FilePerms::READ)
+            } else {
+                (DirPerms::all(), FilePerms::all())
+            };
+            builder
+                .preopened_dir(&mount.host_path, &mount.guest_path, dir_perms, file_perms)
+                .with_context(|| {
+                    format!(
+                        "Failed to preopen '{}' at '{}'",
+                        mount.host_path.display(),
+                        mount.guest_path
+                    )
+                })?;
+        }
+
+        let mut proxy = HttpProxy::new(&self.skill_name, &self.instance_id, network.clone())
+            .with_audit_logger(self.audit_logger.clone());
+        if let Some(config) = &self.credentials_from {
+            proxy = proxy.with_instance_credentials(config)?;
+        }
 
         let wasi = builder.build();
         let table = ResourceTable::new();
@@ -124,19 +392,148 @@ impl SandboxBuilder {
             instance_dir = %self.instance_dir.display(),
             temp_dir = %self.temp_dir.display(),
             config_count = config.len(),
+            allowed_hosts = network.allowed_rules().len(),
+            mounts = mounts.len(),
             "Created sandbox environment"
         );
 
         Ok(HostState {
             wasi,
+            http: WasiHttpCtx::new(),
             table,
             instance_id: self.instance_id,
+            skill_name: self.skill_name,
             config,
+            network,
+            mounts,
+            memory: MemoryMeter::default(),
+            proxy,
+            _scratch: scratch,
         })
     }
+
+    /// Turn declared mounts into host directories to preopen
+    fn resolve_mounts(&self) -> Result<Vec<GrantedMount>> {
+        let mut granted = Vec::new();
+        // Guest parent directory -> staging directory for file mounts
+        let mut staged: BTreeMap<String, PathBuf> = BTreeMap::new();
+        let mounts_dir = self.temp_dir.join("mounts");
+
+        for mount in &self.mounts {
+            if !mount.target.starts_with('/') {
+                anyhow::bail!(
+                    "Mount '{}' target must be an absolute guest path, got '{}'",
+                    mount.id,
+                    mount.target
+                );
+            }
+
+            match &mount.mount_type {
+                MountType::Directory => {
+                    let source = mount.source_path();
+                    if !source.is_dir() {
+                        if mount.required {
+                            anyhow::bail!(
+                                "Required mount '{}' source is not a directory: {}",
+                                mount.id,
+                                source.display()
+                            );
+                        }
+                        tracing::debug!(mount = %mount.id, "Skipping optional mount with missing source");
+                        continue;
+                    }
+                    granted.push(GrantedMount {
+                        id: mount.id.clone(),
+                        host_path: source,
+                        guest_path: mount.target.clone(),
+                        read_only: mount.read_only,
+                    });
+                }
+                MountType::File | MountType::ConfigFile { .. } => {
+                    if !mount.read_only {
+                        anyhow::bail!(
+                            "Mount '{}': WASM skills only support read-only file mounts; mount the parent directory for write access",
+                            mount.id
+                        );
+                    }
+
+                    let contents = match mount.render_config() {
+                        Some(rendered) => rendered.into_bytes(),
+                        None => match std::fs::read(mount.source_path()) {
+                            Ok(bytes) => bytes,
+                            Err(_) if !mount.required => {
+                                tracing::debug!(mount = %mount.id, "Skipping optional mount with missing source");
+                                continue;
+                            }
+                            Err(e) => {
+                                return Err(e).with_context(|| {
+                                    format!(
+                                        "Failed to read mount '{}' source: {}",
+                                        mount.id,
+                                        mount.expand_source()
+                                    )
+                                })
+                            }
+                        },
+                    };
+
+                    let target = Path::new(&mount.target);
+                    let file_name = target
+                        .file_name()
+                        .with_context(|| format!("Mount '{}' target has no file name", mount.id))?;
+                    let guest_parent = target
+                        .parent()
+                        .map(|p| p.to_string_lossy().to_string())
+                        .unwrap_or_else(|| "/".to_string());
+
+                    let next_index = staged.len();
+                    let staging_dir = staged
+                        .entry(guest_parent)
+                        .or_insert_with(|| mounts_dir.join(format!("files-{}", next_index)))
+                        .clone();
+                    std::fs::create_dir_all(&staging_dir)?;
+                    write_private(&staging_dir.join(file_name), &contents)
+                        .with_context(|| format!("Failed to stage mount '{}'", mount.id))?;
+                }
+                MountType::Tmpfs { .. } => {
+                    // Size limits are not enforced for WASM; the directory is
+                    // removed with the rest of the sandbox scratch space when
+                    // the execution's state is dropped.
+                    let dir = mounts_dir.join(format!("tmpfs-{}", mount.id));
+                    std::fs::create_dir_all(&dir)?;
+                    granted.push(GrantedMount {
+                        id: mount.id.clone(),
+                        host_path: dir,
+                        guest_path: mount.target.clone(),
+                        read_only: mount.read_only,
+                    });
+                }
+                MountType::Volume => {
+                    tracing::warn!(
+                        mount = %mount.id,
+                        "Docker volume mounts are not available to WASM skills; skipping"
+                    );
+                }
+            }
+        }
+
+        for (guest_parent, staging_dir) in staged {
+            granted.push(GrantedMount {
+                id: format!("staged:{}", guest_parent),
+                host_path: staging_dir,
+                guest_path: guest_parent,
+                read_only: true,
+            });
+        }
+
+        Ok(granted)
+    }
 }
 
 /// Cleanup temporary sandbox directories
+///
+/// Sandboxes remove their own scratch space when dropped; this catches
+/// directories left behind by a process that was killed mid-execution.
 pub fn cleanup_temp_dirs() -> Result<()> {
     let sandbox_root = std::env::temp_dir().join("skill-engine").join("sandbox");
 
@@ -208,4 +605,139 @@ mod tests {
 
         assert_eq!(sandbox.instance_id, "test");
     }
+
+    #[test]
+    fn test_network_policy_from_config() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let sandbox = SandboxBuilder::new("test", temp_dir.path().to_path_buf())
+            .build()
+            .unwrap();
+        assert!(!sandbox.network.allows_any());
+
+        let mut config = InstanceConfig::default();
+        config.capabilities.network = Some(
+            skill_context::NetworkConfig::enabled().allow_host("api.github.com:443"),
+        );
+
+        let sandbox = SandboxBuilder::new("test", temp_dir.path().to_path_buf())
+            .env_from_config(&config)
+            .build()
+            .unwrap();
+        assert!(sandbox.network.is_allowed("api.github.com", 443));
+        assert!(!sandbox.network.is_allowed("example.com", 443));
+    }
+
+    #[test]
+    fn test_mounts_are_preopened_as_declared() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path().join("data");
+        std::fs::create_dir_all(&data_dir).unwrap();
+        let config_file = temp_dir.path().join("settings.json");
+        std::fs::write(&config_file, "{}").unwrap();
+
+        let sandbox = SandboxBuilder::new("test", temp_dir.path().to_path_buf())
+            .mount(Mount::directory("data", data_dir.to_string_lossy(), "/data").as_read_only())
+            .mount(Mount::file("settings", config_file.to_string_lossy(), "/etc/app/settings.json"))
+            .mount(Mount::config_file("generated", "debug = true", "/etc/app/generated.toml"))
+            .mount(Mount::tmpfs("scratch", "/scratch", 16))
+            .mount(Mount::directory("missing", "/definitely/missing", "/missing").as_optional())
+            .build()
+            .unwrap();
+
+        let data = sandbox.mounts.iter().find(|m| m.guest_path == "/data").unwrap();
+        assert_eq!(data.host_path, data_dir);
+        assert!(data.read_only);
+
+        let scratch = sandbox.mounts.iter().find(|m| m.guest_path == "/scratch").unwrap();
+        assert!(!scratch.read_only);
+
+        // Both files share one read-only staging directory at their parent
+        let staged = sandbox.mounts.iter().find(|m| m.guest_path == "/etc/app").unwrap();
+        assert!(staged.read_only);
+        assert_eq!(
+            std::fs::read_to_string(staged.host_path.join("settings.json")).unwrap(),
+            "{}"
+        );
+        assert_eq!(
+            std::fs::read_to_string(staged.host_path.join("generated.toml")).unwrap(),
+            "debug = true"
+        );
+
+        assert!(!sandbox.mounts.iter().any(|m| m.guest_path == "/missing"));
+        assert_eq!(sandbox.mounts.len(), 3);
+    }
+
+    #[test]
+    fn test_staged_files_are_removed_after_execution() {
+        let temp_dir = TempDir::new().unwrap();
+        std::env::set_var("SANDBOX_TEST_TOKEN", "s3cr3t");
+
+        let sandbox = SandboxBuilder::new("test", temp_dir.path().to_path_buf())
+            .mount(Mount::config_file(
+                "generated",
+                "token = \"${SANDBOX_TEST_TOKEN}\"",
+                "/etc/app/generated.toml",
+            ))
+            .mount(Mount::tmpfs("scratch", "/scratch", 16))
+            .build()
+            .unwrap();
+
+        let staged = sandbox.mounts.iter().find(|m| m.guest_path == "/etc/app").unwrap();
+        let file = staged.host_path.join("generated.toml");
+        assert!(std::fs::read_to_string(&file).unwrap().contains("s3cr3t"));
+        let scratch = staged.host_path.parent().unwrap().parent().unwrap().to_path_buf();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(&file), 0o600);
+            assert_eq!(mode(&scratch), 0o700);
+        }
+
+        drop(sandbox);
+        assert!(!file.exists());
+        assert!(!scratch.exists());
+
+        // A failed build cleans up too
+        let builder = SandboxBuilder::new("test", temp_dir.path().to_path_buf())
+            .mount(Mount::config_file("generated", "x = 1", "/etc/x.toml"))
+            .mount(Mount::directory("missing", "/definitely/missing", "/missing"));
+        let scratch = builder.temp_dir.clone();
+        assert!(builder.build().is_err());
+        assert!(!scratch.exists());
+    }
+
+    #[test]
+    fn test_invalid_mounts_are_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = SandboxBuilder::new("test", temp_dir.path().to_path_buf())
+            .mount(Mount::directory("missing", "/definitely/missing", "/missing"))
+            .build();
+        assert!(result.is_err());
+
+        let result = SandboxBuilder::new("test", temp_dir.path().to_path_buf())
+            .mount(Mount::tmpfs("relative", "scratch", 16))
+            .build();
+        assert!(result.is_err());
+
+        let result = SandboxBuilder::new("test", temp_dir.path().to_path_buf())
+            .mount(Mount::config_file("rw", "x = 1", "/etc/x.toml").as_read_write())
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_memory_meter_tracks_peak() {
+        use wasmtime::ResourceLimiter;
+
+        let mut meter = MemoryMeter::default();
+        // Instantiation allocates the initial pages, then the guest grows
+        meter.memory_growing(0, 2 * 65536, None).unwrap();
+        meter.memory_growing(2 * 65536, 5 * 65536, None).unwrap();
+        // A second memory adds to the total
+        assert!(meter.memory_growing(0, 65536, None).unwrap());
+        assert_eq!(meter.peak_kb(), 6 * 64);
+    }
 }
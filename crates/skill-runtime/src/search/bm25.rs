@@ -8,11 +8,13 @@ use std::path::PathBuf;
 use tantivy::{
     collector::TopDocs,
     doc,
-    query::QueryParser,
-    schema::{Field, Schema, Value, STORED, TEXT},
-    Index, IndexReader, IndexWriter, TantivyDocument,
+    query::{BooleanQuery, Occur, Query, QueryParser, TermQuery},
+    schema::{Field, IndexRecordOption, Schema, Value, STORED, STRING, TEXT},
+    Index, IndexReader, IndexWriter, TantivyDocument, Term,
 };
 
+use crate::vector_store::{DocumentMetadata, Filter};
+
 /// Configuration for BM25 index
 #[derive(Debug, Clone)]
 pub struct BM25Config {
@@ -79,9 +81,46 @@ pub struct BM25Index {
     skill_name_field: Field,
     description_field: Field,
     full_text_field: Field,
+    // Exact-match keyword fields used for filter push-down
+    filter_fields: FilterFields,
     config: BM25Config,
 }
 
+/// Untokenized keyword fields that structured filters are applied against
+#[derive(Debug, Clone, Copy)]
+struct FilterFields {
+    skill: Field,
+    instance: Field,
+    tool: Field,
+    category: Field,
+    runtime: Field,
+    tag: Field,
+}
+
+impl FilterFields {
+    fn build(schema_builder: &mut tantivy::schema::SchemaBuilder) -> Self {
+        Self {
+            skill: schema_builder.add_text_field("skill_key", STRING),
+            instance: schema_builder.add_text_field("instance_key", STRING),
+            tool: schema_builder.add_text_field("tool_key", STRING),
+            category: schema_builder.add_text_field("category_key", STRING),
+            runtime: schema_builder.add_text_field("runtime_key", STRING),
+            tag: schema_builder.add_text_field("tag_key", STRING),
+        }
+    }
+
+    fn from_schema(schema: &Schema) -> Result<Self> {
+        Ok(Self {
+            skill: schema.get_field("skill_key").context("Missing skill_key field")?,
+            instance: schema.get_field("instance_key").context("Missing instance_key field")?,
+            tool: schema.get_field("tool_key").context("Missing tool_key field")?,
+            category: schema.get_field("category_key").context("Missing category_key field")?,
+            runtime: schema.get_field("runtime_key").context("Missing runtime_key field")?,
+            tag: schema.get_field("tag_key").context("Missing tag_key field")?,
+        })
+    }
+}
+
 impl BM25Index {
     /// Create a new BM25 index
     pub fn new(config: BM25Config) -> Result<Self> {
@@ -93,6 +132,7 @@ impl BM25Index {
         let skill_name_field = schema_builder.add_text_field("skill_name", TEXT | STORED);
         let description_field = schema_builder.add_text_field("description", TEXT);
         let full_text_field = schema_builder.add_text_field("full_text", TEXT);
+        let filter_fields = FilterFields::build(&mut schema_builder);
 
         let schema = schema_builder.build();
 
@@ -123,6 +163,7 @@ impl BM25Index {
             skill_name_field,
             description_field,
             full_text_field,
+            filter_fields,
             config,
         })
     }
@@ -138,6 +179,7 @@ impl BM25Index {
         let skill_name_field = schema.get_field("skill_name").context("Missing skill_name field")?;
         let description_field = schema.get_field("description").context("Missing description field")?;
         let full_text_field = schema.get_field("full_text").context("Missing full_text field")?;
+        let filter_fields = FilterFields::from_schema(&schema)?;
 
         let reader = index
             .reader_builder()
@@ -156,6 +198,7 @@ impl BM25Index {
             skill_name_field,
             description_field,
             full_text_field,
+            filter_fields,
             config,
         })
     }
@@ -169,16 +212,51 @@ impl BM25Index {
         description: &str,
         full_text: &str,
     ) -> Result<()> {
+        let metadata = DocumentMetadata {
+            skill_name: Some(skill_name.to_string()),
+            tool_name: Some(tool_name.to_string()),
+            ..Default::default()
+        };
+        self.add_document_with_metadata(id, description, full_text, &metadata)
+    }
+
+    /// Add a document with metadata so structured filters can be pushed down
+    pub fn add_document_with_metadata(
+        &mut self,
+        id: &str,
+        description: &str,
+        full_text: &str,
+        metadata: &DocumentMetadata,
+    ) -> Result<()> {
+        let fields = self.filter_fields;
         let writer = self.writer.as_mut().context("Index not writable")?;
 
-        let doc = doc!(
+        let tool_name = metadata.tool_name.as_deref().unwrap_or("");
+        let skill_name = metadata.skill_name.as_deref().unwrap_or("");
+
+        let mut doc = doc!(
             self.id_field => id,
             self.tool_name_field => tool_name,
             self.skill_name_field => skill_name,
             self.description_field => description,
             self.full_text_field => full_text,
+            fields.skill => skill_name,
+            fields.tool => tool_name,
         );
 
+        if let Some(ref instance) = metadata.instance_name {
+            doc.add_text(fields.instance, instance);
+        }
+        if let Some(ref category) = metadata.category {
+            doc.add_text(fields.category, category);
+        }
+        if let Some(ref runtime) = metadata.runtime {
+            doc.add_text(fields.runtime, runtime);
+        }
+        for tag in &metadata.tags {
+            doc.add_text(fields.tag, tag);
+        }
+
         writer.add_document(doc).context("Failed to add document")?;
         Ok(())
     }
@@ -204,6 +282,20 @@ impl BM25Index {
 
     /// Search the index
     pub fn search(&self, query: &str, top_k: usize) -> Result<Vec<BM25SearchResult>> {
+        self.search_filtered(query, top_k, None)
+    }
+
+    /// Search the index, restricting matches to documents that satisfy `filter`
+    ///
+    /// Skill, instance, tool, category, runtime and tag conditions are applied
+    /// inside the index. Custom metadata and `min_score` are not indexed and
+    /// must be checked by the caller.
+    pub fn search_filtered(
+        &self,
+        query: &str,
+        top_k: usize,
+        filter: Option<&Filter>,
+    ) -> Result<Vec<BM25SearchResult>> {
         let searcher = self.reader.searcher();
 
         // Search across all text fields
@@ -220,8 +312,23 @@ impl BM25Index {
             .parse_query(query)
             .context("Failed to parse query")?;
 
+        let final_query: Box<dyn Query> = match filter {
+            Some(f) if !f.is_empty() => {
+                let mut clauses: Vec<(Occur, Box<dyn Query>)> = vec![(Occur::Must, parsed_query)];
+                clauses.extend(
+                    self.filter_terms(f)
+                        .into_iter()
+                        .map(|term| -> (Occur, Box<dyn Query>) {
+                            (Occur::Must, Box::new(TermQuery::new(term, IndexRecordOption::Basic)))
+                        }),
+                );
+                Box::new(BooleanQuery::new(clauses))
+            }
+            _ => parsed_query,
+        };
+
         let top_docs = searcher
-            .search(&parsed_query, &TopDocs::with_limit(top_k))
+            .search(&final_query, &TopDocs::with_limit(top_k))
             .context("Search failed")?;
 
         let mut results = Vec::with_capacity(top_docs.len());
@@ -260,6 +367,31 @@ impl BM25Index {
         Ok(results)
     }
 
+    /// Build exact-match terms for the indexed parts of a filter
+    fn filter_terms(&self, filter: &Filter) -> Vec<Term> {
+        let fields = self.filter_fields;
+        let mut terms = Vec::new();
+
+        let exact = [
+            (fields.skill, &filter.skill_name),
+            (fields.instance, &filter.instance_name),
+            (fields.tool, &filter.tool_name),
+            (fields.category, &filter.category),
+            (fields.runtime, &filter.runtime),
+        ];
+        for (field, value) in exact {
+            if let Some(value) = value {
+                terms.push(Term::from_field_text(field, value));
+            }
+        }
+
+        for tag in &filter.tags {
+            terms.push(Term::from_field_text(fields.tag, tag));
+        }
+
+        terms
+    }
+
     /// Get document count
     pub fn document_count(&self) -> u64 {
         self.reader.searcher().num_docs()
@@ -342,6 +474,53 @@ mod tests {
         assert!(results.is_empty());
     }
 
+    #[test]
+    fn test_filtered_search() {
+        let mut index = BM25Index::new(BM25Config::in_memory()).unwrap();
+
+        let native = DocumentMetadata {
+            skill_name: Some("kubernetes".to_string()),
+            tool_name: Some("list_pods".to_string()),
+            instance_name: Some("prod".to_string()),
+            runtime: Some("native".to_string()),
+            tags: vec!["k8s".to_string()],
+            ..Default::default()
+        };
+        let wasm = DocumentMetadata {
+            skill_name: Some("aws".to_string()),
+            tool_name: Some("list_buckets".to_string()),
+            instance_name: Some("dev".to_string()),
+            runtime: Some("wasm".to_string()),
+            tags: vec!["aws".to_string(), "cloud".to_string()],
+            ..Default::default()
+        };
+
+        index
+            .add_document_with_metadata("k8s", "List pods", "list pods resources", &native)
+            .unwrap();
+        index
+            .add_document_with_metadata("aws", "List buckets", "list buckets resources", &wasm)
+            .unwrap();
+        index.commit().unwrap();
+
+        let results = index.search("list resources", 10).unwrap();
+        assert_eq!(results.len(), 2);
+
+        let filter = Filter::new().runtime("native");
+        let results = index.search_filtered("list resources", 10, Some(&filter)).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "k8s");
+
+        let filter = Filter::new().tags(vec!["aws".to_string()]).instance("dev");
+        let results = index.search_filtered("list resources", 10, Some(&filter)).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "aws");
+
+        let filter = Filter::new().instance("staging");
+        let results = index.search_filtered("list resources", 10, Some(&filter)).unwrap();
+        assert!(results.is_empty());
+    }
+
     #[test]
     fn test_multi_term_search() {
         let mut index = BM25Index::new(BM25Config::in_memory()).unwrap();
@@ -458,7 +458,7 @@ impl ContextCompressor {
     /// Extract call pattern from example
     fn extract_call_pattern(&self, example: &str) -> String {
         // Look for function call patterns
-        if let Some(start) = example.find('(') {
+        if example.contains('(') {
             if let Some(end) = example.find(')') {
                 let call = &example[..=end];
                 // Find the start of the function name
@@ -0,0 +1,374 @@
+//! Search quality evaluation
+//!
+//! Runs a labeled query set against a [`SearchPipeline`] and reports
+//! recall@k, MRR and NDCG@k, so changes to embedding models, hybrid search
+//! or reranking can be measured instead of eyeballed.
+//!
+//! # Suite format
+//!
+//! Suites are TOML or JSON files listing queries and the tools that should
+//! be returned for them. Expected tools are written as `skill:tool`,
+//! `skill@instance:tool`, or a raw document ID.
+//!
+//! ```toml
+//! [[case]]
+//! query = "list running pods"
+//! expected = ["kubernetes:get"]
+//!
+//! [[case]]
+//! query = "upload a file to s3"
+//! expected = ["aws:s3-upload", "aws:s3-cp"]
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Instant;
+
+use super::{PipelineSearchResult, SearchPipeline};
+
+/// A labeled query and the tools it is expected to retrieve
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalCase {
+    /// Natural language query
+    pub query: String,
+    /// Expected tools (`skill:tool`, `skill@instance:tool`, or document ID)
+    pub expected: Vec<String>,
+}
+
+/// A set of labeled queries
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EvalSuite {
+    /// Queries to evaluate
+    #[serde(alias = "case")]
+    pub cases: Vec<EvalCase>,
+}
+
+impl EvalSuite {
+    /// Load a suite from a `.json` or `.toml` file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read eval suite: {}", path.display()))?;
+
+        let suite: Self = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => serde_json::from_str(&content)
+                .with_context(|| format!("Invalid JSON eval suite: {}", path.display()))?,
+            _ => toml::from_str(&content)
+                .with_context(|| format!("Invalid TOML eval suite: {}", path.display()))?,
+        };
+
+        if suite.cases.is_empty() {
+            anyhow::bail!("Eval suite {} contains no cases", path.display());
+        }
+        if let Some(case) = suite.cases.iter().find(|c| c.expected.is_empty()) {
+            anyhow::bail!("Eval case '{}' has no expected tools", case.query);
+        }
+
+        Ok(suite)
+    }
+}
+
+/// Outcome of a single query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryOutcome {
+    /// Query text
+    pub query: String,
+    /// Expected tools
+    pub expected: Vec<String>,
+    /// 1-based rank at which each expected tool was found (same order as `expected`)
+    pub found_at: Vec<Option<usize>>,
+    /// Tools actually returned, in rank order
+    pub returned: Vec<String>,
+    /// Fraction of expected tools found in the top k
+    pub recall: f64,
+    /// 1 / rank of the first expected tool (0 if none found)
+    pub reciprocal_rank: f64,
+    /// Normalized discounted cumulative gain at k
+    pub ndcg: f64,
+    /// Search latency in milliseconds
+    pub latency_ms: f64,
+}
+
+/// Aggregate metrics for a run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct EvalMetrics {
+    /// Mean recall@k
+    pub recall: f64,
+    /// Mean reciprocal rank
+    pub mrr: f64,
+    /// Mean NDCG@k
+    pub ndcg: f64,
+    /// Mean search latency in milliseconds
+    pub mean_latency_ms: f64,
+}
+
+impl EvalMetrics {
+    /// Metric differences `other - self`
+    pub fn delta(&self, other: &EvalMetrics) -> EvalMetrics {
+        EvalMetrics {
+            recall: other.recall - self.recall,
+            mrr: other.mrr - self.mrr,
+            ndcg: other.ndcg - self.ndcg,
+            mean_latency_ms: other.mean_latency_ms - self.mean_latency_ms,
+        }
+    }
+}
+
+/// Result of evaluating a suite against one configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvalReport {
+    /// Label for the configuration under test
+    pub label: String,
+    /// Cutoff used for recall and NDCG
+    pub k: usize,
+    /// Aggregate metrics
+    pub metrics: EvalMetrics,
+    /// Per-query outcomes
+    pub queries: Vec<QueryOutcome>,
+}
+
+impl EvalReport {
+    /// Build a report from per-query outcomes
+    pub fn from_outcomes(label: impl Into<String>, k: usize, queries: Vec<QueryOutcome>) -> Self {
+        let n = queries.len().max(1) as f64;
+        let metrics = EvalMetrics {
+            recall: queries.iter().map(|q| q.recall).sum::<f64>() / n,
+            mrr: queries.iter().map(|q| q.reciprocal_rank).sum::<f64>() / n,
+            ndcg: queries.iter().map(|q| q.ndcg).sum::<f64>() / n,
+            mean_latency_ms: queries.iter().map(|q| q.latency_ms).sum::<f64>() / n,
+        };
+
+        Self {
+            label: label.into(),
+            k,
+            metrics,
+            queries,
+        }
+    }
+
+    /// Queries that did not retrieve any expected tool
+    pub fn misses(&self) -> impl Iterator<Item = &QueryOutcome> {
+        self.queries.iter().filter(|q| q.reciprocal_rank == 0.0)
+    }
+}
+
+/// Evaluate a suite against a pipeline that already has documents indexed
+pub async fn evaluate(
+    pipeline: &SearchPipeline,
+    suite: &EvalSuite,
+    k: usize,
+    label: impl Into<String>,
+) -> Result<EvalReport> {
+    let mut outcomes = Vec::with_capacity(suite.cases.len());
+
+    for case in &suite.cases {
+        let start = Instant::now();
+        let results = pipeline
+            .search(&case.query, k)
+            .await
+            .with_context(|| format!("Search failed for eval query '{}'", case.query))?;
+        let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        outcomes.push(score_case(case, &results, k, latency_ms));
+    }
+
+    Ok(EvalReport::from_outcomes(label, k, outcomes))
+}
+
+/// Score a single case against the results returned for it
+pub fn score_case(
+    case: &EvalCase,
+    results: &[PipelineSearchResult],
+    k: usize,
+    latency_ms: f64,
+) -> QueryOutcome {
+    let top: Vec<&PipelineSearchResult> = results.iter().take(k).collect();
+
+    let found_at: Vec<Option<usize>> = case
+        .expected
+        .iter()
+        .map(|expected| {
+            top.iter()
+                .position(|r| result_matches(expected, r))
+                .map(|p| p + 1)
+        })
+        .collect();
+
+    let ranks: Vec<usize> = found_at.iter().flatten().copied().collect();
+    let expected_count = case.expected.len().max(1);
+
+    let recall = ranks.len() as f64 / expected_count as f64;
+    let reciprocal_rank = ranks.iter().min().map_or(0.0, |&r| 1.0 / r as f64);
+
+    let dcg: f64 = ranks.iter().map(|&r| discount(r)).sum();
+    let ideal: f64 = (1..=expected_count.min(k)).map(discount).sum();
+    let ndcg = if ideal > 0.0 { dcg / ideal } else { 0.0 };
+
+    QueryOutcome {
+        query: case.query.clone(),
+        expected: case.expected.clone(),
+        found_at,
+        returned: top.iter().map(|r| result_label(r)).collect(),
+        recall,
+        reciprocal_rank,
+        ndcg,
+        latency_ms,
+    }
+}
+
+fn discount(rank: usize) -> f64 {
+    1.0 / ((rank + 1) as f64).log2()
+}
+
+/// Whether a result matches an expected tool reference
+fn result_matches(expected: &str, result: &PipelineSearchResult) -> bool {
+    if expected == result.id {
+        return true;
+    }
+
+    let Some((skill_part, tool)) = expected.split_once(':') else {
+        return false;
+    };
+    let (skill, instance) = match skill_part.split_once('@') {
+        Some((skill, instance)) => (skill, Some(instance)),
+        None => (skill_part, None),
+    };
+
+    let meta = &result.metadata;
+    meta.skill_name.as_deref() == Some(skill)
+        && meta.tool_name.as_deref() == Some(tool)
+        && instance.map_or(true, |i| meta.instance_name.as_deref() == Some(i))
+}
+
+/// `skill@instance:tool` label for a result, falling back to its ID
+fn result_label(result: &PipelineSearchResult) -> String {
+    let meta = &result.metadata;
+    match (&meta.skill_name, &meta.tool_name) {
+        (Some(skill), Some(tool)) => match &meta.instance_name {
+            Some(instance) => format!("{}@{}:{}", skill, instance, tool),
+            None => format!("{}:{}", skill, tool),
+        },
+        _ => result.id.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector_store::DocumentMetadata;
+
+    fn result(skill: &str, tool: &str) -> PipelineSearchResult {
+        PipelineSearchResult {
+            id: format!("{}@default/{}", skill, tool),
+            content: String::new(),
+            score: 0.5,
+            metadata: DocumentMetadata {
+                skill_name: Some(skill.to_string()),
+                instance_name: Some("default".to_string()),
+                tool_name: Some(tool.to_string()),
+                ..Default::default()
+            },
+            rerank_score: None,
+            explanation: None,
+        }
+    }
+
+    fn case(query: &str, expected: &[&str]) -> EvalCase {
+        EvalCase {
+            query: query.to_string(),
+            expected: expected.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_score_case_perfect_hit() {
+        let results = vec![result("kubernetes", "get"), result("aws", "s3-list")];
+        let outcome = score_case(&case("list pods", &["kubernetes:get"]), &results, 5, 1.0);
+
+        assert_eq!(outcome.found_at, vec![Some(1)]);
+        assert_eq!(outcome.recall, 1.0);
+        assert_eq!(outcome.reciprocal_rank, 1.0);
+        assert!((outcome.ndcg - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_score_case_partial_hit() {
+        let results = vec![
+            result("aws", "s3-list"),
+            result("kubernetes", "get"),
+            result("kubernetes", "describe"),
+        ];
+        let outcome = score_case(
+            &case(
+                "inspect pods",
+                &["kubernetes@default:get", "kubernetes:logs"],
+            ),
+            &results,
+            3,
+            1.0,
+        );
+
+        assert_eq!(outcome.found_at, vec![Some(2), None]);
+        assert_eq!(outcome.recall, 0.5);
+        assert_eq!(outcome.reciprocal_rank, 0.5);
+        // dcg = 1/log2(3); idcg = 1 + 1/log2(3)
+        let expected_ndcg = (1.0 / 3f64.log2()) / (1.0 + 1.0 / 3f64.log2());
+        assert!((outcome.ndcg - expected_ndcg).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_score_case_respects_k() {
+        let results = vec![result("aws", "s3-list"), result("kubernetes", "get")];
+        let outcome = score_case(&case("list pods", &["kubernetes:get"]), &results, 1, 1.0);
+
+        assert_eq!(outcome.found_at, vec![None]);
+        assert_eq!(outcome.reciprocal_rank, 0.0);
+        assert_eq!(outcome.returned, vec!["aws@default:s3-list"]);
+    }
+
+    #[test]
+    fn test_report_metrics_and_delta() {
+        let results = vec![result("kubernetes", "get")];
+        let hit = score_case(&case("a", &["kubernetes:get"]), &results, 5, 10.0);
+        let miss = score_case(&case("b", &["aws:s3-list"]), &results, 5, 20.0);
+        let report = EvalReport::from_outcomes("baseline", 5, vec![hit, miss]);
+
+        assert_eq!(report.metrics.recall, 0.5);
+        assert_eq!(report.metrics.mrr, 0.5);
+        assert_eq!(report.metrics.mean_latency_ms, 15.0);
+        assert_eq!(report.misses().count(), 1);
+
+        let better = EvalMetrics {
+            recall: 1.0,
+            ..report.metrics
+        };
+        assert_eq!(report.metrics.delta(&better).recall, 0.5);
+    }
+
+    #[test]
+    fn test_suite_parses_toml_and_json() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let toml_path = dir.path().join("suite.toml");
+        std::fs::write(
+            &toml_path,
+            "[[case]]\nquery = \"list pods\"\nexpected = [\"kubernetes:get\"]\n",
+        )
+        .unwrap();
+        let suite = EvalSuite::from_file(&toml_path).unwrap();
+        assert_eq!(suite.cases.len(), 1);
+
+        let json_path = dir.path().join("suite.json");
+        std::fs::write(
+            &json_path,
+            r#"{"cases": [{"query": "list pods", "expected": ["kubernetes:get"]}]}"#,
+        )
+        .unwrap();
+        let suite = EvalSuite::from_file(&json_path).unwrap();
+        assert_eq!(suite.cases[0].expected, vec!["kubernetes:get"]);
+
+        let empty_path = dir.path().join("empty.toml");
+        std::fs::write(&empty_path, "[[case]]\nquery = \"x\"\nexpected = []\n").unwrap();
+        assert!(EvalSuite::from_file(&empty_path).is_err());
+    }
+}
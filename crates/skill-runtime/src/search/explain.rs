@@ -0,0 +1,122 @@
+//! Relevancy explanations for search results
+//!
+//! Records how each stage of the pipeline contributed to a result's final
+//! position, so hybrid and reranker settings can be tuned against real
+//! queries instead of guesswork.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Words ignored when reporting matched query terms
+const EXPLAIN_STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "for", "from", "how", "i", "in", "is", "it", "me", "my", "of", "on",
+    "or", "the", "to", "with",
+];
+
+/// Per-result breakdown of how a search score was produced
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreExplanation {
+    /// Similarity score from the vector store (dense retrieval)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dense_score: Option<f32>,
+    /// 1-based rank in the dense result list
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dense_rank: Option<usize>,
+    /// BM25 score (only when hybrid search is enabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bm25_score: Option<f32>,
+    /// 1-based rank in the BM25 result list
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bm25_rank: Option<usize>,
+    /// Combined Reciprocal Rank Fusion score (only when hybrid search is enabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fusion_score: Option<f32>,
+    /// 1-based rank after retrieval, before reranking
+    pub retrieval_rank: usize,
+    /// Cross-encoder score (only when reranking is enabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rerank_score: Option<f32>,
+    /// Positions moved by the reranker (positive means promoted)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rerank_delta: Option<i64>,
+    /// Query terms found in the document content
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matched_terms: Vec<String>,
+}
+
+impl ScoreExplanation {
+    /// Explanation for a result that came from dense retrieval only
+    pub fn dense(score: f32, rank: usize) -> Self {
+        Self {
+            dense_score: Some(score),
+            dense_rank: Some(rank),
+            retrieval_rank: rank,
+            ..Default::default()
+        }
+    }
+
+    /// RRF contribution of the dense list, given the fusion constant
+    pub fn dense_contribution(&self, rrf_k: f32) -> Option<f32> {
+        self.dense_rank.map(|rank| rrf_contribution(rank, rrf_k))
+    }
+
+    /// RRF contribution of the BM25 list, given the fusion constant
+    pub fn bm25_contribution(&self, rrf_k: f32) -> Option<f32> {
+        self.bm25_rank.map(|rank| rrf_contribution(rank, rrf_k))
+    }
+}
+
+/// Reciprocal Rank Fusion contribution of a 1-based rank
+pub fn rrf_contribution(rank: usize, rrf_k: f32) -> f32 {
+    1.0 / (rrf_k + rank as f32)
+}
+
+/// Query terms (lowercased, deduplicated, in query order) that occur in `content`
+pub fn matched_terms(query: &str, content: &str) -> Vec<String> {
+    let content_terms: HashSet<String> = terms(content).collect();
+    let mut seen = HashSet::new();
+
+    terms(query)
+        .filter(|t| !EXPLAIN_STOP_WORDS.contains(&t.as_str()))
+        .filter(|t| content_terms.contains(t))
+        .filter(|t| seen.insert(t.clone()))
+        .collect()
+}
+
+fn terms(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
+        .map(|t| t.trim_matches(|c| c == '-' || c == '_').to_lowercase())
+        .filter(|t| !t.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matched_terms() {
+        let terms = matched_terms(
+            "List the pods in the cluster, list pods",
+            "kubernetes:get - Get pods, services and deployments from a cluster",
+        );
+        assert_eq!(terms, vec!["pods", "cluster"]);
+    }
+
+    #[test]
+    fn test_matched_terms_keeps_hyphenated_words() {
+        let terms = matched_terms("s3-list buckets", "aws:s3-list - List S3 buckets");
+        assert_eq!(terms, vec!["s3-list", "buckets"]);
+    }
+
+    #[test]
+    fn test_rrf_contributions() {
+        let explanation = ScoreExplanation {
+            dense_rank: Some(1),
+            bm25_rank: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(explanation.dense_contribution(60.0), Some(1.0 / 61.0));
+        assert_eq!(explanation.bm25_contribution(60.0), Some(1.0 / 64.0));
+        assert_eq!(ScoreExplanation::default().bm25_contribution(60.0), None);
+    }
+}
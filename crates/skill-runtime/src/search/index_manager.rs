@@ -1,15 +1,26 @@
 //! Persistent index manager with incremental updates
 //!
 //! Provides index management for persistent storage, incremental updates,
-//! and automatic synchronization of skill embeddings.
+//! and automatic synchronization of skill embeddings, and splits verbose
+//! tool documentation into overlapping chunks before it is embedded.
+//!
+//! Indexes can be saved as [`IndexSnapshot`]s and restored on startup, so
+//! servers in CI or ephemeral containers don't re-embed every tool.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
+use std::io::{BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 
+use super::pipeline::{IndexDocument, PARENT_ID_KEY};
+use crate::vector_store::{EmbeddedDocument, VectorStore};
+
 /// Configuration for the index manager
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexConfig {
@@ -371,6 +382,36 @@ impl IndexManager {
         Ok(())
     }
 
+    /// Write every document in `store`, with this index's metadata, to a
+    /// snapshot file
+    ///
+    /// Returns the number of documents saved.
+    pub async fn save_snapshot(&self, store: &dyn VectorStore, path: &Path) -> Result<usize> {
+        let snapshot = IndexSnapshot::capture(store, self.metadata.clone()).await?;
+        snapshot.save(path)?;
+        Ok(snapshot.documents.len())
+    }
+
+    /// Restore a snapshot into `store` without re-embedding
+    ///
+    /// The snapshot's skill checksums replace this index's, so skills that
+    /// haven't changed since it was taken aren't re-indexed. Fails if the
+    /// snapshot was built with a different embedding model.
+    pub async fn load_snapshot(&mut self, store: &dyn VectorStore, path: &Path) -> Result<usize> {
+        let snapshot = IndexSnapshot::load(path)?;
+        snapshot.check_compatible(&self.config.embedding_model, self.config.embedding_dimensions)?;
+
+        let count = snapshot.documents.len();
+        store
+            .upsert(snapshot.documents)
+            .await
+            .context("Failed to restore index snapshot")?;
+        self.metadata = snapshot.metadata;
+        self.metadata.touch();
+        self.save_metadata()?;
+        Ok(count)
+    }
+
     /// Save metadata to disk
     fn save_metadata(&self) -> Result<()> {
         self.metadata.save(&self.config.index_path)
@@ -399,6 +440,268 @@ impl IndexManager {
     }
 }
 
+/// Embedded documents of an index with the metadata they were built with
+///
+/// Written as gzip-compressed JSON; embeddings are stored as-is, so a
+/// restored index searches exactly like the one it was taken from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexSnapshot {
+    /// Embedding model, dimensions and skill checksums of the index
+    pub metadata: IndexMetadata,
+    /// Documents with their embeddings
+    pub documents: Vec<EmbeddedDocument>,
+}
+
+impl IndexSnapshot {
+    /// Snapshot every document in `store`
+    pub async fn capture(store: &dyn VectorStore, mut metadata: IndexMetadata) -> Result<Self> {
+        let mut documents = store.export().await?;
+        documents.sort_by(|a, b| a.id.cmp(&b.id));
+        metadata.document_count = documents.len();
+        Ok(Self { metadata, documents })
+    }
+
+    /// Fail unless the snapshot was embedded with `model` at `dimensions`
+    pub fn check_compatible(&self, model: &str, dimensions: usize) -> Result<()> {
+        if self.metadata.version != IndexMetadata::CURRENT_VERSION {
+            anyhow::bail!(
+                "Index snapshot version {} is not supported (expected {})",
+                self.metadata.version,
+                IndexMetadata::CURRENT_VERSION
+            );
+        }
+        if self.metadata.embedding_model != model || self.metadata.dimensions != dimensions {
+            anyhow::bail!(
+                "Index snapshot was built with {} ({} dimensions), but this index uses {} ({} dimensions)",
+                self.metadata.embedding_model,
+                self.metadata.dimensions,
+                model,
+                dimensions
+            );
+        }
+        Ok(())
+    }
+
+    /// Write the snapshot to `path`
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        // Write to a temp file and rename, so a failed save keeps the old snapshot
+        let temp_path = path.with_extension("tmp");
+        let file = fs::File::create(&temp_path)
+            .with_context(|| format!("Failed to create snapshot file: {}", temp_path.display()))?;
+        let mut encoder = GzEncoder::new(BufWriter::new(file), Compression::default());
+        serde_json::to_writer(&mut encoder, self).context("Failed to serialize index snapshot")?;
+        encoder
+            .finish()
+            .and_then(|mut writer| writer.flush())
+            .context("Failed to write index snapshot")?;
+        fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to write snapshot file: {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Read a snapshot written by [`IndexSnapshot::save`]
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = fs::File::open(path)
+            .with_context(|| format!("Failed to open snapshot file: {}", path.display()))?;
+        serde_json::from_reader(GzDecoder::new(BufReader::new(file)))
+            .with_context(|| format!("Failed to parse index snapshot: {}", path.display()))
+    }
+}
+
+/// How long tool documentation is split before embedding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChunkConfig {
+    /// Split documents longer than this (characters); 0 disables chunking
+    #[serde(default = "default_chunk_max_chars")]
+    pub max_chars: usize,
+    /// Characters of trailing context repeated at the start of the next chunk
+    #[serde(default = "default_chunk_overlap_chars")]
+    pub overlap_chars: usize,
+}
+
+fn default_chunk_max_chars() -> usize {
+    1200
+}
+
+fn default_chunk_overlap_chars() -> usize {
+    200
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        Self {
+            max_chars: default_chunk_max_chars(),
+            overlap_chars: default_chunk_overlap_chars(),
+        }
+    }
+}
+
+/// Split text into overlapping windows of at most `max_chars`, breaking on
+/// lines (and on words for overlong lines)
+///
+/// Each chunk after the first starts with the markdown headings in effect
+/// where it begins, and the document's first line (the tool summary) when
+/// that isn't a heading, so every window says which tool and section it
+/// belongs to. Text that fits is returned as a single chunk.
+pub fn chunk_text(text: &str, config: &ChunkConfig) -> Vec<String> {
+    if config.max_chars == 0 || text.chars().count() <= config.max_chars {
+        return vec![text.to_string()];
+    }
+
+    let title = text
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .filter(|line| !line.starts_with('#'))
+        .unwrap_or_default()
+        .to_string();
+
+    // Lines (overlong ones split on words) with the headings above them
+    let budget = (config.max_chars / 2).max(1);
+    let mut headings: Vec<(usize, String)> = Vec::new();
+    let mut lines: Vec<(String, Vec<String>)> = Vec::new();
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if level > 0 && trimmed[level..].starts_with(' ') {
+            headings.retain(|(l, _)| *l < level);
+            headings.push((level, trimmed.to_string()));
+        }
+        let context: Vec<String> = headings.iter().map(|(_, h)| h.clone()).collect();
+        for piece in split_long_line(line, budget) {
+            lines.push((piece, context.clone()));
+        }
+    }
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < lines.len() {
+        let (_, context) = &lines[start];
+        let mut header: Vec<&str> = Vec::new();
+        if !title.is_empty() && start > 0 {
+            header.push(&title);
+        }
+        // Headings that open the window are already in its body
+        header.extend(
+            context
+                .iter()
+                .filter(|h| lines[start].0.trim_start() != h.as_str())
+                .map(String::as_str),
+        );
+        let header = header.join("\n");
+        let room = config
+            .max_chars
+            .saturating_sub(header.chars().count())
+            .max(budget);
+
+        let mut end = start;
+        let mut size = 0;
+        while end < lines.len() {
+            let len = lines[end].0.chars().count() + 1;
+            if end > start && size + len > room {
+                break;
+            }
+            size += len;
+            end += 1;
+        }
+
+        let body: Vec<&str> = lines[start..end].iter().map(|(l, _)| l.as_str()).collect();
+        let body = body.join("\n");
+        if !body.trim().is_empty() {
+            chunks.push(if header.is_empty() {
+                body
+            } else {
+                format!("{}\n{}", header, body)
+            });
+        }
+        if end >= lines.len() {
+            break;
+        }
+
+        // Step back over up to `overlap_chars` of trailing lines, always
+        // moving forward by at least one line
+        let mut next = end;
+        let mut overlap = 0;
+        while next > start + 1 {
+            let len = lines[next - 1].0.chars().count() + 1;
+            if overlap + len > config.overlap_chars {
+                break;
+            }
+            overlap += len;
+            next -= 1;
+        }
+        start = next;
+    }
+    chunks
+}
+
+/// Split a line longer than `max_chars` at word boundaries
+fn split_long_line(line: &str, max_chars: usize) -> Vec<String> {
+    if line.chars().count() <= max_chars {
+        return vec![line.to_string()];
+    }
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    for word in line.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > max_chars {
+            pieces.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
+
+/// Split long documents into chunks for embedding
+///
+/// The first chunk keeps the document's ID; the others get `<id>#chunk-N`
+/// and are linked to it like examples, so search reports a match in any
+/// chunk as the tool.
+pub fn chunk_documents(documents: Vec<IndexDocument>, config: &ChunkConfig) -> Vec<IndexDocument> {
+    let mut chunked = Vec::with_capacity(documents.len());
+    for mut doc in documents {
+        let mut chunks = chunk_text(&doc.content, config).into_iter();
+        let Some(first) = chunks.next() else {
+            chunked.push(doc);
+            continue;
+        };
+        let parent = doc
+            .metadata
+            .custom
+            .get(PARENT_ID_KEY)
+            .cloned()
+            .unwrap_or_else(|| doc.id.clone());
+        let rest: Vec<IndexDocument> = chunks
+            .enumerate()
+            .map(|(idx, content)| {
+                let mut metadata = doc.metadata.clone();
+                metadata
+                    .custom
+                    .insert(PARENT_ID_KEY.to_string(), parent.clone());
+                IndexDocument {
+                    id: format!("{}#chunk-{}", doc.id, idx + 2),
+                    content,
+                    metadata,
+                }
+            })
+            .collect();
+        doc.content = first;
+        chunked.push(doc);
+        chunked.extend(rest);
+    }
+    chunked
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -595,4 +898,86 @@ mod tests {
         assert!(manager.metadata().skill_checksums.is_empty());
         assert_eq!(manager.metadata().document_count, 0);
     }
+
+    #[tokio::test]
+    async fn test_snapshot_save_load() {
+        use crate::vector_store::InMemoryVectorStore;
+
+        let (config, temp) = temp_config();
+        let mut manager = IndexManager::new(config.clone()).unwrap();
+        let checksum = manager.compute_skill_checksum(temp.path()).unwrap();
+        manager.record_indexed("kubernetes", checksum, 2).unwrap();
+
+        let store = InMemoryVectorStore::new();
+        store
+            .upsert(vec![
+                EmbeddedDocument::new("kubernetes:logs", vec![1.0, 0.0])
+                    .with_skill_name("kubernetes")
+                    .with_content("Print pod logs"),
+                EmbeddedDocument::new("kubernetes:get", vec![0.0, 1.0]),
+            ])
+            .await
+            .unwrap();
+        let path = temp.path().join("snapshots/index.snapshot");
+        assert_eq!(manager.save_snapshot(&store, &path).await.unwrap(), 2);
+
+        // Restores into a fresh index and store with embeddings intact
+        let restored_config = IndexConfig::with_path(temp.path().join("restored"));
+        let mut restored = IndexManager::new(restored_config).unwrap();
+        let restored_store = InMemoryVectorStore::new();
+        assert_eq!(restored.load_snapshot(&restored_store, &path).await.unwrap(), 2);
+        assert!(restored.metadata().skill_checksums.contains_key("kubernetes"));
+        let docs = restored_store.get(vec!["kubernetes:logs".to_string()]).await.unwrap();
+        assert_eq!(docs[0].embedding, vec![1.0, 0.0]);
+        assert_eq!(docs[0].content.as_deref(), Some("Print pod logs"));
+        assert_eq!(docs[0].metadata.skill_name.as_deref(), Some("kubernetes"));
+
+        // Refuses a snapshot embedded with another model
+        let other = IndexConfig::with_path(temp.path().join("other")).with_model("bge-small", 384);
+        let mut other = IndexManager::new(other).unwrap();
+        let err = other.load_snapshot(&InMemoryVectorStore::new(), &path).await.unwrap_err();
+        assert!(err.to_string().contains("built with all-minilm"));
+    }
+
+    #[test]
+    fn test_chunk_text() {
+        let config = ChunkConfig {
+            max_chars: 200,
+            overlap_chars: 60,
+        };
+        assert_eq!(chunk_text("Tool: get", &config), vec!["Tool: get"]);
+
+        let mut text = String::from("Tool: logs | Print pod logs\n## Usage\n");
+        for i in 0..6 {
+            text.push_str(&format!(
+                "Follow the logs of container number {} in the pod\n",
+                i
+            ));
+        }
+        text.push_str("### Flags\n--previous shows logs of the last terminated container\n");
+
+        let chunks = chunk_text(&text, &config);
+        assert!(chunks.len() > 2);
+        assert!(chunks[0].starts_with("Tool: logs"));
+        for chunk in &chunks[1..] {
+            assert!(chunk.starts_with("Tool: logs | Print pod logs\n## Usage"));
+        }
+        // Windows overlap
+        let first_tail = chunks[0].lines().last().unwrap();
+        assert!(chunks[1].contains(first_tail));
+        assert!(chunks.last().unwrap().contains("### Flags\n--previous"));
+
+        let docs = chunk_documents(
+            vec![IndexDocument {
+                id: "kubernetes:logs".to_string(),
+                content: text,
+                metadata: Default::default(),
+            }],
+            &config,
+        );
+        assert_eq!(docs.len(), chunks.len());
+        assert_eq!(docs[0].id, "kubernetes:logs");
+        assert_eq!(docs[1].id, "kubernetes:logs#chunk-2");
+        assert_eq!(docs[1].metadata.custom[PARENT_ID_KEY], "kubernetes:logs");
+    }
 }
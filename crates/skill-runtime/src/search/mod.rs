@@ -11,6 +11,8 @@
 mod bm25;
 #[cfg(feature = "hybrid-search")]
 mod hybrid;
+mod eval;
+mod explain;
 mod fusion;
 #[cfg(feature = "reranker")]
 mod reranker;
@@ -19,7 +21,12 @@ mod context;
 mod query_processor;
 mod index_manager;
 mod pipeline;
+mod session;
 
+pub use eval::{
+    EvalCase, EvalSuite, EvalMetrics, EvalReport, QueryOutcome, evaluate, score_case,
+};
+pub use explain::{ScoreExplanation, matched_terms, rrf_contribution};
 pub use fusion::{FusionMethod, reciprocal_rank_fusion, weighted_sum_fusion};
 
 #[cfg(feature = "hybrid-search")]
@@ -36,17 +43,21 @@ pub use reranker::{
 #[cfg(feature = "context-compression")]
 pub use context::{
     ContextCompressor, CompressionStrategy, CompressionConfig,
-    CompressedToolContext, ToolParameter, CompressionResult,
+    CompressedToolContext, ToolParameter, CompressionResult, ToolDocument, ToolParameterInput,
 };
 
 pub use query_processor::{
     QueryProcessor, QueryIntent, ExtractedEntity, EntityType,
-    ProcessedQuery, QueryExpansion,
+    ProcessedQuery, QueryExpansion, SuggestedFilter,
+};
+
+pub use session::{
+    SearchSession, SessionStore, SessionQuery, SelectedTool, DEFAULT_SESSION_TTL,
 };
 
 pub use index_manager::{
-    IndexManager, IndexConfig, IndexMetadata, SkillChecksum,
-    IndexStats, SyncResult,
+    IndexManager, IndexConfig, IndexMetadata, IndexSnapshot, SkillChecksum,
+    IndexStats, SyncResult, ChunkConfig, chunk_documents, chunk_text,
 };
 
 pub use pipeline::{
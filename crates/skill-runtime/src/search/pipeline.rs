@@ -54,17 +54,23 @@
 //! ```
 
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
-use crate::embeddings::{EmbeddingProvider, EmbeddingProviderFactory};
+use crate::embeddings::{
+    EmbeddingProvider, EmbeddingProviderFactory, FastEmbedModel, ModelSpec, prepare_fastembed_model,
+};
 use crate::search_config::{BackendType, SearchConfig};
+use crate::generation::{BudgetGuard, estimate_tokens};
 use crate::vector_store::{
     EmbeddedDocument, DocumentMetadata, FileVectorStore, Filter, InMemoryVectorStore, VectorStore,
 };
 
 #[cfg(feature = "ai-ingestion")]
-use crate::generation::{ExampleGenerator, GeneratorConfig, GenerationEvent, GeneratedExample, create_llm_provider};
+use crate::generation::{ExampleGenerator, DocDrafter, BudgetedProvider, GeneratorConfig, GenerationEvent, GeneratedExample, create_llm_provider};
+#[cfg(feature = "ai-ingestion")]
+use crate::types::ToolDefinition;
 #[cfg(feature = "ai-ingestion")]
 use crate::skill_md::ToolDocumentation;
 #[cfg(feature = "ai-ingestion")]
@@ -85,9 +91,9 @@ use tokio::sync::RwLock;
 use super::{FastEmbedReranker, RerankerConfig as SearchRerankerConfig, Reranker, RerankDocument};
 
 #[cfg(feature = "context-compression")]
-use super::{ContextCompressor, CompressionConfig, CompressedToolContext};
+use super::{ContextCompressor, CompressionConfig, CompressedToolContext, ToolDocument};
 
-use super::{QueryProcessor, ProcessedQuery};
+use super::{chunk_documents, IndexMetadata, IndexSnapshot, QueryProcessor, ProcessedQuery, ScoreExplanation, SelectedTool, SessionStore, matched_terms};
 
 /// Result from a search operation
 #[derive(Debug, Clone)]
@@ -102,6 +108,8 @@ pub struct PipelineSearchResult {
     pub metadata: DocumentMetadata,
     /// Reranker score (if reranking was performed)
     pub rerank_score: Option<f32>,
+    /// Breakdown of how the score was produced
+    pub explanation: Option<ScoreExplanation>,
 }
 
 /// Statistics about indexed documents
@@ -158,12 +166,103 @@ pub struct IndexDocument {
     pub metadata: DocumentMetadata,
 }
 
+/// Custom metadata key linking an example document to its tool's document
+pub const PARENT_ID_KEY: &str = "parent_id";
+
+impl IndexDocument {
+    /// One document per example, linked to this (tool) document
+    ///
+    /// Each example is embedded on its own so a query phrased like one
+    /// specific example still finds the tool; search results report the
+    /// match as the tool itself. Blank examples are skipped.
+    pub fn example_documents<I, S>(&self, examples: I) -> Vec<IndexDocument>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.linked_documents("example", examples)
+    }
+
+    /// Whether this document is an example linked to a tool's document
+    pub fn is_example(&self) -> bool {
+        self.metadata.custom.contains_key(PARENT_ID_KEY)
+    }
+
+    fn linked_documents<I, S>(&self, kind: &str, examples: I) -> Vec<IndexDocument>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let tool = self.metadata.tool_name.as_deref().unwrap_or(&self.id);
+        examples
+            .into_iter()
+            .filter(|example| !example.as_ref().trim().is_empty())
+            .enumerate()
+            .map(|(idx, example)| {
+                let mut metadata = self.metadata.clone();
+                metadata.custom.insert(PARENT_ID_KEY.to_string(), self.id.clone());
+                IndexDocument {
+                    id: format!("{}#{}-{}", self.id, kind, idx + 1),
+                    content: format!("Example for {}: {}", tool, example.as_ref().trim()),
+                    metadata,
+                }
+            })
+            .collect()
+    }
+}
+
+impl PipelineSearchResult {
+    /// ID of the tool document this result belongs to
+    pub fn parent_id(&self) -> &str {
+        self.metadata.custom.get(PARENT_ID_KEY).map(String::as_str).unwrap_or(&self.id)
+    }
+}
+
+/// Collapse example documents into their tools, keeping one result per tool
+/// scored by its best-matching document (max-score aggregation)
+///
+/// A collapsed result takes the tool's ID, and its content when the tool's
+/// own document was among the candidates.
+fn collapse_by_tool(results: Vec<PipelineSearchResult>) -> Vec<PipelineSearchResult> {
+    let mut collapsed: Vec<PipelineSearchResult> = Vec::with_capacity(results.len());
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut tool_content: HashMap<String, String> = HashMap::new();
+
+    for mut result in results {
+        let parent = result.parent_id().to_string();
+        if parent == result.id {
+            tool_content.insert(parent.clone(), result.content.clone());
+        }
+        result.metadata.custom.remove(PARENT_ID_KEY);
+        result.id = parent.clone();
+
+        match positions.get(&parent) {
+            Some(&pos) if collapsed[pos].score >= result.score => {}
+            Some(&pos) => collapsed[pos] = result,
+            None => {
+                positions.insert(parent, collapsed.len());
+                collapsed.push(result);
+            }
+        }
+    }
+
+    for result in &mut collapsed {
+        if let Some(content) = tool_content.remove(&result.id) {
+            result.content = content;
+        }
+    }
+    collapsed.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    collapsed
+}
+
 /// Unified search pipeline that orchestrates all RAG components
 pub struct SearchPipeline {
     /// Configuration
     config: SearchConfig,
     /// Embedding provider
     embedding_provider: Arc<dyn EmbeddingProvider>,
+    /// Token and cost limits shared by embedding and LLM calls
+    budget: Arc<BudgetGuard>,
     /// Vector store
     vector_store: Arc<dyn VectorStore>,
     /// BM25 index for hybrid search
@@ -178,12 +277,17 @@ pub struct SearchPipeline {
     /// AI example generator
     #[cfg(feature = "ai-ingestion")]
     example_generator: Option<Arc<ExampleGenerator>>,
+    /// AI documentation drafter for tools without a SKILL.md
+    #[cfg(feature = "ai-ingestion")]
+    doc_drafter: Option<Arc<DocDrafter>>,
     /// Query processor
     query_processor: QueryProcessor,
     /// Known skills for query processing
     known_skills: Vec<String>,
     /// Known tools for query processing
     known_tools: Vec<String>,
+    /// Conversational search sessions
+    sessions: SessionStore,
 }
 
 impl SearchPipeline {
@@ -204,6 +308,12 @@ impl SearchPipeline {
             batch_size: 100,
         };
 
+        // Use locally managed FastEmbed models when present (required offline)
+        if embedding_config.provider == crate::embeddings::EmbeddingProviderType::FastEmbed {
+            let model: FastEmbedModel = config.embedding.model.parse().unwrap_or_default();
+            prepare_fastembed_model(ModelSpec::for_embedding(model), config.embedding.offline)?;
+        }
+
         let embedding_provider = EmbeddingProviderFactory::create(&embedding_config)
             .context("Failed to create embedding provider")?;
 
@@ -269,12 +379,17 @@ impl SearchPipeline {
         // Create reranker if enabled
         #[cfg(feature = "reranker")]
         let reranker: Option<Arc<dyn Reranker>> = if config.reranker.enabled {
+            let cache_dir = match ModelSpec::for_reranker(&config.reranker.model) {
+                Some(spec) => prepare_fastembed_model(spec, config.embedding.offline)?,
+                None => None,
+            };
             let reranker_config = SearchRerankerConfig {
                 model: config.reranker.model.parse().unwrap_or_default(),
-                top_k: config.retrieval.final_k,
+                max_documents: config.reranker.max_documents,
+                cache_dir,
                 ..Default::default()
             };
-            let fastembed_reranker = FastEmbedReranker::new(reranker_config)
+            let fastembed_reranker = FastEmbedReranker::with_config(reranker_config)
                 .context("Failed to create reranker")?;
             Some(Arc::new(fastembed_reranker))
         } else {
@@ -285,7 +400,7 @@ impl SearchPipeline {
         #[cfg(feature = "context-compression")]
         let compressor = {
             let compression_config = CompressionConfig {
-                max_tokens_per_result: config.context.max_tokens_per_result,
+                max_tokens_per_tool: config.context.max_tokens_per_result,
                 max_total_tokens: config.context.max_total_tokens,
                 strategy: match config.context.compression {
                     crate::search_config::CompressionStrategy::Extractive => {
@@ -303,37 +418,45 @@ impl SearchPipeline {
                 },
                 ..Default::default()
             };
-            Some(ContextCompressor::new(compression_config)?)
+            Some(ContextCompressor::with_config(compression_config)?)
         };
 
         // Create query processor
         let query_processor = QueryProcessor::new();
 
+        let budget = Arc::new(BudgetGuard::from_config(&config.ai_ingestion.budget));
+
         // Create example generator if AI ingestion is enabled
         #[cfg(feature = "ai-ingestion")]
-        let example_generator = if config.ai_ingestion.enabled {
+        let (example_generator, doc_drafter) = if config.ai_ingestion.enabled {
             match create_llm_provider(&config.ai_ingestion) {
                 Ok(llm) => {
+                    let llm: Arc<dyn crate::generation::LlmProvider> =
+                        Arc::new(BudgetedProvider::new(llm, budget.clone()));
                     let gen_config = GeneratorConfig::from(&config.ai_ingestion);
                     info!(
                         "AI example generation enabled: {} / {}",
                         llm.name(),
                         llm.model()
                     );
-                    Some(Arc::new(ExampleGenerator::new(llm, gen_config)))
+                    (
+                        Some(Arc::new(ExampleGenerator::new(llm.clone(), gen_config.clone()))),
+                        Some(Arc::new(DocDrafter::new(llm, gen_config))),
+                    )
                 }
                 Err(e) => {
                     warn!("Failed to create LLM provider for AI ingestion: {}", e);
-                    None
+                    (None, None)
                 }
             }
         } else {
-            None
+            (None, None)
         };
 
-        Ok(Self {
+        let pipeline = Self {
             config,
             embedding_provider,
+            budget,
             vector_store,
             #[cfg(feature = "hybrid-search")]
             bm25_index,
@@ -343,10 +466,47 @@ impl SearchPipeline {
             compressor,
             #[cfg(feature = "ai-ingestion")]
             example_generator,
+            #[cfg(feature = "ai-ingestion")]
+            doc_drafter,
             query_processor,
             known_skills: Vec::new(),
             known_tools: Vec::new(),
-        })
+            sessions: SessionStore::default(),
+        };
+
+        // A snapshot that doesn't match the embedding model is only a missed
+        // shortcut: tools are embedded as they are indexed instead
+        if let Some(path) = pipeline.config.index.snapshot.as_deref().filter(|p| p.exists()) {
+            let restored = match IndexSnapshot::load(path) {
+                Ok(snapshot) => pipeline.restore_snapshot(snapshot).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = restored {
+                warn!("Ignoring index snapshot {}: {}", path.display(), e);
+            }
+        }
+
+        Ok(pipeline)
+    }
+
+    /// Run an embedding call, enforcing the AI budget for metered providers
+    async fn metered_embedding<T>(
+        &self,
+        tokens: u64,
+        call: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        if !BudgetGuard::is_metered_embedding(self.embedding_provider.provider_name()) {
+            return call.await;
+        }
+        self.budget.check(tokens)?;
+        let result = call.await?;
+        self.budget.record(tokens);
+        Ok(result)
+    }
+
+    /// Token usage of this pipeline's LLM and metered embedding calls
+    pub fn budget(&self) -> &BudgetGuard {
+        &self.budget
     }
 
     /// Create a pipeline with default configuration (FastEmbed, InMemory)
@@ -357,48 +517,75 @@ impl SearchPipeline {
     /// Index documents into the pipeline
     ///
     /// This embeds the documents and stores them in both the vector store
-    /// and BM25 index (if hybrid search is enabled).
+    /// and BM25 index (if hybrid search is enabled). Documents longer than
+    /// `[index.chunking] max_chars` are split into linked chunks first.
     pub async fn index_documents(&self, documents: Vec<IndexDocument>) -> Result<PipelineIndexStats> {
         if documents.is_empty() {
             return Ok(PipelineIndexStats::default());
         }
+        let documents = chunk_documents(documents, &self.config.index.chunking);
 
         info!("Indexing {} documents", documents.len());
 
+        // Reuse embeddings of documents already stored with the same content,
+        // e.g. restored from a snapshot
+        let contents: HashMap<&str, &str> = documents
+            .iter()
+            .map(|d| (d.id.as_str(), d.content.as_str()))
+            .collect();
+        let ids = documents.iter().map(|d| d.id.clone()).collect();
+        let existing: HashMap<String, Vec<f32>> = self
+            .vector_store
+            .get(ids)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|doc| {
+                !doc.embedding.is_empty()
+                    && doc.content.as_deref().is_some_and(|c| contents.get(doc.id.as_str()) == Some(&c))
+            })
+            .map(|doc| (doc.id, doc.embedding))
+            .collect();
+        drop(contents);
+
         // Extract texts for embedding
-        let texts: Vec<String> = documents.iter().map(|d| d.content.clone()).collect();
+        let texts: Vec<String> = documents
+            .iter()
+            .filter(|d| !existing.contains_key(&d.id))
+            .map(|d| d.content.clone())
+            .collect();
+        if !existing.is_empty() {
+            debug!("Reusing embeddings of {} unchanged documents", existing.len());
+        }
 
         // Generate embeddings
-        let embeddings = self.embedding_provider
-            .embed_documents_batched(texts)
-            .await
-            .context("Failed to generate embeddings")?;
+        let embeddings = if texts.is_empty() {
+            Vec::new()
+        } else {
+            let tokens = texts.iter().map(|t| estimate_tokens(t)).sum();
+            self.metered_embedding(tokens, self.embedding_provider.embed_documents_batched(texts))
+                .await
+                .context("Failed to generate embeddings")?
+        };
+        let mut embeddings = embeddings.into_iter();
 
         // Create embedded documents
         let embedded_docs: Vec<EmbeddedDocument> = documents
             .into_iter()
-            .zip(embeddings)
-            .map(|(doc, embedding)| EmbeddedDocument {
-                id: doc.id,
-                content: Some(doc.content),
-                embedding,
-                metadata: doc.metadata,
+            .filter_map(|doc| {
+                let embedding = existing.get(&doc.id).cloned().or_else(|| embeddings.next())?;
+                Some(EmbeddedDocument {
+                    id: doc.id,
+                    content: Some(doc.content),
+                    embedding,
+                    metadata: doc.metadata,
+                })
             })
             .collect();
 
-        let _doc_count = embedded_docs.len();
-
         // Index in BM25 if enabled
         #[cfg(feature = "hybrid-search")]
-        if let Some(ref bm25) = self.bm25_index {
-            let mut bm25_guard = bm25.write().await;
-            for doc in &embedded_docs {
-                if let Some(ref content) = doc.content {
-                    bm25_guard.add_document(&doc.id, content)?;
-                }
-            }
-            debug!("Added {} documents to BM25 index", doc_count);
-        }
+        self.index_keywords(&embedded_docs).await?;
 
         // Upsert to vector store
         let stats = self.vector_store.upsert(embedded_docs).await
@@ -414,10 +601,51 @@ impl SearchPipeline {
         })
     }
 
+    /// Add documents to the BM25 index when hybrid search is enabled
+    #[cfg(feature = "hybrid-search")]
+    async fn index_keywords(&self, documents: &[EmbeddedDocument]) -> Result<()> {
+        if let Some(ref bm25) = self.bm25_index {
+            let mut bm25_guard = bm25.write().await;
+            for doc in documents {
+                if let Some(ref content) = doc.content {
+                    bm25_guard.add_document_with_metadata(&doc.id, content, content, &doc.metadata)?;
+                }
+            }
+            bm25_guard.commit()?;
+            debug!("Added {} documents to BM25 index", documents.len());
+        }
+        Ok(())
+    }
+
+    /// Snapshot the indexed documents, to be restored with
+    /// [`SearchPipeline::restore_snapshot`]
+    pub async fn export_snapshot(&self) -> Result<IndexSnapshot> {
+        let metadata = IndexMetadata::new(&self.config.embedding.model, self.config.embedding.dimensions);
+        IndexSnapshot::capture(self.vector_store.as_ref(), metadata).await
+    }
+
+    /// Load a snapshot's documents without embedding them again
+    ///
+    /// Fails if the snapshot was built with a different embedding model.
+    pub async fn restore_snapshot(&self, snapshot: IndexSnapshot) -> Result<usize> {
+        snapshot.check_compatible(&self.config.embedding.model, self.config.embedding.dimensions)?;
+        let documents = snapshot.documents;
+
+        #[cfg(feature = "hybrid-search")]
+        self.index_keywords(&documents).await?;
+
+        let count = documents.len();
+        self.vector_store.upsert(documents).await
+            .context("Failed to restore index snapshot")?;
+        info!("Restored {} documents from index snapshot", count);
+        Ok(count)
+    }
+
     /// Index documents with AI-generated examples
     ///
     /// When AI ingestion is enabled, this method generates synthetic examples
-    /// for each tool and appends them to the document content before indexing.
+    /// for each tool and indexes each one as its own document, linked to the
+    /// tool's document.
     #[cfg(feature = "ai-ingestion")]
     pub async fn index_documents_with_generation(
         &self,
@@ -449,6 +677,7 @@ impl SearchPipeline {
             if let Some(ref generator) = self.example_generator {
                 let total_tools = tools.len();
                 let mut all_examples = Vec::new();
+                let mut by_tool: HashMap<String, Vec<GeneratedExample>> = HashMap::new();
 
                 // Generate examples for each tool
                 for (idx, tool) in tools.iter().enumerate() {
@@ -458,13 +687,14 @@ impl SearchPipeline {
                         // Collect examples from events
                         if let GenerationEvent::Example { ref example } = event {
                             all_examples.push(example.clone());
+                            by_tool.entry(tool.name.clone()).or_default().push(example.clone());
                         }
                         yield event;
                     }
                 }
 
-                // Enhance documents with generated examples
-                let enhanced_docs = self.enhance_documents_inline(&documents, &all_examples);
+                // Add a document per generated example
+                let enhanced_docs = Self::with_generated_examples(documents, &by_tool);
 
                 // Index the enhanced documents
                 match self.index_documents(enhanced_docs).await {
@@ -521,6 +751,7 @@ impl SearchPipeline {
         generator: &ExampleGenerator,
     ) -> Result<(Vec<IndexDocument>, Vec<GeneratedExample>)> {
         let mut all_examples = Vec::new();
+        let mut by_tool: HashMap<String, Vec<GeneratedExample>> = HashMap::new();
 
         // Generate examples for each tool
         for tool in tools {
@@ -530,6 +761,7 @@ impl SearchPipeline {
                         "Generated {} examples for tool '{}'",
                         examples.len(), tool.name
                     );
+                    by_tool.insert(tool.name.clone(), examples.clone());
                     all_examples.extend(examples);
                 }
                 Err(e) => {
@@ -538,60 +770,74 @@ impl SearchPipeline {
             }
         }
 
-        // Enhance document content with examples
-        let enhanced = self.enhance_documents_inline(&documents, &all_examples);
+        // Add a document per generated example
+        let enhanced = Self::with_generated_examples(documents, &by_tool);
 
         Ok((enhanced, all_examples))
     }
 
-    /// Enhance document content with generated examples (inline)
+    /// Add a document per generated example, linked to its tool's document
     #[cfg(feature = "ai-ingestion")]
-    fn enhance_documents_inline(
-        &self,
-        documents: &[IndexDocument],
-        examples: &[GeneratedExample],
+    pub fn with_generated_examples(
+        mut documents: Vec<IndexDocument>,
+        by_tool: &HashMap<String, Vec<GeneratedExample>>,
     ) -> Vec<IndexDocument> {
-        if examples.is_empty() {
-            return documents.to_vec();
-        }
-
-        // Build example text to append
-        let example_text = Self::format_examples_for_embedding(examples);
-
-        // Enhance each document by appending examples
-        documents
+        let generated: Vec<IndexDocument> = documents
             .iter()
-            .map(|doc| {
-                // Append examples that might be relevant to this document
-                // For now, append all examples - could be more selective based on tool_name
-                let enhanced_content = format!(
-                    "{}\n\n## Generated Examples\n\n{}",
-                    doc.content, example_text
-                );
-
-                IndexDocument {
-                    id: doc.id.clone(),
-                    content: enhanced_content,
-                    metadata: doc.metadata.clone(),
-                }
+            .filter(|doc| !doc.is_example())
+            .filter_map(|doc| {
+                let examples = by_tool.get(doc.metadata.tool_name.as_ref()?)?;
+                Some(doc.linked_documents(
+                    "generated",
+                    examples
+                        .iter()
+                        .map(|e| format!("{}\n{}", e.command, e.explanation)),
+                ))
             })
-            .collect()
+            .flatten()
+            .collect();
+        documents.extend(generated);
+        documents
     }
 
-    /// Format examples for embedding text
+    /// Draft documentation for tools that ship without a SKILL.md
+    ///
+    /// Each returned [`ToolDocumentation`] is flagged as AI-generated. Tools
+    /// the LLM fails to document are skipped with a warning; nothing is
+    /// drafted when AI ingestion isn't enabled.
     #[cfg(feature = "ai-ingestion")]
-    fn format_examples_for_embedding(examples: &[GeneratedExample]) -> String {
-        examples
-            .iter()
-            .map(|e| {
-                format!(
-                    "Example: {}\n{}",
-                    e.command,
-                    e.explanation
-                )
-            })
-            .collect::<Vec<_>>()
-            .join("\n\n")
+    pub async fn draft_tool_docs(
+        &self,
+        skill_name: &str,
+        tools: &[ToolDefinition],
+    ) -> Vec<ToolDocumentation> {
+        let Some(ref drafter) = self.doc_drafter else {
+            return Vec::new();
+        };
+
+        let mut drafted = Vec::with_capacity(tools.len());
+        for tool in tools {
+            match drafter.draft(skill_name, tool).await {
+                Ok(doc) => drafted.push(doc),
+                Err(e) => warn!("Failed to draft docs for '{}:{}': {}", skill_name, tool.name, e),
+            }
+        }
+        drafted
+    }
+
+    /// Generate examples and draft docs with `llm` instead of the provider
+    /// from `[ai_ingestion]`, such as an [`AgentRunner`](crate::AgentRunner)'s
+    ///
+    /// The AI budget still applies.
+    #[cfg(feature = "ai-ingestion")]
+    pub fn with_llm_provider(mut self, llm: Arc<dyn crate::generation::LlmProvider>) -> Self {
+        let llm: Arc<dyn crate::generation::LlmProvider> =
+            Arc::new(BudgetedProvider::new(llm, self.budget.clone()));
+        let gen_config = GeneratorConfig::from(&self.config.ai_ingestion);
+        info!("AI example generation using {} / {}", llm.name(), llm.model());
+        self.example_generator = Some(Arc::new(ExampleGenerator::new(llm.clone(), gen_config.clone())));
+        self.doc_drafter = Some(Arc::new(DocDrafter::new(llm, gen_config)));
+        self
     }
 
     /// Check if AI example generation is enabled
@@ -617,10 +863,44 @@ impl SearchPipeline {
     /// # Returns
     /// Ranked list of search results
     pub async fn search(&self, query: &str, top_k: usize) -> Result<Vec<PipelineSearchResult>> {
-        debug!("Searching for: {} (top_k={})", query, top_k);
+        self.search_in_session(query, top_k, None).await
+    }
+
+    /// Search within a conversational session
+    ///
+    /// When `session_id` is set, follow-up queries such as "now for staging"
+    /// are expanded against the previous query in the session, and the query
+    /// and its top result are recorded for the next turn.
+    pub async fn search_in_session(
+        &self,
+        query: &str,
+        top_k: usize,
+        session_id: Option<&str>,
+    ) -> Result<Vec<PipelineSearchResult>> {
+        self.search_filtered(query, top_k, session_id, None).await
+    }
+
+    /// Search with structured metadata filters
+    ///
+    /// The filter is pushed down into both the vector store and the BM25
+    /// index (when hybrid search is enabled), so `top_k` results are drawn
+    /// only from matching documents.
+    pub async fn search_filtered(
+        &self,
+        query: &str,
+        top_k: usize,
+        session_id: Option<&str>,
+        filter: Option<Filter>,
+    ) -> Result<Vec<PipelineSearchResult>> {
+        debug!(
+            "Searching for: {} (top_k={}, session={:?}, filter={:?})",
+            query, top_k, session_id, filter
+        );
+        let filter = filter.filter(|f| !f.is_empty());
 
         // Process query for understanding
-        let processed = self.query_processor.process(query);
+        let session = session_id.and_then(|id| self.sessions.get(id));
+        let processed = self.query_processor.process_with_session(query, session.as_ref());
         let search_query = if !processed.normalized.is_empty() {
             &processed.normalized
         } else {
@@ -628,13 +908,17 @@ impl SearchPipeline {
         };
 
         debug!(
-            "Query processed: intent={:?}, confidence={:.2}",
-            processed.intent, processed.intent_confidence
+            "Query processed: intent={:?}, confidence={:.2}, follow_up={}",
+            processed.intent, processed.intent_confidence, processed.is_follow_up
         );
 
+        if let Some(id) = session_id {
+            self.sessions.record_query(id, query, search_query);
+        }
+
         // Generate query embedding
-        let query_embedding = self.embedding_provider
-            .embed_query(search_query)
+        let query_embedding = self
+            .metered_embedding(estimate_tokens(search_query), self.embedding_provider.embed_query(search_query))
             .await
             .context("Failed to embed query")?;
 
@@ -642,7 +926,10 @@ impl SearchPipeline {
         let first_stage_k = self.config.retrieval.first_stage_k.max(top_k * 2);
 
         // Perform search (hybrid or dense-only)
-        let candidates = self.retrieve_candidates(&query_embedding, search_query, first_stage_k).await?;
+        let candidates = self
+            .retrieve_candidates(&query_embedding, search_query, filter.as_ref(), first_stage_k)
+            .await?;
+        let candidates = collapse_by_tool(candidates);
 
         if candidates.is_empty() {
             return Ok(Vec::new());
@@ -650,18 +937,44 @@ impl SearchPipeline {
 
         // Rerank if enabled
         #[cfg(feature = "reranker")]
-        let reranked = if let Some(ref reranker) = self.reranker {
+        let mut reranked = if let Some(ref reranker) = self.reranker {
             self.rerank_results(reranker.as_ref(), query, candidates, top_k).await?
         } else {
             candidates.into_iter().take(top_k).collect()
         };
 
         #[cfg(not(feature = "reranker"))]
-        let reranked: Vec<PipelineSearchResult> = candidates.into_iter().take(top_k).collect();
+        let mut reranked: Vec<PipelineSearchResult> = candidates.into_iter().take(top_k).collect();
+
+        // Record which query terms each result actually contains
+        for result in &mut reranked {
+            let terms = matched_terms(search_query, &result.content);
+            result.explanation.get_or_insert_with(Default::default).matched_terms = terms;
+        }
+
+        if let (Some(id), Some(top)) = (session_id, reranked.first()) {
+            if let (Some(skill), Some(tool)) = (&top.metadata.skill_name, &top.metadata.tool_name) {
+                self.sessions.record_selection(id, SelectedTool {
+                    skill: skill.clone(),
+                    tool: tool.clone(),
+                    instance: top.metadata.instance_name.clone(),
+                });
+            }
+        }
 
         Ok(reranked)
     }
 
+    /// Record that a tool was selected (e.g. executed) within a session
+    pub fn record_session_selection(&self, session_id: &str, selection: SelectedTool) {
+        self.sessions.record_selection(session_id, selection);
+    }
+
+    /// Get the conversational search sessions
+    pub fn sessions(&self) -> &SessionStore {
+        &self.sessions
+    }
+
     /// Search with metadata filtering
     pub async fn search_with_filter(
         &self,
@@ -669,51 +982,36 @@ impl SearchPipeline {
         filter: Filter,
         top_k: usize,
     ) -> Result<Vec<PipelineSearchResult>> {
-        let query_embedding = self.embedding_provider
-            .embed_query(query)
-            .await
-            .context("Failed to embed query")?;
-
-        let results = self.vector_store
-            .search(query_embedding, Some(filter), top_k)
-            .await
-            .context("Vector search failed")?;
-
-        Ok(results
-            .into_iter()
-            .map(|r| PipelineSearchResult {
-                id: r.id,
-                content: r.content.unwrap_or_default(),
-                score: r.score,
-                metadata: r.metadata,
-                rerank_score: None,
-            })
-            .collect())
+        self.search_filtered(query, top_k, None, Some(filter)).await
     }
 
     /// Retrieve candidates using hybrid or dense search
     async fn retrieve_candidates(
         &self,
         query_embedding: &[f32],
-        _query_text: &str,
+        #[cfg_attr(not(feature = "hybrid-search"), allow(unused_variables))]
+        query_text: &str,
+        filter: Option<&Filter>,
         k: usize,
     ) -> Result<Vec<PipelineSearchResult>> {
         #[cfg(feature = "hybrid-search")]
         if self.config.retrieval.enable_hybrid {
             if let Some(ref bm25) = self.bm25_index {
-                return self.hybrid_retrieve(query_embedding, query_text, bm25, k).await;
+                return self.hybrid_retrieve(query_embedding, query_text, filter, bm25, k).await;
             }
         }
 
         // Dense-only search
         let results = self.vector_store
-            .search(query_embedding.to_vec(), None, k)
+            .search(query_embedding.to_vec(), filter.cloned(), k)
             .await
             .context("Vector search failed")?;
 
         Ok(results
             .into_iter()
-            .map(|r| PipelineSearchResult {
+            .enumerate()
+            .map(|(idx, r)| PipelineSearchResult {
+                explanation: Some(ScoreExplanation::dense(r.score, idx + 1)),
                 id: r.id,
                 content: r.content.unwrap_or_default(),
                 score: r.score,
@@ -729,6 +1027,7 @@ impl SearchPipeline {
         &self,
         query_embedding: &[f32],
         query_text: &str,
+        filter: Option<&Filter>,
         bm25: &Arc<RwLock<BM25Index>>,
         k: usize,
     ) -> Result<Vec<PipelineSearchResult>> {
@@ -736,13 +1035,13 @@ impl SearchPipeline {
 
         // Dense search
         let dense_results = self.vector_store
-            .search(query_embedding.to_vec(), None, k)
+            .search(query_embedding.to_vec(), filter.cloned(), k)
             .await
             .context("Dense search failed")?;
 
         // BM25 search
         let bm25_guard = bm25.read().await;
-        let sparse_results = bm25_guard.search(query_text, k)?;
+        let sparse_results = bm25_guard.search_filtered(query_text, k, filter)?;
 
         // Convert to common format for fusion
         let dense_scores: Vec<(String, f32)> = dense_results
@@ -752,20 +1051,33 @@ impl SearchPipeline {
 
         let sparse_scores: Vec<(String, f32)> = sparse_results
             .iter()
-            .map(|r| (r.doc_id.clone(), r.score))
+            .map(|r| (r.id.clone(), r.score))
             .collect();
 
         // Reciprocal Rank Fusion
         let rrf_k = self.config.retrieval.rrf_k;
         let fused = reciprocal_rank_fusion(
-            vec![dense_scores, sparse_scores],
+            vec![("dense", dense_scores), ("sparse", sparse_scores)],
             rrf_k,
+            k,
         );
 
         // Rebuild results with fused scores
         let mut results: Vec<PipelineSearchResult> = Vec::with_capacity(k);
 
-        for (id, score) in fused.into_iter().take(k) {
+        for fused_result in fused {
+            let id = fused_result.id;
+            let score = fused_result.score;
+            let explanation = ScoreExplanation {
+                dense_score: fused_result.source_scores.get("dense").copied(),
+                dense_rank: dense_results.iter().position(|r| r.id == id).map(|p| p + 1),
+                bm25_score: fused_result.source_scores.get("sparse").copied(),
+                bm25_rank: sparse_results.iter().position(|r| r.id == id).map(|p| p + 1),
+                fusion_score: Some(score),
+                retrieval_rank: results.len() + 1,
+                ..Default::default()
+            };
+
             // Find the document content from dense results or BM25
             if let Some(dense_match) = dense_results.iter().find(|r| r.id == id) {
                 results.push(PipelineSearchResult {
@@ -774,17 +1086,23 @@ impl SearchPipeline {
                     score,
                     metadata: dense_match.metadata.clone(),
                     rerank_score: None,
+                    explanation: Some(explanation),
                 });
-            } else if let Some(_sparse_match) = sparse_results.iter().find(|r| r.doc_id == id) {
+            } else if sparse_results.iter().any(|r| r.id == id) {
                 // Get full document from vector store
                 if let Ok(docs) = self.vector_store.get(vec![id.clone()]).await {
                     if let Some(doc) = docs.into_iter().next() {
+                        // Custom metadata is not indexed in BM25, re-check here
+                        if filter.is_some_and(|f| !f.matches(&doc.metadata)) {
+                            continue;
+                        }
                         results.push(PipelineSearchResult {
                             id: doc.id,
                             content: doc.content.unwrap_or_default(),
                             score,
                             metadata: doc.metadata,
                             rerank_score: None,
+                            explanation: Some(explanation),
                         });
                     }
                 }
@@ -809,29 +1127,33 @@ impl SearchPipeline {
 
         let rerank_docs: Vec<RerankDocument> = candidates
             .iter()
-            .map(|r| RerankDocument {
-                id: r.id.clone(),
-                text: r.content.clone(),
-            })
+            .map(|r| RerankDocument::with_score(r.id.clone(), r.content.clone(), r.score))
             .collect();
 
         let reranked = reranker.rerank(query, rerank_docs, top_k)?;
 
         // Rebuild results with rerank scores
-        let results: Vec<PipelineSearchResult> = reranked
-            .into_iter()
-            .filter_map(|rr| {
-                candidates.iter().find(|c| c.id == rr.document.id).map(|c| {
-                    PipelineSearchResult {
-                        id: c.id.clone(),
-                        content: c.content.clone(),
-                        score: c.score,
-                        metadata: c.metadata.clone(),
-                        rerank_score: Some(rr.score),
-                    }
-                })
-            })
-            .collect();
+        let mut results: Vec<PipelineSearchResult> = Vec::with_capacity(reranked.len());
+        for rr in reranked {
+            let Some(c) = candidates.get(rr.original_index) else {
+                continue;
+            };
+
+            let retrieval_rank = rr.original_index + 1;
+            let mut explanation = c.explanation.clone().unwrap_or_default();
+            explanation.retrieval_rank = retrieval_rank;
+            explanation.rerank_score = Some(rr.relevance_score);
+            explanation.rerank_delta = Some(retrieval_rank as i64 - (results.len() + 1) as i64);
+
+            results.push(PipelineSearchResult {
+                id: c.id.clone(),
+                content: c.content.clone(),
+                score: c.score,
+                metadata: c.metadata.clone(),
+                rerank_score: Some(rr.relevance_score),
+                explanation: Some(explanation),
+            });
+        }
 
         Ok(results)
     }
@@ -845,21 +1167,27 @@ impl SearchPipeline {
         let compressor = self.compressor.as_ref()
             .context("Context compression not enabled")?;
 
-        let tools: Vec<_> = results
+        let documents: Vec<ToolDocument> = results
             .iter()
             .map(|r| {
-                // Parse as tool if possible
-                super::CompressedToolContext {
-                    name: r.metadata.tool_name.clone().unwrap_or_else(|| r.id.clone()),
+                let name = r.metadata.tool_name.clone().unwrap_or_else(|| r.id.clone());
+                let tool_id = match (&r.metadata.skill_name, &r.metadata.instance_name) {
+                    (Some(skill), Some(instance)) => format!("{}@{}/{}", skill, instance, name),
+                    (Some(skill), None) => format!("{}/{}", skill, name),
+                    _ => r.id.clone(),
+                };
+                ToolDocument {
+                    tool_id,
+                    name,
                     description: r.content.clone(),
                     parameters: Vec::new(),
                     example: None,
-                    score: r.rerank_score.unwrap_or(r.score),
+                    relevance_score: r.rerank_score.unwrap_or(r.score),
                 }
             })
             .collect();
 
-        Ok(tools)
+        Ok(compressor.compress(documents).tools)
     }
 
     /// Check health of all pipeline components
@@ -968,6 +1296,26 @@ impl SearchPipeline {
         self.vector_store.count(None).await
     }
 
+    /// Remove documents from the index, e.g. tools of an uninstalled skill
+    ///
+    /// The BM25 index can't delete single documents, so with hybrid search
+    /// enabled it is cleared and callers re-index the documents they keep.
+    pub async fn remove_documents(&self, ids: Vec<String>) -> Result<usize> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        #[cfg(feature = "hybrid-search")]
+        if let Some(ref bm25) = self.bm25_index {
+            bm25.write().await.clear()?;
+        }
+
+        let stats = self.vector_store.delete(ids).await
+            .context("Failed to delete from vector store")?;
+        debug!("Removed {} documents from the index", stats.deleted);
+        Ok(stats.deleted)
+    }
+
     /// Clear all indexed documents
     pub async fn clear(&self) -> Result<()> {
         // For InMemory, we'd need to recreate it
@@ -1029,6 +1377,12 @@ impl SearchPipeline {
     pub fn process_query(&self, query: &str) -> ProcessedQuery {
         self.query_processor.process(query)
     }
+
+    /// Process a query against a session's context without searching
+    pub fn process_query_in_session(&self, query: &str, session_id: &str) -> ProcessedQuery {
+        let session = self.sessions.get(session_id);
+        self.query_processor.process_with_session(query, session.as_ref())
+    }
 }
 
 #[cfg(test)]
@@ -1094,6 +1448,102 @@ mod tests {
         assert!(results[0].content.to_lowercase().contains("kubernetes"));
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_search_results_are_explained() {
+        let pipeline = SearchPipeline::from_config(SearchConfig::default()).await.unwrap();
+        pipeline.index_documents(vec![
+            IndexDocument {
+                id: "1".to_string(),
+                content: "List all Kubernetes pods in the cluster".to_string(),
+                metadata: DocumentMetadata::default(),
+            },
+            IndexDocument {
+                id: "2".to_string(),
+                content: "Create an S3 bucket in AWS".to_string(),
+                metadata: DocumentMetadata::default(),
+            },
+        ]).await.unwrap();
+
+        let results = pipeline.search("kubernetes pods", 2).await.unwrap();
+        let top = results[0].explanation.as_ref().unwrap();
+        assert_eq!(top.retrieval_rank, 1);
+        assert!(top.dense_score.is_some());
+        assert!(top.matched_terms.contains(&"pods".to_string()));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_remove_documents() {
+        let pipeline = SearchPipeline::from_config(SearchConfig::default()).await.unwrap();
+        pipeline.index_documents(vec![
+            IndexDocument {
+                id: "kubernetes:get".to_string(),
+                content: "List all Kubernetes pods in the cluster".to_string(),
+                metadata: DocumentMetadata::default(),
+            },
+            IndexDocument {
+                id: "aws:create-bucket".to_string(),
+                content: "Create an S3 bucket in AWS".to_string(),
+                metadata: DocumentMetadata::default(),
+            },
+        ]).await.unwrap();
+
+        let removed = pipeline
+            .remove_documents(vec!["aws:create-bucket".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(pipeline.document_count().await.unwrap(), 1);
+
+        let results = pipeline.search("s3 bucket", 5).await.unwrap();
+        assert!(results.iter().all(|r| r.id != "aws:create-bucket"));
+    }
+
+    #[test]
+    fn test_examples_collapse_into_their_tool() {
+        let tool = IndexDocument {
+            id: "kubernetes:logs".to_string(),
+            content: "Print pod logs".to_string(),
+            metadata: DocumentMetadata {
+                tool_name: Some("logs".to_string()),
+                ..Default::default()
+            },
+        };
+        let examples =
+            tool.example_documents(["", "skill run kubernetes logs pod=api follow=true"]);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].id, "kubernetes:logs#example-1");
+        assert!(examples[0].is_example());
+        assert!(examples[0].content.starts_with("Example for logs: "));
+
+        let result = |doc: &IndexDocument, score: f32| PipelineSearchResult {
+            id: doc.id.clone(),
+            content: doc.content.clone(),
+            score,
+            metadata: doc.metadata.clone(),
+            rerank_score: None,
+            explanation: None,
+        };
+        let other = IndexDocument {
+            id: "kubernetes:get".to_string(),
+            content: "Get resources".to_string(),
+            metadata: DocumentMetadata::default(),
+        };
+
+        let collapsed = collapse_by_tool(vec![
+            result(&other, 0.7),
+            result(&examples[0], 0.9),
+            result(&tool, 0.4),
+        ]);
+        assert_eq!(collapsed.len(), 2);
+        assert_eq!(collapsed[0].id, "kubernetes:logs");
+        assert_eq!(collapsed[0].score, 0.9);
+        assert_eq!(collapsed[0].content, "Print pod logs");
+        assert!(collapsed[0].metadata.custom.is_empty());
+        assert_eq!(collapsed[1].id, "kubernetes:get");
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_pipeline_health_check() {
@@ -1119,6 +1569,58 @@ mod tests {
         assert!(!processed.normalized.is_empty());
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_search_filtered_by_runtime() {
+        let config = SearchConfig::default();
+        let pipeline = SearchPipeline::from_config(config).await.unwrap();
+
+        let docs = vec![
+            IndexDocument {
+                id: "1".to_string(),
+                content: "List Kubernetes pods".to_string(),
+                metadata: DocumentMetadata {
+                    skill_name: Some("kubernetes".to_string()),
+                    runtime: Some("native".to_string()),
+                    ..Default::default()
+                },
+            },
+            IndexDocument {
+                id: "2".to_string(),
+                content: "List Kubernetes pods with a WASM client".to_string(),
+                metadata: DocumentMetadata {
+                    skill_name: Some("k8s-wasm".to_string()),
+                    runtime: Some("wasm".to_string()),
+                    ..Default::default()
+                },
+            },
+        ];
+        pipeline.index_documents(docs).await.unwrap();
+
+        let filter = Filter::new().runtime("native");
+        let results = pipeline
+            .search_filtered("kubernetes pods", 5, None, Some(filter))
+            .await
+            .unwrap();
+
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| r.metadata.runtime.as_deref() == Some("native")));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_session_follow_up_search() {
+        let config = SearchConfig::default();
+        let pipeline = SearchPipeline::from_config(config).await.unwrap();
+
+        pipeline.search_in_session("list pods in dev", 5, Some("s1")).await.unwrap();
+        let processed = pipeline.process_query_in_session("now for staging", "s1");
+
+        assert!(processed.is_follow_up);
+        assert!(processed.normalized.contains("pods"));
+        assert!(processed.normalized.contains("staging"));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_empty_search() {
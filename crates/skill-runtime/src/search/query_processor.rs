@@ -5,6 +5,30 @@
 
 use std::collections::{HashMap, HashSet};
 
+use super::session::SearchSession;
+
+/// Leading words that mark a query as a follow-up to the previous one
+const FOLLOW_UP_PREFIXES: &[&str] = &[
+    "now", "same", "also", "and", "then", "again", "instead", "but",
+];
+
+/// Phrases that mark a query as a follow-up to the previous one
+const FOLLOW_UP_PHRASES: &[&str] = &[
+    "what about", "how about", "same for", "same but", "do the same", "do that", "and for",
+];
+
+/// Filler words dropped from follow-up queries before merging with context
+const FOLLOW_UP_FILLER: &[&str] = &[
+    "now", "same", "also", "and", "then", "again", "instead", "but", "what", "about",
+    "how", "do", "the", "that", "it", "this", "for", "in", "on", "with", "please", "one",
+];
+
+/// Environment names recognised as instance hints
+const ENVIRONMENT_TERMS: &[&str] = &[
+    "dev", "development", "staging", "stage", "prod", "production", "qa", "test",
+    "sandbox", "preview", "local",
+];
+
 /// Query intent classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum QueryIntent {
@@ -54,6 +78,8 @@ pub enum EntityType {
     Category,
     /// Target/object (e.g., "pods", "files", "users")
     Target,
+    /// Environment / instance hint (e.g., "staging", "prod")
+    Environment,
 }
 
 /// Extracted entity from query
@@ -108,6 +134,8 @@ pub struct ProcessedQuery {
     pub expansions: Vec<QueryExpansion>,
     /// Suggested search filters
     pub suggested_filters: Vec<SuggestedFilter>,
+    /// Whether the query was expanded as a follow-up using session context
+    pub is_follow_up: bool,
 }
 
 /// Suggested filter for search
@@ -182,7 +210,25 @@ impl QueryProcessor {
 
     /// Process a query for improved search
     pub fn process(&self, query: &str) -> ProcessedQuery {
-        let normalized = self.normalize_query(query);
+        self.process_with_session(query, None)
+    }
+
+    /// Process a query, expanding follow-ups against a search session
+    ///
+    /// When the query looks like a follow-up ("now for staging", "same but
+    /// delete") and the session has history, the previous query is merged
+    /// with the new terms so the search runs against the full intent.
+    pub fn process_with_session(&self, query: &str, session: Option<&SearchSession>) -> ProcessedQuery {
+        let mut normalized = self.normalize_query(query);
+        let mut is_follow_up = false;
+
+        if let Some(session) = session {
+            if let Some(expanded) = self.expand_follow_up(&normalized, session) {
+                normalized = expanded;
+                is_follow_up = true;
+            }
+        }
+
         let tokens = self.tokenize(&normalized);
 
         // Classify intent
@@ -205,7 +251,81 @@ impl QueryProcessor {
             entities,
             expansions,
             suggested_filters,
+            is_follow_up,
+        }
+    }
+
+    /// Check whether a normalized query looks like a follow-up
+    pub fn is_follow_up(&self, normalized: &str) -> bool {
+        let tokens = self.tokenize(normalized);
+        let Some(first) = tokens.first() else {
+            return false;
+        };
+
+        if FOLLOW_UP_PREFIXES.contains(&first.as_str()) {
+            return true;
+        }
+
+        if FOLLOW_UP_PHRASES.iter().any(|p| normalized.starts_with(p)) {
+            return true;
+        }
+
+        // Very short qualifier-only queries carry no intent of their own
+        // (e.g. "for staging", "in prod", "production")
+        tokens.len() <= 3
+            && (["for", "in", "on", "with"].contains(&first.as_str())
+                || tokens.iter().all(|t| is_environment_term(t)))
+    }
+
+    /// Merge a follow-up query with the last query in the session
+    fn expand_follow_up(&self, normalized: &str, session: &SearchSession) -> Option<String> {
+        if !self.is_follow_up(normalized) {
+            return None;
+        }
+
+        let previous = match session.last_query() {
+            Some(q) => q.effective.clone(),
+            None => {
+                let selected = session.last_selected()?;
+                format!("{} {}", selected.tool, selected.skill)
+            }
+        };
+
+        let new_terms: Vec<String> = self
+            .tokenize(normalized)
+            .into_iter()
+            .filter(|t| !FOLLOW_UP_FILLER.contains(&t.as_str()))
+            .collect();
+
+        if new_terms.is_empty() {
+            return Some(previous);
+        }
+
+        let replaces_environment = new_terms.iter().any(|t| is_environment_term(t));
+        let replaces_action = new_terms.iter().any(|t| self.action_verbs.contains(t));
+
+        let mut merged: Vec<String> = self
+            .tokenize(&previous)
+            .into_iter()
+            .filter(|t| !(replaces_environment && is_environment_term(t)))
+            .filter(|t| !(replaces_action && self.action_verbs.contains(t)))
+            .collect();
+
+        for term in new_terms {
+            if !merged.contains(&term) {
+                merged.push(term);
+            }
+        }
+
+        // Keep the skill of the last selected tool in scope
+        if let Some(selected) = session.last_selected() {
+            let skill = selected.skill.to_lowercase();
+            if !merged.contains(&skill) {
+                merged.push(skill);
+            }
         }
+
+        Some(merged.join(" "))
     }
 
     /// Get expanded query terms for search
@@ -424,6 +544,17 @@ impl QueryProcessor {
                 continue;
             }
 
+            // Check for environment names (instance hints)
+            if is_environment_term(&token_lower) {
+                entities.push(ExtractedEntity {
+                    text: token_lower,
+                    entity_type: EntityType::Environment,
+                    confidence: 0.8,
+                    position: pos,
+                });
+                continue;
+            }
+
             // Check for category matches
             for (category, keywords) in &self.categories {
                 if keywords.iter().any(|k| token_lower.contains(k) || k.contains(&token_lower)) {
@@ -485,6 +616,13 @@ impl QueryProcessor {
                         confidence: entity.confidence,
                     });
                 }
+                EntityType::Environment => {
+                    filters.push(SuggestedFilter {
+                        field: "instance_name".to_string(),
+                        value: entity.text.clone(),
+                        confidence: entity.confidence,
+                    });
+                }
                 _ => {}
             }
         }
@@ -493,6 +631,10 @@ impl QueryProcessor {
     }
 }
 
+fn is_environment_term(token: &str) -> bool {
+    ENVIRONMENT_TERMS.contains(&token)
+}
+
 // Note: Using regex-lite instead of full regex for lighter dependency
 mod regex_lite {
     pub struct Regex(String);
@@ -650,6 +792,52 @@ mod tests {
         assert!(terms.len() > 1); // Should have expansions
     }
 
+    #[test]
+    fn test_follow_up_replaces_environment() {
+        let processor = QueryProcessor::new().with_skills(["kubernetes"]);
+        let mut session = SearchSession::new("s1");
+        session.push_query("list kubernetes pods in dev", "list kubernetes pods in dev");
+
+        let query = processor.process_with_session("now for staging", Some(&session));
+        assert!(query.is_follow_up);
+        assert!(query.normalized.contains("staging"));
+        assert!(query.normalized.contains("pods"));
+        assert!(!query.normalized.contains("dev"));
+    }
+
+    #[test]
+    fn test_follow_up_replaces_action() {
+        let processor = QueryProcessor::new();
+        let mut session = SearchSession::new("s1");
+        session.push_query("list s3 buckets", "list s3 buckets");
+
+        let query = processor.process_with_session("same but delete", Some(&session));
+        assert!(query.is_follow_up);
+        assert!(query.normalized.contains("delete"));
+        assert!(query.normalized.contains("buckets"));
+        assert!(!query.normalized.contains("list"));
+    }
+
+    #[test]
+    fn test_standalone_query_not_expanded() {
+        let processor = QueryProcessor::new();
+        let mut session = SearchSession::new("s1");
+        session.push_query("list s3 buckets", "list s3 buckets");
+
+        let query = processor.process_with_session("create a kubernetes deployment", Some(&session));
+        assert!(!query.is_follow_up);
+        assert!(!query.normalized.contains("buckets"));
+    }
+
+    #[test]
+    fn test_environment_filter_suggested() {
+        let processor = QueryProcessor::new();
+
+        let query = processor.process("list pods in staging");
+        assert!(query.suggested_filters.iter()
+            .any(|f| f.field == "instance_name" && f.value == "staging"));
+    }
+
     #[test]
     fn test_normalize_query() {
         let processor = QueryProcessor::new();
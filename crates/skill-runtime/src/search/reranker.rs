@@ -6,6 +6,7 @@
 
 use anyhow::{Context, Result};
 use fastembed::{TextRerank, RerankInitOptions, RerankerModel as FastEmbedRerankerModel};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 /// A document to be reranked
@@ -113,6 +114,8 @@ pub struct RerankerConfig {
     pub min_score_threshold: Option<f32>,
     /// Show download progress for model files
     pub show_download_progress: bool,
+    /// Model cache directory (defaults to FastEmbed's own cache)
+    pub cache_dir: Option<PathBuf>,
 }
 
 impl Default for RerankerConfig {
@@ -122,6 +125,7 @@ impl Default for RerankerConfig {
             max_documents: 50,
             min_score_threshold: None,
             show_download_progress: false,
+            cache_dir: None,
         }
     }
 }
@@ -182,8 +186,11 @@ impl FastEmbedReranker {
     pub fn with_config(config: RerankerConfig) -> Result<Self> {
         let fastembed_model = config.model.to_fastembed_model();
 
-        let options = RerankInitOptions::new(fastembed_model)
+        let mut options = RerankInitOptions::new(fastembed_model)
             .with_show_download_progress(config.show_download_progress);
+        if let Some(ref dir) = config.cache_dir {
+            options = options.with_cache_dir(dir.clone());
+        }
 
         let model = TextRerank::try_new(options)
             .context("Failed to initialize reranker model")?;
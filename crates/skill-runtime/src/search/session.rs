@@ -0,0 +1,272 @@
+//! Short-lived conversational search sessions
+//!
+//! Keeps a small amount of context between related search queries so that
+//! follow-ups like "now for staging" or "same but delete" can be expanded
+//! against what the user asked for previously.
+//!
+//! Sessions are kept in memory only and expire after a period of inactivity.
+//! Session IDs come from clients, so the number of live sessions is capped;
+//! the least recently used one makes room for a new one.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Default time a session is kept alive without activity
+pub const DEFAULT_SESSION_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Maximum number of queries remembered per session
+const MAX_QUERIES_PER_SESSION: usize = 20;
+
+/// Maximum number of selected tools remembered per session
+const MAX_SELECTIONS_PER_SESSION: usize = 10;
+
+/// Maximum number of live sessions
+const MAX_SESSIONS: usize = 1000;
+
+/// A query previously issued within a session
+#[derive(Debug, Clone)]
+pub struct SessionQuery {
+    /// Query as typed by the user
+    pub original: String,
+    /// Query after follow-up expansion (what was actually searched)
+    pub effective: String,
+}
+
+/// A tool the user selected (or that ranked first) within a session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectedTool {
+    /// Skill name
+    pub skill: String,
+    /// Tool name
+    pub tool: String,
+    /// Instance name, if known
+    pub instance: Option<String>,
+}
+
+/// Conversational context for a single search session
+#[derive(Debug, Clone)]
+pub struct SearchSession {
+    /// Session identifier supplied by the client
+    pub id: String,
+    /// Previous queries, oldest first
+    pub queries: Vec<SessionQuery>,
+    /// Previously selected tools, oldest first
+    pub selected_tools: Vec<SelectedTool>,
+    /// Last time the session was used
+    last_active: Instant,
+}
+
+impl SearchSession {
+    /// Create an empty session
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            queries: Vec::new(),
+            selected_tools: Vec::new(),
+            last_active: Instant::now(),
+        }
+    }
+
+    /// The most recent effective query, if any
+    pub fn last_query(&self) -> Option<&SessionQuery> {
+        self.queries.last()
+    }
+
+    /// The most recently selected tool, if any
+    pub fn last_selected(&self) -> Option<&SelectedTool> {
+        self.selected_tools.last()
+    }
+
+    /// Whether this session has no history yet
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty() && self.selected_tools.is_empty()
+    }
+
+    /// Record a query in the session history
+    pub fn push_query(&mut self, original: impl Into<String>, effective: impl Into<String>) {
+        self.queries.push(SessionQuery {
+            original: original.into(),
+            effective: effective.into(),
+        });
+        if self.queries.len() > MAX_QUERIES_PER_SESSION {
+            self.queries.remove(0);
+        }
+        self.touch();
+    }
+
+    /// Record a selected tool in the session history
+    pub fn push_selection(&mut self, selection: SelectedTool) {
+        self.selected_tools.retain(|s| s != &selection);
+        self.selected_tools.push(selection);
+        if self.selected_tools.len() > MAX_SELECTIONS_PER_SESSION {
+            self.selected_tools.remove(0);
+        }
+        self.touch();
+    }
+
+    fn touch(&mut self) {
+        self.last_active = Instant::now();
+    }
+
+    fn is_expired(&self, ttl: Duration) -> bool {
+        self.last_active.elapsed() > ttl
+    }
+}
+
+/// In-memory store of search sessions with inactivity expiry
+pub struct SessionStore {
+    sessions: Mutex<HashMap<String, SearchSession>>,
+    ttl: Duration,
+}
+
+impl Default for SessionStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_SESSION_TTL)
+    }
+}
+
+impl SessionStore {
+    /// Create a store whose sessions expire after `ttl` of inactivity
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            sessions: Mutex::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    /// Get a snapshot of a session, if it exists and has not expired
+    pub fn get(&self, session_id: &str) -> Option<SearchSession> {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        self.prune(&mut sessions);
+        sessions.get(session_id).cloned()
+    }
+
+    /// Record a query for a session, creating the session if needed
+    pub fn record_query(&self, session_id: &str, original: &str, effective: &str) {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        self.session_mut(&mut sessions, session_id)
+            .push_query(original, effective);
+    }
+
+    /// Record a selected tool for a session, creating the session if needed
+    pub fn record_selection(&self, session_id: &str, selection: SelectedTool) {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        self.session_mut(&mut sessions, session_id)
+            .push_selection(selection);
+    }
+
+    /// Forget a session
+    pub fn remove(&self, session_id: &str) -> bool {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        sessions.remove(session_id).is_some()
+    }
+
+    /// Number of live sessions
+    pub fn len(&self) -> usize {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        self.prune(&mut sessions);
+        sessions.len()
+    }
+
+    /// Whether there are no live sessions
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The live session with this ID, created if needed
+    fn session_mut<'a>(
+        &self,
+        sessions: &'a mut HashMap<String, SearchSession>,
+        session_id: &str,
+    ) -> &'a mut SearchSession {
+        self.prune(sessions);
+        if !sessions.contains_key(session_id) && sessions.len() >= MAX_SESSIONS {
+            // Still full of live sessions: make room by dropping the least
+            // recently used
+            if let Some(oldest) = sessions
+                .iter()
+                .min_by_key(|(_, session)| session.last_active)
+                .map(|(id, _)| id.clone())
+            {
+                sessions.remove(&oldest);
+            }
+        }
+        sessions
+            .entry(session_id.to_string())
+            .or_insert_with(|| SearchSession::new(session_id))
+    }
+
+    fn prune(&self, sessions: &mut HashMap<String, SearchSession>) {
+        let ttl = self.ttl;
+        sessions.retain(|_, s| !s.is_expired(ttl));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_get_session() {
+        let store = SessionStore::default();
+        store.record_query("s1", "list pods in dev", "list pods in dev");
+
+        let session = store.get("s1").unwrap();
+        assert_eq!(session.queries.len(), 1);
+        assert_eq!(session.last_query().unwrap().effective, "list pods in dev");
+        assert!(store.get("s2").is_none());
+    }
+
+    #[test]
+    fn test_selection_deduplicated() {
+        let store = SessionStore::default();
+        let selection = SelectedTool {
+            skill: "kubernetes".to_string(),
+            tool: "get".to_string(),
+            instance: None,
+        };
+        store.record_selection("s1", selection.clone());
+        store.record_selection("s1", selection.clone());
+
+        let session = store.get("s1").unwrap();
+        assert_eq!(session.selected_tools.len(), 1);
+        assert_eq!(session.last_selected(), Some(&selection));
+    }
+
+    #[test]
+    fn test_query_history_is_bounded() {
+        let mut session = SearchSession::new("s1");
+        for i in 0..(MAX_QUERIES_PER_SESSION + 5) {
+            session.push_query(format!("q{}", i), format!("q{}", i));
+        }
+        assert_eq!(session.queries.len(), MAX_QUERIES_PER_SESSION);
+        assert_eq!(session.queries[0].original, "q5");
+    }
+
+    #[test]
+    fn test_session_count_is_bounded() {
+        let store = SessionStore::default();
+        store.record_query("oldest", "list pods", "list pods");
+        std::thread::sleep(Duration::from_millis(5));
+        for i in 1..MAX_SESSIONS {
+            store.record_query(&format!("s{}", i), "list pods", "list pods");
+        }
+        assert_eq!(store.len(), MAX_SESSIONS);
+
+        store.record_query("new", "list pods", "list pods");
+        assert_eq!(store.len(), MAX_SESSIONS);
+        assert!(store.get("new").is_some());
+        assert!(store.get("s1").is_some());
+        assert!(store.get("oldest").is_none());
+    }
+
+    #[test]
+    fn test_sessions_expire() {
+        let store = SessionStore::new(Duration::from_millis(0));
+        store.record_query("s1", "list pods", "list pods");
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(store.get("s1").is_none());
+        assert!(store.is_empty());
+    }
+}
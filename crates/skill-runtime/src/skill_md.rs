@@ -25,6 +25,8 @@
 //! - `resource` (required): Resource type
 //! - `namespace` (optional): Kubernetes namespace
 //!
+//! **Annotations**: read-only, idempotent
+//!
 //! **Example**:
 //! ```bash
 //! skill run kubernetes get resource=pods namespace=default
@@ -34,8 +36,11 @@
 use anyhow::{Context, Result};
 use pulldown_cmark::{Event, HeadingLevel, Parser, Tag, TagEnd};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::network::glob_match;
+use crate::requirements::Requirement;
 
 /// YAML frontmatter from SKILL.md
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -50,11 +55,132 @@ pub struct SkillMdFrontmatter {
     #[serde(default, rename = "allowed-tools")]
     pub allowed_tools: Option<String>,
 
+    /// Language of the document (`en`, `de`, `pt-BR`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+
+    /// Typed parameter schemas by tool and parameter name, applied over the
+    /// markdown `**Parameters**:` lists
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub parameters: HashMap<String, HashMap<String, ParameterSchema>>,
+
+    /// Markdown files documenting more tools, relative to the skill
+    /// directory (`tools/*.md`, `docs/**/*.md`)
+    #[serde(
+        default,
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub include: Vec<String>,
+
     /// Additional metadata
     #[serde(flatten)]
     pub extra: HashMap<String, serde_yaml::Value>,
 }
 
+/// Accept `include: tools/*.md` as well as a list of patterns
+fn one_or_many<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(pattern) => vec![pattern],
+        OneOrMany::Many(patterns) => patterns,
+    })
+}
+
+/// Typed schema of a tool parameter, declared in the frontmatter:
+///
+/// ```yaml
+/// parameters:
+///   scale:
+///     replicas: { type: integer, required: true, minimum: 0, maximum: 50 }
+///     deployment: { type: string, pattern: "^[a-z0-9-]+$" }
+/// ```
+///
+/// Fields that are set replace what the markdown documents.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParameterSchema {
+    /// Parameter type (string, integer, number, boolean, array, object)
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub param_type: Option<String>,
+
+    /// Whether the parameter is required
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+
+    /// Parameter description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Default value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_yaml::Value>,
+
+    /// Allowed values
+    #[serde(default, rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub allowed_values: Option<Vec<serde_yaml::Value>>,
+
+    /// Regex the value must match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+
+    /// Smallest allowed value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+
+    /// Largest allowed value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+}
+
+impl ParameterSchema {
+    /// Apply the fields that are set to a parameter's documentation
+    fn apply_to(&self, doc: &mut ParameterDoc) {
+        if let Some(param_type) = &self.param_type {
+            doc.param_type = ParameterType::from_name(param_type);
+        }
+        if let Some(required) = self.required {
+            doc.required = required;
+        }
+        if let Some(description) = &self.description {
+            doc.description = description.clone();
+        }
+        if let Some(default) = &self.default {
+            doc.default = Some(yaml_scalar(default));
+        }
+        if let Some(values) = &self.allowed_values {
+            doc.allowed_values = values.iter().map(yaml_scalar).collect();
+        }
+        if self.pattern.is_some() {
+            doc.pattern = self.pattern.clone();
+        }
+        if self.minimum.is_some() {
+            doc.minimum = self.minimum;
+        }
+        if self.maximum.is_some() {
+            doc.maximum = self.maximum;
+        }
+    }
+}
+
+/// A YAML value as it would be passed on the command line
+fn yaml_scalar(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default(),
+    }
+}
+
 /// Parsed SKILL.md content
 #[derive(Debug, Clone, Default)]
 pub struct SkillMdContent {
@@ -94,6 +220,135 @@ pub struct ToolDocumentation {
 
     /// Code examples for this tool
     pub examples: Vec<CodeExample>,
+
+    /// Behavior hints from the **Annotations**: line
+    pub hints: ToolHints,
+
+    /// Host requirements from the **Requires**: line
+    pub requires: Vec<Requirement>,
+
+    /// Drafted by an LLM from the tool signature rather than written by
+    /// the skill author
+    pub ai_generated: bool,
+}
+
+/// Behavior hints for a tool, published as MCP tool annotations.
+///
+/// Declared in SKILL.md with an `**Annotations**:` line listing any of
+/// `read-only`, `destructive`, `non-destructive`, `idempotent`, `open-world`
+/// and `closed-world`. Hints that aren't declared are inferred from the tool
+/// name by [`ToolHints::resolve`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolHints {
+    /// The tool doesn't modify its environment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    /// The tool may perform destructive updates (delete, drain, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destructive: Option<bool>,
+    /// Repeating the call with the same arguments has no additional effect
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotent: Option<bool>,
+    /// The tool interacts with external systems
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_world: Option<bool>,
+}
+
+/// Verbs marking a tool as read-only when it starts with them
+const READ_ONLY_VERBS: &[&str] = &[
+    "get", "list", "ls", "describe", "show", "view", "logs", "log", "status", "search", "find",
+    "top", "explain", "inspect", "read", "cat", "version", "info", "history", "diff", "query",
+    "check", "validate", "whoami",
+];
+
+/// Verbs marking a tool as destructive when any word of its name is one of them
+const DESTRUCTIVE_VERBS: &[&str] = &[
+    "delete",
+    "drain",
+    "destroy",
+    "remove",
+    "rm",
+    "uninstall",
+    "purge",
+    "drop",
+    "terminate",
+    "kill",
+    "prune",
+    "wipe",
+    "reset",
+    "truncate",
+    "evict",
+];
+
+impl ToolHints {
+    /// Parse an `**Annotations**:` value such as `read-only, idempotent`.
+    pub fn parse(value: &str) -> Self {
+        let mut hints = Self::default();
+        for token in value
+            .split([',', ' '])
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+        {
+            match token.to_lowercase().replace('_', "-").as_str() {
+                "read-only" | "readonly" => hints.read_only = Some(true),
+                "destructive" => hints.destructive = Some(true),
+                "non-destructive" | "nondestructive" => hints.destructive = Some(false),
+                "idempotent" => hints.idempotent = Some(true),
+                "open-world" => hints.open_world = Some(true),
+                "closed-world" => hints.open_world = Some(false),
+                _ => {}
+            }
+        }
+        hints
+    }
+
+    /// Hints implied by a tool name (`get_pods` is read-only, `delete` is destructive).
+    pub fn infer(tool_name: &str) -> Self {
+        let lower = tool_name.to_lowercase();
+        let words: Vec<&str> = lower
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        if words.iter().any(|w| DESTRUCTIVE_VERBS.contains(w)) {
+            Self {
+                read_only: Some(false),
+                destructive: Some(true),
+                ..Self::default()
+            }
+        } else if words.first().is_some_and(|w| READ_ONLY_VERBS.contains(w)) {
+            Self {
+                read_only: Some(true),
+                destructive: Some(false),
+                idempotent: Some(true),
+                ..Self::default()
+            }
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Declared hints, with undeclared ones inferred from the tool name.
+    ///
+    /// A tool declared read-only is never reported as destructive.
+    pub fn resolve(&self, tool_name: &str) -> Self {
+        let inferred = Self::infer(tool_name);
+        let read_only = self.read_only.or(inferred.read_only);
+        let destructive = if read_only == Some(true) {
+            Some(false)
+        } else {
+            self.destructive.or(inferred.destructive)
+        };
+        Self {
+            read_only,
+            destructive,
+            idempotent: self
+                .idempotent
+                .or(inferred.idempotent)
+                .or((read_only == Some(true)).then_some(true)),
+            open_world: self.open_world,
+        }
+    }
 }
 
 /// Parameter type enumeration
@@ -114,6 +369,21 @@ pub enum ParameterType {
     Object,
 }
 
+impl ParameterType {
+    /// Type for a name like `integer`, `int` or `Json`; unknown names are
+    /// strings
+    pub fn from_name(name: &str) -> Self {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "integer" | "int" => ParameterType::Integer,
+            "number" | "float" => ParameterType::Number,
+            "boolean" | "bool" => ParameterType::Boolean,
+            "array" | "list" => ParameterType::Array,
+            "object" | "json" | "map" => ParameterType::Object,
+            _ => ParameterType::String,
+        }
+    }
+}
+
 impl std::fmt::Display for ParameterType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -128,7 +398,7 @@ impl std::fmt::Display for ParameterType {
 }
 
 /// Parameter documentation from markdown
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ParameterDoc {
     /// Parameter name
     pub name: String,
@@ -147,6 +417,15 @@ pub struct ParameterDoc {
 
     /// Allowed values (enum)
     pub allowed_values: Vec<String>,
+
+    /// Regex the value must match
+    pub pattern: Option<String>,
+
+    /// Smallest allowed value (numeric parameters)
+    pub minimum: Option<f64>,
+
+    /// Largest allowed value (numeric parameters)
+    pub maximum: Option<f64>,
 }
 
 /// Code example extracted from markdown
@@ -162,22 +441,41 @@ pub struct CodeExample {
     pub description: Option<String>,
 }
 
-/// Parse a SKILL.md file
+/// Parse a SKILL.md file, merging the files it includes
 pub fn parse_skill_md(path: &Path) -> Result<SkillMdContent> {
     let content = std::fs::read_to_string(path)
         .with_context(|| format!("Failed to read SKILL.md: {}", path.display()))?;
 
-    parse_skill_md_content(&content)
+    parse_skill_md_in(&content, path.parent().unwrap_or(Path::new(".")))
 }
 
 /// Parse SKILL.md content from a string
+///
+/// `include` patterns are not resolved; use [`parse_skill_md_in`] when the
+/// skill directory is known.
 pub fn parse_skill_md_content(content: &str) -> Result<SkillMdContent> {
+    parse_document(content, None)
+}
+
+/// Parse SKILL.md content, merging the tool documentation of the files its
+/// `include` patterns match in `skill_dir`
+pub fn parse_skill_md_in(content: &str, skill_dir: &Path) -> Result<SkillMdContent> {
+    parse_document(content, Some(skill_dir))
+}
+
+fn parse_document(content: &str, skill_dir: Option<&Path>) -> Result<SkillMdContent> {
     // Split frontmatter and body
     let (frontmatter, body) = extract_frontmatter(content)?;
 
     // Parse the markdown body
-    let tool_docs = extract_tool_sections(&body);
-    let examples = extract_code_examples(&body);
+    let mut tool_docs = extract_tool_sections(&body);
+    let mut examples = extract_code_examples(&body);
+    if let Some(skill_dir) = skill_dir {
+        for path in resolve_includes(skill_dir, &frontmatter.include)? {
+            merge_include(&path, &mut tool_docs, &mut examples)?;
+        }
+    }
+    apply_parameter_schemas(&mut tool_docs, &frontmatter.parameters);
     let when_to_use = extract_section(&body, "When to Use");
     let configuration = extract_section(&body, "Configuration");
 
@@ -191,6 +489,154 @@ pub fn parse_skill_md_content(content: &str) -> Result<SkillMdContent> {
     })
 }
 
+/// Files matched by `include` patterns, sorted and deduplicated
+///
+/// Patterns are relative to the skill directory; `*` matches within a path
+/// segment and `**` any number of segments. A pattern without wildcards
+/// must name an existing file.
+fn resolve_includes(skill_dir: &Path, patterns: &[String]) -> Result<Vec<PathBuf>> {
+    let mut files = BTreeSet::new();
+
+    for pattern in patterns {
+        let pattern = pattern.trim().trim_start_matches("./");
+        let relative = Path::new(pattern);
+        if relative.is_absolute()
+            || relative
+                .components()
+                .any(|c| matches!(c, std::path::Component::ParentDir))
+        {
+            anyhow::bail!(
+                "SKILL.md include '{}' must stay inside the skill directory",
+                pattern
+            );
+        }
+
+        let segments: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+        let literal = segments.iter().take_while(|s| !s.contains('*')).count();
+        if literal == segments.len() {
+            let path = skill_dir.join(pattern);
+            if !path.is_file() {
+                anyhow::bail!("SKILL.md includes missing file: {}", path.display());
+            }
+            files.insert(path);
+            continue;
+        }
+
+        let base = skill_dir.join(segments[..literal].join("/"));
+        let before = files.len();
+        for entry in walkdir::WalkDir::new(&base)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter(|e| !is_skill_md_name(&e.file_name().to_string_lossy()))
+        {
+            let Ok(rest) = entry.path().strip_prefix(&base) else {
+                continue;
+            };
+            let rest: Vec<String> = rest
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            let rest: Vec<&str> = rest.iter().map(String::as_str).collect();
+            if path_matches(&segments[literal..], &rest) {
+                files.insert(entry.into_path());
+            }
+        }
+        if files.len() == before {
+            tracing::warn!(pattern = %pattern, "SKILL.md include matched no files");
+        }
+    }
+
+    Ok(files.into_iter().collect())
+}
+
+/// SKILL.md itself or one of its localized variants (`SKILL.de.md`)
+fn is_skill_md_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.starts_with("skill.") && name.ends_with(".md")
+}
+
+/// Match path segments against pattern segments, `**` spanning any number
+fn path_matches(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|i| path_matches(rest, &path[i..])),
+        Some((segment, rest)) => path
+            .split_first()
+            .is_some_and(|(name, tail)| glob_match(segment, name) && path_matches(rest, tail)),
+    }
+}
+
+/// Merge the tools and examples documented in an included file
+///
+/// Included files hold `###` tool sections (optionally under `####` tools
+/// grouped by category) without the `## Tools` heading; a file without
+/// any documents a single tool named after the file. Tools already
+/// documented in SKILL.md keep their documentation.
+fn merge_include(
+    path: &Path,
+    tool_docs: &mut HashMap<String, ToolDocumentation>,
+    examples: &mut Vec<CodeExample>,
+) -> Result<()> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read included file: {}", path.display()))?;
+
+    let has_sections = content
+        .lines()
+        .any(|line| line.trim_start().starts_with("### "));
+    let markdown = if has_sections {
+        format!("## Tools\n\n{}", content)
+    } else {
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        format!("## Tools\n\n### {}\n\n{}", stem, content)
+    };
+
+    for (name, tool) in extract_tool_sections(&markdown) {
+        if tool_docs.contains_key(&name) {
+            tracing::warn!(
+                tool = %name,
+                file = %path.display(),
+                "Tool documented in SKILL.md and an included file; keeping SKILL.md"
+            );
+            continue;
+        }
+        tool_docs.insert(name, tool);
+    }
+    examples.extend(extract_code_examples(&content));
+    Ok(())
+}
+
+/// Apply frontmatter parameter schemas to the documented tools, adding
+/// parameters the markdown doesn't list
+fn apply_parameter_schemas(
+    tool_docs: &mut HashMap<String, ToolDocumentation>,
+    schemas: &HashMap<String, HashMap<String, ParameterSchema>>,
+) {
+    for (tool_name, params) in schemas {
+        let Some(tool) = tool_docs.get_mut(tool_name) else {
+            continue;
+        };
+        let mut names: Vec<&String> = params.keys().collect();
+        names.sort();
+        for name in names {
+            let index = match tool.parameters.iter().position(|p| &p.name == name) {
+                Some(index) => index,
+                None => {
+                    tool.parameters.push(ParameterDoc {
+                        name: name.clone(),
+                        ..Default::default()
+                    });
+                    tool.parameters.len() - 1
+                }
+            };
+            params[name].apply_to(&mut tool.parameters[index]);
+        }
+    }
+}
+
 /// Extract YAML frontmatter from markdown content
 fn extract_frontmatter(content: &str) -> Result<(SkillMdFrontmatter, String)> {
     let content = content.trim();
@@ -380,6 +826,14 @@ fn extract_tool_parameters(markdown: &str, tools: &mut HashMap<String, ToolDocum
             if let Some(params_text) = extract_parameters_section(&tool_section) {
                 tool_doc.parameters = parse_parameters(&params_text);
             }
+            // Look for **Annotations**: line
+            if let Some(annotations) = extract_annotations_line(&tool_section) {
+                tool_doc.hints = ToolHints::parse(&annotations);
+            }
+            // Look for **Requires**: line
+            if let Some(requires) = extract_labeled_line(&tool_section, "**Requires") {
+                tool_doc.requires = Requirement::parse_list(&requires);
+            }
         }
     }
 }
@@ -459,6 +913,26 @@ fn extract_parameters_section(tool_section: &str) -> Option<String> {
     Some(params_lines.join("\n"))
 }
 
+/// Extract the value of an `**Annotations**:` (or `**Hints**:`) line from a tool section
+fn extract_annotations_line(tool_section: &str) -> Option<String> {
+    tool_section.lines().find_map(|line| {
+        let rest = line
+            .trim()
+            .strip_prefix("**Annotations")
+            .or_else(|| line.trim().strip_prefix("**Hints"))?;
+        let value = rest.trim_start_matches([':', '*']).trim();
+        Some(value.to_string())
+    })
+}
+
+/// Extract the value of a `**Label**:` line from a tool section
+fn extract_labeled_line(tool_section: &str, label: &str) -> Option<String> {
+    tool_section.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix(label)?;
+        Some(rest.trim_start_matches([':', '*']).trim().to_string())
+    })
+}
+
 /// Extract all code examples from markdown
 fn extract_code_examples(markdown: &str) -> Vec<CodeExample> {
     let parser = Parser::new(markdown);
@@ -571,6 +1045,8 @@ fn extract_section(markdown: &str, section_name: &str) -> Option<String> {
 /// - `name` (required, integer): description
 /// - `name` (optional, boolean, default: true): description
 /// - `name` (required, enum: value1|value2|value3): description
+/// - `name` (optional, integer, min: 1, max: 10): description
+/// - `name` (required, pattern: `^[a-z0-9-]+$`): description
 pub fn parse_parameters(text: &str) -> Vec<ParameterDoc> {
     let mut params = Vec::new();
 
@@ -604,22 +1080,38 @@ pub fn parse_parameters(text: &str) -> Vec<ParameterDoc> {
             continue;
         };
 
+        // Patterns may hold commas, parentheses and colons, so they're taken
+        // out before the rest is parsed
+        let (pattern, rest) = take_pattern(rest);
+        let rest = rest.as_str();
         let rest_lower = rest.to_lowercase();
 
         // Check for required/optional
         let required = rest_lower.contains("required");
 
-        // Extract type from parentheses content
+        let attributes = rest_lower
+            .strip_prefix('(')
+            .and_then(|s| s.split(')').next())
+            .unwrap_or("");
+
+        // Extract type from parentheses content, ignoring enum values
+        // (`enum: json|yaml` is a string)
         // Patterns: (required), (optional, string), (required, integer), etc.
-        let param_type = if rest_lower.contains("integer") || rest_lower.contains("int)") {
+        let type_text = match attributes.split("enum:").next() {
+            Some(text) if !attributes.is_empty() => text,
+            _ => rest_lower.as_str(),
+        };
+        let param_type = if type_text.contains("integer")
+            || type_text.split(',').any(|part| part.trim() == "int")
+        {
             ParameterType::Integer
-        } else if rest_lower.contains("number") || rest_lower.contains("float") {
+        } else if type_text.contains("number") || type_text.contains("float") {
             ParameterType::Number
-        } else if rest_lower.contains("boolean") || rest_lower.contains("bool") {
+        } else if type_text.contains("boolean") || type_text.contains("bool") {
             ParameterType::Boolean
-        } else if rest_lower.contains("array") || rest_lower.contains("list") {
+        } else if type_text.contains("array") || type_text.contains("list") {
             ParameterType::Array
-        } else if rest_lower.contains("object") || rest_lower.contains("json") {
+        } else if type_text.contains("object") || type_text.contains("json") {
             ParameterType::Object
         } else {
             ParameterType::String
@@ -679,6 +1171,19 @@ pub fn parse_parameters(text: &str) -> Vec<ParameterDoc> {
             rest.to_string()
         };
 
+        // Extract numeric bounds from the parenthesized attributes
+        // Pattern: min: 1, max: 10 (or minimum:/maximum:)
+        let bound = |keys: &[&str]| {
+            keys.iter().find_map(|key| {
+                let pos = attributes.find(key)?;
+                let after = &attributes[pos + key.len()..];
+                let end = after.find(',').unwrap_or(after.len());
+                after[..end].trim().parse::<f64>().ok()
+            })
+        };
+        let minimum = bound(&["minimum:", "min:"]);
+        let maximum = bound(&["maximum:", "max:"]);
+
         params.push(ParameterDoc {
             name,
             required,
@@ -686,12 +1191,177 @@ pub fn parse_parameters(text: &str) -> Vec<ParameterDoc> {
             description,
             default,
             allowed_values,
+            pattern,
+            minimum,
+            maximum,
         });
     }
 
     params
 }
 
+/// Split `pattern: \`...\`` (or an unquoted pattern up to the next `,` or
+/// `)`) out of a parameter line
+fn take_pattern(rest: &str) -> (Option<String>, String) {
+    let Some(start) = rest.to_ascii_lowercase().find("pattern:") else {
+        return (None, rest.to_string());
+    };
+    let value_start = start + "pattern:".len();
+    let after = &rest[value_start..];
+    let trimmed = after.trim_start();
+    let offset = value_start + (after.len() - trimmed.len());
+
+    let (pattern, end) = if let Some(quoted) = trimmed.strip_prefix('`') {
+        match quoted.find('`') {
+            Some(close) => (quoted[..close].to_string(), offset + close + 2),
+            None => return (None, rest.to_string()),
+        }
+    } else {
+        let len = trimmed.find([',', ')']).unwrap_or(trimmed.len());
+        (trimmed[..len].trim().to_string(), offset + len)
+    };
+
+    // Drop the separator before the attribute too: `(required, pattern: ..)`
+    let head = rest[..start].trim_end();
+    let head = head.strip_suffix(',').unwrap_or(head);
+    let tail = &rest[end..];
+    let joined = if head.ends_with('(') {
+        format!("{}{}", head, tail.trim_start().trim_start_matches(',').trim_start())
+    } else {
+        format!("{}{}", head, tail)
+    };
+    (Some(pattern).filter(|p| !p.is_empty()), joined)
+}
+
+/// Environment variable holding the preferred locale for skill descriptions
+pub const LOCALE_ENV: &str = "SKILL_LOCALE";
+
+/// Preferred locale from `SKILL_LOCALE`, normalized (`de_DE.UTF-8` is
+/// `de-DE`); `C` and `POSIX` mean no preference
+pub fn preferred_locale() -> Option<String> {
+    std::env::var(LOCALE_ENV)
+        .ok()
+        .and_then(|locale| normalize_locale(&locale))
+}
+
+fn normalize_locale(locale: &str) -> Option<String> {
+    let locale = locale.trim().split(['.', '@']).next().unwrap_or_default();
+    if locale.is_empty() || locale == "C" || locale == "POSIX" {
+        return None;
+    }
+    Some(locale.replace('_', "-"))
+}
+
+/// Find the `SKILL.<locale>.md` variant for a locale, trying the full
+/// locale (`SKILL.pt-BR.md`) before its language (`SKILL.pt.md`)
+pub fn find_skill_md_variant(skill_dir: &Path, locale: &str) -> Option<PathBuf> {
+    let locale = normalize_locale(locale)?;
+    let language = locale.split('-').next().unwrap_or_default().to_string();
+
+    let mut candidates = vec![locale];
+    if !candidates.contains(&language) {
+        candidates.push(language);
+    }
+    candidates.iter().find_map(|candidate| {
+        [
+            format!("SKILL.{}.md", candidate),
+            format!("skill.{}.md", candidate),
+        ]
+        .into_iter()
+        .map(|name| skill_dir.join(name))
+        .find(|path| path.is_file())
+    })
+}
+
+/// Parse a SKILL.md file with descriptions taken from its variant for
+/// `locale`, falling back to SKILL.md for anything the variant leaves out
+///
+/// Tools, parameters and frontmatter settings always come from SKILL.md;
+/// a variant only translates text. An unreadable variant is skipped with a
+/// warning.
+pub fn parse_skill_md_localized(path: &Path, locale: Option<&str>) -> Result<SkillMdContent> {
+    let mut skill = parse_skill_md(path)?;
+    let Some(locale) = locale.and_then(normalize_locale) else {
+        return Ok(skill);
+    };
+    if skill
+        .frontmatter
+        .lang
+        .as_deref()
+        .is_some_and(|lang| lang.eq_ignore_ascii_case(&locale))
+    {
+        return Ok(skill);
+    }
+
+    let skill_dir = path.parent().unwrap_or(Path::new("."));
+    let Some(variant_path) = find_skill_md_variant(skill_dir, &locale) else {
+        return Ok(skill);
+    };
+    match parse_skill_md(&variant_path) {
+        Ok(variant) => skill.localize(variant, &locale),
+        Err(e) => tracing::warn!(
+            path = %variant_path.display(),
+            error = %e,
+            "Failed to parse localized SKILL.md, using the default"
+        ),
+    }
+    Ok(skill)
+}
+
+impl SkillMdContent {
+    /// Replace descriptions with the ones a localized variant provides
+    fn localize(&mut self, variant: SkillMdContent, locale: &str) {
+        let SkillMdContent {
+            frontmatter,
+            body,
+            mut tool_docs,
+            when_to_use,
+            configuration,
+            ..
+        } = variant;
+
+        if !frontmatter.description.trim().is_empty() {
+            self.frontmatter.description = frontmatter.description;
+        }
+        self.frontmatter.lang = Some(frontmatter.lang.unwrap_or_else(|| locale.to_string()));
+        if !body.trim().is_empty() {
+            self.body = body;
+        }
+        if when_to_use.is_some() {
+            self.when_to_use = when_to_use;
+        }
+        if configuration.is_some() {
+            self.configuration = configuration;
+        }
+
+        for (name, tool) in self.tool_docs.iter_mut() {
+            let Some(translated) = tool_docs.remove(name) else {
+                continue;
+            };
+            if !translated.description.is_empty() {
+                tool.description = translated.description;
+            }
+            if translated.usage.is_some() {
+                tool.usage = translated.usage;
+            }
+            for param in tool.parameters.iter_mut() {
+                if let Some(description) = translated
+                    .parameters
+                    .iter()
+                    .find(|p| p.name == param.name)
+                    .map(|p| p.description.clone())
+                    .filter(|d| !d.is_empty())
+                {
+                    param.description = description;
+                }
+            }
+        }
+        for name in tool_docs.keys() {
+            tracing::debug!(tool = %name, "Localized SKILL.md documents a tool SKILL.md doesn't");
+        }
+    }
+}
+
 /// Find SKILL.md file in a skill directory
 pub fn find_skill_md(skill_dir: &Path) -> Option<std::path::PathBuf> {
     let skill_md = skill_dir.join("SKILL.md");
@@ -856,6 +1526,233 @@ Set up credentials first.
         assert_eq!(params[2].param_type, ParameterType::Integer);
 
         assert_eq!(params[3].name, "format");
+        assert_eq!(params[3].param_type, ParameterType::String);
         assert_eq!(params[3].allowed_values, vec!["json", "yaml", "table"]);
     }
+
+    #[test]
+    fn test_parse_parameter_constraints() {
+        let text = r#"
+**Parameters**:
+- `replicas` (optional, integer, min: 0, max: 50): Desired replica count
+- `name` (required, pattern: `^[a-z0-9]([-a-z0-9]*)?$`): Deployment name
+- `tag` (pattern: ^v[0-9]+$, optional): Image tag
+"#;
+
+        let params = parse_parameters(text);
+        assert_eq!(params[0].param_type, ParameterType::Integer);
+        assert_eq!(params[0].minimum, Some(0.0));
+        assert_eq!(params[0].maximum, Some(50.0));
+        assert_eq!(params[0].description, "Desired replica count");
+
+        assert!(params[1].required);
+        assert_eq!(params[1].pattern.as_deref(), Some("^[a-z0-9]([-a-z0-9]*)?$"));
+        assert_eq!(params[1].description, "Deployment name");
+
+        assert!(!params[2].required);
+        assert_eq!(params[2].pattern.as_deref(), Some("^v[0-9]+$"));
+        assert_eq!(params[2].description, "Image tag");
+    }
+
+    #[test]
+    fn test_frontmatter_parameter_schemas() {
+        let content = r#"---
+name: scaler
+description: Scales deployments
+parameters:
+  scale:
+    replicas: { type: integer, minimum: 0, maximum: 50, default: 1 }
+    strategy: { enum: [rolling, recreate], required: true }
+---
+
+## Tools Provided
+
+### scale
+Scale a deployment.
+
+**Parameters**:
+- `replicas` (optional): Desired replica count
+"#;
+
+        let skill = parse_skill_md_content(content).unwrap();
+        let params = &skill.tool_docs["scale"].parameters;
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0].param_type, ParameterType::Integer);
+        assert_eq!(params[0].maximum, Some(50.0));
+        assert_eq!(params[0].default.as_deref(), Some("1"));
+        assert_eq!(params[0].description, "Desired replica count");
+        assert_eq!(params[1].name, "strategy");
+        assert!(params[1].required);
+        assert_eq!(params[1].allowed_values, vec!["rolling", "recreate"]);
+    }
+
+    #[test]
+    fn test_tool_hints() {
+        let markdown = r#"
+## Tools Provided
+
+### get
+Get resources from the cluster.
+
+### drain
+Drain a node before maintenance.
+
+### apply
+Apply a manifest.
+
+**Annotations**: idempotent, non-destructive, open-world
+"#;
+
+        let tools = extract_tool_sections(markdown);
+        assert_eq!(
+            tools["apply"].hints,
+            ToolHints {
+                read_only: None,
+                destructive: Some(false),
+                idempotent: Some(true),
+                open_world: Some(true),
+            }
+        );
+        assert_eq!(tools["get"].hints, ToolHints::default());
+
+        let get = tools["get"].hints.resolve("get");
+        assert_eq!(get.read_only, Some(true));
+        assert_eq!(get.destructive, Some(false));
+
+        let drain = tools["drain"].hints.resolve("drain");
+        assert_eq!(drain.destructive, Some(true));
+        assert_eq!(ToolHints::infer("delete_bucket").destructive, Some(true));
+        assert_eq!(ToolHints::infer("apply"), ToolHints::default());
+
+        // Declared hints win over the name
+        let declared = ToolHints::parse("read-only").resolve("delete-preview");
+        assert_eq!(declared.read_only, Some(true));
+        assert_eq!(declared.destructive, Some(false));
+    }
+
+    #[test]
+    fn test_includes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("tools/nodes")).unwrap();
+        std::fs::write(
+            dir.path().join("tools/get.md"),
+            "Get resources.\n\n**Parameters**:\n- `resource` (required): Resource type\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("tools/nodes/nodes.md"),
+            "### cordon\nCordon a node.\n\n### drain\nDrain a node.\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("tools/apply.md"), "Apply a manifest.\n").unwrap();
+
+        let content = r#"---
+name: kubernetes
+description: Kubernetes
+include: tools/**/*.md
+parameters:
+  get:
+    resource: { enum: [pods, services] }
+---
+
+## Tools
+
+### apply
+Apply a manifest from SKILL.md.
+"#;
+        let skill = parse_skill_md_in(content, dir.path()).unwrap();
+        let mut names: Vec<&String> = skill.tool_docs.keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["apply", "cordon", "drain", "get"]);
+        assert_eq!(
+            skill.tool_docs["apply"].description,
+            "Apply a manifest from SKILL.md."
+        );
+        let resource = &skill.tool_docs["get"].parameters[0];
+        assert!(resource.required);
+        assert_eq!(resource.allowed_values, vec!["pods", "services"]);
+
+        // Without a skill directory includes aren't resolved
+        let skill = parse_skill_md_content(content).unwrap();
+        assert_eq!(skill.tool_docs.len(), 1);
+
+        let escaping = content.replace("tools/**/*.md", "../shared/*.md");
+        assert!(parse_skill_md_in(&escaping, dir.path()).is_err());
+        let missing = content.replace("tools/**/*.md", "[tools/get.md, tools/missing.md]");
+        assert!(parse_skill_md_in(&missing, dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_localized_variant() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill_md = dir.path().join("SKILL.md");
+        std::fs::write(
+            &skill_md,
+            r#"---
+name: kubernetes
+description: Kubernetes cluster management
+allowed-tools: kubectl
+---
+
+## Tools
+
+### get
+Get resources.
+
+**Parameters**:
+- `resource` (required): Resource type
+- `namespace` (optional): Namespace
+
+### logs
+Print pod logs.
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("SKILL.de.md"),
+            r#"---
+name: kubernetes
+description: Kubernetes-Cluster verwalten
+lang: de
+---
+
+## Tools
+
+### get
+Ressourcen abrufen.
+
+**Parameters**:
+- `resource` (required): Ressourcentyp
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            find_skill_md_variant(dir.path(), "de_AT.UTF-8"),
+            Some(dir.path().join("SKILL.de.md"))
+        );
+        assert_eq!(find_skill_md_variant(dir.path(), "fr"), None);
+
+        let skill = parse_skill_md_localized(&skill_md, Some("de-AT")).unwrap();
+        assert_eq!(
+            skill.frontmatter.description,
+            "Kubernetes-Cluster verwalten"
+        );
+        assert_eq!(skill.frontmatter.lang.as_deref(), Some("de"));
+        assert_eq!(skill.frontmatter.allowed_tools.as_deref(), Some("kubectl"));
+        let get = &skill.tool_docs["get"];
+        assert_eq!(get.description, "Ressourcen abrufen.");
+        assert_eq!(get.parameters[0].description, "Ressourcentyp");
+        assert!(get.parameters[0].required);
+        assert_eq!(get.parameters[1].description, "Namespace");
+        // Untranslated tools fall back to SKILL.md
+        assert_eq!(skill.tool_docs["logs"].description, "Print pod logs.");
+
+        let skill = parse_skill_md_localized(&skill_md, Some("fr")).unwrap();
+        assert_eq!(
+            skill.frontmatter.description,
+            "Kubernetes cluster management"
+        );
+        assert_eq!(skill.frontmatter.lang, None);
+    }
 }
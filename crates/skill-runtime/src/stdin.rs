@@ -0,0 +1,335 @@
+//! Standard input for tool executions.
+//!
+//! Data piped into `skill run` (or sent as `stdin` to the HTTP API) reaches
+//! native commands and Docker containers on their stdin, and WASM skills as
+//! the WASI stdin stream, so tools like `kubectl apply -f -` work unchanged.
+
+use std::io;
+use std::process::{Output, Stdio};
+
+use serde::{Deserialize, Serialize};
+
+use crate::limits::LimitExceeded;
+
+/// Which of a tool's output streams some output came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    /// Standard output
+    Stdout,
+    /// Standard error
+    Stderr,
+}
+
+/// Where a tool's standard input comes from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StdinSource {
+    /// No input; the tool reads end-of-file
+    #[default]
+    Null,
+    /// The current process's stdin, read by the tool as it runs
+    Inherit,
+    /// Fixed data, e.g. from `--stdin-file` or an API request
+    Data(Vec<u8>),
+}
+
+impl StdinSource {
+    /// Whether the tool gets any input.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Run a command to completion with this input, capturing stdout and
+    /// stderr.
+    ///
+    /// With `max_output`, the command is killed as soon as stdout or stderr
+    /// goes over that many bytes; the stream is cut at the limit and stderr
+    /// says why the command stopped.
+    pub async fn output(
+        &self,
+        command: &mut tokio::process::Command,
+        max_output: Option<u64>,
+    ) -> io::Result<Output> {
+        self.output_streaming(command, max_output, |_, _| {}).await
+    }
+
+    /// Like [`StdinSource::output`], but also hands each piece of output to
+    /// `on_output` as the command writes it.
+    ///
+    /// Pieces are raw reads from the pipes, so a multi-byte character may be
+    /// split across two of them.
+    pub async fn output_streaming(
+        &self,
+        command: &mut tokio::process::Command,
+        max_output: Option<u64>,
+        mut on_output: impl FnMut(OutputStream, &[u8]),
+    ) -> io::Result<Output> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        let (stdin, data) = match self {
+            Self::Null => (Stdio::null(), None),
+            Self::Inherit => (Stdio::inherit(), None),
+            Self::Data(data) => (Stdio::piped(), Some(data.clone())),
+        };
+        let mut child = command.stdin(stdin).spawn()?;
+
+        // Write while the child runs so a full stdout pipe can't deadlock it;
+        // dropping the pipe closes the child's stdin
+        let pipe = child.stdin.take();
+        let writer = tokio::spawn(async move {
+            match (pipe, data) {
+                (Some(mut pipe), Some(data)) => pipe.write_all(&data).await,
+                _ => Ok(()),
+            }
+        });
+
+        let mut stdout_pipe = child.stdout.take();
+        let mut stderr_pipe = child.stderr.take();
+        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+        let (mut stdout_buf, mut stderr_buf) = ([0u8; 8192], [0u8; 8192]);
+        let mut exceeded = None;
+        while stdout_pipe.is_some() || stderr_pipe.is_some() {
+            let (stream, read) = tokio::select! {
+                read = async { stdout_pipe.as_mut().unwrap().read(&mut stdout_buf).await },
+                    if stdout_pipe.is_some() => (OutputStream::Stdout, read?),
+                read = async { stderr_pipe.as_mut().unwrap().read(&mut stderr_buf).await },
+                    if stderr_pipe.is_some() => (OutputStream::Stderr, read?),
+            };
+            let (buf, captured) = match stream {
+                OutputStream::Stdout => (&stdout_buf[..read], &mut stdout),
+                OutputStream::Stderr => (&stderr_buf[..read], &mut stderr),
+            };
+            let before = captured.len();
+            let over = capture(captured, buf, max_output);
+            if captured.len() > before {
+                on_output(stream, &captured[before..]);
+            }
+            if read == 0 || over.is_some() {
+                match stream {
+                    OutputStream::Stdout => stdout_pipe = None,
+                    OutputStream::Stderr => stderr_pipe = None,
+                }
+            }
+            if over.is_some() && exceeded.is_none() {
+                exceeded = over;
+                // The command may already have exited
+                let _ = child.start_kill();
+            }
+        }
+
+        let status = child.wait().await?;
+        ignore_broken_pipe(writer.await.map_err(io::Error::other)?)?;
+        if let Some(exceeded) = exceeded {
+            stopped(&mut stderr, exceeded);
+        }
+        Ok(Output {
+            status,
+            stdout,
+            stderr,
+        })
+    }
+
+    /// Blocking variant of [`StdinSource::output`] for `std::process::Command`.
+    pub fn output_blocking(
+        &self,
+        command: &mut std::process::Command,
+        max_output: Option<u64>,
+    ) -> io::Result<Output> {
+        use std::io::Write;
+
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+        let (stdin, data) = match self {
+            Self::Null => (Stdio::null(), None),
+            Self::Inherit => (Stdio::inherit(), None),
+            Self::Data(data) => (Stdio::piped(), Some(data.clone())),
+        };
+        let mut child = command.stdin(stdin).spawn()?;
+
+        let pipe = child.stdin.take();
+        let writer = std::thread::spawn(move || match (pipe, data) {
+            (Some(mut pipe), Some(data)) => pipe.write_all(&data),
+            _ => Ok(()),
+        });
+
+        // Readers report going over the limit on `overflow`; it disconnects
+        // once both have finished
+        let (overflow, overflowed) = std::sync::mpsc::channel();
+        let stdout = child.stdout.take().map(|pipe| {
+            let overflow = overflow.clone();
+            std::thread::spawn(move || read_blocking(pipe, max_output, overflow))
+        });
+        let stderr = child
+            .stderr
+            .take()
+            .map(|pipe| std::thread::spawn(move || read_blocking(pipe, max_output, overflow)));
+        let exceeded = overflowed.recv().ok();
+        if exceeded.is_some() {
+            // The command may already have exited
+            let _ = child.kill();
+        }
+
+        let join = |reader: Option<std::thread::JoinHandle<io::Result<Vec<u8>>>>| match reader {
+            Some(reader) => reader
+                .join()
+                .map_err(|_| io::Error::other("output reader panicked"))?,
+            None => Ok(Vec::new()),
+        };
+        let stdout = join(stdout)?;
+        let mut stderr = join(stderr)?;
+        let status = child.wait()?;
+        ignore_broken_pipe(
+            writer
+                .join()
+                .map_err(|_| io::Error::other("stdin writer panicked"))?,
+        )?;
+        if let Some(exceeded) = exceeded {
+            stopped(&mut stderr, exceeded);
+        }
+        Ok(Output {
+            status,
+            stdout,
+            stderr,
+        })
+    }
+}
+
+/// Append `bytes` to `captured`, keeping at most `limit` bytes in total
+///
+/// Returns the limit that was exceeded if `bytes` didn't fit.
+fn capture(captured: &mut Vec<u8>, bytes: &[u8], limit: Option<u64>) -> Option<LimitExceeded> {
+    let Some(limit) = limit else {
+        captured.extend_from_slice(bytes);
+        return None;
+    };
+    let room = usize::try_from(limit)
+        .unwrap_or(usize::MAX)
+        .saturating_sub(captured.len());
+    if bytes.len() <= room {
+        captured.extend_from_slice(bytes);
+        return None;
+    }
+    captured.extend_from_slice(&bytes[..room]);
+    Some(LimitExceeded::Output { limit })
+}
+
+/// Read a pipe to the end, or until it goes over `limit`
+fn read_blocking(
+    mut pipe: impl io::Read,
+    limit: Option<u64>,
+    overflow: std::sync::mpsc::Sender<LimitExceeded>,
+) -> io::Result<Vec<u8>> {
+    let mut captured = Vec::new();
+    let mut buf = [0u8; 8192];
+    loop {
+        let read = pipe.read(&mut buf)?;
+        if read == 0 {
+            return Ok(captured);
+        }
+        if let Some(exceeded) = capture(&mut captured, &buf[..read], limit) {
+            let _ = overflow.send(exceeded);
+            return Ok(captured);
+        }
+    }
+}
+
+/// Say on stderr why the command was killed
+fn stopped(stderr: &mut Vec<u8>, exceeded: LimitExceeded) {
+    if !stderr.is_empty() && !stderr.ends_with(b"\n") {
+        stderr.push(b'\n');
+    }
+    stderr.extend_from_slice(format!("Execution stopped: {}\n", exceeded).as_bytes());
+}
+
+/// A tool may exit without reading all of its input
+fn ignore_broken_pipe(result: io::Result<()>) -> io::Result<()> {
+    match result {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_output_with_data() {
+        let mut command = tokio::process::Command::new("cat");
+        let output = StdinSource::Data(b"kind: Pod\n".to_vec())
+            .output(&mut command, None)
+            .await
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"kind: Pod\n");
+
+        let mut command = tokio::process::Command::new("cat");
+        let output = StdinSource::Null.output(&mut command, None).await.unwrap();
+        assert!(output.stdout.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_output_streaming_reports_each_stream() {
+        let mut command = tokio::process::Command::new("sh");
+        command.args(["-c", "cat; echo oops >&2"]);
+        let mut seen = Vec::new();
+        let output = StdinSource::Data(b"line\n".to_vec())
+            .output_streaming(&mut command, None, |stream, bytes| {
+                seen.push((stream, bytes.to_vec()))
+            })
+            .await
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"line\n");
+        assert_eq!(output.stderr, b"oops\n");
+
+        let streamed = |wanted| {
+            seen.iter()
+                .filter(|(stream, _)| *stream == wanted)
+                .flat_map(|(_, bytes)| bytes.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(streamed(OutputStream::Stdout), output.stdout);
+        assert_eq!(streamed(OutputStream::Stderr), output.stderr);
+    }
+
+    #[tokio::test]
+    async fn test_output_kills_command_over_limit() {
+        // `yes` never stops on its own
+        let mut command = tokio::process::Command::new("yes");
+        let output = StdinSource::Null.output(&mut command, Some(1000)).await.unwrap();
+        assert!(!output.status.success());
+        assert_eq!(output.stdout.len(), 1000);
+        assert_eq!(
+            String::from_utf8_lossy(&output.stderr),
+            "Execution stopped: output exceeded max_output_size of 1000 bytes\n"
+        );
+
+        let mut command = std::process::Command::new("yes");
+        let output = StdinSource::Null.output_blocking(&mut command, Some(1000)).unwrap();
+        assert!(!output.status.success());
+        assert_eq!(output.stdout.len(), 1000);
+
+        let mut command = tokio::process::Command::new("echo");
+        command.arg("short");
+        let output = StdinSource::Null.output(&mut command, Some(1000)).await.unwrap();
+        assert!(output.status.success());
+        assert!(output.stderr.is_empty());
+    }
+
+    #[test]
+    fn test_output_blocking_ignores_unread_input() {
+        // `true` exits without reading its input
+        let mut command = std::process::Command::new("true");
+        let output = StdinSource::Data(vec![b'x'; 1024 * 1024])
+            .output_blocking(&mut command, None)
+            .unwrap();
+        assert!(output.status.success());
+
+        let mut command = std::process::Command::new("cat");
+        let output = StdinSource::Data(b"hello".to_vec())
+            .output_blocking(&mut command, None)
+            .unwrap();
+        assert_eq!(output.stdout, b"hello");
+    }
+}
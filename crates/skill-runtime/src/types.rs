@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
 /// Metadata about a skill
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,7 +68,10 @@ pub enum ParameterType {
 }
 
 /// Result of tool execution
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// The structured fields after `metadata` are optional so results
+/// serialized before they existed still deserialize.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ExecutionResult {
     /// Whether the execution completed successfully
     pub success: bool,
@@ -76,6 +81,272 @@ pub struct ExecutionResult {
     pub error_message: Option<String>,
     /// Additional metadata about the execution
     pub metadata: Option<HashMap<String, String>>,
+    /// Exit code of the process (native tools)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// Standard error, kept apart from `output`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub stderr: String,
+    /// Wall-clock execution time in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Resources used by the execution, where they can be measured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_usage: Option<ResourceUsage>,
+    /// Whether `output` was cut to fit a size limit
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+impl ExecutionResult {
+    /// Successful result with the given output
+    pub fn success(output: impl Into<String>) -> Self {
+        Self {
+            success: true,
+            output: output.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Failed result with the given error message
+    pub fn failure(error: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            error_message: Some(error.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Result of a finished process: stdout becomes `output`, and stderr
+    /// doubles as the error message when the process failed
+    pub fn from_process_output(output: &std::process::Output) -> Self {
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let success = output.status.success();
+        let error_message = match (success, stderr.is_empty()) {
+            (true, true) => None,
+            (false, true) => Some(format!("Command exited with status: {}", output.status)),
+            _ => Some(stderr.clone()),
+        };
+
+        Self {
+            success,
+            output: stdout,
+            error_message,
+            exit_code: output.status.code(),
+            stderr,
+            ..Default::default()
+        }
+    }
+
+    /// Set the execution time
+    pub fn with_duration(mut self, duration: std::time::Duration) -> Self {
+        self.duration_ms = Some(duration.as_millis() as u64);
+        self
+    }
+
+    /// Set the resources used
+    pub fn with_resource_usage(mut self, usage: Option<ResourceUsage>) -> Self {
+        self.resource_usage = usage;
+        self
+    }
+
+    /// Record the size of `output` and `stderr` in the resource usage
+    ///
+    /// Call before the output is truncated or otherwise shaped.
+    pub fn with_output_metered(mut self) -> Self {
+        let bytes = (self.output.len() + self.stderr.len()) as u64;
+        self.resource_usage = Some(self.resource_usage.unwrap_or_default().with_output_bytes(bytes));
+        self
+    }
+
+    /// The result as a JSON object with stdout and stderr separated, for
+    /// clients that read structured results (MCP `structuredContent`)
+    pub fn structured(&self) -> serde_json::Value {
+        serde_json::json!({
+            "success": self.success,
+            "exit_code": self.exit_code,
+            "stdout": self.output,
+            "stderr": self.stderr,
+            "error": self.error_message,
+            "duration_ms": self.duration_ms,
+            "resource_usage": self.resource_usage,
+            "truncated": self.truncated,
+        })
+    }
+}
+
+/// Resources used by one execution
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    /// User plus system CPU time in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_time_ms: Option<u64>,
+    /// Peak resident memory in kilobytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_memory_kb: Option<u64>,
+    /// Bytes of output (stdout plus stderr) the execution produced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_bytes: Option<u64>,
+}
+
+/// Commands currently inside [`ResourceUsage::measure`]
+static MEASURING: AtomicUsize = AtomicUsize::new(0);
+/// Commands ever started inside [`ResourceUsage::measure`]
+static MEASURED: AtomicU64 = AtomicU64::new(0);
+
+impl ResourceUsage {
+    /// Run a child process to completion and measure what it used
+    ///
+    /// Usage is only attributable when no other measured command overlapped
+    /// this one, so `None` is returned when one did (as well as where the
+    /// platform doesn't report child usage).
+    pub async fn measure<F: Future>(run: F) -> (F::Output, Option<Self>) {
+        let others_running = MEASURING.fetch_add(1, Ordering::SeqCst);
+        let id = MEASURED.fetch_add(1, Ordering::SeqCst) + 1;
+        let before = Self::children();
+
+        let output = run.await;
+
+        let after = Self::children();
+        MEASURING.fetch_sub(1, Ordering::SeqCst);
+        let alone = others_running == 0 && MEASURED.load(Ordering::SeqCst) == id;
+        let usage = after
+            .zip(before)
+            .filter(|_| alone)
+            .map(|(after, before)| after.since(&before));
+        (output, usage)
+    }
+
+    /// Run a future and measure the CPU time spent polling it, in milliseconds
+    ///
+    /// Only time spent on the polling thread counts, which for a WASM guest
+    /// is its own execution; work it hands to other tasks isn't included.
+    /// `None` where the platform doesn't report per-thread CPU time.
+    pub async fn measure_cpu<F: Future>(run: F) -> (F::Output, Option<u64>) {
+        let mut run = std::pin::pin!(run);
+        let mut total = Some(std::time::Duration::ZERO);
+        let output = std::future::poll_fn(|cx| {
+            let before = thread_cpu_time();
+            let poll = run.as_mut().poll(cx);
+            total = match (total, before, thread_cpu_time()) {
+                (Some(total), Some(before), Some(after)) => Some(total + after.saturating_sub(before)),
+                _ => None,
+            };
+            poll
+        })
+        .await;
+        (output, total.map(|cpu| cpu.as_millis() as u64))
+    }
+
+    /// Resources used so far by this process's finished child processes
+    ///
+    /// Take one snapshot before running a command and subtract it with
+    /// [`ResourceUsage::since`] afterwards. Only accurate while one command
+    /// runs at a time; `None` where the platform doesn't report it.
+    pub fn children() -> Option<Self> {
+        #[cfg(target_os = "linux")]
+        {
+            // SAFETY: getrusage only writes into the zeroed struct we pass
+            let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+            if unsafe { libc::getrusage(libc::RUSAGE_CHILDREN, &mut usage) } != 0 {
+                return None;
+            }
+            let millis = |t: libc::timeval| t.tv_sec as u64 * 1000 + t.tv_usec as u64 / 1000;
+            Some(Self {
+                cpu_time_ms: Some(millis(usage.ru_utime) + millis(usage.ru_stime)),
+                // Linux reports ru_maxrss in kilobytes
+                peak_memory_kb: Some(usage.ru_maxrss as u64),
+                output_bytes: None,
+            })
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            None
+        }
+    }
+
+    /// Usage between an earlier snapshot and this one
+    ///
+    /// CPU time is the difference. Peak memory is the high-water mark of
+    /// all children so far, so it's only known to belong to the new command
+    /// when the command raised it.
+    pub fn since(&self, earlier: &Self) -> Self {
+        Self {
+            cpu_time_ms: self
+                .cpu_time_ms
+                .zip(earlier.cpu_time_ms)
+                .map(|(now, then)| now.saturating_sub(then)),
+            peak_memory_kb: self
+                .peak_memory_kb
+                .filter(|now| earlier.peak_memory_kb.map_or(true, |then| *now > then)),
+            output_bytes: self.output_bytes,
+        }
+    }
+
+    /// Set the number of output bytes
+    pub fn with_output_bytes(mut self, bytes: u64) -> Self {
+        self.output_bytes = Some(bytes);
+        self
+    }
+
+    /// Whether nothing was measured
+    pub fn is_empty(&self) -> bool {
+        self.cpu_time_ms.is_none() && self.peak_memory_kb.is_none() && self.output_bytes.is_none()
+    }
+}
+
+impl std::fmt::Display for ResourceUsage {
+    /// `cpu 120ms, peak memory 34.2 MB, output 1.5 KB`, leaving out what
+    /// wasn't measured
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(cpu) = self.cpu_time_ms {
+            parts.push(format!("cpu {}ms", cpu));
+        }
+        if let Some(kb) = self.peak_memory_kb {
+            parts.push(format!("peak memory {}", format_bytes(kb * 1024)));
+        }
+        if let Some(bytes) = self.output_bytes {
+            parts.push(format!("output {}", format_bytes(bytes)));
+        }
+        if parts.is_empty() {
+            return f.write_str("not measured");
+        }
+        f.write_str(&parts.join(", "))
+    }
+}
+
+/// CPU time used by the current thread
+fn thread_cpu_time() -> Option<std::time::Duration> {
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: clock_gettime only writes into the zeroed struct we pass
+        let mut time: libc::timespec = unsafe { std::mem::zeroed() };
+        if unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) } != 0 {
+            return None;
+        }
+        Some(std::time::Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Human-readable byte count (`512 B`, `1.5 KB`, `34.2 MB`)
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
 }
 
 /// Chunk of streaming output
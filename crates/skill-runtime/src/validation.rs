@@ -0,0 +1,671 @@
+//! Linting for skill directories and manifests (`skill validate`)
+//!
+//! Checks are collected into a [`ValidationReport`] rather than failing on the
+//! first problem, so CI can show every issue at once:
+//!
+//! - SKILL.md frontmatter: required `name`/`description`, name format, lengths
+//! - `allowed-tools`: empty, duplicate, or malformed entries
+//! - tool documentation vs the tools the WASM component exports
+//! - manifest TOML: unknown keys, type errors, runtime/source consistency
+//! - dangling references: `default_instance` and `depends_on` targets
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+use crate::dependencies::is_installed;
+use crate::manifest::{SkillManifest, SkillRuntime};
+use crate::skill_md::{parse_skill_md_content, SkillMdContent, ToolDocumentation};
+use crate::types::ToolDefinition;
+
+/// Maximum length of a skill name in SKILL.md
+const MAX_NAME_LEN: usize = 64;
+
+/// Maximum length of a skill description in SKILL.md
+const MAX_DESCRIPTION_LEN: usize = 1024;
+
+const MANIFEST_KEYS: &[&str] = &["version", "defaults", "security", "skills", "templates"];
+
+const SKILL_KEYS: &[&str] = &[
+    "source",
+    "runtime",
+    "ref",
+    "git",
+    "description",
+    "docker",
+    "native",
+    "instances",
+    "default_instance",
+    "services",
+    "tags",
+    "depends_on",
+    "dependencies",
+    "requires",
+];
+
+const INSTANCE_KEYS: &[&str] = &[
+    "config",
+    "env",
+    "capabilities",
+    "description",
+    "template",
+    "vars",
+];
+
+/// Issue severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Fails validation
+    Error,
+    /// Reported, fails only in strict mode
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A single validation finding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationIssue {
+    /// Severity
+    pub severity: Severity,
+    /// Stable identifier for the check (e.g. `frontmatter-name`)
+    pub code: &'static str,
+    /// File, and where useful the element within it (`SKILL.md: tool get`)
+    pub location: String,
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Findings for one validation run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidationReport {
+    /// All issues in the order they were found
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Record an error
+    pub fn error(
+        &mut self,
+        code: &'static str,
+        location: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.push(Severity::Error, code, location.into(), message.into());
+    }
+
+    /// Record a warning
+    pub fn warning(
+        &mut self,
+        code: &'static str,
+        location: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.push(Severity::Warning, code, location.into(), message.into());
+    }
+
+    fn push(&mut self, severity: Severity, code: &'static str, location: String, message: String) {
+        self.issues.push(ValidationIssue {
+            severity,
+            code,
+            location,
+            message,
+        });
+    }
+
+    /// Number of errors
+    pub fn error_count(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    /// Number of warnings
+    pub fn warning_count(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == severity)
+            .count()
+    }
+
+    /// Whether validation fails (errors, or any issue in strict mode)
+    pub fn is_failure(&self, strict: bool) -> bool {
+        self.error_count() > 0 || (strict && !self.issues.is_empty())
+    }
+}
+
+/// Validate SKILL.md content; returns the parsed document if it parses
+pub fn validate_skill_md(
+    content: &str,
+    location: &str,
+    report: &mut ValidationReport,
+) -> Option<SkillMdContent> {
+    if !content.trim_start().starts_with("---") {
+        report.error(
+            "frontmatter-missing",
+            location,
+            "SKILL.md must start with YAML frontmatter (--- name/description ---)",
+        );
+    }
+
+    let skill = match parse_skill_md_content(content) {
+        Ok(skill) => skill,
+        Err(e) => {
+            report.error("frontmatter-invalid", location, format!("{:#}", e));
+            return None;
+        }
+    };
+
+    let name = &skill.frontmatter.name;
+    if name.is_empty() {
+        report.error("frontmatter-name", location, "`name` is required");
+    } else if name.len() > MAX_NAME_LEN
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        || name.starts_with('-')
+        || name.ends_with('-')
+    {
+        report.error(
+            "frontmatter-name",
+            location,
+            format!(
+                "`name` '{}' must be lowercase letters, digits and hyphens (max {} chars)",
+                name, MAX_NAME_LEN
+            ),
+        );
+    }
+
+    let description = skill.frontmatter.description.trim();
+    if description.is_empty() {
+        report.error(
+            "frontmatter-description",
+            location,
+            "`description` is required; it is what agents use to discover the skill",
+        );
+    } else if description.len() > MAX_DESCRIPTION_LEN {
+        report.warning(
+            "frontmatter-description",
+            location,
+            format!(
+                "`description` is {} chars; keep it under {}",
+                description.len(),
+                MAX_DESCRIPTION_LEN
+            ),
+        );
+    }
+
+    if let Some(allowed) = &skill.frontmatter.allowed_tools {
+        validate_allowed_tools(allowed, location, report);
+    }
+
+    let mut tools: Vec<&ToolDocumentation> = skill.tool_docs.values().collect();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    for tool in tools {
+        let tool_location = format!("{}: tool {}", location, tool.name);
+        if tool.description.trim().is_empty() {
+            report.warning(
+                "tool-description",
+                &tool_location,
+                "Tool has no description",
+            );
+        }
+        let mut seen = HashSet::new();
+        for param in &tool.parameters {
+            if !seen.insert(param.name.as_str()) {
+                report.error(
+                    "tool-parameter-duplicate",
+                    &tool_location,
+                    format!("Parameter `{}` is documented more than once", param.name),
+                );
+            }
+        }
+    }
+
+    Some(skill)
+}
+
+/// Check the `allowed-tools` list (`Read, Bash(git:*), skill-run`)
+fn validate_allowed_tools(allowed: &str, location: &str, report: &mut ValidationReport) {
+    let mut seen = HashSet::new();
+    for entry in split_allowed_tools(allowed) {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            report.warning(
+                "allowed-tools",
+                location,
+                "`allowed-tools` contains an empty entry",
+            );
+            continue;
+        }
+        if entry == "*" {
+            report.warning(
+                "allowed-tools",
+                location,
+                "`allowed-tools: *` grants every tool; list the tools the skill needs",
+            );
+        } else if !is_valid_tool_pattern(entry) {
+            report.error(
+                "allowed-tools",
+                location,
+                format!("Malformed `allowed-tools` entry '{}'", entry),
+            );
+        }
+        if !seen.insert(entry.to_string()) {
+            report.warning(
+                "allowed-tools",
+                location,
+                format!("`allowed-tools` lists '{}' more than once", entry),
+            );
+        }
+    }
+}
+
+/// Split on commas outside parentheses (`Bash(git add, git commit)` is one entry)
+fn split_allowed_tools(allowed: &str) -> Vec<&str> {
+    let mut entries = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in allowed.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                entries.push(&allowed[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    entries.push(&allowed[start..]);
+    entries
+}
+
+/// `Name` or `Name(argument pattern)`
+fn is_valid_tool_pattern(entry: &str) -> bool {
+    let (name, rest) = match entry.find('(') {
+        Some(pos) => (&entry[..pos], Some(&entry[pos..])),
+        None => (entry, None),
+    };
+    let name_ok = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'));
+    let args_ok = rest.map_or(true, |r| {
+        r.len() > 2 && r.ends_with(')') && r[1..r.len() - 1].chars().all(|c| c != '(' && c != ')')
+    });
+    name_ok && args_ok
+}
+
+/// Compare documented tools against the tools the WASM component exports
+pub fn validate_tool_exports(
+    docs: &HashMap<String, ToolDocumentation>,
+    exported: &[ToolDefinition],
+    location: &str,
+    report: &mut ValidationReport,
+) {
+    let exported_by_name: HashMap<&str, &ToolDefinition> =
+        exported.iter().map(|t| (t.name.as_str(), t)).collect();
+
+    let mut documented: Vec<&String> = docs.keys().collect();
+    documented.sort();
+    for name in documented {
+        let doc = &docs[name];
+        let tool_location = format!("{}: tool {}", location, name);
+        let Some(tool) = exported_by_name.get(name.as_str()) else {
+            report.error(
+                "tool-not-exported",
+                &tool_location,
+                "Documented tool is not exported by the WASM component",
+            );
+            continue;
+        };
+
+        for param in &doc.parameters {
+            match tool.parameters.iter().find(|p| p.name == param.name) {
+                None => report.warning(
+                    "parameter-not-exported",
+                    &tool_location,
+                    format!(
+                        "Documented parameter `{}` is not accepted by the tool",
+                        param.name
+                    ),
+                ),
+                Some(exported) if exported.required != param.required => report.warning(
+                    "parameter-required-mismatch",
+                    &tool_location,
+                    format!(
+                        "Parameter `{}` is documented as {} but the tool declares it {}",
+                        param.name,
+                        if param.required {
+                            "required"
+                        } else {
+                            "optional"
+                        },
+                        if exported.required {
+                            "required"
+                        } else {
+                            "optional"
+                        },
+                    ),
+                ),
+                Some(_) => {}
+            }
+        }
+        for param in tool.parameters.iter().filter(|p| p.required) {
+            if !doc.parameters.iter().any(|p| p.name == param.name) {
+                report.warning(
+                    "parameter-undocumented",
+                    &tool_location,
+                    format!("Required parameter `{}` is not documented", param.name),
+                );
+            }
+        }
+    }
+
+    let mut undocumented: Vec<&str> = exported
+        .iter()
+        .map(|t| t.name.as_str())
+        .filter(|name| !docs.contains_key(*name))
+        .collect();
+    undocumented.sort();
+    for name in undocumented {
+        report.warning(
+            "tool-undocumented",
+            format!("{}: tool {}", location, name),
+            "Exported tool has no section in SKILL.md",
+        );
+    }
+}
+
+/// Validate a manifest; returns the parsed manifest if it parses
+///
+/// `base_dir` resolves relative local sources.
+pub fn validate_manifest(
+    content: &str,
+    location: &str,
+    base_dir: &Path,
+    report: &mut ValidationReport,
+) -> Option<SkillManifest> {
+    let value: toml::Value = match toml::from_str(content) {
+        Ok(value) => value,
+        Err(e) => {
+            report.error("manifest-toml", location, e.to_string());
+            return None;
+        }
+    };
+    check_unknown_keys(&value, location, report);
+
+    let mut manifest = match SkillManifest::parse(content) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            report.error("manifest-schema", location, format!("{:#}", e));
+            return None;
+        }
+    };
+    manifest.base_dir = base_dir.to_path_buf();
+
+    let mut names: Vec<&String> = manifest.skills.keys().collect();
+    names.sort();
+    for name in names {
+        let skill = &manifest.skills[name];
+        let skill_location = format!("{}: skills.{}", location, name);
+
+        let is_local = skill.source.starts_with("./")
+            || skill.source.starts_with("../")
+            || skill.source.starts_with('/');
+        match skill.runtime {
+            SkillRuntime::Docker if skill.docker.is_none() => report.error(
+                "runtime-docker",
+                &skill_location,
+                "runtime = \"docker\" requires a [skills.<name>.docker] section",
+            ),
+            SkillRuntime::Docker => {}
+            _ if skill.source.trim().is_empty() => {
+                report.error("source-missing", &skill_location, "`source` is required")
+            }
+            _ if is_local && !base_dir.join(&skill.source).exists() => report.error(
+                "source-not-found",
+                &skill_location,
+                format!("Local source '{}' does not exist", skill.source),
+            ),
+            _ => {}
+        }
+
+        if !skill.instances.is_empty() && !skill.instances.contains_key(&skill.default_instance) {
+            let mut instances: Vec<&str> = skill.instances.keys().map(|s| s.as_str()).collect();
+            instances.sort();
+            report.error(
+                "default-instance",
+                &skill_location,
+                format!(
+                    "default_instance '{}' is not defined (instances: {})",
+                    skill.default_instance,
+                    instances.join(", ")
+                ),
+            );
+        }
+
+        for dep in &skill.depends_on {
+            if dep.name() == name {
+                report.error(
+                    "dependency-self",
+                    &skill_location,
+                    "Skill depends on itself",
+                );
+            } else if !manifest.skills.contains_key(dep.name())
+                && dep.source().is_none()
+                && !is_installed(dep.name())
+            {
+                report.error(
+                    "dependency-missing",
+                    &skill_location,
+                    format!(
+                        "Depends on '{}', which is not in the manifest, not installed, and has no source",
+                        dep.name()
+                    ),
+                );
+            }
+        }
+        if let Err(e) = manifest.resolve_dependencies(name) {
+            if e.to_string().starts_with("Dependency cycle") {
+                report.error("dependency-cycle", &skill_location, e.to_string());
+            }
+        }
+    }
+
+    Some(manifest)
+}
+
+fn check_unknown_keys(value: &toml::Value, location: &str, report: &mut ValidationReport) {
+    let Some(table) = value.as_table() else {
+        return;
+    };
+    for key in table.keys() {
+        if !MANIFEST_KEYS.contains(&key.as_str()) {
+            report.warning(
+                "unknown-key",
+                location,
+                format!("Unknown top-level key `{}`", key),
+            );
+        }
+    }
+
+    let Some(skills) = table.get("skills").and_then(|s| s.as_table()) else {
+        return;
+    };
+    for (name, skill) in skills {
+        let Some(skill) = skill.as_table() else {
+            continue;
+        };
+        let skill_location = format!("{}: skills.{}", location, name);
+        for key in skill.keys() {
+            if !SKILL_KEYS.contains(&key.as_str()) {
+                report.warning(
+                    "unknown-key",
+                    &skill_location,
+                    format!("Unknown key `{}`", key),
+                );
+            }
+        }
+
+        let Some(instances) = skill.get("instances").and_then(|i| i.as_table()) else {
+            continue;
+        };
+        for (instance, def) in instances {
+            let Some(def) = def.as_table() else {
+                continue;
+            };
+            for key in def.keys() {
+                if !INSTANCE_KEYS.contains(&key.as_str()) {
+                    report.warning(
+                        "unknown-key",
+                        format!("{}.instances.{}", skill_location, instance),
+                        format!("Unknown key `{}`", key),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Parameter, ParameterType};
+
+    fn codes(report: &ValidationReport) -> Vec<&'static str> {
+        report.issues.iter().map(|i| i.code).collect()
+    }
+
+    #[test]
+    fn test_validate_skill_md_frontmatter() {
+        let mut report = ValidationReport::default();
+        validate_skill_md(
+            "---\nname: My_Skill\ndescription: \"\"\nallowed-tools: Read,, Bash(git:*), Read, Bad(\n---\n# Skill\n",
+            "SKILL.md",
+            &mut report,
+        );
+        assert_eq!(
+            codes(&report),
+            vec![
+                "frontmatter-name",
+                "frontmatter-description",
+                "allowed-tools",
+                "allowed-tools",
+                "allowed-tools"
+            ]
+        );
+        assert_eq!(report.error_count(), 3);
+        assert!(report.is_failure(false));
+
+        let mut report = ValidationReport::default();
+        validate_skill_md(
+            "---\nname: kubernetes\ndescription: Manage clusters\nallowed-tools: Bash(kubectl get, kubectl describe), Read\n---\n",
+            "SKILL.md",
+            &mut report,
+        );
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn test_validate_tool_exports() {
+        let skill = parse_skill_md_content(
+            "---\nname: k8s\ndescription: d\n---\n## Tools\n\n### get\nGet resources\n\n**Parameters**:\n- `resource` (required): Resource type\n\n### delete\nDelete resources\n",
+        )
+        .unwrap();
+        let exported = vec![
+            ToolDefinition {
+                name: "get".to_string(),
+                description: String::new(),
+                parameters: vec![Parameter {
+                    name: "resource".to_string(),
+                    param_type: ParameterType::String,
+                    description: String::new(),
+                    required: false,
+                    default_value: None,
+                }],
+                streaming: false,
+            },
+            ToolDefinition {
+                name: "logs".to_string(),
+                description: String::new(),
+                parameters: vec![],
+                streaming: false,
+            },
+        ];
+
+        let mut report = ValidationReport::default();
+        validate_tool_exports(&skill.tool_docs, &exported, "SKILL.md", &mut report);
+        assert_eq!(
+            codes(&report),
+            vec![
+                "tool-not-exported",
+                "parameter-required-mismatch",
+                "tool-undocumented"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_manifest_references() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let content = r#"
+version = "1"
+colour = "blue"
+
+[skills.deploy]
+source = "./missing"
+default_instance = "prod"
+depends_on = ["helm", "nowhere-skill-xyz"]
+timeout = 3
+
+[skills.deploy.instances.dev]
+config = { region = "us-east-1" }
+
+[skills.helm]
+source = "github:org/helm"
+depends_on = ["deploy"]
+
+[skills.box]
+runtime = "docker"
+source = "docker:alpine"
+"#;
+        let mut report = ValidationReport::default();
+        assert!(validate_manifest(content, "m.toml", dir.path(), &mut report).is_some());
+        assert_eq!(
+            codes(&report),
+            vec![
+                "unknown-key",
+                "unknown-key",
+                "runtime-docker",
+                "source-not-found",
+                "default-instance",
+                "dependency-missing",
+                "dependency-cycle",
+                "dependency-cycle",
+            ]
+        );
+
+        let mut report = ValidationReport::default();
+        assert!(validate_manifest(
+            "[skills.x]\nsource = 1\n",
+            "m.toml",
+            dir.path(),
+            &mut report
+        )
+        .is_none());
+        assert_eq!(codes(&report), vec!["manifest-schema"]);
+    }
+}
@@ -427,6 +427,11 @@ impl VectorStore for FileVectorStore {
         let data = self.data.read().unwrap();
         data.metadata.dimensions
     }
+
+    async fn export(&self) -> Result<Vec<EmbeddedDocument>> {
+        let data = self.data.read().unwrap();
+        Ok(data.documents.values().cloned().collect())
+    }
 }
 
 /// Configuration for file-based vector store
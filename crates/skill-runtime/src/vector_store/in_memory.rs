@@ -249,6 +249,10 @@ impl VectorStore for InMemoryVectorStore {
     fn dimensions(&self) -> Option<usize> {
         self.dimensions
     }
+
+    async fn export(&self) -> Result<Vec<EmbeddedDocument>> {
+        Ok(self.documents.read().unwrap().values().cloned().collect())
+    }
 }
 
 #[cfg(test)]
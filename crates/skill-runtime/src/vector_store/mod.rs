@@ -99,6 +99,13 @@ pub trait VectorStore: Send + Sync {
     fn dimensions(&self) -> Option<usize> {
         None
     }
+
+    /// Every stored document with its embedding, for index snapshots
+    ///
+    /// Backends that can't list their contents return an error.
+    async fn export(&self) -> Result<Vec<EmbeddedDocument>> {
+        anyhow::bail!("The {} vector store does not support snapshots", self.backend_name())
+    }
 }
 
 /// Compute cosine similarity between two vectors
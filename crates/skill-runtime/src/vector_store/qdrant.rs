@@ -212,6 +212,9 @@ impl QdrantVectorStore {
         if let Some(ref category) = filter.category {
             conditions.push(Self::make_keyword_condition("category", category));
         }
+        if let Some(ref runtime) = filter.runtime {
+            conditions.push(Self::make_keyword_condition("runtime", runtime));
+        }
 
         // Tag conditions (all must match)
         for tag in &filter.tags {
@@ -263,6 +266,9 @@ impl QdrantVectorStore {
         if let Some(ref category) = metadata.category {
             payload.insert("category".to_string(), Self::string_value(category));
         }
+        if let Some(ref runtime) = metadata.runtime {
+            payload.insert("runtime".to_string(), Self::string_value(runtime));
+        }
 
         if !metadata.tags.is_empty() {
             let tags_list: Vec<qdrant_client::qdrant::Value> = metadata
@@ -326,6 +332,11 @@ impl QdrantVectorStore {
                         metadata.category = Some(s.clone());
                     }
                 }
+                "runtime" => {
+                    if let Some(qdrant_client::qdrant::value::Kind::StringValue(s)) = &value.kind {
+                        metadata.runtime = Some(s.clone());
+                    }
+                }
                 "tags" => {
                     if let Some(qdrant_client::qdrant::value::Kind::ListValue(list)) = &value.kind {
                         metadata.tags = list
@@ -683,6 +694,7 @@ mod tests {
             instance_name: Some("default".to_string()),
             tool_name: Some("hello".to_string()),
             category: Some("testing".to_string()),
+            runtime: None,
             tags: vec!["tag1".to_string(), "tag2".to_string()],
             custom: HashMap::new(),
         };
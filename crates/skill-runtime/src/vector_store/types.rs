@@ -110,6 +110,10 @@ pub struct DocumentMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub category: Option<String>,
 
+    /// Runtime type of the skill (e.g., "wasm", "docker", "native")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<String>,
+
     /// Tags for additional classification
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
@@ -138,6 +142,10 @@ pub struct Filter {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub category: Option<String>,
 
+    /// Filter by runtime type (exact match, e.g. "native")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<String>,
+
     /// Filter by tags (document must have ALL specified tags)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
@@ -181,6 +189,12 @@ impl Filter {
         self
     }
 
+    /// Filter by runtime type
+    pub fn runtime(mut self, runtime: impl Into<String>) -> Self {
+        self.runtime = Some(runtime.into());
+        self
+    }
+
     /// Filter by tags (must have all)
     pub fn tags(mut self, tags: Vec<String>) -> Self {
         self.tags = tags;
@@ -229,6 +243,13 @@ impl Filter {
             }
         }
 
+        // Check runtime
+        if let Some(ref runtime) = self.runtime {
+            if metadata.runtime.as_ref() != Some(runtime) {
+                return false;
+            }
+        }
+
         // Check tags (all must be present)
         for tag in &self.tags {
             if !metadata.tags.contains(tag) {
@@ -252,6 +273,7 @@ impl Filter {
             && self.instance_name.is_none()
             && self.tool_name.is_none()
             && self.category.is_none()
+            && self.runtime.is_none()
             && self.tags.is_empty()
             && self.custom.is_empty()
             && self.min_score.is_none()
@@ -460,6 +482,7 @@ mod tests {
             instance_name: Some("production".to_string()),
             tool_name: Some("get_pods".to_string()),
             category: Some("infrastructure".to_string()),
+            runtime: Some("native".to_string()),
             tags: vec!["k8s".to_string(), "devops".to_string()],
             custom: HashMap::new(),
         };
@@ -485,6 +508,10 @@ mod tests {
         assert!(!Filter::new()
             .tags(vec!["missing".to_string()])
             .matches(&metadata));
+
+        // Runtime filter
+        assert!(Filter::new().runtime("native").matches(&metadata));
+        assert!(!Filter::new().runtime("wasm").matches(&metadata));
     }
 
     #[test]
@@ -492,6 +519,7 @@ mod tests {
         assert!(Filter::new().is_empty());
         assert!(!Filter::new().skill("test").is_empty());
         assert!(!Filter::new().min_score(0.5).is_empty());
+        assert!(!Filter::new().runtime("docker").is_empty());
     }
 
     #[test]
@@ -0,0 +1,248 @@
+//! Hot reload of installed skills and the manifest.
+//!
+//! [`SkillWatcher`] watches the skill registry (`~/.skill-engine/registry`)
+//! and the manifest files a server loaded (every layer of a layered
+//! manifest). Filesystem events are debounced and collapsed into
+//! [`SkillChanged`] events that long-running servers (the HTTP API and the
+//! MCP server) use to refresh their skills, tools and search index without a
+//! restart.
+
+use anyhow::{Context, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+
+/// Quiet period after the last filesystem event before changes are emitted
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Buffered change events per subscriber
+const CHANNEL_CAPACITY: usize = 64;
+
+/// A change to the installed skills or the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkillChanged {
+    /// A skill was installed or its files changed
+    Updated(String),
+    /// A skill directory was removed from the registry
+    Removed(String),
+    /// A manifest file changed; any manifest skill may be affected
+    Manifest(PathBuf),
+    /// Events were missed; reload everything
+    Rescan,
+}
+
+impl SkillChanged {
+    /// Name of the affected skill, for per-skill changes.
+    pub fn skill_name(&self) -> Option<&str> {
+        match self {
+            Self::Updated(name) | Self::Removed(name) => Some(name),
+            Self::Manifest(_) | Self::Rescan => None,
+        }
+    }
+}
+
+/// Receives [`SkillChanged`] events from a [`SkillWatcher`].
+pub struct SkillChanges {
+    rx: broadcast::Receiver<SkillChanged>,
+}
+
+impl SkillChanges {
+    /// Next change, or `None` once the watcher is dropped.
+    ///
+    /// A subscriber that falls behind gets [`SkillChanged::Rescan`] in place
+    /// of the events it missed.
+    pub async fn recv(&mut self) -> Option<SkillChanged> {
+        match self.rx.recv().await {
+            Ok(change) => Some(change),
+            Err(broadcast::error::RecvError::Lagged(_)) => Some(SkillChanged::Rescan),
+            Err(broadcast::error::RecvError::Closed) => None,
+        }
+    }
+}
+
+/// Watches the skill registry and manifest for changes.
+///
+/// Events stop when the watcher is dropped, so keep it alive for as long as
+/// subscribers need updates. Must be created inside a Tokio runtime.
+pub struct SkillWatcher {
+    _watcher: RecommendedWatcher,
+    sender: broadcast::Sender<SkillChanged>,
+}
+
+impl SkillWatcher {
+    /// Default registry location (`~/.skill-engine/registry`).
+    pub fn default_registry_dir() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Failed to get home directory")?;
+        Ok(home.join(".skill-engine").join("registry"))
+    }
+
+    /// Watch the default registry and the given manifest files.
+    pub fn new(manifest_paths: &[PathBuf]) -> Result<Self> {
+        Self::watch(&Self::default_registry_dir()?, manifest_paths)
+    }
+
+    /// Watch a registry directory and the given manifest files.
+    pub fn watch(registry_dir: &Path, manifest_paths: &[PathBuf]) -> Result<Self> {
+        std::fs::create_dir_all(registry_dir)
+            .with_context(|| format!("Failed to create {}", registry_dir.display()))?;
+        let paths = WatchedPaths {
+            registry_dir: registry_dir
+                .canonicalize()
+                .unwrap_or_else(|_| registry_dir.to_path_buf()),
+            manifest_paths: manifest_paths
+                .iter()
+                .map(|path| path.canonicalize().unwrap_or_else(|_| path.clone()))
+                .collect(),
+        };
+
+        let (raw_tx, raw_rx) = mpsc::unbounded_channel();
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+                Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+                    for path in event.paths {
+                        let _ = raw_tx.send(path);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!(error = %e, "Skill watcher error"),
+            })
+            .context("Failed to create skill watcher")?;
+
+        watcher
+            .watch(&paths.registry_dir, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", paths.registry_dir.display()))?;
+        // Watch manifest directories: editors save by replacing the file
+        let mut manifest_dirs: Vec<&Path> = paths
+            .manifest_paths
+            .iter()
+            .filter_map(|path| path.parent())
+            .collect();
+        manifest_dirs.sort();
+        manifest_dirs.dedup();
+        for dir in manifest_dirs {
+            watcher
+                .watch(dir, RecursiveMode::NonRecursive)
+                .with_context(|| format!("Failed to watch {}", dir.display()))?;
+        }
+
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        tokio::spawn(debounce(raw_rx, sender.clone(), paths));
+
+        Ok(Self {
+            _watcher: watcher,
+            sender,
+        })
+    }
+
+    /// Subscribe to change events.
+    pub fn subscribe(&self) -> SkillChanges {
+        SkillChanges {
+            rx: self.sender.subscribe(),
+        }
+    }
+}
+
+struct WatchedPaths {
+    registry_dir: PathBuf,
+    manifest_paths: Vec<PathBuf>,
+}
+
+impl WatchedPaths {
+    /// Map a changed path to the change it represents.
+    fn classify(&self, path: &Path) -> Option<SkillChanged> {
+        if self.manifest_paths.iter().any(|manifest| manifest == path) {
+            return Some(SkillChanged::Manifest(path.to_path_buf()));
+        }
+
+        let relative = path.strip_prefix(&self.registry_dir).ok()?;
+        let Some(Component::Normal(name)) = relative.components().next() else {
+            return None;
+        };
+        let name = name.to_str()?;
+        if name.starts_with('.') {
+            return None;
+        }
+        if self.registry_dir.join(name).is_dir() {
+            Some(SkillChanged::Updated(name.to_string()))
+        } else {
+            Some(SkillChanged::Removed(name.to_string()))
+        }
+    }
+}
+
+/// Collapse bursts of filesystem events (an install writes many files) into
+/// one event per skill.
+async fn debounce(
+    mut raw: mpsc::UnboundedReceiver<PathBuf>,
+    sender: broadcast::Sender<SkillChanged>,
+    paths: WatchedPaths,
+) {
+    while let Some(first) = raw.recv().await {
+        let mut changed = vec![first];
+        while let Ok(Some(path)) = tokio::time::timeout(DEBOUNCE, raw.recv()).await {
+            changed.push(path);
+        }
+
+        let mut changes: Vec<SkillChanged> = Vec::new();
+        for change in changed.iter().filter_map(|path| paths.classify(path)) {
+            if !changes.contains(&change) {
+                changes.push(change);
+            }
+        }
+        for change in changes {
+            tracing::debug!(change = ?change, "Skill change detected");
+            // No subscribers is fine; nobody is interested yet
+            let _ = sender.send(change);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry_dir = dir.path().join("registry");
+        std::fs::create_dir_all(registry_dir.join("kubernetes")).unwrap();
+        let manifest_path = dir.path().join(".skill-engine.toml");
+        let paths = WatchedPaths {
+            registry_dir: registry_dir.clone(),
+            manifest_paths: vec![manifest_path.clone()],
+        };
+
+        assert_eq!(
+            paths.classify(&registry_dir.join("kubernetes").join("SKILL.md")),
+            Some(SkillChanged::Updated("kubernetes".to_string()))
+        );
+        assert_eq!(
+            paths.classify(&registry_dir.join("aws")),
+            Some(SkillChanged::Removed("aws".to_string()))
+        );
+        assert_eq!(
+            paths.classify(&manifest_path),
+            Some(SkillChanged::Manifest(manifest_path.clone()))
+        );
+        assert_eq!(paths.classify(&registry_dir.join(".tmp-install")), None);
+        assert_eq!(paths.classify(&dir.path().join("other.toml")), None);
+        assert_eq!(paths.classify(&registry_dir), None);
+    }
+
+    #[tokio::test]
+    async fn test_watcher_emits_skill_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let watcher = SkillWatcher::watch(dir.path(), &[]).unwrap();
+        let mut changes = watcher.subscribe();
+
+        let skill_dir = dir.path().join("hello");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(skill_dir.join("SKILL.md"), "# Hello").unwrap();
+
+        let change = tokio::time::timeout(Duration::from_secs(10), changes.recv())
+            .await
+            .expect("no change event");
+        assert_eq!(change, Some(SkillChanged::Updated("hello".to_string())));
+    }
+}
@@ -0,0 +1,1178 @@
+//! Workflows - chaining several tools into one pipeline.
+//!
+//! A workflow file (YAML, or TOML with a `.toml` extension) declares steps
+//! that each call one skill tool. Arguments can reference the workflow's
+//! inputs and earlier steps with `${{ <jq filter> }}` templates, steps run
+//! only when their `when` filter is truthy, failed steps are retried, and
+//! `outputs` binds parts of a step's JSON output for later steps:
+//!
+//! ```yaml
+//! name: deploy
+//! inputs:
+//!   namespace:
+//!     default: staging
+//! steps:
+//!   - id: apply
+//!     tool: kubernetes:apply
+//!     args: { file: deploy.yaml, namespace: "${{ .inputs.namespace }}" }
+//!     retries: 2
+//!   - id: pods
+//!     tool: kubernetes:get
+//!     args: { resource: pods, namespace: "${{ .inputs.namespace }}", output: json }
+//!     outputs:
+//!       first_pod: .items[0].metadata.name
+//!   - id: logs
+//!     tool: kubernetes:logs
+//!     when: .steps.pods.outputs.first_pod != null
+//!     args: { pod: "${{ .steps.pods.outputs.first_pod }}" }
+//! ```
+//!
+//! Templates and conditions see
+//! `{"inputs": {...}, "steps": {"<id>": {"status", "output", "outputs"}}}`.
+//! A template that makes up a whole argument keeps the value's JSON type;
+//! inside a longer string it is interpolated as text.
+//!
+//! Workflows are found by name in `./workflows` and
+//! `~/.skill-engine/workflows`, and run with `skill workflow run` or the MCP
+//! `run_workflow` tool.
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::jq::{run_jq, validate_jq};
+use crate::types::ExecutionResult;
+
+/// File extensions recognised as workflow files
+const WORKFLOW_EXTENSIONS: &[&str] = &["yaml", "yml", "toml"];
+
+/// A workflow definition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Workflow {
+    /// Workflow name; defaults to the file name
+    #[serde(default)]
+    pub name: String,
+    /// What the workflow does
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Inputs the workflow accepts, by name
+    #[serde(default)]
+    pub inputs: BTreeMap<String, WorkflowInput>,
+    /// Steps, run in order
+    pub steps: Vec<WorkflowStep>,
+}
+
+/// An input a workflow accepts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowInput {
+    /// What the input is for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON type of the input; defaults to the type of `default`
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub input_type: Option<InputType>,
+    /// Value used when the input isn't given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    /// Whether the input must be given (ignored with a default)
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl WorkflowInput {
+    /// Declared type, or the type of the default value.
+    pub fn effective_type(&self) -> Option<InputType> {
+        self.input_type
+            .or_else(|| self.default.as_ref().and_then(InputType::of))
+    }
+
+    /// Whether the input has to be given.
+    pub fn is_required(&self) -> bool {
+        self.required && self.default.is_none()
+    }
+}
+
+/// JSON type of a workflow input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InputType {
+    /// Text
+    String,
+    /// Any number
+    Number,
+    /// Whole number
+    Integer,
+    /// `true` or `false`
+    Boolean,
+    /// JSON object
+    Object,
+    /// JSON array
+    Array,
+}
+
+impl InputType {
+    /// Type of a JSON value; `None` for null.
+    pub fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => None,
+            Value::String(_) => Some(Self::String),
+            Value::Number(n) if n.is_f64() => Some(Self::Number),
+            Value::Number(_) => Some(Self::Integer),
+            Value::Bool(_) => Some(Self::Boolean),
+            Value::Object(_) => Some(Self::Object),
+            Value::Array(_) => Some(Self::Array),
+        }
+    }
+
+    /// Whether `value` has this type.
+    pub fn matches(self, value: &Value) -> bool {
+        match self {
+            Self::String => value.is_string(),
+            Self::Number => value.is_number(),
+            Self::Integer => value.is_i64() || value.is_u64(),
+            Self::Boolean => value.is_boolean(),
+            Self::Object => value.is_object(),
+            Self::Array => value.is_array(),
+        }
+    }
+
+    /// JSON Schema `type` keyword.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Integer => "integer",
+            Self::Boolean => "boolean",
+            Self::Object => "object",
+            Self::Array => "array",
+        }
+    }
+}
+
+/// A single tool call in a workflow.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowStep {
+    /// Unique step ID, used to reference the step's results
+    pub id: String,
+    /// Tool to call: `skill:tool` or `skill@instance:tool`
+    pub tool: String,
+    /// Tool arguments; string values may contain `${{ }}` templates
+    #[serde(default)]
+    pub args: BTreeMap<String, Value>,
+    /// jq condition; the step is skipped unless it is truthy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    /// Extra attempts after a failure
+    #[serde(default)]
+    pub retries: u32,
+    /// Seconds to wait between attempts
+    #[serde(default = "default_retry_delay_secs")]
+    pub retry_delay_secs: u64,
+    /// jq filters over the step's JSON output, bound as `.steps.<id>.outputs.<name>`
+    #[serde(default)]
+    pub outputs: BTreeMap<String, String>,
+    /// Keep running later steps if this one fails
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+fn default_retry_delay_secs() -> u64 {
+    1
+}
+
+impl WorkflowStep {
+    /// Split [`WorkflowStep::tool`] into skill, instance and tool names.
+    pub fn target(&self) -> Result<(&str, &str, &str)> {
+        let (skill, tool) = self
+            .tool
+            .rsplit_once(':')
+            .filter(|(skill, tool)| !skill.is_empty() && !tool.is_empty())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Step '{}': tool must be 'skill:tool' or 'skill@instance:tool', got '{}'",
+                    self.id,
+                    self.tool
+                )
+            })?;
+        let (skill, instance) = skill.split_once('@').unwrap_or((skill, "default"));
+        Ok((skill, instance, tool))
+    }
+
+    /// IDs of the steps whose results this step uses (`.steps.<id>` in its
+    /// condition or argument templates).
+    pub fn depends_on(&self) -> BTreeSet<String> {
+        let mut filters: Vec<&str> = self.when.iter().map(String::as_str).collect();
+        for value in self.args.values() {
+            collect_templates(value, &mut filters);
+        }
+
+        let mut ids = BTreeSet::new();
+        for filter in filters {
+            for (start, _) in filter.match_indices(".steps.") {
+                let id: String = filter[start + ".steps.".len()..]
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+                    .collect();
+                if !id.is_empty() {
+                    ids.insert(id);
+                }
+            }
+        }
+        ids
+    }
+}
+
+impl Workflow {
+    /// Parse a YAML workflow.
+    pub fn from_yaml(content: &str) -> Result<Self> {
+        let workflow: Self = serde_yaml::from_str(content).context("Invalid workflow YAML")?;
+        workflow.validate()?;
+        Ok(workflow)
+    }
+
+    /// Parse a TOML workflow.
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let workflow: Self = toml::from_str(content).context("Invalid workflow TOML")?;
+        workflow.validate()?;
+        Ok(workflow)
+    }
+
+    /// Load a workflow file, TOML if it has a `.toml` extension and YAML
+    /// otherwise.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read workflow {}", path.display()))?;
+        let mut workflow: Self = if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::from_str(&content).context("Invalid workflow TOML")?
+        } else {
+            serde_yaml::from_str(&content).context("Invalid workflow YAML")?
+        };
+        if workflow.name.is_empty() {
+            workflow.name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+        }
+        workflow
+            .validate()
+            .with_context(|| format!("Invalid workflow {}", path.display()))?;
+        Ok(workflow)
+    }
+
+    /// Check step IDs, tool references and every jq filter.
+    pub fn validate(&self) -> Result<()> {
+        if self.steps.is_empty() {
+            bail!("Workflow has no steps");
+        }
+        for (name, input) in &self.inputs {
+            if let (Some(input_type), Some(default)) = (input.input_type, &input.default) {
+                if !input_type.matches(default) {
+                    bail!("Input '{}': default is not a {}", name, input_type.as_str());
+                }
+            }
+        }
+        let mut ids = HashSet::new();
+        for step in &self.steps {
+            if step.id.is_empty() {
+                bail!("Every step needs an id");
+            }
+            if !ids.insert(step.id.as_str()) {
+                bail!("Duplicate step id '{}'", step.id);
+            }
+            step.target()?;
+            if let Some(dependency) = step
+                .depends_on()
+                .into_iter()
+                .find(|id| *id == step.id || !ids.contains(id.as_str()))
+            {
+                bail!(
+                    "Step '{}' uses step '{}', which doesn't run before it",
+                    step.id,
+                    dependency
+                );
+            }
+            if let Some(when) = &step.when {
+                validate_jq(when).with_context(|| format!("Step '{}': invalid when", step.id))?;
+            }
+            for (name, filter) in &step.outputs {
+                validate_jq(filter)
+                    .with_context(|| format!("Step '{}': invalid output '{}'", step.id, name))?;
+            }
+            for value in step.args.values() {
+                validate_templates(value)
+                    .with_context(|| format!("Step '{}': invalid argument", step.id))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply defaults to `inputs` and check required and unknown inputs.
+    pub fn resolve_inputs(&self, mut inputs: Map<String, Value>) -> Result<Map<String, Value>> {
+        if let Some(unknown) = inputs.keys().find(|name| !self.inputs.contains_key(*name)) {
+            bail!("Workflow '{}' has no input '{}'", self.name, unknown);
+        }
+        for (name, input) in &self.inputs {
+            if let Some(value) = inputs.get(name) {
+                match input.effective_type() {
+                    Some(input_type) if !input_type.matches(value) => bail!(
+                        "Workflow '{}': input '{}' must be a {}",
+                        self.name,
+                        name,
+                        input_type.as_str()
+                    ),
+                    _ => continue,
+                }
+            }
+            match &input.default {
+                Some(default) => {
+                    inputs.insert(name.clone(), default.clone());
+                }
+                None if input.required => {
+                    bail!("Workflow '{}' requires input '{}'", self.name, name)
+                }
+                None => {}
+            }
+        }
+        Ok(inputs)
+    }
+
+    /// Parse a command-line input value: kept as text for string inputs,
+    /// otherwise parsed as JSON when it is valid JSON.
+    pub fn parse_input(&self, name: &str, raw: &str) -> Value {
+        let input_type = self
+            .inputs
+            .get(name)
+            .and_then(WorkflowInput::effective_type);
+        if input_type == Some(InputType::String) {
+            return Value::String(raw.to_string());
+        }
+        serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+    }
+
+    /// JSON Schema for the workflow's inputs, e.g. for an MCP tool.
+    pub fn input_schema(&self) -> Map<String, Value> {
+        let mut properties = Map::new();
+        for (name, input) in &self.inputs {
+            let mut property = Map::new();
+            if let Some(input_type) = input.effective_type() {
+                property.insert("type".to_string(), input_type.as_str().into());
+            }
+            if let Some(description) = &input.description {
+                property.insert("description".to_string(), description.clone().into());
+            }
+            if let Some(default) = &input.default {
+                property.insert("default".to_string(), default.clone());
+            }
+            properties.insert(name.clone(), Value::Object(property));
+        }
+        let required: Vec<&str> = self
+            .inputs
+            .iter()
+            .filter(|(_, input)| input.is_required())
+            .map(|(name, _)| name.as_str())
+            .collect();
+
+        let mut schema = Map::new();
+        schema.insert("type".to_string(), "object".into());
+        schema.insert("properties".to_string(), Value::Object(properties));
+        schema.insert("required".to_string(), required.into());
+        schema.insert("additionalProperties".to_string(), false.into());
+        schema
+    }
+
+    /// Run every step in order.
+    ///
+    /// Only invalid inputs are errors; step failures are recorded in the
+    /// returned run. `observer` is told about each step as it starts and
+    /// finishes.
+    pub async fn run(
+        &self,
+        inputs: Map<String, Value>,
+        executor: &dyn StepExecutor,
+        observer: Option<&dyn RunObserver>,
+    ) -> Result<WorkflowRun> {
+        let inputs = self.resolve_inputs(inputs)?;
+        let mut run = WorkflowRun {
+            workflow: self.name.clone(),
+            status: RunStatus::Running,
+            inputs,
+            steps: self
+                .steps
+                .iter()
+                .map(|step| StepRun {
+                    id: step.id.clone(),
+                    tool: step.tool.clone(),
+                    status: RunStatus::Pending,
+                    attempts: 0,
+                    output: None,
+                    error: None,
+                    outputs: BTreeMap::new(),
+                    duration_ms: 0,
+                })
+                .collect(),
+            started_at: Utc::now(),
+            finished_at: None,
+        };
+        notify(observer, &run).await;
+
+        let mut aborted = false;
+        for (index, step) in self.steps.iter().enumerate() {
+            if aborted {
+                run.steps[index].status = RunStatus::Skipped;
+                continue;
+            }
+
+            let context = run.context();
+            let should_run = match &step.when {
+                Some(when) => evaluate(when, &context).map(|value| truthy(&value)),
+                None => Ok(true),
+            };
+            let args = render_args(&step.args, &context);
+            let (should_run, args) = match (should_run, args) {
+                (Ok(should_run), Ok(args)) => (should_run, args),
+                (Err(e), _) | (_, Err(e)) => {
+                    run.steps[index].status = RunStatus::Failed;
+                    run.steps[index].error = Some(format!("{:#}", e));
+                    aborted = !step.continue_on_error;
+                    notify(observer, &run).await;
+                    continue;
+                }
+            };
+            if !should_run {
+                run.steps[index].status = RunStatus::Skipped;
+                notify(observer, &run).await;
+                continue;
+            }
+
+            run.steps[index].status = RunStatus::Running;
+            notify(observer, &run).await;
+
+            let started = Instant::now();
+            let result = run_step(step, args, executor, &mut run.steps[index].attempts).await;
+            let step_run = &mut run.steps[index];
+            step_run.duration_ms = started.elapsed().as_millis() as u64;
+            match result {
+                Ok((output, outputs)) => {
+                    step_run.status = RunStatus::Succeeded;
+                    step_run.output = Some(output);
+                    step_run.outputs = outputs;
+                }
+                Err((output, error)) => {
+                    step_run.status = RunStatus::Failed;
+                    step_run.output = output;
+                    step_run.error = Some(error);
+                    aborted = !step.continue_on_error;
+                }
+            }
+            notify(observer, &run).await;
+        }
+
+        run.status = if aborted {
+            RunStatus::Failed
+        } else {
+            RunStatus::Succeeded
+        };
+        run.finished_at = Some(Utc::now());
+        notify(observer, &run).await;
+        Ok(run)
+    }
+}
+
+/// Calls the tools a workflow's steps name.
+#[async_trait]
+pub trait StepExecutor: Send + Sync {
+    /// Execute `skill@instance:tool` with rendered arguments.
+    async fn execute(
+        &self,
+        skill: &str,
+        instance: &str,
+        tool: &str,
+        args: HashMap<String, Value>,
+    ) -> Result<ExecutionResult>;
+}
+
+/// Receives a workflow run's state whenever a step changes status.
+#[async_trait]
+pub trait RunObserver: Send + Sync {
+    /// Called with the whole run after every change.
+    async fn on_update(&self, run: &WorkflowRun);
+}
+
+/// Status of a workflow run or one of its steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunStatus {
+    /// Not started yet
+    Pending,
+    /// In progress
+    Running,
+    /// Finished successfully
+    Succeeded,
+    /// Failed after all attempts
+    Failed,
+    /// Not run: its condition was false or an earlier step failed
+    Skipped,
+}
+
+impl std::fmt::Display for RunStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pending => write!(f, "pending"),
+            Self::Running => write!(f, "running"),
+            Self::Succeeded => write!(f, "succeeded"),
+            Self::Failed => write!(f, "failed"),
+            Self::Skipped => write!(f, "skipped"),
+        }
+    }
+}
+
+/// State of a workflow run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowRun {
+    /// Workflow name
+    pub workflow: String,
+    /// Overall status
+    pub status: RunStatus,
+    /// Inputs after defaults were applied
+    pub inputs: Map<String, Value>,
+    /// Per-step state, in workflow order
+    pub steps: Vec<StepRun>,
+    /// When the run started
+    pub started_at: DateTime<Utc>,
+    /// When the run finished
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// State of one step in a workflow run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepRun {
+    /// Step ID
+    pub id: String,
+    /// Tool the step calls
+    pub tool: String,
+    /// Step status
+    pub status: RunStatus,
+    /// Attempts made
+    pub attempts: u32,
+    /// Output of the last attempt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// Why the step failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Values bound from the output
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub outputs: BTreeMap<String, Value>,
+    /// Time spent on all attempts
+    pub duration_ms: u64,
+}
+
+impl WorkflowRun {
+    /// The first failed step, if any.
+    pub fn failed_step(&self) -> Option<&StepRun> {
+        self.steps
+            .iter()
+            .find(|step| step.status == RunStatus::Failed)
+    }
+
+    /// Value templates and conditions are evaluated against.
+    fn context(&self) -> Value {
+        let steps: Map<String, Value> = self
+            .steps
+            .iter()
+            .map(|step| {
+                let state = serde_json::json!({
+                    "status": step.status,
+                    "output": step.output,
+                    "outputs": step.outputs,
+                });
+                (step.id.clone(), state)
+            })
+            .collect();
+        serde_json::json!({ "inputs": self.inputs, "steps": steps })
+    }
+}
+
+/// Run a step's attempts, returning its output and bound values, or the last
+/// output and error.
+async fn run_step(
+    step: &WorkflowStep,
+    args: HashMap<String, Value>,
+    executor: &dyn StepExecutor,
+    attempts: &mut u32,
+) -> std::result::Result<(String, BTreeMap<String, Value>), (Option<String>, String)> {
+    let (skill, instance, tool) = step.target().map_err(|e| (None, e.to_string()))?;
+    loop {
+        *attempts += 1;
+        let (output, error) = match executor.execute(skill, instance, tool, args.clone()).await {
+            Ok(result) if result.success => {
+                return match bind_outputs(&step.outputs, &result.output) {
+                    Ok(outputs) => Ok((result.output, outputs)),
+                    Err(e) => Err((Some(result.output), format!("{:#}", e))),
+                };
+            }
+            Ok(result) => {
+                let error = result
+                    .error_message
+                    .unwrap_or_else(|| "Tool reported failure".to_string());
+                (Some(result.output), error)
+            }
+            Err(e) => (None, format!("{:#}", e)),
+        };
+
+        if *attempts > step.retries {
+            return Err((output, error));
+        }
+        tracing::warn!(
+            step = %step.id,
+            attempt = *attempts,
+            error = %error,
+            "Workflow step failed, retrying"
+        );
+        tokio::time::sleep(Duration::from_secs(step.retry_delay_secs)).await;
+    }
+}
+
+fn bind_outputs(
+    filters: &BTreeMap<String, String>,
+    output: &str,
+) -> Result<BTreeMap<String, Value>> {
+    if filters.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    let json: Value =
+        serde_json::from_str(output).context("Output is not JSON; cannot bind outputs")?;
+    filters
+        .iter()
+        .map(|(name, filter)| Ok((name.clone(), evaluate(filter, &json)?)))
+        .collect()
+}
+
+async fn notify(observer: Option<&dyn RunObserver>, run: &WorkflowRun) {
+    if let Some(observer) = observer {
+        observer.on_update(run).await;
+    }
+}
+
+/// First result of a jq filter, or null if it produces none.
+fn evaluate(filter: &str, input: &Value) -> Result<Value> {
+    Ok(run_jq(input.clone(), filter)?
+        .into_iter()
+        .next()
+        .unwrap_or(Value::Null))
+}
+
+fn truthy(value: &Value) -> bool {
+    !matches!(value, Value::Null | Value::Bool(false))
+}
+
+fn render_args(args: &BTreeMap<String, Value>, context: &Value) -> Result<HashMap<String, Value>> {
+    args.iter()
+        .map(|(name, value)| {
+            let value = render(value, context)
+                .with_context(|| format!("Failed to render argument '{}'", name))?;
+            Ok((name.clone(), value))
+        })
+        .collect()
+}
+
+/// Substitute `${{ }}` templates in strings, recursively.
+fn render(value: &Value, context: &Value) -> Result<Value> {
+    match value {
+        Value::String(text) => render_string(text, context),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| render(item, context))
+            .collect::<Result<_>>()
+            .map(Value::Array),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, item)| Ok((key.clone(), render(item, context)?)))
+            .collect::<Result<_>>()
+            .map(Value::Object),
+        other => Ok(other.clone()),
+    }
+}
+
+fn render_string(text: &str, context: &Value) -> Result<Value> {
+    let parts = split_templates(text)?;
+    if let [Part::Template(filter)] = parts.as_slice() {
+        return evaluate(filter, context);
+    }
+
+    let mut rendered = String::new();
+    for part in parts {
+        match part {
+            Part::Text(text) => rendered.push_str(text),
+            Part::Template(filter) => match evaluate(filter, context)? {
+                Value::String(s) => rendered.push_str(&s),
+                Value::Null => {}
+                other => rendered.push_str(&other.to_string()),
+            },
+        }
+    }
+    Ok(Value::String(rendered))
+}
+
+fn validate_templates(value: &Value) -> Result<()> {
+    match value {
+        Value::String(text) => split_templates(text)?
+            .into_iter()
+            .try_for_each(|part| match part {
+                Part::Template(filter) => validate_jq(filter),
+                Part::Text(_) => Ok(()),
+            }),
+        Value::Array(items) => items.iter().try_for_each(validate_templates),
+        Value::Object(fields) => fields.values().try_for_each(validate_templates),
+        _ => Ok(()),
+    }
+}
+
+/// Template filters in a value's strings; malformed templates are ignored
+fn collect_templates<'a>(value: &'a Value, filters: &mut Vec<&'a str>) {
+    match value {
+        Value::String(text) => {
+            for part in split_templates(text).unwrap_or_default() {
+                if let Part::Template(filter) = part {
+                    filters.push(filter);
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_templates(item, filters)),
+        Value::Object(fields) => fields
+            .values()
+            .for_each(|item| collect_templates(item, filters)),
+        _ => {}
+    }
+}
+
+enum Part<'a> {
+    Text(&'a str),
+    Template(&'a str),
+}
+
+/// Split text into literal parts and `${{ filter }}` templates.
+fn split_templates(mut text: &str) -> Result<Vec<Part<'_>>> {
+    let mut parts = Vec::new();
+    while let Some(start) = text.find("${{") {
+        if start > 0 {
+            parts.push(Part::Text(&text[..start]));
+        }
+        let rest = &text[start + 3..];
+        let end = rest
+            .find("}}")
+            .ok_or_else(|| anyhow!("Unclosed '${{{{' in '{}'", text))?;
+        let filter = rest[..end].trim();
+        if filter.is_empty() {
+            bail!("Empty '${{{{ }}}}' template");
+        }
+        parts.push(Part::Template(filter));
+        text = &rest[end + 2..];
+    }
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+    Ok(parts)
+}
+
+/// Directories searched for workflows by name, in order.
+pub fn workflow_dirs() -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from("workflows")];
+    if let Some(home) = dirs::home_dir() {
+        paths.push(home.join(".skill-engine").join("workflows"));
+    }
+    paths
+}
+
+/// Find a workflow file from a path or a name in [`workflow_dirs`].
+pub fn resolve_workflow(name_or_path: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(name_or_path);
+    if path.is_file() {
+        return Ok(path);
+    }
+    for dir in workflow_dirs() {
+        for ext in WORKFLOW_EXTENSIONS {
+            let candidate = dir.join(format!("{}.{}", name_or_path, ext));
+            if candidate.is_file() {
+                return Ok(candidate);
+            }
+        }
+    }
+    bail!(
+        "Workflow '{}' not found (looked in ./workflows and ~/.skill-engine/workflows)",
+        name_or_path
+    )
+}
+
+/// Every valid workflow in [`workflow_dirs`], with its path. Earlier
+/// directories shadow later ones; invalid files are skipped with a warning.
+pub fn discover_workflows() -> Vec<(PathBuf, Workflow)> {
+    discover_workflows_in(&workflow_dirs())
+}
+
+/// Every valid workflow in `dirs`, earlier directories shadowing later ones
+pub fn discover_workflows_in(dirs: &[PathBuf]) -> Vec<(PathBuf, Workflow)> {
+    let mut found: Vec<(PathBuf, Workflow)> = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| WORKFLOW_EXTENSIONS.iter().any(|e| ext == *e))
+            })
+            .collect();
+        paths.sort();
+        for path in paths {
+            match Workflow::load(&path) {
+                Ok(workflow) if !found.iter().any(|(_, w)| w.name == workflow.name) => {
+                    found.push((path, workflow))
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Skipping workflow {}: {:#}", path.display(), e),
+            }
+        }
+    }
+    found
+}
+
+#[cfg(feature = "job-queue")]
+pub use recorder::JobRecorder;
+
+#[cfg(feature = "job-queue")]
+mod recorder {
+    use super::{RunObserver, RunStatus, StepExecutor, Workflow, WorkflowRun};
+    use crate::jobs::{
+        create_storage, Job, JobConfig, JobId, JobStatus, JobStorage, StorageResult,
+    };
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use serde_json::{Map, Value};
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    /// Records a workflow run as a job, with per-step status in the job's
+    /// result.
+    pub struct JobRecorder {
+        storage: Arc<dyn JobStorage>,
+        job: Mutex<Job>,
+        id: JobId,
+    }
+
+    impl JobRecorder {
+        /// Add a running job for a run of `workflow`.
+        pub async fn start(
+            storage: Arc<dyn JobStorage>,
+            workflow: &Workflow,
+            inputs: &Map<String, Value>,
+        ) -> StorageResult<Self> {
+            let mut job =
+                Job::workflow(&workflow.name, Value::Object(inputs.clone())).with_max_attempts(1);
+            // The caller runs the workflow; keep queue workers from picking it up
+            job.status = JobStatus::Running;
+            job.attempts = 1;
+            job.started_at = Some(Utc::now());
+            let id = storage.enqueue(job.clone()).await?;
+            storage.update(&job).await?;
+            Ok(Self {
+                storage,
+                job: Mutex::new(job),
+                id,
+            })
+        }
+
+        /// ID of the job recording the run.
+        pub fn job_id(&self) -> JobId {
+            self.id
+        }
+    }
+
+    impl Workflow {
+        /// [`Workflow::run`], recorded in the default job queue.
+        ///
+        /// Returns the run and the ID of the job recording it; recording is
+        /// best-effort, so the run goes ahead if the queue is unavailable.
+        /// `observer` also gets every update.
+        pub async fn run_recorded(
+            &self,
+            inputs: Map<String, Value>,
+            executor: &dyn StepExecutor,
+            observer: Option<&dyn RunObserver>,
+        ) -> Result<(WorkflowRun, Option<JobId>)> {
+            let inputs = self.resolve_inputs(inputs)?;
+            let recorder = match create_storage(&JobConfig::sqlite_default()).await {
+                Ok(storage) => JobRecorder::start(storage, self, &inputs).await,
+                Err(e) => Err(e),
+            };
+            let recorder = recorder
+                .map_err(|e| tracing::warn!("Not recording workflow run: {}", e))
+                .ok();
+
+            let observers = Observers(recorder.as_ref(), observer);
+            let run = self.run(inputs, executor, Some(&observers)).await?;
+            Ok((run, recorder.map(|r| r.job_id())))
+        }
+    }
+
+    /// Forwards updates to the job recorder and the caller's observer
+    struct Observers<'a>(Option<&'a JobRecorder>, Option<&'a dyn RunObserver>);
+
+    #[async_trait]
+    impl RunObserver for Observers<'_> {
+        async fn on_update(&self, run: &WorkflowRun) {
+            if let Some(recorder) = self.0 {
+                recorder.on_update(run).await;
+            }
+            if let Some(observer) = self.1 {
+                observer.on_update(run).await;
+            }
+        }
+    }
+
+    #[async_trait]
+    impl RunObserver for JobRecorder {
+        async fn on_update(&self, run: &WorkflowRun) {
+            let mut job = self.job.lock().await;
+            job.result = serde_json::to_value(run).ok();
+            match run.status {
+                RunStatus::Succeeded => {
+                    job.status = JobStatus::Completed;
+                    job.completed_at = run.finished_at;
+                }
+                RunStatus::Failed => {
+                    job.status = JobStatus::Failed;
+                    job.completed_at = run.finished_at;
+                    job.error = run.failed_step().map(|step| {
+                        format!(
+                            "Step '{}' failed: {}",
+                            step.id,
+                            step.error.as_deref().unwrap_or("unknown error")
+                        )
+                    });
+                }
+                _ => {}
+            }
+            if let Err(e) = self.storage.update(&job).await {
+                tracing::warn!(job_id = %self.id, "Failed to record workflow progress: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    /// Replays canned results and records each call's arguments
+    struct FakeExecutor {
+        results: Mutex<Vec<ExecutionResult>>,
+        calls: Mutex<Vec<(String, HashMap<String, Value>)>>,
+    }
+
+    impl FakeExecutor {
+        fn new(outputs: &[(bool, &str)]) -> Self {
+            Self {
+                results: Mutex::new(
+                    outputs
+                        .iter()
+                        .rev()
+                        .map(|(success, output)| ExecutionResult {
+                            success: *success,
+                            output: output.to_string(),
+                            error_message: (!success).then(|| "boom".to_string()),
+                            ..Default::default()
+                        })
+                        .collect(),
+                ),
+                calls: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl StepExecutor for FakeExecutor {
+        async fn execute(
+            &self,
+            skill: &str,
+            instance: &str,
+            tool: &str,
+            args: HashMap<String, Value>,
+        ) -> Result<ExecutionResult> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((format!("{}@{}:{}", skill, instance, tool), args));
+            self.results
+                .lock()
+                .unwrap()
+                .pop()
+                .ok_or_else(|| anyhow!("unexpected call"))
+        }
+    }
+
+    const DEPLOY: &str = r#"
+name: deploy
+inputs:
+  namespace:
+    default: staging
+  replicas:
+    required: true
+steps:
+  - id: apply
+    tool: kubernetes:apply
+    args:
+      namespace: "${{ .inputs.namespace }}"
+      replicas: "${{ .inputs.replicas }}"
+      label: "env=${{ .inputs.namespace }}"
+  - id: pods
+    tool: kubernetes@prod:get
+    retries: 1
+    retry_delay_secs: 0
+    outputs:
+      first: .items[0].name
+  - id: logs
+    tool: kubernetes:logs
+    when: .steps.pods.outputs.first != null
+    args:
+      pod: "${{ .steps.pods.outputs.first }}"
+  - id: cleanup
+    tool: kubernetes:delete
+    when: .inputs.namespace == "dev"
+"#;
+
+    #[tokio::test]
+    async fn test_run_binds_outputs_and_retries() {
+        let workflow = Workflow::from_yaml(DEPLOY).unwrap();
+        let executor = FakeExecutor::new(&[
+            (true, "applied"),
+            (false, ""),
+            (true, r#"{"items": [{"name": "web-1"}]}"#),
+            (true, "log line"),
+        ]);
+        let inputs = json!({ "replicas": 3 }).as_object().unwrap().clone();
+
+        let run = workflow.run(inputs, &executor, None).await.unwrap();
+        assert_eq!(run.status, RunStatus::Succeeded);
+        let statuses: Vec<_> = run
+            .steps
+            .iter()
+            .map(|s| (s.id.as_str(), s.status, s.attempts))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("apply", RunStatus::Succeeded, 1),
+                ("pods", RunStatus::Succeeded, 2),
+                ("logs", RunStatus::Succeeded, 1),
+                ("cleanup", RunStatus::Skipped, 0),
+            ]
+        );
+        assert_eq!(run.steps[1].outputs["first"], json!("web-1"));
+
+        let calls = executor.calls.lock().unwrap();
+        assert_eq!(calls[0].0, "kubernetes@default:apply");
+        assert_eq!(calls[0].1["namespace"], json!("staging"));
+        assert_eq!(calls[0].1["replicas"], json!(3));
+        assert_eq!(calls[0].1["label"], json!("env=staging"));
+        assert_eq!(calls[1].0, "kubernetes@prod:get");
+        assert_eq!(calls[3].1["pod"], json!("web-1"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_step_skips_the_rest() {
+        let workflow = Workflow::from_yaml(DEPLOY).unwrap();
+        let executor = FakeExecutor::new(&[(false, "error: forbidden")]);
+        let inputs = json!({ "replicas": 1 }).as_object().unwrap().clone();
+
+        let run = workflow.run(inputs, &executor, None).await.unwrap();
+        assert_eq!(run.status, RunStatus::Failed);
+        assert_eq!(run.failed_step().unwrap().id, "apply");
+        assert_eq!(run.steps[0].error.as_deref(), Some("boom"));
+        assert!(run.steps[1..]
+            .iter()
+            .all(|s| s.status == RunStatus::Skipped));
+    }
+
+    #[test]
+    fn test_typed_inputs() {
+        let workflow = Workflow::from_yaml(
+            r#"
+name: scale
+inputs:
+  deployment: { type: string, required: true, description: Deployment name }
+  replicas: { default: 2 }
+steps:
+  - { id: scale, tool: "kubernetes:scale" }
+"#,
+        )
+        .unwrap();
+
+        let schema = Value::Object(workflow.input_schema());
+        assert_eq!(schema["properties"]["deployment"]["type"], "string");
+        assert_eq!(schema["properties"]["replicas"]["type"], "integer");
+        assert_eq!(schema["properties"]["replicas"]["default"], 2);
+        assert_eq!(schema["required"], json!(["deployment"]));
+
+        assert_eq!(workflow.parse_input("deployment", "007"), json!("007"));
+        assert_eq!(workflow.parse_input("replicas", "3"), json!(3));
+        let wrong = json!({ "deployment": 7 }).as_object().unwrap().clone();
+        assert!(workflow.resolve_inputs(wrong).is_err());
+
+        let bad_default =
+            "inputs: { n: { type: integer, default: x } }\nsteps: [{id: a, tool: 'k:get'}]";
+        assert!(Workflow::from_yaml(bad_default).is_err());
+    }
+
+    #[test]
+    fn test_inputs_and_validation() {
+        let workflow = Workflow::from_yaml(DEPLOY).unwrap();
+        assert!(workflow.steps[0].depends_on().is_empty());
+        assert_eq!(
+            workflow.steps[2].depends_on(),
+            BTreeSet::from(["pods".to_string()])
+        );
+        assert!(workflow.resolve_inputs(Map::new()).is_err());
+        let unknown = json!({ "replicas": 1, "zone": "a" })
+            .as_object()
+            .unwrap()
+            .clone();
+        assert!(workflow.resolve_inputs(unknown).is_err());
+
+        let invalid = [
+            "steps: []",
+            "steps: [{id: a, tool: kubernetes}]",
+            "steps: [{id: a, tool: 'k:get'}, {id: a, tool: 'k:get'}]",
+            "steps: [{id: a, tool: 'k:get', when: '.['}]",
+            "steps: [{id: a, tool: 'k:get', args: {x: '${{ .inputs'}}]",
+            "steps: [{id: a, tool: 'k:get', when: '.steps.b.status == \"failed\"'}, {id: b, tool: 'k:get'}]",
+        ];
+        for yaml in invalid {
+            assert!(Workflow::from_yaml(yaml).is_err(), "{}", yaml);
+        }
+
+        let toml = r#"
+            name = "check"
+            [[steps]]
+            id = "pods"
+            tool = "kubernetes:get"
+            args = { resource = "pods" }
+        "#;
+        assert_eq!(
+            Workflow::from_toml(toml).unwrap().steps[0].args["resource"],
+            json!("pods")
+        );
+    }
+}
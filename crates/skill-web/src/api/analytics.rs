@@ -1,6 +1,6 @@
 //! Analytics API client
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 use super::{ApiClient, ApiResult};
@@ -52,6 +52,31 @@ impl AnalyticsApi {
             ))
             .await
     }
+
+    /// Get generated example quality over time
+    pub async fn get_example_quality(&self, days: u32) -> ApiResult<ExampleQualityResponse> {
+        self.client
+            .get(&format!("/analytics/example-quality?days={}", days))
+            .await
+    }
+
+    /// Get execution count, success rate and latency per skill
+    pub async fn get_skill_usage(&self, days: u32) -> ApiResult<SkillUsageResponse> {
+        self.client
+            .get(&format!("/analytics/skills?days={}", days))
+            .await
+    }
+
+    /// Get usage of one skill, per tool and per day
+    pub async fn get_skill_analytics(
+        &self,
+        skill: &str,
+        days: u32,
+    ) -> ApiResult<SkillAnalyticsResponse> {
+        self.client
+            .get(&format!("/analytics/skills/{}?days={}", skill, days))
+            .await
+    }
 }
 
 // Response types
@@ -122,3 +147,67 @@ pub struct TimelineDataPoint {
     pub search_count: usize,
     pub avg_latency_ms: f64,
 }
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExampleQualityResponse {
+    pub evaluations: Vec<ExampleQualityPoint>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExampleQualityPoint {
+    pub skill: String,
+    pub timestamp: DateTime<Utc>,
+    pub sampled: usize,
+    pub parse_rate: f32,
+    pub pass_rate: f32,
+    pub avg_score: f32,
+    pub quality: f32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct UsageStats {
+    pub executions: usize,
+    pub failures: usize,
+    pub success_rate: f32,
+    pub avg_ms: f64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub last_run: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SkillUsageResponse {
+    pub days: u32,
+    pub skills: Vec<SkillUsage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SkillUsage {
+    pub skill: String,
+    #[serde(flatten)]
+    pub stats: UsageStats,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SkillAnalyticsResponse {
+    pub skill: String,
+    pub days: u32,
+    #[serde(flatten)]
+    pub stats: UsageStats,
+    pub tools: Vec<ToolUsage>,
+    pub daily: Vec<DailyUsage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ToolUsage {
+    pub tool: String,
+    #[serde(flatten)]
+    pub stats: UsageStats,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DailyUsage {
+    pub date: NaiveDate,
+    pub executions: usize,
+    pub failures: usize,
+}
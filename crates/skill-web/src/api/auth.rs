@@ -0,0 +1,23 @@
+//! Auth status API operations
+
+use super::client::ApiClient;
+use super::error::ApiResult;
+use super::types::AuthStatusResponse;
+
+/// Auth API client
+#[derive(Clone)]
+pub struct AuthApi {
+    client: ApiClient,
+}
+
+impl AuthApi {
+    /// Create a new auth API client
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// List stored credentials with their expiry state and warnings
+    pub async fn status(&self) -> ApiResult<AuthStatusResponse> {
+        self.client.get("/auth/status").await
+    }
+}
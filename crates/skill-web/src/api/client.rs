@@ -1,8 +1,11 @@
 //! HTTP API client for the skill-http backend
 
 use gloo_net::http::Request;
+use js_sys::{Reflect, Uint8Array};
 use serde::{de::DeserializeOwned, Serialize};
 use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
 
 use super::error::{ApiError, ApiErrorResponse, ApiResult};
 
@@ -155,6 +158,70 @@ impl ApiClient {
         Self::handle_empty_response(response).await
     }
 
+    /// Make a POST request and read the Server-Sent Events it streams back,
+    /// calling `on_event` with each event's JSON data as it arrives
+    pub async fn post_event_stream<E: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+        mut on_event: impl FnMut(E),
+    ) -> ApiResult<()> {
+        let url = self.url(path);
+
+        let response = Request::post(&url)
+            .header("Content-Type", "application/json")
+            .header("Accept", "text/event-stream")
+            .json(body)
+            .map_err(|e| ApiError::Serialization(e.to_string()))?
+            .send()
+            .await
+            .map_err(|e| ApiError::Network(e.to_string()))?;
+
+        if !response.ok() {
+            return Self::handle_empty_response(response).await;
+        }
+        let Some(stream) = response.body() else {
+            return Ok(());
+        };
+        let reader: web_sys::ReadableStreamDefaultReader = stream.get_reader().unchecked_into();
+
+        // Events end with a blank line; keep partial events (and partial
+        // UTF-8 sequences) buffered until the rest arrives
+        let mut buffer = Vec::new();
+        loop {
+            let chunk = JsFuture::from(reader.read())
+                .await
+                .map_err(|e| ApiError::Network(format!("{:?}", e)))?;
+            let done = Reflect::get(&chunk, &"done".into())
+                .ok()
+                .and_then(|done| done.as_bool())
+                .unwrap_or(true);
+            if done {
+                return Ok(());
+            }
+            let value = Reflect::get(&chunk, &"value".into())
+                .map_err(|e| ApiError::Network(format!("{:?}", e)))?;
+            buffer.extend(Uint8Array::new(&value).to_vec());
+
+            while let Some(end) = buffer.windows(2).position(|w| w == b"\n\n") {
+                let event: Vec<u8> = buffer.drain(..end + 2).collect();
+                let event = String::from_utf8_lossy(&event);
+                let data: Vec<&str> = event
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("data:"))
+                    .map(|data| data.strip_prefix(' ').unwrap_or(data))
+                    .collect();
+                // Keep-alive comments carry no data
+                if data.is_empty() {
+                    continue;
+                }
+                let event = serde_json::from_str(&data.join("\n"))
+                    .map_err(|e| ApiError::Deserialization(e.to_string()))?;
+                on_event(event);
+            }
+        }
+    }
+
     /// Make a PUT request with JSON body
     pub async fn put<T: DeserializeOwned, B: Serialize>(
         &self,
@@ -105,6 +105,26 @@ impl ConfigApi {
                 content: content.to_string(),
                 merge,
                 install,
+                lockfile: None,
+                save: false,
+            })
+            .await
+    }
+
+    /// Get the manifest file the server loaded its skills from
+    pub async fn get_manifest(&self) -> ApiResult<ManifestSourceResponse> {
+        self.client.get("/manifest").await
+    }
+
+    /// Save a manifest to the server's manifest file and install its skills
+    pub async fn apply_manifest(&self, content: &str, merge: bool) -> ApiResult<ImportManifestResponse> {
+        self.client
+            .post("/manifest/import", &ImportManifestRequest {
+                content: content.to_string(),
+                merge,
+                install: true,
+                lockfile: None,
+                save: true,
             })
             .await
     }
@@ -0,0 +1,51 @@
+//! Enhance progress API client
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{ApiClient, ApiResult};
+
+#[derive(Clone)]
+pub struct EnhanceApi {
+    client: ApiClient,
+}
+
+impl EnhanceApi {
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// Get progress of the most recent `skill enhance` run
+    pub async fn get_status(&self) -> ApiResult<EnhanceStatusResponse> {
+        self.client.get("/enhance/status").await
+    }
+}
+
+// Response types
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EnhanceStatusResponse {
+    pub run: Option<EnhanceRunStatus>,
+    pub processed: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EnhanceRunStatus {
+    pub run_id: String,
+    pub running: bool,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub finished_at: Option<DateTime<Utc>>,
+    pub skills: Vec<SkillEnhanceProgress>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SkillEnhanceProgress {
+    pub skill: String,
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub pending: usize,
+}
@@ -23,6 +23,17 @@ impl ExecutionsApi {
         self.client.post("/execute", request).await
     }
 
+    /// Execute a skill tool, calling `on_event` as its output streams in
+    pub async fn execute_stream(
+        &self,
+        request: &ExecutionRequest,
+        on_event: impl FnMut(ExecutionStreamEvent),
+    ) -> ApiResult<()> {
+        self.client
+            .post_event_stream("/execute/stream", request, on_event)
+            .await
+    }
+
     /// Execute a skill tool with simple parameters
     pub async fn execute_simple(
         &self,
@@ -37,6 +48,7 @@ impl ExecutionsApi {
             args,
             stream: false,
             timeout_secs: None,
+            output: OutputOptions::default(),
         })
         .await
     }
@@ -56,6 +68,7 @@ impl ExecutionsApi {
             args,
             stream: false,
             timeout_secs: None,
+            output: OutputOptions::default(),
         })
         .await
     }
@@ -75,6 +88,7 @@ impl ExecutionsApi {
             args,
             stream: false,
             timeout_secs: Some(timeout_secs),
+            output: OutputOptions::default(),
         })
         .await
     }
@@ -108,6 +122,28 @@ impl ExecutionsApi {
         self.client.get(&format!("/executions/{}", id)).await
     }
 
+    /// Ask the configured agent to summarize an execution
+    pub async fn summarize(&self, id: &str) -> ApiResult<ExecutionSummaryResponse> {
+        self.client
+            .post(&format!("/executions/{}/summarize", id), &())
+            .await
+    }
+
+    /// Full-text search over execution output and errors
+    pub async fn search(
+        &self,
+        query: &str,
+        days: Option<u32>,
+    ) -> ApiResult<ExecutionSearchResponse> {
+        let mut params = HashMap::from([("q", query.to_string())]);
+        if let Some(days) = days {
+            params.insert("days", days.to_string());
+        }
+        self.client
+            .get_with_query("/executions/search", &params)
+            .await
+    }
+
     /// Get recent executions for a skill
     pub async fn recent_for_skill(
         &self,
@@ -124,6 +160,23 @@ impl ExecutionsApi {
             .collect())
     }
 
+    /// List the files an execution produced
+    pub async fn list_artifacts(&self, id: &str) -> ApiResult<Vec<ExecutionArtifact>> {
+        self.client
+            .get(&format!("/executions/{}/artifacts", id))
+            .await
+    }
+
+    /// Download URL for an execution's artifact
+    pub fn artifact_url(&self, id: &str, name: &str) -> String {
+        let path = format!("/executions/{}/artifacts/{}", id, name);
+        format!(
+            "{}{}",
+            self.client.base_url(),
+            String::from(js_sys::encode_uri(&path))
+        )
+    }
+
     /// Clear all execution history
     pub async fn clear_history(&self) -> ApiResult<()> {
         self.client.delete("/executions").await?;
@@ -25,29 +25,37 @@
 
 pub mod agent;
 pub mod analytics;
+pub mod auth;
 pub mod client;
 pub mod config;
+pub mod enhance;
 pub mod error;
 pub mod executions;
 pub mod feedback;
+pub mod presets;
 pub mod search;
 pub mod services;
 pub mod skills;
 pub mod types;
+pub mod workflows;
 
 pub use agent::AgentApi;
 pub use analytics::AnalyticsApi;
+pub use auth::AuthApi;
 pub use client::ApiClient;
 pub use config::ConfigApi;
+pub use enhance::EnhanceApi;
 pub use error::ApiResult;
 pub use executions::ExecutionsApi;
 pub use feedback::{
     FeedbackApi, SubmitFeedbackRequest,
 };
+pub use presets::PresetsApi;
 pub use search::SearchApi;
 pub use services::ServicesApi;
 pub use skills::SkillsApi;
 pub use types::*;
+pub use workflows::WorkflowsApi;
 
 /// Unified API facade providing access to all API endpoints
 #[derive(Clone)]
@@ -68,6 +76,14 @@ pub struct Api {
     pub feedback: FeedbackApi,
     /// Analytics API operations
     pub analytics: AnalyticsApi,
+    /// Enhance progress API operations
+    pub enhance: EnhanceApi,
+    /// Auth status API operations
+    pub auth: AuthApi,
+    /// Workflow API operations
+    pub workflows: WorkflowsApi,
+    /// Saved run API operations
+    pub presets: PresetsApi,
 }
 
 impl Default for Api {
@@ -105,7 +121,11 @@ impl Api {
             services: ServicesApi::new(client.clone()),
             agent: AgentApi::new(client.clone()),
             feedback: FeedbackApi::new(client.clone()),
-            analytics: AnalyticsApi::new(client),
+            analytics: AnalyticsApi::new(client.clone()),
+            enhance: EnhanceApi::new(client.clone()),
+            auth: AuthApi::new(client.clone()),
+            workflows: WorkflowsApi::new(client.clone()),
+            presets: PresetsApi::new(client),
         }
     }
 
@@ -0,0 +1,55 @@
+//! Saved run (preset) API operations
+
+use super::client::ApiClient;
+use super::error::ApiResult;
+use super::types::{CreatePresetRequest, ExecutionResponse, RunPreset, UpdatePresetRequest};
+
+/// Saved runs API client
+#[derive(Clone)]
+pub struct PresetsApi {
+    client: ApiClient,
+}
+
+impl PresetsApi {
+    /// Create a new presets API client
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// List saved runs, pinned ones first
+    pub async fn list(&self) -> ApiResult<Vec<RunPreset>> {
+        self.client.get("/presets").await
+    }
+
+    /// Save a run under a name
+    pub async fn create(&self, request: &CreatePresetRequest) -> ApiResult<RunPreset> {
+        self.client.post("/presets", request).await
+    }
+
+    /// Rename, edit or pin a saved run
+    pub async fn update(&self, id: &str, request: &UpdatePresetRequest) -> ApiResult<RunPreset> {
+        let path = format!("/presets/{}", urlencoding::encode(id));
+        self.client.put(&path, request).await
+    }
+
+    /// Pin a saved run to the sidebar, or unpin it
+    pub async fn set_pinned(&self, id: &str, pinned: bool) -> ApiResult<RunPreset> {
+        let request = UpdatePresetRequest {
+            pinned: Some(pinned),
+            ..Default::default()
+        };
+        self.update(id, &request).await
+    }
+
+    /// Delete a saved run
+    pub async fn delete(&self, id: &str) -> ApiResult<()> {
+        let path = format!("/presets/{}", urlencoding::encode(id));
+        self.client.delete(&path).await
+    }
+
+    /// Run a saved run and wait for its result
+    pub async fn run(&self, id: &str) -> ApiResult<ExecutionResponse> {
+        let path = format!("/presets/{}/run", urlencoding::encode(id));
+        self.client.post(&path, &()).await
+    }
+}
@@ -28,6 +28,9 @@ impl SearchApi {
             top_k: 10,
             skill_filter: None,
             include_examples: false,
+            session_id: None,
+            filters: None,
+            explain: false,
         })
         .await
     }
@@ -39,6 +42,9 @@ impl SearchApi {
             top_k,
             skill_filter: None,
             include_examples: false,
+            session_id: None,
+            filters: None,
+            explain: false,
         })
         .await
     }
@@ -50,6 +56,9 @@ impl SearchApi {
             top_k: 10,
             skill_filter: Some(skill.to_string()),
             include_examples: false,
+            session_id: None,
+            filters: None,
+            explain: false,
         })
         .await
     }
@@ -61,6 +70,9 @@ impl SearchApi {
             top_k: 10,
             skill_filter: None,
             include_examples: true,
+            session_id: None,
+            filters: None,
+            explain: false,
         })
         .await
     }
@@ -35,22 +35,40 @@ impl SkillsApi {
         self.client.get(&format!("/skills/{}", name)).await
     }
 
-    /// Install a skill from a source
-    pub async fn install(&self, request: &InstallSkillRequest) -> ApiResult<InstallSkillResponse> {
+    /// Get a skill's SKILL.md or README
+    pub async fn get_docs(&self, name: &str) -> ApiResult<SkillDocsResponse> {
+        self.client.get(&format!("/skills/{}/docs", name)).await
+    }
+
+    /// Start installing a skill from a source
+    pub async fn install(&self, request: &InstallSkillRequest) -> ApiResult<InstallJob> {
         self.client.post("/skills", request).await
     }
 
+    /// Get the progress of a skill install
+    pub async fn get_install(&self, id: &str) -> ApiResult<InstallJob> {
+        self.client.get(&format!("/installs/{}", id)).await
+    }
+
+    /// Cancel a skill install
+    pub async fn cancel_install(&self, id: &str) -> ApiResult<InstallJob> {
+        self.client
+            .delete_with_response(&format!("/installs/{}", id))
+            .await
+    }
+
     /// Install a skill from a git URL
     pub async fn install_from_git(
         &self,
         url: &str,
         git_ref: Option<&str>,
         force: bool,
-    ) -> ApiResult<InstallSkillResponse> {
+    ) -> ApiResult<InstallJob> {
         self.install(&InstallSkillRequest {
             source: url.to_string(),
             name: None,
             git_ref: git_ref.map(String::from),
+            instance: None,
             force,
         })
         .await
@@ -61,11 +79,12 @@ impl SkillsApi {
         &self,
         source: &str,
         name: &str,
-    ) -> ApiResult<InstallSkillResponse> {
+    ) -> ApiResult<InstallJob> {
         self.install(&InstallSkillRequest {
             source: source.to_string(),
             name: Some(name.to_string()),
             git_ref: None,
+            instance: None,
             force: false,
         })
         .await
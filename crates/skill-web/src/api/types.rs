@@ -3,7 +3,7 @@
 //! These types mirror the skill-http API types for serialization.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 // ============================================================================
 // Pagination
@@ -62,6 +62,8 @@ pub struct SkillServiceRequirement {
     pub optional: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_port: Option<u16>,
+    #[serde(default)]
+    pub start_on_demand: bool,
     pub status: ServiceStatus,
 }
 
@@ -82,6 +84,14 @@ pub struct SkillSummary {
     pub required_services: Vec<SkillServiceRequirement>,
 }
 
+/// Skill usage documentation from API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillDocsResponse {
+    pub name: String,
+    pub file: String,
+    pub content: String,
+}
+
 /// Skill detail from API
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SkillDetail {
@@ -106,6 +116,15 @@ pub struct ToolInfo {
     pub description: String,
     pub parameters: Vec<ParameterInfo>,
     pub streaming: bool,
+    /// Whether the host meets the tool's requirements
+    #[serde(default = "default_available")]
+    pub available: bool,
+    #[serde(default)]
+    pub unmet_requirements: Vec<String>,
+}
+
+fn default_available() -> bool {
+    true
 }
 
 /// Parameter information
@@ -118,6 +137,14 @@ pub struct ParameterInfo {
     pub required: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default_value: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_values: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
 }
 
 /// Instance information
@@ -138,21 +165,64 @@ pub struct InstallSkillRequest {
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub git_ref: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
     #[serde(default)]
     pub force: bool,
 }
 
-/// Install skill response
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct InstallSkillResponse {
-    pub success: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub name: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub version: Option<String>,
+/// Stage of a skill installation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallStage {
+    Queued,
+    Fetching,
+    Building,
+    Verifying,
+    Installing,
+    Indexing,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl InstallStage {
+    /// Whether the installation is over
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+
+    /// Label shown in the UI
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Queued => "Queued",
+            Self::Fetching => "Fetching",
+            Self::Building => "Building",
+            Self::Verifying => "Verifying",
+            Self::Installing => "Installing",
+            Self::Indexing => "Indexing",
+            Self::Completed => "Completed",
+            Self::Failed => "Failed",
+            Self::Cancelled => "Cancelled",
+        }
+    }
+}
+
+/// A skill installation running in the background
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstallJob {
+    pub id: String,
+    pub source: String,
+    pub stage: InstallStage,
+    pub progress: u8,
+    pub message: String,
+    pub installed: Vec<String>,
+    pub tools_count: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
-    pub tools_count: usize,
+    pub started_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
 }
 
 // ============================================================================
@@ -172,6 +242,30 @@ pub struct ExecutionRequest {
     pub stream: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout_secs: Option<u64>,
+    /// Output shaping applied by the server
+    #[serde(flatten)]
+    pub output: OutputOptions,
+}
+
+/// Output shaping options (grep, head/tail, jq, format, max_output)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OutputOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncate: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grep: Option<String>,
+    #[serde(default)]
+    pub grep_invert: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tail: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jq: Option<String>,
 }
 
 /// Execution response
@@ -185,6 +279,46 @@ pub struct ExecutionResponse {
     pub duration_ms: u64,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<ExecutionArtifact>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub stderr: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_usage: Option<ResourceUsage>,
+    #[serde(default)]
+    pub truncated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<ExecutionCacheInfo>,
+}
+
+/// Set when the result came from (or was stored in) the server's result cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionCacheInfo {
+    pub hit: bool,
+    pub age_ms: u64,
+    pub ttl_ms: u64,
+}
+
+/// CPU time, peak memory and output size of an execution
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    #[serde(default)]
+    pub cpu_time_ms: Option<u64>,
+    #[serde(default)]
+    pub peak_memory_kb: Option<u64>,
+    #[serde(default)]
+    pub output_bytes: Option<u64>,
+}
+
+/// File captured from an execution's declared outputs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionArtifact {
+    pub name: String,
+    pub size: u64,
+    pub content_type: String,
+    pub created_at: String,
 }
 
 /// Execution status
@@ -213,6 +347,93 @@ pub struct ExecutionHistoryEntry {
     pub error: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub args: HashMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<ExecutionEnvironment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_usage: Option<ResourceUsage>,
+}
+
+/// What an execution ran with (config keys and variable names, no values)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionEnvironment {
+    #[serde(default)]
+    pub config_keys: Vec<String>,
+    #[serde(default)]
+    pub env_vars: Vec<String>,
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub binaries: BTreeMap<String, String>,
+}
+
+/// A saved run: a named skill, tool, instance and arguments to run again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunPreset {
+    pub id: String,
+    pub name: String,
+    pub skill: String,
+    pub tool: String,
+    pub instance: String,
+    #[serde(default)]
+    pub args: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub pinned: bool,
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<String>,
+}
+
+/// Request to save a run preset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePresetRequest {
+    pub name: String,
+    pub skill: String,
+    pub tool: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    #[serde(default)]
+    pub args: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// Changes to a run preset; `None` fields are left as they are
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdatePresetRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args: Option<HashMap<String, serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<bool>,
+}
+
+/// An agent's summary of a past execution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionSummaryResponse {
+    pub id: String,
+    pub summary: String,
+    pub runtime: AgentRuntime,
+    pub model: String,
+}
+
+/// An execution matching a history search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionSearchHit {
+    pub execution: ExecutionHistoryEntry,
+    /// Matched words are wrapped in `«` and `»`
+    pub snippet: String,
+}
+
+/// Results of a full-text search over execution history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionSearchResponse {
+    pub query: String,
+    pub results: Vec<ExecutionSearchHit>,
 }
 
 // ============================================================================
@@ -229,6 +450,35 @@ pub struct SearchRequest {
     pub skill_filter: Option<String>,
     #[serde(default)]
     pub include_examples: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filters: Option<SearchFilters>,
+    #[serde(default)]
+    pub explain: bool,
+}
+
+/// Structured search filters
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchFilters {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+}
+
+impl SearchFilters {
+    /// Whether no filter is set
+    pub fn is_empty(&self) -> bool {
+        self.runtime.is_none()
+            && self.tags.is_empty()
+            && self.instance.is_none()
+            && self.category.is_none()
+    }
 }
 
 fn default_top_k() -> usize {
@@ -245,6 +495,28 @@ pub struct SearchResult {
     pub score: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub rerank_score: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<ScoreExplanation>,
+}
+
+/// Per-result scoring breakdown
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreExplanation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dense_score: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dense_rank: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bm25_score: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bm25_rank: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fusion_score: Option<f32>,
+    pub retrieval_rank: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rerank_delta: Option<i64>,
+    #[serde(default)]
+    pub matched_terms: Vec<String>,
 }
 
 /// Search response
@@ -262,6 +534,8 @@ pub struct QueryInfo {
     pub normalized: String,
     pub intent: String,
     pub confidence: f32,
+    #[serde(default)]
+    pub follow_up: bool,
 }
 
 // ============================================================================
@@ -349,6 +623,17 @@ pub struct HealthResponse {
     pub components: HashMap<String, ComponentHealth>,
     pub version: String,
     pub uptime_secs: u64,
+    #[serde(default)]
+    pub executions: ExecutionUtilization,
+}
+
+/// Running and queued executions against the server's limits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionUtilization {
+    pub running: usize,
+    pub queued: usize,
+    pub max_concurrent: usize,
+    pub max_queued: usize,
 }
 
 /// Component health
@@ -385,6 +670,10 @@ pub struct ImportManifestRequest {
     pub merge: bool,
     #[serde(default)]
     pub install: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lockfile: Option<String>,
+    #[serde(default)]
+    pub save: bool,
 }
 
 /// Parsed skill from manifest
@@ -435,9 +724,13 @@ pub struct ImportManifestResponse {
     pub skills_count: usize,
     pub installed_count: usize,
     #[serde(default)]
+    pub installs: Vec<InstallJob>,
+    #[serde(default)]
     pub warnings: Vec<String>,
     #[serde(default)]
     pub errors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub saved_path: Option<String>,
 }
 
 /// Request to validate manifest content
@@ -455,6 +748,39 @@ pub struct ValidateManifestResponse {
     pub errors: Vec<String>,
     #[serde(default)]
     pub warnings: Vec<String>,
+    #[serde(default)]
+    pub diagnostics: Vec<ManifestDiagnostic>,
+}
+
+/// How serious a manifest diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+/// A problem found in a manifest; lines and columns are 1-based
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestDiagnostic {
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    #[serde(default)]
+    pub line: Option<usize>,
+    #[serde(default)]
+    pub column: Option<usize>,
+    #[serde(default)]
+    pub end_line: Option<usize>,
+    #[serde(default)]
+    pub end_column: Option<usize>,
+}
+
+/// The manifest file the server loaded its skills from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestSourceResponse {
+    #[serde(default)]
+    pub path: Option<String>,
+    pub content: String,
 }
 
 // ============================================================================
@@ -474,6 +800,10 @@ pub struct ServiceStatus {
     pub url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    #[serde(default)]
+    pub managed: bool,
+    #[serde(default)]
+    pub restarts: u32,
 }
 
 /// Response listing all system services
@@ -504,6 +834,187 @@ pub struct StopServiceRequest {
     pub service: String,
 }
 
+// ============================================================================
+// Auth Status
+// ============================================================================
+
+/// A credential stored with `skill auth login` (metadata only)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredentialStatusInfo {
+    pub provider_id: String,
+    pub display_name: String,
+    pub auth_type: String,
+    #[serde(default)]
+    pub skill: Option<String>,
+    #[serde(default)]
+    pub instance: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    pub has_refresh_token: bool,
+    /// active, refreshable, expiring_soon or expired
+    pub state: String,
+    #[serde(default)]
+    pub warning: Option<String>,
+}
+
+/// Response listing stored credentials
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthStatusResponse {
+    pub credentials: Vec<CredentialStatusInfo>,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+// ============================================================================
+// Workflows
+// ============================================================================
+
+/// A workflow that can be run from the UI
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowSummary {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// JSON Schema for the workflow's inputs
+    #[serde(default)]
+    pub inputs: serde_json::Value,
+    pub steps: Vec<WorkflowStepInfo>,
+}
+
+/// A workflow step, with the earlier steps it uses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowStepInfo {
+    pub id: String,
+    pub tool: String,
+    #[serde(default)]
+    pub when: Option<String>,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    #[serde(default)]
+    pub retries: u32,
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+/// Request to run a workflow
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunWorkflowRequest {
+    pub inputs: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Status of a workflow run or one of its steps
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkflowStepStatus {
+    #[default]
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+/// State of one step in a workflow run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowStepRun {
+    pub id: String,
+    pub tool: String,
+    pub status: WorkflowStepStatus,
+    pub attempts: u32,
+    #[serde(default)]
+    pub output: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub outputs: HashMap<String, serde_json::Value>,
+    pub duration_ms: u64,
+}
+
+/// State of a workflow run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowRun {
+    pub workflow: String,
+    pub status: WorkflowStepStatus,
+    #[serde(default)]
+    pub inputs: serde_json::Map<String, serde_json::Value>,
+    pub steps: Vec<WorkflowStepRun>,
+    pub started_at: String,
+    #[serde(default)]
+    pub finished_at: Option<String>,
+}
+
+/// Event streamed while a workflow runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WorkflowEvent {
+    Started {
+        workflow: String,
+        steps: Vec<String>,
+    },
+    StepStarted {
+        step: String,
+        tool: String,
+    },
+    StepOutput {
+        step: String,
+        chunk: String,
+    },
+    StepCompleted {
+        step: String,
+        duration_ms: u64,
+        #[serde(default)]
+        outputs: HashMap<String, serde_json::Value>,
+    },
+    StepFailed {
+        step: String,
+        error: String,
+        attempts: u32,
+    },
+    StepSkipped {
+        step: String,
+    },
+    Finished {
+        #[serde(default)]
+        job_id: Option<String>,
+        run: WorkflowRun,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Which output stream a streamed execution's output came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Event streamed while a tool executes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ExecutionStreamEvent {
+    Started {
+        skill: String,
+        tool: String,
+        instance: String,
+    },
+    Output {
+        stream: OutputStream,
+        text: String,
+    },
+    Completed {
+        execution: ExecutionResponse,
+    },
+    Error {
+        status: u16,
+        error: super::error::ApiErrorResponse,
+    },
+}
+
 // ============================================================================
 // Vector DB Testing Types
 // ============================================================================
@@ -531,7 +1042,7 @@ pub struct TestConnectionResponse {
 }
 
 /// Request to test full search pipeline
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TestPipelineRequest {
     pub embedding_provider: String,
     pub embedding_model: String,
@@ -540,6 +1051,33 @@ pub struct TestPipelineRequest {
     pub enable_reranking: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub qdrant_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<usize>,
+    #[serde(default)]
+    pub use_skill_catalog: bool,
+    /// Named configurations to compare; each overrides the settings above
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub configs: Vec<PipelineConfigOverride>,
+}
+
+/// A named pipeline configuration for comparing search settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PipelineConfigOverride {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_backend: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_hybrid: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_reranking: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qdrant_url: Option<String>,
 }
 
 /// Response from testing search pipeline
@@ -550,10 +1088,25 @@ pub struct TestPipelineResponse {
     pub search_results: Vec<PipelineSearchResult>,
     pub duration_ms: u128,
     pub message: String,
+    #[serde(default)]
+    pub runs: Vec<PipelineRun>,
+}
+
+/// Results of one named configuration in a pipeline comparison
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineRun {
+    pub name: String,
+    pub success: bool,
+    pub index_stats: PipelineIndexStats,
+    pub search_results: Vec<PipelineSearchResult>,
+    pub search_duration_ms: u64,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub error: Option<String>,
 }
 
 /// Pipeline indexing statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PipelineIndexStats {
     pub documents_indexed: usize,
     pub indexing_duration_ms: u64,
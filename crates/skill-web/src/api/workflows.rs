@@ -0,0 +1,36 @@
+//! Workflows API operations
+
+use super::client::ApiClient;
+use super::error::ApiResult;
+use super::types::{RunWorkflowRequest, WorkflowEvent, WorkflowSummary};
+
+/// Workflows API client
+#[derive(Clone)]
+pub struct WorkflowsApi {
+    client: ApiClient,
+}
+
+impl WorkflowsApi {
+    /// Create a new workflows API client
+    pub fn new(client: ApiClient) -> Self {
+        Self { client }
+    }
+
+    /// List workflows known to the server
+    pub async fn list(&self) -> ApiResult<Vec<WorkflowSummary>> {
+        self.client.get("/workflows").await
+    }
+
+    /// Run a workflow, calling `on_event` for each step event as it arrives
+    pub async fn run_stream(
+        &self,
+        name: &str,
+        request: &RunWorkflowRequest,
+        on_event: impl FnMut(WorkflowEvent),
+    ) -> ApiResult<()> {
+        let path = format!("/workflows/{}/run", urlencoding::encode(name));
+        self.client
+            .post_event_stream(&path, request, on_event)
+            .await
+    }
+}
@@ -6,11 +6,14 @@ use yew::prelude::*;
 use yew_router::prelude::*;
 
 use crate::components::layout::Layout;
+use crate::hooks::use_theme;
 use crate::router::{switch, Route};
 
 /// Root application component
 #[function_component(App)]
 pub fn app() -> Html {
+    use_theme();
+
     html! {
         <BrowserRouter>
             <Layout>
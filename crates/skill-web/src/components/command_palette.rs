@@ -0,0 +1,608 @@
+//! Command palette - app-wide launcher opened with Ctrl+K (⌘K on macOS)
+//!
+//! Fuzzy-matches skills, tools, recent executions and pages. A query that
+//! names a skill and one of its tools, followed by arguments, launches that
+//! tool with the arguments typed inline, shell style:
+//!
+//! ```text
+//! kubernetes get pods -n prod
+//! github:create-issue repo=org/app --title "Flaky test"
+//! ```
+//!
+//! - `name=value`, `--name value` and `--name=value` set a parameter by name
+//! - `-n value` sets the one parameter whose name starts with `n`
+//! - `--flag` on its own sets a boolean parameter
+//! - other words fill the remaining parameters in order, required ones first
+//!
+//! Tools and recent executions open the Run page with their arguments
+//! pre-filled; skills and pages navigate to them.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, KeyboardEvent};
+use yew::prelude::*;
+use yew_router::prelude::*;
+use yewdux::prelude::*;
+
+use super::icons::SearchIcon;
+use crate::api::types::{ExecutionHistoryEntry, PaginationParams, ParameterInfo, SkillDetail};
+use crate::api::Api;
+use crate::router::Route;
+use crate::store::ui::{UiAction, UiStore};
+
+/// Most results listed at once
+const MAX_RESULTS: usize = 10;
+
+/// Recent executions offered
+const RECENT_EXECUTIONS: usize = 8;
+
+/// Pages the palette can jump to
+const PAGES: &[Route] = &[
+    Route::Dashboard,
+    Route::Skills,
+    Route::Run,
+    Route::History,
+    Route::Workflows,
+    Route::Analytics,
+    Route::Manifest,
+    Route::Settings,
+    Route::SearchTest,
+    Route::Onboarding,
+];
+
+/// What a palette entry is
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EntryKind {
+    Tool,
+    Skill,
+    Execution,
+    Page,
+}
+
+impl EntryKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Tool => "Tool",
+            Self::Skill => "Skill",
+            Self::Execution => "Recent",
+            Self::Page => "Page",
+        }
+    }
+}
+
+/// Where choosing an entry goes
+#[derive(Debug, Clone, PartialEq)]
+enum EntryTarget {
+    /// The Run page for a tool, with arguments pre-filled
+    Run {
+        skill: String,
+        tool: String,
+        args: HashMap<String, String>,
+    },
+    /// Any other page
+    Page(Route),
+}
+
+/// One palette result
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    kind: EntryKind,
+    title: String,
+    detail: String,
+    target: EntryTarget,
+}
+
+/// How well `query` matches `text`: substrings beat characters in order,
+/// prefixes beat other substrings, earlier characters beat later ones
+fn fuzzy_score(text: &str, query: &str) -> Option<f32> {
+    let text = text.to_lowercase();
+    let query = query.to_lowercase();
+    if text.starts_with(&query) {
+        return Some(200.0);
+    }
+    if text.contains(&query) {
+        return Some(100.0);
+    }
+
+    let mut query_chars = query.chars().filter(|c| !c.is_whitespace());
+    let mut current = query_chars.next()?;
+    let mut score = 0.0_f32;
+    for (index, c) in text.chars().enumerate() {
+        if c == current {
+            score += 1.0 / (index as f32 + 1.0);
+            match query_chars.next() {
+                Some(next) => current = next,
+                None => return Some(score),
+            }
+        }
+    }
+    None
+}
+
+/// Split a command line into words, honoring single and double quotes
+fn split_words(input: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    for c in input.chars() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => word.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_word = true;
+            }
+            None if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            None => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+/// The parameter a `--flag` or `key=` names; dashes match underscores
+fn long_param(flag: &str, params: &[ParameterInfo]) -> String {
+    let underscored = flag.replace('-', "_");
+    params
+        .iter()
+        .find(|param| param.name == flag || param.name == underscored)
+        .map(|param| param.name.clone())
+        .unwrap_or(underscored)
+}
+
+/// The parameter a `-x` names: one named `x`, else the only one starting with `x`
+fn short_param(flag: &str, params: &[ParameterInfo]) -> String {
+    if let Some(param) = params.iter().find(|param| param.name == flag) {
+        return param.name.clone();
+    }
+    let mut candidates = params.iter().filter(|param| param.name.starts_with(flag));
+    match (candidates.next(), candidates.next()) {
+        (Some(param), None) => param.name.clone(),
+        _ => flag.to_string(),
+    }
+}
+
+/// Map the words after `skill tool` to the tool's parameters
+fn parse_inline_args(words: &[String], params: &[ParameterInfo]) -> HashMap<String, String> {
+    let is_boolean =
+        |name: &str| params.iter().any(|param| param.name == name && param.param_type == "boolean");
+    let mut args = HashMap::new();
+    let mut positional = Vec::new();
+    let mut words = words.iter().peekable();
+
+    while let Some(word) = words.next() {
+        let (name, value) = if let Some(flag) = word.strip_prefix("--") {
+            match flag.split_once('=') {
+                Some((flag, value)) => (long_param(flag, params), Some(value.to_string())),
+                None => (long_param(flag, params), None),
+            }
+        } else if let Some(flag) = word
+            .strip_prefix('-')
+            .filter(|flag| flag.starts_with(|c: char| c.is_ascii_alphabetic()))
+        {
+            (short_param(flag, params), None)
+        } else if let Some((name, value)) = word
+            .split_once('=')
+            .filter(|(name, _)| !name.is_empty() && !name.contains('/'))
+        {
+            (long_param(name, params), Some(value.to_string()))
+        } else {
+            positional.push(word.clone());
+            continue;
+        };
+
+        let value = value.unwrap_or_else(|| {
+            let takes_value = !is_boolean(&name)
+                || words.peek().is_some_and(|next| matches!(next.as_str(), "true" | "false"));
+            match words.peek() {
+                Some(next) if takes_value && !next.starts_with("--") => {
+                    words.next().cloned().unwrap_or_default()
+                }
+                _ => "true".to_string(),
+            }
+        });
+        args.insert(name, value);
+    }
+
+    // Bare words fill the parameters not set by name, required ones first
+    let open: Vec<String> = params
+        .iter()
+        .filter(|param| param.required)
+        .chain(params.iter().filter(|param| !param.required))
+        .filter(|param| param.param_type != "boolean" && !args.contains_key(&param.name))
+        .map(|param| param.name.clone())
+        .collect();
+    args.extend(open.into_iter().zip(positional));
+    args
+}
+
+/// `skill tool args...` or `skill:tool args...`, launching the tool
+fn command_entry(query: &str, skills: &[SkillDetail]) -> Option<Entry> {
+    let words = split_words(query);
+    let (skill_name, tool_name, rest) = match words.first()?.split_once(':') {
+        Some((skill, tool)) => (skill.to_string(), tool.to_string(), &words[1..]),
+        None => (words.first()?.clone(), words.get(1)?.clone(), words.get(2..)?),
+    };
+    // Without arguments the tool's own entry is the same thing
+    if rest.is_empty() {
+        return None;
+    }
+    let skill = skills
+        .iter()
+        .find(|skill| skill.summary.name.eq_ignore_ascii_case(&skill_name))?;
+    let tool = skill
+        .tools
+        .iter()
+        .find(|tool| tool.name.eq_ignore_ascii_case(&tool_name))?;
+
+    let args = parse_inline_args(rest, &tool.parameters);
+    let mut detail: Vec<String> = args
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect();
+    detail.sort();
+    Some(Entry {
+        kind: EntryKind::Tool,
+        title: format!("Run {}:{}", skill.summary.name, tool.name),
+        detail: detail.join("  "),
+        target: EntryTarget::Run {
+            skill: skill.summary.name.clone(),
+            tool: tool.name.clone(),
+            args,
+        },
+    })
+}
+
+/// A past execution, re-opened with the arguments it ran with
+fn execution_entry(execution: &ExecutionHistoryEntry) -> Entry {
+    let args = execution
+        .args
+        .iter()
+        .map(|(name, value)| {
+            let value = match value {
+                serde_json::Value::String(text) => text.clone(),
+                other => other.to_string(),
+            };
+            (name.clone(), value)
+        })
+        .collect();
+    Entry {
+        kind: EntryKind::Execution,
+        title: format!("{}:{}", execution.skill, execution.tool),
+        detail: format!("{:?} · {}", execution.status, execution.started_at),
+        target: EntryTarget::Run {
+            skill: execution.skill.clone(),
+            tool: execution.tool.clone(),
+            args,
+        },
+    }
+}
+
+fn page_entry(route: &Route) -> Entry {
+    Entry {
+        kind: EntryKind::Page,
+        title: route.display_name().to_string(),
+        detail: route.to_path(),
+        target: EntryTarget::Page(route.clone()),
+    }
+}
+
+/// Entries matching the query, best first
+fn search(query: &str, skills: &[SkillDetail], recent: &[ExecutionHistoryEntry]) -> Vec<Entry> {
+    let query = query.trim();
+    if query.is_empty() {
+        return recent
+            .iter()
+            .map(execution_entry)
+            .chain(PAGES.iter().map(page_entry))
+            .take(MAX_RESULTS)
+            .collect();
+    }
+
+    let mut scored: Vec<(f32, Entry)> = Vec::new();
+    if let Some(entry) = command_entry(query, skills) {
+        scored.push((f32::MAX, entry));
+    }
+    for skill in skills {
+        for tool in &skill.tools {
+            let text = format!("{} {}", skill.summary.name, tool.name);
+            if let Some(score) = fuzzy_score(&text, query) {
+                let entry = Entry {
+                    kind: EntryKind::Tool,
+                    title: format!("{}:{}", skill.summary.name, tool.name),
+                    detail: tool.description.clone(),
+                    target: EntryTarget::Run {
+                        skill: skill.summary.name.clone(),
+                        tool: tool.name.clone(),
+                        args: HashMap::new(),
+                    },
+                };
+                scored.push((score, entry));
+            }
+        }
+    }
+    for skill in skills {
+        if let Some(score) = fuzzy_score(&skill.summary.name, query) {
+            let entry = Entry {
+                kind: EntryKind::Skill,
+                title: skill.summary.name.clone(),
+                detail: skill.summary.description.clone(),
+                target: EntryTarget::Page(Route::SkillDetail {
+                    name: skill.summary.name.clone(),
+                }),
+            };
+            scored.push((score, entry));
+        }
+    }
+    for execution in recent {
+        let text = format!("{} {}", execution.skill, execution.tool);
+        if let Some(score) = fuzzy_score(&text, query) {
+            scored.push((score, execution_entry(execution)));
+        }
+    }
+    for route in PAGES {
+        if let Some(score) = fuzzy_score(route.display_name(), query) {
+            scored.push((score, page_entry(route)));
+        }
+    }
+
+    // Stable, so equal scores keep tools, skills, executions, pages order
+    scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    scored
+        .into_iter()
+        .map(|(_, entry)| entry)
+        .take(MAX_RESULTS)
+        .collect()
+}
+
+#[function_component(CommandPalette)]
+pub fn command_palette() -> Html {
+    let (ui, ui_dispatch) = use_store::<UiStore>();
+    let navigator = use_navigator();
+    let api = use_memo((), |_| Rc::new(Api::new()));
+    let skills = use_state(Vec::<SkillDetail>::new);
+    let recent = use_state(Vec::<ExecutionHistoryEntry>::new);
+    let input_ref = use_node_ref();
+
+    let palette = &ui.command_palette;
+    let results = search(&palette.query, &skills, &recent);
+    let selected = palette.selected_index.min(results.len().saturating_sub(1));
+
+    // Ctrl+K / ⌘K toggles the palette from anywhere
+    {
+        let ui_dispatch = ui_dispatch.clone();
+        use_effect_with((), move |_| {
+            let listener = Closure::<dyn FnMut(KeyboardEvent)>::new(move |e: KeyboardEvent| {
+                if (e.ctrl_key() || e.meta_key()) && e.key().eq_ignore_ascii_case("k") {
+                    e.prevent_default();
+                    ui_dispatch.apply(UiAction::ToggleCommandPalette);
+                }
+            });
+            let document = web_sys::window().and_then(|window| window.document());
+            if let Some(document) = &document {
+                let _ = document
+                    .add_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref());
+            }
+            move || {
+                if let Some(document) = document {
+                    let _ = document.remove_event_listener_with_callback(
+                        "keydown",
+                        listener.as_ref().unchecked_ref(),
+                    );
+                }
+            }
+        });
+    }
+
+    // Focus the input and refresh what can be searched each time it opens;
+    // skill details are loaded once
+    {
+        let api = api.clone();
+        let skills = skills.clone();
+        let recent = recent.clone();
+        let input_ref = input_ref.clone();
+        use_effect_with(palette.open, move |open| {
+            if *open {
+                if let Some(input) = input_ref.cast::<HtmlInputElement>() {
+                    let _ = input.focus();
+                }
+                spawn_local(async move {
+                    let page = PaginationParams::new(1, RECENT_EXECUTIONS);
+                    if let Ok(history) = api.executions.list_history(Some(page)).await {
+                        recent.set(history.items);
+                    }
+                    if !skills.is_empty() {
+                        return;
+                    }
+                    match api.skills.list_all().await {
+                        Ok(summaries) => {
+                            let mut details = Vec::new();
+                            for summary in summaries {
+                                if let Ok(detail) = api.skills.get(&summary.name).await {
+                                    details.push(detail);
+                                }
+                            }
+                            skills.set(details);
+                        }
+                        Err(e) => {
+                            web_sys::console::error_1(
+                                &format!("Failed to load skills for the command palette: {}", e).into(),
+                            );
+                        }
+                    }
+                });
+            }
+            || ()
+        });
+    }
+
+    let choose = {
+        let ui_dispatch = ui_dispatch.clone();
+        Callback::from(move |target: EntryTarget| {
+            ui_dispatch.apply(UiAction::CloseCommandPalette);
+            let Some(navigator) = &navigator else {
+                return;
+            };
+            match target {
+                EntryTarget::Run { skill, tool, args } => {
+                    let route = Route::RunSkillTool { skill, tool };
+                    if args.is_empty() || navigator.push_with_query(&route, &args).is_err() {
+                        navigator.push(&route);
+                    }
+                }
+                EntryTarget::Page(route) => navigator.push(&route),
+            }
+        })
+    };
+
+    let on_input = {
+        let ui_dispatch = ui_dispatch.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            ui_dispatch.apply(UiAction::SetCommandPaletteQuery(input.value()));
+        })
+    };
+
+    let on_keydown = {
+        let ui_dispatch = ui_dispatch.clone();
+        let choose = choose.clone();
+        let results = results.clone();
+        Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+            "ArrowDown" => {
+                e.prevent_default();
+                let last = results.len().saturating_sub(1);
+                ui_dispatch.apply(UiAction::SelectCommandPaletteItem((selected + 1).min(last)));
+            }
+            "ArrowUp" => {
+                e.prevent_default();
+                ui_dispatch.apply(UiAction::SelectCommandPaletteItem(selected.saturating_sub(1)));
+            }
+            "Enter" => {
+                e.prevent_default();
+                if let Some(entry) = results.get(selected) {
+                    choose.emit(entry.target.clone());
+                }
+            }
+            "Escape" => {
+                e.prevent_default();
+                ui_dispatch.apply(UiAction::CloseCommandPalette);
+            }
+            _ => {}
+        })
+    };
+
+    let on_backdrop_click = {
+        let ui_dispatch = ui_dispatch.clone();
+        Callback::from(move |_: MouseEvent| ui_dispatch.apply(UiAction::CloseCommandPalette))
+    };
+
+    if !palette.open {
+        return html! {};
+    }
+
+    html! {
+        <div
+            class="fixed inset-0 z-50 flex items-start justify-center pt-[15vh] bg-gray-900/50"
+            onclick={on_backdrop_click}
+        >
+            <div
+                class="w-full max-w-2xl mx-4 bg-white dark:bg-gray-800 rounded-xl shadow-2xl border border-gray-200 dark:border-gray-700 overflow-hidden"
+                onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}
+            >
+                // Query
+                <div class="flex items-center gap-3 px-4 border-b border-gray-200 dark:border-gray-700">
+                    <SearchIcon class="w-5 h-5 text-gray-400" />
+                    <input
+                        ref={input_ref}
+                        type="text"
+                        class="flex-1 py-4 bg-transparent outline-none text-gray-900 dark:text-white placeholder-gray-400"
+                        placeholder="Search skills, tools and pages, or run: kubernetes get pods -n prod"
+                        value={palette.query.clone()}
+                        oninput={on_input}
+                        onkeydown={on_keydown}
+                    />
+                    <kbd class="text-xs px-1.5 py-0.5 rounded border border-gray-300 dark:border-gray-600 text-gray-500 dark:text-gray-400">
+                        { "Esc" }
+                    </kbd>
+                </div>
+
+                // Results
+                if results.is_empty() {
+                    <div class="px-4 py-8 text-center text-sm text-gray-500 dark:text-gray-400">
+                        if skills.is_empty() && !palette.query.trim().is_empty() {
+                            { "Loading skills..." }
+                        } else {
+                            { "Nothing matches" }
+                        }
+                    </div>
+                } else {
+                    <ul class="max-h-96 overflow-y-auto py-2">
+                        { for results.iter().enumerate().map(|(index, entry)| {
+                            let onclick = {
+                                let choose = choose.clone();
+                                let target = entry.target.clone();
+                                Callback::from(move |_: MouseEvent| choose.emit(target.clone()))
+                            };
+                            let onmouseenter = {
+                                let ui_dispatch = ui_dispatch.clone();
+                                Callback::from(move |_: MouseEvent| {
+                                    ui_dispatch.apply(UiAction::SelectCommandPaletteItem(index))
+                                })
+                            };
+                            html! {
+                                <li>
+                                    <button
+                                        type="button"
+                                        class={classes!(
+                                            "w-full", "flex", "items-center", "gap-3", "px-4", "py-2", "text-left",
+                                            if index == selected {
+                                                "bg-primary-50 dark:bg-primary-900/30"
+                                            } else {
+                                                "hover:bg-gray-50 dark:hover:bg-gray-700/50"
+                                            }
+                                        )}
+                                        {onclick}
+                                        {onmouseenter}
+                                    >
+                                        <span class="w-14 shrink-0 text-xs font-medium text-gray-500 dark:text-gray-400">
+                                            { entry.kind.label() }
+                                        </span>
+                                        <span class="font-mono text-sm text-gray-900 dark:text-white whitespace-nowrap">
+                                            { &entry.title }
+                                        </span>
+                                        <span class="flex-1 text-xs text-gray-500 dark:text-gray-400 truncate">
+                                            { &entry.detail }
+                                        </span>
+                                    </button>
+                                </li>
+                            }
+                        }) }
+                    </ul>
+                }
+
+                // Keyboard hints
+                <div class="flex items-center gap-6 px-4 py-2 border-t border-gray-200 dark:border-gray-700 text-xs text-gray-500 dark:text-gray-400">
+                    <span>{ "↑↓ Navigate" }</span>
+                    <span>{ "↵ Open" }</span>
+                    <span>{ "Esc Close" }</span>
+                    <span class="flex-1 text-right">{ "Ctrl+K anywhere" }</span>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -192,3 +192,23 @@ pub fn analytics_icon(props: &IconProps) -> Html {
         </svg>
     }
 }
+
+/// Bookmark icon (for saved runs)
+#[function_component(BookmarkIcon)]
+pub fn bookmark_icon(props: &IconProps) -> Html {
+    html! {
+        <svg class={&props.class} fill="none" viewBox="0 0 24 24" stroke="currentColor" stroke-width="2">
+            <path stroke-linecap="round" stroke-linejoin="round" d="M5 5a2 2 0 012-2h10a2 2 0 012 2v16l-7-3.5L5 21V5z" />
+        </svg>
+    }
+}
+
+/// Document icon (for the manifest editor)
+#[function_component(DocumentIcon)]
+pub fn document_icon(props: &IconProps) -> Html {
+    html! {
+        <svg class={&props.class} fill="none" viewBox="0 0 24 24" stroke="currentColor" stroke-width="2">
+            <path stroke-linecap="round" stroke-linejoin="round" d="M9 12h6m-6 4h6m2 5H7a2 2 0 01-2-2V5a2 2 0 012-2h5.586a1 1 0 01.707.293l5.414 5.414a1 1 0 01.293.707V19a2 2 0 01-2 2z" />
+        </svg>
+    }
+}
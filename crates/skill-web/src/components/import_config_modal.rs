@@ -740,10 +740,14 @@ pub fn import_config_modal(props: &ImportConfigModalProps) -> Html {
                         if response.success {
                             let count = response.installed_count;
                             import_state.set(ImportState::Complete(count));
-                            notifications.success(
-                                "Import Complete",
-                                format!("Successfully imported {} skill(s)", count),
-                            );
+                            let message = match response.installs.len() {
+                                0 => format!("Successfully imported {} skill(s)", count),
+                                queued => format!(
+                                    "Imported {} skill(s); installing {} in the background",
+                                    count, queued
+                                ),
+                            };
+                            notifications.success("Import Complete", message);
                             on_imported.emit(count);
                             ui_dispatch.apply(UiAction::CloseModal);
                         } else {
@@ -1,13 +1,16 @@
 //! Install Skill Modal component
 //!
-//! Modal dialog for installing skills from various sources.
+//! Modal dialog for installing skills from various sources. The server
+//! installs in the background; the modal follows the install's progress
+//! until it finishes and can cancel it.
 
+use gloo_timers::future::TimeoutFuture;
 use std::rc::Rc;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 use yewdux::prelude::*;
 
-use crate::api::{Api, InstallSkillRequest};
+use crate::api::{Api, InstallJob, InstallSkillRequest, InstallStage};
 use crate::components::use_notifications;
 use crate::store::ui::{UiAction, UiStore};
 
@@ -61,15 +64,27 @@ pub struct InstallSkillModalProps {
     pub on_close: Callback<()>,
 }
 
+/// How often a running install's progress is fetched, in milliseconds
+const INSTALL_POLL_MS: u32 = 500;
+
 /// Installation state
 #[derive(Clone, PartialEq)]
 enum InstallState {
     Idle,
-    Installing,
+    /// Waiting for the server to accept the install
+    Starting,
+    /// The server is installing; the latest progress
+    Installing(InstallJob),
     Success(String),
     Error(String),
 }
 
+impl InstallState {
+    fn is_busy(&self) -> bool {
+        matches!(self, Self::Starting | Self::Installing(_))
+    }
+}
+
 /// Install Skill Modal component
 #[function_component(InstallSkillModal)]
 pub fn install_skill_modal(props: &InstallSkillModalProps) -> Html {
@@ -97,7 +112,7 @@ pub fn install_skill_modal(props: &InstallSkillModalProps) -> Html {
         let on_close_prop = props.on_close.clone();
         let install_state = install_state.clone();
         Callback::from(move |_: MouseEvent| {
-            if *install_state != InstallState::Installing {
+            if !install_state.is_busy() {
                 ui_dispatch.apply(UiAction::CloseModal);
                 on_close_prop.emit(());
             }
@@ -113,7 +128,7 @@ pub fn install_skill_modal(props: &InstallSkillModalProps) -> Html {
             // Only close if clicking directly on the backdrop
             let target = e.target().unwrap();
             let current_target = e.current_target().unwrap();
-            if target == current_target && *install_state != InstallState::Installing {
+            if target == current_target && !install_state.is_busy() {
                 ui_dispatch.apply(UiAction::CloseModal);
                 on_close_prop.emit(());
             }
@@ -211,20 +226,21 @@ pub fn install_skill_modal(props: &InstallSkillModalProps) -> Html {
             // Build request
             let request = InstallSkillRequest {
                 source: full_source,
-                name: if (*instance_name).is_empty() {
+                name: None,
+                git_ref: if (*git_ref).is_empty() {
                     None
                 } else {
-                    Some((*instance_name).clone())
+                    Some((*git_ref).clone())
                 },
-                git_ref: if (*git_ref).is_empty() {
+                instance: if (*instance_name).is_empty() {
                     None
                 } else {
-                    Some((*git_ref).clone())
+                    Some((*instance_name).clone())
                 },
                 force: *force_reinstall,
             };
 
-            install_state.set(InstallState::Installing);
+            install_state.set(InstallState::Starting);
 
             let api = api.clone();
             let install_state = install_state.clone();
@@ -233,29 +249,53 @@ pub fn install_skill_modal(props: &InstallSkillModalProps) -> Html {
             let ui_dispatch = ui_dispatch.clone();
 
             spawn_local(async move {
-                match api.skills.install(&request).await {
-                    Ok(response) => {
-                        if response.success {
-                            let name = response.name.unwrap_or_else(|| "skill".to_string());
-                            let version = response.version.unwrap_or_else(|| "unknown".to_string());
-                            install_state.set(InstallState::Success(name.clone()));
-                            notifications.success(
-                                "Skill Installed",
-                                format!(
-                                    "Successfully installed {} v{} with {} tools",
-                                    name, version, response.tools_count
-                                ),
-                            );
-                            on_installed.emit(name);
-                            ui_dispatch.apply(UiAction::CloseModal);
-                        } else {
-                            let error = response.error.unwrap_or_else(|| "Unknown error".to_string());
+                let mut job = match api.skills.install(&request).await {
+                    Ok(job) => job,
+                    Err(e) => {
+                        let error = e.to_string();
+                        install_state.set(InstallState::Error(error.clone()));
+                        notifications.error("Installation Failed", &error);
+                        return;
+                    }
+                };
+
+                // Follow the install until the server finishes it
+                while !job.stage.is_finished() {
+                    install_state.set(InstallState::Installing(job.clone()));
+                    TimeoutFuture::new(INSTALL_POLL_MS).await;
+                    job = match api.skills.get_install(&job.id).await {
+                        Ok(job) => job,
+                        Err(e) => {
+                            let error = e.to_string();
                             install_state.set(InstallState::Error(error.clone()));
                             notifications.error("Installation Failed", &error);
+                            return;
+                        }
+                    };
+                }
+
+                match job.stage {
+                    InstallStage::Completed => {
+                        let names = job.installed.join(", ");
+                        install_state.set(InstallState::Success(names.clone()));
+                        notifications.success(
+                            "Skill Installed",
+                            format!(
+                                "Successfully installed {} with {} tools",
+                                names, job.tools_count
+                            ),
+                        );
+                        for name in job.installed {
+                            on_installed.emit(name);
                         }
+                        ui_dispatch.apply(UiAction::CloseModal);
                     }
-                    Err(e) => {
-                        let error = e.to_string();
+                    InstallStage::Cancelled => {
+                        install_state.set(InstallState::Idle);
+                        notifications.info("Installation Cancelled", format!("Stopped installing {}", job.source));
+                    }
+                    _ => {
+                        let error = job.error.unwrap_or_else(|| "Unknown error".to_string());
                         install_state.set(InstallState::Error(error.clone()));
                         notifications.error("Installation Failed", &error);
                     }
@@ -264,9 +304,30 @@ pub fn install_skill_modal(props: &InstallSkillModalProps) -> Html {
         })
     };
 
+    // Cancel handler for a running install
+    let on_cancel_install = {
+        let api = api.clone();
+        let install_state = install_state.clone();
+        let notifications = notifications.clone();
+        Callback::from(move |_: MouseEvent| {
+            let InstallState::Installing(job) = &*install_state else {
+                return;
+            };
+            let api = api.clone();
+            let id = job.id.clone();
+            let notifications = notifications.clone();
+            spawn_local(async move {
+                // The progress poll picks up the cancellation
+                if let Err(e) = api.skills.cancel_install(&id).await {
+                    notifications.error("Cancel Failed", e.to_string());
+                }
+            });
+        })
+    };
+
     // Validation
     let is_valid = !(*source_input).trim().is_empty();
-    let is_installing = *install_state == InstallState::Installing;
+    let is_installing = install_state.is_busy();
 
     if !is_open {
         return html! {};
@@ -415,6 +476,29 @@ pub fn install_skill_modal(props: &InstallSkillModalProps) -> Html {
                         </div>
                     }
 
+                    // Install progress
+                    if let InstallState::Installing(ref job) = *install_state {
+                        <div class="space-y-2">
+                            <div class="flex items-center justify-between text-sm">
+                                <span class="font-medium text-gray-700 dark:text-gray-300">
+                                    { job.stage.label() }
+                                </span>
+                                <span class="text-gray-500 dark:text-gray-400">
+                                    { format!("{}%", job.progress) }
+                                </span>
+                            </div>
+                            <div class="w-full h-2 bg-gray-200 dark:bg-gray-700 rounded-full overflow-hidden">
+                                <div
+                                    class="h-full bg-primary-600 transition-all duration-300"
+                                    style={format!("width: {}%", job.progress)}
+                                />
+                            </div>
+                            <p class="text-xs text-gray-500 dark:text-gray-400 truncate">
+                                { &job.message }
+                            </p>
+                        </div>
+                    }
+
                     // Error display
                     if let InstallState::Error(ref error) = *install_state {
                         <div class="bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-lg p-4">
@@ -437,13 +521,22 @@ pub fn install_skill_modal(props: &InstallSkillModalProps) -> Html {
 
                 // Footer
                 <div class="flex items-center justify-end gap-3 p-6 border-t border-gray-200 dark:border-gray-700 bg-gray-50 dark:bg-gray-800/50 rounded-b-xl">
-                    <button
-                        onclick={on_close}
-                        class="btn btn-secondary"
-                        disabled={is_installing}
-                    >
-                        { "Cancel" }
-                    </button>
+                    if matches!(*install_state, InstallState::Installing(_)) {
+                        <button
+                            onclick={on_cancel_install}
+                            class="btn btn-secondary"
+                        >
+                            { "Cancel Install" }
+                        </button>
+                    } else {
+                        <button
+                            onclick={on_close}
+                            class="btn btn-secondary"
+                            disabled={is_installing}
+                        >
+                            { "Cancel" }
+                        </button>
+                    }
                     <button
                         onclick={on_install}
                         class="btn btn-primary"
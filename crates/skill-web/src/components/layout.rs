@@ -4,6 +4,7 @@ use yew::prelude::*;
 use yew_router::prelude::*;
 
 use crate::router::Route;
+use super::command_palette::CommandPalette;
 use super::navbar::Navbar;
 use super::sidebar::Sidebar;
 use super::notifications::NotificationContainer;
@@ -25,6 +26,9 @@ pub fn layout(props: &LayoutProps) -> Html {
             // Global notification container
             <NotificationContainer />
 
+            // App-wide command palette (Ctrl+K)
+            <CommandPalette />
+
             if show_sidebar {
                 <Navbar />
                 <div class="flex">
@@ -0,0 +1,254 @@
+//! Markdown rendering for skill documentation
+//!
+//! Renders CommonMark (with tables, strikethrough and task lists) to HTML.
+//! Skill docs come from third-party skills, so raw HTML in the source is
+//! shown as text and links with script-capable schemes are dropped. Fenced
+//! code blocks get lightweight syntax highlighting for the languages skill
+//! docs use most; colors come from the theme's `--code-token-*` tokens.
+
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+use yew::prelude::*;
+
+/// Props for the Markdown component
+#[derive(Properties, PartialEq)]
+pub struct MarkdownProps {
+    /// Markdown source
+    pub content: AttrValue,
+    /// Extra classes for the wrapper
+    #[prop_or_default]
+    pub class: Classes,
+}
+
+/// Rendered markdown
+#[function_component(Markdown)]
+pub fn markdown(props: &MarkdownProps) -> Html {
+    let rendered = use_memo(props.content.clone(), |content| render_markdown(content));
+
+    html! {
+        <div class={classes!("markdown-body", props.class.clone())}>
+            { Html::from_html_unchecked(AttrValue::from((*rendered).clone())) }
+        </div>
+    }
+}
+
+/// Render markdown to sanitized HTML
+pub fn render_markdown(source: &str) -> String {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut events = Vec::new();
+    let mut code: Option<(String, String)> = None;
+
+    for event in Parser::new_ext(source, options) {
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let lang = match kind {
+                    CodeBlockKind::Fenced(info) => info.split_whitespace().next().unwrap_or("").to_lowercase(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                code = Some((lang, String::new()));
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some((lang, text)) = code.take() {
+                    let lang_attr = if lang.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" data-lang=\"{}\"", escape(&lang))
+                    };
+                    events.push(Event::Html(CowStr::from(format!(
+                        "<pre class=\"code-block\"{}><code>{}</code></pre>\n",
+                        lang_attr,
+                        highlight(&text, &lang)
+                    ))));
+                }
+            }
+            Event::Text(text) if code.is_some() => {
+                if let Some((_, buffer)) = code.as_mut() {
+                    buffer.push_str(&text);
+                }
+            }
+            // Raw HTML from the document is shown, never interpreted
+            Event::Html(raw) | Event::InlineHtml(raw) => events.push(Event::Text(raw)),
+            Event::Start(Tag::Link { link_type, dest_url, title, id }) => {
+                events.push(Event::Start(Tag::Link { link_type, dest_url: safe_url(dest_url), title, id }));
+            }
+            Event::Start(Tag::Image { link_type, dest_url, title, id }) => {
+                events.push(Event::Start(Tag::Image { link_type, dest_url: safe_url(dest_url), title, id }));
+            }
+            event => events.push(event),
+        }
+    }
+
+    let mut output = String::with_capacity(source.len() * 2);
+    html::push_html(&mut output, events.into_iter());
+    output
+}
+
+/// A link target with script-capable schemes replaced
+fn safe_url(url: CowStr<'_>) -> CowStr<'_> {
+    let scheme = url.trim_start().to_lowercase();
+    if ["javascript:", "vbscript:", "data:"].iter().any(|unsafe_scheme| scheme.starts_with(unsafe_scheme)) {
+        CowStr::from("#")
+    } else {
+        url
+    }
+}
+
+/// Escape text for HTML
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// How a language's source is tokenized
+struct Syntax {
+    line_comment: &'static [&'static str],
+    quotes: &'static [char],
+    keywords: &'static [&'static str],
+    /// Highlight `key:` / `key =` at the start of a line
+    keys: bool,
+}
+
+fn syntax(lang: &str) -> Option<Syntax> {
+    Some(match lang {
+        "bash" | "sh" | "shell" | "zsh" | "console" => Syntax {
+            line_comment: &["#"],
+            quotes: &['"', '\''],
+            keywords: &[
+                "if", "then", "else", "elif", "fi", "for", "in", "do", "done", "while", "case", "esac",
+                "function", "return", "export", "local", "echo", "skill",
+            ],
+            keys: false,
+        },
+        "json" => Syntax {
+            line_comment: &[],
+            quotes: &['"'],
+            keywords: &["true", "false", "null"],
+            keys: false,
+        },
+        "yaml" | "yml" | "toml" => Syntax {
+            line_comment: &["#"],
+            quotes: &['"', '\''],
+            keywords: &["true", "false", "null", "yes", "no"],
+            keys: true,
+        },
+        "rust" | "rs" => Syntax {
+            line_comment: &["//"],
+            quotes: &['"'],
+            keywords: &[
+                "fn", "let", "mut", "pub", "use", "mod", "struct", "enum", "impl", "trait", "match", "if",
+                "else", "for", "in", "while", "loop", "return", "async", "await", "self", "Self", "true",
+                "false", "const", "static", "where", "move", "ref", "crate", "super", "type", "dyn",
+            ],
+            keys: false,
+        },
+        "python" | "py" => Syntax {
+            line_comment: &["#"],
+            quotes: &['"', '\''],
+            keywords: &[
+                "def", "class", "import", "from", "as", "if", "elif", "else", "for", "in", "while",
+                "return", "with", "try", "except", "finally", "raise", "lambda", "yield", "async", "await",
+                "None", "True", "False", "and", "or", "not", "is", "pass",
+            ],
+            keys: false,
+        },
+        "javascript" | "js" | "typescript" | "ts" => Syntax {
+            line_comment: &["//"],
+            quotes: &['"', '\'', '`'],
+            keywords: &[
+                "function", "const", "let", "var", "if", "else", "for", "of", "in", "while", "return",
+                "import", "export", "from", "async", "await", "class", "new", "this", "true", "false",
+                "null", "undefined", "interface", "type",
+            ],
+            keys: false,
+        },
+        _ => return None,
+    })
+}
+
+fn token(class: &str, text: &str) -> String {
+    format!("<span class=\"code-token-{}\">{}</span>", class, escape(text))
+}
+
+/// Highlight code of a language, escaping it; unknown languages are only
+/// escaped
+pub fn highlight(code: &str, lang: &str) -> String {
+    let Some(syntax) = syntax(lang) else {
+        return escape(code);
+    };
+    let mut output = String::with_capacity(code.len() * 2);
+    for line in code.split_inclusive('\n') {
+        highlight_line(line, &syntax, &mut output);
+    }
+    output
+}
+
+fn highlight_line(line: &str, syntax: &Syntax, output: &mut String) {
+    let mut rest = line;
+
+    if syntax.keys {
+        let indent = rest.len() - rest.trim_start().len();
+        let body = &rest[indent..];
+        let key_len = body
+            .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.')))
+            .unwrap_or(body.len());
+        let after = body[key_len..].trim_start();
+        if key_len > 0 && (after.starts_with(':') || after.starts_with('=')) {
+            output.push_str(&escape(&rest[..indent]));
+            output.push_str(&token("key", &body[..key_len]));
+            rest = &body[key_len..];
+        }
+    }
+
+    while !rest.is_empty() {
+        if syntax.line_comment.iter().any(|prefix| rest.starts_with(prefix)) {
+            let end = rest.find('\n').unwrap_or(rest.len());
+            output.push_str(&token("comment", &rest[..end]));
+            rest = &rest[end..];
+            continue;
+        }
+
+        let c = rest.chars().next().unwrap_or_default();
+        if syntax.quotes.contains(&c) {
+            let mut end = rest.len();
+            let mut escaped = false;
+            for (i, next) in rest.char_indices().skip(1) {
+                if next == '\n' {
+                    end = i;
+                    break;
+                }
+                if next == c && !escaped {
+                    end = i + next.len_utf8();
+                    break;
+                }
+                escaped = next == '\\' && !escaped;
+            }
+            output.push_str(&token("string", &rest[..end]));
+            rest = &rest[end..];
+        } else if c.is_alphanumeric() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            if word.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+                output.push_str(&token("number", word));
+            } else if syntax.keywords.contains(&word) {
+                output.push_str(&token("keyword", word));
+            } else {
+                output.push_str(&escape(word));
+            }
+            rest = &rest[end..];
+        } else {
+            output.push_str(&escape(&rest[..c.len_utf8()]));
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+}
@@ -6,14 +6,17 @@ pub mod layout;
 pub mod navbar;
 pub mod sidebar;
 pub mod card;
+pub mod command_palette;
 pub mod button;
 pub mod icons;
 pub mod instance_editor;
+pub mod markdown;
 pub mod notifications;
 pub mod install_skill_modal;
 pub mod import_config_modal;
 pub mod run;
 pub mod result_card;
+pub mod search_compare;
 pub mod tooltip;
 pub mod searchable_select;
 
@@ -21,5 +24,6 @@ pub mod searchable_select;
 pub use notifications::use_notifications;
 pub use install_skill_modal::{InstallSkillModal, use_install_skill_modal};
 pub use import_config_modal::{ImportConfigModal, use_import_config_modal};
+pub use markdown::Markdown;
 pub use tooltip::Tooltip;
 pub use searchable_select::SearchableSelect;
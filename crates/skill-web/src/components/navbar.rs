@@ -2,22 +2,21 @@
 
 use yew::prelude::*;
 use yew_router::prelude::*;
+use yewdux::prelude::*;
 
 use crate::router::Route;
+use crate::store::ui::{UiAction, UiStore};
 use super::icons::{SettingsIcon, SearchIcon};
 
 /// Top navigation bar
 #[function_component(Navbar)]
 pub fn navbar() -> Html {
-    let search_query = use_state(String::new);
+    let ui_dispatch = use_dispatch::<UiStore>();
 
-    let on_search_input = {
-        let search_query = search_query.clone();
-        Callback::from(move |e: InputEvent| {
-            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
-            search_query.set(input.value());
-        })
-    };
+    // The search bar opens the command palette
+    let on_search_click = Callback::from(move |_: MouseEvent| {
+        ui_dispatch.apply(UiAction::OpenCommandPalette);
+    });
 
     html! {
         <nav class="fixed top-0 left-0 right-0 h-16 bg-white dark:bg-gray-800 border-b border-gray-200 dark:border-gray-700 z-40">
@@ -34,18 +33,19 @@ pub fn navbar() -> Html {
 
                 // Search bar (centered)
                 <div class="flex-1 max-w-xl mx-8">
-                    <div class="relative">
-                        <div class="absolute inset-y-0 left-0 pl-3 flex items-center pointer-events-none">
+                    <button
+                        type="button"
+                        class="input relative w-full pl-10 text-left text-gray-400"
+                        onclick={on_search_click}
+                    >
+                        <span class="absolute inset-y-0 left-0 pl-3 flex items-center pointer-events-none">
                             <SearchIcon class="w-5 h-5 text-gray-400" />
-                        </div>
-                        <input
-                            type="text"
-                            placeholder="Search skills, tools, or commands..."
-                            class="input pl-10"
-                            value={(*search_query).clone()}
-                            oninput={on_search_input}
-                        />
-                    </div>
+                        </span>
+                        { "Search skills, tools, or commands..." }
+                        <kbd class="absolute inset-y-0 right-3 my-auto h-fit text-xs px-1.5 py-0.5 rounded border border-gray-300 dark:border-gray-600">
+                            { "Ctrl K" }
+                        </kbd>
+                    </button>
                 </div>
 
                 // Right side actions
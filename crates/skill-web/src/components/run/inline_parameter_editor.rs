@@ -3,12 +3,14 @@
 //! Features:
 //! - Inline single-line inputs with validation
 //! - Tab navigation between fields
-//! - Real-time validation (green checkmark / red X)
+//! - Real-time validation (green checkmark / red X) against the parameter's
+//!   type, enum values, pattern and bounds from SKILL.md
+//! - Dropdowns for parameters with enum values
 //! - JSON editor for complex parameters
 //! - Auto-focus first required field
 
 use yew::prelude::*;
-use web_sys::{HtmlInputElement, HtmlTextAreaElement, KeyboardEvent};
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement, KeyboardEvent};
 use wasm_bindgen::JsCast;
 use std::collections::HashMap;
 use crate::api::types::ParameterInfo;
@@ -26,6 +28,28 @@ pub struct InlineParameterEditorProps {
     pub errors: HashMap<String, String>,
 }
 
+/// Parse text typed for a parameter into the JSON value its type expects,
+/// keeping the text as a string when it doesn't parse
+pub fn parse_parameter_value(param_type: &str, value: &str) -> serde_json::Value {
+    match param_type {
+        "number" | "integer" => {
+            value.parse::<i64>()
+                .map(|n| serde_json::json!(n))
+                .unwrap_or_else(|_| serde_json::json!(value))
+        }
+        "boolean" => {
+            let bool_val = value.to_lowercase() == "true" || value == "1";
+            serde_json::json!(bool_val)
+        }
+        "array" | "object" => {
+            // Try to parse as JSON
+            serde_json::from_str(value)
+                .unwrap_or_else(|_| serde_json::json!(value))
+        }
+        _ => serde_json::json!(value),
+    }
+}
+
 #[function_component(InlineParameterEditor)]
 pub fn inline_parameter_editor(props: &InlineParameterEditorProps) -> Html {
     let first_input_ref = use_node_ref();
@@ -63,26 +87,7 @@ pub fn inline_parameter_editor(props: &InlineParameterEditorProps) -> Html {
                 let input: HtmlInputElement = e.target().unwrap().dyn_into().unwrap();
                 let value = input.value();
 
-                // Parse value based on type
-                let json_value = match param_type.as_str() {
-                    "number" | "integer" => {
-                        value.parse::<i64>()
-                            .map(|n| serde_json::json!(n))
-                            .unwrap_or_else(|_| serde_json::json!(value))
-                    }
-                    "boolean" => {
-                        let bool_val = value.to_lowercase() == "true" || value == "1";
-                        serde_json::json!(bool_val)
-                    }
-                    "array" | "object" => {
-                        // Try to parse as JSON
-                        serde_json::from_str(&value)
-                            .unwrap_or_else(|_| serde_json::json!(value))
-                    }
-                    _ => serde_json::json!(value),
-                };
-
-                on_change.emit((param_name.clone(), json_value));
+                on_change.emit((param_name.clone(), parse_parameter_value(&param_type, &value)));
             })
         }
     };
@@ -155,6 +160,61 @@ pub fn inline_parameter_editor(props: &InlineParameterEditorProps) -> Html {
     }
 }
 
+/// Check a value against the parameter's type and constraints, returning
+/// what's wrong with it; the server checks again before running the tool
+pub fn validate_parameter(param: &ParameterInfo, value: Option<&serde_json::Value>) -> Option<String> {
+    let text = match value {
+        None | Some(serde_json::Value::Null) => String::new(),
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    };
+    if text.is_empty() {
+        return param.required.then(|| "Required".to_string());
+    }
+
+    let number = text.trim().parse::<f64>().ok();
+    match param.param_type.as_str() {
+        "integer" if text.trim().parse::<i64>().is_err() => {
+            return Some("Must be a whole number".to_string());
+        }
+        "number" if number.is_none() => return Some("Must be a number".to_string()),
+        _ => {}
+    }
+
+    if !param.allowed_values.is_empty() && !param.allowed_values.contains(&text) {
+        return Some(format!("Must be one of: {}", param.allowed_values.join(", ")));
+    }
+    if let (Some(number), Some(minimum)) = (number, param.minimum) {
+        if number < minimum {
+            return Some(format!("Must be at least {}", minimum));
+        }
+    }
+    if let (Some(number), Some(maximum)) = (number, param.maximum) {
+        if number > maximum {
+            return Some(format!("Must be at most {}", maximum));
+        }
+    }
+    if let Some(pattern) = &param.pattern {
+        // An invalid pattern is left to the server
+        if let Some(regex) = js_regex(pattern) {
+            if !regex.test(&text) {
+                return Some(format!("Must match {}", pattern));
+            }
+        }
+    }
+    None
+}
+
+/// `pattern` as a JS RegExp, or None if it doesn't compile (`RegExp::new`
+/// would throw)
+fn js_regex(pattern: &str) -> Option<js_sys::RegExp> {
+    let constructor = js_sys::Reflect::get(&js_sys::global(), &"RegExp".into()).ok()?;
+    let args = js_sys::Array::of1(&pattern.into());
+    js_sys::Reflect::construct(constructor.unchecked_ref::<js_sys::Function>(), &args)
+        .ok()
+        .map(JsCast::unchecked_into)
+}
+
 // Helper function to render a single parameter
 fn render_parameter(
     param: &ParameterInfo,
@@ -170,10 +230,22 @@ fn render_parameter(
     let current_value = props.values.get(&param.name)
         .and_then(|v| serde_json::to_string(v).ok())
         .unwrap_or_default();
-    let has_error = props.errors.contains_key(&param.name);
-    let error_msg = props.errors.get(&param.name).cloned();
+    let current_text = match props.values.get(&param.name) {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(serde_json::Value::Null) | None => String::new(),
+        Some(other) => other.to_string(),
+    };
+    // Errors from the last run attempt, else live checks of entered values
+    let error_msg = props.errors.get(&param.name).cloned().or_else(|| {
+        props.values.get(&param.name)
+            .and_then(|value| validate_parameter(param, Some(value)))
+    });
+    let has_error = error_msg.is_some();
     let is_valid = !param.required || !current_value.is_empty();
     let is_complex = matches!(param_type.as_str(), "array" | "object");
+    let min = param.minimum.map(|m| m.to_string());
+    let max = param.maximum.map(|m| m.to_string());
+    let pattern = param.pattern.clone();
 
     html! {
         <div class="space-y-2">
@@ -201,7 +273,41 @@ fn render_parameter(
 
             // Input field with validation indicator
             <div class="relative flex items-center gap-2">
-                if is_complex {
+                if !param.allowed_values.is_empty() {
+                    // Dropdown for enum values
+                    <select
+                        class={classes!(
+                            "input",
+                            "flex-1",
+                            has_error.then_some("border-error-500 focus:border-error-500 focus:ring-error-500")
+                        )}
+                        onchange={{
+                            let on_change = props.on_change.clone();
+                            let param_name = param_name.clone();
+                            Callback::from(move |e: Event| {
+                                let select: HtmlSelectElement = e.target_unchecked_into();
+                                let value = select.value();
+                                let value = if value.is_empty() {
+                                    serde_json::Value::Null
+                                } else {
+                                    serde_json::json!(value)
+                                };
+                                on_change.emit((param_name.clone(), value));
+                            })
+                        }}
+                    >
+                        <option value="" selected={current_text.is_empty()}>
+                            { param.default_value.as_ref()
+                                .map(|d| format!("Default: {}", d))
+                                .unwrap_or_else(|| format!("Select {}", param.name)) }
+                        </option>
+                        { for param.allowed_values.iter().map(|value| html! {
+                            <option value={value.clone()} selected={*value == current_text}>
+                                { value }
+                            </option>
+                        }) }
+                    </select>
+                } else if is_complex {
                     // Textarea for arrays/objects
                     <textarea
                         class={classes!(
@@ -235,6 +341,9 @@ fn render_parameter(
                                 .map(|d| format!("Default: {}", d))
                                 .unwrap_or_else(|| format!("Enter {}", param.name))}
                             value={current_value.clone()}
+                            min={min.clone()}
+                            max={max.clone()}
+                            pattern={pattern.clone()}
                             oninput={make_on_input(param_name.clone(), param_type.clone())}
                             onkeydown={make_on_keydown(param_name.clone())}
                         />
@@ -267,6 +376,9 @@ fn render_parameter(
                             } else {
                                 current_value.clone()
                             }}
+                            min={min}
+                            max={max}
+                            pattern={pattern}
                             onchange={if param_type == "boolean" {
                                 let on_change = props.on_change.clone();
                                 let param_name = param_name.clone();
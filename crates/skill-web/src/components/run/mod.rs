@@ -7,12 +7,14 @@ mod skill_selector;
 mod tool_selector;
 #[allow(dead_code)]
 mod wizard_stepper;
-#[allow(dead_code)]
-mod command_palette;
 mod inline_parameter_editor;
+mod output_options_editor;
+mod save_preset_form;
 mod terminal_output;
 
-pub use inline_parameter_editor::InlineParameterEditor;
-pub use terminal_output::TerminalOutput;
+pub use inline_parameter_editor::{parse_parameter_value, validate_parameter, InlineParameterEditor};
+pub use output_options_editor::OutputOptionsEditor;
+pub use save_preset_form::SavePresetForm;
+pub use terminal_output::{TerminalLog, TerminalLogAction, TerminalOutput};
 
 // Re-export WizardStep from hooks for convenience
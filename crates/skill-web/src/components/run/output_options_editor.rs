@@ -0,0 +1,171 @@
+//! Output Options Editor - Collapsible output shaping controls
+//!
+//! Mirrors the MCP `execute` context-engineering options:
+//! - grep (optionally inverted) and head/tail line limits
+//! - jq filter for JSON output
+//! - format transformation and max_output truncation
+
+use yew::prelude::*;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use crate::api::types::OutputOptions;
+
+#[derive(Properties, PartialEq)]
+pub struct OutputOptionsEditorProps {
+    /// Current options
+    pub value: OutputOptions,
+    /// Callback when any option changes
+    pub on_change: Callback<OutputOptions>,
+}
+
+/// Empty input clears the option
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+#[function_component(OutputOptionsEditor)]
+pub fn output_options_editor(props: &OutputOptionsEditorProps) -> Html {
+    // Build an input callback that updates one field
+    let text_input = |update: fn(&mut OutputOptions, Option<String>)| {
+        let value = props.value.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut options = value.clone();
+            update(&mut options, non_empty(input.value()));
+            on_change.emit(options);
+        })
+    };
+    let number_input = |update: fn(&mut OutputOptions, Option<usize>)| {
+        let value = props.value.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut options = value.clone();
+            update(&mut options, input.value().trim().parse().ok());
+            on_change.emit(options);
+        })
+    };
+    let select_input = |update: fn(&mut OutputOptions, Option<String>)| {
+        let value = props.value.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let mut options = value.clone();
+            update(&mut options, non_empty(select.value()));
+            on_change.emit(options);
+        })
+    };
+
+    let on_grep_invert = {
+        let value = props.value.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut options = value.clone();
+            options.grep_invert = input.checked();
+            on_change.emit(options);
+        })
+    };
+
+    let options = &props.value;
+    let number_value = |n: Option<usize>| n.map(|n| n.to_string()).unwrap_or_default();
+    let label_class = "block text-xs font-medium text-gray-700 dark:text-gray-300 mb-1";
+
+    html! {
+        <details class="mt-6 group" open={*options != OutputOptions::default()}>
+            <summary class="cursor-pointer text-sm font-medium text-gray-700 dark:text-gray-300 select-none">
+                { "Output options" }
+                <span class="ml-2 text-xs font-normal text-gray-500 dark:text-gray-400">
+                    { "grep, head/tail, jq, format, max output" }
+                </span>
+            </summary>
+
+            <div class="mt-4 grid grid-cols-1 md:grid-cols-2 gap-4">
+                <div>
+                    <label class={label_class}>{ "grep (regex)" }</label>
+                    <input
+                        type="text"
+                        class="input w-full font-mono text-sm"
+                        placeholder="Running|Pending"
+                        value={options.grep.clone().unwrap_or_default()}
+                        oninput={text_input(|o, v| o.grep = v)}
+                    />
+                    <label class="mt-1 flex items-center gap-2 text-xs text-gray-600 dark:text-gray-400">
+                        <input type="checkbox" checked={options.grep_invert} onchange={on_grep_invert} />
+                        { "Invert match" }
+                    </label>
+                </div>
+
+                <div>
+                    <label class={label_class}>{ "jq filter" }</label>
+                    <input
+                        type="text"
+                        class="input w-full font-mono text-sm"
+                        placeholder=".items[] | select(.status == \"Running\") | .name"
+                        value={options.jq.clone().unwrap_or_default()}
+                        oninput={text_input(|o, v| o.jq = v)}
+                    />
+                </div>
+
+                <div class="grid grid-cols-2 gap-4">
+                    <div>
+                        <label class={label_class}>{ "Head (lines)" }</label>
+                        <input
+                            type="number"
+                            min="0"
+                            class="input w-full text-sm"
+                            value={number_value(options.head)}
+                            oninput={number_input(|o, v| o.head = v)}
+                        />
+                    </div>
+                    <div>
+                        <label class={label_class}>{ "Tail (lines)" }</label>
+                        <input
+                            type="number"
+                            min="0"
+                            class="input w-full text-sm"
+                            value={number_value(options.tail)}
+                            oninput={number_input(|o, v| o.tail = v)}
+                        />
+                    </div>
+                </div>
+
+                <div>
+                    <label class={label_class}>{ "Format" }</label>
+                    <select class="input w-full text-sm" onchange={select_input(|o, v| o.format = v)}>
+                        <option value="" selected={options.format.is_none()}>{ "Raw" }</option>
+                        { for ["json", "lines", "count", "compact"].iter().map(|format| html! {
+                            <option value={*format} selected={options.format.as_deref() == Some(*format)}>
+                                { *format }
+                            </option>
+                        }) }
+                    </select>
+                </div>
+
+                <div class="grid grid-cols-2 gap-4">
+                    <div>
+                        <label class={label_class}>{ "Max output (chars)" }</label>
+                        <input
+                            type="number"
+                            min="0"
+                            class="input w-full text-sm"
+                            value={number_value(options.max_output)}
+                            oninput={number_input(|o, v| o.max_output = v)}
+                        />
+                    </div>
+                    <div>
+                        <label class={label_class}>{ "Truncate" }</label>
+                        <select class="input w-full text-sm" onchange={select_input(|o, v| o.truncate = v)}>
+                            { for ["smart", "head", "tail", "middle"].iter().map(|strategy| html! {
+                                <option value={*strategy} selected={options.truncate.as_deref().unwrap_or("smart") == *strategy}>
+                                    { *strategy }
+                                </option>
+                            }) }
+                        </select>
+                    </div>
+                </div>
+            </div>
+        </details>
+    }
+}
@@ -0,0 +1,143 @@
+//! Save Preset Form - "Save this run" on the Run page
+//!
+//! Saves the selected skill, tool, instance and arguments as a named preset
+//! that can be re-run from the dashboard or pinned to the sidebar.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::api::{Api, CreatePresetRequest};
+use crate::components::notifications::use_notifications;
+use crate::store::presets::{PresetsAction, PresetsStore};
+
+#[derive(Properties, PartialEq)]
+pub struct SavePresetFormProps {
+    /// Skill to save
+    pub skill: String,
+    /// Tool to save
+    pub tool: String,
+    /// Instance to save, `None` for the default one
+    #[prop_or_default]
+    pub instance: Option<String>,
+    /// Arguments to save
+    pub args: HashMap<String, serde_json::Value>,
+}
+
+#[function_component(SavePresetForm)]
+pub fn save_preset_form(props: &SavePresetFormProps) -> Html {
+    let open = use_state(|| false);
+    let name = use_state(String::new);
+    let pinned = use_state(|| false);
+    let saving = use_state(|| false);
+    let presets_dispatch = use_dispatch::<PresetsStore>();
+    let notifications = use_notifications();
+    let api = use_memo((), |_| Rc::new(Api::new()));
+
+    let on_open = {
+        let open = open.clone();
+        let name = name.clone();
+        let default_name = format!("{} {}", props.skill, props.tool);
+        Callback::from(move |_: MouseEvent| {
+            name.set(default_name.clone());
+            open.set(true);
+        })
+    };
+
+    let on_cancel = {
+        let open = open.clone();
+        Callback::from(move |_: MouseEvent| open.set(false))
+    };
+
+    let on_name = {
+        let name = name.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            name.set(input.value());
+        })
+    };
+
+    let on_pinned = {
+        let pinned = pinned.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            pinned.set(input.checked());
+        })
+    };
+
+    let on_save = {
+        let request = CreatePresetRequest {
+            name: name.trim().to_string(),
+            skill: props.skill.clone(),
+            tool: props.tool.clone(),
+            instance: props.instance.clone(),
+            args: props.args.clone(),
+            pinned: *pinned,
+        };
+        let open = open.clone();
+        let saving = saving.clone();
+        let api = api.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            if request.name.is_empty() || *saving {
+                return;
+            }
+            saving.set(true);
+            let request = request.clone();
+            let open = open.clone();
+            let saving = saving.clone();
+            let api = api.clone();
+            let presets_dispatch = presets_dispatch.clone();
+            let notifications = notifications.clone();
+            spawn_local(async move {
+                match api.presets.create(&request).await {
+                    Ok(preset) => {
+                        notifications.success("Run saved", format!("Saved \"{}\"", preset.name));
+                        presets_dispatch.apply(PresetsAction::Upsert(preset));
+                        open.set(false);
+                    }
+                    Err(e) => notifications.error("Failed to save run", e.to_string()),
+                }
+                saving.set(false);
+            });
+        })
+    };
+
+    if !*open {
+        return html! {
+            <button type="button" class="btn btn-secondary px-4 py-2.5 rounded-lg" onclick={on_open}>
+                { "Save Run" }
+            </button>
+        };
+    }
+
+    html! {
+        <form class="flex flex-wrap items-center gap-3" onsubmit={on_save}>
+            <input
+                type="text"
+                class="input w-56"
+                placeholder="Preset name"
+                aria-label="Preset name"
+                value={(*name).clone()}
+                oninput={on_name}
+            />
+            <label class="flex items-center gap-2 text-sm text-gray-600 dark:text-gray-400">
+                <input type="checkbox" checked={*pinned} onchange={on_pinned} />
+                { "Pin to sidebar" }
+            </label>
+            <button
+                type="submit"
+                class="btn btn-secondary px-4 py-2.5 rounded-lg"
+                disabled={*saving || name.trim().is_empty()}
+            >
+                { if *saving { "Saving..." } else { "Save" } }
+            </button>
+            <button type="button" class="btn btn-ghost px-3 py-2.5 rounded-lg" onclick={on_cancel}>
+                { "Cancel" }
+            </button>
+        </form>
+    }
+}
@@ -2,14 +2,314 @@
 //!
 //! Features:
 //! - Slides up from bottom (60vh height)
-//! - Dark terminal background with monospace font
-//! - Syntax highlighting for JSON/YAML output
-//! - Copy button, Re-run button, Close button
+//! - Live output from `POST /api/execute/stream`, stdout and stderr interleaved
+//! - ANSI colors and styles (16, 256 and true-color SGR codes)
+//! - Terminal colors from the theme's `--terminal-output-*` and `--ansi-*` tokens
+//! - Auto-scroll that pauses when scrolled up, with a toggle to resume
+//! - Search within the output, Enter / Shift+Enter to step through matches
+//! - Copy, Download, Re-run and Close buttons
 //! - Minimize to thin bar at bottom
-//! - Auto-scroll to bottom for streaming output
 
+use std::rc::Rc;
+
+use wasm_bindgen::JsCast;
 use yew::prelude::*;
-use crate::api::types::{ExecutionResponse, ExecutionStatus};
+
+use crate::api::types::{ExecutionResponse, ExecutionStatus, OutputStream};
+
+/// Output received so far from a streaming execution
+#[derive(Clone, Default, PartialEq)]
+pub struct TerminalLog {
+    chunks: Vec<Rc<TerminalChunk>>,
+    /// Style in effect at the end of each stream's output so far
+    styles: [AnsiStyle; 2],
+    /// Escape sequence cut off at the end of each stream's output so far
+    pending: [String; 2],
+    /// Bytes of stdout received, escape codes included
+    stdout_len: usize,
+}
+
+/// Changes to a [`TerminalLog`]
+pub enum TerminalLogAction {
+    /// Start over for a new execution
+    Clear,
+    /// Output arrived on a stream
+    Append(OutputStream, String),
+}
+
+impl Reducible for TerminalLog {
+    type Action = TerminalLogAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            TerminalLogAction::Clear => Rc::new(Self::default()),
+            TerminalLogAction::Append(stream, text) => {
+                let mut log = (*self).clone();
+                let index = match stream {
+                    OutputStream::Stdout => 0,
+                    OutputStream::Stderr => 1,
+                };
+                if stream == OutputStream::Stdout {
+                    log.stdout_len += text.len();
+                }
+                let input = std::mem::take(&mut log.pending[index]) + &text;
+                let mut segments = Vec::new();
+                log.pending[index] = parse_ansi(&input, &mut log.styles[index], &mut segments);
+                if !segments.is_empty() {
+                    log.chunks.push(Rc::new(TerminalChunk { stream, segments }));
+                }
+                Rc::new(log)
+            }
+        }
+    }
+}
+
+impl TerminalLog {
+    /// Whether no output has arrived
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+}
+
+/// A piece of output from one stream
+#[derive(Debug, PartialEq)]
+pub struct TerminalChunk {
+    stream: OutputStream,
+    segments: Vec<AnsiSegment>,
+}
+
+impl TerminalChunk {
+    fn stdout(text: &str) -> Self {
+        let mut segments = Vec::new();
+        parse_ansi(text, &mut AnsiStyle::default(), &mut segments);
+        Self {
+            stream: OutputStream::Stdout,
+            segments,
+        }
+    }
+}
+
+/// Text that shares one style
+#[derive(Debug, Clone, PartialEq)]
+struct AnsiSegment {
+    style: AnsiStyle,
+    text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AnsiColor {
+    /// One of the 256 palette colors; the first 16 follow the theme
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl AnsiColor {
+    /// Theme class of one of the 16 base colors, e.g. `ansi-fg-1`
+    fn class(self, property: &str) -> Option<String> {
+        match self {
+            Self::Indexed(index) if index < 16 => Some(format!("ansi-{}-{}", property, index)),
+            _ => None,
+        }
+    }
+
+    /// CSS value of any other color
+    fn rgb(self) -> Option<String> {
+        let (r, g, b) = match self {
+            Self::Indexed(index) if index < 16 => return None,
+            Self::Indexed(index) => palette_rgb(index),
+            Self::Rgb(r, g, b) => (r, g, b),
+        };
+        Some(format!("rgb({}, {}, {})", r, g, b))
+    }
+}
+
+/// RGB of a 256-color palette entry past the first 16
+fn palette_rgb(index: u8) -> (u8, u8, u8) {
+    if index >= 232 {
+        let level = 8 + 10 * (index - 232);
+        return (level, level, level);
+    }
+    let cube = index - 16;
+    let level = |value: u8| if value == 0 { 0 } else { 55 + 40 * value };
+    (level(cube / 36), level((cube / 6) % 6), level(cube % 6))
+}
+
+/// SGR state
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct AnsiStyle {
+    fg: Option<AnsiColor>,
+    bg: Option<AnsiColor>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+}
+
+impl AnsiStyle {
+    /// Apply the parameters of an `ESC [ ... m` sequence
+    fn apply_sgr(&mut self, params: &str) {
+        let mut codes = params
+            .split(';')
+            .map(|code| code.parse::<u16>().unwrap_or(0));
+        while let Some(code) = codes.next() {
+            match code {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                22 => {
+                    self.bold = false;
+                    self.dim = false;
+                }
+                23 => self.italic = false,
+                24 => self.underline = false,
+                30..=37 => self.fg = Some(AnsiColor::Indexed((code - 30) as u8)),
+                38 => self.fg = extended_color(&mut codes),
+                39 => self.fg = None,
+                40..=47 => self.bg = Some(AnsiColor::Indexed((code - 40) as u8)),
+                48 => self.bg = extended_color(&mut codes),
+                49 => self.bg = None,
+                90..=97 => self.fg = Some(AnsiColor::Indexed((code - 90 + 8) as u8)),
+                100..=107 => self.bg = Some(AnsiColor::Indexed((code - 100 + 8) as u8)),
+                _ => {}
+            }
+        }
+    }
+
+    fn classes_and_style(&self) -> (Classes, Option<String>) {
+        let mut classes = Classes::new();
+        let mut style = Vec::new();
+        for (color, property, css_property) in
+            [(self.fg, "fg", "color"), (self.bg, "bg", "background-color")]
+        {
+            let Some(color) = color else { continue };
+            if let Some(class) = color.class(property) {
+                classes.push(class);
+            }
+            if let Some(rgb) = color.rgb() {
+                style.push(format!("{}: {}", css_property, rgb));
+            }
+        }
+        if self.bold {
+            classes.push("font-bold");
+        }
+        if self.dim {
+            classes.push("opacity-70");
+        }
+        if self.italic {
+            classes.push("italic");
+        }
+        if self.underline {
+            classes.push("underline");
+        }
+        (classes, (!style.is_empty()).then(|| style.join("; ")))
+    }
+}
+
+/// The color after `38;` or `48;`: `5;n` or `2;r;g;b`
+fn extended_color(codes: &mut impl Iterator<Item = u16>) -> Option<AnsiColor> {
+    let mut next = || codes.next().map(|code| code.min(255) as u8);
+    match next()? {
+        5 => Some(AnsiColor::Indexed(next()?)),
+        2 => Some(AnsiColor::Rgb(next()?, next()?, next()?)),
+        _ => None,
+    }
+}
+
+/// Split output into styled segments, applying SGR codes and dropping other
+/// escape sequences and carriage returns. Returns an escape sequence cut off
+/// at the end, to be parsed with the next piece of output.
+fn parse_ansi(input: &str, style: &mut AnsiStyle, segments: &mut Vec<AnsiSegment>) -> String {
+    let mut text = String::new();
+    let mut flush = |text: &mut String, style: AnsiStyle| {
+        if text.is_empty() {
+            return;
+        }
+        match segments.last_mut() {
+            Some(last) if last.style == style => last.text.push_str(text),
+            _ => segments.push(AnsiSegment {
+                style,
+                text: text.clone(),
+            }),
+        }
+        text.clear();
+    };
+
+    let mut rest = input;
+    while let Some(position) = rest.find(['\x1b', '\r']) {
+        text.push_str(&rest[..position]);
+        let tail = &rest[position..];
+        if let Some(after) = tail.strip_prefix('\r') {
+            rest = after;
+            continue;
+        }
+
+        let body = &tail[1..];
+        let end = match body.chars().next() {
+            // CSI: parameters, then a final byte in `@`..`~`
+            Some('[') => body[1..]
+                .find(|c: char| ('\x40'..='\x7e').contains(&c))
+                .map(|end| end + 2),
+            // OSC: ends with BEL or ST
+            Some(']') => body
+                .find('\x07')
+                .map(|end| end + 1)
+                .or_else(|| body.find("\x1b\\").map(|end| end + 2)),
+            Some(c) => Some(c.len_utf8()),
+            None => None,
+        };
+        let Some(end) = end else {
+            flush(&mut text, *style);
+            return tail.to_string();
+        };
+        if body.starts_with('[') && body[..end].ends_with('m') {
+            flush(&mut text, *style);
+            style.apply_sgr(&body[1..end - 1]);
+        }
+        rest = &body[end..];
+    }
+    text.push_str(rest);
+    flush(&mut text, *style);
+    String::new()
+}
+
+/// Positions of `query` in `text`, ignoring ASCII case
+fn find_matches(text: &str, query: &str) -> Vec<(usize, usize)> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let haystack = text.to_ascii_lowercase();
+    let needle = query.to_ascii_lowercase();
+    haystack
+        .match_indices(&needle)
+        .map(|(start, found)| (start, start + found.len()))
+        .collect()
+}
+
+/// Offer text as a file download
+fn download_text(filename: &str, text: &str) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    let parts = js_sys::Array::of1(&wasm_bindgen::JsValue::from_str(text));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type("text/plain;charset=utf-8");
+    let Ok(blob) = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options) else {
+        return;
+    };
+    let Ok(url) = web_sys::Url::create_object_url_with_blob(&blob) else {
+        return;
+    };
+    if let Ok(link) = document.create_element("a") {
+        let _ = link.set_attribute("href", &url);
+        let _ = link.set_attribute("download", filename);
+        if let Ok(link) = link.dyn_into::<web_sys::HtmlElement>() {
+            link.click();
+        }
+    }
+    let _ = web_sys::Url::revoke_object_url(&url);
+}
 
 #[derive(Properties, PartialEq)]
 pub struct TerminalOutputProps {
@@ -17,6 +317,12 @@ pub struct TerminalOutputProps {
     pub visible: bool,
     /// Execution result to display
     pub execution: Option<ExecutionResponse>,
+    /// Output streamed while the execution ran
+    #[prop_or_default]
+    pub log: TerminalLog,
+    /// Whether the execution is still running
+    #[prop_or(false)]
+    pub running: bool,
     /// Callback to close the terminal
     pub on_close: Callback<()>,
     /// Callback to re-run the command
@@ -35,16 +341,117 @@ pub struct TerminalOutputProps {
 #[function_component(TerminalOutput)]
 pub fn terminal_output(props: &TerminalOutputProps) -> Html {
     let terminal_ref = use_node_ref();
+    let follow = use_state_eq(|| true);
+    let query = use_state(String::new);
+    let current_match = use_state(|| 0usize);
 
-    // Auto-scroll to bottom when content changes
-    use_effect_with((props.execution.clone(), terminal_ref.clone()), |(execution, terminal_ref)| {
-        if execution.is_some() {
-            if let Some(terminal) = terminal_ref.cast::<web_sys::HtmlElement>() {
-                terminal.set_scroll_top(terminal.scroll_height());
+    // Once finished, JSON is pretty-printed, and output shaped by the output
+    // options replaces the raw stream
+    let chunks: Vec<Rc<TerminalChunk>> = match &props.execution {
+        Some(exec) if !props.running => {
+            let pretty = serde_json::from_str::<serde_json::Value>(&exec.output)
+                .ok()
+                .and_then(|json| serde_json::to_string_pretty(&json).ok());
+            match pretty {
+                Some(pretty) => vec![Rc::new(TerminalChunk::stdout(&pretty))],
+                None if props.log.is_empty() || exec.output.len() != props.log.stdout_len => {
+                    vec![Rc::new(TerminalChunk::stdout(&exec.output))]
+                }
+                None => props.log.chunks.clone(),
             }
         }
-        || ()
-    });
+        _ => props.log.chunks.clone(),
+    };
+    let has_stderr = chunks.iter().any(|chunk| chunk.stream == OutputStream::Stderr);
+    let plain_text: String = chunks
+        .iter()
+        .flat_map(|chunk| chunk.segments.iter())
+        .map(|segment| segment.text.as_str())
+        .collect();
+    let match_count = find_matches(&plain_text, &query).len();
+
+    // Keep the newest output in view unless auto-scroll is paused
+    {
+        let terminal_ref = terminal_ref.clone();
+        let output_len = plain_text.len();
+        use_effect_with(
+            (output_len, props.execution.clone(), *follow, props.minimized),
+            move |(_, _, follow, _)| {
+                if *follow {
+                    if let Some(terminal) = terminal_ref.cast::<web_sys::HtmlElement>() {
+                        terminal.set_scroll_top(terminal.scroll_height());
+                    }
+                }
+                || ()
+            },
+        );
+    }
+
+    // Bring the current search match into view
+    {
+        let terminal_ref = terminal_ref.clone();
+        use_effect_with(((*query).clone(), *current_match), move |_| {
+            if let Some(mark) = terminal_ref
+                .cast::<web_sys::Element>()
+                .and_then(|terminal| terminal.query_selector(".terminal-search-current").ok().flatten())
+            {
+                let options = web_sys::ScrollIntoViewOptions::new();
+                options.set_block(web_sys::ScrollLogicalPosition::Center);
+                mark.scroll_into_view_with_scroll_into_view_options(&options);
+            }
+            || ()
+        });
+    }
+
+    // Scrolling up pauses auto-scroll; scrolling back to the bottom resumes it
+    let on_scroll = {
+        let follow = follow.clone();
+        let terminal_ref = terminal_ref.clone();
+        Callback::from(move |_: Event| {
+            if let Some(terminal) = terminal_ref.cast::<web_sys::HtmlElement>() {
+                let bottom = terminal.scroll_top() + terminal.client_height();
+                follow.set(bottom >= terminal.scroll_height() - 8);
+            }
+        })
+    };
+
+    let on_follow_click = {
+        let follow = follow.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            follow.set(!*follow);
+        })
+    };
+
+    let on_search_input = {
+        let query = query.clone();
+        let current_match = current_match.clone();
+        let follow = follow.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            if !input.value().is_empty() {
+                follow.set(false);
+            }
+            query.set(input.value());
+            current_match.set(0);
+        })
+    };
+
+    let on_search_keydown = {
+        let current_match = current_match.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() != "Enter" || match_count == 0 {
+                return;
+            }
+            e.prevent_default();
+            let next = if e.shift_key() {
+                (*current_match + match_count - 1) % match_count
+            } else {
+                (*current_match + 1) % match_count
+            };
+            current_match.set(next);
+        })
+    };
 
     // Handle close
     let on_close_click = {
@@ -78,35 +485,36 @@ pub fn terminal_output(props: &TerminalOutputProps) -> Html {
     // Handle copy
     let on_copy_click = {
         let on_copy = props.on_copy.clone();
-        let output = props.execution.as_ref().map(|e| e.output.clone());
+        let output = plain_text.clone();
         Callback::from(move |e: MouseEvent| {
             e.prevent_default();
             if let Some(callback) = &on_copy {
-                if let Some(output) = &output {
-                    callback.emit(output.clone());
-                }
+                callback.emit(output.clone());
             }
         })
     };
 
-    // Format output with syntax highlighting (simple for now)
-    let formatted_output = props.execution.as_ref().map(|exec| {
-        // Try to parse as JSON for pretty printing
-        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&exec.output) {
-            serde_json::to_string_pretty(&json).unwrap_or_else(|_| exec.output.clone())
-        } else {
-            exec.output.clone()
-        }
-    });
+    // Handle download
+    let on_download_click = {
+        let output = plain_text.clone();
+        let filename = match &props.execution {
+            Some(exec) => format!("execution-{}.log", exec.id),
+            None => "execution.log".to_string(),
+        };
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            download_text(&filename, &output);
+        })
+    };
 
     // Get status color class
     let status_class = props.execution.as_ref().map(|exec| {
         match exec.status {
-            ExecutionStatus::Success => "text-success-500",
-            ExecutionStatus::Failed | ExecutionStatus::Timeout => "text-error-500",
-            ExecutionStatus::Running => "text-primary-500",
-            ExecutionStatus::Pending => "text-gray-500 dark:text-gray-400",
-            ExecutionStatus::Cancelled => "text-warning-500",
+            ExecutionStatus::Success => "terminal-output-success",
+            ExecutionStatus::Failed | ExecutionStatus::Timeout => "terminal-output-error",
+            ExecutionStatus::Running => "terminal-output-running",
+            ExecutionStatus::Pending => "terminal-output-muted",
+            ExecutionStatus::Cancelled => "terminal-output-warning",
         }
     });
 
@@ -114,11 +522,40 @@ pub fn terminal_output(props: &TerminalOutputProps) -> Html {
         return html! {};
     }
 
+    // Render the output, marking search matches within each segment
+    let mut match_index = 0;
+    let output: Html = chunks
+        .iter()
+        .flat_map(|chunk| chunk.segments.iter().map(move |segment| (chunk.stream, segment)))
+        .map(|(stream, segment)| {
+            let (mut classes, style) = segment.style.classes_and_style();
+            if stream == OutputStream::Stderr && segment.style.fg.is_none() {
+                classes.push("terminal-output-stderr");
+            }
+            let mut parts = Vec::new();
+            let mut last = 0;
+            for (start, end) in find_matches(&segment.text, &query) {
+                parts.push(html! { { &segment.text[last..start] } });
+                let current = match_index == *current_match;
+                parts.push(html! {
+                    <mark class={classes!("terminal-search-match", current.then_some("terminal-search-current"))}>
+                        { &segment.text[start..end] }
+                    </mark>
+                });
+                match_index += 1;
+                last = end;
+            }
+            parts.push(html! { { &segment.text[last..] } });
+            html! { <span class={classes} style={style}>{ for parts }</span> }
+        })
+        .collect();
+
+    let icon_button = "p-2 rounded hover:bg-gray-200 dark:hover:bg-gray-700 text-gray-700 dark:text-gray-300 transition-colors";
+
     html! {
         <div class={classes!(
             "fixed", "bottom-0", "left-0", "right-0",
-            "bg-white", "dark:bg-gray-900",
-            "border-t", "border-gray-200", "dark:border-gray-700",
+            "terminal-output", "border-t",
             "shadow-lg",
             "z-50",
             "transition-all", "duration-200",
@@ -126,20 +563,25 @@ pub fn terminal_output(props: &TerminalOutputProps) -> Html {
             if props.minimized { "h-14" } else { "h-[60vh]" }
         )}>
             // Header bar
-            <div class="flex items-center justify-between px-6 py-3 bg-gray-100 dark:bg-gray-800 border-b border-gray-200 dark:border-gray-700">
+            <div class="flex items-center justify-between px-6 py-3 terminal-output-header border-b">
                 <div class="flex items-center gap-4">
                     // Status indicator
-                    if let Some(exec) = &props.execution {
+                    if props.running {
+                        <div class="flex items-center gap-2">
+                            <span class="text-sm font-semibold terminal-output-running">{ "Running" }</span>
+                            <span class="animate-pulse terminal-output-running">{ "..." }</span>
+                        </div>
+                    } else if let Some(exec) = &props.execution {
                         <div class="flex items-center gap-2">
                             <span class={classes!("text-sm", "font-semibold", status_class)}>
                                 { format!("{:?}", exec.status) }
                             </span>
-                            <span class="text-xs text-gray-500 dark:text-gray-400">
+                            <span class="text-xs terminal-output-muted">
                                 { format!("({}ms)", exec.duration_ms) }
                             </span>
                         </div>
                     } else {
-                        <span class="text-sm text-gray-500 dark:text-gray-400">
+                        <span class="text-sm terminal-output-muted">
                             { "Waiting for execution..." }
                         </span>
                     }
@@ -147,13 +589,58 @@ pub fn terminal_output(props: &TerminalOutputProps) -> Html {
 
                 // Action buttons
                 <div class="flex items-center gap-2">
+                    // Search
+                    if !props.minimized {
+                        <div class="flex items-center gap-2 mr-2">
+                            <input
+                                type="search"
+                                class="input text-xs py-1 w-48"
+                                placeholder="Search output"
+                                value={(*query).clone()}
+                                oninput={on_search_input}
+                                onkeydown={on_search_keydown}
+                            />
+                            if !query.is_empty() {
+                                <span class="text-xs terminal-output-muted whitespace-nowrap">
+                                    { if match_count == 0 {
+                                        "No matches".to_string()
+                                    } else {
+                                        format!("{} of {}", *current_match + 1, match_count)
+                                    } }
+                                </span>
+                            }
+                        </div>
+                    }
+
+                    // Auto-scroll toggle
+                    <button
+                        onclick={on_follow_click}
+                        class={icon_button}
+                        title={if *follow { "Pause auto-scroll" } else { "Resume auto-scroll" }}
+                    >
+                        if *follow {
+                            <svg class="w-4 h-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M10 9v6m4-6v6m7-3a9 9 0 11-18 0 9 9 0 0118 0z" />
+                            </svg>
+                        } else {
+                            <svg class="w-4 h-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M19 14l-7 7m0 0l-7-7m7 7V3" />
+                            </svg>
+                        }
+                    </button>
+
+                    // Download button
+                    if !plain_text.is_empty() {
+                        <button onclick={on_download_click} class={icon_button} title="Download output">
+                            <svg class="w-4 h-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M4 16v1a3 3 0 003 3h10a3 3 0 003-3v-1m-4-4l-4 4m0 0l-4-4m4 4V4" />
+                            </svg>
+                        </button>
+                    }
+
                     // Copy button
-                    if props.on_copy.is_some() && props.execution.is_some() {
-                        <button
-                            onclick={on_copy_click}
-                            class="p-2 rounded hover:bg-gray-200 dark:hover:bg-gray-700 text-gray-700 dark:text-gray-300 transition-colors"
-                            title="Copy output"
-                        >
+                    if props.on_copy.is_some() && !plain_text.is_empty() {
+                        <button onclick={on_copy_click} class={icon_button} title="Copy output">
                             <svg class="w-4 h-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
                                 <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 16H6a2 2 0 01-2-2V6a2 2 0 012-2h8a2 2 0 012 2v2m-6 12h8a2 2 0 002-2v-8a2 2 0 00-2-2h-8a2 2 0 00-2 2v8a2 2 0 002 2z" />
                             </svg>
@@ -161,12 +648,8 @@ pub fn terminal_output(props: &TerminalOutputProps) -> Html {
                     }
 
                     // Re-run button
-                    if props.on_rerun.is_some() {
-                        <button
-                            onclick={on_rerun_click}
-                            class="p-2 rounded hover:bg-gray-200 dark:hover:bg-gray-700 text-gray-700 dark:text-gray-300 transition-colors"
-                            title="Re-run command"
-                        >
+                    if props.on_rerun.is_some() && !props.running {
+                        <button onclick={on_rerun_click} class={icon_button} title="Re-run command">
                             <svg class="w-4 h-4" fill="none" viewBox="0 0 24 24" stroke="currentColor">
                                 <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M4 4v5h.582m15.356 2A8.001 8.001 0 004.582 9m0 0H9m11 11v-5h-.581m0 0a8.003 8.003 0 01-15.357-2m15.357 2H15" />
                             </svg>
@@ -176,7 +659,7 @@ pub fn terminal_output(props: &TerminalOutputProps) -> Html {
                     // Minimize/Maximize button
                     <button
                         onclick={on_minimize_click}
-                        class="p-2 rounded hover:bg-gray-200 dark:hover:bg-gray-700 text-gray-700 dark:text-gray-300 transition-colors"
+                        class={icon_button}
                         title={if props.minimized { "Maximize" } else { "Minimize" }}
                     >
                         if props.minimized {
@@ -207,51 +690,60 @@ pub fn terminal_output(props: &TerminalOutputProps) -> Html {
             if !props.minimized {
                 <div
                     ref={terminal_ref}
-                    class="p-6 overflow-y-auto h-[calc(100%-56px)] bg-gray-50 dark:bg-gray-900"
+                    onscroll={on_scroll}
+                    class="p-6 overflow-y-auto h-[calc(100%-56px)] terminal-output"
                 >
                     if let Some(exec) = &props.execution {
                         // Command executed indicator
-                        <div class="text-gray-600 dark:text-gray-400 mb-4 font-mono text-sm">
+                        <div class="terminal-output-muted mb-4 font-mono text-sm">
                             <span>{ "$ " }</span>
-                            <span class="text-primary-500">{ &exec.id }</span>
+                            <span class="terminal-output-prompt">{ &exec.id }</span>
                         </div>
+                    }
 
-                        // Output
-                        <pre class={classes!(
-                            "whitespace-pre-wrap",
-                            "break-words",
-                            "font-mono",
-                            "text-sm",
-                            "text-gray-900",
-                            "dark:text-gray-100",
-                            status_class
-                        )}>
-                            { formatted_output.as_ref().unwrap_or(&exec.output) }
+                    if !chunks.is_empty() {
+                        <pre class="whitespace-pre-wrap break-words font-mono text-sm">
+                            { output }
                         </pre>
+                    } else if props.running {
+                        // Waiting state
+                        <div class="flex items-center gap-2 terminal-output-muted font-mono text-sm">
+                            <span>{ "Executing" }</span>
+                            <span class="animate-pulse">{ "..." }</span>
+                        </div>
+                    }
 
-                        // Error message (if any)
-                        if let Some(error) = &exec.error {
-                            <div class="mt-4 p-4 bg-error-50 dark:bg-error-900/20 border-l-4 border-error-500 rounded">
-                                <div class="text-error-500 font-semibold mb-2">
-                                    { "Error:" }
+                    if let Some(exec) = props.execution.as_ref().filter(|_| !props.running) {
+                        // Error message, unless stderr already streamed in
+                        if let Some(error) = exec.error.as_ref().filter(|_| !has_stderr) {
+                            <div class="mt-4 p-4 terminal-output-error-box border-l-4 rounded">
+                                <div class="terminal-output-error font-semibold mb-2">
+                                    { match exec.exit_code {
+                                        Some(code) => format!("Error (exit code {}):", code),
+                                        None => "Error:".to_string(),
+                                    } }
                                 </div>
-                                <pre class="text-error-500 whitespace-pre-wrap font-mono text-sm">
+                                <pre class="terminal-output-error whitespace-pre-wrap font-mono text-sm">
                                     { error }
                                 </pre>
                             </div>
+                        } else if exec.status != ExecutionStatus::Success {
+                            if let Some(code) = exec.exit_code {
+                                <div class="mt-4 terminal-output-error font-mono text-sm">
+                                    { format!("✗ Exited with code {} ({}ms)", code, exec.duration_ms) }
+                                </div>
+                            }
                         }
 
                         // Success indicator
                         if exec.status == ExecutionStatus::Success {
-                            <div class="mt-4 text-success-500 font-mono text-sm">
+                            <div class="mt-4 terminal-output-success font-mono text-sm">
                                 { format!("✓ Success ({}ms)", exec.duration_ms) }
                             </div>
                         }
-                    } else {
-                        // Waiting state
-                        <div class="flex items-center gap-2 text-gray-600 dark:text-gray-400 font-mono text-sm">
-                            <span>{ "Executing" }</span>
-                            <span class="animate-pulse">{ "..." }</span>
+                    } else if props.execution.is_none() && !props.running {
+                        <div class="terminal-output-muted font-mono text-sm">
+                            { "Waiting for execution..." }
                         </div>
                     }
                 </div>
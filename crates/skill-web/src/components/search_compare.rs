@@ -0,0 +1,399 @@
+//! Search A/B comparison - run one query against two pipeline configurations
+//!
+//! Both configurations index the same documents into throwaway in-memory
+//! pipelines (via `/api/search/test-pipeline`), so differences in the results
+//! come from the settings alone. Each result shows how far it moved compared
+//! with the other configuration.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::api::{Api, PipelineConfigOverride, PipelineRun, TestPipelineRequest, TestPipelineResponse};
+use crate::components::card::Card;
+use crate::components::use_notifications;
+
+/// One side of the comparison
+#[derive(Clone, PartialEq)]
+struct CompareConfig {
+    name: String,
+    embedding_provider: String,
+    embedding_model: String,
+    enable_hybrid: bool,
+    enable_reranking: bool,
+}
+
+impl CompareConfig {
+    fn to_override(&self) -> PipelineConfigOverride {
+        PipelineConfigOverride {
+            name: self.name.trim().to_string(),
+            embedding_provider: Some(self.embedding_provider.clone()),
+            embedding_model: Some(self.embedding_model.clone()),
+            enable_hybrid: Some(self.enable_hybrid),
+            enable_reranking: Some(self.enable_reranking),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SearchCompareProps {
+    /// Query both configurations search for
+    pub query: AttrValue,
+    /// Results per configuration
+    pub top_k: usize,
+    /// Bumped by the page to run the comparison (e.g. on Enter)
+    #[prop_or_default]
+    pub run: u32,
+}
+
+#[function_component(SearchCompare)]
+pub fn search_compare(props: &SearchCompareProps) -> Html {
+    let config_a = use_state(|| None::<CompareConfig>);
+    let config_b = use_state(|| None::<CompareConfig>);
+    let use_catalog = use_state(|| true);
+    let response = use_state(|| None::<TestPipelineResponse>);
+    let is_running = use_state(|| false);
+
+    let api = use_memo((), |_| Rc::new(Api::new()));
+    let notifications = use_notifications();
+
+    // Start from the current settings, with B flipping hybrid search
+    {
+        let api = api.clone();
+        let config_a = config_a.clone();
+        let config_b = config_b.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                let (provider, model, hybrid, reranking) = match api.search.get_config().await {
+                    Ok(config) => (
+                        config.embedding_provider,
+                        config.embedding_model,
+                        config.hybrid_search_enabled,
+                        config.reranking_enabled,
+                    ),
+                    Err(_) => ("fastembed".to_string(), "all-minilm".to_string(), true, false),
+                };
+                let current = CompareConfig {
+                    name: "Current".to_string(),
+                    embedding_provider: provider,
+                    embedding_model: model,
+                    enable_hybrid: hybrid,
+                    enable_reranking: reranking,
+                };
+                config_b.set(Some(CompareConfig {
+                    name: if hybrid { "Dense only" } else { "Hybrid" }.to_string(),
+                    enable_hybrid: !hybrid,
+                    ..current.clone()
+                }));
+                config_a.set(Some(current));
+            });
+        });
+    }
+
+    let run_comparison = {
+        let api = api.clone();
+        let config_a = config_a.clone();
+        let config_b = config_b.clone();
+        let use_catalog = use_catalog.clone();
+        let response = response.clone();
+        let is_running = is_running.clone();
+        let notifications = notifications.clone();
+        let query = props.query.clone();
+        let top_k = props.top_k;
+        Callback::from(move |_: ()| {
+            let (Some(a), Some(b)) = ((*config_a).clone(), (*config_b).clone()) else {
+                return;
+            };
+            if query.trim().is_empty() || *is_running {
+                return;
+            }
+            if a.name.trim().is_empty() || b.name.trim().is_empty() || a.name.trim() == b.name.trim() {
+                notifications.warning("Name both configurations", "The two configurations need different names");
+                return;
+            }
+
+            let request = TestPipelineRequest {
+                embedding_provider: a.embedding_provider.clone(),
+                embedding_model: a.embedding_model.clone(),
+                vector_backend: "inmemory".to_string(),
+                query: Some(query.to_string()),
+                top_k: Some(top_k),
+                use_skill_catalog: *use_catalog,
+                configs: vec![a.to_override(), b.to_override()],
+                ..Default::default()
+            };
+
+            is_running.set(true);
+            let api = api.clone();
+            let response = response.clone();
+            let is_running = is_running.clone();
+            let notifications = notifications.clone();
+            spawn_local(async move {
+                match api.search.test_pipeline(&request).await {
+                    Ok(result) => {
+                        if !result.success {
+                            notifications.warning("Comparison incomplete", result.message.clone());
+                        }
+                        response.set(Some(result));
+                    }
+                    Err(e) => notifications.error("Comparison failed", e.to_string()),
+                }
+                is_running.set(false);
+            });
+        })
+    };
+
+    // The page's Search button and Enter key run the comparison too; a
+    // count from before this mounted doesn't
+    let handled_run = use_mut_ref(|| props.run);
+    {
+        let run_comparison = run_comparison.clone();
+        use_effect_with(props.run, move |run| {
+            if *handled_run.borrow() != *run {
+                *handled_run.borrow_mut() = *run;
+                run_comparison.emit(());
+            }
+        });
+    }
+
+    let (Some(a), Some(b)) = ((*config_a).clone(), (*config_b).clone()) else {
+        return html! {
+            <div class="flex items-center justify-center py-8">
+                <div class="animate-spin rounded-full h-8 w-8 border-b-2 border-primary-500"></div>
+            </div>
+        };
+    };
+
+    let on_change_a = {
+        let config_a = config_a.clone();
+        Callback::from(move |config: CompareConfig| config_a.set(Some(config)))
+    };
+    let on_change_b = {
+        let config_b = config_b.clone();
+        Callback::from(move |config: CompareConfig| config_b.set(Some(config)))
+    };
+    let on_catalog_toggle = {
+        let use_catalog = use_catalog.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            use_catalog.set(input.checked());
+        })
+    };
+    let on_compare = {
+        let run_comparison = run_comparison.clone();
+        Callback::from(move |_: MouseEvent| run_comparison.emit(()))
+    };
+
+    html! {
+        <>
+            <Card title="Configurations" subtitle="Both run the query against a fresh in-memory index">
+                <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                    <ConfigEditor label="A" config={a} on_change={on_change_a} />
+                    <ConfigEditor label="B" config={b} on_change={on_change_b} />
+                </div>
+                <div class="flex items-center justify-between mt-4">
+                    <label class="flex items-center gap-2 text-sm text-gray-600 dark:text-gray-400">
+                        <input type="checkbox" checked={*use_catalog} onchange={on_catalog_toggle} />
+                        { "Index the installed skills (otherwise a small sample set)" }
+                    </label>
+                    <button
+                        class="btn btn-primary"
+                        onclick={on_compare}
+                        disabled={*is_running || props.query.trim().is_empty()}
+                    >
+                        { if *is_running { "Comparing..." } else { "Compare" } }
+                    </button>
+                </div>
+            </Card>
+
+            if let Some(result) = &*response {
+                <div class="grid grid-cols-1 md:grid-cols-2 gap-6">
+                    { for result.runs.iter().enumerate().map(|(i, run)| {
+                        let other = result.runs.get(1 - i.min(1));
+                        html! { <RunColumn run={run.clone()} other={other.cloned()} /> }
+                    }) }
+                </div>
+            } else if *is_running {
+                <div class="flex items-center justify-center py-8">
+                    <div class="animate-spin rounded-full h-8 w-8 border-b-2 border-primary-500"></div>
+                </div>
+            }
+        </>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct ConfigEditorProps {
+    label: AttrValue,
+    config: CompareConfig,
+    on_change: Callback<CompareConfig>,
+}
+
+#[function_component(ConfigEditor)]
+fn config_editor(props: &ConfigEditorProps) -> Html {
+    let update = |apply: fn(&mut CompareConfig, String)| {
+        let config = props.config.clone();
+        let on_change = props.on_change.clone();
+        move |value: String| {
+            let mut config = config.clone();
+            apply(&mut config, value);
+            on_change.emit(config);
+        }
+    };
+
+    let on_name = {
+        let update = update(|c, v| c.name = v);
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            update(input.value());
+        })
+    };
+    let on_provider = {
+        let update = update(|c, v| c.embedding_provider = v);
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            update(select.value());
+        })
+    };
+    let on_model = {
+        let update = update(|c, v| c.embedding_model = v);
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            update(input.value());
+        })
+    };
+    let on_hybrid = {
+        let update = update(|c, v| c.enable_hybrid = v == "true");
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            update(input.checked().to_string());
+        })
+    };
+    let on_reranking = {
+        let update = update(|c, v| c.enable_reranking = v == "true");
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            update(input.checked().to_string());
+        })
+    };
+
+    let provider = props.config.embedding_provider.as_str();
+
+    html! {
+        <div class="space-y-3 p-4 rounded-lg border border-gray-200 dark:border-gray-700">
+            <div class="flex items-center gap-2">
+                <span class="badge badge-info">{ &props.label }</span>
+                <input
+                    type="text"
+                    class="input flex-1"
+                    placeholder="Name"
+                    value={props.config.name.clone()}
+                    oninput={on_name}
+                />
+            </div>
+            <div class="flex gap-2">
+                <select class="input w-36" onchange={on_provider}>
+                    <option value="fastembed" selected={provider == "fastembed"}>{ "FastEmbed" }</option>
+                    <option value="openai" selected={provider == "openai"}>{ "OpenAI" }</option>
+                    <option value="ollama" selected={provider == "ollama"}>{ "Ollama" }</option>
+                </select>
+                <input
+                    type="text"
+                    class="input flex-1"
+                    placeholder="Embedding model"
+                    value={props.config.embedding_model.clone()}
+                    oninput={on_model}
+                />
+            </div>
+            <div class="flex gap-4 text-sm text-gray-700 dark:text-gray-300">
+                <label class="flex items-center gap-2">
+                    <input type="checkbox" checked={props.config.enable_hybrid} onchange={on_hybrid} />
+                    { "Hybrid search" }
+                </label>
+                <label class="flex items-center gap-2">
+                    <input type="checkbox" checked={props.config.enable_reranking} onchange={on_reranking} />
+                    { "Reranking" }
+                </label>
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct RunColumnProps {
+    run: PipelineRun,
+    /// The other configuration's run, to show how ranks moved
+    other: Option<PipelineRun>,
+}
+
+#[function_component(RunColumn)]
+fn run_column(props: &RunColumnProps) -> Html {
+    let run = &props.run;
+    let other_ranks: HashMap<&str, usize> = props
+        .other
+        .iter()
+        .flat_map(|other| other.search_results.iter().enumerate())
+        .map(|(rank, result)| (result.id.as_str(), rank))
+        .collect();
+    let show_rerank = run.search_results.iter().any(|r| r.rerank_score.is_some());
+
+    html! {
+        <Card
+            title={run.name.clone()}
+            subtitle={format!(
+                "search {}ms · total {}ms · {} documents",
+                run.search_duration_ms, run.duration_ms, run.index_stats.documents_indexed
+            )}
+        >
+            if let Some(error) = &run.error {
+                <p class="text-sm text-error-600">{ error }</p>
+            } else if run.search_results.is_empty() {
+                <p class="text-sm text-gray-500 dark:text-gray-400">{ "No results" }</p>
+            } else {
+                <table class="w-full text-sm">
+                    <thead>
+                        <tr class="text-left text-xs uppercase text-gray-500 dark:text-gray-400">
+                            <th class="pb-2 w-8">{ "#" }</th>
+                            <th class="pb-2">{ "Tool" }</th>
+                            <th class="pb-2 text-right">{ "Score" }</th>
+                            if show_rerank {
+                                <th class="pb-2 text-right">{ "Rerank" }</th>
+                            }
+                            <th class="pb-2 text-right w-14">{ "vs other" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for run.search_results.iter().enumerate().map(|(rank, result)| {
+                            let label = match (&result.metadata.skill_name, &result.metadata.tool_name) {
+                                (Some(skill), Some(tool)) => format!("{}:{}", skill, tool),
+                                _ => result.id.clone(),
+                            };
+                            let (movement, color) = match other_ranks.get(result.id.as_str()) {
+                                None if props.other.is_some() => ("new".to_string(), "text-primary-600 dark:text-primary-400"),
+                                None => (String::new(), ""),
+                                Some(&other) if other == rank => ("=".to_string(), "text-gray-400"),
+                                Some(&other) if other > rank => (format!("↑{}", other - rank), "text-success-600"),
+                                Some(&other) => (format!("↓{}", rank - other), "text-error-600"),
+                            };
+                            html! {
+                                <tr class="border-t border-gray-100 dark:border-gray-700" title={result.content.clone()}>
+                                    <td class="py-1.5 text-gray-400">{ rank + 1 }</td>
+                                    <td class="py-1.5 font-mono text-xs text-gray-900 dark:text-white truncate max-w-0 w-full">{ label }</td>
+                                    <td class="py-1.5 text-right font-mono">{ format!("{:.3}", result.score) }</td>
+                                    if show_rerank {
+                                        <td class="py-1.5 text-right font-mono">
+                                            { result.rerank_score.map(|s| format!("{:.3}", s)).unwrap_or_default() }
+                                        </td>
+                                    }
+                                    <td class={classes!("py-1.5", "text-right", "text-xs", color)}>{ movement }</td>
+                                </tr>
+                            }
+                        }) }
+                    </tbody>
+                </table>
+            }
+        </Card>
+    }
+}
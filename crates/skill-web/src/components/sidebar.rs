@@ -1,10 +1,15 @@
 //! Side navigation component
 
+use std::collections::HashMap;
+use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 use yew_router::prelude::*;
+use yewdux::prelude::*;
 
+use crate::api::Api;
 use crate::router::Route;
-use super::icons::{AnalyticsIcon, DashboardIcon, SkillsIcon, PlayIcon, HistoryIcon, SettingsIcon, SearchIcon};
+use crate::store::presets::{preset_query, PresetsAction, PresetsStore};
+use super::icons::{AnalyticsIcon, BookmarkIcon, DashboardIcon, DocumentIcon, SkillsIcon, PlayIcon, HistoryIcon, SettingsIcon, SearchIcon, LightningIcon};
 
 /// Navigation item structure
 struct NavItem {
@@ -17,6 +22,22 @@ struct NavItem {
 #[function_component(Sidebar)]
 pub fn sidebar() -> Html {
     let route = use_route::<Route>();
+    let (presets_store, presets_dispatch) = use_store::<PresetsStore>();
+
+    // Load saved runs once for the pinned list
+    {
+        let loaded = presets_store.loaded;
+        use_effect_with((), move |_| {
+            if !loaded {
+                spawn_local(async move {
+                    match Api::new().presets.list().await {
+                        Ok(presets) => presets_dispatch.apply(PresetsAction::SetPresets(presets)),
+                        Err(e) => presets_dispatch.apply(PresetsAction::SetError(Some(e.to_string()))),
+                    }
+                });
+            }
+        });
+    }
 
     let nav_items = [NavItem {
             route: Route::Dashboard,
@@ -38,6 +59,11 @@ pub fn sidebar() -> Html {
             label: "History",
             icon: |class| html! { <HistoryIcon class={class} /> },
         },
+        NavItem {
+            route: Route::Workflows,
+            label: "Workflows",
+            icon: |class| html! { <LightningIcon class={class} /> },
+        },
         NavItem {
             route: Route::SearchTest,
             label: "Search Test",
@@ -48,6 +74,11 @@ pub fn sidebar() -> Html {
             label: "Analytics",
             icon: |class| html! { <AnalyticsIcon class={class} /> },
         },
+        NavItem {
+            route: Route::Manifest,
+            label: "Manifest",
+            icon: |class| html! { <DocumentIcon class={class} /> },
+        },
         NavItem {
             route: Route::Settings,
             label: "Settings",
@@ -70,6 +101,29 @@ pub fn sidebar() -> Html {
                 }) }
             </nav>
 
+            // Saved runs pinned from the dashboard; each opens the Run page filled in
+            if presets_store.pinned().next().is_some() {
+                <div class="px-4 pb-24">
+                    <p class="px-3 mb-2 text-xs font-semibold uppercase tracking-wider text-gray-400 dark:text-gray-500">
+                        { "Pinned Runs" }
+                    </p>
+                    <div class="space-y-1">
+                        { for presets_store.pinned().map(|preset| html! {
+                            <Link<Route, HashMap<String, String>>
+                                to={Route::RunSkillTool { skill: preset.skill.clone(), tool: preset.tool.clone() }}
+                                query={Some(preset_query(preset))}
+                                classes="nav-link"
+                            >
+                                <BookmarkIcon class="w-4 h-4" />
+                                <span class="truncate" title={format!("{}:{}", preset.skill, preset.tool)}>
+                                    { &preset.name }
+                                </span>
+                            </Link<Route, HashMap<String, String>>>
+                        }) }
+                    </div>
+                </div>
+            }
+
             // Bottom section with quick actions
             <div class="absolute bottom-0 left-0 right-0 p-4 border-t border-gray-200 dark:border-gray-700">
                 <Link<Route>
@@ -96,8 +150,11 @@ fn is_route_match(current: &Route, target: &Route) -> bool {
         (Route::RunSkillTool { .. }, Route::Run) => true,
         (Route::History, Route::History) => true,
         (Route::HistoryDetail { .. }, Route::History) => true,
+        (Route::Workflows, Route::Workflows) => true,
+        (Route::WorkflowDetail { .. }, Route::Workflows) => true,
         (Route::SearchTest, Route::SearchTest) => true,
         (Route::Analytics, Route::Analytics) => true,
+        (Route::Manifest, Route::Manifest) => true,
         (Route::Settings, Route::Settings) => true,
         _ => current == target,
     }
@@ -1,5 +1,7 @@
 //! Custom Yew hooks for the Skill Engine web interface
 
+pub mod use_theme;
 pub mod use_wizard_state;
 
+pub use use_theme::use_theme;
 pub use use_wizard_state::WizardStep;
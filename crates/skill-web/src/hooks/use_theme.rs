@@ -0,0 +1,70 @@
+//! Theme hook - keeps the document's color scheme in step with the settings
+//!
+//! The theme setting lives in the persisted [`SettingsStore`], so it survives
+//! reloads through localStorage. This hook resolves it to light or dark,
+//! toggling the `dark` class on `<html>` that Tailwind's `dark:` variants and
+//! the theme tokens in `input.css` key off. With the "system" theme it also
+//! follows `prefers-color-scheme` changes while the page is open.
+
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::store::settings::{SettingsStore, Theme};
+use crate::store::ui::{UiAction, UiStore};
+use crate::utils::{system_prefers_dark, DARK_SCHEME_QUERY};
+
+/// Apply a resolved theme to the document
+pub fn apply_theme(dark: bool) {
+    let Some(root) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.document_element())
+    else {
+        return;
+    };
+    let classes = root.class_list();
+    let _ = if dark {
+        classes.add_1("dark")
+    } else {
+        classes.remove_1("dark")
+    };
+    let _ = root.set_attribute("data-theme", if dark { "dark" } else { "light" });
+}
+
+/// Apply the theme setting now and whenever it, or the system preference
+/// it follows, changes
+#[hook]
+pub fn use_theme() {
+    let theme = use_selector(|settings: &SettingsStore| settings.theme.clone());
+    let ui_dispatch = use_dispatch::<UiStore>();
+
+    use_effect_with(theme, move |theme| {
+        let theme: Theme = (**theme).clone();
+        let dark = theme.is_dark(system_prefers_dark());
+        apply_theme(dark);
+        ui_dispatch.apply(UiAction::SetDarkMode(dark));
+
+        // Only the system theme needs to hear about preference changes
+        let query = (theme == Theme::System)
+            .then(|| web_sys::window()?.match_media(DARK_SCHEME_QUERY).ok().flatten())
+            .flatten();
+        let listener = query.as_ref().map(|query| {
+            let ui_dispatch = ui_dispatch.clone();
+            let listener = Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| {
+                let dark = system_prefers_dark();
+                apply_theme(dark);
+                ui_dispatch.apply(UiAction::SetDarkMode(dark));
+            });
+            let _ = query.add_event_listener_with_callback("change", listener.as_ref().unchecked_ref());
+            listener
+        });
+
+        move || {
+            if let (Some(query), Some(listener)) = (query, listener) {
+                let _ = query
+                    .remove_event_listener_with_callback("change", listener.as_ref().unchecked_ref());
+            }
+        }
+    });
+}
@@ -18,11 +18,11 @@ pub use api::client::ApiClient;
 pub use api::error::{ApiError, ApiResult};
 // Re-export API types (the canonical source)
 pub use api::types::{
-    ExecutionHistoryEntry, ExecutionRequest, ExecutionResponse, ExecutionStatus,
-    InstallSkillRequest, InstallSkillResponse, InstanceInfo, PaginatedResponse,
-    PaginationParams, ParameterInfo, QueryInfo, SearchConfigResponse, SearchRequest,
-    SearchResponse, SearchResult, SkillDetail, SkillServiceRequirement, SkillSummary,
-    ToolInfo, UpdateSearchConfigRequest,
+    ExecutionArtifact, ExecutionHistoryEntry, ExecutionRequest, ExecutionResponse, ExecutionStatus,
+    InstallJob, InstallSkillRequest, InstallStage, InstanceInfo, OutputOptions,
+    PaginatedResponse, PaginationParams, ParameterInfo, QueryInfo, ScoreExplanation, SearchConfigResponse,
+    SearchFilters, SearchRequest, SearchResponse, SearchResult, SkillDetail,
+    SkillServiceRequirement, SkillSummary, ToolInfo, UpdateSearchConfigRequest,
 };
 // Re-export store types (avoiding duplicates with api::types)
 pub use store::executions::{ActiveExecution, ExecutionEntry, ExecutionsAction, ExecutionsStore};
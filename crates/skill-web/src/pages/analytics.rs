@@ -5,6 +5,7 @@
 //! - Top queries with feedback counts
 //! - Recent search history
 //! - Feedback statistics by type and result
+//! - Generated example quality per skill (from `skill enhance --evaluate`)
 //! - Time range selector (7, 30, 90 days)
 
 use std::rc::Rc;
@@ -12,7 +13,8 @@ use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
 use crate::api::analytics::{
-    AnalyticsOverviewResponse, FeedbackStatsResponse, TopQueriesResponse,
+    AnalyticsOverviewResponse, ExampleQualityPoint, ExampleQualityResponse, FeedbackStatsResponse,
+    TopQueriesResponse,
 };
 use crate::api::Api;
 use crate::components::card::Card;
@@ -50,6 +52,7 @@ pub fn analytics_page() -> Html {
     let overview = use_state(|| None::<AnalyticsOverviewResponse>);
     let top_queries = use_state(|| None::<TopQueriesResponse>);
     let feedback_stats = use_state(|| None::<FeedbackStatsResponse>);
+    let example_quality = use_state(|| None::<ExampleQualityResponse>);
     let is_loading = use_state(|| false);
 
     // API & notifications
@@ -63,6 +66,7 @@ pub fn analytics_page() -> Html {
         let overview = overview.clone();
         let top_queries = top_queries.clone();
         let feedback_stats = feedback_stats.clone();
+        let example_quality = example_quality.clone();
         let is_loading = is_loading.clone();
         let notifications = notifications.clone();
 
@@ -74,6 +78,7 @@ pub fn analytics_page() -> Html {
             let overview = overview.clone();
             let top_queries = top_queries.clone();
             let feedback_stats = feedback_stats.clone();
+            let example_quality = example_quality.clone();
             let is_loading = is_loading.clone();
             let notifications = notifications.clone();
 
@@ -102,6 +107,13 @@ pub fn analytics_page() -> Html {
                     }
                 }
 
+                match api.analytics.get_example_quality(days).await {
+                    Ok(eq) => example_quality.set(Some(eq)),
+                    Err(e) => {
+                        notifications.error("Failed to load example quality", format!("Error: {}", e));
+                    }
+                }
+
                 is_loading.set(false);
             });
 
@@ -404,9 +416,83 @@ pub fn analytics_page() -> Html {
                                 </div>
                             </Card>
                         }
+
+                        // Example Quality
+                        if let Some(eq) = &*example_quality {
+                            <Card title="Example Quality">
+                                if eq.evaluations.is_empty() {
+                                    <div class="text-center py-8 text-gray-500 dark:text-gray-400">
+                                        <p>{ "No evaluations yet. Run skill enhance --evaluate" }</p>
+                                    </div>
+                                } else {
+                                    <div class="space-y-2">
+                                        { for latest_per_skill(eq).into_iter().map(|(latest, first)| {
+                                            let pass_pct = latest.pass_rate * 100.0;
+                                            let color = if pass_pct >= 80.0 {
+                                                "text-green-600 dark:text-green-400"
+                                            } else if pass_pct >= 50.0 {
+                                                "text-yellow-600 dark:text-yellow-400"
+                                            } else {
+                                                "text-red-600 dark:text-red-400"
+                                            };
+                                            let trend = first
+                                                .map(|f| format!("{:+.0} pts", (latest.pass_rate - f.pass_rate) * 100.0))
+                                                .unwrap_or_default();
+
+                                            html! {
+                                                <div class="flex items-center justify-between p-3 bg-gray-50 dark:bg-gray-800 rounded border border-gray-200 dark:border-gray-700">
+                                                    <div>
+                                                        <p class="text-sm font-medium text-gray-900 dark:text-white">
+                                                            { &latest.skill }
+                                                        </p>
+                                                        <p class="text-xs text-gray-500 dark:text-gray-400">
+                                                            { format!(
+                                                                "{} sampled · score {:.2} · quality {:.2} · {}",
+                                                                latest.sampled,
+                                                                latest.avg_score,
+                                                                latest.quality,
+                                                                latest.timestamp.format("%Y-%m-%d")
+                                                            ) }
+                                                        </p>
+                                                    </div>
+                                                    <div class="text-right">
+                                                        <p class={classes!("text-sm", "font-semibold", color)}>
+                                                            { format!("{:.0}% pass", pass_pct) }
+                                                        </p>
+                                                        <p class="text-xs text-gray-500 dark:text-gray-400">
+                                                            { trend }
+                                                        </p>
+                                                    </div>
+                                                </div>
+                                            }
+                                        }) }
+                                    </div>
+                                }
+                            </Card>
+                        }
                     </>
                 }
             </div>
         </div>
     }
 }
+
+/// Latest evaluation of each skill, with the earliest one in range when there
+/// is more than one
+fn latest_per_skill(
+    response: &ExampleQualityResponse,
+) -> Vec<(&ExampleQualityPoint, Option<&ExampleQualityPoint>)> {
+    let mut skills: Vec<(&ExampleQualityPoint, Option<&ExampleQualityPoint>)> = Vec::new();
+    // Evaluations arrive oldest first
+    for point in &response.evaluations {
+        match skills.iter_mut().find(|(latest, _)| latest.skill == point.skill) {
+            Some(entry) => {
+                let first = entry.1.unwrap_or(entry.0);
+                *entry = (point, Some(first));
+            }
+            None => skills.push((point, None)),
+        }
+    }
+    skills.sort_by(|a, b| a.0.skill.cmp(&b.0.skill));
+    skills
+}
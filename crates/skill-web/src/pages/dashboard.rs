@@ -1,15 +1,21 @@
 //! Dashboard page - main overview with real-time statistics
 
+use std::collections::HashMap;
 use std::rc::Rc;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 use yew_router::prelude::*;
 use yewdux::prelude::*;
 
-use crate::api::{Api, ExecutionHistoryEntry as ApiExecutionEntry, SkillSummary as ApiSkillSummary};
+use crate::api::{
+    Api, CredentialStatusInfo, ExecutionHistoryEntry as ApiExecutionEntry, RunPreset,
+    SkillSummary as ApiSkillSummary,
+};
 use crate::components::card::{Card, StatCard, Trend};
-use crate::components::icons::{CheckIcon, LightningIcon, PlayIcon, SkillsIcon};
+use crate::components::icons::{BookmarkIcon, CheckIcon, LightningIcon, PlayIcon, SkillsIcon, XIcon};
+use crate::components::notifications::use_notifications;
 use crate::router::Route;
+use crate::store::presets::{preset_query, PresetsAction, PresetsStore};
 use crate::store::executions::{ExecutionEntry, ExecutionStatus, ExecutionsAction, ExecutionsStore};
 use crate::store::skills::{SkillRuntime, SkillStatus, SkillSummary, SkillsAction, SkillsStore};
 
@@ -296,6 +302,271 @@ pub fn dashboard_page() -> Html {
                     </div>
                 </Card>
             </div>
+
+            // Saved runs
+            <SavedRunsCard />
+
+            // Stored credentials and their expiry
+            <CredentialsCard />
+        </div>
+    }
+}
+
+/// Card listing saved runs, with one-click re-run and pinning
+#[function_component(SavedRunsCard)]
+fn saved_runs_card() -> Html {
+    let (presets_store, presets_dispatch) = use_store::<PresetsStore>();
+    let running = use_state(|| None::<String>);
+    let navigator = use_navigator();
+    let notifications = use_notifications();
+    let api = use_memo((), |_| Rc::new(Api::new()));
+
+    // Refresh on every visit; the sidebar only loads them once
+    {
+        let api = api.clone();
+        let presets_dispatch = presets_dispatch.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                match api.presets.list().await {
+                    Ok(presets) => presets_dispatch.apply(PresetsAction::SetPresets(presets)),
+                    Err(e) => presets_dispatch.apply(PresetsAction::SetError(Some(e.to_string()))),
+                }
+            });
+        });
+    }
+
+    let on_run = {
+        let api = api.clone();
+        let running = running.clone();
+        let notifications = notifications.clone();
+        Callback::from(move |preset: RunPreset| {
+            if running.is_some() {
+                return;
+            }
+            running.set(Some(preset.id.clone()));
+            let api = api.clone();
+            let running = running.clone();
+            let navigator = navigator.clone();
+            let notifications = notifications.clone();
+            spawn_local(async move {
+                match api.presets.run(&preset.id).await {
+                    Ok(result) => {
+                        if let Some(navigator) = navigator {
+                            navigator.push(&Route::HistoryDetail { id: result.id });
+                        }
+                    }
+                    Err(e) => notifications.error(format!("Failed to run \"{}\"", preset.name), e.to_string()),
+                }
+                running.set(None);
+            });
+        })
+    };
+
+    let on_pin = {
+        let api = api.clone();
+        let notifications = notifications.clone();
+        let presets_dispatch = presets_dispatch.clone();
+        Callback::from(move |preset: RunPreset| {
+            let api = api.clone();
+            let notifications = notifications.clone();
+            let presets_dispatch = presets_dispatch.clone();
+            spawn_local(async move {
+                match api.presets.set_pinned(&preset.id, !preset.pinned).await {
+                    Ok(updated) => presets_dispatch.apply(PresetsAction::Upsert(updated)),
+                    Err(e) => notifications.error("Failed to update saved run", e.to_string()),
+                }
+            });
+        })
+    };
+
+    let on_delete = {
+        let api = api.clone();
+        Callback::from(move |preset: RunPreset| {
+            let api = api.clone();
+            let notifications = notifications.clone();
+            let presets_dispatch = presets_dispatch.clone();
+            spawn_local(async move {
+                match api.presets.delete(&preset.id).await {
+                    Ok(()) => presets_dispatch.apply(PresetsAction::Remove(preset.id)),
+                    Err(e) => notifications.error("Failed to delete saved run", e.to_string()),
+                }
+            });
+        })
+    };
+
+    html! {
+        <Card title="Saved Runs" subtitle="Re-run in one click, or pin to the sidebar">
+            if !presets_store.loaded {
+                <div class="flex items-center justify-center py-8">
+                    <div class="animate-spin rounded-full h-8 w-8 border-b-2 border-primary-600"></div>
+                </div>
+            } else if let Some(err) = &presets_store.error {
+                <p class="text-sm text-red-600 dark:text-red-400">{ err.clone() }</p>
+            } else if presets_store.presets.is_empty() {
+                <p class="text-sm text-gray-500 dark:text-gray-400 py-4 text-center">
+                    { "No saved runs. Use \"Save Run\" on the Run page to keep one here." }
+                </p>
+            } else {
+                <div class="divide-y divide-gray-200 dark:divide-gray-700">
+                    { for presets_store.presets.iter().map(|preset| {
+                        let is_running = running.as_deref() == Some(preset.id.as_str());
+                        let emit = |callback: &Callback<RunPreset>| {
+                            let callback = callback.clone();
+                            let preset = preset.clone();
+                            Callback::from(move |_: MouseEvent| callback.emit(preset.clone()))
+                        };
+                        let last_run = preset
+                            .last_run_at
+                            .as_deref()
+                            .map(|at| format!("last run {}", format_relative_time(at)))
+                            .unwrap_or_else(|| "never run".to_string());
+
+                        html! {
+                            <div class="py-3 flex items-center justify-between gap-3" key={preset.id.clone()}>
+                                <div class="min-w-0">
+                                    <Link<Route, HashMap<String, String>>
+                                        to={Route::RunSkillTool { skill: preset.skill.clone(), tool: preset.tool.clone() }}
+                                        query={Some(preset_query(preset))}
+                                        classes="text-sm font-medium text-gray-900 dark:text-white hover:underline truncate block"
+                                    >
+                                        { &preset.name }
+                                    </Link<Route, HashMap<String, String>>>
+                                    <p class="text-xs text-gray-500 dark:text-gray-400 truncate">
+                                        { format!("{}:{} · {}", preset.skill, preset.tool, last_run) }
+                                    </p>
+                                </div>
+                                <div class="flex items-center gap-1 shrink-0">
+                                    <button
+                                        class="btn btn-primary text-sm"
+                                        title="Run now"
+                                        disabled={running.is_some()}
+                                        onclick={emit(&on_run)}
+                                    >
+                                        if is_running {
+                                            <div class="animate-spin rounded-full h-4 w-4 border-b-2 border-white"></div>
+                                        } else {
+                                            <PlayIcon class="w-4 h-4" />
+                                        }
+                                    </button>
+                                    <button
+                                        class={classes!("btn", "btn-ghost", "text-sm", preset.pinned.then_some("text-primary-600 dark:text-primary-400"))}
+                                        title={if preset.pinned { "Unpin from sidebar" } else { "Pin to sidebar" }}
+                                        onclick={emit(&on_pin)}
+                                    >
+                                        <BookmarkIcon class="w-4 h-4" />
+                                    </button>
+                                    <button
+                                        class="btn btn-ghost text-sm"
+                                        title="Delete"
+                                        onclick={emit(&on_delete)}
+                                    >
+                                        <XIcon class="w-4 h-4" />
+                                    </button>
+                                </div>
+                            </div>
+                        }
+                    }) }
+                </div>
+            }
+        </Card>
+    }
+}
+
+/// Card listing credentials stored with `skill auth login`
+#[function_component(CredentialsCard)]
+fn credentials_card() -> Html {
+    let credentials = use_state(|| None::<Result<Vec<CredentialStatusInfo>, String>>);
+
+    {
+        let credentials = credentials.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                let result = Api::new()
+                    .auth
+                    .status()
+                    .await
+                    .map(|status| status.credentials)
+                    .map_err(|e| e.to_string());
+                credentials.set(Some(result));
+            });
+        });
+    }
+
+    let warning_count = match &*credentials {
+        Some(Ok(list)) => list.iter().filter(|c| c.warning.is_some()).count(),
+        _ => 0,
+    };
+    let subtitle = if warning_count > 0 {
+        format!("{} need attention", warning_count)
+    } else {
+        "From skill auth login".to_string()
+    };
+
+    html! {
+        <Card title="Credentials" subtitle={subtitle}>
+            {
+                match &*credentials {
+                    None => html! {
+                        <div class="flex items-center justify-center py-8">
+                            <div class="animate-spin rounded-full h-8 w-8 border-b-2 border-primary-600"></div>
+                        </div>
+                    },
+                    Some(Err(e)) => html! {
+                        <p class="text-sm text-red-600 dark:text-red-400">{ e.clone() }</p>
+                    },
+                    Some(Ok(list)) if list.is_empty() => html! {
+                        <p class="text-sm text-gray-500 dark:text-gray-400 py-4 text-center">
+                            { "No stored credentials. Run `skill auth login <provider>` to add one." }
+                        </p>
+                    },
+                    Some(Ok(list)) => html! {
+                        <div class="divide-y divide-gray-200 dark:divide-gray-700">
+                            { for list.iter().map(credential_row) }
+                        </div>
+                    },
+                }
+            }
+        </Card>
+    }
+}
+
+fn credential_row(credential: &CredentialStatusInfo) -> Html {
+    let (badge_class, label) = match credential.state.as_str() {
+        "expired" => ("badge badge-error", "Expired"),
+        "expiring_soon" => ("badge badge-warning", "Expiring soon"),
+        "refreshable" => ("badge badge-success", "Auto-refresh"),
+        _ => ("badge badge-success", "Active"),
+    };
+    let scope = match (&credential.skill, &credential.instance) {
+        (Some(skill), Some(instance)) => format!("{}@{}", skill, instance),
+        (Some(skill), None) => skill.clone(),
+        (None, _) => "global".to_string(),
+    };
+    let expires = credential
+        .expires_at
+        .as_deref()
+        .map(format_relative_time)
+        .unwrap_or_else(|| "never".to_string());
+
+    html! {
+        <div class="py-3">
+            <div class="flex items-center justify-between gap-3">
+                <div class="min-w-0">
+                    <p class="text-sm font-medium text-gray-900 dark:text-white truncate">
+                        { format!("{} · {}", credential.display_name, scope) }
+                    </p>
+                    <p class="text-xs text-gray-500 dark:text-gray-400 truncate">
+                        { format!("{} · expires {}", credential.auth_type, expires) }
+                        if !credential.scopes.is_empty() {
+                            { format!(" · {}", credential.scopes.join(", ")) }
+                        }
+                    </p>
+                </div>
+                <span class={badge_class}>{ label }</span>
+            </div>
+            if let Some(warning) = &credential.warning {
+                <p class="text-xs text-warning-600 dark:text-warning-400 mt-1">{ warning.clone() }</p>
+            }
         </div>
     }
 }
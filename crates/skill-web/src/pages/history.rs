@@ -4,9 +4,13 @@ use std::rc::Rc;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
-use crate::api::{Api, ExecutionHistoryEntry, ExecutionStatus as ApiExecutionStatus};
+use crate::api::{
+    Api, ExecutionArtifact, ExecutionHistoryEntry, ExecutionSearchHit,
+    ExecutionStatus as ApiExecutionStatus,
+};
 use crate::components::card::Card;
 use crate::components::icons::{CheckIcon, RefreshIcon, SearchIcon};
+use crate::utils::format_bytes;
 
 /// History page props
 #[derive(Properties, PartialEq)]
@@ -22,6 +26,10 @@ pub fn history_page(_props: &HistoryPageProps) -> Html {
     let executions = use_state(Vec::<ExecutionHistoryEntry>::new);
     let loading = use_state(|| true);
     let error = use_state(|| None::<String>);
+    // Full-text search over output and errors; `None` shows the plain history
+    let output_query = use_state(String::new);
+    let output_days = use_state(|| Some(7u32));
+    let output_results = use_state(|| None::<Vec<ExecutionSearchHit>>);
 
     // API client
     let api = use_memo((), |_| Rc::new(Api::new()));
@@ -89,6 +97,73 @@ pub fn history_page(_props: &HistoryPageProps) -> Html {
         })
     };
 
+    let run_output_search = {
+        let api = api.clone();
+        let output_query = output_query.clone();
+        let output_days = output_days.clone();
+        let output_results = output_results.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+
+        Callback::from(move |_: ()| {
+            let query = output_query.trim().to_string();
+            if query.is_empty() {
+                output_results.set(None);
+                return;
+            }
+            loading.set(true);
+            error.set(None);
+
+            let api = api.clone();
+            let days = *output_days;
+            let output_results = output_results.clone();
+            let loading = loading.clone();
+            let error = error.clone();
+
+            spawn_local(async move {
+                match api.executions.search(&query, days).await {
+                    Ok(response) => output_results.set(Some(response.results)),
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+                loading.set(false);
+            });
+        })
+    };
+
+    let on_output_query = {
+        let output_query = output_query.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            output_query.set(input.value());
+        })
+    };
+
+    let on_output_key = {
+        let run_output_search = run_output_search.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() == "Enter" {
+                run_output_search.emit(());
+            }
+        })
+    };
+
+    let on_output_days = {
+        let output_days = output_days.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            output_days.set(select.value().parse::<u32>().ok());
+        })
+    };
+
+    let on_output_clear = {
+        let output_query = output_query.clone();
+        let output_results = output_results.clone();
+        Callback::from(move |_| {
+            output_query.set(String::new());
+            output_results.set(None);
+        })
+    };
+
     // Filter executions by search query
     let filtered_executions: Vec<&ExecutionHistoryEntry> = executions
         .iter()
@@ -138,6 +213,34 @@ pub fn history_page(_props: &HistoryPageProps) -> Html {
                         />
                     </div>
                 </div>
+                <div class="flex flex-col md:flex-row gap-4 mt-4">
+                    <input
+                        type="text"
+                        placeholder="Search output and errors, e.g. CrashLoopBackOff"
+                        class="input flex-1 font-mono"
+                        value={(*output_query).clone()}
+                        oninput={on_output_query}
+                        onkeypress={on_output_key}
+                    />
+                    <select class="input w-40" onchange={on_output_days}>
+                        <option value="1" selected={*output_days == Some(1)}>{ "Last day" }</option>
+                        <option value="7" selected={*output_days == Some(7)}>{ "Last week" }</option>
+                        <option value="30" selected={*output_days == Some(30)}>{ "Last month" }</option>
+                        <option value="" selected={output_days.is_none()}>{ "Any time" }</option>
+                    </select>
+                    <button
+                        class="btn btn-primary"
+                        onclick={run_output_search.reform(|_| ())}
+                        disabled={*loading || output_query.trim().is_empty()}
+                    >
+                        { "Search output" }
+                    </button>
+                    if output_results.is_some() {
+                        <button class="btn btn-secondary" onclick={on_output_clear}>
+                            { "Clear" }
+                        </button>
+                    }
+                </div>
             </Card>
 
             // Error state
@@ -147,54 +250,97 @@ pub fn history_page(_props: &HistoryPageProps) -> Html {
                 </div>
             }
 
-            // Executions table
-            <Card>
-                if *loading && executions.is_empty() {
-                    <div class="flex items-center justify-center py-12">
-                        <div class="animate-spin rounded-full h-8 w-8 border-b-2 border-primary-600"></div>
-                        <span class="ml-3 text-gray-500">{ "Loading executions..." }</span>
-                    </div>
-                } else if filtered_executions.is_empty() {
-                    <div class="text-center py-12">
-                        <svg class="w-12 h-12 mx-auto text-gray-400" fill="none" viewBox="0 0 24 24" stroke="currentColor">
-                            <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M12 8v4l3 3m6-3a9 9 0 11-18 0 9 9 0 0118 0z" />
-                        </svg>
-                        <h3 class="mt-4 text-lg font-medium text-gray-900 dark:text-white">
-                            { "No executions found" }
-                        </h3>
-                        <p class="mt-2 text-gray-500 dark:text-gray-400">
-                            { "Run some skills to see execution history here." }
+            // Output search results
+            if let Some(hits) = (*output_results).clone() {
+                <Card>
+                    if hits.is_empty() {
+                        <p class="text-center py-12 text-gray-500 dark:text-gray-400">
+                            { format!("No executions mention \"{}\"", output_query.trim()) }
                         </p>
-                    </div>
-                } else {
-                    <div class="overflow-x-auto">
-                        <table class="table">
-                            <thead>
-                                <tr>
-                                    <th>{ "ID" }</th>
-                                    <th>{ "Skill / Tool" }</th>
-                                    <th>{ "Instance" }</th>
-                                    <th>{ "Status" }</th>
-                                    <th>{ "Duration" }</th>
-                                    <th>{ "Time" }</th>
-                                </tr>
-                            </thead>
-                            <tbody>
-                                { for filtered_executions.iter().map(|exec| html! {
-                                    <ExecutionRow execution={(*exec).clone()} />
-                                }) }
-                            </tbody>
-                        </table>
-                    </div>
-
-                    // Summary
-                    <div class="mt-4 flex items-center justify-between">
-                        <p class="text-sm text-gray-500">
-                            { format!("Showing {} executions", filtered_executions.len()) }
+                    } else {
+                        <div class="overflow-x-auto">
+                            <table class="table">
+                                <thead>
+                                    <tr>
+                                        <th>{ "ID" }</th>
+                                        <th>{ "Skill / Tool" }</th>
+                                        <th>{ "Instance" }</th>
+                                        <th>{ "Status" }</th>
+                                        <th>{ "Duration" }</th>
+                                        <th>{ "Time" }</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    { for hits.iter().map(|hit| html! {
+                                        <>
+                                            <ExecutionRow execution={hit.execution.clone()} />
+                                            <tr>
+                                                <td colspan="6" class="pt-0">
+                                                    <pre class="text-xs text-gray-600 dark:text-gray-400 font-mono whitespace-pre-wrap">
+                                                        { highlight_snippet(&hit.snippet) }
+                                                    </pre>
+                                                </td>
+                                            </tr>
+                                        </>
+                                    }) }
+                                </tbody>
+                            </table>
+                        </div>
+                        <p class="mt-4 text-sm text-gray-500">
+                            { format!("{} matching executions", hits.len()) }
                         </p>
-                    </div>
-                }
-            </Card>
+                    }
+                </Card>
+            } else {
+                // Executions table
+                <Card>
+                    if *loading && executions.is_empty() {
+                        <div class="flex items-center justify-center py-12">
+                            <div class="animate-spin rounded-full h-8 w-8 border-b-2 border-primary-600"></div>
+                            <span class="ml-3 text-gray-500">{ "Loading executions..." }</span>
+                        </div>
+                    } else if filtered_executions.is_empty() {
+                        <div class="text-center py-12">
+                            <svg class="w-12 h-12 mx-auto text-gray-400" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M12 8v4l3 3m6-3a9 9 0 11-18 0 9 9 0 0118 0z" />
+                            </svg>
+                            <h3 class="mt-4 text-lg font-medium text-gray-900 dark:text-white">
+                                { "No executions found" }
+                            </h3>
+                            <p class="mt-2 text-gray-500 dark:text-gray-400">
+                                { "Run some skills to see execution history here." }
+                            </p>
+                        </div>
+                    } else {
+                        <div class="overflow-x-auto">
+                            <table class="table">
+                                <thead>
+                                    <tr>
+                                        <th>{ "ID" }</th>
+                                        <th>{ "Skill / Tool" }</th>
+                                        <th>{ "Instance" }</th>
+                                        <th>{ "Status" }</th>
+                                        <th>{ "Duration" }</th>
+                                        <th>{ "Time" }</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    { for filtered_executions.iter().map(|exec| html! {
+                                        <ExecutionRow execution={(*exec).clone()} />
+                                    }) }
+                                </tbody>
+                            </table>
+                        </div>
+
+                        // Summary
+                        <div class="mt-4 flex items-center justify-between">
+                            <p class="text-sm text-gray-500">
+                                { format!("Showing {} executions", filtered_executions.len()) }
+                            </p>
+                        </div>
+                    }
+                </Card>
+            }
         </div>
     }
 }
@@ -210,6 +356,24 @@ struct ExecutionRowProps {
 fn execution_row(props: &ExecutionRowProps) -> Html {
     let exec = &props.execution;
     let expanded = use_state(|| false);
+    let artifacts = use_state(|| None::<Vec<ExecutionArtifact>>);
+    let api = use_memo((), |_| Rc::new(Api::new()));
+
+    // Load artifacts the first time the row is expanded
+    {
+        let api = api.clone();
+        let artifacts = artifacts.clone();
+        let id = exec.id.clone();
+        use_effect_with(*expanded, move |expanded| {
+            if *expanded && artifacts.is_none() {
+                spawn_local(async move {
+                    // Executions of skills without declared outputs have none
+                    let list = api.executions.list_artifacts(&id).await.unwrap_or_default();
+                    artifacts.set(Some(list));
+                });
+            }
+        });
+    }
 
     let (status_badge, status_icon, status_text) = match exec.status {
         ApiExecutionStatus::Success => (
@@ -324,6 +488,77 @@ fn execution_row(props: &ExecutionRowProps) -> Html {
                                 </div>
                             }
 
+                            // Artifacts
+                            if let Some(artifacts) = (*artifacts).as_ref().filter(|a| !a.is_empty()) {
+                                <div>
+                                    <h4 class="font-medium text-gray-900 dark:text-white mb-2">{ "Artifacts" }</h4>
+                                    <ul class="space-y-1 text-sm">
+                                        { for artifacts.iter().map(|artifact| html! {
+                                            <li class="flex items-center justify-between">
+                                                <a
+                                                    href={api.executions.artifact_url(&exec.id, &artifact.name)}
+                                                    download={artifact.name.clone()}
+                                                    class="font-mono text-primary-600 dark:text-primary-400 hover:underline"
+                                                >
+                                                    { &artifact.name }
+                                                </a>
+                                                <span class="text-xs text-gray-500">{ format_bytes(artifact.size) }</span>
+                                            </li>
+                                        }) }
+                                    </ul>
+                                </div>
+                            }
+
+                            // Resources used, where they were measured
+                            if let Some(usage) = &exec.resource_usage {
+                                <div>
+                                    <h4 class="font-medium text-gray-900 dark:text-white mb-2">{ "Resources" }</h4>
+                                    <dl class="grid grid-cols-[max-content_1fr] gap-x-4 gap-y-1 text-sm">
+                                        if let Some(cpu) = usage.cpu_time_ms {
+                                            <dt class="text-gray-500">{ "CPU time" }</dt>
+                                            <dd class="font-mono text-gray-900 dark:text-white">{ format!("{}ms", cpu) }</dd>
+                                        }
+                                        if let Some(memory) = usage.peak_memory_kb {
+                                            <dt class="text-gray-500">{ "Peak memory" }</dt>
+                                            <dd class="font-mono text-gray-900 dark:text-white">{ format_bytes(memory * 1024) }</dd>
+                                        }
+                                        if let Some(bytes) = usage.output_bytes {
+                                            <dt class="text-gray-500">{ "Output" }</dt>
+                                            <dd class="font-mono text-gray-900 dark:text-white">{ format_bytes(bytes) }</dd>
+                                        }
+                                    </dl>
+                                </div>
+                            }
+
+                            // Environment snapshot
+                            if let Some(env) = &exec.environment {
+                                <div>
+                                    <h4 class="font-medium text-gray-900 dark:text-white mb-2">{ "Environment" }</h4>
+                                    <dl class="grid grid-cols-[max-content_1fr] gap-x-4 gap-y-1 text-sm">
+                                        if let Some(context) = &env.context {
+                                            <dt class="text-gray-500">{ "Context" }</dt>
+                                            <dd class="font-mono text-gray-900 dark:text-white">{ context }</dd>
+                                        }
+                                        { for env.binaries.iter().map(|(name, version)| html! {
+                                            <>
+                                                <dt class="text-gray-500">{ name }</dt>
+                                                <dd class="font-mono text-gray-900 dark:text-white">{ version }</dd>
+                                            </>
+                                        }) }
+                                        if !env.config_keys.is_empty() {
+                                            <dt class="text-gray-500">{ "Config" }</dt>
+                                            <dd class="font-mono text-gray-900 dark:text-white">
+                                                { env.config_keys.iter().map(|key| format!("{}=[REDACTED]", key)).collect::<Vec<_>>().join(" ") }
+                                            </dd>
+                                        }
+                                        if !env.env_vars.is_empty() {
+                                            <dt class="text-gray-500">{ "Env vars" }</dt>
+                                            <dd class="font-mono text-gray-900 dark:text-white">{ env.env_vars.join(" ") }</dd>
+                                        }
+                                    </dl>
+                                </div>
+                            }
+
                             // Metadata
                             <div class="grid grid-cols-2 gap-4 text-sm">
                                 <div>
@@ -343,6 +578,20 @@ fn execution_row(props: &ExecutionRowProps) -> Html {
     }
 }
 
+/// Render a search snippet, marking the words wrapped in `«` and `»`
+fn highlight_snippet(snippet: &str) -> Html {
+    let mut parts = Vec::new();
+    for (i, segment) in snippet.split(['«', '»']).enumerate() {
+        // Odd segments sit between an opening and closing marker
+        if i % 2 == 1 {
+            parts.push(html! { <mark class="bg-yellow-200 dark:bg-yellow-700">{ segment }</mark> });
+        } else if !segment.is_empty() {
+            parts.push(html! { { segment } });
+        }
+    }
+    html! { <>{ for parts }</> }
+}
+
 /// Format ISO timestamp to relative or readable time
 fn format_timestamp(timestamp: &str) -> String {
     // Simple formatting - just show date and time part
@@ -0,0 +1,701 @@
+//! Manifest editor page
+//!
+//! Edits the `.skill-engine.toml` the server loaded its skills from:
+//! - TOML editor with syntax highlighting and a line-number gutter
+//! - Diagnostics from `/api/manifest/validate`, located on their lines
+//! - Line diff of the edits against the current manifest
+//! - Apply, which saves the manifest and installs its skills
+
+use gloo_timers::callback::Timeout;
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Element, HtmlTextAreaElement};
+use yew::prelude::*;
+
+use crate::api::{
+    Api, DiagnosticSeverity, ImportManifestResponse, ManifestDiagnostic, ManifestSourceResponse,
+    ValidateManifestResponse,
+};
+use crate::components::card::Card;
+use crate::components::markdown::highlight;
+use crate::components::use_notifications;
+
+/// How long typing has to pause before the manifest is validated
+const VALIDATE_DEBOUNCE_MS: u32 = 400;
+
+/// Editor line height in pixels; matches the `leading-5` the editor layers use
+const LINE_HEIGHT_PX: i32 = 20;
+
+/// Unchanged lines kept around each change in the diff
+const DIFF_CONTEXT: usize = 3;
+
+#[function_component(ManifestPage)]
+pub fn manifest_page() -> Html {
+    let api = use_memo((), |_| Rc::new(Api::new()));
+    let notifications = use_notifications();
+
+    let baseline = use_state(|| None::<ManifestSourceResponse>);
+    let content = use_state(String::new);
+    let validation = use_state(|| None::<ValidateManifestResponse>);
+    let validating = use_state(|| false);
+    let merge = use_state(|| true);
+    let applying = use_state(|| false);
+    let apply_result = use_state(|| None::<ImportManifestResponse>);
+    let show_diff = use_state(|| false);
+    // Bumped per validation so a slow response can't replace a newer one
+    let generation = use_mut_ref(|| 0u32);
+
+    let textarea_ref = use_node_ref();
+    let scroll_ref = use_node_ref();
+
+    // Load the current manifest as the baseline
+    {
+        let api = api.clone();
+        let baseline = baseline.clone();
+        let content = content.clone();
+        let notifications = notifications.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                match api.config.get_manifest().await {
+                    Ok(source) => {
+                        content.set(source.content.clone());
+                        baseline.set(Some(source));
+                    }
+                    Err(e) => {
+                        notifications.error("Failed to load manifest", e.to_string());
+                        baseline.set(Some(ManifestSourceResponse {
+                            path: None,
+                            content: String::new(),
+                        }));
+                    }
+                }
+            });
+        });
+    }
+
+    // Validate after typing pauses
+    {
+        let api = api.clone();
+        let validation = validation.clone();
+        let validating = validating.clone();
+        let generation = generation.clone();
+        use_effect_with((*content).clone(), move |content| {
+            *generation.borrow_mut() += 1;
+            let current = *generation.borrow();
+
+            let timeout = if content.trim().is_empty() {
+                validation.set(None);
+                validating.set(false);
+                None
+            } else {
+                let content = content.clone();
+                validating.set(true);
+                Some(Timeout::new(VALIDATE_DEBOUNCE_MS, move || {
+                    spawn_local(async move {
+                        let result = api.config.validate_manifest(&content).await;
+                        if *generation.borrow() != current {
+                            return;
+                        }
+                        match result {
+                            Ok(response) => validation.set(Some(response)),
+                            Err(e) => validation.set(Some(ValidateManifestResponse {
+                                valid: false,
+                                skills: Vec::new(),
+                                errors: vec![e.to_string()],
+                                warnings: Vec::new(),
+                                diagnostics: vec![ManifestDiagnostic {
+                                    severity: DiagnosticSeverity::Error,
+                                    message: format!("Validation failed: {}", e),
+                                    line: None,
+                                    column: None,
+                                    end_line: None,
+                                    end_column: None,
+                                }],
+                            })),
+                        }
+                        validating.set(false);
+                    });
+                }))
+            };
+
+            move || drop(timeout)
+        });
+    }
+
+    let on_input = {
+        let content = content.clone();
+        Callback::from(move |e: InputEvent| {
+            let textarea: HtmlTextAreaElement = e.target_unchecked_into();
+            content.set(textarea.value());
+        })
+    };
+
+    // Tab indents instead of leaving the editor
+    let on_keydown = {
+        let content = content.clone();
+        Callback::from(move |e: KeyboardEvent| {
+            if e.key() != "Tab" || e.shift_key() || e.ctrl_key() || e.meta_key() || e.alt_key() {
+                return;
+            }
+            e.prevent_default();
+            let textarea: HtmlTextAreaElement = e.target_unchecked_into();
+            let start = textarea.selection_start().ok().flatten().unwrap_or(0);
+            if textarea.set_range_text("  ").is_ok() {
+                let _ = textarea.set_selection_range(start + 2, start + 2);
+                content.set(textarea.value());
+            }
+        })
+    };
+
+    let on_jump = {
+        let content = content.clone();
+        let textarea_ref = textarea_ref.clone();
+        let scroll_ref = scroll_ref.clone();
+        let show_diff = show_diff.clone();
+        Callback::from(move |(line, column): (usize, usize)| {
+            show_diff.set(false);
+            let offset = utf16_offset(&content, line, column);
+            if let Some(textarea) = textarea_ref.cast::<HtmlTextAreaElement>() {
+                let _ = textarea.focus();
+                let _ = textarea.set_selection_range(offset, offset);
+            }
+            if let Some(scroller) = scroll_ref.cast::<Element>() {
+                let top = (line.saturating_sub(3) as i32) * LINE_HEIGHT_PX;
+                scroller.set_scroll_top(top);
+            }
+        })
+    };
+
+    let on_reset = {
+        let baseline = baseline.clone();
+        let content = content.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(source) = &*baseline {
+                content.set(source.content.clone());
+            }
+        })
+    };
+
+    let on_merge_toggle = {
+        let merge = merge.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            merge.set(input.checked());
+        })
+    };
+
+    let on_apply = {
+        let api = api.clone();
+        let content = content.clone();
+        let merge = merge.clone();
+        let applying = applying.clone();
+        let apply_result = apply_result.clone();
+        let baseline = baseline.clone();
+        let notifications = notifications.clone();
+        Callback::from(move |_: MouseEvent| {
+            let api = api.clone();
+            let text = (*content).clone();
+            let merge = *merge;
+            let applying = applying.clone();
+            let apply_result = apply_result.clone();
+            let baseline = baseline.clone();
+            let content = content.clone();
+            let notifications = notifications.clone();
+            applying.set(true);
+            spawn_local(async move {
+                match api.config.apply_manifest(&text, merge).await {
+                    Ok(response) => {
+                        if response.success {
+                            notifications.success(
+                                "Manifest applied",
+                                format!(
+                                    "Saved to {}; installing {} skill(s)",
+                                    response.saved_path.as_deref().unwrap_or("the manifest file"),
+                                    response.installs.len()
+                                ),
+                            );
+                            // The saved file is the new baseline
+                            if let Ok(source) = api.config.get_manifest().await {
+                                content.set(source.content.clone());
+                                baseline.set(Some(source));
+                            }
+                        } else {
+                            notifications.error("Manifest not applied", response.errors.join("; "));
+                        }
+                        apply_result.set(Some(response));
+                    }
+                    Err(e) => notifications.error("Failed to apply manifest", e.to_string()),
+                }
+                applying.set(false);
+            });
+        })
+    };
+
+    let Some(source) = (*baseline).clone() else {
+        return html! {
+            <div class="flex items-center justify-center py-12">
+                <div class="animate-spin rounded-full h-12 w-12 border-b-2 border-primary-500"></div>
+            </div>
+        };
+    };
+
+    let diagnostics = validation
+        .as_ref()
+        .map(|v| v.diagnostics.clone())
+        .unwrap_or_default();
+    let error_count = diagnostics
+        .iter()
+        .filter(|d| d.severity == DiagnosticSeverity::Error)
+        .count();
+    let warning_count = diagnostics.len() - error_count;
+    let changed = *content != source.content;
+    let can_apply = changed
+        && !*applying
+        && !*validating
+        && !content.trim().is_empty()
+        && validation.as_ref().is_some_and(|v| v.valid);
+
+    let apply_title = if !changed {
+        "No changes to apply"
+    } else if error_count > 0 {
+        "Fix the errors before applying"
+    } else {
+        "Save the manifest and install its skills"
+    };
+
+    html! {
+        <div class="space-y-6 animate-fade-in">
+            // Header
+            <div class="flex items-start justify-between gap-4">
+                <div>
+                    <h1 class="text-2xl font-bold text-gray-900 dark:text-white">{ "Manifest" }</h1>
+                    <p class="text-gray-500 dark:text-gray-400 mt-1">
+                        if let Some(path) = &source.path {
+                            { "Editing " }
+                            <code class="font-mono text-sm">{ path }</code>
+                        } else {
+                            { "No manifest is loaded; applying writes a new .skill-engine.toml" }
+                        }
+                    </p>
+                </div>
+                <div class="flex items-center gap-3">
+                    <label class="flex items-center gap-2 text-sm text-gray-600 dark:text-gray-400"
+                        title="Keep skills that are loaded but not declared in this manifest">
+                        <input type="checkbox" checked={*merge} onchange={on_merge_toggle} />
+                        { "Merge" }
+                    </label>
+                    <button class="btn btn-secondary" onclick={on_reset} disabled={!changed || *applying}>
+                        { "Reset" }
+                    </button>
+                    <button class="btn btn-primary" onclick={on_apply} disabled={!can_apply} title={apply_title}>
+                        { if *applying { "Applying..." } else { "Apply" } }
+                    </button>
+                </div>
+            </div>
+
+            <div class="grid grid-cols-1 xl:grid-cols-3 gap-6">
+                // Editor or diff
+                <div class="xl:col-span-2">
+                    <Card
+                        title={if *show_diff { "Changes" } else { "Editor" }}
+                        actions={html! {
+                            <div class="flex items-center gap-1">
+                                <button
+                                    class={classes!("btn", "btn-ghost", "text-sm", (!*show_diff).then_some("bg-gray-100 dark:bg-gray-700"))}
+                                    onclick={{ let show_diff = show_diff.clone(); Callback::from(move |_| show_diff.set(false)) }}
+                                >
+                                    { "Edit" }
+                                </button>
+                                <button
+                                    class={classes!("btn", "btn-ghost", "text-sm", (*show_diff).then_some("bg-gray-100 dark:bg-gray-700"))}
+                                    onclick={{ let show_diff = show_diff.clone(); Callback::from(move |_| show_diff.set(true)) }}
+                                >
+                                    { if changed { "Diff •" } else { "Diff" } }
+                                </button>
+                            </div>
+                        }}
+                    >
+                        if *show_diff {
+                            <DiffView old={AttrValue::from(source.content.clone())} new={AttrValue::from((*content).clone())} />
+                        } else {
+                            <Editor
+                                content={AttrValue::from((*content).clone())}
+                                diagnostics={diagnostics.clone()}
+                                textarea_ref={textarea_ref.clone()}
+                                scroll_ref={scroll_ref.clone()}
+                                on_input={on_input}
+                                on_keydown={on_keydown}
+                            />
+                        }
+                    </Card>
+                </div>
+
+                // Diagnostics and the skills the manifest declares
+                <div class="space-y-6">
+                    <Card title="Diagnostics">
+                        if *validating {
+                            <p class="text-sm text-gray-500 dark:text-gray-400">{ "Validating..." }</p>
+                        } else if validation.is_none() {
+                            <p class="text-sm text-gray-500 dark:text-gray-400">{ "Start typing to validate the manifest." }</p>
+                        } else if diagnostics.is_empty() {
+                            <p class="text-sm text-success-600 dark:text-success-500">{ "No problems found" }</p>
+                        } else {
+                            <p class="text-xs text-gray-500 dark:text-gray-400 mb-3">
+                                { format!("{} error(s), {} warning(s)", error_count, warning_count) }
+                            </p>
+                            <ul class="space-y-2">
+                                { for diagnostics.iter().map(|diagnostic| {
+                                    let (color, label) = match diagnostic.severity {
+                                        DiagnosticSeverity::Error => ("text-error-600 dark:text-error-500", "Error"),
+                                        DiagnosticSeverity::Warning => ("text-warning-600 dark:text-warning-500", "Warning"),
+                                    };
+                                    let location = diagnostic.line.map(|line| (line, diagnostic.column.unwrap_or(1)));
+                                    let onclick = {
+                                        let on_jump = on_jump.clone();
+                                        Callback::from(move |_: MouseEvent| {
+                                            if let Some(location) = location {
+                                                on_jump.emit(location);
+                                            }
+                                        })
+                                    };
+                                    html! {
+                                        <li>
+                                            <button
+                                                class="w-full text-left text-sm rounded p-2 hover:bg-gray-50 dark:hover:bg-gray-700/50"
+                                                onclick={onclick}
+                                                disabled={location.is_none()}
+                                            >
+                                                <span class={classes!("font-medium", color)}>{ label }</span>
+                                                if let Some((line, column)) = location {
+                                                    <span class="ml-2 font-mono text-xs text-gray-400">
+                                                        { format!("{}:{}", line, column) }
+                                                    </span>
+                                                }
+                                                <p class="text-gray-700 dark:text-gray-300 mt-0.5 break-words">
+                                                    { &diagnostic.message }
+                                                </p>
+                                            </button>
+                                        </li>
+                                    }
+                                }) }
+                            </ul>
+                        }
+                    </Card>
+
+                    if let Some(result) = validation.as_ref().filter(|v| !v.skills.is_empty()) {
+                        <Card title="Skills">
+                            <ul class="space-y-2 text-sm">
+                                { for result.skills.iter().map(|skill| html! {
+                                    <li class="flex items-center justify-between gap-2">
+                                        <span class="font-medium text-gray-900 dark:text-white truncate">{ &skill.name }</span>
+                                        <span class="text-xs text-gray-500 dark:text-gray-400 font-mono truncate" title={skill.source.clone()}>
+                                            { &skill.runtime }
+                                        </span>
+                                    </li>
+                                }) }
+                            </ul>
+                        </Card>
+                    }
+
+                    if let Some(result) = &*apply_result {
+                        <Card title="Last Apply">
+                            if result.installs.is_empty() && result.errors.is_empty() {
+                                <p class="text-sm text-gray-500 dark:text-gray-400">{ "Nothing to install" }</p>
+                            }
+                            <ul class="space-y-1 text-sm">
+                                { for result.installs.iter().map(|job| html! {
+                                    <li class="flex items-center justify-between gap-2">
+                                        <span class="font-mono text-xs truncate" title={job.source.clone()}>{ &job.source }</span>
+                                        <span class="text-xs text-gray-500 dark:text-gray-400">{ &job.message }</span>
+                                    </li>
+                                }) }
+                                { for result.errors.iter().map(|error| html! {
+                                    <li class="text-error-600 dark:text-error-500">{ error }</li>
+                                }) }
+                            </ul>
+                        </Card>
+                    }
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct EditorProps {
+    content: AttrValue,
+    diagnostics: Vec<ManifestDiagnostic>,
+    textarea_ref: NodeRef,
+    scroll_ref: NodeRef,
+    on_input: Callback<InputEvent>,
+    on_keydown: Callback<KeyboardEvent>,
+}
+
+/// TOML editor: a transparent textarea over the highlighted source, so the
+/// browser handles editing and the layer below only paints
+#[function_component(Editor)]
+fn editor(props: &EditorProps) -> Html {
+    let highlighted = use_memo(props.content.clone(), |content| {
+        let mut html = highlight(content, "toml");
+        // A trailing newline opens a line the textarea shows but `<pre>` drops
+        if content.is_empty() || content.ends_with('\n') {
+            html.push(' ');
+        }
+        html
+    });
+
+    // Worst severity per line for the gutter markers
+    let mut markers: HashMap<usize, (DiagnosticSeverity, String)> = HashMap::new();
+    for diagnostic in &props.diagnostics {
+        let Some(line) = diagnostic.line else { continue };
+        let entry = markers
+            .entry(line)
+            .or_insert_with(|| (diagnostic.severity, String::new()));
+        if diagnostic.severity == DiagnosticSeverity::Error {
+            entry.0 = DiagnosticSeverity::Error;
+        }
+        if !entry.1.is_empty() {
+            entry.1.push('\n');
+        }
+        entry.1.push_str(&diagnostic.message);
+    }
+
+    let line_count = props.content.split('\n').count();
+
+    html! {
+        <div ref={props.scroll_ref.clone()}
+            class="-m-6 max-h-[36rem] overflow-auto bg-gray-50 dark:bg-gray-900 rounded-b-xl">
+            <div class="flex min-h-[24rem] font-mono text-sm leading-5">
+                <div class="select-none py-3 pl-3 pr-2 text-right text-gray-400 dark:text-gray-500 border-r border-gray-200 dark:border-gray-700">
+                    { for (1..=line_count).map(|line| {
+                        match markers.get(&line) {
+                            Some((severity, message)) => {
+                                let color = match severity {
+                                    DiagnosticSeverity::Error => "text-error-600 dark:text-error-500 font-bold",
+                                    DiagnosticSeverity::Warning => "text-warning-600 dark:text-warning-500 font-bold",
+                                };
+                                html! { <div class={color} title={message.clone()}>{ line }</div> }
+                            }
+                            None => html! { <div>{ line }</div> },
+                        }
+                    }) }
+                </div>
+                <div class="relative flex-1 min-w-0">
+                    <pre class="m-0 p-3 whitespace-pre text-gray-800 dark:text-gray-100 pointer-events-none" aria-hidden="true">
+                        <code>{ Html::from_html_unchecked(AttrValue::from((*highlighted).clone())) }</code>
+                    </pre>
+                    <textarea
+                        ref={props.textarea_ref.clone()}
+                        class="absolute inset-0 w-full h-full p-3 m-0 resize-none overflow-hidden whitespace-pre bg-transparent text-transparent caret-gray-900 dark:caret-white outline-none font-mono text-sm leading-5"
+                        wrap="off"
+                        spellcheck="false"
+                        autocomplete="off"
+                        aria-label="Manifest TOML"
+                        value={props.content.clone()}
+                        oninput={props.on_input.clone()}
+                        onkeydown={props.on_keydown.clone()}
+                    />
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct DiffViewProps {
+    old: AttrValue,
+    new: AttrValue,
+}
+
+/// Line diff of the edits against the current manifest
+#[function_component(DiffView)]
+fn diff_view(props: &DiffViewProps) -> Html {
+    let rows = use_memo((props.old.clone(), props.new.clone()), |(old, new)| {
+        collapse(diff_lines(old, new), DIFF_CONTEXT)
+    });
+
+    if rows.iter().all(|row| matches!(row, DiffRow::Line { op: DiffOp::Same, .. } | DiffRow::Skipped(_))) {
+        return html! {
+            <p class="text-sm text-gray-500 dark:text-gray-400">{ "No changes from the current manifest." }</p>
+        };
+    }
+
+    html! {
+        <div class="-m-6 max-h-[36rem] overflow-auto font-mono text-sm leading-5">
+            { for rows.iter().map(|row| match row {
+                DiffRow::Skipped(count) => html! {
+                    <div class="px-3 py-1 text-xs text-gray-400 bg-gray-50 dark:bg-gray-900/50">
+                        { format!("⋯ {} unchanged line(s)", count) }
+                    </div>
+                },
+                DiffRow::Line { op, old_line, new_line, text } => {
+                    let (class, sign) = match op {
+                        DiffOp::Same => ("", " "),
+                        DiffOp::Added => ("bg-success-50 dark:bg-success-500/10 text-success-600 dark:text-success-500", "+"),
+                        DiffOp::Removed => ("bg-error-50 dark:bg-error-500/10 text-error-600 dark:text-error-500", "-"),
+                    };
+                    html! {
+                        <div class={classes!("flex", class)}>
+                            <span class="w-10 shrink-0 pr-2 text-right text-gray-400 select-none">
+                                { old_line.map(|n| n.to_string()).unwrap_or_default() }
+                            </span>
+                            <span class="w-10 shrink-0 pr-2 text-right text-gray-400 select-none">
+                                { new_line.map(|n| n.to_string()).unwrap_or_default() }
+                            </span>
+                            <span class="w-4 shrink-0 select-none">{ sign }</span>
+                            <span class="whitespace-pre">{ text }</span>
+                        </div>
+                    }
+                }
+            }) }
+        </div>
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DiffOp {
+    Same,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum DiffRow {
+    Line {
+        op: DiffOp,
+        old_line: Option<usize>,
+        new_line: Option<usize>,
+        text: String,
+    },
+    /// A run of unchanged lines folded away
+    Skipped(usize),
+}
+
+/// Diff two texts line by line using the longest common subsequence
+fn diff_lines(old: &str, new: &str) -> Vec<DiffRow> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // Only the part between the common prefix and suffix needs the table
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let a_mid = &a[prefix..a.len() - suffix];
+    let b_mid = &b[prefix..b.len() - suffix];
+
+    // lcs[i][j]: common subsequence length of a_mid[i..] and b_mid[j..]
+    let (n, m) = (a_mid.len(), b_mid.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if a_mid[i] == b_mid[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut rows = Vec::with_capacity(a.len().max(b.len()));
+    let same = |rows: &mut Vec<DiffRow>, i: usize, j: usize, text: &str| {
+        rows.push(DiffRow::Line {
+            op: DiffOp::Same,
+            old_line: Some(i + 1),
+            new_line: Some(j + 1),
+            text: text.to_string(),
+        })
+    };
+
+    for (i, text) in a[..prefix].iter().enumerate() {
+        same(&mut rows, i, i, text);
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && a_mid[i] == b_mid[j] {
+            same(&mut rows, prefix + i, prefix + j, a_mid[i]);
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            rows.push(DiffRow::Line {
+                op: DiffOp::Added,
+                old_line: None,
+                new_line: Some(prefix + j + 1),
+                text: b_mid[j].to_string(),
+            });
+            j += 1;
+        } else {
+            rows.push(DiffRow::Line {
+                op: DiffOp::Removed,
+                old_line: Some(prefix + i + 1),
+                new_line: None,
+                text: a_mid[i].to_string(),
+            });
+            i += 1;
+        }
+    }
+
+    for k in 0..suffix {
+        let (i, j) = (a.len() - suffix + k, b.len() - suffix + k);
+        same(&mut rows, i, j, a[i]);
+    }
+
+    rows
+}
+
+/// Fold unchanged lines further than `context` lines from any change
+fn collapse(rows: Vec<DiffRow>, context: usize) -> Vec<DiffRow> {
+    let changed: Vec<usize> = rows
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| !matches!(row, DiffRow::Line { op: DiffOp::Same, .. }))
+        .map(|(index, _)| index)
+        .collect();
+
+    let near_change = |index: usize| {
+        changed
+            .iter()
+            .any(|&c| index + context >= c && index <= c + context)
+    };
+
+    let mut collapsed = Vec::new();
+    let mut skipped = 0;
+    for (index, row) in rows.into_iter().enumerate() {
+        if near_change(index) {
+            if skipped > 0 {
+                collapsed.push(DiffRow::Skipped(skipped));
+                skipped = 0;
+            }
+            collapsed.push(row);
+        } else {
+            skipped += 1;
+        }
+    }
+    if skipped > 0 {
+        collapsed.push(DiffRow::Skipped(skipped));
+    }
+    collapsed
+}
+
+/// UTF-16 offset of a 1-based line and column, as textarea selections count
+fn utf16_offset(content: &str, line: usize, column: usize) -> u32 {
+    let mut offset = 0;
+    for (index, text) in content.split_inclusive('\n').enumerate() {
+        if index + 1 == line {
+            let within: usize = text
+                .trim_end_matches('\n')
+                .chars()
+                .take(column.saturating_sub(1))
+                .map(char::len_utf16)
+                .sum();
+            return (offset + within) as u32;
+        }
+        offset += text.encode_utf16().count();
+    }
+    offset as u32
+}
@@ -8,6 +8,8 @@ pub mod skills;
 pub mod skill_detail;
 pub mod run;
 pub mod history;
+pub mod manifest;
+pub mod workflows;
 pub mod settings;
 pub mod search_test;
 pub mod onboarding;
@@ -1,10 +1,145 @@
 //! Onboarding wizard page
+//!
+//! Each step sets something up for real through the server's APIs: the
+//! search step tests and saves the embedding provider and vector backend,
+//! the agent step saves the agent configuration, the skills step installs a
+//! starter pack as install jobs and follows them, and the try step runs a
+//! search against the new index. The last step reads back what the server
+//! now has.
 
+use gloo_timers::future::TimeoutFuture;
+use std::rc::Rc;
+use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 use yew_router::prelude::*;
+use yewdux::prelude::*;
 
-use crate::components::icons::{CheckIcon, ChevronRightIcon};
+use crate::api::{
+    AgentModelConfig, AgentRuntime, Api, InstallJob, InstallSkillRequest, InstallStage,
+    RuntimeInfo, SearchResponse, TestConnectionRequest, TestConnectionResponse,
+    UpdateAgentConfigRequest, UpdateSearchConfigRequest,
+};
+use crate::components::icons::{CheckIcon, ChevronRightIcon, XIcon};
 use crate::router::Route;
+use crate::store::settings::{SettingsAction, SettingsStore};
+
+/// Wizard steps, in order
+const STEPS: [(&str, &str); 6] = [
+    ("welcome", "Welcome"),
+    ("search", "Search"),
+    ("agent", "Agent"),
+    ("skills", "Skills"),
+    ("try", "Try It"),
+    ("complete", "Complete"),
+];
+
+/// Skills offered by the skills step: name, description, source, preselected
+const STARTER_PACK: [(&str, &str, &str, bool); 4] = [
+    (
+        "github",
+        "GitHub repositories, issues and pull requests",
+        "github:kubiyabot/skill//examples/wasm-skills/github-skill",
+        true,
+    ),
+    (
+        "http",
+        "HTTP requests to any API",
+        "github:kubiyabot/skill//examples/wasm-skills/http-skill",
+        true,
+    ),
+    (
+        "slack",
+        "Slack messages and channels",
+        "github:kubiyabot/skill//examples/wasm-skills/slack-skill",
+        false,
+    ),
+    (
+        "aws",
+        "AWS cloud services",
+        "github:kubiyabot/skill//examples/wasm-skills/aws-skill",
+        false,
+    ),
+];
+
+/// Queries suggested by the try step
+const SAMPLE_QUERIES: [&str; 3] = [
+    "list open pull requests",
+    "send a GET request to an API",
+    "post a message to a channel",
+];
+
+/// How often the skills step polls its install jobs
+const INSTALL_POLL_MS: u32 = 1000;
+
+fn step_route(step: &str) -> Route {
+    Route::OnboardingStep { step: step.to_string() }
+}
+
+/// Default embedding model of a provider
+fn default_embedding_model(provider: &str) -> &'static str {
+    match provider {
+        "openai" => "text-embedding-3-small",
+        "ollama" => "nomic-embed-text",
+        _ => "all-minilm",
+    }
+}
+
+/// Provider key of an agent runtime in the server's model list
+fn agent_provider(runtime: &AgentRuntime) -> &'static str {
+    match runtime {
+        AgentRuntime::OpenAI => "openai",
+        AgentRuntime::Gemini => "google",
+        _ => "anthropic",
+    }
+}
+
+/// What a runtime needs on the server before it can run
+fn agent_setup_hint(runtime: &AgentRuntime) -> &'static str {
+    match runtime {
+        AgentRuntime::ClaudeCode => "Install the claude CLI on the server",
+        AgentRuntime::OpenAI => "Set OPENAI_API_KEY on the server",
+        AgentRuntime::Gemini => "Set GEMINI_API_KEY or GOOGLE_API_KEY on the server",
+        AgentRuntime::Custom => "Custom runtimes can't be run by the server",
+    }
+}
+
+/// A selectable card in a list of choices
+fn choice_card(
+    group: &'static str,
+    label: &str,
+    desc: Html,
+    selected: bool,
+    onclick: Callback<MouseEvent>,
+) -> Html {
+    html! {
+        <label class={classes!(
+            "flex", "items-start", "gap-4", "p-4", "rounded-lg", "border", "cursor-pointer", "transition-colors",
+            if selected {
+                "border-primary-500 bg-primary-50 dark:bg-primary-900/30"
+            } else {
+                "border-gray-200 dark:border-gray-700 hover:border-gray-300"
+            }
+        )}>
+            <input type="radio" name={group} checked={selected} {onclick} class="mt-1" />
+            <div>
+                <span class="font-medium text-gray-900 dark:text-white">{ label.to_string() }</span>
+                <div class="text-sm text-gray-500 mt-1">{ desc }</div>
+            </div>
+        </label>
+    }
+}
+
+/// An error banner inside a step
+fn error_banner(error: &Option<String>) -> Html {
+    match error {
+        Some(error) => html! {
+            <div class="mt-6 p-3 rounded-lg bg-error-50 dark:bg-red-900/20 text-sm text-error-700 dark:text-red-300">
+                { error.clone() }
+            </div>
+        },
+        None => html! {},
+    }
+}
 
 /// Onboarding page props
 #[derive(Properties, PartialEq)]
@@ -16,13 +151,7 @@ pub struct OnboardingPageProps {
 /// Onboarding page component
 #[function_component(OnboardingPage)]
 pub fn onboarding_page(props: &OnboardingPageProps) -> Html {
-    let steps = [("welcome", "Welcome"),
-        ("search", "Search"),
-        ("credentials", "Credentials"),
-        ("skills", "Skills"),
-        ("complete", "Complete")];
-
-    let current_step_idx = steps.iter().position(|(id, _)| *id == props.step).unwrap_or(0);
+    let current_step_idx = STEPS.iter().position(|(id, _)| *id == props.step).unwrap_or(0);
 
     html! {
         <div class="min-h-screen bg-gradient-to-br from-primary-900 to-primary-950 flex flex-col">
@@ -38,7 +167,7 @@ pub fn onboarding_page(props: &OnboardingPageProps) -> Html {
             <div class="px-6 py-4">
                 <div class="max-w-2xl mx-auto">
                     <div class="flex items-center justify-between">
-                        { for steps.iter().enumerate().map(|(i, (_id, label))| {
+                        { for STEPS.iter().enumerate().map(|(i, (_id, label))| {
                             let is_complete = i < current_step_idx;
                             let is_current = i == current_step_idx;
 
@@ -68,7 +197,7 @@ pub fn onboarding_page(props: &OnboardingPageProps) -> Html {
                                             { *label }
                                         </span>
                                     </div>
-                                    if i < steps.len() - 1 {
+                                    if i < STEPS.len() - 1 {
                                         <div class={classes!(
                                             "flex-1", "h-1", "mx-2", "rounded",
                                             if is_complete { "bg-success-500" } else { "bg-primary-800" }
@@ -88,8 +217,9 @@ pub fn onboarding_page(props: &OnboardingPageProps) -> Html {
                         match props.step.as_str() {
                             "welcome" => html! { <WelcomeStep /> },
                             "search" => html! { <SearchStep /> },
-                            "credentials" => html! { <CredentialsStep /> },
+                            "agent" => html! { <AgentStep /> },
                             "skills" => html! { <SkillsStep /> },
+                            "try" => html! { <TryStep /> },
                             "complete" => html! { <CompleteStep /> },
                             _ => html! { <WelcomeStep /> },
                         }
@@ -108,7 +238,7 @@ fn welcome_step() -> Html {
     let on_start = {
         let navigator = navigator.clone();
         Callback::from(move |_| {
-            navigator.push(&Route::OnboardingStep { step: "search".to_string() });
+            navigator.push(&step_route("search"));
         })
     };
 
@@ -131,10 +261,10 @@ fn welcome_step() -> Html {
 
             <div class="space-y-3 text-left max-w-sm mx-auto mb-8">
                 { for [
-                    "Search Pipeline - How skills are discovered",
-                    "AI Integration - Connect LLM providers",
-                    "Starter Skills - Get productive immediately",
-                    "Claude Code - Seamless integration",
+                    "Search Pipeline - Test and save how skills are found",
+                    "AI Agent - Choose who enhances and summarizes",
+                    "Starter Skills - Install a pack to get productive",
+                    "Try It - Run your first search",
                 ].iter().map(|item| html! {
                     <div class="flex items-center gap-3">
                         <CheckIcon class="w-5 h-5 text-success-500 flex-shrink-0" />
@@ -160,27 +290,117 @@ fn welcome_step() -> Html {
     }
 }
 
-/// Search setup step component
+/// Search setup step: test the embedding provider and vector backend, then
+/// save them
 #[function_component(SearchStep)]
 fn search_step() -> Html {
     let navigator = use_navigator().unwrap();
+    let api = use_memo((), |_| Rc::new(Api::new()));
     let embedding_provider = use_state(|| "fastembed".to_string());
-    let vector_store = use_state(|| "inmemory".to_string());
+    let vector_store = use_state(|| "file".to_string());
+    let testing = use_state(|| false);
+    let test_result = use_state(|| Option::<TestConnectionResponse>::None);
+    let saving = use_state(|| false);
+    let error = use_state(|| Option::<String>::None);
+
+    // Start from what the server has
+    {
+        let api = api.clone();
+        let embedding_provider = embedding_provider.clone();
+        let vector_store = vector_store.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                if let Ok(config) = api.search.get_config().await {
+                    embedding_provider.set(config.embedding_provider);
+                    vector_store.set(config.vector_backend);
+                }
+            });
+        });
+    }
+
+    let on_test = {
+        let api = api.clone();
+        let embedding_provider = embedding_provider.clone();
+        let vector_store = vector_store.clone();
+        let testing = testing.clone();
+        let test_result = test_result.clone();
+        let error = error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let request = TestConnectionRequest {
+                embedding_provider: (*embedding_provider).clone(),
+                embedding_model: default_embedding_model(&embedding_provider).to_string(),
+                vector_backend: (*vector_store).clone(),
+                qdrant_url: None,
+                ollama_url: None,
+            };
+            testing.set(true);
+            test_result.set(None);
+            error.set(None);
+
+            let api = api.clone();
+            let testing = testing.clone();
+            let test_result = test_result.clone();
+            let error = error.clone();
+            spawn_local(async move {
+                match api.search.test_connection(&request).await {
+                    Ok(response) => test_result.set(Some(response)),
+                    Err(e) => error.set(Some(format!("Connection test failed: {}", e))),
+                }
+                testing.set(false);
+            });
+        })
+    };
 
     let on_next = {
+        let api = api.clone();
         let navigator = navigator.clone();
-        Callback::from(move |_| {
-            navigator.push(&Route::OnboardingStep { step: "credentials".to_string() });
+        let embedding_provider = embedding_provider.clone();
+        let vector_store = vector_store.clone();
+        let saving = saving.clone();
+        let error = error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let request = UpdateSearchConfigRequest {
+                embedding_provider: Some((*embedding_provider).clone()),
+                embedding_model: Some(default_embedding_model(&embedding_provider).to_string()),
+                vector_backend: Some((*vector_store).clone()),
+                ..Default::default()
+            };
+            saving.set(true);
+            error.set(None);
+
+            let api = api.clone();
+            let navigator = navigator.clone();
+            let saving = saving.clone();
+            let error = error.clone();
+            spawn_local(async move {
+                // The server refuses a configuration it can't build a pipeline from
+                match api.search.update_config(&request).await {
+                    Ok(_) => navigator.push(&step_route("agent")),
+                    Err(e) => error.set(Some(format!("Couldn't save the search configuration: {}", e))),
+                }
+                saving.set(false);
+            });
         })
     };
 
     let on_back = {
         let navigator = navigator.clone();
         Callback::from(move |_| {
-            navigator.push(&Route::OnboardingStep { step: "welcome".to_string() });
+            navigator.push(&step_route("welcome"));
         })
     };
 
+    let provider_choices = [
+        ("fastembed", "FastEmbed (Recommended)", "Local, offline, no API keys required"),
+        ("openai", "OpenAI", "Cloud-based, needs OPENAI_API_KEY on the server"),
+        ("ollama", "Ollama", "Self-hosted, requires Ollama installation"),
+    ];
+    let backend_choices = [
+        ("file", "File (Recommended)", "Kept on disk, so the index survives restarts"),
+        ("inmemory", "In-Memory", "Rebuilt on every start; fine for development"),
+        ("qdrant", "Qdrant", "Production-ready, requires a Qdrant server"),
+    ];
+
     html! {
         <div class="bg-white dark:bg-gray-800 rounded-2xl shadow-xl p-8">
             <h2 class="text-2xl font-bold text-gray-900 dark:text-white mb-2">
@@ -197,39 +417,15 @@ fn search_step() -> Html {
                         { "Embedding Provider" }
                     </label>
                     <div class="space-y-3">
-                        { for [
-                            ("fastembed", "FastEmbed (Recommended)", "Local, offline, no API keys required"),
-                            ("openai", "OpenAI", "Cloud-based, requires API key"),
-                            ("ollama", "Ollama", "Self-hosted, requires Ollama installation"),
-                        ].iter().map(|(value, label, desc)| {
-                            let is_selected = *embedding_provider == *value;
+                        { for provider_choices.iter().map(|(value, label, desc)| {
                             let provider = embedding_provider.clone();
+                            let test_result = test_result.clone();
                             let val = value.to_string();
-                            let onclick = Callback::from(move |_| provider.set(val.clone()));
-
-                            html! {
-                                <label class={classes!(
-                                    "flex", "items-start", "gap-4", "p-4", "rounded-lg", "border", "cursor-pointer", "transition-colors",
-                                    if is_selected {
-                                        "border-primary-500 bg-primary-50 dark:bg-primary-900/30"
-                                    } else {
-                                        "border-gray-200 dark:border-gray-700 hover:border-gray-300"
-                                    }
-                                )}>
-                                    <input
-                                        type="radio"
-                                        name="embedding"
-                                        value={*value}
-                                        checked={is_selected}
-                                        onclick={onclick}
-                                        class="mt-1"
-                                    />
-                                    <div>
-                                        <span class="font-medium text-gray-900 dark:text-white">{ *label }</span>
-                                        <p class="text-sm text-gray-500 mt-1">{ *desc }</p>
-                                    </div>
-                                </label>
-                            }
+                            let onclick = Callback::from(move |_| {
+                                provider.set(val.clone());
+                                test_result.set(None);
+                            });
+                            choice_card("embedding", label, html! { *desc }, *embedding_provider == *value, onclick)
                         }) }
                     </div>
                 </div>
@@ -240,47 +436,53 @@ fn search_step() -> Html {
                         { "Vector Store" }
                     </label>
                     <div class="space-y-3">
-                        { for [
-                            ("inmemory", "In-Memory (Recommended)", "Great for development and small deployments"),
-                            ("qdrant", "Qdrant", "Production-ready, requires Qdrant server"),
-                        ].iter().map(|(value, label, desc)| {
-                            let is_selected = *vector_store == *value;
+                        { for backend_choices.iter().map(|(value, label, desc)| {
                             let store = vector_store.clone();
+                            let test_result = test_result.clone();
                             let val = value.to_string();
-                            let onclick = Callback::from(move |_| store.set(val.clone()));
+                            let onclick = Callback::from(move |_| {
+                                store.set(val.clone());
+                                test_result.set(None);
+                            });
+                            choice_card("store", label, html! { *desc }, *vector_store == *value, onclick)
+                        }) }
+                    </div>
+                </div>
 
-                            html! {
-                                <label class={classes!(
-                                    "flex", "items-start", "gap-4", "p-4", "rounded-lg", "border", "cursor-pointer", "transition-colors",
-                                    if is_selected {
-                                        "border-primary-500 bg-primary-50 dark:bg-primary-900/30"
+                // Connection test
+                <div class="flex items-center gap-4">
+                    <button class="btn btn-secondary" onclick={on_test} disabled={*testing}>
+                        { if *testing { "Testing..." } else { "Test Connection" } }
+                    </button>
+                    if let Some(result) = (*test_result).clone() {
+                        <div class="text-sm space-y-1">
+                            { for [&result.embedding_provider_status, &result.vector_backend_status].into_iter().map(|status| html! {
+                                <div class="flex items-center gap-2">
+                                    if status.healthy {
+                                        <CheckIcon class="w-4 h-4 text-success-500" />
                                     } else {
-                                        "border-gray-200 dark:border-gray-700 hover:border-gray-300"
+                                        <XIcon class="w-4 h-4 text-error-500" />
                                     }
-                                )}>
-                                    <input
-                                        type="radio"
-                                        name="store"
-                                        value={*value}
-                                        checked={is_selected}
-                                        onclick={onclick}
-                                        class="mt-1"
-                                    />
-                                    <div>
-                                        <span class="font-medium text-gray-900 dark:text-white">{ *label }</span>
-                                        <p class="text-sm text-gray-500 mt-1">{ *desc }</p>
-                                    </div>
-                                </label>
-                            }
-                        }) }
-                    </div>
+                                    <span class="text-gray-700 dark:text-gray-300">
+                                        { status.name.clone() }
+                                        if let Some(message) = status.message.clone() {
+                                            <span class="text-gray-500">{ format!(" - {}", message) }</span>
+                                        }
+                                    </span>
+                                </div>
+                            }) }
+                            <div class="text-xs text-gray-500">{ format!("{}ms", result.duration_ms) }</div>
+                        </div>
+                    }
                 </div>
             </div>
 
+            { error_banner(&error) }
+
             <div class="flex justify-between mt-8">
                 <button class="btn btn-ghost" onclick={on_back}>{ "Back" }</button>
-                <button class="btn btn-primary" onclick={on_next}>
-                    { "Next" }
+                <button class="btn btn-primary" onclick={on_next} disabled={*saving}>
+                    { if *saving { "Saving..." } else { "Save & Continue" } }
                     <ChevronRightIcon class="w-4 h-4 ml-2" />
                 </button>
             </div>
@@ -288,101 +490,222 @@ fn search_step() -> Html {
     }
 }
 
-/// Credentials step component
-#[function_component(CredentialsStep)]
-fn credentials_step() -> Html {
+/// Agent step: choose the runtime that enhances skills and summarizes
+/// executions
+#[function_component(AgentStep)]
+fn agent_step() -> Html {
     let navigator = use_navigator().unwrap();
+    let api = use_memo((), |_| Rc::new(Api::new()));
+    let runtimes = use_state(Vec::<RuntimeInfo>::new);
+    let models = use_state(std::collections::HashMap::<String, Vec<crate::api::ModelInfo>>::new);
+    let runtime = use_state(|| AgentRuntime::ClaudeCode);
+    let saving = use_state(|| false);
+    let error = use_state(|| Option::<String>::None);
+
+    {
+        let api = api.clone();
+        let runtimes = runtimes.clone();
+        let models = models.clone();
+        let runtime = runtime.clone();
+        let error = error.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                match api.agent.get_config().await {
+                    Ok(response) => {
+                        runtime.set(response.config.runtime);
+                        runtimes.set(response.available_runtimes);
+                        models.set(response.available_models);
+                    }
+                    Err(e) => error.set(Some(format!("Couldn't load the agent configuration: {}", e))),
+                }
+            });
+        });
+    }
 
     let on_next = {
+        let api = api.clone();
+        let navigator = navigator.clone();
+        let runtime = runtime.clone();
+        let models = models.clone();
+        let saving = saving.clone();
+        let error = error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let provider = agent_provider(&runtime);
+            let model = models
+                .get(provider)
+                .and_then(|models| models.first())
+                .map(|model| model.id.clone());
+            let request = UpdateAgentConfigRequest {
+                runtime: Some((*runtime).clone()),
+                model_config: model.map(|model| AgentModelConfig {
+                    provider: provider.to_string(),
+                    model,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+            saving.set(true);
+            error.set(None);
+
+            let api = api.clone();
+            let navigator = navigator.clone();
+            let saving = saving.clone();
+            let error = error.clone();
+            spawn_local(async move {
+                match api.agent.update_config(&request).await {
+                    Ok(_) => navigator.push(&step_route("skills")),
+                    Err(e) => error.set(Some(format!("Couldn't save the agent configuration: {}", e))),
+                }
+                saving.set(false);
+            });
+        })
+    };
+
+    let on_skip = {
         let navigator = navigator.clone();
         Callback::from(move |_| {
-            navigator.push(&Route::OnboardingStep { step: "skills".to_string() });
+            navigator.push(&step_route("skills"));
         })
     };
 
     let on_back = {
         let navigator = navigator.clone();
         Callback::from(move |_| {
-            navigator.push(&Route::OnboardingStep { step: "search".to_string() });
+            navigator.push(&step_route("search"));
         })
     };
 
     html! {
         <div class="bg-white dark:bg-gray-800 rounded-2xl shadow-xl p-8">
             <h2 class="text-2xl font-bold text-gray-900 dark:text-white mb-2">
-                { "Credentials" }
+                { "AI Agent" }
             </h2>
             <p class="text-gray-600 dark:text-gray-300 mb-8">
-                { "Add API keys for enhanced features (optional)" }
+                { "Choose who generates skill examples and summarizes executions (optional)" }
             </p>
 
-            <div class="space-y-4">
-                <div>
-                    <label class="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
-                        { "OpenAI API Key" }
-                        <span class="text-gray-400 ml-1">{ "(optional)" }</span>
-                    </label>
-                    <input
-                        type="password"
-                        class="input"
-                        placeholder="sk-..."
-                    />
-                    <p class="text-xs text-gray-500 mt-1">
-                        { "Used for OpenAI embeddings and skill enhancement" }
-                    </p>
-                </div>
-
-                <div>
-                    <label class="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
-                        { "Anthropic API Key" }
-                        <span class="text-gray-400 ml-1">{ "(optional)" }</span>
-                    </label>
-                    <input
-                        type="password"
-                        class="input"
-                        placeholder="sk-ant-..."
-                    />
-                    <p class="text-xs text-gray-500 mt-1">
-                        { "Used for AI-powered skill enhancement" }
-                    </p>
-                </div>
+            <div class="space-y-3">
+                if runtimes.is_empty() && error.is_none() {
+                    <p class="text-sm text-gray-500">{ "Loading runtimes..." }</p>
+                }
+                { for runtimes.iter().map(|info| {
+                    let selected = *runtime == info.runtime;
+                    let choice = runtime.clone();
+                    let value = info.runtime.clone();
+                    let onclick = Callback::from(move |_| choice.set(value.clone()));
+                    let desc = html! {
+                        <>
+                            <p>{ info.description.clone() }</p>
+                            if info.available {
+                                <p class="text-success-600 mt-1">{ "Ready" }</p>
+                            } else {
+                                <p class="text-warning-600 mt-1">{ agent_setup_hint(&info.runtime) }</p>
+                            }
+                        </>
+                    };
+                    choice_card("agent", &info.name, desc, selected, onclick)
+                }) }
             </div>
 
+            { error_banner(&error) }
+
             <div class="flex justify-between mt-8">
                 <button class="btn btn-ghost" onclick={on_back}>{ "Back" }</button>
-                <button class="btn btn-primary" onclick={on_next}>
-                    { "Next" }
-                    <ChevronRightIcon class="w-4 h-4 ml-2" />
-                </button>
+                <div class="flex gap-3">
+                    <button class="btn btn-ghost" onclick={on_skip}>{ "Skip" }</button>
+                    <button class="btn btn-primary" onclick={on_next} disabled={*saving || runtimes.is_empty()}>
+                        { if *saving { "Saving..." } else { "Save & Continue" } }
+                        <ChevronRightIcon class="w-4 h-4 ml-2" />
+                    </button>
+                </div>
             </div>
         </div>
     }
 }
 
-/// Skills step component
+/// Skills step: install the selected starter skills and follow the installs
 #[function_component(SkillsStep)]
 fn skills_step() -> Html {
     let navigator = use_navigator().unwrap();
+    let api = use_memo((), |_| Rc::new(Api::new()));
+    let selected = use_state(|| {
+        STARTER_PACK
+            .iter()
+            .filter(|(_, _, _, default)| *default)
+            .map(|(name, ..)| name.to_string())
+            .collect::<Vec<_>>()
+    });
+    let jobs = use_state(Vec::<InstallJob>::new);
+    let error = use_state(|| Option::<String>::None);
+
+    let installing = jobs.iter().any(|job| !job.stage.is_finished());
+    let started = !jobs.is_empty();
+
+    let on_install = {
+        let api = api.clone();
+        let navigator = navigator.clone();
+        let selected = selected.clone();
+        let jobs = jobs.clone();
+        let error = error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let requests: Vec<InstallSkillRequest> = STARTER_PACK
+                .iter()
+                .filter(|(name, ..)| selected.contains(&name.to_string()))
+                .map(|(name, _, source, _)| InstallSkillRequest {
+                    source: source.to_string(),
+                    name: Some(name.to_string()),
+                    git_ref: None,
+                    instance: None,
+                    force: false,
+                })
+                .collect();
+            if requests.is_empty() {
+                navigator.push(&step_route("try"));
+                return;
+            }
+            error.set(None);
+
+            let api = api.clone();
+            let jobs = jobs.clone();
+            let error = error.clone();
+            spawn_local(async move {
+                let mut current = Vec::new();
+                for request in &requests {
+                    match api.skills.install(request).await {
+                        Ok(job) => current.push(job),
+                        Err(e) => error.set(Some(format!("Couldn't install {}: {}", request.source, e))),
+                    }
+                }
+
+                // The server installs one skill at a time; follow them all
+                while current.iter().any(|job| !job.stage.is_finished()) {
+                    jobs.set(current.clone());
+                    TimeoutFuture::new(INSTALL_POLL_MS).await;
+                    for job in current.iter_mut().filter(|job| !job.stage.is_finished()) {
+                        if let Ok(update) = api.skills.get_install(&job.id).await {
+                            *job = update;
+                        }
+                    }
+                }
+                jobs.set(current);
+            });
+        })
+    };
 
     let on_next = {
         let navigator = navigator.clone();
         Callback::from(move |_| {
-            navigator.push(&Route::OnboardingStep { step: "complete".to_string() });
+            navigator.push(&step_route("try"));
         })
     };
 
     let on_back = {
         let navigator = navigator.clone();
         Callback::from(move |_| {
-            navigator.push(&Route::OnboardingStep { step: "credentials".to_string() });
+            navigator.push(&step_route("agent"));
         })
     };
 
-    let starter_skills = [("kubernetes", "Kubernetes cluster management", true),
-        ("github", "GitHub repository operations", true),
-        ("docker", "Docker container management", false),
-        ("aws", "AWS cloud services", false)];
-
     html! {
         <div class="bg-white dark:bg-gray-800 rounded-2xl shadow-xl p-8">
             <h2 class="text-2xl font-bold text-gray-900 dark:text-white mb-2">
@@ -392,28 +715,214 @@ fn skills_step() -> Html {
                 { "Select skills to install (you can add more later)" }
             </p>
 
-            <div class="space-y-3">
-                { for starter_skills.iter().map(|(name, desc, default)| {
-                    html! {
-                        <label class="flex items-start gap-4 p-4 rounded-lg border border-gray-200 dark:border-gray-700 hover:border-gray-300 cursor-pointer transition-colors">
-                            <input
-                                type="checkbox"
-                                checked={*default}
-                                class="mt-1 rounded border-gray-300"
-                            />
+            if started {
+                <div class="space-y-4">
+                    { for jobs.iter().map(|job| {
+                        let name = job.installed.first().cloned().unwrap_or_else(|| {
+                            STARTER_PACK
+                                .iter()
+                                .find(|(_, _, source, _)| *source == job.source)
+                                .map(|(name, ..)| name.to_string())
+                                .unwrap_or_else(|| job.source.clone())
+                        });
+                        let bar = match job.stage {
+                            InstallStage::Completed => "bg-success-500",
+                            InstallStage::Failed | InstallStage::Cancelled => "bg-error-500",
+                            _ => "bg-primary-500",
+                        };
+                        html! {
                             <div>
-                                <span class="font-medium text-gray-900 dark:text-white">{ *name }</span>
-                                <p class="text-sm text-gray-500 mt-1">{ *desc }</p>
+                                <div class="flex justify-between text-sm mb-1">
+                                    <span class="font-medium text-gray-900 dark:text-white">{ name }</span>
+                                    <span class="text-gray-500">{ job.stage.label() }</span>
+                                </div>
+                                <div class="w-full h-2 bg-gray-200 dark:bg-gray-700 rounded">
+                                    <div class={classes!("h-2", "rounded", "transition-all", bar)} style={format!("width: {}%", job.progress)} />
+                                </div>
+                                <p class="text-xs text-gray-500 mt-1">
+                                    { job.error.clone().unwrap_or_else(|| job.message.clone()) }
+                                </p>
                             </div>
-                        </label>
+                        }
+                    }) }
+                </div>
+            } else {
+                <div class="space-y-3">
+                    { for STARTER_PACK.iter().map(|(name, desc, _, _)| {
+                        let checked = selected.contains(&name.to_string());
+                        let selected = selected.clone();
+                        let name_owned = name.to_string();
+                        let onclick = Callback::from(move |_: MouseEvent| {
+                            let mut names = (*selected).clone();
+                            match names.iter().position(|n| *n == name_owned) {
+                                Some(idx) => {
+                                    names.remove(idx);
+                                }
+                                None => names.push(name_owned.clone()),
+                            }
+                            selected.set(names);
+                        });
+                        html! {
+                            <label class="flex items-start gap-4 p-4 rounded-lg border border-gray-200 dark:border-gray-700 hover:border-gray-300 cursor-pointer transition-colors">
+                                <input
+                                    type="checkbox"
+                                    {checked}
+                                    {onclick}
+                                    class="mt-1 rounded border-gray-300"
+                                />
+                                <div>
+                                    <span class="font-medium text-gray-900 dark:text-white">{ *name }</span>
+                                    <p class="text-sm text-gray-500 mt-1">{ *desc }</p>
+                                </div>
+                            </label>
+                        }
+                    }) }
+                </div>
+            }
+
+            { error_banner(&error) }
+
+            <div class="flex justify-between mt-8">
+                <button class="btn btn-ghost" onclick={on_back} disabled={installing}>{ "Back" }</button>
+                if started {
+                    <button class="btn btn-primary" onclick={on_next}>
+                        { if installing { "Continue While Installing" } else { "Continue" } }
+                        <ChevronRightIcon class="w-4 h-4 ml-2" />
+                    </button>
+                } else {
+                    <button class="btn btn-primary" onclick={on_install}>
+                        { if selected.is_empty() { "Continue" } else { "Install & Continue" } }
+                        <ChevronRightIcon class="w-4 h-4 ml-2" />
+                    </button>
+                }
+            </div>
+        </div>
+    }
+}
+
+/// Try step: run a sample search against the index
+#[function_component(TryStep)]
+fn try_step() -> Html {
+    let navigator = use_navigator().unwrap();
+    let api = use_memo((), |_| Rc::new(Api::new()));
+    let query = use_state(|| SAMPLE_QUERIES[0].to_string());
+    let searching = use_state(|| false);
+    let response = use_state(|| Option::<SearchResponse>::None);
+    let error = use_state(|| Option::<String>::None);
+
+    let run_search = {
+        let api = api.clone();
+        let searching = searching.clone();
+        let response = response.clone();
+        let error = error.clone();
+        Callback::from(move |query: String| {
+            searching.set(true);
+            error.set(None);
+
+            let api = api.clone();
+            let searching = searching.clone();
+            let response = response.clone();
+            let error = error.clone();
+            spawn_local(async move {
+                match api.search.query_top_k(&query, 5).await {
+                    Ok(result) => response.set(Some(result)),
+                    Err(e) => error.set(Some(format!("Search failed: {}", e))),
+                }
+                searching.set(false);
+            });
+        })
+    };
+
+    let on_input = {
+        let query = query.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            query.set(input.value());
+        })
+    };
+
+    let on_search = {
+        let query = query.clone();
+        let run_search = run_search.clone();
+        Callback::from(move |_: MouseEvent| run_search.emit((*query).clone()))
+    };
+
+    let on_next = {
+        let navigator = navigator.clone();
+        Callback::from(move |_| {
+            navigator.push(&step_route("complete"));
+        })
+    };
+
+    let on_back = {
+        let navigator = navigator.clone();
+        Callback::from(move |_| {
+            navigator.push(&step_route("skills"));
+        })
+    };
+
+    html! {
+        <div class="bg-white dark:bg-gray-800 rounded-2xl shadow-xl p-8">
+            <h2 class="text-2xl font-bold text-gray-900 dark:text-white mb-2">
+                { "Try a Search" }
+            </h2>
+            <p class="text-gray-600 dark:text-gray-300 mb-6">
+                { "Describe a task and see which tools the search pipeline finds" }
+            </p>
+
+            <div class="flex gap-3">
+                <input
+                    type="text"
+                    class="input flex-1"
+                    value={(*query).clone()}
+                    oninput={on_input}
+                />
+                <button class="btn btn-primary" onclick={on_search} disabled={*searching || query.trim().is_empty()}>
+                    { if *searching { "Searching..." } else { "Search" } }
+                </button>
+            </div>
+            <div class="flex flex-wrap gap-2 mt-3">
+                { for SAMPLE_QUERIES.iter().map(|sample| {
+                    let query = query.clone();
+                    let run_search = run_search.clone();
+                    let onclick = Callback::from(move |_: MouseEvent| {
+                        query.set(sample.to_string());
+                        run_search.emit(sample.to_string());
+                    });
+                    html! {
+                        <button class="btn btn-ghost btn-sm text-xs" {onclick}>{ *sample }</button>
                     }
                 }) }
             </div>
 
+            if let Some(result) = (*response).clone() {
+                <div class="mt-6 space-y-3">
+                    if result.results.is_empty() {
+                        <p class="text-sm text-gray-500">
+                            { "No tools matched. Skills still installing are searchable once they finish." }
+                        </p>
+                    }
+                    { for result.results.iter().map(|hit| html! {
+                        <div class="p-3 rounded-lg border border-gray-200 dark:border-gray-700">
+                            <div class="flex justify-between text-sm">
+                                <span class="font-medium text-gray-900 dark:text-white">
+                                    { format!("{}:{}", hit.skill, hit.tool) }
+                                </span>
+                                <span class="text-gray-500">{ format!("{:.2}", hit.score) }</span>
+                            </div>
+                            <p class="text-xs text-gray-500 mt-1 line-clamp-2">{ hit.content.clone() }</p>
+                        </div>
+                    }) }
+                    <p class="text-xs text-gray-500">{ format!("{}ms", result.duration_ms) }</p>
+                </div>
+            }
+
+            { error_banner(&error) }
+
             <div class="flex justify-between mt-8">
                 <button class="btn btn-ghost" onclick={on_back}>{ "Back" }</button>
                 <button class="btn btn-primary" onclick={on_next}>
-                    { "Install & Continue" }
+                    { "Next" }
                     <ChevronRightIcon class="w-4 h-4 ml-2" />
                 </button>
             </div>
@@ -421,10 +930,57 @@ fn skills_step() -> Html {
     }
 }
 
+/// What the server has after setup
+#[derive(Clone, PartialEq)]
+struct SetupSummary {
+    search: String,
+    indexed_documents: usize,
+    agent: Option<String>,
+    skills: usize,
+}
+
 /// Complete step component
 #[function_component(CompleteStep)]
 fn complete_step() -> Html {
     let navigator = use_navigator().unwrap();
+    let api = use_memo((), |_| Rc::new(Api::new()));
+    let (_, settings_dispatch) = use_store::<SettingsStore>();
+    let summary = use_state(|| Option::<SetupSummary>::None);
+
+    {
+        let api = api.clone();
+        let summary = summary.clone();
+        use_effect_with((), move |_| {
+            settings_dispatch.apply(SettingsAction::CompleteOnboarding);
+            spawn_local(async move {
+                let search = api.search.get_config().await.ok();
+                let agent = api.agent.get_config().await.ok();
+                let skills = api.skills.list_all().await.map(|skills| skills.len()).unwrap_or(0);
+                summary.set(Some(SetupSummary {
+                    search: search
+                        .as_ref()
+                        .map(|config| {
+                            format!(
+                                "{} ({}) on the {} backend",
+                                config.embedding_provider, config.embedding_model, config.vector_backend
+                            )
+                        })
+                        .unwrap_or_else(|| "unavailable".to_string()),
+                    indexed_documents: search.map_or(0, |config| config.indexed_documents),
+                    agent: agent.map(|agent| {
+                        let runtime = agent
+                            .available_runtimes
+                            .iter()
+                            .find(|info| info.runtime == agent.config.runtime)
+                            .map(|info| info.name.clone())
+                            .unwrap_or_else(|| format!("{:?}", agent.config.runtime));
+                        format!("{} ({})", runtime, agent.config.model_config.model)
+                    }),
+                    skills,
+                }));
+            });
+        });
+    }
 
     let on_finish = {
         let navigator = navigator.clone();
@@ -433,6 +989,21 @@ fn complete_step() -> Html {
         })
     };
 
+    let items: Vec<String> = match &*summary {
+        Some(summary) => {
+            let mut items = vec![
+                format!("Search: {}", summary.search),
+                format!("{} documents indexed", summary.indexed_documents),
+                format!("{} skill(s) installed", summary.skills),
+            ];
+            if let Some(agent) = &summary.agent {
+                items.push(format!("Agent: {}", agent));
+            }
+            items
+        }
+        None => vec!["Reading the server's configuration...".to_string()],
+    };
+
     html! {
         <div class="bg-white dark:bg-gray-800 rounded-2xl shadow-xl p-8 text-center">
             <div class="w-16 h-16 bg-success-100 dark:bg-green-900/30 rounded-full flex items-center justify-center mx-auto mb-6">
@@ -446,18 +1017,12 @@ fn complete_step() -> Html {
             </p>
 
             <div class="space-y-2 text-left max-w-sm mx-auto mb-8">
-                <div class="flex items-center gap-3">
-                    <CheckIcon class="w-5 h-5 text-success-500" />
-                    <span class="text-gray-700 dark:text-gray-300">{ "Search pipeline configured" }</span>
-                </div>
-                <div class="flex items-center gap-3">
-                    <CheckIcon class="w-5 h-5 text-success-500" />
-                    <span class="text-gray-700 dark:text-gray-300">{ "2 skills installed" }</span>
-                </div>
-                <div class="flex items-center gap-3">
-                    <CheckIcon class="w-5 h-5 text-success-500" />
-                    <span class="text-gray-700 dark:text-gray-300">{ "Ready for Claude Code integration" }</span>
-                </div>
+                { for items.into_iter().map(|item| html! {
+                    <div class="flex items-center gap-3">
+                        <CheckIcon class="w-5 h-5 text-success-500 flex-shrink-0" />
+                        <span class="text-gray-700 dark:text-gray-300">{ item }</span>
+                    </div>
+                }) }
             </div>
 
             <button class="btn btn-primary w-full justify-center" onclick={on_finish}>
@@ -3,16 +3,24 @@
 //! Clean, single-page interface with:
 //! - Simple dropdown selection for Skill and Tool
 //! - Dynamic parameter form
-//! - Immediate execution feedback
+//! - Saving the run as a named preset
+//! - Live execution output, streamed into the terminal panel
 
 use std::collections::HashMap;
 use std::rc::Rc;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
+use yew_router::prelude::*;
 use yewdux::prelude::*;
 
-use crate::api::{Api, ExecutionResponse, SkillDetail};
-use crate::components::run::{InlineParameterEditor, TerminalOutput};
+use crate::api::error::ApiError;
+use crate::api::{
+    Api, ExecutionRequest, ExecutionResponse, ExecutionStreamEvent, OutputOptions, SkillDetail,
+};
+use crate::components::run::{
+    parse_parameter_value, validate_parameter, InlineParameterEditor, OutputOptionsEditor,
+    SavePresetForm, TerminalLog, TerminalLogAction, TerminalOutput,
+};
 use crate::components::notifications::use_notifications;
 use crate::store::skills::{SkillsAction, SkillsStore};
 use crate::components::SearchableSelect;
@@ -39,6 +47,7 @@ pub fn run_page(props: &RunPageProps) -> Html {
     let selected_instance = use_state(|| None::<String>);
     let parameters = use_state(HashMap::<String, serde_json::Value>::new);
     let validation_errors = use_state(HashMap::<String, String>::new);
+    let output_options = use_state(OutputOptions::default);
 
     // All skill details (for tool lookup)
     let all_skill_details = use_state(Vec::<SkillDetail>::new);
@@ -50,6 +59,7 @@ pub fn run_page(props: &RunPageProps) -> Html {
     let is_executing = use_state(|| false);
     let terminal_visible = use_state(|| false);
     let terminal_minimized = use_state(|| false);
+    let terminal_log = use_reducer(TerminalLog::default);
     
     // UI Refs
     let result_ref = use_node_ref();
@@ -154,26 +164,67 @@ pub fn run_page(props: &RunPageProps) -> Html {
         });
     }
 
-    // Update current skill detail when selection changes
+    // Update current skill detail when the selection changes or details load
     {
         let selected_skill = selected_skill.clone();
         let all_skill_details = all_skill_details.clone();
         let current_skill_detail = current_skill_detail.clone();
         let selected_tool = selected_tool.clone();
         let parameters = parameters.clone();
+        let previous_skill = use_mut_ref(|| (*selected_skill).clone());
+        let linked = (props.selected_skill.clone(), props.selected_tool.clone());
 
-        use_effect_with((*selected_skill).clone(), move |skill_name| {
-            if let Some(name) = skill_name {
-                let detail = (*all_skill_details).iter()
+        use_effect_with(((*selected_skill).clone(), all_skill_details.len()), move |(skill_name, _)| {
+            let detail = skill_name.as_ref().and_then(|name| {
+                (*all_skill_details).iter()
                     .find(|d| d.summary.name == *name)
-                    .cloned();
-                current_skill_detail.set(detail);
-                // Reset tool and params when skill changes
-                selected_tool.set(None);
+                    .cloned()
+            });
+            current_skill_detail.set(detail);
+
+            // Reset tool and params when skill changes, keeping a deep-linked tool
+            if *previous_skill.borrow() != *skill_name {
+                *previous_skill.borrow_mut() = skill_name.clone();
+                let (linked_skill, linked_tool) = linked;
+                selected_tool.set(linked_tool.filter(|_| linked_skill == *skill_name));
                 parameters.set(HashMap::new());
-            } else {
-                current_skill_detail.set(None);
-                selected_tool.set(None);
+            }
+            || ()
+        });
+    }
+
+    // Pre-fill arguments passed in the query string (e.g. by the command
+    // palette) once the tool's parameters are known
+    {
+        let location = use_location();
+        let query = location
+            .as_ref()
+            .map(|location| location.query_str().to_string())
+            .unwrap_or_default();
+        let args: HashMap<String, String> = location
+            .and_then(|location| location.query::<HashMap<String, String>>().ok())
+            .unwrap_or_default();
+        let tool_params = current_skill_detail.as_ref()
+            .and_then(|detail| detail.tools.iter().find(|t| Some(&t.name) == selected_tool.as_ref()))
+            .map(|tool| tool.parameters.clone());
+        let applied_query = use_mut_ref(String::new);
+        let parameters = parameters.clone();
+
+        use_effect_with((query, tool_params), move |(query, tool_params)| {
+            if let Some(tool_params) = tool_params {
+                if !args.is_empty() && *applied_query.borrow() != *query {
+                    *applied_query.borrow_mut() = query.clone();
+                    let values = args.iter()
+                        .map(|(name, value)| {
+                            let param_type = tool_params.iter()
+                                .find(|param| param.name == *name)
+                                .map(|param| param.param_type.as_str())
+                                .unwrap_or("string");
+                            (name.clone(), parse_parameter_value(param_type, value))
+                        })
+                        .collect();
+                    parameters.set(values);
+                }
             }
             || ()
         });
@@ -182,7 +233,14 @@ pub fn run_page(props: &RunPageProps) -> Html {
     // Handle parameter changes
     let on_parameter_change = {
         let parameters = parameters.clone();
+        let validation_errors = validation_errors.clone();
         Callback::from(move |(name, value): (String, serde_json::Value)| {
+            // A changed value is checked live by the editor again
+            if validation_errors.contains_key(&name) {
+                let mut errors = (*validation_errors).clone();
+                errors.remove(&name);
+                validation_errors.set(errors);
+            }
             let mut params = (*parameters).clone();
             params.insert(name, value);
             parameters.set(params);
@@ -196,9 +254,15 @@ pub fn run_page(props: &RunPageProps) -> Html {
         let selected_tool = selected_tool.clone();
         let selected_instance = selected_instance.clone();
         let parameters = parameters.clone();
+        let output_options = output_options.clone();
         let is_executing = is_executing.clone();
         let execution_result = execution_result.clone();
+        let terminal_log = terminal_log.clone();
+        let terminal_visible = terminal_visible.clone();
+        let terminal_minimized = terminal_minimized.clone();
         let notifications = notifications.clone();
+        let current_skill_detail = current_skill_detail.clone();
+        let validation_errors = validation_errors.clone();
 
         Callback::from(move |e: MouseEvent| {
             e.prevent_default();
@@ -206,26 +270,55 @@ pub fn run_page(props: &RunPageProps) -> Html {
             let tool = (*selected_tool).clone();
 
             if let (Some(skill_name), Some(tool_name)) = (skill, tool) {
+                // Check parameters against their SKILL.md constraints first
+                let errors: HashMap<String, String> = current_skill_detail.as_ref()
+                    .and_then(|detail| detail.tools.iter().find(|t| t.name == tool_name))
+                    .map(|tool| {
+                        tool.parameters.iter()
+                            .filter_map(|param| {
+                                validate_parameter(param, parameters.get(&param.name))
+                                    .map(|error| (param.name.clone(), error))
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let invalid = errors.len();
+                validation_errors.set(errors);
+                if invalid > 0 {
+                    notifications.error(
+                        "Invalid parameters",
+                        format!("Fix {} parameter(s) before running {}", invalid, tool_name),
+                    );
+                    return;
+                }
+
                 is_executing.set(true);
+                execution_result.set(None);
+                terminal_log.dispatch(TerminalLogAction::Clear);
+                terminal_visible.set(true);
+                terminal_minimized.set(false);
 
                 let api = api.clone();
                 let parameters = (*parameters).clone();
+                let output = (*output_options).clone();
                 let instance = (*selected_instance).clone();
                 let is_executing = is_executing.clone();
                 let execution_result = execution_result.clone();
+                let terminal_log = terminal_log.clone();
                 let notifications = notifications.clone();
 
                 spawn_local(async move {
-                    let request = crate::api::ExecutionRequest {
+                    let request = ExecutionRequest {
                         skill: skill_name.clone(),
                         tool: tool_name.clone(),
                         instance,
                         args: parameters,
-                        stream: false,
+                        stream: true,
                         timeout_secs: None,
+                        output,
                     };
 
-                    match api.executions.execute(&request).await {
+                    match stream_execution(&api, &request, &terminal_log).await {
                         Ok(result) => {
                             let duration = result.duration_ms;
                             execution_result.set(Some(result));
@@ -271,8 +364,12 @@ pub fn run_page(props: &RunPageProps) -> Html {
         let selected_tool = selected_tool.clone();
         let selected_instance = selected_instance.clone();
         let parameters = parameters.clone();
+        let output_options = output_options.clone();
         let is_executing = is_executing.clone();
         let execution_result = execution_result.clone();
+        let terminal_log = terminal_log.clone();
+        let terminal_visible = terminal_visible.clone();
+        let terminal_minimized = terminal_minimized.clone();
         let notifications = notifications.clone();
 
         Some(Callback::from(move |_: ()| {
@@ -281,25 +378,32 @@ pub fn run_page(props: &RunPageProps) -> Html {
 
             if let (Some(skill_name), Some(tool_name)) = (skill, tool) {
                 is_executing.set(true);
+                execution_result.set(None);
+                terminal_log.dispatch(TerminalLogAction::Clear);
+                terminal_visible.set(true);
+                terminal_minimized.set(false);
 
                 let api = api.clone();
                 let parameters = (*parameters).clone();
+                let output = (*output_options).clone();
                 let instance = (*selected_instance).clone();
                 let is_executing = is_executing.clone();
                 let execution_result = execution_result.clone();
+                let terminal_log = terminal_log.clone();
                 let notifications = notifications.clone();
 
                 spawn_local(async move {
-                    let request = crate::api::ExecutionRequest {
+                    let request = ExecutionRequest {
                         skill: skill_name.clone(),
                         tool: tool_name.clone(),
                         instance,
                         args: parameters,
-                        stream: false,
+                        stream: true,
                         timeout_secs: None,
+                        output,
                     };
 
-                    match api.executions.execute(&request).await {
+                    match stream_execution(&api, &request, &terminal_log).await {
                         Ok(result) => {
                             let duration = result.duration_ms;
                             execution_result.set(Some(result));
@@ -321,6 +425,11 @@ pub fn run_page(props: &RunPageProps) -> Html {
         }))
     };
 
+    let on_output_options_change = {
+        let output_options = output_options.clone();
+        Callback::from(move |options: OutputOptions| output_options.set(options))
+    };
+
     // Get current tool parameters
     let current_tool_params = current_skill_detail.as_ref()
         .and_then(|detail| {
@@ -461,7 +570,20 @@ pub fn run_page(props: &RunPageProps) -> Html {
                                     </div>
                                 }
 
-                                <div class="mt-8 pt-6 border-t border-gray-200 dark:border-gray-700 flex justify-end">
+                                <OutputOptionsEditor
+                                    value={(*output_options).clone()}
+                                    on_change={on_output_options_change}
+                                />
+
+                                <div class="mt-8 pt-6 border-t border-gray-200 dark:border-gray-700 flex flex-wrap justify-end gap-3">
+                                    if let (Some(skill), Some(tool)) = (selected_skill.as_ref(), selected_tool.as_ref()) {
+                                        <SavePresetForm
+                                            skill={skill.clone()}
+                                            tool={tool.clone()}
+                                            instance={(*selected_instance).clone()}
+                                            args={(*parameters).clone()}
+                                        />
+                                    }
                                     <button
                                         class={classes!(
                                             "btn",
@@ -542,6 +664,9 @@ pub fn run_page(props: &RunPageProps) -> Html {
                                                     </h4>
                                                     <p class="text-xs text-gray-500 dark:text-gray-400">
                                                         { format!("Completed in {}ms", result.duration_ms) }
+                                                        if let Some(cache) = result.cache.filter(|cache| cache.hit) {
+                                                            { format!(" · cached result from {:.1}s ago", cache.age_ms as f64 / 1000.0) }
+                                                        }
                                                     </p>
                                                 </div>
                                             } else {
@@ -645,6 +770,8 @@ pub fn run_page(props: &RunPageProps) -> Html {
             <TerminalOutput
                 visible={*terminal_visible}
                 execution={(*execution_result).clone()}
+                log={(*terminal_log).clone()}
+                running={*is_executing}
                 on_close={on_terminal_close}
                 on_rerun={on_rerun}
                 minimized={*terminal_minimized}
@@ -653,3 +780,28 @@ pub fn run_page(props: &RunPageProps) -> Html {
         </div>
     }
 }
+
+/// Run a tool through the streaming endpoint, appending its output to the
+/// terminal log as it arrives
+async fn stream_execution(
+    api: &Api,
+    request: &ExecutionRequest,
+    log: &UseReducerHandle<TerminalLog>,
+) -> Result<ExecutionResponse, ApiError> {
+    let mut finished = None;
+    api.executions
+        .execute_stream(request, |event| match event {
+            ExecutionStreamEvent::Started { .. } => {}
+            ExecutionStreamEvent::Output { stream, text } => {
+                log.dispatch(TerminalLogAction::Append(stream, text));
+            }
+            ExecutionStreamEvent::Completed { execution } => finished = Some(Ok(execution)),
+            ExecutionStreamEvent::Error { status, error } => {
+                finished = Some(Err(ApiError::from_status(status, error.message)));
+            }
+        })
+        .await?;
+    finished.unwrap_or_else(|| {
+        Err(ApiError::Network("Execution stream ended before the result".to_string()))
+    })
+}
@@ -4,15 +4,18 @@
 //! - Live semantic search with query input
 //! - Results display with scores and metadata
 //! - Configuration toggle (use current settings vs custom)
+//! - Structured filters (runtime, tags, instance)
 //! - Search statistics (latency, results count)
+//! - A/B mode comparing two pipeline configurations side by side
 
 use std::rc::Rc;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
-use crate::api::{Api, SearchRequest, SearchResponse};
+use crate::api::{Api, SearchFilters, SearchRequest, SearchResponse};
 use crate::components::card::Card;
 use crate::components::result_card::ResultCard;
+use crate::components::search_compare::SearchCompare;
 use crate::components::use_notifications;
 
 #[derive(Clone, PartialEq)]
@@ -22,6 +25,28 @@ struct SearchStats {
     query: String,
 }
 
+/// Build structured filters from the filter controls
+fn build_filters(runtime: &str, tags: &str, instance: &str) -> Option<SearchFilters> {
+    let non_empty = |v: &str| {
+        let v = v.trim();
+        (!v.is_empty()).then(|| v.to_string())
+    };
+
+    let filters = SearchFilters {
+        runtime: non_empty(runtime),
+        tags: tags
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(String::from)
+            .collect(),
+        instance: non_empty(instance),
+        category: None,
+    };
+
+    (!filters.is_empty()).then_some(filters)
+}
+
 #[function_component(SearchTestPage)]
 pub fn search_test_page() -> Html {
     // State
@@ -31,6 +56,12 @@ pub fn search_test_page() -> Html {
     let search_stats = use_state(|| None::<SearchStats>);
     let top_k = use_state(|| 10_usize);
     let is_indexing = use_state(|| false);
+    let runtime_filter = use_state(String::new);
+    let tag_filter = use_state(String::new);
+    let instance_filter = use_state(String::new);
+    let compare_mode = use_state(|| false);
+    // Bumped to run the A/B comparison from the query bar
+    let compare_run = use_state(|| 0_u32);
 
     // API & notifications
     let api = use_memo((), |_| Rc::new(Api::new()));
@@ -44,6 +75,11 @@ pub fn search_test_page() -> Html {
         let is_searching = is_searching.clone();
         let search_stats = search_stats.clone();
         let top_k = top_k.clone();
+        let runtime_filter = runtime_filter.clone();
+        let tag_filter = tag_filter.clone();
+        let instance_filter = instance_filter.clone();
+        let compare_mode = compare_mode.clone();
+        let compare_run = compare_run.clone();
         let notifications = notifications.clone();
 
         Callback::from(move |_: web_sys::MouseEvent| {
@@ -51,6 +87,12 @@ pub fn search_test_page() -> Html {
             if query_text.trim().is_empty() {
                 return;
             }
+            if *compare_mode {
+                compare_run.set(*compare_run + 1);
+                return;
+            }
+
+            let filters = build_filters(&runtime_filter, &tag_filter, &instance_filter);
 
             is_searching.set(true);
             results.set(None);
@@ -71,6 +113,9 @@ pub fn search_test_page() -> Html {
                     top_k: top_k_val,
                     skill_filter: None,
                     include_examples: false,
+                    session_id: None,
+                    filters,
+                    explain: false,
                 };
 
                 match api.search.search(&request).await {
@@ -118,6 +163,11 @@ pub fn search_test_page() -> Html {
         let is_searching = is_searching.clone();
         let search_stats = search_stats.clone();
         let top_k = top_k.clone();
+        let runtime_filter = runtime_filter.clone();
+        let tag_filter = tag_filter.clone();
+        let instance_filter = instance_filter.clone();
+        let compare_mode = compare_mode.clone();
+        let compare_run = compare_run.clone();
         let notifications = notifications.clone();
 
         Callback::from(move |e: KeyboardEvent| {
@@ -126,6 +176,12 @@ pub fn search_test_page() -> Html {
                 if query_text.trim().is_empty() {
                     return;
                 }
+                if *compare_mode {
+                    compare_run.set(*compare_run + 1);
+                    return;
+                }
+
+                let filters = build_filters(&runtime_filter, &tag_filter, &instance_filter);
 
                 is_searching.set(true);
                 results.set(None);
@@ -146,6 +202,9 @@ pub fn search_test_page() -> Html {
                         top_k: top_k_val,
                         skill_filter: None,
                         include_examples: false,
+                        session_id: None,
+                        filters,
+                        explain: false,
                     };
 
                     match api.search.search(&request).await {
@@ -188,6 +247,31 @@ pub fn search_test_page() -> Html {
         })
     };
 
+    // Filter change handlers
+    let on_runtime_filter_change = {
+        let runtime_filter = runtime_filter.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            runtime_filter.set(select.value());
+        })
+    };
+
+    let on_tag_filter_change = {
+        let tag_filter = tag_filter.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            tag_filter.set(input.value());
+        })
+    };
+
+    let on_instance_filter_change = {
+        let instance_filter = instance_filter.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            instance_filter.set(input.value());
+        })
+    };
+
     // Index handler
     let on_index = {
         let api = api.clone();
@@ -226,6 +310,26 @@ pub fn search_test_page() -> Html {
         })
     };
 
+    let mode_button = |compare: bool, label: &'static str| {
+        let is_active = *compare_mode == compare;
+        let compare_mode = compare_mode.clone();
+        html! {
+            <button
+                class={classes!(
+                    "px-4", "py-2", "rounded-lg", "text-sm", "font-medium", "transition-colors",
+                    if is_active {
+                        "bg-primary-500 text-white"
+                    } else {
+                        "bg-white dark:bg-gray-800 text-gray-700 dark:text-gray-300 hover:bg-gray-100 dark:hover:bg-gray-700"
+                    }
+                )}
+                onclick={Callback::from(move |_| compare_mode.set(compare))}
+            >
+                { label }
+            </button>
+        }
+    };
+
     html! {
         <div class="min-h-screen bg-gray-50 dark:bg-gray-900 p-6">
             <div class="max-w-6xl mx-auto space-y-6">
@@ -239,6 +343,10 @@ pub fn search_test_page() -> Html {
                             { "Test semantic search with live results from your skill catalog" }
                         </p>
                     </div>
+                    <div class="flex gap-2">
+                        { mode_button(false, "Live Search") }
+                        { mode_button(true, "A/B Compare") }
+                    </div>
                     <button
                         class="btn btn-secondary flex items-center gap-2"
                         onclick={on_index}
@@ -288,12 +396,42 @@ pub fn search_test_page() -> Html {
                                         <span class="animate-spin">{ "⟳" }</span>
                                         { "Searching..." }
                                     </span>
+                                } else if *compare_mode {
+                                    { "Compare" }
                                 } else {
                                     { "Search" }
                                 }
                             </button>
                         </div>
 
+                        // Structured filters (the live index only)
+                        <div class={classes!("flex", "gap-2", compare_mode.then_some("hidden"))}>
+                            <select
+                                class="input w-40"
+                                value={(*runtime_filter).clone()}
+                                onchange={on_runtime_filter_change}
+                            >
+                                <option value="" selected={runtime_filter.is_empty()}>{ "Any runtime" }</option>
+                                <option value="wasm">{ "WASM" }</option>
+                                <option value="docker">{ "Docker" }</option>
+                                <option value="native">{ "Native" }</option>
+                            </select>
+                            <input
+                                type="text"
+                                class="input flex-1"
+                                placeholder="Tags (comma-separated, e.g. 'aws, cloud')"
+                                value={(*tag_filter).clone()}
+                                oninput={on_tag_filter_change}
+                            />
+                            <input
+                                type="text"
+                                class="input w-40"
+                                placeholder="Instance (e.g. 'prod')"
+                                value={(*instance_filter).clone()}
+                                oninput={on_instance_filter_change}
+                            />
+                        </div>
+
                         // Search statistics
                         if let Some(stats) = &*search_stats {
                             <div class="p-3 bg-blue-50 dark:bg-blue-900/20 rounded border border-blue-200 dark:border-blue-800">
@@ -322,47 +460,51 @@ pub fn search_test_page() -> Html {
                     </div>
                 </Card>
 
-                // Results Card
-                <Card title="Search Results">
-                    if let Some(response) = &*results {
-                        if response.results.is_empty() {
+                if *compare_mode {
+                    <SearchCompare query={AttrValue::from((*query).clone())} top_k={*top_k} run={*compare_run} />
+                } else {
+                    // Results Card
+                    <Card title="Search Results">
+                        if let Some(response) = &*results {
+                            if response.results.is_empty() {
+                                <div class="text-center py-8 text-gray-500 dark:text-gray-400">
+                                    <svg class="w-12 h-12 mx-auto mb-3 opacity-50" fill="none" viewBox="0 0 24 24" stroke="currentColor">
+                                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M21 21l-6-6m2-5a7 7 0 11-14 0 7 7 0 0114 0z" />
+                                    </svg>
+                                    <p>{ "No results found for your query." }</p>
+                                </div>
+                            } else {
+                                <div class="space-y-3">
+                                    { for response.results.iter().enumerate().map(|(idx, result)| {
+                                        html! {
+                                            <ResultCard
+                                                index={idx + 1}
+                                                id={format!("{}:{}", result.skill, result.tool)}
+                                                skill={result.skill.clone()}
+                                                tool={result.tool.clone()}
+                                                content={result.content.clone()}
+                                                score={result.score}
+                                                rerank_score={None}
+                                                query={(*query).clone()}
+                                            />
+                                        }
+                                    }) }
+                                </div>
+                            }
+                        } else if *is_searching {
+                            <div class="flex items-center justify-center py-8">
+                                <div class="animate-spin rounded-full h-8 w-8 border-b-2 border-primary-500"></div>
+                            </div>
+                        } else {
                             <div class="text-center py-8 text-gray-500 dark:text-gray-400">
                                 <svg class="w-12 h-12 mx-auto mb-3 opacity-50" fill="none" viewBox="0 0 24 24" stroke="currentColor">
                                     <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M21 21l-6-6m2-5a7 7 0 11-14 0 7 7 0 0114 0z" />
                                 </svg>
-                                <p>{ "No results found for your query." }</p>
-                            </div>
-                        } else {
-                            <div class="space-y-3">
-                                { for response.results.iter().enumerate().map(|(idx, result)| {
-                                    html! {
-                                        <ResultCard
-                                            index={idx + 1}
-                                            id={format!("{}:{}", result.skill, result.tool)}
-                                            skill={result.skill.clone()}
-                                            tool={result.tool.clone()}
-                                            content={result.content.clone()}
-                                            score={result.score}
-                                            rerank_score={None}
-                                            query={(*query).clone()}
-                                        />
-                                    }
-                                }) }
+                                <p>{ "No results yet. Enter a query and click Search." }</p>
                             </div>
                         }
-                    } else if *is_searching {
-                        <div class="flex items-center justify-center py-8">
-                            <div class="animate-spin rounded-full h-8 w-8 border-b-2 border-primary-500"></div>
-                        </div>
-                    } else {
-                        <div class="text-center py-8 text-gray-500 dark:text-gray-400">
-                            <svg class="w-12 h-12 mx-auto mb-3 opacity-50" fill="none" viewBox="0 0 24 24" stroke="currentColor">
-                                <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M21 21l-6-6m2-5a7 7 0 11-14 0 7 7 0 0114 0z" />
-                            </svg>
-                            <p>{ "No results yet. Enter a query and click Search." }</p>
-                        </div>
-                    }
-                </Card>
+                    </Card>
+                }
             </div>
         </div>
     }
@@ -16,13 +16,11 @@ use crate::api::{
 };
 use crate::components::card::Card;
 use crate::components::{use_import_config_modal, use_notifications, ImportConfigModal, Tooltip};
-use crate::store::ui::{UiAction, UiStore};
+use crate::store::settings::{SettingsAction, SettingsStore, Theme};
 
 /// Settings state
 #[derive(Clone, PartialEq)]
 struct SettingsState {
-    // Appearance
-    theme: String,
     // Execution
     default_timeout_secs: u64,
     max_concurrent_executions: usize,
@@ -52,7 +50,6 @@ struct SettingsState {
 impl Default for SettingsState {
     fn default() -> Self {
         Self {
-            theme: "system".to_string(),
             default_timeout_secs: 30,
             max_concurrent_executions: 10,
             include_metadata: false,
@@ -85,7 +82,6 @@ impl SettingsState {
         let is_standard_model = Self::is_standard_model(provider, model);
 
         Self {
-            theme: "system".to_string(),
             default_timeout_secs: config.default_timeout_secs,
             max_concurrent_executions: config.max_concurrent_executions,
             include_metadata: false,
@@ -141,7 +137,8 @@ struct TestResult {
 /// Settings page component
 #[function_component(SettingsPage)]
 pub fn settings_page() -> Html {
-    let (_, ui_dispatch) = use_store::<UiStore>();
+    // The theme is a browser preference, kept in the persisted settings store
+    let (local_settings, settings_dispatch) = use_store::<SettingsStore>();
     let notifications = use_notifications();
     let import_modal = use_import_config_modal();
 
@@ -183,18 +180,11 @@ pub fn settings_page() -> Html {
         });
     }
 
-    // Theme change handler
+    // Theme change handler - applied immediately by the theme hook
     let on_theme_change = {
-        let settings = settings.clone();
-        let has_changes = has_changes.clone();
-        let ui_dispatch = ui_dispatch.clone();
+        let settings_dispatch = settings_dispatch.clone();
         Callback::from(move |value: String| {
-            let mut new_settings = (*settings).clone();
-            new_settings.theme = value.clone();
-            settings.set(new_settings);
-            has_changes.set(true);
-            // Apply theme immediately
-            ui_dispatch.apply(UiAction::SetDarkMode(value == "dark"));
+            settings_dispatch.apply(SettingsAction::SetTheme(Theme::parse(&value)));
         })
     };
 
@@ -458,8 +448,7 @@ pub fn settings_page() -> Html {
 
         Callback::from(move |_: MouseEvent| {
             settings.set(SettingsState {
-                theme: "system".to_string(),
-                default_timeout_secs: 30,
+                    default_timeout_secs: 30,
                 max_concurrent_executions: 4,
                 include_metadata: false,
                 enable_history: true,
@@ -582,6 +571,7 @@ pub fn settings_page() -> Html {
                     enable_hybrid: settings.hybrid_search_enabled,
                     enable_reranking: settings.reranking_enabled,
                     qdrant_url: settings.qdrant_url.clone(),
+                    ..Default::default()
                 };
 
                 match api.search.test_pipeline(&request).await {
@@ -735,7 +725,7 @@ pub fn settings_page() -> Html {
                             </label>
                             <div class="flex gap-4">
                                 { for ["light", "dark", "system"].iter().map(|t| {
-                                    let is_selected = current.theme == *t;
+                                    let is_selected = local_settings.theme.as_str() == *t;
                                     let on_change = on_theme_change.clone();
                                     let value = t.to_string();
 
@@ -756,6 +746,9 @@ pub fn settings_page() -> Html {
                                     }
                                 }) }
                             </div>
+                            <p class="text-xs text-gray-500 mt-2">
+                                { "Saved in this browser and applied immediately. System follows your OS setting." }
+                            </p>
                         </div>
                     </div>
                 </Card>
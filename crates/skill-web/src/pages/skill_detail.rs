@@ -6,10 +6,13 @@ use yew::prelude::*;
 use yew_router::prelude::*;
 use yewdux::prelude::*;
 
+use crate::api::analytics::SkillAnalyticsResponse;
+use crate::api::error::ApiError;
 use crate::api::{
-    Api, ExecutionHistoryEntry, InstanceInfo, ParameterInfo, SkillDetail, ToolInfo,
+    Api, ExecutionHistoryEntry, InstanceInfo, ParameterInfo, SkillDetail, SkillDocsResponse, ToolInfo,
 };
 use crate::components::card::Card;
+use crate::components::Markdown;
 use crate::components::icons::{ChevronRightIcon, PlayIcon};
 use crate::components::instance_editor::{InstanceData, InstanceEditorModal};
 use crate::router::Route;
@@ -28,23 +31,27 @@ pub struct SkillDetailPageProps {
 enum Tab {
     #[default]
     Overview,
+    Docs,
     Tools,
     Instances,
     History,
+    Analytics,
 }
 
 impl Tab {
     fn label(&self) -> &'static str {
         match self {
             Tab::Overview => "Overview",
+            Tab::Docs => "Docs",
             Tab::Tools => "Tools",
             Tab::Instances => "Instances",
             Tab::History => "History",
+            Tab::Analytics => "Analytics",
         }
     }
 
     fn all() -> &'static [Tab] {
-        &[Tab::Overview, Tab::Tools, Tab::Instances, Tab::History]
+        &[Tab::Overview, Tab::Docs, Tab::Tools, Tab::Instances, Tab::History, Tab::Analytics]
     }
 }
 
@@ -172,9 +179,11 @@ pub fn skill_detail_page(props: &SkillDetailPageProps) -> Html {
                     {
                         match *active_tab {
                             Tab::Overview => html! { <OverviewTab skill={skill.clone()} /> },
+                            Tab::Docs => html! { <DocsTab skill_name={props.name.clone()} /> },
                             Tab::Tools => html! { <ToolsTab skill_name={props.name.clone()} tools={skill.tools.clone()} /> },
                             Tab::Instances => html! { <InstancesTab skill_name={props.name.clone()} instances={skill.instances.clone()} /> },
                             Tab::History => html! { <HistoryTab executions={skill_executions.iter().map(|e| (*e).clone()).collect::<Vec<_>>()} /> },
+                            Tab::Analytics => html! { <AnalyticsTab skill_name={props.name.clone()} /> },
                         }
                     }
                 </div>
@@ -403,6 +412,44 @@ fn overview_tab(props: &OverviewTabProps) -> Html {
                 </dl>
             </Card>
 
+            if !summary.required_services.is_empty() {
+                <Card title="Services">
+                    <ul class="divide-y divide-gray-200 dark:divide-gray-700">
+                        { for summary.required_services.iter().map(|service| html! {
+                            <li class="py-3 first:pt-0 last:pb-0 flex items-start justify-between gap-4">
+                                <div class="min-w-0">
+                                    <p class="font-mono text-sm text-gray-900 dark:text-white">{ &service.name }</p>
+                                    if let Some(ref description) = service.description {
+                                        <p class="text-sm text-gray-500 dark:text-gray-400">{ description }</p>
+                                    }
+                                    <p class="text-xs text-gray-500 dark:text-gray-400 mt-1">
+                                        { if service.start_on_demand {
+                                            "Started automatically when a tool runs"
+                                        } else if service.optional {
+                                            "Optional"
+                                        } else {
+                                            "Must be running before tools run"
+                                        } }
+                                    </p>
+                                </div>
+                                if service.status.running {
+                                    <span class="text-xs px-2 py-0.5 bg-green-100 dark:bg-green-900/30 text-green-700 dark:text-green-300 rounded whitespace-nowrap">
+                                        { match service.status.port {
+                                            Some(port) => format!("Running on {}", port),
+                                            None => "Running".to_string(),
+                                        } }
+                                    </span>
+                                } else {
+                                    <span class="text-xs px-2 py-0.5 bg-gray-100 dark:bg-gray-800 text-gray-600 dark:text-gray-400 rounded whitespace-nowrap">
+                                        { "Stopped" }
+                                    </span>
+                                }
+                            </li>
+                        }) }
+                    </ul>
+                </Card>
+            }
+
             if let Some(ref repo) = skill.repository {
                 <Card title="Repository">
                     <a href={repo.clone()} target="_blank" rel="noopener noreferrer" class="text-primary-600 dark:text-primary-400 hover:underline">
@@ -414,6 +461,74 @@ fn overview_tab(props: &OverviewTabProps) -> Html {
     }
 }
 
+// ============================================================================
+// Docs Tab
+// ============================================================================
+
+#[derive(Properties, PartialEq)]
+struct DocsTabProps {
+    skill_name: String,
+}
+
+#[function_component(DocsTab)]
+fn docs_tab(props: &DocsTabProps) -> Html {
+    let docs = use_state(|| None::<SkillDocsResponse>);
+    let error = use_state(|| None::<ApiError>);
+    let api = use_memo((), |_| Rc::new(Api::new()));
+
+    {
+        let api = api.clone();
+        let docs = docs.clone();
+        let error = error.clone();
+        use_effect_with(props.skill_name.clone(), move |skill| {
+            let skill = skill.clone();
+            docs.set(None);
+            error.set(None);
+            spawn_local(async move {
+                match api.skills.get_docs(&skill).await {
+                    Ok(response) => docs.set(Some(response)),
+                    Err(e) => error.set(Some(e)),
+                }
+            });
+        });
+    }
+
+    if let Some(err) = (*error).clone() {
+        return match err {
+            ApiError::NotFound(_) => html! {
+                <Card>
+                    <p class="text-center py-8 text-gray-500 dark:text-gray-400">
+                        { "This skill has no SKILL.md or README" }
+                    </p>
+                </Card>
+            },
+            err => html! {
+                <div class="bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-lg p-4">
+                    <p class="text-red-700 dark:text-red-300">{ format!("Failed to load docs: {}", err) }</p>
+                </div>
+            },
+        };
+    }
+
+    match (*docs).clone() {
+        Some(docs) => html! {
+            <Card>
+                <p class="text-xs text-gray-500 dark:text-gray-400 font-mono mb-4">{ docs.file.clone() }</p>
+                <Markdown content={docs.content.clone()} />
+            </Card>
+        },
+        None => html! {
+            <Card>
+                <div class="animate-pulse space-y-3">
+                    <div class="h-6 bg-gray-200 dark:bg-gray-700 rounded w-1/3"></div>
+                    <div class="h-4 bg-gray-200 dark:bg-gray-700 rounded w-full"></div>
+                    <div class="h-4 bg-gray-200 dark:bg-gray-700 rounded w-5/6"></div>
+                </div>
+            </Card>
+        },
+    }
+}
+
 // ============================================================================
 // Tools Tab
 // ============================================================================
@@ -487,10 +602,20 @@ fn tool_card(props: &ToolCardProps) -> Html {
                                     { "Streaming" }
                                 </span>
                             }
+                            if !tool.available {
+                                <span class="text-xs px-2 py-0.5 bg-amber-100 dark:bg-amber-900/30 text-amber-700 dark:text-amber-300 rounded">
+                                    { "Unavailable" }
+                                </span>
+                            }
                         </div>
                         <p class="mt-1 text-sm text-gray-500 dark:text-gray-400">
                             { &tool.description }
                         </p>
+                        if !tool.unmet_requirements.is_empty() {
+                            <p class="mt-1 text-xs text-amber-700 dark:text-amber-300">
+                                { format!("Requires {}", tool.unmet_requirements.join(", ")) }
+                            </p>
+                        }
                     </div>
                     <Link<Route>
                         to={Route::RunSkillTool {
@@ -549,6 +674,21 @@ struct ParameterRowProps {
 fn parameter_row(props: &ParameterRowProps) -> Html {
     let param = &props.param;
 
+    // Constraints from SKILL.md: enum values, bounds, pattern
+    let mut constraints = Vec::new();
+    if !param.allowed_values.is_empty() {
+        constraints.push(format!("one of: {}", param.allowed_values.join(" | ")));
+    }
+    match (param.minimum, param.maximum) {
+        (Some(min), Some(max)) => constraints.push(format!("{} to {}", min, max)),
+        (Some(min), None) => constraints.push(format!("at least {}", min)),
+        (None, Some(max)) => constraints.push(format!("at most {}", max)),
+        (None, None) => {}
+    }
+    if let Some(ref pattern) = param.pattern {
+        constraints.push(format!("pattern: {}", pattern));
+    }
+
     html! {
         <div class="flex items-start gap-4 text-sm">
             <div class="flex-shrink-0 w-32">
@@ -567,6 +707,11 @@ fn parameter_row(props: &ParameterRowProps) -> Html {
                         { format!("(default: {})", default) }
                     </span>
                 }
+                if !constraints.is_empty() {
+                    <div class="mt-1 text-xs text-gray-400 font-mono">
+                        { constraints.join("; ") }
+                    </div>
+                }
             </div>
         </div>
     }
@@ -835,6 +980,159 @@ fn execution_row(props: &ExecutionRowProps) -> Html {
     }
 }
 
+// ============================================================================
+// Analytics Tab
+// ============================================================================
+
+#[derive(Properties, PartialEq)]
+struct AnalyticsTabProps {
+    skill_name: String,
+}
+
+#[function_component(AnalyticsTab)]
+fn analytics_tab(props: &AnalyticsTabProps) -> Html {
+    let days = use_state(|| 30u32);
+    let analytics = use_state(|| None::<SkillAnalyticsResponse>);
+    let error = use_state(|| None::<String>);
+    let api = use_memo((), |_| Rc::new(Api::new()));
+
+    // Reload whenever the skill or time range changes
+    {
+        let api = api.clone();
+        let analytics = analytics.clone();
+        let error = error.clone();
+        use_effect_with((props.skill_name.clone(), *days), move |(skill, days)| {
+            let skill = skill.clone();
+            let days = *days;
+            analytics.set(None);
+            error.set(None);
+            spawn_local(async move {
+                match api.analytics.get_skill_analytics(&skill, days).await {
+                    Ok(response) => analytics.set(Some(response)),
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+            });
+        });
+    }
+
+    let range_button = |value: u32| {
+        let days = days.clone();
+        let active = *days == value;
+        html! {
+            <button
+                class={classes!("btn", "btn-sm", if active { "btn-primary" } else { "btn-secondary" })}
+                onclick={Callback::from(move |_| days.set(value))}
+            >
+                { format!("{} days", value) }
+            </button>
+        }
+    };
+
+    let stat = |label: &str, value: String| html! {
+        <Card>
+            <p class="text-sm text-gray-500 dark:text-gray-400">{ label.to_string() }</p>
+            <p class="text-2xl font-bold text-gray-900 dark:text-white mt-1">{ value }</p>
+        </Card>
+    };
+
+    html! {
+        <div class="space-y-6">
+            <div class="flex justify-end gap-2">
+                { range_button(7) }
+                { range_button(30) }
+                { range_button(90) }
+            </div>
+
+            if let Some(err) = (*error).clone() {
+                <div class="bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-lg p-4">
+                    <p class="text-red-700 dark:text-red-300">{ format!("Failed to load analytics: {}", err) }</p>
+                </div>
+            } else if let Some(data) = (*analytics).clone() {
+                if data.stats.executions == 0 {
+                    <Card>
+                        <p class="text-center py-8 text-gray-500 dark:text-gray-400">
+                            { format!("No executions in the last {} days", data.days) }
+                        </p>
+                    </Card>
+                } else {
+                    <div class="grid grid-cols-2 md:grid-cols-4 gap-4">
+                        { stat("Executions", data.stats.executions.to_string()) }
+                        { stat("Success rate", format!("{:.1}%", data.stats.success_rate * 100.0)) }
+                        { stat("p50 latency", format_ms(data.stats.p50_ms)) }
+                        { stat("p95 latency", format_ms(data.stats.p95_ms)) }
+                    </div>
+
+                    <Card title="Executions per day">
+                        <DailyChart daily={data.daily.clone()} />
+                    </Card>
+
+                    <Card title="Tools">
+                        <div class="overflow-x-auto">
+                            <table class="table">
+                                <thead>
+                                    <tr>
+                                        <th>{ "Tool" }</th>
+                                        <th>{ "Executions" }</th>
+                                        <th>{ "Success rate" }</th>
+                                        <th>{ "p50" }</th>
+                                        <th>{ "p95" }</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    { for data.tools.iter().map(|tool| html! {
+                                        <tr>
+                                            <td><code class="text-sm">{ &tool.tool }</code></td>
+                                            <td>{ tool.stats.executions }</td>
+                                            <td class={classes!(if tool.stats.success_rate < 0.9 { "text-error-600" } else { "text-success-600" })}>
+                                                { format!("{:.1}%", tool.stats.success_rate * 100.0) }
+                                            </td>
+                                            <td class="text-gray-500">{ format_ms(tool.stats.p50_ms) }</td>
+                                            <td class="text-gray-500">{ format_ms(tool.stats.p95_ms) }</td>
+                                        </tr>
+                                    }) }
+                                </tbody>
+                            </table>
+                        </div>
+                    </Card>
+                }
+            } else {
+                <div class="flex items-center justify-center py-12">
+                    <div class="animate-spin rounded-full h-8 w-8 border-b-2 border-primary-600"></div>
+                </div>
+            }
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct DailyChartProps {
+    daily: Vec<crate::api::analytics::DailyUsage>,
+}
+
+/// Bar per day, the failed part of each bar in red
+#[function_component(DailyChart)]
+fn daily_chart(props: &DailyChartProps) -> Html {
+    let max = props.daily.iter().map(|day| day.executions).max().unwrap_or(1).max(1);
+
+    html! {
+        <div class="flex items-end gap-1 h-32">
+            { for props.daily.iter().map(|day| {
+                let height = day.executions as f64 / max as f64 * 100.0;
+                let failed = day.failures as f64 / day.executions.max(1) as f64 * 100.0;
+                html! {
+                    <div
+                        class="flex-1 flex flex-col justify-end bg-primary-500 rounded-t"
+                        style={format!("height: {:.0}%", height)}
+                        title={format!("{}: {} executions, {} failed", day.date, day.executions, day.failures)}
+                    >
+                        <div class="bg-error-500 rounded-t" style={format!("height: {:.0}%", failed)}></div>
+                    </div>
+                }
+            }) }
+        </div>
+    }
+}
+
 // ============================================================================
 // Skeleton Loader
 // ============================================================================
@@ -894,3 +1192,11 @@ fn format_date(timestamp: &str) -> String {
         timestamp.to_string()
     }
 }
+
+fn format_ms(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    }
+}
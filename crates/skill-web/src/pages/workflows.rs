@@ -0,0 +1,542 @@
+//! Workflows page - fill in a workflow's inputs, run it and follow each step live
+
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::api::{
+    Api, RunWorkflowRequest, WorkflowEvent, WorkflowStepInfo, WorkflowStepStatus, WorkflowSummary,
+};
+use crate::components::card::Card;
+use crate::components::icons::PlayIcon;
+use crate::router::Route;
+
+/// Workflows page props
+#[derive(Properties, PartialEq)]
+pub struct WorkflowsPageProps {
+    #[prop_or_default]
+    pub selected: Option<String>,
+}
+
+/// Workflows page component
+#[function_component(WorkflowsPage)]
+pub fn workflows_page(props: &WorkflowsPageProps) -> Html {
+    let workflows = use_state(Vec::<WorkflowSummary>::new);
+    let loading = use_state(|| true);
+    let error = use_state(|| None::<String>);
+    let values = use_state(HashMap::<String, String>::new);
+    let run = use_reducer(RunState::default);
+
+    let api = use_memo((), |_| Rc::new(Api::new()));
+
+    // Load workflows on mount
+    {
+        let api = api.clone();
+        let workflows = workflows.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                match api.workflows.list().await {
+                    Ok(list) => workflows.set(list),
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+                loading.set(false);
+            });
+        });
+    }
+
+    // Start over when another workflow is picked
+    {
+        let values = values.clone();
+        let run = run.clone();
+        use_effect_with(props.selected.clone(), move |_| {
+            values.set(HashMap::new());
+            run.dispatch(RunAction::Reset);
+        });
+    }
+
+    let selected = props
+        .selected
+        .as_ref()
+        .and_then(|name| workflows.iter().find(|workflow| &workflow.name == name))
+        .cloned();
+
+    let on_run = {
+        let api = api.clone();
+        let values = values.clone();
+        let run = run.clone();
+        let selected = selected.clone();
+
+        Callback::from(move |_| {
+            let Some(workflow) = selected.clone() else {
+                return;
+            };
+            let request = RunWorkflowRequest {
+                inputs: input_values(&workflow.inputs, &values),
+            };
+            let api = api.clone();
+            let run = run.clone();
+            run.dispatch(RunAction::Start);
+
+            spawn_local(async move {
+                let events = run.clone();
+                let result = api
+                    .workflows
+                    .run_stream(&workflow.name, &request, move |event| {
+                        events.dispatch(RunAction::Event(event))
+                    })
+                    .await;
+                run.dispatch(RunAction::Closed(result.err().map(|e| e.to_string())));
+            });
+        })
+    };
+
+    let run_state = Rc::new((*run).clone());
+
+    html! {
+        <div class="space-y-6 animate-fade-in">
+            // Page header
+            <div>
+                <h1 class="text-2xl font-bold text-gray-900 dark:text-white">
+                    { "Workflows" }
+                </h1>
+                <p class="text-gray-500 dark:text-gray-400 mt-1">
+                    { "Run multi-step tool pipelines and watch each step as it happens" }
+                </p>
+            </div>
+
+            if let Some(err) = (*error).clone() {
+                <div class="bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-lg p-4">
+                    <p class="text-red-700 dark:text-red-300">{ format!("Failed to load workflows: {}", err) }</p>
+                </div>
+            }
+
+            <div class="grid grid-cols-1 lg:grid-cols-4 gap-6">
+                // Step 1: pick a workflow
+                <Card title="Workflow" class="lg:col-span-1">
+                    if *loading {
+                        <div class="flex items-center justify-center py-8">
+                            <div class="animate-spin rounded-full h-6 w-6 border-b-2 border-primary-600"></div>
+                        </div>
+                    } else if workflows.is_empty() {
+                        <p class="text-sm text-gray-500 dark:text-gray-400 p-4">
+                            { "No workflows found. Add workflow files to ./workflows or ~/.skill-engine/workflows." }
+                        </p>
+                    } else {
+                        <nav class="p-2 space-y-1">
+                            { for workflows.iter().map(|workflow| {
+                                let active = props.selected.as_deref() == Some(workflow.name.as_str());
+                                let class = if active { "nav-link-active" } else { "nav-link" };
+                                html! {
+                                    <Link<Route>
+                                        to={Route::WorkflowDetail { name: workflow.name.clone() }}
+                                        classes={classes!(class, "flex-col", "items-start")}
+                                    >
+                                        <span class="font-medium">{ &workflow.name }</span>
+                                        <span class="text-xs text-gray-500">
+                                            { format!("{} steps", workflow.steps.len()) }
+                                        </span>
+                                    </Link<Route>>
+                                }
+                            }) }
+                        </nav>
+                    }
+                </Card>
+
+                <div class="lg:col-span-3 space-y-6">
+                    if let Some(workflow) = selected {
+                        // Step 2: fill in the inputs
+                        <Card
+                            title="Inputs"
+                            subtitle={workflow.description.clone().map(AttrValue::from)}
+                        >
+                            <div class="p-6 space-y-4">
+                                <InputForm schema={workflow.inputs.clone()} values={values.clone()} />
+                                <button class="btn btn-primary" onclick={on_run} disabled={run.running}>
+                                    <PlayIcon class={classes!("w-4", "h-4", "mr-2", if run.running { "animate-pulse" } else { "" })} />
+                                    { if run.running { "Running..." } else { "Run workflow" } }
+                                </button>
+                            </div>
+                        </Card>
+
+                        // Step 3: follow the run
+                        <Card title="Steps" subtitle="Each column uses results from the columns before it">
+                            <div class="p-6 space-y-4">
+                                <StepGraph steps={workflow.steps.clone()} run={run_state.clone()} />
+                                <RunSummary run={run_state} />
+                            </div>
+                        </Card>
+                    } else {
+                        <Card>
+                            <div class="text-center py-12 text-gray-500 dark:text-gray-400">
+                                { "Select a workflow to run" }
+                            </div>
+                        </Card>
+                    }
+                </div>
+            </div>
+        </div>
+    }
+}
+
+/// Input form props
+#[derive(Properties, PartialEq)]
+struct InputFormProps {
+    /// JSON Schema for the workflow's inputs
+    schema: serde_json::Value,
+    values: UseStateHandle<HashMap<String, String>>,
+}
+
+/// One field per workflow input; empty fields fall back to the default
+#[function_component(InputForm)]
+fn input_form(props: &InputFormProps) -> Html {
+    let Some(properties) = props.schema["properties"]
+        .as_object()
+        .filter(|p| !p.is_empty())
+    else {
+        return html! {
+            <p class="text-sm text-gray-500 dark:text-gray-400">{ "This workflow takes no inputs." }</p>
+        };
+    };
+    let required: Vec<&str> = props.schema["required"]
+        .as_array()
+        .map(|names| names.iter().filter_map(|name| name.as_str()).collect())
+        .unwrap_or_default();
+
+    html! {
+        <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+            { for properties.iter().map(|(name, property)| {
+                let input_type = property["type"].as_str().unwrap_or("any");
+                let placeholder = match &property["default"] {
+                    serde_json::Value::Null => String::new(),
+                    serde_json::Value::String(default) => default.clone(),
+                    default => default.to_string(),
+                };
+                let oninput = {
+                    let values = props.values.clone();
+                    let name = name.clone();
+                    Callback::from(move |e: InputEvent| {
+                        let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+                        let mut updated = (*values).clone();
+                        updated.insert(name.clone(), input.value());
+                        values.set(updated);
+                    })
+                };
+                html! {
+                    <div>
+                        <label class="block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1">
+                            { name }
+                            if required.contains(&name.as_str()) {
+                                <span class="text-red-500">{ " *" }</span>
+                            }
+                            <span class="ml-2 text-xs text-gray-400">{ input_type }</span>
+                        </label>
+                        <input
+                            type="text"
+                            class="input"
+                            {placeholder}
+                            value={props.values.get(name).cloned().unwrap_or_default()}
+                            {oninput}
+                        />
+                        if let Some(description) = property["description"].as_str() {
+                            <p class="mt-1 text-xs text-gray-500">{ description }</p>
+                        }
+                    </div>
+                }
+            }) }
+        </div>
+    }
+}
+
+/// Step graph props
+#[derive(Properties, PartialEq)]
+struct StepGraphProps {
+    steps: Vec<WorkflowStepInfo>,
+    run: Rc<RunState>,
+}
+
+/// Steps laid out in columns by dependency, colored by their live status
+#[function_component(StepGraph)]
+fn step_graph(props: &StepGraphProps) -> Html {
+    html! {
+        <div class="flex items-start gap-4 overflow-x-auto pb-2">
+            { for dag_levels(&props.steps).into_iter().enumerate().map(|(level, steps)| html! {
+                <>
+                    if level > 0 {
+                        <div class="self-center text-gray-400">{ "→" }</div>
+                    }
+                    <div class="flex flex-col gap-3 min-w-[12rem]">
+                        { for steps.into_iter().map(|step| {
+                            let state = props.run.steps.get(&step.id).cloned().unwrap_or_default();
+                            html! { <StepNode step={step.clone()} {state} /> }
+                        }) }
+                    </div>
+                </>
+            }) }
+        </div>
+    }
+}
+
+/// Step node props
+#[derive(Properties, PartialEq)]
+struct StepNodeProps {
+    step: WorkflowStepInfo,
+    state: StepState,
+}
+
+/// One step: its tool, status, dependencies and output
+#[function_component(StepNode)]
+fn step_node(props: &StepNodeProps) -> Html {
+    let step = &props.step;
+    let state = &props.state;
+    let (border, badge, label) = match state.status {
+        WorkflowStepStatus::Pending => (
+            "border-gray-200 dark:border-gray-700",
+            "badge-neutral",
+            "pending",
+        ),
+        WorkflowStepStatus::Running => ("border-blue-400 animate-pulse", "badge-info", "running"),
+        WorkflowStepStatus::Succeeded => ("border-green-400", "badge-success", "succeeded"),
+        WorkflowStepStatus::Failed => ("border-red-400", "badge-error", "failed"),
+        WorkflowStepStatus::Skipped => (
+            "border-gray-200 dark:border-gray-700 opacity-60",
+            "badge-neutral",
+            "skipped",
+        ),
+    };
+
+    html! {
+        <div class={classes!("rounded-lg", "border-2", "p-3", "bg-white", "dark:bg-gray-800", border)}>
+            <div class="flex items-center justify-between gap-2">
+                <span class="font-medium text-gray-900 dark:text-white">{ &step.id }</span>
+                <span class={classes!("badge", badge)}>{ label }</span>
+            </div>
+            <p class="font-mono text-xs text-gray-500 mt-1">{ &step.tool }</p>
+            if !step.depends_on.is_empty() {
+                <p class="text-xs text-gray-400 mt-1">{ format!("← {}", step.depends_on.join(", ")) }</p>
+            }
+            if let Some(when) = &step.when {
+                <p class="text-xs text-gray-400 mt-1">{ format!("when {}", when) }</p>
+            }
+            if state.duration_ms > 0 {
+                <p class="text-xs text-gray-400 mt-1">
+                    { format!("{}ms", state.duration_ms) }
+                    if state.attempts > 1 {
+                        { format!(" · {} attempts", state.attempts) }
+                    }
+                </p>
+            }
+            if let Some(error) = &state.error {
+                <p class="text-xs text-red-600 dark:text-red-400 mt-2">{ error }</p>
+            }
+            if !state.output.is_empty() {
+                <details class="mt-2">
+                    <summary class="text-xs text-gray-500 cursor-pointer">{ "Output" }</summary>
+                    <pre class="code-block text-xs mt-1 max-h-64 overflow-auto">{ &state.output }</pre>
+                </details>
+            }
+        </div>
+    }
+}
+
+/// Run summary props
+#[derive(Properties, PartialEq)]
+struct RunSummaryProps {
+    run: Rc<RunState>,
+}
+
+/// Outcome of the run once it finishes
+#[function_component(RunSummary)]
+fn run_summary(props: &RunSummaryProps) -> Html {
+    let run = &props.run;
+    if let Some(error) = &run.error {
+        return html! {
+            <div class="bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-lg p-4">
+                <p class="text-red-700 dark:text-red-300">{ error }</p>
+            </div>
+        };
+    }
+    let Some(status) = run.status else {
+        return html! {};
+    };
+    let (class, text) = if status == WorkflowStepStatus::Succeeded {
+        ("text-green-700 dark:text-green-300", "Workflow succeeded")
+    } else {
+        ("text-red-700 dark:text-red-300", "Workflow failed")
+    };
+
+    html! {
+        <div class="flex items-center justify-between text-sm">
+            <span class={classes!("font-medium", class)}>{ text }</span>
+            if let Some(job_id) = &run.job_id {
+                <span class="font-mono text-xs text-gray-500">
+                    { format!("skill workflow status {}", job_id) }
+                </span>
+            }
+        </div>
+    }
+}
+
+/// Live state of the current run, built up from streamed events
+#[derive(Clone, Default, PartialEq)]
+struct RunState {
+    running: bool,
+    steps: HashMap<String, StepState>,
+    status: Option<WorkflowStepStatus>,
+    job_id: Option<String>,
+    error: Option<String>,
+}
+
+/// Live state of one step
+#[derive(Clone, Default, PartialEq)]
+struct StepState {
+    status: WorkflowStepStatus,
+    output: String,
+    error: Option<String>,
+    attempts: u32,
+    duration_ms: u64,
+}
+
+enum RunAction {
+    /// Clear the previous run
+    Reset,
+    /// Clear the previous run and wait for the new one's events
+    Start,
+    /// An event from the run's stream
+    Event(WorkflowEvent),
+    /// The stream ended, with the request error if there was one
+    Closed(Option<String>),
+}
+
+impl Reducible for RunState {
+    type Action = RunAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut state = (*self).clone();
+        match action {
+            RunAction::Reset => {
+                state = RunState::default();
+            }
+            RunAction::Start => {
+                state = RunState {
+                    running: true,
+                    ..Default::default()
+                };
+            }
+            RunAction::Event(event) => state.apply(event),
+            RunAction::Closed(error) => {
+                state.running = false;
+                if state.status.is_none() && state.error.is_none() {
+                    state.error =
+                        Some(error.unwrap_or_else(|| "The run ended without a result".to_string()));
+                }
+            }
+        }
+        Rc::new(state)
+    }
+}
+
+impl RunState {
+    fn apply(&mut self, event: WorkflowEvent) {
+        match event {
+            WorkflowEvent::Started { steps, .. } => {
+                self.steps = steps
+                    .into_iter()
+                    .map(|id| (id, StepState::default()))
+                    .collect();
+            }
+            WorkflowEvent::StepStarted { step, .. } => {
+                self.step(step).status = WorkflowStepStatus::Running;
+            }
+            WorkflowEvent::StepOutput { step, chunk } => {
+                self.step(step).output.push_str(&chunk);
+            }
+            WorkflowEvent::StepCompleted {
+                step, duration_ms, ..
+            } => {
+                let step = self.step(step);
+                step.status = WorkflowStepStatus::Succeeded;
+                step.duration_ms = duration_ms;
+            }
+            WorkflowEvent::StepFailed {
+                step,
+                error,
+                attempts,
+            } => {
+                let step = self.step(step);
+                step.status = WorkflowStepStatus::Failed;
+                step.error = Some(error);
+                step.attempts = attempts;
+            }
+            WorkflowEvent::StepSkipped { step } => {
+                self.step(step).status = WorkflowStepStatus::Skipped;
+            }
+            WorkflowEvent::Finished { job_id, run } => {
+                self.running = false;
+                self.status = Some(run.status);
+                self.job_id = job_id;
+                for step_run in run.steps {
+                    let step = self.step(step_run.id);
+                    step.status = step_run.status;
+                    step.attempts = step_run.attempts;
+                    step.duration_ms = step_run.duration_ms;
+                    step.error = step_run.error;
+                }
+            }
+            WorkflowEvent::Error { message } => {
+                self.running = false;
+                self.error = Some(message);
+            }
+        }
+    }
+
+    fn step(&mut self, id: String) -> &mut StepState {
+        self.steps.entry(id).or_default()
+    }
+}
+
+/// Groups steps into columns: each step sits one column right of the
+/// latest step it depends on
+fn dag_levels(steps: &[WorkflowStepInfo]) -> Vec<Vec<&WorkflowStepInfo>> {
+    let mut level_of: HashMap<&str, usize> = HashMap::new();
+    let mut levels: Vec<Vec<&WorkflowStepInfo>> = Vec::new();
+    for step in steps {
+        let level = step
+            .depends_on
+            .iter()
+            .filter_map(|dep| level_of.get(dep.as_str()))
+            .map(|level| level + 1)
+            .max()
+            .unwrap_or(0);
+        level_of.insert(&step.id, level);
+        if levels.len() <= level {
+            levels.resize_with(level + 1, Vec::new);
+        }
+        levels[level].push(step);
+    }
+    levels
+}
+
+/// Converts the form's text to JSON by each input's type; empty fields are
+/// left out so their defaults apply
+fn input_values(
+    schema: &serde_json::Value,
+    values: &HashMap<String, String>,
+) -> serde_json::Map<String, serde_json::Value> {
+    values
+        .iter()
+        .filter(|(_, raw)| !raw.trim().is_empty())
+        .map(|(name, raw)| {
+            let value = match schema["properties"][name]["type"].as_str() {
+                Some("string") => serde_json::Value::String(raw.clone()),
+                _ => serde_json::from_str(raw)
+                    .unwrap_or_else(|_| serde_json::Value::String(raw.clone())),
+            };
+            (name.clone(), value)
+        })
+        .collect()
+}
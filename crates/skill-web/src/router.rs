@@ -12,6 +12,8 @@ use crate::pages::{
     skill_detail::SkillDetailPage,
     run::RunPage,
     history::HistoryPage,
+    manifest::ManifestPage,
+    workflows::WorkflowsPage,
     settings::SettingsPage,
     search_test::SearchTestPage,
     onboarding::OnboardingPage,
@@ -57,6 +59,18 @@ pub enum Route {
     #[at("/history/:id")]
     HistoryDetail { id: String },
 
+    /// Workflows - run multi-step pipelines
+    #[at("/workflows")]
+    Workflows,
+
+    /// Workflow with pre-selected workflow
+    #[at("/workflows/:name")]
+    WorkflowDetail { name: String },
+
+    /// Manifest editor
+    #[at("/manifest")]
+    Manifest,
+
     /// Settings page
     #[at("/settings")]
     Settings,
@@ -99,6 +113,9 @@ pub fn switch(route: Route) -> Html {
         },
         Route::History => html! { <HistoryPage /> },
         Route::HistoryDetail { id } => html! { <HistoryPage selected_id={Some(id)} /> },
+        Route::Workflows => html! { <WorkflowsPage /> },
+        Route::WorkflowDetail { name } => html! { <WorkflowsPage selected={Some(name)} /> },
+        Route::Manifest => html! { <ManifestPage /> },
         Route::Settings => html! { <SettingsPage /> },
         Route::SearchTest => html! { <SearchTestPage /> },
         Route::Analytics => html! { <AnalyticsPage /> },
@@ -118,6 +135,8 @@ impl Route {
             Route::SkillInstance { .. } => "Instance Configuration",
             Route::Run | Route::RunSkill { .. } | Route::RunSkillTool { .. } => "Run",
             Route::History | Route::HistoryDetail { .. } => "History",
+            Route::Workflows | Route::WorkflowDetail { .. } => "Workflows",
+            Route::Manifest => "Manifest",
             Route::Settings => "Settings",
             Route::SearchTest => "Search Test",
             Route::Analytics => "Analytics",
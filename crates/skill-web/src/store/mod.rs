@@ -6,6 +6,7 @@
 //!
 //! - **SkillsStore**: Installed skills, filtering, sorting, selected skill
 //! - **ExecutionsStore**: Execution history, active execution, streaming output
+//! - **PresetsStore**: Saved runs, shown on the dashboard and pinned to the sidebar
 //! - **SettingsStore**: User preferences, persisted to localStorage
 //! - **UiStore**: Transient UI state (sidebar, notifications, modals)
 //!
@@ -33,6 +34,7 @@
 //! ```
 
 pub mod executions;
+pub mod presets;
 pub mod settings;
 pub mod skills;
 pub mod ui;
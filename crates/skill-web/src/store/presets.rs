@@ -0,0 +1,88 @@
+//! Saved runs store
+//!
+//! Holds the saved runs (presets) shared by the dashboard, the Run page and
+//! the sidebar's pinned list.
+
+use std::collections::HashMap;
+use yewdux::prelude::*;
+
+use crate::api::RunPreset;
+
+/// Saved runs store state
+#[derive(Clone, Debug, Default, PartialEq, Store)]
+pub struct PresetsStore {
+    /// Saved runs, pinned ones first
+    pub presets: Vec<RunPreset>,
+    /// Whether the presets have been fetched from the server
+    pub loaded: bool,
+    /// Error from the last fetch
+    pub error: Option<String>,
+}
+
+impl PresetsStore {
+    /// Presets pinned to the sidebar
+    pub fn pinned(&self) -> impl Iterator<Item = &RunPreset> {
+        self.presets.iter().filter(|preset| preset.pinned)
+    }
+}
+
+/// A preset's arguments as Run page query parameters, so the form opens
+/// filled in
+pub fn preset_query(preset: &RunPreset) -> HashMap<String, String> {
+    preset
+        .args
+        .iter()
+        .map(|(name, value)| {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+/// Saved runs store actions
+pub enum PresetsAction {
+    /// Replace all presets
+    SetPresets(Vec<RunPreset>),
+    /// Add a preset or replace the one with the same ID
+    Upsert(RunPreset),
+    /// Remove a preset by ID
+    Remove(String),
+    /// Set error
+    SetError(Option<String>),
+}
+
+impl Reducer<PresetsStore> for PresetsAction {
+    fn apply(self, mut store: std::rc::Rc<PresetsStore>) -> std::rc::Rc<PresetsStore> {
+        let state = std::rc::Rc::make_mut(&mut store);
+
+        match self {
+            PresetsAction::SetPresets(presets) => {
+                state.presets = presets;
+                state.loaded = true;
+                state.error = None;
+            }
+            PresetsAction::Upsert(preset) => {
+                match state.presets.iter_mut().find(|p| p.id == preset.id) {
+                    Some(existing) => *existing = preset,
+                    None => state.presets.push(preset),
+                }
+                // Same order as the server: pinned first, then by name
+                state
+                    .presets
+                    .sort_by_key(|p| (!p.pinned, p.name.to_lowercase()));
+            }
+            PresetsAction::Remove(id) => {
+                state.presets.retain(|p| p.id != id);
+            }
+            PresetsAction::SetError(error) => {
+                state.error = error;
+                state.loaded = true;
+            }
+        }
+
+        store
+    }
+}
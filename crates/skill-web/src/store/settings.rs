@@ -22,6 +22,25 @@ impl Theme {
             Self::System => "system",
         }
     }
+
+    /// Parse a theme name ("light", "dark" or "system"), falling back to
+    /// the system preference
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "light" => Self::Light,
+            "dark" => Self::Dark,
+            _ => Self::System,
+        }
+    }
+
+    /// Whether this theme renders dark, given the system preference
+    pub fn is_dark(&self, system_prefers_dark: bool) -> bool {
+        match self {
+            Self::Light => false,
+            Self::Dark => true,
+            Self::System => system_prefers_dark,
+        }
+    }
 }
 
 /// Output format options
@@ -199,9 +218,11 @@ impl SettingsStore {
 
     /// Get the effective theme (resolving system preference)
     pub fn effective_theme(&self) -> Theme {
-        // In a real implementation, we'd check the system preference here
-        // For now, just return the stored theme
-        self.theme.clone()
+        if self.theme.is_dark(crate::utils::system_prefers_dark()) {
+            Theme::Dark
+        } else {
+            Theme::Light
+        }
     }
 }
 
@@ -205,6 +205,7 @@ pub enum UiAction {
     // Command palette
     OpenCommandPalette,
     CloseCommandPalette,
+    ToggleCommandPalette,
     SetCommandPaletteQuery(String),
     SelectCommandPaletteItem(usize),
     CommandPaletteUp,
@@ -276,6 +277,12 @@ impl Reducer<UiStore> for UiAction {
             UiAction::CloseCommandPalette => {
                 state.command_palette = CommandPaletteState::default();
             }
+            UiAction::ToggleCommandPalette => {
+                state.command_palette = CommandPaletteState {
+                    open: !state.command_palette.open,
+                    ..CommandPaletteState::default()
+                };
+            }
             UiAction::SetCommandPaletteQuery(query) => {
                 state.command_palette.query = query;
                 state.command_palette.selected_index = 0;
@@ -13,6 +13,21 @@ pub fn format_duration(ms: u64) -> String {
     }
 }
 
+/// Format a byte count for display (`512 B`, `1.5 KB`, `34.2 MB`)
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
 /// Format a timestamp to a relative time string
 pub fn format_relative_time(timestamp: &str) -> String {
     // Placeholder - will be implemented with proper time parsing
@@ -28,6 +43,17 @@ pub fn truncate(s: &str, max_len: usize) -> String {
     }
 }
 
+/// Media query matching a system dark color scheme
+pub const DARK_SCHEME_QUERY: &str = "(prefers-color-scheme: dark)";
+
+/// Whether the operating system prefers a dark color scheme
+pub fn system_prefers_dark() -> bool {
+    web_sys::window()
+        .and_then(|window| window.match_media(DARK_SCHEME_QUERY).ok().flatten())
+        .map(|query| query.matches())
+        .unwrap_or(false)
+}
+
 /// Copy text to clipboard
 pub async fn copy_to_clipboard(text: &str) -> Result<(), String> {
     let window = web_sys::window().ok_or("No window")?;
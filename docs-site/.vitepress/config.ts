@@ -83,7 +83,8 @@ export default defineConfig({
           collapsed: false,
           items: [
             { text: 'Web Interface', link: '/guides/web-interface' },
-            { text: 'Semantic Search', link: '/guides/semantic-search' }
+            { text: 'Semantic Search', link: '/guides/semantic-search' },
+            { text: 'Workflows', link: '/guides/workflows' }
           ]
         },
         {
@@ -179,6 +180,7 @@ export default defineConfig({
             { text: 'Skill Instances', link: '/guides/skill-instances' },
             { text: 'Web Interface', link: '/guides/web-interface' },
             { text: 'Semantic Search', link: '/guides/semantic-search' },
+            { text: 'Workflows', link: '/guides/workflows' },
             { text: 'Manifest', link: '/guides/manifest' },
             { text: 'Environment', link: '/guides/environment' },
             { text: 'Testing', link: '/guides/testing' },